@@ -1,6 +1,17 @@
 use account_utils::read_input_from_user;
+use clap::{Arg, ArgAction, ArgMatches};
+use eth2_keystore::{KdfParams, DEFAULT_PBKDF2_C, DEFAULT_SCRYPT_N};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::thread::available_parallelism;
+use sysinfo::{System, SystemExt};
 
 pub const WALLET_NAME_PROMPT: &str = "Enter wallet name:";
+pub const KDF_FLAG: &str = "kdf";
+pub const SCRYPT_N_FLAG: &str = "scrypt-n";
+pub const PBKDF2_C_FLAG: &str = "pbkdf2-c";
+pub const SCRYPT_KDF: &str = "scrypt";
+pub const PBKDF2_KDF: &str = "pbkdf2";
+pub const THREADS_FLAG: &str = "threads";
 
 /// Reads in a wallet name from the user. If the `--wallet-name` flag is provided, use it. Otherwise
 /// read from an interactive prompt using tty unless the `--stdin-inputs` flag is provided.
@@ -17,3 +28,125 @@ pub fn read_wallet_name_from_cli(
         }
     }
 }
+
+/// Returns the arguments which select the EIP-2335 KDF used to encrypt new wallets and keystores.
+///
+/// Use `kdf_params_from_cli` to read the values of these arguments.
+pub fn kdf_args() -> [Arg; 3] {
+    [
+        Arg::new(KDF_FLAG)
+            .long(KDF_FLAG)
+            .value_name("KDF")
+            .help(
+                "The key derivation function used to encrypt secrets with a password. \
+                scrypt is memory-hard and is the recommended choice, pbkdf2 is faster.",
+            )
+            .action(ArgAction::Set)
+            .value_parser([SCRYPT_KDF, PBKDF2_KDF])
+            .default_value(SCRYPT_KDF)
+            .display_order(0),
+        Arg::new(SCRYPT_N_FLAG)
+            .long(SCRYPT_N_FLAG)
+            .value_name("N")
+            .help(
+                "The scrypt CPU/memory cost parameter. Must be a power of two. Only valid \
+                with --kdf scrypt. Defaults to 262144.",
+            )
+            .action(ArgAction::Set)
+            .conflicts_with(PBKDF2_C_FLAG)
+            .display_order(0),
+        Arg::new(PBKDF2_C_FLAG)
+            .long(PBKDF2_C_FLAG)
+            .value_name("C")
+            .help(
+                "The pbkdf2 iteration count. Only valid with --kdf pbkdf2. Defaults to \
+                262144.",
+            )
+            .action(ArgAction::Set)
+            .display_order(0),
+    ]
+}
+
+/// Reads the arguments defined in `kdf_args`, returning an error if the parameters are invalid.
+pub fn kdf_params_from_cli(matches: &ArgMatches) -> Result<KdfParams, String> {
+    let kdf: String = clap_utils::parse_required(matches, KDF_FLAG)?;
+    let scrypt_n: Option<u32> = clap_utils::parse_optional(matches, SCRYPT_N_FLAG)?;
+    let pbkdf2_c: Option<u32> = clap_utils::parse_optional(matches, PBKDF2_C_FLAG)?;
+
+    let kdf_params = match kdf.as_str() {
+        SCRYPT_KDF if pbkdf2_c.is_some() => {
+            return Err(format!(
+                "--{} requires --{} {}",
+                PBKDF2_C_FLAG, KDF_FLAG, PBKDF2_KDF
+            ))
+        }
+        PBKDF2_KDF if scrypt_n.is_some() => {
+            return Err(format!(
+                "--{} requires --{} {}",
+                SCRYPT_N_FLAG, KDF_FLAG, SCRYPT_KDF
+            ))
+        }
+        SCRYPT_KDF => KdfParams::Scrypt {
+            n: scrypt_n.unwrap_or(DEFAULT_SCRYPT_N),
+        },
+        PBKDF2_KDF => KdfParams::Pbkdf2 {
+            c: pbkdf2_c.unwrap_or(DEFAULT_PBKDF2_C),
+        },
+        unknown => return Err(format!("--{} {} is not supported", KDF_FLAG, unknown)),
+    };
+
+    kdf_params
+        .validate()
+        .map_err(|e| format!("Invalid --{} parameters: {:?}", KDF_FLAG, e))?;
+
+    Ok(kdf_params)
+}
+
+/// Returns the argument which limits the number of keystores encrypted or decrypted at once.
+///
+/// Use `keystore_thread_pool` to read the value of this argument.
+pub fn threads_arg() -> Arg {
+    Arg::new(THREADS_FLAG)
+        .long(THREADS_FLAG)
+        .value_name("THREADS")
+        .help(
+            "The number of keystores to encrypt or decrypt at once. Each scrypt key derivation \
+            uses 256 MiB of memory with the default parameters. Defaults to the number of CPUs, \
+            limited to the number of key derivations which fit in the available memory.",
+        )
+        .action(ArgAction::Set)
+        .display_order(0)
+}
+
+/// Builds the thread pool which encrypts and decrypts keystores with `kdf_params`, sized by the
+/// argument defined in `threads_arg`.
+pub fn keystore_thread_pool(
+    matches: &ArgMatches,
+    kdf_params: KdfParams,
+) -> Result<ThreadPool, String> {
+    let threads = match clap_utils::parse_optional::<usize>(matches, THREADS_FLAG)? {
+        Some(0) => return Err(format!("--{} must be at least 1", THREADS_FLAG)),
+        Some(threads) => threads,
+        None => default_keystore_threads(kdf_params),
+    };
+
+    ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| format!("Unable to create keystore thread pool: {:?}", e))
+}
+
+/// Returns the number of CPUs, limited so that the key derivations of all threads fit in the
+/// available memory. At least one thread is always used.
+fn default_keystore_threads(kdf_params: KdfParams) -> usize {
+    let cpus = available_parallelism().map_or(1, |cpus| cpus.get());
+    let memory_bytes = kdf_params.memory_bytes();
+    if memory_bytes == 0 {
+        return cpus;
+    }
+
+    let mut system = System::new();
+    system.refresh_memory();
+    let fit = system.available_memory() / memory_bytes;
+    cpus.min(usize::try_from(fit).unwrap_or(usize::MAX)).max(1)
+}
@@ -0,0 +1,193 @@
+use crate::validator::exit::{
+    get_geneisis_data, BEACON_SERVER_FLAG, DEFAULT_BEACON_NODE, NO_CONFIRMATION,
+};
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use bls::get_withdrawal_credentials;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use environment::Environment;
+use eth2::{
+    types::{StateId, ValidatorId},
+    BeaconNodeHttpClient, Timeouts,
+};
+use sensitive_url::SensitiveUrl;
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{ChainSpec, Domain, EthSpec, Hash256, SignedBlsToExecutionChange, SignedRoot};
+
+pub const CMD: &str = "broadcast";
+pub const INPUT_PATH_FLAG: &str = "input-path";
+
+pub const CONFIRMATION_PHRASE: &str = "Change my withdrawal credentials";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Publishes BLS to execution changes saved by the `create` command to the beacon \
+            chain, after checking that they match the withdrawal credentials of the validators.",
+        )
+        .arg(
+            Arg::new(INPUT_PATH_FLAG)
+                .long(INPUT_PATH_FLAG)
+                .value_name("PATH")
+                .help("The path of the JSON file containing the signed changes.")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_SERVER_FLAG)
+                .long(BEACON_SERVER_FLAG)
+                .value_name("NETWORK_ADDRESS")
+                .help("Address to a beacon node HTTP API")
+                .default_value(DEFAULT_BEACON_NODE)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(NO_CONFIRMATION)
+                .long(NO_CONFIRMATION)
+                .help(
+                    "Publishes the changes without prompting for confirmation. This should be \
+                    used with caution",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0),
+        )
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    let input_path: PathBuf = clap_utils::parse_required(matches, INPUT_PATH_FLAG)?;
+    let stdin_inputs = cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG);
+    let no_confirmation = matches.get_flag(NO_CONFIRMATION);
+
+    let file =
+        File::open(&input_path).map_err(|e| format!("Unable to open {:?}: {:?}", input_path, e))?;
+    let changes: Vec<SignedBlsToExecutionChange> = serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse {:?}: {:?}", input_path, e))?;
+
+    let spec = env.eth2_config().spec.clone();
+    let server_url: String = clap_utils::parse_required(matches, BEACON_SERVER_FLAG)?;
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&server_url)
+            .map_err(|e| format!("Failed to parse beacon http server: {:?}", e))?,
+        Timeouts::set_all(Duration::from_secs(spec.seconds_per_slot)),
+    );
+
+    env.runtime().block_on(broadcast_bls_to_execution_changes(
+        &changes,
+        &client,
+        &spec,
+        stdin_inputs,
+        no_confirmation,
+    ))
+}
+
+async fn broadcast_bls_to_execution_changes(
+    changes: &[SignedBlsToExecutionChange],
+    client: &BeaconNodeHttpClient,
+    spec: &ChainSpec,
+    stdin_inputs: bool,
+    no_confirmation: bool,
+) -> Result<(), String> {
+    let genesis_data = get_geneisis_data(client).await?;
+
+    for change in changes {
+        let message = &change.message;
+        let validator = client
+            .get_beacon_states_validator_id(
+                StateId::Head,
+                &ValidatorId::Index(message.validator_index),
+            )
+            .await
+            .map_err(|e| format!("Failed to get validator details: {:?}", e))?
+            .ok_or_else(|| {
+                format!(
+                    "Validator {} is not present in the beacon state",
+                    message.validator_index
+                )
+            })?
+            .data
+            .validator;
+
+        let from_bls_pubkey = message.from_bls_pubkey.decompress().map_err(|e| {
+            format!(
+                "Invalid public key for validator {}: {:?}",
+                message.validator_index, e
+            )
+        })?;
+        let expected_credentials = Hash256::from_slice(&get_withdrawal_credentials(
+            &from_bls_pubkey,
+            spec.bls_withdrawal_prefix_byte,
+        ));
+        if validator.withdrawal_credentials != expected_credentials {
+            return Err(format!(
+                "The change for validator {} does not match its withdrawal credentials {:?}. \
+                Either the validator index is incorrect or the credentials have already been \
+                changed",
+                message.validator_index, validator.withdrawal_credentials
+            ));
+        }
+
+        // Changes signed for another network would be rejected by the beacon node.
+        if !change.signature.verify(
+            &from_bls_pubkey,
+            message.signing_root(spec.compute_domain(
+                Domain::BlsToExecutionChange,
+                spec.genesis_fork_version,
+                genesis_data.genesis_validators_root,
+            )),
+        ) {
+            return Err(format!(
+                "The change for validator {} has an invalid signature. Please ensure that it was \
+                created for the same network as your beacon node",
+                message.validator_index
+            ));
+        }
+
+        eprintln!(
+            "Validator {}: withdrawals will be sent to {:?}",
+            message.validator_index, message.to_execution_address
+        );
+    }
+
+    if !no_confirmation {
+        eprintln!();
+        eprintln!("WARNING: THIS IS AN IRREVERSIBLE OPERATION");
+        eprintln!();
+        eprintln!(
+            "Enter the phrase \"{}\" to publish {} changes: ",
+            CONFIRMATION_PHRASE,
+            changes.len()
+        );
+        if account_utils::read_input_from_user(stdin_inputs)? != CONFIRMATION_PHRASE {
+            eprintln!(
+                "Did not publish the changes. Please check that you entered the correct phrase."
+            );
+            return Ok(());
+        }
+    }
+
+    client
+        .post_beacon_pool_bls_to_execution_changes(changes)
+        .await
+        .map_err(|e| format!("Failed to publish changes: {}", e))?;
+    eprintln!(
+        "Successfully published {} changes. They will be processed by the beacon chain in \
+        order, which may take several days if many changes are queued.",
+        changes.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,307 @@
+use crate::validator::create::COUNT_FLAG;
+use crate::validator::recover::{FIRST_INDEX_FLAG, MNEMONIC_FLAG};
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use account_utils::eth2_keystore::keypair_from_secret;
+use account_utils::read_mnemonic_from_cli;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use environment::Environment;
+use eth2_wallet::bip39::Seed;
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType};
+use std::fs::File;
+use std::path::PathBuf;
+use types::{
+    Address, BlsToExecutionChange, ChainSpec, EthSpec, Hash256, SignedBlsToExecutionChange,
+};
+
+pub const CMD: &str = "create";
+pub const VALIDATOR_INDICES_FLAG: &str = "validator-indices";
+pub const EXECUTION_ADDRESS_FLAG: &str = "execution-address";
+pub const GENESIS_VALIDATORS_ROOT_FLAG: &str = "genesis-validators-root";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Signs BLS to execution changes for validators derived from a BIP-39 mnemonic and \
+            saves them to a JSON file. This command does not require network access, so it can \
+            be run on an offline machine. The changes are signed with the withdrawal keys at \
+            consecutive key indexes, counted from `--first-index`.",
+        )
+        .arg(
+            Arg::new(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help("The first of consecutive key indexes to sign changes for.")
+                .action(ArgAction::Set)
+                .default_value("0")
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("COUNT")
+                .help(
+                    "The number of validators to sign changes for. Must equal the number of \
+                    `--validator-indices`.",
+                )
+                .action(ArgAction::Set)
+                .default_value("1")
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VALIDATOR_INDICES_FLAG)
+                .long(VALIDATOR_INDICES_FLAG)
+                .value_name("INDICES")
+                .help(
+                    "A comma-separated list of the beacon chain indices of the validators, in \
+                    the order of their key indexes.",
+                )
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(EXECUTION_ADDRESS_FLAG)
+                .long(EXECUTION_ADDRESS_FLAG)
+                .value_name("ADDRESS")
+                .help(
+                    "The execution address which will receive withdrawals. This cannot be \
+                    changed once the change is processed by the beacon chain.",
+                )
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(GENESIS_VALIDATORS_ROOT_FLAG)
+                .long(GENESIS_VALIDATORS_ROOT_FLAG)
+                .value_name("ROOT")
+                .help(
+                    "The genesis validators root of the network. Only required if it is not \
+                    known for the network given by `--network` or `--testnet-dir`.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("PATH")
+                .help("The path of the JSON file the signed changes are written to.")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help("If present, the mnemonic will be read in from this file.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0),
+        )
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    let first_index: u32 = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
+    let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
+    let validator_indices = parse_validator_indices(&clap_utils::parse_required::<String>(
+        matches,
+        VALIDATOR_INDICES_FLAG,
+    )?)?;
+    let execution_address: Address = clap_utils::parse_required(matches, EXECUTION_ADDRESS_FLAG)?;
+    let output_path: PathBuf = clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?;
+    let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
+    let stdin_inputs = cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG);
+
+    if validator_indices.len() != count as usize {
+        return Err(format!(
+            "{} validator indices were supplied for a --{} of {}",
+            validator_indices.len(),
+            COUNT_FLAG,
+            count
+        ));
+    }
+    if output_path.exists() {
+        return Err(format!("{:?} already exists", output_path));
+    }
+
+    let genesis_validators_root =
+        match clap_utils::parse_optional::<Hash256>(matches, GENESIS_VALIDATORS_ROOT_FLAG)? {
+            Some(root) => root,
+            None => env
+                .eth2_network_config
+                .as_ref()
+                .ok_or("Unknown network, please supply --genesis-validators-root")?
+                .genesis_validators_root::<E>()?
+                .ok_or_else(|| {
+                    format!(
+                        "The genesis state of the network is unknown, please supply --{}",
+                        GENESIS_VALIDATORS_ROOT_FLAG
+                    )
+                })?,
+        };
+    let spec = &env.eth2_config.spec;
+
+    eprintln!();
+    eprintln!(
+        "WARNING: THE EXECUTION ADDRESS {:?} CANNOT BE CHANGED ONCE THESE CHANGES ARE BROADCAST.",
+        execution_address
+    );
+    eprintln!();
+
+    let mnemonic = read_mnemonic_from_cli(mnemonic_path, stdin_inputs)?;
+    let seed = Seed::new(&mnemonic, "");
+
+    let changes = sign_bls_to_execution_changes(
+        seed.as_bytes(),
+        first_index,
+        &validator_indices,
+        execution_address,
+        genesis_validators_root,
+        spec,
+    )?;
+
+    let file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(&output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+    serde_json::to_writer_pretty(file, &changes)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output_path, e))?;
+
+    for (i, change) in changes.iter().enumerate() {
+        println!(
+            "{}/{}\tIndex: {}\tValidator: {}\t{}",
+            i + 1,
+            count,
+            first_index as usize + i,
+            change.message.validator_index,
+            change.message.from_bls_pubkey
+        );
+    }
+    eprintln!(
+        "Saved {} changes to {:?}. Use `lighthouse account validator {} broadcast` to publish \
+        them.",
+        changes.len(),
+        output_path,
+        super::CMD
+    );
+
+    Ok(())
+}
+
+/// Parses a comma-separated list of validator indices.
+fn parse_validator_indices(indices: &str) -> Result<Vec<u64>, String> {
+    indices
+        .split(',')
+        .map(|index| {
+            index
+                .trim()
+                .parse()
+                .map_err(|e| format!("Invalid validator index {}: {:?}", index, e))
+        })
+        .collect()
+}
+
+/// Signs a change for each of `validator_indices`, using the withdrawal keys derived from `seed`
+/// at consecutive key indexes starting from `first_index`.
+///
+/// The changes are signed with the genesis fork version of `spec`, which the beacon chain uses to
+/// verify them regardless of the current fork.
+pub fn sign_bls_to_execution_changes(
+    seed: &[u8],
+    first_index: u32,
+    validator_indices: &[u64],
+    execution_address: Address,
+    genesis_validators_root: Hash256,
+    spec: &ChainSpec,
+) -> Result<Vec<SignedBlsToExecutionChange>, String> {
+    validator_indices
+        .iter()
+        .zip(first_index..)
+        .map(|(&validator_index, key_index)| {
+            let (secret, _) =
+                recover_validator_secret_from_mnemonic(seed, key_index, KeyType::Withdrawal)
+                    .map_err(|e| format!("Unable to recover withdrawal key: {:?}", e))?;
+            let keypair = keypair_from_secret(secret.as_bytes())
+                .map_err(|e| format!("Unable to recover withdrawal key: {:?}", e))?;
+
+            Ok(BlsToExecutionChange {
+                validator_index,
+                from_bls_pubkey: keypair.pk.compress(),
+                to_execution_address: execution_address,
+            }
+            .sign(&keypair.sk, genesis_validators_root, spec))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2_wallet::bip39::{Language, Mnemonic};
+    use types::{Domain, MainnetEthSpec, SignedRoot};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon about";
+
+    #[test]
+    fn changes_are_signed_with_genesis_fork_version() {
+        let spec = MainnetEthSpec::default_spec();
+        let mnemonic = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+        let seed = Seed::new(&mnemonic, "");
+        let genesis_validators_root = Hash256::repeat_byte(42);
+        let execution_address = Address::repeat_byte(1);
+
+        let changes = sign_bls_to_execution_changes(
+            seed.as_bytes(),
+            2,
+            &[100, 200],
+            execution_address,
+            genesis_validators_root,
+            &spec,
+        )
+        .unwrap();
+
+        let domain = spec.compute_domain(
+            Domain::BlsToExecutionChange,
+            spec.genesis_fork_version,
+            genesis_validators_root,
+        );
+        for (change, (validator_index, key_index)) in
+            changes.iter().zip([(100, 2), (200, 3)].into_iter())
+        {
+            let (secret, _) = recover_validator_secret_from_mnemonic(
+                seed.as_bytes(),
+                key_index,
+                KeyType::Withdrawal,
+            )
+            .unwrap();
+            let keypair = keypair_from_secret(secret.as_bytes()).unwrap();
+
+            assert_eq!(change.message.validator_index, validator_index);
+            assert_eq!(change.message.from_bls_pubkey, keypair.pk.compress());
+            assert_eq!(change.message.to_execution_address, execution_address);
+            assert!(change
+                .signature
+                .verify(&keypair.pk, change.message.signing_root(domain)));
+        }
+    }
+
+    #[test]
+    fn validator_indices() {
+        assert_eq!(parse_validator_indices("1, 2,3").unwrap(), vec![1, 2, 3]);
+        assert!(parse_validator_indices("1,,2").is_err());
+    }
+}
@@ -0,0 +1,30 @@
+pub mod broadcast;
+pub mod create;
+
+use clap::{ArgMatches, Command};
+use environment::Environment;
+use types::EthSpec;
+
+pub const CMD: &str = "bls-to-execution-change";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Changes the withdrawal credentials of validators from BLS (0x00) to an execution \
+            address (0x01). The changes can be created on an offline machine and broadcast later.",
+        )
+        .subcommand(create::cli_app())
+        .subcommand(broadcast::cli_app())
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    match matches.subcommand() {
+        Some((create::CMD, matches)) => create::cli_run(matches, env),
+        Some((broadcast::CMD, matches)) => broadcast::cli_run(matches, env),
+        Some((unknown, _)) => Err(format!(
+            "{} does not have a {} command. See --help",
+            CMD, unknown
+        )),
+        _ => Err("No subcommand provided, see --help for options".to_string()),
+    }
+}
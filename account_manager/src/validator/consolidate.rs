@@ -0,0 +1,285 @@
+use crate::validator::execution_request::{
+    add_execution_request_args, check_beacon_node, ExecutionRequestSubmitter,
+};
+use crate::validator::exit::{
+    get_validator_data, BEACON_SERVER_FLAG, DEFAULT_BEACON_NODE, NO_CONFIRMATION,
+};
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use deposit_contract::{
+    encode_consolidation_request_tx_data, CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+};
+use environment::Environment;
+use eth2::{types::ValidatorStatus, BeaconNodeHttpClient, Timeouts};
+use eth2_network_config::Eth2NetworkConfig;
+use safe_arith::SafeArith;
+use sensitive_url::SensitiveUrl;
+use std::time::Duration;
+use types::{Address, ChainSpec, Epoch, EthSpec, PublicKeyBytes};
+
+pub const CMD: &str = "consolidate";
+pub const SOURCE_FLAG: &str = "source";
+pub const TARGET_FLAG: &str = "target";
+
+pub const CONFIRMATION_PHRASE: &str = "Consolidate my validators";
+pub const WEBSITE_URL: &str = "https://lighthouse-book.sigmaprime.io/consolidation.html";
+
+pub fn cli_app() -> Command {
+    let cmd = Command::new(CMD)
+        .about(
+            "Submits an EIP-7251 consolidation request to the execution layer, which moves the \
+            balance of the source validator to the target validator. The request is sent from the \
+            withdrawal address of the source validator, so it must be signed with the key of that \
+            address rather than the validator voting keystore.",
+        )
+        .arg(
+            Arg::new(SOURCE_FLAG)
+                .long(SOURCE_FLAG)
+                .value_name("PUBKEY")
+                .help("The public key of the validator which will be consolidated and exited")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(TARGET_FLAG)
+                .long(TARGET_FLAG)
+                .value_name("PUBKEY")
+                .help(
+                    "The public key of the validator which will receive the balance of the \
+                    source validator. It must have 0x02 withdrawal credentials. If it is the same \
+                    as the source, the request switches the source validator from 0x01 to 0x02 \
+                    withdrawal credentials",
+                )
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(BEACON_SERVER_FLAG)
+                .long(BEACON_SERVER_FLAG)
+                .value_name("NETWORK_ADDRESS")
+                .help("Address to a beacon node HTTP API")
+                .default_value(DEFAULT_BEACON_NODE)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(NO_CONFIRMATION)
+                .long(NO_CONFIRMATION)
+                .help("Submits the request without prompting for confirmation that you understand the implications of a consolidation. This should be used with caution")
+                .display_order(0)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+        );
+    add_execution_request_args(cmd)
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    let source: PublicKeyBytes = clap_utils::parse_required(matches, SOURCE_FLAG)?;
+    let target: PublicKeyBytes = clap_utils::parse_required(matches, TARGET_FLAG)?;
+
+    let stdin_inputs = cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG);
+    let no_confirmation = matches.get_flag(NO_CONFIRMATION);
+
+    let spec = env.eth2_config().spec.clone();
+    let server_url: String = clap_utils::parse_required(matches, BEACON_SERVER_FLAG)?;
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&server_url)
+            .map_err(|e| format!("Failed to parse beacon http server: {:?}", e))?,
+        Timeouts::set_all(Duration::from_secs(env.eth2_config.spec.seconds_per_slot)),
+    );
+
+    let submitter = ExecutionRequestSubmitter::from_matches(matches)?;
+
+    let eth2_network_config = env
+        .eth2_network_config
+        .clone()
+        .expect("network should have a valid config");
+
+    env.runtime().block_on(publish_consolidation_request::<E>(
+        ConsolidationRequest { source, target },
+        &client,
+        &submitter,
+        &spec,
+        &eth2_network_config,
+        stdin_inputs,
+        no_confirmation,
+    ))
+}
+
+/// The source and target validators of an EIP-7251 consolidation request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConsolidationRequest {
+    pub source: PublicKeyBytes,
+    pub target: PublicKeyBytes,
+}
+
+impl ConsolidationRequest {
+    /// Returns the calldata expected by the consolidation request predeploy.
+    pub fn calldata(&self) -> Vec<u8> {
+        encode_consolidation_request_tx_data(&self.source, &self.target)
+    }
+
+    pub fn is_switch_to_compounding(&self) -> bool {
+        self.source == self.target
+    }
+}
+
+/// Checks that the consolidation can be processed by the beacon chain, then builds, signs and
+/// submits the transaction which requests it.
+async fn publish_consolidation_request<E: EthSpec>(
+    request: ConsolidationRequest,
+    client: &BeaconNodeHttpClient,
+    submitter: &ExecutionRequestSubmitter,
+    spec: &ChainSpec,
+    eth2_network_config: &Eth2NetworkConfig,
+    stdin_inputs: bool,
+    no_confirmation: bool,
+) -> Result<(), String> {
+    let epoch = check_beacon_node::<E>(client, eth2_network_config, spec).await?;
+    let source_address = get_source_address(client, &request, epoch, spec).await?;
+
+    if request.is_switch_to_compounding() {
+        eprintln!(
+            "Switching validator {} to compounding withdrawal credentials\n",
+            request.source
+        );
+    } else {
+        eprintln!(
+            "Consolidating validator {} into validator {}\n",
+            request.source, request.target
+        );
+    }
+    let tx = submitter
+        .build_transaction(
+            source_address,
+            CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+            request.calldata(),
+        )
+        .await?;
+
+    if submitter.dry_run {
+        eprintln!("Dry run, the consolidation request was not submitted");
+        return Ok(());
+    }
+
+    if !no_confirmation {
+        eprintln!("WARNING: THIS IS AN IRREVERSIBLE OPERATION\n");
+        if !request.is_switch_to_compounding() {
+            eprintln!(
+                "The source validator will be exited and its balance moved to the target \
+                validator.\n"
+            );
+        }
+        eprintln!(
+            "PLEASE VISIT {} TO MAKE SURE YOU UNDERSTAND THE IMPLICATIONS OF A CONSOLIDATION.",
+            WEBSITE_URL
+        );
+        eprintln!(
+            "Enter the consolidation phrase from the above URL to confirm the consolidation: "
+        );
+    }
+
+    let confirmation = if !no_confirmation {
+        account_utils::read_input_from_user(stdin_inputs)?
+    } else {
+        CONFIRMATION_PHRASE.to_string()
+    };
+
+    if confirmation != CONFIRMATION_PHRASE {
+        eprintln!(
+            "Did not submit the consolidation request. Please check that you entered the correct \
+            consolidation phrase."
+        );
+        return Ok(());
+    }
+
+    submitter.submit(tx, spec).await?;
+
+    Ok(())
+}
+
+/// Returns the withdrawal address of the source validator, which must send the consolidation
+/// request.
+///
+/// Returns an error if the beacon chain would ignore the consolidation request.
+async fn get_source_address(
+    client: &BeaconNodeHttpClient,
+    request: &ConsolidationRequest,
+    epoch: Epoch,
+    spec: &ChainSpec,
+) -> Result<Address, String> {
+    let source_data = get_validator_data(client, &request.source).await?;
+    if source_data.status != ValidatorStatus::ActiveOngoing {
+        return Err(format!(
+            "Source validator {} cannot be consolidated. Validator status: {:?}",
+            request.source, source_data.status
+        ));
+    }
+    let source = &source_data.validator;
+    let source_address = source
+        .get_execution_withdrawal_address(spec)
+        .ok_or_else(|| {
+            format!(
+                "Source validator {} does not have execution withdrawal credentials",
+                request.source
+            )
+        })?;
+
+    if request.is_switch_to_compounding() {
+        if !source.has_eth1_withdrawal_credential(spec) {
+            return Err(format!(
+                "Validator {} does not have 0x01 withdrawal credentials",
+                request.source
+            ));
+        }
+    } else {
+        let eligible_epoch = source
+            .activation_epoch
+            .safe_add(spec.shard_committee_period)
+            .map_err(|e| {
+                format!(
+                    "Failed to calculate eligible epoch, validator activation epoch too high: {:?}",
+                    e
+                )
+            })?;
+        if epoch < eligible_epoch {
+            return Err(format!(
+                "Source validator {} is not eligible for consolidation. It will become eligible \
+                on epoch {}",
+                request.source, eligible_epoch
+            ));
+        }
+
+        let target_data = get_validator_data(client, &request.target).await?;
+        if target_data.status != ValidatorStatus::ActiveOngoing {
+            return Err(format!(
+                "Target validator {} cannot receive a consolidation. Validator status: {:?}",
+                request.target, target_data.status
+            ));
+        }
+        if !target_data
+            .validator
+            .has_compounding_withdrawal_credential(spec)
+        {
+            return Err(format!(
+                "Target validator {} does not have 0x02 withdrawal credentials. Consolidate it \
+                into itself first to switch its withdrawal credentials",
+                request.target
+            ));
+        }
+    }
+
+    Ok(source_address)
+}
@@ -1,4 +1,6 @@
-use crate::common::read_wallet_name_from_cli;
+use crate::common::{
+    kdf_args, kdf_params_from_cli, keystore_thread_pool, read_wallet_name_from_cli, threads_arg,
+};
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::{SECRETS_DIR_FLAG, WALLETS_DIR_FLAG};
 use account_utils::{
@@ -11,6 +13,7 @@ use directory::{
 };
 use environment::Environment;
 use eth2_wallet_manager::WalletManager;
+use rayon::prelude::*;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 use std::ffi::OsStr;
 use std::fs;
@@ -25,6 +28,7 @@ pub const DEPOSIT_GWEI_FLAG: &str = "deposit-gwei";
 pub const STORE_WITHDRAW_FLAG: &str = "store-withdrawal-keystore";
 pub const COUNT_FLAG: &str = "count";
 pub const AT_MOST_FLAG: &str = "at-most";
+pub const FIRST_INDEX_FLAG: &str = "first-index";
 pub const WALLET_PASSWORD_PROMPT: &str = "Enter your wallet's password:";
 
 pub fn cli_app() -> Command {
@@ -114,6 +118,21 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help(
+                    "The EIP-2334 index of the first validator to create (i.e., the `i` in \
+                    m/12381/3600/i/0/0). Defaults to the next unused index of the wallet. \
+                    Indices lower than the next unused index are rejected to prevent \
+                    duplicate keys.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .args(kdf_args())
+        .arg(threads_arg())
         .arg(
             Arg::new(STDIN_INPUTS_FLAG)
                 .action(ArgAction::SetTrue)
@@ -153,6 +172,8 @@ pub fn cli_run<E: EthSpec>(
         .unwrap_or(spec.max_effective_balance);
     let count: Option<usize> = clap_utils::parse_optional(matches, COUNT_FLAG)?;
     let at_most: Option<usize> = clap_utils::parse_optional(matches, AT_MOST_FLAG)?;
+    let first_index: Option<u32> = clap_utils::parse_optional(matches, FIRST_INDEX_FLAG)?;
+    let kdf_params = kdf_params_from_cli(matches)?;
 
     // The command will always fail if the wallet dir does not exist.
     if !wallet_base_dir.exists() {
@@ -226,25 +247,57 @@ pub fn cli_run<E: EthSpec>(
         )
     })?;
 
-    for i in 0..n {
-        let voting_password = random_password();
-        let withdrawal_password = random_password();
-
-        let keystores = wallet
-            .next_validator(
-                wallet_password.as_bytes(),
-                voting_password.as_bytes(),
-                withdrawal_password.as_bytes(),
-            )
-            .map_err(|e| format!("Unable to create validator keys: {:?}", e))?;
-
-        let voting_pubkey = keystores.voting.public_key().ok_or_else(|| {
+    if let Some(first_index) = first_index {
+        wallet.set_nextaccount(first_index).map_err(|e| {
             format!(
-                "Keystore public key is invalid: {}",
-                keystores.voting.pubkey()
+                "Unable to start at --{} {}: {:?}",
+                FIRST_INDEX_FLAG, first_index, e
             )
         })?;
+    }
 
+    let count = u32::try_from(n).map_err(|_| format!("Cannot create {} validators", n))?;
+    let secrets = wallet
+        .next_validator_secrets(wallet_password.as_bytes(), count)
+        .map_err(|e| format!("Unable to create validator keys: {:?}", e))?;
+
+    // Encrypting (and later decrypting, to sign the deposit data) keystores is deliberately slow,
+    // so do it for several validators in parallel. The pool is bounded since each scrypt key
+    // derivation allocates a large buffer.
+    let pool = keystore_thread_pool(matches, kdf_params)?;
+    let validators = pool.install(|| {
+        secrets
+            .into_par_iter()
+            .map(|secrets| {
+                let voting_password = random_password();
+                let withdrawal_password = random_password();
+
+                let keystores = secrets
+                    .into_keystores(
+                        voting_password.as_bytes(),
+                        withdrawal_password.as_bytes(),
+                        kdf_params,
+                    )
+                    .map_err(|e| format!("Unable to create validator keystores: {:?}", e))?;
+
+                let voting_pubkey = keystores.voting.public_key().ok_or_else(|| {
+                    format!(
+                        "Keystore public key is invalid: {}",
+                        keystores.voting.pubkey()
+                    )
+                })?;
+
+                Ok((
+                    keystores,
+                    voting_pubkey,
+                    voting_password,
+                    withdrawal_password,
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })?;
+
+    for (_, voting_pubkey, _, _) in &validators {
         slashing_protection
             .register_validator(voting_pubkey.compress())
             .map_err(|e| {
@@ -254,16 +307,30 @@ pub fn cli_run<E: EthSpec>(
                     e
                 )
             })?;
+    }
+
+    let store_withdrawal_keystore = matches.get_flag(STORE_WITHDRAW_FLAG);
+    let voting_pubkeys = pool.install(|| {
+        validators
+            .into_par_iter()
+            .map(
+                |(keystores, voting_pubkey, voting_password, withdrawal_password)| {
+                    ValidatorDirBuilder::new(validator_dir.clone())
+                        .password_dir(secrets_dir.clone())
+                        .voting_keystore(keystores.voting, voting_password.as_bytes())
+                        .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
+                        .create_eth1_tx_data(deposit_gwei, &spec)
+                        .store_withdrawal_keystore(store_withdrawal_keystore)
+                        .build()
+                        .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
-        ValidatorDirBuilder::new(validator_dir.clone())
-            .password_dir(secrets_dir.clone())
-            .voting_keystore(keystores.voting, voting_password.as_bytes())
-            .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
-            .create_eth1_tx_data(deposit_gwei, &spec)
-            .store_withdrawal_keystore(matches.get_flag(STORE_WITHDRAW_FLAG))
-            .build()
-            .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
+                    Ok(voting_pubkey)
+                },
+            )
+            .collect::<Result<Vec<_>, String>>()
+    })?;
 
+    for (i, voting_pubkey) in voting_pubkeys.iter().enumerate() {
         println!("{}/{}\t{}", i + 1, n, voting_pubkey.as_hex_string());
     }
 
@@ -0,0 +1,368 @@
+//! Functionality shared by the commands which submit EIP-7685 requests to the execution layer,
+//! such as withdrawal and consolidation requests.
+//!
+//! These requests are transactions to a predeploy contract, sent from the withdrawal address of a
+//! validator. They are signed with the key of the withdrawal address, either by a Ledger device, a
+//! Web3Signer or the execution node itself.
+use crate::validator::exit::{get_current_epoch, get_geneisis_data, is_syncing};
+#[cfg(feature = "ledger")]
+use crate::validator::ledger::LedgerSigner;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::BeaconNodeHttpClient;
+use eth2_network_config::Eth2NetworkConfig;
+#[cfg(feature = "ledger")]
+use ethers_core::types::BlockNumber;
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+    TransactionReceipt, U256,
+};
+use ethers_providers::{Http, Middleware, Provider};
+use std::time::Duration;
+use types::{ChainSpec, Epoch, EthSpec};
+
+pub const EXECUTION_ENDPOINT_FLAG: &str = "execution-endpoint";
+pub const WEB3SIGNER_URL_FLAG: &str = "web3signer-url";
+pub const LEDGER_FLAG: &str = "ledger";
+pub const LEDGER_DERIVATION_PATH_FLAG: &str = "ledger-derivation-path";
+pub const MAX_FEE_FLAG: &str = "max-fee";
+pub const DRY_RUN_FLAG: &str = "dry-run";
+
+pub const DEFAULT_EXECUTION_ENDPOINT: &str = "http://localhost:8545/";
+/// The derivation path of the first account of Ledger Live.
+pub const DEFAULT_LEDGER_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+/// The default upper bound on the request fee, in wei.
+///
+/// The fee is 1 wei unless the queue of requests is congested.
+pub const DEFAULT_MAX_FEE: &str = "1000000000";
+
+/// Adds the flags which configure how requests are signed and submitted to `cmd`.
+pub fn add_execution_request_args(cmd: Command) -> Command {
+    cmd.arg(
+        Arg::new(EXECUTION_ENDPOINT_FLAG)
+            .long(EXECUTION_ENDPOINT_FLAG)
+            .value_name("NETWORK_ADDRESS")
+            .help(
+                "Address to the JSON-RPC API of an execution node. The request is submitted \
+                to this node. If neither --ledger nor --web3signer-url is supplied, the node \
+                signs the request with the withdrawal address account in its keystore",
+            )
+            .default_value(DEFAULT_EXECUTION_ENDPOINT)
+            .action(ArgAction::Set)
+            .display_order(0),
+    )
+    .arg(
+        Arg::new(WEB3SIGNER_URL_FLAG)
+            .long(WEB3SIGNER_URL_FLAG)
+            .value_name("NETWORK_ADDRESS")
+            .help(
+                "Address to the Ethereum JSON-RPC API of a Web3Signer which holds the key of \
+                the withdrawal address. The request is signed with `eth_signTransaction` and \
+                submitted to the execution node",
+            )
+            .action(ArgAction::Set)
+            .conflicts_with(LEDGER_FLAG)
+            .display_order(0),
+    )
+    .arg(
+        Arg::new(LEDGER_FLAG)
+            .long(LEDGER_FLAG)
+            .help(
+                "Signs the request with the Ethereum app of a Ledger device connected over USB, \
+                which must hold the withdrawal address. The request is submitted to the \
+                execution node. Requires Lighthouse to be compiled with the `ledger` feature",
+            )
+            .action(ArgAction::SetTrue)
+            .help_heading(FLAG_HEADER)
+            .display_order(0),
+    )
+    .arg(
+        Arg::new(LEDGER_DERIVATION_PATH_FLAG)
+            .long(LEDGER_DERIVATION_PATH_FLAG)
+            .value_name("PATH")
+            .help("The derivation path of the withdrawal address on the Ledger device")
+            .default_value(DEFAULT_LEDGER_DERIVATION_PATH)
+            .requires(LEDGER_FLAG)
+            .action(ArgAction::Set)
+            .display_order(0),
+    )
+    .arg(
+        Arg::new(MAX_FEE_FLAG)
+            .long(MAX_FEE_FLAG)
+            .value_name("WEI")
+            .help(
+                "The maximum request fee to pay, in wei. The request is not submitted if the \
+                fee charged by the predeploy contract is higher",
+            )
+            .default_value(DEFAULT_MAX_FEE)
+            .action(ArgAction::Set)
+            .display_order(0),
+    )
+    .arg(
+        Arg::new(DRY_RUN_FLAG)
+            .long(DRY_RUN_FLAG)
+            .help(
+                "Checks the request and prints the transaction which would be submitted, \
+                without signing or submitting it",
+            )
+            .action(ArgAction::SetTrue)
+            .help_heading(FLAG_HEADER)
+            .display_order(0),
+    )
+}
+
+/// Signs and submits requests to the execution layer.
+pub struct ExecutionRequestSubmitter {
+    execution_client: Provider<Http>,
+    web3signer_client: Option<Provider<Http>>,
+    /// The derivation path of the withdrawal address, if requests are signed by a Ledger device.
+    ledger_derivation_path: Option<String>,
+    max_fee: U256,
+    pub dry_run: bool,
+}
+
+impl ExecutionRequestSubmitter {
+    pub fn from_matches(matches: &ArgMatches) -> Result<Self, String> {
+        let execution_url: String = clap_utils::parse_required(matches, EXECUTION_ENDPOINT_FLAG)?;
+        let execution_client = Provider::<Http>::try_from(execution_url.as_str())
+            .map_err(|e| format!("Failed to parse execution endpoint: {:?}", e))?;
+        let web3signer_client = clap_utils::parse_optional::<String>(matches, WEB3SIGNER_URL_FLAG)?
+            .map(|url| {
+                Provider::<Http>::try_from(url.as_str())
+                    .map_err(|e| format!("Failed to parse web3signer url: {:?}", e))
+            })
+            .transpose()?;
+        let ledger_derivation_path = if matches.get_flag(LEDGER_FLAG) {
+            if cfg!(not(feature = "ledger")) {
+                return Err(format!(
+                    "--{} requires Lighthouse to be compiled with the `ledger` feature",
+                    LEDGER_FLAG
+                ));
+            }
+            Some(clap_utils::parse_required(
+                matches,
+                LEDGER_DERIVATION_PATH_FLAG,
+            )?)
+        } else {
+            None
+        };
+        let max_fee: u64 = clap_utils::parse_required(matches, MAX_FEE_FLAG)?;
+
+        Ok(Self {
+            execution_client,
+            web3signer_client,
+            ledger_derivation_path,
+            max_fee: U256::from(max_fee),
+            dry_run: matches.get_flag(DRY_RUN_FLAG),
+        })
+    }
+
+    /// Returns the fee currently charged by the predeploy at `contract_address`.
+    pub async fn get_fee(&self, contract_address: types::Address) -> Result<U256, String> {
+        // Calling the contract without any calldata returns the current fee.
+        let fee_tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::from_slice(contract_address.as_bytes()))
+            .into();
+        let fee_bytes = self
+            .execution_client
+            .call(&fee_tx, None)
+            .await
+            .map_err(|e| format!("Failed to get request fee: {:?}", e))?;
+        if fee_bytes.len() != 32 {
+            return Err(format!("Unexpected request fee response: {}", fee_bytes));
+        }
+        Ok(U256::from_big_endian(&fee_bytes))
+    }
+
+    /// Builds the transaction which sends `data` from `from` to the predeploy at
+    /// `contract_address`, paying the current fee.
+    ///
+    /// Returns an error if the fee is higher than the maximum.
+    pub async fn build_transaction(
+        &self,
+        from: types::Address,
+        contract_address: types::Address,
+        data: Vec<u8>,
+    ) -> Result<TypedTransaction, String> {
+        let fee = self.get_fee(contract_address).await?;
+        if fee > self.max_fee {
+            return Err(format!(
+                "The request fee of {} wei is higher than the maximum of {} wei. The request \
+                queue may be congested, try again later or raise --{}",
+                fee, self.max_fee, MAX_FEE_FLAG
+            ));
+        }
+
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(Address::from_slice(from.as_bytes()))
+            .to(Address::from_slice(contract_address.as_bytes()))
+            .value(fee)
+            .data(Bytes::from(data))
+            .into();
+        self.execution_client
+            .fill_transaction(&mut tx, None)
+            .await
+            .map_err(|e| format!("Failed to prepare request transaction: {:?}", e))?;
+
+        eprintln!("Withdrawal address: {:?}", from);
+        eprintln!("Request fee: {} wei", fee);
+        eprintln!(
+            "Transaction:\n{}\n",
+            serde_json::to_string_pretty(&tx)
+                .map_err(|e| format!("Failed to serialize transaction: {:?}", e))?
+        );
+
+        Ok(tx)
+    }
+
+    /// Signs and submits `tx`, then waits for it to be included in a block.
+    pub async fn submit(
+        &self,
+        tx: TypedTransaction,
+        spec: &ChainSpec,
+    ) -> Result<TransactionReceipt, String> {
+        let pending_tx = if let Some(derivation_path) = &self.ledger_derivation_path {
+            let signed_tx = self.sign_with_ledger(tx, derivation_path.clone()).await?;
+            self.execution_client
+                .send_raw_transaction(signed_tx)
+                .await
+                .map_err(|e| format!("Failed to submit request transaction: {:?}", e))?
+        } else if let Some(web3signer_client) = &self.web3signer_client {
+            let signed_tx: Bytes = web3signer_client
+                .request("eth_signTransaction", [&tx])
+                .await
+                .map_err(|e| format!("Failed to sign request transaction: {:?}", e))?;
+            self.execution_client
+                .send_raw_transaction(signed_tx)
+                .await
+                .map_err(|e| format!("Failed to submit request transaction: {:?}", e))?
+        } else {
+            self.execution_client
+                .send_transaction(tx, None)
+                .await
+                .map_err(|e| format!("Failed to submit request transaction: {:?}", e))?
+        };
+        eprintln!("Submitted request transaction {:?}", *pending_tx);
+
+        let receipt = pending_tx
+            .interval(Duration::from_secs(spec.seconds_per_slot))
+            .await
+            .map_err(|e| format!("Request transaction failed to resolve: {:?}", e))?
+            .ok_or("Request transaction was dropped from the mempool")?;
+        if receipt.status != Some(1.into()) {
+            return Err(format!(
+                "Request transaction {:?} failed. The request fee may have increased before it \
+                was included",
+                receipt.transaction_hash
+            ));
+        }
+
+        eprintln!(
+            "Request was included in execution block {}. It will be processed by the beacon \
+            chain once the block is finalized, which may take several minutes or longer.",
+            receipt.block_number.unwrap_or_default()
+        );
+
+        Ok(receipt)
+    }
+
+    /// Signs `tx` with the Ledger device connected to this machine, returning the encoded signed
+    /// transaction.
+    #[cfg(feature = "ledger")]
+    async fn sign_with_ledger(
+        &self,
+        mut tx: TypedTransaction,
+        derivation_path: String,
+    ) -> Result<Bytes, String> {
+        let from = *tx.from().ok_or("Request transaction has no sender")?;
+        // Unlike an execution node or Web3Signer, the device can not fill these itself.
+        if tx.nonce().is_none() {
+            let nonce = self
+                .execution_client
+                .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+                .await
+                .map_err(|e| format!("Failed to get withdrawal address nonce: {:?}", e))?;
+            tx.set_nonce(nonce);
+        }
+        if tx.chain_id().is_none() {
+            let chain_id = self
+                .execution_client
+                .get_chainid()
+                .await
+                .map_err(|e| format!("Failed to get execution chain id: {:?}", e))?;
+            tx.set_chain_id(chain_id.as_u64());
+        }
+
+        // Communicating with the device blocks until the user approves the transaction.
+        tokio::task::spawn_blocking(move || {
+            let signer = LedgerSigner::connect(&derivation_path)?;
+            if signer.address() != from {
+                return Err(format!(
+                    "The Ledger account at {} has address {:?}, not the withdrawal address {:?}. \
+                    Use --{} to select the account of the withdrawal address",
+                    derivation_path,
+                    signer.address(),
+                    from,
+                    LEDGER_DERIVATION_PATH_FLAG
+                ));
+            }
+
+            eprintln!("Approve the request transaction on the Ledger device");
+            let signature = signer.sign_transaction(&tx)?;
+            Ok(tx.rlp_signed(&signature))
+        })
+        .await
+        .map_err(|e| format!("Ledger signing task failed: {:?}", e))?
+    }
+
+    #[cfg(not(feature = "ledger"))]
+    async fn sign_with_ledger(
+        &self,
+        _tx: TypedTransaction,
+        _derivation_path: String,
+    ) -> Result<Bytes, String> {
+        Err(format!(
+            "--{} requires Lighthouse to be compiled with the `ledger` feature",
+            LEDGER_FLAG
+        ))
+    }
+}
+
+/// Checks that the beacon node is synced, on the same network and past the Electra fork, which
+/// introduced execution layer requests.
+///
+/// Returns the current epoch.
+pub async fn check_beacon_node<E: EthSpec>(
+    client: &BeaconNodeHttpClient,
+    eth2_network_config: &Eth2NetworkConfig,
+    spec: &ChainSpec,
+) -> Result<Epoch, String> {
+    let genesis_data = get_geneisis_data(client).await?;
+    let testnet_genesis_root = eth2_network_config
+        .genesis_validators_root::<E>()?
+        .ok_or("Genesis state is unknown")?;
+
+    // Verify that the beacon node and validators are on the same network.
+    if genesis_data.genesis_validators_root != testnet_genesis_root {
+        return Err(
+            "Invalid genesis state. Please ensure that your beacon node is on the same network \
+                 as your validators"
+                .to_string(),
+        );
+    }
+
+    // Return immediately if beacon node is not synced
+    if is_syncing(client).await? {
+        return Err("Beacon node is still syncing".to_string());
+    }
+
+    let epoch = get_current_epoch::<E>(genesis_data.genesis_time, spec)
+        .ok_or("Failed to get current epoch. Please check your system time")?;
+    if !spec.fork_name_at_epoch(epoch).electra_enabled() {
+        return Err(
+            "Execution layer requests are not possible before the Electra fork".to_string(),
+        );
+    }
+
+    Ok(epoch)
+}
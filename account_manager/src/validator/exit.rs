@@ -15,7 +15,7 @@ use slot_clock::{SlotClock, SystemTimeSlotClock};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::time::sleep;
-use types::{ChainSpec, Epoch, EthSpec, VoluntaryExit};
+use types::{ChainSpec, Epoch, EthSpec, PublicKeyBytes, VoluntaryExit};
 
 pub const CMD: &str = "exit";
 pub const KEYSTORE_FLAG: &str = "keystore";
@@ -212,7 +212,7 @@ async fn publish_voluntary_exit<E: EthSpec>(
         // by checking the validator status.
         sleep(Duration::from_secs(spec.seconds_per_slot)).await;
 
-        let validator_data = get_validator_data(client, &keypair.pk).await?;
+        let validator_data = get_validator_data(client, &keypair.pk.compress()).await?;
         match validator_data.status {
             ValidatorStatus::ActiveExiting => {
                 let exit_epoch = validator_data.validator.exit_epoch;
@@ -256,7 +256,7 @@ async fn get_validator_index_for_exit(
     epoch: Epoch,
     spec: &ChainSpec,
 ) -> Result<u64, String> {
-    let validator_data = get_validator_data(client, validator_pubkey).await?;
+    let validator_data = get_validator_data(client, &validator_pubkey.compress()).await?;
 
     match validator_data.status {
         ValidatorStatus::ActiveOngoing => {
@@ -283,14 +283,14 @@ async fn get_validator_index_for_exit(
 }
 
 /// Returns the validator data by querying the beacon node client.
-async fn get_validator_data(
+pub(crate) async fn get_validator_data(
     client: &BeaconNodeHttpClient,
-    validator_pubkey: &PublicKey,
+    validator_pubkey: &PublicKeyBytes,
 ) -> Result<ValidatorData, String> {
     Ok(client
         .get_beacon_states_validator_id(
             StateId::Head,
-            &ValidatorId::PublicKey(validator_pubkey.into()),
+            &ValidatorId::PublicKey(*validator_pubkey),
         )
         .await
         .map_err(|e| format!("Failed to get validator details: {:?}", e))?
@@ -305,7 +305,9 @@ async fn get_validator_data(
 }
 
 /// Get genesis data by querying the beacon node client.
-async fn get_geneisis_data(client: &BeaconNodeHttpClient) -> Result<GenesisData, String> {
+pub(crate) async fn get_geneisis_data(
+    client: &BeaconNodeHttpClient,
+) -> Result<GenesisData, String> {
     Ok(client
         .get_beacon_genesis()
         .await
@@ -314,7 +316,7 @@ async fn get_geneisis_data(client: &BeaconNodeHttpClient) -> Result<GenesisData,
 }
 
 /// Gets syncing status from beacon node client and returns true if syncing and false otherwise.
-async fn is_syncing(client: &BeaconNodeHttpClient) -> Result<bool, String> {
+pub(crate) async fn is_syncing(client: &BeaconNodeHttpClient) -> Result<bool, String> {
     Ok(client
         .get_node_syncing()
         .await
@@ -324,7 +326,7 @@ async fn is_syncing(client: &BeaconNodeHttpClient) -> Result<bool, String> {
 }
 
 /// Calculates the current epoch from the genesis time and current time.
-fn get_current_epoch<E: EthSpec>(genesis_time: u64, spec: &ChainSpec) -> Option<Epoch> {
+pub(crate) fn get_current_epoch<E: EthSpec>(genesis_time: u64, spec: &ChainSpec) -> Option<Epoch> {
     let slot_clock = SystemTimeSlotClock::new(
         spec.genesis_slot,
         Duration::from_secs(genesis_time),
@@ -0,0 +1,205 @@
+//! Signs execution layer transactions with the Ethereum app of a Ledger device, connected over
+//! USB HID.
+//!
+//! Only transactions sent from an execution address can be signed this way. Hardware wallets are
+//! unable to sign with BLS keys, so BLS-to-execution changes are not supported.
+use ethers_core::types::{transaction::eip2718::TypedTransaction, Address, Signature, U256};
+use ledger_transport::APDUCommand;
+use ledger_transport_hid::{hidapi::HidApi, TransportNativeHID};
+use std::str::FromStr;
+
+const CLA: u8 = 0xe0;
+const INS_GET_ADDRESS: u8 = 0x02;
+const INS_SIGN_TRANSACTION: u8 = 0x04;
+const P1_FIRST_CHUNK: u8 = 0x00;
+const P1_MORE_CHUNKS: u8 = 0x80;
+/// The maximum length of the data of a single APDU.
+const MAX_CHUNK_LEN: usize = 255;
+const HARDENED: u32 = 0x8000_0000;
+
+const SW_OK: u16 = 0x9000;
+const SW_LOCKED: u16 = 0x5515;
+const SW_REJECTED: u16 = 0x6985;
+const SW_INVALID_DATA: u16 = 0x6a80;
+const SW_WRONG_APP: [u16; 3] = [0x6d00, 0x6e00, 0x6e01];
+
+/// An account of the Ethereum app of a Ledger device.
+pub struct LedgerSigner {
+    transport: TransportNativeHID,
+    path: Vec<u32>,
+    address: Address,
+}
+
+impl LedgerSigner {
+    /// Connects to the first Ledger device found and reads the address of the account at
+    /// `derivation_path`.
+    pub fn connect(derivation_path: &str) -> Result<Self, String> {
+        let path = parse_derivation_path(derivation_path)?;
+        let api = HidApi::new().map_err(|e| format!("Unable to access USB devices: {:?}", e))?;
+        let transport = TransportNativeHID::new(&api)
+            .map_err(|e| format!("Unable to connect to a Ledger device: {}", e))?;
+
+        let mut signer = Self {
+            transport,
+            path,
+            address: Address::zero(),
+        };
+        let response = signer.exchange(INS_GET_ADDRESS, P1_FIRST_CHUNK, signer.path_bytes())?;
+        signer.address = parse_address_response(&response)?;
+        Ok(signer)
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs `tx`, which must be complete, after the user approves it on the device.
+    pub fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        let mut payload = self.path_bytes();
+        payload.extend_from_slice(&tx.rlp());
+
+        let mut response = vec![];
+        for (i, chunk) in payload.chunks(MAX_CHUNK_LEN).enumerate() {
+            let p1 = if i == 0 {
+                P1_FIRST_CHUNK
+            } else {
+                P1_MORE_CHUNKS
+            };
+            response = self.exchange(INS_SIGN_TRANSACTION, p1, chunk.to_vec())?;
+        }
+        if response.len() != 65 {
+            return Err(format!(
+                "Unexpected Ledger signature length: {}",
+                response.len()
+            ));
+        }
+
+        let r = U256::from_big_endian(&response[1..33]);
+        let s = U256::from_big_endian(&response[33..65]);
+        // The `v` returned by the device depends on the transaction type and chain id, so find
+        // the parity which recovers the address of the account instead.
+        let sighash = tx.sighash();
+        (0..=1)
+            .map(|v| Signature { r, s, v })
+            .find(|signature| signature.recover(sighash).ok() == Some(self.address))
+            .ok_or_else(|| "The Ledger signature does not match the account".to_string())
+    }
+
+    fn path_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.path.len() * 4);
+        bytes.push(self.path.len() as u8);
+        for index in &self.path {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn exchange(&self, ins: u8, p1: u8, data: Vec<u8>) -> Result<Vec<u8>, String> {
+        let command = APDUCommand {
+            cla: CLA,
+            ins,
+            p1,
+            p2: 0,
+            data,
+        };
+        let answer = self
+            .transport
+            .exchange(&command)
+            .map_err(|e| format!("Unable to communicate with the Ledger device: {}", e))?;
+
+        match answer.retcode() {
+            SW_OK => Ok(answer.data().to_vec()),
+            SW_LOCKED => Err("The Ledger device is locked".to_string()),
+            SW_REJECTED => Err("The request was rejected on the Ledger device".to_string()),
+            SW_INVALID_DATA => Err(
+                "The Ledger device refused the request. Enable blind signing in the settings of \
+                the Ethereum app, since the request calls a contract which the app does not \
+                recognise"
+                    .to_string(),
+            ),
+            code if SW_WRONG_APP.contains(&code) => {
+                Err("Open the Ethereum app on the Ledger device".to_string())
+            }
+            code => Err(format!("The Ledger device returned error 0x{:04x}", code)),
+        }
+    }
+}
+
+/// Parses a BIP-32 derivation path such as `m/44'/60'/0'/0/0`.
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, String> {
+    let invalid = || format!("Invalid derivation path: {}", path);
+
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(invalid());
+    }
+    let indices = segments
+        .map(|segment| {
+            let (index, hardened) = match segment.strip_suffix('\'') {
+                Some(index) => (index, true),
+                None => (segment, false),
+            };
+            let index = index.parse::<u32>().map_err(|_| invalid())?;
+            if index >= HARDENED {
+                return Err(invalid());
+            }
+            Ok(if hardened { index | HARDENED } else { index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The device accepts at most 10 indices.
+    if indices.is_empty() || indices.len() > 10 {
+        return Err(invalid());
+    }
+    Ok(indices)
+}
+
+/// Parses the response to `INS_GET_ADDRESS`, which contains the length-prefixed public key and
+/// hex address of the account.
+fn parse_address_response(response: &[u8]) -> Result<Address, String> {
+    let invalid = || "Invalid Ledger address response".to_string();
+
+    let pubkey_len = *response.first().ok_or_else(invalid)? as usize;
+    let address_start = 2 + pubkey_len;
+    let address_len = *response.get(address_start - 1).ok_or_else(invalid)? as usize;
+    let address = response
+        .get(address_start..address_start + address_len)
+        .ok_or_else(invalid)?;
+    let address = std::str::from_utf8(address).map_err(|_| invalid())?;
+    Address::from_str(address).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::execution_request::DEFAULT_LEDGER_DERIVATION_PATH;
+
+    #[test]
+    fn derivation_path() {
+        assert_eq!(
+            parse_derivation_path(DEFAULT_LEDGER_DERIVATION_PATH),
+            Ok(vec![44 | HARDENED, 60 | HARDENED, HARDENED, 0, 0])
+        );
+        assert_eq!(parse_derivation_path("m/1/2"), Ok(vec![1, 2]));
+
+        for invalid in ["", "m", "m/", "44'/60'", "m/x", "m/2147483648", "m/1''"] {
+            assert!(parse_derivation_path(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn address_response() {
+        let address = "8ba1f109551bD432803012645Ac136ddd64DBA72";
+        let mut response = vec![65];
+        response.extend_from_slice(&[4; 65]);
+        response.push(address.len() as u8);
+        response.extend_from_slice(address.as_bytes());
+
+        assert_eq!(
+            parse_address_response(&response),
+            Ok(Address::from_str(address).unwrap())
+        );
+        assert!(parse_address_response(&response[..response.len() - 1]).is_err());
+        assert!(parse_address_response(&[]).is_err());
+    }
+}
@@ -1,10 +1,16 @@
+pub mod bls_to_execution_change;
+pub mod consolidate;
 pub mod create;
+pub mod execution_request;
 pub mod exit;
 pub mod import;
+#[cfg(feature = "ledger")]
+pub mod ledger;
 pub mod list;
 pub mod modify;
 pub mod recover;
 pub mod slashing_protection;
+pub mod withdraw;
 
 use crate::{VALIDATOR_DIR_FLAG, VALIDATOR_DIR_FLAG_ALIAS};
 use clap::{Arg, ArgAction, ArgMatches, Command};
@@ -49,6 +55,9 @@ pub fn cli_app() -> Command {
         .subcommand(recover::cli_app())
         .subcommand(slashing_protection::cli_app())
         .subcommand(exit::cli_app())
+        .subcommand(consolidate::cli_app())
+        .subcommand(withdraw::cli_app())
+        .subcommand(bls_to_execution_change::cli_app())
 }
 
 pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
@@ -70,6 +79,11 @@ pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<
             slashing_protection::cli_run(matches, env, validator_base_dir)
         }
         Some((exit::CMD, matches)) => exit::cli_run(matches, env),
+        Some((consolidate::CMD, matches)) => consolidate::cli_run(matches, env),
+        Some((withdraw::CMD, matches)) => withdraw::cli_run(matches, env),
+        Some((bls_to_execution_change::CMD, matches)) => {
+            bls_to_execution_change::cli_run(matches, env)
+        }
         Some((unknown, _)) => Err(format!(
             "{} does not have a {} command. See --help",
             CMD, unknown
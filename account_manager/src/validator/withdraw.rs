@@ -0,0 +1,263 @@
+use crate::validator::execution_request::{
+    add_execution_request_args, check_beacon_node, ExecutionRequestSubmitter,
+};
+use crate::validator::exit::{
+    get_validator_data, BEACON_SERVER_FLAG, DEFAULT_BEACON_NODE, NO_CONFIRMATION,
+};
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use deposit_contract::{encode_withdrawal_request_tx_data, WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS};
+use environment::Environment;
+use eth2::{types::ValidatorStatus, BeaconNodeHttpClient, Timeouts};
+use eth2_network_config::Eth2NetworkConfig;
+use safe_arith::SafeArith;
+use sensitive_url::SensitiveUrl;
+use std::time::Duration;
+use types::{Address, ChainSpec, Epoch, EthSpec, PublicKeyBytes};
+
+pub const CMD: &str = "withdraw";
+pub const VALIDATOR_FLAG: &str = "validator";
+pub const AMOUNT_FLAG: &str = "amount";
+
+pub const CONFIRMATION_PHRASE: &str = "Withdraw from my validator";
+pub const EXIT_CONFIRMATION_PHRASE: &str = crate::validator::exit::CONFIRMATION_PHRASE;
+pub const WEBSITE_URL: &str = "https://lighthouse-book.sigmaprime.io/partial-withdrawal.html";
+
+pub fn cli_app() -> Command {
+    let cmd = Command::new(CMD)
+        .about(
+            "Submits an EIP-7002 withdrawal request to the execution layer, which withdraws \
+            part or all of the balance of a validator to its withdrawal address. The request is \
+            sent from the withdrawal address, so it must be signed with the key of that address \
+            rather than the validator voting keystore.",
+        )
+        .arg(
+            Arg::new(VALIDATOR_FLAG)
+                .long(VALIDATOR_FLAG)
+                .value_name("PUBKEY")
+                .help("The public key of the validator to withdraw from")
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(AMOUNT_FLAG)
+                .long(AMOUNT_FLAG)
+                .value_name("GWEI")
+                .help(
+                    "The amount to withdraw, in gwei. Partial withdrawals require 0x02 \
+                    withdrawal credentials and may not reduce the balance of the validator below \
+                    32 ETH. An amount of 0 requests a full exit of the validator",
+                )
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(BEACON_SERVER_FLAG)
+                .long(BEACON_SERVER_FLAG)
+                .value_name("NETWORK_ADDRESS")
+                .help("Address to a beacon node HTTP API")
+                .default_value(DEFAULT_BEACON_NODE)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new(NO_CONFIRMATION)
+                .long(NO_CONFIRMATION)
+                .help("Submits the request without prompting for confirmation that you understand the implications of a withdrawal. This should be used with caution")
+                .display_order(0)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+        );
+    add_execution_request_args(cmd)
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    let validator: PublicKeyBytes = clap_utils::parse_required(matches, VALIDATOR_FLAG)?;
+    let amount: u64 = clap_utils::parse_required(matches, AMOUNT_FLAG)?;
+
+    let stdin_inputs = cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG);
+    let no_confirmation = matches.get_flag(NO_CONFIRMATION);
+
+    let spec = env.eth2_config().spec.clone();
+    let server_url: String = clap_utils::parse_required(matches, BEACON_SERVER_FLAG)?;
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&server_url)
+            .map_err(|e| format!("Failed to parse beacon http server: {:?}", e))?,
+        Timeouts::set_all(Duration::from_secs(env.eth2_config.spec.seconds_per_slot)),
+    );
+
+    let submitter = ExecutionRequestSubmitter::from_matches(matches)?;
+
+    let eth2_network_config = env
+        .eth2_network_config
+        .clone()
+        .expect("network should have a valid config");
+
+    env.runtime().block_on(publish_withdrawal_request::<E>(
+        validator,
+        amount,
+        &client,
+        &submitter,
+        &spec,
+        &eth2_network_config,
+        stdin_inputs,
+        no_confirmation,
+    ))
+}
+
+/// Checks that the withdrawal can be processed by the beacon chain, then builds, signs and
+/// submits the transaction which requests it.
+#[allow(clippy::too_many_arguments)]
+async fn publish_withdrawal_request<E: EthSpec>(
+    validator: PublicKeyBytes,
+    amount: u64,
+    client: &BeaconNodeHttpClient,
+    submitter: &ExecutionRequestSubmitter,
+    spec: &ChainSpec,
+    eth2_network_config: &Eth2NetworkConfig,
+    stdin_inputs: bool,
+    no_confirmation: bool,
+) -> Result<(), String> {
+    let epoch = check_beacon_node::<E>(client, eth2_network_config, spec).await?;
+    let withdrawal_address =
+        get_withdrawal_address(client, &validator, amount, epoch, spec).await?;
+
+    let is_full_exit = amount == spec.full_exit_request_amount;
+    if is_full_exit {
+        eprintln!("Requesting a full exit of validator {}\n", validator);
+    } else {
+        eprintln!(
+            "Requesting a withdrawal of {} gwei from validator {}\n",
+            amount, validator
+        );
+    }
+    let tx = submitter
+        .build_transaction(
+            withdrawal_address,
+            WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+            encode_withdrawal_request_tx_data(&validator, amount),
+        )
+        .await?;
+
+    if submitter.dry_run {
+        eprintln!("Dry run, the withdrawal request was not submitted");
+        return Ok(());
+    }
+
+    let confirmation_phrase = if is_full_exit {
+        EXIT_CONFIRMATION_PHRASE
+    } else {
+        CONFIRMATION_PHRASE
+    };
+    if !no_confirmation {
+        eprintln!("WARNING: THIS IS AN IRREVERSIBLE OPERATION\n");
+        if is_full_exit {
+            eprintln!("The validator will be exited and its balance withdrawn.\n");
+        }
+        eprintln!(
+            "PLEASE VISIT {} TO MAKE SURE YOU UNDERSTAND THE IMPLICATIONS OF A WITHDRAWAL REQUEST.",
+            WEBSITE_URL
+        );
+        eprintln!(
+            "Enter the phrase \"{}\" to confirm the withdrawal request: ",
+            confirmation_phrase
+        );
+    }
+
+    let confirmation = if !no_confirmation {
+        account_utils::read_input_from_user(stdin_inputs)?
+    } else {
+        confirmation_phrase.to_string()
+    };
+
+    if confirmation != confirmation_phrase {
+        eprintln!(
+            "Did not submit the withdrawal request. Please check that you entered the correct \
+            phrase."
+        );
+        return Ok(());
+    }
+
+    submitter.submit(tx, spec).await?;
+
+    Ok(())
+}
+
+/// Returns the withdrawal address of the validator, which must send the withdrawal request.
+///
+/// Returns an error if the beacon chain would ignore the withdrawal request.
+async fn get_withdrawal_address(
+    client: &BeaconNodeHttpClient,
+    validator_pubkey: &PublicKeyBytes,
+    amount: u64,
+    epoch: Epoch,
+    spec: &ChainSpec,
+) -> Result<Address, String> {
+    let validator_data = get_validator_data(client, validator_pubkey).await?;
+    if validator_data.status != ValidatorStatus::ActiveOngoing {
+        return Err(format!(
+            "Validator {} cannot withdraw. Validator status: {:?}",
+            validator_pubkey, validator_data.status
+        ));
+    }
+    let validator = &validator_data.validator;
+    let withdrawal_address = validator
+        .get_execution_withdrawal_address(spec)
+        .ok_or_else(|| {
+            format!(
+                "Validator {} does not have execution withdrawal credentials",
+                validator_pubkey
+            )
+        })?;
+
+    let eligible_epoch = validator
+        .activation_epoch
+        .safe_add(spec.shard_committee_period)
+        .map_err(|e| {
+            format!(
+                "Failed to calculate eligible epoch, validator activation epoch too high: {:?}",
+                e
+            )
+        })?;
+    if epoch < eligible_epoch {
+        return Err(format!(
+            "Validator {} is not eligible to withdraw. It will become eligible on epoch {}",
+            validator_pubkey, eligible_epoch
+        ));
+    }
+
+    if amount != spec.full_exit_request_amount {
+        if !validator.has_compounding_withdrawal_credential(spec) {
+            return Err(format!(
+                "Validator {} does not have 0x02 withdrawal credentials, so it can only be \
+                exited with an amount of 0. Balances above 32 ETH are withdrawn automatically \
+                for 0x01 withdrawal credentials",
+                validator_pubkey
+            ));
+        }
+        let excess_balance = validator_data
+            .balance
+            .saturating_sub(spec.min_activation_balance);
+        if amount > excess_balance {
+            return Err(format!(
+                "Validator {} has {} gwei available to withdraw, which is less than the \
+                requested {} gwei",
+                validator_pubkey, excess_balance, amount
+            ));
+        }
+    }
+
+    Ok(withdrawal_address)
+}
@@ -1,4 +1,4 @@
-use crate::common::read_wallet_name_from_cli;
+use crate::common::{kdf_args, kdf_params_from_cli, read_wallet_name_from_cli};
 use crate::WALLETS_DIR_FLAG;
 use account_utils::{
     is_password_sufficiently_complex, random_password, read_password_from_user, strip_off_newlines,
@@ -6,7 +6,7 @@ use account_utils::{
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use eth2_wallet::{
     bip39::{Language, Mnemonic, MnemonicType},
-    PlainText,
+    mnemonic_with_os_entropy, PlainText,
 };
 use eth2_wallet_manager::{LockedWallet, WalletManager, WalletType};
 use filesystem::create_with_600_perms;
@@ -22,6 +22,7 @@ pub const TYPE_FLAG: &str = "type";
 pub const MNEMONIC_FLAG: &str = "mnemonic-output-path";
 pub const STDIN_INPUTS_FLAG: &str = "stdin-inputs";
 pub const MNEMONIC_LENGTH_FLAG: &str = "mnemonic-length";
+pub const MIX_OS_ENTROPY_FLAG: &str = "mix-os-entropy";
 pub const MNEMONIC_TYPES: &[MnemonicType] = &[
     MnemonicType::Words12,
     MnemonicType::Words15,
@@ -117,6 +118,17 @@ pub fn cli_app() -> Command {
                 .default_value("24")
                 .display_order(0)
         )
+        .arg(
+            Arg::new(MIX_OS_ENTROPY_FLAG)
+                .long(MIX_OS_ENTROPY_FLAG)
+                .help(
+                    "If present, the mnemonic entropy is mixed with entropy read directly from \
+                    the operating system RNG (which may be backed by a hardware RNG).",
+                )
+                .action(ArgAction::SetTrue)
+                .display_order(0)
+        )
+        .args(kdf_args())
 }
 
 pub fn cli_run(matches: &ArgMatches, wallet_base_dir: PathBuf) -> Result<(), String> {
@@ -126,10 +138,14 @@ pub fn cli_run(matches: &ArgMatches, wallet_base_dir: PathBuf) -> Result<(), Str
     //
     // The `tiny-bip39` crate uses `thread_rng()` for this entropy.
     let mnemonic_length = clap_utils::parse_required(matches, MNEMONIC_LENGTH_FLAG)?;
-    let mnemonic = Mnemonic::new(
-        MnemonicType::for_word_count(mnemonic_length).expect("Mnemonic length already validated"),
-        Language::English,
-    );
+    let mnemonic_type =
+        MnemonicType::for_word_count(mnemonic_length).expect("Mnemonic length already validated");
+    let mnemonic = if matches.get_flag(MIX_OS_ENTROPY_FLAG) {
+        mnemonic_with_os_entropy(mnemonic_type, Language::English)
+            .map_err(|e| format!("Unable to generate mnemonic: {:?}", e))?
+    } else {
+        Mnemonic::new(mnemonic_type, Language::English)
+    };
 
     let wallet = create_wallet_from_mnemonic(matches, wallet_base_dir.as_path(), &mnemonic)?;
 
@@ -171,6 +187,7 @@ pub fn create_wallet_from_mnemonic(
     let wallet_password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
     let type_field: String = clap_utils::parse_required(matches, TYPE_FLAG)?;
     let stdin_inputs = cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG);
+    let kdf_params = kdf_params_from_cli(matches)?;
     let wallet_type = match type_field.as_ref() {
         HD_TYPE => WalletType::Hd,
         unknown => return Err(format!("--{} {} is not supported", TYPE_FLAG, unknown)),
@@ -203,11 +220,12 @@ pub fn create_wallet_from_mnemonic(
     let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
 
     let wallet = mgr
-        .create_wallet(
+        .create_wallet_with_kdf(
             wallet_name,
             wallet_type,
             mnemonic,
             wallet_password.as_bytes(),
+            kdf_params,
         )
         .map_err(|e| format!("Unable to create wallet: {:?}", e))?;
     Ok(wallet)
@@ -1,3 +1,4 @@
+use crate::common::kdf_args;
 use crate::wallet::create::{create_wallet_from_mnemonic, STDIN_INPUTS_FLAG};
 use crate::wallet::create::{HD_TYPE, NAME_FLAG, PASSWORD_FLAG, TYPE_FLAG};
 use account_utils::read_mnemonic_from_cli;
@@ -64,6 +65,7 @@ pub fn cli_app() -> Command {
                 .help("If present, read all user inputs from stdin instead of tty.")
                 .display_order(0),
         )
+        .args(kdf_args())
 }
 
 pub fn cli_run(matches: &ArgMatches, wallet_base_dir: PathBuf) -> Result<(), String> {
@@ -43,13 +43,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Get state
         let state_slot = (epoch + 1).end_slot(T::EthSpec::slots_per_epoch());
 
-        let state_root = self
-            .state_root_at_slot(state_slot)?
-            .ok_or(BeaconChainError::NoStateForSlot(state_slot))?;
-
-        let state = self
-            .get_state(&state_root, Some(state_slot))?
-            .ok_or(BeaconChainError::MissingBeaconState(state_root))?;
+        let state = match self.state_root_at_slot(state_slot)? {
+            Some(state_root) => self
+                .get_state(&state_root, Some(state_slot))?
+                .ok_or(BeaconChainError::MissingBeaconState(state_root))?,
+            // Finalized states between the historic state limits are not stored.
+            None if state_slot < self.store.get_split_slot() => {
+                self.get_replayed_historical_state(state_slot)?
+            }
+            None => return Err(BeaconChainError::NoStateForSlot(state_slot)),
+        };
 
         match state {
             BeaconState::Base(_) => self.compute_attestation_rewards_base(state, validators),
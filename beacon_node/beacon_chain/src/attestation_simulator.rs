@@ -10,7 +10,8 @@ use types::{EthSpec, Slot};
 /// behind the wall-clock slot.
 const SYNCING_TOLERANCE_EPOCHS: u64 = 2;
 
-/// Spawns a routine which produces an unaggregated attestation at every slot.
+/// Spawns a routine which simulates the duties of a validator at every slot: a block proposal, an
+/// unaggregated attestation and a sync committee message.
 ///
 /// This routine will run once per slot
 pub fn start_attestation_simulator_service<T: BeaconChainTypes>(
@@ -23,7 +24,9 @@ pub fn start_attestation_simulator_service<T: BeaconChainTypes>(
     );
 }
 
-/// Loop indefinitely, calling `BeaconChain::produce_unaggregated_attestation` every 4s into each slot.
+/// Loop indefinitely, simulating a proposal at the start of each slot, then calling
+/// `BeaconChain::produce_unaggregated_attestation` and simulating a sync committee message every
+/// 4s into each slot.
 async fn attestation_simulator_service<T: BeaconChainTypes>(
     executor: TaskExecutor,
     chain: Arc<BeaconChain<T>>,
@@ -34,7 +37,13 @@ async fn attestation_simulator_service<T: BeaconChainTypes>(
     loop {
         match chain.slot_clock.duration_to_next_slot() {
             Some(duration) => {
-                sleep(duration + additional_delay).await;
+                sleep(duration).await;
+
+                if let Ok(current_slot) = chain.slot() {
+                    simulate_proposal(&chain, current_slot);
+                }
+
+                sleep(additional_delay).await;
 
                 debug!(
                     chain.log,
@@ -46,6 +55,7 @@ async fn attestation_simulator_service<T: BeaconChainTypes>(
                 executor.spawn(
                     async move {
                         if let Ok(current_slot) = inner_chain.slot() {
+                            simulate_sync_committee_message(&inner_chain, current_slot);
                             produce_unaggregated_attestation(inner_chain, current_slot);
                         }
                     },
@@ -61,17 +71,22 @@ async fn attestation_simulator_service<T: BeaconChainTypes>(
     }
 }
 
+/// Returns `true` if the head slot is too far behind the wall-clock slot to run the simulator.
+///
+/// This helps prevent the simulator from becoming a burden by computing committees from old
+/// states.
+fn is_syncing<T: BeaconChainTypes>(chain: &BeaconChain<T>, current_slot: Slot) -> bool {
+    let syncing_tolerance_slots = SYNCING_TOLERANCE_EPOCHS * T::EthSpec::slots_per_epoch();
+    chain.best_slot() + syncing_tolerance_slots < current_slot
+}
+
 pub fn produce_unaggregated_attestation<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     current_slot: Slot,
 ) {
     // Don't run the attestation simulator when the head slot is far behind the
     // wall-clock slot.
-    //
-    // This helps prevent the simulator from becoming a burden by computing
-    // committees from old states.
-    let syncing_tolerance_slots = SYNCING_TOLERANCE_EPOCHS * T::EthSpec::slots_per_epoch();
-    if chain.best_slot() + syncing_tolerance_slots < current_slot {
+    if is_syncing(&chain, current_slot) {
         return;
     }
 
@@ -105,3 +120,55 @@ pub fn produce_unaggregated_attestation<T: BeaconChainTypes>(
         }
     }
 }
+
+/// Simulates a sync committee message at `current_slot`, which votes for the head block root, and
+/// stores it in the validator monitor for later processing.
+pub fn simulate_sync_committee_message<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    current_slot: Slot,
+) {
+    if is_syncing(chain, current_slot)
+        || !chain
+            .spec
+            .fork_name_at_slot::<T::EthSpec>(current_slot)
+            .altair_enabled()
+    {
+        return;
+    }
+
+    let beacon_block_root = chain.head_beacon_block_root();
+
+    debug!(
+        chain.log,
+        "Simulated sync committee message";
+        "slot" => current_slot,
+        "beacon_block_root" => ?beacon_block_root,
+    );
+
+    chain
+        .validator_monitor
+        .write()
+        .set_simulated_sync_committee_message(current_slot, beacon_block_root);
+}
+
+/// Simulates a block proposal at the start of `current_slot`, which would be built upon the head
+/// block, and stores its parent in the validator monitor for later processing.
+pub fn simulate_proposal<T: BeaconChainTypes>(chain: &BeaconChain<T>, current_slot: Slot) {
+    if is_syncing(chain, current_slot) || chain.best_slot() >= current_slot {
+        return;
+    }
+
+    let parent_root = chain.head_beacon_block_root();
+
+    debug!(
+        chain.log,
+        "Simulated block proposal";
+        "slot" => current_slot,
+        "parent_root" => ?parent_root,
+    );
+
+    chain
+        .validator_monitor
+        .write()
+        .set_simulated_proposal(current_slot, parent_root);
+}
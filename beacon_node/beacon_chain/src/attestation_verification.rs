@@ -65,6 +65,9 @@ use types::{
     SignedAggregateAndProof, Slot, SubnetId,
 };
 
+pub(crate) use batch::{
+    aggregated_attestation_signature_sets, unaggregated_attestation_signature_set,
+};
 pub use batch::{batch_verify_aggregated_attestations, batch_verify_unaggregated_attestations};
 
 /// Returned when an attestation was not successfully verified. It might not have been verified for
@@ -272,7 +275,7 @@ impl From<BeaconChainError> for Error {
 
 /// Used to avoid double-checking signatures.
 #[derive(Copy, Clone)]
-enum CheckAttestationSignature {
+pub(crate) enum CheckAttestationSignature {
     Yes,
     No,
 }
@@ -282,7 +285,7 @@ enum CheckAttestationSignature {
 ///
 /// These attestations have *not* undergone signature verification.
 /// The `observed_attestation_key_root` is the hashed value of an `ObservedAttestationKey`.
-struct IndexedAggregatedAttestation<'a, T: BeaconChainTypes> {
+pub(crate) struct IndexedAggregatedAttestation<'a, T: BeaconChainTypes> {
     signed_aggregate: &'a SignedAggregateAndProof<T::EthSpec>,
     indexed_attestation: IndexedAttestation<T::EthSpec>,
     observed_attestation_key_root: Hash256,
@@ -292,7 +295,7 @@ struct IndexedAggregatedAttestation<'a, T: BeaconChainTypes> {
 /// be derived.
 ///
 /// These attestations have *not* undergone signature verification.
-struct IndexedUnaggregatedAttestation<'a, T: BeaconChainTypes> {
+pub(crate) struct IndexedUnaggregatedAttestation<'a, T: BeaconChainTypes> {
     attestation: AttestationRef<'a, T::EthSpec>,
     indexed_attestation: IndexedAttestation<T::EthSpec>,
     subnet_id: SubnetId,
@@ -397,7 +400,7 @@ fn process_slash_info<T: BeaconChainTypes>(
 ) -> Error {
     use AttestationSlashInfo::*;
 
-    if let Some(slasher) = chain.slasher.as_ref() {
+    if chain.slasher_enabled() {
         let (indexed_attestation, check_signature, err) = match slash_info {
             SignatureNotChecked(attestation, err) => {
                 if let Error::UnknownHeadBlock { .. } = err {
@@ -435,7 +438,7 @@ fn process_slash_info<T: BeaconChainTypes>(
         }
 
         // Supply to slasher.
-        slasher.accept_attestation(indexed_attestation);
+        chain.slasher_accept_attestation(indexed_attestation);
 
         err
     } else {
@@ -457,8 +460,9 @@ impl<'a, T: BeaconChainTypes> IndexedAggregatedAttestation<'a, T> {
     ) -> Result<Self, Error> {
         Self::verify_slashable(signed_aggregate, chain)
             .map(|verified_aggregate| {
-                if let Some(slasher) = chain.slasher.as_ref() {
-                    slasher.accept_attestation(verified_aggregate.indexed_attestation.clone());
+                if chain.slasher_enabled() {
+                    chain
+                        .slasher_accept_attestation(verified_aggregate.indexed_attestation.clone());
                 }
                 verified_aggregate
             })
@@ -713,7 +717,7 @@ impl<'a, T: BeaconChainTypes> VerifiedAggregatedAttestation<'a, T> {
     }
 
     /// Complete the verification of an indexed attestation.
-    fn from_indexed(
+    pub(crate) fn from_indexed(
         signed_aggregate: IndexedAggregatedAttestation<'a, T>,
         chain: &BeaconChain<T>,
         check_signature: CheckAttestationSignature,
@@ -724,8 +728,8 @@ impl<'a, T: BeaconChainTypes> VerifiedAggregatedAttestation<'a, T> {
     }
 
     fn apply_to_slasher(self, chain: &BeaconChain<T>) -> Self {
-        if let Some(slasher) = chain.slasher.as_ref() {
-            slasher.accept_attestation(self.indexed_attestation.clone());
+        if chain.slasher_enabled() {
+            chain.slasher_accept_attestation(self.indexed_attestation.clone());
         }
         self
     }
@@ -893,8 +897,10 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
     ) -> Result<Self, Error> {
         Self::verify_slashable(attestation.to_ref(), subnet_id, chain)
             .map(|verified_unaggregated| {
-                if let Some(slasher) = chain.slasher.as_ref() {
-                    slasher.accept_attestation(verified_unaggregated.indexed_attestation.clone());
+                if chain.slasher_enabled() {
+                    chain.slasher_accept_attestation(
+                        verified_unaggregated.indexed_attestation.clone(),
+                    );
                 }
                 verified_unaggregated
             })
@@ -988,7 +994,7 @@ impl<'a, T: BeaconChainTypes> VerifiedUnaggregatedAttestation<'a, T> {
     }
 
     /// Complete the verification of an indexed attestation.
-    fn from_indexed(
+    pub(crate) fn from_indexed(
         attestation: IndexedUnaggregatedAttestation<'a, T>,
         chain: &BeaconChain<T>,
         check_signature: CheckAttestationSignature,
@@ -999,8 +1005,8 @@ impl<'a, T: BeaconChainTypes> VerifiedUnaggregatedAttestation<'a, T> {
     }
 
     fn apply_to_slasher(self, chain: &BeaconChain<T>) -> Self {
-        if let Some(slasher) = chain.slasher.as_ref() {
-            slasher.accept_attestation(self.indexed_attestation.clone());
+        if chain.slasher_enabled() {
+            chain.slasher_accept_attestation(self.indexed_attestation.clone());
         }
         self
     }
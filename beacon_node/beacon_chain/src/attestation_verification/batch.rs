@@ -13,8 +13,9 @@ use super::{
     CheckAttestationSignature, Error, IndexedAggregatedAttestation, IndexedUnaggregatedAttestation,
     VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
 };
+use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
-use bls::verify_signature_sets;
+use bls::{verify_signature_sets, SignatureSet};
 use state_processing::signature_sets::{
     indexed_attestation_signature_set_from_pubkeys, signed_aggregate_selection_proof_signature_set,
     signed_aggregate_signature_set,
@@ -62,43 +63,11 @@ where
         let mut signature_sets = Vec::with_capacity(num_indexed * 3);
         // Iterate, flattening to get only the `Ok` values.
         for indexed in indexing_results.iter().flatten() {
-            let signed_aggregate = &indexed.signed_aggregate;
-            let indexed_attestation = &indexed.indexed_attestation;
-            let fork = chain
-                .spec
-                .fork_at_epoch(indexed_attestation.data().target.epoch);
-
-            signature_sets.push(
-                signed_aggregate_selection_proof_signature_set(
-                    |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
-                    signed_aggregate,
-                    &fork,
-                    chain.genesis_validators_root,
-                    &chain.spec,
-                )
-                .map_err(BeaconChainError::SignatureSetError)?,
-            );
-            signature_sets.push(
-                signed_aggregate_signature_set(
-                    |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
-                    signed_aggregate,
-                    &fork,
-                    chain.genesis_validators_root,
-                    &chain.spec,
-                )
-                .map_err(BeaconChainError::SignatureSetError)?,
-            );
-            signature_sets.push(
-                indexed_attestation_signature_set_from_pubkeys(
-                    |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
-                    indexed_attestation.signature(),
-                    indexed_attestation,
-                    &fork,
-                    chain.genesis_validators_root,
-                    &chain.spec,
-                )
-                .map_err(BeaconChainError::SignatureSetError)?,
-            );
+            signature_sets.extend(aggregated_attestation_signature_sets(
+                indexed,
+                &pubkey_cache,
+                chain,
+            )?);
         }
 
         metrics::stop_timer(signature_setup_timer);
@@ -169,22 +138,11 @@ where
 
         // Iterate, flattening to get only the `Ok` values.
         for partially_verified in partial_results.iter().flatten() {
-            let indexed_attestation = &partially_verified.indexed_attestation;
-            let fork = chain
-                .spec
-                .fork_at_epoch(indexed_attestation.data().target.epoch);
-
-            let signature_set = indexed_attestation_signature_set_from_pubkeys(
-                |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
-                indexed_attestation.signature(),
-                indexed_attestation,
-                &fork,
-                chain.genesis_validators_root,
-                &chain.spec,
-            )
-            .map_err(BeaconChainError::SignatureSetError)?;
-
-            signature_sets.push(signature_set);
+            signature_sets.push(unaggregated_attestation_signature_set(
+                partially_verified,
+                &pubkey_cache,
+                chain,
+            )?);
         }
 
         metrics::stop_timer(signature_setup_timer);
@@ -212,3 +170,67 @@ where
 
     Ok(final_results)
 }
+
+/// Returns the three signature sets of an aggregate: the selection proof, the signature of the
+/// aggregator and the signature of the aggregated attestation.
+pub(crate) fn aggregated_attestation_signature_sets<'b, T: BeaconChainTypes>(
+    indexed: &'b IndexedAggregatedAttestation<'_, T>,
+    pubkey_cache: &'b ValidatorPubkeyCache<T>,
+    chain: &'b BeaconChain<T>,
+) -> Result<[SignatureSet<'b>; 3], BeaconChainError> {
+    let signed_aggregate = indexed.signed_aggregate;
+    let indexed_attestation = &indexed.indexed_attestation;
+    let fork = chain
+        .spec
+        .fork_at_epoch(indexed_attestation.data().target.epoch);
+
+    Ok([
+        signed_aggregate_selection_proof_signature_set(
+            |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+            signed_aggregate,
+            &fork,
+            chain.genesis_validators_root,
+            &chain.spec,
+        )
+        .map_err(BeaconChainError::SignatureSetError)?,
+        signed_aggregate_signature_set(
+            |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+            signed_aggregate,
+            &fork,
+            chain.genesis_validators_root,
+            &chain.spec,
+        )
+        .map_err(BeaconChainError::SignatureSetError)?,
+        indexed_attestation_signature_set_from_pubkeys(
+            |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+            indexed_attestation.signature(),
+            indexed_attestation,
+            &fork,
+            chain.genesis_validators_root,
+            &chain.spec,
+        )
+        .map_err(BeaconChainError::SignatureSetError)?,
+    ])
+}
+
+/// Returns the signature set of an unaggregated attestation.
+pub(crate) fn unaggregated_attestation_signature_set<'b, T: BeaconChainTypes>(
+    partially_verified: &'b IndexedUnaggregatedAttestation<'_, T>,
+    pubkey_cache: &'b ValidatorPubkeyCache<T>,
+    chain: &'b BeaconChain<T>,
+) -> Result<SignatureSet<'b>, BeaconChainError> {
+    let indexed_attestation = &partially_verified.indexed_attestation;
+    let fork = chain
+        .spec
+        .fork_at_epoch(indexed_attestation.data().target.epoch);
+
+    indexed_attestation_signature_set_from_pubkeys(
+        |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+        indexed_attestation.signature(),
+        indexed_attestation,
+        &fork,
+        chain.genesis_validators_root,
+        &chain.spec,
+    )
+    .map_err(BeaconChainError::SignatureSetError)
+}
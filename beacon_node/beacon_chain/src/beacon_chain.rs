@@ -8,6 +8,7 @@ use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckCaches};
 use crate::beacon_proposer_cache::compute_proposer_duties_from_head;
 use crate::beacon_proposer_cache::BeaconProposerCache;
 use crate::blob_verification::{GossipBlobError, GossipVerifiedBlob};
+use crate::block_production_budget::{BlockProductionStage, BlockProductionTimer};
 use crate::block_times_cache::BlockTimesCache;
 use crate::block_verification::POS_PANDA_BANNER;
 use crate::block_verification::{
@@ -19,7 +20,7 @@ use crate::block_verification_types::{
     AsBlock, AvailableExecutedBlock, BlockImportData, ExecutedBlock, RpcBlock,
 };
 pub use crate::canonical_head::CanonicalHead;
-use crate::chain_config::ChainConfig;
+use crate::chain_config::{ChainConfig, InvalidPayloadHandling, ReOrgConfig};
 use crate::data_availability_checker::{
     Availability, AvailabilityCheckError, AvailableBlock, DataAvailabilityChecker,
 };
@@ -31,9 +32,12 @@ use crate::eth1_finalization_cache::{Eth1FinalizationCache, Eth1FinalizationData
 use crate::events::ServerSentEventHandler;
 use crate::execution_payload::{get_execution_payload, NotifyExecutionLayer, PreparePayloadHandle};
 use crate::fork_choice_signal::{ForkChoiceSignalRx, ForkChoiceSignalTx, ForkChoiceWaitResult};
+use crate::fork_rebroadcast::ForkRebroadcastQueue;
+use crate::gossip_signature_batch::{batch_verify_gossip_signatures, GossipSignatureBatchResults};
 use crate::graffiti_calculator::GraffitiCalculator;
 use crate::head_tracker::{HeadTracker, HeadTrackerReader, SszHeadTracker};
 use crate::historical_blocks::HistoricalBlockError;
+use crate::historical_state_replay::HistoricalStateReplayCache;
 use crate::light_client_finality_update_verification::{
     Error as LightClientFinalityUpdateError, VerifiedLightClientFinalityUpdate,
 };
@@ -55,14 +59,26 @@ use crate::observed_attesters::{
 use crate::observed_blob_sidecars::ObservedBlobSidecars;
 use crate::observed_block_producers::ObservedBlockProducers;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
+use crate::observed_proposal_headers::ObservedProposalHeaders;
 use crate::observed_slashable::ObservedSlashable;
+use crate::persisted_attestation_effectiveness::{
+    PersistedAttestationEffectiveness, PersistedValidatorEffectiveness,
+};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
+use crate::persisted_proposer_preparations::PersistedProposerPreparations;
+use crate::persisted_shuffling_caches::{
+    PersistedCommitteeCache, PersistedEpochProposers, PersistedShufflingCaches,
+};
+use crate::persisted_verified_payloads::PersistedVerifiedPayloads;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
+use crate::slashing_guard::{Conflict, SlashingGuard};
+use crate::speculative_segment_cache::SpeculativeSegmentCache;
 use crate::sync_committee_verification::{
     Error as SyncCommitteeError, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
 };
+use crate::timeliness::TimelinessTracker;
 use crate::validator_monitor::{
     get_slot_delay_ms, timestamp_now, ValidatorMonitor,
     HISTORIC_EPOCHS as VALIDATOR_MONITOR_HISTORIC_EPOCHS,
@@ -72,7 +88,10 @@ use crate::{
     kzg_utils, metrics, AvailabilityPendingExecutedBlock, BeaconChainError, BeaconForkChoiceStore,
     BeaconSnapshot, CachedHead,
 };
-use eth2::types::{EventKind, SseBlobSidecar, SseBlock, SseExtendedPayloadAttributes};
+use eth2::types::{
+    EventKind, SseBlobSidecar, SseBlock, SseExtendedPayloadAttributes, SseOptimisticStatus,
+    SseProposerReOrg,
+};
 use execution_layer::{
     BlockProposalContents, BlockProposalContentsType, BuilderParams, ChainHealth, ExecutionLayer,
     FailedCondition, PayloadAttributes, PayloadStatus,
@@ -121,6 +140,7 @@ use store::{
 };
 use task_executor::{ShutdownReason, TaskExecutor};
 use tokio_stream::Stream;
+use tracing::instrument;
 use tree_hash::TreeHash;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::payload::BlockProductionVersion;
@@ -136,6 +156,12 @@ pub const BEACON_CHAIN_DB_KEY: Hash256 = Hash256::zero();
 pub const OP_POOL_DB_KEY: Hash256 = Hash256::zero();
 pub const ETH1_CACHE_DB_KEY: Hash256 = Hash256::zero();
 pub const FORK_CHOICE_DB_KEY: Hash256 = Hash256::zero();
+pub const PROPOSER_PREPARATIONS_DB_KEY: Hash256 = Hash256::zero();
+pub const VERIFIED_PAYLOADS_DB_KEY: Hash256 = Hash256::zero();
+pub const ATTESTATION_EFFECTIVENESS_DB_KEY: Hash256 = Hash256::zero();
+pub const SHUFFLING_CACHES_DB_KEY: Hash256 = Hash256::zero();
+pub const TIMELINESS_DB_KEY: Hash256 = Hash256::zero();
+pub const FORK_REBROADCAST_DB_KEY: Hash256 = Hash256::zero();
 
 /// Defines how old a block can be before it's no longer a candidate for the early attester cache.
 const EARLY_ATTESTER_CACHE_HISTORIC_SLOTS: u64 = 4;
@@ -162,6 +188,11 @@ pub const INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON: &str =
 pub const INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON: &str =
     "Finalized merge transition block is invalid.";
 
+/// Reported to the user when an invalid payload would re-org the canonical head further than
+/// permitted by the `ChainConfig`.
+pub const INVALID_PAYLOAD_REORG_SHUTDOWN_REASON: &str =
+    "Invalid execution payload would re-org the canonical head.";
+
 /// Defines the behaviour when a block/block-root for a skipped slot is requested.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WhenSlotSkipped {
@@ -216,6 +247,7 @@ pub enum ChainSegmentResult<E: EthSpec> {
 }
 
 /// Configure the signature verification of produced blocks.
+#[derive(Debug, Clone, Copy)]
 pub enum ProduceBlockVerification {
     VerifyRandao,
     NoVerification,
@@ -326,6 +358,7 @@ struct PartialBeaconBlock<E: EthSpec> {
     sync_aggregate: Option<SyncAggregate<E>>,
     prepare_payload_handle: Option<PreparePayloadHandle<E>>,
     bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+    timer: BlockProductionTimer,
 }
 
 pub enum BlockProcessStatus<E: EthSpec> {
@@ -370,6 +403,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub spec: ChainSpec,
     /// Configuration for `BeaconChain` runtime behaviour.
     pub config: ChainConfig,
+    /// Proposer re-org parameters, which may be updated at runtime.
+    pub re_org_config: RwLock<ReOrgConfig>,
     /// Persistent storage for blocks, states, etc. Typically an on-disk store, such as LevelDB.
     pub store: BeaconStore<T>,
     /// Used for spawning async and blocking tasks.
@@ -418,6 +453,12 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub observed_blob_sidecars: RwLock<ObservedBlobSidecars<T::EthSpec>>,
     /// Maintains a record of slashable message seen over the gossip network or RPC.
     pub observed_slashable: RwLock<ObservedSlashable<T::EthSpec>>,
+    /// Maintains a record of block headers seen over gossip or RPC, for equivocation detection.
+    pub(crate) observed_proposal_headers: Mutex<ObservedProposalHeaders<T::EthSpec>>,
+    /// Proposer slashings produced by equivocation detection which are yet to be published.
+    detected_proposer_slashings: Mutex<Vec<ProposerSlashing>>,
+    /// Prevents the publication of conflicting messages from selected validators, if configured.
+    pub(crate) slashing_guard: Option<Mutex<SlashingGuard<T::EthSpec>>>,
     /// Maintains a record of which validators have submitted voluntary exits.
     pub observed_voluntary_exits: Mutex<ObservedOperations<SignedVoluntaryExit, T::EthSpec>>,
     /// Maintains a record of which validators we've seen proposer slashings for.
@@ -470,6 +511,16 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub block_times_cache: Arc<RwLock<BlockTimesCache>>,
     /// A cache used to track pre-finalization block roots for quick rejection.
     pub pre_finalization_block_cache: PreFinalizationBlockCache,
+    /// A cache of finalized states that were replayed because they are not stored.
+    pub(crate) historical_state_replay_cache: HistoricalStateReplayCache<T::EthSpec>,
+    /// A cache of chain segments which have had their signatures verified ahead of import.
+    pub(crate) speculative_segment_cache: SpeculativeSegmentCache<T>,
+    /// Arrival times of gossip blocks, aggregates and blob sidecars.
+    pub timeliness: RwLock<TimelinessTracker>,
+    /// Voluntary exits and BLS to execution changes to re-broadcast once the next fork is reached.
+    pub(crate) fork_rebroadcast: Mutex<ForkRebroadcastQueue>,
+    /// Whether the head was optimistic when last checked by `update_optimistic_status`.
+    pub(crate) head_optimistic: Mutex<Option<bool>>,
     /// A cache used to produce light_client server messages
     pub light_client_server_cache: LightClientServerCache<T>,
     /// Sender to signal the light_client server to produce new updates
@@ -712,6 +763,275 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persists the proposer preparations and validator registrations held by the execution layer
+    /// to disk.
+    pub fn persist_proposer_preparations(&self) -> Result<(), Error> {
+        let Some(execution_layer) = self.execution_layer.as_ref() else {
+            return Ok(());
+        };
+        let Some(preparations) = execution_layer.try_proposer_preparations() else {
+            warn!(
+                self.log,
+                "Unable to persist proposer preparations";
+                "reason" => "preparations are in use"
+            );
+            return Ok(());
+        };
+
+        self.store.put_item(
+            &PROPOSER_PREPARATIONS_DB_KEY,
+            &PersistedProposerPreparations::new(
+                preparations,
+                execution_layer.validator_registrations(),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    /// Restores the proposer preparations and validator registrations persisted by
+    /// `Self::persist_proposer_preparations`, so that blocks can be proposed with the correct fee
+    /// recipient before the validator clients re-send them.
+    ///
+    /// Entries which would have already expired from the execution layer are ignored.
+    pub fn restore_proposer_preparations(&self) -> Result<(), Error> {
+        let Some(execution_layer) = self.execution_layer.as_ref() else {
+            return Ok(());
+        };
+        let Some(persisted) = self
+            .store
+            .get_item::<PersistedProposerPreparations>(&PROPOSER_PREPARATIONS_DB_KEY)?
+        else {
+            return Ok(());
+        };
+
+        // Use the same retention as `ExecutionLayer::clean_proposer_caches`.
+        let current_epoch = self
+            .slot_clock
+            .now_or_genesis()
+            .ok_or(Error::UnableToReadSlot)?
+            .epoch(T::EthSpec::slots_per_epoch());
+        let retain_epoch = current_epoch.saturating_sub(Epoch::new(2));
+        let (preparations, registrations) = persisted.into_parts(retain_epoch);
+        let num_preparations = preparations.len();
+        let num_registrations = registrations.len();
+
+        if execution_layer.try_restore_proposer_preparations(preparations, registrations) {
+            info!(
+                self.log,
+                "Restored proposer preparations";
+                "preparations" => num_preparations,
+                "registrations" => num_registrations,
+            );
+        } else {
+            warn!(
+                self.log,
+                "Unable to restore proposer preparations";
+                "reason" => "preparations are in use"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Persists the block hashes of the payloads which the execution layer has verified as valid
+    /// to disk.
+    pub fn persist_verified_payloads(&self) -> Result<(), Error> {
+        let Some(execution_layer) = self.execution_layer.as_ref() else {
+            return Ok(());
+        };
+
+        self.store.put_item(
+            &VERIFIED_PAYLOADS_DB_KEY,
+            &PersistedVerifiedPayloads {
+                block_hashes: execution_layer.verified_payload_hashes(),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Restores the block hashes persisted by `Self::persist_verified_payloads`, so that payloads
+    /// which were already verified are not sent to the execution layer again after a restart.
+    pub fn restore_verified_payloads(&self) -> Result<(), Error> {
+        let Some(execution_layer) = self.execution_layer.as_ref() else {
+            return Ok(());
+        };
+        let Some(persisted) = self
+            .store
+            .get_item::<PersistedVerifiedPayloads>(&VERIFIED_PAYLOADS_DB_KEY)?
+        else {
+            return Ok(());
+        };
+
+        debug!(
+            self.log,
+            "Restored verified payloads";
+            "count" => persisted.block_hashes.len(),
+        );
+        execution_layer.restore_verified_payload_hashes(persisted.block_hashes);
+
+        Ok(())
+    }
+
+    /// Persists the attestation effectiveness history recorded by the validator monitor to disk.
+    pub fn persist_attestation_effectiveness(&self) -> Result<(), Error> {
+        let validators = self
+            .validator_monitor
+            .read()
+            .attestation_effectiveness_history()
+            .into_iter()
+            .map(|(pubkey, history)| PersistedValidatorEffectiveness { pubkey, history })
+            .collect();
+
+        self.store.put_item(
+            &ATTESTATION_EFFECTIVENESS_DB_KEY,
+            &PersistedAttestationEffectiveness { validators },
+        )?;
+
+        Ok(())
+    }
+
+    /// Persists the proposer and committee shufflings of the current and later epochs to disk.
+    pub fn persist_shuffling_caches(&self) -> Result<(), Error> {
+        let current_epoch = self
+            .slot_clock
+            .now_or_genesis()
+            .ok_or(Error::UnableToReadSlot)?
+            .epoch(T::EthSpec::slots_per_epoch());
+
+        let proposers = self
+            .beacon_proposer_cache
+            .lock()
+            .iter_from_epoch(current_epoch)
+            .map(
+                |(epoch, shuffling_decision_block, fork, proposers)| PersistedEpochProposers {
+                    epoch,
+                    shuffling_decision_block,
+                    fork,
+                    proposers: proposers.iter().map(|index| *index as u64).collect(),
+                },
+            )
+            .collect();
+        let committees = self
+            .shuffling_cache
+            .read()
+            .committee_caches_from_epoch(current_epoch)
+            .map(|(shuffling_id, committee_cache)| PersistedCommitteeCache {
+                shuffling_id: shuffling_id.clone(),
+                committee_cache: (**committee_cache).clone(),
+            })
+            .collect();
+
+        self.store.put_item(
+            &SHUFFLING_CACHES_DB_KEY,
+            &PersistedShufflingCaches {
+                proposers,
+                committees,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Restores the shufflings persisted by `Self::persist_shuffling_caches` into the beacon
+    /// proposer cache and the shuffling cache, so that they need not be recomputed when proposing
+    /// or attesting shortly after a restart.
+    ///
+    /// Shufflings of epochs prior to the current epoch are ignored.
+    pub fn restore_shuffling_caches(&self) -> Result<(), Error> {
+        let Some(persisted) = self
+            .store
+            .get_item::<PersistedShufflingCaches>(&SHUFFLING_CACHES_DB_KEY)?
+        else {
+            return Ok(());
+        };
+
+        let current_epoch = self
+            .slot_clock
+            .now_or_genesis()
+            .ok_or(Error::UnableToReadSlot)?
+            .epoch(T::EthSpec::slots_per_epoch());
+        let mut num_proposers = 0;
+        let mut num_committees = 0;
+
+        let mut beacon_proposer_cache = self.beacon_proposer_cache.lock();
+        for persisted_proposers in persisted.proposers {
+            if persisted_proposers.epoch < current_epoch
+                || persisted_proposers.proposers.len() != T::EthSpec::slots_per_epoch() as usize
+            {
+                continue;
+            }
+            beacon_proposer_cache.insert(
+                persisted_proposers.epoch,
+                persisted_proposers.shuffling_decision_block,
+                persisted_proposers
+                    .proposers
+                    .into_iter()
+                    .map(|index| index as usize)
+                    .collect(),
+                persisted_proposers.fork,
+            )?;
+            num_proposers += 1;
+        }
+        drop(beacon_proposer_cache);
+
+        let mut shuffling_cache = self.shuffling_cache.write();
+        for persisted_committees in persisted.committees {
+            let shuffling_epoch = persisted_committees.shuffling_id.shuffling_epoch;
+            if shuffling_epoch < current_epoch
+                || !persisted_committees
+                    .committee_cache
+                    .is_initialized_at(shuffling_epoch)
+            {
+                continue;
+            }
+            shuffling_cache.insert_committee_cache(
+                persisted_committees.shuffling_id,
+                &persisted_committees.committee_cache,
+            );
+            num_committees += 1;
+        }
+        drop(shuffling_cache);
+
+        debug!(
+            self.log,
+            "Restored shuffling caches";
+            "proposer_shufflings" => num_proposers,
+            "committee_shufflings" => num_committees,
+        );
+
+        Ok(())
+    }
+
+    /// Restores the history persisted by `Self::persist_attestation_effectiveness` into the
+    /// validator monitor.
+    pub fn restore_attestation_effectiveness(&self) -> Result<(), Error> {
+        let Some(persisted) = self
+            .store
+            .get_item::<PersistedAttestationEffectiveness>(&ATTESTATION_EFFECTIVENESS_DB_KEY)?
+        else {
+            return Ok(());
+        };
+
+        debug!(
+            self.log,
+            "Restored attestation effectiveness";
+            "validators" => persisted.validators.len(),
+        );
+        self.validator_monitor
+            .write()
+            .restore_attestation_effectiveness(
+                persisted
+                    .validators
+                    .into_iter()
+                    .map(|validator| (validator.pubkey, validator.history))
+                    .collect(),
+            );
+
+        Ok(())
+    }
+
     /// Returns the slot _right now_ according to `self.slot_clock`. Returns `Err` if the slot is
     /// unavailable.
     ///
@@ -1749,6 +2069,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// ## Errors
     ///
     /// May return an error if the `request_slot` is too far behind the head state.
+    #[instrument(
+        name = "attestation_production",
+        skip_all,
+        fields(slot = %request_slot, index = request_index),
+    )]
     pub fn produce_unaggregated_attestation(
         &self,
         request_slot: Slot,
@@ -1976,6 +2301,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     ///
     /// The attestation must be "unaggregated", that is it must have exactly one
     /// aggregation bit set.
+    #[instrument(
+        name = "attestation_gossip_verification",
+        skip_all,
+        fields(slot = %unaggregated_attestation.data().slot),
+    )]
     pub fn verify_unaggregated_attestation_for_gossip<'a>(
         &self,
         unaggregated_attestation: &'a Attestation<T::EthSpec>,
@@ -2038,6 +2368,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Performs the same validation as `Self::verify_unaggregated_attestation_for_gossip`,
+    /// `Self::verify_aggregated_attestation_for_gossip` and
+    /// `Self::verify_sync_committee_message_for_gossip`, but verifies the signatures of all the
+    /// messages in a single batch.
+    pub fn batch_verify_gossip_signatures<'a, A, G, S>(
+        &self,
+        attestations: A,
+        aggregates: G,
+        sync_messages: S,
+    ) -> GossipSignatureBatchResults<'a, T>
+    where
+        A: Iterator<Item = (&'a Attestation<T::EthSpec>, Option<SubnetId>)>,
+        G: Iterator<Item = &'a SignedAggregateAndProof<T::EthSpec>>,
+        S: Iterator<Item = (SyncCommitteeMessage, SyncSubnetId)>,
+    {
+        batch_verify_gossip_signatures(attestations, aggregates, sync_messages, self)
+    }
+
     /// Accepts some `SyncCommitteeMessage` from the network and attempts to verify it, returning `Ok(_)` if
     /// it is valid to be (re)broadcast on the gossip network.
     pub fn verify_sync_committee_message_for_gossip(
@@ -2453,6 +2801,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
     pub fn import_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, T::EthSpec>) {
         if self.eth1_chain.is_some() {
+            self.queue_voluntary_exit_rebroadcast(exit.as_inner().message.validator_index);
             self.op_pool.insert_voluntary_exit(exit)
         }
     }
@@ -2489,6 +2838,201 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Observe a signature-verified block header received via gossip or RPC.
+    ///
+    /// If a different header has already been seen for the same proposer and slot then a
+    /// `ProposerSlashing` is constructed, imported to the op pool and queued for publication
+    /// (see `Self::take_detected_proposer_slashings`).
+    ///
+    /// No-op unless `ChainConfig::detect_proposer_equivocations` is enabled.
+    pub fn observe_proposal_header(&self, header: &SignedBeaconBlockHeader) {
+        if !self.config.detect_proposer_equivocations {
+            return;
+        }
+
+        let slashing = match self.observed_proposal_headers.lock().observe_header(header) {
+            Ok(Some(slashing)) => slashing,
+            Ok(None) => return,
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Unable to observe block header";
+                    "error" => ?e,
+                    "slot" => header.message.slot,
+                    "proposer_index" => header.message.proposer_index,
+                );
+                return;
+            }
+        };
+
+        metrics::inc_counter(&metrics::PROPOSER_EQUIVOCATIONS_DETECTED);
+        warn!(
+            self.log,
+            "Proposer equivocation detected";
+            "slot" => header.message.slot,
+            "proposer_index" => header.message.proposer_index,
+            "block_root_1" => ?slashing.signed_header_1.message.canonical_root(),
+            "block_root_2" => ?slashing.signed_header_2.message.canonical_root(),
+        );
+
+        match self.verify_proposer_slashing_for_gossip(slashing) {
+            Ok(ObservationOutcome::New(verified_slashing)) => {
+                self.detected_proposer_slashings
+                    .lock()
+                    .push(verified_slashing.as_inner().clone());
+                self.import_proposer_slashing(verified_slashing);
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Proposer slashing already known";
+                    "proposer_index" => header.message.proposer_index,
+                );
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Detected proposer slashing is invalid";
+                    "error" => ?e,
+                    "proposer_index" => header.message.proposer_index,
+                );
+            }
+        }
+    }
+
+    /// Returns the proposer slashings produced by `Self::observe_proposal_header` which have not
+    /// yet been published, removing them from the queue.
+    pub fn take_detected_proposer_slashings(&self) -> Vec<ProposerSlashing> {
+        std::mem::take(&mut *self.detected_proposer_slashings.lock())
+    }
+
+    /// Returns `true` if attestations and block headers should be collected for slashing
+    /// detection, either by the in-process slasher, by the slashing guard or by a standalone
+    /// slasher subscribed to the `slasher_attestation` or `slasher_block_header` events.
+    pub fn slasher_enabled(&self) -> bool {
+        self.slasher.is_some()
+            || self.slashing_guard.is_some()
+            || self.event_handler.as_ref().map_or(false, |event_handler| {
+                event_handler.has_slasher_attestation_subscribers()
+                    || event_handler.has_slasher_block_header_subscribers()
+            })
+    }
+
+    /// Supply a signature-verified `indexed_attestation` to the slasher, the slashing guard and to
+    /// any standalone slasher listening on the event stream.
+    pub fn slasher_accept_attestation(&self, indexed_attestation: IndexedAttestation<T::EthSpec>) {
+        if let Some(slashing_guard) = self.slashing_guard.as_ref() {
+            slashing_guard
+                .lock()
+                .observe_attestation(indexed_attestation.to_ref());
+        }
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_slasher_attestation_subscribers() {
+                event_handler.register(EventKind::SlasherAttestation(Box::new(
+                    indexed_attestation.clone(),
+                )));
+            }
+        }
+        if let Some(slasher) = self.slasher.as_ref() {
+            slasher.accept_attestation(indexed_attestation);
+        }
+    }
+
+    /// Supply a signature-verified block `header` to the slasher, the slashing guard and to any
+    /// standalone slasher listening on the event stream.
+    pub fn slasher_accept_block_header(&self, header: SignedBeaconBlockHeader) {
+        if let Some(slashing_guard) = self.slashing_guard.as_ref() {
+            slashing_guard.lock().observe_block_header(&header);
+        }
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_slasher_block_header_subscribers() {
+                event_handler.register(EventKind::SlasherBlockHeader(Box::new(header.clone())));
+            }
+        }
+        if let Some(slasher) = self.slasher.as_ref() {
+            slasher.accept_block_header(header);
+        }
+    }
+
+    /// Check that a signature-verified `attestation` which is about to be published does not
+    /// conflict with attestations observed from any of the guarded validators in it, and record it
+    /// if it does not.
+    ///
+    /// Always returns `Ok` if the slashing guard is disabled.
+    pub fn slashing_guard_check_attestation(
+        &self,
+        attestation: IndexedAttestationRef<T::EthSpec>,
+    ) -> Result<(), Conflict> {
+        let Some(slashing_guard) = self.slashing_guard.as_ref() else {
+            return Ok(());
+        };
+        let mut slashing_guard = slashing_guard.lock();
+        self.resolve_slashing_guard_pubkeys(&mut slashing_guard);
+        if let Err(conflict) = slashing_guard.check_attestation(attestation) {
+            metrics::inc_counter_vec(&metrics::SLASHING_GUARD_REFUSALS, &["attestation"]);
+            crit!(
+                self.log,
+                "Refusing to publish conflicting attestation";
+                "msg" => "check for another validator client using the same keys",
+                "conflict" => ?conflict,
+            );
+            return Err(conflict);
+        }
+        slashing_guard.observe_attestation(attestation);
+        Ok(())
+    }
+
+    /// Check that a block `header` which is about to be published does not conflict with any
+    /// block observed from the same proposer, if it is guarded.
+    ///
+    /// The header is recorded if it does not conflict and `signature_verified` is `true`. Headers
+    /// with unverified signatures are only checked, so that they can't be used to block the
+    /// proposer's real block.
+    ///
+    /// Always returns `Ok` if the slashing guard is disabled.
+    pub fn slashing_guard_check_block_header(
+        &self,
+        header: &SignedBeaconBlockHeader,
+        signature_verified: bool,
+    ) -> Result<(), Conflict> {
+        let Some(slashing_guard) = self.slashing_guard.as_ref() else {
+            return Ok(());
+        };
+        let mut slashing_guard = slashing_guard.lock();
+        self.resolve_slashing_guard_pubkeys(&mut slashing_guard);
+        if let Err(conflict) = slashing_guard.check_block_header(header) {
+            metrics::inc_counter_vec(&metrics::SLASHING_GUARD_REFUSALS, &["block"]);
+            crit!(
+                self.log,
+                "Refusing to publish conflicting block";
+                "msg" => "check for another validator client using the same keys",
+                "conflict" => ?conflict,
+            );
+            return Err(conflict);
+        }
+        if signature_verified {
+            slashing_guard.observe_block_header(header);
+        }
+        Ok(())
+    }
+
+    /// Look up the validator indices of guarded public keys which were not known previously.
+    pub(crate) fn resolve_slashing_guard_pubkeys(
+        &self,
+        slashing_guard: &mut SlashingGuard<T::EthSpec>,
+    ) {
+        let unresolved = slashing_guard.unresolved_pubkeys();
+        if unresolved.is_empty() {
+            return;
+        }
+        let pubkey_cache = self.validator_pubkey_cache.read();
+        for pubkey in unresolved {
+            if let Some(index) = pubkey_cache.get_index(&pubkey) {
+                slashing_guard.register_index(&pubkey, index as u64);
+            }
+        }
+    }
+
     /// Verify an attester slashing before allowing it to propagate on the gossip network.
     pub fn verify_attester_slashing_for_gossip(
         &self,
@@ -2604,8 +3148,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
 
         if self.eth1_chain.is_some() {
-            self.op_pool
-                .insert_bls_to_execution_change(bls_to_execution_change, received_pre_capella)
+            let validator_index = bls_to_execution_change.as_inner().message.validator_index;
+            let inserted = self
+                .op_pool
+                .insert_bls_to_execution_change(bls_to_execution_change, received_pre_capella);
+            if inserted {
+                self.queue_bls_to_execution_change_rebroadcast(validator_index);
+            }
+            inserted
         } else {
             false
         }
@@ -2793,9 +3343,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             let mut blocks = filtered_chain_segment.split_off(last_index);
             std::mem::swap(&mut blocks, &mut filtered_chain_segment);
 
+            // Use the signatures verified ahead of time by range sync, if they are applicable to
+            // these blocks.
+            let speculatively_verified_blocks = self.take_speculatively_verified_segment(&blocks);
+
             let chain = self.clone();
             let signature_verification_future = self.spawn_blocking_handle(
-                move || signature_verify_chain_segment(blocks, &chain),
+                move || {
+                    if let Some(verified_blocks) = speculatively_verified_blocks {
+                        Ok(verified_blocks)
+                    } else {
+                        signature_verify_chain_segment(blocks, &chain)
+                    }
+                },
                 "signature_verify_chain_segment",
             );
 
@@ -2875,6 +3435,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// ## Errors
     ///
     /// Returns an `Err` if the given block was invalid, or an error was encountered during
+    #[instrument(name = "block_gossip_verification", skip_all, fields(slot = %block.slot()))]
     pub async fn verify_block_for_gossip(
         self: &Arc<Self>,
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
@@ -3086,6 +3647,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     ///
     /// Returns an `Err` if the given block was invalid, or an error was encountered during
     /// verification.
+    #[instrument(
+        name = "block_import",
+        skip_all,
+        fields(slot = %unverified_block.block().slot(), block_root = ?block_root),
+    )]
     pub async fn process_block<B: IntoExecutionPendingBlock<T>>(
         self: &Arc<Self>,
         block_root: Hash256,
@@ -3284,9 +3850,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         blob: GossipVerifiedBlob<T>,
     ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
         let slot = blob.slot();
-        if let Some(slasher) = self.slasher.as_ref() {
-            slasher.accept_block_header(blob.signed_block_header());
-        }
+        self.observe_proposal_header(&blob.signed_block_header());
+        self.slasher_accept_block_header(blob.signed_block_header());
         let availability = self.data_availability_checker.put_gossip_blob(blob)?;
 
         self.process_availability(slot, availability).await
@@ -3298,9 +3863,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self: &Arc<Self>,
         data_columns: Vec<GossipVerifiedDataColumn<T>>,
     ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
-        if let Some(slasher) = self.slasher.as_ref() {
+        if self.slasher_enabled() {
             for data_colum in &data_columns {
-                slasher.accept_block_header(data_colum.signed_block_header());
+                self.slasher_accept_block_header(data_colum.signed_block_header());
             }
         }
 
@@ -3342,9 +3907,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                             block_root,
                         )
                         .map_err(|e| BlockError::BeaconChainError(e.into()))?;
-                    if let Some(slasher) = self.slasher.as_ref() {
-                        slasher.accept_block_header(header);
-                    }
+                    self.observe_proposal_header(&header);
+                    self.slasher_accept_block_header(header);
                 }
             }
         }
@@ -3973,14 +4537,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
-    /// If a slasher is configured, provide the attestations from the block.
+    /// If a slasher is configured or subscribed to events, provide the attestations from the block.
     fn import_block_update_slasher(
         &self,
         block: BeaconBlockRef<T::EthSpec>,
         state: &BeaconState<T::EthSpec>,
         ctxt: &mut ConsensusContext<T::EthSpec>,
     ) {
-        if let Some(slasher) = self.slasher.as_ref() {
+        if self.slasher_enabled() {
             for attestation in block.body().attestations() {
                 let indexed_attestation = match ctxt.get_indexed_attestation(state, attestation) {
                     Ok(indexed) => indexed,
@@ -3995,7 +4559,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         continue;
                     }
                 };
-                slasher.accept_attestation(indexed_attestation.clone_as_indexed_attestation());
+                self.slasher_accept_attestation(indexed_attestation.clone_as_indexed_attestation());
             }
         }
     }
@@ -4264,7 +4828,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     /// Load a beacon state from the database for block production. This is a long-running process
     /// that should not be performed in an `async` context.
-    fn load_state_for_block_production(
+    pub(crate) fn load_state_for_block_production(
         self: &Arc<Self>,
         slot: Slot,
     ) -> Result<(BeaconState<T::EthSpec>, Option<Hash256>), BlockProductionError> {
@@ -4334,10 +4898,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         head_slot: Slot,
         canonical_head: Hash256,
     ) -> Option<(BeaconState<T::EthSpec>, Hash256)> {
-        let re_org_head_threshold = self.config.re_org_head_threshold?;
-        let re_org_parent_threshold = self.config.re_org_parent_threshold?;
-
-        if self.spec.proposer_score_boost.is_none() {
+        let re_org_config = self.re_org_config.read().clone();
+        if re_org_config.enabled && self.spec.proposer_score_boost.is_none() {
             warn!(
                 self.log,
                 "Ignoring proposer re-org configuration";
@@ -4358,19 +4920,68 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 None
             })?;
 
+        let mut event = SseProposerReOrg {
+            slot,
+            head_slot,
+            head_block: canonical_head,
+            parent_block: None,
+            re_org: false,
+            reason: None,
+            proposal_delay_millis: slot_delay.as_millis() as u64,
+            head_weight: None,
+            re_org_head_weight_threshold: None,
+            parent_weight: None,
+            re_org_parent_weight_threshold: None,
+        };
+        let result = if re_org_config.enabled {
+            self.get_state_for_re_org_or_reason(
+                slot,
+                head_slot,
+                canonical_head,
+                slot_delay,
+                &re_org_config,
+                &mut event,
+            )
+        } else {
+            Err("disabled".into())
+        };
+
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_proposer_re_org_subscribers() {
+                event.re_org = result.is_ok();
+                event.reason = result.as_ref().err().cloned();
+                event_handler.register(EventKind::ProposerReOrg(Box::new(event)));
+            }
+        }
+
+        result.ok()
+    }
+
+    /// Determine whether to attempt a proposer re-org, returning the reason if not.
+    ///
+    /// Details of the decision are recorded in `event` for emission via the events API.
+    fn get_state_for_re_org_or_reason(
+        &self,
+        slot: Slot,
+        head_slot: Slot,
+        canonical_head: Hash256,
+        slot_delay: Duration,
+        re_org_config: &ReOrgConfig,
+        event: &mut SseProposerReOrg,
+    ) -> Result<(BeaconState<T::EthSpec>, Hash256), String> {
         // Attempt a proposer re-org if:
         //
         // 1. It seems we have time to propagate and still receive the proposer boost.
         // 2. The current head block was seen late.
         // 3. The `get_proposer_head` conditions from fork choice pass.
-        let proposing_on_time = slot_delay < self.config.re_org_cutoff(self.spec.seconds_per_slot);
+        let proposing_on_time = slot_delay < re_org_config.cutoff(self.spec.seconds_per_slot);
         if !proposing_on_time {
             debug!(
                 self.log,
                 "Not attempting re-org";
                 "reason" => "not proposing on time",
             );
-            return None;
+            return Err("not proposing on time".into());
         }
 
         let head_late = self.block_observed_after_attestation_deadline(canonical_head, head_slot);
@@ -4380,7 +4991,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "Not attempting re-org";
                 "reason" => "head not late"
             );
-            return None;
+            return Err("head not late".into());
         }
 
         // Is the current head weak and appropriate for re-orging?
@@ -4392,10 +5003,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .get_proposer_head(
                 slot,
                 canonical_head,
-                re_org_head_threshold,
-                re_org_parent_threshold,
-                &self.config.re_org_disallowed_offsets,
-                self.config.re_org_max_epochs_since_finalization,
+                re_org_config.head_threshold,
+                re_org_config.parent_threshold,
+                &re_org_config.disallowed_offsets,
+                re_org_config.max_epochs_since_finalization,
             )
             .map_err(|e| match e {
                 ProposerHeadError::DoNotReOrg(reason) => {
@@ -4404,6 +5015,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         "Not attempting re-org";
                         "reason" => %reason,
                     );
+                    reason.to_string()
                 }
                 ProposerHeadError::Error(e) => {
                     warn!(
@@ -4411,22 +5023,28 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         "Not attempting re-org";
                         "error" => ?e,
                     );
+                    format!("error: {e:?}")
                 }
-            })
-            .ok()?;
+            })?;
         drop(proposer_head_timer);
         let re_org_parent_block = proposer_head.parent_node.root;
 
+        event.parent_block = Some(re_org_parent_block);
+        event.head_weight = Some(proposer_head.head_node.weight);
+        event.re_org_head_weight_threshold = Some(proposer_head.re_org_head_weight_threshold);
+        event.parent_weight = Some(proposer_head.parent_node.weight);
+        event.re_org_parent_weight_threshold = Some(proposer_head.re_org_parent_weight_threshold);
+
         let (state_root, state) = self
             .store
             .get_advanced_hot_state_from_cache(re_org_parent_block, slot)
-            .or_else(|| {
+            .ok_or_else(|| {
                 warn!(
                     self.log,
                     "Not attempting re-org";
                     "reason" => "no state in cache"
                 );
-                None
+                "no state in cache".to_string()
             })?;
 
         info!(
@@ -4438,7 +5056,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             "threshold_weight" => proposer_head.re_org_head_weight_threshold
         );
 
-        Some((state, state_root))
+        Ok((state, state_root))
     }
 
     /// Get the proposer index and `prev_randao` value for a proposal at slot `proposal_slot`.
@@ -4639,15 +5257,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let _timer = metrics::start_timer(&metrics::FORK_CHOICE_OVERRIDE_FCU_TIMES);
 
         // Never override if proposer re-orgs are disabled.
-        let re_org_head_threshold = self
-            .config
-            .re_org_head_threshold
-            .ok_or(DoNotReOrg::ReOrgsDisabled)?;
-
-        let re_org_parent_threshold = self
-            .config
-            .re_org_parent_threshold
-            .ok_or(DoNotReOrg::ReOrgsDisabled)?;
+        let re_org_config = self.re_org_config.read().clone();
+        if !re_org_config.enabled {
+            return Err(DoNotReOrg::ReOrgsDisabled.into());
+        }
 
         let head_block_root = canonical_forkchoice_params.head_root;
 
@@ -4657,10 +5270,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .fork_choice_read_lock()
             .get_preliminary_proposer_head(
                 head_block_root,
-                re_org_head_threshold,
-                re_org_parent_threshold,
-                &self.config.re_org_disallowed_offsets,
-                self.config.re_org_max_epochs_since_finalization,
+                re_org_config.head_threshold,
+                re_org_config.parent_threshold,
+                &re_org_config.disallowed_offsets,
+                re_org_config.max_epochs_since_finalization,
             )
             .map_err(|e| e.map_inner_error(Error::ProposerHeadForkChoiceError))?;
 
@@ -4681,7 +5294,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .and_then(|slot_start| {
                     let now = self.slot_clock.now_duration()?;
                     let slot_delay = now.saturating_sub(slot_start);
-                    Some(slot_delay <= self.config.re_org_cutoff(self.spec.seconds_per_slot))
+                    Some(slot_delay <= re_org_config.cutoff(self.spec.seconds_per_slot))
                 })
                 .unwrap_or(false)
         } else {
@@ -4851,12 +5464,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let prepare_payload_handle = partial_beacon_block.prepare_payload_handle.take();
         let block_contents_type_option =
             if let Some(prepare_payload_handle) = prepare_payload_handle {
-                Some(
-                    prepare_payload_handle
-                        .await
-                        .map_err(BlockProductionError::TokioJoin)?
-                        .ok_or(BlockProductionError::ShuttingDown)??,
-                )
+                partial_beacon_block
+                    .timer
+                    .start_stage(BlockProductionStage::Payload);
+                let block_contents_type = prepare_payload_handle
+                    .await
+                    .map_err(BlockProductionError::TokioJoin)?
+                    .ok_or(BlockProductionError::ShuttingDown)??;
+                partial_beacon_block
+                    .timer
+                    .finish_stage(BlockProductionStage::Payload);
+                Some(block_contents_type)
             } else {
                 None
             };
@@ -4950,11 +5568,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             });
         }
 
+        let mut timer = BlockProductionTimer::new(self.config.block_production_budget);
+
         let slot_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_SLOT_PROCESS_TIMES);
+        timer.start_stage(BlockProductionStage::StateAdvance);
 
         // Ensure the state has performed a complete transition into the required slot.
         complete_state_advance(&mut state, state_root_opt, produce_at_slot, &self.spec)?;
 
+        timer.finish_stage(BlockProductionStage::StateAdvance);
         drop(slot_timer);
 
         state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
@@ -5018,24 +5640,34 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .op_pool
             .get_bls_to_execution_changes(&state, &self.spec);
 
+        timer.start_stage(BlockProductionStage::AttestationPacking);
+
         // Iterate through the naive aggregation pool and ensure all the attestations from there
         // are included in the operation pool.
+        //
+        // If the packing deadline has already passed, pack only the attestations which are
+        // already in the operation pool.
         let unagg_import_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_UNAGGREGATED_TIMES);
-        for attestation in self.naive_aggregation_pool.read().iter() {
-            let import = |attestation: &Attestation<T::EthSpec>| {
-                let attesting_indices =
-                    get_attesting_indices_from_state(&state, attestation.to_ref())?;
-                self.op_pool
-                    .insert_attestation(attestation.clone(), attesting_indices)
-            };
-            if let Err(e) = import(attestation) {
-                // Don't stop block production if there's an error, just create a log.
-                error!(
-                    self.log,
-                    "Attestation did not transfer to op pool";
-                    "reason" => ?e
-                );
+        let skip_unagg_import = timer.is_past_deadline(BlockProductionStage::AttestationPacking);
+        if skip_unagg_import {
+            timer.degrade_stage(BlockProductionStage::AttestationPacking);
+        } else {
+            for attestation in self.naive_aggregation_pool.read().iter() {
+                let import = |attestation: &Attestation<T::EthSpec>| {
+                    let attesting_indices =
+                        get_attesting_indices_from_state(&state, attestation.to_ref())?;
+                    self.op_pool
+                        .insert_attestation(attestation.clone(), attesting_indices)
+                };
+                if let Err(e) = import(attestation) {
+                    // Don't stop block production if there's an error, just create a log.
+                    error!(
+                        self.log,
+                        "Attestation did not transfer to op pool";
+                        "reason" => ?e
+                    );
+                }
             }
         }
         drop(unagg_import_timer);
@@ -5056,15 +5688,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.filter_op_pool_attestation(&mut curr_filter_cache, att, &state)
         };
 
-        let mut attestations = self
+        let (mut attestations, packing_deadline_reached) = self
             .op_pool
-            .get_attestations(
+            .get_attestations_until(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                timer.deadline(BlockProductionStage::AttestationPacking),
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
+        if packing_deadline_reached && !skip_unagg_import {
+            timer.degrade_stage(BlockProductionStage::AttestationPacking);
+        }
+        timer.finish_stage(BlockProductionStage::AttestationPacking);
         drop(attestation_packing_timer);
 
         // If paranoid mode is enabled re-check the signatures of every included message.
@@ -5176,6 +5813,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             sync_aggregate,
             prepare_payload_handle,
             bls_to_execution_changes,
+            timer,
         })
     }
 
@@ -5204,6 +5842,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // produce said `execution_payload`.
             prepare_payload_handle: _,
             bls_to_execution_changes,
+            mut timer,
         } = partial_beacon_block;
 
         let (attester_slashings_base, attester_slashings_electra) =
@@ -5245,7 +5884,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         deposits: deposits.into(),
                         voluntary_exits: voluntary_exits.into(),
                         _phantom: PhantomData,
-                    },
+                    }
+                    .into(),
                 }),
                 None,
                 Uint256::zero(),
@@ -5268,7 +5908,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         sync_aggregate: sync_aggregate
                             .ok_or(BlockProductionError::MissingSyncAggregate)?,
                         _phantom: PhantomData,
-                    },
+                    }
+                    .into(),
                 }),
                 None,
                 Uint256::zero(),
@@ -5298,7 +5939,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                                 .to_payload()
                                 .try_into()
                                 .map_err(|_| BlockProductionError::InvalidPayloadFork)?,
-                        },
+                        }
+                        .into(),
                     }),
                     None,
                     execution_payload_value,
@@ -5331,7 +5973,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                                 .try_into()
                                 .map_err(|_| BlockProductionError::InvalidPayloadFork)?,
                             bls_to_execution_changes: bls_to_execution_changes.into(),
-                        },
+                        }
+                        .into(),
                     }),
                     None,
                     execution_payload_value,
@@ -5369,7 +6012,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                                     "Kzg commitments missing from block contents".to_string(),
                                 ),
                             )?,
-                        },
+                        }
+                        .into(),
                     }),
                     maybe_blobs_and_proofs,
                     execution_payload_value,
@@ -5406,7 +6050,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                                 .ok_or(BlockProductionError::InvalidPayloadFork)?,
                             // TODO(electra): finish consolidations when they're more spec'd out
                             consolidations: Vec::new().into(),
-                        },
+                        }
+                        .into(),
                     }),
                     maybe_blobs_and_proofs,
                     execution_payload_value,
@@ -5439,6 +6084,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             ProduceBlockVerification::NoVerification => BlockSignatureStrategy::NoVerification,
         };
 
+        timer.start_stage(BlockProductionStage::BlockProcessing);
+
         // Use a context without block root or proposer index so that both are checked.
         let mut ctxt = ConsensusContext::new(block.slot());
 
@@ -5455,10 +6102,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             &mut ctxt,
             &self.spec,
         )?;
+        timer.finish_stage(BlockProductionStage::BlockProcessing);
         drop(process_timer);
 
         let state_root_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_STATE_ROOT_TIMES);
+        timer.start_stage(BlockProductionStage::StateRoot);
         let state_root = state.update_tree_hash_cache()?;
+        timer.finish_stage(BlockProductionStage::StateRoot);
         drop(state_root_timer);
 
         let (mut block, _) = block.deconstruct();
@@ -5506,6 +6156,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         drop(blobs_verification_timer);
 
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_SUCCESSES);
+        self.log_block_production_report(&timer.report(slot));
 
         trace!(
             self.log,
@@ -5541,6 +6192,69 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             "op" => ?op,
         );
 
+        // Check the depth of the re-org of the canonical head before applying the invalidation,
+        // so that a halted node still has the optimistic chain to inspect.
+        let chain = self.clone();
+        let inner_op = op.clone();
+        let head_block_root = self.canonical_head.cached_head().head_block_root();
+        let reorg_depth = self
+            .spawn_blocking_handle(
+                move || {
+                    chain
+                        .canonical_head
+                        .fork_choice_read_lock()
+                        .proto_array()
+                        .execution_payload_invalidation_depth::<T::EthSpec>(
+                            &inner_op,
+                            head_block_root,
+                        )
+                },
+                "invalid_payload_reorg_depth",
+            )
+            .await?;
+
+        // An error here is logged when the invalidation is applied to fork choice below.
+        if let Ok(reorg_depth) = reorg_depth {
+            let halt = match self.config.invalid_payload_handling {
+                InvalidPayloadHandling::Halt => reorg_depth > 0,
+                InvalidPayloadHandling::Reorg => self
+                    .config
+                    .invalid_payload_max_reorg_depth
+                    .map_or(false, |max_depth| reorg_depth > max_depth),
+            };
+            if halt {
+                crit!(
+                    self.log,
+                    "Halting instead of re-orging away from invalid payload";
+                    "msg" => "the execution engine declared blocks on the canonical chain invalid. \
+                    check the execution engine, and restart with a higher \
+                    --invalid-payload-max-reorg-depth or --invalid-payload-handling reorg to \
+                    re-org away from them.",
+                    "reorg_depth" => reorg_depth,
+                    "head_block_root" => ?head_block_root,
+                    "latest_valid_ancestor" => ?op.latest_valid_ancestor(),
+                    "block_root" => ?op.block_root(),
+                );
+
+                let mut shutdown_sender = self.shutdown_sender();
+                if let Err(e) = shutdown_sender.try_send(ShutdownReason::Failure(
+                    INVALID_PAYLOAD_REORG_SHUTDOWN_REASON,
+                )) {
+                    crit!(
+                        self.log,
+                        "Unable to trigger client shut down";
+                        "msg" => "shut down may already be under way",
+                        "error" => ?e
+                    );
+                }
+
+                return Err(Error::InvalidPayloadReorgHalted {
+                    head_block_root,
+                    reorg_depth,
+                });
+            }
+        }
+
         // Update the execution status in fork choice.
         //
         // Use a blocking task since it interacts with the `canonical_head` lock. Lock contention
@@ -5621,6 +6335,115 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Send the payload of the optimistically imported block with `execution_block_hash` to the
+    /// execution engine again, and update fork choice with its response.
+    ///
+    /// Returns the root of the block and its execution status afterwards, which remains optimistic
+    /// if the execution engine is still syncing.
+    pub async fn reverify_optimistic_payload(
+        self: &Arc<Self>,
+        execution_block_hash: ExecutionBlockHash,
+    ) -> Result<(Hash256, ExecutionStatus), Error> {
+        let chain = self.clone();
+        let block_root = self
+            .spawn_blocking_handle(
+                move || {
+                    chain
+                        .canonical_head
+                        .fork_choice_read_lock()
+                        .proto_array()
+                        .core_proto_array()
+                        .execution_block_hash_to_beacon_block_root(&execution_block_hash)
+                },
+                "reverify_payload_block_root",
+            )
+            .await?
+            .ok_or(Error::UnknownExecutionBlockHash(execution_block_hash))?;
+        let execution_status = self.block_execution_status(block_root)?;
+        if !execution_status.is_strictly_optimistic() {
+            return Err(Error::PayloadNotOptimistic {
+                block_root,
+                execution_status,
+            });
+        }
+
+        let execution_layer = self
+            .execution_layer
+            .as_ref()
+            .ok_or(Error::ExecutionLayerMissing)?;
+        let block = self
+            .get_block(&block_root)
+            .await?
+            .ok_or(Error::MissingBeaconBlock(block_root))?;
+        let status = execution_layer
+            .notify_new_payload(
+                block
+                    .message()
+                    .try_into()
+                    .map_err(Error::BeaconStateError)?,
+            )
+            .await
+            .map_err(Error::ExecutionLayerNewPayloadFailed)?;
+
+        match status {
+            PayloadStatus::Valid => {
+                let chain = self.clone();
+                self.spawn_blocking_handle(
+                    move || {
+                        chain
+                            .canonical_head
+                            .fork_choice_write_lock()
+                            .on_valid_execution_payload(block_root)
+                    },
+                    "reverify_payload_valid",
+                )
+                .await??;
+                self.update_optimistic_status();
+            }
+            PayloadStatus::Syncing | PayloadStatus::Accepted => (),
+            PayloadStatus::Invalid {
+                latest_valid_hash, ..
+            } => {
+                let op = match latest_valid_hash {
+                    Some(latest_valid_ancestor)
+                        if latest_valid_ancestor != ExecutionBlockHash::zero() =>
+                    {
+                        InvalidationOperation::InvalidateMany {
+                            head_block_root: block_root,
+                            always_invalidate_head: true,
+                            latest_valid_ancestor,
+                        }
+                    }
+                    _ => InvalidationOperation::InvalidateOne { block_root },
+                };
+                self.process_invalid_execution_payload(&op).await?;
+            }
+            PayloadStatus::InvalidBlockHash { .. } => {
+                self.process_invalid_execution_payload(&InvalidationOperation::InvalidateOne {
+                    block_root,
+                })
+                .await?;
+            }
+        }
+
+        info!(
+            self.log,
+            "Re-verified optimistic payload";
+            "status" => ?status,
+            "execution_block_hash" => ?execution_block_hash,
+            "block_root" => ?block_root,
+        );
+        Ok((block_root, self.block_execution_status(block_root)?))
+    }
+
+    /// Returns the execution status of `block_root` in fork choice.
+    fn block_execution_status(&self, block_root: Hash256) -> Result<ExecutionStatus, Error> {
+        self.canonical_head
+            .fork_choice_read_lock()
+            .get_block_execution_status(&block_root)
+            .ok_or(Error::MissingBeaconBlock(block_root))
+    }
+
     pub fn block_is_known_to_fork_choice(&self, root: &Hash256) -> bool {
         self.canonical_head
             .fork_choice_read_lock()
@@ -5698,13 +6521,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         fcu_params.head_root,
                         &cached_head,
                     )?;
-                    Ok::<_, Error>(Some((fcu_params, pre_payload_attributes)))
+                    Ok::<_, Error>(Some((
+                        fcu_params,
+                        canonical_fcu_params.head_root,
+                        pre_payload_attributes,
+                    )))
                 },
                 "prepare_beacon_proposer_head_read",
             )
             .await??;
 
-        let Some((forkchoice_update_params, Some(pre_payload_attributes))) = maybe_prep_data else {
+        let Some((forkchoice_update_params, canonical_head_root, Some(pre_payload_attributes))) =
+            maybe_prep_data
+        else {
             // Appropriate log messages have already been logged above and in
             // `get_pre_payload_attributes`.
             return Ok(None);
@@ -5796,6 +6625,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         parent_block_root: head_root,
                         parent_block_number: pre_payload_attributes.parent_block_number,
                         parent_block_hash: forkchoice_update_params.head_hash.unwrap_or_default(),
+                        // The forkchoice parameters are only overridden to build on the parent of
+                        // the head when a re-org is intended.
+                        proposer_re_org: head_root != canonical_head_root,
+                        head_block_root: Some(canonical_head_root),
                         payload_attributes: payload_attributes.into(),
                     },
                     metadata: Default::default(),
@@ -5993,6 +6826,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                             "error" => ?e
                         )
                     };
+                    self.update_optimistic_status();
                     Ok(())
                 }
                 // There's nothing to be done for a syncing response. If the block is already
@@ -6162,6 +6996,58 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .map(|status| status.is_optimistic_or_invalid())
     }
 
+    /// Log and emit an `optimistic_status` event if the head has switched between being optimistic
+    /// and being verified by the execution engine since this was last called.
+    ///
+    /// The first call only records the status of the head.
+    pub(crate) fn update_optimistic_status(&self) {
+        let (head, execution_status) = match self.canonical_head.head_and_execution_status() {
+            Ok(head) => head,
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Unable to read head execution status";
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+        let execution_optimistic = execution_status.is_optimistic_or_invalid();
+        let previous = self.head_optimistic.lock().replace(execution_optimistic);
+        if previous != Some(!execution_optimistic) {
+            return;
+        }
+
+        if execution_optimistic {
+            warn!(
+                self.log,
+                "Head is optimistic";
+                "info" => "the execution engine has not yet verified the head",
+                "slot" => head.head_slot(),
+                "block_root" => ?head.head_block_root(),
+            );
+        } else {
+            info!(
+                self.log,
+                "Head verified by execution engine";
+                "slot" => head.head_slot(),
+                "block_root" => ?head.head_block_root(),
+            );
+        }
+
+        if let Some(event_handler) = self
+            .event_handler
+            .as_ref()
+            .filter(|handler| handler.has_optimistic_status_subscribers())
+        {
+            event_handler.register(EventKind::OptimisticStatus(SseOptimisticStatus {
+                slot: head.head_slot(),
+                block: head.head_block_root(),
+                execution_optimistic,
+            }));
+        }
+    }
+
     pub fn is_optimistic_or_invalid_block_root(
         &self,
         block_slot: Slot,
@@ -6178,6 +7064,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Estimates how far the local clock is ahead of the network in milliseconds (negative if it
+    /// is behind), as the earliest arrival of a block from the last epoch relative to the start of
+    /// its slot.
+    ///
+    /// The estimate includes network latency, and blocks are never accepted more than
+    /// `MAXIMUM_GOSSIP_CLOCK_DISPARITY` early. Returns `None` if no blocks have arrived recently.
+    pub fn estimate_clock_offset_ms(&self) -> Option<i64> {
+        let current_slot = self.slot_clock.now()?;
+        let earliest_slot = current_slot.saturating_sub(T::EthSpec::slots_per_epoch());
+
+        self.block_times_cache
+            .read()
+            .cache
+            .values()
+            .filter(|value| value.slot >= earliest_slot)
+            .filter_map(|value| {
+                let observed = value.timestamps.observed?.as_millis() as i64;
+                let slot_start = self.slot_clock.start_of(value.slot)?.as_millis() as i64;
+                Some(observed - slot_start)
+            })
+            .min()
+    }
+
     /// This function takes a configured weak subjectivity `Checkpoint` and the latest finalized `Checkpoint`.
     /// If the weak subjectivity checkpoint and finalized checkpoint share the same epoch, we compare
     /// roots. If we the weak subjectivity checkpoint is from an older epoch, we iterate back through
@@ -6250,6 +7159,28 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.naive_aggregation_pool.write().prune(slot);
             self.block_times_cache.write().prune(slot);
 
+            // Persist the eth1 caches as they progress, so that the deposit logs do not need to be
+            // downloaded again if the node does not shut down cleanly.
+            if self
+                .eth1_chain
+                .as_ref()
+                .map_or(false, |eth1_chain| eth1_chain.take_checkpoint())
+            {
+                let chain = self.clone();
+                self.task_executor.spawn_blocking(
+                    move || {
+                        if let Err(e) = chain.persist_eth1_cache() {
+                            warn!(
+                                chain.log,
+                                "Failed to persist eth1 cache";
+                                "error" => ?e,
+                            );
+                        }
+                    },
+                    "persist_eth1_cache",
+                );
+            }
+
             // Don't run heavy-weight tasks during sync.
             if self.best_slot() + MAX_PER_SLOT_FORK_CHOICE_DISTANCE < slot {
                 return;
@@ -6809,7 +7740,13 @@ impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
         let drop = || -> Result<(), Error> {
             self.persist_head_and_fork_choice()?;
             self.persist_op_pool()?;
-            self.persist_eth1_cache()
+            self.persist_eth1_cache()?;
+            self.persist_proposer_preparations()?;
+            self.persist_verified_payloads()?;
+            self.persist_attestation_effectiveness()?;
+            self.persist_timeliness()?;
+            self.persist_fork_rebroadcast()?;
+            self.persist_shuffling_caches()
         };
 
         if let Err(e) = drop() {
@@ -132,6 +132,25 @@ impl BeaconProposerCache {
 
         Ok(())
     }
+
+    /// Returns the `(epoch, shuffling_decision_block, fork, proposers)` of each cached epoch which
+    /// is equal to or later than `epoch`, without updating the recency of the entries.
+    pub fn iter_from_epoch(
+        &self,
+        epoch: Epoch,
+    ) -> impl Iterator<Item = (Epoch, Hash256, Fork, &[usize])> + '_ {
+        self.cache
+            .iter()
+            .filter(move |((cached_epoch, _), _)| *cached_epoch >= epoch)
+            .map(|((epoch, shuffling_decision_block), cache)| {
+                (
+                    *epoch,
+                    *shuffling_decision_block,
+                    cache.fork,
+                    cache.proposers.as_slice(),
+                )
+            })
+    }
 }
 
 /// Compute the proposer duties using the head state without cache.
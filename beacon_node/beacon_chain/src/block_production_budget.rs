@@ -0,0 +1,253 @@
+//! An optional timing budget for block production, divided into stages with deadlines measured
+//! from the start of production.
+//!
+//! Stages which can be cut short are degraded when they reach their deadline, e.g. attestation
+//! packing falls back from maximum cover to packing by individual attestation scores. Other
+//! stages can't be skipped, so exceeding their deadlines is only reported.
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use slog::{debug, info};
+use std::fmt;
+use std::time::{Duration, Instant};
+use types::Slot;
+
+/// Percentage of the budget by which attestation packing must be complete. The payload request
+/// runs concurrently with packing.
+pub const ATTESTATION_PACKING_DEADLINE_PERCENT: u32 = 40;
+/// Percentage of the budget by which the execution payload must have been received.
+pub const PAYLOAD_DEADLINE_PERCENT: u32 = 75;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockProductionStage {
+    StateAdvance,
+    AttestationPacking,
+    Payload,
+    BlockProcessing,
+    StateRoot,
+}
+
+impl BlockProductionStage {
+    /// The percentage of the budget by which the stage must be complete.
+    fn deadline_percent(self) -> u32 {
+        match self {
+            BlockProductionStage::StateAdvance | BlockProductionStage::AttestationPacking => {
+                ATTESTATION_PACKING_DEADLINE_PERCENT
+            }
+            BlockProductionStage::Payload => PAYLOAD_DEADLINE_PERCENT,
+            BlockProductionStage::BlockProcessing | BlockProductionStage::StateRoot => 100,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BlockProductionStage::StateAdvance => "state_advance",
+            BlockProductionStage::AttestationPacking => "attestation_packing",
+            BlockProductionStage::Payload => "payload",
+            BlockProductionStage::BlockProcessing => "block_processing",
+            BlockProductionStage::StateRoot => "state_root",
+        }
+    }
+}
+
+/// The time taken by a completed stage.
+#[derive(Debug, Clone, Copy)]
+pub struct StageTime {
+    pub stage: BlockProductionStage,
+    pub time: Duration,
+    /// True if the stage completed after its deadline.
+    pub exceeded: bool,
+}
+
+/// Tracks the time spent in each stage of the production of one block.
+#[derive(Debug)]
+pub struct BlockProductionTimer {
+    start: Instant,
+    budget: Option<Duration>,
+    started: Vec<(BlockProductionStage, Instant)>,
+    stages: Vec<StageTime>,
+    degraded: Vec<BlockProductionStage>,
+}
+
+impl BlockProductionTimer {
+    pub fn new(budget: Option<Duration>) -> Self {
+        Self {
+            start: Instant::now(),
+            budget,
+            started: vec![],
+            stages: vec![],
+            degraded: vec![],
+        }
+    }
+
+    /// Returns the time by which `stage` must be complete, if there is a budget.
+    pub fn deadline(&self, stage: BlockProductionStage) -> Option<Instant> {
+        self.budget
+            .map(|budget| self.start + budget * stage.deadline_percent() / 100)
+    }
+
+    /// Returns `true` if the deadline of `stage` has passed.
+    pub fn is_past_deadline(&self, stage: BlockProductionStage) -> bool {
+        self.deadline(stage)
+            .map_or(false, |deadline| Instant::now() >= deadline)
+    }
+
+    pub fn start_stage(&mut self, stage: BlockProductionStage) {
+        self.started.push((stage, Instant::now()));
+    }
+
+    /// Records the completion of `stage`, which must have been started with `start_stage`.
+    pub fn finish_stage(&mut self, stage: BlockProductionStage) {
+        if let Some(index) = self.started.iter().position(|(s, _)| *s == stage) {
+            let (_, stage_start) = self.started.remove(index);
+            self.stages.push(StageTime {
+                stage,
+                time: stage_start.elapsed(),
+                exceeded: self.is_past_deadline(stage),
+            });
+        }
+    }
+
+    /// Records that `stage` was cut short to meet its deadline.
+    pub fn degrade_stage(&mut self, stage: BlockProductionStage) {
+        self.degraded.push(stage);
+    }
+
+    pub fn report(&self, slot: Slot) -> BlockProductionReport {
+        BlockProductionReport {
+            slot,
+            budget: self.budget,
+            total: self.start.elapsed(),
+            stages: self.stages.clone(),
+            degraded: self.degraded.clone(),
+        }
+    }
+}
+
+/// A report of where the time went while producing a block.
+#[derive(Debug, Clone)]
+pub struct BlockProductionReport {
+    pub slot: Slot,
+    pub budget: Option<Duration>,
+    pub total: Duration,
+    pub stages: Vec<StageTime>,
+    pub degraded: Vec<BlockProductionStage>,
+}
+
+impl BlockProductionReport {
+    /// Returns `true` if the budget was exceeded or any stage missed its deadline.
+    pub fn is_over_budget(&self) -> bool {
+        self.budget.map_or(false, |budget| self.total > budget)
+            || self.stages.iter().any(|stage| stage.exceeded)
+            || !self.degraded.is_empty()
+    }
+}
+
+impl fmt::Display for BlockProductionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}ms", stage.stage.as_str(), stage.time.as_millis())?;
+            if self.degraded.contains(&stage.stage) {
+                write!(f, " (degraded)")?;
+            } else if stage.exceeded {
+                write!(f, " (late)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Logs where the time went while producing a block, at info level if the budget was
+    /// exceeded.
+    pub(crate) fn log_block_production_report(&self, report: &BlockProductionReport) {
+        for stage in &report.stages {
+            if stage.exceeded {
+                metrics::inc_counter_vec(
+                    &metrics::BLOCK_PRODUCTION_BUDGET_EXCEEDED,
+                    &[stage.stage.as_str()],
+                );
+            }
+        }
+        for stage in &report.degraded {
+            metrics::inc_counter_vec(
+                &metrics::BLOCK_PRODUCTION_BUDGET_DEGRADED,
+                &[stage.as_str()],
+            );
+        }
+
+        let budget_ms = report.budget.map(|budget| budget.as_millis());
+        if report.is_over_budget() {
+            info!(
+                self.log,
+                "Block production exceeded budget";
+                "slot" => report.slot,
+                "total_ms" => report.total.as_millis(),
+                "budget_ms" => ?budget_ms,
+                "stages" => %report,
+            );
+        } else {
+            debug!(
+                self.log,
+                "Block production times";
+                "slot" => report.slot,
+                "total_ms" => report.total.as_millis(),
+                "budget_ms" => ?budget_ms,
+                "stages" => %report,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadlines_are_fractions_of_budget() {
+        let timer = BlockProductionTimer::new(Some(Duration::from_millis(1000)));
+        let deadline = |stage| timer.deadline(stage).unwrap() - timer.start;
+        assert_eq!(
+            deadline(BlockProductionStage::AttestationPacking),
+            Duration::from_millis(400)
+        );
+        assert_eq!(
+            deadline(BlockProductionStage::Payload),
+            Duration::from_millis(750)
+        );
+        assert_eq!(
+            deadline(BlockProductionStage::StateRoot),
+            Duration::from_millis(1000)
+        );
+
+        let unlimited = BlockProductionTimer::new(None);
+        assert_eq!(unlimited.deadline(BlockProductionStage::StateRoot), None);
+        assert!(!unlimited.is_past_deadline(BlockProductionStage::StateRoot));
+    }
+
+    #[test]
+    fn reports_late_and_degraded_stages() {
+        let mut timer = BlockProductionTimer::new(Some(Duration::ZERO));
+        timer.start_stage(BlockProductionStage::AttestationPacking);
+        timer.degrade_stage(BlockProductionStage::AttestationPacking);
+        timer.finish_stage(BlockProductionStage::AttestationPacking);
+        timer.start_stage(BlockProductionStage::StateRoot);
+        timer.finish_stage(BlockProductionStage::StateRoot);
+
+        let report = timer.report(Slot::new(1));
+        assert!(report.is_over_budget());
+        assert_eq!(report.stages.len(), 2);
+        assert!(report.stages.iter().all(|stage| stage.exceeded));
+        let summary = report.to_string();
+        assert!(summary.starts_with("attestation_packing: "));
+        assert!(summary.contains("(degraded)"));
+        assert!(summary.contains("state_root: "));
+        assert!(summary.ends_with("(late)"));
+
+        let mut timer = BlockProductionTimer::new(None);
+        timer.start_stage(BlockProductionStage::Payload);
+        timer.finish_stage(BlockProductionStage::Payload);
+        assert!(!timer.report(Slot::new(1)).is_over_budget());
+    }
+}
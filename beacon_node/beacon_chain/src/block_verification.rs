@@ -69,7 +69,7 @@ use crate::{
 };
 use derivative::Derivative;
 use eth2::types::{BlockGossip, EventKind, PublishBlockRequest};
-use execution_layer::PayloadStatus;
+use execution_layer::{NewPayloadRequest, PayloadStatus};
 pub use fork_choice::{AttestationFromBlock, PayloadVerificationStatus};
 use parking_lot::RwLockReadGuard;
 use proto_array::Block as ProtoBlock;
@@ -547,14 +547,15 @@ impl BlockSlashInfo<GossipDataColumnError> {
     }
 }
 
-/// Process invalid blocks to see if they are suitable for the slasher.
+/// Process invalid blocks to see if they are suitable for the slasher or proposer equivocation
+/// detection.
 ///
-/// If no slasher is configured, this is a no-op.
+/// If no slasher is configured and equivocation detection is disabled, this is a no-op.
 pub(crate) fn process_block_slash_info<T: BeaconChainTypes, TErr: BlockBlobError>(
     chain: &BeaconChain<T>,
     slash_info: BlockSlashInfo<TErr>,
 ) -> TErr {
-    if let Some(slasher) = chain.slasher.as_ref() {
+    if chain.slasher_enabled() || chain.config.detect_proposer_equivocations {
         let (verified_header, error) = match slash_info {
             BlockSlashInfo::SignatureNotChecked(header, e) => {
                 if verify_header_signature::<_, TErr>(chain, &header).is_ok() {
@@ -567,7 +568,8 @@ pub(crate) fn process_block_slash_info<T: BeaconChainTypes, TErr: BlockBlobError
             BlockSlashInfo::SignatureValid(header, e) => (header, e),
         };
 
-        slasher.accept_block_header(verified_header);
+        chain.observe_proposal_header(&verified_header);
+        chain.slasher_accept_block_header(verified_header);
         error
     } else {
         match slash_info {
@@ -630,6 +632,7 @@ pub fn signature_verify_chain_segment<T: BeaconChainTypes>(
                 block_root,
                 parent: None,
                 consensus_context,
+                optimistic_payload_verified: false,
             }
         })
         .collect::<Vec<_>>();
@@ -655,6 +658,95 @@ pub fn signature_verify_chain_segment<T: BeaconChainTypes>(
     Ok(signature_verified_blocks)
 }
 
+/// Verify the signatures of a single-epoch `chain_segment` whose parent has *not* yet been
+/// imported, using the state of an earlier, already-imported `anchor_root` block.
+///
+/// The attester shuffling for an epoch is fixed two epochs in advance, so the anchor state can be
+/// used to obtain the committees for the segment as long as the anchor is the last block at or
+/// prior to the end of `segment_epoch - 2`. The proposer shuffling is *not* known in advance, so
+/// the proposal and randao signatures are verified against the proposer index declared in each
+/// block. The declared proposer index is checked against the real shuffling during import.
+///
+/// If the chain imports finalized segments without notifying the execution layer, the block
+/// hashes of the payloads are verified here too, so that the import of the segment is left with
+/// only the state transition.
+///
+/// It is the responsibility of the caller to check the anchor condition before relying on the
+/// returned blocks, see `BeaconChain::take_speculatively_verified_segment`.
+pub fn speculatively_signature_verify_chain_segment<T: BeaconChainTypes>(
+    anchor_root: Hash256,
+    chain_segment: Vec<(Hash256, RpcBlock<T::EthSpec>)>,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<SignatureVerifiedBlock<T>>, BlockError<T::EthSpec>> {
+    let Some(highest_slot) = chain_segment.last().map(|(_, block)| block.slot()) else {
+        return Ok(vec![]);
+    };
+
+    let anchor_block = chain
+        .get_blinded_block(&anchor_root)?
+        .ok_or(BeaconChainError::MissingBeaconBlock(anchor_root))?;
+    let (anchor_state_root, mut anchor_state) = chain
+        .store
+        .get_advanced_hot_state(anchor_root, anchor_block.slot(), anchor_block.state_root())?
+        .ok_or(BeaconChainError::MissingBeaconState(
+            anchor_block.state_root(),
+        ))?;
+
+    let state = cheap_state_advance_to_obtain_committees::<_, BlockError<T::EthSpec>>(
+        &mut anchor_state,
+        Some(anchor_state_root),
+        highest_slot,
+        &chain.spec,
+    )?;
+
+    let (roots, blocks): (Vec<_>, Vec<_>) = chain_segment.into_iter().unzip();
+    let maybe_available_blocks = chain
+        .data_availability_checker
+        .verify_kzg_for_rpc_blocks(blocks)?;
+    let mut signature_verified_blocks = roots
+        .into_iter()
+        .zip(maybe_available_blocks)
+        .map(|(block_root, maybe_available_block)| {
+            let consensus_context = ConsensusContext::new(maybe_available_block.slot())
+                .set_current_block_root(block_root);
+            SignatureVerifiedBlock {
+                block: maybe_available_block,
+                block_root,
+                parent: None,
+                consensus_context,
+                optimistic_payload_verified: false,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let pubkey_cache = get_validator_pubkey_cache(chain)?;
+    let mut signature_verifier = get_signature_verifier(&state, &pubkey_cache, &chain.spec);
+    for svb in &mut signature_verified_blocks {
+        signature_verifier.include_all_signatures_with_declared_proposer(
+            svb.block.as_block(),
+            &mut svb.consensus_context,
+        )?;
+    }
+
+    if signature_verifier.verify().is_err() {
+        return Err(BlockError::InvalidSignature);
+    }
+
+    drop(pubkey_cache);
+
+    // A payload which fails here is verified again during import, which reports the error.
+    if chain.config.optimistic_finalized_sync {
+        for svb in &mut signature_verified_blocks {
+            svb.optimistic_payload_verified = NewPayloadRequest::try_from(svb.block.message())
+                .map_or(false, |request| {
+                    request.perform_optimistic_sync_verifications().is_ok()
+                });
+        }
+    }
+
+    Ok(signature_verified_blocks)
+}
+
 /// A wrapper around a `SignedBeaconBlock` that indicates it has been approved for re-gossiping on
 /// the p2p network.
 #[derive(Derivative)]
@@ -673,6 +765,9 @@ pub struct SignatureVerifiedBlock<T: BeaconChainTypes> {
     block_root: Hash256,
     parent: Option<PreProcessingSnapshot<T::EthSpec>>,
     consensus_context: ConsensusContext<T::EthSpec>,
+    /// The payload has passed the verifications performed in place of notifying the execution
+    /// layer, see `PayloadNotifier::new`.
+    optimistic_payload_verified: bool,
 }
 
 /// Used to await the result of executing payload with a remote EE.
@@ -762,10 +857,10 @@ pub trait IntoExecutionPendingBlock<T: BeaconChainTypes>: Sized {
     ) -> Result<ExecutionPendingBlock<T>, BlockError<T::EthSpec>> {
         self.into_execution_pending_block_slashable(block_root, chain, notify_execution_layer)
             .map(|execution_pending| {
-                // Supply valid block to slasher.
-                if let Some(slasher) = chain.slasher.as_ref() {
-                    slasher.accept_block_header(execution_pending.block.signed_block_header());
-                }
+                // Supply valid block to slasher and equivocation detection.
+                let header = execution_pending.block.signed_block_header();
+                chain.observe_proposal_header(&header);
+                chain.slasher_accept_block_header(header);
                 execution_pending
             })
             .map_err(|slash_info| {
@@ -1095,6 +1190,7 @@ impl<T: BeaconChainTypes> SignatureVerifiedBlock<T> {
                 block,
                 block_root,
                 parent: Some(parent),
+                optimistic_payload_verified: false,
             })
         } else {
             Err(BlockError::InvalidSignature)
@@ -1150,6 +1246,7 @@ impl<T: BeaconChainTypes> SignatureVerifiedBlock<T> {
                 block_root: from.block_root,
                 parent: Some(parent),
                 consensus_context,
+                optimistic_payload_verified: false,
             })
         } else {
             Err(BlockError::InvalidSignature)
@@ -1194,6 +1291,7 @@ impl<T: BeaconChainTypes> IntoExecutionPendingBlock<T> for SignatureVerifiedBloc
             self.consensus_context,
             chain,
             notify_execution_layer,
+            self.optimistic_payload_verified,
         )
         .map_err(|e| BlockSlashInfo::SignatureValid(header, e))
     }
@@ -1290,6 +1388,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
         mut consensus_context: ConsensusContext<T::EthSpec>,
         chain: &Arc<BeaconChain<T>>,
         notify_execution_layer: NotifyExecutionLayer,
+        optimistic_payload_verified: bool,
     ) -> Result<Self, BlockError<T::EthSpec>> {
         chain
             .observed_slashable
@@ -1344,6 +1443,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
             block.block_cloned(),
             &parent.pre_state,
             notify_execution_layer,
+            optimistic_payload_verified,
         )?;
         let is_valid_merge_transition_block =
             is_merge_transition_block(&parent.pre_state, block.message().body());
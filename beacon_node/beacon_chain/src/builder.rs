@@ -2,6 +2,7 @@ use crate::beacon_chain::{
     CanonicalHead, LightClientProducerEvent, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY,
 };
 use crate::beacon_proposer_cache::BeaconProposerCache;
+use crate::chain_config::ReOrgConfig;
 use crate::data_availability_checker::DataAvailabilityChecker;
 use crate::eth1_chain::{CachingEth1Backend, SszEth1};
 use crate::eth1_finalization_cache::Eth1FinalizationCache;
@@ -13,6 +14,7 @@ use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
+use crate::slashing_guard::SlashingGuard;
 use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::ChainConfig;
@@ -29,7 +31,7 @@ use operation_pool::{OperationPool, PersistedOperationPool};
 use parking_lot::{Mutex, RwLock};
 use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use slasher::Slasher;
-use slog::{crit, debug, error, info, o, Logger};
+use slog::{crit, debug, error, info, o, warn, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use state_processing::{per_slot_processing, AllCaches};
 use std::marker::PhantomData;
@@ -887,8 +889,22 @@ where
             }
         };
 
+        let slashing_guard = if self.chain_config.slashing_guard_pubkeys.is_empty() {
+            None
+        } else {
+            let mut slashing_guard =
+                SlashingGuard::new(self.chain_config.slashing_guard_pubkeys.iter().copied());
+            for pubkey in slashing_guard.unresolved_pubkeys() {
+                if let Some(index) = validator_pubkey_cache.get_index(&pubkey) {
+                    slashing_guard.register_index(&pubkey, index as u64);
+                }
+            }
+            Some(Mutex::new(slashing_guard))
+        };
+
         let beacon_chain = BeaconChain {
             spec: self.spec.clone(),
+            re_org_config: RwLock::new(ReOrgConfig::from(&self.chain_config)),
             config: self.chain_config,
             store: store.clone(),
             task_executor: self
@@ -919,6 +935,9 @@ where
             observed_block_producers: <_>::default(),
             observed_blob_sidecars: <_>::default(),
             observed_slashable: <_>::default(),
+            observed_proposal_headers: <_>::default(),
+            detected_proposer_slashings: <_>::default(),
+            slashing_guard,
             observed_voluntary_exits: <_>::default(),
             observed_proposer_slashings: <_>::default(),
             observed_attester_slashings: <_>::default(),
@@ -943,6 +962,11 @@ where
             beacon_proposer_cache,
             block_times_cache: <_>::default(),
             pre_finalization_block_cache: <_>::default(),
+            historical_state_replay_cache: <_>::default(),
+            speculative_segment_cache: <_>::default(),
+            timeliness: <_>::default(),
+            fork_rebroadcast: <_>::default(),
+            head_optimistic: <_>::default(),
             validator_pubkey_cache: RwLock::new(validator_pubkey_cache),
             attester_cache: <_>::default(),
             early_attester_cache: <_>::default(),
@@ -1010,6 +1034,54 @@ where
             "head_slot" => format!("{}", head.beacon_block.slot()),
         );
 
+        if let Err(e) = beacon_chain.restore_proposer_preparations() {
+            warn!(
+                log,
+                "Failed to restore proposer preparations";
+                "error" => ?e
+            );
+        }
+
+        if let Err(e) = beacon_chain.restore_verified_payloads() {
+            warn!(
+                log,
+                "Failed to restore verified payloads";
+                "error" => ?e
+            );
+        }
+
+        if let Err(e) = beacon_chain.restore_attestation_effectiveness() {
+            warn!(
+                log,
+                "Failed to restore attestation effectiveness";
+                "error" => ?e
+            );
+        }
+
+        if let Err(e) = beacon_chain.restore_timeliness() {
+            warn!(
+                log,
+                "Failed to restore arrival timeliness";
+                "error" => ?e
+            );
+        }
+
+        if let Err(e) = beacon_chain.restore_fork_rebroadcast() {
+            warn!(
+                log,
+                "Failed to restore fork re-broadcast queue";
+                "error" => ?e
+            );
+        }
+
+        if let Err(e) = beacon_chain.restore_shuffling_caches() {
+            warn!(
+                log,
+                "Failed to restore shuffling caches";
+                "error" => ?e
+            );
+        }
+
         // Check for states to reconstruct (in the background).
         if beacon_chain.config.reconstruct_historic_states {
             beacon_chain.store_migrator.process_reconstruction();
@@ -1067,6 +1139,22 @@ where
 
         Ok(self)
     }
+
+    /// Sets the `BeaconChain` eth1 back-end to vote for the `Eth1Data` of the state being built
+    /// upon, without syncing the deposit contract.
+    pub fn stub_eth1_backend(mut self) -> Result<Self, String> {
+        let log = self
+            .log
+            .as_ref()
+            .ok_or("stub_eth1_backend requires a log")?;
+
+        let backend =
+            CachingEth1Backend::new(Eth1Config::default(), log.clone(), self.spec.clone())?;
+
+        self.eth1_chain = Some(Eth1Chain::new_stubbed(backend));
+
+        Ok(self)
+    }
 }
 
 impl<TEth1Backend, E, THotStore, TColdStore>
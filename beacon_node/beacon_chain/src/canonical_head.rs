@@ -55,6 +55,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use store::{iter::StateRootsIterator, KeyValueStoreOp, StoreItem};
 use task_executor::{JoinHandle, ShutdownReason};
+use tracing::{instrument, Span};
 use types::*;
 
 /// Simple wrapper around `RwLock` that uses private visibility to prevent any other modules from
@@ -492,14 +493,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// such a case it's critical that the `BeaconChain` keeps importing blocks so that the
     /// situation can be rectified. We avoid returning an error here so that calling functions
     /// can't abort block import because an error is returned here.
+    #[instrument(name = "fork_choice", skip_all, fields(slot = %current_slot))]
     pub async fn recompute_head_at_slot(self: &Arc<Self>, current_slot: Slot) {
         metrics::inc_counter(&metrics::FORK_CHOICE_REQUESTS);
         let _timer = metrics::start_timer(&metrics::FORK_CHOICE_TIMES);
 
         let chain = self.clone();
+        // Continue the span on the blocking thread.
+        let span = Span::current();
         match self
             .spawn_blocking_handle(
-                move || chain.recompute_head_at_slot_internal(current_slot),
+                move || span.in_scope(|| chain.recompute_head_at_slot_internal(current_slot)),
                 "recompute_head_internal",
             )
             .await
@@ -735,6 +739,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     "error" => ?e
                 );
             }
+            self.update_optimistic_status();
         }
 
         // Drop the old cache head nice and early to try and free the memory as soon as possible.
@@ -769,6 +774,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 
     /// Perform updates to caches and other components after the canonical head has been changed.
+    #[instrument(
+        name = "head_update",
+        skip_all,
+        fields(slot = %new_cached_head.head_slot(), block_root = ?new_cached_head.head_block_root()),
+    )]
     fn after_new_head(
         self: &Arc<Self>,
         old_cached_head: &CachedHead<T::EthSpec>,
@@ -880,6 +890,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Register a server-sent-event for a reorg (if necessary).
         if let Some(depth) = reorg_distance {
+            self.spawn_reorg_capture(
+                head_slot,
+                depth.as_u64(),
+                old_snapshot.beacon_block_root,
+                new_snapshot.beacon_block_root,
+            );
+
             if let Some(event_handler) = self
                 .event_handler
                 .as_ref()
@@ -946,6 +963,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .start_slot(T::EthSpec::slots_per_epoch()),
         );
 
+        self.observed_proposal_headers.lock().prune(
+            new_view
+                .finalized_checkpoint
+                .epoch
+                .start_slot(T::EthSpec::slots_per_epoch()),
+        );
+
+        if let Some(slashing_guard) = self.slashing_guard.as_ref() {
+            let mut slashing_guard = slashing_guard.lock();
+            slashing_guard.prune(new_view.finalized_checkpoint.epoch);
+            self.resolve_slashing_guard_pubkeys(&mut slashing_guard);
+        }
+
         self.attester_cache
             .prune_below(new_view.finalized_checkpoint.epoch);
 
@@ -1,7 +1,9 @@
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
-use types::{Checkpoint, Epoch};
+use types::{Checkpoint, Epoch, PublicKeyBytes};
 
 pub const DEFAULT_RE_ORG_HEAD_THRESHOLD: ReOrgThreshold = ReOrgThreshold(20);
 pub const DEFAULT_RE_ORG_PARENT_THRESHOLD: ReOrgThreshold = ReOrgThreshold(160);
@@ -9,6 +11,8 @@ pub const DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION: Epoch = Epoch::new(2);
 /// Default to 1/12th of the slot, which is 1 second on mainnet.
 pub const DEFAULT_RE_ORG_CUTOFF_DENOMINATOR: u32 = 12;
 pub const DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT: u64 = 250;
+/// Default depth of re-org beyond which a forensic bundle is captured.
+pub const DEFAULT_REORG_CAPTURE_DEPTH: u64 = 2;
 
 /// Default fraction of a slot lookahead for payload preparation (12/3 = 4 seconds on mainnet).
 pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
@@ -69,8 +73,21 @@ pub struct ChainConfig {
     /// Low values are useful for execution engines which don't improve their payload after the
     /// first call, and high values are useful for ensuring the EL is given ample notice.
     pub prepare_payload_lookahead: Duration,
+    /// The total time allowed for producing a block, divided between the stages of production.
+    ///
+    /// Stages which reach their deadline are cut short where possible, and stages which exceed
+    /// it are reported.
+    pub block_production_budget: Option<Duration>,
     /// Use EL-free optimistic sync for the finalized part of the chain.
     pub optimistic_finalized_sync: bool,
+    /// Verify the signatures and payloads of the next range sync batch whilst the current batch is
+    /// imported.
+    pub speculative_sync_verification: bool,
+    /// Construct and broadcast proposer slashings for equivocating blocks seen on gossip or RPC.
+    pub detect_proposer_equivocations: bool,
+    /// Refuse to publish blocks and attestations from these validators if they conflict with
+    /// messages already observed from them.
+    pub slashing_guard_pubkeys: Vec<PublicKeyBytes>,
     /// The size of the shuffling cache,
     pub shuffling_cache_size: usize,
     /// If using a weak-subjectivity sync, whether we should download blocks all the way back to
@@ -84,6 +101,42 @@ pub struct ChainConfig {
     pub epochs_per_migration: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// Capture a forensic bundle for re-orgs deeper than this many slots. `None` disables capture.
+    pub reorg_capture_depth: Option<u64>,
+    /// Directory to write re-org bundles to. Re-orgs are not captured if this is `None`.
+    pub reorg_capture_dir: Option<PathBuf>,
+    /// What to do when the execution engine declares optimistically imported blocks on the
+    /// canonical chain invalid.
+    pub invalid_payload_handling: InvalidPayloadHandling,
+    /// Halt rather than re-org the canonical head away from invalid blocks by more than this many
+    /// slots. `None` allows re-orgs of any depth.
+    pub invalid_payload_max_reorg_depth: Option<u64>,
+    /// Refuse to produce blocks and attestations through the HTTP API whilst the head is
+    /// optimistic or the execution layer is offline.
+    pub strict_optimistic_sync: bool,
+}
+
+/// The response to the execution engine declaring blocks on the canonical chain invalid.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub enum InvalidPayloadHandling {
+    /// Re-org away from the invalid blocks, within `invalid_payload_max_reorg_depth`.
+    #[default]
+    Reorg,
+    /// Shut down without applying the invalidation, so that the execution engine and the chain can
+    /// be inspected before any re-org.
+    Halt,
+}
+
+impl FromStr for InvalidPayloadHandling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reorg" => Ok(InvalidPayloadHandling::Reorg),
+            "halt" => Ok(InvalidPayloadHandling::Halt),
+            other => Err(format!("{other} is not reorg or halt")),
+        }
+    }
 }
 
 impl Default for ChainConfig {
@@ -108,24 +161,64 @@ impl Default for ChainConfig {
             paranoid_block_proposal: false,
             checkpoint_sync_url_timeout: 60,
             prepare_payload_lookahead: Duration::from_secs(4),
+            block_production_budget: None,
             // This value isn't actually read except in tests.
             optimistic_finalized_sync: true,
+            speculative_sync_verification: true,
+            detect_proposer_equivocations: false,
+            slashing_guard_pubkeys: vec![],
             shuffling_cache_size: crate::shuffling_cache::DEFAULT_CACHE_SIZE,
             genesis_backfill: false,
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
+            reorg_capture_depth: Some(DEFAULT_REORG_CAPTURE_DEPTH),
+            reorg_capture_dir: None,
+            invalid_payload_handling: InvalidPayloadHandling::default(),
+            invalid_payload_max_reorg_depth: None,
+            strict_optimistic_sync: false,
         }
     }
 }
 
-impl ChainConfig {
+/// Proposer re-org parameters which may be updated at runtime, e.g. via the HTTP API.
+///
+/// This is initialised from the `re_org_*` fields of the `ChainConfig`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct ReOrgConfig {
+    pub enabled: bool,
+    pub head_threshold: ReOrgThreshold,
+    pub parent_threshold: ReOrgThreshold,
+    pub max_epochs_since_finalization: Epoch,
+    pub cutoff_millis: Option<u64>,
+    pub disallowed_offsets: DisallowedReOrgOffsets,
+}
+
+impl ReOrgConfig {
     /// The latest delay from the start of the slot at which to attempt a 1-slot re-org.
-    pub fn re_org_cutoff(&self, seconds_per_slot: u64) -> Duration {
-        self.re_org_cutoff_millis
+    pub fn cutoff(&self, seconds_per_slot: u64) -> Duration {
+        self.cutoff_millis
             .map(Duration::from_millis)
             .unwrap_or_else(|| {
                 Duration::from_secs(seconds_per_slot) / DEFAULT_RE_ORG_CUTOFF_DENOMINATOR
             })
     }
 }
+
+impl From<&ChainConfig> for ReOrgConfig {
+    fn from(config: &ChainConfig) -> Self {
+        Self {
+            enabled: config.re_org_head_threshold.is_some()
+                && config.re_org_parent_threshold.is_some(),
+            head_threshold: config
+                .re_org_head_threshold
+                .unwrap_or(DEFAULT_RE_ORG_HEAD_THRESHOLD),
+            parent_threshold: config
+                .re_org_parent_threshold
+                .unwrap_or(DEFAULT_RE_ORG_PARENT_THRESHOLD),
+            max_epochs_since_finalization: config.re_org_max_epochs_since_finalization,
+            cutoff_millis: config.re_org_cutoff_millis,
+            disallowed_offsets: config.re_org_disallowed_offsets.clone(),
+        }
+    }
+}
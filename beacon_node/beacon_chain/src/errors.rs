@@ -54,6 +54,8 @@ pub enum BeaconChainError {
     },
     SlotClockDidNotStart,
     NoStateForSlot(Slot),
+    /// The maximum number of historical states are already being replayed.
+    TooManyHistoricalStateReplays,
     BeaconStateError(BeaconStateError),
     EpochCacheError(EpochCacheError),
     DBInconsistent(String),
@@ -179,6 +181,16 @@ pub enum BeaconChainError {
         justified_root: Hash256,
         execution_block_hash: Option<ExecutionBlockHash>,
     },
+    InvalidPayloadReorgHalted {
+        head_block_root: Hash256,
+        reorg_depth: u64,
+    },
+    UnknownExecutionBlockHash(ExecutionBlockHash),
+    PayloadNotOptimistic {
+        block_root: Hash256,
+        execution_status: ExecutionStatus,
+    },
+    ExecutionLayerNewPayloadFailed(execution_layer::Error),
     ForkchoiceUpdate(execution_layer::Error),
     FinalizedCheckpointMismatch {
         head_state: Checkpoint,
@@ -294,6 +306,7 @@ pub enum BlockProductionError {
     InvalidBlockVariant(String),
     KzgError(kzg::Error),
     FailedToBuildBlobSidecars(String),
+    RemoteBlockMismatch(String),
 }
 
 easy_from_to!(BlockProcessingError, BlockProductionError);
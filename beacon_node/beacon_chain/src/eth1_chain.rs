@@ -196,6 +196,9 @@ where
     /// When `true`, the backend will be ignored and dummy data from the 2019 Canada interop method
     /// will be used instead.
     use_dummy_backend: bool,
+    /// When `true`, the backend will not be used for `Eth1Data` votes, which will always be the
+    /// `Eth1Data` of the state being built upon.
+    use_stub_votes: bool,
     _phantom: PhantomData<E>,
 }
 
@@ -208,6 +211,7 @@ where
         Self {
             backend,
             use_dummy_backend: false,
+            use_stub_votes: false,
             _phantom: PhantomData,
         }
     }
@@ -219,11 +223,26 @@ where
         }
     }
 
+    /// Creates an `Eth1Chain` which votes for the `Eth1Data` of the state being built upon, so
+    /// that the caches of `backend` are not required for votes.
+    pub fn new_stubbed(backend: T) -> Self {
+        Self {
+            use_stub_votes: true,
+            ..Self::new(backend)
+        }
+    }
+
     /// Returns `true` if the "dummy" backend is being used.
     pub fn is_dummy_backend(&self) -> bool {
         self.use_dummy_backend
     }
 
+    /// Returns `true` if `Eth1Data` votes are stubbed for every state, rather than only those for
+    /// which `is_eth1_voting_complete` is `true`.
+    pub fn is_stubbed(&self) -> bool {
+        self.use_stub_votes
+    }
+
     /// Returns the `Eth1Data` that should be included in a block being produced for the given
     /// `state`.
     pub fn eth1_data_for_block_production(
@@ -234,6 +253,8 @@ where
         if self.use_dummy_backend {
             let dummy_backend: DummyEth1ChainBackend<E> = DummyEth1ChainBackend::default();
             dummy_backend.eth1_data(state, spec)
+        } else if self.use_stub_votes || is_eth1_voting_complete(state) {
+            Ok(state.eth1_data().clone())
         } else {
             self.backend.eth1_data(state, spec)
         }
@@ -290,6 +311,7 @@ where
             Eth1ChainBackend::from_bytes(&ssz_container.backend_bytes, config, log.clone(), spec)?;
         Ok(Self {
             use_dummy_backend: ssz_container.use_dummy_backend,
+            use_stub_votes: false,
             backend,
             _phantom: PhantomData,
         })
@@ -309,6 +331,11 @@ where
         self.backend.finalize_eth1_data(eth1_data);
     }
 
+    /// Returns `true` if the caches have progressed enough that they should be persisted.
+    pub fn take_checkpoint(&self) -> bool {
+        !self.use_dummy_backend && self.backend.take_checkpoint()
+    }
+
     /// Consumes `self`, returning the backend.
     pub fn into_backend(self) -> T {
         self.backend
@@ -343,6 +370,10 @@ pub trait Eth1ChainBackend<E: EthSpec>: Sized + Send + Sync {
     /// so it should be fast.
     fn finalize_eth1_data(&self, eth1_data: Eth1Data);
 
+    /// Returns `true` if the caches have progressed enough since they were last checkpointed that
+    /// they should be persisted.
+    fn take_checkpoint(&self) -> bool;
+
     /// Returns the block at the head of the chain (ignoring follow distance, etc). Used to obtain
     /// an idea of how up-to-date the remote eth1 node is.
     fn head_block(&self) -> Option<Eth1Block>;
@@ -399,6 +430,10 @@ impl<E: EthSpec> Eth1ChainBackend<E> for DummyEth1ChainBackend<E> {
 
     fn finalize_eth1_data(&self, _eth1_data: Eth1Data) {}
 
+    fn take_checkpoint(&self) -> bool {
+        false
+    }
+
     fn head_block(&self) -> Option<Eth1Block> {
         None
     }
@@ -582,6 +617,10 @@ impl<E: EthSpec> Eth1ChainBackend<E> for CachingEth1Backend<E> {
         self.core.set_to_finalize(Some(eth1_data));
     }
 
+    fn take_checkpoint(&self) -> bool {
+        self.core.take_checkpoint()
+    }
+
     fn head_block(&self) -> Option<Eth1Block> {
         self.core.head_block()
     }
@@ -664,6 +703,23 @@ fn find_winning_vote(valid_votes: Eth1DataVoteCount) -> Option<Eth1Data> {
         .map(|((eth1_data, _), _)| eth1_data.clone())
 }
 
+/// Returns `true` if all deposits from the deposit contract have been processed by `state`, after
+/// which deposits are processed from execution requests (EIP-6110) and `Eth1Data` votes no
+/// longer have any effect.
+///
+/// ## Specification
+///
+/// Equivalent to the condition added to `get_eth1_vote` in Electra:
+///
+/// https://github.com/ethereum/consensus-specs/blob/v1.5.0-alpha.2/specs/electra/validator.md#deposits
+pub fn is_eth1_voting_complete<E: EthSpec>(state: &BeaconState<E>) -> bool {
+    state
+        .deposit_requests_start_index()
+        .map_or(false, |start_index| {
+            state.eth1_deposit_index() == start_index
+        })
+}
+
 /// Returns the unix-epoch seconds at the start of the given `slot`.
 fn slot_start_seconds(genesis_unix_seconds: u64, seconds_per_slot: u64, slot: Slot) -> u64 {
     genesis_unix_seconds + slot.as_u64() * seconds_per_slot
@@ -964,6 +1020,30 @@ mod test {
                 "default vote must correspond to last block in candidate blocks"
             );
         }
+
+        #[test]
+        fn stubbed_vote() {
+            let spec = &E::default_spec();
+            let eth1_config = Eth1Config::default();
+            let log = null_logger().unwrap();
+            let eth1_chain = Eth1Chain::<_, E>::new_stubbed(
+                CachingEth1Backend::new(eth1_config, log, MainnetEthSpec::default_spec()).unwrap(),
+            );
+            assert!(eth1_chain.is_stubbed());
+
+            let mut state: BeaconState<E> = BeaconState::new(0, get_eth1_data(0), spec);
+            *state.slot_mut() = Slot::from(<E as EthSpec>::SlotsPerEth1VotingPeriod::to_u64() * 10);
+            *state.eth1_data_mut() = get_eth1_data(42);
+
+            let vote = eth1_chain
+                .eth1_data_for_block_production(&state, spec)
+                .expect("should produce stubbed eth1 data vote");
+            assert_eq!(
+                vote,
+                *state.eth1_data(),
+                "stubbed vote should be state.eth1_data"
+            );
+        }
     }
 
     mod eth1_data_sets {
@@ -1,19 +1,116 @@
+use eth2::types::EventTopic;
 pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+use parking_lot::Mutex;
 use slog::{trace, Logger};
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{error::SendError, Receiver, Sender};
 use types::EthSpec;
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 16;
+/// The number of replayable events retained for clients which reconnect.
+const DEFAULT_REPLAY_CAPACITY: usize = 256;
+
+/// An event which is assigned an id and retained, so that a client which reconnects may replay
+/// the events it missed.
+///
+/// Only the `head`, `block`, `finalized_checkpoint` and `chain_reorg` topics are replayable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayableEvent<E: EthSpec> {
+    pub id: u64,
+    pub kind: EventKind<E>,
+}
+
+/// The result of `ServerSentEventHandler::subscribe_with_replay`.
+pub struct ReplaySubscription<E: EthSpec> {
+    /// The retained events after the client's cursor, in order.
+    pub missed: Vec<ReplayableEvent<E>>,
+    /// `false` if some events after the client's cursor are no longer retained.
+    pub complete: bool,
+    /// Receivers for the events after `missed`.
+    pub receivers: Vec<Receiver<ReplayableEvent<E>>>,
+}
+
+/// Returns `true` if events for `topic` are retained for replay.
+pub fn is_replayable_topic(topic: EventTopic) -> bool {
+    matches!(
+        topic,
+        EventTopic::Head
+            | EventTopic::Block
+            | EventTopic::FinalizedCheckpoint
+            | EventTopic::ChainReorg
+    )
+}
+
+struct ReplayBuffer<E: EthSpec> {
+    next_id: u64,
+    events: VecDeque<(EventTopic, ReplayableEvent<E>)>,
+    capacity: usize,
+}
+
+impl<E: EthSpec> ReplayBuffer<E> {
+    fn new(capacity: usize) -> Self {
+        // Start from the current time in milliseconds, so that ids continue to increase across
+        // restarts and a cursor from a previous run is never mistaken for a recent one.
+        let next_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        Self {
+            next_id,
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn push(&mut self, topic: EventTopic, kind: EventKind<E>) -> ReplayableEvent<E> {
+        let event = ReplayableEvent {
+            id: self.next_id,
+            kind,
+        };
+        self.next_id += 1;
+        if self.capacity > 0 {
+            if self.events.len() == self.capacity {
+                self.events.pop_front();
+            }
+            self.events.push_back((topic, event.clone()));
+        }
+        event
+    }
+
+    /// Returns the retained events for `topics` with ids greater than `last_event_id`, and
+    /// whether every such event is retained.
+    fn events_after(
+        &self,
+        topics: &[EventTopic],
+        last_event_id: u64,
+    ) -> (Vec<ReplayableEvent<E>>, bool) {
+        if last_event_id >= self.next_id {
+            // The cursor is not one we issued.
+            return (vec![], false);
+        }
+        let oldest_id = self
+            .events
+            .front()
+            .map_or(self.next_id, |(_, event)| event.id);
+        let missed = self
+            .events
+            .iter()
+            .filter(|(topic, event)| event.id > last_event_id && topics.contains(topic))
+            .map(|(_, event)| event.clone())
+            .collect();
+        (missed, last_event_id.saturating_add(1) >= oldest_id)
+    }
+}
 
 pub struct ServerSentEventHandler<E: EthSpec> {
     attestation_tx: Sender<EventKind<E>>,
-    block_tx: Sender<EventKind<E>>,
+    block_tx: Sender<ReplayableEvent<E>>,
     blob_sidecar_tx: Sender<EventKind<E>>,
-    finalized_tx: Sender<EventKind<E>>,
-    head_tx: Sender<EventKind<E>>,
+    finalized_tx: Sender<ReplayableEvent<E>>,
+    head_tx: Sender<ReplayableEvent<E>>,
     exit_tx: Sender<EventKind<E>>,
-    chain_reorg_tx: Sender<EventKind<E>>,
+    chain_reorg_tx: Sender<ReplayableEvent<E>>,
     contribution_tx: Sender<EventKind<E>>,
     payload_attributes_tx: Sender<EventKind<E>>,
     late_head: Sender<EventKind<E>>,
@@ -24,6 +121,11 @@ pub struct ServerSentEventHandler<E: EthSpec> {
     attester_slashing_tx: Sender<EventKind<E>>,
     bls_to_execution_change_tx: Sender<EventKind<E>>,
     block_gossip_tx: Sender<EventKind<E>>,
+    proposer_re_org_tx: Sender<EventKind<E>>,
+    slasher_attestation_tx: Sender<EventKind<E>>,
+    slasher_block_header_tx: Sender<EventKind<E>>,
+    optimistic_status_tx: Sender<EventKind<E>>,
+    replay_buffer: Mutex<ReplayBuffer<E>>,
     log: Logger,
 }
 
@@ -53,6 +155,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         let (attester_slashing_tx, _) = broadcast::channel(capacity);
         let (bls_to_execution_change_tx, _) = broadcast::channel(capacity);
         let (block_gossip_tx, _) = broadcast::channel(capacity);
+        let (proposer_re_org_tx, _) = broadcast::channel(capacity);
+        let (slasher_attestation_tx, _) = broadcast::channel(capacity);
+        let (slasher_block_header_tx, _) = broadcast::channel(capacity);
+        let (optimistic_status_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -72,6 +178,11 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
             attester_slashing_tx,
             bls_to_execution_change_tx,
             block_gossip_tx,
+            proposer_re_org_tx,
+            slasher_attestation_tx,
+            slasher_block_header_tx,
+            optimistic_status_tx,
+            replay_buffer: Mutex::new(ReplayBuffer::new(DEFAULT_REPLAY_CAPACITY)),
             log,
         }
     }
@@ -91,28 +202,24 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .send(kind)
                 .map(|count| log_count("attestation", count)),
             EventKind::Block(_) => self
-                .block_tx
-                .send(kind)
+                .send_replayable(&self.block_tx, EventTopic::Block, kind)
                 .map(|count| log_count("block", count)),
             EventKind::BlobSidecar(_) => self
                 .blob_sidecar_tx
                 .send(kind)
                 .map(|count| log_count("blob sidecar", count)),
             EventKind::FinalizedCheckpoint(_) => self
-                .finalized_tx
-                .send(kind)
+                .send_replayable(&self.finalized_tx, EventTopic::FinalizedCheckpoint, kind)
                 .map(|count| log_count("finalized checkpoint", count)),
             EventKind::Head(_) => self
-                .head_tx
-                .send(kind)
+                .send_replayable(&self.head_tx, EventTopic::Head, kind)
                 .map(|count| log_count("head", count)),
             EventKind::VoluntaryExit(_) => self
                 .exit_tx
                 .send(kind)
                 .map(|count| log_count("exit", count)),
             EventKind::ChainReorg(_) => self
-                .chain_reorg_tx
-                .send(kind)
+                .send_replayable(&self.chain_reorg_tx, EventTopic::ChainReorg, kind)
                 .map(|count| log_count("chain reorg", count)),
             EventKind::ContributionAndProof(_) => self
                 .contribution_tx
@@ -154,17 +261,81 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .block_gossip_tx
                 .send(kind)
                 .map(|count| log_count("block gossip", count)),
+            EventKind::ProposerReOrg(_) => self
+                .proposer_re_org_tx
+                .send(kind)
+                .map(|count| log_count("proposer re-org", count)),
+            EventKind::SlasherAttestation(_) => self
+                .slasher_attestation_tx
+                .send(kind)
+                .map(|count| log_count("slasher attestation", count)),
+            EventKind::SlasherBlockHeader(_) => self
+                .slasher_block_header_tx
+                .send(kind)
+                .map(|count| log_count("slasher block header", count)),
+            EventKind::OptimisticStatus(_) => self
+                .optimistic_status_tx
+                .send(kind)
+                .map(|count| log_count("optimistic status", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
         }
     }
 
+    /// Retain `kind` for replay and send it to the subscribers of `tx`.
+    fn send_replayable(
+        &self,
+        tx: &Sender<ReplayableEvent<E>>,
+        topic: EventTopic,
+        kind: EventKind<E>,
+    ) -> Result<usize, SendError<EventKind<E>>> {
+        // Send while holding the lock, so that `subscribe_with_replay` sees each event either in
+        // the buffer or on its receivers, but not both.
+        let mut replay_buffer = self.replay_buffer.lock();
+        let event = replay_buffer.push(topic, kind);
+        tx.send(event)
+            .map_err(|SendError(event)| SendError(event.kind))
+    }
+
+    /// Subscribe to the replayable `topics`, first returning the retained events with ids greater
+    /// than `last_event_id`.
+    ///
+    /// Topics which are not replayable are ignored.
+    pub fn subscribe_with_replay(
+        &self,
+        topics: &[EventTopic],
+        last_event_id: Option<u64>,
+    ) -> ReplaySubscription<E> {
+        let replay_buffer = self.replay_buffer.lock();
+        let (missed, complete) = match last_event_id {
+            Some(last_event_id) => replay_buffer.events_after(topics, last_event_id),
+            None => (vec![], true),
+        };
+        let receivers = topics
+            .iter()
+            .filter_map(|topic| match topic {
+                EventTopic::Head => Some(self.head_tx.subscribe()),
+                EventTopic::Block => Some(self.block_tx.subscribe()),
+                EventTopic::FinalizedCheckpoint => Some(self.finalized_tx.subscribe()),
+                EventTopic::ChainReorg => Some(self.chain_reorg_tx.subscribe()),
+                _ => None,
+            })
+            .collect();
+        drop(replay_buffer);
+
+        ReplaySubscription {
+            missed,
+            complete,
+            receivers,
+        }
+    }
+
     pub fn subscribe_attestation(&self) -> Receiver<EventKind<E>> {
         self.attestation_tx.subscribe()
     }
 
-    pub fn subscribe_block(&self) -> Receiver<EventKind<E>> {
+    pub fn subscribe_block(&self) -> Receiver<ReplayableEvent<E>> {
         self.block_tx.subscribe()
     }
 
@@ -172,11 +343,11 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.blob_sidecar_tx.subscribe()
     }
 
-    pub fn subscribe_finalized(&self) -> Receiver<EventKind<E>> {
+    pub fn subscribe_finalized(&self) -> Receiver<ReplayableEvent<E>> {
         self.finalized_tx.subscribe()
     }
 
-    pub fn subscribe_head(&self) -> Receiver<EventKind<E>> {
+    pub fn subscribe_head(&self) -> Receiver<ReplayableEvent<E>> {
         self.head_tx.subscribe()
     }
 
@@ -184,7 +355,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.exit_tx.subscribe()
     }
 
-    pub fn subscribe_reorgs(&self) -> Receiver<EventKind<E>> {
+    pub fn subscribe_reorgs(&self) -> Receiver<ReplayableEvent<E>> {
         self.chain_reorg_tx.subscribe()
     }
 
@@ -228,6 +399,22 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.block_gossip_tx.subscribe()
     }
 
+    pub fn subscribe_proposer_re_org(&self) -> Receiver<EventKind<E>> {
+        self.proposer_re_org_tx.subscribe()
+    }
+
+    pub fn subscribe_slasher_attestation(&self) -> Receiver<EventKind<E>> {
+        self.slasher_attestation_tx.subscribe()
+    }
+
+    pub fn subscribe_slasher_block_header(&self) -> Receiver<EventKind<E>> {
+        self.slasher_block_header_tx.subscribe()
+    }
+
+    pub fn subscribe_optimistic_status(&self) -> Receiver<EventKind<E>> {
+        self.optimistic_status_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -287,4 +474,20 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
     pub fn has_block_gossip_subscribers(&self) -> bool {
         self.block_gossip_tx.receiver_count() > 0
     }
+
+    pub fn has_proposer_re_org_subscribers(&self) -> bool {
+        self.proposer_re_org_tx.receiver_count() > 0
+    }
+
+    pub fn has_slasher_attestation_subscribers(&self) -> bool {
+        self.slasher_attestation_tx.receiver_count() > 0
+    }
+
+    pub fn has_slasher_block_header_subscribers(&self) -> bool {
+        self.slasher_block_header_tx.receiver_count() > 0
+    }
+
+    pub fn has_optimistic_status_subscribers(&self) -> bool {
+        self.optimistic_status_tx.receiver_count() > 0
+    }
 }
@@ -57,11 +57,14 @@ pub struct PayloadNotifier<T: BeaconChainTypes> {
 }
 
 impl<T: BeaconChainTypes> PayloadNotifier<T> {
+    /// If `optimistic_payload_verified` is `true` then the optimistic sync verifications of the
+    /// payload have already been performed, e.g. whilst range sync imported the previous batch.
     pub fn new(
         chain: Arc<BeaconChain<T>>,
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
         state: &BeaconState<T::EthSpec>,
         notify_execution_layer: NotifyExecutionLayer,
+        optimistic_payload_verified: bool,
     ) -> Result<Self, BlockError<T::EthSpec>> {
         let payload_verification_status = if is_execution_enabled(state, block.message().body()) {
             // Perform the initial stages of payload verification.
@@ -80,6 +83,11 @@ impl<T: BeaconChainTypes> PayloadNotifier<T> {
             .map_err(BlockError::PerBlockProcessingError)?;
 
             match notify_execution_layer {
+                NotifyExecutionLayer::No
+                    if chain.config.optimistic_finalized_sync && optimistic_payload_verified =>
+                {
+                    Some(PayloadVerificationStatus::Optimistic)
+                }
                 NotifyExecutionLayer::No if chain.config.optimistic_finalized_sync => {
                     // Create a NewPayloadRequest (no clones required) and check optimistic sync verifications
                     let new_payload_request: NewPayloadRequest<T::EthSpec> =
@@ -0,0 +1,194 @@
+//! Re-broadcast of voluntary exits and BLS to execution changes received shortly before a fork.
+//!
+//! Operations received before a fork are only published on the gossip topics of the old fork,
+//! which peers stop forwarding once they transition, so they are easily lost. Operations imported
+//! within `FORK_REBROADCAST_EPOCHS` of the next fork are queued, and once the fork is reached they
+//! are re-validated: those which are still valid are returned to be published on the topics of
+//! the new fork, and those which are not are removed from the op pool. The queue is persisted, so
+//! that it survives a restart across the fork.
+use crate::beacon_chain::FORK_REBROADCAST_DB_KEY;
+use crate::persisted_fork_rebroadcast::PersistedForkRebroadcast;
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use slog::{debug, info};
+use types::{Epoch, EthSpec, SignedBlsToExecutionChange, SignedVoluntaryExit};
+
+/// The number of epochs before a fork during which imported operations are queued.
+pub const FORK_REBROADCAST_EPOCHS: u64 = 2;
+
+/// The validator indices of the operations queued for re-broadcast at a fork.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ForkRebroadcastQueue {
+    fork_epoch: Epoch,
+    voluntary_exits: Vec<u64>,
+    bls_to_execution_changes: Vec<u64>,
+}
+
+impl ForkRebroadcastQueue {
+    /// Returns the queue for the fork at `fork_epoch`, discarding any queue for another fork.
+    fn for_fork(&mut self, fork_epoch: Epoch) -> &mut Self {
+        if self.fork_epoch != fork_epoch {
+            *self = Self {
+                fork_epoch,
+                ..Self::default()
+            };
+        }
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.voluntary_exits.is_empty() && self.bls_to_execution_changes.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.voluntary_exits.len() + self.bls_to_execution_changes.len()
+    }
+}
+
+/// The operations to re-broadcast once a fork has been reached.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ForkRebroadcast {
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
+fn queue_index(indices: &mut Vec<u64>, validator_index: u64) {
+    if !indices.contains(&validator_index) {
+        indices.push(validator_index);
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns the epoch of the next fork, if it is at most `FORK_REBROADCAST_EPOCHS` away.
+    fn fork_rebroadcast_epoch(&self) -> Option<Epoch> {
+        let slot = self.slot().ok()?;
+        let (_, fork_epoch) = self.spec.next_fork_epoch::<T::EthSpec>(slot)?;
+        let current_epoch = slot.epoch(T::EthSpec::slots_per_epoch());
+        (fork_epoch <= current_epoch + FORK_REBROADCAST_EPOCHS).then_some(fork_epoch)
+    }
+
+    /// Queue the voluntary exit of `validator_index` for re-broadcast, if the next fork is near.
+    pub(crate) fn queue_voluntary_exit_rebroadcast(&self, validator_index: u64) {
+        if let Some(fork_epoch) = self.fork_rebroadcast_epoch() {
+            let mut queue = self.fork_rebroadcast.lock();
+            queue_index(
+                &mut queue.for_fork(fork_epoch).voluntary_exits,
+                validator_index,
+            );
+        }
+    }
+
+    /// Queue the BLS to execution change of `validator_index` for re-broadcast, if the next fork
+    /// is near.
+    pub(crate) fn queue_bls_to_execution_change_rebroadcast(&self, validator_index: u64) {
+        if let Some(fork_epoch) = self.fork_rebroadcast_epoch() {
+            let mut queue = self.fork_rebroadcast.lock();
+            queue_index(
+                &mut queue.for_fork(fork_epoch).bls_to_execution_changes,
+                validator_index,
+            );
+        }
+    }
+
+    /// Take the queued operations if the fork they were queued for has been reached.
+    ///
+    /// The operations are re-validated against the new fork. Those which are still valid are
+    /// returned for re-broadcast, and those which are not are removed from the op pool.
+    pub fn take_fork_rebroadcast(&self) -> Result<ForkRebroadcast, BeaconChainError> {
+        let current_epoch = self.epoch()?;
+        let queue = {
+            let mut queue = self.fork_rebroadcast.lock();
+            if queue.is_empty() || current_epoch < queue.fork_epoch {
+                return Ok(ForkRebroadcast::default());
+            }
+            std::mem::take(&mut *queue)
+        };
+
+        let head = self.head_snapshot();
+        let fork = self.spec.fork_at_epoch(current_epoch);
+        let rebroadcast = ForkRebroadcast {
+            voluntary_exits: self.op_pool.revalidate_voluntary_exits(
+                &queue.voluntary_exits,
+                &head.beacon_state,
+                &fork,
+                &self.spec,
+            ),
+            bls_to_execution_changes: self.op_pool.revalidate_bls_to_execution_changes(
+                &queue.bls_to_execution_changes,
+                &head.beacon_state,
+                &fork,
+                &self.spec,
+            ),
+        };
+
+        let valid = rebroadcast.voluntary_exits.len() + rebroadcast.bls_to_execution_changes.len();
+        info!(
+            self.log,
+            "Re-broadcasting operations at fork";
+            "fork_epoch" => queue.fork_epoch,
+            "voluntary_exits" => rebroadcast.voluntary_exits.len(),
+            "bls_to_execution_changes" => rebroadcast.bls_to_execution_changes.len(),
+            "dropped" => queue.len().saturating_sub(valid),
+        );
+        Ok(rebroadcast)
+    }
+
+    /// Persists the queue of operations to re-broadcast at the next fork to disk.
+    pub fn persist_fork_rebroadcast(&self) -> Result<(), BeaconChainError> {
+        let queue = self.fork_rebroadcast.lock().clone();
+        self.store.put_item(
+            &FORK_REBROADCAST_DB_KEY,
+            &PersistedForkRebroadcast {
+                fork_epoch: queue.fork_epoch,
+                voluntary_exits: queue.voluntary_exits,
+                bls_to_execution_changes: queue.bls_to_execution_changes,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Restores the queue persisted by `Self::persist_fork_rebroadcast`.
+    pub fn restore_fork_rebroadcast(&self) -> Result<(), BeaconChainError> {
+        let Some(persisted) = self
+            .store
+            .get_item::<PersistedForkRebroadcast>(&FORK_REBROADCAST_DB_KEY)?
+        else {
+            return Ok(());
+        };
+
+        let queue = ForkRebroadcastQueue {
+            fork_epoch: persisted.fork_epoch,
+            voluntary_exits: persisted.voluntary_exits,
+            bls_to_execution_changes: persisted.bls_to_execution_changes,
+        };
+        debug!(
+            self.log,
+            "Restored fork re-broadcast queue";
+            "fork_epoch" => queue.fork_epoch,
+            "operations" => queue.len(),
+        );
+        *self.fork_rebroadcast.lock() = queue;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_is_reset_for_new_fork() {
+        let mut queue = ForkRebroadcastQueue::default();
+        queue_index(&mut queue.for_fork(Epoch::new(10)).voluntary_exits, 1);
+        queue_index(&mut queue.for_fork(Epoch::new(10)).voluntary_exits, 1);
+        queue_index(
+            &mut queue.for_fork(Epoch::new(10)).bls_to_execution_changes,
+            2,
+        );
+        assert_eq!(queue.len(), 2);
+
+        queue_index(&mut queue.for_fork(Epoch::new(20)).voluntary_exits, 3);
+        assert_eq!(queue.fork_epoch, Epoch::new(20));
+        assert_eq!(queue.voluntary_exits, vec![3]);
+        assert!(queue.bls_to_execution_changes.is_empty());
+    }
+}
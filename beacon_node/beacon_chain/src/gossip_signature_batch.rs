@@ -0,0 +1,197 @@
+//! Provides verification of the signatures of unaggregated attestations, aggregated attestations
+//! and sync committee messages from gossip in a single BLS batch.
+//!
+//! The per-topic batches in `attestation_verification::batch` only combine messages of the same
+//! kind, which limits their size when the load is spread across topics. Here, messages of all three
+//! kinds are verified up until the point of signature verification. Then, the signatures of all
+//! messages which passed those checks are verified in one batch.
+//!
+//! If the batch fails, the signatures of every message in it are verified individually, as for the
+//! per-topic batches, so a poisoned batch costs a single failed batch verification on top of
+//! individual verification. A message whose signatures are not proven valid by the batch always
+//! has them verified individually, so each result has the same fidelity as individual verification.
+use crate::attestation_verification::{
+    aggregated_attestation_signature_sets, unaggregated_attestation_signature_set,
+    CheckAttestationSignature, Error as AttestationError, IndexedAggregatedAttestation,
+    IndexedUnaggregatedAttestation, VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
+};
+use crate::sync_committee_verification::{
+    CheckSyncCommitteeMessageSignature, Error as SyncCommitteeError,
+    PartiallyVerifiedSyncCommitteeMessage, VerifiedSyncCommitteeMessage,
+};
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use bls::verify_signature_sets;
+use types::{Attestation, SignedAggregateAndProof, SubnetId, SyncCommitteeMessage, SyncSubnetId};
+
+/// The results of `batch_verify_gossip_signatures`, with a one-to-one mapping to each kind of
+/// message supplied as input.
+pub struct GossipSignatureBatchResults<'a, T: BeaconChainTypes> {
+    pub attestations: Vec<Result<VerifiedUnaggregatedAttestation<'a, T>, AttestationError>>,
+    pub aggregates: Vec<Result<VerifiedAggregatedAttestation<'a, T>, AttestationError>>,
+    pub sync_messages: Vec<Result<VerifiedSyncCommitteeMessage, SyncCommitteeError>>,
+}
+
+/// Verify unaggregated attestations, aggregated attestations and sync committee messages using a
+/// single batch BLS signature verification.
+///
+/// See module-level docs for more info.
+pub fn batch_verify_gossip_signatures<'a, T, A, G, S>(
+    attestations: A,
+    aggregates: G,
+    sync_messages: S,
+    chain: &BeaconChain<T>,
+) -> GossipSignatureBatchResults<'a, T>
+where
+    T: BeaconChainTypes,
+    A: Iterator<Item = (&'a Attestation<T::EthSpec>, Option<SubnetId>)>,
+    G: Iterator<Item = &'a SignedAggregateAndProof<T::EthSpec>>,
+    S: Iterator<Item = (SyncCommitteeMessage, SyncSubnetId)>,
+{
+    // Perform partial verification of all messages, collecting the results.
+    let attestations = attestations
+        .map(|(attestation, subnet_id)| {
+            IndexedUnaggregatedAttestation::verify(attestation, subnet_id, chain)
+        })
+        .collect::<Vec<_>>();
+    let aggregates = aggregates
+        .map(|aggregate| IndexedAggregatedAttestation::verify(aggregate, chain))
+        .collect::<Vec<_>>();
+    let sync_messages = sync_messages
+        .map(|(sync_message, subnet_id)| {
+            PartiallyVerifiedSyncCommitteeMessage::verify(sync_message, subnet_id, chain)
+        })
+        .collect::<Vec<_>>();
+
+    // Whether the signatures of each partially verified message were proven valid by the batch,
+    // in the order of attestations, aggregates and then sync messages.
+    let mut signatures_valid = Vec::new();
+
+    let num_partially_verified = attestations.iter().flatten().count()
+        + aggregates.iter().flatten().count()
+        + sync_messages.iter().flatten().count();
+
+    if num_partially_verified > 0 {
+        let signature_setup_timer =
+            metrics::start_timer(&metrics::GOSSIP_SIGNATURE_BATCH_SIGNATURE_SETUP_TIMES);
+
+        let pubkey_cache = chain.validator_pubkey_cache.read();
+
+        // The signature sets of each message. A message whose signature sets cannot be produced is
+        // left out of the batch, so that the error is reported by individual verification.
+        let mut signature_sets = Vec::with_capacity(num_partially_verified);
+        for indexed in attestations.iter().flatten() {
+            signature_sets.push(
+                unaggregated_attestation_signature_set(indexed, &pubkey_cache, chain)
+                    .ok()
+                    .map(|signature_set| vec![signature_set]),
+            );
+        }
+        for indexed in aggregates.iter().flatten() {
+            signature_sets.push(
+                aggregated_attestation_signature_sets(indexed, &pubkey_cache, chain)
+                    .ok()
+                    .map(Vec::from),
+            );
+        }
+        for partially_verified in sync_messages.iter().flatten() {
+            signature_sets.push(
+                partially_verified
+                    .signature_set(&pubkey_cache, chain)
+                    .ok()
+                    .map(|signature_set| vec![signature_set]),
+            );
+        }
+        let in_batch = signature_sets
+            .iter()
+            .map(Option::is_some)
+            .collect::<Vec<_>>();
+        let batch = signature_sets
+            .iter()
+            .flatten()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        metrics::stop_timer(signature_setup_timer);
+
+        let _signature_verification_timer =
+            metrics::start_timer(&metrics::GOSSIP_SIGNATURE_BATCH_SIGNATURE_TIMES);
+
+        // A batch of a single message is left for individual verification, which verifies its
+        // signatures anyway.
+        let batch_valid = if in_batch.iter().filter(|in_batch| **in_batch).count() > 1 {
+            let batch_valid = verify_signature_sets(batch.into_iter());
+            if !batch_valid {
+                metrics::inc_counter(&metrics::GOSSIP_SIGNATURE_BATCH_FAILURES);
+            }
+            batch_valid
+        } else {
+            false
+        };
+
+        signatures_valid = in_batch
+            .into_iter()
+            .map(|in_batch| in_batch && batch_valid)
+            .collect();
+    }
+
+    let num_unverified = signatures_valid.iter().filter(|valid| !**valid).count();
+    metrics::inc_counter_by(
+        &metrics::GOSSIP_SIGNATURE_BATCH_UNVERIFIED_MESSAGES,
+        num_unverified as u64,
+    );
+
+    // Complete the verification of each message, verifying the signatures of any message which
+    // were not proven valid by the batch.
+    let mut signatures_valid = signatures_valid.into_iter();
+    let mut next_signature_valid = || signatures_valid.next().unwrap_or(false);
+
+    let attestations = attestations
+        .into_iter()
+        .map(|result| {
+            result.and_then(|indexed| {
+                let check_signature = if next_signature_valid() {
+                    CheckAttestationSignature::No
+                } else {
+                    CheckAttestationSignature::Yes
+                };
+                VerifiedUnaggregatedAttestation::from_indexed(indexed, chain, check_signature)
+            })
+        })
+        .collect();
+    let aggregates = aggregates
+        .into_iter()
+        .map(|result| {
+            result.and_then(|indexed| {
+                let check_signature = if next_signature_valid() {
+                    CheckAttestationSignature::No
+                } else {
+                    CheckAttestationSignature::Yes
+                };
+                VerifiedAggregatedAttestation::from_indexed(indexed, chain, check_signature)
+            })
+        })
+        .collect();
+    let sync_messages = sync_messages
+        .into_iter()
+        .map(|result| {
+            result.and_then(|partially_verified| {
+                let check_signature = if next_signature_valid() {
+                    CheckSyncCommitteeMessageSignature::No
+                } else {
+                    CheckSyncCommitteeMessageSignature::Yes
+                };
+                VerifiedSyncCommitteeMessage::from_partially_verified(
+                    partially_verified,
+                    chain,
+                    check_signature,
+                )
+            })
+        })
+        .collect();
+
+    GossipSignatureBatchResults {
+        attestations,
+        aggregates,
+        sync_messages,
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use lru::LruCache;
+use parking_lot::Mutex;
+use slog::debug;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+use types::non_zero_usize::new_non_zero_usize;
+use types::{BeaconState, EthSpec, Hash256, Slot};
+
+/// The number of replayed states to keep, which allows requests for nearby epochs to replay from
+/// a recent state rather than from the start of the gap.
+const REPLAYED_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(4);
+/// The maximum number of states that may be replayed at once.
+const MAX_CONCURRENT_REPLAYS: usize = 2;
+
+/// Cache of finalized states reconstructed by replaying blocks, for states which are not stored
+/// because they lie between the historic state limits of the database.
+///
+/// This happens after checkpoint sync when historic states have not been reconstructed, or have
+/// only been partly reconstructed.
+pub struct HistoricalStateReplayCache<E: EthSpec> {
+    states: Mutex<LruCache<Slot, BeaconState<E>>>,
+    active_replays: Mutex<usize>,
+}
+
+impl<E: EthSpec> Default for HistoricalStateReplayCache<E> {
+    fn default() -> Self {
+        Self {
+            states: Mutex::new(LruCache::new(REPLAYED_STATE_CACHE_SIZE)),
+            active_replays: Mutex::new(0),
+        }
+    }
+}
+
+/// Releases a replay slot when dropped.
+struct ReplayPermit<'a> {
+    active_replays: &'a Mutex<usize>,
+}
+
+impl<'a> ReplayPermit<'a> {
+    fn acquire(active_replays: &'a Mutex<usize>) -> Result<Self, BeaconChainError> {
+        let mut active = active_replays.lock();
+        if *active >= MAX_CONCURRENT_REPLAYS {
+            return Err(BeaconChainError::TooManyHistoricalStateReplays);
+        }
+        *active += 1;
+        Ok(Self { active_replays })
+    }
+}
+
+impl<'a> Drop for ReplayPermit<'a> {
+    fn drop(&mut self) {
+        let mut active = self.active_replays.lock();
+        *active = active.saturating_sub(1);
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns the canonical finalized state at `slot`, which lies between the historic state
+    /// limits of the database and so is not stored.
+    ///
+    /// The state is reconstructed by replaying blocks from the nearest earlier state, which is
+    /// either a previously replayed state or the state at the lower historic state limit. This
+    /// requires the blocks to have been backfilled.
+    pub fn get_replayed_historical_state(
+        &self,
+        slot: Slot,
+    ) -> Result<BeaconState<T::EthSpec>, BeaconChainError> {
+        let cache = &self.historical_state_replay_cache;
+        if let Some(state) = cache.states.lock().get(&slot) {
+            return Ok(state.clone());
+        }
+
+        let _permit = ReplayPermit::acquire(&cache.active_replays)?;
+
+        let (lower_limit, _) = self.store.get_historic_state_limits();
+        let cached_state = cache
+            .states
+            .lock()
+            .iter()
+            .filter(|(state_slot, _)| **state_slot > lower_limit && **state_slot <= slot)
+            .max_by_key(|(state_slot, _)| **state_slot)
+            .map(|(_, state)| state.clone());
+        let start_state = match cached_state {
+            Some(state) => state,
+            None => self
+                .store
+                .load_cold_state_by_slot(lower_limit)?
+                .ok_or(BeaconChainError::NoStateForSlot(lower_limit))?,
+        };
+        let start_slot = start_state.slot();
+
+        // Every block after the start state is needed.
+        if self.store.get_oldest_block_slot() > start_slot + 1 {
+            return Err(BeaconChainError::NoStateForSlot(slot));
+        }
+        let end_block_root = self
+            .block_root_at_slot(slot, WhenSlotSkipped::Prev)?
+            .ok_or(BeaconChainError::NoStateForSlot(slot))?;
+
+        let timer = Instant::now();
+        let blocks = self
+            .store
+            .load_blocks_to_replay(start_slot, slot, end_block_root)?;
+        let num_blocks = blocks.len();
+        let mut state = self.store.replay_blocks(
+            start_state,
+            blocks,
+            slot,
+            None::<std::iter::Empty<Result<(Hash256, Slot), store::Error>>>,
+            None,
+        )?;
+        state.apply_pending_mutations()?;
+
+        debug!(
+            self.log,
+            "Replayed historical state";
+            "slot" => slot,
+            "start_slot" => start_slot,
+            "blocks" => num_blocks,
+            "time_ms" => timer.elapsed().as_millis(),
+        );
+
+        cache.states.lock().put(slot, state.clone());
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_permits_are_limited() {
+        let active_replays = Mutex::new(0);
+
+        let permits = (0..MAX_CONCURRENT_REPLAYS)
+            .map(|_| ReplayPermit::acquire(&active_replays).unwrap())
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            ReplayPermit::acquire(&active_replays),
+            Err(BeaconChainError::TooManyHistoricalStateReplays)
+        ));
+
+        // Dropping a permit allows another replay to start.
+        drop(permits);
+        assert_eq!(*active_replays.lock(), 0);
+        ReplayPermit::acquire(&active_replays).unwrap();
+    }
+}
@@ -10,6 +10,7 @@ pub mod beacon_proposer_cache;
 mod beacon_snapshot;
 pub mod bellatrix_readiness;
 pub mod blob_verification;
+pub mod block_production_budget;
 pub mod block_reward;
 mod block_times_cache;
 mod block_verification;
@@ -29,10 +30,13 @@ mod eth1_finalization_cache;
 pub mod events;
 pub mod execution_payload;
 pub mod fork_choice_signal;
+pub mod fork_rebroadcast;
 pub mod fork_revert;
+pub mod gossip_signature_batch;
 pub mod graffiti_calculator;
 mod head_tracker;
 pub mod historical_blocks;
+mod historical_state_replay;
 pub mod kzg_utils;
 pub mod light_client_finality_update_verification;
 pub mod light_client_optimistic_update_verification;
@@ -45,18 +49,30 @@ mod observed_attesters;
 mod observed_blob_sidecars;
 pub mod observed_block_producers;
 pub mod observed_operations;
+mod observed_proposal_headers;
 mod observed_slashable;
 pub mod otb_verification_service;
+mod persisted_attestation_effectiveness;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
+mod persisted_fork_rebroadcast;
+mod persisted_proposer_preparations;
+mod persisted_shuffling_caches;
+mod persisted_timeliness;
+mod persisted_verified_payloads;
 mod pre_finalization_cache;
 pub mod proposer_prep_service;
+pub mod remote_block_production;
+pub mod reorg_capture;
 pub mod schema_change;
 pub mod shuffling_cache;
+pub mod slashing_guard;
+mod speculative_segment_cache;
 pub mod state_advance_timer;
 pub mod sync_committee_rewards;
 pub mod sync_committee_verification;
 pub mod test_utils;
+pub mod timeliness;
 pub mod validator_monitor;
 pub mod validator_pubkey_cache;
 
@@ -66,7 +82,7 @@ pub use self::beacon_chain::{
     ChainSegmentResult, ForkChoiceError, LightClientProducerEvent, OverrideForkchoiceUpdate,
     ProduceBlockVerification, StateSkipConfig, WhenSlotSkipped,
     INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
-    INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
+    INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON, INVALID_PAYLOAD_REORG_SHUTDOWN_REASON,
 };
 pub use self::beacon_snapshot::BeaconSnapshot;
 pub use self::chain_config::ChainConfig;
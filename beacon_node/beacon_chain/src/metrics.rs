@@ -19,6 +19,14 @@ pub const VALIDATOR_MONITOR_ATTESTATION_SIMULATOR_SOURCE_ATTESTER_HIT_TOTAL: &st
     "validator_monitor_attestation_simulator_source_attester_hit_total";
 pub const VALIDATOR_MONITOR_ATTESTATION_SIMULATOR_SOURCE_ATTESTER_MISS_TOTAL: &str =
     "validator_monitor_attestation_simulator_source_attester_miss_total";
+pub const VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_HIT_TOTAL: &str =
+    "validator_monitor_sync_committee_simulator_hit_total";
+pub const VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_MISS_TOTAL: &str =
+    "validator_monitor_sync_committee_simulator_miss_total";
+pub const VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_HIT_TOTAL: &str =
+    "validator_monitor_proposal_simulator_hit_total";
+pub const VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_MISS_TOTAL: &str =
+    "validator_monitor_proposal_simulator_miss_total";
 
 lazy_static! {
     /*
@@ -148,6 +156,18 @@ lazy_static! {
         "beacon_block_production_state_root_seconds",
         "Time taken to calculate the block's state root"
     );
+    pub static ref BLOCK_PRODUCTION_BUDGET_EXCEEDED: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_block_production_budget_exceeded_total",
+            "Count of block production stages which completed after their deadline",
+            &["stage"]
+        );
+    pub static ref BLOCK_PRODUCTION_BUDGET_DEGRADED: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_block_production_budget_degraded_total",
+            "Count of block production stages which were cut short to meet their deadline",
+            &["stage"]
+        );
 
     /*
      * Block Statistics
@@ -277,6 +297,26 @@ lazy_static! {
         "Time spent on the signature verification of batch unaggregate attestation processing"
     );
 
+    /*
+     * Gossip signature batches
+     */
+    pub static ref GOSSIP_SIGNATURE_BATCH_SIGNATURE_SETUP_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_gossip_signature_batch_signature_setup_seconds",
+        "Time spent on setting up for the signature verification of batches of mixed gossip messages"
+    );
+    pub static ref GOSSIP_SIGNATURE_BATCH_SIGNATURE_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_gossip_signature_batch_signature_seconds",
+        "Time spent on the batch signature verification of mixed gossip messages"
+    );
+    pub static ref GOSSIP_SIGNATURE_BATCH_FAILURES: Result<IntCounter> = try_create_int_counter(
+        "beacon_gossip_signature_batch_failures_total",
+        "Count of batch signature verifications which failed, causing individual verification"
+    );
+    pub static ref GOSSIP_SIGNATURE_BATCH_UNVERIFIED_MESSAGES: Result<IntCounter> = try_create_int_counter(
+        "beacon_gossip_signature_batch_unverified_messages_total",
+        "Count of messages whose signatures were not proven valid by a batch and were verified individually"
+    );
+
     /*
      * Shuffling cache
      */
@@ -353,6 +393,10 @@ lazy_static! {
         "beacon_fork_choice_reorg_distance",
         "The distance of each re-org of the fork choice algorithm"
     );
+    pub static ref REORG_CAPTURES_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_reorg_captures_total",
+        "Count of re-orgs for which a forensic bundle was written to disk"
+    );
     pub static ref FORK_CHOICE_REORG_COUNT_INTEROP: Result<IntCounter> = try_create_int_counter(
         "beacon_reorgs_total",
         "Count of occasions fork choice has switched to a different chain"
@@ -621,6 +665,13 @@ lazy_static! {
             "The attestation inclusion distance calculated during per epoch processing",
             &["validator"]
         );
+    pub static ref VALIDATOR_MONITOR_ATTESTATION_EFFECTIVENESS: Result<GaugeVec> =
+        try_create_float_gauge_vec(
+            "validator_monitor_attestation_effectiveness_percent",
+            "The percentage of the maximum attestation reward earned over the last day, \
+            weighted by the correctness of the source, target and head votes",
+            &["validator"]
+        );
     pub static ref VALIDATOR_MONITOR_PREV_EPOCH_ATTESTATIONS_TOTAL: Result<IntGaugeVec> =
         try_create_int_gauge_vec(
             "validator_monitor_prev_epoch_attestations_total",
@@ -861,6 +912,13 @@ lazy_static! {
         "Duration between the start of the block's slot and the time the block was observed.",
     );
 
+    pub static ref GOSSIP_ARRIVAL_DELAY_SECONDS: Result<HistogramVec> = try_create_histogram_vec_with_buckets(
+        "beacon_gossip_arrival_delay_seconds",
+        "Duration between the start of the slot and the arrival of a valid gossip message for it",
+        linear_buckets(0.5, 0.5, 24),
+        &["kind"]
+    );
+
     pub static ref BEACON_BLOCK_DELAY_CONSENSUS_VERIFICATION_TIME: Result<IntGauge> = try_create_int_gauge(
         "beacon_block_delay_consensus_verification_time",
         "The time taken to verify the block within Lighthouse",
@@ -1015,6 +1073,26 @@ lazy_static! {
         "Time spent verifying the signature set during backfill sync, including setup"
     );
 
+    /*
+     * Fork pruning
+     */
+    pub static ref FORK_PRUNING_BLOCKS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_fork_pruning_blocks_total",
+        "Number of blocks of abandoned forks pruned from the database"
+    );
+    pub static ref FORK_PRUNING_STATES_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_fork_pruning_states_total",
+        "Number of states of abandoned forks pruned from the database"
+    );
+    pub static ref FORK_PRUNING_RECLAIMED_BYTES_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_fork_pruning_reclaimed_bytes_total",
+        "Size of the blocks, payloads, blobs and states of abandoned forks pruned from the database"
+    );
+    pub static ref FORK_PRUNING_RETAINED_BLOCKS: Result<IntGauge> = try_create_int_gauge(
+        "beacon_fork_pruning_retained_blocks",
+        "Number of blocks of abandoned forks retained until --prune-forks-after-epochs elapses"
+    );
+
     /*
      * Pre-finalization block cache.
      */
@@ -1029,6 +1107,45 @@ lazy_static! {
             "Number of block roots subject to single block lookups"
         );
 
+    /*
+     * Speculative chain segment verification.
+     */
+    pub static ref SPECULATIVE_SEGMENT_VERIFICATION_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_speculative_segment_verification_seconds",
+        "Time spent speculatively verifying chain segment signatures ahead of import"
+    );
+    pub static ref SPECULATIVE_SEGMENT_VERIFICATION_FAILURES: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_speculative_segment_verification_failures_total",
+            "Count of chain segments which failed speculative signature verification"
+        );
+    pub static ref SPECULATIVE_SEGMENT_VERIFICATION_SKIPPED: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_speculative_segment_verification_skipped_total",
+            "Count of chain segments not verified ahead of import as another was still verifying"
+        );
+    pub static ref SPECULATIVE_SEGMENT_CACHE_HITS: Result<IntCounter> = try_create_int_counter(
+        "beacon_speculative_segment_cache_hits_total",
+        "Count of chain segments imported using speculatively verified signatures"
+    );
+    pub static ref SPECULATIVE_SEGMENT_CACHE_MISSES: Result<IntCounter> = try_create_int_counter(
+        "beacon_speculative_segment_cache_misses_total",
+        "Count of speculatively verified chain segments discarded without being used"
+    );
+
+    /*
+     * Proposer equivocation detection
+     */
+    pub static ref PROPOSER_EQUIVOCATIONS_DETECTED: Result<IntCounter> = try_create_int_counter(
+        "beacon_proposer_equivocations_detected_total",
+        "Count of conflicting block headers seen for the same proposer and slot"
+    );
+    pub static ref SLASHING_GUARD_REFUSALS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_slashing_guard_refusals_total",
+        "Count of messages from guarded validators which were not published due to a conflict",
+        &["type"]
+    );
+
     /*
      * Blob sidecar Verification
      */
@@ -1132,6 +1249,28 @@ lazy_static! {
         "Incremented if a validator is not flagged as a previous slot source attester \
         during per slot processing",
     );
+    pub static ref VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_HIT: Result<IntCounter> =
+    try_create_int_counter(
+        VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_HIT_TOTAL,
+        "Incremented if a simulated sync committee message voted for the canonical block root",
+    );
+    pub static ref VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_MISS: Result<IntCounter> =
+    try_create_int_counter(
+        VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_MISS_TOTAL,
+        "Incremented if a simulated sync committee message did not vote for the canonical block \
+        root",
+    );
+    pub static ref VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_HIT: Result<IntCounter> =
+    try_create_int_counter(
+        VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_HIT_TOTAL,
+        "Incremented if a simulated block proposal would have been built on the canonical chain",
+    );
+    pub static ref VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_MISS: Result<IntCounter> =
+    try_create_int_counter(
+        VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_MISS_TOTAL,
+        "Incremented if a simulated block proposal would not have been built on the canonical \
+        chain",
+    );
     /*
      * Missed block metrics
      */
@@ -1273,6 +1412,53 @@ pub fn scrape_for_metrics<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) {
         .scrape_metrics(&beacon_chain.slot_clock, &beacon_chain.spec);
 }
 
+/// Returns the names of the metrics which `scrape_for_metrics` sets from the state of the chain.
+pub fn chain_metric_names() -> Vec<String> {
+    [
+        &*HEAD_STATE_SLOT,
+        &*HEAD_STATE_SLOT_INTEROP,
+        &*HEAD_STATE_ROOT,
+        &*HEAD_STATE_LATEST_BLOCK_SLOT,
+        &*HEAD_STATE_CURRENT_JUSTIFIED_ROOT,
+        &*HEAD_STATE_CURRENT_JUSTIFIED_EPOCH,
+        &*HEAD_STATE_CURRENT_JUSTIFIED_EPOCH_INTEROP,
+        &*HEAD_STATE_PREVIOUS_JUSTIFIED_ROOT,
+        &*HEAD_STATE_PREVIOUS_JUSTIFIED_EPOCH,
+        &*HEAD_STATE_PREVIOUS_JUSTIFIED_EPOCH_INTEROP,
+        &*HEAD_STATE_FINALIZED_ROOT,
+        &*HEAD_STATE_FINALIZED_EPOCH,
+        &*HEAD_STATE_FINALIZED_EPOCH_INTEROP,
+        &*HEAD_STATE_TOTAL_VALIDATORS,
+        &*HEAD_STATE_ACTIVE_VALIDATORS,
+        &*HEAD_STATE_ACTIVE_VALIDATORS_INTEROP,
+        &*HEAD_STATE_VALIDATOR_BALANCES,
+        &*HEAD_STATE_SLASHED_VALIDATORS,
+        &*HEAD_STATE_WITHDRAWN_VALIDATORS,
+        &*HEAD_STATE_ETH1_DEPOSIT_INDEX,
+        &*HEAD_STATE_ETH1_DEPOSITS_INTEROP,
+        &*ATTN_OBSERVATION_PREV_EPOCH_ATTESTERS,
+        &*ATTN_OBSERVATION_PREV_EPOCH_AGGREGATORS,
+        &*SYNC_COMM_OBSERVATION_PREV_SLOT_SIGNERS,
+        &*SYNC_COMM_OBSERVATION_PREV_SLOT_AGGREGATORS,
+        &*BLOCK_PROCESSING_SNAPSHOT_CACHE_SIZE,
+        &*BEACON_REQRESP_PRE_IMPORT_CACHE_SIZE,
+        &*DATA_AVAILABILITY_OVERFLOW_MEMORY_BLOCK_CACHE_SIZE,
+        &*DATA_AVAILABILITY_OVERFLOW_MEMORY_STATE_CACHE_SIZE,
+        &*PRE_FINALIZATION_BLOCK_CACHE_SIZE,
+        &*PRE_FINALIZATION_BLOCK_LOOKUP_COUNT,
+        &*OP_POOL_NUM_ATTESTATIONS,
+        &*OP_POOL_NUM_ATTESTATION_DATA,
+        &*OP_POOL_MAX_AGGREGATES_PER_DATA,
+        &*OP_POOL_NUM_ATTESTER_SLASHINGS,
+        &*OP_POOL_NUM_PROPOSER_SLASHINGS,
+        &*OP_POOL_NUM_VOLUNTARY_EXITS,
+        &*OP_POOL_NUM_SYNC_CONTRIBUTIONS,
+    ]
+    .into_iter()
+    .filter_map(metric_name)
+    .collect()
+}
+
 /// Scrape the given `state` assuming it's the head state, updating the `DEFAULT_REGISTRY`.
 fn scrape_head_state<E: EthSpec>(state: &BeaconState<E>, state_root: Hash256) {
     set_gauge_by_slot(&HEAD_STATE_SLOT, state.slot());
@@ -1,6 +1,7 @@
 use crate::beacon_chain::BEACON_CHAIN_DB_KEY;
 use crate::errors::BeaconChainError;
 use crate::head_tracker::{HeadTracker, SszHeadTracker};
+use crate::metrics;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use parking_lot::Mutex;
 use slog::{debug, error, info, warn, Logger};
@@ -9,8 +10,10 @@ use std::mem;
 use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::compaction::CompactionTrigger;
 use store::hot_cold_store::{migrate_database, HotColdDBError};
 use store::iter::RootsIterator;
+use store::metadata::PendingForkPrune;
 use store::{Error, ItemStore, StoreItem, StoreOp};
 pub use store::{HotColdDB, MemoryStore};
 use types::{
@@ -18,8 +21,6 @@ use types::{
     SignedBeaconBlockHash, Slot,
 };
 
-/// Compact at least this frequently, finalization permitting (7 days).
-const MAX_COMPACTION_PERIOD_SECONDS: u64 = 604800;
 /// Compact at *most* this frequently, to prevent over-compaction during sync (2 hours).
 const MIN_COMPACTION_PERIOD_SECONDS: u64 = 7200;
 /// Compact after a large finality gap, if we respect `MIN_COMPACTION_PERIOD_SECONDS`.
@@ -668,22 +669,53 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             head_tracker_lock.remove(&head_hash);
         }
 
-        let mut batch: Vec<StoreOp<E>> = abandoned_blocks
+        // The blocks of abandoned forks may be retained for some epochs after finalization, e.g.
+        // for debugging. Their states are always pruned, as they can be regenerated.
+        let prune_forks_after_epochs = store.get_config().prune_forks_after_epochs;
+        let mut pending_fork_prunes = store.load_pending_fork_prunes()?;
+        let mut blocks_to_prune: Vec<Hash256> = vec![];
+        pending_fork_prunes.pending.retain(|pending| {
+            let due = pending.abandoned_epoch + prune_forks_after_epochs
+                <= new_finalized_checkpoint.epoch;
+            if due {
+                blocks_to_prune.extend_from_slice(&pending.block_roots);
+            }
+            !due
+        });
+        if prune_forks_after_epochs == 0 {
+            blocks_to_prune.extend(abandoned_blocks.into_iter().map(Into::into));
+        } else if !abandoned_blocks.is_empty() {
+            pending_fork_prunes.pending.push(PendingForkPrune {
+                abandoned_epoch: new_finalized_checkpoint.epoch,
+                block_roots: abandoned_blocks.into_iter().map(Into::into).collect(),
+            });
+        }
+        let states_to_prune = abandoned_states
             .into_iter()
-            .map(Into::into)
-            .flat_map(|block_root: Hash256| {
+            .map(|(slot, state_hash)| (state_hash.into(), slot))
+            .collect::<Vec<(Hash256, Slot)>>();
+
+        // Measure the space to be reclaimed before the data is deleted.
+        let reclaimed_bytes = store.stored_size(&blocks_to_prune, &states_to_prune)?;
+
+        let mut batch: Vec<StoreOp<E>> = blocks_to_prune
+            .iter()
+            .flat_map(|block_root| {
                 [
-                    StoreOp::DeleteBlock(block_root),
-                    StoreOp::DeleteExecutionPayload(block_root),
-                    StoreOp::DeleteBlobs(block_root),
+                    StoreOp::DeleteBlock(*block_root),
+                    StoreOp::DeleteExecutionPayload(*block_root),
+                    StoreOp::DeleteBlobs(*block_root),
                 ]
             })
             .chain(
-                abandoned_states
-                    .into_iter()
-                    .map(|(slot, state_hash)| StoreOp::DeleteState(state_hash.into(), Some(slot))),
+                states_to_prune
+                    .iter()
+                    .map(|(state_root, slot)| StoreOp::DeleteState(*state_root, Some(*slot))),
             )
             .collect();
+        batch.push(StoreOp::KeyValueOp(
+            store.pending_fork_prunes_store_op(&pending_fork_prunes),
+        ));
 
         // Persist the head in case the process is killed or crashes here. This prevents
         // the head tracker reverting after our mutation above.
@@ -704,6 +736,36 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
 
         store.do_atomically_with_block_and_blobs_cache(batch)?;
 
+        metrics::inc_counter_by(
+            &metrics::FORK_PRUNING_BLOCKS_TOTAL,
+            blocks_to_prune.len() as u64,
+        );
+        metrics::inc_counter_by(
+            &metrics::FORK_PRUNING_STATES_TOTAL,
+            states_to_prune.len() as u64,
+        );
+        metrics::inc_counter_by(
+            &metrics::FORK_PRUNING_RECLAIMED_BYTES_TOTAL,
+            reclaimed_bytes,
+        );
+        metrics::set_gauge(
+            &metrics::FORK_PRUNING_RETAINED_BLOCKS,
+            pending_fork_prunes
+                .pending
+                .iter()
+                .map(|pending| pending.block_roots.len())
+                .sum::<usize>() as i64,
+        );
+        if !blocks_to_prune.is_empty() || !states_to_prune.is_empty() {
+            debug!(
+                log,
+                "Pruned abandoned forks";
+                "blocks" => blocks_to_prune.len(),
+                "states" => states_to_prune.len(),
+                "reclaimed_bytes" => reclaimed_bytes,
+            );
+        }
+
         // Do a quick separate pass to delete obsoleted hot states, usually pre-states from the state
         // advance which are not canonical due to blocks being applied on top.
         store.prune_old_hot_states()?;
@@ -715,8 +777,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         })
     }
 
-    /// Compact the database if it has been more than `COMPACTION_PERIOD_SECONDS` since it
-    /// was last compacted.
+    /// Start a background compaction of the database if it has been more than the configured
+    /// compaction period since it was last compacted, or if a large number of epochs were just
+    /// pruned.
     pub fn run_compaction(
         db: Arc<HotColdDB<E, Hot, Cold>>,
         old_finalized_epoch: Epoch,
@@ -738,25 +801,55 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             .as_ref()
             .map_or(0, Duration::as_secs);
 
-        if seconds_since_last_compaction > MAX_COMPACTION_PERIOD_SECONDS
-            || (new_finalized_epoch - old_finalized_epoch > COMPACTION_FINALITY_DISTANCE
-                && seconds_since_last_compaction > MIN_COMPACTION_PERIOD_SECONDS)
+        let trigger = if seconds_since_last_compaction > db.get_config().compaction_period.as_secs()
         {
-            info!(
-                log,
-                "Starting database compaction";
-                "old_finalized_epoch" => old_finalized_epoch,
-                "new_finalized_epoch" => new_finalized_epoch,
-            );
-            db.compact()?;
+            CompactionTrigger::Schedule
+        } else if new_finalized_epoch - old_finalized_epoch > COMPACTION_FINALITY_DISTANCE
+            && seconds_since_last_compaction > MIN_COMPACTION_PERIOD_SECONDS
+        {
+            CompactionTrigger::Prune
+        } else {
+            return Ok(());
+        };
+
+        debug!(
+            log,
+            "Database compaction due";
+            "trigger" => trigger.as_str(),
+            "old_finalized_epoch" => old_finalized_epoch,
+            "new_finalized_epoch" => new_finalized_epoch,
+        );
+        Self::spawn_compaction(db, trigger, log);
+        Ok(())
+    }
 
-            let finish_time = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or(start_time);
-            db.store_compaction_timestamp(finish_time)?;
+    /// Request a compaction of the database, regardless of when it was last compacted.
+    pub fn process_compaction(&self) {
+        Self::spawn_compaction(self.db.clone(), CompactionTrigger::Manual, &self.log);
+    }
 
-            info!(log, "Database compaction complete");
+    /// Compact the database on a new thread, so that the compaction does not delay migrations.
+    ///
+    /// Does nothing if a compaction is already in progress.
+    fn spawn_compaction(
+        db: Arc<HotColdDB<E, Hot, Cold>>,
+        trigger: CompactionTrigger,
+        log: &Logger,
+    ) {
+        if db.compaction_progress().in_progress {
+            debug!(log, "Database compaction already in progress");
+            return;
+        }
+        let thread_log = log.clone();
+        let result = thread::Builder::new()
+            .name("compaction".to_string())
+            .spawn(move || {
+                if let Err(e) = db.compact_throttled(trigger) {
+                    warn!(thread_log, "Database compaction failed"; "error" => ?e);
+                }
+            });
+        if let Err(e) = result {
+            warn!(log, "Unable to spawn compaction thread"; "error" => ?e);
         }
-        Ok(())
     }
 }
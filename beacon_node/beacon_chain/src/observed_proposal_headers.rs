@@ -0,0 +1,260 @@
+//! Provides the `ObservedProposalHeaders` struct which records the signed block headers seen via
+//! gossip or RPC, so that proposer equivocations can be turned into `ProposerSlashing`s without
+//! running the full slasher.
+
+use crate::observed_block_producers::{Error, ProposalKey};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use types::{EthSpec, ProposerSlashing, SignedBeaconBlockHeader, Slot, Unsigned};
+
+/// The number of epochs prior to the highest observed header for which headers are retained.
+pub const EPOCHS_RETAINED: u64 = 4;
+
+/// The maximum number of headers retained, regardless of their slots.
+pub const MAX_OBSERVED_HEADERS: usize = 8_192;
+
+/// Maintains a cache of the first signed header observed for each `(block.slot, block.proposer)`.
+///
+/// The cache supports pruning based upon the finalized epoch, which is not automatic: you must call
+/// `Self::prune` manually. Since finalization may stall, the cache also bounds itself as headers are
+/// observed, dropping headers more than `EPOCHS_RETAINED` epochs older than the highest observed
+/// header and the headers of the earliest slots once there are more than `MAX_OBSERVED_HEADERS`.
+///
+/// Only signature-verified headers may be supplied to this cache, otherwise it could be used to
+/// construct invalid slashings.
+pub struct ObservedProposalHeaders<E: EthSpec> {
+    finalized_slot: Slot,
+    highest_slot: Slot,
+    items: HashMap<ProposalKey, SignedBeaconBlockHeader>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Default for ObservedProposalHeaders<E> {
+    /// Instantiates `Self` with `finalized_slot == 0`.
+    fn default() -> Self {
+        Self {
+            finalized_slot: Slot::new(0),
+            highest_slot: Slot::new(0),
+            items: HashMap::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E: EthSpec> ObservedProposalHeaders<E> {
+    /// Observe the signed `header`, returning a `ProposerSlashing` if a different header has
+    /// already been observed for the same proposer and slot.
+    ///
+    /// Headers more than `EPOCHS_RETAINED` epochs older than the highest observed header are
+    /// ignored, so equivocations that old are not detected.
+    ///
+    /// The supplied `header` **MUST** be signature verified (see struct-level documentation).
+    ///
+    /// ## Errors
+    ///
+    /// - `header.proposer_index` is greater than `VALIDATOR_REGISTRY_LIMIT`.
+    /// - `header.slot` is equal to or less than the latest pruned `finalized_slot`.
+    pub fn observe_header(
+        &mut self,
+        header: &SignedBeaconBlockHeader,
+    ) -> Result<Option<ProposerSlashing>, Error> {
+        let slot = header.message.slot;
+        let proposer_index = header.message.proposer_index;
+
+        if proposer_index >= E::ValidatorRegistryLimit::to_u64() {
+            return Err(Error::ValidatorIndexTooHigh(proposer_index));
+        }
+
+        let finalized_slot = self.finalized_slot;
+        if finalized_slot > 0 && slot <= finalized_slot {
+            return Err(Error::FinalizedBlock {
+                slot,
+                finalized_slot,
+            });
+        }
+
+        if slot < self.lowest_retained_slot() {
+            return Ok(None);
+        }
+
+        let slashing = match self.items.entry(ProposalKey::new(proposer_index, slot)) {
+            Entry::Occupied(occupied_entry) => {
+                let existing = occupied_entry.get();
+                if existing.message == header.message {
+                    Ok(None)
+                } else {
+                    Ok(Some(ProposerSlashing {
+                        signed_header_1: existing.clone(),
+                        signed_header_2: header.clone(),
+                    }))
+                }
+            }
+            Entry::Vacant(vacant_entry) => {
+                vacant_entry.insert(header.clone());
+                Ok(None)
+            }
+        };
+
+        if slot > self.highest_slot {
+            self.highest_slot = slot;
+            let lowest_retained_slot = self.lowest_retained_slot();
+            self.items.retain(|key, _| key.slot >= lowest_retained_slot);
+        }
+        self.prune_excess();
+
+        slashing
+    }
+
+    /// Returns the earliest slot for which headers are retained, relative to the highest observed
+    /// header.
+    fn lowest_retained_slot(&self) -> Slot {
+        self.highest_slot
+            .saturating_sub(EPOCHS_RETAINED.saturating_mul(E::slots_per_epoch()))
+    }
+
+    /// Removes the headers of the earliest slots until at most `MAX_OBSERVED_HEADERS` remain.
+    fn prune_excess(&mut self) {
+        while self.items.len() > MAX_OBSERVED_HEADERS {
+            let Some(earliest_slot) = self.items.keys().map(|key| key.slot).min() else {
+                return;
+            };
+            self.items.retain(|key, _| key.slot > earliest_slot);
+        }
+    }
+
+    /// Removes all observations of blocks equal to or earlier than `finalized_slot`.
+    ///
+    /// Stores `finalized_slot` in `self`, so that `self` will reject any block that has a slot
+    /// equal to or less than `finalized_slot`.
+    ///
+    /// No-op if `finalized_slot == 0`.
+    pub fn prune(&mut self, finalized_slot: Slot) {
+        if finalized_slot == 0 {
+            return;
+        }
+
+        self.finalized_slot = finalized_slot;
+        self.items.retain(|key, _| key.slot > finalized_slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlockHeader, Hash256, MainnetEthSpec, Signature};
+
+    type E = MainnetEthSpec;
+
+    fn get_header(slot: u64, proposer: u64, state_root: Hash256) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: slot.into(),
+                proposer_index: proposer,
+                parent_root: Hash256::zero(),
+                state_root,
+                body_root: Hash256::zero(),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    #[test]
+    fn detects_equivocation() {
+        let mut cache = ObservedProposalHeaders::<E>::default();
+
+        let header_a = get_header(1, 0, Hash256::repeat_byte(1));
+        let header_b = get_header(1, 0, Hash256::repeat_byte(2));
+
+        assert_eq!(cache.observe_header(&header_a), Ok(None), "first header");
+        assert_eq!(cache.observe_header(&header_a), Ok(None), "repeat header");
+        assert_eq!(
+            cache.observe_header(&get_header(1, 1, Hash256::repeat_byte(2))),
+            Ok(None),
+            "other proposer"
+        );
+        assert_eq!(
+            cache.observe_header(&get_header(2, 0, Hash256::repeat_byte(2))),
+            Ok(None),
+            "other slot"
+        );
+        assert_eq!(
+            cache.observe_header(&header_b),
+            Ok(Some(ProposerSlashing {
+                signed_header_1: header_a.clone(),
+                signed_header_2: header_b,
+            })),
+            "equivocation"
+        );
+    }
+
+    #[test]
+    fn pruning() {
+        let mut cache = ObservedProposalHeaders::<E>::default();
+
+        let header_a = get_header(1, 0, Hash256::repeat_byte(1));
+        let header_b = get_header(2, 0, Hash256::repeat_byte(1));
+        assert_eq!(cache.observe_header(&header_a), Ok(None));
+        assert_eq!(cache.observe_header(&header_b), Ok(None));
+
+        cache.prune(Slot::new(1));
+        assert_eq!(cache.items.len(), 1, "only slot 2 should remain");
+        assert_eq!(
+            cache.observe_header(&header_a),
+            Err(Error::FinalizedBlock {
+                slot: Slot::new(1),
+                finalized_slot: Slot::new(1),
+            }),
+            "cannot observe finalized header"
+        );
+    }
+
+    #[test]
+    fn pruning_by_slot_window() {
+        let mut cache = ObservedProposalHeaders::<E>::default();
+        let window = EPOCHS_RETAINED * E::slots_per_epoch();
+
+        let header_a = get_header(1, 0, Hash256::repeat_byte(1));
+        assert_eq!(cache.observe_header(&header_a), Ok(None));
+        assert_eq!(
+            cache.observe_header(&get_header(1 + window, 1, Hash256::repeat_byte(1))),
+            Ok(None)
+        );
+        assert_eq!(cache.items.len(), 2, "slot 1 is still within the window");
+
+        assert_eq!(
+            cache.observe_header(&get_header(2 + window, 2, Hash256::repeat_byte(1))),
+            Ok(None)
+        );
+        assert_eq!(cache.items.len(), 2, "slot 1 should have been pruned");
+        assert_eq!(
+            cache.observe_header(&get_header(1, 0, Hash256::repeat_byte(2))),
+            Ok(None),
+            "headers older than the window are ignored"
+        );
+        assert_eq!(cache.items.len(), 2, "old headers are not retained");
+    }
+
+    #[test]
+    fn pruning_by_max_entries() {
+        let mut cache = ObservedProposalHeaders::<E>::default();
+        let headers_per_slot = 256;
+
+        for i in 0..=MAX_OBSERVED_HEADERS as u64 {
+            let header = get_header(1 + i / headers_per_slot, i, Hash256::repeat_byte(1));
+            assert_eq!(cache.observe_header(&header), Ok(None));
+            assert!(cache.items.len() <= MAX_OBSERVED_HEADERS);
+        }
+
+        // The headers of the earliest slot are dropped first.
+        assert_eq!(
+            cache.items.len(),
+            MAX_OBSERVED_HEADERS + 1 - headers_per_slot as usize
+        );
+        assert!(cache.items.keys().all(|key| key.slot > 1));
+        assert!(cache.items.contains_key(&ProposalKey::new(
+            MAX_OBSERVED_HEADERS as u64,
+            Slot::new(33)
+        )));
+    }
+}
@@ -0,0 +1,34 @@
+use crate::validator_monitor::AttestationEffectiveness;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::PublicKeyBytes;
+
+/// The attestation effectiveness history of a single validator.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedValidatorEffectiveness {
+    pub pubkey: PublicKeyBytes,
+    /// Ordered from oldest to newest epoch.
+    pub history: Vec<AttestationEffectiveness>,
+}
+
+/// The attestation effectiveness recorded by the validator monitor, persisted so that the history
+/// survives a restart of the beacon node.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedAttestationEffectiveness {
+    pub validators: Vec<PersistedValidatorEffectiveness>,
+}
+
+impl StoreItem for PersistedAttestationEffectiveness {
+    fn db_column() -> DBColumn {
+        DBColumn::AttestationEffectiveness
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
@@ -0,0 +1,27 @@
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::Epoch;
+
+/// The validator indices of the operations queued for re-broadcast at a fork, persisted so that
+/// they are still re-broadcast if the beacon node is restarted across the fork.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedForkRebroadcast {
+    pub fork_epoch: Epoch,
+    pub voluntary_exits: Vec<u64>,
+    pub bls_to_execution_changes: Vec<u64>,
+}
+
+impl StoreItem for PersistedForkRebroadcast {
+    fn db_column() -> DBColumn {
+        DBColumn::ForkRebroadcast
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
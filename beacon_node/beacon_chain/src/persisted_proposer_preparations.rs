@@ -0,0 +1,95 @@
+use eth2::lighthouse::CachedValidatorRegistration;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::{Epoch, ProposerPreparationData, SignedValidatorRegistrationData};
+
+/// The proposer preparations and validator registrations supplied by validator clients, persisted
+/// so that a restarted beacon node can propose before the validator clients re-send them.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedProposerPreparations {
+    pub preparations: Vec<PersistedProposerPreparation>,
+    pub registrations: Vec<PersistedValidatorRegistration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedProposerPreparation {
+    pub update_epoch: Epoch,
+    pub preparation_data: ProposerPreparationData,
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedValidatorRegistration {
+    pub registration: SignedValidatorRegistrationData,
+    pub last_seen_epoch: Epoch,
+    pub last_sent_epoch: Option<Epoch>,
+}
+
+impl PersistedProposerPreparations {
+    pub fn new(
+        preparations: Vec<(Epoch, ProposerPreparationData)>,
+        registrations: Vec<CachedValidatorRegistration>,
+    ) -> Self {
+        Self {
+            preparations: preparations
+                .into_iter()
+                .map(
+                    |(update_epoch, preparation_data)| PersistedProposerPreparation {
+                        update_epoch,
+                        preparation_data,
+                    },
+                )
+                .collect(),
+            registrations: registrations
+                .into_iter()
+                .map(|entry| PersistedValidatorRegistration {
+                    registration: entry.registration,
+                    last_seen_epoch: entry.last_seen_epoch,
+                    last_sent_epoch: entry.last_sent_epoch,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the preparations and registrations which were last updated at or after
+    /// `retain_epoch`, in the form used by the `ExecutionLayer`.
+    pub fn into_parts(
+        self,
+        retain_epoch: Epoch,
+    ) -> (
+        Vec<(Epoch, ProposerPreparationData)>,
+        Vec<CachedValidatorRegistration>,
+    ) {
+        let preparations = self
+            .preparations
+            .into_iter()
+            .filter(|preparation| preparation.update_epoch >= retain_epoch)
+            .map(|preparation| (preparation.update_epoch, preparation.preparation_data))
+            .collect();
+        let registrations = self
+            .registrations
+            .into_iter()
+            .filter(|entry| entry.last_seen_epoch >= retain_epoch)
+            .map(|entry| CachedValidatorRegistration {
+                registration: entry.registration,
+                last_seen_epoch: entry.last_seen_epoch,
+                last_sent_epoch: entry.last_sent_epoch,
+            })
+            .collect();
+        (preparations, registrations)
+    }
+}
+
+impl StoreItem for PersistedProposerPreparations {
+    fn db_column() -> DBColumn {
+        DBColumn::ProposerPreparations
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
@@ -0,0 +1,42 @@
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::{beacon_state::CommitteeCache, AttestationShufflingId, Epoch, Fork, Hash256};
+
+/// The proposers of an epoch, as stored in the `BeaconProposerCache`.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedEpochProposers {
+    pub epoch: Epoch,
+    pub shuffling_decision_block: Hash256,
+    pub fork: Fork,
+    pub proposers: Vec<u64>,
+}
+
+/// A committee cache, as stored in the `ShufflingCache`.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedCommitteeCache {
+    pub shuffling_id: AttestationShufflingId,
+    pub committee_cache: CommitteeCache,
+}
+
+/// The proposer and committee shufflings of the current and next epochs, persisted so that a
+/// restarted beacon node does not need to recompute them before it can propose or attest.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedShufflingCaches {
+    pub proposers: Vec<PersistedEpochProposers>,
+    pub committees: Vec<PersistedCommitteeCache>,
+}
+
+impl StoreItem for PersistedShufflingCaches {
+    fn db_column() -> DBColumn {
+        DBColumn::ShufflingCaches
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
@@ -0,0 +1,26 @@
+use eth2::lighthouse::SlotTimeliness;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+
+/// The arrival time history of the timeliness tracker, persisted so that the history survives a
+/// restart of the beacon node.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedTimeliness {
+    /// Ordered from oldest to newest slot.
+    pub slots: Vec<SlotTimeliness>,
+}
+
+impl StoreItem for PersistedTimeliness {
+    fn db_column() -> DBColumn {
+        DBColumn::ArrivalTimeliness
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
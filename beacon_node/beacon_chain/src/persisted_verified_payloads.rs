@@ -0,0 +1,26 @@
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::ExecutionBlockHash;
+
+/// The block hashes of the execution payloads verified as valid by the execution engine, persisted
+/// so that a restarted beacon node does not need to send them to the execution engine again.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedVerifiedPayloads {
+    /// Ordered from least to most recently used.
+    pub block_hashes: Vec<ExecutionBlockHash>,
+}
+
+impl StoreItem for PersistedVerifiedPayloads {
+    fn db_column() -> DBColumn {
+        DBColumn::VerifiedPayloads
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
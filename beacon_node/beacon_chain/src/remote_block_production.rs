@@ -0,0 +1,160 @@
+//! Verification of blocks produced by a remote beacon node.
+//!
+//! Block production can be delegated to another beacon node with `--block-production-endpoint`,
+//! so that the heavy work of building blocks is kept off lightweight attestation nodes. The block
+//! returned by the remote is only proposed if it builds on the same parent that this node would
+//! have built on, and is valid on top of it.
+use crate::{
+    kzg_utils, BeaconBlockResponse, BeaconChain, BeaconChainTypes, BlockProductionError,
+    ProduceBlockVerification,
+};
+use slog::debug;
+use state_processing::{
+    per_block_processing, state_advance::complete_state_advance, BlockSignatureStrategy,
+    ConsensusContext, VerifyBlockRoot,
+};
+use std::sync::Arc;
+use types::{
+    BeaconBlock, BlobsList, EthSpec, FullPayload, Hash256, KzgProofs, RelativeEpoch, Signature,
+    SignedBeaconBlock, Uint256,
+};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Verify a block produced by a remote beacon node against the local view of the chain.
+    ///
+    /// The block is processed on top of the state this node would have produced a block on, and
+    /// its state root and blobs are checked. The `execution_payload_value` is reported by the
+    /// remote and can't be verified locally.
+    pub async fn verify_remote_block(
+        self: &Arc<Self>,
+        block: BeaconBlock<T::EthSpec>,
+        blob_items: Option<(KzgProofs<T::EthSpec>, BlobsList<T::EthSpec>)>,
+        execution_payload_value: Uint256,
+        verification: ProduceBlockVerification,
+    ) -> Result<BeaconBlockResponse<T::EthSpec, FullPayload<T::EthSpec>>, BlockProductionError>
+    {
+        let chain = self.clone();
+        self.task_executor
+            .spawn_blocking_handle(
+                move || {
+                    chain.verify_remote_block_blocking(
+                        block,
+                        blob_items,
+                        execution_payload_value,
+                        verification,
+                    )
+                },
+                "verify_remote_block",
+            )
+            .ok_or(BlockProductionError::ShuttingDown)?
+            .await
+            .map_err(BlockProductionError::TokioJoin)?
+    }
+
+    fn verify_remote_block_blocking(
+        self: &Arc<Self>,
+        block: BeaconBlock<T::EthSpec>,
+        blob_items: Option<(KzgProofs<T::EthSpec>, BlobsList<T::EthSpec>)>,
+        execution_payload_value: Uint256,
+        verification: ProduceBlockVerification,
+    ) -> Result<BeaconBlockResponse<T::EthSpec, FullPayload<T::EthSpec>>, BlockProductionError>
+    {
+        let slot = block.slot();
+        let (mut state, state_root_opt) = self.load_state_for_block_production(slot)?;
+        if state.slot() > slot {
+            return Err(BlockProductionError::StateSlotTooHigh {
+                produce_at_slot: slot,
+                state_slot: state.slot(),
+            });
+        }
+        complete_state_advance(&mut state, state_root_opt, slot, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        state.apply_pending_mutations()?;
+
+        let expected_parent_root = *state
+            .get_block_root(slot.saturating_sub(1_u64))
+            .map_err(|_| BlockProductionError::UnableToGetBlockRootFromState)?;
+        if block.parent_root() != expected_parent_root {
+            return Err(BlockProductionError::RemoteBlockMismatch(format!(
+                "parent root {:?} does not match the local parent {:?}",
+                block.parent_root(),
+                expected_parent_root
+            )));
+        }
+
+        let consensus_block_value = self
+            .compute_beacon_block_reward(block.to_ref(), Hash256::zero(), &mut state)
+            .map(|reward| reward.total)
+            .unwrap_or(0);
+
+        let signature_strategy = match verification {
+            ProduceBlockVerification::VerifyRandao => BlockSignatureStrategy::VerifyRandao,
+            ProduceBlockVerification::NoVerification => BlockSignatureStrategy::NoVerification,
+        };
+        // The block is not signed yet, that is the task of a validator client.
+        let block = SignedBeaconBlock::from_block(block, Signature::empty());
+        let mut ctxt = ConsensusContext::new(slot);
+        per_block_processing(
+            &mut state,
+            &block,
+            signature_strategy,
+            VerifyBlockRoot::True,
+            &mut ctxt,
+            &self.spec,
+        )?;
+
+        let state_root = state.update_tree_hash_cache()?;
+        let (block, _) = block.deconstruct();
+        if block.state_root() != state_root {
+            return Err(BlockProductionError::RemoteBlockMismatch(format!(
+                "state root {:?} does not match the local state root {:?}",
+                block.state_root(),
+                state_root
+            )));
+        }
+
+        let expected_kzg_commitments = block.body().blob_kzg_commitments().ok();
+        let num_kzg_commitments = expected_kzg_commitments.map_or(0, |c| c.len());
+        match (&blob_items, expected_kzg_commitments) {
+            (Some((kzg_proofs, blobs)), Some(expected_kzg_commitments)) => {
+                if expected_kzg_commitments.len() != blobs.len() {
+                    return Err(BlockProductionError::MissingKzgCommitment(format!(
+                        "Missing KZG commitment for slot {}. Expected {}, got: {}",
+                        slot,
+                        blobs.len(),
+                        expected_kzg_commitments.len()
+                    )));
+                }
+                let kzg = self
+                    .kzg
+                    .as_ref()
+                    .ok_or(BlockProductionError::TrustedSetupNotInitialized)?;
+                kzg_utils::validate_blobs::<T::EthSpec>(
+                    kzg,
+                    expected_kzg_commitments,
+                    blobs.iter().collect(),
+                    kzg_proofs,
+                )
+                .map_err(BlockProductionError::KzgError)?;
+            }
+            (None, _) if num_kzg_commitments > 0 => return Err(BlockProductionError::MissingBlobs),
+            _ => (),
+        }
+
+        debug!(
+            self.log,
+            "Verified remotely produced block";
+            "slot" => slot,
+            "parent" => ?block.parent_root(),
+            "blobs" => num_kzg_commitments,
+        );
+
+        Ok(BeaconBlockResponse {
+            block,
+            state,
+            blob_items,
+            execution_payload_value,
+            consensus_block_value,
+        })
+    }
+}
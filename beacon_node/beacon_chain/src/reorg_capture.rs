@@ -0,0 +1,236 @@
+//! Capture of forensic bundles when the head is re-orged by more than
+//! `ChainConfig::reorg_capture_depth` slots.
+//!
+//! Each bundle is written as JSON to `ChainConfig::reorg_capture_dir`, named by its id, so that
+//! the details of a re-org are available after the fact without debug logging. Only the most
+//! recent `MAX_REORG_CAPTURES` bundles are kept.
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ReorgCapture, ReorgCaptureBlock, ReorgCaptureSummary};
+use slog::{info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use types::{Hash256, Slot};
+
+/// The maximum number of bundles kept on disk.
+pub const MAX_REORG_CAPTURES: usize = 64;
+
+/// The maximum number of blocks captured from each side of the re-org.
+pub const MAX_REORG_CAPTURE_BLOCKS: usize = 64;
+
+const REORG_CAPTURE_EXTENSION: &str = "json";
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Capture a re-org from `old_head_block` to `new_head_block` in the background, if its
+    /// `depth` exceeds the configured minimum.
+    pub(crate) fn spawn_reorg_capture(
+        self: &Arc<Self>,
+        slot: Slot,
+        depth: u64,
+        old_head_block: Hash256,
+        new_head_block: Hash256,
+    ) {
+        let (Some(min_depth), Some(dir)) = (
+            self.config.reorg_capture_depth,
+            self.config.reorg_capture_dir.clone(),
+        ) else {
+            return;
+        };
+        if depth <= min_depth {
+            return;
+        }
+
+        let summary = ReorgCaptureSummary {
+            id: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_millis() as u64),
+            slot,
+            depth,
+            old_head_block,
+            new_head_block,
+        };
+        let chain = self.clone();
+        self.task_executor.spawn_blocking(
+            move || {
+                let id = summary.id;
+                let capture = chain.capture_reorg(summary);
+                match write_reorg_capture(&dir, &capture) {
+                    Ok(()) => {
+                        metrics::inc_counter(&metrics::REORG_CAPTURES_TOTAL);
+                        info!(
+                            chain.log,
+                            "Captured re-org";
+                            "id" => id,
+                            "depth" => depth,
+                            "dir" => ?dir,
+                        );
+                    }
+                    Err(e) => warn!(chain.log, "Unable to capture re-org"; "error" => e),
+                }
+            },
+            "reorg_capture",
+        );
+    }
+
+    fn capture_reorg(&self, summary: ReorgCaptureSummary) -> ReorgCapture {
+        let fork_choice = self.canonical_head.fork_choice_read_lock();
+        let proto_array = fork_choice.proto_array();
+
+        // Walk back from both heads until the chains meet, or the limit is reached.
+        let ancestors = |head: Hash256| {
+            let mut roots = vec![];
+            let mut root = head;
+            while roots.len() < MAX_REORG_CAPTURE_BLOCKS {
+                let Some(block) = proto_array.get_block(&root) else {
+                    break;
+                };
+                roots.push(root);
+                match block.parent_root {
+                    Some(parent_root) => root = parent_root,
+                    None => break,
+                }
+            }
+            roots
+        };
+        let mut old_roots = ancestors(summary.old_head_block);
+        let mut new_roots = ancestors(summary.new_head_block);
+        let old_set = old_roots.iter().copied().collect::<HashSet<_>>();
+        if let Some(common) = new_roots.iter().position(|root| old_set.contains(root)) {
+            let ancestor = new_roots[common];
+            new_roots.truncate(common);
+            if let Some(common) = old_roots.iter().position(|root| *root == ancestor) {
+                old_roots.truncate(common);
+            }
+        }
+
+        let weights = old_roots
+            .iter()
+            .chain(&new_roots)
+            .map(|root| (*root, proto_array.get_weight(root)))
+            .collect::<Vec<_>>();
+        let justified_checkpoint = fork_choice.justified_checkpoint();
+        let finalized_checkpoint = fork_choice.finalized_checkpoint();
+        let fork_choice_dump = proto_array.core_proto_array().clone();
+        drop(fork_choice);
+
+        let weight_of = |root: &Hash256| {
+            weights
+                .iter()
+                .find(|(weight_root, _)| weight_root == root)
+                .and_then(|(_, weight)| *weight)
+        };
+        let old_chain = old_roots
+            .iter()
+            .map(|root| self.reorg_capture_block(*root, weight_of(root)))
+            .collect();
+        let new_chain = new_roots
+            .iter()
+            .map(|root| self.reorg_capture_block(*root, weight_of(root)))
+            .collect();
+
+        ReorgCapture {
+            summary,
+            justified_checkpoint,
+            finalized_checkpoint,
+            old_chain,
+            new_chain,
+            fork_choice: fork_choice_dump,
+        }
+    }
+
+    fn reorg_capture_block(&self, block_root: Hash256, weight: Option<u64>) -> ReorgCaptureBlock {
+        let block = self.store.get_blinded_block(&block_root).ok().flatten();
+        let delays = block.as_ref().and_then(|block| {
+            let slot_start = self.slot_clock.start_of(block.slot())?;
+            Some(
+                self.block_times_cache
+                    .read()
+                    .get_block_delays(block_root, slot_start),
+            )
+        });
+        let as_millis = |delay: Option<Duration>| delay.map(|delay| delay.as_millis() as u64);
+
+        ReorgCaptureBlock {
+            block_root,
+            header: block.as_ref().map(|block| block.signed_block_header()),
+            attestations: block
+                .as_ref()
+                .map(|block| block.message().body().attestations_len()),
+            weight,
+            observed_delay_ms: as_millis(delays.as_ref().and_then(|d| d.observed)),
+            imported_delay_ms: as_millis(delays.as_ref().and_then(|d| d.imported)),
+            set_as_head_delay_ms: as_millis(delays.as_ref().and_then(|d| d.set_as_head)),
+        }
+    }
+
+    /// Returns the summaries of the captured re-orgs, oldest first.
+    pub fn reorg_captures(&self) -> Result<Vec<ReorgCaptureSummary>, String> {
+        let Some(dir) = &self.config.reorg_capture_dir else {
+            return Ok(vec![]);
+        };
+        reorg_capture_ids(dir)?
+            .into_iter()
+            .map(|id| read_reorg_capture(&capture_path(dir, id)).map(|capture| capture.summary))
+            .collect()
+    }
+
+    /// Returns the captured re-org with the given `id`, if it exists.
+    pub fn reorg_capture(&self, id: u64) -> Result<Option<ReorgCapture>, String> {
+        let Some(dir) = &self.config.reorg_capture_dir else {
+            return Ok(None);
+        };
+        let path = capture_path(dir, id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_reorg_capture(&path).map(Some)
+    }
+}
+
+fn capture_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{id}.{REORG_CAPTURE_EXTENSION}"))
+}
+
+/// Returns the ids of the bundles in `dir`, in ascending order.
+fn reorg_capture_ids(dir: &Path) -> Result<Vec<u64>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("unable to read {}: {e}", dir.display())),
+    };
+    let mut ids = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()?.to_str()? != REORG_CAPTURE_EXTENSION {
+                return None;
+            }
+            path.file_stem()?.to_str()?.parse::<u64>().ok()
+        })
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+fn read_reorg_capture(path: &Path) -> Result<ReorgCapture, String> {
+    let bytes = fs::read(path).map_err(|e| format!("unable to read {}: {e}", path.display()))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("invalid capture {}: {e}", path.display()))
+}
+
+/// Write `capture` to `dir`, and delete the oldest bundles beyond `MAX_REORG_CAPTURES`.
+fn write_reorg_capture(dir: &Path, capture: &ReorgCapture) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("unable to create {}: {e}", dir.display()))?;
+    let path = capture_path(dir, capture.summary.id);
+    let bytes = serde_json::to_vec(capture).map_err(|e| format!("{e:?}"))?;
+    fs::write(&path, bytes).map_err(|e| format!("unable to write {}: {e}", path.display()))?;
+
+    let ids = reorg_capture_ids(dir)?;
+    let excess = ids.len().saturating_sub(MAX_REORG_CAPTURES);
+    for id in &ids[..excess] {
+        let path = capture_path(dir, *id);
+        fs::remove_file(&path).map_err(|e| format!("unable to remove {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
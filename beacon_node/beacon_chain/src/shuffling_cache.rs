@@ -128,6 +128,21 @@ impl ShufflingCache {
         self.cache.contains_key(key)
     }
 
+    /// Returns the committee caches for shufflings of `epoch` or later, ignoring promises.
+    pub fn committee_caches_from_epoch(
+        &self,
+        epoch: Epoch,
+    ) -> impl Iterator<Item = (&AttestationShufflingId, &Arc<CommitteeCache>)> {
+        self.cache
+            .iter()
+            .filter_map(move |(shuffling_id, item)| match item {
+                CacheItem::Committee(committee_cache) if shuffling_id.shuffling_epoch >= epoch => {
+                    Some((shuffling_id, committee_cache))
+                }
+                _ => None,
+            })
+    }
+
     pub fn insert_committee_cache<C: ToArcCommitteeCache>(
         &mut self,
         key: AttestationShufflingId,
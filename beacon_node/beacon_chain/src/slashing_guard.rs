@@ -0,0 +1,335 @@
+//! Provides the `SlashingGuard`, which prevents the beacon node from publishing blocks and
+//! attestations signed by selected validators if they conflict with messages from the same
+//! validators that the node has already observed.
+//!
+//! This is a second line of defence behind the validator client's slashing protection database. It
+//! catches, for example, the same keys being run by two validator clients at once.
+
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use tree_hash::TreeHash;
+use types::{
+    Epoch, EthSpec, Hash256, IndexedAttestationRef, PublicKeyBytes, SignedBeaconBlockHeader, Slot,
+};
+
+/// The reason a message was refused: it is slashable alongside an observed message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+    DoubleVote {
+        validator_index: u64,
+        target_epoch: Epoch,
+        existing_data_root: Hash256,
+        new_data_root: Hash256,
+    },
+    SurroundVote {
+        validator_index: u64,
+        existing: (Epoch, Epoch),
+        new: (Epoch, Epoch),
+    },
+    DoubleProposal {
+        validator_index: u64,
+        slot: Slot,
+        existing_block_root: Hash256,
+        new_block_root: Hash256,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AttestationRecord {
+    source_epoch: Epoch,
+    target_epoch: Epoch,
+    data_root: Hash256,
+}
+
+impl AttestationRecord {
+    /// Returns a `Conflict` if `self` and `existing` are slashable together.
+    fn conflict(&self, existing: &Self, validator_index: u64) -> Option<Conflict> {
+        if self.target_epoch == existing.target_epoch {
+            (self.data_root != existing.data_root).then_some(Conflict::DoubleVote {
+                validator_index,
+                target_epoch: self.target_epoch,
+                existing_data_root: existing.data_root,
+                new_data_root: self.data_root,
+            })
+        } else if (self.source_epoch < existing.source_epoch
+            && existing.target_epoch < self.target_epoch)
+            || (existing.source_epoch < self.source_epoch
+                && self.target_epoch < existing.target_epoch)
+        {
+            Some(Conflict::SurroundVote {
+                validator_index,
+                existing: (existing.source_epoch, existing.target_epoch),
+                new: (self.source_epoch, self.target_epoch),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Records the attestations and block proposals observed for a set of guarded validators.
+///
+/// The cache supports pruning based upon the finalized epoch. It does not automatically prune, you
+/// must call `Self::prune` manually.
+///
+/// Only signature-verified messages may be supplied to this cache, otherwise it could be used to
+/// stop guarded validators from publishing.
+pub struct SlashingGuard<E: EthSpec> {
+    /// The guarded public keys, along with their validator indices once known.
+    pubkeys: HashMap<PublicKeyBytes, Option<u64>>,
+    /// The known validator indices of the guarded public keys.
+    indices: HashSet<u64>,
+    attestations: HashMap<u64, Vec<AttestationRecord>>,
+    proposals: HashMap<(u64, Slot), Hash256>,
+    finalized_epoch: Epoch,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> SlashingGuard<E> {
+    pub fn new(pubkeys: impl IntoIterator<Item = PublicKeyBytes>) -> Self {
+        Self {
+            pubkeys: pubkeys.into_iter().map(|pubkey| (pubkey, None)).collect(),
+            indices: HashSet::new(),
+            attestations: HashMap::new(),
+            proposals: HashMap::new(),
+            finalized_epoch: Epoch::new(0),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the guarded public keys whose validator indices are not yet known.
+    pub fn unresolved_pubkeys(&self) -> Vec<PublicKeyBytes> {
+        self.pubkeys
+            .iter()
+            .filter(|(_, index)| index.is_none())
+            .map(|(pubkey, _)| *pubkey)
+            .collect()
+    }
+
+    /// Record the validator index of a guarded `pubkey`.
+    pub fn register_index(&mut self, pubkey: &PublicKeyBytes, validator_index: u64) {
+        if let Some(index) = self.pubkeys.get_mut(pubkey) {
+            *index = Some(validator_index);
+            self.indices.insert(validator_index);
+        }
+    }
+
+    /// Returns `true` if the validator at `validator_index` is guarded.
+    pub fn is_guarded(&self, validator_index: u64) -> bool {
+        self.indices.contains(&validator_index)
+    }
+
+    /// Record `attestation` for any guarded validators attesting in it.
+    pub fn observe_attestation(&mut self, attestation: IndexedAttestationRef<E>) {
+        let Some(record) = self.attestation_record(attestation) else {
+            return;
+        };
+        for &validator_index in attestation.attesting_indices_iter() {
+            if self.is_guarded(validator_index) {
+                let records = self.attestations.entry(validator_index).or_default();
+                if !records.contains(&record) {
+                    records.push(record);
+                }
+            }
+        }
+    }
+
+    /// Check `attestation` against those observed for any guarded validators attesting in it.
+    pub fn check_attestation(&self, attestation: IndexedAttestationRef<E>) -> Result<(), Conflict> {
+        let Some(record) = self.attestation_record(attestation) else {
+            return Ok(());
+        };
+        for &validator_index in attestation.attesting_indices_iter() {
+            if let Some(conflict) = self
+                .attestations
+                .get(&validator_index)
+                .into_iter()
+                .flatten()
+                .find_map(|existing| record.conflict(existing, validator_index))
+            {
+                return Err(conflict);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `None` if `attestation` is prior to finalization.
+    fn attestation_record(
+        &self,
+        attestation: IndexedAttestationRef<E>,
+    ) -> Option<AttestationRecord> {
+        let data = attestation.data();
+        (data.target.epoch >= self.finalized_epoch).then(|| AttestationRecord {
+            source_epoch: data.source.epoch,
+            target_epoch: data.target.epoch,
+            data_root: data.tree_hash_root(),
+        })
+    }
+
+    /// Record the proposal in `header` if its proposer is guarded.
+    pub fn observe_block_header(&mut self, header: &SignedBeaconBlockHeader) {
+        let validator_index = header.message.proposer_index;
+        let slot = header.message.slot;
+        if self.is_guarded(validator_index) && slot >= self.finalized_slot() {
+            self.proposals
+                .entry((validator_index, slot))
+                .or_insert_with(|| header.message.canonical_root());
+        }
+    }
+
+    /// Check the proposal in `header` against any observed for the same proposer and slot.
+    pub fn check_block_header(&self, header: &SignedBeaconBlockHeader) -> Result<(), Conflict> {
+        let validator_index = header.message.proposer_index;
+        let slot = header.message.slot;
+        let Some(&existing_block_root) = self.proposals.get(&(validator_index, slot)) else {
+            return Ok(());
+        };
+        let new_block_root = header.message.canonical_root();
+        if existing_block_root == new_block_root {
+            Ok(())
+        } else {
+            Err(Conflict::DoubleProposal {
+                validator_index,
+                slot,
+                existing_block_root,
+                new_block_root,
+            })
+        }
+    }
+
+    fn finalized_slot(&self) -> Slot {
+        self.finalized_epoch.start_slot(E::slots_per_epoch())
+    }
+
+    /// Removes all attestations with targets prior to `finalized_epoch`, and all proposals prior
+    /// to its start slot.
+    ///
+    /// Stores `finalized_epoch` in `self`, so that `self` will ignore any such messages.
+    pub fn prune(&mut self, finalized_epoch: Epoch) {
+        self.finalized_epoch = finalized_epoch;
+        let finalized_slot = self.finalized_slot();
+        self.attestations.retain(|_, records| {
+            records.retain(|record| record.target_epoch >= finalized_epoch);
+            !records.is_empty()
+        });
+        self.proposals
+            .retain(|(_, slot), _| *slot >= finalized_slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        AggregateSignature, AttestationData, BeaconBlockHeader, Checkpoint, IndexedAttestation,
+        IndexedAttestationBase, MainnetEthSpec, Signature,
+    };
+
+    type E = MainnetEthSpec;
+
+    const GUARDED: u64 = 3;
+
+    fn guard() -> SlashingGuard<E> {
+        let pubkey = PublicKeyBytes::empty();
+        let mut guard = SlashingGuard::new([pubkey]);
+        guard.register_index(&pubkey, GUARDED);
+        guard
+    }
+
+    fn att(indices: &[u64], source: u64, target: u64, root: u8) -> IndexedAttestation<E> {
+        IndexedAttestation::Base(IndexedAttestationBase {
+            attesting_indices: indices.to_vec().into(),
+            data: AttestationData {
+                slot: Slot::new(0),
+                index: 0,
+                beacon_block_root: Hash256::repeat_byte(root),
+                source: Checkpoint {
+                    epoch: Epoch::new(source),
+                    root: Hash256::zero(),
+                },
+                target: Checkpoint {
+                    epoch: Epoch::new(target),
+                    root: Hash256::zero(),
+                },
+            },
+            signature: AggregateSignature::empty(),
+        })
+    }
+
+    fn header(proposer: u64, slot: u64, root: u8) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: Slot::new(slot),
+                proposer_index: proposer,
+                parent_root: Hash256::zero(),
+                state_root: Hash256::repeat_byte(root),
+                body_root: Hash256::zero(),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    #[test]
+    fn attestation_conflicts() {
+        let mut guard = guard();
+
+        guard.observe_attestation(att(&[GUARDED], 2, 4, 1).to_ref());
+        assert_eq!(
+            guard.check_attestation(att(&[GUARDED], 2, 4, 1).to_ref()),
+            Ok(())
+        );
+        assert_eq!(
+            guard.check_attestation(att(&[GUARDED], 4, 5, 1).to_ref()),
+            Ok(())
+        );
+        assert_eq!(
+            guard.check_attestation(att(&[GUARDED], 3, 4, 1).to_ref()),
+            Ok(())
+        );
+        assert!(matches!(
+            guard.check_attestation(att(&[GUARDED], 2, 4, 2).to_ref()),
+            Err(Conflict::DoubleVote { .. })
+        ));
+        assert!(matches!(
+            guard.check_attestation(att(&[GUARDED], 1, 6, 1).to_ref()),
+            Err(Conflict::SurroundVote { .. })
+        ));
+        assert!(matches!(
+            guard.check_attestation(att(&[1, GUARDED], 3, 4, 3).to_ref()),
+            Err(Conflict::DoubleVote { .. })
+        ));
+        // Unguarded validators are not checked.
+        assert_eq!(guard.check_attestation(att(&[1], 1, 6, 1).to_ref()), Ok(()));
+    }
+
+    #[test]
+    fn proposal_conflicts() {
+        let mut guard = guard();
+
+        guard.observe_block_header(&header(GUARDED, 10, 1));
+        assert_eq!(guard.check_block_header(&header(GUARDED, 10, 1)), Ok(()));
+        assert_eq!(guard.check_block_header(&header(GUARDED, 11, 2)), Ok(()));
+        assert!(matches!(
+            guard.check_block_header(&header(GUARDED, 10, 2)),
+            Err(Conflict::DoubleProposal { .. })
+        ));
+        // Unguarded validators are not recorded.
+        guard.observe_block_header(&header(1, 12, 1));
+        assert_eq!(guard.check_block_header(&header(1, 12, 2)), Ok(()));
+    }
+
+    #[test]
+    fn pruning() {
+        let mut guard = guard();
+
+        guard.observe_attestation(att(&[GUARDED], 0, 1, 1).to_ref());
+        guard.observe_block_header(&header(GUARDED, 1, 1));
+        guard.prune(Epoch::new(2));
+        assert!(guard.attestations.is_empty());
+        assert!(guard.proposals.is_empty());
+
+        // Messages prior to finalization are ignored.
+        guard.observe_attestation(att(&[GUARDED], 0, 1, 1).to_ref());
+        assert!(guard.attestations.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+//! Provides a cache of chain segments whose signatures have been verified *speculatively*, i.e.
+//! before the parent of the segment has been imported.
+//!
+//! During range sync the signature verification of batch `N + 1` can be performed whilst batch
+//! `N` is being imported, since the attester shuffling for an epoch is decided two epochs in
+//! advance. The block hashes of the payloads are checked at the same time when the segment will be
+//! imported without notifying the execution layer. The verified blocks are stored here and picked
+//! up by `process_chain_segment` once the parent has been imported, so that the import of batch
+//! `N + 1` can skip straight to the state transition.
+//!
+//! Only one segment is verified at a time. If verification falls behind import then further
+//! segments are imported without being verified ahead of time, rather than queueing speculative
+//! work for the workers.
+use crate::block_verification::{
+    speculatively_signature_verify_chain_segment, SignatureVerifiedBlock,
+};
+use crate::block_verification_types::{AsBlock, RpcBlock};
+use crate::{metrics, BeaconChain, BeaconChainTypes, BlockError};
+use parking_lot::Mutex;
+use slog::debug;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use types::{Epoch, EthSpec, Hash256};
+
+/// The maximum number of speculatively verified segments to hold at any one time.
+///
+/// Range sync only verifies a single batch ahead of the one being imported, so this only needs to
+/// be large enough to handle a couple of chains being swapped.
+const MAX_SPECULATIVE_SEGMENTS: usize = 2;
+
+/// A chain segment whose signatures were verified against the state of `anchor_root`.
+struct SpeculativeSegment<T: BeaconChainTypes> {
+    anchor_root: Hash256,
+    epoch: Epoch,
+    block_roots: Vec<Hash256>,
+    blocks: Vec<SignatureVerifiedBlock<T>>,
+}
+
+pub struct SpeculativeSegmentCache<T: BeaconChainTypes> {
+    segments: Mutex<VecDeque<SpeculativeSegment<T>>>,
+    /// Set whilst a segment is being verified.
+    verifying: AtomicBool,
+}
+
+impl<T: BeaconChainTypes> Default for SpeculativeSegmentCache<T> {
+    fn default() -> Self {
+        Self {
+            segments: Mutex::new(VecDeque::with_capacity(MAX_SPECULATIVE_SEGMENTS)),
+            verifying: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Marks a segment as being verified until dropped.
+struct VerifyingGuard<'a>(&'a AtomicBool);
+
+impl Drop for VerifyingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<T: BeaconChainTypes> SpeculativeSegmentCache<T> {
+    /// Returns a guard if no other segment is being verified.
+    fn start_verifying(&self) -> Option<VerifyingGuard<'_>> {
+        self.verifying
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| VerifyingGuard(&self.verifying))
+    }
+
+    fn insert(&self, segment: SpeculativeSegment<T>) {
+        let mut segments = self.segments.lock();
+        segments.retain(|existing| existing.block_roots != segment.block_roots);
+        while segments.len() >= MAX_SPECULATIVE_SEGMENTS {
+            segments.pop_front();
+            metrics::inc_counter(&metrics::SPECULATIVE_SEGMENT_CACHE_MISSES);
+        }
+        segments.push_back(segment);
+    }
+
+    fn remove(&self, block_roots: &[Hash256]) -> Option<SpeculativeSegment<T>> {
+        let mut segments = self.segments.lock();
+        let position = segments
+            .iter()
+            .position(|segment| segment.block_roots == block_roots)?;
+        segments.remove(position)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Verify the signatures of `chain_segment` ahead of its import, using the state of the
+    /// already-imported `anchor_root` block.
+    ///
+    /// The segment is skipped if another segment is still being verified. The segment must be
+    /// contained within a single epoch. The result is stored in the
+    /// speculative segment cache and used by `process_chain_segment` if the anchor turns out to
+    /// be the shuffling decision block for the segment. Any failure here is not an indication
+    /// that the segment is invalid, since the segment will be fully verified during import.
+    pub async fn speculatively_verify_chain_segment(
+        self: &Arc<Self>,
+        anchor_root: Hash256,
+        chain_segment: Vec<RpcBlock<T::EthSpec>>,
+    ) -> Result<(), BlockError<T::EthSpec>> {
+        let Some(epoch) = chain_segment.first().map(|block| block.epoch()) else {
+            return Ok(());
+        };
+        if chain_segment.iter().any(|block| block.epoch() != epoch) {
+            debug!(
+                self.log,
+                "Skipping speculative verification of multi-epoch segment";
+                "start_epoch" => epoch,
+            );
+            return Ok(());
+        }
+
+        let Some(_verifying) = self.speculative_segment_cache.start_verifying() else {
+            debug!(
+                self.log,
+                "Skipping speculative verification of segment";
+                "reason" => "previous segment still verifying",
+                "epoch" => epoch,
+            );
+            metrics::inc_counter(&metrics::SPECULATIVE_SEGMENT_VERIFICATION_SKIPPED);
+            return Ok(());
+        };
+
+        let chain_segment = chain_segment
+            .into_iter()
+            .map(|block| (block.block_root(), block))
+            .collect::<Vec<_>>();
+        let block_roots = chain_segment.iter().map(|(root, _)| *root).collect();
+
+        let chain = self.clone();
+        let result = self
+            .spawn_blocking_handle(
+                move || {
+                    let _timer =
+                        metrics::start_timer(&metrics::SPECULATIVE_SEGMENT_VERIFICATION_TIMES);
+                    speculatively_signature_verify_chain_segment(anchor_root, chain_segment, &chain)
+                },
+                "speculatively_verify_chain_segment",
+            )
+            .await?;
+        if result.is_err() {
+            metrics::inc_counter(&metrics::SPECULATIVE_SEGMENT_VERIFICATION_FAILURES);
+        }
+        let blocks = result?;
+
+        self.speculative_segment_cache.insert(SpeculativeSegment {
+            anchor_root,
+            epoch,
+            block_roots,
+            blocks,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the speculatively verified blocks for `chain_segment`, if any.
+    ///
+    /// The blocks are only returned if the parent of the segment is known to fork choice and the
+    /// anchor that was used during verification is the attester shuffling decision block for the
+    /// segment's epoch. This guarantees that the committees used during speculative verification
+    /// are identical to the ones that would have been obtained from the parent state.
+    pub(crate) fn take_speculatively_verified_segment(
+        &self,
+        chain_segment: &[(Hash256, RpcBlock<T::EthSpec>)],
+    ) -> Option<Vec<SignatureVerifiedBlock<T>>> {
+        let (_, first_block) = chain_segment.first()?;
+        let block_roots = chain_segment
+            .iter()
+            .map(|(root, _)| *root)
+            .collect::<Vec<_>>();
+        let segment = self.speculative_segment_cache.remove(&block_roots)?;
+
+        let parent = self
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_block(&first_block.parent_root());
+        let shuffling_id = parent.and_then(|parent| {
+            let parent_epoch = parent.slot.epoch(T::EthSpec::slots_per_epoch());
+            if parent_epoch == segment.epoch {
+                Some(parent.current_epoch_shuffling_id)
+            } else if parent_epoch + 1 == segment.epoch {
+                Some(parent.next_epoch_shuffling_id)
+            } else {
+                None
+            }
+        });
+
+        match shuffling_id {
+            Some(shuffling_id)
+                if shuffling_id.shuffling_epoch == segment.epoch
+                    && shuffling_id.shuffling_decision_block == segment.anchor_root =>
+            {
+                metrics::inc_counter(&metrics::SPECULATIVE_SEGMENT_CACHE_HITS);
+                Some(segment.blocks)
+            }
+            _ => {
+                debug!(
+                    self.log,
+                    "Discarding speculatively verified segment";
+                    "reason" => "shuffling decision block mismatch",
+                    "anchor_root" => ?segment.anchor_root,
+                    "epoch" => segment.epoch,
+                );
+                metrics::inc_counter(&metrics::SPECULATIVE_SEGMENT_CACHE_MISSES);
+                None
+            }
+        }
+    }
+}
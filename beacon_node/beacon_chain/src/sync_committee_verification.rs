@@ -17,6 +17,9 @@
 //! ```ignore
 //!      types::SyncCommitteeMessage      types::SignedContributionAndProof
 //!              |                                    |
+//!              ▼                                    |
+//!  PartiallyVerifiedSyncCommitteeMessage            |
+//!              |                                    |
 //!              ▼                                    ▼
 //!      VerifiedSyncCommitteeMessage               VerifiedSyncContribution
 //!              |                                    |
@@ -27,10 +30,11 @@
 //! ```
 
 use crate::observed_attesters::SlotSubcommitteeIndex;
+use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::{
     metrics, observed_aggregates::ObserveOutcome, BeaconChain, BeaconChainError, BeaconChainTypes,
 };
-use bls::{verify_signature_sets, PublicKeyBytes};
+use bls::{verify_signature_sets, PublicKeyBytes, SignatureSet};
 use derivative::Derivative;
 use safe_arith::ArithError;
 use slot_clock::SlotClock;
@@ -276,6 +280,24 @@ pub struct SyncCommitteeData {
     pub subcommittee_index: u64,
 }
 
+/// Wraps a `SyncCommitteeMessage` that has passed all gossip checks other than signature
+/// verification.
+pub struct PartiallyVerifiedSyncCommitteeMessage {
+    sync_message: SyncCommitteeMessage,
+    subnet_id: SyncSubnetId,
+    pubkey: PublicKeyBytes,
+    subnet_positions: HashMap<SyncSubnetId, Vec<usize>>,
+    head_root: Hash256,
+    signature: AggregateSignature,
+}
+
+/// Used to avoid double-checking signatures.
+#[derive(Copy, Clone)]
+pub(crate) enum CheckSyncCommitteeMessageSignature {
+    Yes,
+    No,
+}
+
 /// Wraps a `SyncCommitteeMessage` that has been verified for propagation on the gossip network.
 #[derive(Clone)]
 pub struct VerifiedSyncCommitteeMessage {
@@ -443,9 +465,9 @@ impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
     }
 }
 
-impl VerifiedSyncCommitteeMessage {
-    /// Returns `Ok(Self)` if the `sync_message` is valid to be (re)published on the gossip
-    /// network.
+impl PartiallyVerifiedSyncCommitteeMessage {
+    /// Returns `Ok(Self)` if the `sync_message` passes all gossip checks other than signature
+    /// verification.
     ///
     /// `subnet_id` is the subnet from which we received this sync message. This function will
     /// verify that it was received on the correct subnet.
@@ -482,21 +504,6 @@ impl VerifiedSyncCommitteeMessage {
         let validator_index = sync_message.validator_index;
         let head_root = chain.canonical_head.cached_head().head_block_root();
         let new_root = sync_message.beacon_block_root;
-        let should_override_prev = |prev_root: &Hash256, new_root: &Hash256| {
-            let roots_differ = new_root != prev_root;
-            let new_elects_head = new_root == &head_root;
-
-            if roots_differ {
-                // Track sync committee messages that differ from each other.
-                metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS);
-                if new_elects_head {
-                    // Track sync committee messages that swap from an old block to a new block.
-                    metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS_TO_HEAD);
-                }
-            }
-
-            roots_differ && new_elects_head
-        };
         if let Some(prev_root) = chain
             .observed_sync_contributors
             .read()
@@ -506,7 +513,7 @@ impl VerifiedSyncCommitteeMessage {
             )
             .map_err(BeaconChainError::from)?
         {
-            if !should_override_prev(&prev_root, &new_root) {
+            if !should_override_prev(&prev_root, &new_root, &head_root) {
                 return Err(Error::PriorSyncCommitteeMessageKnown {
                     validator_index,
                     slot: sync_message.slot,
@@ -516,8 +523,80 @@ impl VerifiedSyncCommitteeMessage {
             }
         }
 
+        Ok(Self {
+            signature: AggregateSignature::from(&sync_message.signature),
+            sync_message,
+            subnet_id,
+            pubkey,
+            subnet_positions,
+            head_root,
+        })
+    }
+
+    /// Returns the signature set of the sync committee message, so that it may be verified in a
+    /// batch with other signatures.
+    pub(crate) fn signature_set<'a, T: BeaconChainTypes>(
+        &'a self,
+        pubkey_cache: &'a ValidatorPubkeyCache<T>,
+        chain: &'a BeaconChain<T>,
+    ) -> Result<SignatureSet<'a>, Error> {
+        sync_committee_message_signature_set(
+            chain,
+            pubkey_cache,
+            &self.sync_message,
+            &self.signature,
+            &self.pubkey,
+        )
+    }
+
+    /// Returns the wrapped `SyncCommitteeMessage`.
+    pub fn sync_message(&self) -> &SyncCommitteeMessage {
+        &self.sync_message
+    }
+}
+
+impl VerifiedSyncCommitteeMessage {
+    /// Returns `Ok(Self)` if the `sync_message` is valid to be (re)published on the gossip
+    /// network.
+    ///
+    /// `subnet_id` is the subnet from which we received this sync message. This function will
+    /// verify that it was received on the correct subnet.
+    pub fn verify<T: BeaconChainTypes>(
+        sync_message: SyncCommitteeMessage,
+        subnet_id: SyncSubnetId,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let partially_verified =
+            PartiallyVerifiedSyncCommitteeMessage::verify(sync_message, subnet_id, chain)?;
+        Self::from_partially_verified(
+            partially_verified,
+            chain,
+            CheckSyncCommitteeMessageSignature::Yes,
+        )
+    }
+
+    /// Completes the verification of a `PartiallyVerifiedSyncCommitteeMessage`.
+    ///
+    /// The signature is only verified if `check_signature` is `Yes`. It must only be `No` if the
+    /// signature has already been verified, e.g. in a batch.
+    pub(crate) fn from_partially_verified<T: BeaconChainTypes>(
+        partially_verified: PartiallyVerifiedSyncCommitteeMessage,
+        chain: &BeaconChain<T>,
+        check_signature: CheckSyncCommitteeMessageSignature,
+    ) -> Result<Self, Error> {
+        let PartiallyVerifiedSyncCommitteeMessage {
+            sync_message,
+            subnet_id,
+            pubkey,
+            subnet_positions,
+            head_root,
+            signature: _,
+        } = partially_verified;
+
         // The aggregate signature of the sync committee message is valid.
-        verify_sync_committee_message(chain, &sync_message, &pubkey)?;
+        if let CheckSyncCommitteeMessageSignature::Yes = check_signature {
+            verify_sync_committee_message(chain, &sync_message, &pubkey)?;
+        }
 
         // Now that the sync committee message has been fully verified, store that we have received a valid
         // sync committee message from this validator.
@@ -525,6 +604,8 @@ impl VerifiedSyncCommitteeMessage {
         // It's important to double check that the sync committee message still hasn't been observed, since
         // there can be a race-condition if we receive two sync committee messages at the same time and
         // process them in different threads.
+        let validator_index = sync_message.validator_index;
+        let new_root = sync_message.beacon_block_root;
         if let Some(prev_root) = chain
             .observed_sync_contributors
             .write()
@@ -532,7 +613,7 @@ impl VerifiedSyncCommitteeMessage {
                 SlotSubcommitteeIndex::new(sync_message.slot, subnet_id.into()),
                 validator_index as usize,
                 sync_message.beacon_block_root,
-                should_override_prev,
+                |prev_root, new_root| should_override_prev(prev_root, new_root, &head_root),
             )
             .map_err(BeaconChainError::from)?
         {
@@ -681,6 +762,35 @@ pub fn verify_sync_committee_message<T: BeaconChainTypes>(
 
     let pubkey_cache = chain.validator_pubkey_cache.read();
 
+    let agg_sig = AggregateSignature::from(&sync_message.signature);
+    let signature_set = sync_committee_message_signature_set(
+        chain,
+        &pubkey_cache,
+        sync_message,
+        &agg_sig,
+        pubkey_bytes,
+    )?;
+
+    metrics::stop_timer(signature_setup_timer);
+
+    let _signature_verification_timer =
+        metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_SIGNATURE_TIMES);
+
+    if signature_set.verify() {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+/// Returns the signature set of `sync_message`, signed by the validator with `pubkey_bytes`.
+fn sync_committee_message_signature_set<'a, T: BeaconChainTypes>(
+    chain: &'a BeaconChain<T>,
+    pubkey_cache: &'a ValidatorPubkeyCache<T>,
+    sync_message: &SyncCommitteeMessage,
+    signature: &'a AggregateSignature,
+    pubkey_bytes: &PublicKeyBytes,
+) -> Result<SignatureSet<'a>, Error> {
     let pubkey = pubkey_cache
         .get_pubkey_from_pubkey_bytes(pubkey_bytes)
         .map(Cow::Borrowed)
@@ -689,10 +799,9 @@ pub fn verify_sync_committee_message<T: BeaconChainTypes>(
     let next_slot_epoch = (sync_message.get_slot() + 1).epoch(T::EthSpec::slots_per_epoch());
     let fork = chain.spec.fork_at_epoch(next_slot_epoch);
 
-    let agg_sig = AggregateSignature::from(&sync_message.signature);
     let signature_set = sync_committee_message_set_from_pubkeys::<T::EthSpec>(
         pubkey,
-        &agg_sig,
+        signature,
         sync_message.slot.epoch(T::EthSpec::slots_per_epoch()),
         sync_message.beacon_block_root,
         &fork,
@@ -701,14 +810,25 @@ pub fn verify_sync_committee_message<T: BeaconChainTypes>(
     )
     .map_err(BeaconChainError::SignatureSetError)?;
 
-    metrics::stop_timer(signature_setup_timer);
-
-    let _signature_verification_timer =
-        metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_SIGNATURE_TIMES);
+    Ok(signature_set)
+}
 
-    if signature_set.verify() {
-        Ok(())
-    } else {
-        Err(Error::InvalidSignature)
+/// Returns `true` if a sync committee message for `new_root` should replace a previously observed
+/// message for `prev_root` from the same validator.
+///
+/// Messages are only replaced if they switch to the current head.
+fn should_override_prev(prev_root: &Hash256, new_root: &Hash256, head_root: &Hash256) -> bool {
+    let roots_differ = new_root != prev_root;
+    let new_elects_head = new_root == head_root;
+
+    if roots_differ {
+        // Track sync committee messages that differ from each other.
+        metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS);
+        if new_elects_head {
+            // Track sync committee messages that swap from an old block to a new block.
+            metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS_TO_HEAD);
+        }
     }
+
+    roots_differ && new_elects_head
 }
@@ -0,0 +1,267 @@
+//! Analytics of the arrival times of gossip blocks, aggregates and blob sidecars.
+//!
+//! The delay between the start of a slot and the arrival of each valid message for it is recorded
+//! in a Prometheus histogram, and collected per slot. Once a slot is more than
+//! `TIMELINESS_SETTLE_SLOTS` slots old its delays are reduced to deciles and added to a history of
+//! the last `TIMELINESS_HISTORY_SLOTS` slots, which is persisted across restarts.
+use crate::beacon_chain::TIMELINESS_DB_KEY;
+use crate::persisted_timeliness::PersistedTimeliness;
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{ArrivalDeciles, SlotTimeliness, ARRIVAL_DECILES};
+use slog::debug;
+use slot_clock::SlotClock;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+use types::Slot;
+
+/// The number of slots kept in the history (one day of mainnet slots).
+pub const TIMELINESS_HISTORY_SLOTS: usize = 7200;
+
+/// The number of slots after a slot during which arrivals for it are still collected.
+pub const TIMELINESS_SETTLE_SLOTS: u64 = 2;
+
+/// A kind of gossip message whose arrival time is recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrivalKind {
+    Block,
+    Aggregate,
+    BlobSidecar,
+}
+
+impl ArrivalKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArrivalKind::Block => "block",
+            ArrivalKind::Aggregate => "aggregate",
+            ArrivalKind::BlobSidecar => "blob_sidecar",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingArrivals {
+    blocks: Vec<u64>,
+    aggregates: Vec<u64>,
+    blob_sidecars: Vec<u64>,
+}
+
+impl PendingArrivals {
+    fn delays_mut(&mut self, kind: ArrivalKind) -> &mut Vec<u64> {
+        match kind {
+            ArrivalKind::Block => &mut self.blocks,
+            ArrivalKind::Aggregate => &mut self.aggregates,
+            ArrivalKind::BlobSidecar => &mut self.blob_sidecars,
+        }
+    }
+
+    fn into_slot_timeliness(self, slot: Slot) -> SlotTimeliness {
+        SlotTimeliness {
+            slot,
+            blocks: deciles(self.blocks),
+            aggregates: deciles(self.aggregates),
+            blob_sidecars: deciles(self.blob_sidecars),
+        }
+    }
+}
+
+/// The arrival times of the slots which are still being collected, and the deciles of the slots
+/// which have settled.
+#[derive(Debug, Default)]
+pub struct TimelinessTracker {
+    pending: BTreeMap<Slot, PendingArrivals>,
+    /// Ordered from oldest to newest slot.
+    history: VecDeque<SlotTimeliness>,
+}
+
+impl TimelinessTracker {
+    /// Record the arrival of a message for `slot`, `delay_ms` after the start of the slot.
+    ///
+    /// Arrivals for slots which have already settled are ignored.
+    pub fn record(&mut self, kind: ArrivalKind, slot: Slot, delay_ms: u64, current_slot: Slot) {
+        let settled = self
+            .history
+            .back()
+            .map_or(false, |latest| slot <= latest.slot);
+        if !settled && slot + TIMELINESS_SETTLE_SLOTS >= current_slot {
+            self.pending
+                .entry(slot)
+                .or_default()
+                .delays_mut(kind)
+                .push(delay_ms);
+        }
+        self.settle(current_slot);
+    }
+
+    /// Move the slots which are more than `TIMELINESS_SETTLE_SLOTS` prior to `current_slot` into
+    /// the history.
+    pub fn settle(&mut self, current_slot: Slot) {
+        while let Some(entry) = self.pending.first_entry() {
+            if *entry.key() + TIMELINESS_SETTLE_SLOTS >= current_slot {
+                break;
+            }
+            let slot = *entry.key();
+            self.push_history(entry.remove().into_slot_timeliness(slot));
+        }
+    }
+
+    fn push_history(&mut self, slot_timeliness: SlotTimeliness) {
+        self.history.push_back(slot_timeliness);
+        while self.history.len() > TIMELINESS_HISTORY_SLOTS {
+            self.history.pop_front();
+        }
+    }
+
+    /// Returns the settled slots between `start_slot` and `end_slot` (inclusive).
+    pub fn history(&self, start_slot: Slot, end_slot: Slot) -> Vec<SlotTimeliness> {
+        self.history
+            .iter()
+            .filter(|slot_timeliness| {
+                slot_timeliness.slot >= start_slot && slot_timeliness.slot <= end_slot
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the history, including the slots which have not yet settled.
+    fn snapshot(&self) -> Vec<SlotTimeliness> {
+        let mut slots = self.history.iter().cloned().collect::<Vec<_>>();
+        for (slot, pending) in &self.pending {
+            slots.push(SlotTimeliness {
+                slot: *slot,
+                blocks: deciles(pending.blocks.clone()),
+                aggregates: deciles(pending.aggregates.clone()),
+                blob_sidecars: deciles(pending.blob_sidecars.clone()),
+            });
+        }
+        slots
+    }
+
+    /// Replace the history with `slots`, which must be ordered from oldest to newest.
+    fn restore(&mut self, slots: Vec<SlotTimeliness>) {
+        self.pending.clear();
+        self.history.clear();
+        for slot_timeliness in slots {
+            self.push_history(slot_timeliness);
+        }
+    }
+}
+
+/// Returns the deciles of `delays`, using the nearest rank.
+fn deciles(mut delays: Vec<u64>) -> ArrivalDeciles {
+    delays.sort_unstable();
+    let deciles_ms = if delays.is_empty() {
+        vec![]
+    } else {
+        (0..ARRIVAL_DECILES)
+            .map(|i| delays[i * (delays.len() - 1) / (ARRIVAL_DECILES - 1)])
+            .collect()
+    };
+    ArrivalDeciles {
+        count: delays.len() as u64,
+        deciles_ms,
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Record the arrival of a valid gossip message for `slot`, `delay` after the start of the
+    /// slot.
+    pub fn record_arrival(&self, kind: ArrivalKind, slot: Slot, delay: Duration) {
+        metrics::observe_timer_vec(
+            &metrics::GOSSIP_ARRIVAL_DELAY_SECONDS,
+            &[kind.as_str()],
+            delay,
+        );
+        if let Some(current_slot) = self.slot_clock.now() {
+            self.timeliness
+                .write()
+                .record(kind, slot, delay.as_millis() as u64, current_slot);
+        }
+    }
+
+    /// Returns the arrival times of the settled slots between `start_slot` and `end_slot`
+    /// (inclusive).
+    pub fn timeliness(&self, start_slot: Slot, end_slot: Slot) -> Vec<SlotTimeliness> {
+        let mut timeliness = self.timeliness.write();
+        if let Some(current_slot) = self.slot_clock.now() {
+            timeliness.settle(current_slot);
+        }
+        timeliness.history(start_slot, end_slot)
+    }
+
+    /// Persists the arrival time history to disk, including the slots which have not yet settled.
+    pub fn persist_timeliness(&self) -> Result<(), BeaconChainError> {
+        let slots = self.timeliness.read().snapshot();
+        self.store
+            .put_item(&TIMELINESS_DB_KEY, &PersistedTimeliness { slots })?;
+        Ok(())
+    }
+
+    /// Restores the history persisted by `Self::persist_timeliness`.
+    pub fn restore_timeliness(&self) -> Result<(), BeaconChainError> {
+        let Some(persisted) = self
+            .store
+            .get_item::<PersistedTimeliness>(&TIMELINESS_DB_KEY)?
+        else {
+            return Ok(());
+        };
+
+        debug!(
+            self.log,
+            "Restored arrival timeliness";
+            "slots" => persisted.slots.len(),
+        );
+        self.timeliness.write().restore(persisted.slots);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deciles_of_delays() {
+        assert_eq!(deciles(vec![]), ArrivalDeciles::default());
+
+        let delays = (0..=100).rev().collect();
+        let result = deciles(delays);
+        assert_eq!(result.count, 101);
+        assert_eq!(
+            result.deciles_ms,
+            vec![0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100]
+        );
+
+        assert_eq!(deciles(vec![7]).deciles_ms, vec![7; ARRIVAL_DECILES]);
+    }
+
+    #[test]
+    fn slots_settle_into_bounded_history() {
+        let mut tracker = TimelinessTracker::default();
+        tracker.record(ArrivalKind::Block, Slot::new(1), 500, Slot::new(1));
+        tracker.record(ArrivalKind::Aggregate, Slot::new(1), 8_000, Slot::new(2));
+        tracker.record(ArrivalKind::Block, Slot::new(2), 1_000, Slot::new(2));
+        assert!(tracker.history(Slot::new(0), Slot::new(10)).is_empty());
+
+        tracker.settle(Slot::new(4));
+        let history = tracker.history(Slot::new(0), Slot::new(10));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].slot, Slot::new(1));
+        assert_eq!(history[0].blocks.count, 1);
+        assert_eq!(history[0].aggregates.deciles_ms[0], 8_000);
+        assert_eq!(history[0].blob_sidecars.count, 0);
+
+        // Arrivals for settled slots are ignored.
+        tracker.record(ArrivalKind::Block, Slot::new(1), 500, Slot::new(4));
+        assert_eq!(
+            tracker.history(Slot::new(1), Slot::new(1))[0].blocks.count,
+            1
+        );
+
+        for slot in 3..TIMELINESS_HISTORY_SLOTS as u64 + 10 {
+            tracker.record(ArrivalKind::Block, Slot::new(slot), 0, Slot::new(slot));
+        }
+        let history = tracker.history(Slot::new(0), Slot::max_value());
+        assert_eq!(history.len(), TIMELINESS_HISTORY_SLOTS);
+        assert!(history.windows(2).all(|w| w[0].slot < w[1].slot));
+    }
+}
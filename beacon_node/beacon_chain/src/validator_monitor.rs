@@ -10,11 +10,12 @@ use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use smallvec::SmallVec;
+use ssz_derive::{Decode, Encode};
 use state_processing::common::get_attestation_participation_flag_indices;
 use state_processing::per_epoch_processing::{
     errors::EpochProcessingError, EpochProcessingSummary,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
 use std::marker::PhantomData;
 use std::str::Utf8Error;
@@ -22,7 +23,8 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use store::AbstractExecPayload;
 use types::consts::altair::{
-    TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+    TIMELY_HEAD_FLAG_INDEX, TIMELY_HEAD_WEIGHT, TIMELY_SOURCE_FLAG_INDEX, TIMELY_SOURCE_WEIGHT,
+    TIMELY_TARGET_FLAG_INDEX, TIMELY_TARGET_WEIGHT,
 };
 use types::{
     Attestation, AttestationData, AttesterSlashingRef, BeaconBlockRef, BeaconState,
@@ -41,6 +43,14 @@ const TOTAL_LABEL: &str = "total";
 /// will be kept around for `HISTORIC_EPOCHS` before it is pruned.
 pub const HISTORIC_EPOCHS: usize = 10;
 
+/// The number of epochs of attestation effectiveness kept for each monitored validator (roughly a
+/// week on mainnet).
+pub const ATTESTATION_EFFECTIVENESS_EPOCHS: usize = 1575;
+
+/// The number of epochs over which the attestation effectiveness Prometheus gauges are averaged
+/// (roughly a day on mainnet).
+pub const ATTESTATION_EFFECTIVENESS_METRIC_EPOCHS: u64 = 225;
+
 /// Once the validator monitor reaches this number of validators it will stop
 /// tracking their metrics/logging individually in an effort to reduce
 /// Prometheus cardinality and log volume.
@@ -124,11 +134,11 @@ pub struct EpochSummary {
      * SyncCommitteeMessages in the current epoch
      */
     /// The number of sync committee messages seen.
-    sync_committee_messages: usize,
+    pub sync_committee_messages: usize,
     /// The delay between when the sync committee message should have been produced and when it was observed.
     sync_committee_message_min_delay: Option<Duration>,
     /// The number of times a validator's sync signature was included in the sync aggregate.
-    sync_signature_block_inclusions: usize,
+    pub sync_signature_block_inclusions: usize,
     /// The number of times a validator's sync signature was aggregated into a sync contribution.
     sync_signature_contribution_inclusions: usize,
 
@@ -277,6 +287,84 @@ impl ValidatorMetrics {
     }
 }
 
+/// The outcome of a validator's attestation for a single epoch, as determined during per-epoch
+/// processing.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct AttestationEffectiveness {
+    pub epoch: Epoch,
+    /// `true` if any attestation from the validator was included on-chain.
+    pub included: bool,
+    pub source_hit: bool,
+    pub target_hit: bool,
+    pub head_hit: bool,
+    /// The lowest inclusion distance of the validator's attestations, if known.
+    pub inclusion_distance: Option<u64>,
+}
+
+impl AttestationEffectiveness {
+    /// Returns the percentage of the maximum attestation reward that was earned, weighting each
+    /// correct vote by its Altair reward weight.
+    pub fn score(&self) -> f64 {
+        let max_weight = TIMELY_SOURCE_WEIGHT + TIMELY_TARGET_WEIGHT + TIMELY_HEAD_WEIGHT;
+        let weight = [
+            (self.source_hit, TIMELY_SOURCE_WEIGHT),
+            (self.target_hit, TIMELY_TARGET_WEIGHT),
+            (self.head_hit, TIMELY_HEAD_WEIGHT),
+        ]
+        .into_iter()
+        .filter(|(hit, _)| *hit)
+        .map(|(_, weight)| weight)
+        .sum::<u64>();
+
+        100.0 * weight as f64 / max_weight as f64
+    }
+}
+
+/// The attestation effectiveness of a validator, aggregated over a range of epochs.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AttestationEffectivenessSummary {
+    /// The number of epochs in which the validator was expected to attest.
+    pub epochs: u64,
+    pub included: u64,
+    pub source_hits: u64,
+    pub target_hits: u64,
+    pub head_hits: u64,
+    /// The mean inclusion distance of the included attestations whose distance is known.
+    pub mean_inclusion_distance: Option<f64>,
+    /// The mean `AttestationEffectiveness::score`, from 0 to 100.
+    pub effectiveness: f64,
+}
+
+impl AttestationEffectivenessSummary {
+    pub fn from_history<'a>(history: impl Iterator<Item = &'a AttestationEffectiveness>) -> Self {
+        let mut summary = Self::default();
+        let mut total_score = 0.0;
+        let mut total_distance = 0;
+        let mut distances = 0;
+
+        for effectiveness in history {
+            summary.epochs += 1;
+            summary.included += effectiveness.included as u64;
+            summary.source_hits += effectiveness.source_hit as u64;
+            summary.target_hits += effectiveness.target_hit as u64;
+            summary.head_hits += effectiveness.head_hit as u64;
+            total_score += effectiveness.score();
+            if let Some(distance) = effectiveness.inclusion_distance {
+                total_distance += distance;
+                distances += 1;
+            }
+        }
+
+        if summary.epochs > 0 {
+            summary.effectiveness = total_score / summary.epochs as f64;
+        }
+        if distances > 0 {
+            summary.mean_inclusion_distance = Some(total_distance as f64 / distances as f64);
+        }
+        summary
+    }
+}
+
 /// A validator that is being monitored by the `ValidatorMonitor`.
 pub struct MonitoredValidator {
     /// A human-readable identifier for the validator.
@@ -287,6 +375,8 @@ pub struct MonitoredValidator {
     pub summaries: RwLock<SummaryMap>,
     /// Validator metrics to be exposed over the HTTP API.
     pub metrics: RwLock<ValidatorMetrics>,
+    /// The outcome of the validator's attestations for the last `ATTESTATION_EFFECTIVENESS_EPOCHS`.
+    pub attestation_effectiveness: RwLock<BTreeMap<Epoch, AttestationEffectiveness>>,
 }
 
 impl MonitoredValidator {
@@ -298,6 +388,7 @@ impl MonitoredValidator {
             index,
             summaries: <_>::default(),
             metrics: <_>::default(),
+            attestation_effectiveness: <_>::default(),
         }
     }
 
@@ -321,6 +412,24 @@ impl MonitoredValidator {
         })
     }
 
+    /// Records the outcome of an attestation, pruning the oldest epochs from the history.
+    fn register_attestation_effectiveness(&self, effectiveness: AttestationEffectiveness) {
+        let mut history = self.attestation_effectiveness.write();
+        history.insert(effectiveness.epoch, effectiveness);
+        while history.len() > ATTESTATION_EFFECTIVENESS_EPOCHS {
+            history.pop_first();
+        }
+    }
+
+    /// Returns the attestation effectiveness of the validator from `start_epoch` onwards.
+    pub fn attestation_effectiveness_summary(
+        &self,
+        start_epoch: Epoch,
+    ) -> AttestationEffectivenessSummary {
+        let history = self.attestation_effectiveness.read();
+        AttestationEffectivenessSummary::from_history(history.range(start_epoch..).map(|(_, e)| e))
+    }
+
     /// Maps `func` across the `self.summaries`.
     ///
     /// ## Warning
@@ -401,6 +510,12 @@ pub struct ValidatorMonitor<E: EthSpec> {
     beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>>,
     // Unaggregated attestations generated by the committee index at each slot.
     unaggregated_attestations: HashMap<Slot, Attestation<E>>,
+    /// The block roots voted for by the simulated sync committee message at each slot.
+    simulated_sync_committee_messages: HashMap<Slot, Hash256>,
+    /// The parent roots of the simulated block proposal at each slot.
+    simulated_proposals: HashMap<Slot, Hash256>,
+    /// Attestation effectiveness restored from disk for validators which are not yet monitored.
+    pending_attestation_effectiveness: HashMap<PublicKeyBytes, Vec<AttestationEffectiveness>>,
     log: Logger,
     _phantom: PhantomData<E>,
 }
@@ -425,6 +540,9 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             missed_blocks: <_>::default(),
             beacon_proposer_cache,
             unaggregated_attestations: <_>::default(),
+            simulated_sync_committee_messages: <_>::default(),
+            simulated_proposals: <_>::default(),
+            pending_attestation_effectiveness: <_>::default(),
             log,
             _phantom: PhantomData,
         };
@@ -450,16 +568,85 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             .map(|(index, _)| *index);
 
         let log = self.log.clone();
+        let pending_effectiveness = &mut self.pending_attestation_effectiveness;
         self.validators.entry(pubkey).or_insert_with(|| {
             info!(
                 log,
                 "Started monitoring validator";
                 "pubkey" => %pubkey,
             );
-            MonitoredValidator::new(pubkey, index_opt)
+            let validator = MonitoredValidator::new(pubkey, index_opt);
+            for effectiveness in pending_effectiveness.remove(&pubkey).unwrap_or_default() {
+                validator.register_attestation_effectiveness(effectiveness);
+            }
+            validator
         });
     }
 
+    /// Returns the attestation effectiveness history of every validator, including any which was
+    /// restored for validators that are not yet monitored, so that it can be persisted.
+    pub fn attestation_effectiveness_history(
+        &self,
+    ) -> Vec<(PublicKeyBytes, Vec<AttestationEffectiveness>)> {
+        self.validators
+            .iter()
+            .map(|(pubkey, validator)| {
+                let history = validator.attestation_effectiveness.read();
+                (*pubkey, history.values().copied().collect())
+            })
+            .chain(
+                self.pending_attestation_effectiveness
+                    .iter()
+                    .map(|(pubkey, history)| (*pubkey, history.clone())),
+            )
+            .collect()
+    }
+
+    /// Restores the history returned by `Self::attestation_effectiveness_history`.
+    ///
+    /// The history of validators which are not monitored is held until they are added, so that it
+    /// is not lost for validators which are registered automatically after a restart.
+    pub fn restore_attestation_effectiveness(
+        &mut self,
+        history: Vec<(PublicKeyBytes, Vec<AttestationEffectiveness>)>,
+    ) {
+        for (pubkey, effectiveness) in history {
+            if let Some(validator) = self.validators.get(&pubkey) {
+                for epoch_effectiveness in effectiveness {
+                    validator.register_attestation_effectiveness(epoch_effectiveness);
+                }
+            } else {
+                self.pending_attestation_effectiveness
+                    .insert(pubkey, effectiveness);
+            }
+        }
+    }
+
+    /// Returns the attestation effectiveness of each monitored validator with a known index from
+    /// `start_epoch` onwards, ranked from most to least effective.
+    pub fn get_attestation_effectiveness(
+        &self,
+        start_epoch: Epoch,
+    ) -> Vec<(u64, AttestationEffectivenessSummary)> {
+        let mut summaries = self
+            .validators
+            .values()
+            .filter_map(|validator| {
+                let index = validator.index?;
+                Some((
+                    index,
+                    validator.attestation_effectiveness_summary(start_epoch),
+                ))
+            })
+            .collect::<Vec<_>>();
+        summaries.sort_by(|(a_index, a), (b_index, b)| {
+            b.effectiveness
+                .total_cmp(&a.effectiveness)
+                .then_with(|| a_index.cmp(b_index))
+        });
+        summaries
+    }
+
     /// Add an unaggregated attestation
     pub fn set_unaggregated_attestation(&mut self, attestation: Attestation<E>) {
         let unaggregated_attestations = &mut self.unaggregated_attestations;
@@ -478,6 +665,28 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         self.unaggregated_attestations.get(&slot)
     }
 
+    /// Add the block root voted for by a simulated sync committee message at `slot`.
+    pub fn set_simulated_sync_committee_message(&mut self, slot: Slot, beacon_block_root: Hash256) {
+        insert_simulated_duty(
+            &mut self.simulated_sync_committee_messages,
+            slot,
+            beacon_block_root,
+        );
+    }
+
+    pub fn get_simulated_sync_committee_message(&self, slot: Slot) -> Option<Hash256> {
+        self.simulated_sync_committee_messages.get(&slot).copied()
+    }
+
+    /// Add the parent root of a simulated block proposal at `slot`.
+    pub fn set_simulated_proposal(&mut self, slot: Slot, parent_root: Hash256) {
+        insert_simulated_duty(&mut self.simulated_proposals, slot, parent_root);
+    }
+
+    pub fn get_simulated_proposal(&self, slot: Slot) -> Option<Hash256> {
+        self.simulated_proposals.get(&slot).copied()
+    }
+
     /// Reads information from the given `state`. The `state` *must* be valid (i.e, able to be
     /// imported).
     pub fn process_valid_state(
@@ -503,6 +712,8 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         // Add missed non-finalized blocks for the monitored validators
         self.add_validators_missed_blocks(state);
         self.process_unaggregated_attestations(state, spec);
+        self.process_simulated_sync_committee_messages(state);
+        self.process_simulated_proposals(state);
 
         // Update metrics for individual validators.
         for monitored_validator in self.validators.values() {
@@ -770,6 +981,63 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         }
     }
 
+    /// Process the sync committee messages generated by the `attestation_simulator_service` and
+    /// check whether they voted for the canonical block root at their slot, as required for the
+    /// sync committee reward.
+    fn process_simulated_sync_committee_messages(&mut self, state: &BeaconState<E>) {
+        for (slot, beacon_block_root) in
+            take_lagged_simulated_duties(&mut self.simulated_sync_committee_messages, state)
+        {
+            let Ok(canonical_root) = state.get_block_root(slot) else {
+                continue;
+            };
+            let hit = *canonical_root == beacon_block_root;
+            if hit {
+                metrics::inc_counter(&metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_HIT);
+            } else {
+                metrics::inc_counter(&metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_MISS);
+            }
+
+            debug!(
+                self.log,
+                "Simulated sync committee message evaluated";
+                "slot" => slot,
+                "beacon_block_root" => ?beacon_block_root,
+                "hit" => hit,
+            );
+        }
+    }
+
+    /// Process the block proposals generated by the `attestation_simulator_service` and check
+    /// whether they would have been built upon the canonical chain.
+    fn process_simulated_proposals(&mut self, state: &BeaconState<E>) {
+        for (slot, parent_root) in
+            take_lagged_simulated_duties(&mut self.simulated_proposals, state)
+        {
+            let Some(Ok(canonical_parent_root)) = slot
+                .as_u64()
+                .checked_sub(1)
+                .map(|parent_slot| state.get_block_root(Slot::new(parent_slot)))
+            else {
+                continue;
+            };
+            let hit = *canonical_parent_root == parent_root;
+            if hit {
+                metrics::inc_counter(&metrics::VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_HIT);
+            } else {
+                metrics::inc_counter(&metrics::VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_MISS);
+            }
+
+            debug!(
+                self.log,
+                "Simulated block proposal evaluated";
+                "slot" => slot,
+                "parent_root" => ?parent_root,
+                "hit" => hit,
+            );
+        }
+    }
+
     /// Run `func` with the `TOTAL_LABEL` and optionally the
     /// `individual_id`.
     ///
@@ -799,6 +1067,8 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         let mut head_miss = Vec::new();
         let mut target_miss = Vec::new();
         let mut suboptimal_inclusion = Vec::new();
+        let mut total_effectiveness = 0.0;
+        let mut effectiveness_count = 0;
 
         // We subtract two from the state of the epoch that generated these summaries.
         //
@@ -806,6 +1076,8 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         // - One to account for the state advancing an epoch whilst generating the validator
         //     statuses.
         let prev_epoch = epoch - 2;
+        let effectiveness_start_epoch =
+            prev_epoch.saturating_sub(ATTESTATION_EFFECTIVENESS_METRIC_EPOCHS - 1);
         for (pubkey, monitored_validator) in self.validators.iter() {
             if let Some(i) = monitored_validator.index {
                 let i = i as usize;
@@ -981,6 +1253,27 @@ impl<E: EthSpec> ValidatorMonitor<E> {
                 }
                 drop(validator_metrics);
 
+                monitored_validator.register_attestation_effectiveness(AttestationEffectiveness {
+                    epoch: prev_epoch,
+                    included: previous_epoch_matched_any,
+                    source_hit: previous_epoch_matched_source,
+                    target_hit: previous_epoch_matched_target,
+                    head_hit: previous_epoch_matched_head,
+                    inclusion_distance: min_inclusion_distance,
+                });
+                let effectiveness = monitored_validator
+                    .attestation_effectiveness_summary(effectiveness_start_epoch)
+                    .effectiveness;
+                total_effectiveness += effectiveness;
+                effectiveness_count += 1;
+                if self.individual_tracking() {
+                    metrics::set_float_gauge_vec(
+                        &metrics::VALIDATOR_MONITOR_ATTESTATION_EFFECTIVENESS,
+                        &[id],
+                        effectiveness,
+                    );
+                }
+
                 // Indicates the number of sync committee signatures that made it into
                 // a sync aggregate in the current_epoch (state.epoch - 1).
                 // Note: Unlike attestations, sync committee signatures must be included in the
@@ -1074,6 +1367,14 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             );
         }
 
+        if effectiveness_count > 0 {
+            metrics::set_float_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_ATTESTATION_EFFECTIVENESS,
+                &[TOTAL_LABEL],
+                total_effectiveness / effectiveness_count as f64,
+            );
+        }
+
         Ok(())
     }
 
@@ -1093,6 +1394,11 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         self.validators.values().map(|val| val.id.clone()).collect()
     }
 
+    /// Returns all of the monitored validators.
+    pub fn monitored_validators(&self) -> impl Iterator<Item = &MonitoredValidator> {
+        self.validators.values()
+    }
+
     pub fn get_monitored_validator(&self, index: u64) -> Option<&MonitoredValidator> {
         if let Some(pubkey) = self.indices.get(&index) {
             self.validators.get(pubkey)
@@ -1108,6 +1414,15 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             .count() as u64
     }
 
+    /// Returns the `(validator_index, slot)` of each non-finalized block missed by a monitored
+    /// validator.
+    pub fn get_monitored_validator_missed_blocks(&self) -> Vec<(u64, Slot)> {
+        self.missed_blocks
+            .iter()
+            .map(|missed_block| (missed_block.validator_index, missed_block.slot))
+            .collect()
+    }
+
     pub fn get_beacon_proposer_cache(&self) -> Arc<Mutex<BeaconProposerCache>> {
         self.beacon_proposer_cache.clone()
     }
@@ -2069,6 +2384,39 @@ impl<E: EthSpec> ValidatorMonitor<E> {
     }
 }
 
+/// Inserts a simulated duty, removing the oldest duty if there are too many.
+fn insert_simulated_duty(duties: &mut HashMap<Slot, Hash256>, slot: Slot, root: Hash256) {
+    if duties.len() >= MAX_UNAGGREGATED_ATTESTATION_HASHMAP_LENGTH {
+        if let Some(oldest_slot) = duties.keys().min().copied() {
+            duties.remove(&oldest_slot);
+        }
+    }
+    duties.insert(slot, root);
+}
+
+/// Removes and returns the simulated duties which are old enough to be evaluated against `state`
+/// without being affected by re-orgs, as for the unaggregated attestations. Duties which are too
+/// old to be evaluated are discarded.
+fn take_lagged_simulated_duties<E: EthSpec>(
+    duties: &mut HashMap<Slot, Hash256>,
+    state: &BeaconState<E>,
+) -> Vec<(Slot, Hash256)> {
+    let lagged_slot = state
+        .slot()
+        .saturating_sub(UNAGGREGATED_ATTESTATION_LAG_SLOTS as u64);
+    let lagged_slots = duties
+        .keys()
+        .filter(|slot| **slot < lagged_slot)
+        .copied()
+        .collect::<Vec<_>>();
+    lagged_slots
+        .into_iter()
+        .filter_map(|slot| duties.remove(&slot).map(|root| (slot, root)))
+        .filter(|(slot, _)| slot.epoch(E::slots_per_epoch()) >= state.previous_epoch())
+        .sorted_by_key(|(slot, _)| *slot)
+        .collect()
+}
+
 fn register_simulated_attestation(
     data: &AttestationData,
     head_hit: bool,
@@ -1,6 +1,8 @@
 #![cfg(not(debug_assertions))]
 
-use beacon_chain::attestation_simulator::produce_unaggregated_attestation;
+use beacon_chain::attestation_simulator::{
+    produce_unaggregated_attestation, simulate_proposal, simulate_sync_committee_message,
+};
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
 use beacon_chain::validator_monitor::UNAGGREGATED_ATTESTATION_LAG_SLOTS;
@@ -9,7 +11,7 @@ use lazy_static::lazy_static;
 use std::sync::Arc;
 use tree_hash::TreeHash;
 use types::{
-    AggregateSignature, Attestation, EthSpec, Keypair, MainnetEthSpec, RelativeEpoch, Slot,
+    AggregateSignature, Attestation, Epoch, EthSpec, Keypair, MainnetEthSpec, RelativeEpoch, Slot,
 };
 
 pub const VALIDATOR_COUNT: usize = 16;
@@ -104,6 +106,82 @@ async fn produces_attestations_from_attestation_simulator_service() {
     });
 }
 
+/// This test builds a chain that is testing the performance of the sync committee messages and
+/// block proposals simulated by the attestation simulator service.
+#[tokio::test]
+async fn simulates_sync_committee_messages_and_proposals() {
+    let num_blocks_produced = MainnetEthSpec::slots_per_epoch() * 2;
+
+    let mut spec = MainnetEthSpec::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec)
+        .keypairs(KEYPAIRS[..].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    let chain = &harness.chain;
+
+    for slot in 1..=num_blocks_produced {
+        harness.advance_slot();
+        let slot = Slot::new(slot);
+
+        // The proposal is simulated at the start of the slot, before the block arrives.
+        simulate_proposal(chain, slot);
+        harness
+            .extend_chain(
+                1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        simulate_sync_committee_message(chain, slot);
+
+        let validator_monitor = chain.validator_monitor.read();
+        assert_eq!(
+            validator_monitor.get_simulated_proposal(slot),
+            Some(
+                chain
+                    .block_root_at_slot(slot - 1, WhenSlotSkipped::Prev)
+                    .unwrap()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            validator_monitor.get_simulated_sync_committee_message(slot),
+            Some(chain.head_beacon_block_root())
+        );
+    }
+
+    // Every simulated duty which is old enough to be evaluated should be a hit.
+    let expected_hit_metrics_count =
+        num_blocks_produced - UNAGGREGATED_ATTESTATION_LAG_SLOTS as u64 - 1;
+    let metric_families = lighthouse_metrics::gather();
+    let metric_count = |name: &str| {
+        metric_families
+            .iter()
+            .find(|mf| mf.get_name() == name)
+            .map_or(0, |mf| mf.get_metric()[0].get_counter().get_value() as u64)
+    };
+    assert_eq!(
+        metric_count(metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_HIT_TOTAL),
+        expected_hit_metrics_count
+    );
+    assert_eq!(
+        metric_count(metrics::VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_HIT_TOTAL),
+        expected_hit_metrics_count
+    );
+    assert_eq!(
+        metric_count(metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_SIMULATOR_MISS_TOTAL),
+        0
+    );
+    assert_eq!(
+        metric_count(metrics::VALIDATOR_MONITOR_PROPOSAL_SIMULATOR_MISS_TOTAL),
+        0
+    );
+}
+
 /// This test builds a chain that is just long enough to finalize an epoch then it produces an
 /// attestation at each slot from genesis through to three epochs past the head.
 ///
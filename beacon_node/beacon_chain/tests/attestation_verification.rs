@@ -4,7 +4,9 @@ use beacon_chain::attestation_verification::{
     batch_verify_aggregated_attestations, batch_verify_unaggregated_attestations, Error,
 };
 use beacon_chain::observed_aggregates::ObservedAttestationKey;
-use beacon_chain::test_utils::{MakeAttestationOptions, HARNESS_GENESIS_TIME};
+use beacon_chain::test_utils::{
+    MakeAttestationOptions, RelativeSyncCommittee, HARNESS_GENESIS_TIME,
+};
 use beacon_chain::{
     attestation_verification::Error as AttnError,
     test_utils::{
@@ -19,13 +21,14 @@ use ssz_types::BitVector;
 use state_processing::{
     per_block_processing::errors::AttestationValidationError, per_slot_processing,
 };
+use std::collections::HashSet;
 use tree_hash::TreeHash;
 use types::{
     signed_aggregate_and_proof::SignedAggregateAndProofRefMut,
     test_utils::generate_deterministic_keypair, Address, AggregateSignature, Attestation,
     AttestationRef, AttestationRefMut, BeaconStateError, BitList, ChainSpec, Epoch, EthSpec,
     ForkName, Hash256, Keypair, MainnetEthSpec, SecretKey, SelectionProof, SignedAggregateAndProof,
-    Slot, SubnetId, Unsigned,
+    Slot, SubnetId, SyncCommitteeMessage, SyncSubnetId, Unsigned,
 };
 
 pub type E = MainnetEthSpec;
@@ -1745,3 +1748,206 @@ async fn aggregated_attestation_verification_use_head_state_fork() {
         );
     }
 }
+
+/// Messages supplied to both `BeaconChain::batch_verify_gossip_signatures` and individual
+/// verification.
+struct GossipMessages {
+    attestations: Vec<(Attestation<E>, SubnetId)>,
+    aggregates: Vec<SignedAggregateAndProof<E>>,
+    sync_messages: Vec<(SyncCommitteeMessage, SyncSubnetId)>,
+}
+
+/// The accept/reject outcome of verifying each message, comparable across chains.
+#[derive(Debug, PartialEq)]
+struct GossipOutcomes {
+    attestations: Vec<Result<(), String>>,
+    aggregates: Vec<Result<(), String>>,
+    sync_messages: Vec<Result<(), String>>,
+}
+
+fn outcome<T, Err: std::fmt::Debug>(result: Result<T, Err>) -> Result<(), String> {
+    result.map(|_| ()).map_err(|e| format!("{e:?}"))
+}
+
+/// Returns a harness with Altair enabled at genesis, at a slot after the first epoch.
+///
+/// No attestations are included, so that none of the validators have been observed attesting.
+async fn get_altair_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec)
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize + 3,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(vec![]),
+        )
+        .await;
+    harness
+}
+
+/// Produce attestations and aggregates for the last two slots and sync messages for the head,
+/// replacing the signatures of the messages for which `invalid` returns `true`.
+fn get_gossip_messages(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+    invalid: fn(usize) -> bool,
+) -> GossipMessages {
+    let (state, state_root) = harness.get_current_state_and_root();
+    let head_slot = state.slot();
+    let bad_sk = &KEYPAIRS[0].sk;
+
+    let mut attestations = vec![];
+    let mut aggregates = vec![];
+    for slot in [head_slot - 1, head_slot] {
+        let block_root = harness
+            .chain
+            .block_root_at_slot(slot, WhenSlotSkipped::Prev)
+            .unwrap()
+            .unwrap();
+        for (committee_attestations, aggregate) in harness.make_attestations(
+            &(0..VALIDATOR_COUNT).collect::<Vec<_>>(),
+            &state,
+            state_root,
+            block_root.into(),
+            slot,
+        ) {
+            attestations.extend(committee_attestations);
+            aggregates.extend(aggregate);
+        }
+    }
+
+    let mut seen_validators = HashSet::new();
+    let mut sync_messages = harness
+        .make_sync_committee_messages(
+            &state,
+            harness.head_block_root(),
+            head_slot,
+            RelativeSyncCommittee::Current,
+        )
+        .remove(0)
+        .into_iter()
+        .filter(|(message, _)| seen_validators.insert(message.validator_index))
+        .map(|(message, _)| (message, SyncSubnetId::new(0)))
+        .take(16)
+        .collect::<Vec<_>>();
+
+    for (i, (attestation, _)) in attestations.iter_mut().enumerate() {
+        if invalid(i) {
+            let mut signature = AggregateSignature::infinity();
+            signature.add_assign(&bad_sk.sign(Hash256::repeat_byte(42)));
+            *attestation.signature_mut() = signature;
+        }
+    }
+    for (i, aggregate) in aggregates.iter_mut().enumerate() {
+        if invalid(i) {
+            match aggregate.to_mut() {
+                SignedAggregateAndProofRefMut::Base(aggregate) => {
+                    aggregate.signature = bad_sk.sign(Hash256::repeat_byte(42))
+                }
+                SignedAggregateAndProofRefMut::Electra(aggregate) => {
+                    aggregate.signature = bad_sk.sign(Hash256::repeat_byte(42))
+                }
+            }
+        }
+    }
+    for (i, (sync_message, _)) in sync_messages.iter_mut().enumerate() {
+        if invalid(i) {
+            sync_message.signature = bad_sk.sign(Hash256::repeat_byte(42));
+        }
+    }
+
+    assert!(attestations.len() > 1);
+    assert!(aggregates.len() > 1);
+    assert!(sync_messages.len() > 1);
+    GossipMessages {
+        attestations,
+        aggregates,
+        sync_messages,
+    }
+}
+
+/// Verify the same messages in a batch on one chain and individually on an identical chain, and
+/// check that each message is accepted or rejected in the same way by both.
+async fn assert_gossip_signature_batch_matches_individual(invalid: fn(usize) -> bool) {
+    let batch_harness = get_altair_harness().await;
+    let individual_harness = get_altair_harness().await;
+    assert_eq!(
+        batch_harness.head_block_root(),
+        individual_harness.head_block_root()
+    );
+
+    let messages = get_gossip_messages(&batch_harness, invalid);
+
+    let results = batch_harness.chain.batch_verify_gossip_signatures(
+        messages
+            .attestations
+            .iter()
+            .map(|(attestation, subnet_id)| (attestation, Some(*subnet_id))),
+        messages.aggregates.iter(),
+        messages.sync_messages.iter().cloned(),
+    );
+    let batch = GossipOutcomes {
+        attestations: results.attestations.into_iter().map(outcome).collect(),
+        aggregates: results.aggregates.into_iter().map(outcome).collect(),
+        sync_messages: results.sync_messages.into_iter().map(outcome).collect(),
+    };
+
+    let chain = &individual_harness.chain;
+    let individual = GossipOutcomes {
+        attestations: messages
+            .attestations
+            .iter()
+            .map(|(attestation, subnet_id)| {
+                outcome(
+                    chain.verify_unaggregated_attestation_for_gossip(attestation, Some(*subnet_id)),
+                )
+            })
+            .collect(),
+        aggregates: messages
+            .aggregates
+            .iter()
+            .map(|aggregate| outcome(chain.verify_aggregated_attestation_for_gossip(aggregate)))
+            .collect(),
+        sync_messages: messages
+            .sync_messages
+            .iter()
+            .map(|(sync_message, subnet_id)| {
+                outcome(
+                    chain
+                        .verify_sync_committee_message_for_gossip(sync_message.clone(), *subnet_id),
+                )
+            })
+            .collect(),
+    };
+
+    assert_eq!(batch, individual);
+
+    // Check that exactly the messages with replaced signatures were rejected.
+    for outcomes in [&batch.attestations, &batch.aggregates, &batch.sync_messages] {
+        for (i, outcome) in outcomes.iter().enumerate() {
+            assert_eq!(outcome.is_err(), invalid(i), "message {i}: {outcome:?}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn gossip_signature_batch_all_valid() {
+    assert_gossip_signature_batch_matches_individual(|_| false).await;
+}
+
+#[tokio::test]
+async fn gossip_signature_batch_all_invalid() {
+    assert_gossip_signature_batch_matches_individual(|_| true).await;
+}
+
+#[tokio::test]
+async fn gossip_signature_batch_mixed() {
+    assert_gossip_signature_batch_matches_individual(|i| i % 3 == 1).await;
+}
@@ -295,6 +295,132 @@ async fn chain_segment_varying_chunk_size() {
     }
 }
 
+/// Import the chain segment one epoch at a time, verifying the signatures of each epoch with the
+/// state of `anchor_epoch_fn(epoch)` before the previous epoch has been imported.
+async fn import_chain_segment_speculatively(
+    anchor_epoch_fn: impl Fn(Epoch) -> Epoch,
+    notify_execution_layer: NotifyExecutionLayer,
+) {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let (chain_segment, chain_segment_blobs) = get_chain_segment().await;
+    let blocks: Vec<RpcBlock<E>> = chain_segment_blocks(&chain_segment, &chain_segment_blobs);
+
+    harness
+        .chain
+        .slot_clock
+        .set_slot(blocks.last().unwrap().slot().as_u64());
+
+    let epoch_blocks = |epoch: Epoch| {
+        blocks
+            .iter()
+            .filter(|block| block.epoch() == epoch)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let last_epoch = blocks.last().unwrap().epoch();
+
+    harness
+        .chain
+        .process_chain_segment(epoch_blocks(Epoch::new(0)), notify_execution_layer)
+        .await
+        .into_block_error()
+        .expect("should import first epoch");
+
+    for epoch in (1..=last_epoch.as_u64()).map(Epoch::new) {
+        let next_epoch = epoch + 1;
+        if next_epoch <= last_epoch {
+            let anchor_root = epoch_blocks(anchor_epoch_fn(epoch))
+                .first()
+                .unwrap()
+                .parent_root();
+            // A failure here is not fatal, the segment is fully verified during import.
+            let _ = harness
+                .chain
+                .speculatively_verify_chain_segment(anchor_root, epoch_blocks(next_epoch))
+                .await;
+        }
+
+        harness
+            .chain
+            .process_chain_segment(epoch_blocks(epoch), notify_execution_layer)
+            .await
+            .into_block_error()
+            .unwrap_or_else(|_| panic!("should import epoch {}", epoch));
+    }
+
+    harness.chain.recompute_head_at_current_slot().await;
+
+    assert_eq!(
+        harness.head_block_root(),
+        blocks.last().unwrap().canonical_root(),
+        "harness should have last block as head"
+    );
+}
+
+#[tokio::test]
+async fn chain_segment_speculative_verification() {
+    import_chain_segment_speculatively(|epoch| epoch, NotifyExecutionLayer::Yes).await;
+}
+
+#[tokio::test]
+async fn chain_segment_speculative_verification_without_execution_layer() {
+    // The payloads are verified ahead of import when the execution layer is not notified.
+    import_chain_segment_speculatively(|epoch| epoch, NotifyExecutionLayer::No).await;
+}
+
+#[tokio::test]
+async fn chain_segment_speculative_verification_wrong_anchor() {
+    // Using a stale anchor must never prevent the segment from being imported.
+    import_chain_segment_speculatively(|epoch| epoch - 1, NotifyExecutionLayer::Yes).await;
+}
+
+#[tokio::test]
+async fn chain_segment_speculative_verification_invalid_signature() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let (chain_segment, chain_segment_blobs) = get_chain_segment().await;
+    let blocks: Vec<RpcBlock<E>> = chain_segment_blocks(&chain_segment, &chain_segment_blobs);
+
+    harness
+        .chain
+        .slot_clock
+        .set_slot(blocks.last().unwrap().slot().as_u64());
+
+    let epoch_blocks = |epoch: u64| {
+        blocks
+            .iter()
+            .filter(|block| block.epoch() == Epoch::new(epoch))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    harness
+        .chain
+        .process_chain_segment(epoch_blocks(0), NotifyExecutionLayer::Yes)
+        .await
+        .into_block_error()
+        .expect("should import first epoch");
+
+    // Corrupt the signature of a block in the third epoch, which is verified using the state of
+    // the anchor prior to the second epoch.
+    let anchor_root = epoch_blocks(1).first().unwrap().parent_root();
+    let mut segment = epoch_blocks(2);
+    let (block, _) = segment[1].as_block().clone().deconstruct();
+    segment[1] = RpcBlock::new(
+        None,
+        Arc::new(SignedBeaconBlock::from_block(block, junk_signature())),
+        None,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        harness
+            .chain
+            .speculatively_verify_chain_segment(anchor_root, segment)
+            .await,
+        Err(BlockError::InvalidSignature)
+    ));
+}
+
 #[tokio::test]
 async fn chain_segment_non_linear_parent_roots() {
     let harness = get_harness(VALIDATOR_COUNT);
@@ -1277,6 +1403,64 @@ async fn verify_block_for_gossip_slashing_detection() {
     slasher_dir.close().unwrap();
 }
 
+#[tokio::test]
+async fn verify_block_for_gossip_equivocation_detection() {
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            detect_proposer_equivocations: true,
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS.to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    let state = harness.get_current_state();
+    let ((block1, blobs1), _) = harness.make_block(state.clone(), Slot::new(1)).await;
+    let ((block2, _blobs2), _) = harness.make_block(state, Slot::new(1)).await;
+
+    let verified_block = harness.chain.verify_block_for_gossip(block1).await.unwrap();
+
+    if let Some((kzg_proofs, blobs)) = blobs1 {
+        let sidecars =
+            BlobSidecar::build_sidecars(blobs, verified_block.block(), kzg_proofs).unwrap();
+        for sidecar in sidecars {
+            let blob_index = sidecar.index;
+            let verified_blob = harness
+                .chain
+                .verify_blob_sidecar_for_gossip(sidecar, blob_index)
+                .unwrap();
+            harness
+                .chain
+                .process_gossip_blob(verified_blob)
+                .await
+                .unwrap();
+        }
+    }
+    harness
+        .chain
+        .process_block(
+            verified_block.block_root,
+            verified_block,
+            NotifyExecutionLayer::Yes,
+            BlockImportSource::Lookup,
+            || Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(harness.chain.take_detected_proposer_slashings().is_empty());
+
+    unwrap_err(harness.chain.verify_block_for_gossip(block2).await);
+
+    // The conflicting headers should have been turned into a slashing without a slasher.
+    let proposer_slashings = harness.chain.take_detected_proposer_slashings();
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(harness.chain.op_pool.num_proposer_slashings(), 1);
+    assert!(harness.chain.take_detected_proposer_slashings().is_empty());
+}
+
 #[tokio::test]
 async fn verify_block_for_gossip_doppelganger_detection() {
     let harness = get_harness(VALIDATOR_COUNT);
@@ -1404,7 +1588,8 @@ async fn add_base_block_to_altair_chain() {
                 deposits: altair_body.deposits.clone(),
                 voluntary_exits: altair_body.voluntary_exits.clone(),
                 _phantom: PhantomData,
-            },
+            }
+            .into(),
         },
         signature: Signature::empty(),
     });
@@ -1540,7 +1725,8 @@ async fn add_altair_block_to_base_chain() {
                 voluntary_exits: base_body.voluntary_exits.clone(),
                 sync_aggregate: SyncAggregate::empty(),
                 _phantom: PhantomData,
-            },
+            }
+            .into(),
         },
         signature: Signature::empty(),
     });
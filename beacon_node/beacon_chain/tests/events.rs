@@ -1,11 +1,12 @@
 use beacon_chain::blob_verification::GossipVerifiedBlob;
 use beacon_chain::test_utils::BeaconChainHarness;
-use eth2::types::{EventKind, SseBlobSidecar};
+use beacon_chain::ServerSentEventHandler;
+use eth2::types::{EventKind, EventTopic, SseBlobSidecar, SseHead};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use std::sync::Arc;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{BlobSidecar, EthSpec, ForkName, MinimalEthSpec};
+use types::{BlobSidecar, EthSpec, ForkName, Hash256, MinimalEthSpec, Slot};
 
 type E = MinimalEthSpec;
 
@@ -91,3 +92,55 @@ async fn blob_sidecar_event_on_process_rpc_blobs() {
     }
     assert_eq!(sse_blobs, expected_sse_blobs);
 }
+
+fn head_event(slot: u64) -> EventKind<E> {
+    EventKind::Head(SseHead {
+        slot: Slot::new(slot),
+        block: Hash256::zero(),
+        state: Hash256::zero(),
+        current_duty_dependent_root: Hash256::zero(),
+        previous_duty_dependent_root: Hash256::zero(),
+        epoch_transition: false,
+        execution_optimistic: false,
+    })
+}
+
+/// Verifies that head events missed since a given event id are replayed to a new subscriber.
+#[tokio::test]
+async fn head_events_replayed_after_last_event_id() {
+    let event_handler = ServerSentEventHandler::<E>::new_with_capacity(logging::test_logger(), 16);
+
+    let mut receiver = event_handler
+        .subscribe_with_replay(&[EventTopic::Head], None)
+        .receivers
+        .remove(0);
+    for slot in 0..4 {
+        event_handler.register(head_event(slot));
+    }
+    let ids = (0..4)
+        .map(|_| receiver.try_recv().unwrap().id)
+        .collect::<Vec<_>>();
+    assert!(ids.windows(2).all(|pair| pair[1] == pair[0] + 1));
+
+    // A client which saw the second event should be sent the last two.
+    let subscription = event_handler.subscribe_with_replay(&[EventTopic::Head], Some(ids[1]));
+    assert!(subscription.complete);
+    assert_eq!(
+        subscription
+            .missed
+            .iter()
+            .map(|event| (event.id, event.kind.clone()))
+            .collect::<Vec<_>>(),
+        vec![(ids[2], head_event(2)), (ids[3], head_event(3))]
+    );
+
+    // Events are only replayed for the requested topics.
+    let subscription = event_handler.subscribe_with_replay(&[EventTopic::Block], Some(ids[1]));
+    assert!(subscription.complete);
+    assert!(subscription.missed.is_empty());
+
+    // An id which was never issued cannot be resumed from.
+    let subscription = event_handler.subscribe_with_replay(&[EventTopic::Head], Some(ids[3] + 1));
+    assert!(!subscription.complete);
+    assert!(subscription.missed.is_empty());
+}
@@ -6,12 +6,14 @@ use beacon_chain::otb_verification_service::{
 };
 use beacon_chain::{
     canonical_head::{CachedHead, CanonicalHead},
+    chain_config::InvalidPayloadHandling,
     test_utils::{BeaconChainHarness, EphemeralHarnessType},
     BeaconChainError, BlockError, ChainConfig, ExecutionPayloadError, NotifyExecutionLayer,
     OverrideForkchoiceUpdate, StateSkipConfig, WhenSlotSkipped,
     INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
-    INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
+    INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON, INVALID_PAYLOAD_REORG_SHUTDOWN_REASON,
 };
+use eth2::types::{EventKind, SseOptimisticStatus};
 use execution_layer::{
     json_structures::{JsonForkchoiceStateV1, JsonPayloadAttributes, JsonPayloadAttributesV1},
     test_utils::ExecutionBlockGenerator,
@@ -52,16 +54,27 @@ impl InvalidPayloadRig {
         Self::new_with_spec(spec)
     }
 
-    fn new_with_spec(mut spec: ChainSpec) -> Self {
+    fn new_with_spec(spec: ChainSpec) -> Self {
+        Self::new_with_spec_and_chain_config(
+            spec,
+            ChainConfig {
+                reconstruct_historic_states: true,
+                ..ChainConfig::default()
+            },
+        )
+    }
+
+    fn new_with_chain_config(chain_config: ChainConfig) -> Self {
+        Self::new_with_spec_and_chain_config(E::default_spec(), chain_config)
+    }
+
+    fn new_with_spec_and_chain_config(mut spec: ChainSpec, chain_config: ChainConfig) -> Self {
         spec.altair_fork_epoch = Some(Epoch::new(0));
         spec.bellatrix_fork_epoch = Some(Epoch::new(0));
 
         let harness = BeaconChainHarness::builder(MainnetEthSpec)
             .spec(spec)
-            .chain_config(ChainConfig {
-                reconstruct_historic_states: true,
-                ..ChainConfig::default()
-            })
+            .chain_config(chain_config)
             .logger(test_logger())
             .deterministic_keypairs(VALIDATOR_COUNT)
             .mock_execution_layer()
@@ -933,6 +946,222 @@ async fn invalid_after_optimistic_sync() {
     assert_eq!(head, roots[1]);
 }
 
+/// Ensure that the invalidation is not applied and the node shuts down when configured to halt.
+#[tokio::test]
+async fn invalid_payload_halts_instead_of_reorg() {
+    let mut rig = InvalidPayloadRig::new_with_chain_config(ChainConfig {
+        reconstruct_historic_states: true,
+        invalid_payload_handling: InvalidPayloadHandling::Halt,
+        ..ChainConfig::default()
+    })
+    .enable_attestations();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+
+    let roots = rig.build_blocks(3, Payload::Syncing).await;
+    assert_eq!(rig.harness.head_block_root(), roots[2]);
+
+    let is_valid = Payload::Invalid {
+        latest_valid_hash: Some(rig.block_hash(roots[0])),
+    };
+    rig.import_block_parametric(is_valid, is_valid, None, |error| {
+        matches!(
+            error,
+            BlockError::BeaconChainError(BeaconChainError::InvalidPayloadReorgHalted {
+                reorg_depth: 2,
+                ..
+            })
+        )
+    })
+    .await;
+
+    assert_eq!(
+        rig.harness.shutdown_reasons(),
+        vec![ShutdownReason::Failure(
+            INVALID_PAYLOAD_REORG_SHUTDOWN_REASON
+        )]
+    );
+    for root in &roots {
+        assert!(rig.execution_status(*root).is_strictly_optimistic());
+    }
+    rig.recompute_head().await;
+    assert_eq!(rig.harness.head_block_root(), roots[2]);
+}
+
+/// Ensure that re-orgs away from invalid blocks within the maximum depth are applied.
+#[tokio::test]
+async fn invalid_payload_reorg_within_max_depth() {
+    let mut rig = InvalidPayloadRig::new_with_chain_config(ChainConfig {
+        reconstruct_historic_states: true,
+        invalid_payload_max_reorg_depth: Some(1),
+        ..ChainConfig::default()
+    })
+    .enable_attestations();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+
+    let roots = rig.build_blocks(3, Payload::Syncing).await;
+    rig.import_block(Payload::Invalid {
+        latest_valid_hash: Some(rig.block_hash(roots[1])),
+    })
+    .await;
+
+    assert!(rig.harness.shutdown_reasons().is_empty());
+    assert!(rig.execution_status(roots[2]).is_invalid());
+    rig.recompute_head().await;
+    assert_eq!(rig.harness.head_block_root(), roots[1]);
+}
+
+/// Ensure that the node halts rather than re-org further than the maximum depth.
+#[tokio::test]
+async fn invalid_payload_reorg_beyond_max_depth() {
+    let mut rig = InvalidPayloadRig::new_with_chain_config(ChainConfig {
+        reconstruct_historic_states: true,
+        invalid_payload_max_reorg_depth: Some(1),
+        ..ChainConfig::default()
+    })
+    .enable_attestations();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+
+    let roots = rig.build_blocks(3, Payload::Syncing).await;
+    let is_valid = Payload::Invalid {
+        latest_valid_hash: Some(rig.block_hash(roots[0])),
+    };
+    rig.import_block_parametric(is_valid, is_valid, None, |error| {
+        matches!(
+            error,
+            BlockError::BeaconChainError(BeaconChainError::InvalidPayloadReorgHalted {
+                reorg_depth: 2,
+                ..
+            })
+        )
+    })
+    .await;
+
+    assert_eq!(
+        rig.harness.shutdown_reasons(),
+        vec![ShutdownReason::Failure(
+            INVALID_PAYLOAD_REORG_SHUTDOWN_REASON
+        )]
+    );
+    assert!(rig.execution_status(roots[2]).is_strictly_optimistic());
+}
+
+#[tokio::test]
+async fn reverify_optimistic_payload_valid() {
+    let mut rig = InvalidPayloadRig::new().enable_attestations();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+
+    let parent = rig.import_block(Payload::Syncing).await;
+    let child = rig.import_block(Payload::Syncing).await;
+
+    // The execution engine is still syncing.
+    let (block_root, execution_status) = rig
+        .harness
+        .chain
+        .reverify_optimistic_payload(rig.block_hash(parent))
+        .await
+        .unwrap();
+    assert_eq!(block_root, parent);
+    assert!(execution_status.is_strictly_optimistic());
+
+    let mock_execution_layer = rig.harness.mock_execution_layer.as_ref().unwrap();
+    mock_execution_layer
+        .server
+        .all_payloads_valid_on_new_payload();
+    let (block_root, execution_status) = rig
+        .harness
+        .chain
+        .reverify_optimistic_payload(rig.block_hash(child))
+        .await
+        .unwrap();
+    assert_eq!(block_root, child);
+    assert!(execution_status.is_valid_and_post_bellatrix());
+    assert!(rig.execution_status(parent).is_valid_and_post_bellatrix());
+
+    // Blocks which are no longer optimistic are not sent to the execution engine again.
+    assert!(matches!(
+        rig.harness
+            .chain
+            .reverify_optimistic_payload(rig.block_hash(child))
+            .await,
+        Err(BeaconChainError::PayloadNotOptimistic { .. })
+    ));
+}
+
+/// Ensure an event is emitted when the head becomes optimistic, and again once it is verified.
+#[tokio::test]
+async fn optimistic_status_events() {
+    let mut rig = InvalidPayloadRig::new().enable_attestations();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+
+    let event_handler = rig.harness.chain.event_handler.as_ref().unwrap();
+    let mut receiver = event_handler.subscribe_optimistic_status();
+
+    let block_root = rig.import_block(Payload::Syncing).await;
+    let slot = rig.harness.chain.head_snapshot().beacon_block.slot();
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        EventKind::OptimisticStatus(SseOptimisticStatus {
+            slot,
+            block: block_root,
+            execution_optimistic: true,
+        })
+    );
+
+    // Importing another optimistic block does not change the status.
+    let block_root = rig.import_block(Payload::Syncing).await;
+    let slot = rig.harness.chain.head_snapshot().beacon_block.slot();
+    assert!(receiver.try_recv().is_err());
+
+    let mock_execution_layer = rig.harness.mock_execution_layer.as_ref().unwrap();
+    mock_execution_layer
+        .server
+        .all_payloads_valid_on_new_payload();
+    rig.harness
+        .chain
+        .reverify_optimistic_payload(rig.block_hash(block_root))
+        .await
+        .unwrap();
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        EventKind::OptimisticStatus(SseOptimisticStatus {
+            slot,
+            block: block_root,
+            execution_optimistic: false,
+        })
+    );
+}
+
+#[tokio::test]
+async fn reverify_optimistic_payload_invalid() {
+    let mut rig = InvalidPayloadRig::new().enable_attestations();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+
+    let parent = rig.import_block(Payload::Syncing).await;
+    let child = rig.import_block(Payload::Syncing).await;
+
+    let mock_execution_layer = rig.harness.mock_execution_layer.as_ref().unwrap();
+    mock_execution_layer
+        .server
+        .all_payloads_invalid_on_new_payload(rig.block_hash(parent));
+    let (_, execution_status) = rig
+        .harness
+        .chain
+        .reverify_optimistic_payload(rig.block_hash(child))
+        .await
+        .unwrap();
+    assert!(execution_status.is_invalid());
+    assert!(rig.execution_status(parent).is_strictly_optimistic());
+
+    rig.recompute_head().await;
+    assert_eq!(rig.harness.head_block_root(), parent);
+}
+
 #[tokio::test]
 async fn manually_validate_child() {
     let mut rig = InvalidPayloadRig::new().enable_attestations();
@@ -1998,6 +2227,74 @@ async fn recover_from_invalid_head_after_persist_and_reboot() {
     );
 }
 
+#[tokio::test]
+async fn proposer_preparations_persist_across_reboot() {
+    let rig = InvalidPayloadRig::new();
+    let slot_clock = rig.harness.chain.slot_clock.clone();
+    let current_epoch = rig.harness.chain.epoch().unwrap();
+    let fee_recipient = Address::repeat_byte(42);
+
+    rig.execution_layer()
+        .update_proposer_preparation(
+            current_epoch,
+            &[ProposerPreparationData {
+                validator_index: 0,
+                fee_recipient,
+            }],
+        )
+        .await;
+    rig.harness.chain.persist_proposer_preparations().unwrap();
+
+    let resumed = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(rig.harness.spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .resumed_ephemeral_store(rig.harness.chain.store.clone())
+        .mock_execution_layer()
+        .testing_slot_clock(slot_clock)
+        .build();
+    drop(rig);
+
+    let execution_layer = resumed.chain.execution_layer.as_ref().unwrap();
+    assert!(execution_layer.has_proposer_preparation_data(0).await);
+    assert!(!execution_layer.has_proposer_preparation_data(1).await);
+    assert_eq!(
+        execution_layer.get_suggested_fee_recipient(0).await,
+        fee_recipient
+    );
+}
+
+#[tokio::test]
+async fn verified_payloads_persist_across_reboot() {
+    let mut rig = InvalidPayloadRig::new();
+    rig.move_to_terminal_block();
+    let valid_root = rig.import_block(Payload::Valid).await;
+    let syncing_root = rig.import_block(Payload::Syncing).await;
+    let valid_hash = rig.block_hash(valid_root);
+    let syncing_hash = rig.block_hash(syncing_root);
+    let verified = rig.execution_layer().verified_payload_hashes();
+    assert!(verified.contains(&valid_hash));
+    assert!(!verified.contains(&syncing_hash));
+    rig.harness.chain.persist_verified_payloads().unwrap();
+
+    let resumed = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(rig.harness.spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .resumed_ephemeral_store(rig.harness.chain.store.clone())
+        .mock_execution_layer()
+        .testing_slot_clock(rig.harness.chain.slot_clock.clone())
+        .build();
+    drop(rig);
+
+    let verified = resumed
+        .chain
+        .execution_layer
+        .as_ref()
+        .unwrap()
+        .verified_payload_hashes();
+    assert!(verified.contains(&valid_hash));
+    assert!(!verified.contains(&syncing_hash));
+}
+
 #[tokio::test]
 async fn weights_after_resetting_optimistic_status() {
     let mut rig = InvalidPayloadRig::new().enable_attestations();
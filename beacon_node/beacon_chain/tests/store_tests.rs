@@ -1371,6 +1371,105 @@ async fn prunes_abandoned_fork_between_two_finalized_checkpoints() {
     assert!(!rig.chain.knows_head(&stray_head));
 }
 
+// Ensure blocks from abandoned forks are retained for `prune_forks_after_epochs`, but their
+// states are not
+#[tokio::test]
+async fn retains_abandoned_fork_blocks_until_prune_forks_after_epochs() {
+    const HONEST_VALIDATOR_COUNT: usize = 32;
+    const ADVERSARIAL_VALIDATOR_COUNT: usize = 16;
+    const VALIDATOR_COUNT: usize = HONEST_VALIDATOR_COUNT + ADVERSARIAL_VALIDATOR_COUNT;
+    const PRUNE_FORKS_AFTER_EPOCHS: u64 = 2;
+    let honest_validators: Vec<usize> = (0..HONEST_VALIDATOR_COUNT).collect();
+    let adversarial_validators: Vec<usize> = (HONEST_VALIDATOR_COUNT..VALIDATOR_COUNT).collect();
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(
+        &db_path,
+        StoreConfig {
+            prune_forks_after_epochs: PRUNE_FORKS_AFTER_EPOCHS,
+            ..StoreConfig::default()
+        },
+        test_spec::<E>(),
+    );
+    let rig = get_harness(store.clone(), VALIDATOR_COUNT);
+    let slots_per_epoch = rig.slots_per_epoch();
+    let (state, state_root) = rig.get_current_state_and_root();
+
+    let canonical_chain_slots: Vec<Slot> = (1..=rig.epoch_start_slot(1)).map(Slot::new).collect();
+    let (_, _, _, mut state) = rig
+        .add_attested_blocks_at_slots(
+            state,
+            state_root,
+            &canonical_chain_slots,
+            &honest_validators,
+        )
+        .await;
+    let canonical_chain_slot: u64 = rig.get_current_slot().into();
+
+    let stray_slots: Vec<Slot> = (canonical_chain_slot + 1..rig.epoch_start_slot(2))
+        .map(Slot::new)
+        .collect();
+    let (current_state, current_state_root) = rig.get_current_state_and_root();
+    let (stray_blocks, stray_states, stray_head, _) = rig
+        .add_attested_blocks_at_slots(
+            current_state,
+            current_state_root,
+            &stray_slots,
+            &adversarial_validators,
+        )
+        .await;
+
+    // Finalize past the fork, abandoning it.
+    let finalization_slots: Vec<Slot> = ((canonical_chain_slot + 1)
+        ..=(canonical_chain_slot + slots_per_epoch * 5))
+        .map(Slot::new)
+        .collect();
+    let state_root = state.update_tree_hash_cache().unwrap();
+    rig.add_attested_blocks_at_slots(state, state_root, &finalization_slots, &honest_validators)
+        .await;
+    let abandoned_epoch = rig
+        .chain
+        .canonical_head
+        .cached_head()
+        .finalized_checkpoint()
+        .epoch;
+    assert!(abandoned_epoch > 0);
+    assert!(!rig.chain.knows_head(&stray_head));
+
+    // The fork's states are pruned immediately, and its blocks are retained.
+    for &state_hash in stray_states.values() {
+        assert!(!rig.hot_state_exists(state_hash));
+    }
+    for &block_hash in stray_blocks.values() {
+        assert!(
+            rig.block_exists(block_hash),
+            "abandoned block {block_hash:?} should be retained",
+        );
+    }
+
+    // Finalize `PRUNE_FORKS_AFTER_EPOCHS` more epochs, after which the blocks are pruned.
+    rig.extend_chain(
+        (slots_per_epoch * (PRUNE_FORKS_AFTER_EPOCHS + 1)) as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    )
+    .await;
+    assert!(
+        rig.chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch
+            >= abandoned_epoch + PRUNE_FORKS_AFTER_EPOCHS
+    );
+    for &block_hash in stray_blocks.values() {
+        assert!(
+            !rig.block_exists(block_hash),
+            "abandoned block {block_hash:?} should have been pruned",
+        );
+    }
+    assert!(store.load_pending_fork_prunes().unwrap().pending.is_empty());
+}
+
 #[tokio::test]
 async fn pruning_does_not_touch_abandoned_block_shared_with_canonical_chain() {
     const HONEST_VALIDATOR_COUNT: usize = 32;
@@ -2609,6 +2708,24 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
         assert_eq!(state.canonical_root().unwrap(), state_root);
     }
 
+    // Attestation rewards for epochs whose states are not stored are computed by replaying
+    // blocks, and match those of the node with full history. Later epochs replay from the state
+    // replayed for the epoch before, and repeating the first epoch hits the cache.
+    let unstored_epochs = (0..)
+        .map(Epoch::new)
+        .take_while(|epoch| (*epoch + 1).end_slot(E::slots_per_epoch()) < oldest_state_slot)
+        .collect::<Vec<_>>();
+    for &rewards_epoch in unstored_epochs.iter().chain(unstored_epochs.first()) {
+        let expected = harness
+            .chain
+            .compute_attestation_rewards(rewards_epoch, vec![])
+            .unwrap();
+        let rewards = beacon_chain
+            .compute_attestation_rewards(rewards_epoch, vec![])
+            .unwrap();
+        assert_eq!(rewards, expected);
+    }
+
     // Anchor slot is still set to the slot of the checkpoint block.
     assert_eq!(store.get_anchor_slot(), Some(wss_block.slot()));
 
@@ -2849,6 +2966,72 @@ async fn finalizes_after_resuming_from_db() {
     );
 }
 
+#[tokio::test]
+async fn shuffling_caches_persist_across_restart() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize * 2 + 1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let head = harness.chain.head_snapshot();
+    let current_epoch = head.beacon_state.current_epoch();
+    let proposer_decision_root = head
+        .beacon_state
+        .proposer_shuffling_decision_root(head.beacon_block_root)
+        .unwrap();
+    let shuffling_id = AttestationShufflingId::new(
+        head.beacon_block_root,
+        &head.beacon_state,
+        RelativeEpoch::Current,
+    )
+    .unwrap();
+    assert!(harness
+        .chain
+        .beacon_proposer_cache
+        .lock()
+        .get_epoch::<E>(proposer_decision_root, current_epoch)
+        .is_some());
+    assert!(harness.chain.shuffling_cache.read().contains(&shuffling_id));
+
+    harness.chain.persist_head_and_fork_choice().unwrap();
+    harness.chain.persist_shuffling_caches().unwrap();
+
+    let original_chain = harness.chain;
+    let resumed_harness = BeaconChainHarness::<DiskHarnessType<E>>::builder(MinimalEthSpec)
+        .spec(store.get_chain_spec().clone())
+        .keypairs(KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec())
+        .resumed_disk_store(store)
+        .testing_slot_clock(original_chain.slot_clock.clone())
+        .execution_layer(original_chain.execution_layer.clone())
+        .build();
+
+    let original_proposers = original_chain
+        .beacon_proposer_cache
+        .lock()
+        .get_epoch::<E>(proposer_decision_root, current_epoch)
+        .cloned();
+    let resumed_proposers = resumed_harness
+        .chain
+        .beacon_proposer_cache
+        .lock()
+        .get_epoch::<E>(proposer_decision_root, current_epoch)
+        .cloned();
+    assert!(resumed_proposers.is_some());
+    assert_eq!(resumed_proposers, original_proposers);
+    assert!(resumed_harness
+        .chain
+        .shuffling_cache
+        .read()
+        .contains(&shuffling_id));
+}
+
 #[tokio::test]
 async fn revert_minority_fork_on_resume() {
     let validator_count = 16;
@@ -227,6 +227,75 @@ async fn find_reorgs() {
     );
 }
 
+#[tokio::test]
+async fn captures_deep_reorgs() {
+    let capture_dir = tempfile::tempdir().unwrap();
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            reorg_capture_depth: Some(2),
+            reorg_capture_dir: Some(capture_dir.path().to_path_buf()),
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    let all_validators = (0..VALIDATOR_COUNT).collect::<Vec<_>>();
+    let fork_slots = (1..=6).map(Slot::new).collect::<Vec<_>>();
+    let (genesis_state, genesis_state_root) = harness.get_current_state_and_root();
+
+    // A chain without attestations becomes the head, then is re-orged out by an attested chain.
+    let (_, _, old_head, _) = harness
+        .add_attested_blocks_at_slots(genesis_state.clone(), genesis_state_root, &fork_slots, &[])
+        .await;
+    let old_head = Hash256::from(old_head);
+    assert_eq!(harness.head_block_root(), old_head);
+    let (_, _, new_head, _) = harness
+        .add_attested_blocks_at_slots(
+            genesis_state,
+            genesis_state_root,
+            &fork_slots,
+            &all_validators,
+        )
+        .await;
+    assert_eq!(harness.head_block_root(), Hash256::from(new_head));
+
+    // The bundle is written in the background.
+    let mut summaries = vec![];
+    for _ in 0..100 {
+        summaries = harness.chain.reorg_captures().unwrap();
+        if !summaries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].old_head_block, old_head);
+    assert_eq!(summaries[0].depth, fork_slots.len() as u64);
+
+    let capture = harness
+        .chain
+        .reorg_capture(summaries[0].id)
+        .unwrap()
+        .expect("capture should exist");
+    assert_eq!(capture.old_chain.len(), fork_slots.len());
+    assert_eq!(capture.old_chain[0].block_root, old_head);
+    assert!(capture
+        .old_chain
+        .iter()
+        .chain(&capture.new_chain)
+        .all(|block| block.header.is_some()));
+    assert!(!capture.new_chain.is_empty());
+    assert!(harness
+        .chain
+        .reorg_capture(summaries[0].id + 1)
+        .unwrap()
+        .is_none());
+}
+
 #[tokio::test]
 async fn chooses_fork() {
     let harness = get_harness(VALIDATOR_COUNT);
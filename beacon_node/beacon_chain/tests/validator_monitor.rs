@@ -1,7 +1,9 @@
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::validator_monitor::{ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS};
+use beacon_chain::validator_monitor::{
+    ValidatorMonitor, ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS,
+};
 use lazy_static::lazy_static;
 use logging::test_logger;
 use types::{Epoch, EthSpec, Keypair, MainnetEthSpec, PublicKeyBytes, Slot};
@@ -387,3 +389,55 @@ async fn produces_missed_blocks() {
         );
     }
 }
+
+#[tokio::test]
+async fn attestation_effectiveness_is_recorded_and_restored() {
+    let validator_count = 16;
+    let monitored_index = 0;
+
+    let harness = get_harness(validator_count, vec![monitored_index]);
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize * 5,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let (summary, history) = {
+        let validator_monitor = harness.chain.validator_monitor.read();
+        let ranked = validator_monitor.get_attestation_effectiveness(Epoch::new(0));
+        assert_eq!(ranked.len(), 1);
+        let (index, summary) = ranked[0];
+        assert_eq!(index, monitored_index as u64);
+        (
+            summary,
+            validator_monitor.attestation_effectiveness_history(),
+        )
+    };
+
+    // Every validator attested to the correct head in every slot.
+    assert!(summary.epochs > 0);
+    assert_eq!(summary.included, summary.epochs);
+    assert_eq!(summary.effectiveness, 100.0);
+    assert_eq!(summary.mean_inclusion_distance, Some(1.0));
+
+    // History restored before the validator is monitored is applied once it is added.
+    let mut validator_monitor = ValidatorMonitor::<E>::new(
+        ValidatorMonitorConfig::default(),
+        <_>::default(),
+        test_logger(),
+    );
+    validator_monitor.restore_attestation_effectiveness(history);
+    let state = harness.get_current_state();
+    validator_monitor.process_valid_state(state.current_epoch(), &state, &harness.chain.spec);
+    assert!(validator_monitor
+        .get_attestation_effectiveness(Epoch::new(0))
+        .is_empty());
+
+    validator_monitor.add_validator_pubkey(KEYPAIRS[monitored_index].pk.compress());
+    assert_eq!(
+        validator_monitor.get_attestation_effectiveness(Epoch::new(0)),
+        vec![(monitored_index as u64, summary)]
+    );
+}
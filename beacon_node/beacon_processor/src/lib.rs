@@ -37,6 +37,10 @@
 //! Whenever the manager receives a notification that a worker has finished a parcel of work, it
 //! checks the queues to see if there are more parcels of work that can be spawned in a new worker
 //! task.
+//!
+//! Each parcel of work belongs to a `WorkClass`. If deadlines are configured, queued work which
+//! passes the deadline of its class is shed instead of being given to a worker, see the
+//! `work_class` module.
 
 use crate::work_reprocessing_queue::{
     QueuedBackfillBatch, QueuedGossipBlock, ReprocessQueueMessage,
@@ -50,28 +54,33 @@ use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::cmp;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
 use types::{
     Attestation, BeaconState, ChainSpec, Hash256, RelativeEpoch, SignedAggregateAndProof, SubnetId,
+    SyncCommitteeMessage, SyncSubnetId,
 };
 use types::{EthSpec, Slot};
+pub use work_class::{parse_work_class_deadlines, parse_work_class_workers, WorkClass};
 use work_reprocessing_queue::IgnoredRpcBlock;
 use work_reprocessing_queue::{
     spawn_reprocess_scheduler, QueuedAggregate, QueuedLightClientUpdate, QueuedRpcBlock,
     QueuedUnaggregate, ReadyWork,
 };
+use worker_pool::{PinnedThread, WorkerPool, WorkerPools};
 
 mod metrics;
+pub mod work_class;
 pub mod work_reprocessing_queue;
+mod worker_pool;
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
 ///
@@ -202,6 +211,16 @@ const WORKER_TASK_NAME: &str = "beacon_processor_worker";
 const DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE: usize = 64;
 const DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE: usize = 64;
 
+/// The maximum number of messages in a batch of unaggregated attestations, aggregates and sync
+/// committee messages, when `BeaconProcessorConfig::enable_gossip_signature_batching` is set.
+///
+/// As for the batches above, a single invalid signature causes every message in the batch to be
+/// verified individually, so larger batches waste more work when poisoned.
+const DEFAULT_MAX_GOSSIP_SIGNATURE_BATCH_SIZE: usize = 128;
+
+/// The maximum time to wait for a batch of gossip signatures to fill before it is verified.
+const DEFAULT_GOSSIP_SIGNATURE_BATCH_WINDOW: Duration = Duration::from_millis(10);
+
 /// Unique IDs used for metrics and testing.
 pub const WORKER_FREED: &str = "worker_freed";
 pub const NOTHING_TO_DO: &str = "nothing_to_do";
@@ -209,6 +228,7 @@ pub const GOSSIP_ATTESTATION: &str = "gossip_attestation";
 pub const GOSSIP_ATTESTATION_BATCH: &str = "gossip_attestation_batch";
 pub const GOSSIP_AGGREGATE: &str = "gossip_aggregate";
 pub const GOSSIP_AGGREGATE_BATCH: &str = "gossip_aggregate_batch";
+pub const GOSSIP_SIGNATURE_BATCH: &str = "gossip_signature_batch";
 pub const GOSSIP_BLOCK: &str = "gossip_block";
 pub const GOSSIP_BLOBS_SIDECAR: &str = "gossip_blobs_sidecar";
 pub const GOSSIP_BLOBS_COLUMN_SIDECAR: &str = "gossip_blobs_column_sidecar";
@@ -248,6 +268,19 @@ pub struct BeaconProcessorConfig {
     pub max_gossip_attestation_batch_size: usize,
     pub max_gossip_aggregate_batch_size: usize,
     pub enable_backfill_rate_limiting: bool,
+    /// Verify the signatures of unaggregated attestations, aggregates and sync committee messages
+    /// together, rather than in per-topic batches.
+    pub enable_gossip_signature_batching: bool,
+    pub gossip_signature_batch_window: Duration,
+    pub max_gossip_signature_batch_size: usize,
+    /// The time after which queued work of each class is shed. Classes without an entry are never
+    /// shed, and there are no entries by default.
+    pub work_class_deadlines: BTreeMap<WorkClass, Duration>,
+    /// The number of workers reserved for each class of work, in addition to `max_workers`. Work
+    /// of a class with reserved workers is only processed by those workers.
+    pub class_workers: BTreeMap<WorkClass, usize>,
+    /// Pin the blocking threads of each pool of workers to its own set of cores.
+    pub pin_workers: bool,
 }
 
 impl Default for BeaconProcessorConfig {
@@ -259,6 +292,12 @@ impl Default for BeaconProcessorConfig {
             max_gossip_attestation_batch_size: DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE,
             max_gossip_aggregate_batch_size: DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE,
             enable_backfill_rate_limiting: true,
+            enable_gossip_signature_batching: false,
+            gossip_signature_batch_window: DEFAULT_GOSSIP_SIGNATURE_BATCH_WINDOW,
+            max_gossip_signature_batch_size: DEFAULT_MAX_GOSSIP_SIGNATURE_BATCH_SIZE,
+            work_class_deadlines: BTreeMap::new(),
+            class_workers: BTreeMap::new(),
+            pin_workers: false,
         }
     }
 }
@@ -293,18 +332,74 @@ impl<E: EthSpec> Default for BeaconProcessorChannels<E> {
     }
 }
 
+/// An item in a work queue, along with the time it was queued.
+struct QueuedItem<T> {
+    item: T,
+    queued_at: Instant,
+}
+
+/// The class of the work in a queue, the time after which its work is shed and the pool of workers
+/// which processes it.
+#[derive(Clone, Copy)]
+struct QueueClass {
+    class: WorkClass,
+    deadline: Option<Duration>,
+    pool: WorkerPool,
+}
+
+impl QueueClass {
+    fn new(class: WorkClass, config: &BeaconProcessorConfig) -> Self {
+        Self {
+            class,
+            deadline: config.work_class_deadlines.get(&class).copied(),
+            pool: WorkerPool::of(class, config),
+        }
+    }
+
+    /// Returns `true` if an item queued at `queued_at` has passed its deadline.
+    fn is_expired(&self, queued_at: Instant, now: Instant) -> bool {
+        self.deadline.map_or(false, |deadline| {
+            now.saturating_duration_since(queued_at) > deadline
+        })
+    }
+
+    /// Records the time that an item spent in the queue, or that it was shed.
+    ///
+    /// Returns `true` if the item should be processed.
+    fn on_dequeue(&self, queued_at: Instant, now: Instant) -> bool {
+        if self.is_expired(queued_at, now) {
+            metrics::inc_counter_vec(
+                &metrics::BEACON_PROCESSOR_WORK_EVENTS_EXPIRED_COUNT,
+                &[self.class.as_str()],
+            );
+            false
+        } else {
+            metrics::observe_timer_vec(
+                &metrics::BEACON_PROCESSOR_QUEUE_LATENCY_SECONDS,
+                &[self.class.as_str()],
+                now.saturating_duration_since(queued_at),
+            );
+            true
+        }
+    }
+}
+
 /// A simple first-in-first-out queue with a maximum length.
+///
+/// Items which pass the deadline of the queue's class are shed when the queue is next used.
 struct FifoQueue<T> {
-    queue: VecDeque<T>,
+    queue: VecDeque<QueuedItem<T>>,
     max_length: usize,
+    class: QueueClass,
 }
 
 impl<T> FifoQueue<T> {
     /// Create a new, empty queue with the given length.
-    pub fn new(max_length: usize) -> Self {
+    pub fn new(max_length: usize, class: QueueClass) -> Self {
         Self {
             queue: VecDeque::default(),
             max_length,
+            class,
         }
     }
 
@@ -312,6 +407,7 @@ impl<T> FifoQueue<T> {
     ///
     /// Drops `item` if the queue is full.
     pub fn push(&mut self, item: T, item_desc: &str, log: &Logger) {
+        self.shed_expired(Instant::now());
         if self.queue.len() == self.max_length {
             error!(
                 log,
@@ -321,33 +417,76 @@ impl<T> FifoQueue<T> {
                 "queue" => item_desc,
             )
         } else {
-            self.queue.push_back(item);
+            self.queue.push_back(QueuedItem {
+                item,
+                queued_at: Instant::now(),
+            });
         }
     }
 
-    /// Remove the next item from the queue.
+    /// Remove the next item from the queue, shedding any expired items before it.
     pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
+        let now = Instant::now();
+        while let Some(queued) = self.queue.pop_front() {
+            if self.class.on_dequeue(queued.queued_at, now) {
+                return Some(queued.item);
+            }
+        }
+        None
+    }
+
+    /// Shed the expired items, which are all at the front of the queue.
+    fn shed_expired(&mut self, now: Instant) {
+        while let Some(queued) = self.queue.front() {
+            if !self.class.is_expired(queued.queued_at, now) {
+                break;
+            }
+            let queued_at = queued.queued_at;
+            self.queue.pop_front();
+            self.class.on_dequeue(queued_at, now);
+        }
     }
 
     /// Returns the current length of the queue.
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Remove the next item from the queue, if its work is processed by `pool`.
+    pub fn pop_from(&mut self, pool: WorkerPool) -> Option<T> {
+        if self.class.pool == pool {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the length of the queue, or zero if its work is not processed by `pool`.
+    pub fn len_in(&self, pool: WorkerPool) -> usize {
+        if self.class.pool == pool {
+            self.len()
+        } else {
+            0
+        }
+    }
 }
 
 /// A simple last-in-first-out queue with a maximum length.
+///
+/// Items which pass the deadline of the queue's class are shed when the queue is next used.
 struct LifoQueue<T> {
-    queue: VecDeque<T>,
+    queue: VecDeque<QueuedItem<T>>,
     max_length: usize,
+    class: QueueClass,
 }
 
 impl<T> LifoQueue<T> {
     /// Create a new, empty queue with the given length.
-    pub fn new(max_length: usize) -> Self {
+    pub fn new(max_length: usize, class: QueueClass) -> Self {
         Self {
             queue: VecDeque::default(),
             max_length,
+            class,
         }
     }
 
@@ -355,15 +494,39 @@ impl<T> LifoQueue<T> {
     ///
     /// If the queue is full, the item at the back of the queue is dropped.
     pub fn push(&mut self, item: T) {
+        self.shed_expired(Instant::now());
         if self.queue.len() == self.max_length {
             self.queue.pop_back();
         }
-        self.queue.push_front(item);
+        self.queue.push_front(QueuedItem {
+            item,
+            queued_at: Instant::now(),
+        });
     }
 
     /// Remove the next item from the queue.
+    ///
+    /// If the next item has expired then so has every other item, and the queue is emptied.
     pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
+        let now = Instant::now();
+        while let Some(queued) = self.queue.pop_front() {
+            if self.class.on_dequeue(queued.queued_at, now) {
+                return Some(queued.item);
+            }
+        }
+        None
+    }
+
+    /// Shed the expired items, which are all at the back of the queue.
+    fn shed_expired(&mut self, now: Instant) {
+        while let Some(queued) = self.queue.back() {
+            if !self.class.is_expired(queued.queued_at, now) {
+                break;
+            }
+            let queued_at = queued.queued_at;
+            self.queue.pop_back();
+            self.class.on_dequeue(queued_at, now);
+        }
     }
 
     /// Returns `true` if the queue is full.
@@ -375,6 +538,24 @@ impl<T> LifoQueue<T> {
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Remove the next item from the queue, if its work is processed by `pool`.
+    pub fn pop_from(&mut self, pool: WorkerPool) -> Option<T> {
+        if self.class.pool == pool {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the length of the queue, or zero if its work is not processed by `pool`.
+    pub fn len_in(&self, pool: WorkerPool) -> usize {
+        if self.class.pool == pool {
+            self.len()
+        } else {
+            0
+        }
+    }
 }
 
 /// A handle that sends a message on the provided channel to a receiver when it gets dropped.
@@ -520,6 +701,25 @@ pub struct GossipAggregatePackage<E: EthSpec> {
     pub seen_timestamp: Duration,
 }
 
+/// Items required to verify a batch of gossip sync committee messages.
+#[derive(Debug)]
+pub struct GossipSyncSignaturePackage {
+    pub message_id: MessageId,
+    pub peer_id: PeerId,
+    pub sync_signature: Box<SyncCommitteeMessage>,
+    pub subnet_id: SyncSubnetId,
+    pub seen_timestamp: Duration,
+}
+
+/// Unaggregated attestations, aggregates and sync committee messages to have their signatures
+/// verified in a single batch.
+#[derive(Debug)]
+pub struct GossipSignatureBatch<E: EthSpec> {
+    pub attestations: Vec<GossipAttestationPackage<E>>,
+    pub aggregates: Vec<GossipAggregatePackage<E>>,
+    pub sync_messages: Vec<GossipSyncSignaturePackage>,
+}
+
 #[derive(Clone)]
 pub struct BeaconProcessorSend<E: EthSpec>(pub mpsc::Sender<WorkEvent<E>>);
 
@@ -542,6 +742,7 @@ impl<E: EthSpec> BeaconProcessorSend<E> {
 pub type AsyncFn = Pin<Box<dyn Future<Output = ()> + Send + Sync>>;
 pub type BlockingFn = Box<dyn FnOnce() + Send + Sync>;
 pub type BlockingFnWithManualSendOnIdle = Box<dyn FnOnce(SendOnDrop) + Send + Sync>;
+pub type GossipSignatureBatchFn<E> = Box<dyn FnOnce(GossipSignatureBatch<E>) + Send + Sync>;
 pub enum BlockingOrAsync {
     Blocking(BlockingFn),
     Async(AsyncFn),
@@ -554,6 +755,7 @@ pub enum Work<E: EthSpec> {
         attestation: Box<GossipAttestationPackage<E>>,
         process_individual: Box<dyn FnOnce(GossipAttestationPackage<E>) + Send + Sync>,
         process_batch: Box<dyn FnOnce(Vec<GossipAttestationPackage<E>>) + Send + Sync>,
+        process_signature_batch: GossipSignatureBatchFn<E>,
     },
     UnknownBlockAttestation {
         process_fn: BlockingFn,
@@ -566,6 +768,7 @@ pub enum Work<E: EthSpec> {
         aggregate: Box<GossipAggregatePackage<E>>,
         process_individual: Box<dyn FnOnce(GossipAggregatePackage<E>) + Send + Sync>,
         process_batch: Box<dyn FnOnce(Vec<GossipAggregatePackage<E>>) + Send + Sync>,
+        process_signature_batch: GossipSignatureBatchFn<E>,
     },
     UnknownBlockAggregate {
         process_fn: BlockingFn,
@@ -578,6 +781,10 @@ pub enum Work<E: EthSpec> {
         aggregates: Vec<GossipAggregatePackage<E>>,
         process_batch: Box<dyn FnOnce(Vec<GossipAggregatePackage<E>>) + Send + Sync>,
     },
+    GossipSignatureBatch {
+        batch: GossipSignatureBatch<E>,
+        process_batch: GossipSignatureBatchFn<E>,
+    },
     GossipBlock(AsyncFn),
     GossipBlobSidecar(AsyncFn),
     GossipDataColumnSidecar(AsyncFn),
@@ -589,7 +796,11 @@ pub enum Work<E: EthSpec> {
     GossipVoluntaryExit(BlockingFn),
     GossipProposerSlashing(BlockingFn),
     GossipAttesterSlashing(BlockingFn),
-    GossipSyncSignature(BlockingFn),
+    GossipSyncSignature {
+        sync_signature: Box<GossipSyncSignaturePackage>,
+        process_individual: Box<dyn FnOnce(GossipSyncSignaturePackage) + Send + Sync>,
+        process_signature_batch: GossipSignatureBatchFn<E>,
+    },
     GossipSyncContribution(BlockingFn),
     GossipLightClientFinalityUpdate(BlockingFn),
     GossipLightClientOptimisticUpdate(BlockingFn),
@@ -631,6 +842,7 @@ impl<E: EthSpec> Work<E> {
             Work::GossipAttestationBatch { .. } => GOSSIP_ATTESTATION_BATCH,
             Work::GossipAggregate { .. } => GOSSIP_AGGREGATE,
             Work::GossipAggregateBatch { .. } => GOSSIP_AGGREGATE_BATCH,
+            Work::GossipSignatureBatch { .. } => GOSSIP_SIGNATURE_BATCH,
             Work::GossipBlock(_) => GOSSIP_BLOCK,
             Work::GossipBlobSidecar(_) => GOSSIP_BLOBS_SIDECAR,
             Work::GossipDataColumnSidecar(_) => GOSSIP_BLOBS_COLUMN_SIDECAR,
@@ -638,7 +850,7 @@ impl<E: EthSpec> Work<E> {
             Work::GossipVoluntaryExit(_) => GOSSIP_VOLUNTARY_EXIT,
             Work::GossipProposerSlashing(_) => GOSSIP_PROPOSER_SLASHING,
             Work::GossipAttesterSlashing(_) => GOSSIP_ATTESTER_SLASHING,
-            Work::GossipSyncSignature(_) => GOSSIP_SYNC_SIGNATURE,
+            Work::GossipSyncSignature { .. } => GOSSIP_SYNC_SIGNATURE,
             Work::GossipSyncContribution(_) => GOSSIP_SYNC_CONTRIBUTION,
             Work::GossipLightClientFinalityUpdate(_) => GOSSIP_LIGHT_CLIENT_FINALITY_UPDATE,
             Work::GossipLightClientOptimisticUpdate(_) => GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE,
@@ -663,16 +875,59 @@ impl<E: EthSpec> Work<E> {
             Work::ApiRequestP1 { .. } => API_REQUEST_P1,
         }
     }
+
+    /// Returns the class of the work, which determines its deadline.
+    pub fn class(&self) -> WorkClass {
+        match self {
+            Work::GossipBlock(_)
+            | Work::GossipBlobSidecar(_)
+            | Work::GossipDataColumnSidecar(_)
+            | Work::DelayedImportBlock { .. } => WorkClass::Block,
+            Work::RpcBlock { .. }
+            | Work::RpcBlobs { .. }
+            | Work::IgnoredRpcBlock { .. }
+            | Work::ChainSegment(_) => WorkClass::Sync,
+            Work::GossipAggregate { .. }
+            | Work::GossipAggregateBatch { .. }
+            | Work::UnknownBlockAggregate { .. } => WorkClass::Aggregate,
+            Work::GossipAttestation { .. }
+            | Work::GossipAttestationBatch { .. }
+            | Work::GossipSignatureBatch { .. }
+            | Work::UnknownBlockAttestation { .. } => WorkClass::Attestation,
+            Work::GossipSyncSignature { .. } | Work::GossipSyncContribution(_) => {
+                WorkClass::SyncCommittee
+            }
+            Work::GossipVoluntaryExit(_)
+            | Work::GossipProposerSlashing(_)
+            | Work::GossipAttesterSlashing(_)
+            | Work::GossipBlsToExecutionChange(_) => WorkClass::Operation,
+            Work::GossipLightClientFinalityUpdate(_)
+            | Work::GossipLightClientOptimisticUpdate(_)
+            | Work::UnknownLightClientOptimisticUpdate { .. }
+            | Work::LightClientBootstrapRequest(_)
+            | Work::LightClientOptimisticUpdateRequest(_)
+            | Work::LightClientFinalityUpdateRequest(_) => WorkClass::LightClient,
+            Work::Status(_)
+            | Work::BlocksByRangeRequest(_)
+            | Work::BlocksByRootsRequest(_)
+            | Work::BlobsByRangeRequest(_)
+            | Work::BlobsByRootsRequest(_) => WorkClass::Rpc,
+            Work::ApiRequestP0(_) | Work::ApiRequestP1(_) => WorkClass::Api,
+            Work::ChainSegmentBackfill(_) => WorkClass::Backfill,
+        }
+    }
 }
 
 /// Unifies all the messages processed by the `BeaconProcessor`.
 enum InboundEvent<E: EthSpec> {
-    /// A worker has completed a task and is free.
-    WorkerIdle,
+    /// A worker in the given pool has completed a task and is free.
+    WorkerIdle(WorkerPool),
     /// There is new work to be done.
     WorkEvent(WorkEvent<E>),
     /// A work event that was queued for re-processing has become ready.
     ReprocessingWork(WorkEvent<E>),
+    /// The window for collecting a batch of gossip signatures has elapsed.
+    GossipSignatureBatchWindowElapsed,
 }
 
 /// Combines the various incoming event streams for the `BeaconProcessor` into a single stream.
@@ -681,11 +936,13 @@ enum InboundEvent<E: EthSpec> {
 /// control (specifically in the ordering of event processing).
 struct InboundEvents<E: EthSpec> {
     /// Used by workers when they finish a task.
-    idle_rx: mpsc::Receiver<()>,
+    idle_rx: mpsc::Receiver<WorkerPool>,
     /// Used by upstream processes to send new work to the `BeaconProcessor`.
     event_rx: mpsc::Receiver<WorkEvent<E>>,
     /// Used internally for queuing work ready to be re-processed.
     reprocess_work_rx: mpsc::Receiver<ReadyWork>,
+    /// Fires when the window for collecting the pending batch of gossip signatures has elapsed.
+    gossip_signature_batch_window: Option<Pin<Box<tokio::time::Sleep>>>,
 }
 
 impl<E: EthSpec> Stream for InboundEvents<E> {
@@ -695,8 +952,8 @@ impl<E: EthSpec> Stream for InboundEvents<E> {
         // Always check for idle workers before anything else. This allows us to ensure that a big
         // stream of new events doesn't suppress the processing of existing events.
         match self.idle_rx.poll_recv(cx) {
-            Poll::Ready(Some(())) => {
-                return Poll::Ready(Some(InboundEvent::WorkerIdle));
+            Poll::Ready(Some(pool)) => {
+                return Poll::Ready(Some(InboundEvent::WorkerIdle(pool)));
             }
             Poll::Ready(None) => {
                 return Poll::Ready(None);
@@ -704,6 +961,15 @@ impl<E: EthSpec> Stream for InboundEvents<E> {
             Poll::Pending => {}
         }
 
+        // Check the batch window before new work, so that a stream of new events doesn't delay
+        // the verification of the pending batch.
+        if let Some(window) = self.gossip_signature_batch_window.as_mut() {
+            if window.as_mut().poll(cx).is_ready() {
+                self.gossip_signature_batch_window = None;
+                return Poll::Ready(Some(InboundEvent::GossipSignatureBatchWindowElapsed));
+            }
+        }
+
         // Poll for delayed blocks before polling for new work. It might be the case that a delayed
         // block is required to successfully process some new work.
         match self.reprocess_work_rx.poll_recv(cx) {
@@ -766,67 +1032,149 @@ impl<E: EthSpec> BeaconProcessor<E> {
         queue_lengths: BeaconProcessorQueueLengths,
     ) -> Result<(), String> {
         // Used by workers to communicate that they are finished a task.
-        let (idle_tx, idle_rx) = mpsc::channel::<()>(MAX_IDLE_QUEUE_LEN);
+        let (idle_tx, idle_rx) = mpsc::channel::<WorkerPool>(MAX_IDLE_QUEUE_LEN);
+
+        let mut worker_pools = WorkerPools::new(&self.config, num_cpus::get())?;
+        if self.config.pin_workers && !PinnedThread::is_supported() {
+            warn!(
+                self.log,
+                "Unable to pin beacon processor workers";
+                "msg" => "pinning threads to cores is only supported on Linux"
+            );
+        }
+
+        let queue_class = |class| QueueClass::new(class, &self.config);
 
         // Using LIFO queues for attestations since validator profits rely upon getting fresh
         // attestations into blocks. Additionally, later attestations contain more information than
         // earlier ones, so we consider them more valuable.
-        let mut aggregate_queue = LifoQueue::new(queue_lengths.aggregate_queue);
+        let mut aggregate_queue = LifoQueue::new(
+            queue_lengths.aggregate_queue,
+            queue_class(WorkClass::Aggregate),
+        );
         let mut aggregate_debounce = TimeLatch::default();
-        let mut attestation_queue = LifoQueue::new(queue_lengths.attestation_queue);
+        let mut attestation_queue = LifoQueue::new(
+            queue_lengths.attestation_queue,
+            queue_class(WorkClass::Attestation),
+        );
         let mut attestation_debounce = TimeLatch::default();
-        let mut unknown_block_aggregate_queue =
-            LifoQueue::new(queue_lengths.unknown_block_aggregate_queue);
-        let mut unknown_block_attestation_queue =
-            LifoQueue::new(queue_lengths.unknown_block_attestation_queue);
+        let mut unknown_block_aggregate_queue = LifoQueue::new(
+            queue_lengths.unknown_block_aggregate_queue,
+            queue_class(WorkClass::Aggregate),
+        );
+        let mut unknown_block_attestation_queue = LifoQueue::new(
+            queue_lengths.unknown_block_attestation_queue,
+            queue_class(WorkClass::Attestation),
+        );
 
-        let mut sync_message_queue = LifoQueue::new(queue_lengths.sync_message_queue);
-        let mut sync_contribution_queue = LifoQueue::new(queue_lengths.sync_contribution_queue);
+        let mut sync_message_queue = LifoQueue::new(
+            queue_lengths.sync_message_queue,
+            queue_class(WorkClass::SyncCommittee),
+        );
+        let mut sync_contribution_queue = LifoQueue::new(
+            queue_lengths.sync_contribution_queue,
+            queue_class(WorkClass::SyncCommittee),
+        );
 
         // Using a FIFO queue for voluntary exits since it prevents exit censoring. I don't have
         // a strong feeling about queue type for exits.
-        let mut gossip_voluntary_exit_queue =
-            FifoQueue::new(queue_lengths.gossip_voluntary_exit_queue);
+        let mut gossip_voluntary_exit_queue = FifoQueue::new(
+            queue_lengths.gossip_voluntary_exit_queue,
+            queue_class(WorkClass::Operation),
+        );
 
         // Using a FIFO queue for slashing to prevent people from flushing their slashings from the
         // queues with lots of junk messages.
-        let mut gossip_proposer_slashing_queue =
-            FifoQueue::new(queue_lengths.gossip_proposer_slashing_queue);
-        let mut gossip_attester_slashing_queue =
-            FifoQueue::new(queue_lengths.gossip_attester_slashing_queue);
+        let mut gossip_proposer_slashing_queue = FifoQueue::new(
+            queue_lengths.gossip_proposer_slashing_queue,
+            queue_class(WorkClass::Operation),
+        );
+        let mut gossip_attester_slashing_queue = FifoQueue::new(
+            queue_lengths.gossip_attester_slashing_queue,
+            queue_class(WorkClass::Operation),
+        );
 
         // Using a FIFO queue for light client updates to maintain sequence order.
-        let mut finality_update_queue = FifoQueue::new(queue_lengths.finality_update_queue);
-        let mut optimistic_update_queue = FifoQueue::new(queue_lengths.optimistic_update_queue);
-        let mut unknown_light_client_update_queue =
-            FifoQueue::new(queue_lengths.unknown_light_client_update_queue);
+        let mut finality_update_queue = FifoQueue::new(
+            queue_lengths.finality_update_queue,
+            queue_class(WorkClass::LightClient),
+        );
+        let mut optimistic_update_queue = FifoQueue::new(
+            queue_lengths.optimistic_update_queue,
+            queue_class(WorkClass::LightClient),
+        );
+        let mut unknown_light_client_update_queue = FifoQueue::new(
+            queue_lengths.unknown_light_client_update_queue,
+            queue_class(WorkClass::LightClient),
+        );
 
         // Using a FIFO queue since blocks need to be imported sequentially.
-        let mut rpc_block_queue = FifoQueue::new(queue_lengths.rpc_block_queue);
-        let mut rpc_blob_queue = FifoQueue::new(queue_lengths.rpc_blob_queue);
-        let mut chain_segment_queue = FifoQueue::new(queue_lengths.chain_segment_queue);
-        let mut backfill_chain_segment = FifoQueue::new(queue_lengths.backfill_chain_segment);
-        let mut gossip_block_queue = FifoQueue::new(queue_lengths.gossip_block_queue);
-        let mut gossip_blob_queue = FifoQueue::new(queue_lengths.gossip_blob_queue);
-        let mut gossip_data_column_queue = FifoQueue::new(queue_lengths.gossip_data_column_queue);
-        let mut delayed_block_queue = FifoQueue::new(queue_lengths.delayed_block_queue);
-
-        let mut status_queue = FifoQueue::new(queue_lengths.status_queue);
-        let mut bbrange_queue = FifoQueue::new(queue_lengths.bbrange_queue);
-        let mut bbroots_queue = FifoQueue::new(queue_lengths.bbroots_queue);
-        let mut blbroots_queue = FifoQueue::new(queue_lengths.blbroots_queue);
-        let mut blbrange_queue = FifoQueue::new(queue_lengths.blbrange_queue);
-
-        let mut gossip_bls_to_execution_change_queue =
-            FifoQueue::new(queue_lengths.gossip_bls_to_execution_change_queue);
-
-        let mut lc_bootstrap_queue = FifoQueue::new(queue_lengths.lc_bootstrap_queue);
-        let mut lc_optimistic_update_queue =
-            FifoQueue::new(queue_lengths.lc_optimistic_update_queue);
-        let mut lc_finality_update_queue = FifoQueue::new(queue_lengths.lc_finality_update_queue);
-
-        let mut api_request_p0_queue = FifoQueue::new(queue_lengths.api_request_p0_queue);
-        let mut api_request_p1_queue = FifoQueue::new(queue_lengths.api_request_p1_queue);
+        let mut rpc_block_queue =
+            FifoQueue::new(queue_lengths.rpc_block_queue, queue_class(WorkClass::Sync));
+        let mut rpc_blob_queue =
+            FifoQueue::new(queue_lengths.rpc_blob_queue, queue_class(WorkClass::Sync));
+        let mut chain_segment_queue = FifoQueue::new(
+            queue_lengths.chain_segment_queue,
+            queue_class(WorkClass::Sync),
+        );
+        let mut backfill_chain_segment = FifoQueue::new(
+            queue_lengths.backfill_chain_segment,
+            queue_class(WorkClass::Backfill),
+        );
+        let mut gossip_block_queue = FifoQueue::new(
+            queue_lengths.gossip_block_queue,
+            queue_class(WorkClass::Block),
+        );
+        let mut gossip_blob_queue = FifoQueue::new(
+            queue_lengths.gossip_blob_queue,
+            queue_class(WorkClass::Block),
+        );
+        let mut gossip_data_column_queue = FifoQueue::new(
+            queue_lengths.gossip_data_column_queue,
+            queue_class(WorkClass::Block),
+        );
+        let mut delayed_block_queue = FifoQueue::new(
+            queue_lengths.delayed_block_queue,
+            queue_class(WorkClass::Block),
+        );
+
+        let mut status_queue =
+            FifoQueue::new(queue_lengths.status_queue, queue_class(WorkClass::Rpc));
+        let mut bbrange_queue =
+            FifoQueue::new(queue_lengths.bbrange_queue, queue_class(WorkClass::Rpc));
+        let mut bbroots_queue =
+            FifoQueue::new(queue_lengths.bbroots_queue, queue_class(WorkClass::Rpc));
+        let mut blbroots_queue =
+            FifoQueue::new(queue_lengths.blbroots_queue, queue_class(WorkClass::Rpc));
+        let mut blbrange_queue =
+            FifoQueue::new(queue_lengths.blbrange_queue, queue_class(WorkClass::Rpc));
+
+        let mut gossip_bls_to_execution_change_queue = FifoQueue::new(
+            queue_lengths.gossip_bls_to_execution_change_queue,
+            queue_class(WorkClass::Operation),
+        );
+
+        let mut lc_bootstrap_queue = FifoQueue::new(
+            queue_lengths.lc_bootstrap_queue,
+            queue_class(WorkClass::LightClient),
+        );
+        let mut lc_optimistic_update_queue = FifoQueue::new(
+            queue_lengths.lc_optimistic_update_queue,
+            queue_class(WorkClass::LightClient),
+        );
+        let mut lc_finality_update_queue = FifoQueue::new(
+            queue_lengths.lc_finality_update_queue,
+            queue_class(WorkClass::LightClient),
+        );
+
+        let mut api_request_p0_queue = FifoQueue::new(
+            queue_lengths.api_request_p0_queue,
+            queue_class(WorkClass::Api),
+        );
+        let mut api_request_p1_queue = FifoQueue::new(
+            queue_lengths.api_request_p1_queue,
+            queue_class(WorkClass::Api),
+        );
 
         // Channels for sending work to the re-process scheduler (`work_reprocessing_tx`) and to
         // receive them back once they are ready (`ready_work_rx`).
@@ -850,16 +1198,46 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 idle_rx,
                 event_rx,
                 reprocess_work_rx: ready_work_rx,
+                gossip_signature_batch_window: None,
             };
 
             let enable_backfill_rate_limiting = self.config.enable_backfill_rate_limiting;
+            let enable_gossip_signature_batching = self.config.enable_gossip_signature_batching;
+            // The time at which the pending gossip signatures should be verified, even if they do
+            // not fill a batch.
+            let mut gossip_signature_batch_deadline: Option<Instant> = None;
 
             loop {
+                // The pool to give work to when there is no new work event.
+                let mut idle_pool = WorkerPool::Shared;
                 let work_event = match inbound_events.next().await {
-                    Some(InboundEvent::WorkerIdle) => {
+                    Some(InboundEvent::WorkerIdle(pool)) => {
                         self.current_workers = self.current_workers.saturating_sub(1);
+                        worker_pools.on_idle(pool);
+                        idle_pool = pool;
                         None
                     }
+                    Some(InboundEvent::GossipSignatureBatchWindowElapsed) => {
+                        // Give the batch to a pool with a free worker and pending signatures. If
+                        // there is none, the batch will be verified once a worker is idle.
+                        let pool = [&aggregate_queue, &attestation_queue, &sync_message_queue]
+                            .into_iter()
+                            .map(|queue| queue.class.pool)
+                            .find(|pool| {
+                                worker_pools.can_spawn(*pool)
+                                    && aggregate_queue.len_in(*pool)
+                                        + attestation_queue.len_in(*pool)
+                                        + sync_message_queue.len_in(*pool)
+                                        > 0
+                            });
+                        match pool {
+                            Some(pool) => {
+                                idle_pool = pool;
+                                None
+                            }
+                            None => continue,
+                        }
+                    }
                     Some(InboundEvent::WorkEvent(event)) if enable_backfill_rate_limiting => {
                         match QueuedBackfillBatch::try_from(event) {
                             Ok(backfill_batch) => {
@@ -935,47 +1313,95 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     let _ = work_journal_tx.try_send(id);
                 }
 
-                let can_spawn = self.current_workers < self.config.max_workers;
+                let pool = work_event.as_ref().map_or(idle_pool, |event| {
+                    WorkerPool::of(event.work.class(), &self.config)
+                });
+                let can_spawn = worker_pools.can_spawn(pool);
                 let drop_during_sync = work_event
                     .as_ref()
                     .map_or(false, |event| event.drop_during_sync);
 
-                let idle_tx = idle_tx.clone();
+                let idle_tx = IdleTx {
+                    tx: idle_tx.clone(),
+                    pool,
+                    cores: worker_pools.cores(pool),
+                };
+                let num_workers = self.current_workers;
                 match work_event {
                     // There is no new work event, but we are able to spawn a new worker.
                     //
                     // We don't check the `work.drop_during_sync` here. We assume that if it made
                     // it into the queue at any point then we should process it.
                     None if can_spawn => {
+                        let num_pending_signatures = aggregate_queue.len_in(pool)
+                            + attestation_queue.len_in(pool)
+                            + sync_message_queue.len_in(pool);
+
                         // Check for chain segments first, they're the most efficient way to get
                         // blocks into the system.
-                        if let Some(item) = chain_segment_queue.pop() {
+                        if let Some(item) = chain_segment_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check sync blocks before gossip blocks, since we've already explicitly
                         // requested these blocks.
-                        } else if let Some(item) = rpc_block_queue.pop() {
+                        } else if let Some(item) = rpc_block_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = rpc_blob_queue.pop() {
+                        } else if let Some(item) = rpc_blob_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check delayed blocks before gossip blocks, the gossip blocks might rely
                         // on the delayed ones.
-                        } else if let Some(item) = delayed_block_queue.pop() {
+                        } else if let Some(item) = delayed_block_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check gossip blocks before gossip attestations, since a block might be
                         // required to verify some attestations.
-                        } else if let Some(item) = gossip_block_queue.pop() {
+                        } else if let Some(item) = gossip_block_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_blob_queue.pop() {
+                        } else if let Some(item) = gossip_blob_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_data_column_queue.pop() {
+                        } else if let Some(item) = gossip_data_column_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check the priority 0 API requests after blocks and blobs, but before attestations.
-                        } else if let Some(item) = api_request_p0_queue.pop() {
+                        } else if let Some(item) = api_request_p0_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
+                        // If enabled, verify aggregates, unaggregates and sync committee messages
+                        // together once the batch is full or its window has elapsed. Until then,
+                        // the worker may be given lower priority work.
+                        } else if enable_gossip_signature_batching
+                            && num_pending_signatures > 0
+                            && (num_pending_signatures
+                                >= self.config.max_gossip_signature_batch_size
+                                || gossip_signature_batch_deadline
+                                    .map_or(true, |deadline| Instant::now() >= deadline))
+                        {
+                            if let Some(item) = pop_gossip_signature_batch(
+                                &mut aggregate_queue,
+                                &mut attestation_queue,
+                                &mut sync_message_queue,
+                                pool,
+                                self.config.max_gossip_signature_batch_size,
+                                &self.log,
+                            ) {
+                                self.spawn_worker(item, idle_tx);
+                            }
+
+                            // Any signatures left behind have already waited long enough. Wake
+                            // the manager so that they may be given to a worker in another pool.
+                            let num_remaining = aggregate_queue.len()
+                                + attestation_queue.len()
+                                + sync_message_queue.len();
+                            gossip_signature_batch_deadline =
+                                (num_remaining > 0).then(Instant::now);
+                            if num_remaining > 0
+                                && inbound_events.gossip_signature_batch_window.is_none()
+                            {
+                                inbound_events.gossip_signature_batch_window =
+                                    Some(Box::pin(tokio::time::sleep(Duration::ZERO)));
+                            }
                         // Check the aggregates, *then* the unaggregates since we assume that
                         // aggregates are more valuable to local validators and effectively give us
                         // more information with less signature verification time.
-                        } else if aggregate_queue.len() > 0 {
+                        } else if !enable_gossip_signature_batching
+                            && aggregate_queue.len_in(pool) > 0
+                        {
                             let batch_size = cmp::min(
                                 aggregate_queue.len(),
                                 self.config.max_gossip_aggregate_batch_size,
@@ -983,7 +1409,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
 
                             if batch_size < 2 {
                                 // One single aggregate is in the queue, process it individually.
-                                if let Some(item) = aggregate_queue.pop() {
+                                if let Some(item) = aggregate_queue.pop_from(pool) {
                                     self.spawn_worker(item, idle_tx);
                                 }
                             } else {
@@ -995,12 +1421,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                 let mut aggregates = Vec::with_capacity(batch_size);
                                 let mut process_batch_opt = None;
                                 for _ in 0..batch_size {
-                                    if let Some(item) = aggregate_queue.pop() {
+                                    if let Some(item) = aggregate_queue.pop_from(pool) {
                                         match item {
                                             Work::GossipAggregate {
                                                 aggregate,
                                                 process_individual: _,
                                                 process_batch,
+                                                process_signature_batch: _,
                                             } => {
                                                 aggregates.push(*aggregate);
                                                 if process_batch_opt.is_none() {
@@ -1035,7 +1462,9 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         // Check the unaggregated attestation queue.
                         //
                         // Potentially use batching.
-                        } else if attestation_queue.len() > 0 {
+                        } else if !enable_gossip_signature_batching
+                            && attestation_queue.len_in(pool) > 0
+                        {
                             let batch_size = cmp::min(
                                 attestation_queue.len(),
                                 self.config.max_gossip_attestation_batch_size,
@@ -1043,7 +1472,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
 
                             if batch_size < 2 {
                                 // One single attestation is in the queue, process it individually.
-                                if let Some(item) = attestation_queue.pop() {
+                                if let Some(item) = attestation_queue.pop_from(pool) {
                                     self.spawn_worker(item, idle_tx);
                                 }
                             } else {
@@ -1055,12 +1484,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                 let mut attestations = Vec::with_capacity(batch_size);
                                 let mut process_batch_opt = None;
                                 for _ in 0..batch_size {
-                                    if let Some(item) = attestation_queue.pop() {
+                                    if let Some(item) = attestation_queue.pop_from(pool) {
                                         match item {
                                             Work::GossipAttestation {
                                                 attestation,
                                                 process_individual: _,
                                                 process_batch,
+                                                process_signature_batch: _,
                                             } => {
                                                 attestations.push(*attestation);
                                                 if process_batch_opt.is_none() {
@@ -1095,59 +1525,65 @@ impl<E: EthSpec> BeaconProcessor<E> {
                             }
                         // Check sync committee messages after attestations as their rewards are lesser
                         // and they don't influence fork choice.
-                        } else if let Some(item) = sync_contribution_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = sync_message_queue.pop() {
+                        } else if let Some(item) = sync_contribution_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
+                        } else if !enable_gossip_signature_batching
+                            && sync_message_queue.len_in(pool) > 0
+                        {
+                            if let Some(item) = sync_message_queue.pop_from(pool) {
+                                self.spawn_worker(item, idle_tx);
+                            }
                         // Aggregates and unaggregates queued for re-processing are older and we
                         // care about fresher ones, so check those first.
-                        } else if let Some(item) = unknown_block_aggregate_queue.pop() {
+                        } else if let Some(item) = unknown_block_aggregate_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = unknown_block_attestation_queue.pop() {
+                        } else if let Some(item) = unknown_block_attestation_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check RPC methods next. Status messages are needed for sync so
                         // prioritize them over syncing requests from other peers (BlocksByRange
                         // and BlocksByRoot)
-                        } else if let Some(item) = status_queue.pop() {
+                        } else if let Some(item) = status_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = bbrange_queue.pop() {
+                        } else if let Some(item) = bbrange_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = bbroots_queue.pop() {
+                        } else if let Some(item) = bbroots_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = blbrange_queue.pop() {
+                        } else if let Some(item) = blbrange_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = blbroots_queue.pop() {
+                        } else if let Some(item) = blbroots_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check slashings after all other consensus messages so we prioritize
                         // following head.
                         //
                         // Check attester slashings before proposer slashings since they have the
                         // potential to slash multiple validators at once.
-                        } else if let Some(item) = gossip_attester_slashing_queue.pop() {
+                        } else if let Some(item) = gossip_attester_slashing_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_proposer_slashing_queue.pop() {
+                        } else if let Some(item) = gossip_proposer_slashing_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Check exits and address changes late since our validators don't get
                         // rewards from them.
-                        } else if let Some(item) = gossip_voluntary_exit_queue.pop() {
+                        } else if let Some(item) = gossip_voluntary_exit_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_bls_to_execution_change_queue.pop() {
+                        } else if let Some(item) =
+                            gossip_bls_to_execution_change_queue.pop_from(pool)
+                        {
                             self.spawn_worker(item, idle_tx);
                         // Check the priority 1 API requests after we've
                         // processed all the interesting things from the network
                         // and things required for us to stay in good repute
                         // with our P2P peers.
-                        } else if let Some(item) = api_request_p1_queue.pop() {
+                        } else if let Some(item) = api_request_p1_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Handle backfill sync chain segments.
-                        } else if let Some(item) = backfill_chain_segment.pop() {
+                        } else if let Some(item) = backfill_chain_segment.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                         // Handle light client requests.
-                        } else if let Some(item) = lc_bootstrap_queue.pop() {
+                        } else if let Some(item) = lc_bootstrap_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = lc_optimistic_update_queue.pop() {
+                        } else if let Some(item) = lc_optimistic_update_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = lc_finality_update_queue.pop() {
+                        } else if let Some(item) = lc_finality_update_queue.pop_from(pool) {
                             self.spawn_worker(item, idle_tx);
                             // This statement should always be the final else statement.
                         } else {
@@ -1191,8 +1627,27 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     // it.
                     Some(WorkEvent { work, .. }) => {
                         let work_id = work.str_id();
+                        let is_gossip_signature = matches!(
+                            work,
+                            Work::GossipAttestation { .. }
+                                | Work::GossipAggregate { .. }
+                                | Work::GossipSyncSignature { .. }
+                        );
 
                         match work {
+                            // Gossip signatures are always queued when batching is enabled, so that
+                            // they may be verified together.
+                            Work::GossipAttestation { .. } if enable_gossip_signature_batching => {
+                                attestation_queue.push(work)
+                            }
+                            Work::GossipAggregate { .. } if enable_gossip_signature_batching => {
+                                aggregate_queue.push(work)
+                            }
+                            Work::GossipSyncSignature { .. }
+                                if enable_gossip_signature_batching =>
+                            {
+                                sync_message_queue.push(work)
+                            }
                             _ if can_spawn => self.spawn_worker(work, idle_tx),
                             Work::GossipAttestation { .. } => attestation_queue.push(work),
                             // Attestation batches are formed internally within the
@@ -1210,6 +1665,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                     "Unsupported inbound event";
                                     "type" => "GossipAggregateBatch"
                             ),
+                            // Signature batches are formed internally within the
+                            // `BeaconProcessor`, they are not sent from external services.
+                            Work::GossipSignatureBatch { .. } => crit!(
+                                    self.log,
+                                    "Unsupported inbound event";
+                                    "type" => "GossipSignatureBatch"
+                            ),
                             Work::GossipBlock { .. } => {
                                 gossip_block_queue.push(work, work_id, &self.log)
                             }
@@ -1292,9 +1754,38 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                 api_request_p1_queue.push(work, work_id, &self.log)
                             }
                         }
+
+                        if enable_gossip_signature_batching && is_gossip_signature {
+                            let num_pending_signatures = aggregate_queue.len()
+                                + attestation_queue.len()
+                                + sync_message_queue.len();
+                            let deadline = if num_pending_signatures
+                                >= self.config.max_gossip_signature_batch_size
+                            {
+                                // The batch is full, verify it as soon as a worker is free.
+                                Instant::now()
+                            } else {
+                                gossip_signature_batch_deadline.unwrap_or_else(|| {
+                                    Instant::now() + self.config.gossip_signature_batch_window
+                                })
+                            };
+
+                            if gossip_signature_batch_deadline != Some(deadline)
+                                || inbound_events.gossip_signature_batch_window.is_none()
+                            {
+                                gossip_signature_batch_deadline = Some(deadline);
+                                inbound_events.gossip_signature_batch_window =
+                                    Some(Box::pin(tokio::time::sleep_until(deadline.into())));
+                            }
+                        }
                     }
                 }
 
+                // `spawn_worker` increments `current_workers`, regardless of the pool.
+                if self.current_workers > num_workers {
+                    worker_pools.on_spawn(pool);
+                }
+
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_WORKERS_ACTIVE_TOTAL,
                     self.current_workers as i64,
@@ -1396,7 +1887,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
     /// Spawns a blocking worker thread to process some `Work`.
     ///
     /// Sends an message on `idle_tx` when the work is complete and the task is stopping.
-    fn spawn_worker(&mut self, work: Work<E>, idle_tx: mpsc::Sender<()>) {
+    fn spawn_worker(&mut self, work: Work<E>, idle_tx: IdleTx) {
         let work_id = work.str_id();
         let worker_timer =
             metrics::start_timer_vec(&metrics::BEACON_PROCESSOR_WORKER_TIME, &[work_id]);
@@ -1411,7 +1902,8 @@ impl<E: EthSpec> BeaconProcessor<E> {
         // This helps ensure that the worker is always freed in the case of an early exit or panic.
         // As such, this instantiation should happen as early in the function as possible.
         let send_idle_on_drop = SendOnDrop {
-            tx: idle_tx,
+            tx: idle_tx.tx,
+            pool: idle_tx.pool,
             _worker_timer: worker_timer,
             log: self.log.clone(),
         };
@@ -1426,11 +1918,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
             "Spawning beacon processor worker";
             "work" => work_id,
             "worker" => worker_id,
+            "pool" => %idle_tx.pool,
         );
 
         let task_spawner = TaskSpawner {
             executor,
             send_idle_on_drop,
+            cores: idle_tx.cores,
         };
 
         match work {
@@ -1438,6 +1932,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 attestation,
                 process_individual,
                 process_batch: _,
+                process_signature_batch: _,
             } => task_spawner.spawn_blocking(move || {
                 process_individual(*attestation);
             }),
@@ -1451,6 +1946,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 aggregate,
                 process_individual,
                 process_batch: _,
+                process_signature_batch: _,
             } => task_spawner.spawn_blocking(move || {
                 process_individual(*aggregate);
             }),
@@ -1460,6 +1956,19 @@ impl<E: EthSpec> BeaconProcessor<E> {
             } => task_spawner.spawn_blocking(move || {
                 process_batch(aggregates);
             }),
+            Work::GossipSyncSignature {
+                sync_signature,
+                process_individual,
+                process_signature_batch: _,
+            } => task_spawner.spawn_blocking(move || {
+                process_individual(*sync_signature);
+            }),
+            Work::GossipSignatureBatch {
+                batch,
+                process_batch,
+            } => task_spawner.spawn_blocking(move || {
+                process_batch(batch);
+            }),
             Work::ChainSegment(process_fn) => task_spawner.spawn_async(async move {
                 process_fn.await;
             }),
@@ -1497,7 +2006,6 @@ impl<E: EthSpec> BeaconProcessor<E> {
             Work::GossipVoluntaryExit(process_fn)
             | Work::GossipProposerSlashing(process_fn)
             | Work::GossipAttesterSlashing(process_fn)
-            | Work::GossipSyncSignature(process_fn)
             | Work::GossipSyncContribution(process_fn)
             | Work::GossipLightClientFinalityUpdate(process_fn)
             | Work::GossipLightClientOptimisticUpdate(process_fn)
@@ -1512,6 +2020,83 @@ impl<E: EthSpec> BeaconProcessor<E> {
     }
 }
 
+/// Pops up to `max_batch_size` aggregates, unaggregated attestations and sync committee messages
+/// (in that order of priority) from their queues into a single `Work::GossipSignatureBatch`.
+///
+/// Returns `None` if all the queues are empty.
+fn pop_gossip_signature_batch<E: EthSpec>(
+    aggregate_queue: &mut LifoQueue<Work<E>>,
+    attestation_queue: &mut LifoQueue<Work<E>>,
+    sync_message_queue: &mut LifoQueue<Work<E>>,
+    pool: WorkerPool,
+    max_batch_size: usize,
+    log: &Logger,
+) -> Option<Work<E>> {
+    let mut batch = GossipSignatureBatch {
+        attestations: vec![],
+        aggregates: vec![],
+        sync_messages: vec![],
+    };
+    let mut batch_size = 0;
+    let mut process_batch_opt = None;
+
+    for queue in [aggregate_queue, attestation_queue, sync_message_queue] {
+        while batch_size < max_batch_size {
+            let Some(item) = queue.pop_from(pool) else {
+                break;
+            };
+
+            let process_batch = match item {
+                Work::GossipAggregate {
+                    aggregate,
+                    process_signature_batch,
+                    ..
+                } => {
+                    batch.aggregates.push(*aggregate);
+                    process_signature_batch
+                }
+                Work::GossipAttestation {
+                    attestation,
+                    process_signature_batch,
+                    ..
+                } => {
+                    batch.attestations.push(*attestation);
+                    process_signature_batch
+                }
+                Work::GossipSyncSignature {
+                    sync_signature,
+                    process_signature_batch,
+                    ..
+                } => {
+                    batch.sync_messages.push(*sync_signature);
+                    process_signature_batch
+                }
+                _ => {
+                    error!(log, "Invalid item in gossip signature queue");
+                    continue;
+                }
+            };
+
+            batch_size += 1;
+            if process_batch_opt.is_none() {
+                process_batch_opt = Some(process_batch);
+            }
+        }
+    }
+
+    process_batch_opt.map(|process_batch| Work::GossipSignatureBatch {
+        batch,
+        process_batch,
+    })
+}
+
+/// The channel used by a worker to signal that it is idle, along with the pool it belongs to.
+struct IdleTx {
+    tx: mpsc::Sender<WorkerPool>,
+    pool: WorkerPool,
+    cores: Option<Arc<[usize]>>,
+}
+
 /// Spawns tasks that are either:
 ///
 /// - Blocking (i.e. intensive methods that shouldn't run on the core `tokio` executor)
@@ -1519,9 +2104,12 @@ impl<E: EthSpec> BeaconProcessor<E> {
 ///
 /// Takes a `SendOnDrop` and ensures it is dropped after the task completes. This frees the beacon
 /// processor worker so a new task can be started.
+///
+/// Blocking tasks are pinned to `cores`, if provided.
 struct TaskSpawner {
     executor: TaskExecutor,
     send_idle_on_drop: SendOnDrop,
+    cores: Option<Arc<[usize]>>,
 }
 
 impl TaskSpawner {
@@ -1541,9 +2129,12 @@ impl TaskSpawner {
     where
         F: FnOnce() + Send + 'static,
     {
+        let cores = self.cores;
         self.executor.spawn_blocking(
-            || {
+            move || {
+                let pinned = cores.and_then(|cores| PinnedThread::pin(&cores));
                 task();
+                drop(pinned);
                 drop(self.send_idle_on_drop)
             },
             WORKER_TASK_NAME,
@@ -1562,7 +2153,8 @@ impl TaskSpawner {
 ///
 /// https://doc.rust-lang.org/std/ops/trait.Drop.html#panics
 pub struct SendOnDrop {
-    tx: mpsc::Sender<()>,
+    tx: mpsc::Sender<WorkerPool>,
+    pool: WorkerPool,
     // The field is unused, but it's here to ensure the timer is dropped once the task has finished.
     _worker_timer: Option<metrics::HistogramTimer>,
     log: Logger,
@@ -1570,7 +2162,7 @@ pub struct SendOnDrop {
 
 impl Drop for SendOnDrop {
     fn drop(&mut self) {
-        if let Err(e) = self.tx.try_send(()) {
+        if let Err(e) = self.tx.try_send(self.pool) {
             warn!(
                 self.log,
                 "Unable to free worker";
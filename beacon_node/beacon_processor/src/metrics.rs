@@ -41,6 +41,17 @@ lazy_static::lazy_static! {
         "beacon_processor_event_handling_seconds",
         "Time spent handling a new message and allocating it to a queue or worker."
     );
+    pub static ref BEACON_PROCESSOR_QUEUE_LATENCY_SECONDS: Result<HistogramVec> = try_create_histogram_vec_with_buckets(
+        "beacon_processor_queue_latency_seconds",
+        "Time that work spent queued before being given to a worker, per class of work.",
+        decimal_buckets(-3, 1),
+        &["class"]
+    );
+    pub static ref BEACON_PROCESSOR_WORK_EVENTS_EXPIRED_COUNT: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_processor_work_events_expired_count",
+        "Count of work events which were shed after passing the deadline of their class.",
+        &["class"]
+    );
     // Gossip blocks.
     pub static ref BEACON_PROCESSOR_GOSSIP_BLOCK_QUEUE_TOTAL: Result<IntGauge> = try_create_int_gauge(
         "beacon_processor_gossip_block_queue_total",
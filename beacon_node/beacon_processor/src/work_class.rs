@@ -0,0 +1,225 @@
+//! Groups the work of the `BeaconProcessor` into classes which share a deadline.
+//!
+//! Work which is still queued once the deadline for its class has passed is shed, rather than
+//! being processed late. Under overload this keeps the workers busy with fresh gossip, instead of
+//! verifying messages which have since lost their value. No class has a deadline unless one is
+//! configured, so by default no work is shed.
+//!
+//! Deadlines do not change the order in which queued work is given to workers, which remains the
+//! fixed order of the `BeaconProcessor` (blocks, then aggregates, then attestations and so on, with
+//! backfill last). A deadline only decides whether work which has waited behind higher priority
+//! work is still worth processing.
+//!
+//! Only classes whose work may be dropped without stalling another component can have a deadline.
+//! Blocks, sync batches and RPC requests are always processed, since their producers wait on the
+//! result.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A group of work which shares a deadline and latency metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkClass {
+    /// Blocks, blobs and data columns from gossip, including delayed blocks.
+    Block,
+    /// Blocks, blobs and chain segments requested by sync.
+    Sync,
+    /// Aggregated attestations from gossip.
+    Aggregate,
+    /// Unaggregated attestations from gossip.
+    Attestation,
+    /// Sync committee messages and contributions from gossip.
+    SyncCommittee,
+    /// Exits, slashings and BLS to execution changes from gossip.
+    Operation,
+    /// Light client updates from gossip and requests for them.
+    LightClient,
+    /// Requests from peers for blocks, blobs and status.
+    Rpc,
+    /// Requests to the HTTP API.
+    Api,
+    /// Chain segments from backfill sync.
+    Backfill,
+}
+
+impl WorkClass {
+    pub const ALL: [WorkClass; 10] = [
+        WorkClass::Block,
+        WorkClass::Sync,
+        WorkClass::Aggregate,
+        WorkClass::Attestation,
+        WorkClass::SyncCommittee,
+        WorkClass::Operation,
+        WorkClass::LightClient,
+        WorkClass::Rpc,
+        WorkClass::Api,
+        WorkClass::Backfill,
+    ];
+
+    /// Returns the name of the class, as used in metrics and on the command line.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkClass::Block => "block",
+            WorkClass::Sync => "sync",
+            WorkClass::Aggregate => "aggregate",
+            WorkClass::Attestation => "attestation",
+            WorkClass::SyncCommittee => "sync_committee",
+            WorkClass::Operation => "operation",
+            WorkClass::LightClient => "light_client",
+            WorkClass::Rpc => "rpc",
+            WorkClass::Api => "api",
+            WorkClass::Backfill => "backfill",
+        }
+    }
+
+    /// Returns `true` if work of this class may be dropped once its deadline has passed.
+    pub fn can_shed(self) -> bool {
+        match self {
+            WorkClass::Aggregate
+            | WorkClass::Attestation
+            | WorkClass::SyncCommittee
+            | WorkClass::Operation
+            | WorkClass::LightClient
+            | WorkClass::Api => true,
+            WorkClass::Block | WorkClass::Sync | WorkClass::Rpc | WorkClass::Backfill => false,
+        }
+    }
+}
+
+impl FromStr for WorkClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|class| class.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "{} is not a work class, must be one of block, sync, aggregate, attestation, \
+                     sync_committee, operation, light_client, rpc, api or backfill",
+                    s
+                )
+            })
+    }
+}
+
+impl fmt::Display for WorkClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Parse a comma-separated list of `<class>=<millis>` pairs, such as `attestation=4000,all=12000`.
+///
+/// The class `all` sets the deadline for every class which can be shed and has no entry of its own.
+/// An empty list disables shedding.
+pub fn parse_work_class_deadlines(s: &str) -> Result<BTreeMap<WorkClass, Duration>, String> {
+    parse_work_class_values::<u64>(s, "a deadline", WorkClass::can_shed).map(|deadlines| {
+        deadlines
+            .into_iter()
+            .map(|(class, millis)| (class, Duration::from_millis(millis)))
+            .collect()
+    })
+}
+
+/// Parse a comma-separated list of `<class>=<workers>` pairs, such as `attestation=4,api=2`.
+pub fn parse_work_class_workers(s: &str) -> Result<BTreeMap<WorkClass, usize>, String> {
+    let workers = parse_work_class_values::<usize>(s, "workers", |_| true)?;
+    if let Some((class, _)) = workers.iter().find(|(_, workers)| **workers == 0) {
+        return Err(format!("{} must have at least one worker", class));
+    }
+    Ok(workers)
+}
+
+/// Parse a comma-separated list of `<class>=<value>` pairs, where each class must satisfy
+/// `can_configure`.
+///
+/// The class `all` sets the value for every such class without its own entry.
+fn parse_work_class_values<T: FromStr + Copy>(
+    s: &str,
+    value_desc: &str,
+    can_configure: fn(WorkClass) -> bool,
+) -> Result<BTreeMap<WorkClass, T>, String> {
+    let mut all = None;
+    let mut values = BTreeMap::new();
+    for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (class, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("{} is not of the form <class>=<value>", pair))?;
+        let value = value
+            .trim()
+            .parse::<T>()
+            .map_err(|_| format!("{} has an invalid value", pair))?;
+        match class.trim() {
+            "all" => all = Some(value),
+            class => {
+                let class = class.parse::<WorkClass>()?;
+                if !can_configure(class) {
+                    return Err(format!("{} work cannot be given {}", class, value_desc));
+                }
+                values.insert(class, value);
+            }
+        }
+    }
+    if let Some(all) = all {
+        for class in WorkClass::ALL
+            .into_iter()
+            .filter(|class| can_configure(*class))
+        {
+            values.entry(class).or_insert(all);
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_names() {
+        for class in WorkClass::ALL {
+            assert_eq!(class.to_string().parse::<WorkClass>(), Ok(class));
+        }
+    }
+
+    #[test]
+    fn parse_deadlines() {
+        let deadlines = parse_work_class_deadlines("attestation=4000, all=12000").unwrap();
+        assert_eq!(
+            deadlines.len(),
+            WorkClass::ALL
+                .iter()
+                .filter(|class| class.can_shed())
+                .count()
+        );
+        assert_eq!(
+            deadlines[&WorkClass::Attestation],
+            Duration::from_millis(4000)
+        );
+        assert_eq!(deadlines[&WorkClass::Api], Duration::from_secs(12));
+        assert!(!deadlines.contains_key(&WorkClass::Block));
+
+        assert!(parse_work_class_deadlines("").unwrap().is_empty());
+
+        assert!(parse_work_class_deadlines("block=1000").is_err());
+        assert!(parse_work_class_deadlines("attestation").is_err());
+        assert!(parse_work_class_deadlines("attestation=x").is_err());
+        assert!(parse_work_class_deadlines("unknown=1").is_err());
+    }
+
+    #[test]
+    fn parse_workers() {
+        let workers = parse_work_class_workers("attestation=4,api=2").unwrap();
+        assert_eq!(
+            workers,
+            BTreeMap::from([(WorkClass::Attestation, 4), (WorkClass::Api, 2)])
+        );
+
+        assert!(parse_work_class_workers("api=0").is_err());
+        assert!(parse_work_class_workers("api=-1").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Pools of workers for the `BeaconProcessor`.
+//!
+//! By default all work is processed by a single pool of `max_workers` workers. Operators may
+//! reserve workers for a `WorkClass`, in which case work of that class is only processed by its own
+//! pool. This isolates classes from each other, e.g. so that a burst of HTTP API requests cannot
+//! starve the verification of gossip attestations.
+//!
+//! Optionally, the blocking threads of each pool can be pinned to a disjoint set of cores.
+use crate::work_class::WorkClass;
+use crate::BeaconProcessorConfig;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A pool of workers, either shared by all classes of work or dedicated to one class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerPool {
+    Shared,
+    Dedicated(WorkClass),
+}
+
+impl WorkerPool {
+    /// Returns the pool which processes work of `class`.
+    pub fn of(class: WorkClass, config: &BeaconProcessorConfig) -> Self {
+        if config.class_workers.contains_key(&class) {
+            WorkerPool::Dedicated(class)
+        } else {
+            WorkerPool::Shared
+        }
+    }
+}
+
+impl fmt::Display for WorkerPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkerPool::Shared => write!(f, "shared"),
+            WorkerPool::Dedicated(class) => write!(f, "{}", class),
+        }
+    }
+}
+
+/// The capacity and usage of a single pool.
+struct PoolState {
+    max_workers: usize,
+    active_workers: usize,
+    cores: Option<Arc<[usize]>>,
+}
+
+/// Tracks the active workers of each pool.
+pub(crate) struct WorkerPools {
+    pools: BTreeMap<Option<WorkClass>, PoolState>,
+}
+
+impl WorkerPools {
+    /// Creates the pools described by `config`, allocating cores to them if workers are pinned.
+    ///
+    /// Each dedicated pool is given one core per worker, and the shared pool is given the remaining
+    /// `num_cores`.
+    pub fn new(config: &BeaconProcessorConfig, num_cores: usize) -> Result<Self, String> {
+        let mut next_core = 0;
+        let mut pools = BTreeMap::new();
+
+        for (class, max_workers) in &config.class_workers {
+            if *max_workers == 0 {
+                return Err(format!(
+                    "the {} worker pool must have at least one worker",
+                    class
+                ));
+            }
+            let cores = config.pin_workers.then(|| {
+                let cores = (next_core..next_core + max_workers).collect::<Arc<[usize]>>();
+                next_core += max_workers;
+                cores
+            });
+            pools.insert(
+                Some(*class),
+                PoolState {
+                    max_workers: *max_workers,
+                    active_workers: 0,
+                    cores,
+                },
+            );
+        }
+
+        let shared_cores = if config.pin_workers {
+            if next_core >= num_cores {
+                return Err(format!(
+                    "unable to pin workers, the dedicated pools require {} cores and leave none \
+                     of the {} available cores for the shared pool",
+                    next_core, num_cores
+                ));
+            }
+            Some((next_core..num_cores).collect::<Arc<[usize]>>())
+        } else {
+            None
+        };
+        pools.insert(
+            None,
+            PoolState {
+                max_workers: config.max_workers,
+                active_workers: 0,
+                cores: shared_cores,
+            },
+        );
+
+        Ok(Self { pools })
+    }
+
+    fn state(&self, pool: WorkerPool) -> Option<&PoolState> {
+        self.pools.get(&Self::key(pool))
+    }
+
+    fn key(pool: WorkerPool) -> Option<WorkClass> {
+        match pool {
+            WorkerPool::Shared => None,
+            WorkerPool::Dedicated(class) => Some(class),
+        }
+    }
+
+    /// Returns `true` if another worker can be spawned in `pool`.
+    pub fn can_spawn(&self, pool: WorkerPool) -> bool {
+        self.state(pool)
+            .map_or(false, |state| state.active_workers < state.max_workers)
+    }
+
+    /// Returns the cores which the workers of `pool` are pinned to, if any.
+    pub fn cores(&self, pool: WorkerPool) -> Option<Arc<[usize]>> {
+        self.state(pool).and_then(|state| state.cores.clone())
+    }
+
+    /// Records that a worker was spawned in `pool`.
+    pub fn on_spawn(&mut self, pool: WorkerPool) {
+        if let Some(state) = self.pools.get_mut(&Self::key(pool)) {
+            state.active_workers = state.active_workers.saturating_add(1);
+        }
+    }
+
+    /// Records that a worker in `pool` has finished its work.
+    pub fn on_idle(&mut self, pool: WorkerPool) {
+        if let Some(state) = self.pools.get_mut(&Self::key(pool)) {
+            state.active_workers = state.active_workers.saturating_sub(1);
+        }
+    }
+}
+
+/// Pins the current thread to a set of cores, restoring its previous affinity when dropped.
+///
+/// Blocking threads are re-used by `tokio` for other tasks, so the affinity must not outlive the
+/// work it was set for.
+pub(crate) struct PinnedThread {
+    #[cfg(target_os = "linux")]
+    previous: libc::cpu_set_t,
+}
+
+impl PinnedThread {
+    /// Returns `None` if the affinity of the thread could not be set.
+    #[cfg(target_os = "linux")]
+    pub fn pin(cores: &[usize]) -> Option<Self> {
+        let set_size = std::mem::size_of::<libc::cpu_set_t>();
+        // Safety: `cpu_set_t` is a plain bitmask for which all zeroes is a valid value, and both
+        // calls are given a pointer to a `cpu_set_t` along with its size.
+        unsafe {
+            let mut previous: libc::cpu_set_t = std::mem::zeroed();
+            if libc::sched_getaffinity(0, set_size, &mut previous) != 0 {
+                return None;
+            }
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            for core in cores {
+                libc::CPU_SET(*core, &mut set);
+            }
+            if libc::sched_setaffinity(0, set_size, &set) != 0 {
+                return None;
+            }
+            Some(Self { previous })
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn pin(_cores: &[usize]) -> Option<Self> {
+        None
+    }
+
+    /// Returns `true` if threads can be pinned on this platform.
+    pub fn is_supported() -> bool {
+        cfg!(target_os = "linux")
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for PinnedThread {
+    fn drop(&mut self) {
+        // Safety: see `PinnedThread::pin`.
+        unsafe {
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &self.previous);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(class_workers: &[(WorkClass, usize)], pin_workers: bool) -> BeaconProcessorConfig {
+        BeaconProcessorConfig {
+            max_workers: 2,
+            class_workers: class_workers.iter().copied().collect(),
+            pin_workers,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dedicated_pools_are_isolated() {
+        let config = config(&[(WorkClass::Api, 1)], false);
+        let mut pools = WorkerPools::new(&config, 4).unwrap();
+        let api = WorkerPool::of(WorkClass::Api, &config);
+        let shared = WorkerPool::of(WorkClass::Attestation, &config);
+        assert_eq!(api, WorkerPool::Dedicated(WorkClass::Api));
+        assert_eq!(shared, WorkerPool::Shared);
+
+        pools.on_spawn(api);
+        assert!(!pools.can_spawn(api));
+        assert!(pools.can_spawn(shared));
+
+        pools.on_spawn(shared);
+        pools.on_spawn(shared);
+        assert!(!pools.can_spawn(shared));
+
+        pools.on_idle(api);
+        assert!(pools.can_spawn(api));
+        assert!(!pools.can_spawn(shared));
+        assert_eq!(pools.cores(api), None);
+    }
+
+    #[test]
+    fn cores_are_allocated_per_pool() {
+        let config = config(&[(WorkClass::Attestation, 2), (WorkClass::Api, 1)], true);
+        let pools = WorkerPools::new(&config, 6).unwrap();
+        assert_eq!(
+            pools.cores(WorkerPool::Dedicated(WorkClass::Attestation)),
+            Some(Arc::from([0, 1]))
+        );
+        assert_eq!(
+            pools.cores(WorkerPool::Dedicated(WorkClass::Api)),
+            Some(Arc::from([2]))
+        );
+        assert_eq!(pools.cores(WorkerPool::Shared), Some(Arc::from([3, 4, 5])));
+
+        assert!(WorkerPools::new(&config, 3).is_err());
+        assert!(WorkerPools::new(&self::config(&[(WorkClass::Api, 0)], false), 4).is_err());
+    }
+}
@@ -1,8 +1,13 @@
+use crate::clock_monitor::spawn_clock_monitor;
 use crate::compute_light_client_updates::{
     compute_light_client_updates, LIGHT_CLIENT_SERVER_CHANNEL_CAPACITY,
 };
 use crate::config::{ClientGenesis, Config as ClientConfig};
+use crate::disk_usage_monitor::spawn_disk_usage_monitor;
 use crate::notifier::spawn_notifier;
+use crate::replay::{spawn_replay, ReplayConfig};
+use crate::slasher_store::SlasherBackingStore;
+use crate::webhook_notifier::spawn_webhook_notifier;
 use crate::Client;
 use beacon_chain::attestation_simulator::start_attestation_simulator_service;
 use beacon_chain::data_availability_checker::start_availability_cache_maintenance_service;
@@ -34,7 +39,7 @@ use genesis::{interop_genesis_state, Eth1GenesisService, DEFAULT_ETH1_BLOCK_HASH
 use lighthouse_network::{prometheus_client::registry::Registry, NetworkGlobals};
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use network::{NetworkConfig, NetworkSenders, NetworkService};
-use slasher::Slasher;
+use slasher::{BackingStore, Slasher};
 use slasher_service::SlasherService;
 use slog::{debug, info, warn, Logger};
 use ssz::Decode;
@@ -46,8 +51,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use timer::spawn_timer;
 use tokio::sync::oneshot;
 use types::{
-    test_utils::generate_deterministic_keypairs, BeaconState, BlobSidecarList, ChainSpec, EthSpec,
-    ExecutionBlockHash, Hash256, SignedBeaconBlock,
+    test_utils::generate_deterministic_keypairs, BeaconState, BlobSidecarList, ChainSpec,
+    DepositTreeSnapshot, EthSpec, ExecutionBlockHash, Hash256, SignedBeaconBlock,
 };
 
 /// Interval between polling the eth1 node for genesis information.
@@ -85,6 +90,7 @@ pub struct ClientBuilder<T: BeaconChainTypes> {
     libp2p_registry: Option<Registry>,
     db_path: Option<PathBuf>,
     freezer_db_path: Option<PathBuf>,
+    blobs_db_path: Option<PathBuf>,
     http_api_config: http_api::Config,
     http_metrics_config: http_metrics::Config,
     slasher: Option<Arc<Slasher<T::EthSpec>>>,
@@ -120,6 +126,7 @@ where
             libp2p_registry: None,
             db_path: None,
             freezer_db_path: None,
+            blobs_db_path: None,
             http_api_config: <_>::default(),
             http_metrics_config: <_>::default(),
             slasher: None,
@@ -153,6 +160,17 @@ where
         self
     }
 
+    /// Returns the hot database of the store, for use by the slasher's `BeaconStore` backend.
+    ///
+    /// Must be called after the store has been initialized.
+    pub fn slasher_backing_store(&self) -> Result<Arc<dyn BackingStore>, String> {
+        let store = self
+            .store
+            .clone()
+            .ok_or("slasher_backing_store requires a store")?;
+        Ok(Arc::new(SlasherBackingStore::new(store)))
+    }
+
     /// Initializes the `BeaconChainBuilder`. The `build_beacon_chain` method will need to be
     /// called later in order to actually instantiate the `BeaconChain`.
     pub async fn beacon_chain_builder(
@@ -362,6 +380,21 @@ where
                 };
                 let genesis_state = genesis_state(&runtime_context, &config, log).await?;
 
+                // A snapshot provided by the user must be usable, rather than silently falling
+                // back to syncing all of the deposits from the execution node.
+                let service = match config.deposit_snapshot.as_ref() {
+                    Some(snapshot) if config.sync_eth1_chain => {
+                        Some(eth1_service_from_deposit_snapshot(
+                            config.eth1,
+                            log,
+                            spec,
+                            snapshot,
+                            &anchor_state,
+                        )?)
+                    }
+                    _ => None,
+                };
+
                 builder
                     .weak_subjectivity_state(
                         anchor_state,
@@ -369,7 +402,7 @@ where
                         anchor_blobs,
                         genesis_state,
                     )
-                    .map(|v| (v, None))?
+                    .map(|v| (v, service))?
             }
             ClientGenesis::CheckpointSyncUrl { url } => {
                 info!(
@@ -391,7 +424,11 @@ where
                     )),
                 );
 
-                let deposit_snapshot = if config.sync_eth1_chain {
+                let deposit_snapshot = if !config.sync_eth1_chain {
+                    None
+                } else if let Some(snapshot) = config.deposit_snapshot.clone() {
+                    Some(snapshot)
+                } else {
                     // We want to fetch deposit snapshot before fetching the finalized beacon state to
                     // ensure that the snapshot is not newer than the beacon state that satisfies the
                     // deposit finalization conditions
@@ -432,8 +469,6 @@ where
                             None
                         }
                     }
-                } else {
-                    None
                 };
 
                 debug!(
@@ -500,29 +535,29 @@ where
                     "block_root" => ?block_root,
                 );
 
-                let service =
-                    deposit_snapshot.and_then(|snapshot| match Eth1Service::from_deposit_snapshot(
-                        config.eth1,
-                        context.log().clone(),
-                        spec,
-                        &snapshot,
-                    ) {
-                        Ok(service) => {
-                            info!(
-                                context.log(),
-                                "Loaded deposit tree snapshot";
-                                "deposits loaded" => snapshot.deposit_count,
-                            );
-                            Some(service)
-                        }
-                        Err(e) => {
-                            warn!(context.log(),
-                                "Unable to load deposit snapshot";
-                                "error" => ?e
-                            );
-                            None
+                let service = deposit_snapshot
+                    .map(|snapshot| {
+                        eth1_service_from_deposit_snapshot(
+                            config.eth1,
+                            log,
+                            spec,
+                            &snapshot,
+                            &state,
+                        )
+                    })
+                    .transpose()
+                    .or_else(|e| {
+                        // Only a snapshot downloaded from the remote is allowed to fail.
+                        if config.deposit_snapshot.is_some() {
+                            return Err(e);
                         }
-                    });
+                        warn!(
+                            context.log(),
+                            "Unable to load deposit snapshot";
+                            "error" => e
+                        );
+                        Ok(None)
+                    })?;
 
                 builder
                     .weak_subjectivity_state(state, block, blobs, genesis_state)
@@ -566,6 +601,7 @@ where
                         eth1_service: Some(genesis_service.eth1_service.clone()),
                         log: context.log().clone(),
                         sse_logging_components: runtime_context.sse_logging_components.clone(),
+                        runtime_log_levels: runtime_context.runtime_log_levels.clone(),
                     });
 
                     // Discard the error from the oneshot.
@@ -779,6 +815,81 @@ where
         Ok(self)
     }
 
+    /// Immediately starts the service that checks the local clock against the network.
+    pub fn clock_monitor(self, ntp_server: Option<String>) -> Result<Self, String> {
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or("clock_monitor requires a runtime_context")?
+            .service_context("clock_monitor".into());
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or("clock_monitor requires a beacon chain")?;
+        let seconds_per_slot = self
+            .chain_spec
+            .as_ref()
+            .ok_or("clock_monitor requires a chain spec")?
+            .seconds_per_slot;
+
+        spawn_clock_monitor(context.executor, beacon_chain, ntp_server, seconds_per_slot)
+            .map_err(|e| format!("Unable to start clock monitor: {}", e))?;
+
+        Ok(self)
+    }
+
+    /// Immediately starts the service that sends notifications to the configured webhooks.
+    pub fn webhook_notifier(self, config: &webhook::Config) -> Result<Self, String> {
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or("webhook_notifier requires a runtime_context")?
+            .service_context("webhook_notifier".into());
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or("webhook_notifier requires a beacon chain")?;
+        let network_globals = self
+            .network_globals
+            .clone()
+            .ok_or("webhook_notifier requires a libp2p network")?;
+        let seconds_per_slot = self
+            .chain_spec
+            .as_ref()
+            .ok_or("webhook_notifier requires a chain spec")?
+            .seconds_per_slot;
+
+        spawn_webhook_notifier(
+            context.executor,
+            beacon_chain,
+            network_globals,
+            config,
+            seconds_per_slot,
+        )
+        .map_err(|e| format!("Unable to start webhook notifier: {}", e))?;
+
+        Ok(self)
+    }
+
+    /// Immediately starts the service that replays an archive of blocks, shutting down the node
+    /// once it is complete.
+    pub fn replay(self, config: &ReplayConfig) -> Result<Self, String> {
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or("replay requires a runtime_context")?
+            .service_context("replay".into());
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or("replay requires a beacon chain")?;
+
+        spawn_replay(context.executor, beacon_chain, config.clone())
+            .map_err(|e| format!("Unable to start replay: {}", e))?;
+
+        Ok(self)
+    }
+
     /// Consumes the builder, returning a `Client` if all necessary components have been
     /// specified.
     ///
@@ -813,6 +924,7 @@ where
                     beacon_processor_channels.work_reprocessing_tx.clone(),
                 ),
                 sse_logging_components: runtime_context.sse_logging_components.clone(),
+                runtime_log_levels: runtime_context.runtime_log_levels.clone(),
                 log: log.clone(),
             });
 
@@ -903,6 +1015,22 @@ where
                 state_advance_log,
             );
 
+            if let (Some(db_path), Some(freezer_db_path), Some(blobs_db_path)) =
+                (&self.db_path, &self.freezer_db_path, &self.blobs_db_path)
+            {
+                spawn_disk_usage_monitor(
+                    runtime_context
+                        .service_context("disk_usage".into())
+                        .executor,
+                    beacon_chain.clone(),
+                    vec![
+                        db_path.clone(),
+                        freezer_db_path.clone(),
+                        blobs_db_path.clone(),
+                    ],
+                );
+            }
+
             if let Some(execution_layer) = beacon_chain.execution_layer.as_ref() {
                 // Only send a head update *after* genesis.
                 if let Ok(current_slot) = beacon_chain.slot() {
@@ -1060,6 +1188,7 @@ where
 
         self.db_path = Some(hot_path.into());
         self.freezer_db_path = Some(cold_path.into());
+        self.blobs_db_path = Some(blobs_path.into());
 
         let inner_spec = spec.clone();
         let deposit_contract_deploy_block = context
@@ -1191,6 +1320,27 @@ where
 
         Ok(self)
     }
+
+    /// Use an eth1 backend which votes for the `Eth1Data` of the state being built upon, rather
+    /// than syncing the deposit contract.
+    ///
+    /// Blocks cannot be produced while deposits from the deposit contract remain to be included.
+    /// Once they have all been included, deposits are processed from execution requests and the
+    /// stubbed votes are equivalent to those of a fully synced backend.
+    ///
+    /// ## Notes
+    ///
+    /// The client is given the `CachingEth1Backend` type, but the http backend is never started and the
+    /// caches are never used.
+    pub fn stub_eth1_backend(mut self) -> Result<Self, String> {
+        let beacon_chain_builder = self
+            .beacon_chain_builder
+            .ok_or("stub_eth1_backend requires a beacon_chain_builder")?;
+
+        self.beacon_chain_builder = Some(beacon_chain_builder.stub_eth1_backend()?);
+
+        Ok(self)
+    }
 }
 
 impl<TEth1Backend, E, THotStore, TColdStore>
@@ -1229,6 +1379,49 @@ where
 }
 
 /// Obtain the genesis state from the `eth2_network_config` in `context`.
+/// Creates an eth1 service from a deposit tree snapshot, if the snapshot is consistent with the
+/// deposits of the finalized `state` that the node is starting from.
+///
+/// Deposits before the snapshot are pruned, so the snapshot must not include any deposits which
+/// are yet to be processed by the `state`.
+fn eth1_service_from_deposit_snapshot<E: EthSpec>(
+    config: Eth1Config,
+    log: &Logger,
+    spec: ChainSpec,
+    snapshot: &DepositTreeSnapshot,
+    state: &BeaconState<E>,
+) -> Result<Eth1Service, String> {
+    if !snapshot.is_valid() {
+        return Err("Deposit snapshot root does not match its finalized deposits".to_string());
+    }
+    if snapshot.deposit_count > state.eth1_deposit_index() {
+        return Err(format!(
+            "Deposit snapshot is newer than the checkpoint state, it has {} deposits but the \
+            state has only processed {}",
+            snapshot.deposit_count,
+            state.eth1_deposit_index()
+        ));
+    }
+    let eth1_data = state.eth1_data();
+    if snapshot.deposit_count == eth1_data.deposit_count
+        && snapshot.deposit_root != eth1_data.deposit_root
+    {
+        return Err(format!(
+            "Deposit snapshot root {:?} does not match the checkpoint state's eth1 data {:?}",
+            snapshot.deposit_root, eth1_data.deposit_root
+        ));
+    }
+
+    let service = Eth1Service::from_deposit_snapshot(config, log.clone(), spec, snapshot)
+        .map_err(|e| format!("Unable to load deposit snapshot: {:?}", e))?;
+    info!(
+        log,
+        "Loaded deposit tree snapshot";
+        "deposits loaded" => snapshot.deposit_count,
+    );
+    Ok(service)
+}
+
 async fn genesis_state<E: EthSpec>(
     context: &RuntimeContext<E>,
     config: &ClientConfig,
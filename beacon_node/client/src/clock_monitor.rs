@@ -0,0 +1,92 @@
+use crate::metrics;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use slog::{debug, warn};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use std::time::Duration;
+use system_health::clock::{
+    is_safe_gossip_clock_offset, is_safe_ntp_clock_offset, query_ntp_offset,
+};
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The time to wait for a response from the NTP server.
+const NTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Spawns a service which compares the local clock to the network once per epoch.
+///
+/// The offset is always estimated from the arrival times of gossip blocks, and is also measured
+/// against `ntp_server` if one is provided. Both are recorded in metrics, and a warning is logged
+/// whenever either is unsafe.
+pub fn spawn_clock_monitor<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    ntp_server: Option<String>,
+    seconds_per_slot: u64,
+) -> Result<(), String> {
+    let log = executor.log().clone();
+    let slot_duration = Duration::from_secs(seconds_per_slot);
+    let inner_executor = executor.clone();
+
+    let clock_future = async move {
+        loop {
+            // Check half way through the first slot of each epoch, once its block has arrived.
+            let wait = match beacon_chain
+                .slot_clock
+                .duration_to_next_epoch(T::EthSpec::slots_per_epoch())
+            {
+                Some(duration) => duration + slot_duration / 2,
+                None => {
+                    warn!(log, "Unable to read current slot");
+                    sleep(slot_duration).await;
+                    continue;
+                }
+            };
+            sleep(wait).await;
+
+            if let Some(offset) = beacon_chain.estimate_clock_offset_ms() {
+                metrics::set_gauge(&metrics::CLOCK_OFFSET_GOSSIP, offset);
+                if !is_safe_gossip_clock_offset(offset, slot_duration) {
+                    warn!(
+                        log,
+                        "Local clock may be out of sync";
+                        "info" => "blocks are arriving too early or too late, check NTP",
+                        "offset_ms" => offset,
+                        "source" => "gossip",
+                    );
+                }
+            }
+
+            let Some(server) = ntp_server.clone() else {
+                continue;
+            };
+            let query = inner_executor
+                .spawn_blocking_handle(move || query_ntp_offset(&server, NTP_TIMEOUT), "ntp_query");
+            match query {
+                Some(handle) => match handle.await {
+                    Ok(Ok(offset)) => {
+                        metrics::set_gauge(&metrics::CLOCK_OFFSET_NTP, offset);
+                        if is_safe_ntp_clock_offset(offset) {
+                            debug!(log, "Measured clock offset"; "offset_ms" => offset);
+                        } else {
+                            warn!(
+                                log,
+                                "Local clock is out of sync";
+                                "info" => "duties may be missed, check NTP",
+                                "offset_ms" => offset,
+                                "source" => "ntp",
+                            );
+                        }
+                    }
+                    Ok(Err(e)) => warn!(log, "Unable to query NTP server"; "error" => e),
+                    Err(e) => warn!(log, "NTP query failed"; "error" => ?e),
+                },
+                None => break,
+            }
+        }
+    };
+
+    executor.spawn(clock_future, "clock_monitor");
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::replay::ReplayConfig;
 use beacon_chain::graffiti_calculator::GraffitiOrigin;
 use beacon_chain::validator_monitor::ValidatorMonitorConfig;
 use beacon_chain::TrustedSetup;
@@ -10,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
+use types::DepositTreeSnapshot;
 
 /// Default directory name for the freezer database under the top-level data dir.
 const DEFAULT_FREEZER_DB_DIR: &str = "freezer_db";
@@ -62,6 +64,9 @@ pub struct Config {
     ///
     /// This is the method used for the 2019 client interop in Canada.
     pub dummy_eth1_backend: bool,
+    /// If true, the node will vote for the `Eth1Data` of the state being built upon rather than
+    /// syncing the deposit contract.
+    pub stub_eth1_votes: bool,
     pub sync_eth1_chain: bool,
     /// Graffiti to be inserted everytime we create a block if the validator doesn't specify.
     pub beacon_graffiti: GraffitiOrigin,
@@ -70,6 +75,10 @@ pub struct Config {
     /// The `genesis` field is not serialized or deserialized by `serde` to ensure it is defined
     /// via the CLI at runtime, instead of from a configuration file saved to disk.
     pub genesis: ClientGenesis,
+    /// A deposit tree snapshot to initialize the deposit cache from when starting from a
+    /// checkpoint, instead of downloading one from the checkpoint sync URL.
+    #[serde(skip)]
+    pub deposit_snapshot: Option<DepositTreeSnapshot>,
     pub store: store::StoreConfig,
     pub network: network::NetworkConfig,
     pub chain: beacon_chain::ChainConfig,
@@ -79,12 +88,19 @@ pub struct Config {
     pub http_api: http_api::Config,
     pub http_metrics: http_metrics::Config,
     pub monitoring_api: Option<monitoring_api::Config>,
+    pub webhook: Option<webhook::Config>,
+    /// An NTP server to compare the local clock against, in addition to gossip block arrivals.
+    pub ntp_server: Option<String>,
     pub slasher: Option<slasher::Config>,
     pub logger_config: LoggerConfig,
     pub beacon_processor: BeaconProcessorConfig,
     pub genesis_state_url: Option<String>,
     pub genesis_state_url_timeout: Duration,
     pub allow_insecure_genesis_sync: bool,
+    /// Path to a shared library for verifying BLS signatures, see `bls::accelerator`.
+    pub bls_accelerator: Option<PathBuf>,
+    /// If set, blocks are replayed from an archive instead of syncing from the network.
+    pub replay: Option<ReplayConfig>,
 }
 
 impl Default for Config {
@@ -96,10 +112,12 @@ impl Default for Config {
             blobs_db_path: None,
             log_file: PathBuf::from(""),
             genesis: <_>::default(),
+            deposit_snapshot: None,
             store: <_>::default(),
             network: NetworkConfig::default(),
             chain: <_>::default(),
             dummy_eth1_backend: false,
+            stub_eth1_votes: false,
             sync_eth1_chain: false,
             eth1: <_>::default(),
             execution_layer: None,
@@ -108,6 +126,8 @@ impl Default for Config {
             http_api: <_>::default(),
             http_metrics: <_>::default(),
             monitoring_api: None,
+            webhook: None,
+            ntp_server: None,
             slasher: None,
             validator_monitor: <_>::default(),
             logger_config: LoggerConfig::default(),
@@ -116,6 +136,8 @@ impl Default for Config {
             // This default value should always be overwritten by the CLI default value.
             genesis_state_url_timeout: Duration::from_secs(60),
             allow_insecure_genesis_sync: false,
+            bls_accelerator: None,
+            replay: None,
         }
     }
 }
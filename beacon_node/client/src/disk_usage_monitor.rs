@@ -0,0 +1,72 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use directory::size_of_dir;
+use slog::{debug, warn};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::retention::DiskUsageSample;
+use sysinfo::{System, SystemExt};
+use system_health::observe_disk_space;
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+
+/// The interval between measurements of the database's size.
+pub const DISK_USAGE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Spawns a service which periodically measures the size of the databases at `db_paths` and the
+/// free space on the disk holding the first of them, and records it in the store.
+///
+/// The samples drive the store's retention policy and disk usage forecast. When the retention
+/// policy is activated, payloads which were retained prior to activation are pruned.
+pub fn spawn_disk_usage_monitor<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    db_paths: Vec<PathBuf>,
+) {
+    let log = executor.log().clone();
+    let inner_executor = executor.clone();
+
+    let monitor_future = async move {
+        loop {
+            let store = beacon_chain.store.clone();
+            let paths = db_paths.clone();
+            let measure = move || {
+                let db_bytes = paths.iter().map(|path| size_of_dir(path)).sum();
+                let mut sysinfo = System::new();
+                sysinfo.refresh_disks_list();
+                let (_, disk_bytes_free) = observe_disk_space(&sysinfo, &paths[0]);
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs());
+
+                let sample = DiskUsageSample {
+                    timestamp,
+                    db_bytes,
+                    disk_bytes_free,
+                };
+                if store.record_disk_usage(sample) && !store.get_config().prune_payloads {
+                    store.try_prune_execution_payloads(false)?;
+                }
+                Ok::<_, store::Error>(sample)
+            };
+
+            match inner_executor.spawn_blocking_handle(measure, "disk_usage_sample") {
+                Some(handle) => match handle.await {
+                    Ok(Ok(sample)) => debug!(
+                        log,
+                        "Measured database size";
+                        "db_bytes" => sample.db_bytes,
+                        "disk_bytes_free" => sample.disk_bytes_free,
+                    ),
+                    Ok(Err(e)) => warn!(log, "Unable to prune payloads"; "error" => ?e),
+                    Err(e) => warn!(log, "Disk usage measurement failed"; "error" => ?e),
+                },
+                None => break,
+            }
+
+            sleep(DISK_USAGE_INTERVAL).await;
+        }
+    };
+
+    executor.spawn(monitor_future, "disk_usage_monitor");
+}
@@ -1,7 +1,12 @@
+mod clock_monitor;
 mod compute_light_client_updates;
 pub mod config;
+mod disk_usage_monitor;
 mod metrics;
 mod notifier;
+mod replay;
+mod slasher_store;
+mod webhook_notifier;
 
 pub mod builder;
 pub mod error;
@@ -15,6 +20,8 @@ pub use beacon_chain::{BeaconChainTypes, Eth1ChainBackend};
 pub use builder::ClientBuilder;
 pub use config::{ClientGenesis, Config as ClientConfig};
 pub use eth2_config::Eth2Config;
+pub use replay::ReplayConfig;
+pub use slasher_store::SlasherBackingStore;
 
 /// The core "beacon node" client.
 ///
@@ -44,6 +51,14 @@ impl<T: BeaconChainTypes> Client<T> {
         self.http_metrics_listen_addr
     }
 
+    /// Serve the per-chain metrics of this client from the metrics server of the process, with an
+    /// `instance` label of `name`.
+    pub fn register_instance_metrics(&self, name: String) {
+        if let Some(chain) = &self.beacon_chain {
+            http_metrics::register_instance(name, Arc::downgrade(chain));
+        }
+    }
+
     /// Returns the list of libp2p addresses the client is listening to.
     pub fn libp2p_listen_addresses(&self) -> Option<Vec<Multiaddr>> {
         self.network_globals.as_ref().map(|n| n.listen_multiaddrs())
@@ -16,4 +16,14 @@ lazy_static! {
         "notifier_head_slot",
         "The head slot sourced from the beacon chain notifier"
     );
+
+    pub static ref CLOCK_OFFSET_GOSSIP: Result<IntGauge> = try_create_int_gauge(
+        "beacon_clock_offset_gossip_milliseconds",
+        "Estimate of how far the local clock is ahead of the network, from block arrival times"
+    );
+
+    pub static ref CLOCK_OFFSET_NTP: Result<IntGauge> = try_create_int_gauge(
+        "beacon_clock_offset_ntp_milliseconds",
+        "How far the local clock is ahead of the configured NTP server"
+    );
 }
@@ -4,6 +4,7 @@ use beacon_chain::{
     capella_readiness::CapellaReadiness,
     deneb_readiness::DenebReadiness,
     electra_readiness::ElectraReadiness,
+    eth1_chain::is_eth1_voting_complete,
     BeaconChain, BeaconChainTypes, ExecutionStatus,
 };
 use lighthouse_network::{types::SyncState, NetworkGlobals};
@@ -674,8 +675,12 @@ fn eth1_logging<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>, log: &Logger
 
     // Perform some logging about the eth1 chain
     if let Some(eth1_chain) = beacon_chain.eth1_chain.as_ref() {
-        // No need to do logging if using the dummy backend.
-        if eth1_chain.is_dummy_backend() {
+        // No need to do logging if using the dummy backend, or if the deposit contract cache is
+        // not used for votes.
+        if eth1_chain.is_dummy_backend() || eth1_chain.is_stubbed() {
+            return;
+        }
+        if is_eth1_voting_complete(&beacon_chain.head_snapshot().beacon_state) {
             return;
         }
 
@@ -0,0 +1,308 @@
+//! Replays an archive of blocks and blobs through full block import, without networking, so that
+//! real workloads can be reproduced and profiled locally.
+//!
+//! The archive is a directory containing either or both of:
+//!
+//! - `.era` files, in the format served by era archives. Only the blocks are read, era files do
+//!   not contain blobs.
+//! - `block_<slot>.ssz` files containing a `SignedBeaconBlock`, each with an optional
+//!   `blobs_<slot>.ssz` file containing its `BlobSidecarList`.
+//!
+//! Era files are replayed in file name order, followed by the SSZ files in slot order. Blocks
+//! which are already known or finalized are skipped, so the archive may start before the node's
+//! head, but it must not skip any blocks after it.
+use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::{
+    AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError, NotifyExecutionLayer,
+};
+use serde::{Deserialize, Serialize};
+use slog::{crit, debug, info, Logger};
+use ssz::Decode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use store::era;
+use task_executor::{ShutdownReason, TaskExecutor};
+use types::{BlobSidecarList, BlockImportSource, ChainSpec, EthSpec, SignedBeaconBlock, Slot};
+
+/// The number of imported blocks between progress logs.
+const PROGRESS_LOG_INTERVAL: usize = 256;
+
+/// Configuration for replaying an archive of blocks instead of syncing from the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayConfig {
+    /// The directory containing the archive.
+    pub dir: PathBuf,
+    /// If true, execution payloads are sent to the execution layer. Otherwise they are imported
+    /// optimistically.
+    pub execution_layer: bool,
+}
+
+/// A file in the archive, containing one or more blocks.
+enum ArchiveFile {
+    Era(PathBuf),
+    Ssz {
+        slot: Slot,
+        block: PathBuf,
+        blobs: Option<PathBuf>,
+    },
+}
+
+/// The time taken to import each block.
+#[derive(Default)]
+struct ReplayTimes {
+    imported: Vec<(Slot, Duration)>,
+    skipped: usize,
+}
+
+impl ReplayTimes {
+    fn total(&self) -> Duration {
+        self.imported.iter().map(|(_, time)| *time).sum()
+    }
+
+    /// Returns the time at the `percentile` of all import times.
+    fn percentile(&self, percentile: usize) -> Duration {
+        let mut times = self
+            .imported
+            .iter()
+            .map(|(_, time)| *time)
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        let index = (times.len() * percentile / 100).min(times.len().saturating_sub(1));
+        times.get(index).copied().unwrap_or_default()
+    }
+
+    fn slowest(&self) -> Option<(Slot, Duration)> {
+        self.imported.iter().copied().max_by_key(|(_, time)| *time)
+    }
+}
+
+/// Spawns a service which replays the archive in `config.dir` and then shuts down the node.
+pub fn spawn_replay<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+    config: ReplayConfig,
+) -> Result<(), String> {
+    let log = executor.log().clone();
+    let files = list_archive(&config.dir)?;
+
+    info!(
+        log,
+        "Starting replay";
+        "dir" => %config.dir.display(),
+        "files" => files.len(),
+        "execution_layer" => config.execution_layer,
+    );
+
+    let mut shutdown_sender = executor.shutdown_sender();
+    executor.spawn(
+        async move {
+            let reason = match replay(&chain, &files, &config, &log).await {
+                Ok(()) => ShutdownReason::Success("Replay complete"),
+                Err(e) => {
+                    crit!(log, "Replay failed"; "error" => e);
+                    ShutdownReason::Failure("Replay failed")
+                }
+            };
+            let _ = shutdown_sender.try_send(reason);
+        },
+        "replay",
+    );
+
+    Ok(())
+}
+
+async fn replay<T: BeaconChainTypes>(
+    chain: &Arc<BeaconChain<T>>,
+    files: &[ArchiveFile],
+    config: &ReplayConfig,
+    log: &Logger,
+) -> Result<(), String> {
+    let notify_execution_layer = if config.execution_layer {
+        NotifyExecutionLayer::Yes
+    } else {
+        NotifyExecutionLayer::No
+    };
+
+    let start = Instant::now();
+    let mut times = ReplayTimes::default();
+    for file in files {
+        for (block, blobs) in read_blocks::<T::EthSpec>(file, &chain.spec)? {
+            let slot = block.slot();
+            match import_block(chain, block, blobs, notify_execution_layer).await? {
+                Some(time) => {
+                    debug!(
+                        log,
+                        "Replayed block";
+                        "slot" => slot,
+                        "time_ms" => time.as_millis(),
+                    );
+                    times.imported.push((slot, time));
+                    if times.imported.len() % PROGRESS_LOG_INTERVAL == 0 {
+                        info!(
+                            log,
+                            "Replay progress";
+                            "slot" => slot,
+                            "imported" => times.imported.len(),
+                            "blocks_per_sec" => format!(
+                                "{:.2}",
+                                times.imported.len() as f64 / start.elapsed().as_secs_f64()
+                            ),
+                        );
+                    }
+                }
+                None => times.skipped += 1,
+            }
+        }
+    }
+
+    let imported = times.imported.len();
+    let total = times.total();
+    let (slowest_slot, slowest_time) = times.slowest().unwrap_or_default();
+    info!(
+        log,
+        "Replay complete";
+        "imported" => imported,
+        "skipped" => times.skipped,
+        "elapsed_secs" => start.elapsed().as_secs(),
+        "import_secs" => total.as_secs(),
+        "mean_ms" => total.as_millis().checked_div(imported as u128).unwrap_or(0),
+        "p50_ms" => times.percentile(50).as_millis(),
+        "p99_ms" => times.percentile(99).as_millis(),
+        "slowest_ms" => slowest_time.as_millis(),
+        "slowest_slot" => slowest_slot,
+        "head_slot" => chain.head_snapshot().beacon_block.slot(),
+    );
+
+    Ok(())
+}
+
+/// Imports `block` and updates the head, returning the time taken or `None` if the block was
+/// skipped.
+async fn import_block<T: BeaconChainTypes>(
+    chain: &Arc<BeaconChain<T>>,
+    block: Arc<SignedBeaconBlock<T::EthSpec>>,
+    blobs: Option<BlobSidecarList<T::EthSpec>>,
+    notify_execution_layer: NotifyExecutionLayer,
+) -> Result<Option<Duration>, String> {
+    let block_root = block.canonical_root();
+    let slot = block.slot();
+    let rpc_block = RpcBlock::new(Some(block_root), block, blobs)
+        .map_err(|e| format!("Invalid blobs for block at slot {}: {:?}", slot, e))?;
+
+    let start = Instant::now();
+    match chain
+        .process_block(
+            block_root,
+            rpc_block,
+            notify_execution_layer,
+            BlockImportSource::RangeSync,
+            || Ok(()),
+        )
+        .await
+    {
+        Ok(AvailabilityProcessingStatus::Imported(_)) => (),
+        Ok(AvailabilityProcessingStatus::MissingComponents(..)) => {
+            return Err(format!(
+                "Block {:?} at slot {} requires blobs which are not in the archive",
+                block_root, slot
+            ));
+        }
+        Err(BlockError::BlockIsAlreadyKnown(_))
+        | Err(BlockError::WouldRevertFinalizedSlot { .. })
+        | Err(BlockError::GenesisBlock) => return Ok(None),
+        Err(e) => {
+            return Err(format!(
+                "Unable to import block {:?} at slot {}: {:?}",
+                block_root, slot, e
+            ));
+        }
+    }
+    chain.recompute_head_at_current_slot().await;
+
+    Ok(Some(start.elapsed()))
+}
+
+/// Returns the files of the archive in `dir`, in the order they should be replayed.
+fn list_archive(dir: &Path) -> Result<Vec<ArchiveFile>, String> {
+    let mut era_files = vec![];
+    let mut ssz_files = vec![];
+    let entries = fs::read_dir(dir).map_err(|e| format!("Unable to read {:?}: {:?}", dir, e))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Unable to read {:?}: {:?}", dir, e))?
+            .path();
+        let file_name = path.file_name().and_then(|name| name.to_str());
+        if path
+            .extension()
+            .map_or(false, |extension| extension == "era")
+        {
+            era_files.push(path);
+        } else if let Some(slot) = file_name
+            .and_then(|name| name.strip_prefix("block_"))
+            .and_then(|name| name.strip_suffix(".ssz"))
+            .and_then(|slot| slot.parse::<u64>().ok())
+        {
+            let blobs = dir.join(format!("blobs_{}.ssz", slot));
+            ssz_files.push(ArchiveFile::Ssz {
+                slot: Slot::new(slot),
+                block: path,
+                blobs: blobs.exists().then_some(blobs),
+            });
+        }
+    }
+
+    era_files.sort();
+    ssz_files.sort_by_key(|file| match file {
+        ArchiveFile::Ssz { slot, .. } => *slot,
+        ArchiveFile::Era(_) => Slot::new(0),
+    });
+
+    if era_files.is_empty() && ssz_files.is_empty() {
+        return Err(format!("No .era or block_<slot>.ssz files in {:?}", dir));
+    }
+
+    Ok(era_files
+        .into_iter()
+        .map(ArchiveFile::Era)
+        .chain(ssz_files)
+        .collect())
+}
+
+/// Reads the blocks in `file`, along with their blobs.
+#[allow(clippy::type_complexity)]
+fn read_blocks<E: EthSpec>(
+    file: &ArchiveFile,
+    spec: &ChainSpec,
+) -> Result<Vec<(Arc<SignedBeaconBlock<E>>, Option<BlobSidecarList<E>>)>, String> {
+    let read =
+        |path: &Path| fs::read(path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e));
+
+    match file {
+        ArchiveFile::Era(path) => Ok(era::read_blocks(&read(path)?, spec)
+            .map_err(|e| format!("Invalid era file {:?}: {:?}", path, e))?
+            .into_iter()
+            .map(|block| (Arc::new(block), None))
+            .collect()),
+        ArchiveFile::Ssz { slot, block, blobs } => {
+            let block = SignedBeaconBlock::from_ssz_bytes(&read(block)?, spec)
+                .map_err(|e| format!("Invalid block {:?}: {:?}", block, e))?;
+            if block.slot() != *slot {
+                return Err(format!(
+                    "Block in block_{}.ssz is at slot {}",
+                    slot,
+                    block.slot()
+                ));
+            }
+            let blobs = blobs
+                .as_ref()
+                .map(|path| {
+                    BlobSidecarList::from_ssz_bytes(&read(path)?)
+                        .map_err(|e| format!("Invalid blobs {:?}: {:?}", path, e))
+                })
+                .transpose()?;
+            Ok(vec![(Arc::new(block), blobs)])
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Allows the slasher to keep its tables in the beacon node's hot database.
+use slasher::{BackingStore, BackingStoreIter};
+use std::sync::Arc;
+use store::{get_key_for_col, DBColumn, HotColdDB, ItemStore, KeyValueStore, KeyValueStoreOp};
+use types::EthSpec;
+
+/// Adapts the hot database of a `HotColdDB` to the slasher's `BackingStore` interface.
+///
+/// All of the slasher's data is stored in the `DBColumn::Slasher` column.
+pub struct SlasherBackingStore<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> SlasherBackingStore<E, Hot, Cold> {
+    pub fn new(store: Arc<HotColdDB<E, Hot, Cold>>) -> Self {
+        Self { store }
+    }
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackingStore
+    for SlasherBackingStore<E, Hot, Cold>
+{
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        self.store
+            .hot_db
+            .get_bytes(DBColumn::Slasher.into(), key)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    fn iter_from<'a>(&'a self, prefix: &[u8], from: &[u8]) -> BackingStoreIter<'a> {
+        Box::new(
+            self.store
+                .hot_db
+                .iter_raw_entries_from(DBColumn::Slasher, prefix, from)
+                .map(|result| result.map_err(|e| format!("{e:?}"))),
+        )
+    }
+
+    fn write_batch(&self, ops: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<(), String> {
+        let column = DBColumn::Slasher.into();
+        let batch = ops
+            .into_iter()
+            .map(|(key, value)| {
+                let db_key = get_key_for_col(column, &key);
+                match value {
+                    Some(value) => KeyValueStoreOp::PutKeyValue(db_key, value),
+                    None => KeyValueStoreOp::DeleteKey(db_key),
+                }
+            })
+            .collect();
+        self.store
+            .hot_db
+            .do_atomically(batch)
+            .map_err(|e| format!("{e:?}"))
+    }
+}
@@ -0,0 +1,160 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::EventKind;
+use lighthouse_network::NetworkGlobals;
+use slog::{info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::sleep;
+use types::{EthSpec, Slot};
+use webhook::{Config, Notification, SlashingKind, WebhookClient};
+
+/// Spawns a service which sends webhook notifications for the events selected in `config`.
+///
+/// Slashings and forks are notified as they are observed. Missed proposals, finality stalls and
+/// low peer counts are checked once per slot, with stalls and low peer counts notified only when
+/// they begin.
+pub fn spawn_webhook_notifier<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network: Arc<NetworkGlobals<T::EthSpec>>,
+    config: &Config,
+    seconds_per_slot: u64,
+) -> Result<(), String> {
+    let log = executor.log().clone();
+    let client = WebhookClient::new(config, executor.clone(), log.clone());
+    let event_handler = beacon_chain
+        .event_handler
+        .as_ref()
+        .ok_or("webhook notifier requires an event handler")?;
+
+    info!(
+        log,
+        "Starting webhook notifier";
+        "endpoints" => config.endpoints.len(),
+    );
+
+    // Forward slashings and re-orgs as they happen.
+    let mut reorgs = event_handler.subscribe_reorgs();
+    let mut attester_slashings = event_handler.subscribe_attester_slashing();
+    let mut proposer_slashings = event_handler.subscribe_proposer_slashing();
+    let event_client = client.clone();
+    let event_log = log.clone();
+    let event_future = async move {
+        loop {
+            let event = tokio::select! {
+                event = reorgs.recv() => event.map(|event| event.kind),
+                event = attester_slashings.recv() => event,
+                event = proposer_slashings.recv() => event,
+            };
+            let notification = match event {
+                Ok(EventKind::ChainReorg(reorg)) => Notification::Fork {
+                    slot: reorg.slot,
+                    depth: reorg.depth,
+                    old_head_block: reorg.old_head_block,
+                    new_head_block: reorg.new_head_block,
+                },
+                Ok(EventKind::AttesterSlashing(slashing)) => {
+                    let attestation_2 = slashing.attestation_2();
+                    let mut validator_indices = slashing
+                        .attestation_1()
+                        .attesting_indices_iter()
+                        .filter(|index| attestation_2.attesting_indices_iter().any(|i| i == *index))
+                        .copied()
+                        .collect::<Vec<_>>();
+                    validator_indices.sort_unstable();
+                    Notification::Slashing {
+                        kind: SlashingKind::Attester,
+                        validator_indices,
+                    }
+                }
+                Ok(EventKind::ProposerSlashing(slashing)) => Notification::Slashing {
+                    kind: SlashingKind::Proposer,
+                    validator_indices: vec![slashing.signed_header_1.message.proposer_index],
+                },
+                Ok(_) => continue,
+                Err(RecvError::Lagged(n)) => {
+                    warn!(event_log, "Webhook notifier dropped events"; "count" => n);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            event_client.notify(notification);
+        }
+    };
+    executor.spawn(event_future, "webhook_events");
+
+    let slot_duration = Duration::from_secs(seconds_per_slot);
+    let finality_stall_epochs = config.finality_stall_epochs;
+    let low_peer_count = config.low_peer_count;
+    let slot_future = async move {
+        let mut notified_missed_blocks = HashSet::<(u64, Slot)>::new();
+        let mut finality_stalled = false;
+        let mut low_peers = false;
+
+        loop {
+            // Check half way through each slot, as per the slot notifier.
+            let wait = match beacon_chain.slot_clock.duration_to_next_slot() {
+                Some(duration) => duration + slot_duration / 2,
+                None => {
+                    warn!(log, "Unable to read current slot");
+                    sleep(slot_duration).await;
+                    continue;
+                }
+            };
+            sleep(wait).await;
+
+            let Ok(current_slot) = beacon_chain.slot() else {
+                continue;
+            };
+            let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+            let finalized_epoch = beacon_chain
+                .canonical_head
+                .cached_head()
+                .finalized_checkpoint()
+                .epoch;
+
+            let missed_blocks = beacon_chain
+                .validator_monitor
+                .read()
+                .get_monitored_validator_missed_blocks();
+            for (validator_index, slot) in missed_blocks {
+                if notified_missed_blocks.insert((validator_index, slot)) {
+                    client.notify(Notification::MissedProposal {
+                        validator_index,
+                        slot,
+                    });
+                }
+            }
+            let finalized_slot = finalized_epoch.start_slot(T::EthSpec::slots_per_epoch());
+            notified_missed_blocks.retain(|(_, slot)| *slot >= finalized_slot);
+
+            // The chain can only be judged to have stalled once we are synced.
+            let stalled = network.sync_state().is_synced()
+                && current_epoch.saturating_sub(finalized_epoch) >= finality_stall_epochs;
+            if stalled && !finality_stalled {
+                client.notify(Notification::FinalityStall {
+                    current_epoch,
+                    finalized_epoch,
+                });
+            }
+            finality_stalled = stalled;
+
+            let peer_count = network.connected_peers();
+            let low = peer_count < low_peer_count;
+            if low && !low_peers {
+                client.notify(Notification::LowPeerCount {
+                    peer_count,
+                    threshold: low_peer_count,
+                });
+            }
+            low_peers = low;
+        }
+    };
+    executor.spawn(slot_future, "webhook_notifier");
+
+    Ok(())
+}
@@ -16,10 +16,22 @@ use types::{ChainSpec, DepositTreeSnapshot, Eth1Data};
 // selector.
 four_byte_option_impl!(four_byte_option_u64, u64);
 
+/// The progress of the deposit cache when it was last persisted.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DepositCheckpoint {
+    pub last_processed_block: u64,
+    pub finalized_deposit_count: u64,
+}
+
 #[derive(Default)]
 pub struct DepositUpdater {
     pub cache: DepositCache,
     pub last_processed_block: Option<u64>,
+    /// The number of blocks to request logs for at once, which is reduced when requests fail.
+    ///
+    /// Uses `Config::blocks_per_log_query` if `None`.
+    pub blocks_per_log_query: Option<usize>,
+    pub checkpoint: DepositCheckpoint,
 }
 
 impl DepositUpdater {
@@ -28,6 +40,8 @@ impl DepositUpdater {
         DepositUpdater {
             cache,
             last_processed_block: None,
+            blocks_per_log_query: None,
+            checkpoint: DepositCheckpoint::default(),
         }
     }
 
@@ -36,10 +50,22 @@ impl DepositUpdater {
         snapshot: &DepositTreeSnapshot,
     ) -> Result<Self, String> {
         let last_processed_block = Some(snapshot.execution_block_height);
-        Ok(Self {
+        let mut updater = Self {
             cache: DepositCache::from_deposit_snapshot(deposit_contract_deploy_block, snapshot)?,
             last_processed_block,
-        })
+            blocks_per_log_query: None,
+            checkpoint: DepositCheckpoint::default(),
+        };
+        updater.checkpoint = updater.current_checkpoint();
+        Ok(updater)
+    }
+
+    /// Returns the current progress of the cache.
+    pub fn current_checkpoint(&self) -> DepositCheckpoint {
+        DepositCheckpoint {
+            last_processed_block: self.last_processed_block.unwrap_or(0),
+            finalized_deposit_count: self.cache.finalized_deposit_count(),
+        }
     }
 }
 
@@ -113,12 +139,18 @@ impl SszEth1Cache {
     }
 
     pub fn to_inner(&self, config: Config, spec: ChainSpec) -> Result<Inner, String> {
+        let mut deposit_updater = DepositUpdater {
+            cache: self.deposit_cache.to_deposit_cache()?,
+            last_processed_block: self.last_processed_block,
+            blocks_per_log_query: None,
+            checkpoint: DepositCheckpoint::default(),
+        };
+        // The persisted cache is already checkpointed.
+        deposit_updater.checkpoint = deposit_updater.current_checkpoint();
+
         Ok(Inner {
             block_cache: RwLock::new(self.block_cache.clone()),
-            deposit_cache: RwLock::new(DepositUpdater {
-                cache: self.deposit_cache.to_deposit_cache()?,
-                last_processed_block: self.last_processed_block,
-            }),
+            deposit_cache: RwLock::new(deposit_updater),
             endpoint: endpoint_from_config(&config)
                 .map_err(|e| format!("Failed to create endpoint: {:?}", e))?,
             to_finalize: RwLock::new(None),
@@ -18,6 +18,8 @@ lazy_static! {
         try_create_int_gauge("eth1_deposit_cache_len", "Number of deposits in the eth1 cache");
     pub static ref HIGHEST_PROCESSED_DEPOSIT_BLOCK: Result<IntGauge> =
         try_create_int_gauge("eth1_highest_processed_deposit_block", "Number of the last block checked for deposits");
+    pub static ref DEPOSIT_LOG_BATCH_SIZE: Result<IntGauge> =
+        try_create_int_gauge("eth1_deposit_log_batch_size", "Number of blocks requested in each query for deposit logs");
 
     /*
      * Eth1 rpc connection
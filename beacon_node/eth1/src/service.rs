@@ -36,6 +36,10 @@ const GET_BLOCK_TIMEOUT_MILLIS: u64 = STANDARD_TIMEOUT_MILLIS;
 /// Timeout when doing an eth_getLogs to read the deposit contract logs.
 const GET_DEPOSIT_LOG_TIMEOUT_MILLIS: u64 = 60_000;
 
+/// The number of blocks the deposit cache must advance by before it is persisted again, unless
+/// deposits are finalized first.
+pub const DEPOSIT_CHECKPOINT_INTERVAL_BLOCKS: u64 = 65_536;
+
 /// Number of blocks to download if the node detects it is lagging behind due to an inaccurate
 /// relationship between block-number-based follow distance and time-based follow distance.
 const CATCHUP_BATCH_SIZE: u64 = 128;
@@ -620,6 +624,16 @@ impl Service {
         let chain_id = self.config().chain_id.clone();
         let node_far_behind_seconds = self.inner.config.read().node_far_behind_seconds;
 
+        match client.reload_jwt_secrets() {
+            Ok(true) => info!(log, "Reloaded JWT secrets for eth1 endpoint"),
+            Ok(false) => {}
+            Err(e) => warn!(
+                log,
+                "Failed to reload JWT secrets for eth1 endpoint";
+                "error" => ?e,
+            ),
+        }
+
         match endpoint_state(client, &chain_id, &log).await {
             Ok(()) => crate::metrics::set_gauge(&metrics::ETH1_CONNECTED, 1),
             Err(e) => {
@@ -827,11 +841,34 @@ impl Service {
         self.inner.deposit_cache.read().cache.get_deposit_snapshot()
     }
 
+    /// Returns `true` if the deposit cache should be persisted, recording its current progress as
+    /// the latest checkpoint.
+    ///
+    /// A checkpoint is due when deposits have been finalized, or the cache has advanced by
+    /// `DEPOSIT_CHECKPOINT_INTERVAL_BLOCKS` since the previous checkpoint. Logs are only imported
+    /// from blocks beyond the follow distance, so a checkpoint is safe from eth1 re-orgs.
+    pub fn take_checkpoint(&self) -> bool {
+        let mut deposit_updater = self.inner.deposit_cache.write();
+        let previous = deposit_updater.checkpoint;
+        let current = deposit_updater.current_checkpoint();
+
+        let due = current.finalized_deposit_count != previous.finalized_deposit_count
+            || current.last_processed_block
+                >= previous
+                    .last_processed_block
+                    .saturating_add(DEPOSIT_CHECKPOINT_INTERVAL_BLOCKS);
+        if due {
+            deposit_updater.checkpoint = current;
+        }
+        due
+    }
+
     /// Contacts the remote eth1 node and attempts to import deposit logs up to the configured
     /// follow-distance block.
     ///
     /// Will process no more than `BLOCKS_PER_LOG_QUERY * MAX_LOG_REQUESTS_PER_UPDATE` blocks in a
-    /// single update.
+    /// single update. Logs are requested in batches of `BLOCKS_PER_LOG_QUERY` blocks, which are
+    /// halved whenever a request fails and grow back after successful requests.
     ///
     /// If `remote_highest_block_opt` is `Some`, use that value instead of querying `self.endpoint`
     /// for the head of the eth1 chain.
@@ -849,7 +886,7 @@ impl Service {
         let client = self.client();
         let deposit_contract_address = self.config().deposit_contract_address.clone();
 
-        let blocks_per_log_query = self.config().blocks_per_log_query;
+        let max_blocks_per_log_query = self.config().blocks_per_log_query;
         let max_log_requests_per_update = self
             .config()
             .max_log_requests_per_update
@@ -865,44 +902,56 @@ impl Service {
             }
         };
 
-        let block_number_chunks = if let Some(range) = range {
-            range
-                .collect::<Vec<u64>>()
-                .chunks(blocks_per_log_query)
-                .take(max_log_requests_per_update)
-                .map(|vec| {
-                    let first = vec.first().cloned().unwrap_or(0);
-                    let last = vec.last().map(|n| n + 1).unwrap_or(0);
-                    first..last
-                })
-                .collect::<Vec<Range<u64>>>()
-        } else {
-            Vec::new()
-        };
-
+        let mut blocks_per_log_query = self
+            .deposits()
+            .read()
+            .blocks_per_log_query
+            .unwrap_or(max_blocks_per_log_query)
+            .clamp(1, max_blocks_per_log_query.max(1));
+        let mut remaining = range.map(RangeInclusive::into_inner);
+        let mut log_requests = 0;
         let mut logs_imported: usize = 0;
         let deposit_contract_address_ref: &str = &deposit_contract_address;
-        for block_range in block_number_chunks.into_iter() {
-            if block_range.is_empty() {
-                debug!(
-                    self.log,
-                    "No new blocks to scan for logs";
-                );
-                continue;
+        while let Some((start, end)) = remaining.filter(|(start, end)| start <= end) {
+            if log_requests >= max_log_requests_per_update {
+                break;
             }
+            log_requests += 1;
+
+            let block_range = start..std::cmp::min(start + blocks_per_log_query as u64, end + 1);
 
             /*
              * Step 1. Download logs.
+             *
+             * If the request fails, retry the same blocks in smaller batches since the endpoint
+             * may be limiting the size of responses or timing out on large ranges.
              */
-            let block_range_ref = &block_range;
-            let logs = client
+            let logs = match client
                 .get_deposit_logs_in_range(
                     deposit_contract_address_ref,
-                    block_range_ref.clone(),
+                    block_range.clone(),
                     Duration::from_millis(GET_DEPOSIT_LOG_TIMEOUT_MILLIS),
                 )
                 .await
-                .map_err(Error::GetDepositLogsFailed)?;
+            {
+                Ok(logs) => logs,
+                Err(e) if blocks_per_log_query > 1 => {
+                    blocks_per_log_query /= 2;
+                    self.deposits().write().blocks_per_log_query = Some(blocks_per_log_query);
+                    metrics::set_gauge(
+                        &metrics::DEPOSIT_LOG_BATCH_SIZE,
+                        blocks_per_log_query as i64,
+                    );
+                    debug!(
+                        self.log,
+                        "Reducing deposit log batch size";
+                        "blocks_per_log_query" => blocks_per_log_query,
+                        "error" => e,
+                    );
+                    continue;
+                }
+                Err(e) => return Err(Error::GetDepositLogsFailed(e)),
+            };
 
             /*
              * Step 2. Import logs to cache.
@@ -948,12 +997,24 @@ impl Service {
             );
 
             cache.last_processed_block = Some(block_range.end.saturating_sub(1));
+            remaining = Some((block_range.end, end));
+
+            // Grow the batch again after a successful request, up to the configured size.
+            blocks_per_log_query = std::cmp::min(
+                blocks_per_log_query.saturating_mul(2),
+                max_blocks_per_log_query,
+            );
+            cache.blocks_per_log_query = Some(blocks_per_log_query);
 
             metrics::set_gauge(&metrics::DEPOSIT_CACHE_LEN, cache.cache.len() as i64);
             metrics::set_gauge(
                 &metrics::HIGHEST_PROCESSED_DEPOSIT_BLOCK,
                 cache.last_processed_block.unwrap_or(0) as i64,
             );
+            metrics::set_gauge(
+                &metrics::DEPOSIT_LOG_BATCH_SIZE,
+                blocks_per_log_query as i64,
+            );
         }
 
         if logs_imported > 0 {
@@ -1241,6 +1302,7 @@ async fn download_eth1_block(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sloggers::Build;
     use types::MainnetEthSpec;
 
     #[test]
@@ -1275,4 +1337,19 @@ mod tests {
 
         assert!(len > minimum_len as usize);
     }
+
+    #[test]
+    fn deposit_checkpoints() {
+        let log = sloggers::null::NullLoggerBuilder.build().unwrap();
+        let service = Service::new(Config::default(), log, MainnetEthSpec::default_spec()).unwrap();
+        assert!(!service.take_checkpoint());
+
+        service.deposits().write().last_processed_block =
+            Some(DEPOSIT_CHECKPOINT_INTERVAL_BLOCKS - 1);
+        assert!(!service.take_checkpoint());
+
+        service.deposits().write().last_processed_block = Some(DEPOSIT_CHECKPOINT_INTERVAL_BLOCKS);
+        assert!(service.take_checkpoint());
+        assert!(!service.take_checkpoint());
+    }
 }
@@ -2,14 +2,19 @@ use crate::{
     json_structures::{EncodableJsonWithdrawal, JsonWithdrawal},
     keccak::{keccak256, KeccakHasher},
 };
-use alloy_rlp::Encodable;
+use alloy_rlp::{Encodable, RlpEncodable};
 use keccak_hash::KECCAK_EMPTY_LIST_RLP;
 use triehash::ordered_trie_root;
 use types::{
-    EncodableExecutionBlockHeader, EthSpec, ExecutionBlockHash, ExecutionBlockHeader,
-    ExecutionPayloadRef, Hash256,
+    DepositRequest, EncodableExecutionBlockHeader, EthSpec, ExecutionBlockHash,
+    ExecutionBlockHeader, ExecutionLayerWithdrawalRequest, ExecutionPayloadRef, Hash256,
 };
 
+/// The EIP-7685 request type of deposit requests.
+const DEPOSIT_REQUEST_TYPE: u8 = 0x00;
+/// The EIP-7685 request type of withdrawal requests.
+const WITHDRAWAL_REQUEST_TYPE: u8 = 0x01;
+
 /// Calculate the block hash of an execution block.
 ///
 /// Return `(block_hash, transactions_root)`, where `transactions_root` is the root of the RLP
@@ -39,6 +44,23 @@ pub fn calculate_execution_block_hash<E: EthSpec>(
     let rlp_blob_gas_used = payload.blob_gas_used().ok();
     let rlp_excess_blob_gas = payload.excess_blob_gas().ok();
 
+    // Calculate the requests root (post-Electra).
+    let rlp_requests_root = if let ExecutionPayloadRef::Electra(payload) = payload {
+        let deposit_requests = payload
+            .deposit_requests
+            .iter()
+            .map(rlp_encode_deposit_request);
+        let withdrawal_requests = payload
+            .withdrawal_requests
+            .iter()
+            .map(rlp_encode_withdrawal_request);
+        Some(ordered_trie_root::<KeccakHasher, _>(
+            deposit_requests.chain(withdrawal_requests),
+        ))
+    } else {
+        None
+    };
+
     // Construct the block header.
     let exec_block_header = ExecutionBlockHeader::from_payload(
         payload,
@@ -48,6 +70,7 @@ pub fn calculate_execution_block_hash<E: EthSpec>(
         rlp_blob_gas_used,
         rlp_excess_blob_gas,
         parent_beacon_block_root,
+        rlp_requests_root,
     );
 
     // Hash the RLP encoding of the block header.
@@ -65,6 +88,49 @@ pub fn rlp_encode_withdrawal(withdrawal: &JsonWithdrawal) -> Vec<u8> {
     out
 }
 
+#[derive(RlpEncodable)]
+struct EncodableDepositRequest<'a> {
+    pubkey: &'a [u8],
+    withdrawal_credentials: &'a [u8],
+    amount: u64,
+    signature: &'a [u8],
+    index: u64,
+}
+
+#[derive(RlpEncodable)]
+struct EncodableWithdrawalRequest<'a> {
+    source_address: &'a [u8],
+    validator_pubkey: &'a [u8],
+    amount: u64,
+}
+
+/// Encode a deposit request as an EIP-7685 request, prefixed by its type.
+pub fn rlp_encode_deposit_request(request: &DepositRequest) -> Vec<u8> {
+    let signature = request.signature.serialize();
+    let mut out = vec![DEPOSIT_REQUEST_TYPE];
+    EncodableDepositRequest {
+        pubkey: request.pubkey.as_serialized(),
+        withdrawal_credentials: request.withdrawal_credentials.as_bytes(),
+        amount: request.amount,
+        signature: &signature,
+        index: request.index,
+    }
+    .encode(&mut out);
+    out
+}
+
+/// Encode a withdrawal request as an EIP-7685 request, prefixed by its type.
+pub fn rlp_encode_withdrawal_request(request: &ExecutionLayerWithdrawalRequest) -> Vec<u8> {
+    let mut out = vec![WITHDRAWAL_REQUEST_TYPE];
+    EncodableWithdrawalRequest {
+        source_address: request.source_address.as_bytes(),
+        validator_pubkey: request.validator_pubkey.as_serialized(),
+        amount: request.amount,
+    }
+    .encode(&mut out);
+    out
+}
+
 /// RLP encode an execution block header.
 pub fn rlp_encode_block_header(header: &ExecutionBlockHeader) -> Vec<u8> {
     let mut out: Vec<u8> = vec![];
@@ -77,7 +143,10 @@ mod test {
     use super::*;
     use hex::FromHex;
     use std::str::FromStr;
-    use types::{Address, Hash256, Hash64};
+    use types::{
+        Address, ExecutionPayloadElectra, FixedVector, Hash256, Hash64, MainnetEthSpec,
+        PublicKeyBytes, Signature, VariableList, Withdrawal,
+    };
 
     fn test_rlp_encoding(
         header: &ExecutionBlockHeader,
@@ -118,6 +187,7 @@ mod test {
             blob_gas_used: None,
             excess_blob_gas: None,
             parent_beacon_block_root: None,
+            requests_root: None,
         };
         let expected_rlp = "f90200a0e0a94a7a3c9617401586b1a27025d2d9671332d22d540e0af72b069170380f2aa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d4934794ba5e000000000000000000000000000000000000a0ec3c94b18b8a1cff7d60f8d258ec723312932928626b4c9355eb4ab3568ec7f7a050f738580ed699f0469702c7ccc63ed2e51bc034be9479b7bff4e68dee84accfa029b0562f7140574dd0d50dee8a271b22e1a0a7b78fca58f7c60370d8317ba2a9b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000830200000188016345785d8a00008301553482079e42a0000000000000000000000000000000000000000000000000000000000000000088000000000000000082036b";
         let expected_hash =
@@ -149,6 +219,7 @@ mod test {
             blob_gas_used: None,
             excess_blob_gas: None,
             parent_beacon_block_root: None,
+            requests_root: None,
         };
         let expected_rlp = "f901fda0927ca537f06c783a3a2635b8805eef1c8c2124f7444ad4a3389898dd832f2dbea01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d4934794ba5e000000000000000000000000000000000000a0e97859b065bd8dbbb4519c7cb935024de2484c2b7f881181b4360492f0b06b82a050f738580ed699f0469702c7ccc63ed2e51bc034be9479b7bff4e68dee84accfa029b0562f7140574dd0d50dee8a271b22e1a0a7b78fca58f7c60370d8317ba2a9b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800188016345785d8a00008301553482079e42a0000000000000000000000000000000000000000000000000000000000002000088000000000000000082036b";
         let expected_hash =
@@ -181,6 +252,7 @@ mod test {
             blob_gas_used: None,
             excess_blob_gas: None,
             parent_beacon_block_root: None,
+            requests_root: None,
         };
         let expected_hash =
             Hash256::from_str("6da69709cd5a34079b6604d29cd78fc01dacd7c6268980057ad92a2bede87351")
@@ -211,6 +283,7 @@ mod test {
             blob_gas_used: Some(0x0u64),
             excess_blob_gas: Some(0x0u64),
             parent_beacon_block_root: Some(Hash256::from_str("f7d327d2c04e4f12e9cdd492e53d39a1d390f8b1571e3b2a22ac6e1e170e5b1a").unwrap()),
+            requests_root: None,
         };
         let expected_hash =
             Hash256::from_str("a7448e600ead0a23d16f96aa46e8dea9eef8a7c5669a5f0a5ff32709afe9c408")
@@ -241,10 +314,86 @@ mod test {
             blob_gas_used: Some(0x0u64),
             excess_blob_gas: Some(0x0u64),
             parent_beacon_block_root: Some(Hash256::from_str("f7d327d2c04e4f12e9cdd492e53d39a1d390f8b1571e3b2a22ac6e1e170e5b1a").unwrap()),
+            requests_root: None,
         };
         let expected_hash =
             Hash256::from_str("a7448e600ead0a23d16f96aa46e8dea9eef8a7c5669a5f0a5ff32709afe9c408")
                 .unwrap();
         test_rlp_encoding(&header, None, expected_hash);
     }
+
+    /// An Electra payload with a withdrawal, a deposit request and a withdrawal request.
+    ///
+    /// The expected roots and block hash were computed with an independent implementation of
+    /// Keccak-256, RLP and the ordered Merkle-Patricia trie, which reproduces the hash of mainnet
+    /// block 16182891 above. They have not yet been checked against an execution client.
+    #[test]
+    fn test_execution_block_hash_electra_with_requests() {
+        let payload = ExecutionPayloadElectra::<MainnetEthSpec> {
+            parent_hash: ExecutionBlockHash::from_root(Hash256::repeat_byte(0x11)),
+            fee_recipient: Address::repeat_byte(0x22),
+            state_root: Hash256::repeat_byte(0x33),
+            receipts_root: Hash256::from_str(
+                "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            )
+            .unwrap(),
+            logs_bloom: FixedVector::default(),
+            prev_randao: Hash256::repeat_byte(0x44),
+            block_number: 100,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp: 1_700_000_000,
+            extra_data: VariableList::default(),
+            base_fee_per_gas: 7.into(),
+            block_hash: ExecutionBlockHash::zero(),
+            transactions: VariableList::default(),
+            withdrawals: vec![Withdrawal {
+                index: 5,
+                validator_index: 6,
+                address: Address::repeat_byte(0x55),
+                amount: 7,
+            }]
+            .into(),
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+            deposit_requests: vec![DepositRequest {
+                pubkey: PublicKeyBytes::deserialize(&[0xaa; 48]).unwrap(),
+                withdrawal_credentials: Hash256::from_str(
+                    "010000000000000000000000bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                )
+                .unwrap(),
+                amount: 32_000_000_000,
+                signature: Signature::empty(),
+                index: 9,
+            }]
+            .into(),
+            withdrawal_requests: vec![ExecutionLayerWithdrawalRequest {
+                source_address: Address::repeat_byte(0xcc),
+                validator_pubkey: PublicKeyBytes::deserialize(&[0xdd; 48]).unwrap(),
+                amount: 1_000_000_000,
+            }]
+            .into(),
+        };
+        let parent_beacon_block_root = Hash256::repeat_byte(0x66);
+
+        let (block_hash, transactions_root) = calculate_execution_block_hash(
+            ExecutionPayloadRef::Electra(&payload),
+            Some(parent_beacon_block_root),
+        );
+
+        assert_eq!(
+            transactions_root,
+            Hash256::from_str("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .unwrap()
+        );
+        assert_eq!(
+            block_hash,
+            ExecutionBlockHash::from_root(
+                Hash256::from_str(
+                    "d79e35456220ac629e6f5f82b7f845e119e1bd4f093a4c63c522865dcfe6e653"
+                )
+                .unwrap()
+            )
+        );
+    }
 }
@@ -36,6 +36,7 @@ pub mod auth;
 pub mod http;
 pub mod json_structures;
 mod new_payload_request;
+pub mod request_policy;
 
 pub use new_payload_request::{
     NewPayloadRequest, NewPayloadRequestBellatrix, NewPayloadRequestCapella,
@@ -741,6 +742,27 @@ impl EngineCapabilities {
 
         response
     }
+
+    /// Marks `method` as unsupported, so that it is no longer called.
+    pub fn disable(&mut self, method: &str) {
+        match method {
+            ENGINE_NEW_PAYLOAD_V1 => self.new_payload_v1 = false,
+            ENGINE_NEW_PAYLOAD_V2 => self.new_payload_v2 = false,
+            ENGINE_NEW_PAYLOAD_V3 => self.new_payload_v3 = false,
+            ENGINE_NEW_PAYLOAD_V4 => self.new_payload_v4 = false,
+            ENGINE_FORKCHOICE_UPDATED_V1 => self.forkchoice_updated_v1 = false,
+            ENGINE_FORKCHOICE_UPDATED_V2 => self.forkchoice_updated_v2 = false,
+            ENGINE_FORKCHOICE_UPDATED_V3 => self.forkchoice_updated_v3 = false,
+            ENGINE_GET_PAYLOAD_BODIES_BY_HASH_V1 => self.get_payload_bodies_by_hash_v1 = false,
+            ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1 => self.get_payload_bodies_by_range_v1 = false,
+            ENGINE_GET_PAYLOAD_V1 => self.get_payload_v1 = false,
+            ENGINE_GET_PAYLOAD_V2 => self.get_payload_v2 = false,
+            ENGINE_GET_PAYLOAD_V3 => self.get_payload_v3 = false,
+            ENGINE_GET_PAYLOAD_V4 => self.get_payload_v4 = false,
+            ENGINE_GET_CLIENT_VERSION_V1 => self.get_client_version_v1 = false,
+            _ => {}
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
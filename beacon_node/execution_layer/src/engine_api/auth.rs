@@ -1,6 +1,8 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use jsonwebtoken::{encode, get_current_timestamp, Algorithm, EncodingKey, Header};
+use parking_lot::RwLock;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use zeroize::Zeroize;
@@ -68,9 +70,23 @@ pub fn strip_prefix(s: &str) -> &str {
     }
 }
 
-/// Contains the JWT secret and claims parameters.
+/// The secrets which may be used to sign tokens, and the index of the one currently in use.
+struct AuthKeys {
+    keys: Vec<EncodingKey>,
+    active: usize,
+    /// The modification time of the secret file when the keys were read from it.
+    modified: Option<SystemTime>,
+}
+
+/// Contains the JWT secrets and claims parameters.
+///
+/// Several secrets may be provided, in which case tokens are signed with the first secret until a
+/// request is rejected by the server, at which point the next secret is tried. This allows the
+/// secret to be rotated without restarting either client: the new secret is added to the file read
+/// by Lighthouse before the execution node is switched over to it.
 pub struct Auth {
-    key: EncodingKey,
+    keys: RwLock<AuthKeys>,
+    path: Option<PathBuf>,
     id: Option<String>,
     clv: Option<String>,
 }
@@ -78,44 +94,120 @@ pub struct Auth {
 impl Auth {
     pub fn new(secret: JwtKey, id: Option<String>, clv: Option<String>) -> Self {
         Self {
-            key: EncodingKey::from_secret(secret.as_bytes()),
+            keys: RwLock::new(AuthKeys {
+                keys: vec![EncodingKey::from_secret(secret.as_bytes())],
+                active: 0,
+                modified: None,
+            }),
+            path: None,
             id,
             clv,
         }
     }
 
+    /// Create a new `Auth` struct which signs tokens with each of `secrets`, in order.
+    pub fn new_with_secrets(
+        secrets: Vec<JwtKey>,
+        id: Option<String>,
+        clv: Option<String>,
+    ) -> Result<Self, Error> {
+        if secrets.is_empty() {
+            return Err(Error::InvalidKey("No JWT secrets provided".to_string()));
+        }
+        Ok(Self {
+            keys: RwLock::new(AuthKeys {
+                keys: encoding_keys(&secrets),
+                active: 0,
+                modified: None,
+            }),
+            path: None,
+            id,
+            clv,
+        })
+    }
+
     /// Create a new `Auth` struct given the path to the file containing the hex
-    /// encoded jwt key.
+    /// encoded jwt key, or several keys with one per line.
+    ///
+    /// The file is read again by `Self::reload_if_modified` whenever it changes.
     pub fn new_with_path(
         jwt_path: PathBuf,
         id: Option<String>,
         clv: Option<String>,
     ) -> Result<Self, Error> {
-        std::fs::read_to_string(&jwt_path)
-            .map_err(|e| {
-                Error::InvalidKey(format!(
-                    "Failed to read JWT secret file {:?}, error: {:?}",
-                    jwt_path, e
-                ))
-            })
-            .and_then(|ref s| {
-                let secret_bytes = hex::decode(strip_prefix(s.trim_end()))
-                    .map_err(|e| Error::InvalidKey(format!("Invalid hex string: {:?}", e)))?;
-                let secret = JwtKey::from_slice(&secret_bytes).map_err(Error::InvalidKey)?;
-                Ok(Self::new(secret, id, clv))
-            })
+        let (secrets, modified) = read_secrets(&jwt_path)?;
+        Ok(Self {
+            keys: RwLock::new(AuthKeys {
+                keys: encoding_keys(&secrets),
+                active: 0,
+                modified,
+            }),
+            path: Some(jwt_path),
+            id,
+            clv,
+        })
+    }
+
+    /// Returns the number of secrets which may be used to sign tokens.
+    pub fn num_secrets(&self) -> usize {
+        self.keys.read().keys.len()
+    }
+
+    /// Sign subsequent tokens with the secret after the one at index `failed`, wrapping around to
+    /// the first.
+    ///
+    /// Nothing changes if another request has already rotated away from `failed`, so that
+    /// concurrent rejections of the same secret only advance it once.
+    pub fn rotate(&self, failed: usize) {
+        let mut keys = self.keys.write();
+        if keys.active == failed {
+            keys.active = (keys.active + 1) % keys.keys.len();
+        }
+    }
+
+    /// Reads the secret file again if it has been modified since it was last read, returning
+    /// `Ok(true)` if the secrets were replaced.
+    ///
+    /// The existing secrets are kept if the file cannot be read or is invalid, and the file is not
+    /// read again until it is next modified.
+    pub fn reload_if_modified(&self) -> Result<bool, Error> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified.is_some() && modified == self.keys.read().modified {
+            return Ok(false);
+        }
+
+        let (secrets, modified) = read_secrets(path).inspect_err(|_| {
+            self.keys.write().modified = modified;
+        })?;
+        *self.keys.write() = AuthKeys {
+            keys: encoding_keys(&secrets),
+            active: 0,
+            modified,
+        };
+        Ok(true)
     }
 
     /// Generate a JWT token with `claims.iat` set to current time.
     pub fn generate_token(&self) -> Result<String, Error> {
+        self.generate_token_with_index().map(|(token, _)| token)
+    }
+
+    /// Generate a JWT token with `claims.iat` set to current time, along with the index of the
+    /// secret which signed it for use with `Self::rotate`.
+    pub fn generate_token_with_index(&self) -> Result<(String, usize), Error> {
         let claims = self.generate_claims_at_timestamp();
         self.generate_token_with_claims(&claims)
     }
 
     /// Generate a JWT token with the given claims.
-    fn generate_token_with_claims(&self, claims: &Claims) -> Result<String, Error> {
+    fn generate_token_with_claims(&self, claims: &Claims) -> Result<(String, usize), Error> {
         let header = Header::new(DEFAULT_ALGORITHM);
-        Ok(encode(&header, claims, &self.key)?)
+        let keys = self.keys.read();
+        let token = encode(&header, claims, &keys.keys[keys.active])?;
+        Ok((token, keys.active))
     }
 
     /// Generate a `Claims` struct with `iat` set to current time
@@ -145,14 +237,54 @@ impl Auth {
     }
 }
 
+/// Parses the hex encoded secrets in `s`, one per line. Empty lines are ignored.
+pub fn parse_secrets(s: &str) -> Result<Vec<JwtKey>, Error> {
+    let secrets = s
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let secret_bytes = hex::decode(strip_prefix(line))
+                .map_err(|e| Error::InvalidKey(format!("Invalid hex string: {:?}", e)))?;
+            JwtKey::from_slice(&secret_bytes).map_err(Error::InvalidKey)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if secrets.is_empty() {
+        return Err(Error::InvalidKey("No JWT secrets provided".to_string()));
+    }
+    Ok(secrets)
+}
+
+/// Reads the secrets in the file at `path`, along with its modification time.
+fn read_secrets(path: &Path) -> Result<(Vec<JwtKey>, Option<SystemTime>), Error> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let s = std::fs::read_to_string(path).map_err(|e| {
+        Error::InvalidKey(format!(
+            "Failed to read JWT secret file {:?}, error: {:?}",
+            path, e
+        ))
+    })?;
+    Ok((parse_secrets(&s)?, modified))
+}
+
+fn encoding_keys(secrets: &[JwtKey]) -> Vec<EncodingKey> {
+    secrets
+        .iter()
+        .map(|secret| EncodingKey::from_secret(secret.as_bytes()))
+        .collect()
+}
+
 /// Claims struct as defined in https://github.com/ethereum/execution-apis/blob/main/src/engine/authentication.md#jwt-claims
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Claims {
     /// issued-at claim. Represented as seconds passed since UNIX_EPOCH.
     iat: u64,
     /// Optional unique identifier for the CL node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     id: Option<String>,
     /// Optional client version for the CL node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     clv: Option<String>,
 }
 
@@ -169,7 +301,7 @@ mod tests {
             Some("Lighthouse".into()),
         );
         let claims = auth.generate_claims_at_timestamp();
-        let token = auth.generate_token_with_claims(&claims).unwrap();
+        let (token, _) = auth.generate_token_with_claims(&claims).unwrap();
 
         assert_eq!(
             Auth::validate_token(&token, &JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap())
@@ -178,4 +310,68 @@ mod tests {
             claims
         );
     }
+    fn validates_with(auth: &Auth, secret: &JwtKey) -> bool {
+        Auth::validate_token(&auth.generate_token().unwrap(), secret).is_ok()
+    }
+
+    #[test]
+    fn test_multiple_secrets() {
+        let first = JwtKey::random();
+        let second = JwtKey::random();
+        let secrets = parse_secrets(&format!(
+            "0x{}\n\n{}\n",
+            first.hex_string(),
+            second.hex_string()
+        ))
+        .unwrap();
+        let auth = Auth::new_with_secrets(secrets, None, None).unwrap();
+        assert_eq!(auth.num_secrets(), 2);
+
+        assert!(validates_with(&auth, &first));
+        auth.rotate(0);
+        assert!(validates_with(&auth, &second));
+        // A stale rejection of the first secret doesn't rotate again.
+        auth.rotate(0);
+        assert!(validates_with(&auth, &second));
+        auth.rotate(1);
+        assert!(validates_with(&auth, &first));
+
+        assert!(parse_secrets("\n").is_err());
+        assert!(parse_secrets(&format!("{}\n0x1234", first.hex_string())).is_err());
+    }
+
+    #[test]
+    fn test_reload_if_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwt.hex");
+        let first = JwtKey::random();
+        let second = JwtKey::random();
+        std::fs::write(&path, first.hex_string()).unwrap();
+
+        let auth = Auth::new_with_path(path.clone(), None, None).unwrap();
+        assert!(!auth.reload_if_modified().unwrap());
+        assert!(validates_with(&auth, &first));
+
+        std::fs::write(
+            &path,
+            format!("{}\n{}", second.hex_string(), first.hex_string()),
+        )
+        .unwrap();
+        // Ensure the change is visible on filesystems with a coarse modification time.
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(1))
+            .unwrap();
+        assert!(auth.reload_if_modified().unwrap());
+        assert_eq!(auth.num_secrets(), 2);
+        assert!(validates_with(&auth, &second));
+
+        // An invalid file leaves the existing secrets in place.
+        std::fs::write(&path, "not hex").unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(2))
+            .unwrap();
+        assert!(auth.reload_if_modified().is_err());
+        assert!(validates_with(&auth, &second));
+        // The invalid file is not read again until it is modified.
+        assert!(!auth.reload_if_modified().unwrap());
+    }
 }
@@ -3,14 +3,18 @@
 use super::*;
 use crate::auth::Auth;
 use crate::json_structures::*;
+use crate::metrics;
+use crate::request_policy::{error_class, RequestPolicy};
 use lazy_static::lazy_static;
 use lighthouse_version::{COMMIT_PREFIX, VERSION};
 use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::HashSet;
 use tokio::sync::Mutex;
+use tokio::time::sleep;
 
 use std::time::{Duration, Instant};
 
@@ -599,6 +603,7 @@ pub struct HttpJsonRpc {
     pub execution_timeout_multiplier: u32,
     pub engine_capabilities_cache: Mutex<Option<CachedResponse<EngineCapabilities>>>,
     pub engine_version_cache: Mutex<Option<CachedResponse<Vec<ClientVersionV1>>>>,
+    pub request_policy: RequestPolicy,
     auth: Option<Auth>,
 }
 
@@ -613,6 +618,7 @@ impl HttpJsonRpc {
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
+            request_policy: RequestPolicy::default(),
             auth: None,
         })
     }
@@ -628,16 +634,26 @@ impl HttpJsonRpc {
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
+            request_policy: RequestPolicy::default(),
             auth: Some(auth),
         })
     }
 
+    /// Use `request_policy` for the timeouts and retries of requests.
+    pub fn with_request_policy(mut self, request_policy: RequestPolicy) -> Self {
+        self.request_policy = request_policy;
+        self
+    }
+
+    /// Call `method`, retrying according to the request policy of the method, which times out
+    /// after `timeout` unless overridden.
     pub async fn rpc_request<D: DeserializeOwned>(
         &self,
         method: &str,
         params: serde_json::Value,
         timeout: Duration,
     ) -> Result<D, Error> {
+        let policy = self.request_policy.resolve(method, timeout);
         let body = JsonRequestBody {
             jsonrpc: JSONRPC_VERSION,
             method,
@@ -645,19 +661,79 @@ impl HttpJsonRpc {
             id: json!(STATIC_ID),
         };
 
-        let mut request = self
-            .client
-            .post(self.url.full.clone())
-            .timeout(timeout)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&body);
+        let start = Instant::now();
+        let mut retries = 0;
+        loop {
+            // Shorten the last attempt to meet the deadline.
+            let timeout = policy.deadline.map_or(policy.timeout, |deadline| {
+                policy.timeout.min(deadline.saturating_sub(start.elapsed()))
+            });
+            let timer =
+                metrics::start_timer_vec(&metrics::EXECUTION_LAYER_ENGINE_API_TIMES, &[method]);
+            let result = self.send_rpc_request(&body, timeout).await;
+            metrics::stop_timer(timer);
+
+            let Err(error) = &result else {
+                return result;
+            };
+            let (class, retryable) = error_class(error);
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_ENGINE_API_ERRORS,
+                &[method, class],
+            );
+            let past_deadline = policy.deadline.map_or(false, |deadline| {
+                start.elapsed() + policy.backoff >= deadline
+            });
+            if !retryable || retries >= policy.retries || past_deadline {
+                return result;
+            }
+
+            retries += 1;
+            metrics::inc_counter_vec(&metrics::EXECUTION_LAYER_ENGINE_API_RETRIES, &[method]);
+            sleep(policy.backoff).await;
+        }
+    }
 
-        // Generate and add a jwt token to the header if auth is defined.
-        if let Some(auth) = &self.auth {
-            request = request.bearer_auth(auth.generate_token()?);
+    async fn send_rpc_request<D: DeserializeOwned>(
+        &self,
+        body: &JsonRequestBody<'_>,
+        timeout: Duration,
+    ) -> Result<D, Error> {
+        let method = body.method;
+
+        // If the token is rejected, try each of the other secrets in turn since the server may
+        // have switched to a new one.
+        let attempts = self.auth.as_ref().map_or(1, Auth::num_secrets);
+        let mut attempt = 1;
+        let response = loop {
+            let mut request = self
+                .client
+                .post(self.url.full.clone())
+                .timeout(timeout)
+                .header(CONTENT_TYPE, "application/json")
+                .json(body);
+
+            // Generate and add a jwt token to the header if auth is defined.
+            let mut secret_index = 0;
+            if let Some(auth) = &self.auth {
+                let (token, index) = auth.generate_token_with_index()?;
+                request = request.bearer_auth(token);
+                secret_index = index;
+            };
+
+            let response = request.send().await?;
+            match (&self.auth, response.status()) {
+                (Some(auth), StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+                    if attempt < attempts =>
+                {
+                    auth.rotate(secret_index);
+                    attempt += 1;
+                }
+                _ => break response,
+            }
         };
 
-        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
+        let body: JsonResponseBody = response.error_for_status()?.json().await?;
 
         match (body.result, body.error) {
             (result, None) => serde_json::from_value(result).map_err(Into::into),
@@ -665,6 +741,9 @@ impl HttpJsonRpc {
                 if error.message.contains(EIP155_ERROR_STR) {
                     Err(Error::Eip155Failure)
                 } else {
+                    if error.code == METHOD_NOT_FOUND_CODE {
+                        self.disable_capability(method).await;
+                    }
                     Err(Error::ServerMessage {
                         code: error.code,
                         message: error.message,
@@ -673,6 +752,14 @@ impl HttpJsonRpc {
             }
         }
     }
+
+    /// Reads the JWT secret file again if it has changed, returning `Ok(true)` if the secrets were
+    /// replaced.
+    pub fn reload_jwt_secrets(&self) -> Result<bool, Error> {
+        self.auth.as_ref().map_or(Ok(false), |auth| {
+            auth.reload_if_modified().map_err(Into::into)
+        })
+    }
 }
 
 impl std::fmt::Display for HttpJsonRpc {
@@ -1108,6 +1195,19 @@ impl HttpJsonRpc {
         })
     }
 
+    /// Marks `method` as unsupported in the cached capabilities after the execution engine
+    /// rejected it, so that its callers fall back or fail without calling it again until the
+    /// capabilities are next exchanged.
+    async fn disable_capability(&self, method: &str) {
+        // The cache is locked while the capabilities are exchanged.
+        if method == ENGINE_EXCHANGE_CAPABILITIES {
+            return;
+        }
+        if let Some(cached_response) = self.engine_capabilities_cache.lock().await.as_mut() {
+            cached_response.data.disable(method);
+        }
+    }
+
     pub async fn clear_exchange_capabilties_cache(&self) {
         *self.engine_capabilities_cache.lock().await = None;
     }
@@ -1423,6 +1523,38 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn method_not_found_disables_capability() {
+        let tester = Tester::new(true);
+        let client = tester.rpc_client.clone();
+        let capabilities = client.get_engine_capabilities(None).await.unwrap();
+        assert!(capabilities.get_client_version_v1);
+        tester.server.take_previous_request();
+
+        tester.server.push_preloaded_response(json!({
+            "id": STATIC_ID,
+            "jsonrpc": JSONRPC_VERSION,
+            "error": {
+                "code": METHOD_NOT_FOUND_CODE,
+                "message": "the method engine_getClientVersionV1 does not exist"
+            }
+        }));
+        let result = client.get_engine_version(None).await;
+        assert!(matches!(
+            result,
+            Err(Error::ServerMessage {
+                code: METHOD_NOT_FOUND_CODE,
+                ..
+            })
+        ));
+
+        // The method is no longer called, and the version is treated as unknown.
+        let capabilities = client.get_engine_capabilities(None).await.unwrap();
+        assert!(!capabilities.get_client_version_v1);
+        assert!(client.get_engine_version(None).await.unwrap().is_empty());
+        assert!(tester.server.take_previous_request().is_none());
+    }
+
     const HASH_00: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
     const HASH_01: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
 
@@ -1491,7 +1623,7 @@ mod test {
 
     /// Example: if `spec == &[1, 1]`, then two one-byte transactions will be created.
     fn generate_transactions<E: EthSpec>(spec: &[usize]) -> Transactions<E> {
-        let mut txs = VariableList::default();
+        let mut txs = Transactions::default();
 
         for &num_bytes in spec {
             let mut tx = VariableList::default();
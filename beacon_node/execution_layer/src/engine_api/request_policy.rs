@@ -0,0 +1,252 @@
+//! Timeouts and retries of requests to the execution engine.
+//!
+//! Each method is either critical, when it is on the path of importing or producing a block, or
+//! background. The two classes have separate retry counts, backoffs and deadlines, so that
+//! background requests can be retried patiently without delaying blocks, and each of these can be
+//! overridden for a single method along with its timeout.
+//!
+//! Only requests which timed out, failed to connect or were rejected by an unavailable server are
+//! retried. By default nothing is retried and each method keeps its default timeout.
+use super::http::{
+    ENGINE_FORKCHOICE_UPDATED_V1, ENGINE_FORKCHOICE_UPDATED_V2, ENGINE_FORKCHOICE_UPDATED_V3,
+    ENGINE_GET_PAYLOAD_V1, ENGINE_GET_PAYLOAD_V2, ENGINE_GET_PAYLOAD_V3, ENGINE_GET_PAYLOAD_V4,
+    ENGINE_NEW_PAYLOAD_V1, ENGINE_NEW_PAYLOAD_V2, ENGINE_NEW_PAYLOAD_V3, ENGINE_NEW_PAYLOAD_V4,
+    METHOD_NOT_FOUND_CODE,
+};
+use super::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Whether a method is needed to import or produce a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestClass {
+    Critical,
+    Background,
+}
+
+impl RequestClass {
+    pub fn of(method: &str) -> Self {
+        match method {
+            ENGINE_NEW_PAYLOAD_V1
+            | ENGINE_NEW_PAYLOAD_V2
+            | ENGINE_NEW_PAYLOAD_V3
+            | ENGINE_NEW_PAYLOAD_V4
+            | ENGINE_FORKCHOICE_UPDATED_V1
+            | ENGINE_FORKCHOICE_UPDATED_V2
+            | ENGINE_FORKCHOICE_UPDATED_V3
+            | ENGINE_GET_PAYLOAD_V1
+            | ENGINE_GET_PAYLOAD_V2
+            | ENGINE_GET_PAYLOAD_V3
+            | ENGINE_GET_PAYLOAD_V4 => RequestClass::Critical,
+            _ => RequestClass::Background,
+        }
+    }
+}
+
+/// The retries of a class of methods.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClassPolicy {
+    /// The number of times a failed request is retried.
+    pub retries: u32,
+    /// The delay before each retry, in milliseconds.
+    pub backoff_ms: u64,
+    /// The time from the first attempt of a request after which it is no longer retried, in
+    /// milliseconds. The timeout of the last attempt is shortened to meet it.
+    pub deadline_ms: Option<u64>,
+}
+
+/// Overrides of the policy of a single method.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MethodPolicy {
+    /// The timeout of each attempt, in milliseconds, instead of the default timeout of the method
+    /// multiplied by the timeout multiplier.
+    pub timeout_ms: Option<u64>,
+    pub retries: Option<u32>,
+    pub backoff_ms: Option<u64>,
+}
+
+/// The timeouts and retries of all requests to an execution engine.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestPolicy {
+    pub critical: ClassPolicy,
+    pub background: ClassPolicy,
+    /// Overrides keyed by JSON-RPC method name, e.g. `engine_newPayloadV3`.
+    pub methods: HashMap<String, MethodPolicy>,
+}
+
+/// The timeout and retries of a single request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedPolicy {
+    pub timeout: Duration,
+    pub retries: u32,
+    pub backoff: Duration,
+    pub deadline: Option<Duration>,
+}
+
+impl RequestPolicy {
+    /// Returns the policy of a request for `method`, which times out after `default_timeout`
+    /// unless overridden.
+    pub fn resolve(&self, method: &str, default_timeout: Duration) -> ResolvedPolicy {
+        let class = match RequestClass::of(method) {
+            RequestClass::Critical => &self.critical,
+            RequestClass::Background => &self.background,
+        };
+        let method = self.methods.get(method);
+        ResolvedPolicy {
+            timeout: method
+                .and_then(|m| m.timeout_ms)
+                .map_or(default_timeout, Duration::from_millis),
+            retries: method.and_then(|m| m.retries).unwrap_or(class.retries),
+            backoff: Duration::from_millis(
+                method
+                    .and_then(|m| m.backoff_ms)
+                    .unwrap_or(class.backoff_ms),
+            ),
+            deadline: class.deadline_ms.map(Duration::from_millis),
+        }
+    }
+}
+
+/// Parse a comma-separated list of `<method>=<milliseconds>` pairs, such as
+/// `engine_newPayloadV3=12000,engine_getPayloadV3=1500`.
+pub fn parse_method_timeouts(s: &str) -> Result<Vec<(String, u64)>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (method, millis) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("{pair} is not of the form <method>=<milliseconds>"))?;
+            let millis = millis
+                .trim()
+                .parse()
+                .map_err(|e| format!("invalid timeout for {method}: {e}"))?;
+            Ok((method.trim().to_string(), millis))
+        })
+        .collect()
+}
+
+/// Returns the class of `error` for metrics, and whether the request may succeed if retried.
+pub fn error_class(error: &Error) -> (&'static str, bool) {
+    match error {
+        Error::HttpClient(e) => {
+            let e = e.inner();
+            if e.is_timeout() {
+                ("timeout", true)
+            } else if e.is_connect() {
+                ("connection", true)
+            } else if let Some(status) = e.status() {
+                ("http_status", status.is_server_error())
+            } else if e.is_decode() || e.is_body() {
+                ("decode", false)
+            } else {
+                ("http", false)
+            }
+        }
+        Error::Auth(_) => ("auth", false),
+        Error::Json(_) => ("decode", false),
+        Error::ServerMessage { code, .. } if *code == METHOD_NOT_FOUND_CODE => {
+            ("method_not_found", false)
+        }
+        Error::ServerMessage { .. } | Error::Eip155Failure => ("rpc_error", false),
+        _ => ("other", false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_api::http::ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1;
+
+    #[test]
+    fn resolve_defaults() {
+        let policy = RequestPolicy::default();
+        let resolved = policy.resolve(ENGINE_NEW_PAYLOAD_V3, Duration::from_secs(8));
+        assert_eq!(
+            resolved,
+            ResolvedPolicy {
+                timeout: Duration::from_secs(8),
+                retries: 0,
+                backoff: Duration::ZERO,
+                deadline: None,
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_classes_and_overrides() {
+        let policy = RequestPolicy {
+            critical: ClassPolicy {
+                retries: 1,
+                backoff_ms: 100,
+                deadline_ms: Some(4_000),
+            },
+            background: ClassPolicy {
+                retries: 5,
+                backoff_ms: 1_000,
+                deadline_ms: None,
+            },
+            methods: HashMap::from([(
+                ENGINE_GET_PAYLOAD_V3.to_string(),
+                MethodPolicy {
+                    timeout_ms: Some(1_500),
+                    retries: Some(0),
+                    backoff_ms: None,
+                },
+            )]),
+        };
+
+        let new_payload = policy.resolve(ENGINE_NEW_PAYLOAD_V3, Duration::from_secs(8));
+        assert_eq!(new_payload.timeout, Duration::from_secs(8));
+        assert_eq!(new_payload.retries, 1);
+        assert_eq!(new_payload.backoff, Duration::from_millis(100));
+        assert_eq!(new_payload.deadline, Some(Duration::from_secs(4)));
+
+        let get_payload = policy.resolve(ENGINE_GET_PAYLOAD_V3, Duration::from_secs(2));
+        assert_eq!(get_payload.timeout, Duration::from_millis(1_500));
+        assert_eq!(get_payload.retries, 0);
+        assert_eq!(get_payload.backoff, Duration::from_millis(100));
+
+        let bodies = policy.resolve(
+            ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1,
+            Duration::from_secs(10),
+        );
+        assert_eq!(bodies.retries, 5);
+        assert_eq!(bodies.backoff, Duration::from_secs(1));
+        assert_eq!(bodies.deadline, None);
+    }
+
+    #[test]
+    fn parse_timeouts() {
+        assert_eq!(
+            parse_method_timeouts("engine_newPayloadV3=12000, engine_getPayloadV3=1500,").unwrap(),
+            vec![
+                (ENGINE_NEW_PAYLOAD_V3.to_string(), 12_000),
+                (ENGINE_GET_PAYLOAD_V3.to_string(), 1_500),
+            ]
+        );
+        assert!(parse_method_timeouts("engine_newPayloadV3").is_err());
+        assert!(parse_method_timeouts("engine_newPayloadV3=soon").is_err());
+    }
+
+    #[test]
+    fn deserialize_partial() {
+        let policy: RequestPolicy = serde_json::from_str(
+            r#"{
+                "critical": { "retries": 2 },
+                "methods": { "engine_newPayloadV3": { "timeout_ms": 12000 } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(policy.critical.retries, 2);
+        assert_eq!(policy.background, ClassPolicy::default());
+        assert_eq!(
+            policy.methods[ENGINE_NEW_PAYLOAD_V3].timeout_ms,
+            Some(12_000)
+        );
+    }
+}
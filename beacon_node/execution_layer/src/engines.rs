@@ -1,5 +1,8 @@
 //! Provides generic behaviour for multiple execution engines, specifically fallback behaviour.
 
+use crate::engine_api::http::{
+    ENGINE_GET_CLIENT_VERSION_V1, ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1,
+};
 use crate::engine_api::{
     EngineCapabilities, Error as EngineApiError, ForkchoiceUpdatedResponse, PayloadAttributes,
     PayloadId,
@@ -23,6 +26,21 @@ use types::ExecutionBlockHash;
 const PAYLOAD_ID_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(512);
 const CACHED_RESPONSE_AGE_LIMIT: Duration = Duration::from_secs(900); // 15 minutes
 
+/// The optional engine API methods, with the features which are degraded when the execution
+/// engine doesn't support them.
+///
+/// The methods required by each fork are checked by the fork readiness logs instead.
+const OPTIONAL_METHODS: &[(&str, &str)] = &[
+    (
+        ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1,
+        "payloads of historic blocks are fetched one at a time with eth_getBlockByHash",
+    ),
+    (
+        ENGINE_GET_CLIENT_VERSION_V1,
+        "the execution client version is not included in the default graffiti",
+    ),
+];
+
 /// Stores the remembered state of a engine.
 #[derive(Copy, Clone, PartialEq, Debug, Eq, Default)]
 enum EngineStateInternal {
@@ -127,6 +145,8 @@ pub struct Engine {
     payload_id_cache: Mutex<LruCache<PayloadIdCacheKey, PayloadId>>,
     state: RwLock<State>,
     latest_forkchoice_state: RwLock<Option<ForkchoiceState>>,
+    /// The optional methods which the execution engine was last known not to support.
+    unsupported_methods: Mutex<Vec<&'static str>>,
     executor: TaskExecutor,
     log: Logger,
 }
@@ -139,6 +159,7 @@ impl Engine {
             payload_id_cache: Mutex::new(LruCache::new(PAYLOAD_ID_LRU_CACHE_SIZE)),
             state: Default::default(),
             latest_forkchoice_state: Default::default(),
+            unsupported_methods: Default::default(),
             executor,
             log: log.clone(),
         }
@@ -304,20 +325,22 @@ impl Engine {
         match cache_action {
             ResponseCacheAction::None => {}
             ResponseCacheAction::Update => {
-                if let Err(e) = self
+                match self
                     .get_engine_capabilities(Some(CACHED_RESPONSE_AGE_LIMIT))
                     .await
                 {
-                    warn!(self.log,
+                    Err(e) => warn!(self.log,
                         "Error during exchange capabilities";
                         "error" => ?e,
-                    )
-                } else {
-                    // no point in running this if there was an error fetching the capabilities
-                    // as it will just result in an error again
-                    let _ = self
-                        .get_engine_version(Some(CACHED_RESPONSE_AGE_LIMIT))
-                        .await;
+                    ),
+                    Ok(capabilities) => {
+                        self.log_unsupported_methods(&capabilities).await;
+                        // no point in running this if there was an error fetching the
+                        // capabilities as it will just result in an error again
+                        let _ = self
+                            .get_engine_version(Some(CACHED_RESPONSE_AGE_LIMIT))
+                            .await;
+                    }
                 }
             }
             ResponseCacheAction::Clear => {
@@ -333,6 +356,34 @@ impl Engine {
         );
     }
 
+    /// Warns once about each optional method which the execution engine doesn't support, rather
+    /// than each time a feature falls back without it.
+    async fn log_unsupported_methods(&self, capabilities: &EngineCapabilities) {
+        let supported = capabilities.to_response();
+        let mut unsupported_methods = self.unsupported_methods.lock().await;
+        for &(method, degradation) in OPTIONAL_METHODS {
+            let was_unsupported = unsupported_methods.contains(&method);
+            if supported.contains(&method) {
+                if was_unsupported {
+                    info!(
+                        self.log,
+                        "Execution engine now supports method";
+                        "method" => method,
+                    );
+                    unsupported_methods.retain(|m| *m != method);
+                }
+            } else if !was_unsupported {
+                warn!(
+                    self.log,
+                    "Execution engine does not support method";
+                    "info" => degradation,
+                    "method" => method,
+                );
+                unsupported_methods.push(method);
+            }
+        }
+    }
+
     /// Returns the execution engine capabilities resulting from a call to
     /// engine_exchangeCapabilities. If the capabilities cache is not populated,
     /// or if it is populated with a cached result of age >= `age_limit`, this
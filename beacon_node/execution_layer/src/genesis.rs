@@ -0,0 +1,405 @@
+//! The execution payload header of a network which is post-merge from genesis.
+//!
+//! The header describes the genesis block of the execution layer, so it is derived from the
+//! genesis JSON given to the execution clients (as accepted by `geth init`) by computing the state
+//! root of its allocations and the hash of the block.
+use crate::block_hash::rlp_encode_block_header;
+use crate::keccak::{keccak256, KeccakHasher};
+use alloy_rlp::RlpEncodable;
+use keccak_hash::{KECCAK_EMPTY_LIST_RLP, KECCAK_NULL_RLP};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use triehash::sec_trie_root;
+use types::{
+    Address, EthSpec, ExecutionBlockHash, ExecutionBlockHeader, ExecutionPayload,
+    ExecutionPayloadBellatrix, ExecutionPayloadCapella, ExecutionPayloadDeneb,
+    ExecutionPayloadElectra, ExecutionPayloadHeader, ForkName, Hash256, Hash64, Uint256,
+    VariableList,
+};
+
+/// The base fee of the genesis block if the genesis JSON does not set one.
+const INITIAL_BASE_FEE: u64 = 1_000_000_000;
+/// The gas limit of the genesis block if the genesis JSON does not set one.
+const DEFAULT_GAS_LIMIT: u64 = 4_712_388;
+
+/// A number given as either a JSON number, or a hex or decimal string.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Quantity {
+    Number(serde_json::Number),
+    String(String),
+}
+
+impl Quantity {
+    fn to_u256(&self) -> Result<alloy_primitives::U256, String> {
+        let s = match self {
+            Quantity::Number(n) => n.to_string(),
+            Quantity::String(s) => s.clone(),
+        };
+        alloy_primitives::U256::from_str(&s).map_err(|e| format!("invalid quantity {s}: {e}"))
+    }
+
+    fn to_u64(&self) -> Result<u64, String> {
+        let value = self.to_u256()?;
+        u64::try_from(value).map_err(|_| format!("quantity {value} does not fit in a u64"))
+    }
+}
+
+/// Parse an optional quantity from the genesis JSON, which is zero if absent.
+fn u64_or_zero(quantity: &Option<Quantity>, name: &str) -> Result<u64, String> {
+    quantity
+        .as_ref()
+        .map_or(Ok(0), Quantity::to_u64)
+        .map_err(|e| format!("invalid {name}: {e}"))
+}
+
+fn to_uint256(value: alloy_primitives::U256) -> Uint256 {
+    Uint256::from_big_endian(&value.to_be_bytes::<32>())
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(|e| format!("invalid hex {s}: {e}"))
+}
+
+/// The fork parameters of the execution layer which must agree with the consensus layer.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenesisConfig {
+    chain_id: u64,
+    terminal_total_difficulty: Option<Quantity>,
+    shanghai_time: Option<u64>,
+    cancun_time: Option<u64>,
+    prague_time: Option<u64>,
+}
+
+/// An account allocated in the genesis state of the execution layer.
+#[derive(Debug, Clone, Deserialize)]
+struct GenesisAccount {
+    balance: Quantity,
+    nonce: Option<Quantity>,
+    code: Option<String>,
+    #[serde(default)]
+    storage: BTreeMap<Hash256, Hash256>,
+}
+
+#[derive(RlpEncodable)]
+struct EncodableAccount<'a> {
+    nonce: u64,
+    balance: alloy_primitives::U256,
+    storage_root: &'a [u8],
+    code_hash: &'a [u8],
+}
+
+impl GenesisAccount {
+    /// RLP encode the account as it is stored in the state trie.
+    fn rlp_encode(&self) -> Result<Vec<u8>, String> {
+        let storage = self
+            .storage
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(key, value)| {
+                let value = alloy_primitives::U256::from_be_slice(value.as_bytes());
+                (*key, alloy_rlp::encode(value))
+            })
+            .collect::<Vec<_>>();
+        let storage_root: Hash256 = if storage.is_empty() {
+            KECCAK_NULL_RLP.as_fixed_bytes().into()
+        } else {
+            sec_trie_root::<KeccakHasher, _, _, _>(storage)
+        };
+        let code = self.code.as_deref().map(decode_hex).transpose()?;
+        let code_hash = keccak256(code.as_deref().unwrap_or_default());
+
+        Ok(alloy_rlp::encode(EncodableAccount {
+            nonce: u64_or_zero(&self.nonce, "nonce")?,
+            balance: self.balance.to_u256()?,
+            storage_root: storage_root.as_bytes(),
+            code_hash: code_hash.as_bytes(),
+        }))
+    }
+}
+
+/// The genesis JSON of the execution layer.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionGenesis {
+    config: GenesisConfig,
+    nonce: Option<Quantity>,
+    timestamp: Option<Quantity>,
+    extra_data: Option<String>,
+    gas_limit: Option<Quantity>,
+    difficulty: Option<Quantity>,
+    mix_hash: Option<Hash256>,
+    coinbase: Option<Address>,
+    #[serde(default)]
+    alloc: BTreeMap<String, GenesisAccount>,
+    number: Option<Quantity>,
+    gas_used: Option<Quantity>,
+    parent_hash: Option<Hash256>,
+    base_fee_per_gas: Option<Quantity>,
+    excess_blob_gas: Option<Quantity>,
+    blob_gas_used: Option<Quantity>,
+}
+
+impl ExecutionGenesis {
+    pub fn chain_id(&self) -> u64 {
+        self.config.chain_id
+    }
+
+    /// The timestamp of the genesis block.
+    pub fn timestamp(&self) -> Result<u64, String> {
+        u64_or_zero(&self.timestamp, "timestamp")
+    }
+
+    /// The difficulty of the genesis block.
+    pub fn difficulty(&self) -> Result<Uint256, String> {
+        self.difficulty
+            .as_ref()
+            .map_or(Ok(alloy_primitives::U256::ZERO), Quantity::to_u256)
+            .map(to_uint256)
+            .map_err(|e| format!("invalid difficulty: {e}"))
+    }
+
+    pub fn terminal_total_difficulty(&self) -> Result<Option<Uint256>, String> {
+        self.config
+            .terminal_total_difficulty
+            .as_ref()
+            .map(|ttd| ttd.to_u256().map(to_uint256))
+            .transpose()
+            .map_err(|e| format!("invalid terminalTotalDifficulty: {e}"))
+    }
+
+    /// The time at which the execution layer activates its counterpart of `fork_name`, for the
+    /// forks which are activated by time.
+    pub fn fork_time(&self, fork_name: ForkName) -> Option<u64> {
+        match fork_name {
+            ForkName::Base | ForkName::Altair | ForkName::Bellatrix => None,
+            ForkName::Capella => self.config.shanghai_time,
+            ForkName::Deneb => self.config.cancun_time,
+            ForkName::Electra => self.config.prague_time,
+        }
+    }
+
+    /// The root of the state trie of the allocations.
+    pub fn state_root(&self) -> Result<Hash256, String> {
+        let accounts = self
+            .alloc
+            .iter()
+            .map(|(address, account)| {
+                let address_bytes = decode_hex(address)?;
+                if address_bytes.len() != Address::len_bytes() {
+                    return Err(format!("invalid address {address}"));
+                }
+                let account = account
+                    .rlp_encode()
+                    .map_err(|e| format!("invalid account {address}: {e}"))?;
+                Ok((address_bytes, account))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(sec_trie_root::<KeccakHasher, _, _, _>(accounts))
+    }
+
+    /// Returns the header of the genesis block as the latest execution payload header of a
+    /// genesis state at `fork_name`.
+    pub fn execution_payload_header<E: EthSpec>(
+        &self,
+        fork_name: ForkName,
+    ) -> Result<ExecutionPayloadHeader<E>, String> {
+        let mut payload: ExecutionPayload<E> = match fork_name {
+            ForkName::Base | ForkName::Altair => {
+                return Err(format!("{fork_name} has no execution payload header"))
+            }
+            ForkName::Bellatrix => ExecutionPayloadBellatrix::default().into(),
+            ForkName::Capella => ExecutionPayloadCapella::default().into(),
+            ForkName::Deneb => ExecutionPayloadDeneb::default().into(),
+            ForkName::Electra => ExecutionPayloadElectra::default().into(),
+        };
+
+        let extra_data = self
+            .extra_data
+            .as_deref()
+            .map(decode_hex)
+            .transpose()?
+            .unwrap_or_default();
+        let extra_data_len = extra_data.len();
+        let extra_data = VariableList::new(extra_data).map_err(|_| {
+            format!(
+                "extraData is {extra_data_len} bytes, longer than the {} bytes of a payload",
+                E::max_extra_data_bytes()
+            )
+        })?;
+        *payload.parent_hash_mut() =
+            ExecutionBlockHash::from_root(self.parent_hash.unwrap_or_default());
+        *payload.fee_recipient_mut() = self.coinbase.unwrap_or_default();
+        *payload.state_root_mut() = self.state_root()?;
+        *payload.receipts_root_mut() = KECCAK_NULL_RLP.as_fixed_bytes().into();
+        *payload.prev_randao_mut() = self.mix_hash.unwrap_or_default();
+        *payload.block_number_mut() = u64_or_zero(&self.number, "number")?;
+        *payload.gas_limit_mut() = self
+            .gas_limit
+            .as_ref()
+            .map_or(Ok(DEFAULT_GAS_LIMIT), Quantity::to_u64)
+            .map_err(|e| format!("invalid gasLimit: {e}"))?;
+        *payload.gas_used_mut() = u64_or_zero(&self.gas_used, "gasUsed")?;
+        *payload.timestamp_mut() = self.timestamp()?;
+        *payload.extra_data_mut() = extra_data;
+        *payload.base_fee_per_gas_mut() = self
+            .base_fee_per_gas
+            .as_ref()
+            .map_or(Ok(INITIAL_BASE_FEE.into()), |fee| {
+                fee.to_u256().map(to_uint256)
+            })
+            .map_err(|e| format!("invalid baseFeePerGas: {e}"))?;
+        if let Ok(blob_gas_used) = payload.blob_gas_used_mut() {
+            *blob_gas_used = u64_or_zero(&self.blob_gas_used, "blobGasUsed")?;
+        }
+        if let Ok(excess_blob_gas) = payload.excess_blob_gas_mut() {
+            *excess_blob_gas = u64_or_zero(&self.excess_blob_gas, "excessBlobGas")?;
+        }
+
+        // The genesis block may be the terminal proof-of-work block, so unlike the blocks of the
+        // payloads its difficulty and nonce are not necessarily zero.
+        let empty_trie_root: Hash256 = KECCAK_NULL_RLP.as_fixed_bytes().into();
+        let is_deneb = payload.blob_gas_used().is_ok();
+        let is_electra = matches!(payload, ExecutionPayload::Electra(_));
+        let mut block_header = ExecutionBlockHeader::from_payload(
+            payload.to_ref(),
+            KECCAK_EMPTY_LIST_RLP.as_fixed_bytes().into(),
+            empty_trie_root,
+            payload.withdrawals().ok().map(|_| empty_trie_root),
+            payload.blob_gas_used().ok(),
+            payload.excess_blob_gas().ok(),
+            is_deneb.then(Hash256::zero),
+            is_electra.then_some(empty_trie_root),
+        );
+        block_header.difficulty = self.difficulty()?;
+        block_header.nonce = Hash64::from_low_u64_be(u64_or_zero(&self.nonce, "nonce")?);
+        *payload.block_hash_mut() =
+            ExecutionBlockHash::from_root(keccak256(&rlp_encode_block_header(&block_header)));
+
+        Ok(ExecutionPayloadHeader::from(payload.to_ref()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tree_hash::TreeHash;
+    use types::{MainnetEthSpec, Withdrawals};
+
+    type E = MainnetEthSpec;
+
+    const DENEB_GENESIS: &str = r#"{
+        "config": {
+            "chainId": 1337,
+            "terminalTotalDifficulty": 0,
+            "terminalTotalDifficultyPassed": true,
+            "shanghaiTime": 0,
+            "cancunTime": 0
+        },
+        "nonce": "0x0",
+        "timestamp": "0x6650f2a0",
+        "extraData": "0x0102",
+        "gasLimit": "0x1c9c380",
+        "difficulty": "0x0",
+        "alloc": {
+            "0x7b8C3a386C0eea54693fFB0DA17373ffC9228139": {
+                "balance": "10000000000000000000000000"
+            },
+            "00000000219ab540356cbb839cbe05303d7705fa": {
+                "balance": "0",
+                "nonce": "0x1",
+                "code": "0x6080604052",
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x000000000000000000000000000000000000000000000000000000000000002a",
+                    "0x0000000000000000000000000000000000000000000000000000000000000002": "0x0000000000000000000000000000000000000000000000000000000000000000"
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn empty_alloc_state_root() {
+        let genesis: ExecutionGenesis =
+            serde_json::from_str(r#"{ "config": { "chainId": 1 } }"#).unwrap();
+        assert_eq!(
+            genesis.state_root().unwrap(),
+            Hash256::from_str("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn deneb_genesis_header() {
+        let genesis: ExecutionGenesis = serde_json::from_str(DENEB_GENESIS).unwrap();
+        assert_eq!(genesis.chain_id(), 1337);
+        assert_eq!(genesis.fork_time(ForkName::Deneb), Some(0));
+        assert_eq!(genesis.fork_time(ForkName::Electra), None);
+        assert_eq!(genesis.terminal_total_difficulty().unwrap(), Some(0.into()));
+
+        let header = genesis
+            .execution_payload_header::<E>(ForkName::Deneb)
+            .unwrap();
+        let ExecutionPayloadHeader::Deneb(header) = header else {
+            panic!("expected a Deneb header");
+        };
+        assert_eq!(
+            header.state_root,
+            Hash256::from_str("0eda27fcc059f4d29d18987e91bce97c582377a2504a01e1a4bc5cb93ec268d6")
+                .unwrap()
+        );
+        assert_eq!(
+            header.block_hash,
+            ExecutionBlockHash::from_str(
+                "4a58671cd4e6b4aa016e55ed2e19baf014a2264b9e76ec32351eab34c58e8fa2"
+            )
+            .unwrap()
+        );
+        assert_eq!(header.timestamp, 1716581024);
+        assert_eq!(header.gas_limit, 30_000_000);
+        assert_eq!(header.base_fee_per_gas, INITIAL_BASE_FEE.into());
+        assert_eq!(header.extra_data.to_vec(), vec![1, 2]);
+        assert_eq!(
+            header.withdrawals_root,
+            Withdrawals::<E>::default().tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn electra_genesis_header() {
+        // The Deneb genesis with Prague activated, whose header also commits to the empty list of
+        // EIP-7685 requests.
+        let mut genesis: ExecutionGenesis = serde_json::from_str(DENEB_GENESIS).unwrap();
+        genesis.config.prague_time = Some(0);
+        assert_eq!(genesis.fork_time(ForkName::Electra), Some(0));
+
+        let header = genesis
+            .execution_payload_header::<E>(ForkName::Electra)
+            .unwrap();
+        let ExecutionPayloadHeader::Electra(header) = header else {
+            panic!("expected an Electra header");
+        };
+        assert_eq!(
+            header.state_root,
+            Hash256::from_str("0eda27fcc059f4d29d18987e91bce97c582377a2504a01e1a4bc5cb93ec268d6")
+                .unwrap()
+        );
+        assert_eq!(
+            header.block_hash,
+            ExecutionBlockHash::from_str(
+                "be4a9c8813d5c2c99b6212c167ac1b49384d76717fc737bab502ff9324b2d792"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn long_extra_data() {
+        let mut genesis: ExecutionGenesis = serde_json::from_str(DENEB_GENESIS).unwrap();
+        genesis.extra_data = Some(format!("0x{}", "00".repeat(33)));
+        assert!(genesis
+            .execution_payload_header::<E>(ForkName::Deneb)
+            .is_err());
+    }
+}
@@ -5,8 +5,10 @@
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
 use crate::payload_cache::PayloadCache;
+use crate::registration_cache::RegistrationCache;
+use crate::verified_payload_cache::VerifiedPayloadCache;
 use arc_swap::ArcSwapOption;
-use auth::{strip_prefix, Auth, JwtKey};
+use auth::{Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
 use builder_client::BuilderHttpClient;
 pub use engine_api::EngineCapabilities;
@@ -15,6 +17,7 @@ pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::CachedValidatorRegistration;
 use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
 use ethers_core::types::Transaction as EthersTransaction;
@@ -22,6 +25,7 @@ use fork_choice::ForkchoiceUpdateParameters;
 use lru::LruCache;
 use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
+use request_policy::RequestPolicy;
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
@@ -52,17 +56,20 @@ use types::{
 use types::{
     BeaconStateError, BlindedPayload, ChainSpec, Epoch, ExecPayload, ExecutionPayloadBellatrix,
     ExecutionPayloadCapella, ExecutionPayloadElectra, FullPayload, ProposerPreparationData,
-    PublicKeyBytes, Signature, Slot,
+    PublicKeyBytes, Signature, SignedValidatorRegistrationData, Slot, Transactions,
 };
 
 mod block_hash;
 mod engine_api;
 pub mod engines;
+pub mod genesis;
 mod keccak;
 mod metrics;
 pub mod payload_cache;
 mod payload_status;
+pub mod registration_cache;
 pub mod test_utils;
+pub mod verified_payload_cache;
 mod versioned_hashes;
 
 /// Indicates the default jwt authenticated execution endpoint.
@@ -356,6 +363,8 @@ struct Inner<E: EthSpec> {
     proposers: RwLock<HashMap<ProposerKey, Proposer>>,
     executor: TaskExecutor,
     payload_cache: PayloadCache<E>,
+    registration_cache: RegistrationCache,
+    verified_payload_cache: VerifiedPayloadCache,
     log: Logger,
     /// Track whether the last `newPayload` call errored.
     ///
@@ -387,6 +396,8 @@ pub struct Config {
     /// Default directory for the jwt secret if not provided through cli.
     pub default_datadir: PathBuf,
     pub execution_timeout_multiplier: Option<u32>,
+    /// Timeouts and retries of requests to the execution engine.
+    pub request_policy: RequestPolicy,
 }
 
 /// Provides access to one execution engine and provides a neat interface for consumption by the
@@ -410,6 +421,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
             jwt_version,
             default_datadir,
             execution_timeout_multiplier,
+            request_policy,
         } = config;
 
         let execution_url = url.ok_or(Error::NoEngine)?;
@@ -417,19 +429,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         // Use the default jwt secret path if not provided via cli.
         let secret_file = secret_file.unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
 
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        } else {
+        if !secret_file.exists() {
             // Create a new file and write a randomly generated secret to it if file does not exist
             warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
             std::fs::File::options()
@@ -438,19 +438,21 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 .open(&secret_file)
                 .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
                 .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
+                    let secret = JwtKey::random();
                     f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
+                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))
                 })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
+                .map_err(Error::InvalidJWTSecret)?;
+        }
 
         let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+            // The secret file may contain several secrets, and is read again whenever it changes.
+            let auth = Auth::new_with_path(secret_file.clone(), jwt_id, jwt_version)
+                .map_err(|e| Error::InvalidJWTSecret(format!("{:?}", e)))?;
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
             let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
-                .map_err(Error::ApiError)?;
+                .map_err(Error::ApiError)?
+                .with_request_policy(request_policy);
             Engine::new(api, executor.clone(), &log)
         };
 
@@ -464,6 +466,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
             execution_blocks: Mutex::new(LruCache::new(EXECUTION_BLOCKS_LRU_CACHE_SIZE)),
             executor,
             payload_cache: PayloadCache::default(),
+            registration_cache: RegistrationCache::default(),
+            verified_payload_cache: VerifiedPayloadCache::default(),
             log,
             last_new_payload_errored: RwLock::new(false),
         };
@@ -510,6 +514,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "local_user_agent" => builder_client.get_user_agent(),
         );
         self.inner.builder.swap(Some(Arc::new(builder_client)));
+        // The new builder has not received any registrations.
+        self.inner.registration_cache.clear();
         Ok(())
     }
 
@@ -616,6 +622,16 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
+        match self.engine().api.reload_jwt_secrets() {
+            Ok(true) => info!(self.log(), "Reloaded JWT secrets"),
+            Ok(false) => {}
+            Err(e) => warn!(
+                self.log(),
+                "Failed to reload JWT secrets";
+                "error" => ?e,
+                "info" => "continuing to use the previous secrets"
+            ),
+        }
         self.engine().upcheck().await;
     }
 
@@ -735,6 +751,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         });
         drop(proposer_preparation_data);
 
+        self.inner.registration_cache.prune(retain_epoch);
+
         let retain_slot = retain_epoch.start_slot(E::slots_per_epoch());
         self.proposers()
             .write()
@@ -744,6 +762,84 @@ impl<E: EthSpec> ExecutionLayer<E> {
         Ok(())
     }
 
+    /// Records `registrations` as submitted by a validator client in `current_epoch`, returning
+    /// those which are new or changed and must be forwarded to the builder.
+    pub fn unsent_validator_registrations(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+        current_epoch: Epoch,
+    ) -> Vec<SignedValidatorRegistrationData> {
+        let unsent = self
+            .inner
+            .registration_cache
+            .unsent(registrations, current_epoch);
+        metrics::inc_counter_by(
+            &metrics::EXECUTION_LAYER_BUILDER_REGISTRATIONS_UNCHANGED,
+            registrations.len().saturating_sub(unsent.len()) as u64,
+        );
+        unsent
+    }
+
+    /// Records that `registrations` were accepted by the builder in `epoch`.
+    pub fn on_validator_registrations_sent(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+        epoch: Epoch,
+    ) {
+        metrics::inc_counter_by(
+            &metrics::EXECUTION_LAYER_BUILDER_REGISTRATIONS_SENT,
+            registrations.len() as u64,
+        );
+        self.inner.registration_cache.on_sent(registrations, epoch);
+    }
+
+    /// Returns the validator registrations which are known to the beacon node.
+    pub fn validator_registrations(&self) -> Vec<CachedValidatorRegistration> {
+        self.inner.registration_cache.registrations()
+    }
+
+    /// Returns the proposer preparation data along with the epoch in which each entry was last
+    /// updated, or `None` if the data is currently locked.
+    ///
+    /// This method is safe to call from both synchronous and asynchronous contexts.
+    pub fn try_proposer_preparations(&self) -> Option<Vec<(Epoch, ProposerPreparationData)>> {
+        let proposer_preparation_data = self.inner.proposer_preparation_data.try_lock().ok()?;
+        Some(
+            proposer_preparation_data
+                .values()
+                .map(|entry| (entry.update_epoch, entry.preparation_data.clone()))
+                .collect(),
+        )
+    }
+
+    /// Restores proposer preparation data and validator registrations which were persisted by a
+    /// previous run of the beacon node. Entries which are already known are not replaced.
+    ///
+    /// Returns `false` if the proposer preparation data is currently locked, in which case nothing
+    /// is restored. This method is safe to call from both synchronous and asynchronous contexts.
+    pub fn try_restore_proposer_preparations(
+        &self,
+        preparations: Vec<(Epoch, ProposerPreparationData)>,
+        registrations: Vec<CachedValidatorRegistration>,
+    ) -> bool {
+        let Ok(mut proposer_preparation_data) = self.inner.proposer_preparation_data.try_lock()
+        else {
+            return false;
+        };
+        for (update_epoch, preparation_data) in preparations {
+            proposer_preparation_data
+                .entry(preparation_data.validator_index)
+                .or_insert(ProposerPreparationDataEntry {
+                    update_epoch,
+                    preparation_data,
+                });
+        }
+        drop(proposer_preparation_data);
+
+        self.inner.registration_cache.restore(registrations);
+        true
+    }
+
     /// Returns `true` if there have been any validators registered via
     /// `Self::update_proposer_preparation`.
     pub async fn has_any_proposer_preparation_data(&self) -> bool {
@@ -1357,6 +1453,24 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let block_hash = new_payload_request.block_hash();
         let parent_hash = new_payload_request.parent_hash();
 
+        // A payload which was verified before, possibly by a previous run of the beacon node, does
+        // not need to be sent to the EL again. Its contents must still match its block hash.
+        if self.inner.verified_payload_cache.contains(&block_hash)
+            && new_payload_request
+                .perform_optimistic_sync_verifications()
+                .is_ok()
+        {
+            metrics::inc_counter(&metrics::EXECUTION_LAYER_VERIFIED_PAYLOAD_CACHE_HITS);
+            debug!(
+                self.log(),
+                "Payload already verified";
+                "parent_hash" => ?parent_hash,
+                "block_hash" => ?block_hash,
+                "block_number" => block_number,
+            );
+            return Ok(PayloadStatus::Valid);
+        }
+
         let result = self
             .engine()
             .request(|engine| engine.api.new_payload(new_payload_request))
@@ -1380,9 +1494,25 @@ impl<E: EthSpec> ExecutionLayer<E> {
         }
         *self.inner.last_new_payload_errored.write().await = result.is_err();
 
-        process_payload_status(block_hash, result, self.log())
+        let status = process_payload_status(block_hash, result, self.log())
             .map_err(Box::new)
-            .map_err(Error::EngineError)
+            .map_err(Error::EngineError)?;
+        if status == PayloadStatus::Valid {
+            self.inner.verified_payload_cache.insert(block_hash);
+        }
+        Ok(status)
+    }
+
+    /// Returns the block hashes of the payloads verified as valid by the EL, from least to most
+    /// recently used.
+    pub fn verified_payload_hashes(&self) -> Vec<ExecutionBlockHash> {
+        self.inner.verified_payload_cache.block_hashes()
+    }
+
+    /// Adds `block_hashes` to the payloads verified as valid by the EL, such as those persisted by
+    /// a previous run of the beacon node.
+    pub fn restore_verified_payload_hashes(&self, block_hashes: Vec<ExecutionBlockHash>) {
+        self.inner.verified_payload_cache.restore(block_hashes);
     }
 
     /// Update engine sync status.
@@ -1901,7 +2031,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         };
 
         let convert_transactions = |transactions: Vec<EthersTransaction>| {
-            VariableList::new(
+            Transactions::<E>::new(
                 transactions
                     .into_iter()
                     .map(|tx| VariableList::new(tx.rlp().to_vec()))
@@ -32,6 +32,23 @@ lazy_static::lazy_static! {
         decimal_buckets(-2, 1),
         &["method"]
     );
+    pub static ref EXECUTION_LAYER_ENGINE_API_TIMES: Result<HistogramVec> =
+        try_create_histogram_vec_with_buckets(
+        "execution_layer_engine_api_request_seconds",
+        "Duration of each attempt of a JSON-RPC request to the EL",
+        decimal_buckets(-3, 1),
+        &["method"]
+    );
+    pub static ref EXECUTION_LAYER_ENGINE_API_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_engine_api_request_errors_total",
+        "Count of failed attempts of JSON-RPC requests to the EL, by class of error",
+        &["method", "class"]
+    );
+    pub static ref EXECUTION_LAYER_ENGINE_API_RETRIES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_engine_api_request_retries_total",
+        "Count of JSON-RPC requests to the EL which were retried",
+        &["method"]
+    );
     pub static ref EXECUTION_LAYER_PAYLOAD_ATTRIBUTES_LOOKAHEAD: Result<Histogram> = try_create_histogram(
         "execution_layer_payload_attributes_lookahead",
         "Duration between an fcU call with PayloadAttributes and when the block should be produced",
@@ -79,6 +96,18 @@ lazy_static::lazy_static! {
         "The reasons why a payload from a builder was rejected",
         &["reason"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_REGISTRATIONS_SENT: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_builder_registrations_sent",
+        "Count of validator registrations accepted by the builder",
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_REGISTRATIONS_UNCHANGED: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_builder_registrations_unchanged",
+        "Count of validator registrations not forwarded to the builder as they were already sent",
+    );
+    pub static ref EXECUTION_LAYER_VERIFIED_PAYLOAD_CACHE_HITS: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_verified_payload_cache_hits",
+        "Count of payloads considered valid without calling newPayload as they were already verified",
+    );
     pub static ref EXECUTION_LAYER_PAYLOAD_BIDS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "execution_layer_payload_bids",
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::MAX.",
@@ -0,0 +1,200 @@
+//! Caches the validator registrations which are forwarded to the builder.
+//!
+//! Validator clients submit the registrations of all of their validators every epoch, and almost
+//! all of them are unchanged from the previous epoch. Forwarding the full set each time causes
+//! operators with many validators to hit the rate limits of relays, so only new or changed
+//! registrations are forwarded. Unchanged registrations are re-sent every
+//! `REGISTRATION_RESEND_EPOCHS`, in case the builder has lost them.
+use eth2::lighthouse::CachedValidatorRegistration;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use types::{Epoch, PublicKeyBytes, SignedValidatorRegistrationData};
+
+/// The number of epochs after which an unchanged registration is forwarded to the builder again.
+pub const REGISTRATION_RESEND_EPOCHS: u64 = 256;
+
+#[derive(Default)]
+pub struct RegistrationCache {
+    registrations: Mutex<HashMap<PublicKeyBytes, CachedValidatorRegistration>>,
+}
+
+impl RegistrationCache {
+    /// Records `registrations` as submitted in `current_epoch`, returning those which must be
+    /// forwarded to the builder.
+    pub fn unsent(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+        current_epoch: Epoch,
+    ) -> Vec<SignedValidatorRegistrationData> {
+        let mut cache = self.registrations.lock();
+        registrations
+            .iter()
+            .filter(|registration| {
+                let entry = cache
+                    .entry(registration.message.pubkey)
+                    .and_modify(|entry| {
+                        if entry.registration != **registration {
+                            entry.registration = (*registration).clone();
+                            entry.last_sent_epoch = None;
+                        }
+                    })
+                    .or_insert_with(|| CachedValidatorRegistration {
+                        registration: (*registration).clone(),
+                        last_seen_epoch: current_epoch,
+                        last_sent_epoch: None,
+                    });
+                entry.last_seen_epoch = current_epoch;
+
+                entry.last_sent_epoch.map_or(true, |last_sent_epoch| {
+                    last_sent_epoch + REGISTRATION_RESEND_EPOCHS <= current_epoch
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Records that `registrations` were accepted by the builder in `epoch`.
+    ///
+    /// A registration which has since been replaced in the cache is left unsent.
+    pub fn on_sent(&self, registrations: &[SignedValidatorRegistrationData], epoch: Epoch) {
+        let mut cache = self.registrations.lock();
+        for registration in registrations {
+            if let Some(entry) = cache.get_mut(&registration.message.pubkey) {
+                if entry.registration == *registration {
+                    entry.last_sent_epoch = Some(epoch);
+                }
+            }
+        }
+    }
+
+    /// Adds `registrations` which are not already cached, such as those persisted by a previous
+    /// run of the beacon node.
+    pub fn restore(&self, registrations: Vec<CachedValidatorRegistration>) {
+        let mut cache = self.registrations.lock();
+        for entry in registrations {
+            cache
+                .entry(entry.registration.message.pubkey)
+                .or_insert(entry);
+        }
+    }
+
+    /// Removes the registrations which have not been submitted since `retain_epoch`.
+    pub fn prune(&self, retain_epoch: Epoch) {
+        self.registrations
+            .lock()
+            .retain(|_, entry| entry.last_seen_epoch >= retain_epoch);
+    }
+
+    /// Removes all registrations, so that they are all forwarded when next submitted.
+    pub fn clear(&self) {
+        self.registrations.lock().clear();
+    }
+
+    /// Returns all cached registrations, in no particular order.
+    pub fn registrations(&self) -> Vec<CachedValidatorRegistration> {
+        self.registrations.lock().values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Address, Signature, ValidatorRegistrationData};
+
+    fn registration(index: u8, gas_limit: u64) -> SignedValidatorRegistrationData {
+        SignedValidatorRegistrationData {
+            message: ValidatorRegistrationData {
+                fee_recipient: Address::repeat_byte(index),
+                gas_limit,
+                timestamp: 0,
+                pubkey: PublicKeyBytes::deserialize(&[index; 48]).unwrap(),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    #[test]
+    fn only_changed_registrations_are_unsent() {
+        let cache = RegistrationCache::default();
+        let registrations = vec![registration(1, 30_000_000), registration(2, 30_000_000)];
+
+        let epoch = Epoch::new(1);
+        assert_eq!(cache.unsent(&registrations, epoch), registrations);
+        cache.on_sent(&registrations, epoch);
+
+        let epoch = Epoch::new(2);
+        assert!(cache.unsent(&registrations, epoch).is_empty());
+
+        let changed = vec![registration(1, 30_000_000), registration(2, 36_000_000)];
+        assert_eq!(cache.unsent(&changed, epoch), vec![changed[1].clone()]);
+
+        // The original registration was replaced before it could be marked as sent.
+        cache.on_sent(&registrations[1..], epoch);
+        assert_eq!(cache.unsent(&changed, epoch), vec![changed[1].clone()]);
+    }
+
+    #[test]
+    fn failed_registrations_are_retried() {
+        let cache = RegistrationCache::default();
+        let registrations = vec![registration(1, 30_000_000)];
+
+        assert_eq!(cache.unsent(&registrations, Epoch::new(1)), registrations);
+        assert_eq!(cache.unsent(&registrations, Epoch::new(2)), registrations);
+    }
+
+    #[test]
+    fn unchanged_registrations_are_resent() {
+        let cache = RegistrationCache::default();
+        let registrations = vec![registration(1, 30_000_000)];
+
+        cache.unsent(&registrations, Epoch::new(0));
+        cache.on_sent(&registrations, Epoch::new(0));
+
+        assert!(cache
+            .unsent(&registrations, Epoch::new(REGISTRATION_RESEND_EPOCHS - 1))
+            .is_empty());
+        assert_eq!(
+            cache.unsent(&registrations, Epoch::new(REGISTRATION_RESEND_EPOCHS)),
+            registrations
+        );
+    }
+
+    #[test]
+    fn restore_does_not_replace_registrations() {
+        let cache = RegistrationCache::default();
+        let registrations = vec![registration(1, 30_000_000)];
+        cache.unsent(&registrations, Epoch::new(2));
+
+        let persisted = vec![
+            CachedValidatorRegistration {
+                registration: registration(1, 36_000_000),
+                last_seen_epoch: Epoch::new(1),
+                last_sent_epoch: Some(Epoch::new(1)),
+            },
+            CachedValidatorRegistration {
+                registration: registration(2, 30_000_000),
+                last_seen_epoch: Epoch::new(1),
+                last_sent_epoch: Some(Epoch::new(1)),
+            },
+        ];
+        cache.restore(persisted);
+
+        // The restored registration is not forwarded again, unlike the newer one.
+        let both = vec![registration(1, 30_000_000), registration(2, 30_000_000)];
+        assert_eq!(cache.unsent(&both, Epoch::new(2)), registrations);
+    }
+
+    #[test]
+    fn prune_removes_stale_registrations() {
+        let cache = RegistrationCache::default();
+        cache.unsent(&[registration(1, 30_000_000)], Epoch::new(1));
+        cache.unsent(&[registration(2, 30_000_000)], Epoch::new(3));
+
+        cache.prune(Epoch::new(2));
+
+        let cached = cache.registrations();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].registration, registration(2, 30_000_000));
+        assert_eq!(cached[0].last_seen_epoch, Epoch::new(3));
+    }
+}
@@ -0,0 +1,95 @@
+//! Caches the block hashes of execution payloads which the execution engine has verified as valid.
+//!
+//! The cache is persisted across restarts by the beacon chain. After a restart the execution
+//! engine may be syncing, in which case payloads sent to it are not verified and the beacon node
+//! remains optimistic. A payload which hits the cache is instead considered valid without
+//! contacting the execution engine, once its contents have been checked against its block hash.
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use types::non_zero_usize::new_non_zero_usize;
+use types::ExecutionBlockHash;
+
+/// Roughly a day of blocks.
+pub const DEFAULT_VERIFIED_PAYLOAD_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(8192);
+
+pub struct VerifiedPayloadCache {
+    block_hashes: Mutex<LruCache<ExecutionBlockHash, ()>>,
+}
+
+impl Default for VerifiedPayloadCache {
+    fn default() -> Self {
+        VerifiedPayloadCache {
+            block_hashes: Mutex::new(LruCache::new(DEFAULT_VERIFIED_PAYLOAD_CACHE_SIZE)),
+        }
+    }
+}
+
+impl VerifiedPayloadCache {
+    /// Records that the payload with `block_hash` was verified as valid.
+    pub fn insert(&self, block_hash: ExecutionBlockHash) {
+        self.block_hashes.lock().put(block_hash, ());
+    }
+
+    /// Returns `true` if the payload with `block_hash` was verified as valid.
+    pub fn contains(&self, block_hash: &ExecutionBlockHash) -> bool {
+        self.block_hashes.lock().get(block_hash).is_some()
+    }
+
+    /// Returns the cached block hashes, from least to most recently used.
+    pub fn block_hashes(&self) -> Vec<ExecutionBlockHash> {
+        self.block_hashes
+            .lock()
+            .iter()
+            .rev()
+            .map(|(block_hash, _)| *block_hash)
+            .collect()
+    }
+
+    /// Adds `block_hashes` which are not already cached, such as those persisted by a previous run
+    /// of the beacon node. They should be ordered from least to most recently used.
+    pub fn restore(&self, block_hashes: Vec<ExecutionBlockHash>) {
+        let mut cache = self.block_hashes.lock();
+        for block_hash in block_hashes {
+            if !cache.contains(&block_hash) {
+                cache.put(block_hash, ());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::Hash256;
+
+    fn block_hash(i: u64) -> ExecutionBlockHash {
+        ExecutionBlockHash::from_root(Hash256::from_low_u64_be(i))
+    }
+
+    #[test]
+    fn restore_preserves_recency() {
+        let cache = VerifiedPayloadCache::default();
+        cache.insert(block_hash(1));
+        cache.insert(block_hash(2));
+        assert!(cache.contains(&block_hash(1)));
+        assert!(!cache.contains(&block_hash(3)));
+        assert_eq!(cache.block_hashes(), vec![block_hash(2), block_hash(1)]);
+
+        let restored = VerifiedPayloadCache::default();
+        restored.restore(cache.block_hashes());
+        assert_eq!(restored.block_hashes(), cache.block_hashes());
+    }
+
+    #[test]
+    fn least_recently_used_are_evicted() {
+        let cache = VerifiedPayloadCache::default();
+        let size = DEFAULT_VERIFIED_PAYLOAD_CACHE_SIZE.get() as u64;
+        for i in 0..=size {
+            cache.insert(block_hash(i));
+        }
+        assert!(!cache.contains(&block_hash(0)));
+        assert!(cache.contains(&block_hash(size)));
+        assert_eq!(cache.block_hashes().len() as u64, size);
+    }
+}
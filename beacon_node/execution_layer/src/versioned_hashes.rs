@@ -102,9 +102,7 @@ mod test {
 
         #[derive(Deserialize)]
         #[serde(transparent)]
-        struct TestTransactions<E: EthSpec>(
-            #[serde(with = "ssz_types::serde_utils::list_of_hex_var_list")] types::Transactions<E>,
-        );
+        struct TestTransactions<E: EthSpec>(types::Transactions<E>);
 
         let TestTransactions(raw_transactions): TestTransactions<E> = serde_json::from_str(r#"[
             "0x03f901388501a1f0ff430f843b9aca00843b9aca0082520894e7249813d8ccf6fa95a2203f46a64166073d58878080c002f8c6a0012e98362c814f1724262c0d211a1463418a5f6382a8d457b37a2698afbe7b5ea00100ef985761395dfa8ed5ce91f3f2180b612401909e4cb8f33b90c8a454d9baa0013d45411623b90d90f916e4025ada74b453dd4ca093c017c838367c9de0f801a001753e2af0b1e70e7ef80541355b2a035cc9b2c177418bb2a4402a9b346cf84da0011789b520a8068094a92aa0b04db8d8ef1c6c9818947c5210821732b8744049a0011c4c4f95597305daa5f62bf5f690e37fa11f5de05a95d05cac4e2119e394db80a0ccd86a742af0e042d08cbb35d910ddc24bbc6538f9e53be6620d4b6e1bb77662a01a8bacbc614940ac2f5c23ffc00a122c9f085046883de65c88ab0edb859acb99",
@@ -16,10 +16,20 @@ pub fn bls_withdrawal_credentials(pubkey: &PublicKey, spec: &ChainSpec) -> Hash2
     Hash256::from_slice(&credentials)
 }
 
-fn eth1_withdrawal_credentials(pubkey: &PublicKey, spec: &ChainSpec) -> Hash256 {
+pub fn eth1_withdrawal_credentials(pubkey: &PublicKey, spec: &ChainSpec) -> Hash256 {
+    execution_withdrawal_credentials(pubkey, spec.eth1_address_withdrawal_prefix_byte)
+}
+
+/// Returns 0x02 credentials which withdraw to the same fake execution address as
+/// `eth1_withdrawal_credentials`.
+pub fn compounding_withdrawal_credentials(pubkey: &PublicKey, spec: &ChainSpec) -> Hash256 {
+    execution_withdrawal_credentials(pubkey, spec.compounding_withdrawal_prefix_byte)
+}
+
+fn execution_withdrawal_credentials(pubkey: &PublicKey, prefix_byte: u8) -> Hash256 {
     let fake_execution_address = &hash(&pubkey.as_ssz_bytes())[0..20];
     let mut credentials = [0u8; 32];
-    credentials[0] = spec.eth1_address_withdrawal_prefix_byte;
+    credentials[0] = prefix_byte;
     credentials[12..].copy_from_slice(fake_execution_address);
     Hash256::from_slice(&credentials)
 }
@@ -137,7 +147,7 @@ pub fn interop_genesis_state_with_withdrawal_credentials<E: EthSpec>(
 #[cfg(test)]
 mod test {
     use super::*;
-    use types::{test_utils::generate_deterministic_keypairs, MinimalEthSpec};
+    use types::{test_utils::generate_deterministic_keypairs, Epoch, MinimalEthSpec};
 
     type TestEthSpec = MinimalEthSpec;
 
@@ -283,4 +293,41 @@ mod test {
             "validator count should be correct"
         );
     }
+
+    #[test]
+    fn execution_payload_header_for_other_fork() {
+        let mut spec = TestEthSpec::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(0));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+        let keypairs = generate_deterministic_keypairs(16);
+
+        let result = interop_genesis_state::<TestEthSpec>(
+            &keypairs,
+            42,
+            Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+            Some(ExecutionPayloadHeader::Capella(<_>::default())),
+            &spec,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn compounding_credentials_share_eth1_address() {
+        let spec = &TestEthSpec::default_spec();
+        let keypair = &generate_deterministic_keypairs(1)[0];
+
+        let eth1 = eth1_withdrawal_credentials(&keypair.pk, spec);
+        let compounding = compounding_withdrawal_credentials(&keypair.pk, spec);
+
+        assert_eq!(
+            compounding.as_bytes()[0],
+            spec.compounding_withdrawal_prefix_byte,
+            "first byte of withdrawal creds should be compounding prefix"
+        );
+        assert_eq!(
+            &compounding.as_bytes()[1..],
+            &eth1.as_bytes()[1..],
+            "compounding creds should withdraw to the eth1 address"
+        );
+    }
 }
@@ -6,7 +6,8 @@ pub use eth1::Config as Eth1Config;
 pub use eth1::Eth1Endpoint;
 pub use eth1_genesis_service::{Eth1GenesisService, Statistics};
 pub use interop::{
-    bls_withdrawal_credentials, interop_genesis_state, interop_genesis_state_with_eth1,
+    bls_withdrawal_credentials, compounding_withdrawal_credentials, eth1_withdrawal_credentials,
+    interop_genesis_state, interop_genesis_state_with_eth1,
     interop_genesis_state_with_withdrawal_credentials, DEFAULT_ETH1_BLOCK_HASH,
 };
 pub use types::test_utils::generate_deterministic_keypairs;
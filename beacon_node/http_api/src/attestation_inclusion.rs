@@ -0,0 +1,275 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::{
+    lighthouse::{AttestationInclusion, AttestationInclusionBlock},
+    types::ValidatorId,
+};
+use std::cmp;
+use types::{
+    AttestationDuty, AttestationRef, BeaconState, BeaconStateError, Epoch, EthSpec, RelativeEpoch,
+};
+use warp_utils::reject::{
+    beacon_chain_error, beacon_state_error, custom_bad_request, custom_not_found,
+};
+
+/// Returns the canonical blocks which included the attestation of `validator_id` in `epoch`.
+pub fn attestation_inclusion<T: BeaconChainTypes>(
+    validator_id: &ValidatorId,
+    epoch: Epoch,
+    chain: &BeaconChain<T>,
+) -> Result<AttestationInclusion, warp::Rejection> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    if epoch > current_slot.epoch(slots_per_epoch) {
+        return Err(custom_bad_request(format!(
+            "epoch {epoch} is after the current epoch"
+        )));
+    }
+
+    // The execution status is not returned, the blocks may be optimistic.
+    let (mut state, _execution_optimistic, _finalized) =
+        StateId::from_slot(epoch.start_slot(slots_per_epoch)).state(chain)?;
+    state
+        .build_committee_cache(RelativeEpoch::Current, &chain.spec)
+        .map_err(beacon_state_error)?;
+    state.update_pubkey_cache().map_err(beacon_state_error)?;
+
+    let validator_index = match validator_id {
+        ValidatorId::Index(index) => *index as usize,
+        ValidatorId::PublicKey(pubkey) => state
+            .get_validator_index(pubkey)
+            .map_err(beacon_state_error)?
+            .ok_or_else(|| custom_not_found(format!("unknown validator {pubkey:?}")))?,
+    };
+    let duty = state
+        .get_attestation_duties(validator_index, RelativeEpoch::Current)
+        .map_err(beacon_state_error)?
+        .ok_or_else(|| {
+            custom_not_found(format!(
+                "validator {validator_index} is not in a committee in epoch {epoch}"
+            ))
+        })?;
+
+    // Attestations may be included until the end of the epoch after their own.
+    let start_slot = duty.slot + chain.spec.min_attestation_inclusion_delay;
+    let end_slot = cmp::min((epoch + 1).end_slot(slots_per_epoch), current_slot);
+
+    let mut blocks = vec![];
+    if start_slot <= end_slot {
+        let mut block_roots = chain
+            .forwards_iter_block_roots_until(start_slot, end_slot)
+            .map_err(beacon_chain_error)?
+            .map(|res| res.map(|(root, _)| root))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(beacon_chain_error)?;
+        block_roots.dedup();
+
+        for block_root in block_roots {
+            let block = chain
+                .get_blinded_block(&block_root)
+                .and_then(|block| block.ok_or(BeaconChainError::MissingBeaconBlock(block_root)))
+                .map_err(beacon_chain_error)?;
+            // The root of a skipped slot is that of the block before it.
+            if block.slot() < start_slot {
+                continue;
+            }
+
+            for (attestation_index, attestation) in
+                block.message().body().attestations().enumerate()
+            {
+                if let Some(aggregation_bit) =
+                    aggregation_bit(attestation, &duty, &state).map_err(beacon_state_error)?
+                {
+                    blocks.push(AttestationInclusionBlock {
+                        block_root,
+                        slot: block.slot(),
+                        inclusion_delay: block.slot().as_u64() - duty.slot.as_u64(),
+                        attestation_index: attestation_index as u64,
+                        aggregation_bit: aggregation_bit as u64,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(AttestationInclusion {
+        validator_index: validator_index as u64,
+        epoch,
+        attestation_slot: duty.slot,
+        committee_index: duty.index,
+        committee_position: duty.committee_position as u64,
+        blocks,
+    })
+}
+
+/// Returns the position of the bit of the attester of `duty` in the aggregation bits of
+/// `attestation`, if the attestation is from the committee of `duty` and the bit is set.
+fn aggregation_bit<E: EthSpec>(
+    attestation: AttestationRef<E>,
+    duty: &AttestationDuty,
+    state: &BeaconState<E>,
+) -> Result<Option<usize>, BeaconStateError> {
+    if attestation.data().slot != duty.slot {
+        return Ok(None);
+    }
+
+    let (bit, is_set) = match attestation {
+        AttestationRef::Base(attestation) => {
+            if attestation.data.index != duty.index {
+                return Ok(None);
+            }
+            let bit = duty.committee_position;
+            (bit, attestation.aggregation_bits.get(bit))
+        }
+        AttestationRef::Electra(attestation) => {
+            let committee_indices = attestation.get_committee_indices();
+            if !committee_indices.contains(&duty.index) {
+                return Ok(None);
+            }
+            // The aggregation bits of the committees are concatenated in order of their index.
+            let mut bit = duty.committee_position;
+            for index in committee_indices
+                .into_iter()
+                .take_while(|index| *index < duty.index)
+            {
+                bit += state
+                    .get_beacon_committee(duty.slot, index)?
+                    .committee
+                    .len();
+            }
+            (bit, attestation.aggregation_bits.get(bit))
+        }
+    };
+    Ok(is_set.unwrap_or(false).then_some(bit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        test_utils::generate_deterministic_keypairs, AggregateSignature, Attestation,
+        AttestationBase, AttestationData, AttestationElectra, BitList, BitVector, Hash256,
+        MinimalEthSpec, Slot,
+    };
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+
+    fn genesis_state() -> BeaconState<E> {
+        let spec = E::default_spec();
+        let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT);
+        let mut state =
+            genesis::interop_genesis_state(&keypairs, 0, Hash256::repeat_byte(0x42), None, &spec)
+                .unwrap();
+        state
+            .build_committee_cache(RelativeEpoch::Current, &spec)
+            .unwrap();
+        state
+    }
+
+    fn data(slot: Slot, index: u64) -> AttestationData {
+        AttestationData {
+            slot,
+            index,
+            beacon_block_root: Hash256::zero(),
+            source: Default::default(),
+            target: Default::default(),
+        }
+    }
+
+    fn base_attestation(slot: Slot, index: u64, len: usize, bit: usize) -> Attestation<E> {
+        let mut aggregation_bits = BitList::with_capacity(len).unwrap();
+        aggregation_bits.set(bit, true).unwrap();
+        Attestation::Base(AttestationBase {
+            aggregation_bits,
+            data: data(slot, index),
+            signature: AggregateSignature::empty(),
+        })
+    }
+
+    fn electra_attestation(slot: Slot, committees: &[(u64, usize)], bit: usize) -> Attestation<E> {
+        let len = committees.iter().map(|(_, len)| len).sum();
+        let mut aggregation_bits = BitList::with_capacity(len).unwrap();
+        aggregation_bits.set(bit, true).unwrap();
+        let mut committee_bits = BitVector::new();
+        for (index, _) in committees {
+            committee_bits.set(*index as usize, true).unwrap();
+        }
+        Attestation::Electra(AttestationElectra {
+            aggregation_bits,
+            data: data(slot, 0),
+            committee_bits,
+            signature: AggregateSignature::empty(),
+        })
+    }
+
+    /// Returns the duty of the last attester of the second committee at `slot`, and the sizes of
+    /// the first two committees.
+    fn duty(state: &BeaconState<E>, slot: Slot) -> (AttestationDuty, usize, usize) {
+        let committees = state.get_beacon_committees_at_slot(slot).unwrap();
+        assert!(committees.len() >= 2, "test requires several committees");
+        let (first, second) = (committees[0].committee.len(), committees[1].committee.len());
+        let duty = AttestationDuty {
+            slot,
+            index: 1,
+            committee_position: second - 1,
+            committee_len: second,
+            committees_at_slot: committees.len() as u64,
+        };
+        (duty, first, second)
+    }
+
+    #[test]
+    fn base_aggregation_bit() {
+        let state = genesis_state();
+        let slot = Slot::new(1);
+        let (duty, first, second) = duty(&state, slot);
+        let position = duty.committee_position;
+        let bit = |attestation: &Attestation<E>| {
+            aggregation_bit(attestation.to_ref(), &duty, &state).unwrap()
+        };
+
+        assert_eq!(
+            bit(&base_attestation(slot, 1, second, position)),
+            Some(position)
+        );
+        // Another attester of the same committee.
+        assert_eq!(bit(&base_attestation(slot, 1, second, 0)), None);
+        // Another committee, or another slot.
+        assert_eq!(bit(&base_attestation(slot, 0, first, position)), None);
+        assert_eq!(bit(&base_attestation(slot + 1, 1, second, position)), None);
+    }
+
+    #[test]
+    fn electra_aggregation_bit() {
+        let state = genesis_state();
+        let slot = Slot::new(1);
+        let (duty, first, second) = duty(&state, slot);
+        let position = duty.committee_position;
+        let bit = |attestation: &Attestation<E>| {
+            aggregation_bit(attestation.to_ref(), &duty, &state).unwrap()
+        };
+
+        // The bits of the second committee follow those of the first.
+        let both = [(0, first), (1, second)];
+        assert_eq!(
+            bit(&electra_attestation(slot, &both, first + position)),
+            Some(first + position)
+        );
+        assert_eq!(bit(&electra_attestation(slot, &both, position)), None);
+        assert_eq!(
+            bit(&electra_attestation(slot, &[(1, second)], position)),
+            Some(position)
+        );
+        // Another committee, or another slot.
+        assert_eq!(
+            bit(&electra_attestation(slot, &[(0, first)], position)),
+            None
+        );
+        assert_eq!(
+            bit(&electra_attestation(slot + 1, &both, first + position)),
+            None
+        );
+    }
+}
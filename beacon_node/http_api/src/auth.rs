@@ -0,0 +1,259 @@
+//! Token-based authorization for the HTTP API.
+//!
+//! Authorization is optional and is enabled by supplying a token file. Each token in the file is
+//! granted one of the roles in `ApiRole`, and each endpoint requires a minimum role, as determined
+//! by `required_role`. Roles are ordered, so a token with the `admin` role may use every endpoint.
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use warp::filters::path::FullPath;
+use warp::http::Method;
+use warp::Filter;
+
+/// The level of access granted to an API token, in increasing order of privilege.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiRole {
+    /// Access to endpoints which read chain and node data.
+    ReadOnly,
+    /// Additional access to the endpoints required by a validator client.
+    Validator,
+    /// Access to all endpoints, including the debug and Lighthouse-specific endpoints.
+    Admin,
+}
+
+impl FromStr for ApiRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(ApiRole::ReadOnly),
+            "validator" => Ok(ApiRole::Validator),
+            "admin" => Ok(ApiRole::Admin),
+            other => Err(format!(
+                "{} is not a valid role, must be one of read-only, validator or admin",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ApiRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiRole::ReadOnly => write!(f, "read-only"),
+            ApiRole::Validator => write!(f, "validator"),
+            ApiRole::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// The set of API tokens accepted by the server, along with their roles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiTokens {
+    tokens: HashMap<String, ApiRole>,
+}
+
+impl ApiTokens {
+    /// Read the tokens from the file at `path`. See `Self::parse` for the format.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read API token file {:?}: {}", path, e))?;
+        Self::parse(&contents).map_err(|e| format!("Invalid API token file {:?}: {}", path, e))
+    }
+
+    /// Parse tokens from `contents`, which should contain one `<role>:<token>` pair per line.
+    ///
+    /// Blank lines and lines beginning with `#` are ignored.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut tokens = HashMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (role, token) = line
+                .split_once(':')
+                .ok_or_else(|| format!("line {} is not of the form <role>:<token>", i + 1))?;
+            let role = role.trim().parse::<ApiRole>()?;
+            let token = token.trim();
+            if token.is_empty() {
+                return Err(format!("line {} has an empty token", i + 1));
+            }
+            if tokens.insert(token.to_string(), role).is_some() {
+                return Err(format!("line {} repeats a token", i + 1));
+            }
+        }
+        if tokens.is_empty() {
+            return Err("no tokens found".to_string());
+        }
+        Ok(Self { tokens })
+    }
+
+    /// Returns the role granted by the value of an `Authorization` header, if any.
+    ///
+    /// As per the validator client, the token is accepted with either the `Bearer` or `Basic`
+    /// scheme.
+    pub fn role(&self, authorization: &str) -> Option<ApiRole> {
+        let token = authorization
+            .strip_prefix("Bearer ")
+            .or_else(|| authorization.strip_prefix("Basic "))?;
+        self.tokens.get(token).copied()
+    }
+}
+
+/// Returns the minimum role required to make a request with `method` to `path`.
+pub fn required_role(method: &Method, path: &str) -> ApiRole {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    match segments.as_slice() {
+        ["eth", _, "debug", ..] => ApiRole::Admin,
+        // Status endpoints which are safe to expose to monitoring.
+        ["lighthouse", "health" | "syncing" | "staking"]
+        | ["lighthouse", "eth1", "syncing"]
+        | ["lighthouse", "ui", "health" | "validator_count"] => ApiRole::ReadOnly,
+        // Chain data endpoints for explorers.
+        ["lighthouse", "blocks" | "validator_queues"]
+        | ["lighthouse", "blocks", _, "execution_requests"]
+        | ["lighthouse", "execution_requests", _]
+        | ["lighthouse", "rewards", "blocks"] => ApiRole::ReadOnly,
+        ["lighthouse", ..] => ApiRole::Admin,
+        ["eth", _, "validator", ..] => ApiRole::Validator,
+        ["eth", _, "beacon", "blocks" | "blinded_blocks" | "pool", ..]
+            if method == Method::POST =>
+        {
+            ApiRole::Validator
+        }
+        _ => ApiRole::ReadOnly,
+    }
+}
+
+/// Returns a `warp` filter which rejects requests without an `Authorization` header granting the
+/// role required by the endpoint.
+///
+/// Requests without a known token are rejected with a 401, and requests with a token lacking the
+/// required role with a 403.
+///
+/// If `api_tokens` is `None` all requests are accepted.
+pub fn authorization_filter(api_tokens: Option<Arc<ApiTokens>>) -> warp::filters::BoxedFilter<()> {
+    let Some(api_tokens) = api_tokens else {
+        return warp::any().boxed();
+    };
+    warp::any()
+        .map(move || api_tokens.clone())
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(warp::filters::header::header("Authorization"))
+        .and_then(
+            |api_tokens: Arc<ApiTokens>,
+             method: Method,
+             path: FullPath,
+             authorization: String| async move {
+                let required = required_role(&method, path.as_str());
+                match api_tokens.role(&authorization) {
+                    Some(role) if role >= required => Ok(()),
+                    Some(role) => Err(warp_utils::reject::invalid_auth(format!(
+                        "token has the {} role, the {} role is required",
+                        role, required
+                    ))),
+                    None => Err(warp_utils::reject::unauthorized(
+                        "unknown API token".to_string(),
+                    )),
+                }
+            },
+        )
+        .untuple_one()
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tokens() {
+        let tokens = ApiTokens::parse(
+            "# Comment\n\
+             read-only:abc\n\
+             \n\
+             validator: def\n\
+             admin:ghi\n",
+        )
+        .unwrap();
+        assert_eq!(tokens.role("Bearer abc"), Some(ApiRole::ReadOnly));
+        assert_eq!(tokens.role("Bearer def"), Some(ApiRole::Validator));
+        assert_eq!(tokens.role("Basic ghi"), Some(ApiRole::Admin));
+        assert_eq!(tokens.role("ghi"), None);
+        assert_eq!(tokens.role("Bearer xyz"), None);
+
+        assert!(ApiTokens::parse("").is_err());
+        assert!(ApiTokens::parse("abc").is_err());
+        assert!(ApiTokens::parse("root:abc").is_err());
+        assert!(ApiTokens::parse("admin:").is_err());
+        assert!(ApiTokens::parse("admin:abc\nvalidator:abc").is_err());
+    }
+
+    #[test]
+    fn endpoint_roles() {
+        let get = Method::GET;
+        let post = Method::POST;
+
+        assert_eq!(
+            required_role(&get, "/eth/v1/node/health"),
+            ApiRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&get, "/eth/v2/beacon/blocks/head"),
+            ApiRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&post, "/eth/v1/beacon/states/head/validators"),
+            ApiRole::ReadOnly
+        );
+        assert_eq!(required_role(&get, "/eth/v1/events"), ApiRole::ReadOnly);
+        assert_eq!(
+            required_role(&get, "/lighthouse/syncing"),
+            ApiRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&get, "/lighthouse/validator_queues"),
+            ApiRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&get, "/lighthouse/blocks/head/execution_requests"),
+            ApiRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&get, "/lighthouse/blocks/dry_run"),
+            ApiRole::Admin
+        );
+
+        assert_eq!(
+            required_role(&post, "/eth/v2/beacon/blocks"),
+            ApiRole::Validator
+        );
+        assert_eq!(
+            required_role(&post, "/eth/v1/beacon/pool/attestations"),
+            ApiRole::Validator
+        );
+        assert_eq!(
+            required_role(&get, "/eth/v3/validator/blocks/1"),
+            ApiRole::Validator
+        );
+        assert_eq!(
+            required_role(&post, "/eth/v1/validator/duties/attester/1"),
+            ApiRole::Validator
+        );
+
+        assert_eq!(
+            required_role(&get, "/eth/v2/debug/beacon/states/head"),
+            ApiRole::Admin
+        );
+        assert_eq!(required_role(&get, "/lighthouse/peers"), ApiRole::Admin);
+        assert_eq!(
+            required_role(&post, "/lighthouse/database/reconstruct"),
+            ApiRole::Admin
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{BlockDryRun, DryRunBlock};
+use slot_clock::SlotClock;
+use state_processing::state_advance::complete_state_advance;
+use state_processing::{
+    per_block_processing, BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
+};
+use std::sync::Arc;
+use types::{EthSpec, Hash256, Signature, SignedBeaconBlock};
+
+/// Run gossip and consensus validation of `block` without importing or publishing it.
+///
+/// The gossip checks read the caches used for gossip without updating them, so a dry run does not
+/// prevent the block from being published later. The execution payload is not sent to the
+/// execution layer.
+pub fn dry_run_block<T: BeaconChainTypes>(
+    block: DryRunBlock<T::EthSpec>,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<BlockDryRun, warp::Rejection> {
+    let (block, signed) = match block {
+        DryRunBlock::Signed(block) => (block, true),
+        DryRunBlock::Unsigned(block) => (
+            SignedBeaconBlock::from_block(block, Signature::empty()),
+            false,
+        ),
+    };
+    let block_root = block.canonical_root();
+
+    let gossip_error = check_gossip(&block, block_root, &chain).err();
+    let (computed_state_root, consensus_error) = match process_block(&block, signed, &chain) {
+        Ok(state_root) if state_root != block.state_root() => (
+            Some(state_root),
+            Some(format!(
+                "state root mismatch: block {:?}, computed {:?}",
+                block.state_root(),
+                state_root
+            )),
+        ),
+        Ok(state_root) => (Some(state_root), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    Ok(BlockDryRun {
+        block_root,
+        slot: block.slot(),
+        proposer_index: block.message().proposer_index(),
+        signed,
+        valid: gossip_error.is_none() && consensus_error.is_none(),
+        gossip_error,
+        consensus_error,
+        computed_state_root,
+    })
+}
+
+/// The gossip checks which don't depend on the state transition. The proposer index and
+/// signatures are checked by `process_block`.
+fn check_gossip<T: BeaconChainTypes>(
+    block: &SignedBeaconBlock<T::EthSpec>,
+    block_root: Hash256,
+    chain: &BeaconChain<T>,
+) -> Result<(), String> {
+    block
+        .fork_name(&chain.spec)
+        .map_err(|e| format!("inconsistent fork: {:?}", e))?;
+
+    let present_slot = chain
+        .slot_clock
+        .now_with_future_tolerance(chain.spec.maximum_gossip_clock_disparity())
+        .ok_or_else(|| "unable to read slot clock".to_string())?;
+    if block.slot() > present_slot {
+        return Err(format!(
+            "block is from a future slot, the present slot is {}",
+            present_slot
+        ));
+    }
+
+    let finalized_slot = chain
+        .canonical_head
+        .cached_head()
+        .finalized_checkpoint()
+        .epoch
+        .start_slot(T::EthSpec::slots_per_epoch());
+    if block.slot() <= finalized_slot {
+        return Err(format!(
+            "block is not later than the finalized slot {}",
+            finalized_slot
+        ));
+    }
+
+    let parent = {
+        let fork_choice = chain.canonical_head.fork_choice_read_lock();
+        if fork_choice.contains_block(&block_root) {
+            return Err("block is already known".to_string());
+        }
+        fork_choice
+            .get_block(&block.parent_root())
+            .ok_or_else(|| format!("parent {:?} is unknown", block.parent_root()))?
+    };
+    if parent.slot >= block.slot() {
+        return Err(format!(
+            "block is not later than its parent at slot {}",
+            parent.slot
+        ));
+    }
+
+    // Only the first block from each proposer in a slot is propagated.
+    let seen = chain
+        .observed_block_producers
+        .read()
+        .proposer_has_been_observed(block.message(), block_root)
+        .map_err(|e| format!("unable to check proposer: {:?}", e))?;
+    if seen.proposer_previously_observed() {
+        return Err("a block has already been seen from this proposer at this slot".to_string());
+    }
+
+    Ok(())
+}
+
+/// Apply `block` to its parent state, returning the post-state root.
+///
+/// All signatures of a signed block are verified, but only the RANDAO reveal of an unsigned
+/// block.
+fn process_block<T: BeaconChainTypes>(
+    block: &SignedBeaconBlock<T::EthSpec>,
+    signed: bool,
+    chain: &BeaconChain<T>,
+) -> Result<Hash256, String> {
+    let parent_block = chain
+        .get_blinded_block(&block.parent_root())
+        .map_err(|e| format!("unable to load parent block: {:?}", e))?
+        .ok_or_else(|| format!("parent {:?} is unavailable", block.parent_root()))?;
+    let parent_state_root = parent_block.state_root();
+    let mut state = chain
+        .get_state(&parent_state_root, Some(parent_block.slot()))
+        .map_err(|e| format!("unable to load parent state: {:?}", e))?
+        .ok_or_else(|| format!("parent state {:?} is unavailable", parent_state_root))?;
+
+    complete_state_advance(
+        &mut state,
+        Some(parent_state_root),
+        block.slot(),
+        &chain.spec,
+    )
+    .map_err(|e| format!("unable to advance parent state: {:?}", e))?;
+
+    let signature_strategy = if signed {
+        BlockSignatureStrategy::VerifyBulk
+    } else {
+        BlockSignatureStrategy::VerifyRandao
+    };
+    let mut ctxt = ConsensusContext::new(block.slot());
+    per_block_processing(
+        &mut state,
+        block,
+        signature_strategy,
+        VerifyBlockRoot::True,
+        &mut ctxt,
+        &chain.spec,
+    )
+    .map_err(|e| format!("{:?}", e))?;
+
+    state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("unable to compute state root: {:?}", e))
+}
@@ -0,0 +1,91 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{BlockSummariesQuery, BlockSummary};
+use std::sync::Arc;
+use types::{AbstractExecPayload, BeaconBlockRef, EthSpec, ExecPayload, Hash256};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
+
+/// The maximum number of epochs of blocks which may be summarised by a single request.
+const MAX_REQUEST_RANGE_EPOCHS: u64 = 4;
+
+/// Summarise the canonical blocks with slots in the range given by `query`.
+///
+/// Slots after the head are ignored. Ranges longer than `MAX_REQUEST_RANGE_EPOCHS` are rejected.
+pub fn get_block_summaries<T: BeaconChainTypes>(
+    query: BlockSummariesQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<BlockSummary>, warp::Rejection> {
+    let start_slot = query.start_slot;
+    if start_slot > query.end_slot {
+        return Err(custom_bad_request(format!(
+            "invalid start and end: {}, {}",
+            start_slot, query.end_slot
+        )));
+    }
+    let max_slots = MAX_REQUEST_RANGE_EPOCHS * T::EthSpec::slots_per_epoch();
+    if query.end_slot - start_slot >= max_slots {
+        return Err(custom_bad_request(format!(
+            "range of {} slots exceeds the maximum of {} slots",
+            query.end_slot - start_slot + 1,
+            max_slots
+        )));
+    }
+
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    let end_slot = std::cmp::min(query.end_slot, head_slot);
+    if start_slot > end_slot {
+        return Ok(vec![]);
+    }
+
+    let mut summaries = vec![];
+    let mut prev_block_root = None;
+    for result in chain
+        .forwards_iter_block_roots_until(start_slot, end_slot)
+        .map_err(beacon_chain_error)?
+    {
+        let (block_root, _) = result.map_err(beacon_chain_error)?;
+        // Skipped slots repeat the root of the previous block.
+        if prev_block_root == Some(block_root) {
+            continue;
+        }
+        prev_block_root = Some(block_root);
+
+        let block = chain
+            .get_blinded_block(&block_root)
+            .map_err(beacon_chain_error)?
+            .ok_or_else(|| {
+                custom_server_error(format!("canonical block {:?} unknown", block_root))
+            })?;
+        // If the start slot was skipped the first root belongs to an earlier block.
+        if block.slot() < start_slot {
+            continue;
+        }
+
+        summaries.push(summarise_block(block_root, block.message()));
+    }
+
+    Ok(summaries)
+}
+
+fn summarise_block<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    block_root: Hash256,
+    block: BeaconBlockRef<'_, E, Payload>,
+) -> BlockSummary {
+    let body = block.body();
+    BlockSummary {
+        slot: block.slot(),
+        block_root,
+        parent_root: block.parent_root(),
+        state_root: block.state_root(),
+        proposer_index: block.proposer_index(),
+        graffiti: *body.graffiti(),
+        execution_block_hash: body
+            .execution_payload()
+            .ok()
+            .map(|payload| payload.block_hash()),
+        attestation_count: body.attestations_len(),
+        deposit_count: body.deposits().len(),
+        voluntary_exit_count: body.voluntary_exits().len(),
+        proposer_slashing_count: body.proposer_slashings().len(),
+        attester_slashing_count: body.attester_slashings_len(),
+    }
+}
@@ -0,0 +1,51 @@
+use crate::BlockId;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use state_processing::transition_report::{transition_with_report, TransitionReport};
+use state_processing::ConsensusContext;
+use std::sync::Arc;
+use types::SignedBlindedBeaconBlock;
+use warp_utils::reject::{custom_bad_request, custom_not_found, custom_server_error};
+
+/// Re-run the state transition for the block identified by `block_id`, reporting the time taken
+/// by, and changes made by, each stage.
+///
+/// Signatures are not verified as the block has already been imported.
+pub fn get_block_transition_report<T: BeaconChainTypes>(
+    block_id: BlockId,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<TransitionReport, warp::Rejection> {
+    let (block, _, _) = block_id.blinded_block(&chain)?;
+    if block.slot() == chain.spec.genesis_slot {
+        return Err(custom_bad_request(
+            "cannot report on the genesis block".to_string(),
+        ));
+    }
+
+    let parent_block: SignedBlindedBeaconBlock<T::EthSpec> = chain
+        .get_blinded_block(&block.parent_root())
+        .and_then(|maybe_block| {
+            maybe_block.ok_or_else(|| BeaconChainError::MissingBeaconBlock(block.parent_root()))
+        })
+        .map_err(|e| custom_not_found(format!("Parent block is not available! {:?}", e)))?;
+
+    let mut state = chain
+        .get_state(&parent_block.state_root(), Some(parent_block.slot()))
+        .and_then(|maybe_state| {
+            maybe_state
+                .ok_or_else(|| BeaconChainError::MissingBeaconState(parent_block.state_root()))
+        })
+        .map_err(|e| custom_not_found(format!("Parent state is not available! {:?}", e)))?;
+
+    let mut ctxt = ConsensusContext::new(block.slot())
+        .set_current_block_root(block.canonical_root())
+        .set_proposer_index(block.message().proposer_index());
+
+    transition_with_report(
+        &mut state,
+        Some(parent_block.state_root()),
+        &block,
+        &mut ctxt,
+        &chain.spec,
+    )
+    .map_err(|e| custom_server_error(format!("state transition failed: {:?}", e)))
+}
@@ -1,7 +1,12 @@
 use beacon_chain::store::metadata::CURRENT_SCHEMA_VERSION;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::DatabaseInfo;
+use eth2::lighthouse::{DatabaseBackupRequest, DatabaseInfo};
+use std::fs::{self, OpenOptions};
+use std::io::BufWriter;
 use std::sync::Arc;
+use store::backup::BackupSummary;
+use store::retention::DiskUsageForecast;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
 
 pub fn info<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
@@ -11,6 +16,7 @@ pub fn info<T: BeaconChainTypes>(
     let config = store.get_config().clone();
     let anchor = store.get_anchor_info();
     let blob_info = store.get_blob_info();
+    let compaction = store.compaction_progress();
 
     Ok(DatabaseInfo {
         schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
@@ -18,5 +24,43 @@ pub fn info<T: BeaconChainTypes>(
         split,
         anchor,
         blob_info,
+        compaction,
+    })
+}
+
+pub fn forecast<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<DiskUsageForecast, warp::Rejection> {
+    Ok(chain.store.disk_usage_forecast())
+}
+
+/// Write a backup of the database to a new file at `request.path`.
+///
+/// Existing files are never overwritten, and the file is removed if the backup fails.
+pub fn backup<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    request: DatabaseBackupRequest,
+) -> Result<BackupSummary, warp::Rejection> {
+    let path = request.path;
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .map_err(|e| custom_bad_request(format!("unable to create {}: {e}", path.display())))?;
+
+    let mut writer = BufWriter::new(file);
+    let result = chain
+        .store
+        .backup(&mut writer)
+        .map_err(|e| format!("{e:?}"))
+        .and_then(|summary| {
+            let file = writer.into_inner().map_err(|e| e.to_string())?;
+            file.sync_all().map_err(|e| e.to_string())?;
+            Ok(summary)
+        });
+
+    result.map_err(|e| {
+        let _ = fs::remove_file(&path);
+        custom_server_error(format!("backup failed: {e}"))
     })
 }
@@ -0,0 +1,186 @@
+use crate::block_id::BlockId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{EpochExecutionRequests, ExecutionRequests};
+use eth2::types::ExecutionOptimisticFinalizedResponse;
+use std::sync::Arc;
+use types::{BeaconBlockBodyRef, Epoch, EthSpec, SignedBeaconBlock};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
+
+/// Returns the execution requests of the block identified by `block_id`.
+pub async fn get_block_execution_requests<T: BeaconChainTypes>(
+    block_id: BlockId,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ExecutionOptimisticFinalizedResponse<ExecutionRequests<T::EthSpec>>, warp::Rejection> {
+    let (block, execution_optimistic, finalized) = block_id.full_block(&chain).await?;
+    Ok(ExecutionOptimisticFinalizedResponse {
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+        data: execution_requests(&block),
+    })
+}
+
+/// Returns the totals of the execution requests of the canonical blocks in `epoch`.
+pub async fn get_epoch_execution_requests<T: BeaconChainTypes>(
+    epoch: Epoch,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<EpochExecutionRequests, warp::Rejection> {
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    let start_slot = epoch.start_slot(T::EthSpec::slots_per_epoch());
+    if start_slot > head_slot {
+        return Err(custom_bad_request(format!(
+            "epoch {} is after the head",
+            epoch
+        )));
+    }
+    let end_slot = std::cmp::min(epoch.end_slot(T::EthSpec::slots_per_epoch()), head_slot);
+
+    let mut block_roots = chain
+        .forwards_iter_block_roots_until(start_slot, end_slot)
+        .map_err(beacon_chain_error)?
+        .map(|result| result.map(|(block_root, _)| block_root))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(beacon_chain_error)?;
+    // Skipped slots repeat the root of the previous block.
+    block_roots.dedup();
+
+    let mut totals = EpochExecutionRequests {
+        epoch,
+        ..Default::default()
+    };
+    for block_root in block_roots {
+        let block = chain
+            .get_block(&block_root)
+            .await
+            .map_err(beacon_chain_error)?
+            .ok_or_else(|| {
+                custom_server_error(format!("canonical block {:?} unknown", block_root))
+            })?;
+        // If the start slot was skipped the first root belongs to an earlier block.
+        if block.slot() < start_slot {
+            continue;
+        }
+
+        add_block_requests(&mut totals, &execution_requests(&block));
+    }
+
+    Ok(totals)
+}
+
+/// Adds the `requests` of a single block to `totals`.
+fn add_block_requests<E: EthSpec>(
+    totals: &mut EpochExecutionRequests,
+    requests: &ExecutionRequests<E>,
+) {
+    totals.blocks += 1;
+    totals.deposits += requests.deposits.len() as u64;
+    totals.withdrawals += requests.withdrawals.len() as u64;
+    totals.consolidations += requests.consolidations.len() as u64;
+    for deposit in requests.deposits.iter() {
+        totals.deposit_amount = totals.deposit_amount.saturating_add(deposit.amount);
+    }
+    for withdrawal in requests.withdrawals.iter() {
+        if withdrawal.amount == 0 {
+            totals.full_exits += 1;
+        } else {
+            totals.withdrawal_amount = totals.withdrawal_amount.saturating_add(withdrawal.amount);
+        }
+    }
+}
+
+fn execution_requests<E: EthSpec>(block: &SignedBeaconBlock<E>) -> ExecutionRequests<E> {
+    match block.message().body() {
+        BeaconBlockBodyRef::Electra(body) => {
+            let payload = &body.execution_payload.execution_payload;
+            ExecutionRequests {
+                deposits: payload.deposit_requests.clone(),
+                withdrawals: payload.withdrawal_requests.clone(),
+                consolidations: body.consolidations.clone(),
+            }
+        }
+        _ => ExecutionRequests::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        BeaconBlock, BeaconBlockElectra, DepositRequest, ExecutionLayerWithdrawalRequest,
+        MainnetEthSpec, Signature,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn deposit(amount: u64) -> DepositRequest {
+        DepositRequest {
+            pubkey: Default::default(),
+            withdrawal_credentials: Default::default(),
+            amount,
+            signature: Signature::empty(),
+            index: 0,
+        }
+    }
+
+    fn withdrawal(amount: u64) -> ExecutionLayerWithdrawalRequest {
+        ExecutionLayerWithdrawalRequest {
+            source_address: Default::default(),
+            validator_pubkey: Default::default(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn requests_of_electra_block() {
+        let spec = E::default_spec();
+        let mut block = BeaconBlockElectra::<E>::empty(&spec);
+        let payload = &mut block.body.execution_payload.execution_payload;
+        payload.deposit_requests = vec![deposit(32)].into();
+        payload.withdrawal_requests = vec![withdrawal(0)].into();
+        let block = SignedBeaconBlock::from_block(BeaconBlock::Electra(block), Signature::empty());
+
+        let requests = execution_requests(&block);
+        assert_eq!(requests.deposits.to_vec(), vec![deposit(32)]);
+        assert_eq!(requests.withdrawals.to_vec(), vec![withdrawal(0)]);
+        assert!(requests.consolidations.is_empty());
+
+        // Earlier blocks have no requests.
+        let block =
+            SignedBeaconBlock::from_block(BeaconBlock::<E>::empty(&spec), Signature::empty());
+        assert_eq!(execution_requests(&block), ExecutionRequests::default());
+    }
+
+    #[test]
+    fn epoch_totals() {
+        let mut totals = EpochExecutionRequests::default();
+        add_block_requests(
+            &mut totals,
+            &ExecutionRequests::<E> {
+                deposits: vec![deposit(32), deposit(1)].into(),
+                withdrawals: vec![withdrawal(0), withdrawal(5)].into(),
+                consolidations: Default::default(),
+            },
+        );
+        add_block_requests(
+            &mut totals,
+            &ExecutionRequests::<E> {
+                withdrawals: vec![withdrawal(0), withdrawal(7)].into(),
+                ..Default::default()
+            },
+        );
+        add_block_requests(&mut totals, &ExecutionRequests::<E>::default());
+
+        assert_eq!(
+            totals,
+            EpochExecutionRequests {
+                epoch: Epoch::new(0),
+                blocks: 3,
+                deposits: 2,
+                deposit_amount: 33,
+                withdrawals: 4,
+                full_exits: 2,
+                withdrawal_amount: 12,
+                consolidations: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::version::{unsupported_version_rejection, V1, V2};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{
+    ForkChoiceDetail, ForkChoiceDetailNode, ForkChoiceHeadCandidate, ForkChoiceLatestMessage,
+    OptimisticBlock, PayloadReverification,
+};
+use eth2::types::{ChainHeadData, ChainHeadMetadata, EndpointVersion};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use types::{Epoch, EthSpec, ExecutionBlockHash, Hash256};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_not_found};
+
+/// Returns a detailed view of fork choice, including the latest messages voting for each of the
+/// viable head candidates.
+pub fn get_fork_choice_detail<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ForkChoiceDetail, warp::Rejection> {
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let head_root = chain.canonical_head.cached_head().head_block_root();
+
+    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+    let proto_array_fork_choice = fork_choice.proto_array();
+    let proto_array = proto_array_fork_choice.core_proto_array();
+
+    let root_at = |index: Option<usize>| {
+        index
+            .and_then(|index| proto_array.nodes.get(index))
+            .map(|node| node.root)
+    };
+
+    // A node is a leaf if no other node names it as a parent.
+    let mut has_children = vec![false; proto_array.nodes.len()];
+    for node in &proto_array.nodes {
+        if let Some(parent) = node.parent.and_then(|parent| has_children.get_mut(parent)) {
+            *parent = true;
+        }
+    }
+
+    let nodes = proto_array
+        .nodes
+        .iter()
+        .map(|node| ForkChoiceDetailNode {
+            slot: node.slot,
+            block_root: node.root,
+            parent_root: root_at(node.parent),
+            justified_checkpoint: node.justified_checkpoint,
+            finalized_checkpoint: node.finalized_checkpoint,
+            unrealized_justified_checkpoint: node.unrealized_justified_checkpoint,
+            unrealized_finalized_checkpoint: node.unrealized_finalized_checkpoint,
+            weight: node.weight,
+            validity: node.execution_status.to_string(),
+            execution_block_hash: node
+                .execution_status
+                .block_hash()
+                .map(|block_hash| block_hash.into_root()),
+            best_child: root_at(node.best_child),
+            best_descendant: root_at(node.best_descendant),
+            viable_for_head: proto_array.node_is_viable_for_head::<T::EthSpec>(node, current_slot),
+        })
+        .collect::<Vec<_>>();
+
+    let mut latest_messages: HashMap<Hash256, Vec<ForkChoiceLatestMessage>> = HashMap::new();
+    for (validator_index, block_root, epoch) in proto_array_fork_choice.iter_latest_messages() {
+        latest_messages
+            .entry(block_root)
+            .or_default()
+            .push(ForkChoiceLatestMessage {
+                validator_index: validator_index as u64,
+                epoch,
+            });
+    }
+
+    let head_candidates = nodes
+        .iter()
+        .zip(has_children)
+        .filter(|(node, has_children)| node.viable_for_head && !has_children)
+        .map(|(node, _)| ForkChoiceHeadCandidate {
+            block_root: node.block_root,
+            slot: node.slot,
+            weight: node.weight,
+            latest_messages: latest_messages.remove(&node.block_root).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(ForkChoiceDetail {
+        justified_checkpoint: fork_choice.justified_checkpoint(),
+        finalized_checkpoint: fork_choice.finalized_checkpoint(),
+        head_root,
+        proposer_boost_root: fork_choice.proposer_boost_root(),
+        nodes,
+        head_candidates,
+    })
+}
+
+/// Returns the blocks in fork choice whose execution payloads have not yet been verified by the
+/// execution engine, in order of slot.
+pub fn get_optimistic_blocks<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Vec<OptimisticBlock> {
+    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+    let mut blocks = fork_choice
+        .proto_array()
+        .core_proto_array()
+        .nodes
+        .iter()
+        .filter(|node| node.execution_status.is_strictly_optimistic())
+        .filter_map(|node| {
+            Some(OptimisticBlock {
+                block_root: node.root,
+                slot: node.slot,
+                execution_block_hash: node.execution_status.block_hash()?,
+            })
+        })
+        .collect::<Vec<_>>();
+    blocks.sort_by_key(|block| block.slot);
+    blocks
+}
+
+/// Sends the payload of the optimistic block with `execution_block_hash` to the execution engine
+/// again, returning its execution status afterwards.
+pub async fn reverify_optimistic_block<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    execution_block_hash: ExecutionBlockHash,
+) -> Result<PayloadReverification, warp::Rejection> {
+    let (block_root, execution_status) = chain
+        .reverify_optimistic_payload(execution_block_hash)
+        .await
+        .map_err(|e| match e {
+            BeaconChainError::UnknownExecutionBlockHash(block_hash) => custom_not_found(format!(
+                "no block in fork choice has execution block hash {block_hash:?}"
+            )),
+            BeaconChainError::PayloadNotOptimistic {
+                block_root,
+                execution_status,
+            } => custom_bad_request(format!(
+                "block {block_root:?} is {execution_status}, not optimistic"
+            )),
+            e => beacon_chain_error(e),
+        })?;
+    Ok(PayloadReverification {
+        block_root,
+        execution_block_hash,
+        validity: execution_status.to_string(),
+    })
+}
+
+/// Returns the heads of the chain. For v2 requests, each head known to fork choice includes
+/// metadata describing its weight, execution status and the votes for it.
+pub fn get_chain_heads<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    endpoint_version: EndpointVersion,
+) -> Result<Vec<ChainHeadData>, warp::Rejection> {
+    let heads = chain.heads();
+    if endpoint_version == V1 {
+        return Ok(heads
+            .into_iter()
+            .map(|(root, slot)| ChainHeadData {
+                slot,
+                root,
+                execution_optimistic: None,
+                metadata: None,
+            })
+            .collect());
+    } else if endpoint_version != V2 {
+        return Err(unsupported_version_rejection(endpoint_version));
+    }
+
+    let head_roots = heads.iter().map(|(root, _)| *root).collect::<HashSet<_>>();
+    let mut votes: HashMap<Hash256, Vec<(u64, Epoch)>> = HashMap::new();
+
+    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+    let finalized_slot = fork_choice
+        .finalized_checkpoint()
+        .epoch
+        .start_slot(T::EthSpec::slots_per_epoch());
+    for (validator_index, block_root, epoch) in fork_choice.proto_array().iter_latest_messages() {
+        if head_roots.contains(&block_root) {
+            votes
+                .entry(block_root)
+                .or_default()
+                .push((validator_index as u64, epoch));
+        }
+    }
+    let heads = heads
+        .into_iter()
+        .map(|(root, slot)| {
+            let execution_optimistic = fork_choice.is_optimistic_or_invalid_block(&root).ok();
+            let weight = fork_choice.proto_array().get_weight(&root);
+            let execution_status = fork_choice.get_block_execution_status(&root);
+            (root, slot, execution_optimistic, weight, execution_status)
+        })
+        .collect::<Vec<_>>();
+    drop(fork_choice);
+
+    let validator_monitor = chain.validator_monitor.read();
+    Ok(heads
+        .into_iter()
+        .map(
+            |(root, slot, execution_optimistic, weight, execution_status)| {
+                let head_votes = votes.remove(&root).unwrap_or_default();
+                let metadata = weight
+                    .zip(execution_status)
+                    .map(|(weight, execution_status)| ChainHeadMetadata {
+                        weight,
+                        last_vote_epoch: head_votes.iter().map(|(_, epoch)| *epoch).max(),
+                        execution_status: execution_status.to_string(),
+                        finalized_distance: slot.saturating_sub(finalized_slot).as_u64(),
+                        monitored_validator_voted: head_votes.iter().any(|(validator_index, _)| {
+                            validator_monitor
+                                .get_monitored_validator(*validator_index)
+                                .is_some()
+                        }),
+                    });
+                ChainHeadData {
+                    slot,
+                    root,
+                    execution_optimistic,
+                    metadata,
+                }
+            },
+        )
+        .collect())
+}
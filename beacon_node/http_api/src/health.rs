@@ -0,0 +1,85 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{DegradationReason, Readiness, ReadinessStatus};
+use lighthouse_network::NetworkGlobals;
+use parking_lot::RwLock;
+use slot_clock::SlotClock;
+use std::path::Path;
+use std::sync::Arc;
+use sysinfo::System;
+use system_health::{clock::is_safe_gossip_clock_offset, observe_disk_space};
+
+/// The node is degraded when it has less than this much free disk space.
+pub const MIN_FREE_DISK_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Determines whether the node is ready to serve validators.
+///
+/// `chain` is `None` whilst waiting for genesis, in which case the node is never ready.
+pub fn observe_readiness<T: BeaconChainTypes>(
+    chain: Option<&BeaconChain<T>>,
+    network_globals: Option<&NetworkGlobals<T::EthSpec>>,
+    el_offline: bool,
+    sysinfo: Arc<RwLock<System>>,
+    data_dir: &Path,
+    target_peers: usize,
+) -> Result<Readiness, warp::Rejection> {
+    let mut reasons = vec![];
+
+    let (sync_distance, is_optimistic, clock_offset_ms) = if let Some(chain) = chain {
+        let head_slot = chain.canonical_head.cached_head().head_slot();
+        let current_slot = chain.slot_clock.now_or_genesis().ok_or_else(|| {
+            warp_utils::reject::custom_server_error("Unable to read slot clock".into())
+        })?;
+        let is_optimistic = chain
+            .is_optimistic_or_invalid_head()
+            .map_err(warp_utils::reject::beacon_chain_error)?;
+
+        // Taking advantage of saturating subtraction on slot.
+        (
+            Some(current_slot - head_slot),
+            is_optimistic,
+            chain.estimate_clock_offset_ms(),
+        )
+    } else {
+        reasons.push(DegradationReason::ChainUninitialized);
+        (None, false, None)
+    };
+
+    let is_syncing = network_globals.map_or(true, |globals| !globals.sync_state().is_synced());
+    if chain.is_some() && is_syncing {
+        reasons.push(DegradationReason::Syncing);
+    }
+    if el_offline {
+        reasons.push(DegradationReason::ExecutionLayerOffline);
+    }
+    if is_optimistic {
+        reasons.push(DegradationReason::OptimisticHead);
+    }
+
+    let connected_peers = network_globals.map_or(0, |globals| globals.connected_peers());
+    if connected_peers * 2 < target_peers {
+        reasons.push(DegradationReason::LowPeerCount);
+    }
+
+    let (_, disk_bytes_free) = observe_disk_space(&sysinfo.read(), data_dir);
+    if disk_bytes_free < MIN_FREE_DISK_BYTES {
+        reasons.push(DegradationReason::LowDiskSpace);
+    }
+
+    if let (Some(chain), Some(offset)) = (chain, clock_offset_ms) {
+        if !is_safe_gossip_clock_offset(offset, chain.slot_clock.slot_duration()) {
+            reasons.push(DegradationReason::ClockDrift);
+        }
+    }
+
+    Ok(Readiness {
+        status: ReadinessStatus::from_reasons(&reasons),
+        reasons,
+        sync_distance,
+        el_offline,
+        is_optimistic,
+        connected_peers,
+        target_peers,
+        disk_bytes_free,
+        clock_offset_ms,
+    })
+}
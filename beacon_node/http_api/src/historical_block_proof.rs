@@ -0,0 +1,62 @@
+use crate::{BlockId, StateId};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use std::sync::Arc;
+use types::{EthSpec, Hash256, HistoricalBlockProof, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
+
+/// Proves that the block identified by `block_id` is committed to by the `historical_roots` or
+/// `historical_summaries` of the state identified by `state_id`.
+///
+/// The block must be canonical, and the state must be from a later era than the block.
+pub fn get_historical_block_proof<T: BeaconChainTypes>(
+    state_id: StateId,
+    block_id: BlockId,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<HistoricalBlockProof, warp::Rejection> {
+    let (block, _, _) = block_id.blinded_block(&chain)?;
+    let block_root = block.canonical_root();
+    let slot = block.slot();
+    let (state, _, _) = state_id.state(&chain)?;
+
+    let slots_per_era = T::EthSpec::slots_per_historical_root() as u64;
+    let start_slot = Slot::new(slot.as_u64() / slots_per_era * slots_per_era);
+    let end_slot = start_slot + slots_per_era;
+    if state.slot() < end_slot {
+        return Err(custom_bad_request(format!(
+            "the state at slot {} has no historical root for slot {}, use a state at slot {} \
+             or later",
+            state.slot(),
+            slot,
+            end_slot
+        )));
+    }
+
+    let block_roots =
+        collect_roots(chain.forwards_iter_block_roots_until(start_slot, end_slot - 1))?;
+    if block_roots.get((slot - start_slot).as_usize()) != Some(&block_root) {
+        return Err(custom_bad_request(format!(
+            "block {:?} is not canonical",
+            block_root
+        )));
+    }
+    let state_roots = if HistoricalBlockProof::requires_state_roots(&state, slot) {
+        Some(collect_roots(
+            chain.forwards_iter_state_roots_until(start_slot, end_slot - 1),
+        )?)
+    } else {
+        None
+    };
+
+    HistoricalBlockProof::new(&state, slot, &block_roots, state_roots.as_deref())
+        .map_err(|e| custom_server_error(format!("unable to compute proof: {:?}", e)))
+}
+
+fn collect_roots(
+    iter: Result<impl Iterator<Item = Result<(Hash256, Slot), BeaconChainError>>, BeaconChainError>,
+) -> Result<Vec<Hash256>, warp::Rejection> {
+    iter.and_then(|iter| {
+        iter.map(|result| result.map(|(root, _)| root))
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .map_err(beacon_chain_error)
+}
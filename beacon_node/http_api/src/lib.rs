@@ -5,21 +5,34 @@
 //! There are also some additional, non-standard endpoints behind the `/lighthouse/` path which are
 //! used for development.
 
+mod attestation_inclusion;
 mod attestation_performance;
 mod attester_duties;
+mod auth;
+mod block_dry_run;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
+mod block_summaries;
+mod block_transition;
 mod build_block_contents;
 mod builder_states;
 mod database;
+mod execution_requests;
+mod fork_choice;
+mod health;
+mod historical_block_proof;
+mod limits;
+mod log_levels;
 mod metrics;
 mod produce_block;
 mod proposer_duties;
+mod proposer_re_org;
 mod publish_attestations;
 mod publish_blocks;
 mod standard_block_rewards;
 mod state_id;
+mod state_proof;
 mod sync_committee_rewards;
 mod sync_committees;
 mod task_spawner;
@@ -27,72 +40,89 @@ pub mod test_utils;
 mod ui;
 mod validator;
 mod validator_inclusion;
+mod validator_queues;
 mod validators;
 mod version;
 
+pub use crate::auth::{ApiRole, ApiTokens};
+pub use crate::block_packing_efficiency::get_block_packing_efficiency;
+pub use crate::block_rewards::get_block_rewards;
+pub use crate::limits::{parse_route_class_values, RequestLimits, RouteClass};
 use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_block_v3};
 use crate::version::fork_versioned_response;
 use beacon_chain::{
-    attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
-    validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, WhenSlotSkipped,
+    attestation_verification::VerifiedAttestation,
+    events::{is_replayable_topic, ReplayableEvent},
+    observed_operations::ObservationOutcome,
+    validator_monitor::timestamp_now,
+    AttestationError as AttnError, BeaconChain, BeaconChainError, BeaconChainTypes,
+    WhenSlotSkipped,
 };
 use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 pub use block_id::BlockId;
 use builder_states::get_next_withdrawals;
 use bytes::Bytes;
 use directory::DEFAULT_ROOT_DIR;
+use eth2::types::EventKind;
 use eth2::types::{
     self as api_types, BroadcastValidation, EndpointVersion, ForkChoice, ForkChoiceNode,
     PublishBlockRequest, ValidatorBalancesRequestBody, ValidatorId, ValidatorStatus,
     ValidatorsRequestBody,
 };
-use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
+use eth2::{
+    BeaconNodeHttpClient, Timeouts, CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER,
+    SSZ_CONTENT_TYPE_HEADER,
+};
+use lighthouse_network::discovery::validate_custom_enr_field;
 use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
 use lighthouse_version::version_with_platform;
-use logging::SSELoggingComponents;
+use logging::{RuntimeLogLevels, SSELoggingComponents};
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
 use operation_pool::ReceivedPreCapella;
 use parking_lot::RwLock;
 pub use publish_blocks::{
     publish_blinded_block, publish_block, reconstruct_block, ProvenancedBlock,
 };
+use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
 pub use state_id::StateId;
+use std::convert::Infallible;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use sysinfo::{System, SystemExt};
 use system_health::{observe_nat, observe_system_health_bn};
 use task_spawner::{Priority, TaskSpawner};
 use tokio::sync::{
+    broadcast,
     mpsc::{Sender, UnboundedSender},
     oneshot,
 };
 use tokio_stream::{
     wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
-    StreamExt,
+    Stream, StreamExt,
 };
 use types::{
     fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
-    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec,
+    ExecutionBlockHash, ForkName, ForkVersionedResponse, Hash256, ProposerPreparationData,
+    ProposerSlashing, RelativeEpoch, SignedAggregateAndProof, SignedBlindedBeaconBlock,
+    SignedBlsToExecutionChange, SignedContributionAndProof, SignedValidatorRegistrationData,
+    SignedVoluntaryExit, Slot, SyncCommitteeMessage, SyncContributionData,
 };
 use validator::pubkey_to_validator_index;
 use version::{
     add_consensus_version_header, add_ssz_content_type_header,
     execution_optimistic_finalized_fork_versioned_response, inconsistent_fork_rejection,
-    unsupported_version_rejection, V1, V2, V3,
+    ssz_response, unsupported_version_rejection, V1, V2, V3,
 };
-use warp::http::StatusCode;
+use warp::http::{HeaderMap, StatusCode};
 use warp::hyper::Body;
 use warp::sse::Event;
 use warp::Reply;
@@ -108,6 +138,10 @@ const API_PREFIX: &str = "eth";
 /// finalized head.
 const SYNC_TOLERANCE_EPOCHS: u64 = 8;
 
+/// The fraction of a slot allowed for fetching a block from the `block_production_endpoint`,
+/// leaving time to produce a block locally if the request fails.
+const BLOCK_PRODUCTION_ENDPOINT_TIMEOUT_QUOTIENT: u32 = 6;
+
 /// A custom type which allows for both unsecured and TLS-enabled HTTP servers.
 type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
 
@@ -133,6 +167,7 @@ pub struct Context<T: BeaconChainTypes> {
     pub beacon_processor_reprocess_send: Option<Sender<ReprocessQueueMessage>>,
     pub eth1_service: Option<eth1::Service>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub runtime_log_levels: Option<RuntimeLogLevels>,
     pub log: Logger,
 }
 
@@ -151,6 +186,19 @@ pub struct Config {
     #[serde(with = "eth2::types::serde_status_code")]
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
+    /// A file of API tokens and their roles. If set, all requests must supply a token.
+    pub api_token_file: Option<PathBuf>,
+    /// Compress responses for clients which accept gzip or zstd encoding.
+    pub enable_compression: bool,
+    /// Timeouts, body size limits and concurrency caps for each class of endpoints.
+    pub request_limits: RequestLimits,
+    /// The target number of peers, against which `GET lighthouse/health` checks the peer count.
+    pub target_peers: usize,
+    /// A beacon node which blocks are fetched from, rather than being produced locally.
+    pub block_production_endpoint: Option<SensitiveUrl>,
+    /// A path which all routes are served beneath, e.g. `holesky` to serve
+    /// `/holesky/eth/v1/node/version`.
+    pub path_prefix: Option<String>,
 }
 
 impl Default for Config {
@@ -167,6 +215,12 @@ impl Default for Config {
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
+            api_token_file: None,
+            enable_compression: true,
+            request_limits: RequestLimits::default(),
+            target_peers: 100,
+            block_production_endpoint: None,
+            path_prefix: None,
         }
     }
 }
@@ -324,6 +378,16 @@ pub fn serve<T: BeaconChainTypes>(
     let config = ctx.config.clone();
     let log = ctx.log.clone();
 
+    let api_tokens = config
+        .api_token_file
+        .as_deref()
+        .map(ApiTokens::from_file)
+        .transpose()?
+        .map(Arc::new);
+    let authorization_filter = auth::authorization_filter(api_tokens.clone());
+    let request_limits_filter = limits::request_limits_filter(&config.request_limits);
+    let body_limits = limits::BodyLimits::new(&config.request_limits);
+
     // Configure CORS.
     let cors_builder = {
         let builder = warp::cors()
@@ -487,6 +551,48 @@ pub fn serve<T: BeaconChainTypes>(
                 },
             );
 
+    // Create a `warp` filter that rejects requests to produce blocks and attestations whilst the
+    // node is syncing or, in strict optimistic sync mode, whilst the head is optimistic or the
+    // execution layer is offline.
+    let not_while_optimistic_filter = not_while_syncing_filter
+        .clone()
+        .and(chain_filter.clone())
+        .then(
+            |not_synced: Result<(), Rejection>, chain: Arc<BeaconChain<T>>| async move {
+                not_synced?;
+                if !chain.config.strict_optimistic_sync {
+                    return Ok::<_, Rejection>(());
+                }
+
+                let el_offline = if let Some(el) = &chain.execution_layer {
+                    el.is_offline_or_erroring().await
+                } else {
+                    true
+                };
+                let is_optimistic = chain
+                    .is_optimistic_or_invalid_head()
+                    .map_err(warp_utils::reject::beacon_chain_error)?;
+                if !el_offline && !is_optimistic {
+                    return Ok(());
+                }
+
+                let head_slot = chain.canonical_head.cached_head().head_slot();
+                let current_slot = chain.slot_clock.now_or_genesis().ok_or_else(|| {
+                    warp_utils::reject::custom_server_error("unable to read slot clock".to_string())
+                })?;
+                let reason = if el_offline {
+                    "EL_OFFLINE"
+                } else {
+                    "OPTIMISTIC"
+                };
+                Err(warp_utils::reject::not_synced(format!(
+                    "{reason}, is_optimistic: {is_optimistic}, el_offline: {el_offline}, \
+                     head_slot: {head_slot}, sync_distance: {}",
+                    current_slot - head_slot
+                )))
+            },
+        );
+
     // Create a `warp` filter that provides access to the logger.
     let inner_ctx = ctx.clone();
     let log_filter = warp::any().map(move || inner_ctx.log.clone());
@@ -494,6 +600,20 @@ pub fn serve<T: BeaconChainTypes>(
     let inner_components = ctx.sse_logging_components.clone();
     let sse_component_filter = warp::any().map(move || inner_components.clone());
 
+    // Create a `warp` filter that provides access to the runtime log levels.
+    let inner_log_levels = ctx.runtime_log_levels.clone();
+    let log_levels_filter =
+        warp::any()
+            .map(move || inner_log_levels.clone())
+            .and_then(|log_levels| async move {
+                match log_levels {
+                    Some(log_levels) => Ok(log_levels),
+                    None => Err(warp_utils::reject::custom_not_found(
+                        "Log levels are not configurable.".to_string(),
+                    )),
+                }
+            });
+
     // Create a `warp` filter that provides access to local system information.
     let system_info = Arc::new(RwLock::new(sysinfo::System::new()));
     {
@@ -530,8 +650,13 @@ pub fn serve<T: BeaconChainTypes>(
         .beacon_processor_send
         .clone()
         .filter(|_| config.enable_beacon_processor);
-    let task_spawner_filter =
-        warp::any().map(move || TaskSpawner::new(beacon_processor_send.clone()));
+    let request_limits = Arc::new(config.request_limits.clone());
+    let task_spawner_filter = warp::method().and(warp::path::full()).map(
+        move |method: warp::http::Method, path: warp::filters::path::FullPath| {
+            let timeout = request_limits.timeout(RouteClass::of(&method, path.as_str()));
+            TaskSpawner::new(beacon_processor_send.clone()).with_timeout(timeout)
+        },
+    );
     let beacon_processor_reprocess_send = ctx
         .beacon_processor_reprocess_send
         .clone()
@@ -540,6 +665,19 @@ pub fn serve<T: BeaconChainTypes>(
 
     let duplicate_block_status_code = ctx.config.duplicate_block_status_code;
 
+    // Create a client for the beacon node which block production is delegated to, if any.
+    let block_production_client = ctx
+        .config
+        .block_production_endpoint
+        .clone()
+        .zip(ctx.chain.as_ref())
+        .map(|(url, chain)| {
+            let timeout = Duration::from_secs(chain.spec.seconds_per_slot)
+                / BLOCK_PRODUCTION_ENDPOINT_TIMEOUT_QUOTIENT;
+            BeaconNodeHttpClient::new(url, Timeouts::set_all(timeout))
+        });
+    let block_production_client_filter = warp::any().map(move || block_production_client.clone());
+
     /*
      *
      * Start of HTTP method definitions.
@@ -586,18 +724,25 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("root"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (root, execution_optimistic, finalized) = state_id.root(&chain)?;
-                    Ok(api_types::GenericResponse::from(api_types::RootData::from(
-                        root,
-                    )))
-                    .map(|resp| {
-                        resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                    })
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&root)),
+                        _ => Ok(warp::reply::json(
+                            &api_types::GenericResponse::from(api_types::RootData::from(root))
+                                .add_execution_optimistic_finalized(
+                                    execution_optimistic,
+                                    finalized,
+                                ),
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -607,18 +752,26 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("fork"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (fork, execution_optimistic, finalized) =
                         state_id.fork_and_execution_optimistic_and_finalized(&chain)?;
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data: fork,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&fork)),
+                        _ => Ok(warp::reply::json(
+                            &api_types::ExecutionOptimisticFinalizedResponse {
+                                data: fork,
+                                execution_optimistic: Some(execution_optimistic),
+                                finalized: Some(finalized),
+                            },
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -628,11 +781,13 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("finality_checkpoints"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (data, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -649,11 +804,17 @@ pub fn serve<T: BeaconChainTypes>(
                             },
                         )?;
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&data)),
+                        _ => Ok(warp::reply::json(
+                            &api_types::ExecutionOptimisticFinalizedResponse {
+                                data,
+                                execution_optimistic: Some(execution_optimistic),
+                                finalized: Some(finalized),
+                            },
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -685,7 +846,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("validator_balances"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
@@ -701,7 +862,7 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET beacon/states/{state_id}/validators?id,status
+    // GET beacon/states/{state_id}/validators?id,status,start_index,end_index
     let get_beacon_state_validators = beacon_states_path
         .clone()
         .and(warp::path("validators"))
@@ -712,13 +873,15 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query_res: Result<api_types::ValidatorsQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let query = query_res?;
                     crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
-                        &query.id,
-                        &query.status,
+                        query.id,
+                        query.status,
+                        query.start_index,
+                        query.end_index,
                     )
                 })
             },
@@ -729,18 +892,20 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("validators"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query: ValidatorsRequestBody| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
-                        &query.ids,
-                        &query.statuses,
+                        query.ids,
+                        query.statuses,
+                        query.start_index,
+                        query.end_index,
                     )
                 })
             },
@@ -1070,12 +1235,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("randao"))
         .and(warp::query::<api_types::RandaoQuery>())
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: api_types::RandaoQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query: api_types::RandaoQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (randao, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -1090,10 +1257,17 @@ pub fn serve<T: BeaconChainTypes>(
                             },
                         )?;
 
-                    Ok(
-                        api_types::GenericResponse::from(api_types::RandaoMix { randao })
-                            .add_execution_optimistic_finalized(execution_optimistic, finalized),
-                    )
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&randao)),
+                        _ => Ok(warp::reply::json(
+                            &api_types::GenericResponse::from(api_types::RandaoMix { randao })
+                                .add_execution_optimistic_finalized(
+                                    execution_optimistic,
+                                    finalized,
+                                ),
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -1257,7 +1431,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("beacon"))
         .and(warp::path("blocks"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1287,7 +1461,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("beacon"))
         .and(warp::path("blocks"))
         .and(warp::path::end())
-        .and(warp::body::bytes())
+        .and(body_limits.bytes())
         .and(consensus_version_header_filter)
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
@@ -1327,7 +1501,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blocks"))
         .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1359,7 +1533,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blocks"))
         .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
-        .and(warp::body::bytes())
+        .and(body_limits.bytes())
         .and(consensus_version_header_filter)
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
@@ -1404,7 +1578,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("beacon"))
         .and(warp::path("blinded_blocks"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1434,7 +1608,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("beacon"))
         .and(warp::path("blinded_blocks"))
         .and(warp::path::end())
-        .and(warp::body::bytes())
+        .and(body_limits.bytes())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1472,7 +1646,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blinded_blocks"))
         .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1503,7 +1677,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blinded_blocks"))
         .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
-        .and(warp::body::bytes())
+        .and(body_limits.bytes())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1605,10 +1779,12 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("root"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
                 // Prioritise requests for the head block root, as it is used by some VCs (including
                 // the Lighthouse VC) to create sync committee messages.
                 let priority = if let BlockId(eth2::types::BlockId::Head) = block_id {
@@ -1616,12 +1792,18 @@ pub fn serve<T: BeaconChainTypes>(
                 } else {
                     Priority::P1
                 };
-                task_spawner.blocking_json_task(priority, move || {
+                task_spawner.blocking_response_task(priority, move || {
                     let (block_root, execution_optimistic, finalized) = block_id.root(&chain)?;
-                    Ok(
-                        api_types::GenericResponse::from(api_types::RootData::from(block_root))
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&block_root)),
+                        _ => Ok(warp::reply::json(
+                            &api_types::GenericResponse::from(api_types::RootData::from(
+                                block_root,
+                            ))
                             .add_execution_optimistic_finalized(execution_optimistic, finalized),
-                    )
+                        )
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -1631,11 +1813,13 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("attestations"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |endpoint_version: EndpointVersion,
              block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
                 task_spawner.blocking_response_task(Priority::P1, move || {
                     let (block, execution_optimistic, finalized) =
                         block_id.blinded_block(&chain)?;
@@ -1648,17 +1832,20 @@ pub fn serve<T: BeaconChainTypes>(
                         .attestations()
                         .map(|att| att.clone_as_attestation())
                         .collect::<Vec<_>>();
-                    let res = execution_optimistic_finalized_fork_versioned_response(
-                        endpoint_version,
-                        fork_name,
-                        execution_optimistic,
-                        finalized,
-                        &atts,
-                    )?;
-                    Ok(add_consensus_version_header(
-                        warp::reply::json(&res).into_response(),
-                        fork_name,
-                    ))
+                    let resp = match accept_header {
+                        Some(api_types::Accept::Ssz) => ssz_response(&atts),
+                        _ => {
+                            let res = execution_optimistic_finalized_fork_versioned_response(
+                                endpoint_version,
+                                fork_name,
+                                execution_optimistic,
+                                finalized,
+                                &atts,
+                            )?;
+                            warp::reply::json(&res).into_response()
+                        }
+                    };
+                    Ok(add_consensus_version_header(resp, fork_name))
                 })
             },
         );
@@ -1777,7 +1964,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("attestations"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .and(reprocess_send_filter)
         .and(log_filter.clone())
@@ -1868,7 +2055,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("attester_slashings"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .then(
             // V1 and V2 are identical except V2 has a consensus version header in the request.
@@ -1957,7 +2144,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("proposer_slashings"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
@@ -2015,7 +2202,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("voluntary_exits"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
@@ -2071,7 +2258,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("sync_committees"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
@@ -2108,7 +2295,7 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("bls_to_execution_changes"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
@@ -2500,7 +2687,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("attestations"))
         .and(warp::path::param::<Epoch>())
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
@@ -2520,6 +2707,12 @@ pub fn serve<T: BeaconChainTypes>(
                                     "missing state at slot {slot}"
                                 ))
                             }
+                            BeaconChainError::TooManyHistoricalStateReplays => {
+                                warp_utils::reject::too_many_requests(
+                                    "too many historical states are being replayed, retry later"
+                                        .to_string(),
+                                )
+                            }
                             BeaconChainError::BeaconStateError(
                                 BeaconStateError::UnknownValidator(validator_index),
                             ) => warp_utils::reject::custom_bad_request(format!(
@@ -2550,7 +2743,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("sync_committee"))
         .and(block_id_or_err)
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(log_filter.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
@@ -2678,25 +2871,24 @@ pub fn serve<T: BeaconChainTypes>(
                                 ))
                             })
                     }
-                    _ => state_id.map_state_and_execution_optimistic_and_finalized(
-                        &chain,
-                        |state, execution_optimistic, finalized| {
-                            let fork_name = state
-                                .fork_name(&chain.spec)
-                                .map_err(inconsistent_fork_rejection)?;
-                            let res = execution_optimistic_finalized_fork_versioned_response(
-                                endpoint_version,
-                                fork_name,
-                                execution_optimistic,
-                                finalized,
-                                &state,
-                            )?;
-                            Ok(add_consensus_version_header(
-                                warp::reply::json(&res).into_response(),
-                                fork_name,
-                            ))
-                        },
-                    ),
+                    _ => {
+                        // States are too large to buffer as JSON, so stream the response.
+                        let (state, execution_optimistic, finalized) = state_id.state(&chain)?;
+                        let fork_name = state
+                            .fork_name(&chain.spec)
+                            .map_err(inconsistent_fork_rejection)?;
+                        let res = execution_optimistic_finalized_fork_versioned_response(
+                            endpoint_version,
+                            fork_name,
+                            execution_optimistic,
+                            finalized,
+                            state,
+                        )?;
+                        Ok(add_consensus_version_header(
+                            warp_utils::reply::json_stream(res),
+                            fork_name,
+                        ))
+                    }
                 })
             },
         );
@@ -2714,29 +2906,8 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
-                    let heads = chain
-                        .heads()
-                        .into_iter()
-                        .map(|(root, slot)| {
-                            let execution_optimistic = if endpoint_version == V1 {
-                                None
-                            } else if endpoint_version == V2 {
-                                chain
-                                    .canonical_head
-                                    .fork_choice_read_lock()
-                                    .is_optimistic_or_invalid_block(&root)
-                                    .ok()
-                            } else {
-                                return Err(unsupported_version_rejection(endpoint_version));
-                            };
-                            Ok(api_types::ChainHeadData {
-                                slot,
-                                root,
-                                execution_optimistic,
-                            })
-                        })
-                        .collect::<Result<Vec<_>, warp::Rejection>>();
-                    Ok(api_types::GenericResponse::from(heads?))
+                    fork_choice::get_chain_heads(chain, endpoint_version)
+                        .map(api_types::GenericResponse::from)
                 })
             },
         );
@@ -3156,10 +3327,11 @@ pub fn serve<T: BeaconChainTypes>(
         }))
         .and(warp::path::end())
         .and(warp::header::optional::<api_types::Accept>("accept"))
-        .and(not_while_syncing_filter.clone())
+        .and(not_while_optimistic_filter.clone())
         .and(warp::query::<api_types::ValidatorBlocksQuery>())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(block_production_client_filter)
         .and(log_filter.clone())
         .then(
             |endpoint_version: EndpointVersion,
@@ -3169,6 +3341,7 @@ pub fn serve<T: BeaconChainTypes>(
              query: api_types::ValidatorBlocksQuery,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
+             block_production_client: Option<BeaconNodeHttpClient>,
              log: Logger| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     debug!(
@@ -3180,7 +3353,15 @@ pub fn serve<T: BeaconChainTypes>(
                     not_synced_filter?;
 
                     if endpoint_version == V3 {
-                        produce_block_v3(accept_header, chain, slot, query).await
+                        produce_block_v3(
+                            accept_header,
+                            chain,
+                            slot,
+                            query,
+                            block_production_client,
+                            log,
+                        )
+                        .await
                     } else {
                         produce_block_v2(endpoint_version, accept_header, chain, slot, query).await
                     }
@@ -3198,7 +3379,7 @@ pub fn serve<T: BeaconChainTypes>(
             ))
         }))
         .and(warp::path::end())
-        .and(not_while_syncing_filter.clone())
+        .and(not_while_optimistic_filter.clone())
         .and(warp::query::<api_types::ValidatorBlocksQuery>())
         .and(warp::header::optional::<api_types::Accept>("accept"))
         .and(task_spawner_filter.clone())
@@ -3224,7 +3405,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("attestation_data"))
         .and(warp::path::end())
         .and(warp::query::<api_types::ValidatorAttestationDataQuery>())
-        .and(not_while_syncing_filter.clone())
+        .and(not_while_optimistic_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3262,7 +3443,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("aggregate_attestation"))
         .and(warp::path::end())
         .and(warp::query::<api_types::ValidatorAggregateAttestationQuery>())
-        .and(not_while_syncing_filter.clone())
+        .and(not_while_optimistic_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3321,7 +3502,7 @@ pub fn serve<T: BeaconChainTypes>(
         }))
         .and(warp::path::end())
         .and(not_while_syncing_filter.clone())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3349,7 +3530,7 @@ pub fn serve<T: BeaconChainTypes>(
         }))
         .and(warp::path::end())
         .and(not_while_syncing_filter.clone())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3371,7 +3552,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("sync_committee_contribution"))
         .and(warp::path::end())
         .and(warp::query::<SyncContributionData>())
-        .and(not_while_syncing_filter.clone())
+        .and(not_while_optimistic_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3407,7 +3588,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(not_while_syncing_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
@@ -3526,8 +3707,8 @@ pub fn serve<T: BeaconChainTypes>(
         .and(not_while_syncing_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
-        .and(warp_utils::json::json())
-        .and(network_tx_filter)
+        .and(body_limits.json())
+        .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
             |not_synced_filter: Result<(), Rejection>,
@@ -3554,7 +3735,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validator"))
         .and(warp::path("beacon_committee_subscriptions"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(validator_subscription_tx_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
@@ -3610,7 +3791,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(log_filter.clone())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .then(
             |not_synced_filter: Result<(), Rejection>,
              task_spawner: TaskSpawner<T::EthSpec>,
@@ -3663,7 +3844,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(log_filter.clone())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
@@ -3756,10 +3937,21 @@ pub fn serve<T: BeaconChainTypes>(
                                 ))
                             })?;
 
+                        // Only forward the registrations which the builder has not already
+                        // accepted.
+                        let unsent_registration_data = execution_layer
+                            .unsent_validator_registrations(
+                                &filtered_registration_data,
+                                current_epoch,
+                            );
+
                         info!(
                             log,
                             "Forwarding register validator request to connected builder";
-                            "count" => filtered_registration_data.len(),
+                            "count" => unsent_registration_data.len(),
+                            "unchanged" => filtered_registration_data
+                                .len()
+                                .saturating_sub(unsent_registration_data.len()),
                         );
 
                         // It's a waste of a `BeaconProcessor` worker to just
@@ -3768,25 +3960,34 @@ pub fn serve<T: BeaconChainTypes>(
                         // send the response back to our original HTTP request
                         // task via a channel.
                         let builder_future = async move {
-                            let arc_builder = chain
+                            let execution_layer = chain
                                 .execution_layer
                                 .as_ref()
                                 .ok_or(BeaconChainError::ExecutionLayerMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?
-                                .builder();
+                                .map_err(warp_utils::reject::beacon_chain_error)?;
+                            let arc_builder = execution_layer.builder();
                             let builder = arc_builder
                                 .as_ref()
                                 .ok_or(BeaconChainError::BuilderMissing)
                                 .map_err(warp_utils::reject::beacon_chain_error)?;
+                            if unsent_registration_data.is_empty() {
+                                return Ok(warp::reply::json(&()).into_response());
+                            }
                             builder
-                                .post_builder_validators(&filtered_registration_data)
+                                .post_builder_validators(&unsent_registration_data)
                                 .await
-                                .map(|resp| warp::reply::json(&resp).into_response())
+                                .map(|resp| {
+                                    execution_layer.on_validator_registrations_sent(
+                                        &unsent_registration_data,
+                                        current_epoch,
+                                    );
+                                    warp::reply::json(&resp).into_response()
+                                })
                                 .map_err(|e| {
                                     warn!(
                                         log,
                                         "Relay error when registering validator(s)";
-                                        "num_registrations" => filtered_registration_data.len(),
+                                        "num_registrations" => unsent_registration_data.len(),
                                         "error" => ?e
                                     );
                                     // Forward the HTTP status code if we are able to, otherwise fall back
@@ -3837,7 +4038,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validator"))
         .and(warp::path("sync_committee_subscriptions"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(validator_subscription_tx_filter)
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
@@ -3883,7 +4084,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("liveness"))
         .and(warp::path::param::<Epoch>())
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3925,7 +4126,7 @@ pub fn serve<T: BeaconChainTypes>(
     let post_lighthouse_liveness = warp::path("lighthouse")
         .and(warp::path("liveness"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -3968,17 +4169,62 @@ pub fn serve<T: BeaconChainTypes>(
         );
 
     // GET lighthouse/health
+    let inner_ctx = ctx.clone();
     let get_lighthouse_health = warp::path("lighthouse")
         .and(warp::path("health"))
         .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::HealthQuery>())
         .and(task_spawner_filter.clone())
-        .then(|task_spawner: TaskSpawner<T::EthSpec>| {
-            task_spawner.blocking_json_task(Priority::P0, move || {
-                eth2::lighthouse::Health::observe()
-                    .map(api_types::GenericResponse::from)
-                    .map_err(warp_utils::reject::custom_bad_request)
-            })
-        });
+        .and(system_info_filter.clone())
+        .and(data_dir_filter.clone())
+        .then(
+            move |query: eth2::lighthouse::HealthQuery,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  sysinfo,
+                  data_dir: PathBuf| {
+                let chain = inner_ctx.chain.clone();
+                let network_globals = inner_ctx.network_globals.clone();
+                let target_peers = inner_ctx.config.target_peers;
+                async move {
+                    let el_offline = match chain.as_ref().and_then(|c| c.execution_layer.as_ref()) {
+                        Some(el) => el.is_offline_or_erroring().await,
+                        None => true,
+                    };
+
+                    task_spawner
+                        .blocking_response_task(Priority::P0, move || {
+                            let node_health = eth2::lighthouse::Health::observe()
+                                .map_err(warp_utils::reject::custom_bad_request)?;
+                            let readiness = health::observe_readiness(
+                                chain.as_deref(),
+                                network_globals.as_deref(),
+                                el_offline,
+                                sysinfo,
+                                &data_dir,
+                                target_peers,
+                            )?;
+
+                            let status = if query.strict
+                                && readiness.status == eth2::lighthouse::ReadinessStatus::NotReady
+                            {
+                                warp::http::StatusCode::SERVICE_UNAVAILABLE
+                            } else {
+                                warp::http::StatusCode::OK
+                            };
+                            Ok(warp::reply::with_status(
+                                warp::reply::json(&api_types::GenericResponse::from(
+                                    eth2::lighthouse::BeaconNodeHealth {
+                                        health: node_health,
+                                        readiness,
+                                    },
+                                )),
+                                status,
+                            ))
+                        })
+                        .await
+                }
+            },
+        );
 
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
@@ -4023,12 +4269,50 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/ui/attestation_effectiveness
+    let get_lighthouse_ui_attestation_effectiveness = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("attestation_effectiveness"))
+        .and(warp::path::end())
+        .and(warp::query::<ui::AttestationEffectivenessQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: ui::AttestationEffectivenessQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    ui::get_attestation_effectiveness(query, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/ui/validator_performance
+    let get_lighthouse_ui_validator_performance = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("validator_performance"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::ValidatorPerformanceQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::ValidatorPerformanceQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    ui::get_validator_performance(query, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // POST lighthouse/ui/validator_metrics
     let post_lighthouse_ui_validator_metrics = warp::path("lighthouse")
         .and(warp::path("ui"))
         .and(warp::path("validator_metrics"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -4047,7 +4331,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("ui"))
         .and(warp::path("validator_info"))
         .and(warp::path::end())
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -4112,32 +4396,116 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET lighthouse/peers/connected
-    let get_lighthouse_peers_connected = warp::path("lighthouse")
-        .and(warp::path("peers"))
-        .and(warp::path("connected"))
+    // GET lighthouse/network/diversity
+    let get_lighthouse_network_diversity = warp::path("lighthouse")
+        .and(warp::path("network"))
+        .and(warp::path("diversity"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
-                    Ok(network_globals
-                        .peers
-                        .read()
-                        .connected_peers()
-                        .map(|(peer_id, peer_info)| eth2::lighthouse::Peer {
-                            peer_id: peer_id.to_string(),
-                            peer_info: peer_info.clone(),
-                        })
-                        .collect::<Vec<_>>())
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.peer_diversity(),
+                    ))
                 })
             },
         );
 
-    // GET lighthouse/proto_array
-    let get_lighthouse_proto_array = warp::path("lighthouse")
+    // GET lighthouse/network/gossip_trace
+    let get_lighthouse_network_gossip_trace = warp::path("lighthouse")
+        .and(warp::path("network"))
+        .and(warp::path("gossip_trace"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    network_globals
+                        .gossip_trace()
+                        .map(api_types::GenericResponse::from)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(
+                                "gossip tracing is disabled".to_string(),
+                            )
+                        })
+                })
+            },
+        );
+
+    // POST lighthouse/network/enr
+    let post_lighthouse_network_enr = warp::path("lighthouse")
+        .and(warp::path("network"))
+        .and(warp::path("enr"))
+        .and(warp::path::end())
+        .and(body_limits.json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter)
+        .then(
+            |request: eth2::lighthouse::EnrUpdateRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let fields = request
+                        .fields
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let bytes = value
+                                .strip_prefix("0x")
+                                .and_then(|value| hex::decode(value).ok())
+                                .ok_or_else(|| {
+                                    warp_utils::reject::custom_bad_request(format!(
+                                        "value of {key} must be 0x-prefixed hex"
+                                    ))
+                                })?;
+                            validate_custom_enr_field(&key, &bytes)
+                                .map_err(warp_utils::reject::custom_bad_request)?;
+                            Ok((key, bytes))
+                        })
+                        .collect::<Result<_, warp::Rejection>>()?;
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::UpdateEnr {
+                            fields,
+                            udp_socket: request.udp_socket,
+                            refresh: request.refresh,
+                        },
+                    )?;
+                    Ok(())
+                })
+            },
+        );
+
+    // GET lighthouse/peers/connected
+    let get_lighthouse_peers_connected = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("connected"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals
+                        .peers
+                        .read()
+                        .connected_peers()
+                        .map(|(peer_id, peer_info)| eth2::lighthouse::Peer {
+                            peer_id: peer_id.to_string(),
+                            peer_info: peer_info.clone(),
+                        })
+                        .collect::<Vec<_>>())
+                })
+            },
+        );
+
+    // GET lighthouse/proto_array
+    let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
@@ -4158,6 +4526,108 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/fork_choice/detail
+    let get_lighthouse_fork_choice_detail = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("detail"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    fork_choice::get_fork_choice_detail(chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/fork_choice/optimistic_blocks
+    let get_lighthouse_fork_choice_optimistic_blocks = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("optimistic_blocks"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        fork_choice::get_optimistic_blocks(chain),
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/fork_choice/optimistic_blocks/{execution_block_hash}/reverify
+    let post_lighthouse_fork_choice_optimistic_blocks_reverify = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("optimistic_blocks"))
+        .and(
+            warp::path::param::<ExecutionBlockHash>().or_else(|_| async {
+                Err(warp_utils::reject::custom_bad_request(
+                    "Invalid execution block hash".to_string(),
+                ))
+            }),
+        )
+        .and(warp::path("reverify"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |execution_block_hash: ExecutionBlockHash,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    fork_choice::reverify_optimistic_block(chain, execution_block_hash)
+                        .await
+                        .map(|reverification| {
+                            warp::reply::json(&api_types::GenericResponse::from(reverification))
+                                .into_response()
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/reorgs
+    let get_lighthouse_reorgs = warp::path("lighthouse")
+        .and(warp::path("reorgs"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    chain
+                        .reorg_captures()
+                        .map(api_types::GenericResponse::from)
+                        .map_err(warp_utils::reject::custom_server_error)
+                })
+            },
+        );
+
+    // GET lighthouse/reorgs/{id}
+    let get_lighthouse_reorg = warp::path("lighthouse")
+        .and(warp::path("reorgs"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |id: u64, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    match chain
+                        .reorg_capture(id)
+                        .map_err(warp_utils::reject::custom_server_error)?
+                    {
+                        Some(capture) => Ok(api_types::GenericResponse::from(capture)),
+                        None => Err(warp_utils::reject::custom_not_found(format!(
+                            "no re-org captured with id {id}"
+                        ))),
+                    }
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -4195,6 +4665,27 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validators/{validator_id}/attestation_inclusion/{epoch}
+    let get_lighthouse_validator_attestation_inclusion = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<ValidatorId>())
+        .and(warp::path("attestation_inclusion"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |validator_id: ValidatorId,
+             epoch: Epoch,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    attestation_inclusion::attestation_inclusion(&validator_id, epoch, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/eth1/syncing
     let get_lighthouse_eth1_syncing = warp::path("lighthouse")
         .and(warp::path("eth1"))
@@ -4273,6 +4764,21 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validator_queues
+    let get_lighthouse_validator_queues = warp::path("lighthouse")
+        .and(warp::path("validator_queues"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_queues::get_validator_queues(chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/staking
     let get_lighthouse_staking = warp::path("lighthouse")
         .and(warp::path("staking"))
@@ -4309,6 +4815,18 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/database/forecast
+    let get_lighthouse_database_forecast = database_path
+        .and(warp::path("forecast"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || database::forecast(chain))
+            },
+        );
+
     // POST lighthouse/database/reconstruct
     let post_lighthouse_database_reconstruct = database_path
         .and(warp::path("reconstruct"))
@@ -4328,6 +4846,127 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/compact
+    let post_lighthouse_database_compact = database_path
+        .and(warp::path("compact"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    chain.store_migrator.process_compaction();
+                    Ok("success")
+                })
+            },
+        );
+
+    // POST lighthouse/database/backup
+    let post_lighthouse_database_backup = database_path
+        .and(warp::path("backup"))
+        .and(warp::path::end())
+        .and(body_limits.json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request: eth2::lighthouse::DatabaseBackupRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner
+                    .blocking_json_task(Priority::P1, move || database::backup(chain, request))
+            },
+        );
+
+    // GET lighthouse/proposer_re_org_config
+    let get_lighthouse_proposer_re_org_config = warp::path("lighthouse")
+        .and(warp::path("proposer_re_org_config"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    proposer_re_org::get_config(chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/proposer_re_org_config
+    let post_lighthouse_proposer_re_org_config = warp::path("lighthouse")
+        .and(warp::path("proposer_re_org_config"))
+        .and(warp::path::end())
+        .and(body_limits.json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |update: eth2::lighthouse::ProposerReOrgConfigUpdate,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    proposer_re_org::update_config(chain, update)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/logging
+    let get_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(log_levels_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, log_levels: RuntimeLogLevels| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(log_levels::get_levels(
+                        &log_levels,
+                    )))
+                })
+            },
+        );
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(body_limits.json())
+        .and(task_spawner_filter.clone())
+        .and(log_levels_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |update: eth2::lighthouse::LogLevelUpdate,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             log_levels: RuntimeLogLevels,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    log_levels::update_level(log_levels, update, log)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/builder/registrations
+    let get_lighthouse_builder_registrations = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("registrations"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    Ok(api_types::GenericResponse::from(
+                        execution_layer.validator_registrations(),
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4343,11 +4982,122 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/blocks
+    let get_lighthouse_blocks = warp::path("lighthouse")
+        .and(warp::path("blocks"))
+        .and(warp::query::<eth2::lighthouse::BlockSummariesQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    block_summaries::get_block_summaries(query, chain)
+                })
+            },
+        );
+
+    // GET lighthouse/blocks/{block_id}/execution_requests
+    let get_lighthouse_block_execution_requests = warp::path("lighthouse")
+        .and(warp::path("blocks"))
+        .and(warp::path::param::<BlockId>())
+        .and(warp::path("execution_requests"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_id: BlockId,
+             accept_header: Option<api_types::Accept>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let response =
+                        execution_requests::get_block_execution_requests(block_id, chain).await?;
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&response.data)),
+                        _ => Ok(warp::reply::json(&response).into_response()),
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/execution_requests/{epoch}
+    let get_lighthouse_execution_requests = warp::path("lighthouse")
+        .and(warp::path("execution_requests"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |epoch: Epoch,
+             accept_header: Option<api_types::Accept>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let totals =
+                        execution_requests::get_epoch_execution_requests(epoch, chain).await?;
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Ok(ssz_response(&totals)),
+                        _ => Ok(warp::reply::json(&api_types::GenericResponse::from(totals))
+                            .into_response()),
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/timeliness
+    let get_lighthouse_timeliness = warp::path("lighthouse")
+        .and(warp::path("timeliness"))
+        .and(warp::query::<eth2::lighthouse::TimelinessQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::TimelinessQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let start_slot = query.start_slot.unwrap_or(Slot::new(0));
+                    let end_slot = query.end_slot.unwrap_or_else(Slot::max_value);
+                    if start_slot > end_slot {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "start_slot ({start_slot}) must not be greater than end_slot \
+                             ({end_slot})"
+                        )));
+                    }
+                    Ok(api_types::GenericResponse::from(
+                        chain.timeliness(start_slot, end_slot),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/rewards/blocks
+    let get_lighthouse_rewards_blocks = warp::path("lighthouse")
+        .and(warp::path("rewards"))
+        .and(warp::path("blocks"))
+        .and(warp::query::<eth2::lighthouse::BlockRangeRewardsQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(|query, task_spawner: TaskSpawner<T::EthSpec>, chain, log| {
+            task_spawner.blocking_json_task(Priority::P1, move || {
+                standard_block_rewards::get_beacon_block_rewards_range(query, chain, log)
+            })
+        });
+
     // POST lighthouse/analysis/block_rewards
     let post_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
         .and(warp::path("block_rewards"))
-        .and(warp_utils::json::json())
+        .and(body_limits.json())
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
@@ -4360,6 +5110,24 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/blocks/dry_run
+    let post_lighthouse_blocks_dry_run = warp::path("lighthouse")
+        .and(warp::path("blocks"))
+        .and(warp::path("dry_run"))
+        .and(warp::path::end())
+        .and(body_limits.json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block: eth2::lighthouse::DryRunBlock<T::EthSpec>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    block_dry_run::dry_run_block(block, chain)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/attestation_performance/{index}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4393,6 +5161,83 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/analysis/block_transition/{block_id}
+    let get_lighthouse_block_transition = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("block_transition"))
+        .and(warp::path::param::<BlockId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid block ID".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_id: BlockId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    block_transition::get_block_transition_report(block_id, chain)
+                })
+            },
+        );
+
+    // GET lighthouse/historical_block_proof/{state_id}/{block_id}
+    let get_lighthouse_historical_block_proof = warp::path("lighthouse")
+        .and(warp::path("historical_block_proof"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path::param::<BlockId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid block ID".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |state_id: StateId,
+             block_id: BlockId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    historical_block_proof::get_historical_block_proof(state_id, block_id, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/beacon/states/{state_id}/proof?gindex
+    let get_lighthouse_beacon_states_proof = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("states"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path("proof"))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::StateProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |state_id: StateId,
+             query_res: Result<api_types::StateProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    state_proof::get_state_proof(state_id, query.gindex, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -4416,100 +5261,108 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("events"))
         .and(warp::path::end())
         .and(multi_key_query::<api_types::EventQuery>())
+        .and(warp::header::optional::<u64>("last-event-id"))
         .and(task_spawner_filter.clone())
         .and(chain_filter)
         .then(
             |topics_res: Result<api_types::EventQuery, warp::Rejection>,
+             last_event_id: Option<u64>,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_response_task(Priority::P0, move || {
                     let topics = topics_res?;
-                    // for each topic subscribed spawn a new subscription
-                    let mut receivers = Vec::with_capacity(topics.topics.len());
-
-                    if let Some(event_handler) = chain.event_handler.as_ref() {
-                        for topic in topics.topics {
-                            let receiver = match topic {
-                                api_types::EventTopic::Head => event_handler.subscribe_head(),
-                                api_types::EventTopic::Block => event_handler.subscribe_block(),
-                                api_types::EventTopic::BlobSidecar => {
-                                    event_handler.subscribe_blob_sidecar()
-                                }
-                                api_types::EventTopic::Attestation => {
-                                    event_handler.subscribe_attestation()
-                                }
-                                api_types::EventTopic::VoluntaryExit => {
-                                    event_handler.subscribe_exit()
-                                }
-                                api_types::EventTopic::FinalizedCheckpoint => {
-                                    event_handler.subscribe_finalized()
-                                }
-                                api_types::EventTopic::ChainReorg => {
-                                    event_handler.subscribe_reorgs()
-                                }
-                                api_types::EventTopic::ContributionAndProof => {
-                                    event_handler.subscribe_contributions()
-                                }
-                                api_types::EventTopic::PayloadAttributes => {
-                                    event_handler.subscribe_payload_attributes()
-                                }
-                                api_types::EventTopic::LateHead => {
-                                    event_handler.subscribe_late_head()
-                                }
-                                api_types::EventTopic::LightClientFinalityUpdate => {
-                                    event_handler.subscribe_light_client_finality_update()
-                                }
-                                api_types::EventTopic::LightClientOptimisticUpdate => {
-                                    event_handler.subscribe_light_client_optimistic_update()
-                                }
-                                api_types::EventTopic::BlockReward => {
-                                    event_handler.subscribe_block_reward()
-                                }
-                                api_types::EventTopic::AttesterSlashing => {
-                                    event_handler.subscribe_attester_slashing()
-                                }
-                                api_types::EventTopic::ProposerSlashing => {
-                                    event_handler.subscribe_proposer_slashing()
-                                }
-                                api_types::EventTopic::BlsToExecutionChange => {
-                                    event_handler.subscribe_bls_to_execution_change()
-                                }
-                                api_types::EventTopic::BlockGossip => {
-                                    event_handler.subscribe_block_gossip()
-                                }
-                            };
-
-                            receivers.push(
-                                BroadcastStream::new(receiver)
-                                    .map(|msg| {
-                                        match msg {
-                                            Ok(data) => Event::default()
-                                                .event(data.topic_name())
-                                                .json_data(data)
-                                                .unwrap_or_else(|e| {
-                                                    Event::default()
-                                                        .comment(format!("error - bad json: {e:?}"))
-                                                }),
-                                            // Do not terminate the stream if the channel fills
-                                            // up. Just drop some messages and send a comment to
-                                            // the client.
-                                            Err(BroadcastStreamRecvError::Lagged(n)) => {
-                                                Event::default().comment(format!(
-                                                    "error - dropped {n} messages"
-                                                ))
-                                            }
-                                        }
-                                    })
-                                    .map(Ok::<_, std::convert::Infallible>),
-                            );
-                        }
-                    } else {
+                    let Some(event_handler) = chain.event_handler.as_ref() else {
                         return Err(warp_utils::reject::custom_server_error(
                             "event handler was not initialized".to_string(),
                         ));
+                    };
+
+                    // Subscribe to the replayable topics first, replaying any events missed
+                    // since the client's `Last-Event-ID`.
+                    let replayable_topics = topics
+                        .topics
+                        .iter()
+                        .copied()
+                        .filter(|topic| is_replayable_topic(*topic))
+                        .collect::<Vec<_>>();
+                    let subscription =
+                        event_handler.subscribe_with_replay(&replayable_topics, last_event_id);
+
+                    let mut missed = Vec::with_capacity(subscription.missed.len() + 1);
+                    if !subscription.complete {
+                        missed.push(
+                            Event::default()
+                                .comment("error - unable to replay all missed events".to_string()),
+                        );
                     }
+                    missed.extend(subscription.missed.into_iter().map(replayable_sse_event));
 
-                    let s = futures::stream::select_all(receivers);
+                    // for each topic subscribed spawn a new subscription
+                    let mut receivers = Vec::with_capacity(topics.topics.len());
+                    for receiver in subscription.receivers {
+                        receivers.push(sse_stream(receiver, replayable_sse_event));
+                    }
+
+                    for topic in topics.topics {
+                        let receiver = match topic {
+                            // Subscribed to above.
+                            api_types::EventTopic::Head
+                            | api_types::EventTopic::Block
+                            | api_types::EventTopic::FinalizedCheckpoint
+                            | api_types::EventTopic::ChainReorg => continue,
+                            api_types::EventTopic::BlobSidecar => {
+                                event_handler.subscribe_blob_sidecar()
+                            }
+                            api_types::EventTopic::Attestation => {
+                                event_handler.subscribe_attestation()
+                            }
+                            api_types::EventTopic::VoluntaryExit => event_handler.subscribe_exit(),
+                            api_types::EventTopic::ContributionAndProof => {
+                                event_handler.subscribe_contributions()
+                            }
+                            api_types::EventTopic::PayloadAttributes => {
+                                event_handler.subscribe_payload_attributes()
+                            }
+                            api_types::EventTopic::LateHead => event_handler.subscribe_late_head(),
+                            api_types::EventTopic::LightClientFinalityUpdate => {
+                                event_handler.subscribe_light_client_finality_update()
+                            }
+                            api_types::EventTopic::LightClientOptimisticUpdate => {
+                                event_handler.subscribe_light_client_optimistic_update()
+                            }
+                            api_types::EventTopic::BlockReward => {
+                                event_handler.subscribe_block_reward()
+                            }
+                            api_types::EventTopic::AttesterSlashing => {
+                                event_handler.subscribe_attester_slashing()
+                            }
+                            api_types::EventTopic::ProposerSlashing => {
+                                event_handler.subscribe_proposer_slashing()
+                            }
+                            api_types::EventTopic::BlsToExecutionChange => {
+                                event_handler.subscribe_bls_to_execution_change()
+                            }
+                            api_types::EventTopic::BlockGossip => {
+                                event_handler.subscribe_block_gossip()
+                            }
+                            api_types::EventTopic::ProposerReOrg => {
+                                event_handler.subscribe_proposer_re_org()
+                            }
+                            api_types::EventTopic::SlasherAttestation => {
+                                event_handler.subscribe_slasher_attestation()
+                            }
+                            api_types::EventTopic::SlasherBlockHeader => {
+                                event_handler.subscribe_slasher_block_header()
+                            }
+                            api_types::EventTopic::OptimisticStatus => {
+                                event_handler.subscribe_optimistic_status()
+                            }
+                        };
+                        receivers.push(sse_stream(receiver, sse_event));
+                    }
+
+                    let s = tokio_stream::iter(missed.into_iter().map(Ok))
+                        .chain(futures::stream::select_all(receivers));
 
                     Ok(warp::sse::reply(warp::sse::keep_alive().stream(s)))
                 })
@@ -4527,9 +5380,13 @@ pub fn serve<T: BeaconChainTypes>(
             |task_spawner: TaskSpawner<T::EthSpec>, sse_component: Option<SSELoggingComponents>| {
                 task_spawner.blocking_response_task(Priority::P1, move || {
                     if let Some(logging_components) = sse_component {
+                        // Send the recent warnings and errors before any new events.
+                        let (recent, receiver) = logging_components.subscribe_with_recent();
                         // Build a JSON stream
-                        let s = BroadcastStream::new(logging_components.sender.subscribe()).map(
-                            |msg| {
+                        let s = tokio_stream::iter(recent)
+                            .map(Ok::<_, BroadcastStreamRecvError>)
+                            .chain(BroadcastStream::new(receiver))
+                            .map(|msg| {
                                 match msg {
                                     Ok(data) => {
                                         // Serialize to json
@@ -4547,8 +5404,7 @@ pub fn serve<T: BeaconChainTypes>(
                                         format!("Unable to receive event {}", e),
                                     )),
                                 }
-                            },
-                        );
+                            });
 
                         Ok::<_, warp::Rejection>(warp::sse::reply(
                             warp::sse::keep_alive().stream(s),
@@ -4562,132 +5418,188 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let enable_compression = config.enable_compression;
+
+    // Match and strip the segments of the path prefix, if any, before routing the request.
+    let path_prefix_filter = config
+        .path_prefix
+        .iter()
+        .flat_map(|prefix| prefix.split('/'))
+        .filter(|segment| !segment.is_empty())
+        .fold(warp::any().boxed(), |filter, segment| {
+            filter.and(warp::path(segment.to_string())).boxed()
+        });
+
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
-    let routes = warp::get()
+    let routes = path_prefix_filter
+        .and(request_limits_filter)
+        .and(authorization_filter)
         .and(
-            get_beacon_genesis
-                .uor(get_beacon_state_root)
-                .uor(get_beacon_state_fork)
-                .uor(get_beacon_state_finality_checkpoints)
-                .uor(get_beacon_state_validator_balances)
-                .uor(get_beacon_state_validators_id)
-                .uor(get_beacon_state_validators)
-                .uor(get_beacon_state_committees)
-                .uor(get_beacon_state_sync_committees)
-                .uor(get_beacon_state_randao)
-                .uor(get_beacon_headers)
-                .uor(get_beacon_headers_block_id)
-                .uor(get_beacon_block)
-                .uor(get_beacon_block_attestations)
-                .uor(get_beacon_blinded_block)
-                .uor(get_beacon_block_root)
-                .uor(get_blobs)
-                .uor(get_beacon_pool_attestations)
-                .uor(get_beacon_pool_attester_slashings)
-                .uor(get_beacon_pool_proposer_slashings)
-                .uor(get_beacon_pool_voluntary_exits)
-                .uor(get_beacon_pool_bls_to_execution_changes)
-                .uor(get_beacon_deposit_snapshot)
-                .uor(get_beacon_rewards_blocks)
-                .uor(get_config_fork_schedule)
-                .uor(get_config_spec)
-                .uor(get_config_deposit_contract)
-                .uor(get_debug_beacon_states)
-                .uor(get_debug_beacon_heads)
-                .uor(get_debug_fork_choice)
-                .uor(get_node_identity)
-                .uor(get_node_version)
-                .uor(get_node_syncing)
-                .uor(get_node_health)
-                .uor(get_node_peers_by_id)
-                .uor(get_node_peers)
-                .uor(get_node_peer_count)
-                .uor(get_validator_duties_proposer)
-                .uor(get_validator_blocks)
-                .uor(get_validator_blinded_blocks)
-                .uor(get_validator_attestation_data)
-                .uor(get_validator_aggregate_attestation)
-                .uor(get_validator_sync_committee_contribution)
-                .uor(get_lighthouse_health)
-                .uor(get_lighthouse_ui_health)
-                .uor(get_lighthouse_ui_validator_count)
-                .uor(get_lighthouse_syncing)
-                .uor(get_lighthouse_nat)
-                .uor(get_lighthouse_peers)
-                .uor(get_lighthouse_peers_connected)
-                .uor(get_lighthouse_proto_array)
-                .uor(get_lighthouse_validator_inclusion_global)
-                .uor(get_lighthouse_validator_inclusion)
-                .uor(get_lighthouse_eth1_syncing)
-                .uor(get_lighthouse_eth1_block_cache)
-                .uor(get_lighthouse_eth1_deposit_cache)
-                .uor(get_lighthouse_staking)
-                .uor(get_lighthouse_database_info)
-                .uor(get_lighthouse_block_rewards)
-                .uor(get_lighthouse_attestation_performance)
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_optimistic_update),
-                )
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_finality_update),
+            warp::get()
+                .and(
+                    get_beacon_genesis
+                        .uor(get_beacon_state_root)
+                        .uor(get_beacon_state_fork)
+                        .uor(get_beacon_state_finality_checkpoints)
+                        .uor(get_beacon_state_validator_balances)
+                        .uor(get_beacon_state_validators_id)
+                        .uor(get_beacon_state_validators)
+                        .uor(get_beacon_state_committees)
+                        .uor(get_beacon_state_sync_committees)
+                        .uor(get_beacon_state_randao)
+                        .uor(get_beacon_headers)
+                        .uor(get_beacon_headers_block_id)
+                        .uor(get_beacon_block)
+                        .uor(get_beacon_block_attestations)
+                        .uor(get_beacon_blinded_block)
+                        .uor(get_beacon_block_root)
+                        .uor(get_blobs)
+                        .uor(get_beacon_pool_attestations)
+                        .uor(get_beacon_pool_attester_slashings)
+                        .uor(get_beacon_pool_proposer_slashings)
+                        .uor(get_beacon_pool_voluntary_exits)
+                        .uor(get_beacon_pool_bls_to_execution_changes)
+                        .uor(get_beacon_deposit_snapshot)
+                        .uor(get_beacon_rewards_blocks)
+                        .uor(get_config_fork_schedule)
+                        .uor(get_config_spec)
+                        .uor(get_config_deposit_contract)
+                        .uor(get_debug_beacon_states)
+                        .uor(get_debug_beacon_heads)
+                        .uor(get_debug_fork_choice)
+                        .uor(get_node_identity)
+                        .uor(get_node_version)
+                        .uor(get_node_syncing)
+                        .uor(get_node_health)
+                        .uor(get_node_peers_by_id)
+                        .uor(get_node_peers)
+                        .uor(get_node_peer_count)
+                        .uor(get_validator_duties_proposer)
+                        .uor(get_validator_blocks)
+                        .uor(get_validator_blinded_blocks)
+                        .uor(get_validator_attestation_data)
+                        .uor(get_validator_aggregate_attestation)
+                        .uor(get_validator_sync_committee_contribution)
+                        .uor(get_lighthouse_health)
+                        .uor(get_lighthouse_ui_health)
+                        .uor(get_lighthouse_ui_validator_count)
+                        .uor(get_lighthouse_ui_attestation_effectiveness)
+                        .uor(get_lighthouse_ui_validator_performance)
+                        .uor(get_lighthouse_syncing)
+                        .uor(get_lighthouse_nat)
+                        .uor(get_lighthouse_peers)
+                        .uor(get_lighthouse_peers_connected)
+                        .uor(get_lighthouse_network_diversity)
+                        .uor(get_lighthouse_network_gossip_trace)
+                        .uor(get_lighthouse_proto_array)
+                        .uor(get_lighthouse_fork_choice_detail)
+                        .uor(get_lighthouse_fork_choice_optimistic_blocks)
+                        .uor(get_lighthouse_reorgs)
+                        .uor(get_lighthouse_reorg)
+                        .uor(get_lighthouse_validator_inclusion_global)
+                        .uor(get_lighthouse_validator_inclusion)
+                        .uor(get_lighthouse_validator_attestation_inclusion)
+                        .uor(get_lighthouse_eth1_syncing)
+                        .uor(get_lighthouse_eth1_block_cache)
+                        .uor(get_lighthouse_eth1_deposit_cache)
+                        .uor(get_lighthouse_staking)
+                        .uor(get_lighthouse_validator_queues)
+                        .uor(get_lighthouse_database_info)
+                        .uor(get_lighthouse_database_forecast)
+                        .uor(get_lighthouse_proposer_re_org_config)
+                        .uor(get_lighthouse_logging)
+                        .uor(get_lighthouse_builder_registrations)
+                        .uor(get_lighthouse_block_rewards)
+                        .uor(get_lighthouse_blocks)
+                        .uor(get_lighthouse_block_execution_requests)
+                        .uor(get_lighthouse_execution_requests)
+                        .uor(get_lighthouse_timeliness)
+                        .uor(get_lighthouse_rewards_blocks)
+                        .uor(get_lighthouse_attestation_performance)
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_optimistic_update),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_finality_update),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_bootstrap),
+                        )
+                        .uor(get_lighthouse_block_packing_efficiency)
+                        .uor(get_lighthouse_block_transition)
+                        .uor(get_lighthouse_historical_block_proof)
+                        .uor(get_lighthouse_beacon_states_proof)
+                        .uor(get_lighthouse_merge_readiness)
+                        .uor(get_events)
+                        .uor(get_expected_withdrawals)
+                        .uor(lighthouse_log_events.boxed())
+                        .recover(warp_utils::reject::handle_rejection),
                 )
+                .boxed()
                 .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_bootstrap),
-                )
-                .uor(get_lighthouse_block_packing_efficiency)
-                .uor(get_lighthouse_merge_readiness)
-                .uor(get_events)
-                .uor(get_expected_withdrawals)
-                .uor(lighthouse_log_events.boxed())
-                .recover(warp_utils::reject::handle_rejection),
-        )
-        .boxed()
-        .uor(
-            warp::post().and(
-                warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
-                    // Routes which expect `application/octet-stream` go within this `and`.
-                    .and(
-                        post_beacon_blocks_ssz
-                            .uor(post_beacon_blocks_v2_ssz)
-                            .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
-                    )
-                    .uor(post_beacon_blocks)
-                    .uor(post_beacon_blinded_blocks)
-                    .uor(post_beacon_blocks_v2)
-                    .uor(post_beacon_blinded_blocks_v2)
-                    .uor(post_beacon_pool_attestations)
-                    .uor(post_beacon_pool_attester_slashings)
-                    .uor(post_beacon_pool_proposer_slashings)
-                    .uor(post_beacon_pool_voluntary_exits)
-                    .uor(post_beacon_pool_sync_committees)
-                    .uor(post_beacon_pool_bls_to_execution_changes)
-                    .uor(post_beacon_state_validators)
-                    .uor(post_beacon_state_validator_balances)
-                    .uor(post_beacon_rewards_attestations)
-                    .uor(post_beacon_rewards_sync_committee)
-                    .uor(post_validator_duties_attester)
-                    .uor(post_validator_duties_sync)
-                    .uor(post_validator_aggregate_and_proofs)
-                    .uor(post_validator_contribution_and_proofs)
-                    .uor(post_validator_beacon_committee_subscriptions)
-                    .uor(post_validator_sync_committee_subscriptions)
-                    .uor(post_validator_prepare_beacon_proposer)
-                    .uor(post_validator_register_validator)
-                    .uor(post_validator_liveness_epoch)
-                    .uor(post_lighthouse_liveness)
-                    .uor(post_lighthouse_database_reconstruct)
-                    .uor(post_lighthouse_block_rewards)
-                    .uor(post_lighthouse_ui_validator_metrics)
-                    .uor(post_lighthouse_ui_validator_info)
-                    .recover(warp_utils::reject::handle_rejection),
-            ),
+                    warp::post().and(
+                        warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
+                            // Routes which expect `application/octet-stream` go within this `and`.
+                            .and(
+                                post_beacon_blocks_ssz
+                                    .uor(post_beacon_blocks_v2_ssz)
+                                    .uor(post_beacon_blinded_blocks_ssz)
+                                    .uor(post_beacon_blinded_blocks_v2_ssz),
+                            )
+                            .uor(post_beacon_blocks)
+                            .uor(post_beacon_blinded_blocks)
+                            .uor(post_beacon_blocks_v2)
+                            .uor(post_beacon_blinded_blocks_v2)
+                            .uor(post_beacon_pool_attestations)
+                            .uor(post_beacon_pool_attester_slashings)
+                            .uor(post_beacon_pool_proposer_slashings)
+                            .uor(post_beacon_pool_voluntary_exits)
+                            .uor(post_beacon_pool_sync_committees)
+                            .uor(post_beacon_pool_bls_to_execution_changes)
+                            .uor(post_beacon_state_validators)
+                            .uor(post_beacon_state_validator_balances)
+                            .uor(post_beacon_rewards_attestations)
+                            .uor(post_beacon_rewards_sync_committee)
+                            .uor(post_validator_duties_attester)
+                            .uor(post_validator_duties_sync)
+                            .uor(post_validator_aggregate_and_proofs)
+                            .uor(post_validator_contribution_and_proofs)
+                            .uor(post_validator_beacon_committee_subscriptions)
+                            .uor(post_validator_sync_committee_subscriptions)
+                            .uor(post_validator_prepare_beacon_proposer)
+                            .uor(post_validator_register_validator)
+                            .uor(post_validator_liveness_epoch)
+                            .uor(post_lighthouse_liveness)
+                            .uor(post_lighthouse_database_reconstruct)
+                            .uor(post_lighthouse_fork_choice_optimistic_blocks_reverify)
+                            .uor(post_lighthouse_database_compact)
+                            .uor(post_lighthouse_database_backup)
+                            .uor(post_lighthouse_network_enr)
+                            .uor(post_lighthouse_proposer_re_org_config)
+                            .uor(post_lighthouse_logging)
+                            .uor(post_lighthouse_block_rewards)
+                            .uor(post_lighthouse_blocks_dry_run)
+                            .uor(post_lighthouse_ui_validator_metrics)
+                            .uor(post_lighthouse_ui_validator_info)
+                            .recover(warp_utils::reject::handle_rejection),
+                    ),
+                ),
         )
+        // Hold the permit for the request's route class until the response has been sent.
+        .map(|permit: limits::RequestPermit, reply| limits::hold_until_sent(permit, reply))
         .recover(warp_utils::reject::handle_rejection)
+        .and(warp::header::headers_cloned())
+        .map(move |reply, headers: HeaderMap| {
+            if enable_compression {
+                warp_utils::compression::compress(reply, &headers)
+            } else {
+                reply.into_response()
+            }
+        })
         .with(slog_logging(log.clone()))
         .with(prometheus_metrics())
         // Add a `Server` header.
@@ -4723,11 +5635,46 @@ pub fn serve<T: BeaconChainTypes>(
         log,
         "HTTP API started";
         "listen_address" => %http_server.0,
+        "authorization" => api_tokens.is_some(),
     );
 
     Ok(http_server)
 }
 
+/// A stream of server-sent events.
+type SseStream = Pin<Box<dyn Stream<Item = Result<Event, Infallible>> + Send>>;
+
+/// Convert a broadcast `receiver` into a stream of server-sent events.
+fn sse_stream<T: Clone + Send + 'static>(
+    receiver: broadcast::Receiver<T>,
+    to_event: fn(T) -> Event,
+) -> SseStream {
+    Box::pin(
+        BroadcastStream::new(receiver)
+            .map(move |msg| match msg {
+                Ok(data) => to_event(data),
+                // Do not terminate the stream if the channel fills up. Just drop some messages
+                // and send a comment to the client.
+                Err(BroadcastStreamRecvError::Lagged(n)) => {
+                    Event::default().comment(format!("error - dropped {n} messages"))
+                }
+            })
+            .map(Ok),
+    )
+}
+
+fn sse_event<E: EthSpec>(data: EventKind<E>) -> Event {
+    Event::default()
+        .event(data.topic_name())
+        .json_data(data)
+        .unwrap_or_else(|e| Event::default().comment(format!("error - bad json: {e:?}")))
+}
+
+/// As per `sse_event`, but including the event's id so that the client may resume from it.
+fn replayable_sse_event<E: EthSpec>(event: ReplayableEvent<E>) -> Event {
+    sse_event(event.kind).id(event.id.to_string())
+}
+
 /// Publish a message to the libp2p pubsub network.
 fn publish_pubsub_message<E: EthSpec>(
     network_tx: &UnboundedSender<NetworkMessage<E>>,
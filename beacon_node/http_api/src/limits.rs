@@ -0,0 +1,332 @@
+//! Limits on the requests served by the HTTP API.
+//!
+//! Endpoints are grouped into classes, as per `RouteClass::of`, and each class may be given a
+//! timeout, a maximum request body size and a cap on the number of requests served concurrently.
+//! Requests which exceed the limits are shed with a 413, 429 or 503 response.
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use warp::filters::path::FullPath;
+use warp::http::Method;
+use warp::hyper::body::{Body, HttpBody};
+use warp::reply::{Reply, Response};
+use warp::{Filter, Rejection};
+
+/// A group of endpoints which share limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteClass {
+    /// The `/eth/*/validator` endpoints.
+    Validator,
+    /// Submission of blocks and pool operations.
+    Submission,
+    /// The `/eth/*/debug` endpoints.
+    Debug,
+    /// The `/lighthouse` endpoints.
+    Lighthouse,
+    /// The `/eth/v1/events` stream.
+    Events,
+    /// All other endpoints.
+    Standard,
+}
+
+impl RouteClass {
+    pub const ALL: [RouteClass; 6] = [
+        RouteClass::Validator,
+        RouteClass::Submission,
+        RouteClass::Debug,
+        RouteClass::Lighthouse,
+        RouteClass::Events,
+        RouteClass::Standard,
+    ];
+
+    /// Returns the class of a request with `method` to `path`.
+    pub fn of(method: &Method, path: &str) -> Self {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        match segments.as_slice() {
+            ["eth", _, "validator", ..] => RouteClass::Validator,
+            ["eth", _, "beacon", "blocks" | "blinded_blocks" | "pool", ..]
+                if method == Method::POST =>
+            {
+                RouteClass::Submission
+            }
+            ["eth", _, "debug", ..] => RouteClass::Debug,
+            ["eth", _, "events"] => RouteClass::Events,
+            ["lighthouse", ..] => RouteClass::Lighthouse,
+            _ => RouteClass::Standard,
+        }
+    }
+}
+
+impl FromStr for RouteClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|class| class.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "{} is not a route class, must be one of validator, submission, debug, \
+                     lighthouse, events or standard",
+                    s
+                )
+            })
+    }
+}
+
+impl fmt::Display for RouteClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteClass::Validator => write!(f, "validator"),
+            RouteClass::Submission => write!(f, "submission"),
+            RouteClass::Debug => write!(f, "debug"),
+            RouteClass::Lighthouse => write!(f, "lighthouse"),
+            RouteClass::Events => write!(f, "events"),
+            RouteClass::Standard => write!(f, "standard"),
+        }
+    }
+}
+
+/// Parse a comma-separated list of `<class>=<value>` pairs, such as `validator=2000,all=6000`.
+///
+/// The class `all` sets the value for every class without its own entry.
+pub fn parse_route_class_values<T: FromStr + Clone>(
+    s: &str,
+) -> Result<BTreeMap<RouteClass, T>, String> {
+    let mut all = None;
+    let mut values = BTreeMap::new();
+    for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (class, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("{} is not of the form <class>=<value>", pair))?;
+        let value = value
+            .trim()
+            .parse::<T>()
+            .map_err(|_| format!("{} has an invalid value", pair))?;
+        match class.trim() {
+            "all" => all = Some(value),
+            class => {
+                values.insert(class.parse::<RouteClass>()?, value);
+            }
+        }
+    }
+    if let Some(all) = all {
+        for class in RouteClass::ALL {
+            values.entry(class).or_insert_with(|| all.clone());
+        }
+    }
+    Ok(values)
+}
+
+/// The limits applied to each class of requests. Classes without an entry are unlimited.
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestLimits {
+    /// The time allowed to produce a response. The event stream is never timed out.
+    pub timeouts: BTreeMap<RouteClass, Duration>,
+    /// The maximum size of a request body, in bytes.
+    pub max_body_sizes: BTreeMap<RouteClass, u64>,
+    /// The maximum number of requests served at once.
+    pub max_concurrent: BTreeMap<RouteClass, usize>,
+}
+
+impl RequestLimits {
+    /// Returns the timeout for requests in `class`, if any.
+    pub fn timeout(&self, class: RouteClass) -> Option<Duration> {
+        if class == RouteClass::Events {
+            return None;
+        }
+        self.timeouts.get(&class).copied()
+    }
+}
+
+/// Held for the duration of a request, releasing its slot for the route class when dropped.
+pub struct RequestPermit(Option<OwnedSemaphorePermit>);
+
+/// Converts `reply` into a response which holds `permit` until its body has been sent.
+///
+/// Streamed responses, such as large states, are still being produced after the reply is
+/// returned, so the permit must not be released until the stream ends.
+pub fn hold_until_sent(permit: RequestPermit, reply: impl Reply) -> Response {
+    let response = reply.into_response();
+    if permit.0.is_none() || response.body().size_hint().exact().is_some() {
+        return response;
+    }
+    response.map(|body| {
+        Body::wrap_stream(body.map(move |chunk| {
+            let _permit = &permit;
+            chunk
+        }))
+    })
+}
+
+/// Reads request bodies, enforcing the body size limits on the bytes received.
+///
+/// The `content-length` is checked by `request_limits_filter`, but requests may omit it, for
+/// example when the body is sent in chunks, so the limits must also be applied while reading.
+#[derive(Clone)]
+pub struct BodyLimits(Arc<BTreeMap<RouteClass, u64>>);
+
+impl BodyLimits {
+    pub fn new(limits: &RequestLimits) -> Self {
+        Self(Arc::new(limits.max_body_sizes.clone()))
+    }
+
+    /// Returns a filter which reads the request body, rejecting it with a 413 as soon as it
+    /// exceeds the limit for the request's class.
+    pub fn bytes(&self) -> impl Filter<Extract = (Bytes,), Error = Rejection> + Clone {
+        let max_body_sizes = self.0.clone();
+        warp::method()
+            .and(warp::path::full())
+            .and(warp::body::stream())
+            .and_then(move |method: Method, path: FullPath, body| {
+                let class = RouteClass::of(&method, path.as_str());
+                let max = max_body_sizes.get(&class).copied();
+                async move { read_body(body, max, class).await }
+            })
+    }
+
+    /// Returns a filter which reads the request body as JSON, as per `warp_utils::json::json`.
+    pub fn json<T: DeserializeOwned + Send>(
+        &self,
+    ) -> impl Filter<Extract = (T,), Error = Rejection> + Clone {
+        warp_utils::json::json_from(self.bytes())
+    }
+}
+
+async fn read_body<B: Buf>(
+    body: impl Stream<Item = Result<B, warp::Error>>,
+    max: Option<u64>,
+    class: RouteClass,
+) -> Result<Bytes, Rejection> {
+    let mut body = Box::pin(body);
+    let mut bytes = BytesMut::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| {
+            warp_utils::reject::custom_bad_request(format!("unable to read request body: {}", e))
+        })?;
+        if let Some(max) = max {
+            if (bytes.len() + chunk.remaining()) as u64 > max {
+                return Err(warp_utils::reject::payload_too_large(format!(
+                    "request body exceeds the {} byte limit for {} requests",
+                    max, class
+                )));
+            }
+        }
+        bytes.put(chunk);
+    }
+    Ok(bytes.freeze())
+}
+
+/// Returns a `warp` filter which rejects requests that would exceed the body size or concurrency
+/// limits for their class.
+///
+/// Only the `content-length` of the request is checked here, and the body must be read with
+/// `BodyLimits`. The extracted `RequestPermit` should be held until the response has been sent,
+/// using `hold_until_sent`.
+pub fn request_limits_filter(
+    limits: &RequestLimits,
+) -> warp::filters::BoxedFilter<(RequestPermit,)> {
+    let max_body_sizes = Arc::new(limits.max_body_sizes.clone());
+    let semaphores: Arc<HashMap<RouteClass, Arc<Semaphore>>> = Arc::new(
+        limits
+            .max_concurrent
+            .iter()
+            .map(|(class, max)| (*class, Arc::new(Semaphore::new(*max))))
+            .collect(),
+    );
+
+    warp::method()
+        .and(warp::path::full())
+        .and(warp::header::optional::<u64>("content-length"))
+        .and_then(
+            move |method: Method, path: FullPath, content_length: Option<u64>| {
+                let max_body_sizes = max_body_sizes.clone();
+                let semaphores = semaphores.clone();
+                async move {
+                    let class = RouteClass::of(&method, path.as_str());
+
+                    if let (Some(length), Some(max)) = (content_length, max_body_sizes.get(&class))
+                    {
+                        if length > *max {
+                            return Err(warp_utils::reject::payload_too_large(format!(
+                                "request body of {} bytes exceeds the {} byte limit for {} \
+                                 requests",
+                                length, max, class
+                            )));
+                        }
+                    }
+
+                    let permit = semaphores
+                        .get(&class)
+                        .map(|semaphore| {
+                            semaphore.clone().try_acquire_owned().map_err(|_| {
+                                warp_utils::reject::too_many_requests(format!(
+                                    "the limit of concurrent {} requests has been reached",
+                                    class
+                                ))
+                            })
+                        })
+                        .transpose()?;
+
+                    Ok(RequestPermit(permit))
+                }
+            },
+        )
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_classes() {
+        let get = Method::GET;
+        let post = Method::POST;
+
+        assert_eq!(
+            RouteClass::of(&get, "/eth/v3/validator/blocks/1"),
+            RouteClass::Validator
+        );
+        assert_eq!(
+            RouteClass::of(&post, "/eth/v2/beacon/blocks"),
+            RouteClass::Submission
+        );
+        assert_eq!(
+            RouteClass::of(&get, "/eth/v2/beacon/blocks/head"),
+            RouteClass::Standard
+        );
+        assert_eq!(
+            RouteClass::of(&get, "/eth/v2/debug/beacon/states/head"),
+            RouteClass::Debug
+        );
+        assert_eq!(RouteClass::of(&get, "/eth/v1/events"), RouteClass::Events);
+        assert_eq!(
+            RouteClass::of(&get, "/lighthouse/health"),
+            RouteClass::Lighthouse
+        );
+    }
+
+    #[test]
+    fn parse_values() {
+        let values = parse_route_class_values::<u64>("validator=2, all=5").unwrap();
+        assert_eq!(values.len(), RouteClass::ALL.len());
+        assert_eq!(values[&RouteClass::Validator], 2);
+        assert_eq!(values[&RouteClass::Debug], 5);
+
+        let values = parse_route_class_values::<u64>("debug=1").unwrap();
+        assert_eq!(values.len(), 1);
+
+        assert!(parse_route_class_values::<u64>("debug").is_err());
+        assert!(parse_route_class_values::<u64>("debug=x").is_err());
+        assert!(parse_route_class_values::<u64>("unknown=1").is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use eth2::lighthouse::{LogLevel, LogLevelUpdate};
+use logging::RuntimeLogLevels;
+use slog::{info, Level, Logger};
+use warp_utils::reject::custom_bad_request;
+
+/// The names of levels, as accepted by `--debug-level`.
+const LEVEL_NAMES: [(&str, Level); 6] = [
+    ("crit", Level::Critical),
+    ("error", Level::Error),
+    ("warn", Level::Warning),
+    ("info", Level::Info),
+    ("debug", Level::Debug),
+    ("trace", Level::Trace),
+];
+
+fn level_name(level: Level) -> &'static str {
+    LEVEL_NAMES
+        .iter()
+        .find(|(_, l)| *l == level)
+        .map_or("info", |(name, _)| name)
+}
+
+fn parse_level(name: &str) -> Result<Level, warp::Rejection> {
+    LEVEL_NAMES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, level)| *level)
+        .ok_or_else(|| custom_bad_request(format!("unknown log level: {}", name)))
+}
+
+pub fn get_levels(log_levels: &RuntimeLogLevels) -> Vec<LogLevel> {
+    log_levels
+        .levels()
+        .into_iter()
+        .map(|(target, level)| LogLevel {
+            target,
+            level: level_name(level).to_string(),
+        })
+        .collect()
+}
+
+pub fn update_level(
+    log_levels: RuntimeLogLevels,
+    update: LogLevelUpdate,
+    log: Logger,
+) -> Result<Vec<LogLevel>, warp::Rejection> {
+    if update.target.is_empty() {
+        return Err(custom_bad_request("target must not be empty".to_string()));
+    }
+
+    match update.level {
+        Some(name) => {
+            let level = parse_level(&name)?;
+            log_levels.set(update.target.clone(), level);
+            info!(
+                log,
+                "Updated log level";
+                "target" => update.target,
+                "level" => level_name(level),
+            );
+        }
+        None => {
+            if log_levels.clear(&update.target) {
+                info!(
+                    log,
+                    "Reset log level";
+                    "target" => update.target,
+                );
+            }
+        }
+    }
+
+    Ok(get_levels(&log_levels))
+}
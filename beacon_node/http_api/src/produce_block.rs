@@ -10,8 +10,11 @@ use beacon_chain::{
     BeaconBlockResponseWrapper, BeaconChain, BeaconChainTypes, ProduceBlockVerification,
 };
 use eth2::types::{
-    self as api_types, EndpointVersion, ProduceBlockV3Metadata, SkipRandaoVerification,
+    self as api_types, EndpointVersion, ProduceBlockV3Metadata, ProduceBlockV3Response,
+    SkipRandaoVerification,
 };
+use eth2::BeaconNodeHttpClient;
+use slog::{warn, Logger};
 use ssz::Encode;
 use std::sync::Arc;
 use types::{payload::BlockProductionVersion, *};
@@ -47,6 +50,8 @@ pub async fn produce_block_v3<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     slot: Slot,
     query: api_types::ValidatorBlocksQuery,
+    block_production_client: Option<BeaconNodeHttpClient>,
+    log: Logger,
 ) -> Result<Response<Body>, warp::Rejection> {
     let randao_reveal = query.randao_reveal.decompress().map_err(|e| {
         warp_utils::reject::custom_bad_request(format!(
@@ -56,6 +61,20 @@ pub async fn produce_block_v3<T: BeaconChainTypes>(
     })?;
 
     let randao_verification = get_randao_verification(&query, randao_reveal.is_infinity())?;
+
+    if let Some(client) = block_production_client {
+        match produce_block_v3_remote(&client, &chain, slot, &query, randao_verification).await {
+            Ok(block_response) => return build_response_v3(chain, block_response, accept_header),
+            Err(e) => warn!(
+                log,
+                "Falling back to local block production";
+                "error" => e,
+                "endpoint" => %client,
+                "slot" => slot,
+            ),
+        }
+    }
+
     let builder_boost_factor = if query.builder_boost_factor == Some(DEFAULT_BOOST_FACTOR) {
         None
     } else {
@@ -79,6 +98,46 @@ pub async fn produce_block_v3<T: BeaconChainTypes>(
     build_response_v3(chain, block_response_type, accept_header)
 }
 
+/// Fetch a block from the beacon node which block production is delegated to, and verify it
+/// against the local view of the chain.
+///
+/// Only a full block can be verified and published by this node, so the remote is asked to use
+/// its local execution payload rather than a builder.
+async fn produce_block_v3_remote<T: BeaconChainTypes>(
+    client: &BeaconNodeHttpClient,
+    chain: &Arc<BeaconChain<T>>,
+    slot: Slot,
+    query: &api_types::ValidatorBlocksQuery,
+    verification: ProduceBlockVerification,
+) -> Result<BeaconBlockResponseWrapper<T::EthSpec>, String> {
+    let (response, metadata) = client
+        .get_validator_blocks_v3_modular_ssz::<T::EthSpec>(
+            slot,
+            &query.randao_reveal,
+            query.graffiti.as_ref(),
+            query.skip_randao_verification,
+            Some(0),
+        )
+        .await
+        .map_err(|e| format!("failed to fetch block: {:?}", e))?;
+
+    let ProduceBlockV3Response::Full(block_contents) = response else {
+        return Err("remote produced a blinded block".to_string());
+    };
+    let (block, blob_items) = block_contents.deconstruct();
+
+    chain
+        .verify_remote_block(
+            block,
+            blob_items,
+            metadata.execution_payload_value,
+            verification,
+        )
+        .await
+        .map(BeaconBlockResponseWrapper::Full)
+        .map_err(|e| format!("invalid block: {:?}", e))
+}
+
 pub fn build_response_v3<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     block_response: BeaconBlockResponseWrapper<T::EthSpec>,
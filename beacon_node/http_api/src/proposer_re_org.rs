@@ -0,0 +1,111 @@
+use beacon_chain::chain_config::{DisallowedReOrgOffsets, ReOrgConfig, ReOrgThreshold};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ProposerReOrgConfig, ProposerReOrgConfigUpdate};
+use slog::info;
+use std::sync::Arc;
+use types::consts::bellatrix::INTERVALS_PER_SLOT;
+use types::EthSpec;
+
+/// The largest head threshold, as a percentage of a committee. A head block with the votes of its
+/// entire committee can not be weak.
+const MAX_HEAD_THRESHOLD: u64 = 100;
+
+fn to_api_config<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    config: &ReOrgConfig,
+) -> ProposerReOrgConfig {
+    ProposerReOrgConfig {
+        enabled: config.enabled,
+        head_threshold: config.head_threshold.0,
+        parent_threshold: config.parent_threshold.0,
+        max_epochs_since_finalization: config.max_epochs_since_finalization,
+        cutoff_millis: config.cutoff(chain.spec.seconds_per_slot).as_millis() as u64,
+        disallowed_offsets: config.disallowed_offsets.offsets().to_vec(),
+    }
+}
+
+pub fn get_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ProposerReOrgConfig, warp::Rejection> {
+    let config = chain.re_org_config.read().clone();
+    Ok(to_api_config(&chain, &config))
+}
+
+/// Check that the values of `update` are within their meaningful ranges.
+fn validate_update<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    update: &ProposerReOrgConfigUpdate,
+) -> Result<(), String> {
+    if let Some(head_threshold) = update.head_threshold {
+        if head_threshold > MAX_HEAD_THRESHOLD {
+            return Err(format!(
+                "head_threshold {head_threshold} exceeds {MAX_HEAD_THRESHOLD}% of a committee"
+            ));
+        }
+    }
+    if let Some(parent_threshold) = update.parent_threshold {
+        // The parent can at most hold the weight of every validator, i.e. 100% of the committees
+        // of an epoch.
+        let max_parent_threshold = 100 * T::EthSpec::slots_per_epoch();
+        if parent_threshold > max_parent_threshold {
+            return Err(format!(
+                "parent_threshold {parent_threshold} exceeds the weight of all validators, \
+                 {max_parent_threshold}% of a committee"
+            ));
+        }
+    }
+    if let Some(cutoff_millis) = update.cutoff_millis {
+        // A re-org block must be proposed before the attestation deadline to gain the votes of its
+        // committee.
+        let attestation_deadline_millis = chain.spec.seconds_per_slot * 1000 / INTERVALS_PER_SLOT;
+        if cutoff_millis == 0 || cutoff_millis >= attestation_deadline_millis {
+            return Err(format!(
+                "cutoff_millis {cutoff_millis} must be between 0 and the attestation deadline of \
+                 {attestation_deadline_millis}ms"
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn update_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    update: ProposerReOrgConfigUpdate,
+) -> Result<ProposerReOrgConfig, warp::Rejection> {
+    validate_update(&chain, &update).map_err(warp_utils::reject::custom_bad_request)?;
+    let disallowed_offsets = update
+        .disallowed_offsets
+        .map(DisallowedReOrgOffsets::new::<T::EthSpec>)
+        .transpose()
+        .map_err(|e| {
+            warp_utils::reject::custom_bad_request(format!("invalid disallowed offsets: {e:?}"))
+        })?;
+
+    let mut config = chain.re_org_config.write();
+    if let Some(enabled) = update.enabled {
+        config.enabled = enabled;
+    }
+    if let Some(head_threshold) = update.head_threshold {
+        config.head_threshold = ReOrgThreshold(head_threshold);
+    }
+    if let Some(parent_threshold) = update.parent_threshold {
+        config.parent_threshold = ReOrgThreshold(parent_threshold);
+    }
+    if let Some(max_epochs_since_finalization) = update.max_epochs_since_finalization {
+        config.max_epochs_since_finalization = max_epochs_since_finalization;
+    }
+    if let Some(cutoff_millis) = update.cutoff_millis {
+        config.cutoff_millis = Some(cutoff_millis);
+    }
+    if let Some(disallowed_offsets) = disallowed_offsets {
+        config.disallowed_offsets = disallowed_offsets;
+    }
+
+    info!(
+        chain.log,
+        "Updated proposer re-org config";
+        "config" => ?*config,
+    );
+
+    Ok(to_api_config(&chain, &config))
+}
@@ -36,8 +36,8 @@
 //! attestations and there's no immediate cause for concern.
 use crate::task_spawner::{Priority, TaskSpawner};
 use beacon_chain::{
-    validator_monitor::timestamp_now, AttestationError, BeaconChain, BeaconChainError,
-    BeaconChainTypes,
+    slashing_guard::Conflict, validator_monitor::timestamp_now, AttestationError, BeaconChain,
+    BeaconChainError, BeaconChainTypes,
 };
 use beacon_processor::work_reprocessing_queue::{QueuedUnaggregate, ReprocessQueueMessage};
 use eth2::types::Failure;
@@ -59,6 +59,7 @@ enum Error {
     Publication,
     ForkChoice(#[allow(dead_code)] BeaconChainError),
     AggregationPool(#[allow(dead_code)] AttestationError),
+    SlashingGuard(#[allow(dead_code)] Conflict),
     ReprocessDisabled,
     ReprocessFull,
     ReprocessTimeout,
@@ -82,6 +83,11 @@ fn verify_and_publish_attestation<T: BeaconChainTypes>(
         .verify_unaggregated_attestation_for_gossip(attestation, None)
         .map_err(Error::Validation)?;
 
+    // Refuse to publish attestations which conflict with those seen from guarded validators.
+    chain
+        .slashing_guard_check_attestation(attestation.indexed_attestation().to_ref())
+        .map_err(Error::SlashingGuard)?;
+
     // Publish.
     network_tx
         .send(NetworkMessage::Publish {
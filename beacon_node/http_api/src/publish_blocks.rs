@@ -21,7 +21,7 @@ use tree_hash::TreeHash;
 use types::{
     AbstractExecPayload, BeaconBlockRef, BlobSidecarList, BlockImportSource, EthSpec, ExecPayload,
     ExecutionBlockHash, ForkName, FullPayload, FullPayloadBellatrix, Hash256, SignedBeaconBlock,
-    SignedBlindedBeaconBlock, VariableList,
+    SignedBlindedBeaconBlock, Slot, VariableList,
 };
 use warp::http::StatusCode;
 use warp::{reply::Response, Rejection, Reply};
@@ -126,34 +126,36 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
     let log_clone = log.clone();
 
     /* if we can form a `GossipVerifiedBlock`, we've passed our basic gossip checks */
-    let (gossip_verified_block, gossip_verified_blobs) =
-        match block_contents.into_gossip_verified_block(&chain) {
-            Ok(b) => b,
-            Err(BlockContentsError::BlockError(BlockError::BlockIsAlreadyKnown(_)))
-            | Err(BlockContentsError::BlobError(
-                beacon_chain::blob_verification::GossipBlobError::RepeatBlob { .. },
-            )) => {
-                // Allow the status code for duplicate blocks to be overridden based on config.
-                return Ok(warp::reply::with_status(
-                    warp::reply::json(&ErrorMessage {
-                        code: duplicate_status_code.as_u16(),
-                        message: "duplicate block".to_string(),
-                        stacktraces: vec![],
-                    }),
-                    duplicate_status_code,
-                )
-                .into_response());
-            }
-            Err(e) => {
-                warn!(
-                    log,
-                    "Not publishing block - not gossip verified";
-                    "slot" => slot,
-                    "error" => %e
-                );
-                return Err(warp_utils::reject::custom_bad_request(e.to_string()));
-            }
-        };
+    let gossip_verification_result = block_contents.into_gossip_verified_block(&chain);
+    // Publish any equivocation found by gossip verification, even if the block is rejected.
+    publish_detected_proposer_slashings(&chain, network_tx, &log);
+    let (gossip_verified_block, gossip_verified_blobs) = match gossip_verification_result {
+        Ok(b) => b,
+        Err(BlockContentsError::BlockError(BlockError::BlockIsAlreadyKnown(_)))
+        | Err(BlockContentsError::BlobError(
+            beacon_chain::blob_verification::GossipBlobError::RepeatBlob { .. },
+        )) => {
+            // Allow the status code for duplicate blocks to be overridden based on config.
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&ErrorMessage {
+                    code: duplicate_status_code.as_u16(),
+                    message: "duplicate block".to_string(),
+                    stacktraces: vec![],
+                }),
+                duplicate_status_code,
+            )
+            .into_response());
+        }
+        Err(e) => {
+            warn!(
+                log,
+                "Not publishing block - not gossip verified";
+                "slot" => slot,
+                "error" => %e
+            );
+            return Err(warp_utils::reject::custom_bad_request(e.to_string()));
+        }
+    };
 
     // Clone here, so we can take advantage of the `Arc`. The block in `BlockContents` is not,
     // `Arc`'d but blobs are.
@@ -168,6 +170,15 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
 
     let block_root = block_root.unwrap_or(gossip_verified_block.block_root);
 
+    // Refuse to publish blocks which conflict with those seen from guarded proposers.
+    if let Err(conflict) =
+        chain.slashing_guard_check_block_header(&block.signed_block_header(), true)
+    {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "block conflicts with a block already seen from this proposer: {conflict:?}"
+        )));
+    }
+
     if let BroadcastValidation::Gossip = validation_level {
         publish_block(
             block.clone(),
@@ -226,15 +237,17 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
         }
     }
 
-    match Box::pin(chain.process_block(
+    let process_result = Box::pin(chain.process_block(
         block_root,
         gossip_verified_block,
         NotifyExecutionLayer::Yes,
         BlockImportSource::HttpApi,
         publish_fn,
     ))
-    .await
-    {
+    .await;
+    publish_detected_proposer_slashings(&chain, network_tx, &log);
+
+    match process_result {
         Ok(AvailabilityProcessingStatus::Imported(root)) => {
             info!(
                 log,
@@ -304,6 +317,32 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
     }
 }
 
+/// Publish any proposer slashings detected while verifying a block received via the HTTP API.
+///
+/// Blocks from gossip have their slashings published by the network beacon processor, which never
+/// sees blocks published via the HTTP API, so they must be published here.
+fn publish_detected_proposer_slashings<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    network_tx: &UnboundedSender<NetworkMessage<T::EthSpec>>,
+    log: &Logger,
+) {
+    let slashings = chain.take_detected_proposer_slashings();
+    if slashings.is_empty() {
+        return;
+    }
+    let messages = slashings
+        .into_iter()
+        .map(|slashing| PubsubMessage::ProposerSlashing(Box::new(slashing)))
+        .collect();
+    if crate::publish_pubsub_messages(network_tx, messages).is_err() {
+        warn!(
+            log,
+            "Unable to publish detected proposer slashings";
+            "info" => "network channel closed"
+        );
+    }
+}
+
 /// Handles a request from the HTTP API for blinded blocks. This converts blinded blocks into full
 /// blocks before publishing.
 pub async fn publish_blinded_block<T: BeaconChainTypes>(
@@ -315,6 +354,24 @@ pub async fn publish_blinded_block<T: BeaconChainTypes>(
     duplicate_status_code: StatusCode,
 ) -> Result<Response, Rejection> {
     let block_root = blinded_block.canonical_root();
+    // Check before the relay is asked to reveal the payload, as it will publish the block. The
+    // signature is yet to be verified, so the header is not recorded.
+    if let Err(conflict) =
+        chain.slashing_guard_check_block_header(&blinded_block.signed_block_header(), false)
+    {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "block conflicts with a block already seen from this proposer: {conflict:?}"
+        )));
+    }
+    if validation_level == BroadcastValidation::ConsensusAndEquivocation {
+        check_blinded_equivocation(
+            &chain,
+            blinded_block.slot(),
+            blinded_block.message().proposer_index(),
+            block_root,
+            &log,
+        )?;
+    }
     let full_block: ProvenancedBlock<T, PublishBlockRequest<T::EthSpec>> =
         reconstruct_block(chain.clone(), block_root, blinded_block, log.clone()).await?;
     publish_block::<T, _>(
@@ -466,6 +523,42 @@ fn late_block_logging<T: BeaconChainTypes, P: AbstractExecPayload<T::EthSpec>>(
     }
 }
 
+/// Reject a blinded block which equivocates with a header that has already been seen.
+///
+/// Full blocks are checked by `check_slashable` before they are published, but a blinded block
+/// with a payload from a builder is published by the relay as soon as the payload is revealed, so
+/// it must be checked beforehand. The rejection matches the one for a full block which fails
+/// gossip verification as an equivocation, so that both endpoints respond alike.
+fn check_blinded_equivocation<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    slot: Slot,
+    proposer_index: u64,
+    block_root: Hash256,
+    log: &Logger,
+) -> Result<(), Rejection> {
+    let is_slashable = chain
+        .observed_slashable
+        .read()
+        .is_slashable(slot, proposer_index, block_root)
+        .map_err(|e| {
+            warp_utils::reject::custom_bad_request(format!(
+                "unable to check for equivocation: {e:?}"
+            ))
+        })?;
+    if is_slashable {
+        warn!(
+            log,
+            "Not publishing equivocating block";
+            "slot" => slot,
+            "proposer_index" => proposer_index,
+        );
+        return Err(warp_utils::reject::custom_bad_request(
+            BlockContentsError::<T::EthSpec>::BlockError(BlockError::Slashable).to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Check if any of the blobs or the block are slashable. Returns `BlockError::Slashable` if so.
 fn check_slashable<T: BeaconChainTypes>(
     chain_clone: &BeaconChain<T>,
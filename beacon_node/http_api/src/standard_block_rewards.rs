@@ -1,10 +1,17 @@
 use crate::sync_committee_rewards::get_state_before_applying_block;
 use crate::BlockId;
 use crate::ExecutionOptimistic;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::StandardBlockReward;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use eth2::lighthouse::{BlockRangeReward, BlockRangeRewardsQuery, StandardBlockReward};
+use slog::{warn, Logger};
+use state_processing::BlockReplayer;
 use std::sync::Arc;
-use warp_utils::reject::beacon_chain_error;
+use types::EthSpec;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The maximum number of epochs of blocks which may be replayed by a single range request.
+const MAX_REQUEST_RANGE_EPOCHS: u64 = 4;
+
 /// The difference between block_rewards and beacon_block_rewards is the later returns block
 /// reward format that satisfies beacon-api specs
 pub fn compute_beacon_block_rewards<T: BeaconChainTypes>(
@@ -25,3 +32,88 @@ pub fn compute_beacon_block_rewards<T: BeaconChainTypes>(
 
     Ok((rewards, execution_optimistic, finalized))
 }
+
+/// Compute the rewards of the canonical blocks with slots in the range given by `query`.
+///
+/// The blocks are replayed on top of a single state, rather than loading the state prior to each
+/// block as `compute_beacon_block_rewards` does.
+pub fn get_beacon_block_rewards_range<T: BeaconChainTypes>(
+    query: BlockRangeRewardsQuery,
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) -> Result<Vec<BlockRangeReward>, warp::Rejection> {
+    let start_slot = query.start;
+    let end_slot = query.end;
+
+    if start_slot > end_slot || start_slot == 0 {
+        return Err(custom_bad_request(format!(
+            "invalid start and end: {}, {}",
+            start_slot, end_slot
+        )));
+    }
+    let max_slots = MAX_REQUEST_RANGE_EPOCHS * T::EthSpec::slots_per_epoch();
+    if end_slot - start_slot >= max_slots {
+        return Err(custom_bad_request(format!(
+            "range of {} slots exceeds the maximum of {} slots",
+            end_slot - start_slot + 1,
+            max_slots
+        )));
+    }
+    let prior_slot = start_slot - 1;
+
+    let end_block_root = chain
+        .block_root_at_slot(end_slot, WhenSlotSkipped::Prev)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| custom_bad_request(format!("block at end slot {} unknown", end_slot)))?;
+
+    let blocks = chain
+        .store
+        .load_blocks_to_replay(start_slot, end_slot, end_block_root)
+        .map_err(|e| beacon_chain_error(e.into()))?;
+
+    let state_root = chain
+        .state_root_at_slot(prior_slot)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| custom_bad_request(format!("prior state at slot {} unknown", prior_slot)))?;
+
+    let state = chain
+        .get_state(&state_root, Some(prior_slot))
+        .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
+        .map_err(beacon_chain_error)?;
+
+    let mut rewards = Vec::with_capacity(blocks.len());
+
+    let block_replayer = BlockReplayer::new(state, &chain.spec)
+        .pre_block_hook(Box::new(|state, block| {
+            let block_root = block.canonical_root();
+            let reward = chain.compute_beacon_block_reward(block.message(), block_root, state)?;
+            rewards.push(BlockRangeReward {
+                slot: block.slot(),
+                block_root,
+                reward,
+            });
+            Ok(())
+        }))
+        .state_root_iter(
+            chain
+                .forwards_iter_state_roots_until(prior_slot, end_slot)
+                .map_err(beacon_chain_error)?,
+        )
+        .no_signature_verification()
+        .minimal_block_root_verification()
+        .apply_blocks(blocks, None)
+        .map_err(beacon_chain_error)?;
+
+    if block_replayer.state_root_miss() {
+        warn!(
+            log,
+            "Block reward state root miss";
+            "start_slot" => start_slot,
+            "end_slot" => end_slot,
+        );
+    }
+
+    drop(block_replayer);
+
+    Ok(rewards)
+}
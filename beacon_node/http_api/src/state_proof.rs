@@ -0,0 +1,35 @@
+use crate::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use std::sync::Arc;
+use types::state_proof::Error;
+use types::StateProof;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// The maximum number of generalized indices in a single request.
+const MAX_GINDICES: usize = 256;
+
+/// Proves the nodes at `gindices` of the state identified by `state_id`.
+pub fn get_state_proof<T: BeaconChainTypes>(
+    state_id: StateId,
+    gindices: Vec<u64>,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<StateProof, warp::Rejection> {
+    if gindices.len() > MAX_GINDICES {
+        return Err(custom_bad_request(format!(
+            "at most {} gindices may be requested at once",
+            MAX_GINDICES
+        )));
+    }
+    let (state, _, _) = state_id.state(&chain)?;
+
+    StateProof::new(&state, &gindices).map_err(|e| match e {
+        Error::NoGeneralizedIndices
+        | Error::InvalidGeneralizedIndex(_)
+        | Error::UnsupportedGeneralizedIndex(_) => {
+            custom_bad_request(format!("unable to prove gindices: {:?}", e))
+        }
+        Error::InvalidProofLength { .. } | Error::ArithError(_) => {
+            custom_server_error(format!("unable to compute proof: {:?}", e))
+        }
+    })
+}
@@ -1,6 +1,7 @@
 use beacon_processor::{BeaconProcessorSend, BlockingOrAsync, Work, WorkEvent};
 use serde::Serialize;
 use std::future::Future;
+use std::time::Duration;
 use tokio::sync::{mpsc::error::TrySendError, oneshot};
 use types::EthSpec;
 use warp::reply::{Reply, Response};
@@ -34,15 +35,44 @@ pub struct TaskSpawner<E: EthSpec> {
     /// Used to send tasks to the `BeaconProcessor`. The tokio executor will be
     /// used if this is `None`.
     beacon_processor_send: Option<BeaconProcessorSend<E>>,
+    /// The time allowed for a task to complete before the request fails.
+    timeout: Option<Duration>,
 }
 
 impl<E: EthSpec> TaskSpawner<E> {
     pub fn new(beacon_processor_send: Option<BeaconProcessorSend<E>>) -> Self {
         Self {
             beacon_processor_send,
+            timeout: None,
         }
     }
 
+    /// Fail requests whose task does not complete within `timeout`.
+    ///
+    /// A blocking task which times out is not interrupted, but its result is discarded.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Await `future`, returning a "service unavailable" rejection if it exceeds `self.timeout`.
+    async fn run_with_timeout<T>(
+        &self,
+        future: impl Future<Output = Result<T, warp::Rejection>>,
+    ) -> Result<T, warp::Rejection> {
+        let Some(timeout) = self.timeout else {
+            return future.await;
+        };
+        tokio::time::timeout(timeout, future)
+            .await
+            .unwrap_or_else(|_| {
+                Err(warp_utils::reject::service_unavailable(format!(
+                    "request timed out after {}ms",
+                    timeout.as_millis()
+                )))
+            })
+    }
+
     /// Executes a "blocking" (non-async) task which returns an arbitrary value.
     pub async fn blocking_task<F, T>(
         self,
@@ -53,32 +83,35 @@ impl<E: EthSpec> TaskSpawner<E> {
         F: FnOnce() -> Result<T, warp::Rejection> + Send + Sync + 'static,
         T: Send + 'static,
     {
-        if let Some(beacon_processor_send) = &self.beacon_processor_send {
-            // Create a closure that will execute `func` and send the result to
-            // a channel held by this thread.
-            let (tx, rx) = oneshot::channel();
-            let process_fn = move || {
-                // Execute the function, collect the return value.
-                let func_result = func();
-                // Send the result down the channel. Ignore any failures; the
-                // send can only fail if the receiver is dropped.
-                let _ = tx.send(func_result);
-            };
+        let task = async {
+            if let Some(beacon_processor_send) = &self.beacon_processor_send {
+                // Create a closure that will execute `func` and send the result to
+                // a channel held by this thread.
+                let (tx, rx) = oneshot::channel();
+                let process_fn = move || {
+                    // Execute the function, collect the return value.
+                    let func_result = func();
+                    // Send the result down the channel. Ignore any failures; the
+                    // send can only fail if the receiver is dropped.
+                    let _ = tx.send(func_result);
+                };
 
-            // Send the function to the beacon processor for execution at some arbitrary time.
-            send_to_beacon_processor(
-                beacon_processor_send,
-                priority,
-                BlockingOrAsync::Blocking(Box::new(process_fn)),
-                rx,
-            )
-            .await
-            .and_then(|x| x)
-        } else {
-            // There is no beacon processor so spawn a task directly on the
-            // tokio executor.
-            warp_utils::task::blocking_task(func).await
-        }
+                // Send the function to the beacon processor for execution at some arbitrary time.
+                send_to_beacon_processor(
+                    beacon_processor_send,
+                    priority,
+                    BlockingOrAsync::Blocking(Box::new(process_fn)),
+                    rx,
+                )
+                .await
+                .and_then(|x| x)
+            } else {
+                // There is no beacon processor so spawn a task directly on the
+                // tokio executor.
+                warp_utils::task::blocking_task(func).await
+            }
+        };
+        self.run_with_timeout(task).await
     }
 
     /// Executes a "blocking" (non-async) task which returns a `Response`.
@@ -123,37 +156,40 @@ impl<E: EthSpec> TaskSpawner<E> {
         priority: Priority,
         func: impl Future<Output = Result<Response, warp::Rejection>> + Send + Sync + 'static,
     ) -> Result<Response, warp::Rejection> {
-        if let Some(beacon_processor_send) = &self.beacon_processor_send {
-            // Create a wrapper future that will execute `func` and send the
-            // result to a channel held by this thread.
-            let (tx, rx) = oneshot::channel();
-            let process_fn = async move {
-                // Await the future, collect the return value.
-                let func_result = func.await;
-                // Send the result down the channel. Ignore any failures; the
-                // send can only fail if the receiver is dropped.
-                let _ = tx.send(func_result);
-            };
+        let task = async {
+            if let Some(beacon_processor_send) = &self.beacon_processor_send {
+                // Create a wrapper future that will execute `func` and send the
+                // result to a channel held by this thread.
+                let (tx, rx) = oneshot::channel();
+                let process_fn = async move {
+                    // Await the future, collect the return value.
+                    let func_result = func.await;
+                    // Send the result down the channel. Ignore any failures; the
+                    // send can only fail if the receiver is dropped.
+                    let _ = tx.send(func_result);
+                };
 
-            // Send the function to the beacon processor for execution at some arbitrary time.
-            send_to_beacon_processor(
-                beacon_processor_send,
-                priority,
-                BlockingOrAsync::Async(Box::pin(process_fn)),
-                rx,
-            )
-            .await
-            .and_then(|x| x)
-        } else {
-            // There is no beacon processor so spawn a task directly on the
-            // tokio executor.
-            tokio::task::spawn(func)
+                // Send the function to the beacon processor for execution at some arbitrary time.
+                send_to_beacon_processor(
+                    beacon_processor_send,
+                    priority,
+                    BlockingOrAsync::Async(Box::pin(process_fn)),
+                    rx,
+                )
                 .await
-                .map_err(|_| {
-                    warp_utils::reject::custom_server_error("Tokio failed to spawn task".into())
-                })
                 .and_then(|x| x)
-        }
+            } else {
+                // There is no beacon processor so spawn a task directly on the
+                // tokio executor.
+                tokio::task::spawn(func)
+                    .await
+                    .map_err(|_| {
+                        warp_utils::reject::custom_server_error("Tokio failed to spawn task".into())
+                    })
+                    .and_then(|x| x)
+            }
+        };
+        self.run_with_timeout(task).await
     }
 }
 
@@ -182,7 +218,7 @@ async fn send_to_beacon_processor<E: EthSpec, T>(
         Err(TrySendError::Closed(_)) => "The task was dropped. The server is shutting down.",
     };
 
-    Err(warp_utils::reject::custom_server_error(
+    Err(warp_utils::reject::service_unavailable(
         error_message.to_string(),
     ))
 }
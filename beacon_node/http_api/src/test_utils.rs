@@ -15,16 +15,19 @@ use lighthouse_network::{
         ConnectionId, NetworkBehaviour,
     },
     rpc::methods::{MetaData, MetaDataV2},
-    types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield, SyncState},
-    ConnectedPoint, Enr, NetworkGlobals, PeerId, PeerManager,
+    types::{
+        is_traced_received_topic, EnrAttestationBitfield, EnrSyncCommitteeBitfield, SyncState,
+        DEFAULT_GOSSIP_TRACE_CAPACITY,
+    },
+    ConnectedPoint, Enr, MessageTrace, NetworkGlobals, PeerId, PeerManager,
 };
-use logging::test_logger;
+use logging::{test_logger, RuntimeLogLevels};
 use network::{NetworkReceivers, NetworkSenders};
 use sensitive_url::SensitiveUrl;
 use slog::Logger;
 use std::future::Future;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use store::MemoryStore;
 use task_executor::test_utils::TestRuntime;
@@ -132,9 +135,28 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     test_runtime: &TestRuntime,
     log: Logger,
 ) -> ApiServer<T, impl Future<Output = ()>> {
-    // Use port 0 to allocate a new unused port.
-    let port = 0;
+    create_api_server_with_config(
+        chain,
+        test_runtime,
+        log,
+        Config {
+            enabled: true,
+            // Use port 0 to allocate a new unused port.
+            listen_port: 0,
+            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+            enable_light_client_server: true,
+            ..Config::default()
+        },
+    )
+    .await
+}
 
+pub async fn create_api_server_with_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    test_runtime: &TestRuntime,
+    log: Logger,
+    config: Config,
+) -> ApiServer<T, impl Future<Output = ()>> {
     let (network_senders, network_receivers) = NetworkSenders::new();
 
     // Default metadata
@@ -145,13 +167,12 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     });
     let enr_key = CombinedKey::generate_secp256k1();
     let enr = Enr::builder().build(&enr_key).unwrap();
-    let network_globals = Arc::new(NetworkGlobals::new(
-        enr.clone(),
-        meta_data,
-        vec![],
-        false,
-        &log,
-    ));
+    let mut network_globals = NetworkGlobals::new(enr.clone(), meta_data, vec![], false, &log);
+    network_globals.gossip_trace = Some(Arc::new(Mutex::new(MessageTrace::new(
+        DEFAULT_GOSSIP_TRACE_CAPACITY,
+        is_traced_received_topic,
+    ))));
+    let network_globals = Arc::new(network_globals);
 
     // Only a peer manager can add peers, so we create a dummy manager.
     let config = lighthouse_network::peer_manager::config::Config::default();
@@ -217,13 +238,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     .unwrap();
 
     let ctx = Arc::new(Context {
-        config: Config {
-            enabled: true,
-            listen_port: port,
-            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
-            enable_light_client_server: true,
-            ..Config::default()
-        },
+        config,
         chain: Some(chain),
         network_senders: Some(network_senders),
         network_globals: Some(network_globals),
@@ -231,6 +246,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         beacon_processor_reprocess_send: Some(reprocess_send),
         eth1_service: Some(eth1_service),
         sse_logging_components: None,
+        runtime_log_levels: Some(RuntimeLogLevels::new()),
         log,
     });
 
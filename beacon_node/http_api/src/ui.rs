@@ -1,7 +1,11 @@
 use beacon_chain::{
-    validator_monitor::HISTORIC_EPOCHS, BeaconChain, BeaconChainError, BeaconChainTypes,
+    validator_monitor::{
+        ATTESTATION_EFFECTIVENESS_EPOCHS, ATTESTATION_EFFECTIVENESS_METRIC_EPOCHS, HISTORIC_EPOCHS,
+    },
+    BeaconChain, BeaconChainError, BeaconChainTypes,
 };
-use eth2::types::{Epoch, ValidatorStatus};
+use eth2::lighthouse::{ValidatorEpochPerformance, ValidatorPerformanceQuery};
+use eth2::types::{Epoch, EthSpec, ValidatorStatus};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -255,3 +259,146 @@ pub fn post_validator_monitor_metrics<T: BeaconChainTypes>(
 
     Ok(ValidatorMetricsResponse { validators })
 }
+
+#[derive(PartialEq, Serialize, Deserialize)]
+pub struct AttestationEffectivenessQuery {
+    epochs: Option<u64>,
+}
+
+#[derive(PartialEq, Serialize, Deserialize)]
+pub struct ValidatorAttestationEffectiveness {
+    rank: usize,
+    index: u64,
+    epochs: u64,
+    attestations_included: u64,
+    attestations_missed: u64,
+    source_hits: u64,
+    target_hits: u64,
+    head_hits: u64,
+    mean_inclusion_distance: Option<f64>,
+    effectiveness: f64,
+}
+
+#[derive(PartialEq, Serialize, Deserialize)]
+pub struct AttestationEffectivenessResponse {
+    start_epoch: Epoch,
+    validators: Vec<ValidatorAttestationEffectiveness>,
+}
+
+/// Returns the attestation effectiveness of the monitored validators over the last `epochs`,
+/// ranked from most to least effective.
+pub fn get_attestation_effectiveness<T: BeaconChainTypes>(
+    query: AttestationEffectivenessQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<AttestationEffectivenessResponse, warp::Rejection> {
+    let epochs = query
+        .epochs
+        .unwrap_or(ATTESTATION_EFFECTIVENESS_METRIC_EPOCHS);
+    if epochs == 0 || epochs > ATTESTATION_EFFECTIVENESS_EPOCHS as u64 {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "epochs must be between 1 and {}",
+            ATTESTATION_EFFECTIVENESS_EPOCHS
+        )));
+    }
+
+    let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
+    let start_epoch = current_epoch.saturating_sub(epochs);
+
+    let validators = chain
+        .validator_monitor
+        .read()
+        .get_attestation_effectiveness(start_epoch)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (index, summary))| ValidatorAttestationEffectiveness {
+            rank: i + 1,
+            index,
+            epochs: summary.epochs,
+            attestations_included: summary.included,
+            attestations_missed: summary.epochs - summary.included,
+            source_hits: summary.source_hits,
+            target_hits: summary.target_hits,
+            head_hits: summary.head_hits,
+            mean_inclusion_distance: summary.mean_inclusion_distance,
+            effectiveness: summary.effectiveness,
+        })
+        .collect();
+
+    Ok(AttestationEffectivenessResponse {
+        start_epoch,
+        validators,
+    })
+}
+
+/// Returns the outcome of the duties of each monitored validator in each epoch of the range given
+/// by `query`, ordered by epoch and then validator index.
+///
+/// Epochs after the current epoch are ignored, as are epochs for which the validator monitor holds
+/// no history for a validator.
+pub fn get_validator_performance<T: BeaconChainTypes>(
+    query: ValidatorPerformanceQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<ValidatorEpochPerformance>, warp::Rejection> {
+    let start_epoch = query.start_epoch;
+    if start_epoch > query.end_epoch {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "invalid start and end: {}, {}",
+            start_epoch, query.end_epoch
+        )));
+    }
+    let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
+    let end_epoch = std::cmp::min(query.end_epoch, current_epoch);
+    if end_epoch.saturating_sub(start_epoch) >= ATTESTATION_EFFECTIVENESS_EPOCHS as u64 {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "at most {} epochs may be requested",
+            ATTESTATION_EFFECTIVENESS_EPOCHS
+        )));
+    }
+
+    let validator_monitor = chain.validator_monitor.read();
+    let mut missed_blocks = HashMap::<(u64, Epoch), u64>::new();
+    for (validator_index, slot) in validator_monitor.get_monitored_validator_missed_blocks() {
+        *missed_blocks
+            .entry((validator_index, slot.epoch(T::EthSpec::slots_per_epoch())))
+            .or_default() += 1;
+    }
+
+    let mut performance = vec![];
+    for validator in validator_monitor.monitored_validators() {
+        let Some(validator_index) = validator.index else {
+            continue;
+        };
+        let summaries = validator.summaries.read();
+        let attestation_effectiveness = validator.attestation_effectiveness.read();
+
+        for epoch in (start_epoch.as_u64()..=end_epoch.as_u64()).map(Epoch::new) {
+            let summary = summaries.get(&epoch);
+            let attestation = attestation_effectiveness.get(&epoch);
+            let blocks_missed = missed_blocks
+                .get(&(validator_index, epoch))
+                .copied()
+                .unwrap_or(0);
+            if summary.is_none() && attestation.is_none() && blocks_missed == 0 {
+                continue;
+            }
+
+            performance.push(ValidatorEpochPerformance {
+                epoch,
+                validator_index,
+                attestation_included: attestation.map(|a| a.included),
+                attestation_source_hit: attestation.map(|a| a.source_hit),
+                attestation_target_hit: attestation.map(|a| a.target_hit),
+                attestation_head_hit: attestation.map(|a| a.head_hit),
+                attestation_inclusion_distance: attestation.and_then(|a| a.inclusion_distance),
+                blocks_proposed: summary.map(|s| s.blocks as u64),
+                blocks_missed,
+                sync_committee_messages: summary.map(|s| s.sync_committee_messages as u64),
+                sync_signature_block_inclusions: summary
+                    .map(|s| s.sync_signature_block_inclusions as u64),
+            });
+        }
+    }
+    performance.sort_by_key(|p| (p.epoch, p.validator_index));
+
+    Ok(performance)
+}
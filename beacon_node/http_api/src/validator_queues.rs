@@ -0,0 +1,247 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ValidatorQueue, ValidatorQueues};
+use std::sync::Arc;
+use types::{BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, RelativeEpoch};
+use warp_utils::reject::beacon_state_error;
+
+/// Compute the validator queues of the head state.
+pub fn get_validator_queues<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ValidatorQueues, warp::Rejection> {
+    let mut state = chain.head_snapshot().beacon_state.clone();
+    state
+        .build_committee_cache(RelativeEpoch::Current, &chain.spec)
+        .map_err(beacon_state_error)?;
+    state
+        .build_total_active_balance_cache(&chain.spec)
+        .map_err(beacon_state_error)?;
+    compute_validator_queues(&state, &chain.spec).map_err(beacon_state_error)
+}
+
+/// The wait times are estimates for an item joining the back of each queue now. They include the
+/// delay between a validator leaving a queue and its activation or exit, but not the time for its
+/// eligibility for activation to be finalized.
+fn compute_validator_queues<E: EthSpec>(
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<ValidatorQueues, BeaconStateError> {
+    let current_epoch = state.current_epoch();
+    let activation_exit_epoch = spec.compute_activation_exit_epoch(current_epoch)?;
+    let seconds_per_epoch = spec.seconds_per_slot.saturating_mul(E::slots_per_epoch());
+    let queue = |length, balance, churn_limit, processed_epoch: Epoch| {
+        let wait_epochs = processed_epoch.saturating_sub(current_epoch).as_u64();
+        ValidatorQueue {
+            length,
+            balance,
+            churn_limit,
+            wait_epochs,
+            wait_seconds: wait_epochs.saturating_mul(seconds_per_epoch),
+        }
+    };
+    // The epoch at which the back of a queue is processed, if `churn_limit` is dequeued each epoch.
+    let dequeued_epoch = |queued: u64, churn_limit: u64| {
+        activation_exit_epoch.saturating_add(queued.div_ceil(churn_limit.max(1)))
+    };
+
+    let mut activation_length = 0;
+    let mut activation_balance = 0_u64;
+    let mut exit_length = 0;
+    let mut exit_balance = 0_u64;
+    let mut latest_exit_epoch = activation_exit_epoch;
+    let mut latest_exit_epoch_churn = 0;
+    for validator in state.validators().iter() {
+        if validator.activation_eligibility_epoch != spec.far_future_epoch
+            && validator.activation_epoch == spec.far_future_epoch
+        {
+            activation_length += 1;
+            activation_balance = activation_balance.saturating_add(validator.effective_balance);
+        }
+        if validator.exit_epoch != spec.far_future_epoch {
+            if validator.exit_epoch > current_epoch {
+                exit_length += 1;
+                exit_balance = exit_balance.saturating_add(validator.effective_balance);
+            }
+            if validator.exit_epoch > latest_exit_epoch {
+                latest_exit_epoch = validator.exit_epoch;
+                latest_exit_epoch_churn = 1;
+            } else if validator.exit_epoch == latest_exit_epoch {
+                latest_exit_epoch_churn += 1;
+            }
+        }
+    }
+
+    if let BeaconState::Electra(electra) = state {
+        let churn_limit = state.get_activation_exit_churn_limit(spec)?;
+
+        let deposit_balance = electra
+            .pending_balance_deposits
+            .iter()
+            .fold(0_u64, |total, deposit| total.saturating_add(deposit.amount));
+        let activation = queue(
+            electra.pending_balance_deposits.len(),
+            deposit_balance,
+            churn_limit,
+            dequeued_epoch(
+                deposit_balance.saturating_sub(electra.deposit_balance_to_consume),
+                churn_limit,
+            ),
+        );
+
+        let exit = queue(
+            exit_length,
+            exit_balance,
+            churn_limit,
+            std::cmp::max(electra.earliest_exit_epoch, activation_exit_epoch),
+        );
+
+        let consolidation_balance = electra
+            .pending_consolidations
+            .iter()
+            .filter_map(|consolidation| {
+                state
+                    .validators()
+                    .get(consolidation.source_index as usize)
+                    .map(|validator| validator.effective_balance)
+            })
+            .fold(0_u64, u64::saturating_add);
+        let consolidation = queue(
+            electra.pending_consolidations.len(),
+            consolidation_balance,
+            state.get_consolidation_churn_limit(spec)?,
+            std::cmp::max(electra.earliest_consolidation_epoch, activation_exit_epoch),
+        );
+
+        Ok(ValidatorQueues {
+            epoch: current_epoch,
+            activation,
+            exit,
+            consolidation: Some(consolidation),
+            pending_partial_withdrawals: Some(electra.pending_partial_withdrawals.len()),
+        })
+    } else {
+        let activation_churn_limit = state.get_activation_churn_limit(spec)?;
+        let activation = queue(
+            activation_length,
+            activation_balance,
+            activation_churn_limit,
+            dequeued_epoch(activation_length as u64, activation_churn_limit),
+        );
+
+        // As in `initiate_validator_exit`, a new exit joins the latest exit epoch unless it is full.
+        let exit_churn_limit = state.get_validator_churn_limit(spec)?;
+        let exit_epoch = if latest_exit_epoch_churn >= exit_churn_limit {
+            latest_exit_epoch.saturating_add(1_u64)
+        } else {
+            latest_exit_epoch
+        };
+        let exit = queue(exit_length, exit_balance, exit_churn_limit, exit_epoch);
+
+        Ok(ValidatorQueues {
+            epoch: current_epoch,
+            activation,
+            exit,
+            consolidation: None,
+            pending_partial_withdrawals: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{test_utils::generate_deterministic_keypairs, Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+
+    fn genesis_state(spec: &ChainSpec) -> BeaconState<E> {
+        let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT);
+        let mut state =
+            genesis::interop_genesis_state(&keypairs, 0, Hash256::repeat_byte(0x42), None, spec)
+                .unwrap();
+        state
+            .build_committee_cache(RelativeEpoch::Current, spec)
+            .unwrap();
+        state.build_total_active_balance_cache(spec).unwrap();
+        state
+    }
+
+    #[test]
+    fn empty_queues() {
+        let spec = E::default_spec();
+        let state = genesis_state(&spec);
+        let queues = compute_validator_queues(&state, &spec).unwrap();
+        let activation_exit_epoch = spec.compute_activation_exit_epoch(Epoch::new(0)).unwrap();
+
+        assert_eq!(queues.activation.length, 0);
+        assert_eq!(
+            queues.activation.wait_epochs,
+            activation_exit_epoch.as_u64()
+        );
+        assert_eq!(queues.exit.length, 0);
+        assert_eq!(queues.exit.wait_epochs, activation_exit_epoch.as_u64());
+        assert_eq!(queues.consolidation, None);
+        assert_eq!(queues.pending_partial_withdrawals, None);
+    }
+
+    #[test]
+    fn activation_queue_wait() {
+        let spec = E::default_spec();
+        let mut state = genesis_state(&spec);
+        let churn_limit = state.get_activation_churn_limit(&spec).unwrap();
+        let queued = churn_limit * 2 + 1;
+        for i in 0..queued as usize {
+            state.validators_mut().get_mut(i).unwrap().activation_epoch = spec.far_future_epoch;
+        }
+
+        let queues = compute_validator_queues(&state, &spec).unwrap();
+        let activation_exit_epoch = spec.compute_activation_exit_epoch(Epoch::new(0)).unwrap();
+
+        assert_eq!(queues.activation.length, queued as usize);
+        assert_eq!(
+            queues.activation.balance,
+            queued * spec.max_effective_balance
+        );
+        assert_eq!(queues.activation.churn_limit, churn_limit);
+        // The back of the queue is dequeued in the third epoch.
+        assert_eq!(
+            queues.activation.wait_epochs,
+            activation_exit_epoch.as_u64() + 3
+        );
+        assert_eq!(
+            queues.activation.wait_seconds,
+            queues.activation.wait_epochs * spec.seconds_per_slot * E::slots_per_epoch()
+        );
+    }
+
+    #[test]
+    fn exit_queue_wait() {
+        let spec = E::default_spec();
+        let mut state = genesis_state(&spec);
+        let churn_limit = state.get_validator_churn_limit(&spec).unwrap();
+        let activation_exit_epoch = spec.compute_activation_exit_epoch(Epoch::new(0)).unwrap();
+
+        // Exits which don't fill the churn of the latest exit epoch leave room for another.
+        for i in 0..churn_limit as usize - 1 {
+            state.validators_mut().get_mut(i).unwrap().exit_epoch = activation_exit_epoch;
+        }
+        let queues = compute_validator_queues(&state, &spec).unwrap();
+        assert_eq!(queues.exit.length, churn_limit as usize - 1);
+        assert_eq!(queues.exit.wait_epochs, activation_exit_epoch.as_u64());
+
+        // Once the churn is full, a new exit is pushed to the next epoch.
+        state
+            .validators_mut()
+            .get_mut(VALIDATOR_COUNT - 1)
+            .unwrap()
+            .exit_epoch = activation_exit_epoch;
+        let queues = compute_validator_queues(&state, &spec).unwrap();
+        assert_eq!(queues.exit.length, churn_limit as usize);
+        assert_eq!(
+            queues.exit.balance,
+            churn_limit * spec.max_effective_balance
+        );
+        assert_eq!(queues.exit.wait_epochs, activation_exit_epoch.as_u64() + 1);
+    }
+}
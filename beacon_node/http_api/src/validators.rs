@@ -4,72 +4,130 @@ use eth2::types::{
     self as api_types, ExecutionOptimisticFinalizedResponse, ValidatorBalanceData, ValidatorData,
     ValidatorId, ValidatorStatus,
 };
+use std::io::{self, Write};
+use std::ops::Range;
 use std::{collections::HashSet, sync::Arc};
+use types::{BeaconState, Validator};
+use warp::reply::Response;
 
+/// Returns the validators of the state at `state_id` which match the query, as a streamed JSON
+/// response.
+///
+/// The response is written directly from the state, so the full set of validators is never held
+/// in memory as `ValidatorData` or JSON. Validators are selected using their ids, or the range
+/// `start_index..end_index`, before any state data is read.
 pub fn get_beacon_state_validators<T: BeaconChainTypes>(
     state_id: StateId,
     chain: Arc<BeaconChain<T>>,
-    query_ids: &Option<Vec<ValidatorId>>,
-    query_statuses: &Option<Vec<ValidatorStatus>>,
-) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>, warp::Rejection> {
-    let (data, execution_optimistic, finalized) = state_id
-        .map_state_and_execution_optimistic_and_finalized(
-            &chain,
-            |state, execution_optimistic, finalized| {
-                let epoch = state.current_epoch();
-                let far_future_epoch = chain.spec.far_future_epoch;
-                let ids_filter_set: Option<HashSet<&ValidatorId>> =
-                    query_ids.as_ref().map(HashSet::from_iter);
+    query_ids: Option<Vec<ValidatorId>>,
+    query_statuses: Option<Vec<ValidatorStatus>>,
+    start_index: Option<u64>,
+    end_index: Option<u64>,
+) -> Result<Response, warp::Rejection> {
+    let (state, execution_optimistic, finalized) = state_id.state(&chain)?;
 
-                Ok((
-                    state
-                        .validators()
-                        .iter()
-                        .zip(state.balances().iter())
-                        .enumerate()
-                        // filter by validator id(s) if provided
-                        .filter(|(index, (validator, _))| {
-                            ids_filter_set.as_ref().map_or(true, |ids_set| {
-                                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
-                                    || ids_set.contains(&ValidatorId::Index(*index as u64))
-                            })
-                        })
-                        // filter by status(es) if provided and map the result
-                        .filter_map(|(index, (validator, balance))| {
-                            let status = api_types::ValidatorStatus::from_validator(
-                                validator,
-                                epoch,
-                                far_future_epoch,
-                            );
+    let num_validators = state.validators().len();
+    let start = start_index.map_or(0, |i| i as usize).min(num_validators);
+    let end = end_index
+        .map_or(num_validators, |i| i as usize)
+        .clamp(start, num_validators);
+    let selected_indices = query_ids
+        .map(|ids| resolve_validator_indices(&chain, &state, &ids, start..end))
+        .transpose()?;
 
-                            let status_matches = query_statuses.as_ref().map_or(true, |statuses| {
-                                statuses.contains(&status)
-                                    || statuses.contains(&status.superstatus())
-                            });
+    let epoch = state.current_epoch();
+    let far_future_epoch = chain.spec.far_future_epoch;
 
-                            if status_matches {
-                                Some(ValidatorData {
-                                    index: index as u64,
-                                    balance: *balance,
-                                    status,
-                                    validator: validator.clone(),
-                                })
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                    execution_optimistic,
-                    finalized,
-                ))
-            },
-        )?;
+    Ok(warp_utils::reply::stream(
+        "application/json",
+        move |writer| {
+            write!(
+                writer,
+                r#"{{"execution_optimistic":{},"finalized":{},"data":["#,
+                execution_optimistic, finalized
+            )?;
 
-    Ok(ExecutionOptimisticFinalizedResponse {
-        data,
-        execution_optimistic: Some(execution_optimistic),
-        finalized: Some(finalized),
-    })
+            let mut first = true;
+            let mut write_validator = |index: usize, validator: &Validator, balance: u64| {
+                let status = ValidatorStatus::from_validator(validator, epoch, far_future_epoch);
+                let status_matches = query_statuses.as_ref().map_or(true, |statuses| {
+                    statuses.contains(&status) || statuses.contains(&status.superstatus())
+                });
+                if !status_matches {
+                    return Ok(());
+                }
+                if !first {
+                    writer.write_all(b",")?;
+                }
+                first = false;
+                let data = ValidatorData {
+                    index: index as u64,
+                    balance,
+                    status,
+                    validator: validator.clone(),
+                };
+                serde_json::to_writer(&mut *writer, &data).map_err(io::Error::from)
+            };
+
+            if let Some(indices) = selected_indices {
+                for index in indices {
+                    if let (Some(validator), Some(balance)) =
+                        (state.validators().get(index), state.balances().get(index))
+                    {
+                        write_validator(index, validator, *balance)?;
+                    }
+                }
+            } else {
+                let validators = state
+                    .validators()
+                    .iter_from(start)
+                    .map_err(milhouse_error)?;
+                let balances = state.balances().iter_from(start).map_err(milhouse_error)?;
+                for (index, (validator, balance)) in (start..end).zip(validators.zip(balances)) {
+                    write_validator(index, validator, *balance)?;
+                }
+            }
+
+            writer.write_all(b"]}")
+        },
+    ))
+}
+
+/// Returns the sorted, de-duplicated indices of the validators in `ids` which are present in
+/// `state` and within `range`.
+fn resolve_validator_indices<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    state: &BeaconState<T::EthSpec>,
+    ids: &[ValidatorId],
+    range: Range<usize>,
+) -> Result<Vec<usize>, warp::Rejection> {
+    let mut indices = Vec::with_capacity(ids.len());
+    for id in ids {
+        let index = match id {
+            ValidatorId::Index(index) => Some(*index as usize),
+            // The pubkey cache is append-only, so an index found for the head is also correct
+            // for earlier states which contain the validator.
+            ValidatorId::PublicKey(pubkey) => chain
+                .validator_index(pubkey)
+                .map_err(warp_utils::reject::beacon_chain_error)?
+                .filter(|&index| {
+                    state
+                        .validators()
+                        .get(index)
+                        .map_or(false, |validator| validator.pubkey == *pubkey)
+                }),
+        };
+        if let Some(index) = index.filter(|index| range.contains(index)) {
+            indices.push(index);
+        }
+    }
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+fn milhouse_error(e: types::milhouse::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{:?}", e))
 }
 
 pub fn get_beacon_state_validator_balances<T: BeaconChainTypes>(
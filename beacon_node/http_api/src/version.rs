@@ -4,6 +4,7 @@ use eth2::{
     EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER, SSZ_CONTENT_TYPE_HEADER,
 };
 use serde::Serialize;
+use ssz::Encode;
 use types::{
     fork_versioned_response::{
         ExecutionOptimisticFinalizedForkVersionedResponse, ExecutionOptimisticFinalizedMetadata,
@@ -64,6 +65,11 @@ pub fn add_ssz_content_type_header<T: Reply>(reply: T) -> Response {
     reply::with_header(reply, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER).into_response()
 }
 
+/// Create a response with the SSZ encoding of `item` as its body.
+pub fn ssz_response<T: Encode>(item: &T) -> Response {
+    add_ssz_content_type_header(Response::new(item.as_ssz_bytes().into()))
+}
+
 /// Add the `Eth-Consensus-Version` header to a response.
 pub fn add_consensus_version_header<T: Reply>(reply: T, fork_name: ForkName) -> Response {
     reply::with_header(reply, CONSENSUS_VERSION_HEADER, fork_name.to_string()).into_response()
@@ -1,11 +1,13 @@
 use beacon_chain::{
     test_utils::{AttestationStrategy, BlockStrategy},
-    GossipVerifiedBlock, IntoGossipVerifiedBlockContents,
+    ChainConfig, GossipVerifiedBlock, IntoGossipVerifiedBlockContents,
 };
 use eth2::reqwest::StatusCode;
 use eth2::types::{BroadcastValidation, PublishBlockRequest};
 use http_api::test_utils::InteractiveTester;
 use http_api::{publish_blinded_block, publish_block, reconstruct_block, ProvenancedBlock};
+use lighthouse_network::PubsubMessage;
+use network::NetworkMessage;
 use std::sync::Arc;
 use types::{Epoch, EthSpec, ForkName, Hash256, MainnetEthSpec, Slot};
 use warp::Rejection;
@@ -28,6 +30,7 @@ type E = MainnetEthSpec;
  *  -  `broadcast_validation=consensus_and_equivocation`
  *    -  Invalid (400)
  *    -  Invalid due to early equivocation (400)
+ *    -  Invalid due to equivocation with a seen header (400)
  *    -  Only gossip (400)
  *    -  Only consensus (400)
  *    -  Pass (200)
@@ -747,6 +750,74 @@ pub async fn equivocation_full_pass() {
         .block_is_known_to_fork_choice(&block.canonical_root()));
 }
 
+/// This test checks that a block which equivocates with a header that has been seen, but not
+/// imported, is rejected when using `broadcast_validation=consensus_and_equivocation`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn equivocation_seen_header() {
+    let validation_level: Option<BroadcastValidation> =
+        Some(BroadcastValidation::ConsensusAndEquivocation);
+
+    // Validator count needs to be at least 32 or proposer boost gets set to 0 when computing
+    // `validator_count // 32`.
+    let validator_count = 64;
+    let num_initial: u64 = 31;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+
+    // Create some chain depth.
+    tester.harness.advance_slot();
+    tester
+        .harness
+        .extend_chain(
+            num_initial as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    tester.harness.advance_slot();
+
+    let slot_a = Slot::new(num_initial);
+    let slot_b = slot_a + 1;
+
+    let state_a = tester.harness.get_current_state();
+    let ((block_a, _), _) = tester.harness.make_block(state_a.clone(), slot_b).await;
+    let ((block_b, blobs_b), _) = tester.harness.make_block(state_a, slot_b).await;
+    assert_ne!(block_a.canonical_root(), block_b.canonical_root());
+
+    /* only the header of `block_a` has been seen, e.g. from a blob sidecar */
+    tester
+        .harness
+        .chain
+        .observed_slashable
+        .write()
+        .observe_slashable(
+            block_a.slot(),
+            block_a.message().proposer_index(),
+            block_a.canonical_root(),
+        )
+        .unwrap();
+
+    let response: Result<(), eth2::Error> = tester
+        .client
+        .post_beacon_blocks_v2(
+            &PublishBlockRequest::new(block_b.clone(), blobs_b),
+            validation_level,
+        )
+        .await;
+    assert!(response.is_err());
+
+    let error_response: eth2::Error = response.err().unwrap();
+
+    assert_eq!(error_response.status(), Some(StatusCode::BAD_REQUEST));
+
+    assert!(
+        matches!(error_response, eth2::Error::ServerMessage(err) if err.message == "BAD_REQUEST: proposal for this slot and proposer has already been seen".to_string())
+    );
+    assert!(!tester
+        .harness
+        .chain
+        .block_is_known_to_fork_choice(&block_b.canonical_root()));
+}
+
 /// This test checks that a block that is **invalid** from a gossip perspective gets rejected when using `broadcast_validation=gossip`.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 pub async fn blinded_gossip_invalid() {
@@ -1395,3 +1466,161 @@ pub async fn blinded_equivocation_full_pass() {
         .chain
         .block_is_known_to_fork_choice(&block.canonical_root()));
 }
+
+/// This test checks that a blinded block which equivocates with a header that has been seen, but
+/// not imported, is rejected before its payload is revealed when using
+/// `broadcast_validation=consensus_and_equivocation`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn blinded_equivocation_seen_header() {
+    let validation_level: Option<BroadcastValidation> =
+        Some(BroadcastValidation::ConsensusAndEquivocation);
+
+    // Validator count needs to be at least 32 or proposer boost gets set to 0 when computing
+    // `validator_count // 32`.
+    let validator_count = 64;
+    let num_initial: u64 = 31;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+
+    // Create some chain depth.
+    tester.harness.advance_slot();
+    tester
+        .harness
+        .extend_chain(
+            num_initial as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    tester.harness.advance_slot();
+
+    let slot_a = Slot::new(num_initial);
+    let slot_b = slot_a + 1;
+
+    let state_a = tester.harness.get_current_state();
+    let (block_a, _) = tester
+        .harness
+        .make_blinded_block(state_a.clone(), slot_b)
+        .await;
+    let (block_b, _) = tester.harness.make_blinded_block(state_a, slot_b).await;
+    assert_ne!(block_a.canonical_root(), block_b.canonical_root());
+
+    /* only the header of `block_a` has been seen, e.g. from a blob sidecar */
+    tester
+        .harness
+        .chain
+        .observed_slashable
+        .write()
+        .observe_slashable(
+            block_a.slot(),
+            block_a.message().proposer_index(),
+            block_a.canonical_root(),
+        )
+        .unwrap();
+
+    let response: Result<(), eth2::Error> = tester
+        .client
+        .post_beacon_blinded_blocks_v2(&block_b, validation_level)
+        .await;
+    assert!(response.is_err());
+
+    let error_response: eth2::Error = response.err().unwrap();
+
+    assert_eq!(error_response.status(), Some(StatusCode::BAD_REQUEST));
+
+    assert!(
+        matches!(error_response, eth2::Error::ServerMessage(err) if err.message == "BAD_REQUEST: BlockError(Slashable)".to_string())
+    );
+    assert!(!tester
+        .harness
+        .chain
+        .block_is_known_to_fork_choice(&block_b.canonical_root()));
+}
+
+/// This test checks that a proposer slashing detected while verifying a block published via the
+/// HTTP API is published to the network straight away, even though the block itself is rejected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn equivocation_detected_slashing_is_published() {
+    let validation_level = BroadcastValidation::Gossip;
+
+    // Validator count needs to be at least 32 or proposer boost gets set to 0 when computing
+    // `validator_count // 32`.
+    let validator_count = 64;
+    let num_initial: u64 = 31;
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        Some(Box::new(move |builder| {
+            builder
+                .deterministic_keypairs(validator_count)
+                .fresh_ephemeral_store()
+                .chain_config(ChainConfig {
+                    detect_proposer_equivocations: true,
+                    ..ChainConfig::default()
+                })
+        })),
+        None,
+    )
+    .await;
+    let test_logger = tester.harness.logger().clone();
+
+    // Create some chain depth.
+    tester.harness.advance_slot();
+    tester
+        .harness
+        .extend_chain(
+            num_initial as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    tester.harness.advance_slot();
+
+    let slot_b = Slot::new(num_initial) + 1;
+    let state_a = tester.harness.get_current_state();
+    let ((block_a, blobs_a), _) = tester.harness.make_block(state_a.clone(), slot_b).await;
+    let ((block_b, blobs_b), _) = tester.harness.make_block(state_a, slot_b).await;
+    assert_ne!(block_a.canonical_root(), block_b.canonical_root());
+
+    let (network_tx, mut network_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    publish_block(
+        None,
+        ProvenancedBlock::local(PublishBlockRequest::new(block_a, blobs_a)),
+        tester.harness.chain.clone(),
+        &network_tx,
+        test_logger.clone(),
+        validation_level,
+        StatusCode::ACCEPTED,
+    )
+    .await
+    .expect("first block should be published");
+
+    let publication_result = publish_block(
+        None,
+        ProvenancedBlock::local(PublishBlockRequest::new(block_b, blobs_b)),
+        tester.harness.chain.clone(),
+        &network_tx,
+        test_logger,
+        validation_level,
+        StatusCode::ACCEPTED,
+    )
+    .await;
+    assert!(publication_result.is_err());
+
+    let mut proposer_slashings = vec![];
+    while let Ok(message) = network_rx.try_recv() {
+        if let NetworkMessage::Publish { messages } = message {
+            proposer_slashings.extend(messages.into_iter().filter_map(|message| match message {
+                PubsubMessage::ProposerSlashing(slashing) => Some(slashing),
+                _ => None,
+            }));
+        }
+    }
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(proposer_slashings[0].signed_header_1.message.slot, slot_b,);
+    assert!(tester
+        .harness
+        .chain
+        .take_detected_proposer_slashings()
+        .is_empty());
+}
@@ -5,10 +5,16 @@ use beacon_chain::{
     ChainConfig,
 };
 use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
+use eth2::lighthouse::ProposerReOrgConfigUpdate;
+use eth2::reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    ClientBuilder, StatusCode,
+};
 use eth2::types::ProduceBlockV3Response;
-use eth2::types::{DepositContractData, StateId};
+use eth2::types::{DepositContractData, EventKind, StateId};
+use eth2::{BeaconNodeHttpClient, SensitiveUrl, Timeouts};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
-use http_api::test_utils::InteractiveTester;
+use http_api::test_utils::{create_api_server_with_config, ApiServer, InteractiveTester};
 use parking_lot::Mutex;
 use slot_clock::SlotClock;
 use state_processing::{
@@ -17,6 +23,7 @@ use state_processing::{
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast::error::TryRecvError;
 use types::{
     Address, Epoch, EthSpec, ExecPayload, ExecutionBlockHash, ForkName, MainnetEthSpec,
     MinimalEthSpec, ProposerPreparationData, Slot,
@@ -184,6 +191,8 @@ pub struct ReOrgTest {
     expect_withdrawals_change_on_epoch: bool,
     /// Epoch offsets to avoid proposing reorg blocks at.
     disallowed_offsets: Vec<u64>,
+    /// Whether to disable re-orgs via the HTTP API after startup.
+    disable_via_api: bool,
 }
 
 impl Default for ReOrgTest {
@@ -202,10 +211,88 @@ impl Default for ReOrgTest {
             misprediction: false,
             expect_withdrawals_change_on_epoch: false,
             disallowed_offsets: vec![],
+            disable_via_api: false,
         }
     }
 }
 
+// Test that re-orgs can be disabled at runtime without a restart.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn proposer_boost_re_org_disabled_via_api() {
+    proposer_boost_re_org_test(ReOrgTest {
+        should_re_org: false,
+        disable_via_api: true,
+        ..Default::default()
+    })
+    .await;
+}
+
+// Test that out of range re-org settings are rejected with a 400 and leave the config unchanged.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn proposer_re_org_config_rejects_invalid_values() {
+    let tester = InteractiveTester::<E>::new(None, 32).await;
+    let client = &tester.client;
+    let original = client
+        .get_lighthouse_proposer_re_org_config()
+        .await
+        .unwrap()
+        .data;
+    let attestation_deadline_millis = E::default_spec().seconds_per_slot * 1000 / 3;
+
+    for update in [
+        ProposerReOrgConfigUpdate {
+            head_threshold: Some(101),
+            ..Default::default()
+        },
+        ProposerReOrgConfigUpdate {
+            parent_threshold: Some(100 * E::slots_per_epoch() + 1),
+            ..Default::default()
+        },
+        ProposerReOrgConfigUpdate {
+            cutoff_millis: Some(0),
+            ..Default::default()
+        },
+        ProposerReOrgConfigUpdate {
+            cutoff_millis: Some(attestation_deadline_millis),
+            ..Default::default()
+        },
+        ProposerReOrgConfigUpdate {
+            enabled: Some(false),
+            head_threshold: Some(1000),
+            ..Default::default()
+        },
+    ] {
+        let err = client
+            .post_lighthouse_proposer_re_org_config(&update)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().map(|status| status.as_u16()), Some(400));
+    }
+    assert_eq!(
+        client
+            .get_lighthouse_proposer_re_org_config()
+            .await
+            .unwrap()
+            .data,
+        original
+    );
+
+    // Parent thresholds above a single committee are valid.
+    let config = client
+        .post_lighthouse_proposer_re_org_config(&ProposerReOrgConfigUpdate {
+            head_threshold: Some(100),
+            parent_threshold: Some(160),
+            cutoff_millis: Some(attestation_deadline_millis - 1),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(config.head_threshold, 100);
+    assert_eq!(config.parent_threshold, 160);
+    assert_eq!(config.cutoff_millis, attestation_deadline_millis - 1);
+}
+
 // Test that the beacon node will try to perform proposer boost re-orgs on late blocks when
 // configured.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -388,6 +475,7 @@ pub async fn proposer_boost_re_org_test(
         misprediction,
         expect_withdrawals_change_on_epoch,
         disallowed_offsets,
+        disable_via_api,
     }: ReOrgTest,
 ) {
     assert!(head_slot > 0);
@@ -429,6 +517,21 @@ pub async fn proposer_boost_re_org_test(
     )
     .await;
     let harness = &tester.harness;
+
+    if disable_via_api {
+        let config = tester
+            .client
+            .post_lighthouse_proposer_re_org_config(&ProposerReOrgConfigUpdate {
+                enabled: Some(false),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .data;
+        assert!(!config.enabled);
+        assert_eq!(config.head_threshold, re_org_threshold);
+    }
+
     let mock_el = harness.mock_execution_layer.as_ref().unwrap();
     let execution_ctx = mock_el.server.ctx.clone();
     let slot_clock = &harness.chain.slot_clock;
@@ -578,6 +681,19 @@ pub async fn proposer_boost_re_org_test(
     );
     harness.process_attestations(block_b_head_votes);
 
+    let mut payload_attributes_events = harness
+        .chain
+        .event_handler
+        .as_ref()
+        .unwrap()
+        .subscribe_payload_attributes();
+    let mut proposer_re_org_events = harness
+        .chain
+        .event_handler
+        .as_ref()
+        .unwrap()
+        .subscribe_proposer_re_org();
+
     let payload_lookahead = harness.chain.config.prepare_payload_lookahead;
     let fork_choice_lookahead = Duration::from_millis(500);
     while harness.get_current_slot() != slot_c {
@@ -653,6 +769,42 @@ pub async fn proposer_boost_re_org_test(
         .into();
     assert_eq!(harness.head_block_root(), block_root_c);
 
+    // Check that the last payload attributes event for block C indicates whether a re-org was
+    // intended.
+    let mut last_event = None;
+    loop {
+        match payload_attributes_events.try_recv() {
+            Ok(EventKind::PayloadAttributes(event)) if event.data.proposal_slot == slot_c => {
+                last_event = Some(event.data);
+            }
+            // Only the most recent events are needed, so it doesn't matter if some were missed.
+            Ok(_) | Err(TryRecvError::Lagged(_)) => {}
+            Err(_) => break,
+        }
+    }
+    let last_event = last_event.expect("payload attributes event for block C");
+    assert_eq!(last_event.proposer_re_org, should_re_org);
+    assert_eq!(last_event.parent_block_root, block_c.parent_root());
+    assert_eq!(last_event.head_block_root, Some(block_b_root));
+
+    // Re-orgs disabled via the API are reported with a reason on the events API.
+    if disable_via_api {
+        let mut last_event = None;
+        loop {
+            match proposer_re_org_events.try_recv() {
+                Ok(EventKind::ProposerReOrg(event)) if event.slot == slot_c => {
+                    last_event = Some(event);
+                }
+                Ok(_) | Err(TryRecvError::Lagged(_)) => {}
+                Err(_) => break,
+            }
+        }
+        let last_event = last_event.expect("proposer re-org event for block C");
+        assert!(!last_event.re_org);
+        assert_eq!(last_event.reason.as_deref(), Some("disabled"));
+        assert_eq!(last_event.head_block, block_b_root);
+    }
+
     // Check the fork choice updates that were sent.
     let forkchoice_updates = forkchoice_updates.lock();
     let block_a_exec_hash = block_a
@@ -922,3 +1074,99 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+// Test that requests without a known API token are rejected with a 401, and requests with a token
+// lacking the role required by the endpoint with a 403.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn api_token_authorization() {
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+
+    let token_dir = tempfile::tempdir().unwrap();
+    let token_file = token_dir.path().join("api-tokens.txt");
+    std::fs::write(
+        &token_file,
+        "read-only:read-only-token\nvalidator:validator-token\n",
+    )
+    .unwrap();
+
+    let ApiServer {
+        server,
+        listening_socket,
+        ..
+    } = create_api_server_with_config(
+        harness.chain.clone(),
+        &harness.runtime,
+        harness.logger().clone(),
+        http_api::Config {
+            enabled: true,
+            listen_port: 0,
+            api_token_file: Some(token_file),
+            ..http_api::Config::default()
+        },
+    )
+    .await;
+    tokio::spawn(server);
+
+    let client = |authorization: Option<&str>| {
+        let mut headers = HeaderMap::new();
+        if let Some(authorization) = authorization {
+            headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        }
+        BeaconNodeHttpClient::from_components(
+            SensitiveUrl::parse(&format!(
+                "http://{}:{}",
+                listening_socket.ip(),
+                listening_socket.port()
+            ))
+            .unwrap(),
+            ClientBuilder::new()
+                .default_headers(headers)
+                .build()
+                .unwrap(),
+            Timeouts::set_all(Duration::from_secs(1)),
+        )
+    };
+    let epoch = Epoch::new(0);
+
+    // Missing or unknown tokens.
+    for unauthorized in [
+        client(None),
+        client(Some("Bearer unknown-token")),
+        client(Some("read-only-token")),
+    ] {
+        assert_eq!(
+            unauthorized.get_node_version().await.unwrap_err().status(),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+    }
+
+    // A read-only token may read node data, but not use the validator endpoints.
+    let read_only = client(Some("Bearer read-only-token"));
+    read_only.get_node_version().await.unwrap();
+    assert_eq!(
+        read_only
+            .get_validator_duties_proposer(epoch)
+            .await
+            .unwrap_err()
+            .status(),
+        Some(StatusCode::FORBIDDEN)
+    );
+
+    // A validator token may use the validator endpoints, but not the admin endpoints.
+    let validator = client(Some("Bearer validator-token"));
+    validator.get_node_version().await.unwrap();
+    validator
+        .get_validator_duties_proposer(epoch)
+        .await
+        .unwrap();
+    assert_eq!(
+        validator
+            .get_debug_beacon_heads()
+            .await
+            .unwrap_err()
+            .status(),
+        Some(StatusCode::FORBIDDEN)
+    );
+}
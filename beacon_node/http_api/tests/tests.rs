@@ -1,10 +1,14 @@
 use beacon_chain::test_utils::RelativeSyncCommittee;
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
+    timeliness::{ArrivalKind, TIMELINESS_SETTLE_SLOTS},
     BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{
+        DatabaseBackupRequest, DryRunBlock, EnrUpdateRequest, GossipTrace, LogLevel, LogLevelUpdate,
+    },
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -24,13 +28,14 @@ use http_api::{
     BlockId, StateId,
 };
 use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
 use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
 use state_processing::state_advance::partial_state_advance;
+use std::collections::BTreeMap;
 use std::convert::TryInto;
 use std::sync::Arc;
 use tokio::time::Duration;
@@ -807,6 +812,14 @@ impl ApiTester {
             );
 
             assert_eq!(result, expected, "{:?}", state_id);
+
+            let result_ssz = self
+                .client
+                .get_beacon_states_finality_checkpoints_ssz(state_id.0)
+                .await
+                .unwrap();
+
+            assert_eq!(result_ssz, expected, "{:?}", state_id);
         }
 
         self
@@ -1033,6 +1046,134 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_states_validators_in_range(self) -> Self {
+        let ranges = [
+            (None, None),
+            (Some(1), Some(4)),
+            (Some(4), None),
+            (None, Some(2)),
+            (Some(3), Some(1)),
+            (Some(u64::MAX), None),
+        ];
+        for state_id in self.interesting_state_ids() {
+            for statuses in self.interesting_validator_statuses() {
+                for (start_index, end_index) in ranges {
+                    let result = self
+                        .client
+                        .get_beacon_states_validators_in_range(
+                            state_id.0,
+                            None,
+                            Some(statuses.as_slice()).filter(|s| !s.is_empty()),
+                            start_index,
+                            end_index,
+                        )
+                        .await
+                        .unwrap()
+                        .map(|res| res.data);
+
+                    let expected = state_id.state(&self.chain).ok().map(|(state, _, _)| {
+                        let epoch = state.current_epoch();
+                        let far_future_epoch = self.chain.spec.far_future_epoch;
+                        let start = start_index.unwrap_or(0);
+                        let end = end_index.unwrap_or(u64::MAX);
+
+                        state
+                            .validators()
+                            .iter()
+                            .zip(state.balances().iter())
+                            .enumerate()
+                            .filter(|(i, _)| (start..end).contains(&(*i as u64)))
+                            .filter_map(|(i, (validator, balance))| {
+                                let status = ValidatorStatus::from_validator(
+                                    validator,
+                                    epoch,
+                                    far_future_epoch,
+                                );
+                                (statuses.is_empty()
+                                    || statuses.contains(&status)
+                                    || statuses.contains(&status.superstatus()))
+                                .then(|| ValidatorData {
+                                    index: i as u64,
+                                    balance: *balance,
+                                    status,
+                                    validator: validator.clone(),
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    });
+
+                    assert_eq!(
+                        result, expected,
+                        "{:?} {:?}..{:?}",
+                        state_id, start_index, end_index
+                    );
+                }
+            }
+        }
+
+        self
+    }
+
+    pub async fn test_beacon_states_validator_ids_in_range(self) -> Self {
+        let state_id = CoreStateId::Head;
+        let (state, _, _) = StateId(state_id).state(&self.chain).unwrap();
+        let pubkey =
+            |index: usize| ValidatorId::PublicKey(state.validators().get(index).unwrap().pubkey);
+
+        // Unordered, duplicated and unknown ids, by both index and pubkey.
+        let ids = vec![
+            ValidatorId::Index(5),
+            pubkey(2),
+            ValidatorId::Index(0),
+            ValidatorId::Index(2),
+            ValidatorId::Index(state.validators().len() as u64),
+            ValidatorId::PublicKey(PublicKeyBytes::empty()),
+            pubkey(4),
+        ];
+        let ranges = [
+            (None, None, vec![0, 2, 4, 5]),
+            (Some(1), Some(5), vec![2, 4]),
+            (Some(3), None, vec![4, 5]),
+            (Some(5), Some(3), vec![]),
+        ];
+
+        for (start_index, end_index, expected) in ranges {
+            let result = self
+                .client
+                .get_beacon_states_validators_in_range(
+                    state_id,
+                    Some(&ids),
+                    None,
+                    start_index,
+                    end_index,
+                )
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+
+            let expected = expected
+                .into_iter()
+                .map(|index: usize| {
+                    let validator = state.validators().get(index).unwrap();
+                    ValidatorData {
+                        index: index as u64,
+                        balance: *state.balances().get(index).unwrap(),
+                        status: ValidatorStatus::from_validator(
+                            validator,
+                            state.current_epoch(),
+                            self.chain.spec.far_future_epoch,
+                        ),
+                        validator: validator.clone(),
+                    }
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(result, expected, "{:?}..{:?}", start_index, end_index);
+        }
+
+        self
+    }
+
     pub async fn test_beacon_states_validator_id(self) -> Self {
         for state_id in self.interesting_state_ids() {
             let state_opt = state_id
@@ -2385,13 +2526,9 @@ impl ApiTester {
     }
 
     pub async fn test_get_debug_beacon_heads(self) -> Self {
-        let result = self
-            .client
-            .get_debug_beacon_heads()
-            .await
-            .unwrap()
-            .data
-            .into_iter()
+        let heads = self.client.get_debug_beacon_heads().await.unwrap().data;
+        let result = heads
+            .iter()
             .map(|head| (head.root, head.slot))
             .collect::<Vec<_>>();
 
@@ -2399,6 +2536,42 @@ impl ApiTester {
 
         assert_eq!(result, expected);
 
+        let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+        let finalized_slot = fork_choice
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(E::slots_per_epoch());
+        for head in heads {
+            let metadata = head.metadata.expect("heads should be known to fork choice");
+            assert_eq!(
+                Some(metadata.weight),
+                fork_choice.proto_array().get_weight(&head.root)
+            );
+            assert_eq!(
+                Some(metadata.execution_status),
+                fork_choice
+                    .get_block_execution_status(&head.root)
+                    .map(|status| status.to_string())
+            );
+            assert_eq!(
+                metadata.finalized_distance,
+                (head.slot - finalized_slot).as_u64()
+            );
+            let expected_last_vote_epoch = fork_choice
+                .proto_array()
+                .iter_latest_messages()
+                .filter(|(_, block_root, _)| *block_root == head.root)
+                .map(|(_, _, epoch)| epoch)
+                .max();
+            assert_eq!(metadata.last_vote_epoch, expected_last_vote_epoch);
+            // The tester doesn't monitor any validators.
+            assert!(!metadata.monitored_validator_voted);
+        }
+        drop(fork_choice);
+
+        let legacy_heads = self.client.get_debug_beacon_heads_v1().await.unwrap().data;
+        assert!(legacy_heads.iter().all(|head| head.metadata.is_none()));
+
         self
     }
 
@@ -3699,6 +3872,24 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_builder_registrations(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+        let registrations = self
+            .client
+            .get_lighthouse_builder_registrations()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(registrations.len(), self.validator_keypairs().len());
+        for registration in registrations {
+            assert_eq!(registration.last_seen_epoch, current_epoch);
+            assert_eq!(registration.last_sent_epoch, Some(current_epoch));
+        }
+
+        self
+    }
+
     pub async fn test_post_validator_register_validator_slashed(self) -> Self {
         // slash a validator
         self.client
@@ -5324,7 +5515,36 @@ impl ApiTester {
 
     #[cfg(target_os = "linux")]
     pub async fn test_get_lighthouse_health(self) -> Self {
-        self.client.get_lighthouse_health().await.unwrap();
+        let readiness = self
+            .client
+            .get_lighthouse_health()
+            .await
+            .unwrap()
+            .data
+            .readiness;
+
+        // The test harness has no peers, so the node can never be fully ready.
+        assert_ne!(readiness.status, eth2::lighthouse::ReadinessStatus::Ready);
+        assert!(readiness
+            .reasons
+            .contains(&eth2::lighthouse::DegradationReason::LowPeerCount));
+        assert_eq!(readiness.connected_peers, 0);
+        assert!(readiness.sync_distance.is_some());
+        assert_eq!(
+            readiness.status,
+            eth2::lighthouse::ReadinessStatus::from_reasons(&readiness.reasons)
+        );
+
+        // Strict mode only fails when the node is not ready.
+        let strict = self.client.get_lighthouse_health_strict().await;
+        if readiness.status == eth2::lighthouse::ReadinessStatus::NotReady {
+            assert_eq!(
+                strict.unwrap_err().status(),
+                Some(StatusCode::SERVICE_UNAVAILABLE)
+            );
+        } else {
+            assert_eq!(strict.unwrap().data.readiness.status, readiness.status);
+        }
 
         self
     }
@@ -5348,6 +5568,211 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_reorgs(self) -> Self {
+        let summaries = self.client.get_lighthouse_reorgs().await.unwrap().data;
+        assert_eq!(summaries, self.chain.reorg_captures().unwrap());
+
+        for summary in summaries {
+            let capture = self
+                .client
+                .get_lighthouse_reorg(summary.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert_eq!(capture.summary, summary);
+        }
+        assert!(self
+            .client
+            .get_lighthouse_reorg(u64::MAX)
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_network_diversity(self) -> Self {
+        let diversity = self
+            .client
+            .get_lighthouse_network_diversity()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(diversity.connected_peers, 1);
+        assert!(!diversity.geoip_enabled);
+        assert_eq!(diversity.clients.len(), 1);
+        assert_eq!(diversity.clients["Unknown"].peers, 1);
+        assert_eq!(diversity.clients["Unknown"].percent, 100.0);
+        assert_eq!(diversity.countries["unknown"].peers, 1);
+        assert_eq!(diversity.hosting_providers["unknown"].peers, 1);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_network_gossip_trace(self) -> Self {
+        let trace = self
+            .client
+            .get_lighthouse_network_gossip_trace()
+            .await
+            .unwrap()
+            .data;
+
+        // Nothing is published or received over gossip by the test harness.
+        assert_eq!(trace, GossipTrace::default());
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_network_enr(mut self) -> Self {
+        let request = EnrUpdateRequest {
+            fields: BTreeMap::from([("operator".to_string(), "0x6c68".to_string())]),
+            udp_socket: None,
+            refresh: true,
+        };
+        self.client
+            .post_lighthouse_network_enr(&request)
+            .await
+            .unwrap();
+
+        match self.network_rx.network_recv.recv().await {
+            Some(NetworkMessage::UpdateEnr {
+                fields,
+                udp_socket,
+                refresh,
+            }) => {
+                assert_eq!(fields["operator"], vec![0x6c, 0x68]);
+                assert_eq!(udp_socket, None);
+                assert!(refresh);
+            }
+            _ => panic!("ENR updates should be sent to the network"),
+        }
+
+        // Fields maintained by Lighthouse and invalid values are rejected.
+        for (key, value) in [("eth2", "0x00"), ("operator", "6c68")] {
+            let request = EnrUpdateRequest {
+                fields: BTreeMap::from([(key.to_string(), value.to_string())]),
+                ..Default::default()
+            };
+            let error = self
+                .client
+                .post_lighthouse_network_enr(&request)
+                .await
+                .unwrap_err();
+            assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        }
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_timeliness(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        {
+            let mut timeliness = self.chain.timeliness.write();
+            timeliness.record(ArrivalKind::Block, slot, 1_500, slot);
+            timeliness.record(ArrivalKind::Aggregate, slot, 8_000, slot);
+            timeliness.record(ArrivalKind::Aggregate, slot, 9_000, slot);
+            timeliness.settle(slot + TIMELINESS_SETTLE_SLOTS + 1);
+        }
+
+        let slots = self
+            .client
+            .get_lighthouse_timeliness(Some(slot), Some(slot))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].slot, slot);
+        assert_eq!(slots[0].blocks.count, 1);
+        assert_eq!(slots[0].blocks.deciles_ms[5], 1_500);
+        assert_eq!(slots[0].aggregates.count, 2);
+        assert_eq!(slots[0].aggregates.deciles_ms.first(), Some(&8_000));
+        assert_eq!(slots[0].aggregates.deciles_ms.last(), Some(&9_000));
+        assert_eq!(slots[0].blob_sidecars.count, 0);
+
+        let all_slots = self
+            .client
+            .get_lighthouse_timeliness(None, None)
+            .await
+            .unwrap()
+            .data;
+        assert!(all_slots.contains(&slots[0]));
+
+        assert!(self
+            .client
+            .get_lighthouse_timeliness(Some(slot + 1), Some(slot))
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_fork_choice_detail(self) -> Self {
+        let detail = self
+            .client
+            .get_lighthouse_fork_choice_detail()
+            .await
+            .unwrap()
+            .data;
+
+        let head_root = self.chain.canonical_head.cached_head().head_block_root();
+        assert_eq!(detail.head_root, head_root);
+
+        let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+        assert_eq!(
+            detail.nodes.len(),
+            fork_choice.proto_array().core_proto_array().nodes.len()
+        );
+        assert!(detail
+            .head_candidates
+            .iter()
+            .any(|candidate| candidate.block_root == head_root));
+
+        for candidate in &detail.head_candidates {
+            for message in &candidate.latest_messages {
+                assert_eq!(
+                    fork_choice
+                        .proto_array()
+                        .latest_message(message.validator_index as usize),
+                    Some((candidate.block_root, message.epoch))
+                );
+            }
+        }
+        drop(fork_choice);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_fork_choice_optimistic_blocks(self) -> Self {
+        let blocks = self
+            .client
+            .get_lighthouse_fork_choice_optimistic_blocks()
+            .await
+            .unwrap()
+            .data;
+
+        let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+        for block in &blocks {
+            assert!(fork_choice
+                .get_block_execution_status(&block.block_root)
+                .unwrap()
+                .is_strictly_optimistic());
+        }
+        drop(fork_choice);
+
+        let error = self
+            .client
+            .post_lighthouse_fork_choice_optimistic_blocks_reverify(
+                ExecutionBlockHash::repeat_byte(0x42),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -5368,6 +5793,39 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_validator_attestation_inclusion(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        let inclusion = self
+            .client
+            .get_lighthouse_validator_attestation_inclusion(ValidatorId::Index(0), epoch)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(inclusion.validator_index, 0);
+        assert_eq!(inclusion.epoch, epoch);
+        let block = inclusion
+            .blocks
+            .first()
+            .expect("attestation should be included");
+        assert!(block.slot > inclusion.attestation_slot);
+        assert_eq!(
+            block.inclusion_delay,
+            block.slot.as_u64() - inclusion.attestation_slot.as_u64()
+        );
+
+        let pubkey = self.chain.validator_pubkey_bytes(0).unwrap().unwrap();
+        let by_pubkey = self
+            .client
+            .get_lighthouse_validator_attestation_inclusion(ValidatorId::PublicKey(pubkey), epoch)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(by_pubkey, inclusion);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
         self.client.get_lighthouse_eth1_syncing().await.unwrap();
 
@@ -5415,6 +5873,454 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_database_forecast(self) -> Self {
+        let forecast = self
+            .client
+            .get_lighthouse_database_forecast()
+            .await
+            .unwrap();
+
+        assert_eq!(forecast, self.chain.store.disk_usage_forecast());
+        assert!(!forecast.retention_active);
+
+        self
+    }
+
+    pub async fn test_lighthouse_logging(self) -> Self {
+        assert!(self
+            .client
+            .get_lighthouse_logging()
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+
+        let update = |target: &str, level: Option<&str>| LogLevelUpdate {
+            target: target.to_string(),
+            level: level.map(String::from),
+        };
+        let levels = self
+            .client
+            .post_lighthouse_logging(&update("network::sync", Some("debug")))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            levels,
+            vec![LogLevel {
+                target: "network::sync".to_string(),
+                level: "debug".to_string(),
+            }]
+        );
+        assert_eq!(
+            self.client.get_lighthouse_logging().await.unwrap().data,
+            levels
+        );
+
+        assert!(self
+            .client
+            .post_lighthouse_logging(&update("network::sync", Some("verbose")))
+            .await
+            .is_err());
+
+        let levels = self
+            .client
+            .post_lighthouse_logging(&update("network::sync", None))
+            .await
+            .unwrap()
+            .data;
+        assert!(levels.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_blocks(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+        // The longest permitted range, including slots after the head.
+        let end_slot = head_slot + 10;
+        let start_slot = end_slot + 1 - 4 * SLOTS_PER_EPOCH;
+
+        let result = self
+            .client
+            .get_lighthouse_blocks(start_slot, end_slot)
+            .await
+            .unwrap();
+
+        let expected_roots = (start_slot.as_u64()..=head_slot.as_u64())
+            .filter_map(|slot| {
+                self.chain
+                    .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(result.len(), expected_roots.len());
+        for (summary, root) in result.iter().zip(expected_roots) {
+            let block = self.chain.get_blinded_block(&root).unwrap().unwrap();
+            assert_eq!(summary.block_root, root);
+            assert_eq!(summary.slot, block.slot());
+            assert_eq!(summary.proposer_index, block.message().proposer_index());
+            assert_eq!(
+                summary.attestation_count,
+                block.message().body().attestations_len()
+            );
+        }
+
+        // A single-slot range returns only the block at that slot.
+        let single = self
+            .client
+            .get_lighthouse_blocks(head_slot, head_slot)
+            .await
+            .unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].slot, head_slot);
+
+        // A range entirely after the head is empty.
+        assert!(self
+            .client
+            .get_lighthouse_blocks(head_slot + 1, end_slot)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // An inverted range is invalid.
+        assert!(self
+            .client
+            .get_lighthouse_blocks(head_slot, start_slot)
+            .await
+            .is_err());
+
+        // A range longer than 4 epochs is rejected.
+        assert!(self
+            .client
+            .get_lighthouse_blocks(start_slot - 1, end_slot)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_execution_requests(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let block = &head.beacon_block;
+
+        let json = self
+            .client
+            .get_lighthouse_block_execution_requests::<E>(CoreBlockId::Head)
+            .await
+            .unwrap()
+            .unwrap();
+        let ssz = self
+            .client
+            .get_lighthouse_block_execution_requests_ssz::<E>(CoreBlockId::Head)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(json.data, ssz);
+        let expected_deposits = block
+            .message()
+            .body()
+            .execution_payload()
+            .ok()
+            .and_then(|payload| payload.deposit_requests().ok().flatten())
+            .unwrap_or_default();
+        assert_eq!(json.data.deposits, expected_deposits);
+
+        let epoch = block.epoch();
+        let totals = self
+            .client
+            .get_lighthouse_execution_requests(epoch)
+            .await
+            .unwrap()
+            .data;
+        let start_slot = epoch.start_slot(E::slots_per_epoch());
+        let blocks = (start_slot.as_u64()..=block.slot().as_u64())
+            .filter(|slot| {
+                self.chain
+                    .block_root_at_slot(Slot::new(*slot), WhenSlotSkipped::None)
+                    .unwrap()
+                    .is_some()
+            })
+            .count();
+        assert_eq!(totals.epoch, epoch);
+        assert_eq!(totals.blocks, blocks as u64);
+        assert!(totals.full_exits <= totals.withdrawals);
+
+        // Epochs after the head are invalid.
+        assert!(self
+            .client
+            .get_lighthouse_execution_requests(epoch + 1)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_blocks_dry_run(self) -> Self {
+        let block = self.next_block.signed_block().as_ref().clone();
+        let state_root = block.state_root();
+
+        let result = self
+            .client
+            .post_lighthouse_blocks_dry_run(&DryRunBlock::Signed(block.clone()))
+            .await
+            .unwrap();
+        assert_eq!(result.block_root, block.canonical_root());
+        assert_eq!(result.slot, block.slot());
+        assert!(result.signed);
+        assert!(result.valid, "{:?}", result);
+        assert_eq!(result.computed_state_root, Some(state_root));
+
+        // The block is neither imported nor observed by a dry run.
+        assert!(!self.chain.block_is_known_to_fork_choice(&result.block_root));
+        assert!(!self
+            .chain
+            .observed_block_producers
+            .read()
+            .proposer_has_been_observed(block.message(), result.block_root)
+            .unwrap()
+            .proposer_previously_observed());
+
+        // A block with an invalid signature fails consensus validation only.
+        let (message, signature) = block.clone().deconstruct();
+        let bad_signature = SignedBeaconBlock::from_block(message.clone(), Signature::empty());
+        let result = self
+            .client
+            .post_lighthouse_blocks_dry_run(&DryRunBlock::Signed(bad_signature))
+            .await
+            .unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.gossip_error, None);
+        assert_eq!(result.computed_state_root, None);
+        assert!(result.consensus_error.is_some());
+
+        // A block which builds on an unknown parent fails both.
+        let mut orphan = message.clone();
+        *orphan.parent_root_mut() = Hash256::repeat_byte(0xff);
+        let result = self
+            .client
+            .post_lighthouse_blocks_dry_run(&DryRunBlock::Signed(SignedBeaconBlock::from_block(
+                orphan, signature,
+            )))
+            .await
+            .unwrap();
+        assert!(!result.valid);
+        assert!(result.gossip_error.unwrap().contains("is unknown"));
+        assert!(result.consensus_error.unwrap().contains("unavailable"));
+
+        // The head block is already known.
+        let head = self.chain.head_beacon_block().as_ref().clone();
+        let result = self
+            .client
+            .post_lighthouse_blocks_dry_run(&DryRunBlock::Signed(head))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.gossip_error.as_deref(),
+            Some("block is already known")
+        );
+
+        // An unsigned block with the wrong state root fails consensus validation only.
+        let mut message = message;
+        *message.state_root_mut() = Hash256::zero();
+        let result = self
+            .client
+            .post_lighthouse_blocks_dry_run(&DryRunBlock::Unsigned(message))
+            .await
+            .unwrap();
+        assert!(!result.signed);
+        assert!(!result.valid);
+        assert_eq!(result.gossip_error, None);
+        assert_eq!(result.computed_state_root, Some(state_root));
+        assert!(result
+            .consensus_error
+            .unwrap()
+            .starts_with("state root mismatch"));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_ui_validator_performance(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+
+        let performance = self
+            .client
+            .get_lighthouse_ui_validator_performance(Epoch::new(0), current_epoch + 10)
+            .await
+            .unwrap()
+            .data;
+        assert!(performance
+            .windows(2)
+            .all(|pair| (pair[0].epoch, pair[0].validator_index)
+                < (pair[1].epoch, pair[1].validator_index)));
+        assert!(performance.iter().all(|p| p.epoch <= current_epoch));
+
+        // An inverted range is invalid.
+        assert!(self
+            .client
+            .get_lighthouse_ui_validator_performance(current_epoch + 1, current_epoch)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_queues(self) -> Self {
+        let queues = self
+            .client
+            .get_lighthouse_validator_queues()
+            .await
+            .unwrap()
+            .data;
+        let head = self.chain.head_snapshot();
+
+        // All of the validators are active from genesis and none have exited.
+        assert_eq!(queues.epoch, head.beacon_state.current_epoch());
+        assert_eq!(queues.activation.length, 0);
+        assert_eq!(queues.exit.length, 0);
+        assert!(queues.activation.churn_limit > 0);
+        assert!(queues.exit.churn_limit > 0);
+        assert_eq!(
+            queues.exit.wait_seconds,
+            queues.exit.wait_epochs * self.chain.spec.seconds_per_slot * E::slots_per_epoch()
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_rewards_blocks(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+        let start_slot = head_slot + 1 - 4 * SLOTS_PER_EPOCH;
+
+        let result = self
+            .client
+            .get_lighthouse_rewards_blocks(start_slot, head_slot)
+            .await
+            .unwrap();
+
+        let summaries = self
+            .client
+            .get_lighthouse_blocks(start_slot, head_slot)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), summaries.len());
+        for (reward, summary) in result.iter().zip(summaries) {
+            assert_eq!(reward.block_root, summary.block_root);
+            assert_eq!(reward.slot, summary.slot);
+            assert_eq!(reward.reward.proposer_index, summary.proposer_index);
+            assert_eq!(
+                reward.reward.total,
+                reward.reward.attestations
+                    + reward.reward.sync_aggregate
+                    + reward.reward.proposer_slashings
+                    + reward.reward.attester_slashings
+            );
+        }
+
+        // Replaying a single block from its own prior state gives the same reward as replaying it
+        // as part of the longer range.
+        for reward in result.iter().rev().take(3) {
+            let single = self
+                .client
+                .get_lighthouse_rewards_blocks(reward.slot, reward.slot)
+                .await
+                .unwrap();
+            assert_eq!(single.len(), 1);
+            assert_eq!(single[0].block_root, reward.block_root);
+            assert_eq!(single[0].reward, reward.reward);
+        }
+
+        // Slot 0 has no block rewards.
+        assert!(self
+            .client
+            .get_lighthouse_rewards_blocks(Slot::new(0), head_slot)
+            .await
+            .is_err());
+
+        // A range longer than 4 epochs is rejected.
+        assert!(self
+            .client
+            .get_lighthouse_rewards_blocks(start_slot - 1, head_slot)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_block_transition(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let head_slot = head.beacon_block.slot();
+
+        let report = self
+            .client
+            .get_lighthouse_analysis_block_transition(CoreBlockId::Head)
+            .await
+            .unwrap();
+
+        assert_eq!(report.slot, head_slot);
+        let stages = report
+            .block_processing
+            .iter()
+            .map(|stage| stage.stage.as_str())
+            .collect::<Vec<_>>();
+        assert!(stages.contains(&"block_header"));
+        assert!(stages.contains(&"operations"));
+
+        // The genesis block has no transition.
+        assert!(self
+            .client
+            .get_lighthouse_analysis_block_transition(CoreBlockId::Genesis)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_historical_block_proof(self) -> Self {
+        // The test chain is shorter than an era, so the head state has no historical roots.
+        assert!(self
+            .client
+            .get_lighthouse_historical_block_proof(CoreStateId::Head, CoreBlockId::Head)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_beacon_states_proof(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let num_fields = head.beacon_state.num_fields_pow2() as u64;
+        // The `slot` and `finalized_checkpoint` fields.
+        let gindices = [num_fields + 2, num_fields + 20];
+
+        let proof = self
+            .client
+            .get_lighthouse_beacon_states_proof(CoreStateId::Head, &gindices)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(proof.state_root, head.beacon_state_root());
+        assert_eq!(proof.gindices, gindices);
+        assert_eq!(
+            proof.leaves,
+            vec![
+                head.beacon_state.slot().tree_hash_root(),
+                head.beacon_state.finalized_checkpoint().tree_hash_root(),
+            ]
+        );
+        assert!(proof.verify());
+
+        // The root of the finalized checkpoint is not within a list or vector.
+        assert!(self
+            .client
+            .get_lighthouse_beacon_states_proof(CoreStateId::Head, &[(num_fields + 20) * 2 + 1])
+            .await
+            .is_err());
+
+        self
+    }
+
     pub async fn test_post_lighthouse_database_reconstruct(self) -> Self {
         let response = self
             .client
@@ -5425,6 +6331,40 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_compact(self) -> Self {
+        let response = self
+            .client
+            .post_lighthouse_database_compact()
+            .await
+            .unwrap();
+        assert_eq!(response, "success");
+        self
+    }
+
+    pub async fn test_post_lighthouse_database_backup(self) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let request = DatabaseBackupRequest {
+            path: dir.path().join("backup"),
+        };
+
+        let summary = self
+            .client
+            .post_lighthouse_database_backup(&request)
+            .await
+            .unwrap();
+        assert_eq!(summary.split_slot, self.chain.store.get_split_slot());
+        assert!(summary.entries > 0);
+        assert!(request.path.metadata().unwrap().len() > 0);
+
+        // Existing files are not overwritten.
+        assert!(self
+            .client
+            .post_lighthouse_database_backup(&request)
+            .await
+            .is_err());
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5960,6 +6900,10 @@ async fn beacon_get() {
         .await
         .test_beacon_states_validators()
         .await
+        .test_beacon_states_validators_in_range()
+        .await
+        .test_beacon_states_validator_ids_in_range()
+        .await
         .test_beacon_states_validator_balances()
         .await
         .test_beacon_states_committees()
@@ -6502,6 +7446,21 @@ async fn post_validator_register_validator() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_builder_registrations() {
+    ApiTester::new()
+        .await
+        .test_post_validator_register_validator()
+        .await
+        .test_get_lighthouse_builder_registrations()
+        .await
+        // Registering the same validators again leaves them sent.
+        .test_post_validator_register_validator()
+        .await
+        .test_get_lighthouse_builder_registrations()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_validator_register_validator_slashed() {
     ApiTester::new()
@@ -6822,10 +7781,26 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_fork_choice_detail()
+        .await
+        .test_get_lighthouse_fork_choice_optimistic_blocks()
+        .await
+        .test_get_lighthouse_reorgs()
+        .await
+        .test_get_lighthouse_timeliness()
+        .await
+        .test_get_lighthouse_network_diversity()
+        .await
+        .test_get_lighthouse_network_gossip_trace()
+        .await
+        .test_post_lighthouse_network_enr()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
         .await
+        .test_get_lighthouse_validator_attestation_inclusion()
+        .await
         .test_get_lighthouse_eth1_syncing()
         .await
         .test_get_lighthouse_eth1_block_cache()
@@ -6836,8 +7811,34 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_database_info()
         .await
+        .test_get_lighthouse_database_forecast()
+        .await
+        .test_lighthouse_logging()
+        .await
+        .test_get_lighthouse_blocks()
+        .await
+        .test_post_lighthouse_blocks_dry_run()
+        .await
+        .test_get_lighthouse_execution_requests()
+        .await
+        .test_get_lighthouse_validator_queues()
+        .await
+        .test_get_lighthouse_ui_validator_performance()
+        .await
+        .test_get_lighthouse_rewards_blocks()
+        .await
+        .test_get_lighthouse_analysis_block_transition()
+        .await
+        .test_get_lighthouse_historical_block_proof()
+        .await
+        .test_get_lighthouse_beacon_states_proof()
+        .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_compact()
+        .await
+        .test_post_lighthouse_database_backup()
+        .await
         .test_post_lighthouse_liveness()
         .await;
 }
@@ -3,6 +3,8 @@
 //! For other endpoints, see the `http_api` crate.
 mod metrics;
 
+pub use metrics::{register_instance, INSTANCE_LABEL};
+
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use lighthouse_network::prometheus_client::registry::Registry;
 use lighthouse_version::version_with_platform;
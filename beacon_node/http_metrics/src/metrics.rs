@@ -1,8 +1,47 @@
 use crate::Context;
-use beacon_chain::BeaconChainTypes;
-use lighthouse_metrics::TextEncoder;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use lighthouse_metrics::{LabelPair, MetricFamily, TextEncoder};
 use lighthouse_network::prometheus_client::encoding::text::encode;
 use malloc_utils::scrape_allocator_metrics;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError, Weak};
+
+/// The label which holds the name of the instance that a per-chain metric was scraped from.
+pub const INSTANCE_LABEL: &str = "instance";
+
+/// The additional beacon nodes running in this process, whose per-chain metrics are served by the
+/// metrics server of the primary beacon node.
+static INSTANCES: Mutex<Vec<Instance>> = Mutex::new(Vec::new());
+
+struct Instance {
+    name: String,
+    /// Scrapes the per-chain metrics of the instance, returning `false` if it has shut down.
+    scrape: Box<dyn Fn() -> bool + Send>,
+}
+
+/// Serve the per-chain metrics of `chain` from the metrics server of this process, labelled with
+/// `name`.
+///
+/// The metrics of all of the beacon nodes of a process are recorded in the same registry. Metrics
+/// which are updated as events occur are shared between them, however the metrics which are
+/// scraped from the state of a chain are scraped from each instance in turn.
+pub fn register_instance<T: BeaconChainTypes>(name: String, chain: Weak<BeaconChain<T>>) {
+    let scrape = move || {
+        let Some(chain) = chain.upgrade() else {
+            return false;
+        };
+        slot_clock::scrape_for_metrics::<T::EthSpec, T::SlotClock>(&chain.slot_clock);
+        beacon_chain::scrape_for_metrics(&chain);
+        true
+    };
+    INSTANCES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Instance {
+            name,
+            scrape: Box::new(scrape),
+        });
+}
 
 pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
     ctx: &Context<T>,
@@ -25,6 +64,13 @@ pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
     // We proceed by, first updating all the static metrics using `scrape_for_metrics(..)`. Then,
     // using `lighthouse_metrics::gather(..)` to collect the global `DEFAULT_REGISTRY` metrics into
     // a string that can be returned via HTTP.
+    //
+    // The per-chain metrics of any additional instances are scraped first, so that the registry is
+    // left with the metrics of this beacon node. The lock is held for the whole scrape so that
+    // concurrent scrapes can not mix the metrics of different chains.
+
+    let mut instances = INSTANCES.lock().unwrap_or_else(PoisonError::into_inner);
+    let instance_metrics = scrape_instances(&mut instances);
 
     if let Some(beacon_chain) = ctx.chain.as_ref() {
         slot_clock::scrape_for_metrics::<T::EthSpec, T::SlotClock>(&beacon_chain.slot_clock);
@@ -47,9 +93,11 @@ pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
         scrape_allocator_metrics();
     }
 
-    encoder
-        .encode_utf8(&lighthouse_metrics::gather(), &mut buffer)
-        .unwrap();
+    let mut families = lighthouse_metrics::gather();
+    drop(instances);
+    add_instance_metrics(&mut families, instance_metrics);
+
+    encoder.encode_utf8(&families, &mut buffer).unwrap();
     // encode gossipsub metrics also if they exist
     if let Some(registry) = ctx.gossipsub_registry.as_ref() {
         if let Ok(registry_locked) = registry.lock() {
@@ -59,3 +107,54 @@ pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
 
     Ok(buffer)
 }
+
+/// Scrape the per-chain metrics of each of the `instances`, labelled with the name of the
+/// instance. Instances which have shut down are removed.
+fn scrape_instances(instances: &mut Vec<Instance>) -> HashMap<String, MetricFamily> {
+    let chain_metric_names = slot_clock::chain_metric_names()
+        .into_iter()
+        .chain(beacon_chain::metrics::chain_metric_names())
+        .collect::<Vec<_>>();
+
+    let mut scraped = HashMap::<String, MetricFamily>::new();
+    instances.retain(|instance| {
+        if !(instance.scrape)() {
+            return false;
+        }
+        for mut family in lighthouse_metrics::gather() {
+            if !chain_metric_names.contains(&family.get_name().to_string()) {
+                continue;
+            }
+            let mut metrics = family.take_metric().into_vec();
+            for metric in &mut metrics {
+                let mut label = LabelPair::default();
+                label.set_name(INSTANCE_LABEL.to_string());
+                label.set_value(instance.name.clone());
+                metric.mut_label().push(label);
+            }
+            let family = scraped
+                .entry(family.get_name().to_string())
+                .or_insert(family);
+            for metric in metrics {
+                family.mut_metric().push(metric);
+            }
+        }
+        true
+    });
+    scraped
+}
+
+/// Add the labelled metrics of the additional instances to the `families` of this beacon node.
+fn add_instance_metrics(
+    families: &mut Vec<MetricFamily>,
+    mut instance_metrics: HashMap<String, MetricFamily>,
+) {
+    for family in families.iter_mut() {
+        if let Some(mut instance_family) = instance_metrics.remove(family.get_name()) {
+            for metric in instance_family.take_metric().into_vec() {
+                family.mut_metric().push(metric);
+            }
+        }
+    }
+    families.extend(instance_metrics.into_values());
+}
@@ -1,4 +1,4 @@
-use beacon_chain::test_utils::EphemeralHarnessType;
+use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
 use environment::null_logger;
 use http_metrics::Config;
 use reqwest::header::HeaderValue;
@@ -56,3 +56,49 @@ async fn returns_200_ok() {
     }
     .await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn labels_instance_metrics() {
+    let log = null_logger().unwrap();
+
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .default_spec()
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .build();
+    http_metrics::register_instance("devnet".to_string(), Arc::downgrade(&harness.chain));
+
+    let context = Arc::new(Context {
+        config: Config {
+            enabled: true,
+            listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            listen_port: 0,
+            allow_origin: None,
+            allocator_metrics_enabled: false,
+        },
+        chain: None,
+        db_path: None,
+        freezer_db_path: None,
+        gossipsub_registry: None,
+        log,
+    });
+
+    let (_shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server_shutdown = async {
+        let _ = shutdown_rx.await;
+    };
+    let (listening_socket, server) = http_metrics::serve(context, server_shutdown).unwrap();
+    tokio::spawn(server);
+
+    let url = format!(
+        "http://{}:{}/metrics",
+        listening_socket.ip(),
+        listening_socket.port()
+    );
+    let metrics = reqwest::get(&url).await.unwrap().text().await.unwrap();
+
+    // The per-chain metrics of the instance are labelled, the shared metrics are not.
+    assert!(metrics.contains("beacon_head_slot{instance=\"devnet\"} 0"));
+    assert!(metrics.contains("beacon_head_state_total_validators_total{instance=\"devnet\"} 8"));
+    assert!(!metrics.contains("beacon_block_processing_requests_total{instance="));
+}
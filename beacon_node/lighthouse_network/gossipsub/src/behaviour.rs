@@ -25,6 +25,7 @@ use std::{
     collections::{BTreeSet, HashMap},
     fmt,
     net::IpAddr,
+    sync::{Arc, Mutex, PoisonError},
     task::{Context, Poll},
     time::Duration,
 };
@@ -58,6 +59,7 @@ use super::rpc_proto::proto;
 use super::subscription_filter::{AllowAllSubscriptionFilter, TopicSubscriptionFilter};
 use super::time_cache::DuplicateCache;
 use super::topic::{Hasher, Topic, TopicHash};
+use super::trace::MessageTrace;
 use super::transform::{DataTransform, IdentityTransform};
 use super::types::{
     ControlAction, FailedMessages, Message, MessageAcceptance, MessageId, PeerInfo, RawMessage,
@@ -342,6 +344,12 @@ pub struct Behaviour<D = IdentityTransform, F = AllowAllSubscriptionFilter> {
 
     /// Tracks recently sent `IWANT` messages and checks if peers respond to them.
     gossip_promises: GossipPromises,
+
+    /// Optional tracing of the propagation of published and received messages.
+    message_trace: Option<Arc<Mutex<MessageTrace>>>,
+
+    /// Topics whose meshes are maintained at `mesh_n_high` peers rather than `mesh_n`.
+    boosted_topics: HashSet<TopicHash>,
 }
 
 impl<D, F> Behaviour<D, F>
@@ -479,6 +487,8 @@ where
             data_transform,
             failed_messages: Default::default(),
             gossip_promises: Default::default(),
+            message_trace: None,
+            boosted_topics: HashSet::new(),
         })
     }
 }
@@ -739,6 +749,8 @@ where
 
         // Send to peers we know are subscribed to the topic.
         let mut publish_failed = true;
+        let mut sent_to = vec![];
+        let mut failed = vec![];
         for peer_id in recipient_peers.iter() {
             if let Some(peer) = self.connected_peers.get_mut(peer_id) {
                 tracing::trace!(peer=%peer_id, "Sending message to peer");
@@ -747,8 +759,12 @@ where
                     self.config.publish_queue_duration(),
                     self.metrics.as_mut(),
                 ) {
-                    Ok(_) => publish_failed = false,
+                    Ok(_) => {
+                        publish_failed = false;
+                        sent_to.push(*peer_id);
+                    }
                     Err(_) => {
+                        failed.push(*peer_id);
                         self.failed_messages.entry(*peer_id).or_default().priority += 1;
 
                         tracing::warn!(peer_id=%peer_id, "Publish queue full. Could not publish to peer");
@@ -764,6 +780,8 @@ where
             }
         }
 
+        self.trace_message(|trace| trace.record_published(&msg_id, &topic_hash, sent_to, failed));
+
         if recipient_peers.is_empty() {
             return Err(PublishError::InsufficientPeers);
         }
@@ -935,6 +953,34 @@ where
         Ok(())
     }
 
+    /// Records the propagation of published and received messages in `message_trace`, which may
+    /// be shared with the application to inspect the traces.
+    pub fn with_message_trace(&mut self, message_trace: Arc<Mutex<MessageTrace>>) {
+        self.message_trace = Some(message_trace);
+    }
+
+    /// Raises the target degree of the mesh for `topic` from `mesh_n` to `mesh_n_high` peers, or
+    /// restores it if `boost` is false. Peers are grafted or pruned on the next heartbeat.
+    ///
+    /// The boost is independent of the subscription to the topic, so it may be set before joining
+    /// the topic, and must be removed explicitly.
+    pub fn set_mesh_boost(&mut self, topic: &TopicHash, boost: bool) {
+        let changed = if boost {
+            self.boosted_topics.insert(topic.clone())
+        } else {
+            self.boosted_topics.remove(topic)
+        };
+        if changed {
+            tracing::debug!(%topic, %boost, "Set mesh boost for topic");
+        }
+    }
+
+    fn trace_message(&self, f: impl FnOnce(&mut MessageTrace)) {
+        if let Some(message_trace) = &self.message_trace {
+            f(&mut message_trace.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
     /// Sets scoring parameters for a topic.
     ///
     /// The [`Self::with_peer_score()`] must first be called to initialise peer scoring.
@@ -977,6 +1023,7 @@ where
         }
 
         let mut added_peers = HashSet::new();
+        let (_, mesh_n) = mesh_degree(&self.config, &self.boosted_topics, topic_hash);
 
         if let Some(m) = self.metrics.as_mut() {
             m.joined(topic_hash)
@@ -999,7 +1046,7 @@ where
 
             // Add up to mesh_n of them them to the mesh
             // NOTE: These aren't randomly added, currently FIFO
-            let add_peers = std::cmp::min(peers.len(), self.config.mesh_n());
+            let add_peers = std::cmp::min(peers.len(), mesh_n);
             tracing::debug!(
                 topic=%topic_hash,
                 "JOIN: Adding {:?} peers from the fanout for topic",
@@ -1022,12 +1069,12 @@ where
         }
 
         // check if we need to get more peers, which we randomly select
-        if added_peers.len() < self.config.mesh_n() {
+        if added_peers.len() < mesh_n {
             // get the peers
             let new_peers = get_random_peers(
                 &self.connected_peers,
                 topic_hash,
-                self.config.mesh_n() - added_peers.len(),
+                mesh_n - added_peers.len(),
                 |peer| {
                     !added_peers.contains(peer)
                         && !self.explicit_peers.contains(peer)
@@ -1264,6 +1311,12 @@ where
         };
 
         for (topic, ids) in ihave_msgs {
+            self.trace_message(|trace| {
+                for id in &ids {
+                    trace.record_ihave(id, peer_id);
+                }
+            });
+
             // only process the message if we are subscribed
             if !self.mesh.contains_key(&topic) {
                 tracing::debug!(
@@ -1362,6 +1415,8 @@ where
         tracing::debug!(peer=%peer_id, "Handling IWANT for peer");
 
         for id in iwant_msgs {
+            self.trace_message(|trace| trace.record_iwant(&id, peer_id));
+
             // If we have it and the IHAVE count is not above the threshold,
             // foward the message.
             if let Some((msg, count)) = self
@@ -1820,6 +1875,7 @@ where
 
         if !self.duplicate_cache.insert(msg_id.clone()) {
             tracing::debug!(message=%msg_id, "Message already received, ignoring");
+            self.trace_message(|trace| trace.record_duplicate(&msg_id));
             if let Some((peer_score, ..)) = &mut self.peer_score {
                 peer_score.duplicated_message(propagation_source, &msg_id, &message.topic);
             }
@@ -1839,6 +1895,10 @@ where
         // Consider the message as delivered for gossip promises.
         self.gossip_promises.message_delivered(&msg_id);
 
+        self.trace_message(|trace| {
+            trace.record_received(&msg_id, &message.topic, propagation_source)
+        });
+
         // Tells score that message arrived (but is maybe not fully validated yet).
         if let Some((peer_score, ..)) = &mut self.peer_score {
             peer_score.validate_message(propagation_source, &msg_id, &message.topic);
@@ -2144,6 +2204,7 @@ where
 
         // maintain the mesh for each topic
         for (topic_hash, peers) in self.mesh.iter_mut() {
+            let (mesh_n_low, mesh_n) = mesh_degree(&self.config, &self.boosted_topics, topic_hash);
             let explicit_peers = &self.explicit_peers;
             let backoffs = &self.backoffs;
             let outbound_peers = &self.outbound_peers;
@@ -2184,15 +2245,15 @@ where
             }
 
             // too little peers - add some
-            if peers.len() < self.config.mesh_n_low() {
+            if peers.len() < mesh_n_low {
                 tracing::debug!(
                     topic=%topic_hash,
                     "HEARTBEAT: Mesh low. Topic contains: {} needs: {}",
                     peers.len(),
-                    self.config.mesh_n_low()
+                    mesh_n_low
                 );
                 // not enough peers - get mesh_n - current_length more
-                let desired_peers = self.config.mesh_n() - peers.len();
+                let desired_peers = mesh_n - peers.len();
                 let peer_list =
                     get_random_peers(&self.connected_peers, topic_hash, desired_peers, |peer| {
                         !peers.contains(peer)
@@ -2220,7 +2281,7 @@ where
                     peers.len(),
                     self.config.mesh_n_high()
                 );
-                let excess_peer_no = peers.len() - self.config.mesh_n();
+                let excess_peer_no = peers.len() - mesh_n;
 
                 // shuffle the peers and then sort by score ascending beginning with the worst
                 let mut rng = thread_rng();
@@ -3488,6 +3549,21 @@ fn peer_removed_from_mesh(
     });
 }
 
+/// Returns the degree below which the mesh for `topic_hash` is grafted, and the target degree.
+///
+/// Boosted topics are grafted up to `mesh_n_high` whenever they have fewer peers.
+fn mesh_degree(
+    config: &Config,
+    boosted_topics: &HashSet<TopicHash>,
+    topic_hash: &TopicHash,
+) -> (usize, usize) {
+    if boosted_topics.contains(topic_hash) {
+        (config.mesh_n_high(), config.mesh_n_high())
+    } else {
+        (config.mesh_n_low(), config.mesh_n())
+    }
+}
+
 /// Helper function to get a subset of random gossipsub peers for a `topic_hash`
 /// filtered by the function `f`. The number of peers to get equals the output of `n_map`
 /// that gets as input the number of filtered peers.
@@ -1858,6 +1858,30 @@ fn test_mesh_subtraction() {
     assert_eq!(gs.mesh.get(&topics[0]).unwrap().len(), config.mesh_n());
 }
 
+/// Tests that boosted meshes are maintained at mesh_n_high
+#[test]
+fn test_mesh_boost() {
+    let config = Config::default();
+
+    let (mut gs, _peers, _receivers, topics) = inject_nodes1()
+        .peer_no(config.mesh_n_high() + 10)
+        .topics(vec!["test".into()])
+        .to_subscribe(true)
+        .gs_config(config.clone())
+        .create_network();
+    assert_eq!(gs.mesh.get(&topics[0]).unwrap().len(), config.mesh_n());
+
+    // Boosted meshes are grafted up to mesh_n_high.
+    gs.set_mesh_boost(&topics[0], true);
+    gs.heartbeat();
+    assert_eq!(gs.mesh.get(&topics[0]).unwrap().len(), config.mesh_n_high());
+
+    // Once the boost is removed, the mesh is only pruned when it exceeds mesh_n_high.
+    gs.set_mesh_boost(&topics[0], false);
+    gs.heartbeat();
+    assert_eq!(gs.mesh.get(&topics[0]).unwrap().len(), config.mesh_n_high());
+}
+
 #[test]
 fn test_connect_to_px_peers_on_handle_prune() {
     let config: Config = Config::default();
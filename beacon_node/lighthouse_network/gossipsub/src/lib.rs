@@ -107,6 +107,7 @@ mod rpc_proto;
 mod subscription_filter;
 mod time_cache;
 mod topic;
+mod trace;
 mod transform;
 mod types;
 
@@ -124,6 +125,7 @@ pub use self::subscription_filter::{
     WhitelistSubscriptionFilter,
 };
 pub use self::topic::{Hasher, Topic, TopicHash};
+pub use self::trace::{MessageTrace, PublishedTrace, ReceivedTrace};
 pub use self::transform::{DataTransform, IdentityTransform};
 pub use self::types::{FailedMessages, Message, MessageAcceptance, MessageId, RawMessage};
 
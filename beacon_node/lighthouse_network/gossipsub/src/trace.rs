@@ -0,0 +1,215 @@
+// Copyright 2020 Sigma Prime Pty Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Bounded tracing of the propagation of messages.
+//!
+//! For each message we publish, the peers it was sent to and the peers which acknowledged it with
+//! an IHAVE or requested it with an IWANT are recorded. For each received message on a traced
+//! topic, the peer it was first seen from and the number of duplicates are recorded. Only the
+//! most recent `capacity` published and received messages are kept.
+
+use crate::{MessageId, TopicHash};
+use hashlink::LinkedHashMap;
+use libp2p::identity::PeerId;
+use web_time::SystemTime;
+
+/// The trace of a message published by the local node.
+#[derive(Debug, Clone)]
+pub struct PublishedTrace {
+    pub topic: TopicHash,
+    pub published_at: SystemTime,
+    /// Peers the message was queued for.
+    pub sent_to: Vec<PeerId>,
+    /// Peers the message could not be queued for, because their queue was full.
+    pub failed: Vec<PeerId>,
+    /// Peers which advertised the message back to us in an IHAVE.
+    pub ihave_from: Vec<PeerId>,
+    /// Peers which requested the message from us with an IWANT.
+    pub iwant_from: Vec<PeerId>,
+}
+
+/// The trace of a message received from the network.
+#[derive(Debug, Clone)]
+pub struct ReceivedTrace {
+    pub topic: TopicHash,
+    pub first_seen_at: SystemTime,
+    pub first_seen_from: PeerId,
+    /// The number of times the message was received again after it was first seen.
+    pub duplicates: u64,
+}
+
+/// The traces of the most recently published and received messages.
+#[derive(Debug)]
+pub struct MessageTrace {
+    capacity: usize,
+    /// Received messages are only traced on the topics for which this returns `true`.
+    received_filter: fn(&TopicHash) -> bool,
+    published: LinkedHashMap<MessageId, PublishedTrace>,
+    received: LinkedHashMap<MessageId, ReceivedTrace>,
+}
+
+impl MessageTrace {
+    pub fn new(capacity: usize, received_filter: fn(&TopicHash) -> bool) -> Self {
+        Self {
+            capacity,
+            received_filter,
+            published: LinkedHashMap::new(),
+            received: LinkedHashMap::new(),
+        }
+    }
+
+    /// The published messages, oldest first.
+    pub fn published(&self) -> impl Iterator<Item = (&MessageId, &PublishedTrace)> {
+        self.published.iter()
+    }
+
+    /// The received messages, oldest first.
+    pub fn received(&self) -> impl Iterator<Item = (&MessageId, &ReceivedTrace)> {
+        self.received.iter()
+    }
+
+    pub(crate) fn record_published(
+        &mut self,
+        msg_id: &MessageId,
+        topic: &TopicHash,
+        sent_to: Vec<PeerId>,
+        failed: Vec<PeerId>,
+    ) {
+        let trace = PublishedTrace {
+            topic: topic.clone(),
+            published_at: SystemTime::now(),
+            sent_to,
+            failed,
+            ihave_from: vec![],
+            iwant_from: vec![],
+        };
+        insert_bounded(&mut self.published, self.capacity, msg_id, trace);
+    }
+
+    pub(crate) fn record_ihave(&mut self, msg_id: &MessageId, peer_id: &PeerId) {
+        if let Some(trace) = self.published.get_mut(msg_id) {
+            push_unique(&mut trace.ihave_from, peer_id);
+        }
+    }
+
+    pub(crate) fn record_iwant(&mut self, msg_id: &MessageId, peer_id: &PeerId) {
+        if let Some(trace) = self.published.get_mut(msg_id) {
+            push_unique(&mut trace.iwant_from, peer_id);
+        }
+    }
+
+    pub(crate) fn record_received(
+        &mut self,
+        msg_id: &MessageId,
+        topic: &TopicHash,
+        propagation_source: &PeerId,
+    ) {
+        if !(self.received_filter)(topic) {
+            return;
+        }
+        let trace = ReceivedTrace {
+            topic: topic.clone(),
+            first_seen_at: SystemTime::now(),
+            first_seen_from: *propagation_source,
+            duplicates: 0,
+        };
+        insert_bounded(&mut self.received, self.capacity, msg_id, trace);
+    }
+
+    pub(crate) fn record_duplicate(&mut self, msg_id: &MessageId) {
+        if let Some(trace) = self.received.get_mut(msg_id) {
+            trace.duplicates += 1;
+        }
+    }
+}
+
+fn insert_bounded<T>(
+    traces: &mut LinkedHashMap<MessageId, T>,
+    capacity: usize,
+    msg_id: &MessageId,
+    trace: T,
+) {
+    if capacity == 0 {
+        return;
+    }
+    traces.insert(msg_id.clone(), trace);
+    while traces.len() > capacity {
+        traces.pop_front();
+    }
+}
+
+fn push_unique(peers: &mut Vec<PeerId>, peer_id: &PeerId) {
+    if !peers.contains(peer_id) {
+        peers.push(*peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_topics(_: &TopicHash) -> bool {
+        true
+    }
+
+    #[test]
+    fn bounded_published_trace() {
+        let mut trace = MessageTrace::new(2, all_topics);
+        let topic = TopicHash::from_raw("topic");
+        let peer = PeerId::random();
+        for i in 0..3u8 {
+            trace.record_published(&MessageId::new(&[i]), &topic, vec![peer], vec![]);
+        }
+        let ids = trace
+            .published()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![MessageId::new(&[1]), MessageId::new(&[2])]);
+
+        let other = PeerId::random();
+        trace.record_ihave(&MessageId::new(&[2]), &other);
+        trace.record_ihave(&MessageId::new(&[2]), &other);
+        trace.record_iwant(&MessageId::new(&[2]), &peer);
+        // Messages which are no longer traced are ignored.
+        trace.record_iwant(&MessageId::new(&[0]), &peer);
+        let (_, latest) = trace.published().last().unwrap();
+        assert_eq!(latest.sent_to, vec![peer]);
+        assert_eq!(latest.ihave_from, vec![other]);
+        assert_eq!(latest.iwant_from, vec![peer]);
+    }
+
+    #[test]
+    fn received_trace_is_filtered() {
+        fn blocks_only(topic: &TopicHash) -> bool {
+            topic.as_str() == "blocks"
+        }
+        let mut trace = MessageTrace::new(8, blocks_only);
+        let peer = PeerId::random();
+        trace.record_received(&MessageId::new(&[1]), &TopicHash::from_raw("blocks"), &peer);
+        trace.record_received(&MessageId::new(&[2]), &TopicHash::from_raw("other"), &peer);
+        trace.record_duplicate(&MessageId::new(&[1]));
+        trace.record_duplicate(&MessageId::new(&[2]));
+
+        let received = trace.received().collect::<Vec<_>>();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].1.first_seen_from, peer);
+        assert_eq!(received[0].1.duplicates, 1);
+    }
+}
@@ -1,6 +1,7 @@
-use crate::listen_addr::{ListenAddr, ListenAddress};
+use crate::discovery::dns::EnrTreeLink;
+use crate::listen_addr::{IpVersion, ListenAddr, ListenAddress};
 use crate::rpc::config::{InboundRateLimiterConfig, OutboundRateLimiterConfig};
-use crate::types::GossipKind;
+use crate::types::{GossipKind, DEFAULT_GOSSIP_TRACE_CAPACITY};
 use crate::{Enr, PeerIdSerialized};
 use directory::{
     DEFAULT_BEACON_NODE_DIR, DEFAULT_HARDCODED_NETWORK, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR,
@@ -8,6 +9,7 @@ use directory::{
 use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::num::NonZeroU16;
 use std::path::PathBuf;
@@ -79,6 +81,10 @@ pub struct Config {
     /// List of nodes to initially connect to, on Multiaddr format.
     pub boot_nodes_multiaddr: Vec<Multiaddr>,
 
+    /// Links to DNS trees (EIP-1459) of nodes to initially connect to, which are periodically
+    /// refreshed.
+    pub boot_nodes_dns: Vec<EnrTreeLink>,
+
     /// List of libp2p nodes to initially connect to.
     pub libp2p_nodes: Vec<Multiaddr>,
 
@@ -97,6 +103,18 @@ pub struct Config {
     /// Disables quic support.
     pub disable_quic_support: bool,
 
+    /// The address family dialed first when a peer advertises addresses of both. If `None`, QUIC
+    /// addresses are dialed before TCP addresses regardless of their family.
+    pub ip_preference: Option<IpVersion>,
+
+    /// Only make outbound libp2p connections. The node doesn't listen for libp2p connections,
+    /// rejects inbound connections and doesn't advertise TCP or QUIC ports in its ENR.
+    pub outbound_only: bool,
+
+    /// Only connect to trusted peers, for a node behind sentry nodes. Connections with other peers
+    /// are rejected, and trusted peers in `libp2p_nodes` are re-dialed whenever they disconnect.
+    pub trusted_peers_only: bool,
+
     /// Attempt to construct external port mappings with UPnP.
     pub upnp_enabled: bool,
 
@@ -138,6 +156,19 @@ pub struct Config {
 
     /// Configuration for the inbound rate limiter (requests received by this node).
     pub inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
+
+    /// A CSV GeoIP database used to report the locations of peers.
+    pub geoip_db: Option<PathBuf>,
+
+    /// Custom key/value fields to include in the local ENR.
+    pub enr_custom_fields: BTreeMap<String, Vec<u8>>,
+
+    /// The number of published and received gossip messages whose propagation is traced. Tracing
+    /// is disabled if zero.
+    pub gossip_trace_capacity: usize,
+
+    /// Raise the gossipsub mesh degree of attestation subnets with upcoming aggregation duties.
+    pub aggregator_mesh_boost: bool,
 }
 
 impl Config {
@@ -329,12 +360,16 @@ impl Default for Config {
             discv5_config,
             boot_nodes_enr: vec![],
             boot_nodes_multiaddr: vec![],
+            boot_nodes_dns: vec![],
             libp2p_nodes: vec![],
             trusted_peers: vec![],
             disable_peer_scoring: false,
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             disable_quic_support: false,
+            ip_preference: None,
+            outbound_only: false,
+            trusted_peers_only: false,
             upnp_enabled: true,
             network_load: 4,
             private: false,
@@ -348,6 +383,10 @@ impl Default for Config {
             outbound_rate_limiter_config: None,
             invalid_block_storage: None,
             inbound_rate_limiter_config: None,
+            geoip_db: None,
+            enr_custom_fields: BTreeMap::new(),
+            gossip_trace_capacity: DEFAULT_GOSSIP_TRACE_CAPACITY,
+            aggregator_mesh_boost: true,
         }
     }
 }
@@ -0,0 +1,400 @@
+//! Resolution of bootnode lists published as signed DNS trees, as specified in EIP-1459.
+//!
+//! A tree is referenced by a link of the form `enrtree://<public key>@<domain>`. The TXT record at
+//! `<domain>` is the root of the tree, which is signed by the public key and references the hashes
+//! of the root entries of the ENR and link subtrees. Every other entry is found at
+//! `<hash>.<domain>`, where the hash is the base32 encoding of the first 16 bytes of the keccak256
+//! hash of the entry's content, so the whole tree is authenticated by the root's signature.
+//!
+//! Only the ENR subtree is resolved. Links to other trees are not followed.
+use crate::Enr;
+use data_encoding::{BASE32_NOPAD, BASE64URL_NOPAD};
+use discv5::enr::k256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
+use futures::FutureExt;
+use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+use hickory_resolver::TokioAsyncResolver;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use slog::warn;
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tiny_keccak::{Hasher, Keccak};
+use tokio::time::Interval;
+
+/// The interval between checks of each tree for a new sequence number.
+pub const ENR_TREE_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// The maximum number of entries resolved from a single tree.
+pub const MAX_ENR_TREE_ENTRIES: usize = 2048;
+
+const LINK_PREFIX: &str = "enrtree://";
+const ROOT_PREFIX: &str = "enrtree-root:v1";
+const BRANCH_PREFIX: &str = "enrtree-branch:";
+const ENR_PREFIX: &str = "enr:";
+
+/// The number of bytes of the keccak256 hash of an entry which form its subdomain.
+const ENTRY_HASH_BYTES: usize = 16;
+
+/// A link to a DNS tree, `enrtree://<base32 public key>@<domain>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnrTreeLink {
+    public_key: VerifyingKey,
+    pub domain: String,
+}
+
+impl FromStr for EnrTreeLink {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (public_key, domain) = s
+            .strip_prefix(LINK_PREFIX)
+            .and_then(|link| link.split_once('@'))
+            .ok_or_else(|| {
+                format!("Invalid ENR tree link {s}, expected {LINK_PREFIX}<key>@<domain>")
+            })?;
+        let public_key = BASE32_NOPAD
+            .decode(public_key.as_bytes())
+            .ok()
+            .and_then(|bytes| VerifyingKey::from_sec1_bytes(&bytes).ok())
+            .ok_or_else(|| format!("Invalid public key in ENR tree link {s}"))?;
+        if domain.is_empty() {
+            return Err(format!("Missing domain in ENR tree link {s}"));
+        }
+        Ok(Self {
+            public_key,
+            domain: domain.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for EnrTreeLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let public_key = self.public_key.to_encoded_point(true);
+        write!(
+            f,
+            "{LINK_PREFIX}{}@{}",
+            BASE32_NOPAD.encode(public_key.as_bytes()),
+            self.domain
+        )
+    }
+}
+
+impl Serialize for EnrTreeLink {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EnrTreeLink {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The ENRs of a resolved tree.
+#[derive(Debug, Clone)]
+pub struct EnrTree {
+    /// The sequence number of the tree's root, which is incremented whenever the tree changes.
+    pub seq: u64,
+    pub enrs: Vec<Enr>,
+}
+
+#[derive(Debug, PartialEq)]
+struct EnrTreeRoot {
+    enr_root: String,
+    seq: u64,
+}
+
+#[derive(Debug)]
+enum EnrTreeEntry {
+    Branch(Vec<String>),
+    Enr(Box<Enr>),
+    Link,
+}
+
+type TreeQuery = BoxFuture<'static, (usize, Result<Option<EnrTree>, String>)>;
+
+/// A stream of the trees at a set of links, which are resolved immediately and then re-resolved
+/// every `ENR_TREE_REFRESH_INTERVAL`. A tree is only yielded again if its sequence number changes.
+pub struct EnrTreeRefresh {
+    resolver: TokioAsyncResolver,
+    /// Each link, with the sequence number of its last resolved tree.
+    trees: Vec<(EnrTreeLink, Option<u64>)>,
+    interval: Interval,
+    queries: FuturesUnordered<TreeQuery>,
+}
+
+impl EnrTreeRefresh {
+    /// Uses the system's DNS configuration, or the default configuration if it can't be read.
+    ///
+    /// NOTE: This requires running within a tokio execution environment.
+    pub fn new(links: Vec<EnrTreeLink>, log: &slog::Logger) -> Self {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf().unwrap_or_else(|e| {
+            warn!(log, "Unable to read the system DNS configuration"; "error" => %e);
+            TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default())
+        });
+        Self {
+            resolver,
+            trees: links.into_iter().map(|link| (link, None)).collect(),
+            interval: tokio::time::interval(ENR_TREE_REFRESH_INTERVAL),
+            queries: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl Stream for EnrTreeRefresh {
+    type Item = (EnrTreeLink, Result<EnrTree, String>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        while this.interval.poll_tick(cx).is_ready() {
+            // Don't start another round while the previous one is still resolving.
+            if !this.queries.is_empty() {
+                continue;
+            }
+            for (index, (link, seq)) in this.trees.iter().enumerate() {
+                let resolver = this.resolver.clone();
+                let link = link.clone();
+                let seq = *seq;
+                this.queries.push(
+                    async move { (index, resolve_enr_tree(&resolver, &link, seq).await) }.boxed(),
+                );
+            }
+        }
+
+        while let Poll::Ready(Some((index, result))) = this.queries.poll_next_unpin(cx) {
+            let (link, seq) = &mut this.trees[index];
+            match result {
+                Ok(None) => {}
+                Ok(Some(tree)) => {
+                    *seq = Some(tree.seq);
+                    return Poll::Ready(Some((link.clone(), Ok(tree))));
+                }
+                Err(e) => return Poll::Ready(Some((link.clone(), Err(e)))),
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Resolve the ENRs of the tree at `link`.
+///
+/// Returns `Ok(None)` without resolving the subtree if the root's sequence number is `known_seq`.
+pub async fn resolve_enr_tree(
+    resolver: &TokioAsyncResolver,
+    link: &EnrTreeLink,
+    known_seq: Option<u64>,
+) -> Result<Option<EnrTree>, String> {
+    let root = lookup_txt(resolver, &link.domain)
+        .await?
+        .into_iter()
+        .find(|record| record.starts_with(ROOT_PREFIX))
+        .ok_or_else(|| format!("No ENR tree root at {}", link.domain))?;
+    let root = parse_root(&root, &link.public_key)?;
+    if known_seq == Some(root.seq) {
+        return Ok(None);
+    }
+
+    let mut enrs = vec![];
+    let mut pending = VecDeque::from([root.enr_root]);
+    let mut resolved = 0;
+    while let Some(hash) = pending.pop_front() {
+        resolved += 1;
+        if resolved > MAX_ENR_TREE_ENTRIES {
+            return Err(format!(
+                "ENR tree at {} has more than {MAX_ENR_TREE_ENTRIES} entries",
+                link.domain
+            ));
+        }
+
+        let name = format!("{hash}.{}", link.domain);
+        let entry = lookup_txt(resolver, &name)
+            .await?
+            .into_iter()
+            .find(|record| entry_hash(record).eq_ignore_ascii_case(&hash))
+            .ok_or_else(|| format!("No entry matching its hash at {name}"))?;
+        match parse_entry(&entry)? {
+            EnrTreeEntry::Branch(children) => pending.extend(children),
+            EnrTreeEntry::Enr(enr) => enrs.push(*enr),
+            EnrTreeEntry::Link => return Err(format!("Unexpected link in ENR subtree at {name}")),
+        }
+    }
+
+    Ok(Some(EnrTree {
+        seq: root.seq,
+        enrs,
+    }))
+}
+
+/// Returns the TXT records at `name`, with the strings of each record concatenated.
+async fn lookup_txt(resolver: &TokioAsyncResolver, name: &str) -> Result<Vec<String>, String> {
+    let lookup = resolver
+        .txt_lookup(name)
+        .await
+        .map_err(|e| format!("Unable to resolve {name}: {e}"))?;
+    Ok(lookup
+        .iter()
+        .filter_map(|txt| String::from_utf8(txt.txt_data().concat()).ok())
+        .collect())
+}
+
+/// Parse a root, `enrtree-root:v1 e=<enr root> l=<link root> seq=<seq> sig=<signature>`, and
+/// verify its signature by `public_key`.
+fn parse_root(record: &str, public_key: &VerifyingKey) -> Result<EnrTreeRoot, String> {
+    let (content, signature) = record
+        .split_once(" sig=")
+        .ok_or_else(|| format!("Unsigned ENR tree root {record}"))?;
+
+    let mut enr_root = None;
+    let mut seq = None;
+    for field in content
+        .strip_prefix(ROOT_PREFIX)
+        .ok_or_else(|| format!("Invalid ENR tree root {record}"))?
+        .split_whitespace()
+    {
+        match field.split_once('=') {
+            Some(("e", hash)) => enr_root = Some(hash.to_string()),
+            Some(("seq", value)) => seq = value.parse().ok(),
+            _ => {}
+        }
+    }
+    let (Some(enr_root), Some(seq)) = (enr_root, seq) else {
+        return Err(format!("Invalid ENR tree root {record}"));
+    };
+
+    // The signature is [r || s || v], and may be padded.
+    let signature = BASE64URL_NOPAD
+        .decode(signature.trim_end_matches('=').as_bytes())
+        .ok()
+        .filter(|bytes| bytes.len() == 65)
+        .and_then(|bytes| Signature::from_slice(&bytes[..64]).ok())
+        .ok_or_else(|| format!("Invalid signature on ENR tree root {record}"))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    public_key
+        .verify_prehash(&keccak256(content.as_bytes()), &signature)
+        .map_err(|_| format!("Incorrect signature on ENR tree root {record}"))?;
+
+    Ok(EnrTreeRoot { enr_root, seq })
+}
+
+fn parse_entry(record: &str) -> Result<EnrTreeEntry, String> {
+    if let Some(children) = record.strip_prefix(BRANCH_PREFIX) {
+        Ok(EnrTreeEntry::Branch(
+            children
+                .split(',')
+                .map(str::trim)
+                .filter(|child| !child.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ))
+    } else if record.starts_with(ENR_PREFIX) {
+        record
+            .parse()
+            .map(|enr| EnrTreeEntry::Enr(Box::new(enr)))
+            .map_err(|e| format!("Invalid ENR in tree: {e}"))
+    } else if record.starts_with(LINK_PREFIX) {
+        Ok(EnrTreeEntry::Link)
+    } else {
+        Err(format!("Unknown ENR tree entry {record}"))
+    }
+}
+
+/// The subdomain of an entry.
+fn entry_hash(record: &str) -> String {
+    BASE32_NOPAD.encode(&keccak256(record.as_bytes())[..ENTRY_HASH_BYTES])
+}
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0; 32];
+    hasher.update(bytes);
+    hasher.finalize(&mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use discv5::enr::k256::ecdsa::{signature::hazmat::PrehashSigner, SigningKey};
+
+    fn signed_root(key: &SigningKey, content: &str) -> String {
+        let signature: Signature = key.sign_prehash(&keccak256(content.as_bytes())).unwrap();
+        // The recovery id isn't used for verification.
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(0);
+        format!("{content} sig={}", BASE64URL_NOPAD.encode(&bytes))
+    }
+
+    #[test]
+    fn link_round_trip() {
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let encoded = BASE32_NOPAD.encode(&key.verifying_key().to_encoded_point(true).as_bytes());
+        let link = format!("enrtree://{encoded}@nodes.example.org");
+
+        let parsed = link.parse::<EnrTreeLink>().unwrap();
+        assert_eq!(parsed.domain, "nodes.example.org");
+        assert_eq!(parsed.to_string(), link);
+
+        assert!("enrtree://nodes.example.org"
+            .parse::<EnrTreeLink>()
+            .is_err());
+        assert!(format!("enrtree://{encoded}@")
+            .parse::<EnrTreeLink>()
+            .is_err());
+        assert!("enrtree://AAAA@nodes.example.org"
+            .parse::<EnrTreeLink>()
+            .is_err());
+    }
+
+    #[test]
+    fn root_signature() {
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let content =
+            "enrtree-root:v1 e=JWXYDBPXYWG6FX3GMDIBFA6CJ4 l=C7HRFPF3BLGF3YR4DY5KX3SMBE seq=3";
+        let root = signed_root(&key, content);
+
+        assert_eq!(
+            parse_root(&root, key.verifying_key()).unwrap(),
+            EnrTreeRoot {
+                enr_root: "JWXYDBPXYWG6FX3GMDIBFA6CJ4".to_string(),
+                seq: 3,
+            }
+        );
+
+        let other_key = SigningKey::from_slice(&[2; 32]).unwrap();
+        assert!(parse_root(&root, other_key.verifying_key()).is_err());
+        let tampered = root.replace("seq=3", "seq=4");
+        assert!(parse_root(&tampered, key.verifying_key()).is_err());
+        assert!(parse_root(content, key.verifying_key()).is_err());
+    }
+
+    #[test]
+    fn entries() {
+        match parse_entry("enrtree-branch:2XS2367YHAXJFGLZHVAWLQD4ZY,H4FHT4B454P6UXFD7JCYQ5PWDY")
+            .unwrap()
+        {
+            EnrTreeEntry::Branch(children) => assert_eq!(children.len(), 2),
+            entry => panic!("expected branch, got {entry:?}"),
+        }
+        assert!(matches!(
+            parse_entry("enrtree-branch:").unwrap(),
+            EnrTreeEntry::Branch(children) if children.is_empty()
+        ));
+        assert!(matches!(
+            parse_entry("enrtree://AM5FCQLWIZX2QFPNJAP7VUERCCRNGRHWZG3YYHIUV7BVDQ5FDPRT2@morenodes.example.org").unwrap(),
+            EnrTreeEntry::Link
+        ));
+        assert!(parse_entry("enr:invalid").is_err());
+        assert!(parse_entry("something else").is_err());
+
+        assert_eq!(entry_hash("enrtree-branch:").len(), 26);
+    }
+}
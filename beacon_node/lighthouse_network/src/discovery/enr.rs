@@ -25,6 +25,27 @@ pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
 /// The ENR field specifying the sync committee subnet bitfield.
 pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
 
+/// The ENR fields which are maintained by Lighthouse or discovery, and can't be customised.
+pub const RESERVED_ENR_KEYS: [&str; 13] = [
+    "id",
+    "secp256k1",
+    "ip",
+    "ip6",
+    "tcp",
+    "tcp6",
+    "udp",
+    "udp6",
+    QUIC_ENR_KEY,
+    QUIC6_ENR_KEY,
+    ETH2_ENR_KEY,
+    ATTESTATION_BITFIELD_ENR_KEY,
+    SYNC_COMMITTEE_BITFIELD_ENR_KEY,
+];
+
+/// The maximum length of the value of a custom ENR field, so that the ENR stays within the
+/// 300 byte limit.
+pub const MAX_CUSTOM_ENR_FIELD_BYTES: usize = 64;
+
 /// Extension trait for ENR's within Eth2.
 pub trait Eth2Enr {
     /// The attestation subnet bitfield associated with the ENR.
@@ -66,6 +87,34 @@ impl Eth2Enr for Enr {
     }
 }
 
+/// Checks that a custom ENR field doesn't replace a reserved field and isn't too large.
+pub fn validate_custom_enr_field(key: &str, value: &[u8]) -> Result<(), String> {
+    if key.is_empty() {
+        return Err("ENR field keys must not be empty".to_string());
+    }
+    if RESERVED_ENR_KEYS.contains(&key) {
+        return Err(format!("ENR field {key} is reserved"));
+    }
+    if value.len() > MAX_CUSTOM_ENR_FIELD_BYTES {
+        return Err(format!(
+            "ENR field {key} is longer than {MAX_CUSTOM_ENR_FIELD_BYTES} bytes"
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the fields of `enr` which are not reserved.
+fn custom_enr_fields(enr: &Enr) -> Vec<(&[u8], &[u8])> {
+    enr.iter()
+        .map(|(key, value)| (key.as_slice(), value))
+        .filter(|(key, _)| {
+            !RESERVED_ENR_KEYS
+                .iter()
+                .any(|reserved| reserved.as_bytes() == *key)
+        })
+        .collect()
+}
+
 /// Either use the given ENR or load an ENR from file if it exists and matches the current NodeId
 /// and sequence number.
 /// If an ENR exists, with the same NodeId, this function checks to see if the loaded ENR from
@@ -164,8 +213,9 @@ pub fn build_enr<E: EthSpec>(
 
     // Add QUIC fields to the ENR.
     // Since QUIC is used as an alternative transport for the libp2p protocols,
-    // the related fields should only be added when both QUIC and libp2p are enabled
-    if !config.disable_quic_support {
+    // the related fields should only be added when both QUIC and libp2p are enabled.
+    // Nodes which don't accept inbound connections advertise neither QUIC nor TCP.
+    if !config.disable_quic_support && !config.outbound_only {
         // If we are listening on ipv4, add the quic ipv4 port.
         if let Some(quic4_port) = config.enr_quic4_port.or_else(|| {
             config
@@ -194,6 +244,7 @@ pub fn build_enr<E: EthSpec>(
             .v4()
             .and_then(|v4_addr| v4_addr.tcp_port.try_into().ok())
     });
+    let tcp4_port = tcp4_port.filter(|_| !config.outbound_only);
     if let Some(tcp4_port) = tcp4_port {
         builder.tcp4(tcp4_port.get());
     }
@@ -204,6 +255,7 @@ pub fn build_enr<E: EthSpec>(
             .v6()
             .and_then(|v6_addr| v6_addr.tcp_port.try_into().ok())
     });
+    let tcp6_port = tcp6_port.filter(|_| !config.outbound_only);
     if let Some(tcp6_port) = tcp6_port {
         builder.tcp6(tcp6_port.get());
     }
@@ -221,6 +273,12 @@ pub fn build_enr<E: EthSpec>(
 
     builder.add_value(SYNC_COMMITTEE_BITFIELD_ENR_KEY, &bitfield.as_ssz_bytes());
 
+    // set the custom fields on our ENR
+    for (key, value) in &config.enr_custom_fields {
+        validate_custom_enr_field(key, value)?;
+        builder.add_value(key, value);
+    }
+
     builder
         .build(enr_key)
         .map_err(|e| format!("Could not build Local ENR: {:?}", e))
@@ -248,6 +306,8 @@ fn compare_enr(local_enr: &Enr, disk_enr: &Enr) -> bool {
         // otherwise we use a new ENR. This will likely only be true for non-validating nodes
         && local_enr.get(ATTESTATION_BITFIELD_ENR_KEY) == disk_enr.get(ATTESTATION_BITFIELD_ENR_KEY)
         && local_enr.get(SYNC_COMMITTEE_BITFIELD_ENR_KEY) == disk_enr.get(SYNC_COMMITTEE_BITFIELD_ENR_KEY)
+        // custom fields must match, including any which have been removed
+        && custom_enr_fields(local_enr) == custom_enr_fields(disk_enr)
 }
 
 /// Loads enr from the given directory
@@ -3,6 +3,7 @@
 //! This module creates a libp2p dummy-behaviour built around the discv5 protocol. It handles
 //! queries and manages access to the discovery routing table.
 
+pub mod dns;
 pub(crate) mod enr;
 pub mod enr_ext;
 
@@ -11,7 +12,10 @@ use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, Enr, NetworkConfig, NetworkGlobals, Subnet, SubnetDiscovery};
 use crate::{metrics, ClearDialError};
 use discv5::{enr::NodeId, Discv5};
-pub use enr::{build_enr, load_enr_from_disk, use_or_load_enr, CombinedKey, Eth2Enr};
+use dns::EnrTreeRefresh;
+pub use enr::{
+    build_enr, load_enr_from_disk, use_or_load_enr, validate_custom_enr_field, CombinedKey, Eth2Enr,
+};
 pub use enr_ext::{peer_id_to_node_id, CombinedKeyExt, EnrExt};
 pub use libp2p::identity::{Keypair, PublicKey};
 
@@ -34,7 +38,7 @@ use slog::{crit, debug, error, info, trace, warn};
 use ssz::Encode;
 use std::num::NonZeroUsize;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     net::{IpAddr, SocketAddr},
     path::Path,
     pin::Pin,
@@ -183,6 +187,9 @@ pub struct Discovery<E: EthSpec> {
     /// The discv5 event stream.
     event_stream: EventStream,
 
+    /// Bootnodes published in DNS trees, if any are configured.
+    enr_trees: Option<EnrTreeRefresh>,
+
     /// Indicates if the discovery service has been started. When the service is disabled, this is
     /// always false.
     pub started: bool,
@@ -308,11 +315,15 @@ impl<E: EthSpec> Discovery<E> {
             }
         }
 
+        let enr_trees = (!config.boot_nodes_dns.is_empty())
+            .then(|| EnrTreeRefresh::new(config.boot_nodes_dns.clone(), &log));
+
+        // Nodes which don't accept inbound connections never advertise their libp2p ports.
         let update_ports = UpdatePorts {
-            tcp4: config.enr_tcp4_port.is_none(),
-            tcp6: config.enr_tcp6_port.is_none(),
-            quic4: config.enr_quic4_port.is_none(),
-            quic6: config.enr_quic6_port.is_none(),
+            tcp4: config.enr_tcp4_port.is_none() && !config.outbound_only,
+            tcp6: config.enr_tcp6_port.is_none() && !config.outbound_only,
+            quic4: config.enr_quic4_port.is_none() && !config.outbound_only,
+            quic6: config.enr_quic6_port.is_none() && !config.outbound_only,
         };
 
         Ok(Self {
@@ -323,6 +334,7 @@ impl<E: EthSpec> Discovery<E> {
             active_queries: FuturesUnordered::new(),
             discv5,
             event_stream,
+            enr_trees,
             started: !config.disable_discovery,
             update_ports,
             log,
@@ -563,6 +575,55 @@ impl<E: EthSpec> Discovery<E> {
         Ok(())
     }
 
+    /// Sets custom fields of the local ENR. If `refresh` is set and no field changed, the sequence
+    /// number is incremented anyway, so that peers re-fetch the ENR.
+    ///
+    /// This returns Ok(true) if the ENR was updated, otherwise Ok(false) if nothing was done.
+    pub fn update_enr_custom_fields(
+        &mut self,
+        fields: &BTreeMap<String, Vec<u8>>,
+        refresh: bool,
+    ) -> Result<bool, String> {
+        let local_enr = self.discv5.local_enr();
+        let mut updated = false;
+        for (key, value) in fields {
+            enr::validate_custom_enr_field(key, value)?;
+            if local_enr.get(key) == Some(value.as_slice()) {
+                continue;
+            }
+            self.discv5
+                .enr_insert(key, value)
+                .map_err(|e| format!("{:?}", e))?;
+            updated = true;
+        }
+
+        if refresh && !updated {
+            // Re-inserting an unchanged field increments the sequence number.
+            let eth2 = local_enr
+                .get(ETH2_ENR_KEY)
+                .ok_or("ENR has no eth2 field")?
+                .to_vec();
+            self.discv5
+                .enr_insert(ETH2_ENR_KEY, &eth2)
+                .map_err(|e| format!("{:?}", e))?;
+            updated = true;
+        }
+
+        if updated {
+            // replace the global version
+            *self.network_globals.local_enr.write() = self.discv5.local_enr();
+            // persist modified enr to disk
+            enr::save_enr_to_disk(Path::new(&self.enr_dir), &self.local_enr(), &self.log);
+            info!(
+                self.log,
+                "Updated local ENR";
+                "seq" => self.local_enr().seq(),
+                "fields" => fields.len(),
+            );
+        }
+        Ok(updated)
+    }
+
     /// Updates the `eth2` field of our local ENR.
     pub fn update_eth2_enr(&mut self, enr_fork_id: EnrForkId) {
         // to avoid having a reference to the spec constant, for the logging we assume
@@ -1010,6 +1071,28 @@ impl<E: EthSpec> NetworkBehaviour for Discovery<E> {
             return Poll::Ready(ToSwarm::GenerateEvent(DiscoveredPeers { peers }));
         }
 
+        // Add the nodes of any DNS trees which have been resolved or updated
+        if let Some(enr_trees) = self.enr_trees.as_mut() {
+            while let Poll::Ready(Some((link, result))) = enr_trees.poll_next_unpin(cx) {
+                match result {
+                    Ok(tree) => {
+                        debug!(self.log, "Resolved DNS bootnodes"; "tree" => %link, "seq" => tree.seq, "nodes" => tree.enrs.len());
+                        for enr in tree.enrs {
+                            if enr.node_id() == self.local_enr().node_id() {
+                                continue;
+                            }
+                            if let Err(e) = self.discv5.add_enr(enr) {
+                                debug!(self.log, "Could not add DNS bootnode to the routing table"; "error" => e.to_string());
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        warn!(self.log, "Unable to resolve DNS bootnodes"; "tree" => %link, "error" => e)
+                    }
+                }
+            }
+        }
+
         // Process the server event stream
         match self.event_stream {
             EventStream::Awaiting(ref mut fut) => {
@@ -102,8 +102,8 @@ impl<'a> std::fmt::Display for ClearDialError<'a> {
 }
 
 pub use crate::types::{
-    error, Enr, EnrSyncCommitteeBitfield, GossipTopic, NetworkGlobals, PubsubMessage, Subnet,
-    SubnetDiscovery,
+    error, Enr, EnrSyncCommitteeBitfield, GossipTopic, GossipTrace, NetworkGlobals,
+    PublishedGossipTrace, PubsubMessage, ReceivedGossipTrace, Subnet, SubnetDiscovery,
 };
 
 pub use prometheus_client;
@@ -111,12 +111,13 @@ pub use prometheus_client;
 pub use config::Config as NetworkConfig;
 pub use discovery::{CombinedKeyExt, EnrExt, Eth2Enr};
 pub use discv5;
-pub use gossipsub::{IdentTopic, MessageAcceptance, MessageId, Topic, TopicHash};
+pub use gossipsub::{IdentTopic, MessageAcceptance, MessageId, MessageTrace, Topic, TopicHash};
 pub use libp2p;
 pub use libp2p::{core::ConnectedPoint, PeerId, Swarm};
 pub use libp2p::{multiaddr, Multiaddr};
 pub use metrics::scrape_discovery_metrics;
 pub use peer_manager::{
+    diversity::{DiversityShare, PeerDiversity},
     peerdb::client::Client,
     peerdb::score::{PeerAction, ReportSource},
     peerdb::PeerDB,
@@ -1,4 +1,5 @@
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
 
 use libp2p::{multiaddr::Protocol, Multiaddr};
 use serde::{Deserialize, Serialize};
@@ -126,3 +127,48 @@ impl slog::KV for ListenAddress {
         slog::Result::Ok(())
     }
 }
+
+/// An IP address family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpVersion {
+    Ipv4,
+    Ipv6,
+}
+
+impl IpVersion {
+    pub fn of(ip: &IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(_) => IpVersion::Ipv4,
+            IpAddr::V6(_) => IpVersion::Ipv6,
+        }
+    }
+
+    /// Returns the family of the IP address which `multiaddr` starts with, if any.
+    pub fn of_multiaddr(multiaddr: &Multiaddr) -> Option<Self> {
+        match multiaddr.iter().next()? {
+            Protocol::Ip4(_) => Some(IpVersion::Ipv4),
+            Protocol::Ip6(_) => Some(IpVersion::Ipv6),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IpVersion::Ipv4 => "ipv4",
+            IpVersion::Ipv6 => "ipv6",
+        }
+    }
+}
+
+impl FromStr for IpVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ipv4" => Ok(IpVersion::Ipv4),
+            "ipv6" => Ok(IpVersion::Ipv6),
+            _ => Err(format!("Invalid IP version {s}, expected ipv4 or ipv6")),
+        }
+    }
+}
@@ -20,6 +20,12 @@ lazy_static! {
     pub static ref PEERS_CONNECTED_MULTI: Result<IntGaugeVec> =
     try_create_int_gauge_vec("libp2p_peers_multi", "Count of libp2p peers currently connected", &["direction", "transport"]);
 
+    pub static ref PEERS_PER_IP_VERSION: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "libp2p_peers_per_ip_version",
+        "Count of libp2p peers currently connected, by the IP version of their address",
+        &["ip_version"]
+    );
+
     pub static ref TCP_PEERS_CONNECTED: Result<IntGauge> = try_create_int_gauge(
         "libp2p_tcp_peers",
         "Count of libp2p peers currently connected via TCP"
@@ -61,6 +67,16 @@ lazy_static! {
         "The connected peers via client implementation",
         &["Client"]
     );
+    pub static ref PEERS_PER_COUNTRY: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "libp2p_peers_per_country",
+        "The connected peers by the country of their IP address, if a GeoIP database is loaded",
+        &["country"]
+    );
+    pub static ref PEERS_PER_HOSTING_PROVIDER: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "libp2p_peers_per_hosting_provider",
+        "The connected peers by hosting provider, if a GeoIP database is loaded",
+        &["provider"]
+    );
     pub static ref FAILED_ATTESTATION_PUBLISHES_PER_SUBNET: Result<IntGaugeVec> =
         try_create_int_gauge_vec(
             "gossipsub_failed_attestation_publishes_per_subnet",
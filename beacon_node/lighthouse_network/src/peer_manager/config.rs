@@ -1,3 +1,5 @@
+use crate::IpVersion;
+
 /// The time in seconds between re-status's peers.
 pub const DEFAULT_STATUS_INTERVAL: u64 = 300;
 
@@ -20,6 +22,12 @@ pub struct Config {
     pub metrics_enabled: bool,
     /// Whether quic is enabled.
     pub quic_enabled: bool,
+    /// The address family dialed first, if any.
+    pub ip_preference: Option<IpVersion>,
+    /// Whether inbound connections are rejected.
+    pub outbound_only: bool,
+    /// Whether connections with peers which are not trusted are rejected.
+    pub trusted_peers_only: bool,
     /// Target number of peers to connect to.
     pub target_peer_count: usize,
 
@@ -40,6 +48,9 @@ impl Default for Config {
             discovery_enabled: true,
             metrics_enabled: false,
             quic_enabled: true,
+            ip_preference: None,
+            outbound_only: false,
+            trusted_peers_only: false,
             target_peer_count: DEFAULT_TARGET_PEERS,
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
@@ -0,0 +1,257 @@
+//! Geolocation of peers, and reporting of the diversity of the connected peers by client,
+//! country and hosting provider.
+//!
+//! The GeoIP database is a CSV file with one network per line, which can be generated from the
+//! MaxMind GeoLite2 country and ASN databases:
+//!
+//! ```text
+//! # network,country,organization,hosting
+//! 1.2.3.0/24,DE,Hetzner Online GmbH,true
+//! 2001:db8::/32,US,Example Broadband,false
+//! ```
+//!
+//! Networks must not overlap. Empty lines and lines starting with `#` are ignored.
+use super::peerdb::PeerDB;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+use types::EthSpec;
+
+/// The key used for peers which can't be located.
+pub const UNKNOWN: &str = "unknown";
+
+/// The hosting provider key used for located peers which are not hosted.
+pub const NOT_HOSTED: &str = "none";
+
+/// The location of a network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeoIpRecord {
+    /// ISO 3166-1 alpha-2 country code.
+    pub country: String,
+    /// The organization which owns the network's autonomous system.
+    pub organization: String,
+    /// Whether the organization is a hosting provider.
+    pub hosting: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GeoIpNetwork {
+    start: u128,
+    end: u128,
+    record: GeoIpRecord,
+}
+
+/// An in-memory GeoIP database, mapping networks to their location.
+#[derive(Debug, Default, Clone)]
+pub struct GeoIpDatabase {
+    /// Ordered by the start of the network.
+    networks: Vec<GeoIpNetwork>,
+}
+
+impl GeoIpDatabase {
+    /// Load the CSV database at `path`.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let csv = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {e}", path.display()))?;
+        Self::from_csv(&csv)
+    }
+
+    /// Parse a CSV database.
+    pub fn from_csv(csv: &str) -> Result<Self, String> {
+        let mut networks = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| parse_network(line).map_err(|e| format!("line {}: {e}", i + 1)))
+            .collect::<Result<Vec<_>, _>>()?;
+        networks.sort_by_key(|network| network.start);
+        if let Some(pair) = networks
+            .windows(2)
+            .find(|pair| pair[1].start <= pair[0].end)
+        {
+            return Err(format!(
+                "overlapping networks for {} and {}",
+                pair[0].record.organization, pair[1].record.organization
+            ));
+        }
+        Ok(Self { networks })
+    }
+
+    /// Returns the location of `ip`, if it is in the database.
+    pub fn lookup(&self, ip: IpAddr) -> Option<&GeoIpRecord> {
+        let ip = ip_to_u128(ip);
+        let index = self
+            .networks
+            .partition_point(|network| network.start <= ip)
+            .checked_sub(1)?;
+        let network = &self.networks[index];
+        (ip <= network.end).then_some(&network.record)
+    }
+
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+}
+
+/// IPv4 addresses are mapped into the IPv6 address space, so that both can be searched together.
+fn ip_to_u128(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(ip) => u128::from(ip.to_ipv6_mapped()),
+        IpAddr::V6(ip) => u128::from(ip),
+    }
+}
+
+fn parse_network(line: &str) -> Result<GeoIpNetwork, String> {
+    let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+    let [network, country, organization, hosting] = fields[..] else {
+        return Err(format!("expected 4 fields, found {}", fields.len()));
+    };
+    let (address, prefix) = network
+        .split_once('/')
+        .ok_or_else(|| format!("invalid network {network}"))?;
+    let address = address
+        .parse::<IpAddr>()
+        .map_err(|e| format!("invalid network {network}: {e}"))?;
+    let prefix = prefix
+        .parse::<u32>()
+        .map_err(|e| format!("invalid network {network}: {e}"))?;
+    let prefix = match address {
+        IpAddr::V4(_) if prefix <= 32 => prefix + 96,
+        IpAddr::V6(_) if prefix <= 128 => prefix,
+        _ => return Err(format!("invalid prefix length in {network}")),
+    };
+    let host_mask = u128::MAX.checked_shr(prefix).unwrap_or(0);
+    let start = ip_to_u128(address) & !host_mask;
+
+    Ok(GeoIpNetwork {
+        start,
+        end: start | host_mask,
+        record: GeoIpRecord {
+            country: country.to_uppercase(),
+            organization: organization.to_string(),
+            hosting: hosting
+                .parse()
+                .map_err(|e| format!("invalid hosting flag {hosting}: {e}"))?,
+        },
+    })
+}
+
+/// The number of peers in a category, and their percentage of the connected peers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiversityShare {
+    pub peers: usize,
+    pub percent: f64,
+}
+
+/// The diversity of the connected peers, as returned by `/lighthouse/network/diversity`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerDiversity {
+    pub connected_peers: usize,
+    /// Whether a GeoIP database is loaded. If not, all peers are counted as `unknown` in
+    /// `countries` and `hosting_providers`.
+    pub geoip_enabled: bool,
+    pub clients: BTreeMap<String, DiversityShare>,
+    pub countries: BTreeMap<String, DiversityShare>,
+    /// Peers on networks which are not owned by a hosting provider are counted as `none`.
+    pub hosting_providers: BTreeMap<String, DiversityShare>,
+}
+
+impl PeerDiversity {
+    /// Compute the diversity of the peers connected in `peers`.
+    pub fn new<E: EthSpec>(peers: &PeerDB<E>, geoip: Option<&GeoIpDatabase>) -> Self {
+        let mut diversity = PeerDiversity {
+            geoip_enabled: geoip.is_some(),
+            ..Default::default()
+        };
+
+        let count = |map: &mut BTreeMap<String, DiversityShare>, key: &str| {
+            map.entry(key.to_string()).or_default().peers += 1;
+        };
+        for (_, peer_info) in peers.connected_peers() {
+            diversity.connected_peers += 1;
+            count(&mut diversity.clients, peer_info.client().kind.as_ref());
+
+            let record = geoip.and_then(|geoip| {
+                peer_info
+                    .seen_ip_addresses()
+                    .find_map(|ip| geoip.lookup(ip))
+            });
+            match record {
+                Some(record) => {
+                    count(&mut diversity.countries, &record.country);
+                    let provider = if record.hosting {
+                        record.organization.as_str()
+                    } else {
+                        NOT_HOSTED
+                    };
+                    count(&mut diversity.hosting_providers, provider);
+                }
+                None => {
+                    count(&mut diversity.countries, UNKNOWN);
+                    count(&mut diversity.hosting_providers, UNKNOWN);
+                }
+            }
+        }
+
+        let total = diversity.connected_peers;
+        for share in diversity
+            .clients
+            .values_mut()
+            .chain(diversity.countries.values_mut())
+            .chain(diversity.hosting_providers.values_mut())
+        {
+            share.percent = share.peers as f64 * 100.0 / total as f64;
+        }
+        diversity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+# network,country,organization,hosting
+1.2.3.0/24,de,Hetzner Online GmbH,true
+
+10.0.0.0/8,US,Example Broadband,false
+2001:db8::/32,JP,Example Cloud,true
+";
+
+    #[test]
+    fn lookup() {
+        let db = GeoIpDatabase::from_csv(CSV).unwrap();
+        assert_eq!(db.len(), 3);
+
+        let hetzner = db.lookup("1.2.3.255".parse().unwrap()).unwrap();
+        assert_eq!(hetzner.country, "DE");
+        assert!(hetzner.hosting);
+        assert_eq!(
+            db.lookup("10.255.0.1".parse().unwrap())
+                .unwrap()
+                .organization,
+            "Example Broadband"
+        );
+        assert_eq!(
+            db.lookup("2001:db8::1".parse().unwrap()).unwrap().country,
+            "JP"
+        );
+        assert!(db.lookup("1.2.4.0".parse().unwrap()).is_none());
+        assert!(db.lookup("0.0.0.0".parse().unwrap()).is_none());
+        assert!(db.lookup("::1".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn invalid_csv() {
+        assert!(GeoIpDatabase::from_csv("1.2.3.0/24,DE,Hetzner").is_err());
+        assert!(GeoIpDatabase::from_csv("1.2.3.0/33,DE,Hetzner,true").is_err());
+        assert!(GeoIpDatabase::from_csv("1.2.3.0/24,DE,Hetzner,maybe").is_err());
+        assert!(GeoIpDatabase::from_csv("1.2.0.0/16,DE,A,true\n1.2.3.0/24,DE,B,true").is_err());
+    }
+}
@@ -4,7 +4,7 @@ use crate::discovery::enr_ext::EnrExt;
 use crate::rpc::{GoodbyeReason, MetaData, Protocol, RPCError, RPCResponseErrorCode};
 use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, metrics, Gossipsub};
-use crate::{NetworkGlobals, PeerId};
+use crate::{IpVersion, NetworkGlobals, PeerId};
 use crate::{Subnet, SubnetDiscovery};
 use delay_map::HashSetDelay;
 use discv5::Enr;
@@ -38,6 +38,7 @@ use std::net::IpAddr;
 use strum::IntoEnumIterator;
 
 pub mod config;
+pub mod diversity;
 mod network_behaviour;
 
 /// The heartbeat performs regular updates such as updating reputations and performing discovery
@@ -109,6 +110,12 @@ pub struct PeerManager<E: EthSpec> {
     metrics_enabled: bool,
     /// Keeps track of whether the QUIC protocol is enabled or not.
     quic_enabled: bool,
+    /// The address family dialed first, if any.
+    ip_preference: Option<IpVersion>,
+    /// Whether inbound connections are rejected.
+    outbound_only: bool,
+    /// Whether connections with peers which are not trusted are rejected.
+    trusted_peers_only: bool,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
@@ -155,6 +162,9 @@ impl<E: EthSpec> PeerManager<E> {
             ping_interval_inbound,
             ping_interval_outbound,
             quic_enabled,
+            ip_preference,
+            outbound_only,
+            trusted_peers_only,
         } = cfg;
 
         // Set up the peer manager heartbeat interval
@@ -174,6 +184,9 @@ impl<E: EthSpec> PeerManager<E> {
             discovery_enabled,
             metrics_enabled,
             quic_enabled,
+            ip_preference,
+            outbound_only,
+            trusted_peers_only,
             log: log.clone(),
         })
     }
@@ -1172,6 +1185,9 @@ impl<E: EthSpec> PeerManager<E> {
         // Update peer score metrics;
         self.update_peer_score_metrics();
 
+        // Update peer diversity metrics;
+        self.update_peer_diversity_metrics();
+
         // Maintain minimum count for sync committee peers.
         self.maintain_sync_committee_peers();
 
@@ -1264,11 +1280,38 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    // Update metrics related to the location of peers.
+    fn update_peer_diversity_metrics(&self) {
+        if !self.metrics_enabled || self.network_globals.geoip.is_none() {
+            return;
+        }
+        // reset the gauges
+        let _ = metrics::PEERS_PER_COUNTRY
+            .as_ref()
+            .map(|gauge| gauge.reset());
+        let _ = metrics::PEERS_PER_HOSTING_PROVIDER
+            .as_ref()
+            .map(|gauge| gauge.reset());
+
+        let diversity = self.network_globals.peer_diversity();
+        for (country, share) in &diversity.countries {
+            metrics::set_gauge_vec(&metrics::PEERS_PER_COUNTRY, &[country], share.peers as i64);
+        }
+        for (provider, share) in &diversity.hosting_providers {
+            metrics::set_gauge_vec(
+                &metrics::PEERS_PER_HOSTING_PROVIDER,
+                &[provider],
+                share.peers as i64,
+            );
+        }
+    }
+
     // Update peer count related metrics.
     fn update_peer_count_metrics(&self) {
         let mut peers_connected = 0;
         let mut clients_per_peer = HashMap::new();
         let mut peers_connected_mutli: HashMap<(&str, &str), i32> = HashMap::new();
+        let mut peers_per_ip_version: HashMap<&str, i64> = HashMap::new();
 
         for (_, peer_info) in self.network_globals.peers.read().connected_peers() {
             peers_connected += 1;
@@ -1299,6 +1342,12 @@ impl<E: EthSpec> PeerManager<E> {
             *peers_connected_mutli
                 .entry((direction, transport))
                 .or_default() += 1;
+
+            let ip_version = peer_info
+                .seen_ip_addresses()
+                .next()
+                .map_or("unknown", |ip| IpVersion::of(&ip).as_str());
+            *peers_per_ip_version.entry(ip_version).or_default() += 1;
         }
 
         // PEERS_CONNECTED
@@ -1326,6 +1375,15 @@ impl<E: EthSpec> PeerManager<E> {
                 );
             }
         }
+
+        // PEERS_PER_IP_VERSION
+        for ip_version in ["ipv4", "ipv6", "unknown"] {
+            metrics::set_gauge_vec(
+                &metrics::PEERS_PER_IP_VERSION,
+                &[ip_version],
+                *peers_per_ip_version.get(ip_version).unwrap_or(&0),
+            );
+        }
     }
 }
 
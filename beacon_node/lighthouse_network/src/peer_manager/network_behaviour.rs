@@ -16,7 +16,7 @@ use types::EthSpec;
 use crate::discovery::enr_ext::EnrExt;
 use crate::rpc::GoodbyeReason;
 use crate::types::SyncState;
-use crate::{metrics, ClearDialError};
+use crate::{metrics, ClearDialError, IpVersion};
 
 use super::{ConnectingType, PeerManager, PeerManagerEvent};
 
@@ -107,10 +107,14 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
             };
 
             // Prioritize Quic connections over Tcp ones.
-            let multiaddrs = quic_multiaddrs
+            let mut multiaddrs: Vec<_> = quic_multiaddrs
                 .into_iter()
                 .chain(enr.multiaddr_tcp())
                 .collect();
+            if let Some(ip_preference) = self.ip_preference {
+                // The sort is stable, so Quic is still prioritized within each address family.
+                multiaddrs.sort_by_key(|addr| IpVersion::of_multiaddr(addr) != Some(ip_preference));
+            }
             return Poll::Ready(ToSwarm::Dial {
                 opts: DialOpts::peer_id(peer_id)
                     .condition(PeerCondition::Disconnected)
@@ -171,6 +175,12 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
         _local_addr: &libp2p::Multiaddr,
         remote_addr: &libp2p::Multiaddr,
     ) -> Result<(), ConnectionDenied> {
+        if self.outbound_only {
+            return Err(ConnectionDenied::new(
+                "Connection to peer rejected: inbound connections are disabled",
+            ));
+        }
+
         // get the IP address to verify it's not banned.
         let ip = match remote_addr.iter().next() {
             Some(libp2p::multiaddr::Protocol::Ip6(ip)) => IpAddr::V6(ip),
@@ -205,6 +215,7 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
                 "Connection to peer rejected: peer has a bad score",
             ));
         }
+        self.check_trusted(&peer_id)?;
         Ok(ConnectionHandler)
     }
 
@@ -216,6 +227,7 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
         _role_override: libp2p::core::Endpoint,
     ) -> Result<libp2p::swarm::THandler<Self>, libp2p::swarm::ConnectionDenied> {
         trace!(self.log, "Outbound connection"; "peer_id" => %peer_id, "multiaddr" => %addr);
+        self.check_trusted(&peer_id)?;
         match self.ban_status(&peer_id) {
             Some(cause) => {
                 error!(self.log, "Connected a banned peer. Rejecting connection"; "peer_id" => %peer_id);
@@ -227,6 +239,23 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
 }
 
 impl<E: EthSpec> PeerManager<E> {
+    /// Rejects connections with peers which are not trusted, if only trusted peers are allowed.
+    fn check_trusted(&self, peer_id: &PeerId) -> Result<(), ConnectionDenied> {
+        if self.trusted_peers_only
+            && !self
+                .network_globals
+                .peers
+                .read()
+                .peer_info(peer_id)
+                .map_or(false, |info| info.is_trusted())
+        {
+            return Err(ConnectionDenied::new(
+                "Connection to peer rejected: peer is not trusted",
+            ));
+        }
+        Ok(())
+    }
+
     fn on_connection_established(
         &mut self,
         peer_id: PeerId,
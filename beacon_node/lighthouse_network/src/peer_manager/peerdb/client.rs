@@ -35,6 +35,8 @@ pub enum ClientKind {
     Lodestar,
     /// A Caplin node.
     Caplin,
+    /// A Grandine node.
+    Grandine,
     /// An unknown client.
     Unknown,
 }
@@ -91,6 +93,11 @@ impl std::fmt::Display for Client {
             ),
             ClientKind::Lodestar => write!(f, "Lodestar: version: {}", self.version),
             ClientKind::Caplin => write!(f, "Caplin"),
+            ClientKind::Grandine => write!(
+                f,
+                "Grandine: version: {}, os_version: {}",
+                self.version, self.os_version
+            ),
             ClientKind::Unknown => {
                 if let Some(agent_string) = &self.agent_string {
                     write!(f, "Unknown: {}", agent_string)
@@ -185,6 +192,16 @@ fn client_from_agent_version(agent_version: &str) -> (ClientKind, String, String
             }
             (kind, version, os_version)
         }
+        Some("Grandine") => {
+            let kind = ClientKind::Grandine;
+            if let Some(agent_version) = agent_split.next() {
+                version = agent_version.into();
+                if let Some(agent_os_version) = agent_split.next() {
+                    os_version = agent_os_version.into();
+                }
+            }
+            (kind, version, os_version)
+        }
         Some("erigon") => {
             let client_kind = if let Some("caplin") = agent_split.next() {
                 ClientKind::Caplin
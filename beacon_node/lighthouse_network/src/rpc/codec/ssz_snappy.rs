@@ -804,7 +804,7 @@ mod tests {
         let tx = VariableList::from(vec![0; 1024]);
         let txs = VariableList::from(std::iter::repeat(tx).take(5000).collect::<Vec<_>>());
 
-        block.body.execution_payload.execution_payload.transactions = txs;
+        block.body.execution_payload.execution_payload.transactions = txs.into();
 
         let block = BeaconBlock::Bellatrix(block);
         assert!(block.ssz_bytes_len() <= max_rpc_size(fork_context, spec.max_chunk_size as usize));
@@ -823,7 +823,7 @@ mod tests {
         let tx = VariableList::from(vec![0; 1024]);
         let txs = VariableList::from(std::iter::repeat(tx).take(100000).collect::<Vec<_>>());
 
-        block.body.execution_payload.execution_payload.transactions = txs;
+        block.body.execution_payload.execution_payload.transactions = txs.into();
 
         let block = BeaconBlock::Bellatrix(block);
         assert!(block.ssz_bytes_len() > max_rpc_size(fork_context, spec.max_chunk_size as usize));
@@ -5,8 +5,8 @@ use crate::discovery::{
     subnet_predicate, DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS,
 };
 use crate::peer_manager::{
-    config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
-    ConnectionDirection, PeerManager, PeerManagerEvent,
+    config::Config as PeerManagerCfg, diversity::GeoIpDatabase, peerdb::score::PeerAction,
+    peerdb::score::ReportSource, ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
 use crate::rpc::methods::MetadataRequest;
@@ -14,9 +14,10 @@ use crate::rpc::*;
 use crate::service::behaviour::BehaviourEvent;
 pub use crate::service::behaviour::Gossipsub;
 use crate::types::{
-    attestation_sync_committee_topics, fork_core_topics, subnet_from_topic_hash, GossipEncoding,
-    GossipKind, GossipTopic, SnappyTransform, Subnet, SubnetDiscovery, ALTAIR_CORE_TOPICS,
-    BASE_CORE_TOPICS, CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS, LIGHT_CLIENT_GOSSIP_TOPICS,
+    attestation_sync_committee_topics, fork_core_topics, is_traced_received_topic,
+    subnet_from_topic_hash, GossipEncoding, GossipKind, GossipTopic, SnappyTransform, Subnet,
+    SubnetDiscovery, ALTAIR_CORE_TOPICS, BASE_CORE_TOPICS, CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS,
+    LIGHT_CLIENT_GOSSIP_TOPICS,
 };
 use crate::EnrExt;
 use crate::Eth2Enr;
@@ -24,21 +25,25 @@ use crate::{error, metrics, Enr, NetworkGlobals, PubsubMessage, TopicHash};
 use api_types::{AppRequestId, PeerRequestId, Request, RequestId, Response};
 use futures::stream::StreamExt;
 use gossipsub::{
-    IdentTopic as Topic, MessageAcceptance, MessageAuthenticity, MessageId, PublishError,
-    TopicScoreParams,
+    IdentTopic as Topic, MessageAcceptance, MessageAuthenticity, MessageId, MessageTrace,
+    PublishError, TopicScoreParams,
 };
 use gossipsub_scoring_parameters::{lighthouse_gossip_thresholds, PeerScoreSettings};
 use libp2p::multiaddr::{self, Multiaddr, Protocol as MProtocol};
 use libp2p::swarm::behaviour::toggle::Toggle;
+use libp2p::swarm::dial_opts::{DialOpts, PeerCondition};
 use libp2p::swarm::{Swarm, SwarmEvent};
 use libp2p::{identify, PeerId, SwarmBuilder};
 use slog::{crit, debug, info, o, trace, warn};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
 use std::num::{NonZeroU8, NonZeroUsize};
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::{
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::Duration,
 };
 use types::{
     consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, EthSpec, ForkContext, Slot, SubnetId,
@@ -56,6 +61,9 @@ pub const TARGET_SUBNET_PEERS: usize = 3;
 
 const MAX_IDENTIFY_ADDRESSES: usize = 10;
 
+/// The interval between attempts to re-dial disconnected persistent peers.
+const PERSISTENT_PEER_REDIAL_INTERVAL: Duration = Duration::from_secs(30);
+
 /// The types of events than can be obtained from polling the behaviour.
 #[derive(Debug)]
 pub enum NetworkEvent<E: EthSpec> {
@@ -126,6 +134,11 @@ pub struct Network<E: EthSpec> {
     /// The interval for updating gossipsub scores
     update_gossipsub_scores: tokio::time::Interval,
     gossip_cache: GossipCache,
+    /// Trusted peers which are re-dialed whenever they disconnect, when only trusted peers are
+    /// allowed.
+    persistent_peers: Vec<(PeerId, Multiaddr)>,
+    /// The interval for re-dialing disconnected `persistent_peers`.
+    redial_persistent_peers: tokio::time::Interval,
     /// This node's PeerId.
     pub local_peer_id: PeerId,
     /// Logger for behaviour actions.
@@ -165,13 +178,25 @@ impl<E: EthSpec> Network<E> {
             )?;
             // Construct the metadata
             let meta_data = utils::load_or_build_metadata(&config.network_dir, &log);
-            let globals = NetworkGlobals::new(
+            let mut globals = NetworkGlobals::new(
                 enr,
                 meta_data,
                 trusted_peers,
                 config.disable_peer_scoring,
                 &log,
             );
+            if let Some(path) = &config.geoip_db {
+                let geoip = GeoIpDatabase::open(path)
+                    .map_err(|e| format!("Unable to load GeoIP database: {e}"))?;
+                info!(log, "Loaded GeoIP database"; "networks" => geoip.len(), "path" => ?path);
+                globals.geoip = Some(geoip);
+            }
+            if config.gossip_trace_capacity > 0 {
+                globals.gossip_trace = Some(Arc::new(Mutex::new(MessageTrace::new(
+                    config.gossip_trace_capacity,
+                    is_traced_received_topic,
+                ))));
+            }
             Arc::new(globals)
         };
 
@@ -283,6 +308,10 @@ impl<E: EthSpec> Network<E> {
                 .with_peer_score(params, thresholds)
                 .expect("Valid score params and thresholds");
 
+            if let Some(message_trace) = &network_globals.gossip_trace {
+                gossipsub.with_message_trace(message_trace.clone());
+            }
+
             // Mark trusted peers as explicit.
             for explicit_peer in config.trusted_peers.iter() {
                 gossipsub.add_explicit_peer(&PeerId::from(explicit_peer.clone()));
@@ -356,6 +385,9 @@ impl<E: EthSpec> Network<E> {
             let peer_manager_cfg = PeerManagerCfg {
                 discovery_enabled: !config.disable_discovery,
                 quic_enabled: !config.disable_quic_support,
+                ip_preference: config.ip_preference,
+                outbound_only: config.outbound_only,
+                trusted_peers_only: config.trusted_peers_only,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
                 ..Default::default()
@@ -443,6 +475,32 @@ impl<E: EthSpec> Network<E> {
             }
         };
 
+        let persistent_peers = if config.trusted_peers_only {
+            config
+                .libp2p_nodes
+                .iter()
+                .filter_map(|multiaddr| {
+                    let peer_id = multiaddr.iter().find_map(|proto| match proto {
+                        MProtocol::P2p(peer_id) => Some(peer_id),
+                        _ => None,
+                    })?;
+                    network_globals
+                        .peers
+                        .read()
+                        .peer_info(&peer_id)
+                        .map_or(false, |info| info.is_trusted())
+                        .then(|| (peer_id, multiaddr.clone()))
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        // The first dial happens on start, so delay the first re-dial.
+        let redial_persistent_peers = tokio::time::interval_at(
+            tokio::time::Instant::now() + PERSISTENT_PEER_REDIAL_INTERVAL,
+            PERSISTENT_PEER_REDIAL_INTERVAL,
+        );
+
         let mut network = Network {
             swarm,
             network_globals,
@@ -452,6 +510,8 @@ impl<E: EthSpec> Network<E> {
             score_settings,
             update_gossipsub_scores,
             gossip_cache,
+            persistent_peers,
+            redial_persistent_peers,
             local_peer_id,
             log,
         };
@@ -473,7 +533,18 @@ impl<E: EthSpec> Network<E> {
         info!(self.log, "Libp2p Starting"; "peer_id" => %enr.peer_id(), "bandwidth_config" => format!("{}-{}", config.network_load, NetworkLoad::from(config.network_load).name));
         debug!(self.log, "Attempting to open listening ports"; config.listen_addrs(), "discovery_enabled" => !config.disable_discovery, "quic_enabled" => !config.disable_quic_support);
 
-        for listen_multiaddr in config.listen_addrs().libp2p_addresses() {
+        if config.outbound_only {
+            info!(
+                self.log,
+                "Not listening for libp2p connections, only outbound connections will be made"
+            );
+        }
+
+        for listen_multiaddr in config
+            .listen_addrs()
+            .libp2p_addresses()
+            .filter(|_| !config.outbound_only)
+        {
             // If QUIC is disabled, ignore listening on QUIC ports
             if config.disable_quic_support
                 && listen_multiaddr.iter().any(|v| v == MProtocol::QuicV1)
@@ -516,8 +587,17 @@ impl<E: EthSpec> Network<E> {
             dial(multiaddr.clone());
         }
 
+        // A node behind sentries only connects to its trusted peers.
+        let (mut boot_nodes, boot_nodes_multiaddr) = if config.trusted_peers_only {
+            (vec![], vec![])
+        } else {
+            (
+                config.boot_nodes_enr.clone(),
+                config.boot_nodes_multiaddr.clone(),
+            )
+        };
+
         // attempt to connect to any specified boot-nodes
-        let mut boot_nodes = config.boot_nodes_enr.clone();
         boot_nodes.dedup();
 
         for bootnode_enr in boot_nodes {
@@ -553,7 +633,7 @@ impl<E: EthSpec> Network<E> {
             }
         }
 
-        for multiaddr in &config.boot_nodes_multiaddr {
+        for multiaddr in &boot_nodes_multiaddr {
             // check TCP support for dialing
             if multiaddr
                 .iter()
@@ -776,6 +856,12 @@ impl<E: EthSpec> Network<E> {
         }
     }
 
+    /// Raise the target mesh degree of a gossipsub topic to `mesh_n_high`, or restore it.
+    pub fn set_mesh_boost(&mut self, topic: GossipTopic, boost: bool) {
+        let topic: Topic = topic.into();
+        self.gossipsub_mut().set_mesh_boost(&topic.hash(), boost);
+    }
+
     /// Publishes a list of messages on the pubsub (gossipsub) behaviour, choosing the encoding.
     pub fn publish(&mut self, messages: Vec<PubsubMessage<E>>) {
         for message in messages {
@@ -1005,6 +1091,27 @@ impl<E: EthSpec> Network<E> {
         self.update_metadata_bitfields();
     }
 
+    /// Updates custom fields and the UDP socket of the local ENR, optionally forcing a new sequence
+    /// number so that peers re-fetch it.
+    pub fn update_enr(
+        &mut self,
+        fields: &BTreeMap<String, Vec<u8>>,
+        udp_socket: Option<SocketAddr>,
+        refresh: bool,
+    ) {
+        if let Some(socket_addr) = udp_socket {
+            if let Err(e) = self.discovery_mut().update_enr_udp_socket(socket_addr) {
+                warn!(self.log, "Could not update ENR socket"; "error" => e);
+            }
+        }
+        if let Err(e) = self
+            .discovery_mut()
+            .update_enr_custom_fields(fields, refresh)
+        {
+            warn!(self.log, "Could not update ENR fields"; "error" => e);
+        }
+    }
+
     /// Attempts to discover new peers for a given subnet. The `min_ttl` gives the time at which we
     /// would like to retain the peers for.
     pub fn discover_subnet_peers(&mut self, subnets_to_discover: Vec<SubnetDiscovery>) {
@@ -1866,6 +1973,11 @@ impl<E: EthSpec> Network<E> {
             this.peer_manager.update_gossipsub_scores(&this.gossipsub);
         }
 
+        // re-dial any persistent peers which have disconnected
+        while self.redial_persistent_peers.poll_tick(cx).is_ready() {
+            self.redial_persistent_peers();
+        }
+
         // poll the gossipsub cache to clear expired messages
         while let Poll::Ready(Some(result)) = self.gossip_cache.poll_next_unpin(cx) {
             match result {
@@ -1883,6 +1995,32 @@ impl<E: EthSpec> Network<E> {
         Poll::Pending
     }
 
+    /// Dial the persistent peers which are neither connected nor being dialed.
+    fn redial_persistent_peers(&mut self) {
+        for (peer_id, multiaddr) in &self.persistent_peers {
+            if self
+                .network_globals
+                .peers
+                .read()
+                .is_connected_or_dialing(peer_id)
+            {
+                continue;
+            }
+            let mut address = multiaddr.clone();
+            strip_peer_id(&mut address);
+            let opts = DialOpts::peer_id(*peer_id)
+                .condition(PeerCondition::Disconnected)
+                .addresses(vec![address])
+                .build();
+            match self.swarm.dial(opts) {
+                Ok(()) => debug!(self.log, "Re-dialing persistent peer"; "peer_id" => %peer_id),
+                Err(e) => {
+                    debug!(self.log, "Could not re-dial persistent peer"; "peer_id" => %peer_id, "error" => ?e)
+                }
+            }
+        }
+    }
+
     pub async fn next_event(&mut self) -> NetworkEvent<E> {
         futures::future::poll_fn(|cx| self.poll_network(cx)).await
     }
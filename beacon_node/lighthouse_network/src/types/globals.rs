@@ -1,12 +1,15 @@
 //! A collection of variables that are accessible outside of the network thread itself.
+use crate::peer_manager::diversity::{GeoIpDatabase, PeerDiversity};
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV2};
-use crate::types::{BackFillState, SyncState};
+use crate::types::{BackFillState, GossipTrace, SyncState};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
+use gossipsub::MessageTrace;
 use parking_lot::RwLock;
 use std::collections::HashSet;
+use std::sync::{Arc, Mutex, PoisonError};
 use types::EthSpec;
 
 pub struct NetworkGlobals<E: EthSpec> {
@@ -26,6 +29,10 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// The GeoIP database used to locate peers, if one was provided.
+    pub geoip: Option<GeoIpDatabase>,
+    /// The propagation traces of gossip messages, shared with gossipsub, if tracing is enabled.
+    pub gossip_trace: Option<Arc<Mutex<MessageTrace>>>,
 }
 
 impl<E: EthSpec> NetworkGlobals<E> {
@@ -45,6 +52,8 @@ impl<E: EthSpec> NetworkGlobals<E> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            geoip: None,
+            gossip_trace: None,
         }
     }
 
@@ -64,6 +73,19 @@ impl<E: EthSpec> NetworkGlobals<E> {
         self.listen_multiaddrs.read().clone()
     }
 
+    /// Returns the diversity of the connected peers by client, country and hosting provider.
+    pub fn peer_diversity(&self) -> PeerDiversity {
+        PeerDiversity::new(&self.peers.read(), self.geoip.as_ref())
+    }
+
+    /// Returns the propagation traces of the most recent gossip messages, if tracing is enabled.
+    pub fn gossip_trace(&self) -> Option<GossipTrace> {
+        let trace = self.gossip_trace.as_ref()?;
+        Some(GossipTrace::from(
+            &*trace.lock().unwrap_or_else(PoisonError::into_inner),
+        ))
+    }
+
     /// Returns the number of libp2p connected peers.
     pub fn connected_peers(&self) -> usize {
         self.peers.read().connected_peer_ids().count()
@@ -0,0 +1,91 @@
+//! The propagation traces of gossip messages, as returned by `/lighthouse/network/gossip_trace`.
+use super::topics::{GossipKind, GossipTopic};
+use gossipsub::{MessageId, MessageTrace, TopicHash};
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The default number of published and received messages which are traced.
+pub const DEFAULT_GOSSIP_TRACE_CAPACITY: usize = 256;
+
+/// The traces of the most recently published and received gossip messages, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GossipTrace {
+    pub published: Vec<PublishedGossipTrace>,
+    pub received: Vec<ReceivedGossipTrace>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublishedGossipTrace {
+    pub message_id: String,
+    pub topic: String,
+    pub published_at_ms: u64,
+    /// Peers the message was queued for.
+    pub sent_to: Vec<String>,
+    /// Peers the message could not be queued for.
+    pub failed: Vec<String>,
+    /// Peers which advertised the message back to us in an IHAVE.
+    pub ihave_from: Vec<String>,
+    /// Peers which requested the message from us with an IWANT.
+    pub iwant_from: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceivedGossipTrace {
+    pub message_id: String,
+    pub topic: String,
+    pub first_seen_at_ms: u64,
+    pub first_seen_from: String,
+    pub duplicates: u64,
+}
+
+impl From<&MessageTrace> for GossipTrace {
+    fn from(trace: &MessageTrace) -> Self {
+        let peers =
+            |peers: &[PeerId]| -> Vec<String> { peers.iter().map(ToString::to_string).collect() };
+        GossipTrace {
+            published: trace
+                .published()
+                .map(|(message_id, published)| PublishedGossipTrace {
+                    message_id: encode_message_id(message_id),
+                    topic: published.topic.to_string(),
+                    published_at_ms: unix_millis(published.published_at),
+                    sent_to: peers(&published.sent_to),
+                    failed: peers(&published.failed),
+                    ihave_from: peers(&published.ihave_from),
+                    iwant_from: peers(&published.iwant_from),
+                })
+                .collect(),
+            received: trace
+                .received()
+                .map(|(message_id, received)| ReceivedGossipTrace {
+                    message_id: encode_message_id(message_id),
+                    topic: received.topic.to_string(),
+                    first_seen_at_ms: unix_millis(received.first_seen_at),
+                    first_seen_from: received.first_seen_from.to_string(),
+                    duplicates: received.duplicates,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Received messages are only traced on the block and aggregate topics, so that their traces
+/// aren't evicted by the far more numerous unaggregated attestations.
+pub fn is_traced_received_topic(topic: &TopicHash) -> bool {
+    GossipTopic::decode(topic.as_str()).map_or(false, |topic| {
+        matches!(
+            topic.kind(),
+            GossipKind::BeaconBlock | GossipKind::BeaconAggregateAndProof
+        )
+    })
+}
+
+fn encode_message_id(message_id: &MessageId) -> String {
+    format!("0x{}", hex::encode(&message_id.0))
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
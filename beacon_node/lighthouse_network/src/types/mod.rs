@@ -1,5 +1,6 @@
 pub mod error;
 mod globals;
+mod gossip_trace;
 mod pubsub;
 mod subnet;
 mod sync_state;
@@ -13,6 +14,10 @@ pub type EnrSyncCommitteeBitfield<E> = BitVector<<E as EthSpec>::SyncCommitteeSu
 pub type Enr = discv5::enr::Enr<discv5::enr::CombinedKey>;
 
 pub use globals::NetworkGlobals;
+pub use gossip_trace::{
+    is_traced_received_topic, GossipTrace, PublishedGossipTrace, ReceivedGossipTrace,
+    DEFAULT_GOSSIP_TRACE_CAPACITY,
+};
 pub use pubsub::{PubsubMessage, SnappyTransform};
 pub use subnet::{Subnet, SubnetDiscovery};
 pub use sync_state::{BackFillState, SyncState};
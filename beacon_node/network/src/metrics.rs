@@ -42,6 +42,10 @@ lazy_static! {
         "validator_subnet_subscriptions_aggregator_total",
         "Count of validator subscription requests where the subscriber is an aggregator."
     );
+    pub static ref AGGREGATOR_BOOSTED_SUBNETS: Result<IntGauge> = try_create_int_gauge(
+        "validator_subnet_aggregator_boosted_subnets",
+        "Number of attestation subnets whose gossipsub mesh degree is raised for aggregation duties."
+    );
     pub static ref SYNC_COMMITTEE_SUBSCRIPTION_REQUESTS: Result<IntCounter> = try_create_int_counter(
         "validator_sync_committee_subnet_subscriptions_total",
         "Count of validator sync committee subscription requests."
@@ -11,10 +11,12 @@ use beacon_chain::store::Error;
 use beacon_chain::{
     attestation_verification::{self, Error as AttnError, VerifiedAttestation},
     data_availability_checker::AvailabilityCheckErrorCategory,
+    gossip_signature_batch::GossipSignatureBatchResults,
     light_client_finality_update_verification::Error as LightClientFinalityUpdateError,
     light_client_optimistic_update_verification::Error as LightClientOptimisticUpdateError,
     observed_operations::ObservationOutcome,
     sync_committee_verification::{self, Error as SyncCommitteeError},
+    timeliness::ArrivalKind,
     validator_monitor::{get_block_delay_ms, get_slot_delay_ms},
     AvailabilityProcessingStatus, BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError,
     GossipVerifiedBlock, NotifyExecutionLayer,
@@ -31,6 +33,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use store::hot_cold_store::HotColdDBError;
 use tokio::sync::mpsc;
+use tracing::instrument;
 use types::{
     beacon_block::BlockImportSource, Attestation, AttestationRef, AttesterSlashing, BlobSidecar,
     DataColumnSidecar, DataColumnSubnetId, EthSpec, Hash256, IndexedAttestation,
@@ -45,7 +48,7 @@ use beacon_processor::{
         QueuedAggregate, QueuedGossipBlock, QueuedLightClientUpdate, QueuedUnaggregate,
         ReprocessQueueMessage,
     },
-    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage,
+    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage, GossipSignatureBatch,
 };
 
 /// Set to `true` to introduce stricter penalties for peers who send some types of late consensus
@@ -195,6 +198,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     ///
     /// Raises a log if there are errors.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(name = "gossip_attestation", skip_all, fields(slot = %attestation.data().slot))]
     pub fn process_gossip_attestation(
         self: Arc<Self>,
         message_id: MessageId,
@@ -251,6 +255,24 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             }
         };
 
+        // Map the results into a new `Vec` so that `results` no longer holds a reference to
+        // `packages`.
+        #[allow(clippy::needless_collect)] // The clippy suggestion fails the borrow checker.
+        let results = results
+            .into_iter()
+            .map(|result| result.map(|verified| verified.into_indexed_attestation()))
+            .collect::<Vec<_>>();
+
+        self.process_gossip_attestation_batch_results(packages, results, reprocess_tx);
+    }
+
+    /// Handles the results of the batch verification of the attestations in `packages`.
+    fn process_gossip_attestation_batch_results(
+        self: &Arc<Self>,
+        packages: Vec<GossipAttestationPackage<T::EthSpec>>,
+        results: Vec<Result<IndexedAttestation<T::EthSpec>, AttnError>>,
+        reprocess_tx: Option<mpsc::Sender<ReprocessQueueMessage>>,
+    ) {
         // Sanity check.
         if results.len() != packages.len() {
             // The log is `crit` since in this scenario we might be penalizing/rewarding the wrong
@@ -263,14 +285,6 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             )
         }
 
-        // Map the results into a new `Vec` so that `results` no longer holds a reference to
-        // `packages`.
-        #[allow(clippy::needless_collect)] // The clippy suggestion fails the borrow checker.
-        let results = results
-            .into_iter()
-            .map(|result| result.map(|verified| verified.into_indexed_attestation()))
-            .collect::<Vec<_>>();
-
         for (result, package) in results.into_iter().zip(packages.into_iter()) {
             let result = match result {
                 Ok(indexed_attestation) => Ok(VerifiedUnaggregate {
@@ -462,6 +476,24 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             }
         };
 
+        // Map the results into a new `Vec` so that `results` no longer holds a reference to
+        // `packages`.
+        #[allow(clippy::needless_collect)] // The clippy suggestion fails the borrow checker.
+        let results = results
+            .into_iter()
+            .map(|result| result.map(|verified| verified.into_indexed_attestation()))
+            .collect::<Vec<_>>();
+
+        self.process_gossip_aggregate_batch_results(packages, results, reprocess_tx);
+    }
+
+    /// Handles the results of the batch verification of the aggregates in `packages`.
+    fn process_gossip_aggregate_batch_results(
+        self: &Arc<Self>,
+        packages: Vec<GossipAggregatePackage<T::EthSpec>>,
+        results: Vec<Result<IndexedAttestation<T::EthSpec>, AttnError>>,
+        reprocess_tx: Option<mpsc::Sender<ReprocessQueueMessage>>,
+    ) {
         // Sanity check.
         if results.len() != packages.len() {
             // The log is `crit` since in this scenario we might be penalizing/rewarding the wrong
@@ -474,14 +506,6 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             )
         }
 
-        // Map the results into a new `Vec` so that `results` no longer holds a reference to
-        // `packages`.
-        #[allow(clippy::needless_collect)] // The clippy suggestion fails the borrow checker.
-        let results = results
-            .into_iter()
-            .map(|result| result.map(|verified| verified.into_indexed_attestation()))
-            .collect::<Vec<_>>();
-
         for (result, package) in results.into_iter().zip(packages.into_iter()) {
             let result = match result {
                 Ok(indexed_attestation) => Ok(VerifiedAggregate {
@@ -505,6 +529,82 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         }
     }
 
+    /// Process a batch of unaggregated attestations, aggregates and sync committee messages
+    /// received from the gossip network, verifying all of their signatures in a single batch.
+    ///
+    /// Each message is then handled in the same way as `Self::process_gossip_attestation`,
+    /// `Self::process_gossip_aggregate` and `Self::process_gossip_sync_committee_signature`.
+    pub fn process_gossip_signature_batch(
+        self: Arc<Self>,
+        batch: GossipSignatureBatch<T::EthSpec>,
+        reprocess_tx: Option<mpsc::Sender<ReprocessQueueMessage>>,
+    ) {
+        let GossipSignatureBatch {
+            attestations,
+            aggregates,
+            sync_messages,
+        } = batch;
+
+        let GossipSignatureBatchResults {
+            attestations: attestation_results,
+            aggregates: aggregate_results,
+            sync_messages: sync_message_results,
+        } = self.chain.batch_verify_gossip_signatures(
+            attestations
+                .iter()
+                .map(|package| (package.attestation.as_ref(), Some(package.subnet_id))),
+            aggregates.iter().map(|package| package.aggregate.as_ref()),
+            sync_messages
+                .iter()
+                .map(|package| (*package.sync_signature.clone(), package.subnet_id)),
+        );
+
+        // Map the results into new `Vec`s so that they no longer hold references to the packages.
+        #[allow(clippy::needless_collect)] // The clippy suggestion fails the borrow checker.
+        let attestation_results = attestation_results
+            .into_iter()
+            .map(|result| result.map(|verified| verified.into_indexed_attestation()))
+            .collect::<Vec<_>>();
+        #[allow(clippy::needless_collect)] // The clippy suggestion fails the borrow checker.
+        let aggregate_results = aggregate_results
+            .into_iter()
+            .map(|result| result.map(|verified| verified.into_indexed_attestation()))
+            .collect::<Vec<_>>();
+
+        self.process_gossip_aggregate_batch_results(
+            aggregates,
+            aggregate_results,
+            reprocess_tx.clone(),
+        );
+        self.process_gossip_attestation_batch_results(
+            attestations,
+            attestation_results,
+            reprocess_tx,
+        );
+
+        // Sanity check.
+        if sync_message_results.len() != sync_messages.len() {
+            // The log is `crit` since in this scenario we might be penalizing/rewarding the wrong
+            // peer.
+            crit!(
+                self.log,
+                "Batch sync message result mismatch";
+                "results" => sync_message_results.len(),
+                "packages" => sync_messages.len(),
+            )
+        }
+
+        for (result, package) in sync_message_results.into_iter().zip(sync_messages) {
+            self.process_gossip_sync_committee_signature_result(
+                result,
+                package.message_id,
+                package.peer_id,
+                package.sync_signature.slot,
+                package.seen_timestamp,
+            );
+        }
+    }
+
     fn process_gossip_aggregate_result(
         self: &Arc<Self>,
         result: Result<VerifiedAggregate<T>, RejectedAggregate<T::EthSpec>>,
@@ -541,6 +641,13 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     &metrics::BEACON_PROCESSOR_AGGREGATED_ATTESTATION_VERIFIED_TOTAL,
                 );
 
+                let slot = verified_aggregate.attestation().data().slot;
+                self.chain.record_arrival(
+                    ArrivalKind::Aggregate,
+                    slot,
+                    get_slot_delay_ms(seen_timestamp, slot, &self.chain.slot_clock),
+                );
+
                 if let Err(e) = self
                     .chain
                     .apply_attestation_to_fork_choice(&verified_aggregate)
@@ -685,6 +792,8 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         {
             Ok(gossip_verified_blob) => {
                 metrics::inc_counter(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFIED_TOTAL);
+                self.chain
+                    .record_arrival(ArrivalKind::BlobSidecar, slot, delay);
 
                 if delay >= self.chain.slot_clock.unagg_attestation_production_delay() {
                     metrics::inc_counter(&metrics::BEACON_BLOB_GOSSIP_ARRIVED_LATE_TOTAL);
@@ -824,6 +933,8 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 }
             }
         }
+
+        self.publish_detected_proposer_slashings();
     }
 
     pub async fn process_gossip_verified_blob(
@@ -983,6 +1094,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     ///
     /// Raises a log if there are errors.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(name = "gossip_block", skip_all, fields(slot = %block.slot()))]
     pub async fn process_gossip_block(
         self: Arc<Self>,
         message_id: MessageId,
@@ -1026,6 +1138,8 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 );
             }
         }
+
+        self.publish_detected_proposer_slashings();
     }
 
     /// Process the beacon block received from the gossip network and
@@ -1056,6 +1170,8 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 &metrics::BEACON_BLOCK_DELAY_GOSSIP,
                 block_delay.as_millis() as i64,
             );
+            self.chain
+                .record_arrival(ArrivalKind::Block, block.slot(), block_delay);
         }
 
         let block_root = if let Ok(verified_block) = &verification_result {
@@ -1741,10 +1857,31 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         seen_timestamp: Duration,
     ) {
         let message_slot = sync_signature.slot;
-        let sync_signature = match self
+        let result = self
             .chain
-            .verify_sync_committee_message_for_gossip(sync_signature, subnet_id)
-        {
+            .verify_sync_committee_message_for_gossip(sync_signature, subnet_id);
+
+        self.process_gossip_sync_committee_signature_result(
+            result,
+            message_id,
+            peer_id,
+            message_slot,
+            seen_timestamp,
+        );
+    }
+
+    fn process_gossip_sync_committee_signature_result(
+        self: &Arc<Self>,
+        result: Result<
+            sync_committee_verification::VerifiedSyncCommitteeMessage,
+            SyncCommitteeError,
+        >,
+        message_id: MessageId,
+        peer_id: PeerId,
+        message_slot: Slot,
+        seen_timestamp: Duration,
+    ) {
+        let sync_signature = match result {
             Ok(sync_signature) => sync_signature,
             Err(e) => {
                 self.handle_sync_committee_message_failure(
@@ -5,13 +5,13 @@ use beacon_chain::{builder::Witness, eth1_chain::CachingEth1Backend, BeaconChain
 use beacon_chain::{BeaconChainTypes, NotifyExecutionLayer};
 use beacon_processor::{
     work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorChannels, BeaconProcessorSend,
-    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage, Work,
-    WorkEvent as BeaconWorkEvent,
+    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage, GossipSignatureBatchFn,
+    GossipSyncSignaturePackage, Work, WorkEvent as BeaconWorkEvent,
 };
 use lighthouse_network::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
 use lighthouse_network::{
     rpc::{BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest, StatusMessage},
-    Client, MessageId, NetworkGlobals, PeerId, PeerRequestId,
+    Client, MessageId, NetworkGlobals, PeerId, PeerRequestId, PubsubMessage,
 };
 use slog::{debug, Logger};
 use slot_clock::ManualSlotClock;
@@ -65,6 +65,16 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             .map_err(Into::into)
     }
 
+    /// Returns a closure for processing a batch of gossip attestations, aggregates and sync
+    /// committee messages.
+    fn gossip_signature_batch_fn(self: &Arc<Self>) -> GossipSignatureBatchFn<T::EthSpec> {
+        let processor = self.clone();
+        Box::new(move |batch| {
+            let reprocess_tx = processor.reprocess_tx.clone();
+            processor.process_gossip_signature_batch(batch, Some(reprocess_tx))
+        })
+    }
+
     /// Create a new `Work` event for some unaggregated attestation.
     pub fn send_unaggregated_attestation(
         self: &Arc<Self>,
@@ -110,6 +120,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 }),
                 process_individual: Box::new(process_individual),
                 process_batch: Box::new(process_batch),
+                process_signature_batch: self.gossip_signature_batch_fn(),
             },
         })
     }
@@ -155,6 +166,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 }),
                 process_individual: Box::new(process_individual),
                 process_batch: Box::new(process_batch),
+                process_signature_batch: self.gossip_signature_batch_fn(),
             },
         })
     }
@@ -263,19 +275,29 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         seen_timestamp: Duration,
     ) -> Result<(), Error<T::EthSpec>> {
         let processor = self.clone();
-        let process_fn = move || {
+        let process_individual = move |package: GossipSyncSignaturePackage| {
             processor.process_gossip_sync_committee_signature(
-                message_id,
-                peer_id,
-                sync_signature,
-                subnet_id,
-                seen_timestamp,
+                package.message_id,
+                package.peer_id,
+                *package.sync_signature,
+                package.subnet_id,
+                package.seen_timestamp,
             )
         };
 
         self.try_send(BeaconWorkEvent {
             drop_during_sync: true,
-            work: Work::GossipSyncSignature(Box::new(process_fn)),
+            work: Work::GossipSyncSignature {
+                sync_signature: Box::new(GossipSyncSignaturePackage {
+                    message_id,
+                    peer_id,
+                    sync_signature: Box::new(sync_signature),
+                    subnet_id,
+                    seen_timestamp,
+                }),
+                process_individual: Box::new(process_individual),
+                process_signature_batch: self.gossip_signature_batch_fn(),
+            },
         })
     }
 
@@ -513,6 +535,40 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to verify the signatures of `blocks` ahead of their import, using
+    /// the state of the `anchor_root` block.
+    ///
+    /// No result is sent back to sync. If successful, the verified blocks are used when the same
+    /// blocks are later sent via `Self::send_chain_segment`.
+    pub fn send_speculative_chain_segment_verification(
+        self: &Arc<Self>,
+        anchor_root: Hash256,
+        blocks: Vec<RpcBlock<T::EthSpec>>,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn = async move {
+            let num_blocks = blocks.len();
+            if let Err(e) = processor
+                .chain
+                .speculatively_verify_chain_segment(anchor_root, blocks)
+                .await
+            {
+                debug!(
+                    processor.log,
+                    "Speculative chain segment verification failed";
+                    "blocks" => num_blocks,
+                    "anchor_root" => ?anchor_root,
+                    "error" => ?e,
+                );
+            }
+        };
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::ChainSegment(Box::pin(process_fn)),
+        })
+    }
+
     /// Create a new work event to process `StatusMessage`s from the RPC network.
     pub fn send_status_message(
         self: &Arc<Self>,
@@ -660,6 +716,19 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         });
     }
 
+    /// Publish any proposer slashings constructed by the beacon chain's equivocation detection.
+    fn publish_detected_proposer_slashings(&self) {
+        let slashings = self.chain.take_detected_proposer_slashings();
+        if !slashings.is_empty() {
+            self.send_network_message(NetworkMessage::Publish {
+                messages: slashings
+                    .into_iter()
+                    .map(|slashing| PubsubMessage::ProposerSlashing(Box::new(slashing)))
+                    .collect(),
+            });
+        }
+    }
+
     /// Send a message to `network_tx`.
     ///
     /// Creates a log if there is an internal error.
@@ -163,6 +163,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             .await;
 
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_RPC_BLOCK_IMPORTED_TOTAL);
+        self.publish_detected_proposer_slashings();
 
         // RPC block imported, regardless of process type
         if let &Ok(AvailabilityProcessingStatus::Imported(hash)) = &result {
@@ -261,6 +262,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         }
 
         let result = self.chain.process_rpc_blobs(slot, block_root, blobs).await;
+        self.publish_detected_proposer_slashings();
 
         match &result {
             Ok(AvailabilityProcessingStatus::Imported(hash)) => {
@@ -27,7 +27,8 @@ use lighthouse_network::{
     MessageId, NetworkEvent, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::SocketAddr;
 use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
 use strum::IntoStaticStr;
@@ -101,6 +102,13 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Set custom fields and the UDP socket of the local ENR, and optionally force a new
+    /// sequence number.
+    UpdateEnr {
+        fields: BTreeMap<String, Vec<u8>>,
+        udp_socket: Option<SocketAddr>,
+        refresh: bool,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -185,6 +193,10 @@ pub struct NetworkService<T: BeaconChainTypes> {
     next_unsubscribe: Pin<Box<OptionFuture<Sleep>>>,
     /// Subscribe to all the subnets once synced.
     subscribe_all_subnets: bool,
+    /// Raise the mesh degree of subnets with upcoming aggregation duties.
+    aggregator_mesh_boost: bool,
+    /// The subnets whose mesh degree is currently raised for aggregation duties.
+    boosted_subnets: HashSet<SubnetId>,
     /// Shutdown beacon node after sync is complete.
     shutdown_after_sync: bool,
     /// Whether metrics are enabled or not.
@@ -350,6 +362,8 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             next_fork_subscriptions,
             next_unsubscribe,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            aggregator_mesh_boost: config.aggregator_mesh_boost,
+            boosted_subnets: HashSet::new(),
             shutdown_after_sync: config.shutdown_after_sync,
             metrics_enabled: config.metrics_enabled,
             metrics_update,
@@ -446,7 +460,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                         metrics::update_sync_metrics(&self.network_globals);
                     }
 
-                    _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
+                    _ = self.gossipsub_parameter_update.tick() => {
+                        self.update_gossipsub_parameters();
+                        // Catch up on a fork which was reached while the node was offline.
+                        self.rebroadcast_at_fork();
+                    }
 
                     // handle a message sent to the network
                     Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, &mut shutdown_sender).await,
@@ -673,6 +691,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::UpdateEnr {
+                fields,
+                udp_socket,
+                refresh,
+            } => self.libp2p.update_enr(&fields, udp_socket, refresh),
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -789,6 +812,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 {
                     warn!(self.log, "Attestation validator subscription failed"; "error" => e);
                 }
+                self.update_aggregator_mesh_boost();
             }
             ValidatorSubscriptionMessage::SyncCommitteeSubscribe { subscriptions } => {
                 if let Err(e) = self
@@ -858,6 +882,50 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 self.libp2p.discover_subnet_peers(subnets_to_discover);
             }
         }
+        self.update_aggregator_mesh_boost();
+    }
+
+    /// Raise the mesh degree of the subnets we are subscribed to for aggregation duties, so that
+    /// the mesh has formed by the time of the duty, and restore it once the duty has passed.
+    fn update_aggregator_mesh_boost(&mut self) {
+        if !self.aggregator_mesh_boost {
+            return;
+        }
+        let aggregator_subnets = self
+            .attestation_service
+            .aggregator_subnets()
+            .copied()
+            .collect::<HashSet<_>>();
+        if aggregator_subnets == self.boosted_subnets {
+            return;
+        }
+
+        let fork_digests = self.required_gossip_fork_digests();
+        let changes = aggregator_subnets
+            .difference(&self.boosted_subnets)
+            .map(|subnet_id| (*subnet_id, true))
+            .chain(
+                self.boosted_subnets
+                    .difference(&aggregator_subnets)
+                    .map(|subnet_id| (*subnet_id, false)),
+            )
+            .collect::<Vec<_>>();
+        for (subnet_id, boost) in changes {
+            debug!(self.log, "Updating aggregator mesh boost"; "subnet" => ?subnet_id, "boost" => boost);
+            for fork_digest in &fork_digests {
+                let topic = GossipTopic::new(
+                    Subnet::Attestation(subnet_id).into(),
+                    GossipEncoding::default(),
+                    *fork_digest,
+                );
+                self.libp2p.set_mesh_boost(topic, boost);
+            }
+        }
+        metrics::set_gauge(
+            &metrics::AGGREGATOR_BOOSTED_SUBNETS,
+            aggregator_subnets.len() as i64,
+        );
+        self.boosted_subnets = aggregator_subnets;
     }
 
     fn on_sync_committee_service_message(&mut self, msg: SubnetServiceMessage) {
@@ -920,11 +988,42 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             // Remove topic weight from old fork topics to prevent peers that left on the mesh on
             // old topics from being penalized for not sending us messages.
             self.libp2p.remove_topic_weight_except(new_fork_digest);
+
+            self.rebroadcast_at_fork();
         } else {
             crit!(self.log, "Unknown new enr fork id"; "new_fork_id" => ?new_enr_fork_id);
         }
     }
 
+    /// Publish the voluntary exits and BLS to execution changes which were received shortly
+    /// before the fork on the topics of the new fork, once it has been reached.
+    fn rebroadcast_at_fork(&mut self) {
+        if self.network_globals.connected_peers() == 0 {
+            return;
+        }
+        match self.beacon_chain.take_fork_rebroadcast() {
+            Ok(rebroadcast) => {
+                let messages = rebroadcast
+                    .voluntary_exits
+                    .into_iter()
+                    .map(|exit| PubsubMessage::VoluntaryExit(Box::new(exit)))
+                    .chain(
+                        rebroadcast
+                            .bls_to_execution_changes
+                            .into_iter()
+                            .map(|change| PubsubMessage::BlsToExecutionChange(Box::new(change))),
+                    )
+                    .collect::<Vec<_>>();
+                if !messages.is_empty() {
+                    self.libp2p.publish(messages);
+                }
+            }
+            Err(e) => {
+                warn!(self.log, "Unable to re-broadcast operations at fork"; "error" => ?e);
+            }
+        }
+    }
+
     fn subscribed_core_topics(&self) -> bool {
         let core_topics = core_topics_to_subscribe::<T::EthSpec>(
             self.fork_context.current_fork(),
@@ -24,10 +24,11 @@ use crate::metrics;
 /// slot is less than this number, skip the peer discovery process.
 /// Subnet discovery query takes at most 30 secs, 2 slots take 24s.
 pub(crate) const MIN_PEER_DISCOVERY_SLOT_LOOK_AHEAD: u64 = 2;
-/// The fraction of a slot that we subscribe to a subnet before the required slot.
+/// The number of slots that we subscribe to a subnet before an aggregation duty.
 ///
-/// Currently a whole slot ahead.
-const ADVANCE_SUBSCRIBE_SLOT_FRACTION: u32 = 1;
+/// Subscribing two slots ahead allows the mesh to form, and unaggregated attestations to start
+/// flowing, before the duty.
+pub(crate) const ADVANCE_SUBSCRIBE_SLOTS: u32 = 2;
 
 /// The number of slots after an aggregator duty where we remove the entry from
 /// `aggregate_validators_on_subnet` delay map.
@@ -399,6 +400,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             .unwrap_or(true)
     }
 
+    /// The subnets we are currently subscribed to for aggregation duties.
+    pub(crate) fn aggregator_subnets(&self) -> impl Iterator<Item = &SubnetId> {
+        self.short_lived_subscriptions.keys()
+    }
+
     /* Internal private functions */
 
     /// Adds an event to the event queue and notifies that this service is ready to be polled
@@ -469,7 +475,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         // The short time we schedule the subscription before it's actually required. This
         // ensures we are subscribed on time, and allows consecutive subscriptions to the same
         // subnet to overlap, reducing subnet churn.
-        let advance_subscription_duration = slot_duration / ADVANCE_SUBSCRIBE_SLOT_FRACTION;
+        let advance_subscription_duration = slot_duration * ADVANCE_SUBSCRIBE_SLOTS;
         // The time to the required slot.
         let time_to_subscription_slot = self
             .beacon_chain
@@ -613,8 +613,9 @@ mod attestation_service {
         assert_eq!(attestation_service.subscription_count(), 2);
 
         println!("{events:?}");
-        let subscription_slot = current_slot + subscription_slot2 - 1; // one less do to the
-                                                                       // advance subscription time
+        // less the advance subscription time
+        let subscription_slot =
+            current_slot + subscription_slot2 - attestation_subnets::ADVANCE_SUBSCRIBE_SLOTS as u64;
         let wait_slots = attestation_service
             .beacon_chain
             .slot_clock
@@ -379,6 +379,14 @@ impl<E: EthSpec, B: BatchConfig> BatchInfo<E, B> {
         }
     }
 
+    /// Returns the downloaded blocks of the batch if it is awaiting processing.
+    pub fn blocks_awaiting_processing(&self) -> Option<&[RpcBlock<E>]> {
+        match &self.state {
+            BatchState::AwaitingProcessing(_, blocks, _) => Some(blocks),
+            _ => None,
+        }
+    }
+
     pub fn start_processing(&mut self) -> Result<(Vec<RpcBlock<E>>, Duration), WrongState> {
         match self.state.poison() {
             BatchState::AwaitingProcessing(peer, blocks, start_instant) => {
@@ -4,7 +4,7 @@ use crate::metrics;
 use crate::network_beacon_processor::ChainSegmentProcessId;
 use crate::sync::network_context::RangeRequestId;
 use crate::sync::{network_context::SyncNetworkContext, BatchOperationOutcome, BatchProcessResult};
-use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::block_verification_types::{AsBlock, RpcBlock};
 use beacon_chain::BeaconChainTypes;
 use fnv::FnvHashMap;
 use lighthouse_network::service::api_types::Id;
@@ -25,6 +25,11 @@ use types::{Epoch, EthSpec, Hash256, Slot};
 pub const EPOCHS_PER_BATCH: u64 = 1;
 
 /// The maximum number of batches to queue before requesting more.
+///
+/// Together with speculative verification this pipelines range sync: whilst batch `N` is imported,
+/// batch `N + 1` has its signatures and payload block hashes verified and the following batches
+/// are downloaded. Downloads stop once this many batches are waiting, so that a node which imports
+/// slower than it downloads does not buffer an unbounded number of blocks.
 const BATCH_BUFFER_SIZE: u8 = 5;
 
 /// A return type for functions that act on a `Chain` which informs the caller whether the chain
@@ -111,6 +116,16 @@ pub struct SyncingChain<T: BeaconChainTypes> {
     /// The current processing batch, if any.
     current_processing_batch: Option<BatchId>,
 
+    /// The parent root of the first block of the current processing batch, if any.
+    ///
+    /// This is the attester shuffling decision block for the batch after the current one, and is
+    /// used to verify the next batch's signatures whilst the current batch is imported.
+    processing_batch_anchor: Option<Hash256>,
+
+    /// The last batch sent for speculative signature verification, and the anchor it was sent
+    /// with. Only one batch is verified ahead of the processing batch at any time.
+    speculative_verification_batch: Option<(BatchId, Hash256)>,
+
     /// The chain's log.
     log: slog::Logger,
 }
@@ -158,6 +173,8 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             attempted_optimistic_starts: HashSet::default(),
             state: ChainSyncingState::Stopped,
             current_processing_batch: None,
+            processing_batch_anchor: None,
+            speculative_verification_batch: None,
             log: log.new(o!("chain" => id)),
         }
     }
@@ -334,6 +351,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
 
         let process_id = ChainSegmentProcessId::RangeBatchId(self.id, batch_id);
         self.current_processing_batch = Some(batch_id);
+        self.processing_batch_anchor = blocks.first().map(|block| block.parent_root());
 
         if let Err(e) = beacon_processor.send_chain_segment(process_id, blocks) {
             crit!(self.log, "Failed to send chain segment to processor."; "msg" => "process_batch",
@@ -344,17 +362,70 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             // re-downloaded.
             self.on_batch_process_result(network, batch_id, &BatchProcessResult::NonFaultyFailure)
         } else {
+            self.speculatively_verify_next_batch(network);
             Ok(KeepChain)
         }
     }
 
+    /// Sends the batch following the current processing batch to have its signatures verified
+    /// whilst the current processing batch is imported.
+    ///
+    /// This is a best-effort optimisation: the result is never reported back to the chain, and
+    /// the batch is fully verified when it is processed regardless. The beacon chain skips the
+    /// batch if the previous one is still being verified.
+    fn speculatively_verify_next_batch(&mut self, network: &mut SyncNetworkContext<T>) {
+        if !network.chain.config.speculative_sync_verification {
+            return;
+        }
+
+        let (Some(processing_id), Some(anchor_root)) =
+            (self.current_processing_batch, self.processing_batch_anchor)
+        else {
+            return;
+        };
+
+        let next_batch_id = processing_id + EPOCHS_PER_BATCH;
+        if self.speculative_verification_batch == Some((next_batch_id, anchor_root)) {
+            return;
+        }
+
+        let Some(blocks) = self
+            .batches
+            .get(&next_batch_id)
+            .and_then(|batch| batch.blocks_awaiting_processing())
+            .filter(|blocks| !blocks.is_empty())
+            .map(|blocks| blocks.to_vec())
+        else {
+            return;
+        };
+
+        let Some(beacon_processor) = network.beacon_processor_if_enabled() else {
+            return;
+        };
+
+        if let Err(e) =
+            beacon_processor.send_speculative_chain_segment_verification(anchor_root, blocks)
+        {
+            debug!(self.log, "Failed to send batch for speculative verification";
+                "batch" => next_batch_id, "error" => %e);
+        } else {
+            self.speculative_verification_batch = Some((next_batch_id, anchor_root));
+        }
+    }
+
     /// Processes the next ready batch, prioritizing optimistic batches over the processing target.
     fn process_completed_batches(
         &mut self,
         network: &mut SyncNetworkContext<T>,
     ) -> ProcessingResult {
         // Only process batches if this chain is Syncing and only process one batch at a time
-        if self.state != ChainSyncingState::Syncing || self.current_processing_batch.is_some() {
+        if self.state != ChainSyncingState::Syncing {
+            return Ok(KeepChain);
+        }
+        if self.current_processing_batch.is_some() {
+            // Make use of the time spent importing the current batch by verifying the signatures
+            // of the next one.
+            self.speculatively_verify_next_batch(network);
             return Ok(KeepChain);
         }
 
@@ -472,6 +543,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             _ => {
                 // batch_id matches, continue
                 self.current_processing_batch = None;
+                self.processing_batch_anchor = None;
                 self.batches.get_mut(&batch_id).ok_or_else(|| {
                     RemoveChain::WrongChainState(format!(
                         "Current processing batch not found: {}",
@@ -706,6 +778,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                     if let Some(processing_id) = self.current_processing_batch {
                         if id <= processing_id {
                             self.current_processing_batch = None;
+                            self.processing_batch_anchor = None;
                         }
                     }
                 }
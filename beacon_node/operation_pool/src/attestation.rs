@@ -1,10 +1,13 @@
-use crate::attestation_storage::{CompactAttestationRef, CompactIndexedAttestation};
+use crate::attestation_storage::{
+    AttestationMap, CompactAttestationRef, CompactIndexedAttestation,
+    CompactIndexedAttestationElectra,
+};
 use crate::max_cover::MaxCover;
 use crate::reward_cache::RewardCache;
 use state_processing::common::{
     attesting_indices_base::get_attesting_indices, base, get_attestation_participation_flag_indices,
 };
-use std::collections::HashMap;
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
 use types::{
     beacon_state::BeaconStateBase,
     consts::altair::{PARTICIPATION_FLAG_WEIGHTS, WEIGHT_DENOMINATOR},
@@ -145,18 +148,19 @@ impl<'a, E: EthSpec> MaxCover for AttMaxCover<'a, E> {
     /// impossible without the validator double voting. I.e. it is only suboptimal in the presence
     /// of slashable voting, which is rare.
     ///
-    /// Post-Electra this optimisation is still OK. The `self.att.data.index` will always be 0 for
-    /// all Electra attestations, so when a new attestation is added to the solution, we will
-    /// remove its validators from all attestations at the same slot. It may happen that the
-    /// included attestation and the attestation being updated have no validators in common, in
-    /// which case the `retain` will be a no-op. We could consider optimising this in future by only
-    /// executing the `retain` when the `committee_bits` of the two attestations intersect.
+    /// Post-Electra the `self.att.data.index` is always 0, so the committees are identified by
+    /// the `committee_bits` instead. The attestations in the pool are for a single committee, so
+    /// the rewards of each item are the marginal rewards for its committee, and the items chosen
+    /// for different committees are aggregated on-chain afterwards by `pack_on_chain_aggregates`.
     fn update_covering_set(
         &mut self,
         best_att: &CompactAttestationRef<'a, E>,
         covered_validators: &HashMap<u64, u64>,
     ) {
-        if self.att.data.slot == best_att.data.slot && self.att.data.index == best_att.data.index {
+        if self.att.data.slot == best_att.data.slot
+            && self.att.data.index == best_att.data.index
+            && self.att.indexed.committees_intersect(best_att.indexed)
+        {
             self.fresh_validators_rewards
                 .retain(|k, _| !covered_validators.contains_key(k))
         }
@@ -167,6 +171,115 @@ impl<'a, E: EthSpec> MaxCover for AttMaxCover<'a, E> {
     }
 }
 
+/// An attestation for inclusion in an Electra block, and the proposer reward for including it.
+#[derive(Debug, Clone)]
+pub struct OnChainAggregate<E: EthSpec> {
+    pub attestation: Attestation<E>,
+    pub reward: u64,
+}
+
+/// The attestations for some of the committees of an on-chain aggregate.
+struct OnChainAggregateBin<E: EthSpec> {
+    attestations: BTreeMap<u64, CompactIndexedAttestationElectra<E>>,
+    reward: u64,
+}
+
+impl<E: EthSpec> OnChainAggregateBin<E> {
+    /// Add `att` to the bin, aggregating it with the bin's attestation for the same committee if
+    /// their validators are disjoint. Returns `false` if it can't be added.
+    fn try_add(
+        &mut self,
+        committee_index: u64,
+        att: &CompactIndexedAttestationElectra<E>,
+        reward: u64,
+    ) -> bool {
+        match self.attestations.entry(committee_index) {
+            Entry::Vacant(entry) => {
+                entry.insert(att.clone());
+            }
+            Entry::Occupied(mut entry) => {
+                if !entry.get().should_aggregate(att) {
+                    return false;
+                }
+                entry.get_mut().aggregate_same_committee(att);
+            }
+        }
+        self.reward += reward;
+        true
+    }
+}
+
+/// Pack the single-committee attestations chosen by max cover into on-chain aggregates for an
+/// Electra block, in descending order of reward.
+///
+/// Attestations with the same data are aggregated across committees. When more than one
+/// attestation was chosen for a committee, they are merged if their validators are disjoint, or
+/// otherwise split across several on-chain aggregates. The reward of each on-chain aggregate is
+/// the sum of the marginal rewards of its attestations at the time they were chosen.
+pub fn pack_on_chain_aggregates<E: EthSpec>(
+    cover: Vec<AttMaxCover<E>>,
+) -> Vec<OnChainAggregate<E>> {
+    let mut aggregates = vec![];
+    let mut bins_by_data = HashMap::<_, Vec<OnChainAggregateBin<E>>>::new();
+
+    for item in cover {
+        let reward = item.score() as u64;
+        let att = item.att;
+        let (electra_att, committee_index) = match att.indexed {
+            CompactIndexedAttestation::Electra(electra_att)
+                if electra_att.committee_bits.num_set_bits() == 1 =>
+            {
+                match electra_att.committee_index() {
+                    Some(committee_index) => (electra_att, committee_index),
+                    None => continue,
+                }
+            }
+            _ => {
+                // Already covers multiple committees, or is from before Electra.
+                aggregates.push(OnChainAggregate {
+                    attestation: att.clone_as_attestation(),
+                    reward,
+                });
+                continue;
+            }
+        };
+
+        let bins = bins_by_data.entry((att.checkpoint, att.data)).or_default();
+        if !bins
+            .iter_mut()
+            .any(|bin| bin.try_add(committee_index, electra_att, reward))
+        {
+            bins.push(OnChainAggregateBin {
+                attestations: BTreeMap::from([(committee_index, electra_att.clone())]),
+                reward,
+            });
+        }
+    }
+
+    for ((checkpoint, data), bins) in bins_by_data {
+        for bin in bins {
+            let Some(on_chain_aggregate) =
+                AttestationMap::compute_on_chain_aggregate(bin.attestations)
+            else {
+                continue;
+            };
+            let indexed = CompactIndexedAttestation::Electra(on_chain_aggregate);
+            let att = CompactAttestationRef {
+                checkpoint,
+                data,
+                indexed: &indexed,
+            };
+            aggregates.push(OnChainAggregate {
+                attestation: att.clone_as_attestation(),
+                reward: bin.reward,
+            });
+        }
+    }
+
+    aggregates.sort_by_key(|aggregate| std::cmp::Reverse(aggregate.reward));
+    aggregates
+}
+
 /// Extract the validators for which `attestation` would be their earliest in the epoch.
 ///
 /// The reward paid to a proposer for including an attestation is proportional to the number
@@ -210,3 +323,143 @@ pub fn earliest_attestation_validators<E: EthSpec>(
 
     new_validators
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation_storage::{CheckpointKey, CompactAttestationData};
+    use types::{AggregateSignature, BitVector, Checkpoint, Epoch, Hash256, MainnetEthSpec, Slot};
+
+    type E = MainnetEthSpec;
+
+    const COMMITTEE_SIZE: usize = 4;
+
+    fn checkpoint() -> CheckpointKey {
+        CheckpointKey {
+            source: Checkpoint::default(),
+            target_epoch: Epoch::new(0),
+        }
+    }
+
+    fn data(beacon_block_root: u8) -> CompactAttestationData {
+        CompactAttestationData {
+            slot: Slot::new(0),
+            index: 0,
+            beacon_block_root: Hash256::repeat_byte(beacon_block_root),
+            target_root: Hash256::zero(),
+        }
+    }
+
+    /// A single-committee attestation from the validators at `positions` in committee
+    /// `committee_index`.
+    fn indexed(committee_index: usize, positions: &[usize]) -> CompactIndexedAttestation<E> {
+        let mut aggregation_bits = BitList::with_capacity(COMMITTEE_SIZE).unwrap();
+        for &position in positions {
+            aggregation_bits.set(position, true).unwrap();
+        }
+        let mut committee_bits = BitVector::new();
+        committee_bits.set(committee_index, true).unwrap();
+        CompactIndexedAttestation::Electra(CompactIndexedAttestationElectra {
+            attesting_indices: positions
+                .iter()
+                .map(|position| (committee_index * COMMITTEE_SIZE + position) as u64)
+                .collect(),
+            aggregation_bits,
+            signature: AggregateSignature::infinity(),
+            committee_bits,
+        })
+    }
+
+    /// Pack `attestations`, rewarding each of their validators with 1.
+    fn pack(
+        attestations: &[(CompactAttestationData, CompactIndexedAttestation<E>)],
+    ) -> Vec<OnChainAggregate<E>> {
+        let checkpoint = checkpoint();
+        let cover = attestations
+            .iter()
+            .map(|(data, indexed)| AttMaxCover {
+                att: CompactAttestationRef {
+                    checkpoint: &checkpoint,
+                    data,
+                    indexed,
+                },
+                fresh_validators_rewards: indexed
+                    .attesting_indices()
+                    .iter()
+                    .map(|validator_index| (*validator_index, 1))
+                    .collect(),
+            })
+            .collect();
+        pack_on_chain_aggregates(cover)
+    }
+
+    fn committee_indices(aggregate: &OnChainAggregate<E>) -> Vec<u64> {
+        aggregate
+            .attestation
+            .as_electra()
+            .unwrap()
+            .get_committee_indices()
+    }
+
+    #[test]
+    fn pack_disjoint_committees() {
+        let aggregates = pack(&[
+            (data(1), indexed(2, &[0])),
+            (data(1), indexed(0, &[0, 1])),
+            (data(1), indexed(1, &[3])),
+        ]);
+
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates[0].reward, 4);
+        assert_eq!(committee_indices(&aggregates[0]), vec![0, 1, 2]);
+        assert_eq!(aggregates[0].attestation.num_set_aggregation_bits(), 4);
+    }
+
+    #[test]
+    fn pack_disjoint_attestations_for_the_same_committee() {
+        let aggregates = pack(&[
+            (data(1), indexed(0, &[0, 1])),
+            (data(1), indexed(0, &[2, 3])),
+        ]);
+
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(committee_indices(&aggregates[0]), vec![0]);
+        assert_eq!(aggregates[0].attestation.num_set_aggregation_bits(), 4);
+    }
+
+    #[test]
+    fn pack_overlapping_attestations_for_the_same_committee() {
+        let aggregates = pack(&[
+            (data(1), indexed(0, &[0, 1])),
+            (data(1), indexed(0, &[1, 2, 3])),
+            (data(1), indexed(1, &[0])),
+        ]);
+
+        // The overlapping attestations can't share an on-chain aggregate, and the committee 1
+        // attestation joins the first aggregate it fits in.
+        assert_eq!(aggregates.len(), 2);
+        assert_eq!(aggregates[0].reward, 3);
+        assert_eq!(committee_indices(&aggregates[0]), vec![0, 1]);
+        assert_eq!(aggregates[1].reward, 3);
+        assert_eq!(committee_indices(&aggregates[1]), vec![0]);
+        assert_eq!(aggregates[1].attestation.num_set_aggregation_bits(), 3);
+    }
+
+    #[test]
+    fn pack_different_data_separately() {
+        let aggregates = pack(&[(data(1), indexed(0, &[0])), (data(2), indexed(1, &[0, 1]))]);
+
+        // Sorted by descending reward.
+        assert_eq!(aggregates.len(), 2);
+        assert_eq!(aggregates[0].reward, 2);
+        assert_eq!(
+            aggregates[0].attestation.data().beacon_block_root,
+            Hash256::repeat_byte(2)
+        );
+        assert_eq!(aggregates[1].reward, 1);
+        assert_eq!(
+            aggregates[1].attestation.data().beacon_block_root,
+            Hash256::repeat_byte(1)
+        );
+    }
+}
@@ -169,6 +169,23 @@ impl<E: EthSpec> CompactIndexedAttestation<E> {
         }
     }
 
+    /// Returns `true` if the attestations may have validators in common.
+    ///
+    /// Base attestations are identified by their committee index, but all Electra attestations
+    /// have an index of 0 and are identified by their `committee_bits`.
+    pub fn committees_intersect(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                CompactIndexedAttestation::Electra(this),
+                CompactIndexedAttestation::Electra(other),
+            ) => !this
+                .committee_bits
+                .intersection(&other.committee_bits)
+                .is_zero(),
+            _ => true,
+        }
+    }
+
     /// Returns `true` if aggregated, otherwise `false`.
     pub fn aggregate(&mut self, other: &Self) -> bool {
         match (self, other) {
@@ -328,78 +345,8 @@ impl<E: EthSpec> AttestationMap<E> {
         }
     }
 
-    /// Aggregate Electra attestations for the same attestation data signed by different
-    /// committees.
-    ///
-    /// Non-Electra attestations are left as-is.
-    pub fn aggregate_across_committees(&mut self, checkpoint_key: CheckpointKey) {
-        let Some(attestation_map) = self.checkpoint_map.get_mut(&checkpoint_key) else {
-            return;
-        };
-        for compact_indexed_attestations in attestation_map.attestations.values_mut() {
-            let unaggregated_attestations = std::mem::take(compact_indexed_attestations);
-            let mut aggregated_attestations: Vec<CompactIndexedAttestation<E>> = vec![];
-
-            // Aggregate the best attestations for each committee and leave the rest.
-            let mut best_attestations_by_committee: BTreeMap<
-                u64,
-                CompactIndexedAttestationElectra<E>,
-            > = BTreeMap::new();
-
-            for committee_attestation in unaggregated_attestations {
-                let mut electra_attestation = match committee_attestation {
-                    CompactIndexedAttestation::Electra(att)
-                        if att.committee_bits.num_set_bits() == 1 =>
-                    {
-                        att
-                    }
-                    CompactIndexedAttestation::Electra(att) => {
-                        // Aggregate already covers multiple committees, leave it as-is.
-                        aggregated_attestations.push(CompactIndexedAttestation::Electra(att));
-                        continue;
-                    }
-                    CompactIndexedAttestation::Base(att) => {
-                        // Leave as-is.
-                        aggregated_attestations.push(CompactIndexedAttestation::Base(att));
-                        continue;
-                    }
-                };
-                if let Some(committee_index) = electra_attestation.committee_index() {
-                    if let Some(existing_attestation) =
-                        best_attestations_by_committee.get_mut(&committee_index)
-                    {
-                        // Search for the best (most aggregation bits) attestation for this committee
-                        // index.
-                        if electra_attestation.aggregation_bits.num_set_bits()
-                            > existing_attestation.aggregation_bits.num_set_bits()
-                        {
-                            // New attestation is better than the previously known one for this
-                            // committee. Replace it.
-                            std::mem::swap(existing_attestation, &mut electra_attestation);
-                        }
-                        // Put the inferior attestation into the list of aggregated attestations
-                        // without performing any cross-committee aggregation.
-                        aggregated_attestations
-                            .push(CompactIndexedAttestation::Electra(electra_attestation));
-                    } else {
-                        // First attestation seen for this committee. Place it in the map
-                        // provisionally.
-                        best_attestations_by_committee.insert(committee_index, electra_attestation);
-                    }
-                }
-            }
-
-            if let Some(on_chain_aggregate) =
-                Self::compute_on_chain_aggregate(best_attestations_by_committee)
-            {
-                aggregated_attestations
-                    .push(CompactIndexedAttestation::Electra(on_chain_aggregate));
-            }
-
-            *compact_indexed_attestations = aggregated_attestations;
-        }
-    }
-
+    /// Aggregate attestations for the same attestation data signed by different committees, in
+    /// increasing order of committee index.
     pub fn compute_on_chain_aggregate(
         mut attestations_by_committee: BTreeMap<u64, CompactIndexedAttestationElectra<E>>,
     ) -> Option<CompactIndexedAttestationElectra<E>> {
@@ -475,3 +422,51 @@ impl<E: EthSpec> AttestationDataMap<E> {
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn electra(committee_indices: &[usize]) -> CompactIndexedAttestation<E> {
+        let mut committee_bits = BitVector::new();
+        for &committee_index in committee_indices {
+            committee_bits.set(committee_index, true).unwrap();
+        }
+        CompactIndexedAttestation::Electra(CompactIndexedAttestationElectra {
+            attesting_indices: vec![],
+            aggregation_bits: BitList::with_capacity(1).unwrap(),
+            signature: AggregateSignature::infinity(),
+            committee_bits,
+        })
+    }
+
+    fn base() -> CompactIndexedAttestation<E> {
+        CompactIndexedAttestation::Base(CompactIndexedAttestationBase {
+            attesting_indices: vec![],
+            aggregation_bits: BitList::with_capacity(1).unwrap(),
+            signature: AggregateSignature::infinity(),
+        })
+    }
+
+    #[test]
+    fn committees_intersect_disjoint() {
+        assert!(!electra(&[0]).committees_intersect(&electra(&[1])));
+        assert!(!electra(&[0, 2]).committees_intersect(&electra(&[1, 3])));
+    }
+
+    #[test]
+    fn committees_intersect_overlapping() {
+        assert!(electra(&[0]).committees_intersect(&electra(&[0])));
+        assert!(electra(&[0, 1]).committees_intersect(&electra(&[1, 2])));
+    }
+
+    #[test]
+    fn committees_intersect_base() {
+        // Base attestations don't record their committee, so they may always intersect.
+        assert!(base().committees_intersect(&base()));
+        assert!(base().committees_intersect(&electra(&[0])));
+    }
+}
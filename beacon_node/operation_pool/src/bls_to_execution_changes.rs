@@ -61,6 +61,23 @@ impl<E: EthSpec> BlsToExecutionChanges<E> {
         }
     }
 
+    pub fn get(
+        &self,
+        validator_index: u64,
+    ) -> Option<&Arc<SigVerifiedOp<SignedBlsToExecutionChange, E>>> {
+        self.by_validator_index.get(&validator_index)
+    }
+
+    /// Remove the change for `validator_index`, if it exists.
+    pub fn remove(&mut self, validator_index: u64) {
+        if self.by_validator_index.remove(&validator_index).is_some() {
+            self.queue.retain(|address_change| {
+                address_change.as_inner().message.validator_index != validator_index
+            });
+            self.received_pre_capella_indices.remove(&validator_index);
+        }
+    }
+
     /// FIFO ordering, used for persistence to disk.
     pub fn iter_fifo(
         &self,
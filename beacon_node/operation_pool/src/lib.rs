@@ -8,6 +8,7 @@ mod metrics;
 mod persistence;
 mod reward_cache;
 mod sync_aggregate_id;
+mod sync_contribution;
 
 pub use crate::bls_to_execution_changes::ReceivedPreCapella;
 pub use attestation::{earliest_attestation_validators, AttMaxCover};
@@ -23,33 +24,45 @@ use types::EpochCacheError;
 use crate::attestation_storage::{AttestationMap, CheckpointKey};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
 use crate::sync_aggregate_id::SyncAggregateId;
+use crate::sync_contribution::{insert_sync_contribution, pack_sync_contributions};
+use attestation::pack_on_chain_aggregates;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use itertools::Itertools;
+use max_cover::{maximum_cover, maximum_cover_until};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
-    get_slashable_indices_modular, verify_exit, VerifySignatures,
+    get_slashable_indices_modular, verify_bls_to_execution_change, verify_exit, VerifySignatures,
 };
 use state_processing::{SigVerifiedOp, VerifyOperation};
-use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::Instant;
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
-    Epoch, EthSpec, ProposerSlashing, SignedBeaconBlock, SignedBlsToExecutionChange,
+    Epoch, EthSpec, Fork, ProposerSlashing, SignedBeaconBlock, SignedBlsToExecutionChange,
     SignedVoluntaryExit, Slot, SyncAggregate, SyncCommitteeContribution, Validator,
 };
 
+/// The maximum number of single-committee attestations chosen by max cover for an Electra block.
+///
+/// Each on-chain aggregate can include one attestation per committee, but the cost of max cover
+/// grows with the number of attestations chosen, and beyond a few per committee the marginal ones
+/// add little reward.
+pub const MAX_ELECTRA_COVER_LIMIT: usize = 256;
+
 type SyncContributions<E> = RwLock<HashMap<SyncAggregateId, Vec<SyncCommitteeContribution<E>>>>;
 
 #[derive(Default, Debug)]
 pub struct OperationPool<E: EthSpec + Default> {
     /// Map from attestation ID (see below) to vectors of attestations.
     attestations: RwLock<AttestationMap<E>>,
-    /// Map from sync aggregate ID to the best `SyncCommitteeContribution`s seen for that ID.
+    /// Map from sync aggregate ID to the `SyncCommitteeContribution`s seen for that ID, excluding
+    /// those whose bits are covered by another contribution for the same subcommittee.
     sync_contributions: SyncContributions<E>,
     /// Set of attester slashings, and the fork version they were verified against.
     attester_slashings: RwLock<HashSet<SigVerifiedOp<AttesterSlashing<E>, E>>>,
@@ -114,46 +127,16 @@ impl<E: EthSpec> OperationPool<E> {
     ) -> Result<(), OpPoolError> {
         let aggregate_id = SyncAggregateId::new(contribution.slot, contribution.beacon_block_root);
         let mut contributions = self.sync_contributions.write();
-
-        match contributions.entry(aggregate_id) {
-            Entry::Vacant(entry) => {
-                // If no contributions exist for the key, insert the given contribution.
-                entry.insert(vec![contribution]);
-            }
-            Entry::Occupied(mut entry) => {
-                // If contributions exists for this key, check whether there exists a contribution
-                // with a matching `subcommittee_index`. If one exists, check whether the new or
-                // old contribution has more aggregation bits set. If the new one does, add it to the
-                // pool in place of the old one.
-                let existing_contributions = entry.get_mut();
-                match existing_contributions
-                    .iter_mut()
-                    .find(|existing_contribution| {
-                        existing_contribution.subcommittee_index == contribution.subcommittee_index
-                    }) {
-                    Some(existing_contribution) => {
-                        // Only need to replace the contribution if the new contribution has more
-                        // bits set.
-                        if existing_contribution.aggregation_bits.num_set_bits()
-                            < contribution.aggregation_bits.num_set_bits()
-                        {
-                            *existing_contribution = contribution;
-                        }
-                    }
-                    None => {
-                        // If there has been no previous sync contribution for this subcommittee index,
-                        // add it to the pool.
-                        existing_contributions.push(contribution);
-                    }
-                }
-            }
-        };
+        insert_sync_contribution(contributions.entry(aggregate_id).or_default(), contribution);
         Ok(())
     }
 
     /// Calculate the `SyncAggregate` from the sync contributions that exist in the pool for the
     /// slot previous to the slot associated with `state`. Return the calculated `SyncAggregate` if
     /// contributions exist at this slot, or else `None`.
+    ///
+    /// The contributions for each subcommittee are packed into as many participants as possible,
+    /// see `pack_sync_contributions`.
     pub fn get_sync_aggregate(
         &self,
         state: &BeaconState<E>,
@@ -167,7 +150,9 @@ impl<E: EthSpec> OperationPool<E> {
         self.sync_contributions
             .read()
             .get(&id)
-            .map(|contributions| SyncAggregate::from_contributions(contributions))
+            .map(|contributions| {
+                SyncAggregate::from_contributions(&pack_sync_contributions(contributions))
+            })
             .transpose()
             .map_err(|e| e.into())
     }
@@ -255,6 +240,29 @@ impl<E: EthSpec> OperationPool<E> {
         curr_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
         spec: &ChainSpec,
     ) -> Result<Vec<Attestation<E>>, OpPoolError> {
+        self.get_attestations_until(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            None,
+            spec,
+        )
+        .map(|(attestations, _)| attestations)
+    }
+
+    /// Get a list of attestations for inclusion in a block as in `get_attestations`, spending no
+    /// more than the time until `deadline` on the maximum cover.
+    ///
+    /// Returns `true` along with the attestations if the deadline was reached, in which case the
+    /// attestations are packed by their individual scores rather than by maximum cover.
+    pub fn get_attestations_until(
+        &self,
+        state: &BeaconState<E>,
+        prev_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
+        curr_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
+        deadline: Option<Instant>,
+        spec: &ChainSpec,
+    ) -> Result<(Vec<Attestation<E>>, bool), OpPoolError> {
         let fork_name = state.fork_name_unchecked();
         if !matches!(state, BeaconState::Base(_)) {
             // Epoch cache must be initialized to fetch base reward values in the max cover `score`
@@ -283,15 +291,7 @@ impl<E: EthSpec> OperationPool<E> {
         let mut num_prev_valid = 0_i64;
         let mut num_curr_valid = 0_i64;
 
-        // TODO(electra): Work out how to do this more elegantly. This is a bit of a hack.
-        let mut all_attestations = self.attestations.write();
-
-        if fork_name.electra_enabled() {
-            all_attestations.aggregate_across_committees(prev_epoch_key);
-            all_attestations.aggregate_across_committees(curr_epoch_key);
-        }
-
-        let all_attestations = parking_lot::RwLockWriteGuard::downgrade(all_attestations);
+        let all_attestations = self.attestations.read();
 
         let prev_epoch_att = self
             .get_valid_attestations_for_epoch(
@@ -316,11 +316,21 @@ impl<E: EthSpec> OperationPool<E> {
             )
             .inspect(|_| num_curr_valid += 1);
 
-        let curr_epoch_limit = if fork_name.electra_enabled() {
+        let block_limit = if fork_name.electra_enabled() {
             E::MaxAttestationsElectra::to_usize()
         } else {
             E::MaxAttestations::to_usize()
         };
+        // Post-Electra the max cover chooses single-committee attestations, each on-chain
+        // aggregate including up to one of them per committee.
+        let curr_epoch_limit = if fork_name.electra_enabled() {
+            block_limit
+                .saturating_mul(E::MaxCommitteesPerSlot::to_usize())
+                .min(MAX_ELECTRA_COVER_LIMIT)
+                .max(block_limit)
+        } else {
+            block_limit
+        };
         let prev_epoch_limit = if let BeaconState::Base(base_state) = state {
             std::cmp::min(
                 E::MaxPendingAttestations::to_usize()
@@ -331,30 +341,53 @@ impl<E: EthSpec> OperationPool<E> {
             curr_epoch_limit
         };
 
-        let (prev_cover, curr_cover) = rayon::join(
-            move || {
-                let _timer = metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
-                // If we're in the genesis epoch, just use the current epoch attestations.
-                if prev_epoch_key == curr_epoch_key {
-                    vec![]
-                } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
-                }
-            },
-            move || {
-                let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(curr_epoch_att, curr_epoch_limit, "curr_epoch_attestations")
-            },
-        );
+        let ((prev_cover, prev_deadline_reached), (curr_cover, curr_deadline_reached)) =
+            rayon::join(
+                move || {
+                    let _timer =
+                        metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
+                    // If we're in the genesis epoch, just use the current epoch attestations.
+                    if prev_epoch_key == curr_epoch_key {
+                        (vec![], false)
+                    } else {
+                        maximum_cover_until(
+                            prev_epoch_att,
+                            prev_epoch_limit,
+                            "prev_epoch_attestations",
+                            deadline,
+                        )
+                    }
+                },
+                move || {
+                    let _timer =
+                        metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
+                    maximum_cover_until(
+                        curr_epoch_att,
+                        curr_epoch_limit,
+                        "curr_epoch_attestations",
+                        deadline,
+                    )
+                },
+            );
 
         metrics::set_gauge(&metrics::NUM_PREV_EPOCH_ATTESTATIONS, num_prev_valid);
         metrics::set_gauge(&metrics::NUM_CURR_EPOCH_ATTESTATIONS, num_curr_valid);
 
-        Ok(max_cover::merge_solutions(
-            curr_cover,
-            prev_cover,
-            curr_epoch_limit,
-        ))
+        let attestations = if fork_name.electra_enabled() {
+            pack_on_chain_aggregates(curr_cover)
+                .into_iter()
+                .merge_by(
+                    pack_on_chain_aggregates(prev_cover),
+                    |aggregate1, aggregate2| aggregate1.reward >= aggregate2.reward,
+                )
+                .take(block_limit)
+                .map(|aggregate| aggregate.attestation)
+                .collect()
+        } else {
+            max_cover::merge_solutions(curr_cover, prev_cover, block_limit)
+        };
+
+        Ok((attestations, prev_deadline_reached || curr_deadline_reached))
     }
 
     /// Remove attestations which are too old to be included in a block.
@@ -457,6 +490,54 @@ impl<E: EthSpec> OperationPool<E> {
         .collect()
     }
 
+    /// Return the indices of all validators that would be slashed by the slashings in the pool.
+    pub fn get_pooled_slashing_indices(&self, state: &BeaconState<E>) -> HashSet<u64> {
+        let mut indices = self
+            .proposer_slashings
+            .read()
+            .keys()
+            .copied()
+            .collect::<HashSet<_>>();
+        for slashing in self.attester_slashings.read().iter() {
+            if let Ok(slashed) =
+                get_slashable_indices_modular(state, slashing.as_inner().to_ref(), |_, _| true)
+            {
+                indices.extend(slashed);
+            }
+        }
+        indices
+    }
+
+    /// Filter `slashings` down to those which are worth adding to the pool and publishing.
+    ///
+    /// Validators which are already slashed in `state`, or which would be slashed by a slashing
+    /// already in the pool, are ignored. The slashings returned are a maximum cover of the
+    /// remaining validators, so a slashing is dropped if the validators it slashes are already
+    /// covered by the other slashings returned.
+    pub fn filter_redundant_attester_slashings(
+        &self,
+        slashings: Vec<SigVerifiedOp<AttesterSlashing<E>, E>>,
+        state: &BeaconState<E>,
+    ) -> Vec<SigVerifiedOp<AttesterSlashing<E>, E>> {
+        let pooled_indices = self.get_pooled_slashing_indices(state);
+
+        let selected = maximum_cover(
+            slashings.iter().filter_map(|slashing| {
+                AttesterSlashingMaxCover::new(slashing.as_inner().to_ref(), &pooled_indices, state)
+            }),
+            slashings.len(),
+            "new_attester_slashings",
+        )
+        .into_iter()
+        .map(|cover| AttesterSlashingMaxCover::convert_to_object(cover.intermediate()))
+        .collect::<HashSet<_>>();
+
+        slashings
+            .into_iter()
+            .filter(|slashing| selected.contains(slashing.as_inner()))
+            .collect()
+    }
+
     /// Prune proposer slashings for validators which are exited in the finalized epoch.
     pub fn prune_proposer_slashings(&self, head_state: &BeaconState<E>) {
         prune_validator_hash_map(
@@ -531,6 +612,35 @@ impl<E: EthSpec> OperationPool<E> {
         )
     }
 
+    /// Re-validate the voluntary exits of `validator_indices` against `state` after a transition
+    /// to `fork`, removing those which are no longer valid.
+    ///
+    /// Returns the exits which are still valid.
+    pub fn revalidate_voluntary_exits(
+        &self,
+        validator_indices: &[u64],
+        state: &BeaconState<E>,
+        fork: &Fork,
+        spec: &ChainSpec,
+    ) -> Vec<SignedVoluntaryExit> {
+        let mut voluntary_exits = self.voluntary_exits.write();
+        validator_indices
+            .iter()
+            .filter_map(|validator_index| {
+                let exit = voluntary_exits.get(validator_index)?;
+                if exit.signature_is_still_valid(fork)
+                    && verify_exit(state, None, exit.as_inner(), VerifySignatures::False, spec)
+                        .is_ok()
+                {
+                    Some(exit.as_inner().clone())
+                } else {
+                    voluntary_exits.remove(validator_index);
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Prune if validator has already exited at or before the finalized checkpoint of the head.
     pub fn prune_voluntary_exits(&self, head_state: &BeaconState<E>) {
         prune_validator_hash_map(
@@ -570,6 +680,40 @@ impl<E: EthSpec> OperationPool<E> {
             .insert(verified_change, received_pre_capella)
     }
 
+    /// Re-validate the BLS to execution changes of `validator_indices` against `state` after a
+    /// transition to `fork`, removing those which are no longer valid.
+    ///
+    /// Returns the changes which are still valid.
+    pub fn revalidate_bls_to_execution_changes(
+        &self,
+        validator_indices: &[u64],
+        state: &BeaconState<E>,
+        fork: &Fork,
+        spec: &ChainSpec,
+    ) -> Vec<SignedBlsToExecutionChange> {
+        let mut bls_to_execution_changes = self.bls_to_execution_changes.write();
+        validator_indices
+            .iter()
+            .filter_map(|validator_index| {
+                let address_change = bls_to_execution_changes.get(*validator_index)?;
+                if address_change.signature_is_still_valid(fork)
+                    && verify_bls_to_execution_change(
+                        state,
+                        address_change.as_inner(),
+                        VerifySignatures::False,
+                        spec,
+                    )
+                    .is_ok()
+                {
+                    Some(address_change.as_inner().clone())
+                } else {
+                    bls_to_execution_changes.remove(*validator_index);
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Get a list of execution changes for inclusion in a block.
     ///
     /// They're in random `HashMap` order, which isn't exactly fair, but isn't unfair either.
@@ -1282,6 +1426,11 @@ mod release_tests {
             match fork_name {
                 ForkName::Electra => {
                     assert!(att.num_set_aggregation_bits() >= small_step_size);
+                    // Each on-chain aggregate should include an attestation from every committee.
+                    assert_eq!(
+                        att.committee_bits().unwrap().num_set_bits(),
+                        committees.len()
+                    );
                 }
                 _ => {
                     assert!(att.num_set_aggregation_bits() >= big_step_size);
@@ -1290,6 +1439,66 @@ mod release_tests {
         }
     }
 
+    /// Attestations with more distinct data than fit in a block are limited to the block's
+    /// maximum, each covering every committee post-Electra.
+    #[test]
+    fn attestation_packing_limit() {
+        let num_committees = 4;
+        let num_roots = 10;
+
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(num_committees);
+
+        let mut state = get_current_state_initialize_epoch_cache(&harness, &spec);
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let slot = state.slot();
+        let num_validators = num_committees
+            * MainnetEthSpec::slots_per_epoch() as usize
+            * spec.target_committee_size;
+
+        // Give each root a disjoint set of validators, and aggregate them per committee.
+        for i in 0..num_roots {
+            let attesting_validators = (0..num_validators)
+                .filter(|validator_index| validator_index % num_roots == i)
+                .collect::<Vec<_>>();
+            let attestations = harness.make_attestations(
+                &attesting_validators,
+                &state,
+                Hash256::zero(),
+                SignedBeaconBlockHash::from(Hash256::repeat_byte(i as u8 + 1)),
+                slot,
+            );
+            for (atts, _) in attestations {
+                let (att_0, _) = atts.first().unwrap().clone();
+                let aggregate = atts.iter().fold(att_0, |mut att, (new_att, _)| {
+                    att.aggregate(new_att.to_ref());
+                    att
+                });
+                let attesting_indices =
+                    get_attesting_indices_from_state(&state, aggregate.to_ref()).unwrap();
+                op_pool
+                    .insert_attestation(aggregate, attesting_indices)
+                    .unwrap();
+            }
+        }
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+        let best_attestations = op_pool
+            .get_attestations(&state, |_| true, |_| true, spec)
+            .expect("should have best attestations");
+
+        if state.fork_name_unchecked().electra_enabled() {
+            let max_attestations = <MainnetEthSpec as EthSpec>::MaxAttestationsElectra::to_usize();
+            assert!(num_roots > max_attestations);
+            assert_eq!(best_attestations.len(), max_attestations);
+            for att in &best_attestations {
+                assert_eq!(att.committee_bits().unwrap().num_set_bits(), num_committees);
+            }
+        } else {
+            assert_eq!(best_attestations.len(), num_roots * num_committees);
+        }
+    }
+
     #[test]
     fn attestation_rewards() {
         let small_step_size = 2;
@@ -1539,6 +1748,44 @@ mod release_tests {
         assert_eq!(best_slashings.1, vec![slashing_1, slashing_3]);
     }
 
+    // Check that newly found slashings which are redundant with the pool or with each other are
+    // filtered out.
+    #[test]
+    fn filter_redundant_attester_slashings() {
+        let harness = get_harness(32, None);
+        let spec = &harness.spec;
+        let state = harness.get_current_state();
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let p_slashing = harness.make_proposer_slashing(7);
+        let pooled = harness.make_attester_slashing(vec![1, 2]);
+        op_pool.insert_proposer_slashing(p_slashing.validate(&state, spec).unwrap());
+        op_pool.insert_attester_slashing(pooled.validate(&state, spec).unwrap());
+
+        let redundant_with_pool = harness.make_attester_slashing(vec![1, 2, 7]);
+        let covering = harness.make_attester_slashing(vec![2, 3, 4, 5]);
+        let redundant_with_covering = harness.make_attester_slashing(vec![3, 4]);
+        let new = harness.make_attester_slashing(vec![6]);
+
+        let filtered = op_pool.filter_redundant_attester_slashings(
+            [
+                &redundant_with_pool,
+                &covering,
+                &redundant_with_covering,
+                &new,
+            ]
+            .into_iter()
+            .map(|slashing| slashing.clone().validate(&state, spec).unwrap())
+            .collect(),
+            &state,
+        );
+        let filtered = filtered
+            .into_iter()
+            .map(|slashing| slashing.into_inner())
+            .collect::<HashSet<_>>();
+        assert_eq!(filtered, hashset![covering, new]);
+    }
+
     // Max coverage of attester slashings taking into account proposer slashings
     #[test]
     fn max_coverage_attester_proposer_slashings() {
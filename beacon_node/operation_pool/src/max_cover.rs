@@ -1,5 +1,6 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::time::Instant;
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -51,6 +52,24 @@ impl<T> MaxCoverItem<T> {
 /// * Time complexity: `O(limit * items_iter.len())`
 /// * Space complexity: `O(item_iter.len())`
 pub fn maximum_cover<I, T>(items_iter: I, limit: usize, label: &str) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    maximum_cover_until(items_iter, limit, label, None).0
+}
+
+/// Compute an approximate maximum cover as in `maximum_cover`, until `deadline`.
+///
+/// If the deadline is reached, the rest of the solution is filled with the remaining items with
+/// the highest scores, without accounting for their overlap with each other. Returns `true` along
+/// with the solution if the deadline was reached.
+pub fn maximum_cover_until<I, T>(
+    items_iter: I,
+    limit: usize,
+    label: &str,
+    deadline: Option<Instant>,
+) -> (Vec<T>, bool)
 where
     I: IntoIterator<Item = T>,
     T: MaxCover,
@@ -71,6 +90,21 @@ where
     let mut result = vec![];
 
     for _ in 0..limit {
+        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            let mut remaining = all_items
+                .into_iter()
+                .filter(|x| x.available && x.item.score() != 0)
+                .map(|x| x.item)
+                .collect::<Vec<_>>();
+            remaining.sort_by_key(|item| std::cmp::Reverse(item.score()));
+            result.extend(
+                remaining
+                    .into_iter()
+                    .take(limit.saturating_sub(result.len())),
+            );
+            return (result, true);
+        }
+
         // Select the item with the maximum score.
         let best = match all_items
             .iter_mut()
@@ -81,7 +115,7 @@ where
                 x.available = false;
                 x.item.clone()
             }
-            None => return result,
+            None => return (result, false),
         };
 
         // Update the covering sets of the other items, for the inclusion of the selected item.
@@ -97,7 +131,7 @@ where
         result.push(best);
     }
 
-    result
+    (result, false)
 }
 
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
@@ -222,4 +256,18 @@ mod test {
         assert_eq!(quality(&cover), 19);
         assert_eq!(cover.len(), 5);
     }
+
+    // Once the deadline is reached the highest scoring items are taken, even if they overlap.
+    #[test]
+    fn deadline_reached() {
+        let sets = example_system();
+        let (cover, deadline_reached) =
+            maximum_cover_until(sets.clone(), 2, "test", Some(Instant::now()));
+        assert!(deadline_reached);
+        assert_eq!(cover, vec![sets[1].clone(), sets[2].clone()]);
+
+        let (cover, deadline_reached) = maximum_cover_until(sets.clone(), 2, "test", None);
+        assert!(!deadline_reached);
+        assert_eq!(cover, vec![sets[1].clone(), sets[0].clone()]);
+    }
 }
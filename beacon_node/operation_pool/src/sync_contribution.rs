@@ -0,0 +1,219 @@
+use crate::max_cover::{maximum_cover, MaxCover};
+use std::collections::BTreeMap;
+use types::{BitVector, EthSpec, SyncCommitteeContribution};
+
+/// The maximum number of contributions kept for each subcommittee of a `SyncAggregateId`.
+pub const MAX_SYNC_CONTRIBUTIONS_PER_SUBCOMMITTEE: usize = 16;
+
+/// Insert `contribution` alongside the `existing` contributions for the same slot and block root.
+///
+/// Contributions whose bits are a subset of another contribution for the same subcommittee are
+/// redundant, and are not kept. If the subcommittee has more than
+/// `MAX_SYNC_CONTRIBUTIONS_PER_SUBCOMMITTEE` contributions, the one with the fewest bits is
+/// dropped.
+pub fn insert_sync_contribution<E: EthSpec>(
+    existing: &mut Vec<SyncCommitteeContribution<E>>,
+    contribution: SyncCommitteeContribution<E>,
+) {
+    let same_subcommittee = |other: &SyncCommitteeContribution<E>| {
+        other.subcommittee_index == contribution.subcommittee_index
+    };
+
+    if existing.iter().any(|other| {
+        same_subcommittee(other)
+            && contribution
+                .aggregation_bits
+                .is_subset(&other.aggregation_bits)
+    }) {
+        return;
+    }
+    existing.retain(|other| {
+        !same_subcommittee(other)
+            || !other
+                .aggregation_bits
+                .is_subset(&contribution.aggregation_bits)
+    });
+
+    let subcommittee_index = contribution.subcommittee_index;
+    existing.push(contribution);
+
+    let subcommittee_len = existing
+        .iter()
+        .filter(|other| other.subcommittee_index == subcommittee_index)
+        .count();
+    if subcommittee_len > MAX_SYNC_CONTRIBUTIONS_PER_SUBCOMMITTEE {
+        if let Some(worst) = existing
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| other.subcommittee_index == subcommittee_index)
+            .min_by_key(|(_, other)| other.aggregation_bits.num_set_bits())
+            .map(|(i, _)| i)
+        {
+            existing.swap_remove(worst);
+        }
+    }
+}
+
+/// Pack the contributions for each subcommittee into a single contribution with as many bits set
+/// as possible.
+///
+/// Signatures can only be aggregated for contributions with disjoint bits, so the contributions
+/// to merge are chosen using a maximum cover in which each chosen contribution excludes all of the
+/// contributions that overlap it.
+pub fn pack_sync_contributions<E: EthSpec>(
+    contributions: &[SyncCommitteeContribution<E>],
+) -> Vec<SyncCommitteeContribution<E>> {
+    let mut by_subcommittee = BTreeMap::<u64, Vec<_>>::new();
+    for contribution in contributions {
+        by_subcommittee
+            .entry(contribution.subcommittee_index)
+            .or_default()
+            .push(SyncContributionMaxCover::new(contribution));
+    }
+
+    by_subcommittee
+        .into_values()
+        .filter_map(|items| {
+            let limit = items.len();
+            maximum_cover(items, limit, "sync_contributions")
+                .into_iter()
+                .map(|item| item.contribution.clone())
+                .reduce(|mut packed, contribution| {
+                    packed.aggregate(&contribution);
+                    packed
+                })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct SyncContributionMaxCover<'a, E: EthSpec> {
+    contribution: &'a SyncCommitteeContribution<E>,
+    /// The bits of the contribution, or none if it overlaps a contribution already chosen.
+    bits: BitVector<E::SyncSubcommitteeSize>,
+}
+
+impl<'a, E: EthSpec> SyncContributionMaxCover<'a, E> {
+    fn new(contribution: &'a SyncCommitteeContribution<E>) -> Self {
+        Self {
+            contribution,
+            bits: contribution.aggregation_bits.clone(),
+        }
+    }
+}
+
+impl<'a, E: EthSpec> MaxCover for SyncContributionMaxCover<'a, E> {
+    type Object = SyncCommitteeContribution<E>;
+    type Intermediate = &'a SyncCommitteeContribution<E>;
+    type Set = BitVector<E::SyncSubcommitteeSize>;
+
+    fn intermediate(&self) -> &&'a SyncCommitteeContribution<E> {
+        &self.contribution
+    }
+
+    fn convert_to_object(contribution: &&'a SyncCommitteeContribution<E>) -> Self::Object {
+        (*contribution).clone()
+    }
+
+    fn covering_set(&self) -> &BitVector<E::SyncSubcommitteeSize> {
+        &self.bits
+    }
+
+    /// A contribution which overlaps the chosen one can't be aggregated with it, so it no longer
+    /// covers anything.
+    fn update_covering_set(
+        &mut self,
+        _best_contribution: &&'a SyncCommitteeContribution<E>,
+        covered_bits: &BitVector<E::SyncSubcommitteeSize>,
+    ) {
+        if !self.bits.intersection(covered_bits).is_zero() {
+            self.bits = BitVector::new();
+        }
+    }
+
+    fn score(&self) -> usize {
+        self.bits.num_set_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{AggregateSignature, Hash256, MainnetEthSpec, Slot};
+
+    type E = MainnetEthSpec;
+
+    fn contribution(subcommittee_index: u64, bits: &[usize]) -> SyncCommitteeContribution<E> {
+        let mut aggregation_bits = BitVector::new();
+        for bit in bits {
+            aggregation_bits.set(*bit, true).unwrap();
+        }
+        SyncCommitteeContribution {
+            slot: Slot::new(1),
+            beacon_block_root: Hash256::zero(),
+            subcommittee_index,
+            aggregation_bits,
+            signature: AggregateSignature::infinity(),
+        }
+    }
+
+    fn packed_bits(contributions: &[SyncCommitteeContribution<E>]) -> Vec<usize> {
+        pack_sync_contributions(contributions)
+            .iter()
+            .map(|contribution| contribution.aggregation_bits.num_set_bits())
+            .collect()
+    }
+
+    #[test]
+    fn insert_drops_redundant_contributions() {
+        let mut contributions = vec![];
+        insert_sync_contribution(&mut contributions, contribution(0, &[0, 1]));
+        insert_sync_contribution(&mut contributions, contribution(0, &[0]));
+        insert_sync_contribution(&mut contributions, contribution(0, &[0, 1]));
+        insert_sync_contribution(&mut contributions, contribution(1, &[0]));
+        assert_eq!(contributions.len(), 2);
+
+        insert_sync_contribution(&mut contributions, contribution(0, &[0, 1, 2]));
+        insert_sync_contribution(&mut contributions, contribution(0, &[3]));
+        assert_eq!(contributions.len(), 3);
+        assert_eq!(
+            contributions[1].aggregation_bits,
+            contribution(0, &[0, 1, 2]).aggregation_bits
+        );
+    }
+
+    #[test]
+    fn insert_evicts_fewest_bits() {
+        let mut contributions = vec![contribution(1, &[0])];
+        for i in 0..MAX_SYNC_CONTRIBUTIONS_PER_SUBCOMMITTEE {
+            insert_sync_contribution(&mut contributions, contribution(0, &[2 * i, 2 * i + 1]));
+        }
+        insert_sync_contribution(&mut contributions, contribution(0, &[100]));
+        assert_eq!(
+            contributions.len(),
+            MAX_SYNC_CONTRIBUTIONS_PER_SUBCOMMITTEE + 1
+        );
+        assert!(contributions
+            .iter()
+            .all(|contribution| contribution.subcommittee_index == 1
+                || contribution.aggregation_bits.num_set_bits() == 2));
+    }
+
+    #[test]
+    fn pack_disjoint_contributions() {
+        let contributions = [contribution(0, &[0, 1]), contribution(0, &[2, 3])];
+        assert_eq!(packed_bits(&contributions), vec![4]);
+    }
+
+    #[test]
+    fn pack_overlapping_contributions() {
+        // The best pair of disjoint contributions is the first and last.
+        let contributions = [
+            contribution(0, &[0, 1, 2]),
+            contribution(0, &[2, 3]),
+            contribution(0, &[3, 4, 5]),
+            contribution(1, &[0]),
+        ];
+        assert_eq!(packed_bits(&contributions), vec![6, 1]);
+    }
+}
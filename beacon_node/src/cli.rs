@@ -190,6 +190,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("boot-nodes-dns")
+                .long("boot-nodes-dns")
+                .value_name("ENRTREE LIST")
+                .help("One or more comma-delimited links to DNS trees of boot nodes as specified \
+                      in EIP-1459, e.g. `enrtree://<key>@nodes.example.org`. The trees are \
+                      resolved at startup and re-resolved periodically, in addition to any \
+                      --boot-nodes.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("network-load")
                 .long("network-load")
@@ -286,6 +297,17 @@ pub fn cli_app() -> Command {
                 .num_args(1..=2)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("enr-field")
+                .long("enr-field")
+                .value_name("KEY=VALUE")
+                .help("Sets a custom field on the local ENR, where the value is hex encoded with a \
+                      0x prefix, e.g. `--enr-field operator=0x6c68`. May be set multiple times. \
+                      Fields maintained by Lighthouse such as `eth2` and `ip` can't be set. \
+                      Fields can also be updated at runtime via the /lighthouse/network/enr API.")
+                .action(ArgAction::Append)
+                .display_order(0)
+        )
         .arg(
             Arg::new("enr-match")
                 .short('e')
@@ -326,6 +348,17 @@ pub fn cli_app() -> Command {
                 .hide(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("ip-preference")
+                .long("ip-preference")
+                .value_name("IP_VERSION")
+                .help("The IP version dialed first when a peer advertises both IPv4 and IPv6 \
+                      addresses in its ENR. By default QUIC addresses are dialed before TCP \
+                      addresses, regardless of their IP version.")
+                .value_parser(["ipv4", "ipv6"])
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-quic")
                 .long("disable-quic")
@@ -334,6 +367,15 @@ pub fn cli_app() -> Command {
                 .help("Disables the quic transport. The node will rely solely on the TCP transport for libp2p connections.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("disable-aggregator-mesh-boost")
+                .long("disable-aggregator-mesh-boost")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Do not raise the gossipsub mesh degree of attestation subnets on which a \
+                       connected validator has an upcoming aggregation duty.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-peer-scoring")
                 .long("disable-peer-scoring")
@@ -353,6 +395,28 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("trusted-peers-only")
+                .long("trusted-peers-only")
+                .help("Only connect to the peers given by --trusted-peers, for a node behind \
+                      sentry nodes. Discovery is disabled, connections with other peers are \
+                      rejected, and trusted peers given by --libp2p-addresses are re-dialed \
+                      whenever they disconnect.")
+                .requires("trusted-peers")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("outbound-only")
+                .long("outbound-only")
+                .help("Only make outbound libp2p connections. The node doesn't listen for \
+                      libp2p connections, rejects inbound connections, doesn't advertise TCP or \
+                      QUIC ports in its ENR and disables UPnP.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("genesis-backfill")
                 .long("genesis-backfill")
@@ -369,6 +433,28 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("geoip-db")
+                .long("geoip-db")
+                .value_name("FILE")
+                .help("Path to a CSV GeoIP database used to report the countries and hosting \
+                       providers of connected peers via the /lighthouse/network/diversity API \
+                       and metrics. Each line of the file is formatted as \
+                       `network,country,organization,hosting`, e.g. \
+                       `1.2.3.0/24,DE,Example GmbH,true`.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("gossip-trace-capacity")
+                .long("gossip-trace-capacity")
+                .value_name("COUNT")
+                .help("The number of recently published and received gossip messages whose \
+                       propagation is traced and served via the /lighthouse/network/gossip_trace \
+                       API. Set to 0 to disable tracing.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("self-limiter")
             .long("self-limiter")
@@ -493,6 +579,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-path-prefix")
+                .long("http-path-prefix")
+                .requires("enable_http")
+                .value_name("PREFIX")
+                .help("Serve the HTTP API beneath this path, e.g. `holesky` to serve \
+                       `/holesky/eth/v1/node/version`. Useful to tell apart the APIs of several \
+                       beacon nodes behind a single reverse proxy.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-spec-fork")
                 .long("http-spec-fork")
@@ -532,6 +629,30 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-token-file")
+                .long("http-token-file")
+                .requires("enable_http")
+                .value_name("PATH")
+                .help("Path to a file of API tokens, one per line in the form <role>:<token>, \
+                    where <role> is one of read-only, validator or admin. If supplied, every \
+                    request to the HTTP API must present a token with sufficient access in an \
+                    `Authorization: Bearer` header. The validator role is required for block \
+                    and pool submissions and the validator endpoints, and the admin role for \
+                    the debug and Lighthouse-specific endpoints.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-disable-compression")
+                .long("http-disable-compression")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .requires("enable_http")
+                .help("Do not compress HTTP API responses. By default, responses are \
+                    compressed with zstd or gzip if the client supports it.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-allow-sync-stalled")
                 .long("http-allow-sync-stalled")
@@ -553,6 +674,44 @@ pub fn cli_app() -> Command {
                        Increasing this value can prevent messages from being dropped.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-request-timeouts")
+                .long("http-request-timeouts")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .value_name("CLASS=MILLIS,...")
+                .help("Timeouts for HTTP API requests, in milliseconds, as a comma-separated list \
+                       of CLASS=MILLIS pairs. The classes are validator, submission, debug, \
+                       lighthouse, events and standard, and the class \"all\" applies to every \
+                       class without its own entry. Requests which time out receive a 503 \
+                       response. The events stream is never timed out. [default: no timeouts]")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-max-body-sizes")
+                .long("http-max-body-sizes")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .value_name("CLASS=BYTES,...")
+                .help("Maximum sizes of HTTP API request bodies, in bytes, as a comma-separated \
+                       list of CLASS=BYTES pairs, using the same classes as \
+                       --http-request-timeouts. Larger requests receive a 413 response. Take care \
+                       that the submission limit allows for SSZ blocks with blobs. \
+                       [default: no limits]")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-max-concurrent-requests")
+                .long("http-max-concurrent-requests")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .value_name("CLASS=N,...")
+                .help("Maximum numbers of HTTP API requests to serve at once, as a \
+                       comma-separated list of CLASS=N pairs, using the same classes as \
+                       --http-request-timeouts. Requests beyond the limit receive a 429 \
+                       response. [default: no limits]")
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-duplicate-block-status")
                 .long("http-duplicate-block-status")
@@ -655,6 +814,73 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("ntp-server")
+                .long("ntp-server")
+                .value_name("HOST[:PORT]")
+                .help("An NTP server to check the local clock against once per epoch. The clock \
+                       is always checked against the arrival times of gossip blocks, and a \
+                       warning is logged if either check finds it out of sync.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("webhook-config")
+                .long("webhook-config")
+                .value_name("FILE")
+                .help("Path to a YAML file configuring webhooks to be notified of missed proposals \
+                       by monitored validators, finality stalls, slashings, forks and low peer \
+                       counts.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("watch-config")
+                .long("watch-config")
+                .value_name("FILE")
+                .help("Path to a watch configuration file. When set, the canonical chain, validators, \
+                       attestations, block rewards and block packing are written to the watch \
+                       PostgreSQL database by the beacon node itself, rather than by a separate \
+                       watch updater. Requires Lighthouse to be compiled with the `watch` feature.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("additional-instance")
+                .long("additional-instance")
+                .value_name("FILE")
+                .help("Path to a file of the `lighthouse bn` arguments of another beacon node to \
+                       run in this process, e.g. for a devnet alongside mainnet. The instance \
+                       shares the runtime, logs and metrics server of this process, and must use \
+                       the same preset and a separate data directory and ports. May be supplied \
+                       multiple times.")
+                .action(ArgAction::Append)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("replay-from-dir")
+                .long("replay-from-dir")
+                .value_name("DIR")
+                .help("Replay the blocks in DIR through full block import with timing output, \
+                       then shut down. DIR may contain .era files, and block_<slot>.ssz files \
+                       each with an optional blobs_<slot>.ssz file. Networking is disabled and \
+                       execution payloads are imported optimistically unless \
+                       --replay-with-execution-layer is set. The node must start from a state \
+                       before the first block to be imported, e.g. using --checkpoint-state.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("replay-with-execution-layer")
+                .long("replay-with-execution-layer")
+                .help("Send the execution payloads of blocks replayed by --replay-from-dir to the \
+                       execution layer.")
+                .requires("replay-from-dir")
+                .requires("execution-endpoint")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
 
         /*
          * Standard staking flags
@@ -693,6 +919,19 @@ pub fn cli_app() -> Command {
                       Identical to the method used at the 2019 Canada interop.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("stub-eth1-votes")
+                .long("stub-eth1-votes")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .conflicts_with("dummy-eth1")
+                .help("If present, Eth1Data votes repeat the Eth1Data of the state being built \
+                      upon and deposit logs are not synced from the execution node. Only \
+                      permitted on networks which schedule Electra. Blocks cannot be produced \
+                      while deposits from the deposit contract remain to be included. Votes are \
+                      stubbed automatically once all such deposits are included.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("eth1-purge-cache")
                 .long("eth1-purge-cache")
@@ -791,7 +1030,10 @@ pub fn cli_app() -> Command {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. The file \
+                       may contain several secrets, one per line, which are tried in order if \
+                       the execution endpoint rejects a token. The file is read again whenever \
+                       it changes, so secrets can be rotated without a restart.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -816,7 +1058,7 @@ pub fn cli_app() -> Command {
                 .help("Used by the beacon node to communicate a unique identifier to execution nodes \
                        during JWT authentication. It corresponds to the 'id' field in the JWT claims object.\
                        Set to empty by default")
-                .requires("execution-jwt")
+                .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -828,7 +1070,7 @@ pub fn cli_app() -> Command {
                 .help("Used by the beacon node to communicate a client version to execution nodes \
                        during JWT authentication. It corresponds to the 'clv' field in the JWT claims object.\
                        Set to empty by default")
-                .requires("execution-jwt")
+                .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -862,6 +1104,69 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("execution-request-policy")
+                .long("execution-request-policy")
+                .value_name("PATH")
+                .help("Path to a JSON file with the timeouts and retries of requests to the \
+                    execution engine. It may set the `retries`, `backoff_ms` and `deadline_ms` \
+                    of the `critical` methods used to import and produce blocks and of the \
+                    `background` methods, and the `timeout_ms`, `retries` and `backoff_ms` of \
+                    single `methods`. The other execution request flags take precedence.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-critical-retries")
+                .long("execution-critical-retries")
+                .value_name("COUNT")
+                .help("The number of times a request to the execution engine which is needed to \
+                    import or produce a block is retried after a timeout or connection error.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-critical-deadline")
+                .long("execution-critical-deadline")
+                .value_name("MILLISECONDS")
+                .help("The time after the first attempt of a request to the execution engine \
+                    which is needed to import or produce a block, after which it is not retried.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-background-retries")
+                .long("execution-background-retries")
+                .value_name("COUNT")
+                .help("The number of times any other request to the execution engine is retried \
+                    after a timeout or connection error.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-retry-backoff")
+                .long("execution-retry-backoff")
+                .value_name("MILLISECONDS")
+                .help("The delay before each retry of a request to the execution engine.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-method-timeouts")
+                .long("execution-method-timeouts")
+                .value_name("METHOD=MILLISECONDS,...")
+                .help("Comma-separated timeouts of requests for single execution engine methods, \
+                    e.g. `engine_newPayloadV3=12000`, which replace their default timeouts \
+                    multiplied by --execution-timeout-multiplier.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-header-timeout")
                 .long("builder-header-timeout")
@@ -899,6 +1204,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("bls-accelerator")
+                .long("bls-accelerator")
+                .value_name("FILE")
+                .help("Path to a shared library which verifies batches of BLS signatures on \
+                       dedicated hardware. If the library fails its self-test at startup, \
+                       signatures are verified in software. Only load libraries from trusted \
+                       sources.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Database purging and compaction.
          */
@@ -927,6 +1243,26 @@ pub fn cli_app() -> Command {
                 .default_value("true")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("compaction-period")
+                .long("compaction-period")
+                .value_name("HOURS")
+                .help("Automatically compact the database at least this often, finalization \
+                       permitting. Has no effect if auto-compaction is disabled.")
+                .action(ArgAction::Set)
+                .default_value("168")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("compaction-throttle")
+                .long("compaction-throttle")
+                .value_name("MILLISECONDS")
+                .help("Time to pause between each chunk of a database compaction. Higher values \
+                       reduce the IO load of compaction, at the cost of it taking longer.")
+                .action(ArgAction::Set)
+                .default_value("100")
+                .display_order(0)
+        )
         .arg(
             Arg::new("prune-payloads")
                 .long("prune-payloads")
@@ -968,6 +1304,29 @@ pub fn cli_app() -> Command {
                 .default_value("0")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("prune-forks-after-epochs")
+                .long("prune-forks-after-epochs")
+                .value_name("EPOCHS")
+                .help("Retain the blocks, payloads and blobs of abandoned forks for this many \
+                       epochs after they are abandoned at finalization, e.g. for debugging, \
+                       before pruning them. States of abandoned forks are always pruned at \
+                       finalization.")
+                .action(ArgAction::Set)
+                .default_value("0")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("db-target-size")
+                .long("db-target-size")
+                .value_name("GIGABYTES")
+                .help("Target maximum size of the database. While the database is larger than \
+                       this, blobs are pruned up to the data availability boundary regardless \
+                       of --blob-prune-margin-epochs, and execution payloads are pruned \
+                       regardless of --prune-payloads.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
 
         /*
          * Misc.
@@ -1099,6 +1458,21 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("slasher-auto")
+                .long("slasher-auto")
+                .help(
+                    "Tune the slasher's chunk sizes, history length and update period \
+                     automatically, based on the number of validators and the time taken to \
+                     process each batch. The values of the other slasher flags are used as \
+                     starting points. Changes to the database layout are applied on restart by \
+                     migrating the existing database."
+                )
+                .requires("slasher")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("slasher-broadcast")
                 .long("slasher-broadcast")
@@ -1172,6 +1546,18 @@ pub fn cli_app() -> Command {
                 .conflicts_with("checkpoint-state")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("deposit-snapshot-file")
+                .long("deposit-snapshot-file")
+                .help("Initialize the deposit tree from an EIP-4881 deposit snapshot when starting \
+                       from a checkpoint, instead of syncing all of the deposits from the execution \
+                       node. The file is the JSON response of /eth/v1/beacon/deposit_snapshot, and \
+                       must not be newer than the checkpoint state. When used with \
+                       --checkpoint-sync-url, it takes precedence over the remote's snapshot.")
+                .value_name("FILE")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("checkpoint-sync-url-timeout")
                 .long("checkpoint-sync-url-timeout")
@@ -1243,6 +1629,26 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("slashing-guard-pubkeys")
+                .long("slashing-guard-pubkeys")
+                .help("A comma-separated list of 0x-prefixed validator public keys. \
+                        The beacon node will refuse to publish blocks and attestations from \
+                        these validators via the HTTP API if they conflict with messages from \
+                        the same validators that it has already observed.")
+                .value_name("PUBKEYS")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("slashing-guard-file")
+                .long("slashing-guard-file")
+                .help("As per --slashing-guard-pubkeys, but the comma-separated list is \
+                    contained within a file at the given path.")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-lock-timeouts")
                 .long("disable-lock-timeouts")
@@ -1259,6 +1665,26 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("reorg-capture-depth")
+                .long("reorg-capture-depth")
+                .value_name("SLOTS")
+                .help("Write a forensic bundle to the reorg_captures directory in the data \
+                       directory for each re-org of the head which is deeper than this many \
+                       slots. Bundles are available via the /lighthouse/reorgs HTTP API.")
+                .default_value("2")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("disable-reorg-capture")
+                .long("disable-reorg-capture")
+                .help("Do not write forensic bundles for deep re-orgs of the head.")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("reorg-capture-depth")
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("proposer-reorg-threshold")
                 .long("proposer-reorg-threshold")
@@ -1348,6 +1774,29 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("block-production-budget")
+                .long("block-production-budget")
+                .value_name("MILLISECONDS")
+                .help("The total time allowed for producing a block. Attestation packing is cut \
+                       short if it runs past 40% of the budget, and a report of the time spent in \
+                       each stage is logged if any stage misses its deadline. By default there \
+                       is no budget.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("block-production-endpoint")
+                .long("block-production-endpoint")
+                .requires("enable_http")
+                .value_name("BEACON_NODE")
+                .help("URL of a beacon node to fetch blocks from, rather than producing them \
+                       locally. Each block is verified against this node's view of the chain \
+                       before it is returned to the validator client. If the request fails or \
+                       the block is invalid, the block is produced locally.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1425,6 +1874,39 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("invalid-payload-handling")
+                .long("invalid-payload-handling")
+                .value_name("MODE")
+                .help("What to do when the execution layer declares optimistically imported \
+                       blocks on the canonical chain invalid. With `reorg` the head is re-orged \
+                       away from them, and with `halt` Lighthouse shuts down without applying \
+                       the invalidation so that the chain can be inspected.")
+                .value_parser(["reorg", "halt"])
+                .default_value("reorg")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("invalid-payload-max-reorg-depth")
+                .long("invalid-payload-max-reorg-depth")
+                .value_name("SLOTS")
+                .help("Shut down rather than re-org the head away from blocks declared invalid \
+                       by the execution layer by more than this many slots. By default re-orgs \
+                       of any depth are allowed.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("strict-optimistic-sync")
+                .long("strict-optimistic-sync")
+                .help("Refuse requests to produce blocks, attestations, aggregates and sync \
+                       committee contributions while the head is optimistic or the execution \
+                       layer is offline, responding with a 503 which describes the sync status.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-deposit-contract-sync")
                 .long("disable-deposit-contract-sync")
@@ -1445,6 +1927,27 @@ pub fn cli_app() -> Command {
                        Lighthouse and only passed to the EL if initial verification fails.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("disable-speculative-sync-verification")
+                .long("disable-speculative-sync-verification")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Disable the verification of signatures and payload block hashes of the \
+                       next range sync batch whilst the current batch is being imported. \
+                       Speculative verification reduces sync time and should only be disabled \
+                       for debugging.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("detect-proposer-equivocations")
+                .long("detect-proposer-equivocations")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Record the block headers seen on gossip and RPC and, when a proposer \
+                       signs two different blocks for the same slot, add a proposer slashing \
+                       to the op pool and publish it. This does not require the slasher.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("light-client-server")
                 .long("light-client-server")
@@ -1551,6 +2054,77 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-processor-signature-batching")
+                .long("beacon-processor-signature-batching")
+                .help("Verify the signatures of gossip attestations, aggregates and sync committee \
+                       messages together in a single batch, rather than in a batch per topic. \
+                       If a batch fails, every message in it is verified individually.")
+                .hide(true)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-signature-batch-size")
+                .long("beacon-processor-signature-batch-size")
+                .value_name("INTEGER")
+                .help("Specifies the maximum number of gossip messages in a signature verification \
+                       batch when --beacon-processor-signature-batching is enabled.")
+                .hide(true)
+                .default_value("128")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-signature-batch-window")
+                .long("beacon-processor-signature-batch-window")
+                .value_name("MILLISECONDS")
+                .help("Specifies the maximum time to wait for a signature verification batch to \
+                       fill when --beacon-processor-signature-batching is enabled.")
+                .hide(true)
+                .default_value("10")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-work-deadlines")
+                .long("beacon-processor-work-deadlines")
+                .value_name("CLASS=MILLIS,...")
+                .help("Specifies the time after which queued work is dropped rather than processed, \
+                       as a comma-separated list of CLASS=MILLIS pairs. The classes which may be \
+                       given a deadline are aggregate, attestation, sync_committee, operation, \
+                       light_client and api, and the class \"all\" applies to each of them without \
+                       its own entry. Deadlines do not change the order in which work is \
+                       processed. [default: no deadlines]")
+                .hide(true)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-class-workers")
+                .long("beacon-processor-class-workers")
+                .value_name("CLASS=N,...")
+                .help("Reserves workers for classes of work, as a comma-separated list of CLASS=N \
+                       pairs, using the same classes as --beacon-processor-work-deadlines as well \
+                       as block, sync, rpc and backfill. Work of a class with reserved workers is \
+                       only processed by those workers, which are in addition to \
+                       --beacon-processor-max-workers. [default: no reserved workers]")
+                .hide(true)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-pin-workers")
+                .long("beacon-processor-pin-workers")
+                .help("Pins the threads of each pool of beacon processor workers to its own set of \
+                       cores. Each pool of reserved workers is given one core per worker, and the \
+                       remaining cores are used by all other work. Only supported on Linux.")
+                .hide(true)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-duplicate-warn-logs")
                 .long("disable-duplicate-warn-logs")
@@ -5,15 +5,17 @@ use beacon_chain::chain_config::{
 };
 use beacon_chain::graffiti_calculator::GraffitiOrigin;
 use beacon_chain::TrustedSetup;
+use beacon_processor::{parse_work_class_deadlines, parse_work_class_workers};
 use clap::{parser::ValueSource, ArgMatches, Id};
 use clap_utils::flags::DISABLE_MALLOC_TUNING_FLAG;
 use clap_utils::{parse_flag, parse_required};
-use client::{ClientConfig, ClientGenesis};
+use client::{ClientConfig, ClientGenesis, ReplayConfig};
 use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
+use execution_layer::request_policy::parse_method_timeouts;
 use execution_layer::DEFAULT_JWT_FILE;
 use genesis::Eth1Endpoint;
-use http_api::TlsConfig;
+use http_api::{parse_route_class_values, ApiTokens, TlsConfig};
 use lighthouse_network::ListenAddress;
 use lighthouse_network::{multiaddr::Protocol, Enr, Multiaddr, NetworkConfig, PeerIdSerialized};
 use sensitive_url::SensitiveUrl;
@@ -28,7 +30,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 use types::graffiti::GraffitiString;
-use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes};
+use types::{Checkpoint, DepositTreeSnapshot, Epoch, EthSpec, Hash256, PublicKeyBytes};
 
 /// Gets the fully-initialized global client.
 ///
@@ -90,6 +92,7 @@ pub fn get_config<E: EthSpec>(
     let data_dir_ref = client_config.data_dir().clone();
 
     set_network_config(&mut client_config.network, cli_args, &data_dir_ref, log)?;
+    client_config.http_api.target_peers = client_config.network.target_peers;
 
     /*
      * Staking flag
@@ -129,6 +132,17 @@ pub fn get_config<E: EthSpec>(
             client_config.http_api.allow_origin = Some(allow_origin.to_string());
         }
 
+        if let Some(prefix) = cli_args.get_one::<String>("http-path-prefix") {
+            let segments = prefix
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>();
+            if segments.is_empty() {
+                return Err("--http-path-prefix must not be empty".into());
+            }
+            client_config.http_api.path_prefix = Some(segments.join("/"));
+        }
+
         if cli_args.get_one::<String>("http-spec-fork").is_some() {
             warn!(
                 log,
@@ -152,6 +166,36 @@ pub fn get_config<E: EthSpec>(
             });
         }
 
+        if let Some(token_file) = cli_args.get_one::<String>("http-token-file") {
+            // Read the file now, to report any errors on startup.
+            let token_file = PathBuf::from(token_file);
+            ApiTokens::from_file(&token_file)?;
+            client_config.http_api.api_token_file = Some(token_file);
+        }
+
+        if cli_args.get_flag("http-disable-compression") {
+            client_config.http_api.enable_compression = false;
+        }
+
+        if let Some(timeouts) = cli_args.get_one::<String>("http-request-timeouts") {
+            client_config.http_api.request_limits.timeouts =
+                parse_route_class_values::<u64>(timeouts)
+                    .map_err(|e| format!("Invalid --http-request-timeouts: {}", e))?
+                    .into_iter()
+                    .map(|(class, millis)| (class, Duration::from_millis(millis)))
+                    .collect();
+        }
+
+        if let Some(sizes) = cli_args.get_one::<String>("http-max-body-sizes") {
+            client_config.http_api.request_limits.max_body_sizes = parse_route_class_values(sizes)
+                .map_err(|e| format!("Invalid --http-max-body-sizes: {}", e))?;
+        }
+
+        if let Some(limits) = cli_args.get_one::<String>("http-max-concurrent-requests") {
+            client_config.http_api.request_limits.max_concurrent = parse_route_class_values(limits)
+                .map_err(|e| format!("Invalid --http-max-concurrent-requests: {}", e))?;
+        }
+
         if cli_args.get_flag("http-allow-sync-stalled") {
             warn!(
                 log,
@@ -169,6 +213,9 @@ pub fn get_config<E: EthSpec>(
         client_config.http_api.duplicate_block_status_code =
             parse_required(cli_args, "http-duplicate-block-status")?;
 
+        client_config.http_api.block_production_endpoint =
+            clap_utils::parse_optional(cli_args, "block-production-endpoint")?;
+
         client_config.http_api.enable_light_client_server =
             cli_args.get_flag("light-client-server");
     }
@@ -225,6 +272,17 @@ pub fn get_config<E: EthSpec>(
         });
     }
 
+    if let Some(ntp_server) = cli_args.get_one::<String>("ntp-server") {
+        client_config.ntp_server = Some(ntp_server.clone());
+    }
+
+    /*
+     * Webhook notifications
+     */
+    if let Some(webhook_config) = cli_args.get_one::<String>("webhook-config") {
+        client_config.webhook = Some(webhook::Config::from_file(Path::new(webhook_config))?);
+    }
+
     // Log a warning indicating an open HTTP server if it wasn't specified explicitly
     // (e.g. using the --staking flag).
     if cli_args.get_flag("staking") {
@@ -357,6 +415,36 @@ pub fn get_config<E: EthSpec>(
             clap_utils::parse_required(cli_args, "execution-timeout-multiplier")?;
         el_config.execution_timeout_multiplier = Some(execution_timeout_multiplier);
 
+        if let Some(path) = cli_args.get_one::<String>("execution-request-policy") {
+            let file = fs::File::open(path)
+                .map_err(|e| format!("Unable to open execution request policy: {:?}", e))?;
+            el_config.request_policy = serde_json::from_reader(file)
+                .map_err(|e| format!("Unable to parse execution request policy: {:?}", e))?;
+        }
+        let request_policy = &mut el_config.request_policy;
+        if let Some(retries) = clap_utils::parse_optional(cli_args, "execution-critical-retries")? {
+            request_policy.critical.retries = retries;
+        }
+        if let Some(deadline) = clap_utils::parse_optional(cli_args, "execution-critical-deadline")?
+        {
+            request_policy.critical.deadline_ms = Some(deadline);
+        }
+        if let Some(retries) = clap_utils::parse_optional(cli_args, "execution-background-retries")?
+        {
+            request_policy.background.retries = retries;
+        }
+        if let Some(backoff) = clap_utils::parse_optional(cli_args, "execution-retry-backoff")? {
+            request_policy.critical.backoff_ms = backoff;
+            request_policy.background.backoff_ms = backoff;
+        }
+        if let Some(timeouts) = cli_args.get_one::<String>("execution-method-timeouts") {
+            for (method, timeout) in parse_method_timeouts(timeouts)
+                .map_err(|e| format!("Invalid --execution-method-timeouts: {}", e))?
+            {
+                request_policy.methods.entry(method).or_default().timeout_ms = Some(timeout);
+            }
+        }
+
         client_config.eth1.endpoint = Eth1Endpoint::Auth {
             endpoint: execution_endpoint,
             jwt_path: secret_file,
@@ -427,6 +515,12 @@ pub fn get_config<E: EthSpec>(
             .parse()
             .map_err(|_| "auto-compact-db takes a boolean".to_string())?;
     }
+    if let Some(hours) = clap_utils::parse_optional::<u64>(cli_args, "compaction-period")? {
+        client_config.store.compaction_period = Duration::from_secs(hours * 60 * 60);
+    }
+    if let Some(millis) = clap_utils::parse_optional(cli_args, "compaction-throttle")? {
+        client_config.store.compaction_throttle = Duration::from_millis(millis);
+    }
 
     if let Some(prune_payloads) = clap_utils::parse_optional(cli_args, "prune-payloads")? {
         client_config.store.prune_payloads = prune_payloads;
@@ -454,6 +548,16 @@ pub fn get_config<E: EthSpec>(
         client_config.store.blob_prune_margin_epochs = blob_prune_margin_epochs;
     }
 
+    if let Some(prune_forks_after_epochs) =
+        clap_utils::parse_optional(cli_args, "prune-forks-after-epochs")?
+    {
+        client_config.store.prune_forks_after_epochs = prune_forks_after_epochs;
+    }
+
+    if let Some(gigabytes) = clap_utils::parse_optional::<u64>(cli_args, "db-target-size")? {
+        client_config.store.target_disk_usage = Some(gigabytes * 1_000_000_000);
+    }
+
     /*
      * Zero-ports
      *
@@ -531,6 +635,8 @@ pub fn get_config<E: EthSpec>(
 
     client_config.allow_insecure_genesis_sync = cli_args.get_flag("allow-insecure-genesis-sync");
 
+    client_config.bls_accelerator = clap_utils::parse_optional(cli_args, "bls-accelerator")?;
+
     client_config.genesis = if eth2_network_config.genesis_state_is_known() {
         // Set up weak subjectivity sync, or start from the hardcoded genesis state.
         if let (Some(initial_state_path), Some(initial_block_path), opt_initial_blobs_path) = (
@@ -577,6 +683,19 @@ pub fn get_config<E: EthSpec>(
         ClientGenesis::DepositContract
     };
 
+    if let Some(path) = cli_args.get_one::<String>("deposit-snapshot-file") {
+        if !matches!(
+            client_config.genesis,
+            ClientGenesis::WeakSubjSszBytes { .. } | ClientGenesis::CheckpointSyncUrl { .. }
+        ) {
+            return Err(
+                "--deposit-snapshot-file requires --checkpoint-state or --checkpoint-sync-url"
+                    .to_string(),
+            );
+        }
+        client_config.deposit_snapshot = Some(load_deposit_snapshot(Path::new(path))?);
+    }
+
     if cli_args.get_flag("reconstruct-historic-states") {
         client_config.chain.reconstruct_historic_states = true;
         client_config.chain.genesis_backfill = true;
@@ -706,6 +825,8 @@ pub fn get_config<E: EthSpec>(
             slasher_config.backend = backend;
         }
 
+        slasher_config.auto = cli_args.get_flag("slasher-auto");
+
         client_config.slasher = Some(slasher_config);
     }
 
@@ -752,6 +873,37 @@ pub fn get_config<E: EthSpec>(
             .individual_tracking_threshold = count;
     }
 
+    if let Some(pubkeys) = cli_args.get_one::<String>("slashing-guard-pubkeys") {
+        let pubkeys = pubkeys
+            .split(',')
+            .map(PublicKeyBytes::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid --slashing-guard-pubkeys value: {:?}", e))?;
+        client_config
+            .chain
+            .slashing_guard_pubkeys
+            .extend_from_slice(&pubkeys);
+    }
+
+    if let Some(path) = cli_args.get_one::<String>("slashing-guard-file") {
+        let string = fs::read(path)
+            .map_err(|e| format!("Unable to read --slashing-guard-file: {}", e))
+            .and_then(|bytes| {
+                String::from_utf8(bytes)
+                    .map_err(|e| format!("--slashing-guard-file is not utf8: {}", e))
+            })?;
+        let pubkeys = string
+            .trim_end() // Remove trailing white space
+            .split(',')
+            .map(PublicKeyBytes::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid --slashing-guard-file contents: {:?}", e))?;
+        client_config
+            .chain
+            .slashing_guard_pubkeys
+            .extend_from_slice(&pubkeys);
+    }
+
     if cli_args.get_flag("disable-lock-timeouts") {
         warn!(
             log,
@@ -760,6 +912,14 @@ pub fn get_config<E: EthSpec>(
         );
     }
 
+    if cli_args.get_flag("disable-reorg-capture") {
+        client_config.chain.reorg_capture_depth = None;
+    } else {
+        client_config.chain.reorg_capture_depth =
+            clap_utils::parse_optional(cli_args, "reorg-capture-depth")?;
+    }
+    client_config.chain.reorg_capture_dir = Some(client_config.data_dir().join("reorg_captures"));
+
     if cli_args.get_flag("disable-proposer-reorgs") {
         client_config.chain.re_org_head_threshold = None;
         client_config.chain.re_org_parent_threshold = None;
@@ -802,6 +962,22 @@ pub fn get_config<E: EthSpec>(
         client_config.sync_eth1_chain = false;
     }
 
+    // Votes can only be stubbed safely on networks which will stop processing deposits from the
+    // deposit contract. This also overrides any previous flags that enable deposit syncing.
+    if cli_args.get_flag("stub-eth1-votes") {
+        if spec
+            .electra_fork_epoch
+            .map_or(true, |epoch| epoch == spec.far_future_epoch)
+        {
+            return Err(
+                "--stub-eth1-votes is only permitted on networks which schedule Electra"
+                    .to_string(),
+            );
+        }
+        client_config.stub_eth1_votes = true;
+        client_config.sync_eth1_chain = false;
+    }
+
     client_config.chain.prepare_payload_lookahead =
         clap_utils::parse_optional(cli_args, "prepare-payload-lookahead")?
             .map(Duration::from_millis)
@@ -812,6 +988,9 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.always_prepare_payload = cli_args.get_flag("always-prepare-payload");
 
+    client_config.chain.block_production_budget =
+        clap_utils::parse_optional(cli_args, "block-production-budget")?.map(Duration::from_millis);
+
     if let Some(timeout) =
         clap_utils::parse_optional(cli_args, "fork-choice-before-proposal-timeout")?
     {
@@ -820,6 +999,13 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.always_reset_payload_statuses = cli_args.get_flag("reset-payload-statuses");
 
+    if let Some(handling) = clap_utils::parse_optional(cli_args, "invalid-payload-handling")? {
+        client_config.chain.invalid_payload_handling = handling;
+    }
+    client_config.chain.invalid_payload_max_reorg_depth =
+        clap_utils::parse_optional(cli_args, "invalid-payload-max-reorg-depth")?;
+    client_config.chain.strict_optimistic_sync = cli_args.get_flag("strict-optimistic-sync");
+
     client_config.chain.paranoid_block_proposal = cli_args.get_flag("paranoid-block-proposal");
 
     /*
@@ -846,6 +1032,29 @@ pub fn get_config<E: EthSpec>(
     client_config.chain.optimistic_finalized_sync =
         !cli_args.get_flag("disable-optimistic-finalized-sync");
 
+    if let Some(dir) = clap_utils::parse_optional(cli_args, "replay-from-dir")? {
+        let execution_layer = cli_args.get_flag("replay-with-execution-layer");
+        // Without an execution layer, payloads can only be imported optimistically.
+        if !execution_layer && !client_config.chain.optimistic_finalized_sync {
+            return Err(
+                "--replay-from-dir cannot be used with --disable-optimistic-finalized-sync \
+                 unless --replay-with-execution-layer is set"
+                    .to_string(),
+            );
+        }
+        client_config.replay = Some(ReplayConfig {
+            dir,
+            execution_layer,
+        });
+    }
+
+    // Speculative signature verification during range sync.
+    client_config.chain.speculative_sync_verification =
+        !cli_args.get_flag("disable-speculative-sync-verification");
+
+    client_config.chain.detect_proposer_equivocations =
+        cli_args.get_flag("detect-proposer-equivocations");
+
     if cli_args.get_flag("genesis-backfill") {
         client_config.chain.genesis_backfill = true;
     }
@@ -888,10 +1097,71 @@ pub fn get_config<E: EthSpec>(
         .beacon_processor
         .max_gossip_aggregate_batch_size =
         clap_utils::parse_required(cli_args, "beacon-processor-aggregate-batch-size")?;
+    client_config
+        .beacon_processor
+        .enable_gossip_signature_batching =
+        cli_args.get_flag("beacon-processor-signature-batching");
+    client_config
+        .beacon_processor
+        .max_gossip_signature_batch_size =
+        clap_utils::parse_required(cli_args, "beacon-processor-signature-batch-size")?;
+    client_config.beacon_processor.gossip_signature_batch_window = Duration::from_millis(
+        clap_utils::parse_required(cli_args, "beacon-processor-signature-batch-window")?,
+    );
+
+    if client_config
+        .beacon_processor
+        .max_gossip_signature_batch_size
+        == 0
+    {
+        return Err("--beacon-processor-signature-batch-size must be a non-zero value".to_string());
+    }
+
+    if let Some(deadlines) = cli_args.get_one::<String>("beacon-processor-work-deadlines") {
+        client_config.beacon_processor.work_class_deadlines = parse_work_class_deadlines(deadlines)
+            .map_err(|e| format!("Invalid --beacon-processor-work-deadlines: {}", e))?;
+    }
+
+    if let Some(workers) = cli_args.get_one::<String>("beacon-processor-class-workers") {
+        client_config.beacon_processor.class_workers = parse_work_class_workers(workers)
+            .map_err(|e| format!("Invalid --beacon-processor-class-workers: {}", e))?;
+    }
+    client_config.beacon_processor.pin_workers = cli_args.get_flag("beacon-processor-pin-workers");
 
     Ok(client_config)
 }
 
+/// Loads a deposit tree snapshot from a JSON file, either as returned by the
+/// `/eth/v1/beacon/deposit_snapshot` endpoint or without the enclosing `data` field.
+fn load_deposit_snapshot(path: &Path) -> Result<DepositTreeSnapshot, String> {
+    let file = fs::File::open(path).map_err(|e| {
+        format!(
+            "Unable to open deposit snapshot {}: {:?}",
+            path.display(),
+            e
+        )
+    })?;
+    let mut json: serde_json::Value = serde_json::from_reader(file).map_err(|e| {
+        format!(
+            "Unable to parse deposit snapshot {}: {:?}",
+            path.display(),
+            e
+        )
+    })?;
+    if let Some(data) = json.get_mut("data") {
+        json = data.take();
+    }
+    let snapshot: DepositTreeSnapshot = serde_json::from_value(json)
+        .map_err(|e| format!("Invalid deposit snapshot {}: {:?}", path.display(), e))?;
+    if !snapshot.is_valid() {
+        return Err(format!(
+            "Invalid deposit snapshot {}: the deposit root does not match the finalized deposits",
+            path.display()
+        ));
+    }
+    Ok(snapshot)
+}
+
 /// Gets the listening_addresses for lighthouse based on the cli options.
 pub fn parse_listening_addresses(
     cli_args: &ArgMatches,
@@ -1142,6 +1412,12 @@ pub fn set_network_config(
         config.shutdown_after_sync = true;
     }
 
+    config.geoip_db = clap_utils::parse_optional(cli_args, "geoip-db")?;
+
+    if let Some(capacity) = clap_utils::parse_optional(cli_args, "gossip-trace-capacity")? {
+        config.gossip_trace_capacity = capacity;
+    }
+
     config.set_listening_addr(parse_listening_addresses(cli_args, log)?);
 
     // A custom target-peers command will overwrite the --proposer-only default.
@@ -1183,6 +1459,13 @@ pub fn set_network_config(
         config.boot_nodes_multiaddr = multiaddrs;
     }
 
+    if let Some(boot_nodes_dns) = cli_args.get_one::<String>("boot-nodes-dns") {
+        config.boot_nodes_dns = boot_nodes_dns
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+    }
+
     if let Some(libp2p_addresses_str) = cli_args.get_one::<String>("libp2p-addresses") {
         config.libp2p_nodes = libp2p_addresses_str
             .split(',')
@@ -1194,6 +1477,10 @@ pub fn set_network_config(
             .collect::<Result<Vec<Multiaddr>, _>>()?;
     }
 
+    if parse_flag(cli_args, "disable-aggregator-mesh-boost") {
+        config.aggregator_mesh_boost = false;
+    }
+
     if parse_flag(cli_args, "disable-peer-scoring") {
         config.disable_peer_scoring = true;
     }
@@ -1298,6 +1585,22 @@ pub fn set_network_config(
         }
     }
 
+    if let Some(enr_fields) = cli_args.get_many::<String>("enr-field") {
+        for field in enr_fields {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid ENR field {field}, expected KEY=VALUE"))?;
+            let value = value
+                .strip_prefix("0x")
+                .and_then(|value| hex::decode(value).ok())
+                .ok_or_else(|| {
+                    format!("Invalid ENR field {field}, the value must be 0x-prefixed hex")
+                })?;
+            lighthouse_network::discovery::validate_custom_enr_field(key, &value)?;
+            config.enr_custom_fields.insert(key.to_string(), value);
+        }
+    }
+
     if let Some(enr_addresses) = cli_args.get_many::<String>("enr-address") {
         let mut enr_ip4 = None;
         let mut enr_ip6 = None;
@@ -1394,6 +1697,8 @@ pub fn set_network_config(
         config.disable_quic_support = true;
     }
 
+    config.ip_preference = clap_utils::parse_optional(cli_args, "ip-preference")?;
+
     if parse_flag(cli_args, "disable-upnp") {
         config.upnp_enabled = false;
     }
@@ -1402,6 +1707,22 @@ pub fn set_network_config(
         config.private = true;
     }
 
+    if parse_flag(cli_args, "outbound-only") {
+        config.outbound_only = true;
+        config.upnp_enabled = false;
+    }
+
+    if parse_flag(cli_args, "trusted-peers-only") {
+        config.trusted_peers_only = true;
+        config.disable_discovery = true;
+        if config.libp2p_nodes.is_empty() {
+            warn!(
+                log,
+                "No --libp2p-addresses given, trusted peers must connect to this node"
+            );
+        }
+    }
+
     if parse_flag(cli_args, "metrics") {
         config.metrics_enabled = true;
     }
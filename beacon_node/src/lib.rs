@@ -12,7 +12,7 @@ pub use client::{Client, ClientBuilder, ClientConfig, ClientGenesis};
 pub use config::{get_config, get_data_dir, get_slots_per_restore_point, set_network_config};
 use environment::RuntimeContext;
 pub use eth2_config::Eth2Config;
-use slasher::{DatabaseBackendOverride, Slasher};
+use slasher::{DatabaseBackend, DatabaseBackendOverride, Slasher};
 use slog::{info, warn};
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
@@ -82,6 +82,25 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             );
         }
 
+        if let Some(path) = &client_config.bls_accelerator {
+            // Safety: the library is explicitly configured by the operator, who is responsible for
+            // its provenance.
+            let accelerator = unsafe { bls::accelerator::FfiAccelerator::load(path)? };
+            match bls::accelerator::install(Box::new(accelerator)) {
+                Ok(()) => info!(
+                    log,
+                    "Using BLS accelerator";
+                    "path" => %path.display(),
+                ),
+                Err(e) => warn!(
+                    log,
+                    "BLS accelerator disabled";
+                    "msg" => "signatures will be verified in software",
+                    "error" => e,
+                ),
+            }
+        }
+
         let builder = ClientBuilder::new(context.eth_spec_instance.clone())
             .runtime_context(context)
             .chain_spec(spec.clone())
@@ -116,9 +135,15 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
                 }
                 _ => {}
             }
+            let backing_store = if slasher_config.backend == DatabaseBackend::BeaconStore {
+                Some(builder.slasher_backing_store()?)
+            } else {
+                None
+            };
             let slasher = Arc::new(
-                Slasher::open(
+                Slasher::open_with_backing_store(
                     slasher_config,
+                    backing_store,
                     Arc::new(spec),
                     log.new(slog::o!("service" => "slasher")),
                 )
@@ -157,6 +182,13 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
                 "reason" => "dummy eth1 backend is enabled"
             );
             builder.dummy_eth1_backend()?
+        } else if client_config.stub_eth1_votes {
+            info!(
+                log,
+                "Block production enabled";
+                "method" => "stubbed eth1 votes"
+            );
+            builder.stub_eth1_backend()?
         } else {
             info!(
                 log,
@@ -168,15 +200,30 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
 
         let builder = builder.system_time_slot_clock()?;
 
-        // Inject the executor into the discv5 network config.
-        let discv5_executor = Discv5Executor(executor);
-        client_config.network.discv5_config.executor = Some(Box::new(discv5_executor));
+        let builder = builder.build_beacon_chain()?;
+
+        // Replaying an archive is done without networking, so that only block import is measured.
+        let builder = if let Some(replay_config) = &client_config.replay {
+            builder.replay(replay_config)?
+        } else {
+            // Inject the executor into the discv5 network config.
+            let discv5_executor = Discv5Executor(executor);
+            client_config.network.discv5_config.executor = Some(Box::new(discv5_executor));
+
+            let builder = builder
+                .network(&client_config.network)
+                .await?
+                .notifier()?
+                .clock_monitor(client_config.ntp_server.clone())?;
+
+            if let Some(webhook_config) = &client_config.webhook {
+                builder.webhook_notifier(webhook_config)?
+            } else {
+                builder
+            }
+        };
 
         builder
-            .build_beacon_chain()?
-            .network(&client_config.network)
-            .await?
-            .notifier()?
             .http_metrics_config(client_config.http_metrics.clone())
             .build()
             .map(Self)
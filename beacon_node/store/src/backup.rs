@@ -0,0 +1,240 @@
+//! Online backups of the hot, freezer and blobs databases.
+//!
+//! A backup is a snappy framed stream of every entry in the three databases, read from snapshots
+//! taken while the split is locked. The split can't advance between the snapshots, so restoring a
+//! backup is equivalent to recovering from a crash at the time it was taken. The stream is laid
+//! out as:
+//!
+//! ```text
+//! BACKUP_MAGIC | (db: u8, key_len: u32, key, value_len: u32, value)* | END_OF_BACKUP
+//! ```
+//!
+//! All lengths are little endian.
+use crate::hot_cold_store::HotColdDB;
+use crate::{Error, ItemStore, KeyValueStore, KeyValueStoreOp};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use types::{EthSpec, Slot};
+
+/// Identifies a backup stream, and the version of its format.
+pub const BACKUP_MAGIC: &[u8; 8] = b"LHDBBAK1";
+
+/// Marks the end of the stream, so that a truncated backup is not mistaken for a complete one.
+pub const END_OF_BACKUP: u8 = 0xff;
+
+/// The number of entries written to the database in each batch when restoring.
+pub const RESTORE_BATCH_SIZE: usize = 1024;
+
+const HOT_DB: u8 = 0;
+const COLD_DB: u8 = 1;
+const BLOBS_DB: u8 = 2;
+
+#[derive(Debug)]
+pub enum BackupError {
+    Io(String),
+    InvalidMagic,
+    UnknownDatabase(u8),
+}
+
+/// A description of a completed backup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub split_slot: Slot,
+    pub entries: u64,
+    /// Uncompressed size of the keys and values.
+    pub bytes: u64,
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Write a consistent backup of the databases to `writer`, without blocking other writes.
+    pub fn backup<W: Write>(&self, writer: W) -> Result<BackupSummary, Error> {
+        let (split_slot, hot, cold, blobs) = {
+            let split = self.split.read();
+            (
+                split.slot,
+                self.hot_db.snapshot(),
+                self.cold_db.snapshot(),
+                self.blobs_db.snapshot(),
+            )
+        };
+
+        let mut encoder = snap::write::FrameEncoder::new(writer);
+        write_all(&mut encoder, BACKUP_MAGIC)?;
+
+        let mut summary = BackupSummary {
+            split_slot,
+            entries: 0,
+            bytes: 0,
+        };
+        for (db, snapshot) in [(HOT_DB, hot), (COLD_DB, cold), (BLOBS_DB, blobs)] {
+            snapshot.for_each_entry(&mut |key, value| {
+                write_all(&mut encoder, &[db])?;
+                write_all(&mut encoder, &(key.len() as u32).to_le_bytes())?;
+                write_all(&mut encoder, key)?;
+                write_all(&mut encoder, &(value.len() as u32).to_le_bytes())?;
+                write_all(&mut encoder, value)?;
+                summary.entries += 1;
+                summary.bytes += (key.len() + value.len()) as u64;
+                Ok(())
+            })?;
+        }
+
+        write_all(&mut encoder, &[END_OF_BACKUP])?;
+        encoder.flush().map_err(io_error)?;
+        Ok(summary)
+    }
+}
+
+/// Write the entries of a backup read from `reader` into empty databases.
+///
+/// Returns the number of entries restored.
+pub fn restore_backup<E: EthSpec, Hot: KeyValueStore<E>, Cold: KeyValueStore<E>, R: Read>(
+    reader: R,
+    hot_db: &Hot,
+    cold_db: &Cold,
+    blobs_db: &Cold,
+) -> Result<u64, Error> {
+    let mut decoder = snap::read::FrameDecoder::new(reader);
+    let mut magic = [0; BACKUP_MAGIC.len()];
+    decoder.read_exact(&mut magic).map_err(io_error)?;
+    if &magic != BACKUP_MAGIC {
+        return Err(BackupError::InvalidMagic.into());
+    }
+
+    let mut entries = 0;
+    let mut batch_db = HOT_DB;
+    let mut batch = vec![];
+    loop {
+        let db = read_array::<1>(&mut decoder)?[0];
+        if db != batch_db || batch.len() >= RESTORE_BATCH_SIZE || db == END_OF_BACKUP {
+            write_batch(
+                batch_db,
+                std::mem::take(&mut batch),
+                hot_db,
+                cold_db,
+                blobs_db,
+            )?;
+            batch_db = db;
+        }
+        if db == END_OF_BACKUP {
+            return Ok(entries);
+        }
+
+        let key = read_vec(&mut decoder)?;
+        let value = read_vec(&mut decoder)?;
+        batch.push(KeyValueStoreOp::PutKeyValue(key, value));
+        entries += 1;
+    }
+}
+
+fn write_batch<E: EthSpec, Hot: KeyValueStore<E>, Cold: KeyValueStore<E>>(
+    db: u8,
+    batch: Vec<KeyValueStoreOp>,
+    hot_db: &Hot,
+    cold_db: &Cold,
+    blobs_db: &Cold,
+) -> Result<(), Error> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    match db {
+        HOT_DB => hot_db.do_atomically(batch),
+        COLD_DB => cold_db.do_atomically(batch),
+        BLOBS_DB => blobs_db.do_atomically(batch),
+        _ => Err(BackupError::UnknownDatabase(db).into()),
+    }
+}
+
+fn write_all<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), Error> {
+    writer.write_all(bytes).map_err(io_error)
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> Result<[u8; N], Error> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes).map_err(io_error)?;
+    Ok(bytes)
+}
+
+fn read_vec(reader: &mut impl Read) -> Result<Vec<u8>, Error> {
+    let len = u32::from_le_bytes(read_array(reader)?) as usize;
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes).map_err(io_error)?;
+    Ok(bytes)
+}
+
+fn io_error(e: std::io::Error) -> Error {
+    BackupError::Io(e.to_string()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DBColumn, MemoryStore, StoreConfig};
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use types::{Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+    type Store = HotColdDB<E, MemoryStore<E>, MemoryStore<E>>;
+
+    #[test]
+    fn round_trip() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let db = Store::open_ephemeral(StoreConfig::default(), E::default_spec(), log).unwrap();
+        for i in 0..RESTORE_BATCH_SIZE as u64 + 1 {
+            let key = Hash256::from_low_u64_be(i);
+            db.hot_db
+                .put_bytes(DBColumn::BeaconBlock.as_str(), key.as_bytes(), &[1; 64])
+                .unwrap();
+        }
+        db.cold_db
+            .put_bytes(DBColumn::BeaconStateRoots.as_str(), &[2], &[3])
+            .unwrap();
+        db.blobs_db
+            .put_bytes(DBColumn::BeaconBlob.as_str(), &[4], &[5])
+            .unwrap();
+
+        let mut backup = vec![];
+        let summary = db.backup(&mut backup).unwrap();
+        assert_eq!(summary.entries, RESTORE_BATCH_SIZE as u64 + 3);
+
+        let (hot, cold, blobs) = (
+            MemoryStore::<E>::open(),
+            MemoryStore::<E>::open(),
+            MemoryStore::<E>::open(),
+        );
+        let entries = restore_backup::<E, _, _, _>(backup.as_slice(), &hot, &cold, &blobs).unwrap();
+        assert_eq!(entries, summary.entries);
+        assert_eq!(
+            cold.get_bytes(DBColumn::BeaconStateRoots.as_str(), &[2])
+                .unwrap(),
+            Some(vec![3])
+        );
+        assert_eq!(
+            blobs
+                .get_bytes(DBColumn::BeaconBlob.as_str(), &[4])
+                .unwrap(),
+            Some(vec![5])
+        );
+        assert_eq!(
+            hot.get_bytes(DBColumn::BeaconBlock.as_str(), Hash256::zero().as_bytes())
+                .unwrap(),
+            Some(vec![1; 64])
+        );
+    }
+
+    #[test]
+    fn truncated_backup() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let db = Store::open_ephemeral(StoreConfig::default(), E::default_spec(), log).unwrap();
+        let mut backup = vec![];
+        db.backup(&mut backup).unwrap();
+        backup.truncate(backup.len() - 1);
+
+        let (hot, cold, blobs) = (
+            MemoryStore::<E>::open(),
+            MemoryStore::<E>::open(),
+            MemoryStore::<E>::open(),
+        );
+        assert!(restore_backup::<E, _, _, _>(backup.as_slice(), &hot, &cold, &blobs).is_err());
+    }
+}
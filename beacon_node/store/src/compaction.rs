@@ -0,0 +1,145 @@
+//! Background compaction of the hot database, split into chunks so that it can be throttled and
+//! its progress reported.
+use crate::hot_cold_store::HotColdDB;
+use crate::{metrics, DBColumn, Error, ItemStore};
+use serde::{Deserialize, Serialize};
+use slog::{debug, info};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+
+/// Columns which are compacted, as they have the most churn, i.e. entries being created and
+/// deleted.
+pub const COMPACTION_COLUMNS: [DBColumn; 3] = [
+    DBColumn::BeaconState,
+    DBColumn::BeaconStateSummary,
+    DBColumn::BeaconBlock,
+];
+
+/// The number of chunks each column is divided into, by the first byte of the key.
+pub const COMPACTION_CHUNKS_PER_COLUMN: u8 = 16;
+
+/// The reason a compaction was started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionTrigger {
+    /// A large number of epochs were pruned at once.
+    Prune,
+    /// The compaction period elapsed.
+    Schedule,
+    /// Requested via the HTTP API.
+    Manual,
+}
+
+impl CompactionTrigger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompactionTrigger::Prune => "prune",
+            CompactionTrigger::Schedule => "schedule",
+            CompactionTrigger::Manual => "manual",
+        }
+    }
+}
+
+/// The progress of the current or most recent compaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionProgress {
+    pub in_progress: bool,
+    pub trigger: Option<CompactionTrigger>,
+    pub chunks_completed: usize,
+    pub chunks_total: usize,
+    /// Time at which the most recent compaction completed, in seconds since the UNIX epoch.
+    pub last_completed: Option<u64>,
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    pub fn compaction_progress(&self) -> CompactionProgress {
+        self.compaction_progress.read().clone()
+    }
+
+    /// Compact the hot database one chunk at a time, pausing for `compaction_throttle` between
+    /// chunks so that the node's own reads and writes are not starved.
+    ///
+    /// Returns `false` without compacting if a compaction is already in progress.
+    pub fn compact_throttled(&self, trigger: CompactionTrigger) -> Result<bool, Error> {
+        let chunks_total = COMPACTION_COLUMNS.len() * COMPACTION_CHUNKS_PER_COLUMN as usize;
+        {
+            let mut progress = self.compaction_progress.write();
+            if progress.in_progress {
+                return Ok(false);
+            }
+            *progress = CompactionProgress {
+                in_progress: true,
+                trigger: Some(trigger),
+                chunks_completed: 0,
+                chunks_total,
+                last_completed: progress.last_completed,
+            };
+        }
+        metrics::set_gauge(&metrics::COMPACTION_IN_PROGRESS, 1);
+        metrics::set_gauge(&metrics::COMPACTION_CHUNKS_TOTAL, chunks_total as i64);
+        metrics::set_gauge(&metrics::COMPACTION_CHUNKS_COMPLETED, 0);
+        metrics::inc_counter_vec(&metrics::COMPACTIONS_TOTAL, &[trigger.as_str()]);
+
+        info!(
+            self.log,
+            "Starting database compaction";
+            "trigger" => trigger.as_str(),
+            "chunks" => chunks_total,
+        );
+        let start = Instant::now();
+        let result = self.compact_chunks();
+
+        let finish_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        {
+            let mut progress = self.compaction_progress.write();
+            progress.in_progress = false;
+            if result.is_ok() {
+                progress.last_completed = Some(finish_time.as_secs());
+            }
+        }
+        metrics::set_gauge(&metrics::COMPACTION_IN_PROGRESS, 0);
+        result?;
+
+        self.store_compaction_timestamp(finish_time)?;
+        metrics::observe_duration(&metrics::COMPACTION_TIMES, start.elapsed());
+        info!(
+            self.log,
+            "Database compaction complete";
+            "time_secs" => start.elapsed().as_secs(),
+        );
+        Ok(true)
+    }
+
+    fn compact_chunks(&self) -> Result<(), Error> {
+        let chunk_width = u8::MAX / COMPACTION_CHUNKS_PER_COLUMN + 1;
+        for column in COMPACTION_COLUMNS {
+            for chunk in 0..COMPACTION_CHUNKS_PER_COLUMN {
+                let first = chunk * chunk_width;
+                let last = first + (chunk_width - 1);
+                self.hot_db
+                    .compact_column_range(column, &[first], &[last])?;
+
+                let chunks_completed = {
+                    let mut progress = self.compaction_progress.write();
+                    progress.chunks_completed += 1;
+                    progress.chunks_completed
+                };
+                metrics::set_gauge(
+                    &metrics::COMPACTION_CHUNKS_COMPLETED,
+                    chunks_completed as i64,
+                );
+                debug!(
+                    self.log,
+                    "Compacted database chunk";
+                    "column" => column.as_str(),
+                    "chunk" => chunk,
+                );
+
+                std::thread::sleep(self.config.compaction_throttle);
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::num::NonZeroUsize;
+use std::time::Duration;
 use types::non_zero_usize::new_non_zero_usize;
 use types::{EthSpec, MinimalEthSpec};
 
@@ -13,6 +14,9 @@ pub const DEFAULT_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(1);
 pub const DEFAULT_EPOCHS_PER_BLOB_PRUNE: u64 = 1;
 pub const DEFAULT_BLOB_PUNE_MARGIN_EPOCHS: u64 = 0;
+/// Compact at least this frequently, finalization permitting (7 days).
+pub const DEFAULT_COMPACTION_PERIOD: Duration = Duration::from_secs(604800);
+pub const DEFAULT_COMPACTION_THROTTLE: Duration = Duration::from_millis(100);
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,6 +35,10 @@ pub struct StoreConfig {
     pub compact_on_init: bool,
     /// Whether to compact the database during database pruning.
     pub compact_on_prune: bool,
+    /// Compact the database at least this frequently, if `compact_on_prune` is set.
+    pub compaction_period: Duration,
+    /// The time to pause between each chunk of a background compaction, to limit its IO load.
+    pub compaction_throttle: Duration,
     /// Whether to prune payloads on initialization and finalization.
     pub prune_payloads: bool,
     /// Whether to prune blobs older than the blob data availability boundary.
@@ -40,6 +48,10 @@ pub struct StoreConfig {
     /// The margin for blob pruning in epochs. The oldest blobs are pruned up until
     /// data_availability_boundary - blob_prune_margin_epochs. Default: 0.
     pub blob_prune_margin_epochs: u64,
+    /// Size of the database in bytes above which blobs and payloads are pruned more aggressively.
+    pub target_disk_usage: Option<u64>,
+    /// Number of finalized epochs to retain the blocks of abandoned forks for before pruning them.
+    pub prune_forks_after_epochs: u64,
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -64,10 +76,14 @@ impl Default for StoreConfig {
             historic_state_cache_size: DEFAULT_HISTORIC_STATE_CACHE_SIZE,
             compact_on_init: false,
             compact_on_prune: true,
+            compaction_period: DEFAULT_COMPACTION_PERIOD,
+            compaction_throttle: DEFAULT_COMPACTION_THROTTLE,
             prune_payloads: true,
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
             blob_prune_margin_epochs: DEFAULT_BLOB_PUNE_MARGIN_EPOCHS,
+            target_disk_usage: None,
+            prune_forks_after_epochs: 0,
         }
     }
 }
@@ -0,0 +1,423 @@
+//! Reading and writing of era files.
+//!
+//! An era file is an [e2store] encoded archive of the blocks of one era, i.e. the
+//! `SLOTS_PER_HISTORICAL_ROOT` slots preceding the era's boundary slot, and the state at the
+//! boundary slot. The records are laid out as:
+//!
+//! ```text
+//! Version | CompressedSignedBeaconBlock* | CompressedBeaconState | SlotIndex(block)? | SlotIndex(state)
+//! ```
+//!
+//! Era 0 only contains the genesis state and has no block index. Blocks and states are SSZ
+//! encoded and snappy framed. The `.era1` files of the execution layer use the same e2store
+//! container, so `E2StoreReader` can read them, but their records are not understood here.
+//!
+//! [e2store]: https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md
+use crate::Error;
+use ssz::{Decode, Encode};
+use std::io::{Read, Write};
+use tree_hash::TreeHash;
+use types::{BeaconState, ChainSpec, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// The type of the version record which starts every e2store file.
+pub const VERSION: [u8; 2] = [0x65, 0x32];
+/// The type of a snappy framed, SSZ encoded `SignedBeaconBlock`.
+pub const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+/// The type of a snappy framed, SSZ encoded `BeaconState`.
+pub const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+/// The type of an index from slots to record offsets.
+pub const SLOT_INDEX: [u8; 2] = [0x69, 0x32];
+
+/// The length of a record header: a 2 byte type, 4 byte length and 2 reserved bytes.
+const HEADER_LEN: usize = 8;
+
+#[derive(Debug, PartialEq)]
+pub enum EraError {
+    TruncatedHeader { offset: usize },
+    TruncatedRecord { offset: usize },
+    RecordTooLong { len: usize },
+    MissingVersion,
+    MissingState,
+    MultipleStates { offset: usize },
+    Compression { offset: usize, message: String },
+    UnalignedState { slot: Slot },
+    BlockOutsideEra { slot: Slot, era: u64 },
+    BlocksNotAscending { slot: Slot },
+    MissingHistoricalRoot { era: u64 },
+}
+
+/// A record of an e2store file.
+pub struct Record<'a> {
+    pub record_type: [u8; 2],
+    /// The offset of the record's header from the start of the file.
+    pub offset: usize,
+    pub data: &'a [u8],
+}
+
+impl Record<'_> {
+    /// Returns the snappy decompressed data of the record.
+    pub fn decompress(&self) -> Result<Vec<u8>, EraError> {
+        let mut bytes = vec![];
+        snap::read::FrameDecoder::new(self.data)
+            .read_to_end(&mut bytes)
+            .map_err(|e| EraError::Compression {
+                offset: self.offset,
+                message: e.to_string(),
+            })?;
+        Ok(bytes)
+    }
+}
+
+/// Iterates over the records of an e2store file.
+pub struct E2StoreReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> E2StoreReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+}
+
+impl<'a> Iterator for E2StoreReader<'a> {
+    type Item = Result<Record<'a>, EraError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.bytes.len() {
+            return None;
+        }
+
+        let offset = self.offset;
+        let Some(header) = self.bytes.get(offset..offset + HEADER_LEN) else {
+            self.offset = self.bytes.len();
+            return Some(Err(EraError::TruncatedHeader { offset }));
+        };
+        let record_type = [header[0], header[1]];
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        let start = offset + HEADER_LEN;
+        let Some(data) = self.bytes.get(start..start + len) else {
+            self.offset = self.bytes.len();
+            return Some(Err(EraError::TruncatedRecord { offset }));
+        };
+
+        self.offset = start + len;
+        Some(Ok(Record {
+            record_type,
+            offset,
+            data,
+        }))
+    }
+}
+
+/// Builds an e2store file in memory.
+#[derive(Default)]
+pub struct E2StoreWriter {
+    bytes: Vec<u8>,
+}
+
+impl E2StoreWriter {
+    /// Returns a writer for a file which starts with a version record.
+    pub fn new() -> Self {
+        let mut writer = Self::default();
+        // A version record is empty, so this can't fail.
+        let _ = writer.write_record(VERSION, &[]);
+        writer
+    }
+
+    /// Appends a record, returning its offset.
+    pub fn write_record(&mut self, record_type: [u8; 2], data: &[u8]) -> Result<usize, EraError> {
+        let len =
+            u32::try_from(data.len()).map_err(|_| EraError::RecordTooLong { len: data.len() })?;
+        let offset = self.bytes.len();
+        self.bytes.extend_from_slice(&record_type);
+        self.bytes.extend_from_slice(&len.to_le_bytes());
+        self.bytes.extend_from_slice(&[0, 0]);
+        self.bytes.extend_from_slice(data);
+        Ok(offset)
+    }
+
+    /// Appends a record containing the snappy framed `data`, returning its offset.
+    pub fn write_compressed(
+        &mut self,
+        record_type: [u8; 2],
+        data: &[u8],
+    ) -> Result<usize, EraError> {
+        let mut encoder = snap::write::FrameEncoder::new(vec![]);
+        let offset = self.bytes.len();
+        encoder.write_all(data).map_err(|e| EraError::Compression {
+            offset,
+            message: e.to_string(),
+        })?;
+        let compressed = encoder.into_inner().map_err(|e| EraError::Compression {
+            offset,
+            message: e.to_string(),
+        })?;
+        self.write_record(record_type, &compressed)
+    }
+
+    /// Appends a slot index for the records at `offsets`, one per slot from `starting_slot`.
+    ///
+    /// Offsets are relative to the start of the index, with `None` (e.g. a skipped slot) encoded
+    /// as zero.
+    pub fn write_slot_index(
+        &mut self,
+        starting_slot: Slot,
+        offsets: &[Option<usize>],
+    ) -> Result<usize, EraError> {
+        let index_offset = self.bytes.len() as i64;
+        let mut data = Vec::with_capacity((offsets.len() + 2) * 8);
+        data.extend_from_slice(&(starting_slot.as_u64() as i64).to_le_bytes());
+        for offset in offsets {
+            let relative = offset.map_or(0, |offset| offset as i64 - index_offset);
+            data.extend_from_slice(&relative.to_le_bytes());
+        }
+        data.extend_from_slice(&(offsets.len() as i64).to_le_bytes());
+        self.write_record(SLOT_INDEX, &data)
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// The contents of an era file.
+#[derive(Debug)]
+pub struct EraFile<E: EthSpec> {
+    /// The blocks of the era, in ascending slot order.
+    pub blocks: Vec<SignedBeaconBlock<E>>,
+    /// The state at the era's boundary slot.
+    pub state: BeaconState<E>,
+}
+
+impl<E: EthSpec> EraFile<E> {
+    /// Returns the era number, i.e. the number of historical roots before the state.
+    pub fn era(&self) -> u64 {
+        self.state.slot().as_u64() / E::slots_per_historical_root() as u64
+    }
+
+    /// Returns the first slot of the blocks in this era.
+    pub fn start_slot(&self) -> Slot {
+        self.state
+            .slot()
+            .saturating_sub(E::slots_per_historical_root() as u64)
+    }
+
+    pub fn from_bytes(bytes: &[u8], spec: &ChainSpec) -> Result<Self, Error> {
+        let mut records = E2StoreReader::new(bytes);
+        match records.next().transpose()? {
+            Some(record) if record.record_type == VERSION => (),
+            _ => return Err(EraError::MissingVersion.into()),
+        }
+
+        let mut blocks: Vec<SignedBeaconBlock<E>> = vec![];
+        let mut state = None;
+        for record in records {
+            let record = record?;
+            match record.record_type {
+                COMPRESSED_SIGNED_BEACON_BLOCK => {
+                    let block = SignedBeaconBlock::from_ssz_bytes(&record.decompress()?, spec)?;
+                    if blocks
+                        .last()
+                        .map_or(false, |prev| prev.slot() >= block.slot())
+                    {
+                        return Err(EraError::BlocksNotAscending { slot: block.slot() }.into());
+                    }
+                    blocks.push(block);
+                }
+                COMPRESSED_BEACON_STATE => {
+                    if state.is_some() {
+                        return Err(EraError::MultipleStates {
+                            offset: record.offset,
+                        }
+                        .into());
+                    }
+                    state = Some(BeaconState::from_ssz_bytes(&record.decompress()?, spec)?);
+                }
+                _ => (),
+            }
+        }
+
+        let era_file = Self {
+            blocks,
+            state: state.ok_or(EraError::MissingState)?,
+        };
+        era_file.verify_slots()?;
+        Ok(era_file)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.verify_slots()?;
+
+        let mut writer = E2StoreWriter::new();
+        let start_slot = self.start_slot();
+        let mut block_offsets = vec![None; E::slots_per_historical_root()];
+        for block in &self.blocks {
+            let offset =
+                writer.write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, &block.as_ssz_bytes())?;
+            if let Some(slot_offset) = block_offsets.get_mut((block.slot() - start_slot).as_usize())
+            {
+                *slot_offset = Some(offset);
+            }
+        }
+        let state_offset =
+            writer.write_compressed(COMPRESSED_BEACON_STATE, &self.state.as_ssz_bytes())?;
+
+        if self.era() > 0 {
+            writer.write_slot_index(start_slot, &block_offsets)?;
+        }
+        writer.write_slot_index(self.state.slot(), &[Some(state_offset)])?;
+
+        Ok(writer.into_bytes())
+    }
+
+    /// Returns the file name used by other clients, `<config-name>-<era>-<short-root>.era`.
+    ///
+    /// The short root is the first 4 bytes of the historical root of the era, or of the genesis
+    /// validators root for era 0.
+    pub fn file_name(&self, config_name: &str) -> Result<String, Error> {
+        let era = self.era();
+        let root = if era == 0 {
+            self.state.genesis_validators_root()
+        } else {
+            let index = era as usize - 1;
+            let historical_roots = self.state.historical_roots();
+            match historical_roots.get(index) {
+                Some(root) => *root,
+                None => self
+                    .state
+                    .historical_summaries()?
+                    .get(index - historical_roots.len())
+                    .map(TreeHash::tree_hash_root)
+                    .ok_or(EraError::MissingHistoricalRoot { era })?,
+            }
+        };
+        Ok(format!(
+            "{}-{:05}-{}.era",
+            config_name,
+            era,
+            short_root(root)
+        ))
+    }
+
+    fn verify_slots(&self) -> Result<(), EraError> {
+        let slot = self.state.slot();
+        if slot % E::slots_per_historical_root() as u64 != 0 {
+            return Err(EraError::UnalignedState { slot });
+        }
+        let start_slot = self.start_slot();
+        if let Some(block) = self
+            .blocks
+            .iter()
+            .find(|block| block.slot() < start_slot || block.slot() >= slot)
+        {
+            return Err(EraError::BlockOutsideEra {
+                slot: block.slot(),
+                era: self.era(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Reads only the blocks of an era file, without decoding its state.
+pub fn read_blocks<E: EthSpec>(
+    bytes: &[u8],
+    spec: &ChainSpec,
+) -> Result<Vec<SignedBeaconBlock<E>>, Error> {
+    E2StoreReader::new(bytes)
+        .filter(|record| {
+            record.as_ref().map_or(true, |record| {
+                record.record_type == COMPRESSED_SIGNED_BEACON_BLOCK
+            })
+        })
+        .map(|record| {
+            Ok(SignedBeaconBlock::from_ssz_bytes(
+                &record?.decompress()?,
+                spec,
+            )?)
+        })
+        .collect()
+}
+
+fn short_root(root: Hash256) -> String {
+    root.as_bytes()[..4]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, Eth1Data, MinimalEthSpec, Signature};
+
+    type E = MinimalEthSpec;
+
+    fn block_at(slot: u64, spec: &ChainSpec) -> SignedBeaconBlock<E> {
+        let mut block = BeaconBlock::empty(spec);
+        *block.slot_mut() = Slot::new(slot);
+        SignedBeaconBlock::from_block(block, Signature::empty())
+    }
+
+    fn era_one(spec: &ChainSpec) -> EraFile<E> {
+        let mut state = BeaconState::new(0, Eth1Data::default(), spec);
+        *state.slot_mut() = Slot::new(E::slots_per_historical_root() as u64);
+        EraFile {
+            blocks: vec![block_at(1, spec), block_at(3, spec)],
+            state,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let spec = E::default_spec();
+        let era_file = era_one(&spec);
+        let bytes = era_file.to_bytes().unwrap();
+
+        let decoded = EraFile::<E>::from_bytes(&bytes, &spec).unwrap();
+        assert_eq!(decoded.era(), 1);
+        assert_eq!(decoded.blocks, era_file.blocks);
+        assert_eq!(decoded.state.as_ssz_bytes(), era_file.state.as_ssz_bytes());
+        assert_eq!(read_blocks::<E>(&bytes, &spec).unwrap(), era_file.blocks);
+
+        assert!(EraFile::<E>::from_bytes(&bytes[..bytes.len() - 1], &spec).is_err());
+    }
+
+    #[test]
+    fn block_index_points_at_blocks() {
+        let spec = E::default_spec();
+        let bytes = era_one(&spec).to_bytes().unwrap();
+        let records = E2StoreReader::new(&bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // Version, 2 blocks, state, block index and state index.
+        assert_eq!(records.len(), 6);
+        let index = &records[4];
+        assert_eq!(index.record_type, SLOT_INDEX);
+        let entry = |i: usize| i64::from_le_bytes(index.data[i * 8..i * 8 + 8].try_into().unwrap());
+        let count = E::slots_per_historical_root();
+        assert_eq!(entry(0), 0);
+        assert_eq!(entry(count + 1), count as i64);
+        assert_eq!(entry(1), 0);
+        assert_eq!(entry(2) + index.offset as i64, records[1].offset as i64);
+        assert_eq!(entry(4) + index.offset as i64, records[2].offset as i64);
+    }
+
+    #[test]
+    fn rejects_blocks_outside_era() {
+        let spec = E::default_spec();
+        let mut era_file = era_one(&spec);
+        era_file
+            .blocks
+            .push(block_at(E::slots_per_historical_root() as u64, &spec));
+        assert_eq!(
+            era_file.verify_slots(),
+            Err(EraError::BlockOutsideEra {
+                slot: Slot::new(E::slots_per_historical_root() as u64),
+                era: 1,
+            })
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use crate::backup::BackupError;
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::era::EraError;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -57,6 +59,8 @@ pub enum Error {
         state_root: Hash256,
         slot: Slot,
     },
+    EraError(EraError),
+    BackupError(BackupError),
 }
 
 pub trait HandleUnavailable<T> {
@@ -127,6 +131,18 @@ impl From<EpochCacheError> for Error {
     }
 }
 
+impl From<EraError> for Error {
+    fn from(e: EraError) -> Error {
+        Error::EraError(e)
+    }
+}
+
+impl From<BackupError> for Error {
+    fn from(e: BackupError) -> Error {
+        Error::BackupError(e)
+    }
+}
+
 #[derive(Debug)]
 pub struct DBError {
     pub message: String,
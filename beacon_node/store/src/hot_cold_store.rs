@@ -1,6 +1,7 @@
 use crate::chunked_vector::{
     store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
 };
+use crate::compaction::CompactionProgress;
 use crate::config::{
     OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
@@ -12,15 +13,17 @@ use crate::leveldb_store::BytesKey;
 use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
-    AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
-    BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
-    PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
+    AnchorInfo, BlobInfo, CompactionTimestamp, PendingForkPrunes, PruningCheckpoint, SchemaVersion,
+    ANCHOR_INFO_KEY, BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
+    PENDING_FORK_PRUNES_KEY, PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
 };
 use crate::metrics;
+use crate::retention::Retention;
 use crate::state_cache::{PutStateOutcome, StateCache};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStore,
+    KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
 use leveldb::iterator::LevelDBIterator;
@@ -74,6 +77,10 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     state_cache: Mutex<StateCache<E>>,
     /// LRU cache of replayed states.
     historic_state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
+    /// Progress of the current or most recent background compaction.
+    pub(crate) compaction_progress: RwLock<CompactionProgress>,
+    /// Disk usage samples and the state of the retention policy.
+    pub(crate) retention: RwLock<Retention>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -186,6 +193,8 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            compaction_progress: RwLock::new(CompactionProgress::default()),
+            retention: RwLock::new(Retention::default()),
             config,
             spec,
             log,
@@ -222,6 +231,8 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            compaction_progress: RwLock::new(CompactionProgress::default()),
+            retention: RwLock::new(Retention::default()),
             config,
             spec,
             log,
@@ -2004,6 +2015,43 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         PruningCheckpoint { checkpoint }.as_kv_store_op(PRUNING_CHECKPOINT_KEY)
     }
 
+    /// Load the blocks of abandoned forks which are retained until they are old enough to prune.
+    pub fn load_pending_fork_prunes(&self) -> Result<PendingForkPrunes, Error> {
+        Ok(self
+            .hot_db
+            .get(&PENDING_FORK_PRUNES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Create a staged store for the blocks of abandoned forks which are yet to be pruned.
+    pub fn pending_fork_prunes_store_op(&self, pending: &PendingForkPrunes) -> KeyValueStoreOp {
+        pending.as_kv_store_op(PENDING_FORK_PRUNES_KEY)
+    }
+
+    /// Return the number of bytes used to store the given blocks, with their payloads and blobs,
+    /// and the given states.
+    ///
+    /// This is an estimate of the space reclaimed by deleting them, before compaction.
+    pub fn stored_size(
+        &self,
+        block_roots: &[Hash256],
+        states: &[(Hash256, Slot)],
+    ) -> Result<u64, Error> {
+        let mut size = 0;
+        for block_root in block_roots {
+            size += stored_len(&self.hot_db, DBColumn::BeaconBlock, block_root)?;
+            size += stored_len(&self.hot_db, DBColumn::ExecPayload, block_root)?;
+            size += stored_len(&self.blobs_db, DBColumn::BeaconBlob, block_root)?;
+        }
+        for (state_root, slot) in states {
+            size += stored_len(&self.hot_db, DBColumn::BeaconStateSummary, state_root)?;
+            if *slot % E::slots_per_epoch() == 0 {
+                size += stored_len(&self.hot_db, DBColumn::BeaconState, state_root)?;
+            }
+        }
+        Ok(size)
+    }
+
     /// Load the timestamp of the last compaction as a `Duration` since the UNIX epoch.
     pub fn load_compaction_timestamp(&self) -> Result<Option<Duration>, Error> {
         Ok(self
@@ -2185,7 +2233,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
 
         let pruning_enabled = self.get_config().prune_blobs;
-        let margin_epochs = self.get_config().blob_prune_margin_epochs;
+        let margin_epochs = self.effective_blob_prune_margin_epochs();
         let epochs_per_blob_prune = self.get_config().epochs_per_blob_prune;
 
         if !force && !pruning_enabled {
@@ -2538,6 +2586,17 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 }
 
+/// Return the length of the value stored under `key` in `column`, or 0 if there is none.
+fn stored_len<E: EthSpec, S: KeyValueStore<E>>(
+    db: &S,
+    column: DBColumn,
+    key: &Hash256,
+) -> Result<u64, Error> {
+    Ok(db
+        .get_bytes(column.into(), key.as_bytes())?
+        .map_or(0, |bytes| bytes.len() as u64))
+}
+
 /// Advance the split point of the store, moving new finalized states to the freezer.
 pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     store: Arc<HotColdDB<E, Hot, Cold>>,
@@ -2595,7 +2654,7 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
         // delete the payload for the finalized block itself, but that's OK as we only guarantee
         // that payloads are present for slots >= the split slot. The payload fetching code is also
         // forgiving of missing payloads.
-        if store.config.prune_payloads {
+        if store.effective_prune_payloads() {
             hot_db_ops.push(StoreOp::DeleteExecutionPayload(block_root));
         }
 
@@ -7,6 +7,7 @@ use leveldb::database::Database;
 use leveldb::error::Error as LevelDBError;
 use leveldb::iterator::{Iterable, KeyIterator, LevelDBIterator};
 use leveldb::options::{Options, ReadOptions, WriteOptions};
+use leveldb::snapshots::{Snapshot, Snapshots};
 use parking_lot::Mutex;
 use std::marker::PhantomData;
 use std::path::Path;
@@ -180,6 +181,21 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
         Ok(())
     }
 
+    fn compact_column_range(
+        &self,
+        column: DBColumn,
+        start: &[u8],
+        end: &[u8],
+    ) -> Result<(), Error> {
+        let key_size = std::cmp::max(column.key_size(), 32);
+        let mut end = end.to_vec();
+        end.resize(std::cmp::max(end.len(), key_size), 0xff);
+        let start_key = BytesKey::from_vec(get_key_for_col(column.as_str(), start));
+        let end_key = BytesKey::from_vec(get_key_for_col(column.as_str(), &end));
+        self.db.compact(&start_key, &end_key);
+        Ok(())
+    }
+
     fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
         let start_key = BytesKey::from_vec(get_key_for_col(column.into(), from));
 
@@ -214,6 +230,22 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
         )
     }
 
+    fn iter_raw_entries_from(&self, column: DBColumn, prefix: &[u8], from: &[u8]) -> RawEntryIter {
+        let prefix_key = get_key_for_col(column.into(), prefix);
+        let start_key = BytesKey::from_vec(get_key_for_col(column.into(), from));
+
+        let iter = self.db.iter(self.read_options());
+        iter.seek(&start_key);
+
+        Box::new(
+            iter.take_while(move |(key, _)| key.key.starts_with(prefix_key.as_slice()))
+                .map(move |(bytes_key, value)| {
+                    let subkey = &bytes_key.key[column.as_bytes().len()..];
+                    Ok((Vec::from(subkey), value))
+                }),
+        )
+    }
+
     fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
         let start_key = BytesKey::from_vec(get_key_for_col(column.into(), prefix));
 
@@ -249,10 +281,33 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
                 }),
         )
     }
+
+    fn snapshot(&self) -> Box<dyn KeyValueSnapshot + '_> {
+        Box::new(LevelDBSnapshot {
+            snapshot: self.db.snapshot(),
+        })
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
 
+/// A LevelDB snapshot, which pins the state of the database at the time it was taken.
+struct LevelDBSnapshot<'a> {
+    snapshot: Snapshot<'a, BytesKey>,
+}
+
+impl KeyValueSnapshot for LevelDBSnapshot<'_> {
+    fn for_each_entry(
+        &self,
+        f: &mut dyn FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        for (key, value) in self.snapshot.iter(ReadOptions::new()) {
+            f(&key.key, &value)?;
+        }
+        Ok(())
+    }
+}
+
 /// Used for keying leveldb.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BytesKey {
@@ -297,6 +352,10 @@ impl BytesKey {
     pub fn from_vec(key: Vec<u8>) -> Self {
         Self { key }
     }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
 }
 
 impl From<LevelDBError> for Error {
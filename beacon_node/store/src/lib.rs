@@ -7,11 +7,14 @@
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
 //! tests for implementation examples.
+pub mod backup;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
+pub mod compaction;
 pub mod config;
 pub mod consensus_context;
+pub mod era;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
@@ -23,6 +26,7 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+pub mod retention;
 pub mod state_cache;
 
 pub mod iter;
@@ -83,15 +87,16 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
     /// Compact a single column in the database, freeing space used by deleted items.
     fn compact_column(&self, column: DBColumn) -> Result<(), Error>;
 
+    /// Compact the keys of `column` from `start` to `end` (inclusive).
+    ///
+    /// Keys which are shorter than the column's key size are padded, i.e. `start` is the first
+    /// key with that prefix and `end` is the last.
+    fn compact_column_range(&self, column: DBColumn, start: &[u8], end: &[u8])
+        -> Result<(), Error>;
+
     /// Compact a default set of columns that are likely to free substantial space.
     fn compact(&self) -> Result<(), Error> {
-        // Compact state and block related columns as they are likely to have the most churn,
-        // i.e. entries being created and deleted.
-        for column in [
-            DBColumn::BeaconState,
-            DBColumn::BeaconStateSummary,
-            DBColumn::BeaconBlock,
-        ] {
+        for column in compaction::COMPACTION_COLUMNS {
             self.compact_column(column)?;
         }
         Ok(())
@@ -113,8 +118,24 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
         Box::new(std::iter::empty())
     }
 
+    /// Iterate through the entries of `column` whose keys start with `prefix`, beginning at the
+    /// first key `>= from`.
+    fn iter_raw_entries_from(&self, column: DBColumn, prefix: &[u8], from: &[u8]) -> RawEntryIter;
+
     /// Iterate through all keys in a particular column.
     fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K>;
+
+    /// Take a consistent, point-in-time view of the entire database.
+    fn snapshot(&self) -> Box<dyn KeyValueSnapshot + '_>;
+}
+
+/// A consistent, point-in-time view of every entry in a database, unaffected by later writes.
+pub trait KeyValueSnapshot {
+    /// Call `f` with the raw key and value of every entry, in key order.
+    fn for_each_entry(
+        &self,
+        f: &mut dyn FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    ) -> Result<(), Error>;
 }
 
 pub trait Key: Sized + 'static {
@@ -274,6 +295,27 @@ pub enum DBColumn {
     BeaconHistoricalSummaries,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
+    /// For the slasher's tables, when the slasher is configured to use the beacon node's database.
+    #[strum(serialize = "sla")]
+    Slasher,
+    /// For the proposer preparations and validator registrations supplied by validator clients.
+    #[strum(serialize = "ppr")]
+    ProposerPreparations,
+    /// For the block hashes of execution payloads which the execution engine has verified.
+    #[strum(serialize = "vpl")]
+    VerifiedPayloads,
+    /// For the attestation effectiveness history of validators monitored by the validator monitor.
+    #[strum(serialize = "aef")]
+    AttestationEffectiveness,
+    /// For the proposer and committee shufflings of the current and next epochs.
+    #[strum(serialize = "shc")]
+    ShufflingCaches,
+    /// For the arrival times of gossip messages recorded over recent slots.
+    #[strum(serialize = "atl")]
+    ArrivalTimeliness,
+    /// For the operations queued for re-broadcast once the next fork is reached.
+    #[strum(serialize = "frb")]
+    ForkRebroadcast,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -297,6 +339,7 @@ impl DBColumn {
     pub fn key_size(self) -> usize {
         match self {
             Self::OverflowLRUCache => 33, // DEPRECATED
+            Self::Slasher => 0,           // Variable-length keys, use the raw iterators.
             Self::BeaconMeta
             | Self::BeaconBlock
             | Self::BeaconState
@@ -311,7 +354,13 @@ impl DBColumn {
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
-            | Self::OptimisticTransitionBlock => 32,
+            | Self::OptimisticTransitionBlock
+            | Self::ProposerPreparations
+            | Self::VerifiedPayloads
+            | Self::AttestationEffectiveness
+            | Self::ShufflingCaches
+            | Self::ArrivalTimeliness
+            | Self::ForkRebroadcast => 32,
             Self::BeaconBlockRoots
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
@@ -403,6 +452,29 @@ mod tests {
         test_impl(store);
     }
 
+    fn raw_entries_from_impl(store: impl ItemStore<MinimalEthSpec>) {
+        let column = DBColumn::Slasher;
+        for key in [&[1, 0][..], &[1, 1], &[1, 2], &[2, 0]] {
+            store.put_bytes(column.into(), key, key).unwrap();
+        }
+
+        let entries = store
+            .iter_raw_entries_from(column, &[1], &[1, 1])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![(vec![1, 1], vec![1, 1]), (vec![1, 2], vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn raw_entries_from() {
+        let dir = tempdir().unwrap();
+        raw_entries_from_impl(LevelDB::open(dir.path()).unwrap());
+        raw_entries_from_impl(MemoryStore::open());
+    }
+
     #[test]
     fn exists() {
         let store = MemoryStore::<MinimalEthSpec>::open();
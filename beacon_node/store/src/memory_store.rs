@@ -1,6 +1,6 @@
 use crate::{
     get_key_for_col, leveldb_store::BytesKey, ColumnIter, ColumnKeyIter, DBColumn, Error,
-    ItemStore, Key, KeyValueStore, KeyValueStoreOp,
+    ItemStore, Key, KeyValueSnapshot, KeyValueStore, KeyValueStoreOp, RawEntryIter,
 };
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use std::collections::BTreeMap;
@@ -100,6 +100,20 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
         }))
     }
 
+    fn iter_raw_entries_from(&self, column: DBColumn, prefix: &[u8], from: &[u8]) -> RawEntryIter {
+        // Copy the entries out for the same reason as `iter_column_from`.
+        let prefix_key = get_key_for_col(column.as_str(), prefix);
+        let start_key = BytesKey::from_vec(get_key_for_col(column.as_str(), from));
+        let entries = self
+            .db
+            .read()
+            .range(start_key..)
+            .take_while(|(k, _)| k.as_bytes().starts_with(&prefix_key))
+            .map(|(k, v)| Ok((k.as_bytes()[column.as_bytes().len()..].to_vec(), v.clone())))
+            .collect::<Vec<_>>();
+        Box::new(entries.into_iter())
+    }
+
     fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
         Box::new(self.iter_column(column).map(|res| res.map(|(k, _)| k)))
     }
@@ -111,6 +125,34 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
     fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
         Ok(())
     }
+
+    fn compact_column_range(
+        &self,
+        _column: DBColumn,
+        _start: &[u8],
+        _end: &[u8],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Box<dyn KeyValueSnapshot + '_> {
+        Box::new(MemorySnapshot(self.db.read().clone()))
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for MemoryStore<E> {}
+
+/// A copy of the entire database.
+struct MemorySnapshot(DBMap);
+
+impl KeyValueSnapshot for MemorySnapshot {
+    fn for_each_entry(
+        &self,
+        f: &mut dyn FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        for (key, value) in &self.0 {
+            f(key.as_bytes(), value)?;
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use crate::{DBColumn, Error, StoreItem};
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
-use types::{Checkpoint, Hash256, Slot};
+use types::{Checkpoint, Epoch, Hash256, Slot};
 
 pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(21);
 
@@ -16,6 +16,7 @@ pub const PRUNING_CHECKPOINT_KEY: Hash256 = Hash256::repeat_byte(3);
 pub const COMPACTION_TIMESTAMP_KEY: Hash256 = Hash256::repeat_byte(4);
 pub const ANCHOR_INFO_KEY: Hash256 = Hash256::repeat_byte(5);
 pub const BLOB_INFO_KEY: Hash256 = Hash256::repeat_byte(6);
+pub const PENDING_FORK_PRUNES_KEY: Hash256 = Hash256::repeat_byte(7);
 
 /// State upper limit value used to indicate that a node is not storing historic states.
 pub const STATE_UPPER_LIMIT_NO_RETAIN: Slot = Slot::new(u64::MAX);
@@ -84,6 +85,34 @@ impl StoreItem for CompactionTimestamp {
     }
 }
 
+/// Blocks of a fork which was abandoned when `abandoned_epoch` was finalized, which are retained
+/// until `prune_forks_after_epochs` more epochs have been finalized.
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
+pub struct PendingForkPrune {
+    pub abandoned_epoch: Epoch,
+    pub block_roots: Vec<Hash256>,
+}
+
+/// Abandoned forks which are yet to be pruned.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Encode, Decode)]
+pub struct PendingForkPrunes {
+    pub pending: Vec<PendingForkPrune>,
+}
+
+impl StoreItem for PendingForkPrunes {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
 /// Database parameters relevant to weak subjectivity sync.
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct AnchorInfo {
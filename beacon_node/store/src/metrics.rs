@@ -50,6 +50,38 @@ lazy_static! {
         "Total number of deletions from the hot on-disk DB",
         &["col"],
     );
+    /*
+     * Compaction
+     */
+    pub static ref COMPACTION_IN_PROGRESS: Result<IntGauge> = try_create_int_gauge(
+        "store_compaction_in_progress",
+        "Set to 1 while a background compaction of the hot DB is running"
+    );
+    pub static ref COMPACTION_CHUNKS_COMPLETED: Result<IntGauge> = try_create_int_gauge(
+        "store_compaction_chunks_completed",
+        "Number of chunks completed by the current or most recent compaction"
+    );
+    pub static ref COMPACTION_CHUNKS_TOTAL: Result<IntGauge> = try_create_int_gauge(
+        "store_compaction_chunks_total",
+        "Number of chunks in the current or most recent compaction"
+    );
+    pub static ref COMPACTIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "store_compactions_total",
+        "Number of compactions started, by trigger",
+        &["trigger"],
+    );
+    pub static ref COMPACTION_TIMES: Result<Histogram> = try_create_histogram_with_buckets(
+        "store_compaction_seconds",
+        "Time taken to compact the hot DB",
+        Ok(vec![10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 3600.0]),
+    );
+    /*
+     * Retention
+     */
+    pub static ref RETENTION_ACTIVE: Result<IntGauge> = try_create_int_gauge(
+        "store_retention_active",
+        "Set to 1 while the DB exceeds its target size and is being pruned more aggressively"
+    );
     /*
      * Beacon State
      */
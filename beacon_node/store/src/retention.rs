@@ -0,0 +1,152 @@
+//! Tracking of the database's disk usage, and a retention policy which prunes more aggressively
+//! when the database grows beyond `StoreConfig::target_disk_usage`.
+//!
+//! While the policy is active, blobs are pruned right up to the data availability boundary
+//! (ignoring `blob_prune_margin_epochs`) and execution payloads are pruned on finalization, even if
+//! payload pruning is disabled. The policy remains active until usage falls below
+//! `RETENTION_RELEASE_PERCENT` of the target, so that it doesn't flap around the threshold.
+use crate::hot_cold_store::HotColdDB;
+use crate::{metrics, ItemStore};
+use serde::{Deserialize, Serialize};
+use slog::{info, warn};
+use std::collections::VecDeque;
+use types::EthSpec;
+
+/// Maximum number of disk usage samples to forecast from (one week at the default interval).
+pub const MAX_DISK_USAGE_SAMPLES: usize = 7 * 24 * 6;
+
+/// Percentage of the target disk usage which usage must fall below to deactivate the policy.
+pub const RETENTION_RELEASE_PERCENT: u64 = 90;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A measurement of the size of the database, and the free space on its disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskUsageSample {
+    /// Seconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// Combined size of the hot, freezer and blobs databases.
+    pub db_bytes: u64,
+    pub disk_bytes_free: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Retention {
+    samples: VecDeque<DiskUsageSample>,
+    active: bool,
+}
+
+/// A forecast of when the database will reach its target size, or fill the disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskUsageForecast {
+    pub latest: Option<DiskUsageSample>,
+    pub target_disk_usage: Option<u64>,
+    /// Whether the retention policy is currently pruning more aggressively.
+    pub retention_active: bool,
+    /// Number of seconds covered by the samples used for the forecast.
+    pub window_secs: u64,
+    /// Average growth of the database over the window, which may be negative.
+    pub growth_bytes_per_day: Option<i64>,
+    /// `None` if there isn't enough data, the database isn't growing, or no target is set.
+    pub days_until_target: Option<f64>,
+    /// `None` if there isn't enough data or the database isn't growing.
+    pub days_until_full: Option<f64>,
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Record a disk usage sample, and activate or deactivate the retention policy accordingly.
+    ///
+    /// Returns `true` if the policy was activated by this sample.
+    pub fn record_disk_usage(&self, sample: DiskUsageSample) -> bool {
+        let target = self.config.target_disk_usage;
+        let mut retention = self.retention.write();
+
+        retention.samples.push_back(sample);
+        while retention.samples.len() > MAX_DISK_USAGE_SAMPLES {
+            retention.samples.pop_front();
+        }
+
+        let was_active = retention.active;
+        retention.active = match target {
+            Some(target) if sample.db_bytes > target => true,
+            Some(target) if was_active => {
+                sample.db_bytes >= target / 100 * RETENTION_RELEASE_PERCENT
+            }
+            _ => false,
+        };
+        metrics::set_gauge(&metrics::RETENTION_ACTIVE, retention.active as i64);
+
+        if retention.active && !was_active {
+            warn!(
+                self.log,
+                "Database exceeds target size";
+                "msg" => "pruning blobs to the data availability boundary and pruning payloads",
+                "db_bytes" => sample.db_bytes,
+                "target_bytes" => target,
+            );
+        } else if was_active && !retention.active {
+            info!(
+                self.log,
+                "Database below target size";
+                "db_bytes" => sample.db_bytes,
+                "target_bytes" => target,
+            );
+        }
+        retention.active && !was_active
+    }
+
+    /// Returns `true` if the retention policy is active.
+    pub fn retention_active(&self) -> bool {
+        self.retention.read().active
+    }
+
+    /// The blob pruning margin, which is reduced to 0 while the retention policy is active.
+    pub fn effective_blob_prune_margin_epochs(&self) -> u64 {
+        if self.retention_active() {
+            0
+        } else {
+            self.config.blob_prune_margin_epochs
+        }
+    }
+
+    /// Whether to prune payloads, which is forced while the retention policy is active.
+    pub fn effective_prune_payloads(&self) -> bool {
+        self.config.prune_payloads || self.retention_active()
+    }
+
+    /// Forecast disk usage from the growth observed between the oldest and latest samples.
+    pub fn disk_usage_forecast(&self) -> DiskUsageForecast {
+        let target_disk_usage = self.config.target_disk_usage;
+        let retention = self.retention.read();
+        let latest = retention.samples.back().copied();
+
+        let mut forecast = DiskUsageForecast {
+            latest,
+            target_disk_usage,
+            retention_active: retention.active,
+            window_secs: 0,
+            growth_bytes_per_day: None,
+            days_until_target: None,
+            days_until_full: None,
+        };
+
+        let (Some(oldest), Some(latest)) = (retention.samples.front(), latest) else {
+            return forecast;
+        };
+        forecast.window_secs = latest.timestamp.saturating_sub(oldest.timestamp);
+        if forecast.window_secs == 0 {
+            return forecast;
+        }
+
+        let growth = latest.db_bytes as f64 - oldest.db_bytes as f64;
+        let growth_per_day = growth * SECONDS_PER_DAY / forecast.window_secs as f64;
+        forecast.growth_bytes_per_day = Some(growth_per_day as i64);
+
+        if growth_per_day > 0.0 {
+            forecast.days_until_full = Some(latest.disk_bytes_free as f64 / growth_per_day);
+            forecast.days_until_target = target_disk_usage
+                .map(|target| target.saturating_sub(latest.db_bytes) as f64 / growth_per_day);
+        }
+        forecast
+    }
+}
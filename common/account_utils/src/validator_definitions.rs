@@ -80,6 +80,11 @@ pub struct Web3SignerDefinition {
     /// An empty password will be used if this is omitted.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_identity_password: Option<String>,
+
+    /// A URI which must be a subject alternative name of the certificate of the server, such as a
+    /// SPIFFE ID like `spiffe://example.org/web3signer`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_identity: Option<String>,
 }
 
 /// Defines how the validator client should attempt to sign messages for this validator.
@@ -1,7 +1,7 @@
 use ethabi::{Contract, Token};
 use ssz::{Decode, DecodeError as SszDecodeError, Encode};
 use tree_hash::TreeHash;
-use types::{DepositData, Hash256, PublicKeyBytes, SignatureBytes};
+use types::{Address, DepositData, Hash256, PublicKeyBytes, SignatureBytes};
 
 pub use ethabi::Error;
 
@@ -34,6 +34,19 @@ pub mod testnet {
         include_bytes!("../contracts/v0.12.1_testnet_validator_registration.bytecode");
 }
 
+/// The address of the EIP-7002 withdrawal request predeploy.
+pub const WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS: Address = Address([
+    0x00, 0x00, 0x09, 0x61, 0xef, 0x48, 0x0e, 0xb5, 0x5e, 0x80, 0xd1, 0x9a, 0xd8, 0x35, 0x79, 0xa6,
+    0x4c, 0x00, 0x70, 0x02,
+]);
+/// The address of the EIP-7251 consolidation request predeploy.
+pub const CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS: Address = Address([
+    0x00, 0x00, 0xbb, 0xdd, 0xc7, 0xce, 0x48, 0x86, 0x42, 0xfb, 0x57, 0x9f, 0x8b, 0x00, 0xf3, 0xa5,
+    0x90, 0x00, 0x72, 0x51,
+]);
+pub const WITHDRAWAL_REQUEST_DATA_LEN: usize = 56;
+pub const CONSOLIDATION_REQUEST_DATA_LEN: usize = 96;
+
 pub fn encode_eth1_tx_data(deposit_data: &DepositData) -> Result<Vec<u8>, Error> {
     let params = vec![
         Token::Bytes(deposit_data.pubkey.as_ssz_bytes()),
@@ -49,6 +62,29 @@ pub fn encode_eth1_tx_data(deposit_data: &DepositData) -> Result<Vec<u8>, Error>
     function.encode_input(&params)
 }
 
+/// Returns the calldata of a transaction to the withdrawal request predeploy, which is the
+/// validator public key followed by the big-endian amount in gwei.
+///
+/// An `amount` of zero requests a full exit of the validator.
+pub fn encode_withdrawal_request_tx_data(pubkey: &PublicKeyBytes, amount: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(WITHDRAWAL_REQUEST_DATA_LEN);
+    data.extend_from_slice(pubkey.as_serialized());
+    data.extend_from_slice(&amount.to_be_bytes());
+    data
+}
+
+/// Returns the calldata of a transaction to the consolidation request predeploy, which is the
+/// source public key followed by the target public key.
+pub fn encode_consolidation_request_tx_data(
+    source_pubkey: &PublicKeyBytes,
+    target_pubkey: &PublicKeyBytes,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(CONSOLIDATION_REQUEST_DATA_LEN);
+    data.extend_from_slice(source_pubkey.as_serialized());
+    data.extend_from_slice(target_pubkey.as_serialized());
+    data
+}
+
 pub fn decode_eth1_tx_data(
     bytes: &[u8],
     amount: u64,
@@ -127,4 +163,25 @@ mod tests {
             "decode root should match original root"
         );
     }
+
+    #[test]
+    fn withdrawal_request_tx_data() {
+        let pubkey = PublicKeyBytes::from(generate_deterministic_keypair(42).pk);
+
+        let data = encode_withdrawal_request_tx_data(&pubkey, 1_000_000_000);
+        assert_eq!(data.len(), WITHDRAWAL_REQUEST_DATA_LEN);
+        assert_eq!(&data[..48], pubkey.as_serialized());
+        assert_eq!(&data[48..], &1_000_000_000u64.to_be_bytes());
+    }
+
+    #[test]
+    fn consolidation_request_tx_data() {
+        let source = PublicKeyBytes::from(generate_deterministic_keypair(1).pk);
+        let target = PublicKeyBytes::from(generate_deterministic_keypair(2).pk);
+
+        let data = encode_consolidation_request_tx_data(&source, &target);
+        assert_eq!(data.len(), CONSOLIDATION_REQUEST_DATA_LEN);
+        assert_eq!(&data[..48], source.as_serialized());
+        assert_eq!(&data[48..], target.as_serialized());
+    }
 }
@@ -28,10 +28,10 @@ use reqwest::{
 pub use reqwest::{StatusCode, Url};
 pub use sensitive_url::{SensitiveError, SensitiveUrl};
 use serde::{de::DeserializeOwned, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
 use std::fmt;
 use std::future::Future;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use store::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedResponse;
 
@@ -152,6 +152,19 @@ impl Timeouts {
     }
 }
 
+/// Returns headers which authorize requests to a beacon node using the API token in the file at
+/// `path`, for use as the default headers of a `reqwest::Client`.
+pub fn api_token_headers(path: &Path) -> Result<HeaderMap, String> {
+    let token = std::fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read API token file {:?}: {}", path, e))?;
+    let mut value = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+        .map_err(|_| format!("Invalid API token in {:?}", path))?;
+    value.set_sensitive(true);
+    let mut headers = HeaderMap::new();
+    headers.insert(reqwest::header::AUTHORIZATION, value);
+    Ok(headers)
+}
+
 /// A wrapper around `reqwest::Client` which provides convenience methods for interfacing with a
 /// Lighthouse Beacon Node HTTP server (`http_api`).
 #[derive(Clone)]
@@ -526,6 +539,34 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET beacon/states/{state_id}/finality_checkpoints`, in SSZ format.
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_finality_checkpoints_ssz(
+        &self,
+        state_id: StateId,
+    ) -> Result<Option<FinalityCheckpointsData>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("finality_checkpoints");
+
+        match self
+            .get_response(path, |b| b.accept(Accept::Ssz))
+            .await
+            .optional()?
+        {
+            Some(response) => FinalityCheckpointsData::from_ssz_bytes(&response.bytes().await?)
+                .map(Some)
+                .map_err(Error::InvalidSsz),
+            None => Ok(None),
+        }
+    }
+
     /// `GET beacon/states/{state_id}/validator_balances?id`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -638,6 +679,60 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET beacon/states/{state_id}/validators?id,status,start_index,end_index`
+    ///
+    /// Returns the validators with indices in `start_index..end_index`. The range parameters are
+    /// a Lighthouse extension.
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validators_in_range(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+        start_index: Option<u64>,
+        end_index: Option<u64>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("validators");
+
+        if let Some(ids) = ids {
+            let id_string = ids
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("id", &id_string);
+        }
+
+        if let Some(statuses) = statuses {
+            let status_string = statuses
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("status", &status_string);
+        }
+
+        if let Some(start_index) = start_index {
+            path.query_pairs_mut()
+                .append_pair("start_index", &start_index.to_string());
+        }
+
+        if let Some(end_index) = end_index {
+            path.query_pairs_mut()
+                .append_pair("end_index", &end_index.to_string());
+        }
+
+        self.get_opt(path).await
+    }
+
     /// `POST beacon/states/{state_id}/validators`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -656,7 +751,12 @@ impl BeaconNodeHttpClient {
             .push(&state_id.to_string())
             .push("validators");
 
-        let request = ValidatorsRequestBody { ids, statuses };
+        let request = ValidatorsRequestBody {
+            ids,
+            statuses,
+            start_index: None,
+            end_index: None,
+        };
 
         self.post_with_opt_response(path, &request).await
     }
@@ -784,6 +884,29 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET beacon/light_client/updates?start_period,count`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_light_client_updates<E: EthSpec>(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Option<Vec<ForkVersionedResponse<LightClientUpdate<E>>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("updates");
+
+        path.query_pairs_mut()
+            .append_pair("start_period", &start_period.to_string())
+            .append_pair("count", &count.to_string());
+
+        self.get_opt(path).await
+    }
+
     /// `GET beacon/light_client/optimistic_update`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -2561,16 +2684,35 @@ impl BeaconNodeHttpClient {
             .join(",");
         path.query_pairs_mut().append_pair("topics", &topic_string);
 
+        // Messages may be split across or combined within the chunks of the response body, so
+        // only parse complete messages. Comment-only messages (e.g. keep-alives) are skipped.
+        let mut buffer = Vec::new();
         Ok(self
             .client
             .get(path)
             .send()
             .await?
             .bytes_stream()
-            .map(|next| match next {
-                Ok(bytes) => EventKind::from_sse_bytes(bytes.as_ref()),
-                Err(e) => Err(Error::HttpClient(e.into())),
-            }))
+            .map(move |next| match next {
+                Ok(bytes) => {
+                    buffer.extend_from_slice(bytes.as_ref());
+                    let mut events = vec![];
+                    while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                        let message = buffer.drain(..end + 2).collect::<Vec<_>>();
+                        let message = &message[..end];
+                        if message
+                            .split(|byte| *byte == b'\n')
+                            .all(|line| line.is_empty() || line.starts_with(b":"))
+                        {
+                            continue;
+                        }
+                        events.push(EventKind::from_sse_bytes(message));
+                    }
+                    events
+                }
+                Err(e) => vec![Err(Error::HttpClient(e.into()))],
+            })
+            .flat_map(futures::stream::iter))
     }
 
     /// `POST validator/duties/sync/{epoch}`
@@ -1,35 +1,65 @@
 //! This module contains endpoints that are non-standard and only available on Lighthouse servers.
 
+mod attestation_inclusion;
 mod attestation_performance;
 pub mod attestation_rewards;
+mod block_dry_run;
 mod block_packing_efficiency;
 mod block_rewards;
+mod block_summaries;
+mod execution_requests;
+mod reorg_captures;
 mod standard_block_rewards;
 mod sync_committee_rewards;
+mod timeliness;
+mod validator_performance;
+mod validator_queues;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Accept, BlockId, Checkpoint, DepositTreeSnapshot, Epoch, EthSpec, ExecutionBlockHash,
+        ExecutionOptimisticFinalizedResponse, FinalizedExecutionBlock, GenericResponse,
+        HistoricalBlockProof, SignedValidatorRegistrationData, StateId, StateProof, ValidatorId,
     },
-    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
+    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot, Url,
 };
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
-use ssz::four_byte_option_impl;
+use ssz::{four_byte_option_impl, Decode};
 use ssz_derive::{Decode, Encode};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use store::backup::BackupSummary;
+use store::compaction::CompactionProgress;
+use store::retention::DiskUsageForecast;
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
+pub use attestation_inclusion::{AttestationInclusion, AttestationInclusionBlock};
 pub use attestation_performance::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
 };
 pub use attestation_rewards::StandardAttestationRewards;
+pub use block_dry_run::{BlockDryRun, DryRunBlock};
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
-pub use standard_block_rewards::StandardBlockReward;
+pub use block_summaries::{BlockSummariesQuery, BlockSummary};
+pub use execution_requests::{EpochExecutionRequests, ExecutionRequests};
+pub use lighthouse_network::{
+    types::SyncState, DiversityShare, GossipTrace, PeerDiversity, PeerInfo, PublishedGossipTrace,
+    ReceivedGossipTrace,
+};
+pub use reorg_captures::{ReorgCapture, ReorgCaptureBlock, ReorgCaptureSummary};
+pub use standard_block_rewards::{BlockRangeReward, BlockRangeRewardsQuery, StandardBlockReward};
+pub use state_processing::transition_report::{
+    EpochReport, StageReport, StateDiff, TransitionReport,
+};
 pub use sync_committee_rewards::SyncCommitteeReward;
+pub use timeliness::{ArrivalDeciles, SlotTimeliness, TimelinessQuery, ARRIVAL_DECILES};
+pub use validator_performance::{ValidatorEpochPerformance, ValidatorPerformanceQuery};
+pub use validator_queues::{ValidatorQueue, ValidatorQueues};
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -297,6 +327,96 @@ impl Health {
     }
 }
 
+/// Reports on the health of a beacon node, including whether it is ready to serve validators.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealth {
+    #[serde(flatten)]
+    pub health: Health,
+    pub readiness: Readiness,
+}
+
+/// Query parameters for `GET lighthouse/health` on the beacon node.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthQuery {
+    /// Respond with a 503 status code when the node is `not_ready`.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Whether a beacon node is able to serve validators, in a form suitable for load balancers and
+/// liveness/readiness probes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Readiness {
+    /// The aggregate verdict over all of the checks below.
+    pub status: ReadinessStatus,
+    /// The reasons that the node is not `ready`, empty if it is.
+    pub reasons: Vec<DegradationReason>,
+    /// The number of slots between the head and the wall-clock slot, if the chain is initialized.
+    pub sync_distance: Option<Slot>,
+    pub el_offline: bool,
+    pub is_optimistic: bool,
+    pub connected_peers: usize,
+    pub target_peers: usize,
+    /// Free space on the disk holding the data directory.
+    pub disk_bytes_free: u64,
+    /// An estimate of how far the local clock is ahead of the network (negative if it is behind).
+    ///
+    /// This is the earliest arrival time of a recent block relative to the start of its slot, so
+    /// it includes network latency and is `None` if no blocks have been received recently.
+    pub clock_offset_ms: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    /// All checks passed.
+    Ready,
+    /// The node can serve validators but performance may suffer.
+    Degraded,
+    /// The node cannot serve validators.
+    NotReady,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationReason {
+    ChainUninitialized,
+    Syncing,
+    ExecutionLayerOffline,
+    OptimisticHead,
+    LowPeerCount,
+    LowDiskSpace,
+    ClockDrift,
+}
+
+impl DegradationReason {
+    /// Returns `true` if the node cannot serve validators while this reason applies.
+    pub fn is_critical(&self) -> bool {
+        match self {
+            DegradationReason::ChainUninitialized
+            | DegradationReason::Syncing
+            | DegradationReason::ExecutionLayerOffline
+            | DegradationReason::OptimisticHead => true,
+            DegradationReason::LowPeerCount
+            | DegradationReason::LowDiskSpace
+            | DegradationReason::ClockDrift => false,
+        }
+    }
+}
+
+impl ReadinessStatus {
+    /// Returns the aggregate status for the given `reasons`.
+    pub fn from_reasons(reasons: &[DegradationReason]) -> Self {
+        if reasons.iter().any(DegradationReason::is_critical) {
+            ReadinessStatus::NotReady
+        } else if !reasons.is_empty() {
+            ReadinessStatus::Degraded
+        } else {
+            ReadinessStatus::Ready
+        }
+    }
+}
+
 /// Indicates how up-to-date the Eth1 caches are.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Eth1SyncStatusData {
@@ -363,11 +483,162 @@ pub struct DatabaseInfo {
     pub split: Split,
     pub anchor: Option<AnchorInfo>,
     pub blob_info: BlobInfo,
+    pub compaction: CompactionProgress,
+}
+
+/// The proposer re-org settings currently in use by the beacon node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposerReOrgConfig {
+    pub enabled: bool,
+    pub head_threshold: u64,
+    pub parent_threshold: u64,
+    pub max_epochs_since_finalization: Epoch,
+    pub cutoff_millis: u64,
+    pub disallowed_offsets: Vec<u64>,
+}
+
+/// A validator registration held by the beacon node, along with when it was last forwarded to the
+/// builder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedValidatorRegistration {
+    pub registration: SignedValidatorRegistrationData,
+    /// The epoch in which a validator client last submitted the registration.
+    pub last_seen_epoch: Epoch,
+    /// The epoch in which the registration was last accepted by the builder, if it has been.
+    pub last_sent_epoch: Option<Epoch>,
+}
+
+/// A request to write a backup of the database to `path` on the beacon node's filesystem.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseBackupRequest {
+    /// The file to create, which must not already exist.
+    pub path: PathBuf,
+}
+
+/// An update to the local ENR, applied without restarting discovery.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnrUpdateRequest {
+    /// Custom fields to set, with 0x-prefixed hex values. Fields maintained by Lighthouse such as
+    /// `eth2` and `ip` can't be set.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// The externally reachable UDP socket, if it has changed.
+    #[serde(default)]
+    pub udp_socket: Option<SocketAddr>,
+    /// Increment the sequence number even if no field has changed.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// An update to the proposer re-org settings. Fields which are `None` are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProposerReOrgConfigUpdate {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub head_threshold: Option<u64>,
+    #[serde(default)]
+    pub parent_threshold: Option<u64>,
+    #[serde(default)]
+    pub max_epochs_since_finalization: Option<Epoch>,
+    #[serde(default)]
+    pub cutoff_millis: Option<u64>,
+    #[serde(default)]
+    pub disallowed_offsets: Option<Vec<u64>>,
+}
+
+/// The level of a log target which has been overridden at runtime, as returned by
+/// `GET lighthouse/logging`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogLevel {
+    /// A module path such as `network::sync`, which includes its sub-modules.
+    pub target: String,
+    pub level: String,
+}
+
+/// An update to the level of a log target. If `level` is `None` the target reverts to the
+/// configured level of each log output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogLevelUpdate {
+    pub target: String,
+    #[serde(default)]
+    pub level: Option<String>,
+}
+
+/// A detailed view of the fork choice proto-array, as returned by
+/// `GET lighthouse/fork_choice/detail`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceDetail {
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub head_root: Hash256,
+    pub proposer_boost_root: Hash256,
+    pub nodes: Vec<ForkChoiceDetailNode>,
+    pub head_candidates: Vec<ForkChoiceHeadCandidate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceDetailNode {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub parent_root: Option<Hash256>,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub unrealized_justified_checkpoint: Option<Checkpoint>,
+    pub unrealized_finalized_checkpoint: Option<Checkpoint>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub weight: u64,
+    pub validity: String,
+    pub execution_block_hash: Option<Hash256>,
+    pub best_child: Option<Hash256>,
+    pub best_descendant: Option<Hash256>,
+    pub viable_for_head: bool,
+}
+
+/// A leaf of the proto-array which is viable for the head, along with the latest messages that
+/// vote for it directly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceHeadCandidate {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub weight: u64,
+    pub latest_messages: Vec<ForkChoiceLatestMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceLatestMessage {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub epoch: Epoch,
+}
+
+/// A block whose execution payload has not yet been verified by the execution engine, as returned
+/// by `GET lighthouse/fork_choice/optimistic_blocks`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimisticBlock {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    pub execution_block_hash: ExecutionBlockHash,
+}
+
+/// The execution status of a block after its payload was sent to the execution engine again by
+/// `POST lighthouse/fork_choice/optimistic_blocks/{execution_block_hash}/reverify`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadReverification {
+    pub block_root: Hash256,
+    pub execution_block_hash: ExecutionBlockHash,
+    /// One of `valid`, `optimistic` or `invalid`.
+    pub validity: String,
 }
 
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
-    pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
+    pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<BeaconNodeHealth>, Error> {
         let mut path = self.server.full.clone();
 
         path.path_segments_mut()
@@ -378,6 +649,24 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/health?strict=true`
+    ///
+    /// Returns an error with a 503 status code if the node is not ready to serve validators.
+    pub async fn get_lighthouse_health_strict(
+        &self,
+    ) -> Result<GenericResponse<BeaconNodeHealth>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("health");
+
+        path.query_pairs_mut().append_pair("strict", "true");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/syncing`
     pub async fn get_lighthouse_syncing(&self) -> Result<GenericResponse<SyncState>, Error> {
         let mut path = self.server.full.clone();
@@ -410,6 +699,54 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/fork_choice/detail`
+    pub async fn get_lighthouse_fork_choice_detail(
+        &self,
+    ) -> Result<GenericResponse<ForkChoiceDetail>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("detail");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/fork_choice/optimistic_blocks`
+    pub async fn get_lighthouse_fork_choice_optimistic_blocks(
+        &self,
+    ) -> Result<GenericResponse<Vec<OptimisticBlock>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("optimistic_blocks");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/fork_choice/optimistic_blocks/{execution_block_hash}/reverify`
+    pub async fn post_lighthouse_fork_choice_optimistic_blocks_reverify(
+        &self,
+        execution_block_hash: ExecutionBlockHash,
+    ) -> Result<GenericResponse<PayloadReverification>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("optimistic_blocks")
+            .push(&format!("{execution_block_hash:?}"))
+            .push("reverify");
+
+        self.post_with_response(path, &()).await
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/global`
     pub async fn get_lighthouse_validator_inclusion_global(
         &self,
@@ -445,6 +782,25 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/validators/{validator_id}/attestation_inclusion/{epoch}`
+    pub async fn get_lighthouse_validator_attestation_inclusion(
+        &self,
+        validator_id: ValidatorId,
+        epoch: Epoch,
+    ) -> Result<GenericResponse<AttestationInclusion>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_id.to_string())
+            .push("attestation_inclusion")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/eth1/syncing`
     pub async fn get_lighthouse_eth1_syncing(
         &self,
@@ -502,6 +858,41 @@ impl BeaconNodeHttpClient {
         self.get_opt::<(), _>(path).await.map(|opt| opt.is_some())
     }
 
+    /// `GET lighthouse/validator_queues`
+    pub async fn get_lighthouse_validator_queues(
+        &self,
+    ) -> Result<GenericResponse<ValidatorQueues>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_queues");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/ui/validator_performance?start_epoch,end_epoch`
+    pub async fn get_lighthouse_ui_validator_performance(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<Vec<ValidatorEpochPerformance>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("validator_performance");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/database/info`
     pub async fn get_lighthouse_database_info(&self) -> Result<DatabaseInfo, Error> {
         let mut path = self.server.full.clone();
@@ -515,6 +906,19 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/database/forecast`
+    pub async fn get_lighthouse_database_forecast(&self) -> Result<DiskUsageForecast, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("forecast");
+
+        self.get(path).await
+    }
+
     /// `POST lighthouse/database/reconstruct`
     pub async fn post_lighthouse_database_reconstruct(&self) -> Result<String, Error> {
         let mut path = self.server.full.clone();
@@ -528,6 +932,136 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `POST lighthouse/database/compact`
+    pub async fn post_lighthouse_database_compact(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("compact");
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `GET lighthouse/reorgs`
+    pub async fn get_lighthouse_reorgs(
+        &self,
+    ) -> Result<GenericResponse<Vec<ReorgCaptureSummary>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("reorgs");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/reorgs/{id}`
+    pub async fn get_lighthouse_reorg(
+        &self,
+        id: u64,
+    ) -> Result<Option<GenericResponse<ReorgCapture>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("reorgs")
+            .push(&id.to_string());
+
+        self.get_opt(path).await
+    }
+
+    /// `POST lighthouse/database/backup`
+    pub async fn post_lighthouse_database_backup(
+        &self,
+        request: &DatabaseBackupRequest,
+    ) -> Result<BackupSummary, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backup");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/proposer_re_org_config`
+    pub async fn get_lighthouse_proposer_re_org_config(
+        &self,
+    ) -> Result<GenericResponse<ProposerReOrgConfig>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proposer_re_org_config");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/proposer_re_org_config`
+    pub async fn post_lighthouse_proposer_re_org_config(
+        &self,
+        update: &ProposerReOrgConfigUpdate,
+    ) -> Result<GenericResponse<ProposerReOrgConfig>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proposer_re_org_config");
+
+        self.post_with_response(path, update).await
+    }
+
+    /// `GET lighthouse/logging`
+    pub async fn get_lighthouse_logging(&self) -> Result<GenericResponse<Vec<LogLevel>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/logging`
+    pub async fn post_lighthouse_logging(
+        &self,
+        update: &LogLevelUpdate,
+    ) -> Result<GenericResponse<Vec<LogLevel>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.post_with_response(path, update).await
+    }
+
+    /// `GET lighthouse/builder/registrations`
+    pub async fn get_lighthouse_builder_registrations(
+        &self,
+    ) -> Result<GenericResponse<Vec<CachedValidatorRegistration>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("registrations");
+
+        self.get(path).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -553,6 +1087,192 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/blocks?start_slot,end_slot
+    pub async fn get_lighthouse_blocks(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<Vec<BlockSummary>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("blocks");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &start_slot.to_string())
+            .append_pair("end_slot", &end_slot.to_string());
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/blocks/dry_run`
+    pub async fn post_lighthouse_blocks_dry_run<E: EthSpec>(
+        &self,
+        block: &DryRunBlock<E>,
+    ) -> Result<BlockDryRun, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("blocks")
+            .push("dry_run");
+
+        self.post_with_response(path, block).await
+    }
+
+    /// `GET lighthouse/blocks/{block_id}/execution_requests`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_block_execution_requests<E: EthSpec>(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<ExecutionRequests<E>>>, Error> {
+        let path = self.get_lighthouse_block_execution_requests_path(block_id)?;
+        self.get_opt(path).await
+    }
+
+    /// `GET lighthouse/blocks/{block_id}/execution_requests` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_block_execution_requests_ssz<E: EthSpec>(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionRequests<E>>, Error> {
+        let path = self.get_lighthouse_block_execution_requests_path(block_id)?;
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_beacon_blocks_ssz)
+            .await?
+            .map(|bytes| ExecutionRequests::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
+    fn get_lighthouse_block_execution_requests_path(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Url, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("blocks")
+            .push(&block_id.to_string())
+            .push("execution_requests");
+
+        Ok(path)
+    }
+
+    /// `GET lighthouse/execution_requests/{epoch}`
+    pub async fn get_lighthouse_execution_requests(
+        &self,
+        epoch: Epoch,
+    ) -> Result<GenericResponse<EpochExecutionRequests>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution_requests")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/network/diversity`
+    pub async fn get_lighthouse_network_diversity(
+        &self,
+    ) -> Result<GenericResponse<PeerDiversity>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("network")
+            .push("diversity");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/network/gossip_trace`
+    pub async fn get_lighthouse_network_gossip_trace(
+        &self,
+    ) -> Result<GenericResponse<GossipTrace>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("network")
+            .push("gossip_trace");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/network/enr`
+    pub async fn post_lighthouse_network_enr(
+        &self,
+        request: &EnrUpdateRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("network")
+            .push("enr");
+
+        self.post(path, request).await?;
+        Ok(())
+    }
+
+    /// `GET lighthouse/timeliness?start_slot,end_slot`
+    pub async fn get_lighthouse_timeliness(
+        &self,
+        start_slot: Option<Slot>,
+        end_slot: Option<Slot>,
+    ) -> Result<GenericResponse<Vec<SlotTimeliness>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("timeliness");
+
+        if let Some(start_slot) = start_slot {
+            path.query_pairs_mut()
+                .append_pair("start_slot", &start_slot.to_string());
+        }
+        if let Some(end_slot) = end_slot {
+            path.query_pairs_mut()
+                .append_pair("end_slot", &end_slot.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET` lighthouse/rewards/blocks?start,end
+    pub async fn get_lighthouse_rewards_blocks(
+        &self,
+        start: Slot,
+        end: Slot,
+    ) -> Result<Vec<BlockRangeReward>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("rewards")
+            .push("blocks");
+
+        path.query_pairs_mut()
+            .append_pair("start", &start.to_string())
+            .append_pair("end", &end.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/block_packing?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_block_packing(
         &self,
@@ -574,6 +1294,67 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/block_transition/{block_id}
+    pub async fn get_lighthouse_analysis_block_transition(
+        &self,
+        block_id: BlockId,
+    ) -> Result<TransitionReport, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("block_transition")
+            .push(&block_id.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET` lighthouse/historical_block_proof/{state_id}/{block_id}
+    pub async fn get_lighthouse_historical_block_proof(
+        &self,
+        state_id: StateId,
+        block_id: BlockId,
+    ) -> Result<GenericResponse<HistoricalBlockProof>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("historical_block_proof")
+            .push(&state_id.to_string())
+            .push(&block_id.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET` lighthouse/beacon/states/{state_id}/proof?gindex
+    pub async fn get_lighthouse_beacon_states_proof(
+        &self,
+        state_id: StateId,
+        gindices: &[u64],
+    ) -> Result<GenericResponse<StateProof>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("proof");
+
+        let gindices = gindices
+            .iter()
+            .map(|gindex| gindex.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        path.query_pairs_mut().append_pair("gindex", &gindices);
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
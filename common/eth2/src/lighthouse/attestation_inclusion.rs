@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Hash256, Slot};
+
+/// The canonical blocks which included the attestation of a validator in an epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationInclusion {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub epoch: Epoch,
+    /// The slot in which the validator was assigned to attest.
+    pub attestation_slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_index: u64,
+    /// The position of the validator in its committee.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_position: u64,
+    /// The blocks which included the attestation, in order of slot. Empty if the attestation has
+    /// not been included.
+    pub blocks: Vec<AttestationInclusionBlock>,
+}
+
+/// A block which included the attestation of a validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationInclusionBlock {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    /// The number of slots from the attestation slot to the block.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub inclusion_delay: u64,
+    /// The position of the attestation in the attestations of the block.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attestation_index: u64,
+    /// The position of the bit of the validator in the aggregation bits of the attestation.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub aggregation_bit: u64,
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use types::{BeaconBlock, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// A block to validate with the `/lighthouse/blocks/dry_run` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+#[serde(bound = "E: EthSpec")]
+pub enum DryRunBlock<E: EthSpec> {
+    Signed(SignedBeaconBlock<E>),
+    /// A block which is yet to be signed by its proposer, such as one returned by block
+    /// production. Only the RANDAO reveal of an unsigned block is verified.
+    Unsigned(BeaconBlock<E>),
+}
+
+/// The verdict of the `/lighthouse/blocks/dry_run` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockDryRun {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub signed: bool,
+    /// Whether the block passed both gossip and consensus validation.
+    pub valid: bool,
+    /// The reason the block would not be propagated on gossip, if any.
+    pub gossip_error: Option<String>,
+    /// The reason the block failed the state transition, if any.
+    pub consensus_error: Option<String>,
+    /// The post-state root computed by the state transition, if it completed.
+    pub computed_state_root: Option<Hash256>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use types::{ExecutionBlockHash, Graffiti, Hash256, Slot};
+
+/// A summary of a canonical block, as returned by the `/lighthouse/blocks` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockSummary {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub parent_root: Hash256,
+    pub state_root: Hash256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub graffiti: Graffiti,
+    /// The hash of the execution payload, if the block has one.
+    pub execution_block_hash: Option<ExecutionBlockHash>,
+    pub attestation_count: usize,
+    pub deposit_count: usize,
+    pub voluntary_exit_count: usize,
+    pub proposer_slashing_count: usize,
+    pub attester_slashing_count: usize,
+}
+
+/// Query parameters for the `/lighthouse/blocks` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockSummariesQuery {
+    /// Lower slot limit for blocks returned (inclusive).
+    pub start_slot: Slot,
+    /// Upper slot limit for blocks returned (inclusive).
+    pub end_slot: Slot,
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use types::{
+    DepositRequest, Epoch, EthSpec, ExecutionLayerWithdrawalRequest, SignedConsolidation,
+    VariableList,
+};
+
+/// The execution requests of a block, as returned by the
+/// `/lighthouse/blocks/{block_id}/execution_requests` endpoint.
+///
+/// Blocks prior to Electra have no requests.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+#[serde(bound = "E: EthSpec")]
+pub struct ExecutionRequests<E: EthSpec> {
+    pub deposits: VariableList<DepositRequest, E::MaxDepositRequestsPerPayload>,
+    pub withdrawals:
+        VariableList<ExecutionLayerWithdrawalRequest, E::MaxWithdrawalRequestsPerPayload>,
+    pub consolidations: VariableList<SignedConsolidation, E::MaxConsolidations>,
+}
+
+impl<E: EthSpec> Default for ExecutionRequests<E> {
+    fn default() -> Self {
+        Self {
+            deposits: VariableList::empty(),
+            withdrawals: VariableList::empty(),
+            consolidations: VariableList::empty(),
+        }
+    }
+}
+
+/// The execution requests of the canonical blocks of an epoch, as returned by the
+/// `/lighthouse/execution_requests/{epoch}` endpoint.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct EpochExecutionRequests {
+    pub epoch: Epoch,
+    /// The number of canonical blocks in the epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub blocks: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub deposits: u64,
+    /// The total Gwei deposited.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub deposit_amount: u64,
+    /// The number of withdrawal requests, including full exits.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub withdrawals: u64,
+    /// The number of withdrawal requests for a full exit, which have an amount of zero.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub full_exits: u64,
+    /// The total Gwei requested by partial withdrawals.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub withdrawal_amount: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub consolidations: u64,
+}
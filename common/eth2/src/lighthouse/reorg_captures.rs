@@ -0,0 +1,46 @@
+use proto_array::core::ProtoArray;
+use serde::{Deserialize, Serialize};
+use types::{Checkpoint, Hash256, SignedBeaconBlockHeader, Slot};
+
+/// A forensic bundle captured when the head is re-orged, for analysis after the fact.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ReorgCapture {
+    #[serde(flatten)]
+    pub summary: ReorgCaptureSummary,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    /// The blocks of the abandoned chain, from the old head back to the common ancestor.
+    pub old_chain: Vec<ReorgCaptureBlock>,
+    /// The blocks of the new chain, from the new head back to the common ancestor.
+    pub new_chain: Vec<ReorgCaptureBlock>,
+    /// Fork choice immediately after the re-org.
+    pub fork_choice: ProtoArray,
+}
+
+/// The details of a re-org, used to list the captured re-orgs.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ReorgCaptureSummary {
+    /// The time at which the re-org was captured, in milliseconds since the UNIX epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub id: u64,
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub depth: u64,
+    pub old_head_block: Hash256,
+    pub new_head_block: Hash256,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ReorgCaptureBlock {
+    pub block_root: Hash256,
+    /// `None` if the block could not be loaded from the database.
+    pub header: Option<SignedBeaconBlockHeader>,
+    /// The number of attestations included in the block.
+    pub attestations: Option<usize>,
+    /// The block's weight in fork choice.
+    pub weight: Option<u64>,
+    /// Delays from the start of the block's slot, if it was received by this node recently.
+    pub observed_delay_ms: Option<u64>,
+    pub imported_delay_ms: Option<u64>,
+    pub set_as_head_delay_ms: Option<u64>,
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot};
 
 // Details about the rewards for a single block
 // All rewards in GWei
@@ -24,3 +25,21 @@ pub struct StandardBlockReward {
     #[serde(with = "serde_utils::quoted_u64")]
     pub attester_slashings: u64,
 }
+
+/// The rewards for a block, as returned by the `/lighthouse/rewards/blocks` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockRangeReward {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    #[serde(flatten)]
+    pub reward: StandardBlockReward,
+}
+
+/// Query parameters for the `/lighthouse/rewards/blocks` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockRangeRewardsQuery {
+    /// Lower slot limit for block rewards returned (inclusive).
+    pub start: Slot,
+    /// Upper slot limit for block rewards returned (inclusive).
+    pub end: Slot,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use types::Slot;
+
+/// The number of points in `ArrivalDeciles::deciles_ms`, from the minimum to the maximum.
+pub const ARRIVAL_DECILES: usize = 11;
+
+/// The distribution of the arrival times of one kind of gossip message for a slot.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ArrivalDeciles {
+    /// The number of messages which arrived.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub count: u64,
+    /// The 0th, 10th, ..., 100th percentiles of the delay between the start of the slot and the
+    /// arrival of each message, in milliseconds. Empty if no messages arrived.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub deciles_ms: Vec<u64>,
+}
+
+/// The arrival times of the gossip messages for a slot, as returned by the
+/// `/lighthouse/timeliness` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct SlotTimeliness {
+    pub slot: Slot,
+    pub blocks: ArrivalDeciles,
+    pub aggregates: ArrivalDeciles,
+    pub blob_sidecars: ArrivalDeciles,
+}
+
+/// Query parameters for the `/lighthouse/timeliness` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TimelinessQuery {
+    /// Lower slot limit for the slots returned (inclusive).
+    pub start_slot: Option<Slot>,
+    /// Upper slot limit for the slots returned (inclusive).
+    pub end_slot: Option<Slot>,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// Query parameters for the `/lighthouse/ui/validator_performance` endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorPerformanceQuery {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+/// The outcome of the duties of a monitored validator in a single epoch.
+///
+/// Fields are `None` if the beacon node no longer holds the history for the epoch. Attestation
+/// outcomes are kept for longer than block and sync committee counts.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorEpochPerformance {
+    pub epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// Whether any attestation from the validator was included on-chain.
+    pub attestation_included: Option<bool>,
+    pub attestation_source_hit: Option<bool>,
+    pub attestation_target_hit: Option<bool>,
+    pub attestation_head_hit: Option<bool>,
+    pub attestation_inclusion_distance: Option<u64>,
+    /// The number of blocks from the validator observed by the beacon node.
+    pub blocks_proposed: Option<u64>,
+    /// The number of the validator's proposals which were missed, up to finalization.
+    pub blocks_missed: u64,
+    /// The number of sync committee messages from the validator observed by the beacon node.
+    pub sync_committee_messages: Option<u64>,
+    /// The number of blocks which included the validator's sync committee signature.
+    pub sync_signature_block_inclusions: Option<u64>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// The validator queues of the head state, as returned by the `/lighthouse/validator_queues`
+/// endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorQueues {
+    pub epoch: Epoch,
+    pub activation: ValidatorQueue,
+    pub exit: ValidatorQueue,
+    /// The queue of pending consolidations, from Electra.
+    pub consolidation: Option<ValidatorQueue>,
+    /// The number of pending partial withdrawals, from Electra.
+    pub pending_partial_withdrawals: Option<usize>,
+}
+
+/// The length of a queue, and the time a validator joining it now would wait.
+///
+/// Before Electra the churn limits are a number of validators per epoch. From Electra they are an
+/// amount of Gwei per epoch, and the activation queue is the queue of pending deposits.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorQueue {
+    /// The number of validators, or deposits or consolidations, in the queue.
+    pub length: usize,
+    /// The total Gwei in the queue.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub churn_limit: u64,
+    /// The epochs until an item added to the back of the queue now would be processed.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub wait_epochs: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub wait_seconds: u64,
+}
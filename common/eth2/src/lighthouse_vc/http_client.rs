@@ -446,6 +446,168 @@ impl ValidatorClientHttpClient {
         .await
     }
 
+    /// `POST lighthouse/validators/{validator_pubkey}/withdrawal_request`
+    pub async fn post_lighthouse_validators_withdrawal_request(
+        &self,
+        voting_pubkey: &PublicKeyBytes,
+        amount: u64,
+    ) -> Result<GenericResponse<WithdrawalRequestTransaction>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&voting_pubkey.to_string())
+            .push("withdrawal_request");
+
+        self.post(path, &CreateWithdrawalRequest { amount }).await
+    }
+
+    /// `GET lighthouse/validators/exit`
+    pub async fn get_lighthouse_validators_exit(
+        &self,
+    ) -> Result<GenericResponse<ExitSchedule>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("exit");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/validators/exit`
+    pub async fn post_lighthouse_validators_exit(
+        &self,
+        request: &ScheduleExitsRequest,
+    ) -> Result<GenericResponse<ExitSchedule>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("exit");
+
+        self.post(path, &request).await
+    }
+
+    /// `GET lighthouse/validators/exit/presigned`
+    pub async fn get_lighthouse_validators_exit_presigned(
+        &self,
+    ) -> Result<GenericResponse<PresignedExits>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("exit")
+            .push("presigned");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/validators/exit/presigned/heartbeat`
+    pub async fn post_lighthouse_validators_exit_presigned_heartbeat(
+        &self,
+    ) -> Result<GenericResponse<PresignedExits>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("exit")
+            .push("presigned")
+            .push("heartbeat");
+
+        self.post(path, &()).await
+    }
+
+    /// `POST lighthouse/validators/exit/presigned/broadcast`
+    pub async fn post_lighthouse_validators_exit_presigned_broadcast(
+        &self,
+    ) -> Result<GenericResponse<PresignedExits>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("exit")
+            .push("presigned")
+            .push("broadcast");
+
+        self.post(path, &()).await
+    }
+
+    /// `GET lighthouse/duties/simulation`
+    pub async fn get_lighthouse_duties_simulation(
+        &self,
+        epoch: Option<Epoch>,
+    ) -> Result<GenericResponse<DutySimulation>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duties")
+            .push("simulation");
+
+        if let Some(epoch) = epoch {
+            path.query_pairs_mut()
+                .append_pair("epoch", &epoch.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    fn make_validators_handoff_url(&self) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("handoff");
+        Ok(url)
+    }
+
+    /// `GET lighthouse/validators/handoff`
+    pub async fn get_lighthouse_validators_handoff(
+        &self,
+    ) -> Result<GenericResponse<ValidatorHandoffConfig>, Error> {
+        let url = self.make_validators_handoff_url()?;
+        self.get(url).await
+    }
+
+    /// `POST lighthouse/validators/handoff/export`
+    pub async fn post_lighthouse_validators_handoff_export(
+        &self,
+        req: &ValidatorHandoffRequest,
+    ) -> Result<ValidatorHandoffResponse, Error> {
+        let mut url = self.make_validators_handoff_url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("export");
+        self.post(url, req).await
+    }
+
+    /// `POST lighthouse/validators/handoff/import`
+    pub async fn post_lighthouse_validators_handoff_import(
+        &self,
+        req: &ImportValidatorHandoffRequest,
+    ) -> Result<ImportKeystoresResponse, Error> {
+        let mut url = self.make_validators_handoff_url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("import");
+        self.post(url, req).await
+    }
+
     /// `DELETE eth/v1/keystores`
     pub async fn delete_lighthouse_keystores(
         &self,
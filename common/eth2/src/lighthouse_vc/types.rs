@@ -6,7 +6,7 @@ use std::path::PathBuf;
 
 pub use crate::lighthouse::Health;
 pub use crate::lighthouse_vc::std_types::*;
-pub use crate::types::{GenericResponse, VersionData};
+pub use crate::types::{GenericResponse, ProposerData, VersionData};
 pub use types::*;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -155,6 +155,9 @@ pub struct Web3SignerValidatorRequest {
     pub client_identity_password: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_identity: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub builder_boost_factor: Option<u64>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -177,6 +180,156 @@ pub struct VoluntaryExitQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CreateWithdrawalRequest {
+    /// The amount to withdraw in gwei, or zero to exit the validator.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub amount: u64,
+}
+
+/// An unsigned transaction to the EIP-7002 withdrawal request predeploy.
+///
+/// It must be sent from the withdrawal address of the validator, with a value of at least the
+/// fee currently charged by the predeploy.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WithdrawalRequestTransaction {
+    pub to: Address,
+    #[serde(with = "serde_utils::hex_vec")]
+    pub data: Vec<u8>,
+}
+
+/// A request to exit validators gradually, a few at a time.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ScheduleExitsRequest {
+    pub pubkeys: Vec<PublicKeyBytes>,
+    /// The maximum number of voluntary exits to publish each epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub exits_per_epoch: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ScheduledExitStatus {
+    /// The voluntary exit has not been published yet.
+    Pending,
+    /// The voluntary exit was published to the beacon node during `epoch`.
+    Published { epoch: Epoch },
+    /// The voluntary exit could not be signed or was rejected by the beacon node.
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ScheduledExit {
+    pub pubkey: PublicKeyBytes,
+    #[serde(flatten)]
+    pub status: ScheduledExitStatus,
+    /// The epoch at which the validator has exited or is expected to exit, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_epoch: Option<Epoch>,
+}
+
+/// An estimate of the exit queue of the beacon chain.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ExitQueueEstimate {
+    /// The epoch at which the estimate was made.
+    pub epoch: Epoch,
+    /// The number of validators with a 32 ETH balance which may exit each epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub churn_limit: u64,
+    /// The epoch at which a voluntary exit published now is expected to take effect.
+    pub exit_epoch: Epoch,
+}
+
+/// The progress of the voluntary exits scheduled with `POST lighthouse/validators/exit`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ExitSchedule {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub exits_per_epoch: u64,
+    pub exits: Vec<ScheduledExit>,
+    pub exit_queue: Option<ExitQueueEstimate>,
+}
+
+/// The voluntary exits pre-signed by the validator client and the state of its dead man's switch.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PresignedExits {
+    pub exits: Vec<SignedVoluntaryExit>,
+    /// The epoch at which the dead man's switch was last reset.
+    pub heartbeat_epoch: Option<Epoch>,
+    /// The epoch at which the exits will be broadcast unless the dead man's switch is reset.
+    pub deadline_epoch: Option<Epoch>,
+    /// True if the exits will be broadcast at the next slot.
+    pub broadcast_requested: bool,
+    /// The epoch at which the exits were broadcast.
+    pub broadcast_epoch: Option<Epoch>,
+}
+
+#[derive(Deserialize)]
+pub struct DutySimulationQuery {
+    pub epoch: Option<Epoch>,
+}
+
+/// The duties the validator client expects to perform in an epoch, as returned by
+/// `GET lighthouse/duties/simulation`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DutySimulation {
+    pub epoch: Epoch,
+    pub timing: DutyTiming,
+    pub proposals: Vec<ProposerData>,
+    pub attestations: Vec<SimulatedAttestation>,
+    pub sync_committee: Vec<SimulatedSyncCommitteeDuty>,
+}
+
+/// The offset from the start of a slot at which the validator client performs each duty.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DutyTiming {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub block_proposal_ms: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attestation_ms: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub aggregate_ms: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_committee_message_ms: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_committee_contribution_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SimulatedAttestation {
+    pub slot: Slot,
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_length: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_committee_index: u64,
+    /// The selection proof of an aggregator. This is `None` if the validator is not an
+    /// aggregator or if the proof has not been computed yet.
+    pub selection_proof: Option<Signature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SimulatedSyncCommitteeDuty {
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub validator_sync_committee_indices: Vec<u64>,
+    /// The slots and subnets at which the validator will publish a contribution.
+    pub aggregations: Vec<SimulatedSyncAggregation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SimulatedSyncAggregation {
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub subnet_id: u64,
+    pub selection_proof: Signature,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct ExportKeystoresResponse {
     pub data: Vec<SingleExportKeystoresResponse>,
@@ -193,6 +346,45 @@ pub struct SingleExportKeystoresResponse {
     pub validating_keystore_password: Option<ZeroizeString>,
 }
 
+/// The handoff settings of a validator client, used when validators are moved to it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ValidatorHandoffConfig {
+    /// The number of seconds which must pass after a validator is disabled on its previous
+    /// validator client before it may be imported.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub dead_time: u64,
+    /// Whether keystores may only be imported with a handoff.
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ValidatorHandoffRequest {
+    pub pubkeys: Vec<PublicKeyBytes>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidatorHandoffResponse {
+    pub data: Vec<SingleValidatorHandoff>,
+    #[serde(with = "serde_utils::json_str")]
+    pub slashing_protection: Interchange,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SingleValidatorHandoff {
+    pub validating_keystore: KeystoreJsonStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validating_keystore_password: Option<ZeroizeString>,
+    pub handoff: SignedValidatorHandoff,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ImportValidatorHandoffRequest {
+    pub keystores: Vec<KeystoreJsonStr>,
+    pub passwords: Vec<ZeroizeString>,
+    pub handoffs: Vec<SignedValidatorHandoff>,
+    pub slashing_protection: InterchangeJsonStr,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
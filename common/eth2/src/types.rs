@@ -274,7 +274,7 @@ impl From<Hash256> for RootData {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct FinalityCheckpointsData {
     pub previous_justified: Checkpoint,
     pub current_justified: Checkpoint,
@@ -501,6 +501,12 @@ pub struct ValidatorsQuery {
     pub id: Option<Vec<ValidatorId>>,
     #[serde(default, deserialize_with = "option_query_vec")]
     pub status: Option<Vec<ValidatorStatus>>,
+    /// Lighthouse extension: the lowest validator index to return.
+    #[serde(default, deserialize_with = "option_query_single")]
+    pub start_index: Option<u64>,
+    /// Lighthouse extension: one past the highest validator index to return.
+    #[serde(default, deserialize_with = "option_query_single")]
+    pub end_index: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -510,6 +516,12 @@ pub struct ValidatorsRequestBody {
     pub ids: Option<Vec<ValidatorId>>,
     #[serde(default)]
     pub statuses: Option<Vec<ValidatorStatus>>,
+    /// Lighthouse extension: the lowest validator index to return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<u64>,
+    /// Lighthouse extension: one past the highest validator index to return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_index: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -572,6 +584,25 @@ pub struct ChainHeadData {
     pub root: Hash256,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub execution_optimistic: Option<bool>,
+    /// Lighthouse-specific metadata, included in v2 responses for heads known to fork choice.
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ChainHeadMetadata>,
+}
+
+/// The standing of a chain head in fork choice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainHeadMetadata {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub weight: u64,
+    /// The latest epoch of the validators' latest messages which vote for the head, or `None` if
+    /// no latest messages vote for it.
+    pub last_vote_epoch: Option<Epoch>,
+    pub execution_status: String,
+    /// The number of slots between the head and the finalized checkpoint.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub finalized_distance: u64,
+    /// Whether the latest message of any validator in the validator monitor votes for the head.
+    pub monitored_validator_voted: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -638,6 +669,18 @@ where
     Ok(Some(Vec::from(QueryVec::from(vec))))
 }
 
+fn option_query_single<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: FromStr,
+{
+    let mut values = option_query_vec(deserializer)?.unwrap_or_default();
+    if values.len() > 1 {
+        return Err(serde::de::Error::custom("expected a single value"));
+    }
+    Ok(values.pop())
+}
+
 impl<T: FromStr> From<Vec<QueryVec<T>>> for QueryVec<T> {
     fn from(vecs: Vec<QueryVec<T>>) -> Self {
         Self {
@@ -689,6 +732,14 @@ pub struct BlobIndicesQuery {
     pub indices: Option<Vec<u64>>,
 }
 
+/// Query parameters for `GET lighthouse/beacon/states/{state_id}/proof`.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateProofQuery {
+    #[serde(deserialize_with = "query_vec")]
+    pub gindex: Vec<u64>,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ValidatorIndexData(#[serde(with = "serde_utils::quoted_u64_vec")] pub Vec<u64>);
@@ -1004,6 +1055,32 @@ pub struct SseLateHead {
     pub execution_optimistic: bool,
 }
 
+/// Emitted whenever block production decides whether or not to re-org a late head block.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseProposerReOrg {
+    pub slot: Slot,
+    pub head_slot: Slot,
+    pub head_block: Hash256,
+    /// The block that the proposal will be built on, if a re-org is being attempted.
+    pub parent_block: Option<Hash256>,
+    pub re_org: bool,
+    /// The reason for not attempting a re-org.
+    pub reason: Option<String>,
+    pub proposal_delay_millis: u64,
+    pub head_weight: Option<u64>,
+    pub re_org_head_weight_threshold: Option<u64>,
+    pub parent_weight: Option<u64>,
+    pub re_org_parent_weight_threshold: Option<u64>,
+}
+
+/// Emitted whenever the head becomes optimistic, or becomes verified by the execution engine.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseOptimisticStatus {
+    pub slot: Slot,
+    pub block: Hash256,
+    pub execution_optimistic: bool,
+}
+
 #[superstruct(
     variants(V1, V2, V3),
     variant_attributes(derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize))
@@ -1033,6 +1110,14 @@ pub struct SseExtendedPayloadAttributesGeneric<T> {
     #[serde(with = "serde_utils::quoted_u64")]
     pub parent_block_number: u64,
     pub parent_block_hash: ExecutionBlockHash,
+    /// Lighthouse extension: `true` if the proposer intends to re-org the current head block, in
+    /// which case the parent block is the parent of the head block.
+    #[serde(default)]
+    pub proposer_re_org: bool,
+    /// Lighthouse extension: the current head block, which differs from the parent block when
+    /// `proposer_re_org` is `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_block_root: Option<Hash256>,
     pub payload_attributes: T,
 }
 
@@ -1077,6 +1162,8 @@ impl ForkVersionDeserialize for SseExtendedPayloadAttributes {
             parent_block_root: helper.parent_block_root,
             parent_block_number: helper.parent_block_number,
             parent_block_hash: helper.parent_block_hash,
+            proposer_re_org: helper.proposer_re_org,
+            head_block_root: helper.head_block_root,
             payload_attributes: SsePayloadAttributes::deserialize_by_fork::<D>(
                 helper.payload_attributes,
                 fork_name,
@@ -1106,6 +1193,10 @@ pub enum EventKind<E: EthSpec> {
     AttesterSlashing(Box<AttesterSlashing<E>>),
     BlsToExecutionChange(Box<SignedBlsToExecutionChange>),
     BlockGossip(Box<BlockGossip>),
+    ProposerReOrg(Box<SseProposerReOrg>),
+    SlasherAttestation(Box<IndexedAttestation<E>>),
+    SlasherBlockHeader(Box<SignedBeaconBlockHeader>),
+    OptimisticStatus(SseOptimisticStatus),
 }
 
 impl<E: EthSpec> EventKind<E> {
@@ -1129,6 +1220,10 @@ impl<E: EthSpec> EventKind<E> {
             EventKind::AttesterSlashing(_) => "attester_slashing",
             EventKind::BlsToExecutionChange(_) => "bls_to_execution_change",
             EventKind::BlockGossip(_) => "block_gossip",
+            EventKind::ProposerReOrg(_) => "proposer_re_org",
+            EventKind::SlasherAttestation(_) => "slasher_attestation",
+            EventKind::SlasherBlockHeader(_) => "slasher_block_header",
+            EventKind::OptimisticStatus(_) => "optimistic_status",
         }
     }
 
@@ -1227,6 +1322,26 @@ impl<E: EthSpec> EventKind<E> {
             "block_gossip" => Ok(EventKind::BlockGossip(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Gossip: {:?}", e)),
             )?)),
+            "proposer_re_org" => Ok(EventKind::ProposerReOrg(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Proposer Re-org: {:?}", e))
+                })?,
+            )),
+            "slasher_attestation" => Ok(EventKind::SlasherAttestation(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Slasher Attestation: {:?}", e))
+                })?,
+            )),
+            "slasher_block_header" => Ok(EventKind::SlasherBlockHeader(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Slasher Block Header: {:?}", e))
+                })?,
+            )),
+            "optimistic_status" => Ok(EventKind::OptimisticStatus(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Optimistic Status: {:?}", e))
+                })?,
+            )),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -1262,6 +1377,10 @@ pub enum EventTopic {
     ProposerSlashing,
     BlsToExecutionChange,
     BlockGossip,
+    ProposerReOrg,
+    SlasherAttestation,
+    SlasherBlockHeader,
+    OptimisticStatus,
 }
 
 impl FromStr for EventTopic {
@@ -1287,6 +1406,10 @@ impl FromStr for EventTopic {
             "proposer_slashing" => Ok(EventTopic::ProposerSlashing),
             "bls_to_execution_change" => Ok(EventTopic::BlsToExecutionChange),
             "block_gossip" => Ok(EventTopic::BlockGossip),
+            "proposer_re_org" => Ok(EventTopic::ProposerReOrg),
+            "slasher_attestation" => Ok(EventTopic::SlasherAttestation),
+            "slasher_block_header" => Ok(EventTopic::SlasherBlockHeader),
+            "optimistic_status" => Ok(EventTopic::OptimisticStatus),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1313,6 +1436,10 @@ impl fmt::Display for EventTopic {
             EventTopic::ProposerSlashing => write!(f, "proposer_slashing"),
             EventTopic::BlsToExecutionChange => write!(f, "bls_to_execution_change"),
             EventTopic::BlockGossip => write!(f, "block_gossip"),
+            EventTopic::ProposerReOrg => write!(f, "proposer_re_org"),
+            EventTopic::SlasherAttestation => write!(f, "slasher_attestation"),
+            EventTopic::SlasherBlockHeader => write!(f, "slasher_block_header"),
+            EventTopic::OptimisticStatus => write!(f, "optimistic_status"),
         }
     }
 }
@@ -1576,6 +1703,78 @@ mod tests {
         .expect("should decode BlockAndBlobSidecars");
         assert!(matches!(decoded, PublishBlockRequest::BlockContents(_)));
     }
+
+    #[test]
+    fn chain_head_metadata_serde() {
+        let head = ChainHeadData {
+            slot: Slot::new(3),
+            root: Hash256::repeat_byte(1),
+            execution_optimistic: Some(false),
+            metadata: Some(ChainHeadMetadata {
+                weight: 64,
+                last_vote_epoch: Some(Epoch::new(0)),
+                execution_status: "valid".to_string(),
+                finalized_distance: 3,
+                monitored_validator_voted: true,
+            }),
+        };
+
+        // The metadata is flattened into the head.
+        let json = serde_json::to_value(&head).unwrap();
+        assert_eq!(json["weight"], "64");
+        assert_eq!(serde_json::from_value::<ChainHeadData>(json).unwrap(), head);
+
+        // Standard responses have no metadata.
+        let standard = serde_json::json!({
+            "slot": "3",
+            "root": head.root,
+            "execution_optimistic": false,
+        });
+        let decoded = serde_json::from_value::<ChainHeadData>(standard).unwrap();
+        assert_eq!(decoded.metadata, None);
+    }
+
+    #[test]
+    fn payload_attributes_re_org_extension() {
+        let event = SseExtendedPayloadAttributes {
+            proposal_slot: Slot::new(2),
+            proposer_index: 3,
+            parent_block_root: Hash256::repeat_byte(1),
+            parent_block_number: 1,
+            parent_block_hash: ExecutionBlockHash::repeat_byte(2),
+            proposer_re_org: true,
+            head_block_root: Some(Hash256::repeat_byte(4)),
+            payload_attributes: SsePayloadAttributes::V3(SsePayloadAttributesV3 {
+                timestamp: 24,
+                prev_randao: Hash256::repeat_byte(5),
+                suggested_fee_recipient: Address::repeat_byte(6),
+                withdrawals: vec![],
+                parent_beacon_block_root: Hash256::repeat_byte(1),
+            }),
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        let decoded = SseExtendedPayloadAttributes::deserialize_by_fork::<serde_json::Value>(
+            json.clone(),
+            ForkName::Deneb,
+        )
+        .unwrap();
+        assert_eq!(decoded, event);
+
+        // Events from other clients omit the extension fields.
+        let mut standard = json;
+        let fields = standard.as_object_mut().unwrap();
+        fields.remove("proposer_re_org");
+        fields.remove("head_block_root");
+        let decoded = SseExtendedPayloadAttributes::deserialize_by_fork::<serde_json::Value>(
+            standard,
+            ForkName::Deneb,
+        )
+        .unwrap();
+        assert!(!decoded.proposer_re_org);
+        assert_eq!(decoded.head_block_root, None);
+        assert_eq!(decoded.payload_attributes, event.payload_attributes);
+    }
 }
 
 #[derive(Debug, Encode, Serialize, Deserialize)]
@@ -2,7 +2,7 @@ use crate::{
     filesystem::{read, update},
     Error,
 };
-use eth2_wallet::{Uuid, ValidatorKeystores, Wallet};
+use eth2_wallet::{Uuid, ValidatorKeystores, ValidatorSecrets, Wallet};
 use lockfile::Lockfile;
 use std::path::{Path, PathBuf};
 
@@ -91,4 +91,36 @@ impl LockedWallet {
 
         Ok(keystores)
     }
+
+    /// Calls `Wallet::next_validator_secrets` on the underlying `wallet`.
+    ///
+    /// Ensures that the wallet JSON file is updated before the secrets are returned.
+    ///
+    /// ## Errors
+    ///
+    /// - If there is an error generating the validator secrets.
+    /// - If there is a file-system error.
+    pub fn next_validator_secrets(
+        &mut self,
+        wallet_password: &[u8],
+        count: u32,
+    ) -> Result<Vec<ValidatorSecrets>, Error> {
+        let secrets = self.wallet.next_validator_secrets(wallet_password, count)?;
+
+        update(&self.wallet_dir, &self.wallet)?;
+
+        Ok(secrets)
+    }
+
+    /// Calls `Wallet::set_nextaccount` on the underlying `wallet`, persisting the change.
+    ///
+    /// ## Errors
+    ///
+    /// - If `nextaccount` is less than the current `nextaccount` of the wallet.
+    /// - If there is a file-system error.
+    pub fn set_nextaccount(&mut self, nextaccount: u32) -> Result<(), Error> {
+        self.wallet.set_nextaccount(nextaccount)?;
+
+        update(&self.wallet_dir, &self.wallet).map_err(Into::into)
+    }
 }
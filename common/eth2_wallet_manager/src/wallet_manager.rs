@@ -2,7 +2,7 @@ use crate::{
     filesystem::{create, Error as FilesystemError},
     LockedWallet,
 };
-use eth2_wallet::{bip39::Mnemonic, Error as WalletError, Uuid, Wallet, WalletBuilder};
+use eth2_wallet::{bip39::Mnemonic, Error as WalletError, KdfParams, Uuid, Wallet, WalletBuilder};
 use lockfile::LockfileError;
 use std::collections::HashMap;
 use std::ffi::OsString;
@@ -118,17 +118,32 @@ impl WalletManager {
     /// - If a wallet with this name already exists.
     /// - If there is a file-system or parsing error.
     pub fn create_wallet(
+        &self,
+        name: String,
+        wallet_type: WalletType,
+        mnemonic: &Mnemonic,
+        password: &[u8],
+    ) -> Result<LockedWallet, Error> {
+        self.create_wallet_with_kdf(name, wallet_type, mnemonic, password, KdfParams::default())
+    }
+
+    /// As per `Self::create_wallet`, but encrypts the wallet using the KDF described by
+    /// `kdf_params`.
+    pub fn create_wallet_with_kdf(
         &self,
         name: String,
         _wallet_type: WalletType,
         mnemonic: &Mnemonic,
         password: &[u8],
+        kdf_params: KdfParams,
     ) -> Result<LockedWallet, Error> {
         if self.wallets()?.contains_key(&name) {
             return Err(Error::NameAlreadyTaken(name));
         }
 
-        let wallet = WalletBuilder::from_mnemonic(mnemonic, password, name)?.build()?;
+        let wallet = WalletBuilder::from_mnemonic(mnemonic, password, name)?
+            .kdf_params(kdf_params)
+            .build()?;
         let uuid = *wallet.uuid();
 
         let wallet_dir = self.dir.join(format!("{}", uuid));
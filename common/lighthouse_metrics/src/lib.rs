@@ -56,10 +56,10 @@
 use prometheus::{Error, HistogramOpts, Opts};
 use std::time::Duration;
 
-use prometheus::core::{Atomic, GenericGauge, GenericGaugeVec};
+use prometheus::core::{Atomic, Collector, GenericGauge, GenericGaugeVec};
 pub use prometheus::{
     exponential_buckets, linear_buckets,
-    proto::{Metric, MetricFamily, MetricType},
+    proto::{LabelPair, Metric, MetricFamily, MetricType},
     Encoder, Gauge, GaugeVec, Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec,
     IntGauge, IntGaugeVec, Result, TextEncoder, DEFAULT_BUCKETS,
 };
@@ -69,6 +69,16 @@ pub fn gather() -> Vec<prometheus::proto::MetricFamily> {
     prometheus::gather()
 }
 
+/// Returns the name of `metric`, or `None` if it could not be registered.
+pub fn metric_name<C: Collector>(metric: &Result<C>) -> Option<String> {
+    metric
+        .as_ref()
+        .ok()?
+        .desc()
+        .first()
+        .map(|desc| desc.fq_name.clone())
+}
+
 /// Attempts to create an `IntCounter`, returning `Err` if the registry does not accept the counter
 /// (potentially due to naming conflict).
 pub fn try_create_int_counter(name: &str, help: &str) -> Result<IntCounter> {
@@ -19,6 +19,7 @@ thread_local! {
 /// Serialized record.
 #[derive(Clone)]
 pub struct AsyncRecord {
+    time: chrono::DateTime<chrono::Local>,
     msg: String,
     level: Level,
     location: Box<slog::RecordLocation>,
@@ -37,6 +38,7 @@ impl AsyncRecord {
             .expect("`ToSendSerializer` can't fail");
 
         AsyncRecord {
+            time: chrono::Local::now(),
             msg: fmt::format(*record.msg()),
             level: record.level(),
             location: Box::new(*record.location()),
@@ -156,8 +158,9 @@ impl Serialize for AsyncRecord {
     where
         S: serde::Serializer,
     {
-        // Get the current time
-        let dt = chrono::Local::now().format("%b %e %T").to_string();
+        // Use the time the record was logged, which may be earlier than now if the record was
+        // buffered.
+        let dt = self.time.format("%b %e %T").to_string();
 
         let rs = RecordStatic {
             location: &self.location,
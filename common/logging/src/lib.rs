@@ -15,12 +15,18 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
 pub mod async_record;
+mod repeat_suppression;
+mod runtime_log_levels;
 mod sse_logging_components;
 mod tracing_logging_layer;
 mod tracing_metrics_layer;
+mod tracing_otlp_layer;
 
+pub use repeat_suppression::{RepeatSuppressor, REPEAT_SUPPRESSION_WINDOW};
+pub use runtime_log_levels::{RuntimeLevelFilter, RuntimeLogLevels};
 pub use sse_logging_components::SSELoggingComponents;
 pub use tracing_metrics_layer::MetricsLayer;
+pub use tracing_otlp_layer::{shutdown_otlp, TRACING_SPAN_TARGETS};
 
 /// The minimum interval between log messages indicating that a queue is full.
 const LOG_DEBOUNCE_INTERVAL: Duration = Duration::from_secs(30);
@@ -221,8 +227,13 @@ impl TimeLatch {
     }
 }
 
-pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
-    let filter_layer = match tracing_subscriber::EnvFilter::try_from_default_env()
+/// Initializes the logging of dependencies which use `tracing`.
+///
+/// If `tracing_endpoint` is provided, the spans which trace the critical path of the beacon node
+/// are also exported to it over OTLP, in which case this must be called from within a Tokio
+/// runtime.
+pub fn create_tracing_layer(base_tracing_log_path: PathBuf, tracing_endpoint: Option<&str>) {
+    let mut filter_layer = match tracing_subscriber::EnvFilter::try_from_default_env()
         .or_else(|_| tracing_subscriber::EnvFilter::try_new("warn"))
     {
         Ok(filter) => filter,
@@ -232,6 +243,25 @@ pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
         }
     };
 
+    let otlp_layer = match tracing_endpoint.map(tracing_otlp_layer::otlp_layer) {
+        Some(Ok(layer)) => {
+            // Enable the spans to be exported, which would otherwise be filtered out with the
+            // logs of the dependencies.
+            for target in TRACING_SPAN_TARGETS {
+                match format!("{target}=info").parse() {
+                    Ok(directive) => filter_layer = filter_layer.add_directive(directive),
+                    Err(e) => eprintln!("Failed to enable tracing of {target}: {e}"),
+                }
+            }
+            Some(layer)
+        }
+        Some(Err(e)) => {
+            eprintln!("Failed to initialize tracing export: {e}");
+            None
+        }
+        None => None,
+    };
+
     let Ok(libp2p_writer) = RollingFileAppender::builder()
         .rotation(Rotation::DAILY)
         .max_log_files(2)
@@ -270,6 +300,7 @@ pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
         .finish()
         .with(MetricsLayer)
         .with(custom_layer)
+        .with(otlp_layer)
         .try_init()
     {
         eprintln!("Failed to initialize dependency logging {e}");
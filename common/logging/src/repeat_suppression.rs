@@ -0,0 +1,96 @@
+//! This module provides an implementation of `slog::Drain` which limits the rate at which the
+//! same message can be logged, to prevent a burst of repeated messages from flooding the logs.
+
+use parking_lot::Mutex;
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::time::{Duration, Instant};
+
+/// The period over which the number of records logged from each call site is limited.
+pub const REPEAT_SUPPRESSION_WINDOW: Duration = Duration::from_secs(60);
+
+/// The records logged from a single call site during the current window.
+struct CallSite {
+    window_start: Instant,
+    count: usize,
+    suppressed: usize,
+}
+
+/// Passes at most `limit` records from each call site to the wrapped drain per
+/// `REPEAT_SUPPRESSION_WINDOW`, or all records if `limit` is 0.
+///
+/// Records from the same call site are considered repeats even if their values differ. The
+/// number of records which were suppressed is logged with the first record from the call site
+/// in a later window. Critical records are never suppressed.
+pub struct RepeatSuppressor<D> {
+    drain: D,
+    limit: usize,
+    call_sites: AssertUnwindSafe<Mutex<HashMap<(&'static str, u32), CallSite>>>,
+}
+
+impl<D> RepeatSuppressor<D> {
+    pub fn new(drain: D, limit: usize) -> Self {
+        Self {
+            drain,
+            limit,
+            call_sites: AssertUnwindSafe(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Counts a record from `call_site`, returning `None` if it should be suppressed, or the
+    /// number of records suppressed in the previous window otherwise.
+    fn admit(&self, call_site: (&'static str, u32)) -> Option<usize> {
+        let now = Instant::now();
+        let mut call_sites = self.call_sites.lock();
+        let site = call_sites.entry(call_site).or_insert(CallSite {
+            window_start: now,
+            count: 0,
+            suppressed: 0,
+        });
+
+        let mut previously_suppressed = 0;
+        if now.duration_since(site.window_start) >= REPEAT_SUPPRESSION_WINDOW {
+            previously_suppressed = std::mem::take(&mut site.suppressed);
+            site.window_start = now;
+            site.count = 0;
+        }
+
+        site.count += 1;
+        if site.count > self.limit {
+            site.suppressed += 1;
+            None
+        } else {
+            Some(previously_suppressed)
+        }
+    }
+}
+
+impl<D: Drain> Drain for RepeatSuppressor<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        if self.limit == 0 || record.level() == Level::Critical {
+            return self.drain.log(record, values).map(Some);
+        }
+
+        match self.admit((record.file(), record.line())) {
+            None => Ok(None),
+            Some(0) => self.drain.log(record, values).map(Some),
+            Some(suppressed) => {
+                let msg = record.msg().to_string();
+                self.drain.log(
+                    &slog::record!(
+                        Level::Warning,
+                        "",
+                        &format_args!("Suppressed repeated log messages"),
+                        slog::b!("msg" => msg.as_str(), "count" => suppressed)
+                    ),
+                    values,
+                )?;
+                self.drain.log(record, values).map(Some)
+            }
+        }
+    }
+}
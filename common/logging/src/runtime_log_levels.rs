@@ -0,0 +1,97 @@
+//! This module provides an implementation of `slog::Drain` which filters records by level, where
+//! the level of individual targets (module paths) may be changed while the node is running.
+
+use parking_lot::RwLock;
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::collections::BTreeMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+/// The log levels of targets which have been overridden at runtime.
+///
+/// A target is a module path such as `beacon_chain` or `network::sync`, which applies to the
+/// module and all of its sub-modules. The most specific matching target determines the level of
+/// a record.
+#[derive(Clone, Default)]
+pub struct RuntimeLogLevels {
+    targets: Arc<AssertUnwindSafe<RwLock<BTreeMap<String, Level>>>>,
+}
+
+impl RuntimeLogLevels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the level of `target`, replacing any previous level.
+    pub fn set(&self, target: String, level: Level) {
+        self.targets.write().insert(target, level);
+    }
+
+    /// Removes the level of `target`, such that it reverts to the default level of each drain.
+    ///
+    /// Returns `false` if the target had no level.
+    pub fn clear(&self, target: &str) -> bool {
+        self.targets.write().remove(target).is_some()
+    }
+
+    /// Returns the levels of all overridden targets.
+    pub fn levels(&self) -> Vec<(String, Level)> {
+        self.targets
+            .read()
+            .iter()
+            .map(|(target, level)| (target.clone(), *level))
+            .collect()
+    }
+
+    /// Returns the level of the most specific target which contains `module`, if any.
+    pub fn level_for(&self, module: &str) -> Option<Level> {
+        self.targets
+            .read()
+            .iter()
+            .filter(|(target, _)| is_within_target(module, target))
+            .max_by_key(|(target, _)| target.len())
+            .map(|(_, level)| *level)
+    }
+}
+
+/// Returns `true` if `module` is `target` or one of its sub-modules.
+fn is_within_target(module: &str, target: &str) -> bool {
+    module
+        .strip_prefix(target)
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Passes records at or above `default_level` to the wrapped drain, unless the level of their
+/// module has been overridden in `levels`.
+pub struct RuntimeLevelFilter<D> {
+    drain: D,
+    levels: RuntimeLogLevels,
+    default_level: Level,
+}
+
+impl<D> RuntimeLevelFilter<D> {
+    pub fn new(drain: D, levels: RuntimeLogLevels, default_level: Level) -> Self {
+        Self {
+            drain,
+            levels,
+            default_level,
+        }
+    }
+}
+
+impl<D: Drain> Drain for RuntimeLevelFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let level = self
+            .levels
+            .level_for(record.module())
+            .unwrap_or(self.default_level);
+        if record.level().is_at_least(level) {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
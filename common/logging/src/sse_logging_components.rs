@@ -2,21 +2,32 @@
 //! there are subscribers to a HTTP SSE stream.
 
 use crate::async_record::AsyncRecord;
+use parking_lot::Mutex;
 use slog::{Drain, OwnedKVList, Record};
+use std::collections::VecDeque;
 use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
-use tokio::sync::broadcast::Sender;
+use tokio::sync::broadcast::{Receiver, Sender};
 
 /// Default log level for SSE Events.
 // NOTE: Made this a constant. Debug level seems to be pretty intense. Can make this
 // configurable later if needed.
 const LOG_LEVEL: slog::Level = slog::Level::Info;
 
+/// Records at or above this level are kept in the buffer of recent records.
+const RECENT_LOG_LEVEL: slog::Level = slog::Level::Warning;
+
+/// The maximum number of recent records to keep.
+pub const RECENT_LOG_BUFFER_SIZE: usize = 128;
+
 /// The components required in the HTTP API task to receive logged events.
 #[derive(Clone)]
 pub struct SSELoggingComponents {
     /// The channel to receive events from.
     pub sender: Arc<AssertUnwindSafe<Sender<AsyncRecord>>>,
+    /// The most recent warnings and errors, which are sent to new subscribers before any new
+    /// events so that they can show errors which occurred before they connected.
+    recent: Arc<AssertUnwindSafe<Mutex<VecDeque<AsyncRecord>>>>,
 }
 
 impl SSELoggingComponents {
@@ -25,7 +36,23 @@ impl SSELoggingComponents {
         let (sender, _receiver) = tokio::sync::broadcast::channel(channel_size);
 
         let sender = Arc::new(AssertUnwindSafe(sender));
-        SSELoggingComponents { sender }
+        SSELoggingComponents {
+            sender,
+            recent: Arc::new(AssertUnwindSafe(Mutex::new(VecDeque::with_capacity(
+                RECENT_LOG_BUFFER_SIZE,
+            )))),
+        }
+    }
+
+    /// Subscribe to new events, returning the recent warnings and errors which were logged
+    /// before the subscription, oldest first.
+    ///
+    /// No event is both returned and received by the subscription.
+    pub fn subscribe_with_recent(&self) -> (Vec<AsyncRecord>, Receiver<AsyncRecord>) {
+        // Hold the lock while subscribing, since events are sent while it is held.
+        let recent = self.recent.lock();
+        let receiver = self.sender.subscribe();
+        (recent.iter().cloned().collect(), receiver)
     }
 }
 
@@ -35,8 +62,18 @@ impl Drain for SSELoggingComponents {
 
     fn log(&self, record: &Record, logger_values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
         if record.level().is_at_least(LOG_LEVEL) {
+            let async_record = AsyncRecord::from(record, logger_values);
+
+            let mut recent = self.recent.lock();
+            if record.level().is_at_least(RECENT_LOG_LEVEL) {
+                if recent.len() >= RECENT_LOG_BUFFER_SIZE {
+                    recent.pop_front();
+                }
+                recent.push_back(async_record.clone());
+            }
+
             // Attempt to send the logs
-            match self.sender.send(AsyncRecord::from(record, logger_values)) {
+            match self.sender.send(async_record) {
                 Ok(_num_sent) => {} // Everything got sent
                 Err(_err) => {}     // There are no subscribers, do nothing
             }
@@ -44,3 +81,55 @@ impl Drain for SSELoggingComponents {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::{error, info, o, warn, Logger};
+
+    fn msg(record: &AsyncRecord) -> String {
+        serde_json::to_value(record).unwrap()["msg"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn recent_warnings_and_errors_are_replayed() {
+        let components = SSELoggingComponents::new(16);
+        let log = Logger::root(components.clone().fuse(), o!());
+
+        info!(log, "info");
+        warn!(log, "warn");
+        error!(log, "error");
+
+        let (recent, mut receiver) = components.subscribe_with_recent();
+        assert_eq!(
+            recent.iter().map(msg).collect::<Vec<_>>(),
+            ["warn", "error"]
+        );
+
+        // New events are received but not duplicated in the replayed records.
+        error!(log, "new error");
+        assert_eq!(msg(&receiver.try_recv().unwrap()), "new error");
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn recent_buffer_is_bounded() {
+        let components = SSELoggingComponents::new(16);
+        let log = Logger::root(components.clone().fuse(), o!());
+
+        for i in 0..RECENT_LOG_BUFFER_SIZE + 2 {
+            warn!(log, "{}", i);
+        }
+
+        let (recent, _) = components.subscribe_with_recent();
+        assert_eq!(recent.len(), RECENT_LOG_BUFFER_SIZE);
+        assert_eq!(msg(&recent[0]), "2");
+        assert_eq!(
+            msg(recent.last().unwrap()),
+            (RECENT_LOG_BUFFER_SIZE + 1).to_string()
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Exposes [`otlp_layer`]: A tracing layer that exports the spans which trace the critical path of
+//! the beacon node to an OpenTelemetry collector.
+
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace::Config, Resource};
+use tracing_core::{Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// The targets of the spans which trace the critical path of the beacon node, from receiving a
+/// message on gossip through to fork choice, head updates and attestation production.
+///
+/// Only spans from these targets are exported.
+pub const TRACING_SPAN_TARGETS: &[&str] = &["beacon_chain", "network"];
+
+/// Returns a layer which exports spans to the OTLP gRPC `endpoint` in batches.
+///
+/// Must be called from within a Tokio runtime.
+pub fn otlp_layer<S>(endpoint: &str) -> Result<impl Layer<S>, String>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            Config::default().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                "lighthouse",
+            )])),
+        )
+        .install_batch(runtime::Tokio)
+        .map_err(|e| format!("Unable to create OTLP exporter: {e}"))?;
+
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(span_targets()))
+}
+
+/// Returns a filter which enables the spans of `TRACING_SPAN_TARGETS` at `INFO` and above.
+fn span_targets() -> Targets {
+    TRACING_SPAN_TARGETS
+        .iter()
+        .fold(Targets::new(), |targets, target| {
+            targets.with_target(*target, Level::INFO)
+        })
+}
+
+/// Exports any spans which have not yet been exported, and stops exporting.
+pub fn shutdown_otlp() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_critical_path_spans_are_exported() {
+        let targets = span_targets();
+
+        assert!(targets.would_enable("beacon_chain", &Level::INFO));
+        assert!(targets.would_enable("beacon_chain::canonical_head", &Level::INFO));
+        assert!(targets.would_enable("network::network_beacon_processor", &Level::WARN));
+
+        assert!(!targets.would_enable("beacon_chain", &Level::DEBUG));
+        assert!(!targets.would_enable("libp2p_gossipsub", &Level::INFO));
+        assert!(!targets.would_enable("discv5", &Level::INFO));
+    }
+}
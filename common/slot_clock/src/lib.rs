@@ -7,7 +7,7 @@ use std::time::Duration;
 pub use crate::manual_slot_clock::ManualSlotClock as TestingSlotClock;
 pub use crate::manual_slot_clock::ManualSlotClock;
 pub use crate::system_time_slot_clock::SystemTimeSlotClock;
-pub use metrics::scrape_for_metrics;
+pub use metrics::{chain_metric_names, scrape_for_metrics};
 use types::consts::bellatrix::INTERVALS_PER_SLOT;
 pub use types::Slot;
 
@@ -16,6 +16,19 @@ lazy_static! {
     );
 }
 
+/// Returns the names of the metrics set by `scrape_for_metrics`.
+pub fn chain_metric_names() -> Vec<String> {
+    [
+        &*PRESENT_SLOT,
+        &*PRESENT_EPOCH,
+        &*SLOTS_PER_EPOCH,
+        &*SECONDS_PER_SLOT,
+    ]
+    .into_iter()
+    .filter_map(metric_name)
+    .collect()
+}
+
 /// Update the global metrics `DEFAULT_REGISTRY` with info from the slot clock.
 pub fn scrape_for_metrics<E: EthSpec, U: SlotClock>(clock: &U) {
     let present_slot = match clock.now() {
@@ -0,0 +1,155 @@
+//! Estimates of how far the local clock has drifted from the rest of the network.
+//!
+//! All offsets are in milliseconds, and are positive when the local clock is *ahead*.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Clock offsets larger than this are unsafe.
+///
+/// Peers reject gossip messages from more than `MAXIMUM_GOSSIP_CLOCK_DISPARITY` (500ms) in the
+/// future, so we warn well before that point is reached.
+pub const MAX_SAFE_CLOCK_OFFSET_MS: i64 = 250;
+
+/// The default port of an NTP server.
+pub const NTP_PORT: u16 = 123;
+
+/// The number of seconds between the NTP epoch (1900) and the UNIX epoch (1970).
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+const NTP_PACKET_LEN: usize = 48;
+
+/// Returns `true` if an offset estimated from the arrival times of gossip blocks is safe.
+///
+/// Blocks are never accepted more than `MAXIMUM_GOSSIP_CLOCK_DISPARITY` early, so a clock that is
+/// behind can only be detected up to that limit. The estimate includes network latency, so a clock
+/// that is ahead only shows up once every block arrives after the attestation deadline.
+pub fn is_safe_gossip_clock_offset(offset_ms: i64, slot_duration: Duration) -> bool {
+    offset_ms >= -MAX_SAFE_CLOCK_OFFSET_MS && offset_ms <= slot_duration.as_millis() as i64 / 3
+}
+
+/// Returns `true` if an offset measured against an NTP server is safe.
+pub fn is_safe_ntp_clock_offset(offset_ms: i64) -> bool {
+    offset_ms.abs() <= MAX_SAFE_CLOCK_OFFSET_MS
+}
+
+/// Queries an SNTP server (RFC 4330) for the offset of the local clock.
+///
+/// `server` is a `host` or `host:port`. This call blocks for up to `timeout`.
+pub fn query_ntp_offset(server: &str, timeout: Duration) -> Result<i64, String> {
+    let addr = server
+        .to_socket_addrs()
+        .or_else(|_| (server, NTP_PORT).to_socket_addrs())
+        .map_err(|e| format!("Unable to resolve NTP server {}: {}", server, e))?
+        .next()
+        .ok_or_else(|| format!("NTP server {} did not resolve to any address", server))?;
+
+    let socket = UdpSocket::bind(if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    })
+    .map_err(|e| format!("Unable to bind UDP socket: {}", e))?;
+    socket
+        .set_read_timeout(Some(timeout))
+        .map_err(|e| format!("Unable to set NTP timeout: {}", e))?;
+
+    // LI = 0 (no warning), VN = 4, Mode = 3 (client).
+    let mut request = [0u8; NTP_PACKET_LEN];
+    request[0] = 0x23;
+
+    let sent = unix_millis(SystemTime::now());
+    socket
+        .send_to(&request, addr)
+        .map_err(|e| format!("Unable to send NTP request: {}", e))?;
+
+    let mut response = [0u8; NTP_PACKET_LEN];
+    let (len, _) = socket
+        .recv_from(&mut response)
+        .map_err(|e| format!("No response from NTP server {}: {}", server, e))?;
+    let received = unix_millis(SystemTime::now());
+
+    if len < NTP_PACKET_LEN {
+        return Err(format!("NTP response too short: {} bytes", len));
+    }
+
+    ntp_offset(&response, sent, received)
+}
+
+/// Computes the local clock offset from an NTP `response`, given the local times at which the
+/// request was `sent` and the response `received` (in milliseconds since the UNIX epoch).
+fn ntp_offset(response: &[u8; NTP_PACKET_LEN], sent: i64, received: i64) -> Result<i64, String> {
+    // Mode 4 is "server", stratum 0 is a "kiss-o'-death" packet.
+    if response[0] & 0x7 != 4 {
+        return Err("NTP response is not from a server".into());
+    }
+    if response[1] == 0 {
+        return Err("NTP server refused the request".into());
+    }
+
+    let server_received = ntp_timestamp_millis(&response[32..40]);
+    let server_sent = ntp_timestamp_millis(&response[40..48]);
+
+    // The server's clock is ahead of ours by `((t2 - t1) + (t3 - t4)) / 2`.
+    let server_ahead = ((server_received - sent) + (server_sent - received)) / 2;
+    Ok(-server_ahead)
+}
+
+/// Converts an NTP timestamp into milliseconds since the UNIX epoch.
+fn ntp_timestamp_millis(bytes: &[u8]) -> i64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+    let millis = (fraction * 1000) >> 32;
+    (seconds.saturating_sub(NTP_UNIX_OFFSET_SECS) * 1000 + millis) as i64
+}
+
+fn unix_millis(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(server_received: i64, server_sent: i64) -> [u8; NTP_PACKET_LEN] {
+        let mut response = [0u8; NTP_PACKET_LEN];
+        // LI = 0, VN = 4, Mode = 4 (server), stratum 2.
+        response[0] = 0x24;
+        response[1] = 2;
+        for (offset, millis) in [(32, server_received), (40, server_sent)] {
+            let seconds = (millis / 1000) as u64 + NTP_UNIX_OFFSET_SECS;
+            let fraction = (((millis % 1000) as u64) << 32) / 1000;
+            response[offset..offset + 4].copy_from_slice(&(seconds as u32).to_be_bytes());
+            response[offset + 4..offset + 8].copy_from_slice(&(fraction as u32).to_be_bytes());
+        }
+        response
+    }
+
+    #[test]
+    fn offset_of_fast_clock() {
+        // The local clock is 300ms ahead and each leg of the round trip takes 20ms.
+        let sent = 1_700_000_000_300;
+        let response = response(1_700_000_000_020, 1_700_000_000_021);
+        let received = 1_700_000_000_341;
+        let offset = ntp_offset(&response, sent, received).unwrap();
+        assert!((299..=301).contains(&offset), "{}", offset);
+    }
+
+    #[test]
+    fn offset_of_slow_clock() {
+        let sent = 1_700_000_000_000;
+        let response = response(1_700_000_000_520, 1_700_000_000_520);
+        let received = 1_700_000_000_040;
+        let offset = ntp_offset(&response, sent, received).unwrap();
+        assert!((-501..=-499).contains(&offset), "{}", offset);
+    }
+
+    #[test]
+    fn rejects_kiss_of_death() {
+        let mut response = response(0, 0);
+        response[1] = 0;
+        assert!(ntp_offset(&response, 0, 0).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Compression of HTTP responses, negotiated using the `Accept-Encoding` request header.
+//!
+//! Bodies are compressed as they are streamed, so large responses are never held in memory in
+//! compressed form.
+use async_compression::tokio::bufread::{GzipEncoder, ZstdEncoder};
+use futures::TryStreamExt;
+use std::io;
+use tokio_util::io::{ReaderStream, StreamReader};
+use warp::http::header::{
+    HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, VARY,
+};
+use warp::hyper::body::{Body, HttpBody};
+use warp::reply::{Reply, Response};
+
+/// Responses with bodies known to be smaller than this are not compressed.
+pub const MIN_COMPRESSION_SIZE: u64 = 1024;
+
+/// A content encoding supported by `compress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Zstd,
+    Gzip,
+}
+
+impl Encoding {
+    /// Choose an encoding acceptable to the client, preferring zstd over gzip.
+    ///
+    /// Encodings with a quality value of zero are treated as unacceptable, but quality values are
+    /// otherwise ignored.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let mut zstd = false;
+        let mut gzip = false;
+        for item in accept_encoding.split(',') {
+            let mut params = item.split(';').map(str::trim);
+            let name = params.next().unwrap_or_default();
+            let refused = params.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .map_or(false, |q| q <= 0.0)
+            });
+            if refused {
+                continue;
+            }
+            if name.eq_ignore_ascii_case("zstd") {
+                zstd = true;
+            } else if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+                gzip = true;
+            }
+        }
+        if zstd {
+            Some(Encoding::Zstd)
+        } else if gzip {
+            Some(Encoding::Gzip)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Zstd => "zstd",
+            Encoding::Gzip => "gzip",
+        }
+    }
+}
+
+/// Compress the body of `reply` with an encoding acceptable to the client that sent `headers`.
+///
+/// The reply is returned unchanged if the client accepts none of the supported encodings, if it is
+/// already encoded, if it is small or if it is an event stream (which would be delayed by
+/// buffering in the encoder).
+pub fn compress<T: Reply>(reply: T, headers: &HeaderMap) -> Response {
+    let response = reply.into_response();
+
+    let Some(encoding) = headers
+        .get(ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Encoding::negotiate)
+    else {
+        return response;
+    };
+
+    let is_event_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |content_type| {
+            content_type.starts_with("text/event-stream")
+        });
+    let is_small = response
+        .body()
+        .size_hint()
+        .upper()
+        .map_or(false, |size| size < MIN_COMPRESSION_SIZE);
+    if is_event_stream || is_small || response.headers().contains_key(CONTENT_ENCODING) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let reader = StreamReader::new(body.map_err(|e| io::Error::new(io::ErrorKind::Other, e)));
+    let body = match encoding {
+        Encoding::Zstd => Body::wrap_stream(ReaderStream::new(ZstdEncoder::new(reader))),
+        Encoding::Gzip => Body::wrap_stream(ReaderStream::new(GzipEncoder::new(reader))),
+    };
+
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.insert(
+        CONTENT_ENCODING,
+        HeaderValue::from_static(encoding.as_str()),
+    );
+    parts
+        .headers
+        .append(VARY, HeaderValue::from_static("accept-encoding"));
+    Response::from_parts(parts, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate() {
+        assert_eq!(Encoding::negotiate("gzip"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::negotiate("gzip, zstd"), Some(Encoding::Zstd));
+        assert_eq!(Encoding::negotiate("br, GZIP;q=0.5"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::negotiate("zstd;q=0, gzip"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::negotiate("identity"), None);
+        assert_eq!(Encoding::negotiate(""), None);
+    }
+}
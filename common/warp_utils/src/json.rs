@@ -19,15 +19,27 @@ impl Json {
 pub fn json<T: DeserializeOwned + Send>() -> impl Filter<Extract = (T,), Error = Rejection> + Copy {
     warp::header::optional::<String>(CONTENT_TYPE_HEADER)
         .and(warp::body::bytes())
-        .and_then(|header: Option<String>, bytes: Bytes| async move {
-            if let Some(header) = header {
-                if header == SSZ_CONTENT_TYPE_HEADER {
-                    return Err(reject::unsupported_media_type(
-                        "The request's content-type is not supported".to_string(),
-                    ));
-                }
-            }
-            Json::decode(bytes)
-                .map_err(|err| reject::custom_deserialize_error(format!("{:?}", err)))
-        })
+        .and_then(|header: Option<String>, bytes: Bytes| async move { decode(header, bytes) })
+}
+
+/// Like `json`, but reads the request body with the `body` filter.
+pub fn json_from<T, F>(body: F) -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Send,
+    F: Filter<Extract = (Bytes,), Error = Rejection> + Clone,
+{
+    warp::header::optional::<String>(CONTENT_TYPE_HEADER)
+        .and(body)
+        .and_then(|header: Option<String>, bytes: Bytes| async move { decode(header, bytes) })
+}
+
+fn decode<T: DeserializeOwned>(header: Option<String>, bytes: Bytes) -> Result<T, Rejection> {
+    if let Some(header) = header {
+        if header == SSZ_CONTENT_TYPE_HEADER {
+            return Err(reject::unsupported_media_type(
+                "The request's content-type is not supported".to_string(),
+            ));
+        }
+    }
+    Json::decode(bytes).map_err(|err| reject::custom_deserialize_error(format!("{:?}", err)))
 }
@@ -1,10 +1,12 @@
 //! This crate contains functions that are common across multiple `warp` HTTP servers in the
 //! Lighthouse project. E.g., the `http_api` and `http_metrics` crates.
 
+pub mod compression;
 pub mod cors;
 pub mod json;
 pub mod metrics;
 pub mod query;
 pub mod reject;
+pub mod reply;
 pub mod task;
 pub mod uor;
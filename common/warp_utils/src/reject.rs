@@ -136,6 +136,15 @@ pub fn invalid_auth(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(InvalidAuthorization(msg))
 }
 
+#[derive(Debug)]
+pub struct Unauthorized(pub String);
+
+impl Reject for Unauthorized {}
+
+pub fn unauthorized(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(Unauthorized(msg))
+}
+
 #[derive(Debug)]
 pub struct UnsupportedMediaType(pub String);
 
@@ -145,6 +154,33 @@ pub fn unsupported_media_type(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(UnsupportedMediaType(msg))
 }
 
+#[derive(Debug)]
+pub struct PayloadTooLarge(pub String);
+
+impl Reject for PayloadTooLarge {}
+
+pub fn payload_too_large(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(PayloadTooLarge(msg))
+}
+
+#[derive(Debug)]
+pub struct TooManyRequests(pub String);
+
+impl Reject for TooManyRequests {}
+
+pub fn too_many_requests(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(TooManyRequests(msg))
+}
+
+#[derive(Debug)]
+pub struct ServiceUnavailable(pub String);
+
+impl Reject for ServiceUnavailable {}
+
+pub fn service_unavailable(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(ServiceUnavailable(msg))
+}
+
 #[derive(Debug)]
 pub struct IndexedBadRequestErrors {
     pub message: String,
@@ -225,6 +261,18 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::NotSynced>() {
         code = StatusCode::SERVICE_UNAVAILABLE;
         message = format!("SERVICE_UNAVAILABLE: beacon node is syncing: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::PayloadTooLarge>() {
+        code = StatusCode::PAYLOAD_TOO_LARGE;
+        message = format!("PAYLOAD_TOO_LARGE: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::TooManyRequests>() {
+        code = StatusCode::TOO_MANY_REQUESTS;
+        message = format!("TOO_MANY_REQUESTS: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::ServiceUnavailable>() {
+        code = StatusCode::SERVICE_UNAVAILABLE;
+        message = format!("SERVICE_UNAVAILABLE: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::Unauthorized>() {
+        code = StatusCode::UNAUTHORIZED;
+        message = format!("UNAUTHORIZED: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
@@ -0,0 +1,70 @@
+//! Replies whose bodies are produced incrementally, for responses too large to buffer.
+use bytes::Bytes;
+use serde::Serialize;
+use std::io::{self, BufWriter, Write};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use warp::http::header::{HeaderValue, CONTENT_TYPE};
+use warp::hyper::Body;
+use warp::reply::Response;
+
+/// The size of the chunks sent to the client.
+const CHUNK_SIZE: usize = 64 * 1024;
+/// The number of chunks which may be buffered before serialization waits for the client.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Returns a response containing the JSON encoding of `item`.
+///
+/// Unlike `warp::reply::json`, the JSON is not buffered in full. It is serialized on a blocking
+/// thread and sent to the client in chunks as it is produced. If serialization fails part-way, the
+/// body is terminated with an error.
+///
+/// Must be called from within a Tokio runtime.
+pub fn json_stream<T: Serialize + Send + 'static>(item: T) -> Response {
+    stream("application/json", move |writer| {
+        serde_json::to_writer(writer, &item).map_err(io::Error::from)
+    })
+}
+
+/// Returns a response with a body produced by `write`, which is run on a blocking thread.
+///
+/// Everything written is sent to the client in chunks as it is produced. If `write` fails, the
+/// body is terminated with an error.
+///
+/// Must be called from within a Tokio runtime.
+pub fn stream<F>(content_type: &'static str, write: F) -> Response
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    tokio::task::spawn_blocking(move || {
+        let mut writer = BufWriter::with_capacity(CHUNK_SIZE, ChannelWriter(tx.clone()));
+        let result = write(&mut writer).and_then(|()| writer.flush());
+        if let Err(e) = result {
+            // The client may have gone away, in which case there is no one to tell.
+            let _ = tx.blocking_send(Err(e));
+        }
+    });
+
+    let mut response = Response::new(Body::wrap_stream(ReceiverStream::new(rx)));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+/// Sends everything written to it as chunks of a response body.
+struct ChannelWriter(mpsc::Sender<Result<Bytes, io::Error>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "response body dropped"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
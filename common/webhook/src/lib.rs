@@ -0,0 +1,328 @@
+//! Sends notifications of noteworthy events to operator-configured webhooks.
+//!
+//! Each notification is POSTed as JSON to every endpoint subscribed to its kind. Failed deliveries
+//! are retried with exponential backoff and, if the endpoint has a secret, the body is signed with
+//! HMAC-SHA256 in the `X-Lighthouse-Signature` header.
+use ring::hmac;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{debug, warn, Logger};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+use types::{Epoch, Hash256, Slot};
+
+/// The header containing the hex-encoded HMAC-SHA256 of the request body.
+pub const SIGNATURE_HEADER: &str = "X-Lighthouse-Signature";
+/// The header containing the kind of the notification.
+pub const EVENT_HEADER: &str = "X-Lighthouse-Event";
+/// Timeout for each delivery attempt.
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+/// The delay before the first retry, doubling after each further failure.
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// The maximum delay between retries.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+pub const DEFAULT_FINALITY_STALL_EPOCHS: u64 = 4;
+pub const DEFAULT_LOW_PEER_COUNT: usize = 10;
+
+/// The kinds of notification which may be sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    MissedProposal,
+    FinalityStall,
+    Slashing,
+    Fork,
+    LowPeerCount,
+}
+
+/// The configuration of the webhook notifier, as loaded from a YAML file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// The endpoints to notify.
+    pub endpoints: Vec<Endpoint>,
+    /// The number of times to retry a failed delivery.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Notify when the finalized epoch is at least this many epochs behind the current epoch.
+    #[serde(default = "default_finality_stall_epochs")]
+    pub finality_stall_epochs: u64,
+    /// Notify when the number of connected peers falls below this value.
+    #[serde(default = "default_low_peer_count")]
+    pub low_peer_count: usize,
+}
+
+fn default_max_retries() -> u32 {
+    DEFAULT_MAX_RETRIES
+}
+
+fn default_finality_stall_epochs() -> u64 {
+    DEFAULT_FINALITY_STALL_EPOCHS
+}
+
+fn default_low_peer_count() -> usize {
+    DEFAULT_LOW_PEER_COUNT
+}
+
+impl Config {
+    /// Load the configuration from the YAML file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Unable to open webhook config {:?}: {:?}", path, e))?;
+        let config: Self = serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse webhook config {:?}: {:?}", path, e))?;
+        if config.endpoints.is_empty() {
+            return Err(format!("Webhook config {:?} has no endpoints", path));
+        }
+        Ok(config)
+    }
+}
+
+/// A webhook to be notified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub url: SensitiveUrl,
+    /// The key used to sign request bodies, if any.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// The kinds of notification to send. All kinds are sent if empty.
+    #[serde(default)]
+    pub events: Vec<NotificationKind>,
+}
+
+impl Endpoint {
+    pub fn is_subscribed(&self, kind: NotificationKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+/// An event of interest to the operator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum Notification {
+    /// A monitored validator did not produce a block in its slot.
+    MissedProposal {
+        #[serde(with = "serde_utils::quoted_u64")]
+        validator_index: u64,
+        slot: Slot,
+    },
+    /// The chain has not finalized for at least `finality_stall_epochs`.
+    FinalityStall {
+        current_epoch: Epoch,
+        finalized_epoch: Epoch,
+    },
+    /// A slashing of the given validators was observed.
+    Slashing {
+        kind: SlashingKind,
+        #[serde(with = "serde_utils::quoted_u64_vec")]
+        validator_indices: Vec<u64>,
+    },
+    /// The head of the chain switched to a competing fork.
+    Fork {
+        slot: Slot,
+        #[serde(with = "serde_utils::quoted_u64")]
+        depth: u64,
+        old_head_block: Hash256,
+        new_head_block: Hash256,
+    },
+    /// The number of connected peers fell below `low_peer_count`.
+    LowPeerCount { peer_count: usize, threshold: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingKind {
+    Proposer,
+    Attester,
+}
+
+impl Notification {
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            Notification::MissedProposal { .. } => NotificationKind::MissedProposal,
+            Notification::FinalityStall { .. } => NotificationKind::FinalityStall,
+            Notification::Slashing { .. } => NotificationKind::Slashing,
+            Notification::Fork { .. } => NotificationKind::Fork,
+            Notification::LowPeerCount { .. } => NotificationKind::LowPeerCount,
+        }
+    }
+}
+
+/// The body of each request sent to a webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Payload {
+    /// Seconds since the UNIX epoch at which the notification was raised.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub notification: Notification,
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of `body` under `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hex::encode(hmac::sign(&key, body).as_ref())
+}
+
+/// Delivers notifications to the configured endpoints.
+#[derive(Clone)]
+pub struct WebhookClient {
+    client: reqwest::Client,
+    endpoints: Arc<Vec<Endpoint>>,
+    max_retries: u32,
+    executor: TaskExecutor,
+    log: Logger,
+}
+
+impl WebhookClient {
+    pub fn new(config: &Config, executor: TaskExecutor, log: Logger) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoints: Arc::new(config.endpoints.clone()),
+            max_retries: config.max_retries,
+            executor,
+            log,
+        }
+    }
+
+    /// Spawn a task delivering `notification` to each subscribed endpoint.
+    pub fn notify(&self, notification: Notification) {
+        let kind = notification.kind();
+        let payload = Payload {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            notification,
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => Arc::new(body),
+            Err(e) => {
+                warn!(self.log, "Unable to serialize webhook notification"; "error" => ?e);
+                return;
+            }
+        };
+
+        for endpoint in self.endpoints.iter().filter(|e| e.is_subscribed(kind)) {
+            let client = self.clone();
+            let endpoint = endpoint.clone();
+            let body = body.clone();
+            self.executor.spawn(
+                async move { client.deliver(&endpoint, kind, &body).await },
+                "webhook_delivery",
+            );
+        }
+    }
+
+    /// POST `body` to `endpoint`, retrying with exponential backoff on failure.
+    async fn deliver(&self, endpoint: &Endpoint, kind: NotificationKind, body: &[u8]) {
+        let kind_str = serde_json::to_value(kind)
+            .ok()
+            .and_then(|v| v.as_str().map(ToString::to_string))
+            .unwrap_or_default();
+        let signature = endpoint
+            .secret
+            .as_ref()
+            .map(|secret| format!("sha256={}", sign(secret, body)));
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 0..=self.max_retries {
+            let mut request = self
+                .client
+                .post(endpoint.url.full.clone())
+                .timeout(TIMEOUT)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, &kind_str)
+                .body(body.to_vec());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!(
+                        self.log,
+                        "Sent webhook notification";
+                        "event" => &kind_str,
+                        "endpoint" => %endpoint.url,
+                    );
+                    return;
+                }
+                Ok(response) => format!("status {}", response.status()),
+                Err(e) => e.to_string(),
+            };
+
+            if attempt == self.max_retries {
+                warn!(
+                    self.log,
+                    "Failed to send webhook notification";
+                    "event" => &kind_str,
+                    "endpoint" => %endpoint.url,
+                    "attempts" => attempt + 1,
+                    "error" => error,
+                );
+            } else {
+                debug!(
+                    self.log,
+                    "Retrying webhook notification";
+                    "event" => &kind_str,
+                    "endpoint" => %endpoint.url,
+                    "error" => error,
+                    "delay_ms" => backoff.as_millis(),
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_sha256() {
+        // RFC 4231, test case 2.
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn payload_json() {
+        let payload = Payload {
+            timestamp: 1,
+            notification: Notification::MissedProposal {
+                validator_index: 42,
+                slot: Slot::new(7),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            serde_json::json!({
+                "timestamp": 1,
+                "event": "missed_proposal",
+                "data": {
+                    "validator_index": "42",
+                    "slot": "7"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_config() {
+        let config: Config = serde_yaml::from_str(
+            "endpoints:\n  - url: http://localhost:8000/hook\n    events: [slashing, fork]\n",
+        )
+        .unwrap();
+        assert_eq!(config.max_retries, DEFAULT_MAX_RETRIES);
+        assert!(config.endpoints[0].is_subscribed(NotificationKind::Fork));
+        assert!(!config.endpoints[0].is_subscribed(NotificationKind::LowPeerCount));
+    }
+}
@@ -0,0 +1,671 @@
+//! Verification of light client messages, following the sync protocol of the consensus specs.
+//!
+//! This crate only depends on `types` and a few small utility crates, so that light client
+//! updates can be verified without the beacon node. Updates of any fork are converted into an
+//! `Update`, and applied to a `LightClientStore`:
+//!
+//! ```ignore
+//! let mut store =
+//!     LightClientStore::new(trusted_block_root, &bootstrap, genesis_validators_root, spec)?;
+//! let finalized = store.process_update(finality_update.into(), current_slot)?;
+//! let header = store.finalized_header();
+//! ```
+//!
+//! The store is initialized from a bootstrap for a trusted block root, which provides the sync
+//! committee of the period of that block. Every later header is only accepted with the signature
+//! of a known sync committee, and the committee of each following period is only accepted with a
+//! proof against a header which has been signed and finalized.
+//!
+//! Unlike the specs, the store does not keep the best update of a period to force an update when
+//! finality stalls, so it only advances while the chain is finalizing.
+use merkle_proof::verify_merkle_proof;
+use safe_arith::ArithError;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::{
+    light_client_update::{
+        CURRENT_SYNC_COMMITTEE_INDEX, CURRENT_SYNC_COMMITTEE_PROOF_LEN, EXECUTION_PAYLOAD_INDEX,
+        EXECUTION_PAYLOAD_PROOF_LEN, FINALIZED_ROOT_INDEX, FINALIZED_ROOT_PROOF_LEN,
+        NEXT_SYNC_COMMITTEE_INDEX, NEXT_SYNC_COMMITTEE_PROOF_LEN,
+    },
+    BeaconBlockHeader, ChainSpec, Domain, EthSpec, ExecutionBlockHash, Hash256,
+    LightClientBootstrap, LightClientFinalityUpdate, LightClientHeader,
+    LightClientOptimisticUpdate, LightClientUpdate, PublicKey, SignedRoot, Slot, SyncAggregate,
+    SyncCommittee,
+};
+
+/// The reasons for which a bootstrap or update can be rejected.
+#[derive(Debug)]
+pub enum Error {
+    UnexpectedBlockRoot {
+        expected: Hash256,
+        found: Hash256,
+    },
+    InvalidExecutionBranch,
+    InvalidCurrentSyncCommitteeBranch,
+    InvalidNextSyncCommitteeBranch,
+    InvalidFinalityBranch,
+    InvalidSyncCommitteePubkey,
+    InsufficientParticipants(usize),
+    InvalidSlots {
+        signature_slot: Slot,
+        attested_slot: Slot,
+    },
+    UnknownSyncCommittee {
+        signature_period: u64,
+        store_period: u64,
+    },
+    ConflictingNextSyncCommittee,
+    InvalidSignature,
+    Arith(ArithError),
+}
+
+impl From<ArithError> for Error {
+    fn from(e: ArithError) -> Self {
+        Error::Arith(e)
+    }
+}
+
+/// The execution payload header of a verified header.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifiedExecution {
+    pub block_hash: ExecutionBlockHash,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub block_number: u64,
+    pub state_root: Hash256,
+}
+
+/// A beacon block header, and the execution payload header of the block from Capella, which have
+/// been checked against each other.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifiedHeader {
+    pub root: Hash256,
+    pub beacon: BeaconBlockHeader,
+    pub execution: Option<VerifiedExecution>,
+}
+
+/// The parts of a light client update, finality update or optimistic update, which are
+/// verified in the same way.
+pub struct Update<E: EthSpec> {
+    pub attested_header: LightClientHeader<E>,
+    pub next_sync_committee: Option<(Arc<SyncCommittee<E>>, Vec<Hash256>)>,
+    pub finality: Option<(LightClientHeader<E>, Vec<Hash256>)>,
+    pub sync_aggregate: SyncAggregate<E>,
+    pub signature_slot: Slot,
+}
+
+impl<E: EthSpec> From<LightClientUpdate<E>> for Update<E> {
+    fn from(update: LightClientUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header(),
+            next_sync_committee: Some((
+                update.next_sync_committee().clone(),
+                update.next_sync_committee_branch().to_vec(),
+            )),
+            finality: Some((update.finalized_header(), update.finality_branch().to_vec())),
+            sync_aggregate: update.sync_aggregate().clone(),
+            signature_slot: *update.signature_slot(),
+        }
+    }
+}
+
+impl<E: EthSpec> From<LightClientFinalityUpdate<E>> for Update<E> {
+    fn from(update: LightClientFinalityUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header(),
+            next_sync_committee: None,
+            finality: Some((update.finalized_header(), update.finality_branch().to_vec())),
+            sync_aggregate: update.sync_aggregate().clone(),
+            signature_slot: *update.signature_slot(),
+        }
+    }
+}
+
+impl<E: EthSpec> From<LightClientOptimisticUpdate<E>> for Update<E> {
+    fn from(update: LightClientOptimisticUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header(),
+            next_sync_committee: None,
+            finality: None,
+            sync_aggregate: update.sync_aggregate().clone(),
+            signature_slot: *update.signature_slot(),
+        }
+    }
+}
+
+/// A sync committee with its public keys decompressed for signature verification.
+struct VerifiedSyncCommittee {
+    root: Hash256,
+    pubkeys: Vec<PublicKey>,
+}
+
+impl VerifiedSyncCommittee {
+    fn new<E: EthSpec>(committee: &SyncCommittee<E>) -> Result<Self, Error> {
+        let pubkeys = committee
+            .pubkeys
+            .iter()
+            .map(|pubkey| pubkey.decompress())
+            .collect::<Result<_, _>>()
+            .map_err(|_| Error::InvalidSyncCommitteePubkey)?;
+        Ok(Self {
+            root: committee.tree_hash_root(),
+            pubkeys,
+        })
+    }
+}
+
+/// The headers and sync committees of a light client, which have all been verified from a
+/// trusted block root.
+pub struct LightClientStore<E: EthSpec> {
+    finalized_header: VerifiedHeader,
+    optimistic_header: VerifiedHeader,
+    current_sync_committee: VerifiedSyncCommittee,
+    next_sync_committee: Option<VerifiedSyncCommittee>,
+    genesis_validators_root: Hash256,
+    spec: Arc<ChainSpec>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> LightClientStore<E> {
+    /// Initialize the store from a `bootstrap` for the block with `trusted_block_root`.
+    pub fn new(
+        trusted_block_root: Hash256,
+        bootstrap: &LightClientBootstrap<E>,
+        genesis_validators_root: Hash256,
+        spec: Arc<ChainSpec>,
+    ) -> Result<Self, Error> {
+        let header = verify_header(&bootstrap.header())?;
+        if header.root != trusted_block_root {
+            return Err(Error::UnexpectedBlockRoot {
+                expected: trusted_block_root,
+                found: header.root,
+            });
+        }
+
+        let current_sync_committee = bootstrap.current_sync_committee();
+        if !verify_merkle_proof(
+            current_sync_committee.tree_hash_root(),
+            bootstrap.current_sync_committee_branch(),
+            CURRENT_SYNC_COMMITTEE_PROOF_LEN,
+            subtree_index(
+                CURRENT_SYNC_COMMITTEE_INDEX,
+                CURRENT_SYNC_COMMITTEE_PROOF_LEN,
+            ),
+            header.beacon.state_root,
+        ) {
+            return Err(Error::InvalidCurrentSyncCommitteeBranch);
+        }
+
+        Ok(Self {
+            finalized_header: header.clone(),
+            optimistic_header: header,
+            current_sync_committee: VerifiedSyncCommittee::new(current_sync_committee)?,
+            next_sync_committee: None,
+            genesis_validators_root,
+            spec,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The latest header which has been signed by a supermajority of its sync committee and
+    /// finalized.
+    pub fn finalized_header(&self) -> &VerifiedHeader {
+        &self.finalized_header
+    }
+
+    /// The latest header which has been signed by at least half of its sync committee.
+    pub fn optimistic_header(&self) -> &VerifiedHeader {
+        &self.optimistic_header
+    }
+
+    /// The sync committee period of the finalized header.
+    pub fn period(&self) -> Result<u64, Error> {
+        self.period_at(self.finalized_header.beacon.slot)
+    }
+
+    /// Returns whether the sync committee of the period after that of the finalized header is
+    /// known.
+    pub fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    /// The sync committee period of `slot`.
+    pub fn period_at(&self, slot: Slot) -> Result<u64, Error> {
+        Ok(slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(&self.spec)?)
+    }
+
+    /// Verify `update` and apply it to the store, returning whether the finalized header changed.
+    pub fn process_update(&mut self, update: Update<E>, current_slot: Slot) -> Result<bool, Error> {
+        let participants = update.sync_aggregate.sync_committee_bits.num_set_bits();
+        if (participants as u64) < self.spec.min_sync_committee_participants {
+            return Err(Error::InsufficientParticipants(participants));
+        }
+
+        let attested_header = verify_header(&update.attested_header)?;
+        if update.signature_slot > current_slot
+            || update.signature_slot <= attested_header.beacon.slot
+        {
+            return Err(Error::InvalidSlots {
+                signature_slot: update.signature_slot,
+                attested_slot: attested_header.beacon.slot,
+            });
+        }
+
+        let store_period = self.period()?;
+        let signature_period = self.period_at(update.signature_slot)?;
+        let sync_committee = if signature_period == store_period {
+            &self.current_sync_committee
+        } else if signature_period == store_period + 1 {
+            self.next_sync_committee
+                .as_ref()
+                .ok_or(Error::UnknownSyncCommittee {
+                    signature_period,
+                    store_period,
+                })?
+        } else {
+            return Err(Error::UnknownSyncCommittee {
+                signature_period,
+                store_period,
+            });
+        };
+
+        // A genesis finalized header has a zero root in the state, and nothing to finalize.
+        let finalized_header = match update.finality {
+            Some((header, branch)) if header.beacon().slot != self.spec.genesis_slot => {
+                let header = verify_header(&header)?;
+                if header.beacon.slot > attested_header.beacon.slot
+                    || !verify_merkle_proof(
+                        header.root,
+                        &branch,
+                        FINALIZED_ROOT_PROOF_LEN,
+                        subtree_index(FINALIZED_ROOT_INDEX, FINALIZED_ROOT_PROOF_LEN),
+                        attested_header.beacon.state_root,
+                    )
+                {
+                    return Err(Error::InvalidFinalityBranch);
+                }
+                Some(header)
+            }
+            _ => None,
+        };
+
+        let next_sync_committee = match update.next_sync_committee {
+            Some((committee, branch)) => {
+                if !verify_merkle_proof(
+                    committee.tree_hash_root(),
+                    &branch,
+                    NEXT_SYNC_COMMITTEE_PROOF_LEN,
+                    subtree_index(NEXT_SYNC_COMMITTEE_INDEX, NEXT_SYNC_COMMITTEE_PROOF_LEN),
+                    attested_header.beacon.state_root,
+                ) {
+                    return Err(Error::InvalidNextSyncCommitteeBranch);
+                }
+                if let Some(known) = &self.next_sync_committee {
+                    if self.period_at(attested_header.beacon.slot)? == store_period
+                        && known.root != committee.tree_hash_root()
+                    {
+                        return Err(Error::ConflictingNextSyncCommittee);
+                    }
+                }
+                Some(committee)
+            }
+            None => None,
+        };
+
+        // The fork version is that of the slot before the signature slot, in which the sync
+        // committee signed the attested header.
+        let signature_epoch = update
+            .signature_slot
+            .saturating_sub(1u64)
+            .epoch(E::slots_per_epoch());
+        let domain = self.spec.get_domain(
+            signature_epoch,
+            Domain::SyncCommittee,
+            &self.spec.fork_at_epoch(signature_epoch),
+            self.genesis_validators_root,
+        );
+        let signing_root = attested_header.beacon.signing_root(domain);
+        let participant_pubkeys = sync_committee
+            .pubkeys
+            .iter()
+            .zip(update.sync_aggregate.sync_committee_bits.iter())
+            .filter_map(|(pubkey, bit)| bit.then_some(pubkey))
+            .collect::<Vec<_>>();
+        if !update
+            .sync_aggregate
+            .sync_committee_signature
+            .fast_aggregate_verify(signing_root, &participant_pubkeys)
+        {
+            return Err(Error::InvalidSignature);
+        }
+
+        let committee_size = E::sync_committee_size();
+        if participants * 2 >= committee_size
+            && attested_header.beacon.slot > self.optimistic_header.beacon.slot
+        {
+            self.optimistic_header = attested_header.clone();
+        }
+
+        let Some(finalized_header) = finalized_header else {
+            return Ok(false);
+        };
+        if participants * 3 < committee_size * 2 {
+            return Ok(false);
+        }
+
+        // The next sync committee of the update is that of the period after the attested header,
+        // so it is only the next committee of the store if the finalized header is in the same
+        // period.
+        let finalized_period = self.period_at(finalized_header.beacon.slot)?;
+        let next_sync_committee = match next_sync_committee {
+            Some(committee) if self.period_at(attested_header.beacon.slot)? == finalized_period => {
+                Some(VerifiedSyncCommittee::new(&committee)?)
+            }
+            _ => None,
+        };
+        if finalized_period == store_period + 1 {
+            let Some(current_sync_committee) = self.next_sync_committee.take() else {
+                return Ok(false);
+            };
+            self.current_sync_committee = current_sync_committee;
+            self.next_sync_committee = next_sync_committee;
+        } else if finalized_period == store_period {
+            if self.next_sync_committee.is_none() {
+                self.next_sync_committee = next_sync_committee;
+            }
+        } else {
+            return Ok(false);
+        }
+
+        if finalized_header.beacon.slot <= self.finalized_header.beacon.slot {
+            return Ok(false);
+        }
+        if finalized_header.beacon.slot > self.optimistic_header.beacon.slot {
+            self.optimistic_header = finalized_header.clone();
+        }
+        self.finalized_header = finalized_header;
+        Ok(true)
+    }
+}
+
+/// Returns the index of the generalized index `gindex` among the nodes at its `depth`.
+fn subtree_index(gindex: usize, depth: usize) -> usize {
+    gindex % (1 << depth)
+}
+
+/// Check the execution payload header of `header` against its beacon block, as in
+/// `is_valid_light_client_header` of the specs.
+pub fn verify_header<E: EthSpec>(header: &LightClientHeader<E>) -> Result<VerifiedHeader, Error> {
+    let verify_execution = |leaf: Hash256, branch: &[Hash256], body_root: Hash256| {
+        if verify_merkle_proof(
+            leaf,
+            branch,
+            EXECUTION_PAYLOAD_PROOF_LEN,
+            subtree_index(EXECUTION_PAYLOAD_INDEX, EXECUTION_PAYLOAD_PROOF_LEN),
+            body_root,
+        ) {
+            Ok(())
+        } else {
+            Err(Error::InvalidExecutionBranch)
+        }
+    };
+
+    let (beacon, execution) = match header {
+        LightClientHeader::Altair(header) => (header.beacon.clone(), None),
+        LightClientHeader::Capella(header) => {
+            verify_execution(
+                header.execution.tree_hash_root(),
+                &header.execution_branch,
+                header.beacon.body_root,
+            )?;
+            let execution = VerifiedExecution {
+                block_hash: header.execution.block_hash,
+                block_number: header.execution.block_number,
+                state_root: header.execution.state_root,
+            };
+            (header.beacon.clone(), Some(execution))
+        }
+        LightClientHeader::Deneb(header) => {
+            verify_execution(
+                header.execution.tree_hash_root(),
+                &header.execution_branch,
+                header.beacon.body_root,
+            )?;
+            let execution = VerifiedExecution {
+                block_hash: header.execution.block_hash,
+                block_number: header.execution.block_number,
+                state_root: header.execution.state_root,
+            };
+            (header.beacon.clone(), Some(execution))
+        }
+        LightClientHeader::Electra(header) => {
+            verify_execution(
+                header.execution.tree_hash_root(),
+                &header.execution_branch,
+                header.beacon.body_root,
+            )?;
+            let execution = VerifiedExecution {
+                block_hash: header.execution.block_hash,
+                block_number: header.execution.block_number,
+                state_root: header.execution.state_root,
+            };
+            (header.beacon.clone(), Some(execution))
+        }
+    };
+
+    Ok(VerifiedHeader {
+        root: beacon.canonical_root(),
+        beacon,
+        execution,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+        SyncCommitteeStrategy,
+    };
+    use types::{
+        test_utils::{SeedableRng, TestRandom, XorShiftRng},
+        ForkName, LightClientFinalityUpdateDeneb, LightClientHeaderDeneb,
+        LightClientOptimisticUpdateDeneb, LightClientUpdateDeneb, MinimalEthSpec,
+        SignedBeaconBlock,
+    };
+
+    type E = MinimalEthSpec;
+
+    struct Tester {
+        harness: BeaconChainHarness<EphemeralHarnessType<E>>,
+        spec: Arc<ChainSpec>,
+    }
+
+    impl Tester {
+        async fn new() -> Self {
+            let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+            let harness = BeaconChainHarness::builder(E::default())
+                .spec(spec.clone())
+                .deterministic_keypairs(E::sync_committee_size())
+                .fresh_ephemeral_store()
+                .build();
+            harness.advance_slot();
+            harness
+                .extend_chain_with_sync(
+                    E::slots_per_epoch() as usize,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                    SyncCommitteeStrategy::AllValidators,
+                )
+                .await;
+            Self {
+                harness,
+                spec: Arc::new(spec),
+            }
+        }
+
+        async fn block(&self, root: Hash256) -> SignedBeaconBlock<E> {
+            self.harness.chain.get_block(&root).await.unwrap().unwrap()
+        }
+
+        fn bootstrap(&self, block: &SignedBeaconBlock<E>) -> LightClientBootstrap<E> {
+            let mut state = self
+                .harness
+                .chain
+                .get_state(&block.state_root(), Some(block.slot()))
+                .unwrap()
+                .unwrap();
+            LightClientBootstrap::from_beacon_state(&mut state, block, &self.spec).unwrap()
+        }
+
+        fn store(&self, block: &SignedBeaconBlock<E>) -> Result<LightClientStore<E>, Error> {
+            LightClientStore::new(
+                block.canonical_root(),
+                &self.bootstrap(block),
+                self.harness.chain.genesis_validators_root,
+                self.spec.clone(),
+            )
+        }
+
+        /// Returns the head block, its parent and its grandparent.
+        async fn head_blocks(&self) -> [SignedBeaconBlock<E>; 3] {
+            let head = self.harness.chain.head_beacon_block();
+            let parent = self.block(head.parent_root()).await;
+            let grandparent = self.block(parent.parent_root()).await;
+            [(*head).clone(), parent, grandparent]
+        }
+    }
+
+    #[test]
+    fn updates_of_every_kind() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let deneb = |header: &LightClientHeaderDeneb<E>| LightClientHeader::Deneb(header.clone());
+
+        let full = LightClientUpdateDeneb::<E>::random_for_test(&mut rng);
+        let update = Update::from(LightClientUpdate::Deneb(full.clone()));
+        assert_eq!(update.attested_header, deneb(&full.attested_header));
+        assert_eq!(
+            update.next_sync_committee,
+            Some((
+                full.next_sync_committee.clone(),
+                full.next_sync_committee_branch.to_vec()
+            ))
+        );
+        assert_eq!(
+            update.finality,
+            Some((deneb(&full.finalized_header), full.finality_branch.to_vec()))
+        );
+        assert_eq!(update.sync_aggregate, full.sync_aggregate);
+        assert_eq!(update.signature_slot, full.signature_slot);
+
+        let finality = LightClientFinalityUpdateDeneb::<E>::random_for_test(&mut rng);
+        let update = Update::from(LightClientFinalityUpdate::Deneb(finality.clone()));
+        assert_eq!(update.attested_header, deneb(&finality.attested_header));
+        assert_eq!(update.next_sync_committee, None);
+        assert_eq!(
+            update.finality,
+            Some((
+                deneb(&finality.finalized_header),
+                finality.finality_branch.to_vec()
+            ))
+        );
+        assert_eq!(update.signature_slot, finality.signature_slot);
+
+        let optimistic = LightClientOptimisticUpdateDeneb::<E>::random_for_test(&mut rng);
+        let update = Update::from(LightClientOptimisticUpdate::Deneb(optimistic.clone()));
+        assert_eq!(update.attested_header, deneb(&optimistic.attested_header));
+        assert_eq!(update.next_sync_committee, None);
+        assert_eq!(update.finality, None);
+        assert_eq!(update.signature_slot, optimistic.signature_slot);
+    }
+
+    #[tokio::test]
+    async fn bootstrap() {
+        let tester = Tester::new().await;
+        let [head, ..] = tester.head_blocks().await;
+
+        let store = tester.store(&head).unwrap();
+        assert_eq!(store.finalized_header().root, head.canonical_root());
+        assert_eq!(store.optimistic_header().root, head.canonical_root());
+        assert_eq!(store.finalized_header().execution, None);
+        assert!(!store.is_next_sync_committee_known());
+    }
+
+    #[tokio::test]
+    async fn bootstrap_unexpected_root() {
+        let tester = Tester::new().await;
+        let [head, parent, _] = tester.head_blocks().await;
+
+        let result = LightClientStore::new(
+            parent.canonical_root(),
+            &tester.bootstrap(&head),
+            tester.harness.chain.genesis_validators_root,
+            tester.spec.clone(),
+        );
+        assert!(matches!(result, Err(Error::UnexpectedBlockRoot { .. })));
+    }
+
+    #[tokio::test]
+    async fn bootstrap_invalid_sync_committee_branch() {
+        let tester = Tester::new().await;
+        let [head, ..] = tester.head_blocks().await;
+
+        let mut bootstrap = tester.bootstrap(&head);
+        match &mut bootstrap {
+            LightClientBootstrap::Altair(bootstrap) => {
+                bootstrap.current_sync_committee_branch[0] = Hash256::repeat_byte(1)
+            }
+            _ => panic!("bootstrap should be from Altair"),
+        }
+        let result = LightClientStore::new(
+            head.canonical_root(),
+            &bootstrap,
+            tester.harness.chain.genesis_validators_root,
+            tester.spec.clone(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidCurrentSyncCommitteeBranch)
+        ));
+    }
+
+    #[tokio::test]
+    async fn optimistic_update() {
+        let tester = Tester::new().await;
+        let [head, parent, grandparent] = tester.head_blocks().await;
+        let mut store = tester.store(&grandparent).unwrap();
+
+        let sync_aggregate = head.message().body().sync_aggregate().unwrap().clone();
+        let update = LightClientOptimisticUpdate::new(
+            &parent,
+            sync_aggregate.clone(),
+            head.slot(),
+            &tester.spec,
+        )
+        .unwrap();
+
+        // An update can't be signed in the future.
+        let result = store.process_update(update.clone().into(), parent.slot());
+        assert!(matches!(result, Err(Error::InvalidSlots { .. })));
+
+        // An update signed over another header is invalid.
+        let invalid = LightClientOptimisticUpdate::new(
+            &grandparent,
+            sync_aggregate,
+            head.slot(),
+            &tester.spec,
+        )
+        .unwrap();
+        let result = store.process_update(invalid.into(), head.slot());
+        assert!(matches!(result, Err(Error::InvalidSignature)));
+        assert_eq!(store.optimistic_header().root, grandparent.canonical_root());
+
+        assert!(!store.process_update(update.into(), head.slot()).unwrap());
+        assert_eq!(store.optimistic_header().root, parent.canonical_root());
+        assert_eq!(store.finalized_header().root, grandparent.canonical_root());
+    }
+}
@@ -351,6 +351,10 @@ impl DisallowedReOrgOffsets {
         }
         Ok(Self { offsets })
     }
+
+    pub fn offsets(&self) -> &[u64] {
+        &self.offsets
+    }
 }
 
 #[derive(PartialEq)]
@@ -429,6 +433,41 @@ impl ProtoArrayForkChoice {
             .map_err(|e| format!("Failed to process invalid payload: {:?}", e))
     }
 
+    /// Returns the number of slots by which `head_block_root` would be re-orged if `op` were
+    /// processed, i.e. the distance from `head_block_root` back to its latest ancestor which would
+    /// not be invalid.
+    ///
+    /// Returns `0` if `head_block_root` would not be invalidated. The invalidation is applied to a
+    /// copy of the proto array, so this has no effect on fork choice.
+    pub fn execution_payload_invalidation_depth<E: EthSpec>(
+        &self,
+        op: &InvalidationOperation,
+        head_block_root: Hash256,
+    ) -> Result<u64, String> {
+        let mut proto_array = self.proto_array.clone();
+        proto_array
+            .propagate_execution_payload_invalidation::<E>(op)
+            .map_err(|e| format!("Failed to process invalid payload: {:?}", e))?;
+
+        let mut nodes = proto_array.iter_nodes(&head_block_root);
+        let head = nodes
+            .next()
+            .ok_or_else(|| format!("Head block {head_block_root:?} is unknown"))?;
+        if !head.execution_status.is_invalid() {
+            return Ok(0);
+        }
+        let valid_ancestor_slot = nodes
+            .find(|node| !node.execution_status.is_invalid())
+            .map_or(
+                proto_array
+                    .finalized_checkpoint
+                    .epoch
+                    .start_slot(E::slots_per_epoch()),
+                |node| node.slot,
+            );
+        Ok(head.slot.saturating_sub(valid_ancestor_slot).as_u64())
+    }
+
     pub fn process_attestation(
         &mut self,
         validator_index: usize,
@@ -855,6 +894,17 @@ impl ProtoArrayForkChoice {
         }
     }
 
+    /// Returns an iterator over the latest messages of all validators known to fork choice, as
+    /// `(validator_index, block_root, epoch)` tuples.
+    pub fn iter_latest_messages(&self) -> impl Iterator<Item = (usize, Hash256, Epoch)> + '_ {
+        self.votes
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, vote)| **vote != VoteTracker::default())
+            .map(|(validator_index, vote)| (validator_index, vote.next_root, vote.next_epoch))
+    }
+
     /// See `ProtoArray::iter_nodes`
     pub fn iter_nodes<'a>(&'a self, block_root: &Hash256) -> Iter<'a> {
         self.proto_array.iter_nodes(block_root)
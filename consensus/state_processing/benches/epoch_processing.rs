@@ -0,0 +1,111 @@
+//! Compares sequential and parallel single-pass epoch processing.
+//!
+//! Used to choose `DEFAULT_PARALLEL_THRESHOLD`, which should sit around the smallest validator
+//! count at which the parallel variant is faster.
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use state_processing::per_epoch_processing::single_pass::{
+    process_epoch_single_pass, SinglePassConfig,
+};
+use state_processing::upgrade::upgrade_to_altair;
+use types::{
+    consts::altair::NUM_FLAG_INDICES, test_utils::generate_deterministic_keypairs, BeaconState,
+    ChainSpec, Epoch, Eth1Data, EthSpec, List, MainnetEthSpec, ParticipationFlags, Validator,
+};
+
+/// The number of distinct keypairs, which are re-used across validators to keep setup fast.
+const KEYPAIR_COUNT: usize = 1_024;
+
+const VALIDATOR_COUNTS: [usize; 4] = [65_536, 262_144, 1_048_576, 2_097_152];
+
+fn get_state<E: EthSpec>(validator_count: usize, spec: &ChainSpec) -> BeaconState<E> {
+    let pubkeys = generate_deterministic_keypairs(KEYPAIR_COUNT)
+        .into_iter()
+        .map(|keypair| keypair.pk.compress())
+        .collect::<Vec<_>>();
+
+    let mut state = BeaconState::new(0, Eth1Data::default(), spec);
+    *state.validators_mut() = List::new(
+        (0..validator_count)
+            .map(|i| Validator {
+                pubkey: pubkeys[i % KEYPAIR_COUNT],
+                withdrawal_credentials: Default::default(),
+                effective_balance: spec.max_effective_balance,
+                slashed: false,
+                activation_eligibility_epoch: Epoch::new(0),
+                activation_epoch: Epoch::new(0),
+                exit_epoch: spec.far_future_epoch,
+                withdrawable_epoch: spec.far_future_epoch,
+            })
+            .collect(),
+    )
+    .expect("should set validators");
+    *state.balances_mut() =
+        List::new(vec![spec.max_effective_balance; validator_count]).expect("should set balances");
+
+    upgrade_to_altair(&mut state, spec).expect("should upgrade state");
+
+    // Have every validator other than every tenth one participate, so that both rewards and
+    // penalties apply.
+    let mut participating = ParticipationFlags::default();
+    for flag_index in 0..NUM_FLAG_INDICES {
+        participating.add_flag(flag_index).expect("should add flag");
+    }
+    let participation = List::new(
+        (0..validator_count)
+            .map(|i| {
+                if i % 10 == 0 {
+                    ParticipationFlags::default()
+                } else {
+                    participating
+                }
+            })
+            .collect(),
+    )
+    .expect("should create participation");
+    *state
+        .previous_epoch_participation_mut()
+        .expect("should be altair") = participation.clone();
+    *state
+        .current_epoch_participation_mut()
+        .expect("should be altair") = participation;
+
+    *state.slot_mut() = Epoch::new(3).end_slot(E::slots_per_epoch());
+    state
+}
+
+fn all_benches(c: &mut Criterion) {
+    let spec = MainnetEthSpec::default_spec();
+
+    let mut g = c.benchmark_group("epoch_processing");
+    g.sample_size(10);
+
+    for validator_count in VALIDATOR_COUNTS {
+        let state = get_state::<MainnetEthSpec>(validator_count, &spec);
+
+        for (name, parallel_threshold) in [("sequential", usize::MAX), ("parallel", 0)] {
+            g.bench_with_input(
+                BenchmarkId::new(format!("single_pass/{}", name), validator_count),
+                &state,
+                |b, state| {
+                    b.iter_batched_ref(
+                        || state.clone(),
+                        |state| {
+                            black_box(process_epoch_single_pass(
+                                state,
+                                &spec,
+                                SinglePassConfig {
+                                    parallel_threshold,
+                                    ..SinglePassConfig::enable_all()
+                                },
+                            ))
+                        },
+                        BatchSize::LargeInput,
+                    )
+                },
+            );
+        }
+    }
+}
+
+criterion_group!(benches, all_benches);
+criterion_main!(benches);
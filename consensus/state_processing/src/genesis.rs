@@ -18,6 +18,19 @@ pub fn initialize_beacon_state_from_eth1<E: EthSpec>(
     execution_payload_header: Option<ExecutionPayloadHeader<E>>,
     spec: &ChainSpec,
 ) -> Result<BeaconState<E>, BlockProcessingError> {
+    // The header is only used if it is for the fork at genesis.
+    if let Some(header) = &execution_payload_header {
+        let fork_at_slot = spec.fork_name_at_epoch(E::genesis_epoch());
+        if header.fork_name() != fork_at_slot {
+            return Err(BlockProcessingError::InconsistentStateFork(
+                InconsistentFork {
+                    fork_at_slot,
+                    object_fork: header.fork_name(),
+                },
+            ));
+        }
+    }
+
     let genesis_time = eth2_genesis_time(eth1_timestamp, spec)?;
     let eth1_data = Eth1Data {
         // Temporary deposit root
@@ -163,6 +163,39 @@ where
         let verified_proposer_index =
             Some(ctxt.get_proposer_index_from_epoch_state(self.state, self.spec)?);
         self.include_randao_reveal(block, verified_proposer_index)?;
+        self.include_operation_signatures(block, ctxt)
+    }
+
+    /// Includes all signatures on the block (except the deposit signatures) for verification,
+    /// using the `proposer_index` declared by the block rather than one computed from `self.state`.
+    ///
+    /// This is useful when `self.state` has the correct committees for the block's epoch but might
+    /// not have the correct proposer shuffling (e.g. when the state was advanced through an epoch
+    /// without applying its blocks).
+    ///
+    /// The `ctxt` is *not* primed with the proposer index. The caller must ensure the declared
+    /// proposer index is later checked against the proposer shuffling, as happens in
+    /// `process_block_header`.
+    pub fn include_all_signatures_with_declared_proposer<Payload: AbstractExecPayload<E>>(
+        &mut self,
+        block: &'a SignedBeaconBlock<E, Payload>,
+        ctxt: &mut ConsensusContext<E>,
+    ) -> Result<()> {
+        let block_root = Some(ctxt.get_current_block_root(block)?);
+        let declared_proposer_index = Some(block.message().proposer_index());
+
+        self.include_block_proposal(block, block_root, declared_proposer_index)?;
+        self.include_randao_reveal(block, declared_proposer_index)?;
+        self.include_operation_signatures(block, ctxt)
+    }
+
+    /// Includes all signatures on the block (except the deposit, proposal and randao signatures)
+    /// for verification.
+    fn include_operation_signatures<Payload: AbstractExecPayload<E>>(
+        &mut self,
+        block: &'a SignedBeaconBlock<E, Payload>,
+        ctxt: &mut ConsensusContext<E>,
+    ) -> Result<()> {
         self.include_proposer_slashings(block)?;
         self.include_attester_slashings(block)?;
         self.include_attestations(block, ctxt)?;
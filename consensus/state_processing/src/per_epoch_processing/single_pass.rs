@@ -7,6 +7,7 @@ use crate::{
     per_epoch_processing::{Delta, Error, ParticipationEpochSummary},
 };
 use itertools::izip;
+use rayon::prelude::*;
 use safe_arith::{SafeArith, SafeArithIter};
 use std::cmp::{max, min};
 use std::collections::{BTreeSet, HashMap};
@@ -16,9 +17,9 @@ use types::{
         TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
     },
     milhouse::Cow,
-    ActivationQueue, BeaconState, BeaconStateError, ChainSpec, Checkpoint, Epoch, EthSpec,
-    ExitCache, ForkName, List, ParticipationFlags, ProgressiveBalancesCache, RelativeEpoch,
-    Unsigned, Validator,
+    ActivationQueue, BeaconState, BeaconStateError, ChainSpec, Checkpoint, Epoch, EpochCache,
+    EthSpec, ExitCache, ForkName, List, ParticipationFlags, ProgressiveBalancesCache,
+    RelativeEpoch, Unsigned, Validator,
 };
 
 pub struct SinglePassConfig {
@@ -29,8 +30,24 @@ pub struct SinglePassConfig {
     pub pending_balance_deposits: bool,
     pub pending_consolidations: bool,
     pub effective_balance_updates: bool,
+    /// The minimum number of validators for which per-validator work is split across threads.
+    pub parallel_threshold: usize,
+    /// The number of validators processed by each parallel task.
+    pub parallel_chunk_size: usize,
 }
 
+/// The default value of `SinglePassConfig::parallel_threshold`.
+///
+/// Below this many validators the cost of distributing work across threads outweighs the gains.
+/// Revisit with the `epoch_processing` benchmark when changing.
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 262_144;
+
+/// The default value of `SinglePassConfig::parallel_chunk_size`.
+///
+/// The chunk size is independent of the number of threads, so that the division of work, and the
+/// error returned if any, is too.
+pub const DEFAULT_PARALLEL_CHUNK_SIZE: usize = 16_384;
+
 impl Default for SinglePassConfig {
     fn default() -> SinglePassConfig {
         Self::enable_all()
@@ -47,6 +64,8 @@ impl SinglePassConfig {
             pending_balance_deposits: true,
             pending_consolidations: true,
             effective_balance_updates: true,
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
+            parallel_chunk_size: DEFAULT_PARALLEL_CHUNK_SIZE,
         }
     }
 
@@ -59,12 +78,15 @@ impl SinglePassConfig {
             pending_balance_deposits: false,
             pending_consolidations: false,
             effective_balance_updates: false,
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
+            parallel_chunk_size: DEFAULT_PARALLEL_CHUNK_SIZE,
         }
     }
 }
 
 /// Values from the state that are immutable throughout epoch processing.
 struct StateContext {
+    previous_epoch: Epoch,
     current_epoch: Epoch,
     next_epoch: Epoch,
     finalized_checkpoint: Checkpoint,
@@ -114,6 +136,39 @@ pub struct ValidatorInfo {
 }
 
 impl ValidatorInfo {
+    fn new(
+        index: usize,
+        validator: &Validator,
+        previous_epoch_participation: ParticipationFlags,
+        current_epoch_participation: ParticipationFlags,
+        epoch_cache: &EpochCache,
+        state_ctxt: &StateContext,
+    ) -> Result<Self, Error> {
+        let is_active_current_epoch = validator.is_active_at(state_ctxt.current_epoch);
+        let is_active_previous_epoch = validator.is_active_at(state_ctxt.previous_epoch);
+        let is_eligible = is_active_previous_epoch
+            || (validator.slashed
+                && state_ctxt.previous_epoch.safe_add(1)? < validator.withdrawable_epoch);
+
+        let base_reward = if is_eligible {
+            epoch_cache.get_base_reward(index)?
+        } else {
+            0
+        };
+
+        Ok(Self {
+            index,
+            effective_balance: validator.effective_balance,
+            base_reward,
+            is_eligible,
+            is_slashed: validator.slashed,
+            is_active_current_epoch,
+            is_active_previous_epoch,
+            previous_epoch_participation,
+            current_epoch_participation,
+        })
+    }
+
     #[inline]
     pub fn is_unslashed_participating_index(&self, flag_index: usize) -> Result<bool, Error> {
         Ok(self.is_active_previous_epoch
@@ -147,6 +202,7 @@ pub fn process_epoch_single_pass<E: EthSpec>(
     let fork_name = state.fork_name_unchecked();
 
     let state_ctxt = &StateContext {
+        previous_epoch,
         current_epoch,
         next_epoch,
         finalized_checkpoint,
@@ -211,6 +267,27 @@ pub fn process_epoch_single_pass<E: EthSpec>(
     };
     let effective_balances_ctxt = &EffectiveBalancesContext::new(spec)?;
 
+    // Inactivity updates and rewards only depend on values from before epoch processing, so
+    // compute them for large validator sets in parallel, ahead of the sequential pass.
+    let process_rewards = current_epoch != E::genesis_epoch()
+        && (conf.inactivity_updates || conf.rewards_and_penalties);
+    let precomputed_rewards = if process_rewards && num_validators >= conf.parallel_threshold {
+        Some(compute_rewards_and_penalties_in_parallel(
+            validators,
+            balances,
+            previous_epoch_participation,
+            current_epoch_participation,
+            inactivity_scores,
+            epoch_cache,
+            &conf,
+            rewards_ctxt,
+            state_ctxt,
+            spec,
+        )?)
+    } else {
+        None
+    };
+
     // Iterate over the validators and related fields in one pass.
     let mut validators_iter = validators.iter_cow();
     let mut balances_iter = balances.iter_cow();
@@ -231,30 +308,26 @@ pub fn process_epoch_single_pass<E: EthSpec>(
             .next_cow()
             .ok_or(BeaconStateError::UnknownValidator(index))?;
 
-        let is_active_current_epoch = validator.is_active_at(current_epoch);
-        let is_active_previous_epoch = validator.is_active_at(previous_epoch);
-        let is_eligible = is_active_previous_epoch
-            || (validator.slashed && previous_epoch.safe_add(1)? < validator.withdrawable_epoch);
-
-        let base_reward = if is_eligible {
-            epoch_cache.get_base_reward(index)?
-        } else {
-            0
-        };
-
-        let validator_info = &ValidatorInfo {
+        let validator_info = &ValidatorInfo::new(
             index,
-            effective_balance: validator.effective_balance,
-            base_reward,
-            is_eligible,
-            is_slashed: validator.slashed,
-            is_active_current_epoch,
-            is_active_previous_epoch,
+            &validator,
             previous_epoch_participation,
             current_epoch_participation,
-        };
+            epoch_cache,
+            state_ctxt,
+        )?;
 
-        if current_epoch != E::genesis_epoch() {
+        if let Some(precomputed_rewards) = &precomputed_rewards {
+            let (new_inactivity_score, new_balance) = *precomputed_rewards
+                .get(index)
+                .ok_or(BeaconStateError::UnknownValidator(index))?;
+            if new_inactivity_score != *inactivity_score {
+                *inactivity_score.make_mut()? = new_inactivity_score;
+            }
+            if new_balance != *balance {
+                *balance.make_mut()? = new_balance;
+            }
+        } else if process_rewards {
             // `process_inactivity_updates`
             if conf.inactivity_updates {
                 process_single_inactivity_update(
@@ -385,31 +458,43 @@ fn process_single_inactivity_update(
     state_ctxt: &StateContext,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
+    let new_inactivity_score =
+        get_updated_inactivity_score(**inactivity_score, validator_info, state_ctxt, spec)?;
+    // Avoid mutating when the inactivity score is unchanged -- the common case.
+    if new_inactivity_score != **inactivity_score {
+        *inactivity_score.make_mut()? = new_inactivity_score;
+    }
+    Ok(())
+}
+
+fn get_updated_inactivity_score(
+    mut inactivity_score: u64,
+    validator_info: &ValidatorInfo,
+    state_ctxt: &StateContext,
+    spec: &ChainSpec,
+) -> Result<u64, Error> {
     if !validator_info.is_eligible {
-        return Ok(());
+        return Ok(inactivity_score);
     }
 
     // Increase inactivity score of inactive validators
     if validator_info.is_unslashed_participating_index(TIMELY_TARGET_FLAG_INDEX)? {
-        // Avoid mutating when the inactivity score is 0 and can't go any lower -- the common
-        // case.
-        if **inactivity_score == 0 {
-            return Ok(());
+        // The score can't go any lower than 0 -- the common case.
+        if inactivity_score == 0 {
+            return Ok(inactivity_score);
         }
-        inactivity_score.make_mut()?.safe_sub_assign(1)?;
+        inactivity_score.safe_sub_assign(1)?;
     } else {
-        inactivity_score
-            .make_mut()?
-            .safe_add_assign(spec.inactivity_score_bias)?;
+        inactivity_score.safe_add_assign(spec.inactivity_score_bias)?;
     }
 
     // Decrease the score of all validators for forgiveness when not during a leak
     if !state_ctxt.is_in_inactivity_leak {
-        let deduction = min(spec.inactivity_score_recovery_rate, **inactivity_score);
-        inactivity_score.make_mut()?.safe_sub_assign(deduction)?;
+        let deduction = min(spec.inactivity_score_recovery_rate, inactivity_score);
+        inactivity_score.safe_sub_assign(deduction)?;
     }
 
-    Ok(())
+    Ok(inactivity_score)
 }
 
 fn process_single_reward_and_penalty(
@@ -420,8 +505,30 @@ fn process_single_reward_and_penalty(
     state_ctxt: &StateContext,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
+    let new_balance = get_rewarded_balance(
+        **balance,
+        inactivity_score,
+        validator_info,
+        rewards_ctxt,
+        state_ctxt,
+        spec,
+    )?;
+    if new_balance != **balance {
+        *balance.make_mut()? = new_balance;
+    }
+    Ok(())
+}
+
+fn get_rewarded_balance(
+    balance: u64,
+    inactivity_score: &u64,
+    validator_info: &ValidatorInfo,
+    rewards_ctxt: &RewardsAndPenaltiesContext,
+    state_ctxt: &StateContext,
+    spec: &ChainSpec,
+) -> Result<u64, Error> {
     if !validator_info.is_eligible {
-        return Ok(());
+        return Ok(balance);
     }
 
     let mut delta = Delta::default();
@@ -442,13 +549,99 @@ fn process_single_reward_and_penalty(
         spec,
     )?;
 
-    if delta.rewards != 0 || delta.penalties != 0 {
-        let balance = balance.make_mut()?;
-        balance.safe_add_assign(delta.rewards)?;
-        *balance = balance.saturating_sub(delta.penalties);
-    }
+    Ok(balance
+        .safe_add(delta.rewards)?
+        .saturating_sub(delta.penalties))
+}
 
-    Ok(())
+/// Compute the inactivity score and balance of every validator after `process_inactivity_updates`
+/// and `process_rewards_and_penalties`, splitting the validators into chunks processed in
+/// parallel.
+///
+/// The results are returned in validator index order, and are identical to those of the
+/// sequential pass.
+#[allow(clippy::too_many_arguments)]
+fn compute_rewards_and_penalties_in_parallel<N: Unsigned>(
+    validators: &List<Validator, N>,
+    balances: &List<u64, N>,
+    previous_epoch_participation: &List<ParticipationFlags, N>,
+    current_epoch_participation: &List<ParticipationFlags, N>,
+    inactivity_scores: &List<u64, N>,
+    epoch_cache: &EpochCache,
+    conf: &SinglePassConfig,
+    rewards_ctxt: &RewardsAndPenaltiesContext,
+    state_ctxt: &StateContext,
+    spec: &ChainSpec,
+) -> Result<Vec<(u64, u64)>, Error> {
+    let num_validators = validators.len();
+    // A chunk size of 0 would never make progress.
+    let chunk_size = std::cmp::max(conf.parallel_chunk_size, 1);
+
+    let chunks = (0..num_validators)
+        .step_by(chunk_size)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|start| {
+            let end = min(start.safe_add(chunk_size)?, num_validators);
+            let mut results = Vec::with_capacity(end.safe_sub(start)?);
+
+            for (index, validator, &balance, &inactivity_score, &previous, &current) in izip!(
+                start..end,
+                validators.iter_from(start)?,
+                balances.iter_from(start)?,
+                inactivity_scores.iter_from(start)?,
+                previous_epoch_participation.iter_from(start)?,
+                current_epoch_participation.iter_from(start)?,
+            ) {
+                let validator_info = &ValidatorInfo::new(
+                    index,
+                    validator,
+                    previous,
+                    current,
+                    epoch_cache,
+                    state_ctxt,
+                )?;
+
+                let inactivity_score = if conf.inactivity_updates {
+                    get_updated_inactivity_score(
+                        inactivity_score,
+                        validator_info,
+                        state_ctxt,
+                        spec,
+                    )?
+                } else {
+                    inactivity_score
+                };
+                let balance = if conf.rewards_and_penalties {
+                    get_rewarded_balance(
+                        balance,
+                        &inactivity_score,
+                        validator_info,
+                        rewards_ctxt,
+                        state_ctxt,
+                        spec,
+                    )?
+                } else {
+                    balance
+                };
+                results.push((inactivity_score, balance));
+            }
+
+            if results.len() != end.safe_sub(start)? {
+                return Err(
+                    BeaconStateError::UnknownValidator(start.safe_add(results.len())?).into(),
+                );
+            }
+            Ok(results)
+        })
+        .collect::<Vec<Result<Vec<_>, Error>>>();
+
+    // Return the error from the lowest chunk, if any, so that the result is deterministic.
+    let mut results = Vec::with_capacity(num_validators);
+    for chunk in chunks {
+        results.extend(chunk?);
+    }
+    Ok(results)
 }
 
 fn get_flag_index_delta(
@@ -1,10 +1,12 @@
 #![cfg(test)]
 use crate::per_epoch_processing::process_epoch;
-use beacon_chain::test_utils::BeaconChainHarness;
+use crate::per_epoch_processing::single_pass::{process_epoch_single_pass, SinglePassConfig};
+use crate::transition_report::epoch_processing_with_report;
+use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
 use beacon_chain::types::{EthSpec, MinimalEthSpec};
 use bls::Hash256;
 use env_logger::{Builder, Env};
-use types::Slot;
+use types::{ForkName, Slot};
 
 #[tokio::test]
 async fn runs_without_error() {
@@ -38,14 +40,151 @@ async fn runs_without_error() {
     process_epoch(&mut new_head_state, &spec).unwrap();
 }
 
+#[tokio::test]
+async fn epoch_processing_with_report_matches_process_epoch() {
+    let spec = ForkName::Capella.make_genesis_spec(MinimalEthSpec::default_spec());
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            (MinimalEthSpec::slots_per_epoch() * 3 - 1) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let mut expected = harness.get_current_state();
+    let mut staged = expected.clone();
+    process_epoch(&mut expected, &spec).unwrap();
+    let stages = epoch_processing_with_report(&mut staged, &spec).unwrap();
+
+    assert_eq!(
+        staged.update_tree_hash_cache().unwrap(),
+        expected.update_tree_hash_cache().unwrap()
+    );
+    let stage = |name| stages.iter().find(|stage| stage.stage == name).unwrap();
+    assert!(stage("rewards_and_penalties").diff.balances_changed > 0);
+    assert_eq!(stage("randao_mixes_reset").diff.balances_changed, 0);
+    assert_eq!(
+        stage("historical_summaries_update").diff.validators_changed,
+        0
+    );
+}
+
+#[tokio::test]
+async fn parallel_single_pass_matches_sequential() {
+    let spec = ForkName::Capella.make_genesis_spec(MinimalEthSpec::default_spec());
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+    // Leave some validators out of attestations so that penalties and inactivity scores apply.
+    harness
+        .extend_chain(
+            (MinimalEthSpec::slots_per_epoch() * 3 - 1) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators((0..6).collect()),
+        )
+        .await;
+
+    let mut sequential = harness.get_current_state();
+    let mut parallel = sequential.clone();
+    process_epoch_single_pass(
+        &mut sequential,
+        &spec,
+        SinglePassConfig {
+            parallel_threshold: usize::MAX,
+            ..SinglePassConfig::enable_all()
+        },
+    )
+    .unwrap();
+    process_epoch_single_pass(
+        &mut parallel,
+        &spec,
+        SinglePassConfig {
+            parallel_threshold: 0,
+            ..SinglePassConfig::enable_all()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        parallel.update_tree_hash_cache().unwrap(),
+        sequential.update_tree_hash_cache().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn parallel_single_pass_matches_sequential_with_several_chunks() {
+    // 37 validators split into chunks of 8 leaves a final partial chunk of 5.
+    let validator_count = 37;
+    let spec = ForkName::Capella.make_genesis_spec(MinimalEthSpec::default_spec());
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(validator_count)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+    // Leave validators out of attestations across every chunk, so that penalties and inactivity
+    // scores apply throughout.
+    harness
+        .extend_chain(
+            (MinimalEthSpec::slots_per_epoch() * 3 - 1) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(
+                (0..validator_count).filter(|i| i % 3 != 0).collect(),
+            ),
+        )
+        .await;
+
+    let mut sequential = harness.get_current_state();
+    process_epoch_single_pass(
+        &mut sequential,
+        &spec,
+        SinglePassConfig {
+            parallel_threshold: usize::MAX,
+            ..SinglePassConfig::enable_all()
+        },
+    )
+    .unwrap();
+
+    for parallel_chunk_size in [1, 8, validator_count - 1, validator_count] {
+        let mut parallel = harness.get_current_state();
+        process_epoch_single_pass(
+            &mut parallel,
+            &spec,
+            SinglePassConfig {
+                parallel_threshold: 0,
+                parallel_chunk_size,
+                ..SinglePassConfig::enable_all()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            parallel.update_tree_hash_cache().unwrap(),
+            sequential.update_tree_hash_cache().unwrap(),
+            "chunk size {parallel_chunk_size}"
+        );
+    }
+}
+
 #[cfg(not(debug_assertions))]
 mod release_tests {
     use super::*;
     use crate::{
         per_slot_processing::per_slot_processing, EpochProcessingError, SlotProcessingError,
     };
-    use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
-    use types::{Epoch, ForkName, InconsistentFork, MainnetEthSpec};
+    use types::{Epoch, InconsistentFork, MainnetEthSpec};
 
     #[tokio::test]
     async fn altair_state_on_base_fork() {
@@ -30,6 +30,16 @@ pub fn per_slot_processing<E: EthSpec>(
     state_root: Option<Hash256>,
     spec: &ChainSpec,
 ) -> Result<Option<EpochProcessingSummary<E>>, Error> {
+    per_slot_processing_with(state, state_root, spec, per_epoch_processing)
+}
+
+/// As per `per_slot_processing`, but with per-epoch processing performed by `process_epoch`.
+pub(crate) fn per_slot_processing_with<E: EthSpec, T>(
+    state: &mut BeaconState<E>,
+    state_root: Option<Hash256>,
+    spec: &ChainSpec,
+    process_epoch: impl FnOnce(&mut BeaconState<E>, &ChainSpec) -> Result<T, EpochProcessingError>,
+) -> Result<Option<T>, Error> {
     // Verify that the `BeaconState` instantiation matches the fork at `state.slot()`.
     state
         .fork_name(spec)
@@ -40,7 +50,7 @@ pub fn per_slot_processing<E: EthSpec>(
     let summary = if state.slot() > spec.genesis_slot
         && state.slot().safe_add(1)?.safe_rem(E::slots_per_epoch())? == 0
     {
-        Some(per_epoch_processing(state, spec)?)
+        Some(process_epoch(state, spec)?)
     } else {
         None
     };
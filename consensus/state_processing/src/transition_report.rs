@@ -0,0 +1,492 @@
+//! Performs a state transition one stage at a time, reporting the time taken by each stage and the
+//! changes it made to the state.
+//!
+//! The stages are run in specification order, rather than fused as in `per_epoch_processing`, so
+//! the timings are indicative of the cost of each stage rather than of the optimised transition.
+//! The post-state is identical to that produced by `per_slot_processing` and
+//! `per_block_processing`.
+use crate::common::update_progressive_balances_cache::{
+    initialize_progressive_balances_cache, update_progressive_balances_on_epoch_transition,
+};
+use crate::epoch_cache::initialize_epoch_cache;
+use crate::per_block_processing::{
+    is_execution_enabled, process_block_header, process_eth1_data, process_execution_payload,
+    process_operations, process_randao, process_sync_aggregate, process_withdrawals,
+};
+use crate::per_epoch_processing::{
+    altair::{
+        process_justification_and_finalization, process_participation_flag_updates,
+        process_sync_committee_updates,
+    },
+    base,
+    capella::process_historical_summaries_update,
+    historical_roots_update::process_historical_roots_update,
+    resets::{process_eth1_data_reset, process_randao_mixes_reset, process_slashings_reset},
+    single_pass::{process_epoch_single_pass, SinglePassConfig},
+};
+use crate::per_slot_processing::per_slot_processing_with;
+use crate::{
+    BlockProcessingError, ConsensusContext, EpochProcessingError, SlotProcessingError,
+    VerifyBlockRoot, VerifySignatures,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use types::{
+    AbstractExecPayload, BeaconState, ChainSpec, Checkpoint, Epoch, EthSpec, Hash256, List,
+    RelativeEpoch, SignedBeaconBlock, Slot, Validator,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    SlotProcessing(SlotProcessingError),
+    BlockProcessing(BlockProcessingError),
+    /// The block is not later than the state.
+    BlockSlotTooLow {
+        state_slot: Slot,
+        block_slot: Slot,
+    },
+}
+
+impl From<SlotProcessingError> for Error {
+    fn from(e: SlotProcessingError) -> Self {
+        Self::SlotProcessing(e)
+    }
+}
+
+impl From<BlockProcessingError> for Error {
+    fn from(e: BlockProcessingError) -> Self {
+        Self::BlockProcessing(e)
+    }
+}
+
+/// The changes made to a `BeaconState` by a single stage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// The number of validators added to the registry.
+    pub validators_added: usize,
+    /// The number of existing validator records which were modified.
+    pub validators_changed: usize,
+    /// The number of existing validators whose effective balance changed.
+    pub effective_balances_changed: usize,
+    /// The number of existing validators whose balance changed.
+    pub balances_changed: usize,
+    /// The sum of all balance increases, in Gwei.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance_increase: u64,
+    /// The sum of all balance decreases, in Gwei.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance_decrease: u64,
+    pub current_justified_checkpoint_changed: bool,
+    pub finalized_checkpoint_changed: bool,
+}
+
+/// The time taken and changes made by a single stage of the state transition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageReport {
+    pub stage: String,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub duration_us: u64,
+    pub diff: StateDiff,
+}
+
+/// The stages of the per-epoch processing at the end of `epoch`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpochReport {
+    pub epoch: Epoch,
+    pub stages: Vec<StageReport>,
+}
+
+/// A report of the state transition applying a block to its pre-state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionReport {
+    pub slot: Slot,
+    /// The time taken by per-slot processing, excluding per-epoch processing.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot_processing_us: u64,
+    /// The per-epoch processing performed while advancing to the block's slot.
+    pub epoch_processing: Vec<EpochReport>,
+    /// The stages of per-block processing.
+    pub block_processing: Vec<StageReport>,
+}
+
+/// The parts of a `BeaconState` which are compared before and after each stage.
+///
+/// The lists are persistent, so cloning them is cheap.
+struct Snapshot<E: EthSpec> {
+    validators: List<Validator, E::ValidatorRegistryLimit>,
+    balances: List<u64, E::ValidatorRegistryLimit>,
+    current_justified_checkpoint: Checkpoint,
+    finalized_checkpoint: Checkpoint,
+}
+
+impl<E: EthSpec> Snapshot<E> {
+    fn new(state: &BeaconState<E>) -> Self {
+        Self {
+            validators: state.validators().clone(),
+            balances: state.balances().clone(),
+            current_justified_checkpoint: state.current_justified_checkpoint(),
+            finalized_checkpoint: state.finalized_checkpoint(),
+        }
+    }
+
+    fn diff(&self, state: &BeaconState<E>) -> StateDiff {
+        let mut diff = StateDiff {
+            validators_added: state
+                .validators()
+                .len()
+                .saturating_sub(self.validators.len()),
+            current_justified_checkpoint_changed: self.current_justified_checkpoint
+                != state.current_justified_checkpoint(),
+            finalized_checkpoint_changed: self.finalized_checkpoint != state.finalized_checkpoint(),
+            ..StateDiff::default()
+        };
+
+        for (before, after) in self.validators.iter().zip(state.validators().iter()) {
+            if before != after {
+                diff.validators_changed = diff.validators_changed.saturating_add(1);
+            }
+            if before.effective_balance != after.effective_balance {
+                diff.effective_balances_changed = diff.effective_balances_changed.saturating_add(1);
+            }
+        }
+
+        for (before, after) in self.balances.iter().zip(state.balances().iter()) {
+            if before != after {
+                diff.balances_changed = diff.balances_changed.saturating_add(1);
+            }
+            if after > before {
+                diff.balance_increase = diff
+                    .balance_increase
+                    .saturating_add(after.saturating_sub(*before));
+            } else {
+                diff.balance_decrease = diff
+                    .balance_decrease
+                    .saturating_add(before.saturating_sub(*after));
+            }
+        }
+
+        diff
+    }
+}
+
+fn as_micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+/// Runs each stage, recording its duration and the changes it makes to the state.
+#[derive(Default)]
+struct Recorder {
+    stages: Vec<StageReport>,
+}
+
+impl Recorder {
+    fn record<E: EthSpec, T, U>(
+        &mut self,
+        stage: &str,
+        state: &mut BeaconState<E>,
+        f: impl FnOnce(&mut BeaconState<E>) -> Result<T, U>,
+    ) -> Result<T, U> {
+        let before = Snapshot::new(state);
+        let start = Instant::now();
+        let result = f(state)?;
+        let duration = start.elapsed();
+        self.stages.push(StageReport {
+            stage: stage.to_string(),
+            duration_us: as_micros(duration),
+            diff: before.diff(state),
+        });
+        Ok(result)
+    }
+}
+
+/// Advance `state` to the slot of `block` and apply `block`, reporting on each stage.
+///
+/// Signatures are not verified, so the caller must verify them separately if required.
+pub fn transition_with_report<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    state: &mut BeaconState<E>,
+    state_root_opt: Option<Hash256>,
+    block: &SignedBeaconBlock<E, Payload>,
+    ctxt: &mut ConsensusContext<E>,
+    spec: &ChainSpec,
+) -> Result<TransitionReport, Error> {
+    if block.slot() <= state.slot() {
+        return Err(Error::BlockSlotTooLow {
+            state_slot: state.slot(),
+            block_slot: block.slot(),
+        });
+    }
+
+    let mut report = advance_with_report(state, state_root_opt, block.slot(), spec)?;
+    report.block_processing = block_processing_with_report(state, block, ctxt, spec)?;
+    Ok(report)
+}
+
+/// Advance `state` to `target_slot`, as per `complete_state_advance`, reporting on each stage of
+/// per-epoch processing.
+///
+/// The `block_processing` of the returned report is empty.
+pub fn advance_with_report<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    mut state_root_opt: Option<Hash256>,
+    target_slot: Slot,
+    spec: &ChainSpec,
+) -> Result<TransitionReport, Error> {
+    let mut slot_processing_us = 0u64;
+    let mut epoch_processing = vec![];
+    while state.slot() < target_slot {
+        let start = Instant::now();
+        let epoch = state.current_epoch();
+        let mut epoch_duration = Duration::ZERO;
+        let epoch_report =
+            per_slot_processing_with(state, state_root_opt.take(), spec, |state, spec| {
+                let epoch_start = Instant::now();
+                let result = epoch_processing_with_report(state, spec);
+                epoch_duration = epoch_start.elapsed();
+                result
+            })?;
+        let slot_duration = start.elapsed().saturating_sub(epoch_duration);
+        slot_processing_us = slot_processing_us.saturating_add(as_micros(slot_duration));
+        if let Some(stages) = epoch_report {
+            epoch_processing.push(EpochReport { epoch, stages });
+        }
+    }
+
+    Ok(TransitionReport {
+        slot: target_slot,
+        slot_processing_us,
+        epoch_processing,
+        block_processing: vec![],
+    })
+}
+
+/// Performs per-epoch processing, as per `per_epoch_processing`, reporting on each stage.
+///
+/// Phase 0 states are processed in a single `epoch` stage.
+pub fn epoch_processing_with_report<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Vec<StageReport>, EpochProcessingError> {
+    state
+        .fork_name(spec)
+        .map_err(EpochProcessingError::InconsistentStateFork)?;
+
+    let mut recorder = Recorder::default();
+
+    if let BeaconState::Base(_) = state {
+        recorder.record("epoch", state, |state| base::process_epoch(state, spec))?;
+        return Ok(recorder.stages);
+    }
+
+    recorder.record("caches", state, |state| {
+        state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, spec)?;
+        state.build_committee_cache(RelativeEpoch::Next, spec)?;
+        state.build_total_active_balance_cache(spec)?;
+        initialize_epoch_cache(state, spec)?;
+        initialize_progressive_balances_cache::<E>(state, spec)?;
+        Ok::<_, EpochProcessingError>(())
+    })?;
+
+    recorder.record("justification_and_finalization", state, |state| {
+        let justification_and_finalization_state = process_justification_and_finalization(state)?;
+        justification_and_finalization_state.apply_changes_to_state(state);
+        Ok::<_, EpochProcessingError>(())
+    })?;
+
+    recorder.record(
+        "inactivity_updates",
+        state,
+        single_pass(
+            SinglePassConfig {
+                inactivity_updates: true,
+                ..SinglePassConfig::disable_all()
+            },
+            spec,
+        ),
+    )?;
+    recorder.record(
+        "rewards_and_penalties",
+        state,
+        single_pass(
+            SinglePassConfig {
+                rewards_and_penalties: true,
+                ..SinglePassConfig::disable_all()
+            },
+            spec,
+        ),
+    )?;
+    recorder.record(
+        "registry_updates",
+        state,
+        single_pass(
+            SinglePassConfig {
+                registry_updates: true,
+                ..SinglePassConfig::disable_all()
+            },
+            spec,
+        ),
+    )?;
+    recorder.record(
+        "slashings",
+        state,
+        single_pass(
+            SinglePassConfig {
+                slashings: true,
+                ..SinglePassConfig::disable_all()
+            },
+            spec,
+        ),
+    )?;
+    recorder.record("eth1_data_reset", state, process_eth1_data_reset)?;
+
+    if state.fork_name_unchecked().electra_enabled() {
+        recorder.record(
+            "pending_balance_deposits",
+            state,
+            single_pass(
+                SinglePassConfig {
+                    pending_balance_deposits: true,
+                    ..SinglePassConfig::disable_all()
+                },
+                spec,
+            ),
+        )?;
+        recorder.record(
+            "pending_consolidations",
+            state,
+            single_pass(
+                SinglePassConfig {
+                    pending_consolidations: true,
+                    ..SinglePassConfig::disable_all()
+                },
+                spec,
+            ),
+        )?;
+    }
+
+    recorder.record(
+        "effective_balance_updates",
+        state,
+        single_pass(
+            SinglePassConfig {
+                effective_balance_updates: true,
+                ..SinglePassConfig::disable_all()
+            },
+            spec,
+        ),
+    )?;
+
+    recorder.record("slashings_reset", state, process_slashings_reset)?;
+    recorder.record("randao_mixes_reset", state, process_randao_mixes_reset)?;
+
+    if state.historical_summaries().is_ok() {
+        recorder.record(
+            "historical_summaries_update",
+            state,
+            process_historical_summaries_update,
+        )?;
+    } else {
+        recorder.record(
+            "historical_roots_update",
+            state,
+            process_historical_roots_update,
+        )?;
+    }
+
+    recorder.record(
+        "participation_flag_updates",
+        state,
+        process_participation_flag_updates,
+    )?;
+    recorder.record("sync_committee_updates", state, |state| {
+        process_sync_committee_updates(state, spec)
+    })?;
+    recorder.record("advance_caches", state, |state| {
+        state.advance_caches()?;
+        update_progressive_balances_on_epoch_transition(state, spec)?;
+        Ok::<_, EpochProcessingError>(())
+    })?;
+
+    Ok(recorder.stages)
+}
+
+/// Returns a stage performing the parts of the single-pass epoch processing enabled in `conf`.
+fn single_pass<E: EthSpec>(
+    conf: SinglePassConfig,
+    spec: &ChainSpec,
+) -> impl FnOnce(&mut BeaconState<E>) -> Result<(), EpochProcessingError> + '_ {
+    move |state| process_epoch_single_pass(state, spec, conf).map(|_| ())
+}
+
+/// Applies `block` to `state`, as per `per_block_processing` without signature verification,
+/// reporting on each stage.
+pub fn block_processing_with_report<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    state: &mut BeaconState<E>,
+    signed_block: &SignedBeaconBlock<E, Payload>,
+    ctxt: &mut ConsensusContext<E>,
+    spec: &ChainSpec,
+) -> Result<Vec<StageReport>, BlockProcessingError> {
+    let block = signed_block.message();
+    signed_block
+        .fork_name(spec)
+        .map_err(BlockProcessingError::InconsistentBlockFork)?;
+    state
+        .fork_name(spec)
+        .map_err(BlockProcessingError::InconsistentStateFork)?;
+
+    let mut recorder = Recorder::default();
+
+    recorder.record("caches", state, |state| {
+        initialize_epoch_cache(state, spec)?;
+        initialize_progressive_balances_cache(state, spec)?;
+        state.build_slashings_cache()?;
+        state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, spec)?;
+        Ok::<_, BlockProcessingError>(())
+    })?;
+
+    let proposer_index = recorder.record("block_header", state, |state| {
+        process_block_header(
+            state,
+            block.temporary_block_header(),
+            VerifyBlockRoot::True,
+            ctxt,
+            spec,
+        )
+        .map_err(BlockProcessingError::from)
+    })?;
+
+    if is_execution_enabled(state, block.body()) {
+        let body = block.body();
+        recorder.record("withdrawals", state, |state| {
+            process_withdrawals::<E, Payload>(state, body.execution_payload()?, spec)
+        })?;
+        recorder.record("execution_payload", state, |state| {
+            process_execution_payload::<E, Payload>(state, body, spec)
+        })?;
+    }
+
+    recorder.record("randao", state, |state| {
+        process_randao(state, block, VerifySignatures::False, ctxt, spec)
+    })?;
+    recorder.record("eth1_data", state, |state| {
+        process_eth1_data(state, block.body().eth1_data()).map_err(BlockProcessingError::from)
+    })?;
+    recorder.record("operations", state, |state| {
+        process_operations(state, block.body(), VerifySignatures::False, ctxt, spec)
+    })?;
+
+    if let Ok(sync_aggregate) = block.body().sync_aggregate() {
+        recorder.record("sync_aggregate", state, |state| {
+            process_sync_aggregate(
+                state,
+                sync_aggregate,
+                proposer_index,
+                VerifySignatures::False,
+                spec,
+            )
+        })?;
+    }
+
+    Ok(recorder.stages)
+}
@@ -64,17 +64,17 @@ pub struct BeaconBlock<E: EthSpec, Payload: AbstractExecPayload<E> = FullPayload
     #[superstruct(getter(copy))]
     pub state_root: Hash256,
     #[superstruct(only(Base), partial_getter(rename = "body_base"))]
-    pub body: BeaconBlockBodyBase<E, Payload>,
+    pub body: CachedRoot<BeaconBlockBodyBase<E, Payload>>,
     #[superstruct(only(Altair), partial_getter(rename = "body_altair"))]
-    pub body: BeaconBlockBodyAltair<E, Payload>,
+    pub body: CachedRoot<BeaconBlockBodyAltair<E, Payload>>,
     #[superstruct(only(Bellatrix), partial_getter(rename = "body_bellatrix"))]
-    pub body: BeaconBlockBodyBellatrix<E, Payload>,
+    pub body: CachedRoot<BeaconBlockBodyBellatrix<E, Payload>>,
     #[superstruct(only(Capella), partial_getter(rename = "body_capella"))]
-    pub body: BeaconBlockBodyCapella<E, Payload>,
+    pub body: CachedRoot<BeaconBlockBodyCapella<E, Payload>>,
     #[superstruct(only(Deneb), partial_getter(rename = "body_deneb"))]
-    pub body: BeaconBlockBodyDeneb<E, Payload>,
+    pub body: CachedRoot<BeaconBlockBodyDeneb<E, Payload>>,
     #[superstruct(only(Electra), partial_getter(rename = "body_electra"))]
-    pub body: BeaconBlockBodyElectra<E, Payload>,
+    pub body: CachedRoot<BeaconBlockBodyElectra<E, Payload>>,
 }
 
 pub type BlindedBeaconBlock<E> = BeaconBlock<E, BlindedPayload<E>>;
@@ -164,7 +164,7 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlock<E, Payload> {
     /// Note: This method is used instead of an `Into` impl to avoid a `Clone` of an entire block
     /// when you want to have the block _and_ the header.
     ///
-    /// Note: performs a full tree-hash of `self.body`.
+    /// Note: performs a full tree-hash of `self.body`, unless its root is already cached.
     pub fn block_header(&self) -> BeaconBlockHeader {
         self.to_ref().block_header()
     }
@@ -235,7 +235,7 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockRef<'a, E, Payl
     /// Convenience accessor for the `body` as a `BeaconBlockBodyRef`.
     pub fn body(&self) -> BeaconBlockBodyRef<'a, E, Payload> {
         map_beacon_block_ref_into_beacon_block_body_ref!(&'a _, *self, |block, cons| cons(
-            &block.body
+            &*block.body
         ))
     }
 
@@ -282,7 +282,7 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockRefMut<'a, E, P
     /// Convert a mutable reference to a beacon block to a mutable ref to its body.
     pub fn body_mut(self) -> BeaconBlockBodyRefMut<'a, E, Payload> {
         map_beacon_block_ref_mut_into_beacon_block_body_ref_mut!(&'a _, self, |block, cons| cons(
-            &mut block.body
+            &mut *block.body
         ))
     }
 }
@@ -308,7 +308,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockBase
                 deposits: VariableList::empty(),
                 voluntary_exits: VariableList::empty(),
                 _phantom: PhantomData,
-            },
+            }
+            .into(),
         }
     }
 }
@@ -432,7 +433,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockAlta
                 voluntary_exits: VariableList::empty(),
                 sync_aggregate: SyncAggregate::empty(),
                 _phantom: PhantomData,
-            },
+            }
+            .into(),
         }
     }
 }
@@ -440,7 +442,7 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockAlta
 impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockAltair<E, Payload> {
     /// Return an Altair block where the block has maximum size.
     pub fn full(spec: &ChainSpec) -> Self {
-        let base_block: BeaconBlockBase<_, Payload> = BeaconBlockBase::full(spec);
+        let base_body = BeaconBlockBase::<_, Payload>::full(spec).body.into_inner();
         let sync_aggregate = SyncAggregate {
             sync_committee_signature: AggregateSignature::empty(),
             sync_committee_bits: BitVector::default(),
@@ -451,11 +453,11 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockAltair<E, Payload>
             parent_root: Hash256::zero(),
             state_root: Hash256::zero(),
             body: BeaconBlockBodyAltair {
-                proposer_slashings: base_block.body.proposer_slashings,
-                attester_slashings: base_block.body.attester_slashings,
-                attestations: base_block.body.attestations,
-                deposits: base_block.body.deposits,
-                voluntary_exits: base_block.body.voluntary_exits,
+                proposer_slashings: base_body.proposer_slashings,
+                attester_slashings: base_body.attester_slashings,
+                attestations: base_body.attestations,
+                deposits: base_body.deposits,
+                voluntary_exits: base_body.voluntary_exits,
                 sync_aggregate,
                 randao_reveal: Signature::empty(),
                 eth1_data: Eth1Data {
@@ -465,7 +467,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockAltair<E, Payload>
                 },
                 graffiti: Graffiti::default(),
                 _phantom: PhantomData,
-            },
+            }
+            .into(),
         }
     }
 }
@@ -493,7 +496,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockBell
                 voluntary_exits: VariableList::empty(),
                 sync_aggregate: SyncAggregate::empty(),
                 execution_payload: Payload::Bellatrix::default(),
-            },
+            }
+            .into(),
         }
     }
 }
@@ -501,7 +505,7 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockBell
 impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockCapella<E, Payload> {
     /// Return a Capella block where the block has maximum size.
     pub fn full(spec: &ChainSpec) -> Self {
-        let base_block: BeaconBlockBase<_, Payload> = BeaconBlockBase::full(spec);
+        let base_body = BeaconBlockBase::<_, Payload>::full(spec).body.into_inner();
         let bls_to_execution_changes = vec![
             SignedBlsToExecutionChange {
                 message: BlsToExecutionChange {
@@ -524,11 +528,11 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockCapella<E, Payload>
             parent_root: Hash256::zero(),
             state_root: Hash256::zero(),
             body: BeaconBlockBodyCapella {
-                proposer_slashings: base_block.body.proposer_slashings,
-                attester_slashings: base_block.body.attester_slashings,
-                attestations: base_block.body.attestations,
-                deposits: base_block.body.deposits,
-                voluntary_exits: base_block.body.voluntary_exits,
+                proposer_slashings: base_body.proposer_slashings,
+                attester_slashings: base_body.attester_slashings,
+                attestations: base_body.attestations,
+                deposits: base_body.deposits,
+                voluntary_exits: base_body.voluntary_exits,
                 bls_to_execution_changes,
                 sync_aggregate,
                 randao_reveal: Signature::empty(),
@@ -539,7 +543,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockCapella<E, Payload>
                 },
                 graffiti: Graffiti::default(),
                 execution_payload: Payload::Capella::default(),
-            },
+            }
+            .into(),
         }
     }
 }
@@ -568,7 +573,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockCape
                 sync_aggregate: SyncAggregate::empty(),
                 execution_payload: Payload::Capella::default(),
                 bls_to_execution_changes: VariableList::empty(),
-            },
+            }
+            .into(),
         }
     }
 }
@@ -598,7 +604,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockDene
                 execution_payload: Payload::Deneb::default(),
                 bls_to_execution_changes: VariableList::empty(),
                 blob_kzg_commitments: VariableList::empty(),
-            },
+            }
+            .into(),
         }
     }
 }
@@ -606,7 +613,7 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockDene
 impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockElectra<E, Payload> {
     /// Return a Electra block where the block has maximum size.
     pub fn full(spec: &ChainSpec) -> Self {
-        let base_block: BeaconBlockBase<_, Payload> = BeaconBlockBase::full(spec);
+        let base_body = BeaconBlockBase::<_, Payload>::full(spec).body.into_inner();
         let indexed_attestation: IndexedAttestationElectra<E> = IndexedAttestationElectra {
             attesting_indices: VariableList::new(vec![0_u64; E::MaxValidatorsPerSlot::to_usize()])
                 .unwrap(),
@@ -654,11 +661,11 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockElectra<E, Payload>
             parent_root: Hash256::zero(),
             state_root: Hash256::zero(),
             body: BeaconBlockBodyElectra {
-                proposer_slashings: base_block.body.proposer_slashings,
+                proposer_slashings: base_body.proposer_slashings,
                 attester_slashings,
                 attestations: attestations_electra.into(),
-                deposits: base_block.body.deposits,
-                voluntary_exits: base_block.body.voluntary_exits,
+                deposits: base_body.deposits,
+                voluntary_exits: base_body.voluntary_exits,
                 bls_to_execution_changes,
                 sync_aggregate,
                 randao_reveal: Signature::empty(),
@@ -671,7 +678,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockElectra<E, Payload>
                 execution_payload: Payload::Electra::default(),
                 blob_kzg_commitments: VariableList::empty(),
                 consolidations: VariableList::empty(),
-            },
+            }
+            .into(),
         }
     }
 }
@@ -702,7 +710,8 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> EmptyBlock for BeaconBlockElec
                 bls_to_execution_changes: VariableList::empty(),
                 blob_kzg_commitments: VariableList::empty(),
                 consolidations: VariableList::empty(),
-            },
+            }
+            .into(),
         }
     }
 }
@@ -725,7 +734,7 @@ impl<E: EthSpec> From<BeaconBlockBase<E, BlindedPayload<E>>>
             proposer_index,
             parent_root,
             state_root,
-            body: body.into(),
+            body: CachedRoot::new(body.into_inner().into()),
         }
     }
 }
@@ -747,7 +756,7 @@ impl<E: EthSpec> From<BeaconBlockAltair<E, BlindedPayload<E>>>
             proposer_index,
             parent_root,
             state_root,
-            body: body.into(),
+            body: CachedRoot::new(body.into_inner().into()),
         }
     }
 }
@@ -768,14 +777,14 @@ macro_rules! impl_from {
                     body,
                 } = block;
 
-                let (body, payload) = ($body_expr)(body);
+                let (body, payload) = ($body_expr)(body.into_inner());
 
                 ($ty_name {
                     slot,
                     proposer_index,
                     parent_root,
                     state_root,
-                    body,
+                    body: body.into(),
                 }, payload.map(Into::into))
             }
         }
@@ -808,7 +817,7 @@ macro_rules! impl_clone_as_blinded {
                     proposer_index: *proposer_index,
                     parent_root: *parent_root,
                     state_root: *state_root,
-                    body: body.clone_as_blinded(),
+                    body: body.clone_as_blinded().into(),
                 }
             }
         }
@@ -905,7 +914,7 @@ mod tests {
             proposer_index: u64::random_for_test(rng),
             parent_root: Hash256::random_for_test(rng),
             state_root: Hash256::random_for_test(rng),
-            body: BeaconBlockBodyBase::random_for_test(rng),
+            body: BeaconBlockBodyBase::random_for_test(rng).into(),
         };
         let block = BeaconBlock::Base(inner_block.clone());
 
@@ -924,7 +933,7 @@ mod tests {
             proposer_index: u64::random_for_test(rng),
             parent_root: Hash256::random_for_test(rng),
             state_root: Hash256::random_for_test(rng),
-            body: BeaconBlockBodyAltair::random_for_test(rng),
+            body: BeaconBlockBodyAltair::random_for_test(rng).into(),
         };
         let block = BeaconBlock::Altair(inner_block.clone());
 
@@ -943,7 +952,7 @@ mod tests {
             proposer_index: u64::random_for_test(rng),
             parent_root: Hash256::random_for_test(rng),
             state_root: Hash256::random_for_test(rng),
-            body: BeaconBlockBodyCapella::random_for_test(rng),
+            body: BeaconBlockBodyCapella::random_for_test(rng).into(),
         };
         let block = BeaconBlock::Capella(inner_block.clone());
 
@@ -962,7 +971,7 @@ mod tests {
             proposer_index: u64::random_for_test(rng),
             parent_root: Hash256::random_for_test(rng),
             state_root: Hash256::random_for_test(rng),
-            body: BeaconBlockBodyDeneb::random_for_test(rng),
+            body: BeaconBlockBodyDeneb::random_for_test(rng).into(),
         };
         let block = BeaconBlock::Deneb(inner_block.clone());
 
@@ -981,7 +990,7 @@ mod tests {
             proposer_index: u64::random_for_test(rng),
             parent_root: Hash256::random_for_test(rng),
             state_root: Hash256::random_for_test(rng),
-            body: BeaconBlockBodyElectra::random_for_test(rng),
+            body: BeaconBlockBodyElectra::random_for_test(rng).into(),
         };
 
         let block = BeaconBlock::Electra(inner_block.clone());
@@ -523,10 +523,10 @@ impl<E: EthSpec> From<BeaconBlockBodyBellatrix<E, FullPayload<E>>>
                 voluntary_exits,
                 sync_aggregate,
                 execution_payload: BlindedPayloadBellatrix {
-                    execution_payload_header: From::from(&execution_payload),
+                    execution_payload_header: From::from(&*execution_payload),
                 },
             },
-            Some(execution_payload),
+            Some(execution_payload.into_inner()),
         )
     }
 }
@@ -564,11 +564,11 @@ impl<E: EthSpec> From<BeaconBlockBodyCapella<E, FullPayload<E>>>
                 voluntary_exits,
                 sync_aggregate,
                 execution_payload: BlindedPayloadCapella {
-                    execution_payload_header: From::from(&execution_payload),
+                    execution_payload_header: From::from(&*execution_payload),
                 },
                 bls_to_execution_changes,
             },
-            Some(execution_payload),
+            Some(execution_payload.into_inner()),
         )
     }
 }
@@ -607,12 +607,12 @@ impl<E: EthSpec> From<BeaconBlockBodyDeneb<E, FullPayload<E>>>
                 voluntary_exits,
                 sync_aggregate,
                 execution_payload: BlindedPayloadDeneb {
-                    execution_payload_header: From::from(&execution_payload),
+                    execution_payload_header: From::from(&*execution_payload),
                 },
                 bls_to_execution_changes,
                 blob_kzg_commitments,
             },
-            Some(execution_payload),
+            Some(execution_payload.into_inner()),
         )
     }
 }
@@ -652,13 +652,13 @@ impl<E: EthSpec> From<BeaconBlockBodyElectra<E, FullPayload<E>>>
                 voluntary_exits,
                 sync_aggregate,
                 execution_payload: BlindedPayloadElectra {
-                    execution_payload_header: From::from(&execution_payload),
+                    execution_payload_header: From::from(&*execution_payload),
                 },
                 bls_to_execution_changes,
                 blob_kzg_commitments: blob_kzg_commitments.clone(),
                 consolidations,
             },
-            Some(execution_payload),
+            Some(execution_payload.into_inner()),
         )
     }
 }
@@ -704,7 +704,7 @@ impl<E: EthSpec> BeaconBlockBodyBellatrix<E, FullPayload<E>> {
             voluntary_exits: voluntary_exits.clone(),
             sync_aggregate: sync_aggregate.clone(),
             execution_payload: BlindedPayloadBellatrix {
-                execution_payload_header: execution_payload.into(),
+                execution_payload_header: (&**execution_payload).into(),
             },
         }
     }
@@ -737,7 +737,7 @@ impl<E: EthSpec> BeaconBlockBodyCapella<E, FullPayload<E>> {
             voluntary_exits: voluntary_exits.clone(),
             sync_aggregate: sync_aggregate.clone(),
             execution_payload: BlindedPayloadCapella {
-                execution_payload_header: execution_payload.into(),
+                execution_payload_header: (&**execution_payload).into(),
             },
             bls_to_execution_changes: bls_to_execution_changes.clone(),
         }
@@ -772,7 +772,7 @@ impl<E: EthSpec> BeaconBlockBodyDeneb<E, FullPayload<E>> {
             voluntary_exits: voluntary_exits.clone(),
             sync_aggregate: sync_aggregate.clone(),
             execution_payload: BlindedPayloadDeneb {
-                execution_payload_header: execution_payload.into(),
+                execution_payload_header: (&**execution_payload).into(),
             },
             bls_to_execution_changes: bls_to_execution_changes.clone(),
             blob_kzg_commitments: blob_kzg_commitments.clone(),
@@ -809,7 +809,7 @@ impl<E: EthSpec> BeaconBlockBodyElectra<E, FullPayload<E>> {
             voluntary_exits: voluntary_exits.clone(),
             sync_aggregate: sync_aggregate.clone(),
             execution_payload: BlindedPayloadElectra {
-                execution_payload_header: execution_payload.into(),
+                execution_payload_header: (&**execution_payload).into(),
             },
             bls_to_execution_changes: bls_to_execution_changes.clone(),
             blob_kzg_commitments: blob_kzg_commitments.clone(),
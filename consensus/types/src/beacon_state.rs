@@ -1,5 +1,6 @@
 use self::committee_cache::get_active_validator_indices;
 use crate::historical_summary::HistoricalSummary;
+use crate::state_proof::{FieldTree, TreeListField};
 use crate::test_utils::TestRandom;
 use crate::*;
 use compare_fields::CompareFields;
@@ -2480,33 +2481,10 @@ impl<E: EthSpec> BeaconState<E> {
         Ok(())
     }
 
-    pub fn compute_merkle_proof(&self, generalized_index: usize) -> Result<Vec<Hash256>, Error> {
-        // 1. Convert generalized index to field index.
-        let field_index = match generalized_index {
-            light_client_update::CURRENT_SYNC_COMMITTEE_INDEX
-            | light_client_update::NEXT_SYNC_COMMITTEE_INDEX => {
-                // Sync committees are top-level fields, subtract off the generalized indices
-                // for the internal nodes. Result should be 22 or 23, the field offset of the committee
-                // in the `BeaconState`:
-                // https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/beacon-chain.md#beaconstate
-                generalized_index
-                    .checked_sub(self.num_fields_pow2())
-                    .ok_or(Error::IndexNotSupported(generalized_index))?
-            }
-            light_client_update::FINALIZED_ROOT_INDEX => {
-                // Finalized root is the right child of `finalized_checkpoint`, divide by two to get
-                // the generalized index of `state.finalized_checkpoint`.
-                let finalized_checkpoint_generalized_index = generalized_index / 2;
-                // Subtract off the internal nodes. Result should be 105/2 - 32 = 20 which matches
-                // position of `finalized_checkpoint` in `BeaconState`.
-                finalized_checkpoint_generalized_index
-                    .checked_sub(self.num_fields_pow2())
-                    .ok_or(Error::IndexNotSupported(generalized_index))?
-            }
-            _ => return Err(Error::IndexNotSupported(generalized_index)),
-        };
-
-        // 2. Get all `BeaconState` leaves.
+    /// Returns the tree hash root of each field of the `BeaconState`, in order.
+    ///
+    /// These are the leaves of the tree whose root is the state root.
+    pub fn get_beacon_state_leaves(&self) -> Vec<Hash256> {
         let mut leaves = vec![];
         #[allow(clippy::arithmetic_side_effects)]
         match self {
@@ -2541,6 +2519,107 @@ impl<E: EthSpec> BeaconState<E> {
                 });
             }
         };
+        leaves
+    }
+
+    /// Returns the tree of the field at `field_index`, or `None` if the field is not a `List` or
+    /// `Vector`.
+    pub(crate) fn get_tree_list_field_tree(&self, field_index: usize) -> Option<FieldTree> {
+        let mut field_names = vec![];
+        let mut field_tree = None;
+        match self {
+            BeaconState::Base(state) => {
+                map_beacon_state_base_fields!(state, |name, _| {
+                    field_names.push(name);
+                });
+                map_beacon_state_base_tree_list_fields_immutable!(state, |name, field| {
+                    if field_names.get(field_index) == Some(&name) {
+                        field_tree = Some(field.field_tree());
+                    }
+                });
+            }
+            BeaconState::Altair(state) => {
+                map_beacon_state_altair_fields!(state, |name, _| {
+                    field_names.push(name);
+                });
+                map_beacon_state_altair_tree_list_fields_immutable!(state, |name, field| {
+                    if field_names.get(field_index) == Some(&name) {
+                        field_tree = Some(field.field_tree());
+                    }
+                });
+            }
+            BeaconState::Bellatrix(state) => {
+                map_beacon_state_bellatrix_fields!(state, |name, _| {
+                    field_names.push(name);
+                });
+                map_beacon_state_bellatrix_tree_list_fields_immutable!(state, |name, field| {
+                    if field_names.get(field_index) == Some(&name) {
+                        field_tree = Some(field.field_tree());
+                    }
+                });
+            }
+            BeaconState::Capella(state) => {
+                map_beacon_state_capella_fields!(state, |name, _| {
+                    field_names.push(name);
+                });
+                map_beacon_state_capella_tree_list_fields_immutable!(state, |name, field| {
+                    if field_names.get(field_index) == Some(&name) {
+                        field_tree = Some(field.field_tree());
+                    }
+                });
+            }
+            BeaconState::Deneb(state) => {
+                map_beacon_state_deneb_fields!(state, |name, _| {
+                    field_names.push(name);
+                });
+                map_beacon_state_deneb_tree_list_fields_immutable!(state, |name, field| {
+                    if field_names.get(field_index) == Some(&name) {
+                        field_tree = Some(field.field_tree());
+                    }
+                });
+            }
+            BeaconState::Electra(state) => {
+                map_beacon_state_electra_fields!(state, |name, _| {
+                    field_names.push(name);
+                });
+                map_beacon_state_electra_tree_list_fields_immutable!(state, |name, field| {
+                    if field_names.get(field_index) == Some(&name) {
+                        field_tree = Some(field.field_tree());
+                    }
+                });
+            }
+        };
+        field_tree
+    }
+
+    pub fn compute_merkle_proof(&self, generalized_index: usize) -> Result<Vec<Hash256>, Error> {
+        // 1. Convert generalized index to field index.
+        let field_index = match generalized_index {
+            light_client_update::CURRENT_SYNC_COMMITTEE_INDEX
+            | light_client_update::NEXT_SYNC_COMMITTEE_INDEX => {
+                // Sync committees are top-level fields, subtract off the generalized indices
+                // for the internal nodes. Result should be 22 or 23, the field offset of the committee
+                // in the `BeaconState`:
+                // https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/beacon-chain.md#beaconstate
+                generalized_index
+                    .checked_sub(self.num_fields_pow2())
+                    .ok_or(Error::IndexNotSupported(generalized_index))?
+            }
+            light_client_update::FINALIZED_ROOT_INDEX => {
+                // Finalized root is the right child of `finalized_checkpoint`, divide by two to get
+                // the generalized index of `state.finalized_checkpoint`.
+                let finalized_checkpoint_generalized_index = generalized_index / 2;
+                // Subtract off the internal nodes. Result should be 105/2 - 32 = 20 which matches
+                // position of `finalized_checkpoint` in `BeaconState`.
+                finalized_checkpoint_generalized_index
+                    .checked_sub(self.num_fields_pow2())
+                    .ok_or(Error::IndexNotSupported(generalized_index))?
+            }
+            _ => return Err(Error::IndexNotSupported(generalized_index)),
+        };
+
+        // 2. Get all `BeaconState` leaves.
+        let leaves = self.get_beacon_state_leaves();
 
         // 3. Make deposit tree.
         // Use the depth of the `BeaconState` fields (i.e. `log2(32) = 5`).
@@ -158,6 +158,17 @@ async fn cache_initialization() {
     test_cache_initialization(&mut state, RelativeEpoch::Next, &spec);
 }
 
+#[tokio::test]
+async fn beacon_state_leaves_hash_to_state_root() {
+    let mut state = build_state::<MinimalEthSpec>(16).await;
+
+    let leaves = state.get_beacon_state_leaves();
+    let depth = state.num_fields_pow2().trailing_zeros() as usize;
+    let root = merkle_proof::MerkleTree::create(&leaves, depth).hash();
+
+    assert_eq!(root, state.update_tree_hash_cache().unwrap());
+}
+
 /// Tests committee-specific components
 #[cfg(test)]
 mod committees {
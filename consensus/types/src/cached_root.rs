@@ -0,0 +1,325 @@
+use crate::test_utils::TestRandom;
+use crate::Hash256;
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ssz::{Decode, DecodeError, Encode};
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+use tree_hash::TreeHash;
+
+/// A value whose tree hash root is computed at most once and cached.
+///
+/// Blocks are hashed many times over their lifetime, e.g. during production, gossip verification,
+/// import and when served by the HTTP API. Wrapping the block body and the execution payload in
+/// `CachedRoot` lets these share a single Merkleization of each.
+///
+/// The value is transparent to SSZ, serde and tree hashing. The cached root is cleared whenever
+/// the value is borrowed mutably.
+pub struct CachedRoot<T> {
+    value: T,
+    tree_hash_root: OnceLock<Hash256>,
+}
+
+impl<T> CachedRoot<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            tree_hash_root: OnceLock::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the cached tree hash root, if it has been computed.
+    pub fn cached_tree_hash_root(&self) -> Option<Hash256> {
+        self.tree_hash_root.get().copied()
+    }
+}
+
+impl<T> From<T> for CachedRoot<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Default> Default for CachedRoot<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Clone> Clone for CachedRoot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            tree_hash_root: self.tree_hash_root.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CachedRoot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for CachedRoot<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for CachedRoot<T> {}
+
+impl<T: Hash> Hash for CachedRoot<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<T> Deref for CachedRoot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CachedRoot<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.tree_hash_root.take();
+        &mut self.value
+    }
+}
+
+impl<T> Borrow<T> for CachedRoot<T> {
+    fn borrow(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Serialize> Serialize for CachedRoot<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CachedRoot<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+impl<T: Encode> Encode for CachedRoot<T> {
+    fn is_ssz_fixed_len() -> bool {
+        T::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        <T as Encode>::ssz_fixed_len()
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        self.value.ssz_bytes_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.value.ssz_append(buf)
+    }
+}
+
+impl<T: Decode> Decode for CachedRoot<T> {
+    fn is_ssz_fixed_len() -> bool {
+        T::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        <T as Decode>::ssz_fixed_len()
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        T::from_ssz_bytes(bytes).map(Self::new)
+    }
+}
+
+impl<T: TreeHash> TreeHash for CachedRoot<T> {
+    fn tree_hash_type() -> tree_hash::TreeHashType {
+        T::tree_hash_type()
+    }
+
+    fn tree_hash_packed_encoding(&self) -> tree_hash::PackedEncoding {
+        self.value.tree_hash_packed_encoding()
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        T::tree_hash_packing_factor()
+    }
+
+    fn tree_hash_root(&self) -> tree_hash::Hash256 {
+        *self
+            .tree_hash_root
+            .get_or_init(|| self.value.tree_hash_root())
+    }
+}
+
+impl<T: TestRandom> TestRandom for CachedRoot<T> {
+    fn random_for_test(rng: &mut impl RngCore) -> Self {
+        Self::new(T::random_for_test(rng))
+    }
+}
+
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for CachedRoot<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        T::arbitrary(u).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
+    use crate::{
+        BeaconBlock, BeaconBlockBodyRefMut, BeaconBlockDeneb, ExecutionPayloadDeneb, ForkName,
+        MainnetEthSpec, Signature, SignedBeaconBlock,
+    };
+
+    type E = MainnetEthSpec;
+
+    /// Asserts that the tree hash root of `block` matches that of a copy without cached roots.
+    fn assert_root_is_fresh(block: &BeaconBlock<E>) {
+        let fresh =
+            BeaconBlock::<E>::from_ssz_bytes_for_fork(&block.as_ssz_bytes(), ForkName::Deneb)
+                .unwrap();
+        assert_eq!(block.tree_hash_root(), fresh.tree_hash_root());
+    }
+
+    #[test]
+    fn cached_root_matches_value_root() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let payload = CachedRoot::new(ExecutionPayloadDeneb::<E>::random_for_test(rng));
+        let expected = payload.value.tree_hash_root();
+
+        assert_eq!(payload.cached_tree_hash_root(), None);
+        assert_eq!(payload.tree_hash_root(), expected);
+        assert_eq!(payload.cached_tree_hash_root(), Some(expected));
+        assert_eq!(payload.clone().cached_tree_hash_root(), Some(expected));
+    }
+
+    #[test]
+    fn ssz_round_trip_is_transparent() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let payload = ExecutionPayloadDeneb::<E>::random_for_test(rng);
+        let cached = CachedRoot::new(payload.clone());
+
+        assert_eq!(cached.as_ssz_bytes(), payload.as_ssz_bytes());
+        assert_eq!(
+            CachedRoot::<ExecutionPayloadDeneb<E>>::from_ssz_bytes(&payload.as_ssz_bytes()),
+            Ok(cached)
+        );
+    }
+
+    #[test]
+    fn block_mutation_clears_cached_roots() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let mut block = BeaconBlock::Deneb(BeaconBlockDeneb::<E>::random_for_test(rng));
+        let original = block.tree_hash_root();
+
+        block
+            .body_mut()
+            .execution_payload_deneb_mut()
+            .unwrap()
+            .execution_payload
+            .gas_used += 1;
+
+        let inner = block.as_deneb().unwrap();
+        assert_eq!(inner.body.cached_tree_hash_root(), None);
+        assert_eq!(
+            inner
+                .body
+                .execution_payload
+                .execution_payload
+                .cached_tree_hash_root(),
+            None
+        );
+
+        let fresh = BeaconBlock::<E>::from_ssz_bytes_for_fork(
+            &block.as_ssz_bytes(),
+            crate::ForkName::Deneb,
+        )
+        .unwrap();
+        assert_ne!(block.tree_hash_root(), original);
+        assert_eq!(block.tree_hash_root(), fresh.tree_hash_root());
+    }
+
+    #[test]
+    fn every_mutation_path_clears_cached_roots() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let block = BeaconBlock::Deneb(BeaconBlockDeneb::<E>::random_for_test(rng));
+        let original = block.tree_hash_root();
+
+        let mutations: Vec<(&str, fn(&mut BeaconBlock<E>))> = vec![
+            ("body_mut", |block| {
+                let BeaconBlockBodyRefMut::Deneb(body) = block.body_mut() else {
+                    panic!("not a deneb body");
+                };
+                body.eth1_data.deposit_count += 1;
+            }),
+            ("body deref_mut", |block| {
+                block.as_deneb_mut().unwrap().body.eth1_data.deposit_count += 1;
+            }),
+            ("payload deref_mut", |block| {
+                let body = &mut block.as_deneb_mut().unwrap().body;
+                body.execution_payload.execution_payload.gas_limit += 1;
+            }),
+            ("body into_inner round trip", |block| {
+                let inner = block.as_deneb_mut().unwrap();
+                let mut body = inner.body.clone().into_inner();
+                body.eth1_data.deposit_count += 1;
+                inner.body = CachedRoot::new(body);
+            }),
+            ("payload into_inner round trip", |block| {
+                let full_payload = &mut block.as_deneb_mut().unwrap().body.execution_payload;
+                let mut payload = full_payload.execution_payload.clone().into_inner();
+                payload.gas_limit += 1;
+                full_payload.execution_payload = payload.into();
+            }),
+            ("clone", |block| {
+                let mut clone = block.clone();
+                clone.as_deneb_mut().unwrap().body.eth1_data.deposit_count += 1;
+                *block = clone;
+            }),
+        ];
+
+        for (path, mutate) in mutations {
+            let mut block = block.clone();
+            assert_eq!(block.tree_hash_root(), original, "{path}");
+            mutate(&mut block);
+            assert_ne!(block.tree_hash_root(), original, "{path}");
+            assert_root_is_fresh(&block);
+        }
+
+        // Through the message of a signed block.
+        let mut signed = SignedBeaconBlock::from_block(block.clone(), Signature::empty());
+        assert_eq!(signed.canonical_root(), original);
+        let BeaconBlockBodyRefMut::Deneb(body) = signed.message_mut().body_mut() else {
+            panic!("not a deneb body");
+        };
+        body.execution_payload.execution_payload.gas_limit += 1;
+        assert_ne!(signed.canonical_root(), original);
+        let (block, _) = signed.deconstruct();
+        assert_root_is_fresh(&block);
+
+        // A round trip without a mutation recomputes the same root.
+        let mut block = block;
+        let root = block.tree_hash_root();
+        let inner = block.as_deneb_mut().unwrap();
+        inner.body = CachedRoot::new(inner.body.clone().into_inner());
+        assert_eq!(inner.body.cached_tree_hash_root(), None);
+        assert_eq!(block.tree_hash_root(), root);
+    }
+}
@@ -510,6 +510,17 @@ impl ChainSpec {
         )
     }
 
+    /// Get the domain of the handoff of a validator between validator clients.
+    ///
+    /// Handoffs are valid across forks, thus the domain is computed with the genesis fork version.
+    pub fn get_validator_handoff_domain(&self) -> Hash256 {
+        self.compute_domain(
+            Domain::ApplicationMask(ApplicationDomain::ValidatorHandoff),
+            self.genesis_fork_version,
+            Hash256::zero(),
+        )
+    }
+
     /// Return the 32-byte fork data root for the `current_version` and `genesis_validators_root`.
     ///
     /// This is used primarily in signature domains to avoid collisions across forks/chains.
@@ -2169,4 +2180,12 @@ mod yaml_tests {
             [0, 0, 0, 1]
         );
     }
+
+    #[test]
+    fn test_domain_validator_handoff() {
+        assert_eq!(
+            int_to_bytes4(ApplicationDomain::ValidatorHandoff.get_domain_constant()),
+            [0x4c, 0x48, 0, 1]
+        );
+    }
 }
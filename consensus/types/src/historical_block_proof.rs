@@ -0,0 +1,332 @@
+use crate::{BeaconState, BeaconStateError, EthSpec, Hash256, Slot, Unsigned};
+use merkle_proof::{verify_merkle_proof, MerkleTree, MerkleTreeError};
+use safe_arith::{ArithError, SafeArith};
+use serde::{Deserialize, Serialize};
+use tree_hash::TreeHash;
+
+/// The field index of `historical_roots` in every `BeaconState`.
+pub const HISTORICAL_ROOTS_INDEX: usize = 7;
+/// The field index of `historical_summaries` in a Capella or later `BeaconState`.
+pub const HISTORICAL_SUMMARIES_INDEX: usize = 27;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The state does not have a historical root for the era of the block yet.
+    EraNotInState {
+        era: usize,
+        num_eras: usize,
+    },
+    /// The era is before Capella, so the state roots of the era are needed for the proof.
+    MissingStateRoots,
+    InvalidRootsLength {
+        expected: usize,
+        actual: usize,
+    },
+    /// The roots of the era do not match the historical root in the state.
+    HistoricalRootMismatch {
+        era: usize,
+        expected: Hash256,
+        computed: Hash256,
+    },
+    BeaconStateError(BeaconStateError),
+    MerkleTreeError(MerkleTreeError),
+    ArithError(ArithError),
+}
+
+/// A proof that the root of the block at `slot` is committed to by the state at `state_slot`.
+///
+/// The block root is proven against the `HistoricalBatch` root in `state.historical_roots`, or the
+/// `HistoricalSummary` root in `state.historical_summaries` (which is the same root), and that
+/// root is proven against the state root. Both proofs can be checked with `verify_merkle_proof`,
+/// using the length of the branch as the depth.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalBlockProof {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub state_slot: Slot,
+    pub state_root: Hash256,
+    pub historical_root: Hash256,
+    /// The branch from `block_root` to `historical_root`.
+    pub block_proof: Vec<Hash256>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub block_proof_index: u64,
+    /// The branch from `historical_root` to `state_root`.
+    pub state_proof: Vec<Hash256>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub state_proof_index: u64,
+}
+
+impl HistoricalBlockProof {
+    /// Returns `true` if the era of `slot` is before Capella in `state`, and so requires the state
+    /// roots of the era to be passed to `new`.
+    pub fn requires_state_roots<E: EthSpec>(state: &BeaconState<E>, slot: Slot) -> bool {
+        slot.as_usize()
+            .safe_div(E::slots_per_historical_root())
+            .map_or(false, |era| era < state.historical_roots().len())
+    }
+
+    /// Computes the proof for the block at `slot` against `state`.
+    ///
+    /// `block_roots` must contain the block root of every slot of the block's era, repeating the
+    /// previous root at skipped slots, as in `state.block_roots`. `state_roots` are the state
+    /// roots of the same slots, required if `requires_state_roots` is `true`.
+    pub fn new<E: EthSpec>(
+        state: &BeaconState<E>,
+        slot: Slot,
+        block_roots: &[Hash256],
+        state_roots: Option<&[Hash256]>,
+    ) -> Result<Self, Error> {
+        let slots_per_era = E::slots_per_historical_root();
+        let era = slot.as_usize().safe_div(slots_per_era)?;
+        let block_index = slot.as_usize().safe_rem(slots_per_era)?;
+        let era_depth = slots_per_era.trailing_zeros() as usize;
+        check_length(block_roots, slots_per_era)?;
+
+        // Find the historical root of the era, and the roots of the list it is in.
+        let historical_roots = state.historical_roots();
+        let num_historical_roots = historical_roots.len();
+        let (field_index, list_index, leaves, state_roots_root) = if era < num_historical_roots {
+            let state_roots = state_roots.ok_or(Error::MissingStateRoots)?;
+            check_length(state_roots, slots_per_era)?;
+            (
+                HISTORICAL_ROOTS_INDEX,
+                era,
+                historical_roots.iter().copied().collect::<Vec<_>>(),
+                MerkleTree::create(state_roots, era_depth).hash(),
+            )
+        } else {
+            // A state from before Capella has no summaries.
+            let summaries = state.historical_summaries().ok();
+            let num_summaries = summaries.map_or(0, |summaries| summaries.len());
+            let list_index = era.safe_sub(num_historical_roots)?;
+            let summary = summaries
+                .and_then(|summaries| summaries.get(list_index))
+                .ok_or(Error::EraNotInState {
+                    era,
+                    num_eras: num_historical_roots.safe_add(num_summaries)?,
+                })?;
+            let leaves = summaries
+                .map(|summaries| {
+                    summaries
+                        .iter()
+                        .map(TreeHash::tree_hash_root)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            (
+                HISTORICAL_SUMMARIES_INDEX,
+                list_index,
+                leaves,
+                summary.state_summary_root(),
+            )
+        };
+        let list_len = leaves.len();
+        let historical_root = leaves
+            .get(list_index)
+            .copied()
+            .ok_or(Error::EraNotInState {
+                era,
+                num_eras: list_len,
+            })?;
+
+        // Prove the block root against the historical root, which is the root of the era's block
+        // roots and state roots.
+        let (block_root, mut block_proof) =
+            MerkleTree::create(block_roots, era_depth).generate_proof(block_index, era_depth)?;
+        block_proof.push(state_roots_root);
+        let block_proof_depth = era_depth.safe_add(1)?;
+        if !verify_merkle_proof(
+            block_root,
+            &block_proof,
+            block_proof_depth,
+            block_index,
+            historical_root,
+        ) {
+            return Err(Error::HistoricalRootMismatch {
+                era,
+                expected: historical_root,
+                computed: merkle_proof::merkle_root_from_branch(
+                    block_root,
+                    &block_proof,
+                    block_proof_depth,
+                    block_index,
+                ),
+            });
+        }
+
+        // Prove the historical root against the list, mix in the list's length and prove the list
+        // against the state.
+        let list_depth = E::HistoricalRootsLimit::to_usize().trailing_zeros() as usize;
+        let (_, mut state_proof) =
+            MerkleTree::create(&leaves, list_depth).generate_proof(list_index, list_depth)?;
+        let mut length_bytes = [0; 32];
+        length_bytes
+            .get_mut(..8)
+            .ok_or(Error::ArithError(ArithError::Overflow))?
+            .copy_from_slice(&(list_len as u64).to_le_bytes());
+        state_proof.push(Hash256::from(length_bytes));
+
+        let field_depth = state.num_fields_pow2().trailing_zeros() as usize;
+        let state_tree = MerkleTree::create(&state.get_beacon_state_leaves(), field_depth);
+        let (_, field_proof) = state_tree.generate_proof(field_index, field_depth)?;
+        state_proof.extend(field_proof);
+        let state_proof_index = field_index
+            .safe_shl(list_depth.safe_add(1)? as u32)?
+            .safe_add(list_index)?;
+
+        Ok(Self {
+            slot,
+            block_root,
+            state_slot: state.slot(),
+            state_root: state_tree.hash(),
+            historical_root,
+            block_proof,
+            block_proof_index: block_index as u64,
+            state_proof,
+            state_proof_index: state_proof_index as u64,
+        })
+    }
+
+    /// Returns `true` if both proofs are valid.
+    pub fn verify(&self) -> bool {
+        verify_merkle_proof(
+            self.block_root,
+            &self.block_proof,
+            self.block_proof.len(),
+            self.block_proof_index as usize,
+            self.historical_root,
+        ) && verify_merkle_proof(
+            self.historical_root,
+            &self.state_proof,
+            self.state_proof.len(),
+            self.state_proof_index as usize,
+            self.state_root,
+        )
+    }
+}
+
+fn check_length(roots: &[Hash256], expected: usize) -> Result<(), Error> {
+    if roots.len() == expected {
+        Ok(())
+    } else {
+        Err(Error::InvalidRootsLength {
+            expected,
+            actual: roots.len(),
+        })
+    }
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Error {
+        Error::BeaconStateError(e)
+    }
+}
+
+impl From<MerkleTreeError> for Error {
+    fn from(e: MerkleTreeError) -> Error {
+        Error::MerkleTreeError(e)
+    }
+}
+
+impl From<ArithError> for Error {
+    fn from(e: ArithError) -> Error {
+        Error::ArithError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::historical_summary::HistoricalSummary;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use crate::{BeaconStateCapella, List, MinimalEthSpec, Vector};
+
+    type E = MinimalEthSpec;
+
+    /// Returns distinct block and state roots for every slot of `era`.
+    fn era_roots(era: u64) -> (Vec<Hash256>, Vec<Hash256>) {
+        let slots = E::slots_per_historical_root() as u64;
+        let roots = |offset| {
+            (0..slots)
+                .map(|i| Hash256::from_low_u64_be(offset + era * slots + i))
+                .collect::<Vec<_>>()
+        };
+        (roots(1), roots(1_000_000))
+    }
+
+    /// A Capella state with a historical root for era 0 and a historical summary for era 1.
+    fn state() -> BeaconState<E> {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let with_roots =
+            |(block_roots, state_roots): (Vec<Hash256>, Vec<Hash256>)| BeaconStateCapella {
+                block_roots: Vector::new(block_roots).unwrap(),
+                state_roots: Vector::new(state_roots).unwrap(),
+                ..<_>::random_for_test(&mut XorShiftRng::from_seed([7; 16]))
+            };
+        let era_0 = with_roots(era_roots(0));
+        let era_1 = BeaconState::Capella(with_roots(era_roots(1)));
+        BeaconState::Capella(BeaconStateCapella {
+            slot: Slot::new(3 * E::slots_per_historical_root() as u64),
+            historical_roots: List::new(vec![
+                HistoricalSummary::new(&BeaconState::Capella(era_0)).tree_hash_root()
+            ])
+            .unwrap(),
+            historical_summaries: List::new(vec![HistoricalSummary::new(&era_1)]).unwrap(),
+            ..<_>::random_for_test(rng)
+        })
+    }
+
+    #[test]
+    fn proves_historical_root() {
+        let mut state = state();
+        let slot = Slot::new(5);
+        let (block_roots, state_roots) = era_roots(0);
+        assert!(HistoricalBlockProof::requires_state_roots(&state, slot));
+        assert_eq!(
+            HistoricalBlockProof::new(&state, slot, &block_roots, None),
+            Err(Error::MissingStateRoots)
+        );
+
+        let proof =
+            HistoricalBlockProof::new(&state, slot, &block_roots, Some(&state_roots)).unwrap();
+        assert_eq!(proof.block_root, block_roots[5]);
+        assert_eq!(proof.state_root, state.canonical_root().unwrap());
+        assert!(proof.verify());
+    }
+
+    #[test]
+    fn proves_historical_summary() {
+        let mut state = state();
+        let slots = E::slots_per_historical_root() as u64;
+        let slot = Slot::new(slots + 3);
+        let (block_roots, _) = era_roots(1);
+        assert!(!HistoricalBlockProof::requires_state_roots(&state, slot));
+
+        let proof = HistoricalBlockProof::new(&state, slot, &block_roots, None).unwrap();
+        assert_eq!(proof.block_root, block_roots[3]);
+        assert_eq!(proof.state_root, state.canonical_root().unwrap());
+        assert!(proof.verify());
+
+        let mut invalid = proof.clone();
+        invalid.block_root = Hash256::repeat_byte(0xff);
+        assert!(!invalid.verify());
+    }
+
+    #[test]
+    fn rejects_wrong_roots_and_eras() {
+        let state = state();
+        let slots = E::slots_per_historical_root() as u64;
+        let (block_roots, _) = era_roots(0);
+        assert!(matches!(
+            HistoricalBlockProof::new(&state, Slot::new(slots), &block_roots, None),
+            Err(Error::HistoricalRootMismatch { era: 1, .. })
+        ));
+        assert_eq!(
+            HistoricalBlockProof::new(&state, Slot::new(2 * slots), &block_roots, None),
+            Err(Error::EraNotInState {
+                era: 2,
+                num_eras: 2
+            })
+        );
+    }
+}
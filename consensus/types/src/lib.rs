@@ -48,6 +48,7 @@ pub mod fork_name;
 pub mod fork_versioned_response;
 pub mod graffiti;
 pub mod historical_batch;
+pub mod historical_block_proof;
 pub mod historical_summary;
 pub mod indexed_attestation;
 pub mod light_client_bootstrap;
@@ -71,6 +72,7 @@ pub mod signed_consolidation;
 pub mod signed_contribution_and_proof;
 pub mod signed_voluntary_exit;
 pub mod signing_data;
+pub mod state_proof;
 pub mod sync_committee_subscription;
 pub mod sync_duty;
 pub mod validator;
@@ -95,6 +97,7 @@ pub mod sync_committee_contribution;
 pub mod sync_committee_message;
 pub mod sync_selection_proof;
 pub mod sync_subnet_id;
+pub mod validator_handoff;
 pub mod validator_registration_data;
 pub mod withdrawal;
 
@@ -104,11 +107,13 @@ pub mod slot_data;
 pub mod sqlite;
 
 pub mod blob_sidecar;
+pub mod cached_root;
 pub mod data_column_sidecar;
 pub mod data_column_subnet_id;
 pub mod light_client_header;
 pub mod non_zero_usize;
 pub mod runtime_var_list;
+pub mod transactions;
 
 use ethereum_types::{H160, H256};
 
@@ -141,6 +146,7 @@ pub use crate::beacon_committee::{BeaconCommittee, OwnedBeaconCommittee};
 pub use crate::beacon_state::{Error as BeaconStateError, *};
 pub use crate::blob_sidecar::{BlobSidecar, BlobSidecarList, BlobsList};
 pub use crate::bls_to_execution_change::BlsToExecutionChange;
+pub use crate::cached_root::CachedRoot;
 pub use crate::chain_spec::{ChainSpec, Config, Domain};
 pub use crate::checkpoint::Checkpoint;
 pub use crate::config_and_preset::{
@@ -180,6 +186,7 @@ pub use crate::fork_name::{ForkName, InconsistentFork};
 pub use crate::fork_versioned_response::{ForkVersionDeserialize, ForkVersionedResponse};
 pub use crate::graffiti::{Graffiti, GRAFFITI_BYTES_LEN};
 pub use crate::historical_batch::HistoricalBatch;
+pub use crate::historical_block_proof::HistoricalBlockProof;
 pub use crate::indexed_attestation::{
     IndexedAttestation, IndexedAttestationBase, IndexedAttestationElectra, IndexedAttestationRef,
 };
@@ -240,6 +247,7 @@ pub use crate::signed_contribution_and_proof::SignedContributionAndProof;
 pub use crate::signed_voluntary_exit::SignedVoluntaryExit;
 pub use crate::signing_data::{SignedRoot, SigningData};
 pub use crate::slot_epoch::{Epoch, Slot};
+pub use crate::state_proof::StateProof;
 pub use crate::subnet_id::SubnetId;
 pub use crate::sync_aggregate::SyncAggregate;
 pub use crate::sync_aggregator_selection_data::SyncAggregatorSelectionData;
@@ -251,6 +259,7 @@ pub use crate::sync_duty::SyncDuty;
 pub use crate::sync_selection_proof::SyncSelectionProof;
 pub use crate::sync_subnet_id::SyncSubnetId;
 pub use crate::validator::Validator;
+pub use crate::validator_handoff::*;
 pub use crate::validator_registration_data::*;
 pub use crate::validator_subscription::ValidatorSubscription;
 pub use crate::voluntary_exit::VoluntaryExit;
@@ -80,6 +80,16 @@ impl<E: EthSpec> LightClientBootstrap<E> {
         })
     }
 
+    /// The header of the requested block, regardless of the fork.
+    pub fn header(&self) -> LightClientHeader<E> {
+        match self {
+            Self::Altair(inner) => LightClientHeader::Altair(inner.header.clone()),
+            Self::Capella(inner) => LightClientHeader::Capella(inner.header.clone()),
+            Self::Deneb(inner) => LightClientHeader::Deneb(inner.header.clone()),
+            Self::Electra(inner) => LightClientHeader::Electra(inner.header.clone()),
+        }
+    }
+
     pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let bootstrap = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
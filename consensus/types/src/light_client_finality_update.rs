@@ -156,6 +156,26 @@ impl<E: EthSpec> LightClientFinalityUpdate<E> {
         })
     }
 
+    /// The header signed by the sync committee, regardless of the fork.
+    pub fn attested_header(&self) -> LightClientHeader<E> {
+        match self {
+            Self::Altair(inner) => LightClientHeader::Altair(inner.attested_header.clone()),
+            Self::Capella(inner) => LightClientHeader::Capella(inner.attested_header.clone()),
+            Self::Deneb(inner) => LightClientHeader::Deneb(inner.attested_header.clone()),
+            Self::Electra(inner) => LightClientHeader::Electra(inner.attested_header.clone()),
+        }
+    }
+
+    /// The header finalized by the state of the attested header, regardless of the fork.
+    pub fn finalized_header(&self) -> LightClientHeader<E> {
+        match self {
+            Self::Altair(inner) => LightClientHeader::Altair(inner.finalized_header.clone()),
+            Self::Capella(inner) => LightClientHeader::Capella(inner.finalized_header.clone()),
+            Self::Deneb(inner) => LightClientHeader::Deneb(inner.finalized_header.clone()),
+            Self::Electra(inner) => LightClientHeader::Electra(inner.finalized_header.clone()),
+        }
+    }
+
     pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let finality_update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
@@ -154,13 +154,14 @@ impl<E: EthSpec> LightClientHeaderCapella<E> {
             .execution_payload()?
             .execution_payload_capella()?;
 
-        let header = ExecutionPayloadHeaderCapella::from(payload);
+        let header = ExecutionPayloadHeaderCapella::from(&**payload);
         let beacon_block_body = BeaconBlockBody::from(
             block
                 .message()
                 .body_capella()
                 .map_err(|_| Error::BeaconBlockBodyError)?
-                .to_owned(),
+                .to_owned()
+                .into_inner(),
         );
 
         let execution_branch =
@@ -182,13 +183,14 @@ impl<E: EthSpec> LightClientHeaderDeneb<E> {
             .execution_payload()?
             .execution_payload_deneb()?;
 
-        let header = ExecutionPayloadHeaderDeneb::from(payload);
+        let header = ExecutionPayloadHeaderDeneb::from(&**payload);
         let beacon_block_body = BeaconBlockBody::from(
             block
                 .message()
                 .body_deneb()
                 .map_err(|_| Error::BeaconBlockBodyError)?
-                .to_owned(),
+                .to_owned()
+                .into_inner(),
         );
 
         let execution_branch =
@@ -210,13 +212,14 @@ impl<E: EthSpec> LightClientHeaderElectra<E> {
             .execution_payload()?
             .execution_payload_electra()?;
 
-        let header = ExecutionPayloadHeaderElectra::from(payload);
+        let header = ExecutionPayloadHeaderElectra::from(&**payload);
         let beacon_block_body = BeaconBlockBody::from(
             block
                 .message()
                 .body_electra()
                 .map_err(|_| Error::BeaconBlockBodyError)?
-                .to_owned(),
+                .to_owned()
+                .into_inner(),
         );
 
         let execution_branch =
@@ -141,6 +141,16 @@ impl<E: EthSpec> LightClientOptimisticUpdate<E> {
         })
     }
 
+    /// The header signed by the sync committee, regardless of the fork.
+    pub fn attested_header(&self) -> LightClientHeader<E> {
+        match self {
+            Self::Altair(inner) => LightClientHeader::Altair(inner.attested_header.clone()),
+            Self::Capella(inner) => LightClientHeader::Capella(inner.attested_header.clone()),
+            Self::Deneb(inner) => LightClientHeader::Deneb(inner.attested_header.clone()),
+            Self::Electra(inner) => LightClientHeader::Electra(inner.attested_header.clone()),
+        }
+    }
+
     pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let optimistic_update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
@@ -1,5 +1,5 @@
 use super::{EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
-use crate::light_client_header::LightClientHeaderElectra;
+use crate::light_client_header::{LightClientHeader, LightClientHeaderElectra};
 use crate::{
     beacon_state, test_utils::TestRandom, BeaconBlock, BeaconBlockHeader, BeaconState, ChainSpec,
     ForkName, ForkVersionDeserialize, LightClientHeaderAltair, LightClientHeaderCapella,
@@ -19,9 +19,13 @@ use test_random_derive::TestRandom;
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
+/// Generalized index of the finalized checkpoint root in a `BeaconState`.
 pub const FINALIZED_ROOT_INDEX: usize = 105;
+/// Generalized index of the current sync committee in a `BeaconState`.
 pub const CURRENT_SYNC_COMMITTEE_INDEX: usize = 54;
+/// Generalized index of the next sync committee in a `BeaconState`.
 pub const NEXT_SYNC_COMMITTEE_INDEX: usize = 55;
+/// Generalized index of the execution payload in a `BeaconBlockBody`.
 pub const EXECUTION_PAYLOAD_INDEX: usize = 25;
 
 pub type FinalizedRootProofLen = U6;
@@ -280,6 +284,26 @@ impl<E: EthSpec> LightClientUpdate<E> {
 
         Ok(update)
     }
+
+    /// The header signed by the sync committee, regardless of the fork.
+    pub fn attested_header(&self) -> LightClientHeader<E> {
+        match self {
+            Self::Altair(inner) => LightClientHeader::Altair(inner.attested_header.clone()),
+            Self::Capella(inner) => LightClientHeader::Capella(inner.attested_header.clone()),
+            Self::Deneb(inner) => LightClientHeader::Deneb(inner.attested_header.clone()),
+            Self::Electra(inner) => LightClientHeader::Electra(inner.attested_header.clone()),
+        }
+    }
+
+    /// The header finalized by the state of the attested header, regardless of the fork.
+    pub fn finalized_header(&self) -> LightClientHeader<E> {
+        match self {
+            Self::Altair(inner) => LightClientHeader::Altair(inner.finalized_header.clone()),
+            Self::Capella(inner) => LightClientHeader::Capella(inner.finalized_header.clone()),
+            Self::Deneb(inner) => LightClientHeader::Deneb(inner.finalized_header.clone()),
+            Self::Electra(inner) => LightClientHeader::Electra(inner.finalized_header.clone()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 use std::fmt::Debug;
 use std::hash::Hash;
 use test_random_derive::TestRandom;
@@ -158,19 +158,19 @@ pub struct FullPayload<E: EthSpec> {
         only(Bellatrix),
         partial_getter(rename = "execution_payload_bellatrix")
     )]
-    pub execution_payload: ExecutionPayloadBellatrix<E>,
+    pub execution_payload: CachedRoot<ExecutionPayloadBellatrix<E>>,
     #[superstruct(only(Capella), partial_getter(rename = "execution_payload_capella"))]
-    pub execution_payload: ExecutionPayloadCapella<E>,
+    pub execution_payload: CachedRoot<ExecutionPayloadCapella<E>>,
     #[superstruct(only(Deneb), partial_getter(rename = "execution_payload_deneb"))]
-    pub execution_payload: ExecutionPayloadDeneb<E>,
+    pub execution_payload: CachedRoot<ExecutionPayloadDeneb<E>>,
     #[superstruct(only(Electra), partial_getter(rename = "execution_payload_electra"))]
-    pub execution_payload: ExecutionPayloadElectra<E>,
+    pub execution_payload: CachedRoot<ExecutionPayloadElectra<E>>,
 }
 
 impl<E: EthSpec> From<FullPayload<E>> for ExecutionPayload<E> {
     fn from(full_payload: FullPayload<E>) -> Self {
         map_full_payload_into_execution_payload!(full_payload, move |payload, cons| {
-            cons(payload.execution_payload)
+            cons(payload.execution_payload.into_inner())
         })
     }
 }
@@ -179,7 +179,7 @@ impl<'a, E: EthSpec> From<FullPayloadRef<'a, E>> for ExecutionPayload<E> {
     fn from(full_payload_ref: FullPayloadRef<'a, E>) -> Self {
         map_full_payload_ref!(&'a _, full_payload_ref, move |payload, cons| {
             cons(payload);
-            payload.execution_payload.clone().into()
+            (*payload.execution_payload).clone().into()
         })
     }
 }
@@ -201,7 +201,8 @@ impl<E: EthSpec> ExecPayload<E> for FullPayload<E> {
     fn to_execution_payload_header<'a>(&'a self) -> ExecutionPayloadHeader<E> {
         map_full_payload_ref!(&'a _, self.to_ref(), move |inner, cons| {
             cons(inner);
-            let exec_payload_ref: ExecutionPayloadRef<'a, E> = From::from(&inner.execution_payload);
+            let exec_payload_ref: ExecutionPayloadRef<'a, E> =
+                From::from(&*inner.execution_payload);
             ExecutionPayloadHeader::from(exec_payload_ref)
         })
     }
@@ -332,7 +333,7 @@ impl<E: EthSpec> ExecPayload<E> for FullPayload<E> {
 impl<E: EthSpec> FullPayload<E> {
     pub fn execution_payload(self) -> ExecutionPayload<E> {
         map_full_payload_into_execution_payload!(self, |inner, cons| {
-            cons(inner.execution_payload)
+            cons(inner.execution_payload.into_inner())
         })
     }
 
@@ -350,7 +351,7 @@ impl<E: EthSpec> FullPayload<E> {
 impl<'a, E: EthSpec> FullPayloadRef<'a, E> {
     pub fn execution_payload_ref(self) -> ExecutionPayloadRef<'a, E> {
         map_full_payload_ref_into_execution_payload_ref!(&'a _, self, |inner, cons| {
-            cons(&inner.execution_payload)
+            cons(&*inner.execution_payload)
         })
     }
 }
@@ -824,7 +825,7 @@ macro_rules! impl_exec_payload_common {
 
             fn to_execution_payload_header(&self) -> ExecutionPayloadHeader<E> {
                 ExecutionPayloadHeader::$fork_variant($wrapped_type_header::from(
-                    &self.$wrapped_field,
+                    Borrow::<$wrapped_type<E>>::borrow(&self.$wrapped_field),
                 ))
             }
 
@@ -857,7 +858,7 @@ macro_rules! impl_exec_payload_common {
             }
 
             fn is_default_with_zero_roots(&self) -> bool {
-                self.$wrapped_field == $wrapped_type::default()
+                self.$wrapped_field == <_>::default()
             }
 
             fn is_default_with_empty_roots(&self) -> bool {
@@ -900,7 +901,9 @@ macro_rules! impl_exec_payload_common {
 
         impl<E: EthSpec> From<$wrapped_type<E>> for $wrapper_type<E> {
             fn from($wrapped_field: $wrapped_type<E>) -> Self {
-                Self { $wrapped_field }
+                Self {
+                    $wrapped_field: $wrapped_field.into(),
+                }
             }
         }
     };
@@ -1007,7 +1010,7 @@ macro_rules! impl_exec_payload_for_fork {
             Full,
             {
                 |wrapper: &$wrapper_type_full<E>| {
-                    wrapper.execution_payload == $wrapped_type_full::default()
+                    *wrapper.execution_payload == $wrapped_type_full::default()
                 }
             },
             {
@@ -1065,7 +1068,7 @@ macro_rules! impl_exec_payload_for_fork {
         impl<E: EthSpec> Default for $wrapper_type_full<E> {
             fn default() -> Self {
                 Self {
-                    execution_payload: $wrapped_type_full::default(),
+                    execution_payload: $wrapped_type_full::default().into(),
                 }
             }
         }
@@ -1074,7 +1077,7 @@ macro_rules! impl_exec_payload_for_fork {
         impl<'a, E: EthSpec> From<Cow<'a, $wrapped_type_full<E>>> for $wrapper_type_full<E> {
             fn from(execution_payload: Cow<'a, $wrapped_type_full<E>>) -> Self {
                 Self {
-                    execution_payload: $wrapped_type_full::from(execution_payload.into_owned()),
+                    execution_payload: execution_payload.into_owned().into(),
                 }
             }
         }
@@ -322,22 +322,22 @@ impl<E: EthSpec> SignedBeaconBlockBellatrix<E, BlindedPayload<E>> {
                     proposer_index,
                     parent_root,
                     state_root,
-                    body:
-                        BeaconBlockBodyBellatrix {
-                            randao_reveal,
-                            eth1_data,
-                            graffiti,
-                            proposer_slashings,
-                            attester_slashings,
-                            attestations,
-                            deposits,
-                            voluntary_exits,
-                            sync_aggregate,
-                            execution_payload: BlindedPayloadBellatrix { .. },
-                        },
+                    body,
                 },
             signature,
         } = self;
+        let BeaconBlockBodyBellatrix {
+            randao_reveal,
+            eth1_data,
+            graffiti,
+            proposer_slashings,
+            attester_slashings,
+            attestations,
+            deposits,
+            voluntary_exits,
+            sync_aggregate,
+            execution_payload: BlindedPayloadBellatrix { .. },
+        } = body.into_inner();
         SignedBeaconBlockBellatrix {
             message: BeaconBlockBellatrix {
                 slot,
@@ -354,8 +354,11 @@ impl<E: EthSpec> SignedBeaconBlockBellatrix<E, BlindedPayload<E>> {
                     deposits,
                     voluntary_exits,
                     sync_aggregate,
-                    execution_payload: FullPayloadBellatrix { execution_payload },
-                },
+                    execution_payload: FullPayloadBellatrix {
+                        execution_payload: execution_payload.into(),
+                    },
+                }
+                .into(),
             },
             signature,
         }
@@ -374,23 +377,23 @@ impl<E: EthSpec> SignedBeaconBlockCapella<E, BlindedPayload<E>> {
                     proposer_index,
                     parent_root,
                     state_root,
-                    body:
-                        BeaconBlockBodyCapella {
-                            randao_reveal,
-                            eth1_data,
-                            graffiti,
-                            proposer_slashings,
-                            attester_slashings,
-                            attestations,
-                            deposits,
-                            voluntary_exits,
-                            sync_aggregate,
-                            execution_payload: BlindedPayloadCapella { .. },
-                            bls_to_execution_changes,
-                        },
+                    body,
                 },
             signature,
         } = self;
+        let BeaconBlockBodyCapella {
+            randao_reveal,
+            eth1_data,
+            graffiti,
+            proposer_slashings,
+            attester_slashings,
+            attestations,
+            deposits,
+            voluntary_exits,
+            sync_aggregate,
+            execution_payload: BlindedPayloadCapella { .. },
+            bls_to_execution_changes,
+        } = body.into_inner();
         SignedBeaconBlockCapella {
             message: BeaconBlockCapella {
                 slot,
@@ -407,9 +410,12 @@ impl<E: EthSpec> SignedBeaconBlockCapella<E, BlindedPayload<E>> {
                     deposits,
                     voluntary_exits,
                     sync_aggregate,
-                    execution_payload: FullPayloadCapella { execution_payload },
+                    execution_payload: FullPayloadCapella {
+                        execution_payload: execution_payload.into(),
+                    },
                     bls_to_execution_changes,
-                },
+                }
+                .into(),
             },
             signature,
         }
@@ -428,24 +434,24 @@ impl<E: EthSpec> SignedBeaconBlockDeneb<E, BlindedPayload<E>> {
                     proposer_index,
                     parent_root,
                     state_root,
-                    body:
-                        BeaconBlockBodyDeneb {
-                            randao_reveal,
-                            eth1_data,
-                            graffiti,
-                            proposer_slashings,
-                            attester_slashings,
-                            attestations,
-                            deposits,
-                            voluntary_exits,
-                            sync_aggregate,
-                            execution_payload: BlindedPayloadDeneb { .. },
-                            bls_to_execution_changes,
-                            blob_kzg_commitments,
-                        },
+                    body,
                 },
             signature,
         } = self;
+        let BeaconBlockBodyDeneb {
+            randao_reveal,
+            eth1_data,
+            graffiti,
+            proposer_slashings,
+            attester_slashings,
+            attestations,
+            deposits,
+            voluntary_exits,
+            sync_aggregate,
+            execution_payload: BlindedPayloadDeneb { .. },
+            bls_to_execution_changes,
+            blob_kzg_commitments,
+        } = body.into_inner();
         SignedBeaconBlockDeneb {
             message: BeaconBlockDeneb {
                 slot,
@@ -462,10 +468,13 @@ impl<E: EthSpec> SignedBeaconBlockDeneb<E, BlindedPayload<E>> {
                     deposits,
                     voluntary_exits,
                     sync_aggregate,
-                    execution_payload: FullPayloadDeneb { execution_payload },
+                    execution_payload: FullPayloadDeneb {
+                        execution_payload: execution_payload.into(),
+                    },
                     bls_to_execution_changes,
                     blob_kzg_commitments,
-                },
+                }
+                .into(),
             },
             signature,
         }
@@ -484,25 +493,25 @@ impl<E: EthSpec> SignedBeaconBlockElectra<E, BlindedPayload<E>> {
                     proposer_index,
                     parent_root,
                     state_root,
-                    body:
-                        BeaconBlockBodyElectra {
-                            randao_reveal,
-                            eth1_data,
-                            graffiti,
-                            proposer_slashings,
-                            attester_slashings,
-                            attestations,
-                            deposits,
-                            voluntary_exits,
-                            sync_aggregate,
-                            execution_payload: BlindedPayloadElectra { .. },
-                            bls_to_execution_changes,
-                            blob_kzg_commitments,
-                            consolidations,
-                        },
+                    body,
                 },
             signature,
         } = self;
+        let BeaconBlockBodyElectra {
+            randao_reveal,
+            eth1_data,
+            graffiti,
+            proposer_slashings,
+            attester_slashings,
+            attestations,
+            deposits,
+            voluntary_exits,
+            sync_aggregate,
+            execution_payload: BlindedPayloadElectra { .. },
+            bls_to_execution_changes,
+            blob_kzg_commitments,
+            consolidations,
+        } = body.into_inner();
         SignedBeaconBlockElectra {
             message: BeaconBlockElectra {
                 slot,
@@ -519,11 +528,14 @@ impl<E: EthSpec> SignedBeaconBlockElectra<E, BlindedPayload<E>> {
                     deposits,
                     voluntary_exits,
                     sync_aggregate,
-                    execution_payload: FullPayloadElectra { execution_payload },
+                    execution_payload: FullPayloadElectra {
+                        execution_payload: execution_payload.into(),
+                    },
                     bls_to_execution_changes,
                     blob_kzg_commitments,
                     consolidations,
-                },
+                }
+                .into(),
             },
             signature,
         }
@@ -0,0 +1,457 @@
+use crate::{BeaconState, EthSpec, Hash256, Slot, Unsigned};
+use ethereum_hashing::{hash32_concat, ZERO_HASHES};
+use int_to_bytes::int_to_bytes32;
+use merkle_proof::MerkleTree;
+use milhouse::{List, Value, Vector};
+use safe_arith::{ArithError, SafeArith};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use tree_hash::{TreeHash, TreeHashType, BYTES_PER_CHUNK};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NoGeneralizedIndices,
+    /// Generalized indices start from 1, the root of the state.
+    InvalidGeneralizedIndex(u64),
+    /// The node is within a field that is not a `List` or `Vector`, or below the leaves of one.
+    UnsupportedGeneralizedIndex(u64),
+    InvalidProofLength {
+        expected: usize,
+        actual: usize,
+    },
+    ArithError(ArithError),
+}
+
+/// A Merkle multiproof of the nodes at `gindices` of the tree of a `BeaconState`.
+///
+/// The generalized indices are relative to the root of the state. Nodes can be proven down to
+/// the fields of the state, and within `List` and `Vector` fields down to the roots of their
+/// elements (or the chunks of packed basic elements) and the length of lists. The proof contains
+/// the helper nodes of the multiproof in descending order of generalized index, as in the
+/// consensus specs. For a single generalized index this is the usual bottom-up Merkle branch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateProof {
+    pub slot: Slot,
+    pub state_root: Hash256,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub gindices: Vec<u64>,
+    /// The roots of the nodes at `gindices`.
+    pub leaves: Vec<Hash256>,
+    pub proof: Vec<Hash256>,
+}
+
+impl StateProof {
+    /// Computes the proof of the nodes at `gindices` of `state`.
+    ///
+    /// The field roots are read from the tree hash cache of `state`. Nodes within a `List` or
+    /// `Vector` field require hashing the elements of the field.
+    pub fn new<E: EthSpec>(state: &BeaconState<E>, gindices: &[u64]) -> Result<Self, Error> {
+        if gindices.is_empty() {
+            return Err(Error::NoGeneralizedIndices);
+        }
+        let mut tree = StateTree::new(state);
+        let leaves = gindices
+            .iter()
+            .map(|gindex| tree.node(*gindex))
+            .collect::<Result<Vec<_>, _>>()?;
+        let proof = get_helper_indices(gindices)?
+            .into_iter()
+            .map(|gindex| tree.node(gindex))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            slot: state.slot(),
+            state_root: tree.fields.hash(),
+            gindices: gindices.to_vec(),
+            leaves,
+            proof,
+        })
+    }
+
+    /// Returns `true` if the proof is valid.
+    pub fn verify(&self) -> bool {
+        calculate_multi_merkle_root(&self.leaves, &self.proof, &self.gindices)
+            .map_or(false, |root| root == self.state_root)
+    }
+}
+
+/// The nodes of the tree of a `BeaconState`, with the trees of its `List` and `Vector` fields
+/// computed as they are needed.
+struct StateTree<'a, E: EthSpec> {
+    state: &'a BeaconState<E>,
+    depth: usize,
+    fields: MerkleTree,
+    field_trees: HashMap<usize, Option<FieldTree>>,
+}
+
+impl<'a, E: EthSpec> StateTree<'a, E> {
+    fn new(state: &'a BeaconState<E>) -> Self {
+        let depth = state.num_fields_pow2().trailing_zeros() as usize;
+        Self {
+            state,
+            depth,
+            fields: MerkleTree::create(&state.get_beacon_state_leaves(), depth),
+            field_trees: HashMap::new(),
+        }
+    }
+
+    fn node(&mut self, gindex: u64) -> Result<Hash256, Error> {
+        let depth = gindex_depth(gindex)?;
+        let unsupported = Error::UnsupportedGeneralizedIndex(gindex);
+        if depth <= self.depth {
+            return tree_node(&self.fields, gindex, depth).ok_or(unsupported);
+        }
+
+        let (field_index, field_gindex) = split_gindex(gindex, depth, self.depth)?;
+        let state = self.state;
+        self.field_trees
+            .entry(field_index)
+            .or_insert_with(|| state.get_tree_list_field_tree(field_index))
+            .as_ref()
+            .and_then(|field_tree| field_tree.node(field_gindex))
+            .ok_or(unsupported)
+    }
+}
+
+/// The tree of a `List` or `Vector` field of a `BeaconState`.
+pub(crate) struct FieldTree {
+    data: MerkleTree,
+    /// The length of a `List`, which is mixed into its root.
+    length: Option<usize>,
+}
+
+impl FieldTree {
+    fn new<'a, T: Value + TreeHash + 'a>(
+        elements: impl Iterator<Item = &'a T>,
+        limit: usize,
+        length: Option<usize>,
+    ) -> Self {
+        let (leaves, leaf_limit) = if T::tree_hash_type() == TreeHashType::Basic {
+            let mut bytes = elements
+                .flat_map(|element| element.tree_hash_packed_encoding())
+                .collect::<Vec<_>>();
+            let num_chunks = bytes.len().div_ceil(BYTES_PER_CHUNK);
+            bytes.resize(num_chunks.saturating_mul(BYTES_PER_CHUNK), 0);
+            (
+                bytes
+                    .chunks(BYTES_PER_CHUNK)
+                    .map(Hash256::from_slice)
+                    .collect::<Vec<_>>(),
+                limit.div_ceil(T::tree_hash_packing_factor()),
+            )
+        } else {
+            (
+                elements.map(TreeHash::tree_hash_root).collect::<Vec<_>>(),
+                limit,
+            )
+        };
+        let depth = leaf_limit.next_power_of_two().trailing_zeros() as usize;
+        Self {
+            data: MerkleTree::create(&leaves, depth),
+            length,
+        }
+    }
+
+    fn node(&self, gindex: u64) -> Option<Hash256> {
+        let depth = gindex_depth(gindex).ok()?;
+        let Some(length) = self.length else {
+            return tree_node(&self.data, gindex, depth);
+        };
+        match gindex {
+            1 => Some(Hash256::from_slice(&hash32_concat(
+                self.data.hash().as_bytes(),
+                &int_to_bytes32(length as u64),
+            ))),
+            3 => Some(Hash256::from_slice(&int_to_bytes32(length as u64))),
+            _ => {
+                // The data of a list is the left child of its root.
+                let (index, data_gindex) = split_gindex(gindex, depth, 1).ok()?;
+                if index != 0 {
+                    return None;
+                }
+                tree_node(&self.data, data_gindex, depth.safe_sub(1).ok()?)
+            }
+        }
+    }
+}
+
+/// A `List` or `Vector` which can be descended into by a `StateProof`.
+pub(crate) trait TreeListField {
+    fn field_tree(&self) -> FieldTree;
+}
+
+impl<T: Value, N: Unsigned> TreeListField for List<T, N> {
+    fn field_tree(&self) -> FieldTree {
+        FieldTree::new(self.iter(), N::to_usize(), Some(self.len()))
+    }
+}
+
+impl<T: Value, N: Unsigned> TreeListField for Vector<T, N> {
+    fn field_tree(&self) -> FieldTree {
+        FieldTree::new(self.iter(), N::to_usize(), None)
+    }
+}
+
+/// Returns the number of levels between `gindex` and the root.
+fn gindex_depth(gindex: u64) -> Result<usize, Error> {
+    gindex
+        .checked_ilog2()
+        .map(|depth| depth as usize)
+        .ok_or(Error::InvalidGeneralizedIndex(gindex))
+}
+
+/// Splits `gindex`, which is `depth` levels below the root, into the index of its ancestor
+/// `ancestor_depth` levels below the root and its generalized index relative to that ancestor.
+fn split_gindex(gindex: u64, depth: usize, ancestor_depth: usize) -> Result<(usize, u64), Error> {
+    let relative_depth = depth.safe_sub(ancestor_depth)? as u32;
+    let relative_root = 1u64.safe_shl(relative_depth)?;
+    let index = gindex
+        .safe_shr(relative_depth)?
+        .safe_sub(1u64.safe_shl(ancestor_depth as u32)?)?;
+    Ok((
+        index as usize,
+        (gindex & relative_root.safe_sub(1)?) | relative_root,
+    ))
+}
+
+/// Returns the root of the node at `gindex` of `tree`, which is `depth` levels below its root.
+///
+/// Returns `None` if `gindex` is below the leaves of `tree`.
+fn tree_node(tree: &MerkleTree, gindex: u64, depth: usize) -> Option<Hash256> {
+    let mut node = tree;
+    for remaining in (1..=depth).rev() {
+        let is_right = gindex.checked_shr(remaining.safe_sub(1).ok()? as u32)? & 1 == 1;
+        node = match node {
+            MerkleTree::Node(_, left, right) => {
+                if is_right {
+                    right
+                } else {
+                    left
+                }
+            }
+            // Every node below a zero subtree is the root of a zero subtree.
+            MerkleTree::Zero(zero_depth) => {
+                let zero_hash = ZERO_HASHES.get(zero_depth.checked_sub(remaining)?)?;
+                return Some(Hash256::from_slice(zero_hash));
+            }
+            MerkleTree::Leaf(_) | MerkleTree::Finalized(_) => return None,
+        };
+    }
+    Some(node.hash())
+}
+
+fn sibling(gindex: u64) -> u64 {
+    gindex ^ 1
+}
+
+fn parent(gindex: u64) -> Result<u64, ArithError> {
+    gindex.safe_div(2)
+}
+
+/// Returns the generalized indices of the helper nodes needed to prove `gindices`, in descending
+/// order, as in `get_helper_indices` from the consensus specs.
+pub fn get_helper_indices(gindices: &[u64]) -> Result<Vec<u64>, Error> {
+    let mut branch_indices = BTreeSet::new();
+    let mut path_indices = BTreeSet::new();
+    for gindex in gindices {
+        gindex_depth(*gindex)?;
+        let mut node = *gindex;
+        while node > 1 {
+            branch_indices.insert(sibling(node));
+            path_indices.insert(node);
+            node = parent(node)?;
+        }
+    }
+    Ok(branch_indices
+        .difference(&path_indices)
+        .rev()
+        .copied()
+        .collect())
+}
+
+/// Returns the root of the tree proven by a multiproof, as in `calculate_multi_merkle_root` from
+/// the consensus specs.
+pub fn calculate_multi_merkle_root(
+    leaves: &[Hash256],
+    proof: &[Hash256],
+    gindices: &[u64],
+) -> Result<Hash256, Error> {
+    let helper_indices = get_helper_indices(gindices)?;
+    if leaves.len() != gindices.len() {
+        return Err(Error::InvalidProofLength {
+            expected: gindices.len(),
+            actual: leaves.len(),
+        });
+    }
+    if proof.len() != helper_indices.len() {
+        return Err(Error::InvalidProofLength {
+            expected: helper_indices.len(),
+            actual: proof.len(),
+        });
+    }
+    let mut nodes = gindices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .chain(helper_indices.into_iter().zip(proof.iter().copied()))
+        .collect::<HashMap<_, _>>();
+
+    // Hash pairs of known siblings from the bottom of the tree up, until the root is known.
+    let mut keys = nodes.keys().copied().collect::<BTreeSet<_>>();
+    while let Some(gindex) = keys.pop_last() {
+        if gindex <= 1 {
+            break;
+        }
+        let parent = parent(gindex)?;
+        if nodes.contains_key(&parent) {
+            continue;
+        }
+        if let (Some(left), Some(right)) = (nodes.get(&(gindex & !1)), nodes.get(&(gindex | 1))) {
+            let root = Hash256::from_slice(&hash32_concat(left.as_bytes(), right.as_bytes()));
+            nodes.insert(parent, root);
+            keys.insert(parent);
+        }
+    }
+    nodes.get(&1).copied().ok_or(Error::NoGeneralizedIndices)
+}
+
+impl From<ArithError> for Error {
+    fn from(e: ArithError) -> Error {
+        Error::ArithError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use crate::{BeaconStateCapella, MinimalEthSpec, Validator};
+    use merkle_proof::verify_merkle_proof;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATORS_INDEX: u64 = 11;
+    const BALANCES_INDEX: u64 = 12;
+    const FINALIZED_CHECKPOINT_INDEX: u64 = 20;
+
+    fn state() -> BeaconState<E> {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        BeaconState::Capella(BeaconStateCapella {
+            validators: List::new(vec![Validator::random_for_test(rng); 3]).unwrap(),
+            balances: List::new(vec![1, 2, 3, 4, 5]).unwrap(),
+            ..<_>::random_for_test(rng)
+        })
+    }
+
+    /// Returns the generalized index of the field at `field_index`.
+    fn field_gindex(field_index: u64) -> u64 {
+        32 + field_index
+    }
+
+    /// Returns the generalized index of the leaf at `index` of a list field with `depth` levels
+    /// of data.
+    fn list_leaf_gindex(field_index: u64, depth: u32, index: u64) -> u64 {
+        ((field_gindex(field_index) * 2) << depth) + index
+    }
+
+    #[test]
+    fn proves_field() {
+        let mut state = state();
+        let gindex = field_gindex(FINALIZED_CHECKPOINT_INDEX);
+        let proof = StateProof::new(&state, &[gindex]).unwrap();
+        assert_eq!(proof.state_root, state.canonical_root().unwrap());
+        assert_eq!(
+            proof.leaves,
+            vec![state.finalized_checkpoint().tree_hash_root()]
+        );
+        assert!(proof.verify());
+
+        // A single proof is a Merkle branch.
+        assert!(verify_merkle_proof(
+            proof.leaves[0],
+            &proof.proof,
+            5,
+            FINALIZED_CHECKPOINT_INDEX as usize,
+            proof.state_root,
+        ));
+    }
+
+    #[test]
+    fn proves_list_elements_and_length() {
+        let mut state = state();
+        let validator = list_leaf_gindex(VALIDATORS_INDEX, 40, 1);
+        let balances_chunk = list_leaf_gindex(BALANCES_INDEX, 38, 1);
+        let balances_length = field_gindex(BALANCES_INDEX) * 2 + 1;
+        let empty_validator = list_leaf_gindex(VALIDATORS_INDEX, 40, 1000);
+
+        let proof = StateProof::new(
+            &state,
+            &[validator, balances_chunk, balances_length, empty_validator],
+        )
+        .unwrap();
+        assert_eq!(proof.state_root, state.canonical_root().unwrap());
+        let mut balances_chunk_bytes = [0; 32];
+        balances_chunk_bytes[..8].copy_from_slice(&5u64.to_le_bytes());
+        assert_eq!(
+            proof.leaves,
+            vec![
+                state.validators().get(1).unwrap().tree_hash_root(),
+                Hash256::from(balances_chunk_bytes),
+                Hash256::from_slice(&int_to_bytes32(5)),
+                Hash256::zero(),
+            ]
+        );
+        assert!(proof.verify());
+
+        let mut invalid = proof.clone();
+        invalid.leaves[2] = Hash256::from_slice(&int_to_bytes32(6));
+        assert!(!invalid.verify());
+    }
+
+    #[test]
+    fn multiproof_matches_single_proofs() {
+        let state = state();
+        let gindices = [
+            field_gindex(FINALIZED_CHECKPOINT_INDEX),
+            field_gindex(FINALIZED_CHECKPOINT_INDEX + 1),
+            list_leaf_gindex(VALIDATORS_INDEX, 40, 2),
+        ];
+        let multiproof = StateProof::new(&state, &gindices).unwrap();
+        assert!(multiproof.verify());
+
+        // Siblings are not repeated in the proof.
+        let single_proof_len = |gindex| StateProof::new(&state, &[gindex]).unwrap().proof.len();
+        assert!(multiproof.proof.len() < gindices.iter().copied().map(single_proof_len).sum());
+        for (gindex, leaf) in gindices.iter().zip(&multiproof.leaves) {
+            assert_eq!(
+                StateProof::new(&state, &[*gindex]).unwrap().leaves,
+                vec![*leaf]
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_gindices() {
+        let state = state();
+        // The finalized root is within a checkpoint, which isn't a list.
+        let finalized_root = field_gindex(FINALIZED_CHECKPOINT_INDEX) * 2 + 1;
+        assert_eq!(
+            StateProof::new(&state, &[finalized_root]),
+            Err(Error::UnsupportedGeneralizedIndex(finalized_root))
+        );
+        // Below the leaves of a list.
+        let below_leaf = list_leaf_gindex(VALIDATORS_INDEX, 40, 0) * 2;
+        assert_eq!(
+            StateProof::new(&state, &[below_leaf]),
+            Err(Error::UnsupportedGeneralizedIndex(below_leaf))
+        );
+        assert_eq!(
+            StateProof::new(&state, &[0]),
+            Err(Error::InvalidGeneralizedIndex(0))
+        );
+        assert_eq!(
+            StateProof::new(&state, &[]),
+            Err(Error::NoGeneralizedIndices)
+        );
+    }
+}
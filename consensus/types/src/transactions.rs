@@ -0,0 +1,243 @@
+use crate::test_utils::TestRandom;
+use crate::{EthSpec, Hash256, Transaction, VariableList};
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ssz::{Decode, DecodeError, Encode};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+use tree_hash::TreeHash;
+
+type TransactionList<E> = VariableList<
+    Transaction<<E as EthSpec>::MaxBytesPerTransaction>,
+    <E as EthSpec>::MaxTransactionsPerPayload,
+>;
+
+/// The transactions of an execution payload.
+///
+/// Merkleizing the transactions dominates the cost of computing the tree hash root of a payload,
+/// and therefore of the block which contains it. The root is computed at most once and cached,
+/// so that it can be re-used by block production, gossip verification and the HTTP API.
+///
+/// The cached root is cleared whenever the transactions are borrowed mutably.
+pub struct Transactions<E: EthSpec> {
+    transactions: TransactionList<E>,
+    tree_hash_root: OnceLock<Hash256>,
+}
+
+impl<E: EthSpec> Transactions<E> {
+    pub fn new(
+        transactions: Vec<Transaction<E::MaxBytesPerTransaction>>,
+    ) -> Result<Self, ssz_types::Error> {
+        VariableList::new(transactions).map(Self::from)
+    }
+
+    pub fn empty() -> Self {
+        Self::from(VariableList::empty())
+    }
+
+    pub fn into_inner(self) -> TransactionList<E> {
+        self.transactions
+    }
+
+    /// Returns the cached tree hash root, if it has been computed.
+    pub fn cached_tree_hash_root(&self) -> Option<Hash256> {
+        self.tree_hash_root.get().copied()
+    }
+}
+
+impl<E: EthSpec> From<TransactionList<E>> for Transactions<E> {
+    fn from(transactions: TransactionList<E>) -> Self {
+        Self {
+            transactions,
+            tree_hash_root: OnceLock::new(),
+        }
+    }
+}
+
+/// Truncates to the maximum number of transactions, as per `VariableList`.
+impl<E: EthSpec> From<Vec<Transaction<E::MaxBytesPerTransaction>>> for Transactions<E> {
+    fn from(transactions: Vec<Transaction<E::MaxBytesPerTransaction>>) -> Self {
+        Self::from(VariableList::from(transactions))
+    }
+}
+
+impl<E: EthSpec> Default for Transactions<E> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<E: EthSpec> Clone for Transactions<E> {
+    fn clone(&self) -> Self {
+        Self {
+            transactions: self.transactions.clone(),
+            tree_hash_root: self.tree_hash_root.clone(),
+        }
+    }
+}
+
+impl<E: EthSpec> fmt::Debug for Transactions<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.transactions.fmt(f)
+    }
+}
+
+impl<E: EthSpec> PartialEq for Transactions<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.transactions == other.transactions
+    }
+}
+
+impl<E: EthSpec> Eq for Transactions<E> {}
+
+impl<E: EthSpec> Hash for Transactions<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.transactions.hash(state)
+    }
+}
+
+impl<E: EthSpec> Deref for Transactions<E> {
+    type Target = TransactionList<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.transactions
+    }
+}
+
+impl<E: EthSpec> DerefMut for Transactions<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.tree_hash_root.take();
+        &mut self.transactions
+    }
+}
+
+impl<'a, E: EthSpec> IntoIterator for &'a Transactions<E> {
+    type Item = &'a Transaction<E::MaxBytesPerTransaction>;
+    type IntoIter = std::slice::Iter<'a, Transaction<E::MaxBytesPerTransaction>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.transactions.iter()
+    }
+}
+
+impl<E: EthSpec> Serialize for Transactions<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ssz_types::serde_utils::list_of_hex_var_list::serialize(&self.transactions, serializer)
+    }
+}
+
+impl<'de, E: EthSpec> Deserialize<'de> for Transactions<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ssz_types::serde_utils::list_of_hex_var_list::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<E: EthSpec> Encode for Transactions<E> {
+    fn is_ssz_fixed_len() -> bool {
+        <TransactionList<E> as Encode>::is_ssz_fixed_len()
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        self.transactions.ssz_bytes_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.transactions.ssz_append(buf)
+    }
+}
+
+impl<E: EthSpec> Decode for Transactions<E> {
+    fn is_ssz_fixed_len() -> bool {
+        <TransactionList<E> as Decode>::is_ssz_fixed_len()
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        TransactionList::<E>::from_ssz_bytes(bytes).map(Self::from)
+    }
+}
+
+impl<E: EthSpec> TreeHash for Transactions<E> {
+    fn tree_hash_type() -> tree_hash::TreeHashType {
+        TransactionList::<E>::tree_hash_type()
+    }
+
+    fn tree_hash_packed_encoding(&self) -> tree_hash::PackedEncoding {
+        self.transactions.tree_hash_packed_encoding()
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        TransactionList::<E>::tree_hash_packing_factor()
+    }
+
+    fn tree_hash_root(&self) -> tree_hash::Hash256 {
+        *self
+            .tree_hash_root
+            .get_or_init(|| self.transactions.tree_hash_root())
+    }
+}
+
+impl<E: EthSpec> TestRandom for Transactions<E> {
+    fn random_for_test(rng: &mut impl RngCore) -> Self {
+        Self::from(TransactionList::<E>::random_for_test(rng))
+    }
+}
+
+impl<'a, E: EthSpec> arbitrary::Arbitrary<'a> for Transactions<E> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        <TransactionList<E> as arbitrary::Arbitrary>::arbitrary(u).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn transactions(lengths: &[usize]) -> Transactions<E> {
+        Transactions::new(
+            lengths
+                .iter()
+                .map(|&length| VariableList::from(vec![length as u8; length]))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn cached_root_matches_list_root() {
+        let transactions = transactions(&[0, 1, 33, 1024]);
+        let expected = transactions.transactions.tree_hash_root();
+
+        assert_eq!(transactions.cached_tree_hash_root(), None);
+        assert_eq!(transactions.tree_hash_root(), expected);
+        assert_eq!(transactions.cached_tree_hash_root(), Some(expected));
+        assert_eq!(transactions.clone().cached_tree_hash_root(), Some(expected));
+    }
+
+    #[test]
+    fn mutation_clears_cached_root() {
+        let mut transactions = transactions(&[1, 2]);
+        let original = transactions.tree_hash_root();
+
+        transactions.push(VariableList::from(vec![3; 3])).unwrap();
+
+        assert_eq!(transactions.cached_tree_hash_root(), None);
+        assert_ne!(transactions.tree_hash_root(), original);
+        assert_eq!(transactions, self::transactions(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn ssz_round_trip_does_not_carry_cache() {
+        let transactions = transactions(&[5, 6]);
+        let root = transactions.tree_hash_root();
+
+        let decoded = Transactions::<E>::from_ssz_bytes(&transactions.as_ssz_bytes()).unwrap();
+
+        assert_eq!(decoded.cached_tree_hash_root(), None);
+        assert_eq!(decoded.tree_hash_root(), root);
+    }
+}
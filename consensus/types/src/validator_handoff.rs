@@ -0,0 +1,36 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+/// A validator's statement that it has been disabled on its previous validator client, which
+/// allows another validator client to import it.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
+pub struct SignedValidatorHandoff {
+    pub message: ValidatorHandoff,
+    pub signature: Signature,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode, TreeHash)]
+pub struct ValidatorHandoff {
+    pub pubkey: PublicKeyBytes,
+    /// The time at which the validator was disabled, in seconds since the UNIX epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub disabled_at: u64,
+}
+
+impl SignedRoot for ValidatorHandoff {}
+
+impl SignedValidatorHandoff {
+    pub fn verify_signature(&self, spec: &ChainSpec) -> bool {
+        self.message
+            .pubkey
+            .decompress()
+            .map(|pubkey| {
+                let domain = spec.get_validator_handoff_domain();
+                let message = self.message.signing_root(domain);
+                self.signature.verify(&pubkey, message)
+            })
+            .unwrap_or(false)
+    }
+}
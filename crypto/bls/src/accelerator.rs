@@ -0,0 +1,281 @@
+//! An optional interface for offloading the verification of batches of signature sets to
+//! dedicated hardware, such as a GPU or FPGA.
+//!
+//! Once a `SignatureAccelerator` is installed, `verify_signature_sets` performs the cheap
+//! preparation of each set in software (subgroup checking the signature and aggregating the public
+//! keys), then hands the batch to the accelerator. If the accelerator returns an error, the batch
+//! is verified in software instead.
+//!
+//! An accelerator must pass a self-test against known valid and invalid batches before it is
+//! installed.
+//!
+//! ## Loadable backends
+//!
+//! `FfiAccelerator` loads a backend from a shared library which exports the following symbol:
+//!
+//! ```c
+//! int32_t lighthouse_bls_verify_signature_sets(
+//!     const uint8_t *signatures,  // `count` compressed G2 points, 96 bytes each.
+//!     const uint8_t *public_keys, // `count` uncompressed G1 points, 96 bytes each.
+//!     const uint8_t *messages,    // `count` 32 byte messages.
+//!     size_t count
+//! );
+//! ```
+//!
+//! It must return `1` if every signature is valid for its public key and message, `0` if any is
+//! not, or a negative value if the batch could not be verified. Signatures use the
+//! `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite, and have already been subgroup
+//! checked. A backend which verifies the batch as a random linear combination must use fresh
+//! randomness for every call.
+use crate::impls::blst::{blst_core, DST};
+use crate::{Hash256, PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN, SIGNATURE_BYTES_LEN};
+use blst::BLST_ERROR;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The name of the function exported by the libraries loaded by `FfiAccelerator`.
+pub const VERIFY_SIGNATURE_SETS_SYMBOL: &[u8] = b"lighthouse_bls_verify_signature_sets\0";
+
+/// The number of sets in each batch of the self-test.
+const SELF_TEST_BATCH_SIZE: usize = 8;
+
+static ACCELERATOR: OnceLock<Box<dyn SignatureAccelerator>> = OnceLock::new();
+
+/// A signature set which has been prepared for an accelerator.
+#[derive(Clone)]
+pub struct AcceleratedSignatureSet {
+    /// The compressed signature, which has been subgroup checked.
+    pub signature: [u8; SIGNATURE_BYTES_LEN],
+    /// The uncompressed aggregate of the signing keys.
+    pub public_key: [u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN],
+    pub message: Hash256,
+}
+
+/// A backend which verifies batches of signature sets.
+pub trait SignatureAccelerator: Send + Sync {
+    /// A human-readable description of the backend.
+    fn name(&self) -> &str;
+
+    /// Returns `Ok(true)` if all of the `sets` are valid and `Ok(false)` if any is invalid.
+    ///
+    /// An `Err` indicates that the backend was unable to verify the batch.
+    fn verify_signature_sets(&self, sets: &[AcceleratedSignatureSet]) -> Result<bool, String>;
+}
+
+/// Installs `accelerator` for use by `verify_signature_sets` for the rest of the process, if it
+/// passes the self-test.
+pub fn install(accelerator: Box<dyn SignatureAccelerator>) -> Result<(), String> {
+    self_test(accelerator.as_ref())?;
+    ACCELERATOR
+        .set(accelerator)
+        .map_err(|_| "an accelerator is already installed".to_string())
+}
+
+/// Returns the installed accelerator, if any.
+pub fn installed() -> Option<&'static dyn SignatureAccelerator> {
+    ACCELERATOR.get().map(Box::as_ref)
+}
+
+/// Checks that `accelerator` agrees with the software implementation on batches of valid and
+/// invalid signature sets.
+pub fn self_test(accelerator: &dyn SignatureAccelerator) -> Result<(), String> {
+    let valid = self_test_batch();
+
+    let mut wrong_message = valid.clone();
+    wrong_message[SELF_TEST_BATCH_SIZE / 2].message = Hash256::repeat_byte(0xff);
+
+    let mut swapped_signatures = valid.clone();
+    let signature = swapped_signatures[0].signature;
+    swapped_signatures[0].signature = swapped_signatures[1].signature;
+    swapped_signatures[1].signature = signature;
+
+    let cases = [
+        ("a valid batch", &valid[..], true),
+        ("a valid single set", &valid[..1], true),
+        ("a batch with a wrong message", &wrong_message[..], false),
+        (
+            "a batch with swapped signatures",
+            &swapped_signatures[..],
+            false,
+        ),
+    ];
+
+    for (desc, sets, expected) in cases {
+        match accelerator.verify_signature_sets(sets) {
+            Ok(valid) if valid == expected => {}
+            Ok(valid) => {
+                return Err(format!(
+                    "{} verified {} as {}",
+                    accelerator.name(),
+                    desc,
+                    if valid { "valid" } else { "invalid" }
+                ))
+            }
+            Err(e) => {
+                return Err(format!(
+                    "{} failed to verify {}: {}",
+                    accelerator.name(),
+                    desc,
+                    e
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Produces a batch of valid signature sets from deterministic keys.
+fn self_test_batch() -> Vec<AcceleratedSignatureSet> {
+    (0..SELF_TEST_BATCH_SIZE)
+        .map(|i| {
+            let ikm = [i as u8 + 1; 32];
+            let secret_key = blst_core::SecretKey::key_gen(&ikm, &[])
+                .expect("key generation with 32 bytes of ikm cannot fail");
+            let message = Hash256::repeat_byte(i as u8);
+            AcceleratedSignatureSet {
+                signature: secret_key.sign(message.as_bytes(), DST, &[]).compress(),
+                public_key: secret_key.sk_to_pk().serialize(),
+                message,
+            }
+        })
+        .collect()
+}
+
+/// A software implementation of `SignatureAccelerator`, which verifies each set individually.
+///
+/// This is much slower than the default batch verification, and is intended as a reference for
+/// the implementers of backends.
+pub struct SoftwareAccelerator;
+
+impl SignatureAccelerator for SoftwareAccelerator {
+    fn name(&self) -> &str {
+        "software"
+    }
+
+    fn verify_signature_sets(&self, sets: &[AcceleratedSignatureSet]) -> Result<bool, String> {
+        for set in sets {
+            let signature = blst_core::Signature::uncompress(&set.signature)
+                .map_err(|e| format!("invalid signature: {:?}", e))?;
+            let public_key = blst_core::PublicKey::deserialize(&set.public_key)
+                .map_err(|e| format!("invalid public key: {:?}", e))?;
+            let result =
+                signature.verify(true, set.message.as_bytes(), DST, &[], &public_key, false);
+            if result != BLST_ERROR::BLST_SUCCESS {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+type VerifySignatureSetsFn = unsafe extern "C" fn(
+    signatures: *const u8,
+    public_keys: *const u8,
+    messages: *const u8,
+    count: usize,
+) -> i32;
+
+/// An accelerator loaded from a shared library, see the module-level docs for its interface.
+pub struct FfiAccelerator {
+    name: String,
+    verify: VerifySignatureSetsFn,
+    // The library must be kept loaded for as long as `verify` may be called.
+    _library: libloading::Library,
+}
+
+impl FfiAccelerator {
+    /// Loads the shared library at `path`.
+    ///
+    /// ## Safety
+    ///
+    /// Loading a library runs its initialisation routines, and the exported function is trusted
+    /// to implement the interface described in the module-level docs. The library must therefore
+    /// come from a trusted source.
+    pub unsafe fn load(path: &Path) -> Result<Self, String> {
+        let library = libloading::Library::new(path)
+            .map_err(|e| format!("unable to load {}: {}", path.display(), e))?;
+        let verify = *library
+            .get::<VerifySignatureSetsFn>(VERIFY_SIGNATURE_SETS_SYMBOL)
+            .map_err(|e| format!("unable to load symbol from {}: {}", path.display(), e))?;
+        Ok(Self {
+            name: path.display().to_string(),
+            verify,
+            _library: library,
+        })
+    }
+}
+
+impl SignatureAccelerator for FfiAccelerator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn verify_signature_sets(&self, sets: &[AcceleratedSignatureSet]) -> Result<bool, String> {
+        let mut signatures = Vec::with_capacity(sets.len() * SIGNATURE_BYTES_LEN);
+        let mut public_keys = Vec::with_capacity(sets.len() * PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN);
+        let mut messages = Vec::with_capacity(sets.len() * Hash256::len_bytes());
+        for set in sets {
+            signatures.extend_from_slice(&set.signature);
+            public_keys.extend_from_slice(&set.public_key);
+            messages.extend_from_slice(set.message.as_bytes());
+        }
+
+        // Safety: each buffer holds `sets.len()` items of the size required by the interface,
+        // which the library was trusted to implement when it was loaded.
+        let result = unsafe {
+            (self.verify)(
+                signatures.as_ptr(),
+                public_keys.as_ptr(),
+                messages.as_ptr(),
+                sets.len(),
+            )
+        };
+
+        match result {
+            1 => Ok(true),
+            0 => Ok(false),
+            code => Err(format!("backend returned error code {}", code)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysValid;
+
+    impl SignatureAccelerator for AlwaysValid {
+        fn name(&self) -> &str {
+            "always valid"
+        }
+
+        fn verify_signature_sets(&self, _: &[AcceleratedSignatureSet]) -> Result<bool, String> {
+            Ok(true)
+        }
+    }
+
+    struct Unavailable;
+
+    impl SignatureAccelerator for Unavailable {
+        fn name(&self) -> &str {
+            "unavailable"
+        }
+
+        fn verify_signature_sets(&self, _: &[AcceleratedSignatureSet]) -> Result<bool, String> {
+            Err("no device".to_string())
+        }
+    }
+
+    #[test]
+    fn software_accelerator_passes_self_test() {
+        self_test(&SoftwareAccelerator).unwrap();
+    }
+
+    #[test]
+    fn incorrect_accelerators_fail_self_test() {
+        assert!(self_test(&AlwaysValid).is_err());
+        assert!(self_test(&Unavailable).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    accelerator::{self, AcceleratedSignatureSet},
     generic_aggregate_public_key::TAggregatePublicKey,
     generic_aggregate_signature::TAggregateSignature,
     generic_public_key::{
@@ -106,6 +107,22 @@ pub fn verify_signature_sets<'a>(
         pks.push(agg_pk.to_public_key());
     }
 
+    // Offload the batch to the accelerator, if installed, falling back to software if it fails.
+    if let Some(accelerator) = accelerator::installed() {
+        let accelerated_sets = sets
+            .iter()
+            .zip(sigs.iter().zip(pks.iter()))
+            .map(|(set, (sig, pk))| AcceleratedSignatureSet {
+                signature: sig.compress(),
+                public_key: pk.serialize(),
+                message: set.message,
+            })
+            .collect::<Vec<_>>();
+        if let Ok(valid) = accelerator.verify_signature_sets(&accelerated_sets) {
+            return valid;
+        }
+    }
+
     let (sig_refs, pks_refs): (Vec<_>, Vec<_>) = sigs.iter().zip(pks.iter()).unzip();
 
     // Public keys have already been checked for subgroup and infinity
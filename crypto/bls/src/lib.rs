@@ -19,6 +19,8 @@
 
 #[macro_use]
 mod macros;
+#[cfg(feature = "supranational")]
+pub mod accelerator;
 mod generic_aggregate_public_key;
 mod generic_aggregate_signature;
 mod generic_keypair;
@@ -4,7 +4,7 @@
 //! data structures. Specifically, there should not be any actual crypto logic in this file.
 
 use super::hex_bytes::HexBytes;
-use crate::DKLEN;
+use crate::{DEFAULT_SCRYPT_N, DKLEN};
 use hmac::{Hmac, Mac, NewMac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
@@ -127,7 +127,7 @@ impl Scrypt {
     pub fn default_scrypt(salt: Vec<u8>) -> Self {
         Self {
             dklen: DKLEN,
-            n: 262144,
+            n: DEFAULT_SCRYPT_N,
             p: 1,
             r: 8,
             salt: salt.into(),
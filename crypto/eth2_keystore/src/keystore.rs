@@ -4,7 +4,7 @@
 use crate::derived_key::DerivedKey;
 use crate::json_keystore::{
     Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, JsonKeystore,
-    Kdf, KdfModule, Scrypt, Sha256Checksum, Version,
+    Kdf, KdfModule, Pbkdf2, Prf, Scrypt, Sha256Checksum, Version,
 };
 use crate::Uuid;
 use aes::cipher::generic_array::GenericArray;
@@ -59,6 +59,8 @@ pub const IV_SIZE: usize = 16;
 pub const HASH_SIZE: usize = 32;
 /// The default iteraction count, `c`, for PBKDF2.
 pub const DEFAULT_PBKDF2_C: u32 = 262_144;
+/// The default CPU/memory cost parameter, `n`, for scrypt.
+pub const DEFAULT_SCRYPT_N: u32 = 262_144;
 
 /// Provides a new-type wrapper around `String` that is zeroized on `Drop`.
 ///
@@ -357,6 +359,64 @@ pub fn default_kdf(salt: Vec<u8>) -> Kdf {
     Kdf::Scrypt(Scrypt::default_scrypt(salt))
 }
 
+/// The cost parameters of a KDF, excluding the salt.
+///
+/// Allows many keystores to be created with the same KDF settings whilst each receives a unique
+/// salt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KdfParams {
+    Scrypt { n: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams::Scrypt {
+            n: DEFAULT_SCRYPT_N,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Returns a `Kdf` with the parameters of `self` and the given `salt`.
+    ///
+    /// The scrypt `r` and `p` parameters are always those of `Scrypt::default_scrypt`.
+    pub fn kdf(&self, salt: Vec<u8>) -> Kdf {
+        match *self {
+            KdfParams::Scrypt { n } => Kdf::Scrypt(Scrypt {
+                n,
+                ..Scrypt::default_scrypt(salt)
+            }),
+            KdfParams::Pbkdf2 { c } => Kdf::Pbkdf2(Pbkdf2 {
+                c,
+                dklen: DKLEN,
+                prf: Prf::HmacSha256,
+                salt: salt.into(),
+            }),
+        }
+    }
+
+    /// Returns the approximate number of bytes of memory used by a single key derivation.
+    ///
+    /// scrypt allocates `128 * r * n` bytes, i.e. 256 MiB with the default parameters, whilst the
+    /// memory used by pbkdf2 is negligible.
+    pub fn memory_bytes(&self) -> u64 {
+        match *self {
+            KdfParams::Scrypt { n } => {
+                128 * u64::from(Scrypt::default_scrypt(vec![]).r) * u64::from(n)
+            }
+            KdfParams::Pbkdf2 { .. } => 0,
+        }
+    }
+
+    /// Returns an error if a keystore could not be encrypted using these parameters.
+    ///
+    /// Useful for rejecting bad parameters before performing any expensive key derivation.
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_parameters(&self.kdf(vec![0; SALT_SIZE]))
+    }
+}
+
 /// Returns `(cipher_text, checksum)` for the given `plain_text` encrypted with `Cipher` using a
 /// key derived from `password` via the `Kdf` (key derivation function).
 /// Normalizes the password into NFKD form and removes control characters as specified in EIP-2335
@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use eth2_keystore::{Error, Keystore};
+use eth2_keystore::{Error, KdfParams, Keystore};
 
 const PASSWORD: &str = "testpassword";
 
@@ -320,3 +320,33 @@ fn pbkdf2_dklen_not_32() {
 
     assert_eq!(decrypt_error(vector), Error::InvalidPbkdf2Param);
 }
+
+#[test]
+fn kdf_params_validation() {
+    assert_eq!(KdfParams::default().validate(), Ok(()));
+    assert_eq!(KdfParams::Scrypt { n: 1 << 14 }.validate(), Ok(()));
+    assert_eq!(KdfParams::Pbkdf2 { c: 1 << 18 }.validate(), Ok(()));
+
+    assert_eq!(
+        KdfParams::Scrypt { n: 1000 }.validate(),
+        Err(Error::InvalidScryptParam)
+    );
+    assert_eq!(
+        KdfParams::Scrypt { n: 1 }.validate(),
+        Err(Error::InvalidScryptParam)
+    );
+    assert_eq!(
+        KdfParams::Pbkdf2 { c: 0 }.validate(),
+        Err(Error::InvalidPbkdf2Param)
+    );
+}
+
+#[test]
+fn kdf_params_memory_bytes() {
+    assert_eq!(KdfParams::default().memory_bytes(), 256 * 1024 * 1024);
+    assert_eq!(
+        KdfParams::Scrypt { n: 1 << 14 }.memory_bytes(),
+        16 * 1024 * 1024
+    );
+    assert_eq!(KdfParams::Pbkdf2 { c: 1 << 18 }.memory_bytes(), 0);
+}
@@ -3,16 +3,16 @@ use crate::{
         Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, JsonWallet,
         Kdf, KdfModule, Sha256Checksum, TypeField, Version,
     },
-    KeyType, ValidatorPath,
+    KeyType, ValidatorPath, COIN_TYPE, PURPOSE,
 };
-pub use bip39::{Mnemonic, Seed as Bip39Seed};
+pub use bip39::{Language, Mnemonic, MnemonicType, Seed as Bip39Seed};
 pub use eth2_key_derivation::{DerivedKey, DerivedKeyError};
 use eth2_keystore::{
     decrypt, default_kdf, encrypt, keypair_from_secret, Keystore, KeystoreBuilder, IV_SIZE,
     SALT_SIZE,
 };
-pub use eth2_keystore::{Error as KeystoreError, PlainText};
-use rand::prelude::*;
+pub use eth2_keystore::{Error as KeystoreError, KdfParams, PlainText};
+use rand::{prelude::*, rngs::OsRng};
 use serde::{Deserialize, Serialize};
 use std::io::{Read, Write};
 pub use uuid::Uuid;
@@ -24,6 +24,8 @@ pub enum Error {
     EmptyPassword,
     EmptySeed,
     InvalidNextAccount { old: u32, new: u32 },
+    UnableToReadOsEntropy(String),
+    InvalidEntropy(String),
 }
 
 impl From<KeystoreError> for Error {
@@ -49,6 +51,77 @@ pub struct ValidatorKeystores {
     pub withdrawal: Keystore,
 }
 
+/// The secret keys for an eth2 validator, prior to being encrypted into keystores.
+///
+/// Obtained via `Wallet::next_validator_secrets`, which allows the (comparatively expensive)
+/// keystore encryption to happen outside of the wallet, e.g., across several threads.
+pub struct ValidatorSecrets {
+    voting: (PlainText, ValidatorPath),
+    withdrawal: (PlainText, ValidatorPath),
+}
+
+impl ValidatorSecrets {
+    /// Encrypts the secrets into a pair of keystores using the KDF described by `kdf_params`.
+    ///
+    /// Each keystore receives a unique salt and IV generated using `rand::thread_rng()`.
+    ///
+    /// ## Errors
+    ///
+    /// - If either password is empty.
+    /// - If `kdf_params` are invalid.
+    pub fn into_keystores(
+        self,
+        voting_keystore_password: &[u8],
+        withdrawal_keystore_password: &[u8],
+        kdf_params: KdfParams,
+    ) -> Result<ValidatorKeystores, Error> {
+        let encrypt = |(secret, path): (PlainText, ValidatorPath),
+                       password: &[u8]|
+         -> Result<Keystore, Error> {
+            let keypair = keypair_from_secret(secret.as_bytes())?;
+            let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+
+            KeystoreBuilder::new(&keypair, password, format!("{}", path))?
+                .kdf(kdf_params.kdf(salt.to_vec()))
+                .build()
+                .map_err(Into::into)
+        };
+
+        Ok(ValidatorKeystores {
+            voting: encrypt(self.voting, voting_keystore_password)?,
+            withdrawal: encrypt(self.withdrawal, withdrawal_keystore_password)?,
+        })
+    }
+}
+
+/// Generates a new random BIP-39 mnemonic.
+///
+/// The entropy from `rand::thread_rng()` is mixed (via XOR) with entropy read directly from the
+/// operating system RNG, so the mnemonic is no weaker than either source alone.
+///
+/// ## Errors
+///
+/// Returns `Error::UnableToReadOsEntropy` if the operating system RNG is unavailable.
+pub fn mnemonic_with_os_entropy(
+    mnemonic_type: MnemonicType,
+    language: Language,
+) -> Result<Mnemonic, Error> {
+    let mut entropy = PlainText::zero(mnemonic_type.entropy_bits() / 8);
+    let mut os_entropy = PlainText::zero(entropy.len());
+
+    rand::thread_rng().fill_bytes(entropy.as_mut_bytes());
+    OsRng
+        .try_fill_bytes(os_entropy.as_mut_bytes())
+        .map_err(|e| Error::UnableToReadOsEntropy(format!("{}", e)))?;
+
+    for (byte, os_byte) in entropy.as_mut_bytes().iter_mut().zip(os_entropy.as_bytes()) {
+        *byte ^= os_byte;
+    }
+
+    Mnemonic::from_entropy(entropy.as_bytes(), language)
+        .map_err(|e| Error::InvalidEntropy(format!("{:?}", e)))
+}
+
 /// Constructs a `Keystore`.
 ///
 /// Generates the KDF `salt` and AES `IV` using `rand::thread_rng()`.
@@ -105,6 +178,14 @@ impl<'a> WalletBuilder<'a> {
         }
     }
 
+    /// Build the wallet using the KDF described by `kdf_params` instead of
+    /// `eth2_keystore::default_kdf`.
+    pub fn kdf_params(mut self, kdf_params: KdfParams) -> Self {
+        let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+        self.kdf = kdf_params.kdf(salt.to_vec());
+        self
+    }
+
     /// Consumes `self`, returning an encrypted `Wallet`.
     pub fn build(self) -> Result<Wallet, Error> {
         Wallet::encrypt(
@@ -215,6 +296,53 @@ impl Wallet {
         Ok(keystores)
     }
 
+    /// Returns the secrets for the next `count` validators, incrementing `nextaccount` by `count`.
+    ///
+    /// Unlike `Self::next_validator`, the wallet seed is only decrypted once and the secrets are
+    /// not encrypted. Use `ValidatorSecrets::into_keystores` to produce the keystores.
+    ///
+    /// ## Errors
+    ///
+    /// - If `wallet_password` is unable to decrypt `self`.
+    /// - If `self.nextaccount + count` overflows a `u32`.
+    pub fn next_validator_secrets(
+        &mut self,
+        wallet_password: &[u8],
+        count: u32,
+    ) -> Result<Vec<ValidatorSecrets>, Error> {
+        let first = self.json.nextaccount;
+        let nextaccount = first.checked_add(count).ok_or(Error::PathExhausted)?;
+
+        let seed = self.decrypt_seed(wallet_password)?;
+        let master = DerivedKey::from_seed(seed.as_bytes()).map_err(Error::from)?;
+
+        // Both keys share the `m/12381/3600` prefix and the voting key is a child of the
+        // withdrawal key, so avoid deriving those nodes more than once (see `ValidatorPath::new`).
+        let coin = master.child(PURPOSE).child(COIN_TYPE);
+
+        let secrets = (first..nextaccount)
+            .map(|index| {
+                let withdrawal = coin.child(index).child(0);
+                let voting = withdrawal.child(0);
+
+                ValidatorSecrets {
+                    voting: (
+                        voting.secret().to_vec().into(),
+                        ValidatorPath::new(index, KeyType::Voting),
+                    ),
+                    withdrawal: (
+                        withdrawal.secret().to_vec().into(),
+                        ValidatorPath::new(index, KeyType::Withdrawal),
+                    ),
+                }
+            })
+            .collect();
+
+        self.json.nextaccount = nextaccount;
+
+        Ok(secrets)
+    }
+
     /// Returns the value of the JSON wallet `nextaccount` field.
     ///
     /// This will be the index of the next wallet generated with `Self::next_validator`.
@@ -1,8 +1,10 @@
 #![cfg(not(debug_assertions))]
 
+use eth2_keystore::json_keystore::Kdf;
 use eth2_wallet::{
-    bip39::{Language, Mnemonic, Seed},
-    recover_validator_secret, DerivedKey, Error, KeyType, KeystoreError, Wallet, WalletBuilder,
+    bip39::{Language, Mnemonic, MnemonicType, Seed},
+    mnemonic_with_os_entropy, recover_validator_secret, DerivedKey, Error, KdfParams, KeyType,
+    KeystoreError, Wallet, WalletBuilder,
 };
 use std::fs::File;
 use tempfile::tempdir;
@@ -279,3 +281,84 @@ fn key_derivation_from_seed() {
         assert_eq!(wallet.nextaccount(), i + 1, "updated nextaccount");
     }
 }
+
+#[test]
+fn validator_secrets_from_seed() {
+    let mut wallet = wallet_from_seed();
+    let kdf_params = KdfParams::Pbkdf2 { c: 1 << 18 };
+
+    let secrets = wallet
+        .next_validator_secrets(WALLET_PASSWORD, 3)
+        .expect("should derive secrets");
+
+    assert_eq!(secrets.len(), 3, "should derive three validators");
+    assert_eq!(wallet.nextaccount(), 3, "updated nextaccount");
+
+    for (i, secrets) in secrets.into_iter().enumerate() {
+        let i = i as u32;
+        let keystores = secrets
+            .into_keystores(
+                VOTING_KEYSTORE_PASSWORD,
+                WITHDRAWAL_KEYSTORE_PASSWORD,
+                kdf_params,
+            )
+            .expect("should encrypt keystores");
+
+        assert_eq!(
+            keystores.voting.path().unwrap(),
+            format!("m/12381/3600/{}/0/0", i),
+            "voting path should match"
+        );
+        assert_eq!(
+            keystores.withdrawal.path().unwrap(),
+            format!("m/12381/3600/{}/0", i),
+            "withdrawal path should match"
+        );
+        for keystore in [&keystores.voting, &keystores.withdrawal] {
+            match keystore.kdf() {
+                Kdf::Pbkdf2(params) => assert_eq!(params.c, 1 << 18, "pbkdf2 c should match"),
+                Kdf::Scrypt(_) => panic!("keystore should use the supplied kdf"),
+            }
+        }
+
+        let voting_keypair = keystores
+            .voting
+            .decrypt_keypair(VOTING_KEYSTORE_PASSWORD)
+            .expect("should decrypt voting keypair");
+        let withdrawal_keypair = keystores
+            .withdrawal
+            .decrypt_keypair(WITHDRAWAL_KEYSTORE_PASSWORD)
+            .expect("should decrypt withdrawal keypair");
+
+        assert_eq!(
+            voting_keypair.sk.serialize().as_ref(),
+            &manually_derived_voting_key(i)[..],
+            "voting secret should match manually derived"
+        );
+        assert_eq!(
+            withdrawal_keypair.sk.serialize().as_ref(),
+            &manually_derived_withdrawal_key(i)[..],
+            "withdrawal secret should match manually derived"
+        );
+    }
+
+    assert_eq!(
+        wallet
+            .next_validator_secrets(&[1, 2, 3], 1)
+            .err()
+            .expect("should fail with invalid password"),
+        Error::KeystoreError(KeystoreError::InvalidPassword),
+    );
+    assert_eq!(wallet.nextaccount(), 3, "next account should not update");
+}
+
+#[test]
+fn os_entropy_mnemonic() {
+    let a = mnemonic_with_os_entropy(MnemonicType::Words24, Language::English)
+        .expect("should generate mnemonic");
+    let b = mnemonic_with_os_entropy(MnemonicType::Words24, Language::English)
+        .expect("should generate mnemonic");
+
+    assert_eq!(a.phrase().split_whitespace().count(), 24);
+    assert_ne!(a.phrase(), b.phrase(), "mnemonics should be random");
+}
@@ -0,0 +1,561 @@
+//! Export and import of checkpoint archives.
+//!
+//! A checkpoint archive is a directory containing the finalized (split) state of a node, the
+//! finalized block and its blobs, and optionally the unfinalized blocks (with blobs) on the chain
+//! with the highest slot. It allows an operator to initialize a fresh node from one of their own
+//! nodes, without relying on a public checkpoint sync provider.
+use beacon_chain::{
+    block_verification_types::RpcBlock,
+    builder::{BeaconChainBuilder, Witness},
+    eth1_chain::CachingEth1Backend,
+    slot_clock::SystemTimeSlotClock,
+    ChainSegmentResult, Kzg, NotifyExecutionLayer, TrustedSetup,
+};
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use serde::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use ssz::{Decode, Encode};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use store::{DBColumn, HotColdDB, HotStateSummary, KeyValueStore, LevelDB, StoreItem};
+use types::{BeaconState, BlobSidecarList, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+const METADATA_FILE: &str = "metadata.json";
+const STATE_FILE: &str = "state.ssz";
+const BLOCK_FILE: &str = "block.ssz";
+const BLOBS_FILE: &str = "blobs.ssz";
+const BLOCKS_DIR: &str = "blocks";
+
+/// Describes the contents of a checkpoint archive.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckpointMetadata {
+    pub config_name: Option<String>,
+    pub genesis_validators_root: Hash256,
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub state_root: Hash256,
+    /// Unfinalized blocks descending from `block_root`, in ascending slot order.
+    pub blocks: Vec<CheckpointBlock>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckpointBlock {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub has_blobs: bool,
+}
+
+impl CheckpointBlock {
+    fn block_file(&self) -> String {
+        format!("{}_{:?}_block.ssz", self.slot, self.block_root)
+    }
+
+    fn blobs_file(&self) -> String {
+        format!("{}_{:?}_blobs.ssz", self.slot, self.block_root)
+    }
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    fs::write(path, bytes).map_err(|e| format!("Unable to write {}: {e:?}", path.display()))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Unable to read {}: {e:?}", path.display()))
+}
+
+/// Returns the root of the block with the highest slot among the unfinalized hot states.
+fn find_highest_block_root<E: EthSpec>(
+    db: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+) -> Result<Option<Hash256>, String> {
+    let mut highest: Option<HotStateSummary> = None;
+    for res in db
+        .hot_db
+        .iter_column::<Hash256>(DBColumn::BeaconStateSummary)
+    {
+        let (_, bytes) = res.map_err(|e| format!("Unable to read state summary: {e:?}"))?;
+        let summary = HotStateSummary::from_store_bytes(&bytes)
+            .map_err(|e| format!("Unable to decode state summary: {e:?}"))?;
+        if highest.as_ref().map_or(true, |h| summary.slot > h.slot) {
+            highest = Some(summary);
+        }
+    }
+    Ok(highest.map(|summary| summary.latest_block_root))
+}
+
+/// Check that the finalized `state` and `block` of an archive match the roots in its `metadata`,
+/// and each other.
+fn verify_roots<E: EthSpec>(
+    metadata: &CheckpointMetadata,
+    state: &mut BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+) -> Result<(), String> {
+    if block.canonical_root() != metadata.block_root {
+        return Err(format!(
+            "Checkpoint block root does not match metadata: {:?}",
+            metadata.block_root
+        ));
+    }
+
+    let state_root = state
+        .canonical_root()
+        .map_err(|e| format!("Unable to hash checkpoint state: {e:?}"))?;
+    if state_root != metadata.state_root {
+        return Err(format!(
+            "Checkpoint state root {state_root:?} does not match metadata: {:?}",
+            metadata.state_root
+        ));
+    }
+
+    // The state is advanced past the block if the first slots of the finalized epoch were skipped.
+    let block_state_root = if state.slot() == block.slot() {
+        state_root
+    } else {
+        *state
+            .get_state_root(block.slot())
+            .map_err(|e| format!("Checkpoint state is not a descendant of the block: {e:?}"))?
+    };
+    if block.state_root() != block_state_root {
+        return Err(format!(
+            "Checkpoint block state root {:?} does not match the checkpoint state",
+            block.state_root()
+        ));
+    }
+    Ok(())
+}
+
+pub fn export_checkpoint<E: EthSpec>(
+    output_dir: &Path,
+    finalized_only: bool,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    if output_dir.exists() {
+        return Err(format!("{} already exists", output_dir.display()));
+    }
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &client_config.get_db_path(),
+        &client_config.get_freezer_db_path(),
+        &client_config.get_blobs_db_path(),
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let split = db.get_split_info();
+    let state = db
+        .get_state(&split.state_root, Some(split.slot))
+        .map_err(|e| format!("Unable to load finalized state: {e:?}"))?
+        .ok_or("Finalized state missing from database")?;
+    let block = db
+        .get_full_block(&split.block_root)
+        .map_err(|e| format!("Unable to load finalized block: {e:?}"))?
+        .ok_or("Finalized block missing from database")?;
+    let blobs = db
+        .get_blobs(&split.block_root)
+        .map_err(|e| format!("Unable to load finalized blobs: {e:?}"))?
+        .filter(|blobs| !blobs.is_empty());
+
+    // Walk back from the block with the highest slot to the finalized block.
+    let mut recent_blocks = vec![];
+    if !finalized_only {
+        let mut block_root = find_highest_block_root(&db)?.unwrap_or(split.block_root);
+        while block_root != split.block_root {
+            let recent_block = db
+                .get_full_block(&block_root)
+                .map_err(|e| format!("Unable to load block {block_root:?}: {e:?}"))?
+                .ok_or_else(|| format!("Block {block_root:?} missing from database"))?;
+            if recent_block.slot() <= split.slot {
+                return Err(format!(
+                    "Block {block_root:?} does not descend from the finalized block"
+                ));
+            }
+            let recent_blobs = db
+                .get_blobs(&block_root)
+                .map_err(|e| format!("Unable to load blobs for {block_root:?}: {e:?}"))?
+                .filter(|blobs| !blobs.is_empty());
+            let parent_root = recent_block.parent_root();
+            recent_blocks.push((block_root, recent_block, recent_blobs));
+            block_root = parent_root;
+        }
+        recent_blocks.reverse();
+    }
+
+    fs::create_dir_all(output_dir.join(BLOCKS_DIR))
+        .map_err(|e| format!("Unable to create {}: {e:?}", output_dir.display()))?;
+
+    write_file(&output_dir.join(STATE_FILE), &state.as_ssz_bytes())?;
+    write_file(&output_dir.join(BLOCK_FILE), &block.as_ssz_bytes())?;
+    if let Some(blobs) = &blobs {
+        write_file(&output_dir.join(BLOBS_FILE), &blobs.as_ssz_bytes())?;
+    }
+
+    let mut metadata = CheckpointMetadata {
+        config_name: spec.config_name.clone(),
+        genesis_validators_root: state.genesis_validators_root(),
+        slot: split.slot,
+        block_root: split.block_root,
+        state_root: split.state_root,
+        blocks: Vec::with_capacity(recent_blocks.len()),
+    };
+    for (block_root, recent_block, recent_blobs) in &recent_blocks {
+        let checkpoint_block = CheckpointBlock {
+            slot: recent_block.slot(),
+            block_root: *block_root,
+            has_blobs: recent_blobs.is_some(),
+        };
+        let blocks_dir = output_dir.join(BLOCKS_DIR);
+        write_file(
+            &blocks_dir.join(checkpoint_block.block_file()),
+            &recent_block.as_ssz_bytes(),
+        )?;
+        if let Some(recent_blobs) = recent_blobs {
+            write_file(
+                &blocks_dir.join(checkpoint_block.blobs_file()),
+                &recent_blobs.as_ssz_bytes(),
+            )?;
+        }
+        metadata.blocks.push(checkpoint_block);
+    }
+
+    let metadata_bytes = serde_json::to_vec_pretty(&metadata)
+        .map_err(|e| format!("Unable to serialize metadata: {e:?}"))?;
+    write_file(&output_dir.join(METADATA_FILE), &metadata_bytes)?;
+
+    info!(
+        log,
+        "Exported checkpoint";
+        "path" => %output_dir.display(),
+        "slot" => split.slot,
+        "block_root" => ?split.block_root,
+        "recent_blocks" => metadata.blocks.len(),
+    );
+
+    Ok(())
+}
+
+pub fn import_checkpoint<E: EthSpec>(
+    input_dir: &Path,
+    client_config: ClientConfig,
+    genesis_state: BeaconState<E>,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+
+    let metadata: CheckpointMetadata =
+        serde_json::from_slice(&read_file(&input_dir.join(METADATA_FILE))?)
+            .map_err(|e| format!("Unable to parse checkpoint metadata: {e:?}"))?;
+
+    if metadata.genesis_validators_root != genesis_state.genesis_validators_root() {
+        return Err(format!(
+            "Checkpoint is for network {}, not {}",
+            metadata
+                .config_name
+                .as_deref()
+                .unwrap_or("<unknown network>"),
+            spec.config_name.as_deref().unwrap_or("<unknown network>")
+        ));
+    }
+
+    let mut state = BeaconState::from_ssz_bytes(&read_file(&input_dir.join(STATE_FILE))?, spec)
+        .map_err(|e| format!("Unable to parse checkpoint state: {e:?}"))?;
+    let block = SignedBeaconBlock::from_ssz_bytes(&read_file(&input_dir.join(BLOCK_FILE))?, spec)
+        .map_err(|e| format!("Unable to parse checkpoint block: {e:?}"))?;
+    let blobs_path = input_dir.join(BLOBS_FILE);
+    let blobs = if blobs_path.exists() {
+        Some(
+            BlobSidecarList::from_ssz_bytes(&read_file(&blobs_path)?)
+                .map_err(|e| format!("Unable to parse checkpoint blobs: {e:?}"))?,
+        )
+    } else {
+        None
+    };
+    verify_roots(&metadata, &mut state, &block)?;
+
+    let blocks_dir = input_dir.join(BLOCKS_DIR);
+    let recent_blocks = metadata
+        .blocks
+        .iter()
+        .map(|checkpoint_block| {
+            let recent_block = SignedBeaconBlock::from_ssz_bytes(
+                &read_file(&blocks_dir.join(checkpoint_block.block_file()))?,
+                spec,
+            )
+            .map_err(|e| {
+                format!(
+                    "Unable to parse block {:?}: {e:?}",
+                    checkpoint_block.block_root
+                )
+            })?;
+            let recent_blobs = if checkpoint_block.has_blobs {
+                Some(
+                    BlobSidecarList::from_ssz_bytes(&read_file(
+                        &blocks_dir.join(checkpoint_block.blobs_file()),
+                    )?)
+                    .map_err(|e| {
+                        format!(
+                            "Unable to parse blobs for {:?}: {e:?}",
+                            checkpoint_block.block_root
+                        )
+                    })?,
+                )
+            } else {
+                None
+            };
+            RpcBlock::new(
+                Some(checkpoint_block.block_root),
+                Arc::new(recent_block),
+                recent_blobs,
+            )
+            .map_err(|e| format!("Invalid block {:?}: {e:?}", checkpoint_block.block_root))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let kzg = runtime_context
+        .eth2_network_config
+        .as_ref()
+        .and_then(|config| config.kzg_trusted_setup.as_ref())
+        .map(|bytes| {
+            serde_json::from_slice::<TrustedSetup>(bytes)
+                .map_err(|e| format!("Unable to read trusted setup: {e:?}"))
+                .and_then(|trusted_setup| {
+                    Kzg::new_from_trusted_setup(trusted_setup)
+                        .map_err(|e| format!("Failed to load trusted setup: {e:?}"))
+                })
+                .map(Arc::new)
+        })
+        .transpose()?;
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &client_config.create_db_path()?,
+        &client_config.create_freezer_db_path()?,
+        &client_config.create_blobs_db_path()?,
+        |_, _, _| Ok(()),
+        client_config.store.clone(),
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let genesis_time = genesis_state.genesis_time();
+    let builder = BeaconChainBuilder::<
+        Witness<SystemTimeSlotClock, CachingEth1Backend<E>, E, LevelDB<E>, LevelDB<E>>,
+    >::new(runtime_context.eth_spec_instance.clone())
+    .logger(log.clone())
+    .store(db)
+    .task_executor(runtime_context.executor.clone())
+    .custom_spec(spec.clone())
+    .chain_config(client_config.chain.clone())
+    .kzg(kzg);
+
+    if builder.store_contains_beacon_chain()? {
+        return Err("Database already contains a beacon chain, use --purge-db first".into());
+    }
+
+    info!(
+        log,
+        "Importing checkpoint";
+        "slot" => metadata.slot,
+        "block_root" => ?metadata.block_root,
+        "recent_blocks" => recent_blocks.len(),
+    );
+
+    let chain = builder
+        .weak_subjectivity_state(state, block, blobs, genesis_state)?
+        .no_eth1_backend()
+        .slot_clock(SystemTimeSlotClock::new(
+            spec.genesis_slot,
+            Duration::from_secs(genesis_time),
+            Duration::from_secs(spec.seconds_per_slot),
+        ))
+        .shutdown_sender(runtime_context.executor.shutdown_sender())
+        .build()
+        .map(Arc::new)?;
+
+    if !recent_blocks.is_empty() {
+        // The execution payloads of these blocks will be verified once the node is started with
+        // an execution engine, so import them optimistically.
+        let result = runtime_context
+            .executor
+            .block_on_dangerous(
+                chain.process_chain_segment(recent_blocks, NotifyExecutionLayer::No),
+                "import_checkpoint_blocks",
+            )
+            .ok_or("Shutting down")?;
+        match result {
+            ChainSegmentResult::Successful { imported_blocks } => {
+                info!(log, "Imported recent blocks"; "count" => imported_blocks);
+            }
+            ChainSegmentResult::Failed {
+                imported_blocks,
+                error,
+            } => {
+                warn!(
+                    log,
+                    "Failed to import all recent blocks";
+                    "info" => "the remaining blocks will be synced from the network",
+                    "imported_blocks" => imported_blocks,
+                    "error" => ?error,
+                );
+            }
+        }
+        runtime_context
+            .executor
+            .block_on_dangerous(
+                chain.recompute_head_at_current_slot(),
+                "import_checkpoint_head",
+            )
+            .ok_or("Shutting down")?;
+    }
+
+    chain
+        .persist_head_and_fork_choice()
+        .map_err(|e| format!("Unable to persist fork choice: {e:?}"))?;
+
+    info!(
+        log,
+        "Checkpoint imported successfully";
+        "head_slot" => chain.head_snapshot().beacon_block.slot(),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
+    use environment::{Environment, EnvironmentBuilder};
+    use tempfile::TempDir;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    fn client_config(data_dir: &Path) -> ClientConfig {
+        let mut config = ClientConfig::default();
+        config.data_dir = data_dir.to_path_buf();
+        config
+    }
+
+    fn open_db(
+        config: &ClientConfig,
+        env: &Environment<E>,
+    ) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+        HotColdDB::open(
+            &config.create_db_path().unwrap(),
+            &config.create_freezer_db_path().unwrap(),
+            &config.create_blobs_db_path().unwrap(),
+            |_, _, _| Ok(()),
+            config.store.clone(),
+            env.eth2_config.spec.clone(),
+            env.core_context().log().clone(),
+        )
+        .unwrap()
+    }
+
+    /// Build a finalized chain in the database of `config`, returning the genesis state and the
+    /// root of the head block.
+    fn build_chain(config: &ClientConfig, env: &Environment<E>) -> (BeaconState<E>, Hash256) {
+        let context = env.core_context();
+        let harness = BeaconChainHarness::builder(MinimalEthSpec)
+            .spec(env.eth2_config.spec.clone())
+            .deterministic_keypairs(32)
+            .logger(context.log().clone())
+            .fresh_disk_store(open_db(config, env))
+            .build();
+        let genesis_state = harness.get_current_state();
+        harness.advance_slot();
+        context
+            .executor
+            .block_on_dangerous(
+                harness.extend_chain(
+                    5 * E::slots_per_epoch() as usize + 3,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                ),
+                "build_chain",
+            )
+            .unwrap();
+        let head_root = harness.head_block_root();
+        assert!(harness.finalized_checkpoint().epoch > 0);
+        (genesis_state, head_root)
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let env = EnvironmentBuilder::minimal()
+            .null_logger()
+            .unwrap()
+            .multi_threaded_tokio_runtime()
+            .unwrap()
+            .build()
+            .unwrap();
+        let context = env.core_context();
+        let dir = TempDir::new().unwrap();
+        let source = client_config(&dir.path().join("source"));
+        let target = client_config(&dir.path().join("target"));
+        let archive = dir.path().join("checkpoint");
+
+        let (genesis_state, head_root) = build_chain(&source, &env);
+        let source_split = open_db(&source, &env).get_split_info();
+
+        export_checkpoint(&archive, false, source, &context, context.log().clone()).unwrap();
+        import_checkpoint(
+            &archive,
+            target.clone(),
+            genesis_state,
+            &context,
+            context.log().clone(),
+        )
+        .unwrap();
+
+        let db = open_db(&target, &env);
+        let split = db.get_split_info();
+        assert_eq!(split.slot, source_split.slot);
+        assert_eq!(split.state_root, source_split.state_root);
+        assert_eq!(split.block_root, source_split.block_root);
+        // The unfinalized blocks were imported as well.
+        assert!(db.get_full_block(&head_root).unwrap().is_some());
+    }
+
+    #[test]
+    fn import_rejects_mismatched_state_root() {
+        let env = EnvironmentBuilder::minimal()
+            .null_logger()
+            .unwrap()
+            .multi_threaded_tokio_runtime()
+            .unwrap()
+            .build()
+            .unwrap();
+        let context = env.core_context();
+        let dir = TempDir::new().unwrap();
+        let source = client_config(&dir.path().join("source"));
+        let archive = dir.path().join("checkpoint");
+
+        let (genesis_state, _) = build_chain(&source, &env);
+        export_checkpoint(&archive, true, source, &context, context.log().clone()).unwrap();
+
+        let metadata_path = archive.join(METADATA_FILE);
+        let mut metadata: CheckpointMetadata =
+            serde_json::from_slice(&fs::read(&metadata_path).unwrap()).unwrap();
+        metadata.state_root = Hash256::repeat_byte(0xaa);
+        fs::write(&metadata_path, serde_json::to_vec(&metadata).unwrap()).unwrap();
+
+        let err = import_checkpoint(
+            &archive,
+            client_config(&dir.path().join("target")),
+            genesis_state,
+            &context,
+            context.log().clone(),
+        )
+        .unwrap_err();
+        assert!(err.contains("state root"), "{err}");
+    }
+}
@@ -79,6 +79,12 @@ pub enum DatabaseManagerSubcommand {
     PruneBlobs(PruneBlobs),
     PruneStates(PruneStates),
     Compact(Compact),
+    ExportCheckpoint(ExportCheckpoint),
+    ImportCheckpoint(ImportCheckpoint),
+    ExportEra(ExportEra),
+    ImportEra(ImportEra),
+    Relocate(Relocate),
+    Restore(Restore),
 }
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
@@ -99,10 +105,11 @@ pub struct Inspect {
     #[clap(
         long,
         value_name = "TAG",
+        required_unless_present = "verify",
         help = "3-byte column ID (see `DBColumn`)",
         display_order = 0
     )]
-    pub column: String,
+    pub column: Option<String>,
 
     #[clap(
         long,
@@ -155,6 +162,28 @@ pub struct Inspect {
         display_order = 0
     )]
     pub output_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["column", "freezer", "blobs_db"],
+        help = "Check the hot, freezer and blobs databases for inconsistencies, such as states \
+                which should have been pruned and missing blobs, instead of inspecting a column. \
+                Exits with an error if any are found.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub verify: bool,
+
+    #[clap(
+        long,
+        requires = "verify",
+        help = "Repair the inconsistencies found by --verify where possible, by deleting \
+                dangling states and advancing the oldest blob slot past finalized blocks which \
+                are missing their blobs.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub repair: bool,
 }
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
@@ -227,3 +256,131 @@ pub struct Compact {
     )]
     pub output_dir: Option<PathBuf>,
 }
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Export the finalized checkpoint and recent blocks to a directory that can be \
+             imported by another node.",
+    alias = "export_checkpoint"
+)]
+pub struct ExportCheckpoint {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory to write the checkpoint archive to. Must not already exist.",
+        display_order = 0
+    )]
+    pub output_dir: PathBuf,
+
+    #[clap(
+        long,
+        help = "Only export the finalized checkpoint, without the unfinalized blocks that \
+                descend from it.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub finalized_only: bool,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Initialize an empty database from a checkpoint archive created with \
+             `export-checkpoint`.",
+    alias = "import_checkpoint"
+)]
+pub struct ImportCheckpoint {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory containing the checkpoint archive.",
+        display_order = 0
+    )]
+    pub input_dir: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Export finalized blocks and historic states to era files.",
+    alias = "export_era"
+)]
+pub struct ExportEra {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory to write the era files to. Existing era files are overwritten.",
+        display_order = 0
+    )]
+    pub output_dir: PathBuf,
+
+    #[clap(
+        long,
+        value_name = "ERA",
+        help = "The first era to export. Defaults to the earliest era with all of its blocks \
+                in the database.",
+        display_order = 0
+    )]
+    pub start_era: Option<u64>,
+
+    #[clap(
+        long,
+        value_name = "ERA",
+        help = "The last era to export. Defaults to the latest finalized era.",
+        display_order = 0
+    )]
+    pub end_era: Option<u64>,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Import the blocks from era files into the block history of a checkpoint synced \
+             database.",
+    alias = "import_era"
+)]
+pub struct ImportEra {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory containing the era files.",
+        display_order = 0
+    )]
+    pub input_dir: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Copy the databases to a new directory, e.g. on a larger disk, and switch the node \
+             over to them. The initial copy can be made while the beacon node is running."
+)]
+pub struct Relocate {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory to move the databases to.",
+        display_order = 0
+    )]
+    pub target: PathBuf,
+
+    #[clap(
+        long,
+        help = "Copy any changes made since the last run, and replace the original database \
+                directories with links to the new ones. The beacon node must be stopped.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub finalize: bool,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Restore the databases from a backup written by the lighthouse/database/backup \
+             API. The database directories must be empty."
+)]
+pub struct Restore {
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "The backup file to restore from.",
+        display_order = 0
+    )]
+    pub input: PathBuf,
+}
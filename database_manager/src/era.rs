@@ -0,0 +1,293 @@
+//! Export and import of era files.
+//!
+//! Era files archive the finalized blocks of one era (`SLOTS_PER_HISTORICAL_ROOT` slots) and the
+//! state at its end, in the format used by other consensus clients. Exporting requires the blocks
+//! and historic states of the eras to be in the database, i.e. a node that has backfilled and
+//! reconstructed states. Importing fills in the block history of a checkpoint synced node, in the
+//! same way as backfill sync.
+use beacon_chain::{
+    block_verification_types::RpcBlock,
+    builder::{BeaconChainBuilder, Witness},
+    data_availability_checker::MaybeAvailableBlock,
+    eth1_chain::CachingEth1Backend,
+    slot_clock::SystemTimeSlotClock,
+};
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use slog::{debug, info, Logger};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use store::era::EraFile;
+use store::{HotColdDB, LevelDB};
+use types::{BeaconState, EthSpec, Slot};
+
+const ERA_EXTENSION: &str = "era";
+const ERA1_EXTENSION: &str = "era1";
+
+pub fn export_era<E: EthSpec>(
+    output_dir: &Path,
+    start_era: Option<u64>,
+    end_era: Option<u64>,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let config_name = spec
+        .config_name
+        .clone()
+        .ok_or("The network has no config name, which is required to name era files")?;
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &client_config.get_db_path(),
+        &client_config.get_freezer_db_path(),
+        &client_config.get_blobs_db_path(),
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    // Era N contains the blocks from slot (N - 1) * slots_per_era, so all of those blocks must
+    // be in the database, and its state must be finalized.
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let split = db.get_split_info();
+    let oldest_block_slot = db.get_oldest_block_slot().as_u64();
+    let first_era = if oldest_block_slot == 0 {
+        0
+    } else {
+        oldest_block_slot.div_ceil(slots_per_era) + 1
+    };
+    let last_era = split.slot.as_u64() / slots_per_era;
+
+    let start_era = start_era.unwrap_or(first_era);
+    let end_era = end_era.unwrap_or(last_era);
+    if start_era < first_era {
+        return Err(format!(
+            "Era {start_era} is not available, blocks are only stored from slot \
+             {oldest_block_slot} (era {first_era})"
+        ));
+    }
+    if end_era > last_era {
+        return Err(format!(
+            "Era {end_era} is not finalized, the latest finalized era is {last_era}"
+        ));
+    }
+    if start_era > end_era {
+        return Err(format!(
+            "No eras to export between {start_era} and {end_era}"
+        ));
+    }
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Unable to create {}: {e:?}", output_dir.display()))?;
+
+    for era in start_era..=end_era {
+        let era_slot = Slot::new(era * slots_per_era);
+        let state = if era_slot == split.slot {
+            db.get_state(&split.state_root, Some(split.slot))
+        } else {
+            db.load_cold_state_by_slot(era_slot)
+        }
+        .map_err(|e| format!("Unable to load state at slot {era_slot}: {e:?}"))?
+        .ok_or_else(|| {
+            format!(
+                "State at slot {era_slot} is not available, run the node with \
+                 --reconstruct-historic-states to store historic states"
+            )
+        })?;
+
+        // The state's block roots cover the whole era, with skipped slots repeating the root of
+        // the previous block.
+        let mut blocks = vec![];
+        let mut prev_block_root = None;
+        for slot in
+            (era_slot.saturating_sub(slots_per_era).as_u64()..era_slot.as_u64()).map(Slot::new)
+        {
+            let block_root = *state
+                .get_block_root(slot)
+                .map_err(|e| format!("Unable to get block root at slot {slot}: {e:?}"))?;
+            if prev_block_root == Some(block_root) {
+                continue;
+            }
+            prev_block_root = Some(block_root);
+
+            let block = db
+                .get_full_block(&block_root)
+                .map_err(|e| format!("Unable to load block {block_root:?}: {e:?}"))?
+                .ok_or_else(|| format!("Block {block_root:?} missing from database"))?;
+            // If the first slots of the era are skipped, this is the last block of a previous era.
+            if block.slot() == slot {
+                blocks.push(block);
+            }
+        }
+
+        let era_file = EraFile { blocks, state };
+        let path = output_dir.join(
+            era_file
+                .file_name(&config_name)
+                .map_err(|e| format!("Unable to name era {era}: {e:?}"))?,
+        );
+        let bytes = era_file
+            .to_bytes()
+            .map_err(|e| format!("Unable to encode era {era}: {e:?}"))?;
+        fs::write(&path, bytes)
+            .map_err(|e| format!("Unable to write {}: {e:?}", path.display()))?;
+
+        info!(
+            log,
+            "Exported era";
+            "era" => era,
+            "blocks" => era_file.blocks.len(),
+            "path" => %path.display(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the era files in `dir`, from the latest era to the earliest.
+fn list_era_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut paths = vec![];
+    for entry in
+        fs::read_dir(dir).map_err(|e| format!("Unable to read {}: {e:?}", dir.display()))?
+    {
+        let path = entry
+            .map_err(|e| format!("Unable to read {}: {e:?}", dir.display()))?
+            .path();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(ERA_EXTENSION) => paths.push(path),
+            Some(ERA1_EXTENSION) => {
+                return Err(format!(
+                    "{} contains execution layer history, which is stored by the execution \
+                     client rather than the beacon node",
+                    path.display()
+                ));
+            }
+            _ => (),
+        }
+    }
+    if paths.is_empty() {
+        return Err(format!("No .era files in {}", dir.display()));
+    }
+
+    // File names start with the network name and the zero padded era number.
+    paths.sort();
+    paths.reverse();
+    Ok(paths)
+}
+
+pub fn import_era<E: EthSpec>(
+    input_dir: &Path,
+    client_config: ClientConfig,
+    genesis_state: BeaconState<E>,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let paths = list_era_files(input_dir)?;
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &client_config.get_db_path(),
+        &client_config.get_freezer_db_path(),
+        &client_config.get_blobs_db_path(),
+        |_, _, _| Ok(()),
+        client_config.store.clone(),
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let chain = BeaconChainBuilder::<
+        Witness<SystemTimeSlotClock, CachingEth1Backend<E>, E, LevelDB<E>, LevelDB<E>>,
+    >::new(runtime_context.eth_spec_instance.clone())
+    .logger(log.clone())
+    .store(db)
+    .task_executor(runtime_context.executor.clone())
+    .custom_spec(spec.clone())
+    .chain_config(client_config.chain.clone())
+    .resume_from_db()?
+    .no_eth1_backend()
+    .slot_clock(SystemTimeSlotClock::new(
+        spec.genesis_slot,
+        Duration::from_secs(genesis_state.genesis_time()),
+        Duration::from_secs(spec.seconds_per_slot),
+    ))
+    .shutdown_sender(runtime_context.executor.shutdown_sender())
+    .build()?;
+
+    if chain.genesis_validators_root != genesis_state.genesis_validators_root() {
+        return Err(format!(
+            "Error: Wrong network. Genesis state in DB does not match {} genesis.",
+            spec.config_name.as_deref().unwrap_or("<unknown network>")
+        ));
+    }
+
+    for path in paths {
+        let oldest_block_slot = chain.store.get_oldest_block_slot();
+        if oldest_block_slot <= chain.genesis_backfill_slot {
+            break;
+        }
+
+        let bytes =
+            fs::read(&path).map_err(|e| format!("Unable to read {}: {e:?}", path.display()))?;
+        let era_file = EraFile::<E>::from_bytes(&bytes, spec)
+            .map_err(|e| format!("Invalid era file {}: {e:?}", path.display()))?;
+        let era = era_file.era();
+
+        if era_file.state.genesis_validators_root() != chain.genesis_validators_root {
+            return Err(format!("{} is for a different network", path.display()));
+        }
+        if era_file.start_slot() >= oldest_block_slot {
+            debug!(log, "Skipping era with known blocks"; "era" => era);
+            continue;
+        }
+
+        let blocks = era_file
+            .blocks
+            .into_iter()
+            .map(|block| RpcBlock::new_without_blobs(None, Arc::new(block)))
+            .collect();
+        let available_blocks = chain
+            .data_availability_checker
+            .verify_kzg_for_rpc_blocks(blocks)
+            .map_err(|e| format!("Unable to check availability of era {era}: {e:?}"))?
+            .into_iter()
+            .map(|block| match block {
+                MaybeAvailableBlock::Available(block) => Ok(block),
+                MaybeAvailableBlock::AvailabilityPending { block_root, .. } => Err(format!(
+                    "Block {block_root:?} is within the data availability window and requires \
+                     blobs, which are not stored in era files"
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let imported_blocks = chain
+            .import_historical_block_batch(available_blocks)
+            .map_err(|e| format!("Unable to import era {era}: {e:?}"))?;
+
+        info!(
+            log,
+            "Imported era";
+            "era" => era,
+            "blocks" => imported_blocks,
+            "oldest_block_slot" => chain.store.get_oldest_block_slot(),
+        );
+    }
+
+    let oldest_block_slot = chain.store.get_oldest_block_slot();
+    if oldest_block_slot <= chain.genesis_backfill_slot {
+        info!(log, "Block history is complete");
+    } else {
+        info!(
+            log,
+            "Era import finished";
+            "info" => "the remaining blocks will be backfilled from the network",
+            "oldest_block_slot" => oldest_block_slot,
+        );
+    }
+    Ok(())
+}
@@ -1,4 +1,9 @@
+mod checkpoint;
 pub mod cli;
+mod era;
+mod relocate;
+mod restore;
+mod verify;
 use crate::cli::DatabaseManager;
 use crate::cli::Migrate;
 use crate::cli::PruneStates;
@@ -7,10 +12,12 @@ use beacon_chain::{
     slot_clock::SystemTimeSlotClock,
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
+use checkpoint::{export_checkpoint, import_checkpoint};
 use clap::ArgMatches;
 use clap::ValueEnum;
 use cli::{Compact, Inspect};
 use environment::{Environment, RuntimeContext};
+use era::{export_era, import_era};
 use serde::{Deserialize, Serialize};
 use slog::{info, warn, Logger};
 use std::fs;
@@ -119,6 +126,8 @@ pub struct InspectConfig {
 fn parse_inspect_config(inspect_config: &Inspect) -> Result<InspectConfig, String> {
     let column: DBColumn = inspect_config
         .column
+        .as_deref()
+        .ok_or("--column is required")?
         .parse()
         .map_err(|e| format!("Unable to parse column flag: {e:?}"))?;
     let target: InspectTarget = inspect_config.output.clone();
@@ -473,6 +482,32 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+/// Load the genesis state for the configured network.
+fn get_genesis_state<E: EthSpec>(
+    client_config: &ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: &Logger,
+) -> Result<BeaconState<E>, String> {
+    let network_config = runtime_context
+        .eth2_network_config
+        .clone()
+        .ok_or("Missing network config")?;
+
+    runtime_context
+        .executor
+        .block_on_dangerous(
+            network_config.genesis_state::<E>(
+                client_config.genesis_state_url.as_deref(),
+                client_config.genesis_state_url_timeout,
+                log,
+            ),
+            "get_genesis_state",
+        )
+        .ok_or("Shutting down")?
+        .map_err(|e| format!("Error getting genesis state: {e}"))?
+        .ok_or_else(|| "Genesis state missing".to_string())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<E: EthSpec>(
     cli_args: &ArgMatches,
@@ -489,6 +524,9 @@ pub fn run<E: EthSpec>(
             let migrate_config = parse_migrate_config(migrate_config)?;
             migrate_db(migrate_config, client_config, &context, log).map_err(format_err)
         }
+        cli::DatabaseManagerSubcommand::Inspect(inspect_config) if inspect_config.verify => {
+            verify::verify_db(inspect_config.repair, client_config, &context, log)
+        }
         cli::DatabaseManagerSubcommand::Inspect(inspect_config) => {
             let inspect_config = parse_inspect_config(inspect_config)?;
             inspect_db::<E>(inspect_config, client_config)
@@ -503,25 +541,7 @@ pub fn run<E: EthSpec>(
             prune_blobs(client_config, &context, log).map_err(format_err)
         }
         cli::DatabaseManagerSubcommand::PruneStates(prune_states_config) => {
-            let executor = env.core_context().executor;
-            let network_config = context
-                .eth2_network_config
-                .clone()
-                .ok_or("Missing network config")?;
-
-            let genesis_state = executor
-                .block_on_dangerous(
-                    network_config.genesis_state::<E>(
-                        client_config.genesis_state_url.as_deref(),
-                        client_config.genesis_state_url_timeout,
-                        &log,
-                    ),
-                    "get_genesis_state",
-                )
-                .ok_or("Shutting down")?
-                .map_err(|e| format!("Error getting genesis state: {e}"))?
-                .ok_or("Genesis state missing")?;
-
+            let genesis_state = get_genesis_state(&client_config, &context, &log)?;
             let prune_config = parse_prune_states_config(prune_states_config)?;
 
             prune_states(client_config, prune_config, genesis_state, &context, log)
@@ -530,5 +550,50 @@ pub fn run<E: EthSpec>(
             let compact_config = parse_compact_config(compact_config)?;
             compact_db::<E>(compact_config, client_config, log).map_err(format_err)
         }
+        cli::DatabaseManagerSubcommand::ExportCheckpoint(export_config) => export_checkpoint(
+            &export_config.output_dir,
+            export_config.finalized_only,
+            client_config,
+            &context,
+            log,
+        ),
+        cli::DatabaseManagerSubcommand::ImportCheckpoint(import_config) => {
+            let genesis_state = get_genesis_state(&client_config, &context, &log)?;
+            import_checkpoint(
+                &import_config.input_dir,
+                client_config,
+                genesis_state,
+                &context,
+                log,
+            )
+        }
+        cli::DatabaseManagerSubcommand::ExportEra(export_config) => export_era(
+            &export_config.output_dir,
+            export_config.start_era,
+            export_config.end_era,
+            client_config,
+            &context,
+            log,
+        ),
+        cli::DatabaseManagerSubcommand::ImportEra(import_config) => {
+            let genesis_state = get_genesis_state(&client_config, &context, &log)?;
+            import_era(
+                &import_config.input_dir,
+                client_config,
+                genesis_state,
+                &context,
+                log,
+            )
+        }
+        cli::DatabaseManagerSubcommand::Relocate(relocate_config) => relocate::relocate_db(
+            &relocate_config.target,
+            relocate_config.finalize,
+            client_config,
+            &context,
+            log,
+        ),
+        cli::DatabaseManagerSubcommand::Restore(restore_config) => {
+            restore::restore_db(&restore_config.input, client_config, &context, log)
+        }
     }
 }
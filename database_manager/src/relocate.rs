@@ -0,0 +1,358 @@
+//! Relocation of the databases to a new directory, e.g. on a larger volume.
+//!
+//! Relocation happens in two phases, so that the node only needs to be stopped briefly:
+//!
+//! 1. Without `--finalize`, the databases are copied while the beacon node is running. LevelDB
+//!    never modifies a table file once it has been written, so tables which have already been
+//!    copied are skipped, and repeating this phase only copies the tables written since the last
+//!    run. The copy is not consistent, and can't be used by itself.
+//! 2. With `--finalize`, which requires the node to be stopped, the files which changed since the
+//!    last copy are copied again, tables which have since been compacted away are removed, and the
+//!    original database directories are replaced with links to the new ones. The node can then be
+//!    restarted without changing its flags.
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use slog::{info, warn, Logger};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use store::{HotColdDB, LevelDB};
+use types::EthSpec;
+
+/// Extensions of LevelDB table files, which are immutable once written.
+const TABLE_EXTENSIONS: [&str; 2] = ["ldb", "sst"];
+
+/// Suffix added to the original database directories when they are replaced by links.
+const RELOCATED_SUFFIX: &str = "relocated";
+
+struct Database {
+    name: &'static str,
+    path: PathBuf,
+}
+
+/// Copy the databases to `target`, and switch the node over to the copy if `finalize` is set.
+pub fn relocate_db<E: EthSpec>(
+    target: &Path,
+    finalize: bool,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let databases = [
+        Database {
+            name: "chain_db",
+            path: client_config.get_db_path(),
+        },
+        Database {
+            name: "freezer_db",
+            path: client_config.get_freezer_db_path(),
+        },
+        Database {
+            name: "blobs_db",
+            path: client_config.get_blobs_db_path(),
+        },
+    ];
+
+    fs::create_dir_all(target)
+        .map_err(|e| format!("Unable to create {}: {e}", target.display()))?;
+    let target = target
+        .canonicalize()
+        .map_err(|e| format!("Unable to resolve {}: {e}", target.display()))?;
+
+    let mut to_relocate = vec![];
+    for db in &databases {
+        let destination = target.join(db.name);
+        match fs::read_link(&db.path) {
+            Ok(link) if link == destination => {
+                info!(log, "Database already relocated"; "db" => db.name);
+                continue;
+            }
+            _ => (),
+        }
+        if !db.path.is_dir() {
+            warn!(log, "Database not found"; "db" => db.name, "path" => ?db.path);
+            continue;
+        }
+        let source = db
+            .path
+            .canonicalize()
+            .map_err(|e| format!("Unable to resolve {}: {e}", db.path.display()))?;
+        if destination.starts_with(&source) || source.starts_with(&destination) {
+            return Err(format!(
+                "Target {} overlaps with the {} directory {}",
+                target.display(),
+                db.name,
+                source.display()
+            ));
+        }
+        to_relocate.push((db, destination));
+    }
+
+    if finalize {
+        // Opening each database takes its lock, which fails if the node is still running.
+        for (db, _) in &to_relocate {
+            drop(LevelDB::<E>::open(&db.path).map_err(|e| {
+                format!(
+                    "Unable to open {}, ensure the beacon node is stopped: {e:?}",
+                    db.name
+                )
+            })?);
+        }
+    }
+
+    for (db, destination) in &to_relocate {
+        let (files, bytes) = copy_dir(&db.path, destination, finalize)?;
+        info!(
+            log,
+            "Copied database";
+            "db" => db.name,
+            "files" => files,
+            "megabytes" => bytes / (1024 * 1024),
+            "destination" => ?destination,
+        );
+    }
+
+    if !finalize {
+        info!(
+            log,
+            "Initial copy complete";
+            "msg" => "stop the beacon node and re-run this command with --finalize to switch over",
+        );
+        return Ok(());
+    }
+
+    // Check that the copy is usable before switching over to it.
+    let copy_path = |name: &str| {
+        to_relocate
+            .iter()
+            .find(|(db, _)| db.name == name)
+            .map(|(_, destination)| destination.clone())
+            .unwrap_or_else(|| target.join(name))
+    };
+    let copy = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &copy_path("chain_db"),
+        &copy_path("freezer_db"),
+        &copy_path("blobs_db"),
+        |_, _, _| Ok(()),
+        client_config.store,
+        runtime_context.eth2_config.spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open relocated database: {e:?}"))?;
+    info!(
+        log,
+        "Relocated database verified";
+        "split_slot" => copy.get_split_info().slot,
+    );
+    drop(copy);
+
+    for (db, destination) in &to_relocate {
+        let original = relocated_path(&db.path);
+        fs::rename(&db.path, &original)
+            .map_err(|e| format!("Unable to move {}: {e}", db.path.display()))?;
+        link_dir(destination, &db.path)?;
+        info!(
+            log,
+            "Switched database to new location";
+            "db" => db.name,
+            "original" => ?original,
+        );
+    }
+
+    info!(
+        log,
+        "Relocation complete";
+        "msg" => "restart the beacon node, and once it is running delete the original directories",
+    );
+    Ok(())
+}
+
+/// Copy the files in `source` to `destination`, returning the number of files and bytes copied.
+///
+/// Table files which already exist in `destination` with the same size are skipped. If `prune` is
+/// set, files in `destination` which are not in `source` are deleted.
+fn copy_dir(source: &Path, destination: &Path, prune: bool) -> Result<(usize, u64), String> {
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Unable to create {}: {e}", destination.display()))?;
+
+    let mut files = 0;
+    let mut bytes = 0;
+    for entry in read_dir(source)? {
+        let source_file = entry.path();
+        let destination_file = destination.join(entry.file_name());
+        let metadata = match entry.metadata() {
+            Ok(metadata) if metadata.is_file() => metadata,
+            Ok(_) => continue,
+            // Tables may be deleted by a compaction while the node is running.
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Unable to read {}: {e}", source_file.display())),
+        };
+
+        if is_table(&source_file)
+            && fs::metadata(&destination_file).map_or(false, |m| m.len() == metadata.len())
+        {
+            continue;
+        }
+
+        match fs::copy(&source_file, &destination_file) {
+            Ok(copied) => {
+                files += 1;
+                bytes += copied;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to copy {} to {}: {e}",
+                    source_file.display(),
+                    destination_file.display()
+                ))
+            }
+        }
+    }
+
+    if prune {
+        for entry in read_dir(destination)? {
+            if !source.join(entry.file_name()).exists() {
+                fs::remove_file(entry.path())
+                    .map_err(|e| format!("Unable to remove {}: {e}", entry.path().display()))?;
+            }
+        }
+    }
+
+    Ok((files, bytes))
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<fs::DirEntry>, String> {
+    fs::read_dir(dir)
+        .and_then(|entries| entries.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Unable to read {}: {e}", dir.display()))
+}
+
+fn is_table(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| TABLE_EXTENSIONS.contains(&extension))
+}
+
+fn relocated_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(RELOCATED_SUFFIX);
+    path.with_file_name(name)
+}
+
+#[cfg(unix)]
+fn link_dir(destination: &Path, link: &Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(destination, link)
+        .map_err(|e| format!("Unable to link {}: {e}", link.display()))
+}
+
+#[cfg(windows)]
+fn link_dir(destination: &Path, link: &Path) -> Result<(), String> {
+    std::os::windows::fs::symlink_dir(destination, link)
+        .map_err(|e| format!("Unable to link {}: {e}", link.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
+    use environment::EnvironmentBuilder;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn copy_dir_skips_unchanged_tables() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        let destination = dir.path().join("destination");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("000001.ldb"), b"table").unwrap();
+        fs::write(source.join("MANIFEST-000002"), b"manifest").unwrap();
+
+        assert_eq!(copy_dir(&source, &destination, false).unwrap(), (2, 13));
+
+        // Unchanged tables are skipped, but other files are always copied.
+        fs::write(source.join("000003.ldb"), b"new").unwrap();
+        assert_eq!(copy_dir(&source, &destination, false).unwrap(), (2, 11));
+
+        // Pruning removes the files which have been deleted from the source.
+        fs::remove_file(source.join("000001.ldb")).unwrap();
+        copy_dir(&source, &destination, true).unwrap();
+        assert!(!destination.join("000001.ldb").exists());
+        assert_eq!(fs::read(destination.join("000003.ldb")).unwrap(), b"new");
+    }
+
+    #[test]
+    fn relocate_round_trip() {
+        let env = EnvironmentBuilder::minimal()
+            .null_logger()
+            .unwrap()
+            .multi_threaded_tokio_runtime()
+            .unwrap()
+            .build()
+            .unwrap();
+        let context = env.core_context();
+        let log = context.log().clone();
+        let dir = TempDir::new().unwrap();
+        let mut config = ClientConfig::default();
+        config.data_dir = dir.path().join("data");
+        let target = dir.path().join("target");
+
+        let open_db = |config: &ClientConfig| {
+            HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+                &config.create_db_path().unwrap(),
+                &config.create_freezer_db_path().unwrap(),
+                &config.create_blobs_db_path().unwrap(),
+                |_, _, _| Ok(()),
+                config.store.clone(),
+                env.eth2_config.spec.clone(),
+                log.clone(),
+            )
+            .unwrap()
+        };
+
+        let head_root = {
+            let harness = BeaconChainHarness::builder(MinimalEthSpec)
+                .spec(env.eth2_config.spec.clone())
+                .deterministic_keypairs(8)
+                .logger(log.clone())
+                .fresh_disk_store(Arc::new(open_db(&config)))
+                .build();
+            harness.advance_slot();
+            context
+                .executor
+                .block_on_dangerous(
+                    harness.extend_chain(
+                        E::slots_per_epoch() as usize,
+                        BlockStrategy::OnCanonicalHead,
+                        AttestationStrategy::AllValidators,
+                    ),
+                    "build_chain",
+                )
+                .unwrap();
+            harness.head_block_root()
+        };
+
+        relocate_db(&target, false, config.clone(), &context, log.clone()).unwrap();
+        // The databases are only switched over when finalizing.
+        assert!(fs::read_link(config.get_db_path()).is_err());
+
+        relocate_db(&target, true, config.clone(), &context, log.clone()).unwrap();
+        let target = target.canonicalize().unwrap();
+        assert_eq!(
+            fs::read_link(config.get_db_path()).unwrap(),
+            target.join("chain_db")
+        );
+        assert!(relocated_path(&config.get_db_path()).is_dir());
+
+        // Finalizing again is a no-op.
+        relocate_db(&target, true, config.clone(), &context, log.clone()).unwrap();
+
+        let db = open_db(&config);
+        assert!(db.get_blinded_block(&head_root).unwrap().is_some());
+    }
+}
@@ -0,0 +1,66 @@
+//! Restoration of a backup written by the `POST lighthouse/database/backup` API.
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use slog::{info, Logger};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use store::backup::restore_backup;
+use store::{HotColdDB, LevelDB};
+use types::EthSpec;
+
+/// Restore the backup at `input` into the configured database directories, which must be empty.
+pub fn restore_db<E: EthSpec>(
+    input: &Path,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    for path in [&hot_path, &cold_path, &blobs_path] {
+        let is_empty = fs::read_dir(path).map_or(true, |mut entries| entries.next().is_none());
+        if !is_empty {
+            return Err(format!(
+                "Database directory {} is not empty, move it aside before restoring",
+                path.display()
+            ));
+        }
+        fs::create_dir_all(path)
+            .map_err(|e| format!("Unable to create {}: {e}", path.display()))?;
+    }
+
+    let file = File::open(input).map_err(|e| format!("Unable to open {}: {e}", input.display()))?;
+    let hot_db =
+        LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot database: {e:?}"))?;
+    let cold_db = LevelDB::<E>::open(&cold_path)
+        .map_err(|e| format!("Unable to open freezer database: {e:?}"))?;
+    let blobs_db = LevelDB::<E>::open(&blobs_path)
+        .map_err(|e| format!("Unable to open blobs database: {e:?}"))?;
+
+    info!(log, "Restoring database"; "input" => ?input);
+    let entries = restore_backup::<E, _, _, _>(BufReader::new(file), &hot_db, &cold_db, &blobs_db)
+        .map_err(|e| format!("Unable to restore backup: {e:?}"))?;
+    drop((hot_db, cold_db, blobs_db));
+
+    // Check that the restored database can be opened by the beacon node.
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        runtime_context.eth2_config.spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open restored database: {e:?}"))?;
+    info!(
+        log,
+        "Database restored";
+        "entries" => entries,
+        "split_slot" => db.get_split_info().slot,
+    );
+    Ok(())
+}
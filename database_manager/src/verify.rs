@@ -0,0 +1,472 @@
+//! Consistency checks across the hot, freezer and blobs databases.
+//!
+//! The checks look for inconsistencies which can be left behind by an unclean shutdown, such as
+//! states which should have been pruned when the split advanced, or blobs which were never written
+//! for a block. Some of these can be repaired: dangling states are deleted, and the oldest blob
+//! slot is advanced past finalized blocks whose blobs are missing, so that the node no longer
+//! claims to have them.
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use slog::{info, warn, Logger};
+use ssz::Decode;
+use std::collections::HashSet;
+use std::fmt;
+use store::{BlobInfo, DBColumn, HotColdDB, HotStateSummary, KeyValueStore, LevelDB, StoreOp};
+use types::{EthSpec, Hash256, Slot};
+
+type Store<E> = HotColdDB<E, LevelDB<E>, LevelDB<E>>;
+
+/// An inconsistency found in the database.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Anomaly {
+    /// The split state is not stored in the hot database.
+    SplitStateMissing { state_root: Hash256, slot: Slot },
+    /// The split state is stored with a different slot to the split.
+    SplitSlotMismatch {
+        state_root: Hash256,
+        split_slot: Slot,
+        state_slot: Slot,
+    },
+    /// The split block is missing.
+    SplitBlockMissing { block_root: Hash256 },
+    /// A hot state which should have been pruned, or whose block is missing.
+    DanglingState {
+        state_root: Hash256,
+        slot: Slot,
+        reason: &'static str,
+    },
+    /// A block which commits to blobs which are not stored, despite being after the oldest blob
+    /// slot.
+    MissingBlobs {
+        block_root: Hash256,
+        slot: Slot,
+        finalized: bool,
+    },
+}
+
+impl Anomaly {
+    /// Returns `true` if `--repair` is able to fix the anomaly.
+    pub fn is_repairable(&self) -> bool {
+        match self {
+            Anomaly::DanglingState { .. } => true,
+            Anomaly::MissingBlobs { finalized, .. } => *finalized,
+            Anomaly::SplitStateMissing { .. }
+            | Anomaly::SplitSlotMismatch { .. }
+            | Anomaly::SplitBlockMissing { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::SplitStateMissing { state_root, slot } => {
+                write!(f, "split state {state_root:?} at slot {slot} is missing")
+            }
+            Anomaly::SplitSlotMismatch {
+                state_root,
+                split_slot,
+                state_slot,
+            } => write!(
+                f,
+                "split state {state_root:?} has slot {state_slot} but the split is at slot \
+                 {split_slot}"
+            ),
+            Anomaly::SplitBlockMissing { block_root } => {
+                write!(f, "split block {block_root:?} is missing")
+            }
+            Anomaly::DanglingState {
+                state_root,
+                slot,
+                reason,
+            } => write!(f, "dangling state {state_root:?} at slot {slot}: {reason}"),
+            Anomaly::MissingBlobs {
+                block_root,
+                slot,
+                finalized,
+            } => write!(
+                f,
+                "{} block {block_root:?} at slot {slot} is missing its blobs",
+                if *finalized {
+                    "finalized"
+                } else {
+                    "unfinalized"
+                }
+            ),
+        }
+    }
+}
+
+/// Check the database for inconsistencies, and repair those which can be repaired if `repair`
+/// is set.
+///
+/// Returns an error if any inconsistencies remain.
+pub fn verify_db<E: EthSpec>(
+    repair: bool,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = Store::<E>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let anomalies = find_anomalies(&db).map_err(|e| format!("Error reading database: {e:?}"))?;
+
+    for anomaly in &anomalies {
+        warn!(
+            log,
+            "Database inconsistency";
+            "anomaly" => %anomaly,
+            "repairable" => anomaly.is_repairable(),
+        );
+    }
+
+    let remaining = if repair {
+        repair_anomalies(&db, &anomalies, &log)
+            .map_err(|e| format!("Error repairing database: {e:?}"))?;
+        anomalies.iter().filter(|a| !a.is_repairable()).count()
+    } else {
+        anomalies.len()
+    };
+
+    if remaining == 0 {
+        info!(
+            log,
+            "Database verified";
+            "inconsistencies_found" => anomalies.len(),
+            "repaired" => repair,
+        );
+        Ok(())
+    } else {
+        if !repair && anomalies.iter().any(Anomaly::is_repairable) {
+            info!(
+                log,
+                "Re-run this command with --repair to fix repairable inconsistencies"
+            );
+        }
+        Err(format!(
+            "Database has {remaining} unrepaired inconsistencies"
+        ))
+    }
+}
+
+/// Check the split, the hot states and the blobs of the database.
+pub fn find_anomalies<E: EthSpec>(db: &Store<E>) -> Result<Vec<Anomaly>, store::Error> {
+    let mut anomalies = vec![];
+    let split = db.get_split_info();
+
+    // The split state and block must be stored in the hot database.
+    if !split.state_root.is_zero() {
+        match db.load_hot_state_summary(&split.state_root)? {
+            None => anomalies.push(Anomaly::SplitStateMissing {
+                state_root: split.state_root,
+                slot: split.slot,
+            }),
+            Some(summary) if summary.slot != split.slot => {
+                anomalies.push(Anomaly::SplitSlotMismatch {
+                    state_root: split.state_root,
+                    split_slot: split.slot,
+                    state_slot: summary.slot,
+                })
+            }
+            Some(_) => (),
+        }
+    }
+    if !split.block_root.is_zero() && !db.block_exists(&split.block_root)? {
+        anomalies.push(Anomaly::SplitBlockMissing {
+            block_root: split.block_root,
+        });
+    }
+
+    // Hot states prior to the split should have been pruned by the migration which advanced it.
+    let mut hot_block_roots = HashSet::new();
+    for res in db
+        .hot_db
+        .iter_column::<Hash256>(DBColumn::BeaconStateSummary)
+    {
+        let (state_root, summary_bytes) = res?;
+        let summary = HotStateSummary::from_ssz_bytes(&summary_bytes)?;
+
+        let reason = if summary.slot < split.slot {
+            Some("older than the split")
+        } else if summary.slot == split.slot
+            && state_root != split.state_root
+            && !split.state_root.is_zero()
+        {
+            Some("conflicts with the split")
+        } else if !db.block_exists(&summary.latest_block_root)? {
+            Some("block is missing")
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            anomalies.push(Anomaly::DanglingState {
+                state_root,
+                slot: summary.slot,
+                reason,
+            });
+        } else if summary.slot > split.slot {
+            hot_block_roots.insert(summary.latest_block_root);
+        }
+    }
+
+    // Blocks after the oldest blob slot must have their blobs stored.
+    if let Some(oldest_blob_slot) = db.get_blob_info().oldest_blob_slot {
+        let mut block_root = split.block_root;
+        while let Some(block) = db.get_blinded_block(&block_root)? {
+            if block.slot() < oldest_blob_slot {
+                break;
+            }
+            if has_missing_blobs(db, &block_root, &block)? {
+                anomalies.push(Anomaly::MissingBlobs {
+                    block_root,
+                    slot: block.slot(),
+                    finalized: true,
+                });
+            }
+            if block.slot() == 0 {
+                break;
+            }
+            block_root = block.parent_root();
+        }
+
+        for block_root in hot_block_roots {
+            let Some(block) = db.get_blinded_block(&block_root)? else {
+                continue;
+            };
+            if block.slot() > split.slot && has_missing_blobs(db, &block_root, &block)? {
+                anomalies.push(Anomaly::MissingBlobs {
+                    block_root,
+                    slot: block.slot(),
+                    finalized: false,
+                });
+            }
+        }
+    }
+
+    Ok(anomalies)
+}
+
+fn has_missing_blobs<E: EthSpec>(
+    db: &Store<E>,
+    block_root: &Hash256,
+    block: &types::SignedBlindedBeaconBlock<E>,
+) -> Result<bool, store::Error> {
+    let commits_to_blobs = block
+        .message()
+        .body()
+        .blob_kzg_commitments()
+        .map_or(false, |commitments| !commitments.is_empty());
+    Ok(commits_to_blobs && !db.blobs_exist(block_root)?)
+}
+
+/// Repair the repairable `anomalies`.
+fn repair_anomalies<E: EthSpec>(
+    db: &Store<E>,
+    anomalies: &[Anomaly],
+    log: &Logger,
+) -> Result<(), store::Error> {
+    let delete_ops = anomalies
+        .iter()
+        .filter_map(|anomaly| match anomaly {
+            Anomaly::DanglingState {
+                state_root, slot, ..
+            } => Some(StoreOp::DeleteState(*state_root, Some(*slot))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !delete_ops.is_empty() {
+        let num_deleted = delete_ops.len();
+        db.do_atomically_with_block_and_blobs_cache(delete_ops)?;
+        info!(log, "Deleted dangling states"; "count" => num_deleted);
+    }
+
+    // Blobs can't be recovered without the network, so advance the oldest blob slot past the
+    // finalized blocks missing blobs. They will not be served, and can be backfilled later.
+    let latest_missing_blobs_slot = anomalies
+        .iter()
+        .filter_map(|anomaly| match anomaly {
+            Anomaly::MissingBlobs {
+                slot,
+                finalized: true,
+                ..
+            } => Some(*slot),
+            _ => None,
+        })
+        .max();
+    if let Some(slot) = latest_missing_blobs_slot {
+        let blob_info = db.get_blob_info();
+        let oldest_blob_slot = slot + 1;
+        db.compare_and_set_blob_info_with_write(
+            blob_info.clone(),
+            BlobInfo {
+                oldest_blob_slot: Some(oldest_blob_slot),
+                ..blob_info
+            },
+        )?;
+        info!(
+            log,
+            "Advanced oldest blob slot past missing blobs";
+            "oldest_blob_slot" => oldest_blob_slot,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
+    };
+    use environment::{Environment, EnvironmentBuilder};
+    use ssz::Encode;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    /// Build a finalized chain in a database in `dir`.
+    fn build_chain(dir: &TempDir, env: &Environment<E>) -> BeaconChainHarness<DiskHarnessType<E>> {
+        let mut config = ClientConfig::default();
+        config.data_dir = dir.path().to_path_buf();
+        let context = env.core_context();
+        let store = HotColdDB::open(
+            &config.create_db_path().unwrap(),
+            &config.create_freezer_db_path().unwrap(),
+            &config.create_blobs_db_path().unwrap(),
+            |_, _, _| Ok(()),
+            config.store.clone(),
+            env.eth2_config.spec.clone(),
+            context.log().clone(),
+        )
+        .unwrap();
+        let harness = BeaconChainHarness::builder(MinimalEthSpec)
+            .spec(env.eth2_config.spec.clone())
+            .deterministic_keypairs(32)
+            .logger(context.log().clone())
+            .fresh_disk_store(Arc::new(store))
+            .build();
+        harness.advance_slot();
+        context
+            .executor
+            .block_on_dangerous(
+                harness.extend_chain(
+                    5 * E::slots_per_epoch() as usize,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                ),
+                "build_chain",
+            )
+            .unwrap();
+        assert!(harness.finalized_checkpoint().epoch > 0);
+        harness
+    }
+
+    /// Store a copy of the summary of the split state under `state_root`, modified by `f`.
+    fn put_summary(db: &Store<E>, state_root: Hash256, f: impl FnOnce(&mut HotStateSummary)) {
+        let split = db.get_split_info();
+        let mut summary = db
+            .load_hot_state_summary(&split.state_root)
+            .unwrap()
+            .unwrap();
+        f(&mut summary);
+        db.hot_db
+            .put_bytes(
+                DBColumn::BeaconStateSummary.into(),
+                state_root.as_bytes(),
+                &summary.as_ssz_bytes(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn dangling_states_are_found_and_repaired() {
+        let env = EnvironmentBuilder::minimal()
+            .null_logger()
+            .unwrap()
+            .multi_threaded_tokio_runtime()
+            .unwrap()
+            .build()
+            .unwrap();
+        let dir = TempDir::new().unwrap();
+        let harness = build_chain(&dir, &env);
+        let db = &harness.chain.store;
+        let log = env.core_context().log().clone();
+
+        assert_eq!(find_anomalies(db).unwrap(), vec![]);
+
+        let split = db.get_split_info();
+        let stale_root = Hash256::repeat_byte(1);
+        let orphan_root = Hash256::repeat_byte(2);
+        put_summary(db, stale_root, |summary| summary.slot = split.slot - 1);
+        put_summary(db, orphan_root, |summary| {
+            summary.slot = split.slot + 1;
+            summary.latest_block_root = Hash256::repeat_byte(0xff);
+        });
+
+        let mut anomalies = find_anomalies(db).unwrap();
+        anomalies.sort_by_key(|anomaly| match anomaly {
+            Anomaly::DanglingState { state_root, .. } => *state_root,
+            _ => Hash256::zero(),
+        });
+        assert_eq!(
+            anomalies,
+            vec![
+                Anomaly::DanglingState {
+                    state_root: stale_root,
+                    slot: split.slot - 1,
+                    reason: "older than the split",
+                },
+                Anomaly::DanglingState {
+                    state_root: orphan_root,
+                    slot: split.slot + 1,
+                    reason: "block is missing",
+                },
+            ]
+        );
+        assert!(anomalies.iter().all(Anomaly::is_repairable));
+
+        repair_anomalies(db, &anomalies, &log).unwrap();
+        assert_eq!(find_anomalies(db).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn split_anomalies_are_not_repairable() {
+        let state_root = Hash256::repeat_byte(1);
+        let slot = Slot::new(8);
+        assert!(!Anomaly::SplitStateMissing { state_root, slot }.is_repairable());
+        assert!(!Anomaly::SplitBlockMissing {
+            block_root: state_root
+        }
+        .is_repairable());
+        assert!(!Anomaly::MissingBlobs {
+            block_root: state_root,
+            slot,
+            finalized: false,
+        }
+        .is_repairable());
+        assert!(Anomaly::MissingBlobs {
+            block_root: state_root,
+            slot,
+            finalized: true,
+        }
+        .is_repairable());
+    }
+}
@@ -0,0 +1,399 @@
+//! # Craft Attestation
+//!
+//! Use this tool to produce a signed attestation against a `BeaconState`, optionally making it
+//! invalid in a specific way. Useful for building test vectors and simulating attacks.
+//!
+//! Keys are either the interop keys (`--interop-keys`) or hex-encoded secret keys, one per line
+//! (`--secret-keys-path`). Committee members without a key are not included.
+//!
+//! ## Example
+//!
+//! Produce an attestation for committee 0 at slot 65 which votes for an unknown target:
+//!
+//! ```ignore
+//! lcli craft-attestation \
+//!     --pre-state-path /tmp/state.ssz \
+//!     --slot 65 \
+//!     --committee-index 0 \
+//!     --interop-keys \
+//!     --invalid wrong-target \
+//!     --output-path /tmp/attestation.ssz
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use serde::Serialize;
+use ssz::Encode;
+use state_processing::state_advance::complete_state_advance;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use types::{
+    test_utils::generate_deterministic_keypair, Attestation, BeaconState, ChainSpec, Checkpoint,
+    EthSpec, Hash256, PublicKeyBytes, RelativeEpoch, SecretKey, Slot,
+};
+
+/// The ways in which a crafted attestation can be made invalid.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum InvalidAttestation {
+    /// Signed by a random key instead of the committee members.
+    BadSignature,
+    /// Votes for a source which is not the current justified checkpoint.
+    WrongSource,
+    /// Votes for a target root which is not in the chain (`0xff..ff`).
+    WrongTarget,
+    /// Votes for a head block which is not in the chain (`0xff..ff`).
+    UnknownHead,
+    /// Has no aggregation bits set.
+    EmptyAggregationBits,
+}
+
+impl FromStr for InvalidAttestation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bad-signature" => Ok(Self::BadSignature),
+            "wrong-source" => Ok(Self::WrongSource),
+            "wrong-target" => Ok(Self::WrongTarget),
+            "unknown-head" => Ok(Self::UnknownHead),
+            "empty-aggregation-bits" => Ok(Self::EmptyAggregationBits),
+            _ => Err(format!("Invalid kind of attestation \"{}\"", s)),
+        }
+    }
+}
+
+pub enum OutputFormat {
+    Ssz,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ssz" => Ok(Self::Ssz),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Invalid output format \"{}\"", s)),
+        }
+    }
+}
+
+/// The secret keys used to sign crafted objects.
+pub enum SigningKeys {
+    /// The deterministic interop key of every validator.
+    Interop,
+    /// Secret keys indexed by validator index.
+    Secrets(HashMap<usize, SecretKey>),
+}
+
+impl SigningKeys {
+    /// Reads the keys given by `--interop-keys` or `--secret-keys-path`, finding the index of
+    /// each secret key in `state`.
+    pub fn from_matches<E: EthSpec>(
+        matches: &ArgMatches,
+        state: &mut BeaconState<E>,
+    ) -> Result<Self, String> {
+        if matches.get_flag("interop-keys") {
+            return Ok(Self::Interop);
+        }
+
+        let path: PathBuf = parse_optional(matches, "secret-keys-path")?
+            .ok_or("must supply either --interop-keys or --secret-keys-path")?;
+        let contents =
+            fs::read_to_string(&path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+
+        let mut secrets = HashMap::new();
+        for line in contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            let bytes = hex::decode(line.strip_prefix("0x").unwrap_or(line))
+                .map_err(|e| format!("Invalid secret key hex: {:?}", e))?;
+            let secret_key = SecretKey::deserialize(&bytes)
+                .map_err(|e| format!("Invalid secret key: {:?}", e))?;
+            let pubkey = PublicKeyBytes::from(secret_key.public_key());
+            let index = state
+                .get_validator_index(&pubkey)
+                .map_err(|e| format!("Unable to build pubkey cache: {:?}", e))?
+                .ok_or_else(|| format!("Unknown validator {:?}", pubkey))?;
+            secrets.insert(index, secret_key);
+        }
+        Ok(Self::Secrets(secrets))
+    }
+
+    pub fn secret_key(&self, validator_index: usize) -> Option<SecretKey> {
+        match self {
+            Self::Interop => Some(generate_deterministic_keypair(validator_index).sk),
+            Self::Secrets(secrets) => secrets.get(&validator_index).cloned(),
+        }
+    }
+}
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<E>()?;
+
+    let pre_state_path: PathBuf = parse_required(matches, "pre-state-path")?;
+    let slot: Slot = parse_required(matches, "slot")?;
+    let committee_index: u64 = parse_required(matches, "committee-index")?;
+    let beacon_block_root: Option<Hash256> = parse_optional(matches, "beacon-block-root")?;
+    let invalid: Option<InvalidAttestation> = parse_optional(matches, "invalid")?;
+    let output_path: PathBuf = parse_required(matches, "output-path")?;
+    let format: OutputFormat = parse_required(matches, "format")?;
+
+    let mut state: BeaconState<E> =
+        load_from_ssz_with(&pre_state_path, spec, BeaconState::from_ssz_bytes)?;
+    let keys = SigningKeys::from_matches(matches, &mut state)?;
+
+    if slot > state.slot() {
+        info!("Advancing state from slot {} to {}", state.slot(), slot);
+        complete_state_advance(&mut state, None, slot, spec)
+            .map_err(|e| format!("Unable to advance state: {:?}", e))?;
+    }
+    state
+        .build_committee_cache(RelativeEpoch::Current, spec)
+        .map_err(|e| format!("Unable to build committee cache: {:?}", e))?;
+    let head_root = latest_block_root(&mut state)?;
+
+    let attestation = craft_attestation(
+        &state,
+        slot,
+        committee_index,
+        head_root,
+        beacon_block_root,
+        &keys,
+        invalid,
+        spec,
+    )?;
+
+    info!(
+        "Crafted attestation for slot {} with {} attesters",
+        slot,
+        attestation.num_set_aggregation_bits()
+    );
+    write_output(&attestation, &output_path, format)
+}
+
+/// Returns an attestation to `head_root` by every member of the committee who has a key.
+pub(crate) fn craft_attestation<E: EthSpec>(
+    state: &BeaconState<E>,
+    slot: Slot,
+    committee_index: u64,
+    head_root: Hash256,
+    beacon_block_root: Option<Hash256>,
+    keys: &SigningKeys,
+    invalid: Option<InvalidAttestation>,
+    spec: &ChainSpec,
+) -> Result<Attestation<E>, String> {
+    let epoch = slot.epoch(E::slots_per_epoch());
+    if epoch != state.current_epoch() {
+        return Err(format!(
+            "Slot {} is not in the current epoch of the state ({})",
+            slot,
+            state.current_epoch()
+        ));
+    }
+
+    let committee = state
+        .get_beacon_committee(slot, committee_index)
+        .map_err(|e| format!("Unable to get committee: {:?}", e))?;
+
+    let mut beacon_block_root = beacon_block_root.unwrap_or(head_root);
+    let target_root = if epoch.start_slot(E::slots_per_epoch()) == state.slot() {
+        head_root
+    } else {
+        *state
+            .get_block_root_at_epoch(epoch)
+            .map_err(|e| format!("Unable to get target root: {:?}", e))?
+    };
+    let mut source = state.current_justified_checkpoint();
+    let mut target = Checkpoint {
+        epoch,
+        root: target_root,
+    };
+
+    match invalid {
+        Some(InvalidAttestation::WrongSource) => source.epoch += 1,
+        Some(InvalidAttestation::WrongTarget) => target.root = Hash256::repeat_byte(0xff),
+        Some(InvalidAttestation::UnknownHead) => beacon_block_root = Hash256::repeat_byte(0xff),
+        _ => (),
+    }
+
+    let mut attestation = Attestation::empty_for_signing(
+        committee_index,
+        committee.committee.len(),
+        slot,
+        beacon_block_root,
+        source,
+        target,
+        spec,
+    )
+    .map_err(|e| format!("Unable to create attestation: {:?}", e))?;
+
+    if invalid == Some(InvalidAttestation::EmptyAggregationBits) {
+        return Ok(attestation);
+    }
+
+    for (committee_position, &validator_index) in committee.committee.iter().enumerate() {
+        let Some(secret_key) = keys.secret_key(validator_index) else {
+            continue;
+        };
+        let secret_key = if invalid == Some(InvalidAttestation::BadSignature) {
+            SecretKey::random()
+        } else {
+            secret_key
+        };
+        attestation
+            .sign(
+                &secret_key,
+                committee_position,
+                &state.fork(),
+                state.genesis_validators_root(),
+                spec,
+            )
+            .map_err(|e| format!("Unable to sign attestation: {:?}", e))?;
+    }
+
+    Ok(attestation)
+}
+
+/// Returns the root of the most recent block applied to `state`.
+pub fn latest_block_root<E: EthSpec>(state: &mut BeaconState<E>) -> Result<Hash256, String> {
+    let mut header = state.latest_block_header().clone();
+    // The state root is only filled in by the slot processing which follows the block.
+    if header.state_root.is_zero() {
+        header.state_root = state
+            .canonical_root()
+            .map_err(|e| format!("Unable to compute state root: {:?}", e))?;
+    }
+    Ok(header.canonical_root())
+}
+
+pub fn write_output<T: Encode + Serialize>(
+    value: &T,
+    path: &Path,
+    format: OutputFormat,
+) -> Result<(), String> {
+    let bytes = match format {
+        OutputFormat::Ssz => value.as_ssz_bytes(),
+        OutputFormat::Json => serde_json::to_vec_pretty(value)
+            .map_err(|e| format!("Unable to serialize to JSON: {:?}", e))?,
+    };
+
+    let mut file =
+        File::create(path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+    file.write_all(&bytes)
+        .map_err(|e| format!("Unable to write to {:?}: {:?}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use state_processing::per_block_processing::verify_attestation_for_block_inclusion;
+    use state_processing::{ConsensusContext, VerifySignatures};
+    use types::{test_utils::generate_deterministic_keypairs, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+
+    fn genesis_state(spec: &ChainSpec) -> BeaconState<E> {
+        let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT);
+        let mut state =
+            genesis::interop_genesis_state(&keypairs, 0, Hash256::repeat_byte(0x42), None, spec)
+                .unwrap();
+        state
+            .build_committee_cache(RelativeEpoch::Current, spec)
+            .unwrap();
+        state
+    }
+
+    /// Crafts an attestation at the genesis slot and verifies it for inclusion in the next slot.
+    fn craft_and_verify(
+        invalid: Option<InvalidAttestation>,
+    ) -> (Attestation<E>, Result<(), String>) {
+        let spec = E::default_spec();
+        let mut state = genesis_state(&spec);
+        let head_root = latest_block_root(&mut state).unwrap();
+        let attestation = craft_attestation(
+            &state,
+            Slot::new(0),
+            0,
+            head_root,
+            None,
+            &SigningKeys::Interop,
+            invalid,
+            &spec,
+        )
+        .unwrap();
+
+        complete_state_advance(&mut state, None, Slot::new(1), &spec).unwrap();
+        state.build_caches(&spec).unwrap();
+        let result = verify_attestation_for_block_inclusion(
+            &state,
+            attestation.to_ref(),
+            &mut ConsensusContext::new(state.slot()),
+            VerifySignatures::True,
+            &spec,
+        )
+        .map(|_| ())
+        .map_err(|e| format!("{:?}", e));
+        (attestation, result)
+    }
+
+    #[test]
+    fn valid_attestation() {
+        let (attestation, result) = craft_and_verify(None);
+        result.unwrap();
+        assert!(attestation.num_set_aggregation_bits() > 0);
+    }
+
+    #[test]
+    fn invalid_attestations() {
+        assert!(craft_and_verify(Some(InvalidAttestation::BadSignature))
+            .1
+            .is_err());
+        assert!(craft_and_verify(Some(InvalidAttestation::WrongSource))
+            .1
+            .is_err());
+
+        let (attestation, _) = craft_and_verify(Some(InvalidAttestation::WrongTarget));
+        assert_eq!(attestation.data().target.root, Hash256::repeat_byte(0xff));
+
+        let (attestation, _) = craft_and_verify(Some(InvalidAttestation::UnknownHead));
+        assert_eq!(
+            attestation.data().beacon_block_root,
+            Hash256::repeat_byte(0xff)
+        );
+
+        let (attestation, _) = craft_and_verify(Some(InvalidAttestation::EmptyAggregationBits));
+        assert_eq!(attestation.num_set_aggregation_bits(), 0);
+    }
+
+    #[test]
+    fn slot_outside_current_epoch() {
+        let spec = E::default_spec();
+        let mut state = genesis_state(&spec);
+        let head_root = latest_block_root(&mut state).unwrap();
+        assert!(craft_attestation(
+            &state,
+            Slot::new(E::slots_per_epoch()),
+            0,
+            head_root,
+            None,
+            &SigningKeys::Interop,
+            None,
+            &spec,
+        )
+        .is_err());
+    }
+}
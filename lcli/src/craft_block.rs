@@ -0,0 +1,458 @@
+//! # Craft Block
+//!
+//! Use this tool to produce a signed block on top of a `BeaconState`, optionally making it
+//! invalid in a specific way. Useful for building test vectors and simulating attacks.
+//!
+//! The execution payload only contains the fields which are checked by consensus, so the block
+//! can be applied to the state without an execution layer but will not be accepted by one.
+//!
+//! ## Example
+//!
+//! Produce a block at slot 66 containing an attestation from `lcli craft-attestation`, with a
+//! state root which does not match the post-state:
+//!
+//! ```ignore
+//! lcli craft-block \
+//!     --pre-state-path /tmp/state.ssz \
+//!     --slot 66 \
+//!     --attestation-path /tmp/attestation.ssz \
+//!     --interop-keys \
+//!     --invalid wrong-state-root \
+//!     --output-path /tmp/block.ssz \
+//!     --post-state-output-path /tmp/post-state.ssz
+//! ```
+use crate::craft_attestation::{latest_block_root, write_output, OutputFormat, SigningKeys};
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use ssz::Decode;
+use state_processing::per_block_processing::{
+    compute_timestamp_at_slot, get_expected_withdrawals, is_merge_transition_complete,
+};
+use state_processing::state_advance::complete_state_advance;
+use state_processing::{
+    per_block_processing, BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use types::beacon_block::EmptyBlock;
+use types::{
+    map_fork_name, map_fork_name_with, BeaconBlock, BeaconBlockBodyRefMut, BeaconState, ChainSpec,
+    Domain, Epoch, EthSpec, ExecutionBlockHash, ForkName, Graffiti, GraffitiString, Hash256,
+    SecretKey, Signature, SignedBeaconBlock, SignedRoot, Slot, Unsigned, VariableList,
+};
+
+/// The ways in which a crafted block can be made invalid.
+#[derive(Clone, Copy, PartialEq)]
+enum InvalidBlock {
+    /// Signed by a random key instead of the proposer.
+    BadSignature,
+    /// Claims to be proposed by the validator after the proposer.
+    WrongProposer,
+    /// Builds on a parent which is not in the chain (`0xff..ff`).
+    WrongParentRoot,
+    /// Commits to a state root which does not match the post-state (`0xff..ff`).
+    WrongStateRoot,
+    /// Has a RANDAO reveal for the wrong epoch.
+    BadRandao,
+}
+
+impl FromStr for InvalidBlock {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bad-signature" => Ok(Self::BadSignature),
+            "wrong-proposer" => Ok(Self::WrongProposer),
+            "wrong-parent-root" => Ok(Self::WrongParentRoot),
+            "wrong-state-root" => Ok(Self::WrongStateRoot),
+            "bad-randao" => Ok(Self::BadRandao),
+            _ => Err(format!("Invalid kind of block \"{}\"", s)),
+        }
+    }
+}
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<E>()?;
+
+    let pre_state_path: PathBuf = parse_required(matches, "pre-state-path")?;
+    let slot: Slot = parse_required(matches, "slot")?;
+    let graffiti: Option<GraffitiString> = parse_optional(matches, "graffiti")?;
+    let invalid: Option<InvalidBlock> = parse_optional(matches, "invalid")?;
+    let output_path: PathBuf = parse_required(matches, "output-path")?;
+    let post_state_output_path: Option<PathBuf> =
+        parse_optional(matches, "post-state-output-path")?;
+    let format: OutputFormat = parse_required(matches, "format")?;
+    let attestation_paths = matches
+        .get_many::<String>("attestation-path")
+        .into_iter()
+        .flatten()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+
+    let mut state: BeaconState<E> =
+        load_from_ssz_with(&pre_state_path, spec, BeaconState::from_ssz_bytes)?;
+    let keys = SigningKeys::from_matches(matches, &mut state)?;
+
+    let (signed_block, post_state) = craft_block(
+        state,
+        slot,
+        graffiti,
+        &attestation_paths,
+        &keys,
+        invalid,
+        spec,
+    )?;
+
+    info!(
+        "Crafted {} block at slot {} with root {:?}",
+        signed_block.fork_name_unchecked(),
+        slot,
+        signed_block.canonical_root()
+    );
+    write_output(&signed_block, &output_path, format)?;
+
+    if let Some(path) = post_state_output_path {
+        write_output(&post_state, &path, OutputFormat::Ssz)?;
+    }
+
+    Ok(())
+}
+
+/// Returns a block at `slot` built on the latest block of `state`, along with the post-state of
+/// the valid version of the block.
+fn craft_block<E: EthSpec>(
+    mut state: BeaconState<E>,
+    slot: Slot,
+    graffiti: Option<GraffitiString>,
+    attestation_paths: &[PathBuf],
+    keys: &SigningKeys,
+    invalid: Option<InvalidBlock>,
+    spec: &ChainSpec,
+) -> Result<(SignedBeaconBlock<E>, BeaconState<E>), String> {
+    if slot <= state.slot() {
+        return Err(format!(
+            "Slot {} must be later than the state's slot {}",
+            slot,
+            state.slot()
+        ));
+    }
+    let parent_root = latest_block_root(&mut state)?;
+    info!("Advancing state from slot {} to {}", state.slot(), slot);
+    complete_state_advance(&mut state, None, slot, spec)
+        .map_err(|e| format!("Unable to advance state: {:?}", e))?;
+    state
+        .build_caches(spec)
+        .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+
+    let proposer_index = state
+        .get_beacon_proposer_index(slot, spec)
+        .map_err(|e| format!("Unable to compute proposer: {:?}", e))?;
+    let proposer_key = keys
+        .secret_key(proposer_index)
+        .ok_or_else(|| format!("No key for proposer {}", proposer_index))?;
+
+    let fork_name = spec.fork_name_at_slot::<E>(slot);
+    let mut block: BeaconBlock<E> = map_fork_name!(fork_name, BeaconBlock, EmptyBlock::empty(spec));
+    *block.slot_mut() = slot;
+    *block.proposer_index_mut() = proposer_index as u64;
+    *block.parent_root_mut() = parent_root;
+
+    let randao_epoch = if invalid == Some(InvalidBlock::BadRandao) {
+        state.current_epoch() + 1
+    } else {
+        state.current_epoch()
+    };
+    *block.body_mut().randao_reveal_mut() =
+        randao_reveal(&state, randao_epoch, &proposer_key, spec);
+    *block.body_mut().eth1_data_mut() = state.eth1_data().clone();
+    if let Some(graffiti) = graffiti {
+        *block.body_mut().graffiti_mut() = Graffiti::from(graffiti);
+    }
+
+    for path in attestation_paths {
+        add_attestation(&mut block, path)?;
+    }
+    set_execution_payload(&mut block, &state, spec)?;
+
+    // Apply the valid block to find the post-state, before making any of it invalid.
+    let mut post_state = state.clone();
+    let unsigned = block.clone().sign(
+        &proposer_key,
+        &state.fork(),
+        state.genesis_validators_root(),
+        spec,
+    );
+    per_block_processing(
+        &mut post_state,
+        &unsigned,
+        BlockSignatureStrategy::NoVerification,
+        VerifyBlockRoot::True,
+        &mut ConsensusContext::new(slot),
+        spec,
+    )
+    .map_err(|e| format!("Unable to process block: {:?}", e))?;
+    *block.state_root_mut() = post_state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Unable to compute state root: {:?}", e))?;
+
+    let signing_key = match invalid {
+        Some(InvalidBlock::BadSignature) => SecretKey::random(),
+        Some(InvalidBlock::WrongProposer) => {
+            *block.proposer_index_mut() =
+                (proposer_index as u64 + 1) % state.validators().len() as u64;
+            proposer_key
+        }
+        Some(InvalidBlock::WrongParentRoot) => {
+            *block.parent_root_mut() = Hash256::repeat_byte(0xff);
+            proposer_key
+        }
+        Some(InvalidBlock::WrongStateRoot) => {
+            *block.state_root_mut() = Hash256::repeat_byte(0xff);
+            proposer_key
+        }
+        Some(InvalidBlock::BadRandao) | None => proposer_key,
+    };
+    let signed_block = block.sign(
+        &signing_key,
+        &state.fork(),
+        state.genesis_validators_root(),
+        spec,
+    );
+
+    Ok((signed_block, post_state))
+}
+
+fn randao_reveal<E: EthSpec>(
+    state: &BeaconState<E>,
+    epoch: Epoch,
+    secret_key: &SecretKey,
+    spec: &ChainSpec,
+) -> Signature {
+    let domain = spec.get_domain(
+        epoch,
+        Domain::Randao,
+        &state.fork(),
+        state.genesis_validators_root(),
+    );
+    secret_key.sign(epoch.signing_root(domain))
+}
+
+/// Adds the SSZ-encoded attestation at `path` to `block`.
+fn add_attestation<E: EthSpec>(block: &mut BeaconBlock<E>, path: &Path) -> Result<(), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+    match block.body_mut() {
+        BeaconBlockBodyRefMut::Base(body) => push_attestation(&mut body.attestations, &bytes),
+        BeaconBlockBodyRefMut::Altair(body) => push_attestation(&mut body.attestations, &bytes),
+        BeaconBlockBodyRefMut::Bellatrix(body) => push_attestation(&mut body.attestations, &bytes),
+        BeaconBlockBodyRefMut::Capella(body) => push_attestation(&mut body.attestations, &bytes),
+        BeaconBlockBodyRefMut::Deneb(body) => push_attestation(&mut body.attestations, &bytes),
+        BeaconBlockBodyRefMut::Electra(body) => push_attestation(&mut body.attestations, &bytes),
+    }
+    .map_err(|e| format!("Unable to add attestation from {:?}: {}", path, e))
+}
+
+fn push_attestation<T: Decode, N: Unsigned>(
+    attestations: &mut VariableList<T, N>,
+    bytes: &[u8],
+) -> Result<(), String> {
+    let attestation = T::from_ssz_bytes(bytes).map_err(|e| format!("{:?}", e))?;
+    attestations
+        .push(attestation)
+        .map_err(|e| format!("{:?}", e))
+}
+
+/// Fills in the fields of the execution payload which are checked by consensus.
+fn set_execution_payload<E: EthSpec>(
+    block: &mut BeaconBlock<E>,
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let fork_name = state.fork_name_unchecked();
+    // A Bellatrix block before the merge has an empty payload.
+    if !fork_name.bellatrix_enabled() || !is_merge_transition_complete(state) {
+        return Ok(());
+    }
+
+    let slot = block.slot();
+    let parent_hash = state
+        .latest_execution_payload_header()
+        .map_err(|e| format!("Unable to get execution payload header: {:?}", e))?
+        .block_hash();
+    let prev_randao = *state
+        .get_randao_mix(state.current_epoch())
+        .map_err(|e| format!("Unable to get RANDAO mix: {:?}", e))?;
+    let timestamp = compute_timestamp_at_slot(state, slot, spec)
+        .map_err(|e| format!("Unable to compute timestamp: {:?}", e))?;
+    // Each crafted payload has a distinct, made up hash which its children build upon.
+    let block_hash = ExecutionBlockHash::from_root(Hash256::from_low_u64_be(slot.as_u64()));
+    let withdrawals = if fork_name.capella_enabled() {
+        get_expected_withdrawals(state, spec)
+            .map_err(|e| format!("Unable to compute withdrawals: {:?}", e))?
+            .0
+    } else {
+        <_>::default()
+    };
+
+    macro_rules! set_payload_fields {
+        ($payload:expr) => {
+            $payload.parent_hash = parent_hash;
+            $payload.prev_randao = prev_randao;
+            $payload.timestamp = timestamp;
+            $payload.block_hash = block_hash;
+        };
+    }
+
+    match block.body_mut() {
+        BeaconBlockBodyRefMut::Base(_) | BeaconBlockBodyRefMut::Altair(_) => {}
+        BeaconBlockBodyRefMut::Bellatrix(body) => {
+            set_payload_fields!(body.execution_payload.execution_payload);
+        }
+        BeaconBlockBodyRefMut::Capella(body) => {
+            set_payload_fields!(body.execution_payload.execution_payload);
+            body.execution_payload.execution_payload.withdrawals = withdrawals;
+        }
+        BeaconBlockBodyRefMut::Deneb(body) => {
+            set_payload_fields!(body.execution_payload.execution_payload);
+            body.execution_payload.execution_payload.withdrawals = withdrawals;
+        }
+        BeaconBlockBodyRefMut::Electra(body) => {
+            set_payload_fields!(body.execution_payload.execution_payload);
+            body.execution_payload.execution_payload.withdrawals = withdrawals;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::craft_attestation::craft_attestation;
+    use types::{test_utils::generate_deterministic_keypairs, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+
+    fn genesis_state(spec: &ChainSpec) -> BeaconState<E> {
+        let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT);
+        genesis::interop_genesis_state(&keypairs, 0, Hash256::repeat_byte(0x42), None, spec)
+            .unwrap()
+    }
+
+    /// Crafts a block at slot 1 and applies it to the genesis state with full verification,
+    /// returning the post-state root.
+    fn craft_and_apply(
+        invalid: Option<InvalidBlock>,
+        attestation_paths: &[PathBuf],
+    ) -> (SignedBeaconBlock<E>, Result<Hash256, String>) {
+        let spec = E::default_spec();
+        let state = genesis_state(&spec);
+        let slot = Slot::new(1);
+        let (block, _) = craft_block(
+            state.clone(),
+            slot,
+            None,
+            attestation_paths,
+            &SigningKeys::Interop,
+            invalid,
+            &spec,
+        )
+        .unwrap();
+
+        let mut state = state;
+        complete_state_advance(&mut state, None, slot, &spec).unwrap();
+        let result = per_block_processing(
+            &mut state,
+            &block,
+            BlockSignatureStrategy::VerifyIndividual,
+            VerifyBlockRoot::True,
+            &mut ConsensusContext::new(slot),
+            &spec,
+        )
+        .map_err(|e| format!("{:?}", e))
+        .and_then(|()| {
+            state
+                .update_tree_hash_cache()
+                .map_err(|e| format!("{:?}", e))
+        });
+        (block, result)
+    }
+
+    #[test]
+    fn valid_block() {
+        let (block, post_state_root) = craft_and_apply(None, &[]);
+        assert_eq!(post_state_root.unwrap(), block.state_root());
+    }
+
+    #[test]
+    fn valid_block_with_attestation() {
+        let spec = E::default_spec();
+        let mut state = genesis_state(&spec);
+        state
+            .build_committee_cache(types::RelativeEpoch::Current, &spec)
+            .unwrap();
+        let head_root = latest_block_root(&mut state).unwrap();
+        let attestation = craft_attestation(
+            &state,
+            Slot::new(0),
+            0,
+            head_root,
+            None,
+            &SigningKeys::Interop,
+            None,
+            &spec,
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "lcli-craft-block-attestation-{}.ssz",
+            std::process::id()
+        ));
+        write_output(&attestation, &path, OutputFormat::Ssz).unwrap();
+
+        let (block, post_state_root) = craft_and_apply(None, &[path.clone()]);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(block.message().body().attestations_len(), 1);
+        assert_eq!(post_state_root.unwrap(), block.state_root());
+    }
+
+    #[test]
+    fn invalid_blocks() {
+        for invalid in [
+            InvalidBlock::BadSignature,
+            InvalidBlock::WrongProposer,
+            InvalidBlock::WrongParentRoot,
+            InvalidBlock::BadRandao,
+        ] {
+            assert!(craft_and_apply(Some(invalid), &[]).1.is_err());
+        }
+
+        // The state root is only checked after the block has been applied.
+        let (block, post_state_root) = craft_and_apply(Some(InvalidBlock::WrongStateRoot), &[]);
+        assert_eq!(block.state_root(), Hash256::repeat_byte(0xff));
+        assert_ne!(post_state_root.unwrap(), block.state_root());
+    }
+
+    #[test]
+    fn slot_not_after_state() {
+        let spec = E::default_spec();
+        assert!(craft_block(
+            genesis_state(&spec),
+            Slot::new(0),
+            None,
+            &[],
+            &SigningKeys::Interop,
+            None,
+            &spec,
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use compare_fields::{CompareFields, Comparison, FieldComparison};
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use serde_json::Value;
+use std::path::PathBuf;
+use types::{BeaconState, EthSpec, Hash256, Unsigned, Validator};
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<E>()?;
+
+    let path_a: PathBuf = parse_required(matches, "state-a")?;
+    let path_b: PathBuf = parse_required(matches, "state-b")?;
+    let max_entries: usize = parse_required(matches, "max-entries")?;
+
+    info!(
+        "Using {} network ({} spec)",
+        spec.config_name.as_deref().unwrap_or("unknown"),
+        E::spec_name()
+    );
+
+    let mut state_a: BeaconState<E> =
+        load_from_ssz_with(&path_a, spec, BeaconState::from_ssz_bytes)?;
+    let mut state_b: BeaconState<E> =
+        load_from_ssz_with(&path_b, spec, BeaconState::from_ssz_bytes)?;
+
+    let state_root_a = state_a
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Error computing state root of a: {e:?}"))?;
+    let state_root_b = state_b
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Error computing state root of b: {e:?}"))?;
+
+    println!("state_root (generalized index 1)");
+    println!("  a: {:?}", state_root_a);
+    println!("  b: {:?}", state_root_b);
+
+    if state_root_a == state_root_b {
+        println!("States are identical");
+        return Ok(());
+    }
+
+    let (fork_a, fork_b) = (state_a.fork_name_unchecked(), state_b.fork_name_unchecked());
+    if fork_a != fork_b {
+        return Err(format!(
+            "Unable to compare the fields of a {} state with a {} state",
+            fork_a, fork_b
+        ));
+    }
+
+    let leaves_a = state_a.get_beacon_state_leaves();
+    let leaves_b = state_b.get_beacon_state_leaves();
+    // Caches are compared after the tree hashed fields, which are zipped with the leaves.
+    let comparisons = state_a.compare_fields(&state_b);
+    let num_fields_pow2 = state_a.num_fields_pow2();
+
+    for (field_index, ((leaf_a, leaf_b), comparison)) in
+        leaves_a.iter().zip(&leaves_b).zip(&comparisons).enumerate()
+    {
+        if leaf_a == leaf_b {
+            continue;
+        }
+
+        let generalized_index = (num_fields_pow2 + field_index) as u64;
+        let field_name = match comparison {
+            Comparison::Child(child) => &child.field_name,
+            Comparison::Parent { field_name, .. } => field_name,
+        };
+
+        println!();
+        println!(
+            "{} (field {}, generalized index {})",
+            field_name, field_index, generalized_index
+        );
+        println!("  a: {:?}", leaf_a);
+        println!("  b: {:?}", leaf_b);
+
+        match comparison {
+            Comparison::Child(child) => {
+                println!("  a = {}", child.a);
+                println!("  b = {}", child.b);
+            }
+            Comparison::Parent { children, .. } => {
+                let differing = children
+                    .iter()
+                    .filter(|child| child.not_equal())
+                    .collect::<Vec<_>>();
+                println!("  {} of {} entries differ", differing.len(), children.len());
+
+                for child in differing.iter().take(max_entries) {
+                    if field_name == "validators" {
+                        print_validator_diff(&state_a, &state_b, child, generalized_index)?;
+                    } else {
+                        println!("  [{}]", child.field_name);
+                        println!("    a = {}", child.a);
+                        println!("    b = {}", child.b);
+                    }
+                }
+                if differing.len() > max_entries {
+                    println!("  ... and {} more", differing.len() - max_entries);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the fields which differ between the validators at the index of `child`.
+fn print_validator_diff<E: EthSpec>(
+    state_a: &BeaconState<E>,
+    state_b: &BeaconState<E>,
+    child: &FieldComparison,
+    validators_generalized_index: u64,
+) -> Result<(), String> {
+    let index: usize = child
+        .field_name
+        .parse()
+        .map_err(|e| format!("Invalid validator index {}: {:?}", child.field_name, e))?;
+    let validator_a = state_a.validators().get(index);
+    let validator_b = state_b.validators().get(index);
+
+    // The validators list root is mixed in with its length, so its elements are below the left
+    // child, at the depth of the validator registry limit.
+    let depth = E::ValidatorRegistryLimit::to_u64()
+        .next_power_of_two()
+        .trailing_zeros();
+    let generalized_index = ((validators_generalized_index * 2) << depth) + index as u64;
+
+    println!("  [{}] (generalized index {})", index, generalized_index);
+    println!("    a: {:?}", validator_root(validator_a));
+    println!("    b: {:?}", validator_root(validator_b));
+
+    let (Some(validator_a), Some(validator_b)) = (validator_a, validator_b) else {
+        println!("    a = {}", child.a);
+        println!("    b = {}", child.b);
+        return Ok(());
+    };
+
+    let to_json = |validator: &Validator| {
+        serde_json::to_value(validator)
+            .map_err(|e| format!("Unable to serialize validator {}: {:?}", index, e))
+    };
+    if let (Value::Object(fields_a), Value::Object(fields_b)) =
+        (to_json(validator_a)?, to_json(validator_b)?)
+    {
+        for (name, value_a) in fields_a {
+            let value_b = fields_b.get(&name).unwrap_or(&Value::Null);
+            if value_a != *value_b {
+                println!("    {}: {} -> {}", name, value_a, value_b);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validator_root(validator: Option<&Validator>) -> Option<Hash256> {
+    validator.map(tree_hash::TreeHash::tree_hash_root)
+}
@@ -0,0 +1,453 @@
+//! # Fuzz Transition
+//!
+//! Use this tool to differentially fuzz `state_processing` against a second, reference
+//! implementation of the state transition (e.g. the Python executable spec or another client).
+//!
+//! Starting from a valid `SignedBeaconBlock` and its pre-state, each iteration applies a few
+//! random mutations to the SSZ bytes of the block. Mutated blocks which still decode are applied
+//! to the pre-state by both Lighthouse and the reference implementation, as per the spec's
+//! `state_transition` function. A divergence is recorded whenever one implementation accepts a
+//! block that the other rejects, or when both accept it but produce different post-states.
+//!
+//! The reference implementation is an executable which is invoked as:
+//!
+//! ```ignore
+//! <reference-command> <pre_state.ssz> <block.ssz> <post_state.ssz>
+//! ```
+//!
+//! It must exit with status `0` and write the SSZ-encoded post-state to `post_state.ssz` if the
+//! block is valid, or exit with a non-zero status if the block is invalid. The
+//! `FUZZ_VERIFY_SIGNATURES` environment variable is set to `true` or `false` to indicate whether
+//! signatures should be verified.
+//!
+//! Each divergence is saved to its own directory within `--output-dir`, holding the mutated block
+//! and a JSON summary of the results of both implementations. The pre-state is saved once, to the
+//! root of `--output-dir`.
+//!
+//! Logging output is controlled via the `RUST_LOG` environment variable. For example, `export
+//! RUST_LOG=debug`.
+//!
+//! ## Examples
+//!
+//! ### Fuzz against a reference implementation
+//!
+//! Run 1,000 iterations against a script which wraps the Python spec, saving divergences to
+//! `/tmp/divergences`:
+//!
+//! ```ignore
+//! lcli fuzz-transition \
+//!     --pre-state-path /tmp/pre-state-0x6c69.ssz \
+//!     --block-path /tmp/block-0x6c69.ssz \
+//!     --reference-command ./pyspec-transition.sh \
+//!     --iterations 1000 \
+//!     --output-dir /tmp/divergences
+//! ```
+//!
+//! ### Reproduce a run
+//!
+//! Mutations are derived from `--seed`, which is logged at the start of each run. Pass the same
+//! seed to repeat a run:
+//!
+//! ```ignore
+//! lcli fuzz-transition \
+//!     --pre-state-path /tmp/pre-state-0x6c69.ssz \
+//!     --block-path /tmp/block-0x6c69.ssz \
+//!     --reference-command ./pyspec-transition.sh \
+//!     --output-dir /tmp/divergences \
+//!     --seed 42
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2_network_config::Eth2NetworkConfig;
+use log::{debug, info, warn};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+use ssz::Encode;
+use state_processing::state_advance::complete_state_advance;
+use state_processing::{
+    per_block_processing, BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use types::{BeaconState, ChainSpec, EthSpec, Hash256, SignedBeaconBlock};
+
+/// The maximum number of mutations applied to the block in a single iteration.
+const MAX_MUTATIONS: usize = 4;
+
+/// Values which are likely to reach edge cases when written over a `u64` field.
+const INTERESTING_U64S: [u64; 6] = [0, 1, 2, u32::MAX as u64, u64::MAX - 1, u64::MAX];
+
+/// The result of applying a block to a state.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    /// The block is valid and results in a post-state with this root.
+    Valid(Hash256),
+    /// The block is invalid, for this reason.
+    Invalid(String),
+}
+
+impl Outcome {
+    fn agrees_with(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Valid(a), Outcome::Valid(b)) => a == b,
+            (Outcome::Invalid(_), Outcome::Invalid(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A summary of a divergence, saved alongside the mutated block.
+#[derive(Debug, Serialize)]
+struct Divergence {
+    iteration: usize,
+    seed: u64,
+    mutations: Vec<String>,
+    lighthouse: Outcome,
+    reference: Outcome,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    undecodable: usize,
+    valid: usize,
+    invalid: usize,
+    divergences: usize,
+}
+
+pub fn run<E: EthSpec>(
+    _env: Environment<E>,
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<E>()?;
+
+    let pre_state_path: PathBuf = parse_required(matches, "pre-state-path")?;
+    let block_path: PathBuf = parse_required(matches, "block-path")?;
+    let reference_command: PathBuf = parse_required(matches, "reference-command")?;
+    let output_dir: PathBuf = parse_required(matches, "output-dir")?;
+    let iterations: usize = parse_required(matches, "iterations")?;
+    let seed: u64 = parse_optional(matches, "seed")?.unwrap_or_else(rand::random);
+    let verify_signatures = matches.get_flag("verify-signatures");
+
+    info!("Using seed {}", seed);
+
+    let pre_state: BeaconState<E> =
+        load_from_ssz_with(&pre_state_path, spec, BeaconState::from_ssz_bytes)?;
+    let block: SignedBeaconBlock<E> =
+        load_from_ssz_with(&block_path, spec, SignedBeaconBlock::from_ssz_bytes)?;
+
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Unable to create {}: {:?}", output_dir.display(), e))?;
+    let work_dir = output_dir.join("work");
+    fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Unable to create {}: {:?}", work_dir.display(), e))?;
+    let pre_state_output_path = output_dir.join("pre_state.ssz");
+    write_file(&pre_state_output_path, &pre_state.as_ssz_bytes())?;
+
+    let reference_impl = Reference {
+        command: reference_command,
+        pre_state_path: pre_state_output_path,
+        block_path: work_dir.join("block.ssz"),
+        post_state_path: work_dir.join("post_state.ssz"),
+        verify_signatures,
+    };
+
+    /*
+     * Ensure both implementations agree on the unmutated block before fuzzing, otherwise every
+     * iteration would be reported as a divergence.
+     */
+
+    let block_bytes = block.as_ssz_bytes();
+    let lighthouse = lighthouse_transition(&pre_state, &block, verify_signatures, spec);
+    let expected = reference_impl.transition::<E>(&block_bytes, spec)?;
+    if !lighthouse.agrees_with(&expected) {
+        return Err(format!(
+            "Implementations disagree on the unmutated block, lighthouse: {:?}, reference: {:?}",
+            lighthouse, expected
+        ));
+    }
+    info!("Unmutated block: {:?}", lighthouse);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counts = Counts::default();
+
+    for iteration in 0..iterations {
+        let mut bytes = block_bytes.clone();
+        let num_mutations = rng.gen_range(1..=MAX_MUTATIONS);
+        let mutations = (0..num_mutations)
+            .map(|_| mutate(&mut rng, &mut bytes))
+            .collect::<Vec<_>>();
+
+        let Ok(mutated) = SignedBeaconBlock::<E>::from_ssz_bytes(&bytes, spec) else {
+            debug!("Iteration {}: mutated block does not decode", iteration);
+            counts.undecodable += 1;
+            continue;
+        };
+
+        let lighthouse = lighthouse_transition(&pre_state, &mutated, verify_signatures, spec);
+        let reference = reference_impl.transition::<E>(&bytes, spec)?;
+        debug!(
+            "Iteration {}: mutations: {:?}, lighthouse: {:?}, reference: {:?}",
+            iteration, mutations, lighthouse, reference
+        );
+
+        match &lighthouse {
+            Outcome::Valid(_) => counts.valid += 1,
+            Outcome::Invalid(_) => counts.invalid += 1,
+        }
+
+        if !lighthouse.agrees_with(&reference) {
+            counts.divergences += 1;
+            warn!(
+                "Divergence at iteration {}, lighthouse: {:?}, reference: {:?}",
+                iteration, lighthouse, reference
+            );
+            save_divergence(
+                &output_dir,
+                &bytes,
+                &Divergence {
+                    iteration,
+                    seed,
+                    mutations,
+                    lighthouse,
+                    reference,
+                },
+            )?;
+        }
+    }
+
+    info!(
+        "Completed {} iterations, undecodable: {}, valid: {}, invalid: {}, divergences: {}",
+        iterations, counts.undecodable, counts.valid, counts.invalid, counts.divergences
+    );
+
+    if counts.divergences > 0 {
+        info!("Divergences saved to {}", output_dir.display());
+    }
+
+    Ok(())
+}
+
+/// Apply `block` to a clone of `pre_state`, as per the spec's `state_transition` function.
+fn lighthouse_transition<E: EthSpec>(
+    pre_state: &BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+    verify_signatures: bool,
+    spec: &ChainSpec,
+) -> Outcome {
+    let mut state = pre_state.clone();
+
+    if block.slot() <= state.slot() {
+        return Outcome::Invalid(format!(
+            "Block slot {} is not later than state slot {}",
+            block.slot(),
+            state.slot()
+        ));
+    }
+
+    let signature_strategy = if verify_signatures {
+        BlockSignatureStrategy::VerifyIndividual
+    } else {
+        BlockSignatureStrategy::NoVerification
+    };
+
+    let result = complete_state_advance(&mut state, None, block.slot(), spec)
+        .map_err(|e| format!("Slot processing failed: {:?}", e))
+        .and_then(|()| {
+            let mut ctxt = ConsensusContext::new(block.slot());
+            per_block_processing(
+                &mut state,
+                block,
+                signature_strategy,
+                VerifyBlockRoot::True,
+                &mut ctxt,
+                spec,
+            )
+            .map_err(|e| format!("Block processing failed: {:?}", e))
+        })
+        .and_then(|()| {
+            state
+                .update_tree_hash_cache()
+                .map_err(|e| format!("Unable to compute state root: {:?}", e))
+        });
+
+    match result {
+        Ok(root) => Outcome::Valid(root),
+        Err(e) => Outcome::Invalid(e),
+    }
+}
+
+/// The reference implementation, with the paths used to exchange objects with it.
+struct Reference {
+    command: PathBuf,
+    pre_state_path: PathBuf,
+    block_path: PathBuf,
+    post_state_path: PathBuf,
+    verify_signatures: bool,
+}
+
+impl Reference {
+    /// Apply the SSZ-encoded `block_bytes` to the pre-state using the reference implementation.
+    ///
+    /// Returns an error if the reference implementation could not be run, or if it reported the
+    /// block as valid without writing a post-state which decodes.
+    fn transition<E: EthSpec>(
+        &self,
+        block_bytes: &[u8],
+        spec: &ChainSpec,
+    ) -> Result<Outcome, String> {
+        write_file(&self.block_path, block_bytes)?;
+        if self.post_state_path.exists() {
+            fs::remove_file(&self.post_state_path).map_err(|e| {
+                format!(
+                    "Unable to remove {}: {:?}",
+                    self.post_state_path.display(),
+                    e
+                )
+            })?;
+        }
+
+        let output = Command::new(&self.command)
+            .arg(&self.pre_state_path)
+            .arg(&self.block_path)
+            .arg(&self.post_state_path)
+            .env("FUZZ_VERIFY_SIGNATURES", self.verify_signatures.to_string())
+            .output()
+            .map_err(|e| format!("Unable to run {}: {:?}", self.command.display(), e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(Outcome::Invalid(format!(
+                "{}: {}",
+                output.status,
+                stderr.trim()
+            )));
+        }
+
+        let mut post_state: BeaconState<E> =
+            load_from_ssz_with(&self.post_state_path, spec, BeaconState::from_ssz_bytes)?;
+        let root = post_state
+            .update_tree_hash_cache()
+            .map_err(|e| format!("Unable to compute reference state root: {:?}", e))?;
+        Ok(Outcome::Valid(root))
+    }
+}
+
+/// Apply a single random mutation to `bytes`, returning a description of it.
+fn mutate(rng: &mut impl Rng, bytes: &mut [u8]) -> String {
+    if bytes.is_empty() {
+        return "none".to_string();
+    }
+
+    let offset = rng.gen_range(0..bytes.len());
+    match rng.gen_range(0..4) {
+        0 => {
+            let bit = rng.gen_range(0..8);
+            bytes[offset] ^= 1 << bit;
+            format!("flip bit {} of byte {}", bit, offset)
+        }
+        1 => {
+            let value: u8 = rng.gen();
+            bytes[offset] = value;
+            format!("set byte {} to {}", offset, value)
+        }
+        2 => {
+            let end = std::cmp::min(offset + 8, bytes.len());
+            let value = INTERESTING_U64S[rng.gen_range(0..INTERESTING_U64S.len())];
+            bytes[offset..end].copy_from_slice(&value.to_le_bytes()[..end - offset]);
+            format!("set u64 at byte {} to {}", offset, value)
+        }
+        _ => {
+            let end = std::cmp::min(offset + 8, bytes.len());
+            let mut word = [0; 8];
+            word[..end - offset].copy_from_slice(&bytes[offset..end]);
+            let delta: i8 = if rng.gen() { 1 } else { -1 };
+            let value = u64::from_le_bytes(word).wrapping_add_signed(delta.into());
+            bytes[offset..end].copy_from_slice(&value.to_le_bytes()[..end - offset]);
+            format!("add {} to u64 at byte {}", delta, offset)
+        }
+    }
+}
+
+fn save_divergence(
+    output_dir: &Path,
+    block_bytes: &[u8],
+    divergence: &Divergence,
+) -> Result<(), String> {
+    let dir = output_dir.join(format!("divergence-{}", divergence.iteration));
+    fs::create_dir_all(&dir).map_err(|e| format!("Unable to create {}: {:?}", dir.display(), e))?;
+    write_file(&dir.join("block.ssz"), block_bytes)?;
+    let summary = serde_json::to_vec_pretty(divergence)
+        .map_err(|e| format!("Unable to serialize divergence: {:?}", e))?;
+    write_file(&dir.join("summary.json"), &summary)
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    fs::write(path, bytes).map_err(|e| format!("Unable to write {}: {:?}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, Eth1Data, MinimalEthSpec, Signature};
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn outcomes_agree() {
+        let a = Outcome::Valid(Hash256::repeat_byte(1));
+        let b = Outcome::Valid(Hash256::repeat_byte(2));
+        let invalid = Outcome::Invalid("a".into());
+
+        assert!(a.agrees_with(&a.clone()));
+        assert!(!a.agrees_with(&b));
+        assert!(!a.agrees_with(&invalid));
+        assert!(!invalid.agrees_with(&a));
+        // Implementations may give different reasons for rejecting a block.
+        assert!(invalid.agrees_with(&Outcome::Invalid("b".into())));
+    }
+
+    #[test]
+    fn mutations_are_reproducible() {
+        let original = (0..64).collect::<Vec<u8>>();
+        let run = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut bytes = original.clone();
+            let mutations = (0..MAX_MUTATIONS)
+                .map(|_| mutate(&mut rng, &mut bytes))
+                .collect::<Vec<_>>();
+            (bytes, mutations)
+        };
+
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42).1, run(43).1);
+    }
+
+    #[test]
+    fn mutations_stay_in_bounds() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for len in 0..16 {
+            let mut bytes = vec![0xaa; len];
+            for _ in 0..256 {
+                mutate(&mut rng, &mut bytes);
+                assert_eq!(bytes.len(), len);
+            }
+        }
+    }
+
+    #[test]
+    fn block_not_later_than_state_is_invalid() {
+        let spec = E::default_spec();
+        let state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        let block = SignedBeaconBlock::from_block(BeaconBlock::empty(&spec), Signature::empty());
+
+        assert!(matches!(
+            lighthouse_transition(&state, &block, false, &spec),
+            Outcome::Invalid(_)
+        ));
+    }
+}
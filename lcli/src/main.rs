@@ -1,13 +1,19 @@
 mod block_root;
 mod check_deposit_data;
+mod craft_attestation;
+mod craft_block;
+mod diff_states;
+mod fuzz_transition;
 mod generate_bootnode_enr;
 mod indexed_attestations;
 mod mnemonic_validators;
 mod mock_el;
+mod new_testnet;
 mod parse_ssz;
 mod skip_slots;
 mod state_root;
 mod transition_blocks;
+mod upgrade_state;
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use clap_utils::{parse_optional, FLAG_HEADER};
@@ -177,6 +183,16 @@ fn main() {
                         .help("Path to output the block, useful when used with --beacon-url.")
                         .display_order(0)
                 )
+                .arg(
+                    Arg::new("report-output-path")
+                        .long("report-output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to output a JSON report of the time taken by, and changes \
+                            made by, each stage of the transition in the first run. The stages \
+                            are run individually, rather than fused as in normal operation.")
+                        .display_order(0)
+                )
                 .arg(
                     Arg::new("beacon-url")
                         .long("beacon-url")
@@ -230,6 +246,78 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("fuzz-transition")
+                .about("Differentially fuzzes the state transition by applying random mutations \
+                    of a block to a pre-state with both Lighthouse and a reference implementation, \
+                    reporting any divergences.")
+                .arg(
+                    Arg::new("pre-state-path")
+                        .long("pre-state-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to a SSZ file of the pre-state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("block-path")
+                        .long("block-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to a SSZ file of a valid block to mutate.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("reference-command")
+                        .long("reference-command")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to an executable for the reference implementation, invoked \
+                            as `<reference-command> <pre_state.ssz> <block.ssz> <post_state.ssz>`. \
+                            It must write the post-state and exit with status 0 if the block is \
+                            valid, or exit with a non-zero status if it is invalid.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-dir")
+                        .long("output-dir")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Directory in which to save the pre-state and any divergences.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("iterations")
+                        .long("iterations")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .default_value("100")
+                        .help("Number of mutated blocks to generate.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .help("Seed for the random mutations. A random seed is used if not \
+                            provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("verify-signatures")
+                        .long("verify-signatures")
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .help("Verify signatures. Most mutations invalidate the proposer \
+                            signature, so this is disabled by default.")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("pretty-ssz")
                 .about("Parses SSZ-encoded data from a file")
@@ -482,6 +570,269 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("craft-attestation")
+                .about("Produces an attestation against a state, signed by every member of the \
+                        committee which has a key. It can be made intentionally invalid for \
+                        building test vectors.")
+                .arg(
+                    Arg::new("pre-state-path")
+                        .long("pre-state-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to load a BeaconState from as SSZ. It is advanced to --slot \
+                            if required.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("slot")
+                        .long("slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The slot of the attestation.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("interop-keys")
+                        .long("interop-keys")
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .conflicts_with("secret-keys-path")
+                        .help("Sign with the deterministic interop keys.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("secret-keys-path")
+                        .long("secret-keys-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to a file of hex-encoded BLS secret keys, one per line, to \
+                            sign with.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to write the signed attestation to.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .action(ArgAction::Set)
+                        .default_value("ssz")
+                        .value_parser(["ssz", "json"])
+                        .help("Output format to use.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("committee-index")
+                        .long("committee-index")
+                        .value_name("INDEX")
+                        .action(ArgAction::Set)
+                        .default_value("0")
+                        .help("The index of the committee at --slot.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("beacon-block-root")
+                        .long("beacon-block-root")
+                        .value_name("HASH256")
+                        .action(ArgAction::Set)
+                        .help("The head block to vote for. Defaults to the latest block in the \
+                            state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("invalid")
+                        .long("invalid")
+                        .value_name("KIND")
+                        .action(ArgAction::Set)
+                        .value_parser([
+                            "bad-signature",
+                            "wrong-source",
+                            "wrong-target",
+                            "unknown-head",
+                            "empty-aggregation-bits",
+                        ])
+                        .help("Makes the attestation invalid in the given way.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("craft-block")
+                .about("Produces a block on top of a state, signed by the proposer. It can be \
+                        made intentionally invalid for building test vectors.")
+                .arg(
+                    Arg::new("pre-state-path")
+                        .long("pre-state-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to load a BeaconState from as SSZ. It is advanced to --slot \
+                            if required.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("slot")
+                        .long("slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The slot of the block.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("interop-keys")
+                        .long("interop-keys")
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .conflicts_with("secret-keys-path")
+                        .help("Sign with the deterministic interop keys.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("secret-keys-path")
+                        .long("secret-keys-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to a file of hex-encoded BLS secret keys, one per line, to \
+                            sign with.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to write the signed block to.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .action(ArgAction::Set)
+                        .default_value("ssz")
+                        .value_parser(["ssz", "json"])
+                        .help("Output format to use.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("attestation-path")
+                        .long("attestation-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Append)
+                        .help("Path to an SSZ attestation to include in the block, e.g. from \
+                            craft-attestation. May be supplied multiple times.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("graffiti")
+                        .long("graffiti")
+                        .value_name("GRAFFITI")
+                        .action(ArgAction::Set)
+                        .help("The graffiti of the block.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("post-state-output-path")
+                        .long("post-state-output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to write the SSZ state after applying the valid block to.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("invalid")
+                        .long("invalid")
+                        .value_name("KIND")
+                        .action(ArgAction::Set)
+                        .value_parser([
+                            "bad-signature",
+                            "wrong-proposer",
+                            "wrong-parent-root",
+                            "wrong-state-root",
+                            "bad-randao",
+                        ])
+                        .help("Makes the block invalid in the given way.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("diff-states")
+                .about("Prints the fields which differ between two SSZ-encoded states, along \
+                        with the generalized index of each differing field in the state tree. \
+                        Useful for debugging consensus splits.")
+                .arg(
+                    Arg::new("state-a")
+                        .index(1)
+                        .value_name("SSZ_A")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to the first BeaconState as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("state-b")
+                        .index(2)
+                        .value_name("SSZ_B")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to the second BeaconState as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("max-entries")
+                        .long("max-entries")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .default_value("20")
+                        .help("The maximum number of differing entries to print for each list \
+                            or vector field.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("upgrade-state")
+                .about("Applies the upgrade function of the next fork to an SSZ-encoded state, \
+                        printing the value and root of each field which is new or changed by \
+                        the upgrade. Useful for checking fork upgrades before the fork epoch.")
+                .arg(
+                    Arg::new("state")
+                        .index(1)
+                        .value_name("SSZ_STATE")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to the BeaconState to upgrade as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to output the upgraded state as SSZ.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("max-entries")
+                        .long("max-entries")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .default_value("20")
+                        .help("The maximum number of entries to print for each list or vector \
+                            field.")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("mock-el")
                 .about("Creates a mock execution layer server. This is NOT SAFE and should only \
@@ -552,6 +903,174 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("new-testnet")
+                .about("Produces a testnet directory containing a config and a genesis state \
+                        built from the interop keypairs. Electra pending operations can be \
+                        included in the genesis state to test their processing on devnets. \
+                        For testing purposes only, DO NOT USE IN PRODUCTION!")
+                .arg(
+                    Arg::new("output-dir")
+                        .long("output-dir")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The directory in which to write config.yaml and genesis.ssz.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .short('f')
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .help("Overwrites any existing files in the output directory.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("config-name")
+                        .long("config-name")
+                        .value_name("NAME")
+                        .action(ArgAction::Set)
+                        .help("The CONFIG_NAME written to config.yaml.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("genesis-time")
+                        .long("genesis-time")
+                        .value_name("UNIX_SECONDS")
+                        .action(ArgAction::Set)
+                        .help("The genesis time of the network. Defaults to now.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("seconds-per-slot")
+                        .long("seconds-per-slot")
+                        .value_name("SECONDS")
+                        .action(ArgAction::Set)
+                        .help("Overrides the SECONDS_PER_SLOT of the --spec.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("validator-count")
+                        .long("validator-count")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The number of interop validators in the genesis state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("compounding-validators")
+                        .long("compounding-validators")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .default_value("0")
+                        .help("The number of validators, starting from index 0, which have \
+                            0x02 (compounding) withdrawal credentials.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("eth1-withdrawal-validators")
+                        .long("eth1-withdrawal-validators")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .default_value("0")
+                        .help("The number of validators, following the compounding validators, \
+                            which have 0x01 withdrawal credentials. The remaining validators \
+                            have BLS withdrawal credentials.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("altair-fork-epoch")
+                        .long("altair-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to enable the Altair hard fork.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("bellatrix-fork-epoch")
+                        .long("bellatrix-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to enable the Bellatrix hard fork.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("capella-fork-epoch")
+                        .long("capella-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to enable the Capella hard fork.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("deneb-fork-epoch")
+                        .long("deneb-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to enable the Deneb hard fork.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("electra-fork-epoch")
+                        .long("electra-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to enable the Electra hard fork.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("execution-payload-header")
+                        .long("execution-payload-header")
+                        .value_name("FILE")
+                        .action(ArgAction::Set)
+                        .help("An SSZ encoded execution payload header for the fork at genesis, \
+                            used as the latest execution payload header of the genesis state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("execution-genesis-json")
+                        .long("execution-genesis-json")
+                        .value_name("FILE")
+                        .action(ArgAction::Set)
+                        .conflicts_with("execution-payload-header")
+                        .help("The genesis JSON of the execution layer, as given to `geth init`. \
+                            The latest execution payload header of the genesis state is its \
+                            genesis block, and the fork epochs must agree with its fork times. \
+                            Sets the terminal total difficulty and deposit chain ID of the \
+                            config.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("pending-balance-deposits")
+                        .long("pending-balance-deposits")
+                        .value_name("INDEX:AMOUNT,...")
+                        .action(ArgAction::Set)
+                        .help("Pending balance deposits (in Gwei) to include in an Electra \
+                            genesis state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("pending-partial-withdrawals")
+                        .long("pending-partial-withdrawals")
+                        .value_name("INDEX:AMOUNT:EPOCH,...")
+                        .action(ArgAction::Set)
+                        .help("Pending partial withdrawals (in Gwei) and the epoch at which they \
+                            become withdrawable, to include in an Electra genesis state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("pending-consolidations")
+                        .long("pending-consolidations")
+                        .value_name("SOURCE:TARGET,...")
+                        .action(ArgAction::Set)
+                        .help("Pending consolidations of a source validator into a target \
+                            validator, to include in an Electra genesis state.")
+                        .display_order(0)
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -590,6 +1109,7 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             compression: false,
             is_restricted: true,
             sse_logging: false, // No SSE Logging in LCLI
+            log_repeat_limit: 0,
         })
         .map_err(|e| format!("should start logger: {:?}", e))?
         .build()
@@ -631,6 +1151,11 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             skip_slots::run::<E>(env, network_config, matches)
                 .map_err(|e| format!("Failed to skip slots: {}", e))
         }
+        Some(("fuzz-transition", matches)) => {
+            let network_config = get_network_config()?;
+            fuzz_transition::run::<E>(env, network_config, matches)
+                .map_err(|e| format!("Failed to fuzz transition: {}", e))
+        }
         Some(("pretty-ssz", matches)) => {
             let network_config = get_network_config()?;
             run_parse_ssz::<E>(network_config, matches)
@@ -654,8 +1179,30 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             state_root::run::<E>(env, network_config, matches)
                 .map_err(|e| format!("Failed to run state-root command: {}", e))
         }
+        Some(("craft-attestation", matches)) => {
+            let network_config = get_network_config()?;
+            craft_attestation::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run craft-attestation command: {}", e))
+        }
+        Some(("craft-block", matches)) => {
+            let network_config = get_network_config()?;
+            craft_block::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run craft-block command: {}", e))
+        }
+        Some(("diff-states", matches)) => {
+            let network_config = get_network_config()?;
+            diff_states::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run diff-states command: {}", e))
+        }
+        Some(("upgrade-state", matches)) => {
+            let network_config = get_network_config()?;
+            upgrade_state::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run upgrade-state command: {}", e))
+        }
         Some(("mock-el", matches)) => mock_el::run::<E>(env, matches)
             .map_err(|e| format!("Failed to run mock-el command: {}", e)),
+        Some(("new-testnet", matches)) => new_testnet::run::<E>(matches)
+            .map_err(|e| format!("Failed to run new-testnet command: {}", e)),
         Some((other, _)) => Err(format!("Unknown subcommand {}. See --help.", other)),
         _ => Err("No subcommand provided. See --help.".to_string()),
     }
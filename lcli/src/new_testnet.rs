@@ -0,0 +1,384 @@
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use eth2_network_config::{Eth2NetworkConfig, GenesisStateSource, TRUSTED_SETUP_BYTES};
+use execution_layer::genesis::ExecutionGenesis;
+use genesis::{
+    bls_withdrawal_credentials, compounding_withdrawal_credentials, eth1_withdrawal_credentials,
+    generate_deterministic_keypairs, interop_genesis_state_with_withdrawal_credentials,
+    DEFAULT_ETH1_BLOCK_HASH,
+};
+use ssz::Encode;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use types::{
+    BeaconState, ChainSpec, Config, Epoch, EthSpec, ExecutionPayloadHeader, ForkName, Hash256,
+    PendingBalanceDeposit, PendingConsolidation, PendingPartialWithdrawal,
+};
+
+pub fn run<E: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
+    let output_dir: PathBuf = parse_required(matches, "output-dir")?;
+    let force = matches.get_flag("force");
+    let validator_count: usize = parse_required(matches, "validator-count")?;
+    let eth1_validator_count: usize = parse_required(matches, "eth1-withdrawal-validators")?;
+    let compounding_validator_count: usize = parse_required(matches, "compounding-validators")?;
+    let genesis_time: u64 = match parse_optional(matches, "genesis-time")? {
+        Some(genesis_time) => genesis_time,
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| format!("Unable to read system time: {:?}", e))?
+            .as_secs(),
+    };
+
+    if compounding_validator_count + eth1_validator_count > validator_count {
+        return Err(format!(
+            "--compounding-validators and --eth1-withdrawal-validators exceed \
+             --validator-count of {}",
+            validator_count
+        ));
+    }
+
+    let mut spec = E::default_spec();
+    spec.min_genesis_active_validator_count = validator_count as u64;
+    spec.min_genesis_time = genesis_time;
+    if let Some(seconds_per_slot) = parse_optional(matches, "seconds-per-slot")? {
+        spec.seconds_per_slot = seconds_per_slot;
+    }
+    if let Some(config_name) = parse_optional(matches, "config-name")? {
+        spec.config_name = Some(config_name);
+    }
+    for fork_name in ForkName::list_all() {
+        let fork_epoch = match fork_name {
+            ForkName::Base => continue,
+            ForkName::Altair => &mut spec.altair_fork_epoch,
+            ForkName::Bellatrix => &mut spec.bellatrix_fork_epoch,
+            ForkName::Capella => &mut spec.capella_fork_epoch,
+            ForkName::Deneb => &mut spec.deneb_fork_epoch,
+            ForkName::Electra => &mut spec.electra_fork_epoch,
+        };
+        if let Some(epoch) = parse_optional(matches, &format!("{}-fork-epoch", fork_name))? {
+            *fork_epoch = Some(Epoch::new(epoch));
+        }
+    }
+    verify_fork_epochs(&spec)?;
+
+    let genesis_fork = spec.fork_name_at_epoch(Epoch::new(0));
+    let execution_payload_header = if let Some(path) =
+        parse_optional::<PathBuf>(matches, "execution-genesis-json")?
+    {
+        let execution_genesis = read_execution_genesis(&path)?;
+        verify_execution_genesis::<E>(&execution_genesis, genesis_time, &spec)?;
+        if let Some(terminal_total_difficulty) = execution_genesis.terminal_total_difficulty()? {
+            spec.terminal_total_difficulty = terminal_total_difficulty;
+        }
+        spec.deposit_chain_id = execution_genesis.chain_id();
+        spec.deposit_network_id = execution_genesis.chain_id();
+        Some(execution_genesis.execution_payload_header::<E>(genesis_fork)?)
+    } else {
+        parse_optional::<PathBuf>(matches, "execution-payload-header")?
+            .map(|path| read_execution_payload_header::<E>(&path, genesis_fork))
+            .transpose()?
+    };
+    if genesis_fork >= ForkName::Capella && execution_payload_header.is_none() {
+        return Err(format!(
+            "A {} genesis must be post-merge, use --execution-genesis-json or \
+             --execution-payload-header",
+            genesis_fork
+        ));
+    }
+
+    let keypairs = generate_deterministic_keypairs(validator_count);
+    let withdrawal_credentials = keypairs
+        .iter()
+        .enumerate()
+        .map(|(index, keypair)| {
+            if index < compounding_validator_count {
+                compounding_withdrawal_credentials(&keypair.pk, &spec)
+            } else if index < compounding_validator_count + eth1_validator_count {
+                eth1_withdrawal_credentials(&keypair.pk, &spec)
+            } else {
+                bls_withdrawal_credentials(&keypair.pk, &spec)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut state = interop_genesis_state_with_withdrawal_credentials::<E>(
+        &keypairs,
+        &withdrawal_credentials,
+        genesis_time,
+        Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+        execution_payload_header,
+        &spec,
+    )?;
+
+    let pending_balance_deposits: Vec<PendingBalanceDeposit> =
+        parse_list(matches, "pending-balance-deposits", |fields| {
+            let [index, amount] = fields else {
+                return Err("expected INDEX:AMOUNT".to_string());
+            };
+            Ok(PendingBalanceDeposit {
+                index: parse_field(index)?,
+                amount: parse_field(amount)?,
+            })
+        })?;
+    let pending_partial_withdrawals: Vec<PendingPartialWithdrawal> =
+        parse_list(matches, "pending-partial-withdrawals", |fields| {
+            let [index, amount, withdrawable_epoch] = fields else {
+                return Err("expected INDEX:AMOUNT:WITHDRAWABLE_EPOCH".to_string());
+            };
+            Ok(PendingPartialWithdrawal {
+                index: parse_field(index)?,
+                amount: parse_field(amount)?,
+                withdrawable_epoch: Epoch::new(parse_field(withdrawable_epoch)?),
+            })
+        })?;
+    let pending_consolidations: Vec<PendingConsolidation> =
+        parse_list(matches, "pending-consolidations", |fields| {
+            let [source_index, target_index] = fields else {
+                return Err("expected SOURCE_INDEX:TARGET_INDEX".to_string());
+            };
+            Ok(PendingConsolidation {
+                source_index: parse_field(source_index)?,
+                target_index: parse_field(target_index)?,
+            })
+        })?;
+
+    let indices =
+        pending_balance_deposits
+            .iter()
+            .map(|deposit| deposit.index)
+            .chain(
+                pending_partial_withdrawals
+                    .iter()
+                    .map(|withdrawal| withdrawal.index),
+            )
+            .chain(pending_consolidations.iter().flat_map(|consolidation| {
+                [consolidation.source_index, consolidation.target_index]
+            }));
+    for index in indices {
+        if index >= validator_count as u64 {
+            return Err(format!(
+                "Validator index {} is out of range for {} validators",
+                index, validator_count
+            ));
+        }
+    }
+
+    add_pending_operations(
+        &mut state,
+        pending_balance_deposits,
+        pending_partial_withdrawals,
+        pending_consolidations,
+    )?;
+
+    state
+        .drop_all_caches()
+        .map_err(|e| format!("Unable to drop caches: {:?}", e))?;
+
+    let kzg_trusted_setup = if spec.deneb_fork_epoch.is_some() {
+        Some(TRUSTED_SETUP_BYTES.to_vec())
+    } else {
+        None
+    };
+
+    let testnet = Eth2NetworkConfig {
+        deposit_contract_deploy_block: 0,
+        boot_enr: None,
+        genesis_state_source: GenesisStateSource::IncludedBytes,
+        genesis_state_bytes: Some(state.as_ssz_bytes().into()),
+        config: Config::from_chain_spec::<E>(&spec),
+        kzg_trusted_setup,
+    };
+    testnet.write_to_file(output_dir.clone(), force)?;
+
+    println!(
+        "Wrote {} genesis with {} validators to {:?} (genesis_validators_root: {:?})",
+        genesis_fork,
+        validator_count,
+        output_dir,
+        state.genesis_validators_root(),
+    );
+
+    Ok(())
+}
+
+/// Returns an error if a fork is scheduled before the fork which precedes it.
+fn verify_fork_epochs(spec: &ChainSpec) -> Result<(), String> {
+    let mut previous: Option<(ForkName, Epoch)> = None;
+    for (fork_name, epoch) in ForkName::list_all_fork_epochs(spec) {
+        match (previous, epoch) {
+            (Some((previous_fork, previous_epoch)), Some(epoch)) if epoch < previous_epoch => {
+                return Err(format!(
+                    "{} fork epoch {} is before {} fork epoch {}",
+                    fork_name, epoch, previous_fork, previous_epoch
+                ));
+            }
+            (None, Some(_)) if fork_name != ForkName::Altair => {
+                return Err(format!(
+                    "{} fork is scheduled but the fork before it is not",
+                    fork_name
+                ));
+            }
+            (_, Some(epoch)) => previous = Some((fork_name, epoch)),
+            (_, None) => previous = None,
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error if the execution genesis is not post-merge, or if it does not activate the
+/// execution counterpart of each fork at the same time as the consensus fork.
+fn verify_execution_genesis<E: EthSpec>(
+    execution_genesis: &ExecutionGenesis,
+    genesis_time: u64,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    if spec.bellatrix_fork_epoch != Some(Epoch::new(0)) {
+        return Err(
+            "An execution genesis requires a Bellatrix genesis, use --bellatrix-fork-epoch 0"
+                .into(),
+        );
+    }
+
+    let difficulty = execution_genesis.difficulty()?;
+    match execution_genesis.terminal_total_difficulty()? {
+        Some(terminal_total_difficulty) if terminal_total_difficulty <= difficulty => (),
+        _ => {
+            return Err(format!(
+                "The execution genesis is not post-merge, its terminalTotalDifficulty must be at \
+                 most its difficulty of {}",
+                difficulty
+            ))
+        }
+    }
+
+    let execution_genesis_time = execution_genesis.timestamp()?;
+    for fork_name in [ForkName::Capella, ForkName::Deneb, ForkName::Electra] {
+        match (
+            spec.fork_epoch(fork_name),
+            execution_genesis.fork_time(fork_name),
+        ) {
+            (Some(epoch), Some(time)) if epoch == 0 => {
+                if time > execution_genesis_time {
+                    return Err(format!(
+                        "{} is active at genesis but the execution genesis activates it at {}, \
+                         after its timestamp of {}",
+                        fork_name, time, execution_genesis_time
+                    ));
+                }
+            }
+            (Some(epoch), Some(time)) => {
+                let fork_time =
+                    genesis_time + epoch.as_u64() * E::slots_per_epoch() * spec.seconds_per_slot;
+                if time != fork_time || time <= execution_genesis_time {
+                    return Err(format!(
+                        "{} fork epoch {} is at {}, but the execution genesis activates it at {}",
+                        fork_name, epoch, fork_time, time
+                    ));
+                }
+            }
+            (Some(epoch), None) => {
+                return Err(format!(
+                    "{} fork epoch is {}, but the execution genesis does not activate it",
+                    fork_name, epoch
+                ))
+            }
+            (None, Some(time)) => {
+                return Err(format!(
+                    "The execution genesis activates {} at {}, but it is not scheduled",
+                    fork_name, time
+                ))
+            }
+            (None, None) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Inserts pending operations into an Electra genesis state, so that their processing can be
+/// tested from the first epoch.
+fn add_pending_operations<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    pending_balance_deposits: Vec<PendingBalanceDeposit>,
+    pending_partial_withdrawals: Vec<PendingPartialWithdrawal>,
+    pending_consolidations: Vec<PendingConsolidation>,
+) -> Result<(), String> {
+    if pending_balance_deposits.is_empty()
+        && pending_partial_withdrawals.is_empty()
+        && pending_consolidations.is_empty()
+    {
+        return Ok(());
+    }
+
+    let BeaconState::Electra(state) = state else {
+        return Err(
+            "Pending operations require an Electra genesis, use --electra-fork-epoch 0".into(),
+        );
+    };
+
+    for deposit in pending_balance_deposits {
+        state
+            .pending_balance_deposits
+            .push(deposit)
+            .map_err(|e| format!("Too many pending balance deposits: {:?}", e))?;
+    }
+    for withdrawal in pending_partial_withdrawals {
+        state
+            .pending_partial_withdrawals
+            .push(withdrawal)
+            .map_err(|e| format!("Too many pending partial withdrawals: {:?}", e))?;
+    }
+    for consolidation in pending_consolidations {
+        state
+            .pending_consolidations
+            .push(consolidation)
+            .map_err(|e| format!("Too many pending consolidations: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+fn read_execution_genesis(path: &PathBuf) -> Result<ExecutionGenesis, String> {
+    let file = File::open(path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+    serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse execution genesis {:?}: {:?}", path, e))
+}
+
+fn read_execution_payload_header<E: EthSpec>(
+    path: &PathBuf,
+    fork_name: ForkName,
+) -> Result<ExecutionPayloadHeader<E>, String> {
+    let mut bytes = vec![];
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut bytes))
+        .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+
+    ExecutionPayloadHeader::from_ssz_bytes(&bytes, fork_name)
+        .map_err(|e| format!("Unable to decode execution payload header: {:?}", e))
+}
+
+/// Parses a comma-separated list of colon-separated fields, e.g. `0:1,2:3`.
+fn parse_list<T>(
+    matches: &ArgMatches,
+    name: &str,
+    parse_item: impl Fn(&[&str]) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    let Some(list) = parse_optional::<String>(matches, name)? else {
+        return Ok(vec![]);
+    };
+
+    list.split(',')
+        .map(|item| {
+            let fields = item.trim().split(':').collect::<Vec<_>>();
+            parse_item(&fields).map_err(|e| format!("Invalid --{} item {:?}: {}", name, item, e))
+        })
+        .collect()
+}
+
+fn parse_field<T: FromStr>(field: &str) -> Result<T, String> {
+    field
+        .parse()
+        .map_err(|_| format!("unable to parse {:?}", field))
+}
@@ -49,6 +49,18 @@
 //!     --post-state-output-path /tmp/post-state-0x6c69.ssz
 //! ```
 //!
+//! ### Report on each stage of the transition
+//!
+//! Run each stage of per-epoch and per-block processing individually, saving the time taken by
+//! and the changes made by each stage to a JSON file:
+//!
+//! ```ignore
+//! lcli transition-blocks \
+//!     --block-path /tmp/block-0x6c69.ssz \
+//!     --pre-state-path /tmp/pre-state-0x6c69.ssz \
+//!     --report-output-path /tmp/report-0x6c69.json
+//! ```
+//!
 //! ### Isolate block processing for benchmarking
 //!
 //! Try to isolate block processing as much as possible for benchmarking:
@@ -75,6 +87,9 @@ use eth2_network_config::Eth2NetworkConfig;
 use log::{debug, info};
 use ssz::Encode;
 use state_processing::state_advance::complete_state_advance;
+use state_processing::transition_report::{
+    advance_with_report, block_processing_with_report, StageReport, TransitionReport,
+};
 use state_processing::{
     block_signature_verifier::BlockSignatureVerifier, per_block_processing, AllCaches,
     BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
@@ -95,6 +110,7 @@ struct Config {
     no_signature_verification: bool,
     exclude_cache_builds: bool,
     exclude_post_block_thc: bool,
+    report: bool,
 }
 
 pub fn run<E: EthSpec>(
@@ -115,12 +131,14 @@ pub fn run<E: EthSpec>(
         parse_optional(matches, "post-state-output-path")?;
     let pre_state_output_path: Option<PathBuf> = parse_optional(matches, "pre-state-output-path")?;
     let block_output_path: Option<PathBuf> = parse_optional(matches, "block-output-path")?;
+    let report_output_path: Option<PathBuf> = parse_optional(matches, "report-output-path")?;
     let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
     let runs: usize = parse_required(matches, "runs")?;
     let config = Config {
         no_signature_verification: matches.get_flag("no-signature-verification"),
         exclude_cache_builds: matches.get_flag("exclude-cache-builds"),
         exclude_post_block_thc: matches.get_flag("exclude-post-block-thc"),
+        report: report_output_path.is_some(),
     };
 
     info!("Using {} spec", E::spec_name());
@@ -233,6 +251,7 @@ pub fn run<E: EthSpec>(
      */
 
     let mut output_post_state = None;
+    let mut output_report = None;
     let mut saved_ctxt = None;
     for i in 0..runs {
         let pre_state = pre_state.clone();
@@ -240,7 +259,7 @@ pub fn run<E: EthSpec>(
 
         let start = Instant::now();
 
-        let post_state = do_transition(
+        let (post_state, report) = do_transition(
             pre_state,
             block_root,
             block,
@@ -257,6 +276,9 @@ pub fn run<E: EthSpec>(
         if output_post_state.is_none() {
             output_post_state = Some(post_state)
         }
+        if output_report.is_none() {
+            output_report = report;
+        }
     }
 
     /*
@@ -279,6 +301,21 @@ pub fn run<E: EthSpec>(
             .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
     }
 
+    if let Some(path) = report_output_path {
+        let output_report = output_report.ok_or_else(|| {
+            format!(
+                "Report was not computed, cannot save to disk (runs = {})",
+                runs
+            )
+        })?;
+
+        let output_file =
+            File::create(path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
+
+        serde_json::to_writer_pretty(output_file, &output_report)
+            .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
+    }
+
     if let Some(path) = pre_state_output_path {
         let mut output_file =
             File::create(path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
@@ -312,7 +349,7 @@ fn do_transition<E: EthSpec>(
     validator_pubkey_cache: &ValidatorPubkeyCache<EphemeralHarnessType<E>>,
     saved_ctxt: &mut Option<ConsensusContext<E>>,
     spec: &ChainSpec,
-) -> Result<BeaconState<E>, String> {
+) -> Result<(BeaconState<E>, Option<TransitionReport>), String> {
     if !config.exclude_cache_builds {
         let t = Instant::now();
         pre_state
@@ -340,8 +377,21 @@ fn do_transition<E: EthSpec>(
 
     // Transition the parent state to the block slot.
     let t = Instant::now();
-    complete_state_advance(&mut pre_state, Some(state_root), block.slot(), spec)
-        .map_err(|e| format!("Unable to perform complete advance: {e:?}"))?;
+    let mut report = if config.report {
+        let report = advance_with_report(&mut pre_state, Some(state_root), block.slot(), spec)
+            .map_err(|e| format!("Unable to perform complete advance: {e:?}"))?;
+        for epoch_report in &report.epoch_processing {
+            log_stages(
+                &format!("Epoch {}", epoch_report.epoch),
+                &epoch_report.stages,
+            );
+        }
+        Some(report)
+    } else {
+        complete_state_advance(&mut pre_state, Some(state_root), block.slot(), spec)
+            .map_err(|e| format!("Unable to perform complete advance: {e:?}"))?;
+        None
+    };
     debug!("Slot processing: {:?}", t.elapsed());
 
     // Slot and epoch processing should keep the caches fully primed.
@@ -396,15 +446,22 @@ fn do_transition<E: EthSpec>(
     }
 
     let t = Instant::now();
-    per_block_processing(
-        &mut pre_state,
-        &block,
-        BlockSignatureStrategy::NoVerification,
-        VerifyBlockRoot::True,
-        &mut ctxt,
-        spec,
-    )
-    .map_err(|e| format!("State transition failed: {:?}", e))?;
+    if let Some(report) = &mut report {
+        report.block_processing =
+            block_processing_with_report(&mut pre_state, &block, &mut ctxt, spec)
+                .map_err(|e| format!("State transition failed: {:?}", e))?;
+        log_stages("Block", &report.block_processing);
+    } else {
+        per_block_processing(
+            &mut pre_state,
+            &block,
+            BlockSignatureStrategy::NoVerification,
+            VerifyBlockRoot::True,
+            &mut ctxt,
+            spec,
+        )
+        .map_err(|e| format!("State transition failed: {:?}", e))?;
+    }
     debug!("Process block: {:?}", t.elapsed());
 
     if !config.exclude_post_block_thc {
@@ -415,7 +472,16 @@ fn do_transition<E: EthSpec>(
         debug!("Post-block tree hash: {:?}", t.elapsed());
     }
 
-    Ok(pre_state)
+    Ok((pre_state, report))
+}
+
+fn log_stages(prefix: &str, stages: &[StageReport]) {
+    for stage in stages {
+        debug!(
+            "{} {}: {}us, {} balances changed",
+            prefix, stage.stage, stage.duration_us, stage.diff.balances_changed
+        );
+    }
 }
 
 pub fn load_from_ssz_with<T>(
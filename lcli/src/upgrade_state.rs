@@ -0,0 +1,211 @@
+//! # Upgrade-State
+//!
+//! Use this tool to apply the upgrade function of the next fork to a `BeaconState`, printing the
+//! value and hash root of each field which is new or changed by the upgrade. Useful for checking
+//! the upgrade logic against a real state before the fork epoch is reached.
+//!
+//! ## Example
+//!
+//! Upgrade a Deneb state to Electra, and save the upgraded state:
+//!
+//! ```ignore
+//! lcli upgrade-state \
+//!     /tmp/state-deneb.ssz \
+//!     --output-path /tmp/state-electra.ssz
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use compare_fields::{CompareFields, Comparison};
+use eth2_network_config::Eth2NetworkConfig;
+use log::{info, warn};
+use serde_json::Value;
+use ssz::Encode;
+use state_processing::upgrade::{
+    upgrade_to_altair, upgrade_to_bellatrix, upgrade_to_capella, upgrade_to_deneb,
+    upgrade_to_electra,
+};
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::PathBuf;
+use types::{BeaconState, ChainSpec, EthSpec, ForkName};
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<E>()?;
+
+    let state_path: PathBuf = parse_required(matches, "state")?;
+    let output_path: Option<PathBuf> = parse_optional(matches, "output-path")?;
+    let max_entries: usize = parse_required(matches, "max-entries")?;
+
+    info!(
+        "Using {} network ({} spec)",
+        spec.config_name.as_deref().unwrap_or("unknown"),
+        E::spec_name()
+    );
+
+    let mut pre_state: BeaconState<E> =
+        load_from_ssz_with(&state_path, spec, BeaconState::from_ssz_bytes)?;
+    let pre_state_root = pre_state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Error computing pre-state root: {e:?}"))?;
+    let pre_fork = pre_state.fork_name_unchecked();
+
+    let mut post_state = pre_state.clone();
+    let post_fork = upgrade_state(&mut post_state, spec)?;
+    let post_state_root = post_state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Error computing post-state root: {e:?}"))?;
+
+    let epoch = pre_state.current_epoch();
+    match spec.fork_epoch(post_fork) {
+        Some(fork_epoch) if fork_epoch == epoch => (),
+        fork_epoch => warn!(
+            "The state is at epoch {}, but the {} fork is scheduled for epoch {:?}",
+            epoch, post_fork, fork_epoch
+        ),
+    }
+
+    println!(
+        "Upgraded {} state to {} at epoch {}",
+        pre_fork, post_fork, epoch
+    );
+    println!("  pre-state root:  {:?}", pre_state_root);
+    println!("  post-state root: {:?}", post_state_root);
+
+    let to_json = |state: &BeaconState<E>| {
+        serde_json::to_value(state).map_err(|e| format!("Unable to serialize state: {e:?}"))
+    };
+    let (Value::Object(pre_fields), Value::Object(post_fields)) =
+        (to_json(&pre_state)?, to_json(&post_state)?)
+    else {
+        return Err("States did not serialize to objects".to_string());
+    };
+
+    // Comparing the state with itself yields the name of each field, in the same order as the
+    // leaves of the state tree.
+    let leaves = post_state.get_beacon_state_leaves();
+    let comparisons = post_state.compare_fields(&post_state);
+    let num_fields_pow2 = post_state.num_fields_pow2();
+
+    for (field_index, (leaf, comparison)) in leaves.iter().zip(&comparisons).enumerate() {
+        let field_name = match comparison {
+            Comparison::Child(child) => &child.field_name,
+            Comparison::Parent { field_name, .. } => field_name,
+        };
+        let post_value = post_fields.get(field_name).unwrap_or(&Value::Null);
+        let status = match pre_fields.get(field_name) {
+            None => "new",
+            Some(pre_value) if pre_value != post_value => "changed",
+            Some(_) => continue,
+        };
+
+        let generalized_index = num_fields_pow2 + field_index;
+        println!();
+        println!(
+            "{} ({}, field {}, generalized index {})",
+            field_name, status, field_index, generalized_index
+        );
+        println!("  root:  {:?}", leaf);
+        println!("  value: {}", summarize(post_value, max_entries));
+    }
+
+    if let Some(output_path) = output_path {
+        let mut output_file = File::create(output_path)
+            .map_err(|e| format!("Unable to create output file: {:?}", e))?;
+        output_file
+            .write_all(&post_state.as_ssz_bytes())
+            .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Applies the upgrade function of the fork after that of `state`, returning the new fork.
+fn upgrade_state<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<ForkName, String> {
+    let pre_fork = state.fork_name_unchecked();
+    // Some upgrades translate participation using the committee caches.
+    state
+        .build_caches(spec)
+        .map_err(|e| format!("Unable to build caches: {e:?}"))?;
+
+    let result = match pre_fork {
+        ForkName::Base => upgrade_to_altair(state, spec),
+        ForkName::Altair => upgrade_to_bellatrix(state, spec),
+        ForkName::Bellatrix => upgrade_to_capella(state, spec),
+        ForkName::Capella => upgrade_to_deneb(state, spec),
+        ForkName::Deneb => upgrade_to_electra(state, spec),
+        ForkName::Electra => return Err(format!("There is no fork after {}", pre_fork)),
+    };
+    result.map_err(|e| format!("Unable to upgrade {} state: {e:?}", pre_fork))?;
+
+    Ok(state.fork_name_unchecked())
+}
+
+/// Lists longer than `max_entries` are truncated, as they may hold an entry per validator.
+fn summarize(value: &Value, max_entries: usize) -> String {
+    match value {
+        Value::Array(entries) if entries.len() > max_entries => {
+            let shown = Value::Array(entries[..max_entries].to_vec());
+            format!(
+                "{} ... and {} more ({} entries)",
+                shown,
+                entries.len() - max_entries,
+                entries.len()
+            )
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{test_utils::generate_deterministic_keypairs, Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn upgrades_through_every_fork() {
+        let spec = E::default_spec();
+        let keypairs = generate_deterministic_keypairs(16);
+        let mut state: BeaconState<E> =
+            genesis::interop_genesis_state(&keypairs, 0, Hash256::repeat_byte(0x42), None, &spec)
+                .unwrap();
+        assert_eq!(state.fork_name_unchecked(), ForkName::Base);
+
+        let mut forks = vec![];
+        while state.fork_name_unchecked() != ForkName::Electra {
+            let pre_root = state.update_tree_hash_cache().unwrap();
+            let fork = upgrade_state(&mut state, &spec).unwrap();
+            assert_eq!(fork, state.fork_name_unchecked());
+            assert_ne!(state.update_tree_hash_cache().unwrap(), pre_root);
+            forks.push(fork);
+        }
+        assert_eq!(
+            forks,
+            [
+                ForkName::Altair,
+                ForkName::Bellatrix,
+                ForkName::Capella,
+                ForkName::Deneb,
+                ForkName::Electra
+            ]
+        );
+
+        assert!(upgrade_state(&mut state, &spec).is_err());
+    }
+
+    #[test]
+    fn long_lists_are_summarized() {
+        let value = serde_json::json!([1, 2, 3, 4]);
+        assert_eq!(summarize(&value, 4), "[1,2,3,4]");
+        assert_eq!(summarize(&value, 2), "[1,2] ... and 2 more (4 entries)");
+        assert_eq!(summarize(&serde_json::json!("0x00"), 2), "\"0x00\"");
+    }
+}
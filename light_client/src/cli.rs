@@ -0,0 +1,72 @@
+use clap::{Arg, ArgAction, Command};
+use clap_utils::{get_color_style, FLAG_HEADER};
+
+pub const CMD: &str = "light_client";
+pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
+pub const DEFAULT_HTTP_ADDRESS: &str = "127.0.0.1";
+pub const DEFAULT_HTTP_PORT: &str = "5072";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .visible_aliases(["lc", "light-client"])
+        .about(
+            "Run a light client which follows the chain by verifying the sync committee \
+             signatures of light client updates from one or more beacon node APIs, starting from \
+             a trusted checkpoint. The verified headers and their execution block hashes are \
+             served by a local HTTP API.",
+        )
+        .styles(get_color_style())
+        .display_order(0)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new("beacon-nodes")
+                .long("beacon-nodes")
+                .value_name("NETWORK_ADDRESSES")
+                .help(
+                    "Comma-separated addresses of the beacon node APIs to fetch light client \
+                     updates from. They are tried in order, and none of them are trusted.",
+                )
+                .default_value(DEFAULT_BEACON_NODE)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("checkpoint-root")
+                .long("checkpoint-root")
+                .value_name("BLOCK_ROOT")
+                .help(
+                    "The root of a trusted block to start following the chain from, such as a \
+                     recent finalized checkpoint obtained from a source you trust. It must be \
+                     within the weak subjectivity period.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("http-address")
+                .long("http-address")
+                .value_name("ADDRESS")
+                .help("Set the listen address for the light client HTTP API.")
+                .default_value(DEFAULT_HTTP_ADDRESS)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("http-port")
+                .long("http-port")
+                .value_name("PORT")
+                .help("Set the listen TCP port for the light client HTTP API.")
+                .default_value(DEFAULT_HTTP_PORT)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
@@ -0,0 +1,45 @@
+use crate::cli::DEFAULT_BEACON_NODE;
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use types::Hash256;
+
+/// Configuration for a light client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// The beacon nodes to fetch light client updates from, in order of preference.
+    pub beacon_nodes: Vec<SensitiveUrl>,
+    /// The root of the trusted block from which the chain is followed.
+    pub checkpoint_root: Hash256,
+    pub listen_addr: IpAddr,
+    pub listen_port: u16,
+}
+
+impl Config {
+    pub fn from_cli(cli_args: &ArgMatches) -> Result<Self, String> {
+        let beacon_nodes = cli_args
+            .get_one::<String>("beacon-nodes")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_BEACON_NODE)
+            .split(',')
+            .map(|url| {
+                SensitiveUrl::parse(url.trim())
+                    .map_err(|e| format!("Unable to parse beacon node URL: {:?}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let checkpoint_root = parse_required(cli_args, "checkpoint-root")?;
+
+        let listen_addr = parse_required(cli_args, "http-address")?;
+        let listen_port = parse_required(cli_args, "http-port")?;
+
+        Ok(Self {
+            beacon_nodes,
+            checkpoint_root,
+            listen_addr,
+            listen_port,
+        })
+    }
+}
@@ -0,0 +1,103 @@
+//! The HTTP API of the light client, which only serves headers that have been verified.
+use crate::{Config, LightClientStore, VerifiedExecution, VerifiedHeader};
+use eth2::types::GenericResponse;
+use lighthouse_version::version_with_platform;
+use parking_lot::RwLock;
+use slog::{info, Logger};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use types::EthSpec;
+use warp::Filter;
+use warp_utils::reject::{custom_bad_request, custom_not_found};
+
+/// The verified headers which can be requested.
+#[derive(Debug, Clone, Copy)]
+enum HeaderId {
+    Finalized,
+    Optimistic,
+}
+
+impl FromStr for HeaderId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "finalized" => Ok(HeaderId::Finalized),
+            "optimistic" => Ok(HeaderId::Optimistic),
+            other => Err(format!("{other} is not finalized or optimistic")),
+        }
+    }
+}
+
+fn header<E: EthSpec>(store: &LightClientStore<E>, id: HeaderId) -> VerifiedHeader {
+    match id {
+        HeaderId::Finalized => store.finalized_header().clone(),
+        HeaderId::Optimistic => store.optimistic_header().clone(),
+    }
+}
+
+/// Build the HTTP server, which shuts down once `shutdown` completes.
+pub fn serve<E: EthSpec>(
+    config: &Config,
+    store: Arc<RwLock<LightClientStore<E>>>,
+    shutdown: impl Future<Output = ()> + Send + Sync + 'static,
+    log: Logger,
+) -> Result<impl Future<Output = ()>, String> {
+    let store_filter = warp::any().map(move || store.clone());
+    let header_id = warp::path::param::<String>()
+        .and_then(|id: String| async move { id.parse::<HeaderId>().map_err(custom_bad_request) });
+
+    // GET lighthouse/light_client/headers/{finalized|optimistic}
+    let get_header = warp::path("lighthouse")
+        .and(warp::path("light_client"))
+        .and(warp::path("headers"))
+        .and(header_id.clone())
+        .and(warp::path::end())
+        .and(store_filter.clone())
+        .map(|id: HeaderId, store: Arc<RwLock<LightClientStore<E>>>| {
+            warp::reply::json(&GenericResponse::from(header(&store.read(), id)))
+        });
+
+    // GET lighthouse/light_client/execution_block_hash/{finalized|optimistic}
+    let get_execution_block_hash = warp::path("lighthouse")
+        .and(warp::path("light_client"))
+        .and(warp::path("execution_block_hash"))
+        .and(header_id)
+        .and(warp::path::end())
+        .and(store_filter)
+        .and_then(
+            |id: HeaderId, store: Arc<RwLock<LightClientStore<E>>>| async move {
+                let header = header(&store.read(), id);
+                let execution: VerifiedExecution = header.execution.ok_or_else(|| {
+                    custom_not_found(format!(
+                        "block {:?} is before Capella and has no execution payload header",
+                        header.root
+                    ))
+                })?;
+                Ok::<_, warp::Rejection>(warp::reply::json(&GenericResponse::from(execution)))
+            },
+        );
+
+    let routes = warp::get()
+        .and(get_header.or(get_execution_block_hash))
+        .recover(warp_utils::reject::handle_rejection)
+        // Add a `Server` header.
+        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()));
+
+    let (listening_socket, server) = warp::serve(routes)
+        .try_bind_with_graceful_shutdown(
+            SocketAddr::new(config.listen_addr, config.listen_port),
+            shutdown,
+        )
+        .map_err(|e| format!("Unable to start light client HTTP API: {e}"))?;
+
+    info!(
+        log,
+        "HTTP API started";
+        "listen_address" => %listening_socket,
+    );
+
+    Ok(server)
+}
@@ -0,0 +1,264 @@
+//! A light client which follows the chain from a trusted block root without trusting the beacon
+//! nodes it is connected to.
+//!
+//! Light client updates are fetched from the standard API of each beacon node in turn, and only
+//! applied once their sync committee signatures and Merkle proofs have been verified by the
+//! `LightClientStore` of the `light_client_verifier` crate. The latest finalized and optimistic
+//! headers, with their execution block hashes, are served by a local HTTP API.
+mod cli;
+mod config;
+mod http_api;
+
+pub use cli::{cli_app, CMD};
+pub use config::Config;
+pub use light_client_verifier::{LightClientStore, Update, VerifiedExecution, VerifiedHeader};
+
+use environment::RuntimeContext;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use parking_lot::RwLock;
+use slog::{debug, info, warn, Logger};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use types::{ChainSpec, EthSpec, Hash256, Slot};
+
+/// Timeout for requests to the beacon nodes.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+/// Time to wait before retrying after no beacon node could be used.
+const RETRY_DELAY: Duration = Duration::from_secs(6);
+/// The maximum number of sync committee periods to request updates for at once.
+const MAX_UPDATES_PER_REQUEST: u64 = 128;
+
+/// Bootstrap the light client and spawn its tasks on the `context`'s executor.
+pub async fn run<E: EthSpec>(context: RuntimeContext<E>, config: Config) -> Result<(), String> {
+    let log = context.log().clone();
+    let executor = context.executor.clone();
+    let spec = Arc::new(context.eth2_config.spec.clone());
+
+    let beacon_nodes = config
+        .beacon_nodes
+        .iter()
+        .map(|url| BeaconNodeHttpClient::new(url.clone(), Timeouts::set_all(HTTP_TIMEOUT)))
+        .collect::<Vec<_>>();
+
+    let (genesis_validators_root, slot_clock) = wait_for_genesis(&beacon_nodes, &spec, &log).await;
+    let store = bootstrap::<E>(
+        &beacon_nodes,
+        config.checkpoint_root,
+        genesis_validators_root,
+        spec,
+        &log,
+    )
+    .await;
+    info!(
+        log,
+        "Light client bootstrapped";
+        "slot" => store.finalized_header().beacon.slot,
+        "block_root" => ?store.finalized_header().root,
+    );
+    let store = Arc::new(RwLock::new(store));
+
+    let server = http_api::serve(
+        &config,
+        store.clone(),
+        executor.exit(),
+        log.new(slog::o!("service" => "http_api")),
+    )?;
+    executor.spawn_without_exit(server, "light_client_http_api");
+    executor.spawn(
+        sync(beacon_nodes, store, slot_clock, log),
+        "light_client_sync",
+    );
+
+    Ok(())
+}
+
+/// Wait for a beacon node on our network to report the genesis time and validators root.
+///
+/// The genesis validators root is not trusted: a wrong root only causes the signatures of the sync
+/// committee of the trusted block to fail verification.
+async fn wait_for_genesis(
+    beacon_nodes: &[BeaconNodeHttpClient],
+    spec: &ChainSpec,
+    log: &Logger,
+) -> (Hash256, SystemTimeSlotClock) {
+    loop {
+        for beacon_node in beacon_nodes {
+            match beacon_node.get_beacon_genesis().await {
+                Ok(genesis) if genesis.data.genesis_fork_version == spec.genesis_fork_version => {
+                    let slot_clock = SystemTimeSlotClock::new(
+                        spec.genesis_slot,
+                        Duration::from_secs(genesis.data.genesis_time),
+                        Duration::from_secs(spec.seconds_per_slot),
+                    );
+                    return (genesis.data.genesis_validators_root, slot_clock);
+                }
+                Ok(genesis) => warn!(
+                    log,
+                    "Beacon node is on a different network";
+                    "genesis_fork_version" => ?genesis.data.genesis_fork_version,
+                    "beacon_node" => %beacon_node,
+                ),
+                Err(e) => warn!(
+                    log,
+                    "Unable to read genesis from beacon node";
+                    "error" => %e,
+                    "beacon_node" => %beacon_node,
+                ),
+            }
+        }
+        sleep(RETRY_DELAY).await;
+    }
+}
+
+/// Initialize the store from the first beacon node with a valid bootstrap for
+/// `trusted_block_root`.
+async fn bootstrap<E: EthSpec>(
+    beacon_nodes: &[BeaconNodeHttpClient],
+    trusted_block_root: Hash256,
+    genesis_validators_root: Hash256,
+    spec: Arc<ChainSpec>,
+    log: &Logger,
+) -> LightClientStore<E> {
+    loop {
+        for beacon_node in beacon_nodes {
+            let result = match beacon_node
+                .get_light_client_bootstrap::<E>(trusted_block_root)
+                .await
+            {
+                Ok(Some(bootstrap)) => LightClientStore::new(
+                    trusted_block_root,
+                    &bootstrap.data,
+                    genesis_validators_root,
+                    spec.clone(),
+                )
+                .map_err(|e| format!("Invalid bootstrap: {e:?}")),
+                Ok(None) => Err("Bootstrap not found".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match result {
+                Ok(store) => return store,
+                Err(e) => warn!(
+                    log,
+                    "Unable to bootstrap from beacon node";
+                    "error" => e,
+                    "beacon_node" => %beacon_node,
+                ),
+            }
+        }
+        sleep(RETRY_DELAY).await;
+    }
+}
+
+/// Follow the chain by applying the updates from the first beacon node which provides valid ones,
+/// once per slot.
+async fn sync<E: EthSpec>(
+    beacon_nodes: Vec<BeaconNodeHttpClient>,
+    store: Arc<RwLock<LightClientStore<E>>>,
+    slot_clock: SystemTimeSlotClock,
+    log: Logger,
+) {
+    loop {
+        let Some(current_slot) = slot_clock.now() else {
+            sleep(RETRY_DELAY).await;
+            continue;
+        };
+
+        let mut synced = false;
+        for beacon_node in &beacon_nodes {
+            match sync_from(beacon_node, &store, current_slot, &log).await {
+                Ok(()) => {
+                    synced = true;
+                    break;
+                }
+                Err(e) => warn!(
+                    log,
+                    "Unable to sync from beacon node";
+                    "error" => e,
+                    "beacon_node" => %beacon_node,
+                ),
+            }
+        }
+        if !synced {
+            warn!(log, "No beacon node provided valid light client updates");
+        }
+
+        // Updates for a slot are available once its block has been imported.
+        let delay = slot_clock
+            .duration_to_next_slot()
+            .unwrap_or(RETRY_DELAY)
+            .saturating_add(slot_clock.slot_duration() / 3);
+        sleep(delay).await;
+    }
+}
+
+/// Apply the updates from `beacon_node` for each sync committee period from that of the store up
+/// to `current_slot`, and then its latest finality and optimistic updates.
+async fn sync_from<E: EthSpec>(
+    beacon_node: &BeaconNodeHttpClient,
+    store: &RwLock<LightClientStore<E>>,
+    current_slot: Slot,
+    log: &Logger,
+) -> Result<(), String> {
+    let process = |update: Update<E>| {
+        let mut store = store.write();
+        let finalized = store
+            .process_update(update, current_slot)
+            .map_err(|e| format!("Invalid update: {e:?}"))?;
+        if finalized {
+            let header = store.finalized_header();
+            info!(
+                log,
+                "New finalized header";
+                "slot" => header.beacon.slot,
+                "block_root" => ?header.root,
+                "execution_block_hash" => ?header.execution.as_ref().map(|e| e.block_hash),
+            );
+        }
+        Ok::<_, String>(())
+    };
+
+    let (store_period, current_period, next_known) = {
+        let store = store.read();
+        (
+            store.period().map_err(|e| format!("{e:?}"))?,
+            store
+                .period_at(current_slot)
+                .map_err(|e| format!("{e:?}"))?,
+            store.is_next_sync_committee_known(),
+        )
+    };
+    if !next_known || current_period > store_period {
+        let count = (current_period.saturating_sub(store_period) + 1).min(MAX_UPDATES_PER_REQUEST);
+        let updates = beacon_node
+            .get_light_client_updates::<E>(store_period, count)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Light client updates not provided")?;
+        debug!(
+            log,
+            "Fetched light client updates";
+            "start_period" => store_period,
+            "count" => updates.len(),
+        );
+        for update in updates {
+            process(update.data.into())?;
+        }
+    }
+
+    if let Some(update) = beacon_node
+        .get_beacon_light_client_finality_update::<E>()
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        process(update.data.into())?;
+    }
+    if let Some(update) = beacon_node
+        .get_beacon_light_client_optimistic_update::<E>()
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        process(update.data.into())?;
+    }
+    Ok(())
+}
@@ -12,7 +12,7 @@ use eth2_network_config::Eth2NetworkConfig;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::{future, StreamExt};
 
-use logging::SSELoggingComponents;
+use logging::{RepeatSuppressor, RuntimeLevelFilter, RuntimeLogLevels, SSELoggingComponents};
 use serde::{Deserialize, Serialize};
 use slog::{error, info, o, warn, Drain, Duplicate, Level, Logger};
 use sloggers::{file::FileLoggerBuilder, types::Format, types::Severity, Build};
@@ -60,6 +60,8 @@ pub struct LoggerConfig {
     pub compression: bool,
     pub is_restricted: bool,
     pub sse_logging: bool,
+    /// The maximum number of records logged from each call site per minute, or 0 for no limit.
+    pub log_repeat_limit: usize,
 }
 impl Default for LoggerConfig {
     fn default() -> Self {
@@ -76,6 +78,7 @@ impl Default for LoggerConfig {
             compression: false,
             is_restricted: true,
             sse_logging: false,
+            log_repeat_limit: 0,
         }
     }
 }
@@ -91,6 +94,8 @@ pub struct RuntimeContext<E: EthSpec> {
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    /// The log levels of targets, which may be changed at runtime.
+    pub runtime_log_levels: Option<RuntimeLogLevels>,
 }
 
 impl<E: EthSpec> RuntimeContext<E> {
@@ -104,6 +109,7 @@ impl<E: EthSpec> RuntimeContext<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            runtime_log_levels: self.runtime_log_levels.clone(),
         }
     }
 
@@ -123,6 +129,7 @@ pub struct EnvironmentBuilder<E: EthSpec> {
     runtime: Option<Arc<Runtime>>,
     log: Option<Logger>,
     sse_logging_components: Option<SSELoggingComponents>,
+    runtime_log_levels: Option<RuntimeLogLevels>,
     eth_spec_instance: E,
     eth2_config: Eth2Config,
     eth2_network_config: Option<Eth2NetworkConfig>,
@@ -135,6 +142,7 @@ impl EnvironmentBuilder<MinimalEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            runtime_log_levels: None,
             eth_spec_instance: MinimalEthSpec,
             eth2_config: Eth2Config::minimal(),
             eth2_network_config: None,
@@ -149,6 +157,7 @@ impl EnvironmentBuilder<MainnetEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            runtime_log_levels: None,
             eth_spec_instance: MainnetEthSpec,
             eth2_config: Eth2Config::mainnet(),
             eth2_network_config: None,
@@ -163,6 +172,7 @@ impl EnvironmentBuilder<GnosisEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            runtime_log_levels: None,
             eth_spec_instance: GnosisEthSpec,
             eth2_config: Eth2Config::gnosis(),
             eth2_network_config: None,
@@ -235,16 +245,27 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
                 .build()
         };
 
-        let stdout_drain = match config.debug_level.as_str() {
-            "info" => stdout_drain.filter_level(Level::Info),
-            "debug" => stdout_drain.filter_level(Level::Debug),
-            "trace" => stdout_drain.filter_level(Level::Trace),
-            "warn" => stdout_drain.filter_level(Level::Warning),
-            "error" => stdout_drain.filter_level(Level::Error),
-            "crit" => stdout_drain.filter_level(Level::Critical),
+        let stdout_level = match config.debug_level.as_str() {
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            "warn" => Level::Warning,
+            "error" => Level::Error,
+            "crit" => Level::Critical,
             unknown => return Err(format!("Unknown debug-level: {}", unknown)),
         };
 
+        // The levels of individual targets may be changed at runtime via the HTTP API, so each
+        // output filters records itself rather than using a fixed level.
+        let runtime_log_levels = RuntimeLogLevels::new();
+        self.runtime_log_levels = Some(runtime_log_levels.clone());
+
+        let stdout_drain = RuntimeLevelFilter::new(
+            RepeatSuppressor::new(stdout_drain, config.log_repeat_limit),
+            runtime_log_levels.clone(),
+            stdout_level,
+        );
+
         let stdout_logger = Logger::root(stdout_drain.fuse(), o!());
 
         // Disable file logging if values set to 0.
@@ -285,17 +306,18 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
         }
 
         let logfile_level = match config.logfile_debug_level.as_str() {
-            "info" => Severity::Info,
-            "debug" => Severity::Debug,
-            "trace" => Severity::Trace,
-            "warn" => Severity::Warning,
-            "error" => Severity::Error,
-            "crit" => Severity::Critical,
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            "warn" => Level::Warning,
+            "error" => Level::Error,
+            "crit" => Level::Critical,
             unknown => return Err(format!("Unknown loglevel-debug-level: {}", unknown)),
         };
 
         let file_logger = FileLoggerBuilder::new(&path)
-            .level(logfile_level)
+            // Records are filtered by `RuntimeLevelFilter` instead.
+            .level(Severity::Debug)
             .channel_size(LOG_CHANNEL_SIZE)
             .format(match config.logfile_format.as_deref() {
                 Some("JSON") => Format::Json,
@@ -308,7 +330,13 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             .build()
             .map_err(|e| format!("Unable to build file logger: {}", e))?;
 
-        let mut log = Logger::root(Duplicate::new(stdout_logger, file_logger).fuse(), o!());
+        let file_drain = RuntimeLevelFilter::new(
+            RepeatSuppressor::new(file_logger, config.log_repeat_limit),
+            runtime_log_levels,
+            logfile_level,
+        );
+
+        let mut log = Logger::root(Duplicate::new(stdout_logger, file_drain).fuse(), o!());
 
         info!(
             log,
@@ -355,6 +383,7 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             exit,
             log: self.log.ok_or("Cannot build environment without log")?,
             sse_logging_components: self.sse_logging_components,
+            runtime_log_levels: self.runtime_log_levels,
             eth_spec_instance: self.eth_spec_instance,
             eth2_config: self.eth2_config,
             eth2_network_config: self.eth2_network_config.map(Arc::new),
@@ -374,6 +403,7 @@ pub struct Environment<E: EthSpec> {
     exit: async_channel::Receiver<()>,
     log: Logger,
     sse_logging_components: Option<SSELoggingComponents>,
+    runtime_log_levels: Option<RuntimeLogLevels>,
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
@@ -401,6 +431,7 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            runtime_log_levels: self.runtime_log_levels.clone(),
         }
     }
 
@@ -417,9 +448,35 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            runtime_log_levels: self.runtime_log_levels.clone(),
         }
     }
 
+    /// Returns a `Context` for a service of another network, where the `service_name` is added to
+    /// the logger output.
+    ///
+    /// The network must use the same preset as this environment.
+    pub fn network_service_context(
+        &self,
+        service_name: String,
+        eth2_network_config: Eth2NetworkConfig,
+    ) -> Result<RuntimeContext<E>, String> {
+        let eth_spec_id = eth2_network_config.eth_spec_id()?;
+        if eth_spec_id != E::spec_name() {
+            return Err(format!(
+                "The {} preset of {} differs from the {} preset of this process",
+                eth_spec_id,
+                service_name,
+                E::spec_name()
+            ));
+        }
+
+        let mut context = self.service_context(service_name);
+        context.eth2_config.spec = eth2_network_config.chain_spec::<E>()?;
+        context.eth2_network_config = Some(Arc::new(eth2_network_config));
+        Ok(context)
+    }
+
     /// Block the current thread until a shutdown signal is received.
     ///
     /// This can be either the user Ctrl-C'ing or a task requesting to shutdown.
@@ -0,0 +1,224 @@
+//! Additional beacon nodes which run in the same process as the one started on the command line.
+//!
+//! Each instance is configured by a file of the arguments it would be started with by
+//! `lighthouse bn`, separated by whitespace. Lines starting with `#` are ignored:
+//!
+//! ```text
+//! # A devnet alongside mainnet.
+//! --testnet-dir /var/lib/devnet/config
+//! --datadir /var/lib/devnet
+//! --port 9100
+//! --http --http-port 5152 --http-path-prefix devnet
+//! ```
+use beacon_node::ClientConfig;
+use clap::{ArgMatches, Command};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+/// The beacon node flag for the argument files of the additional instances.
+pub const ADDITIONAL_INSTANCE_FLAG: &str = "additional-instance";
+
+/// A beacon node to run alongside the one started on the command line.
+pub struct AdditionalInstance {
+    /// A name for the instance, which is added to its logs.
+    pub name: String,
+    /// The matches of the `beacon_node` subcommand of the instance, which include the global
+    /// flags such as `--network`.
+    pub matches: ArgMatches,
+}
+
+/// Parse the argument files of the additional instances of the beacon node started by `matches`.
+pub fn parse_additional_instances(
+    cli: &Command,
+    matches: &ArgMatches,
+) -> Result<Vec<AdditionalInstance>, String> {
+    let Some(bn_matches) = matches.subcommand_matches("beacon_node") else {
+        return Ok(vec![]);
+    };
+    let Some(paths) = bn_matches.get_many::<String>(ADDITIONAL_INSTANCE_FLAG) else {
+        return Ok(vec![]);
+    };
+
+    paths
+        .map(|path| {
+            let path = PathBuf::from(path);
+            let args = fs::read_to_string(&path)
+                .map_err(|e| format!("Unable to read {}: {e}", path.display()))?;
+            parse_additional_instance(cli, &path, &args)
+        })
+        .collect()
+}
+
+fn parse_additional_instance(
+    cli: &Command,
+    path: &Path,
+    args: &str,
+) -> Result<AdditionalInstance, String> {
+    let args = args
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(str::split_whitespace);
+    let matches = cli
+        .clone()
+        .try_get_matches_from(["lighthouse", "beacon_node"].into_iter().chain(args))
+        .map_err(|e| format!("Invalid arguments in {}: {e}", path.display()))?;
+
+    let bn_matches = matches
+        .subcommand_matches("beacon_node")
+        .ok_or("Additional instances must be beacon nodes")?;
+    if bn_matches.contains_id(ADDITIONAL_INSTANCE_FLAG) {
+        return Err(format!(
+            "--{ADDITIONAL_INSTANCE_FLAG} is not allowed in {}",
+            path.display()
+        ));
+    }
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    Ok(AdditionalInstance {
+        name,
+        matches: bn_matches.clone(),
+    })
+}
+
+/// Check that the named `configs` of the beacon nodes of this process can run alongside each
+/// other. The first config is that of the primary beacon node.
+///
+/// Each instance must have its own data directory and ports. The metrics of every instance are
+/// recorded in the single registry of the process, so they are only served by the primary, which
+/// labels the per-chain metrics of the additional instances with their names.
+pub fn check_instance_configs(configs: &[(&str, &ClientConfig)]) -> Result<(), String> {
+    if configs.len() < 2 {
+        return Ok(());
+    }
+
+    for (name, config) in &configs[1..] {
+        if config.http_metrics.enabled {
+            return Err(format!(
+                "Instance {name} must not enable --metrics, the metrics of additional instances \
+                 are served by the metrics server of the primary beacon node"
+            ));
+        }
+        if config.monitoring_api.is_some() {
+            return Err(format!(
+                "Instance {name} must not enable --monitoring-endpoint, only the primary beacon \
+                 node can be monitored"
+            ));
+        }
+    }
+
+    for (i, (name, config)) in configs.iter().enumerate() {
+        for (other_name, other_config) in &configs[..i] {
+            if config.data_dir == other_config.data_dir {
+                return Err(format!(
+                    "Instances {other_name} and {name} must use a separate --datadir"
+                ));
+            }
+
+            for listener in listeners(config) {
+                if let Some(other) = listeners(other_config)
+                    .into_iter()
+                    .find(|other| listener.clashes_with(other))
+                {
+                    return Err(format!(
+                        "The {} port {} of instance {name} clashes with the {} port {} of \
+                         instance {other_name}",
+                        listener.name,
+                        listener.addr.port(),
+                        other.name,
+                        other.addr.port(),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Transport {
+    Tcp,
+    Udp,
+}
+
+/// A socket which a beacon node listens on.
+struct Listener {
+    name: &'static str,
+    transport: Transport,
+    addr: SocketAddr,
+}
+
+impl Listener {
+    /// Returns `true` if both listeners can not be bound at once. Port 0 selects an unused port.
+    fn clashes_with(&self, other: &Listener) -> bool {
+        self.transport == other.transport
+            && self.addr.port() != 0
+            && self.addr.port() == other.addr.port()
+            && self.addr.is_ipv4() == other.addr.is_ipv4()
+            && (self.addr.ip() == other.addr.ip()
+                || self.addr.ip().is_unspecified()
+                || other.addr.ip().is_unspecified())
+    }
+}
+
+/// Returns the sockets which a beacon node with `config` listens on.
+fn listeners(config: &ClientConfig) -> Vec<Listener> {
+    let network = &config.network;
+    let mut listeners = vec![];
+    let v4 = network.listen_addrs().v4().map(|addr| {
+        (
+            addr.tcp_socket_addr(),
+            addr.discovery_socket_addr(),
+            addr.quic_socket_addr(),
+        )
+    });
+    let v6 = network.listen_addrs().v6().map(|addr| {
+        (
+            addr.tcp_socket_addr(),
+            addr.discovery_socket_addr(),
+            addr.quic_socket_addr(),
+        )
+    });
+    for (tcp, discovery, quic) in v4.into_iter().chain(v6) {
+        listeners.push(Listener {
+            name: "libp2p",
+            transport: Transport::Tcp,
+            addr: tcp,
+        });
+        if !network.disable_discovery {
+            listeners.push(Listener {
+                name: "discovery",
+                transport: Transport::Udp,
+                addr: discovery,
+            });
+        }
+        if !network.disable_quic_support {
+            listeners.push(Listener {
+                name: "QUIC",
+                transport: Transport::Udp,
+                addr: quic,
+            });
+        }
+    }
+    if config.http_api.enabled {
+        listeners.push(Listener {
+            name: "HTTP",
+            transport: Transport::Tcp,
+            addr: SocketAddr::new(config.http_api.listen_addr, config.http_api.listen_port),
+        });
+    }
+    if config.http_metrics.enabled {
+        listeners.push(Listener {
+            name: "metrics",
+            transport: Transport::Tcp,
+            addr: SocketAddr::new(
+                config.http_metrics.listen_addr,
+                config.http_metrics.listen_port,
+            ),
+        });
+    }
+    listeners
+}
@@ -1,4 +1,5 @@
 mod cli;
+mod instances;
 mod metrics;
 
 use beacon_node::ProductionBeaconNode;
@@ -14,6 +15,7 @@ use environment::{EnvironmentBuilder, LoggerConfig};
 use eth2_network_config::{Eth2NetworkConfig, DEFAULT_HARDCODED_NETWORK, HARDCODED_NET_NAMES};
 use ethereum_hashing::have_sha_extensions;
 use futures::TryFutureExt;
+use instances::{check_instance_configs, parse_additional_instances, AdditionalInstance};
 use lazy_static::lazy_static;
 use lighthouse_version::VERSION;
 use malloc_utils::configure_memory_allocator;
@@ -207,6 +209,34 @@ fn main() {
                 .global(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("log-repeat-limit")
+                .long("log-repeat-limit")
+                .value_name("COUNT")
+                .help(
+                    "The maximum number of messages logged from the same place in the code per \
+                    minute. Further messages are suppressed, and the number suppressed is logged \
+                    once the minute has passed. Critical messages are never suppressed. If set \
+                    to 0, messages are never suppressed.",
+                )
+                .action(ArgAction::Set)
+                .default_value("0")
+                .global(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("tracing-endpoint")
+                .long("tracing-endpoint")
+                .value_name("URL")
+                .help(
+                    "The OTLP gRPC endpoint of an OpenTelemetry collector (e.g. \
+                    http://localhost:4317), to which spans tracing the critical path of the \
+                    beacon node are exported. Tracing is disabled by default.",
+                )
+                .action(ArgAction::Set)
+                .global(true)
+                .display_order(0)
+        )
         .arg(
             Arg::new("log-color")
                 .long("log-color")
@@ -413,11 +443,16 @@ fn main() {
         .subcommand(boot_node::cli_app())
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
-        .subcommand(validator_manager::cli_app());
+        .subcommand(validator_manager::cli_app())
+        .subcommand(slasher_standalone::cli_app())
+        .subcommand(light_client::cli_app());
+
+    #[cfg(feature = "simulator")]
+    let cli = cli.subcommand(simulator::cli_app().name("simulate"));
 
     let cli = LighthouseSubcommands::augment_subcommands(cli);
 
-    let matches = cli.get_matches();
+    let matches = cli.clone().get_matches();
 
     // Configure the allocator early in the process, before it has the chance to use the default values for
     // anything important.
@@ -436,6 +471,18 @@ fn main() {
         }
     }
 
+    // The simulator builds its own environment for a local network, so it circumvents ours.
+    #[cfg(feature = "simulator")]
+    if let Some(simulate_matches) = matches.subcommand_matches("simulate") {
+        match simulator::run_simulation(simulate_matches) {
+            Ok(()) => exit(0),
+            Err(e) => {
+                eprintln!("Simulation exited with error: {}", e);
+                exit(1)
+            }
+        }
+    }
+
     let result = get_eth2_network_config(&matches).and_then(|eth2_network_config| {
         let eth_spec_id = eth2_network_config.eth_spec_id()?;
 
@@ -458,12 +505,29 @@ fn main() {
             return Ok(());
         }
 
+        let additional_instances = parse_additional_instances(&cli, &matches)?;
+
         match eth_spec_id {
-            EthSpecId::Mainnet => run(EnvironmentBuilder::mainnet(), &matches, eth2_network_config),
+            EthSpecId::Mainnet => run(
+                EnvironmentBuilder::mainnet(),
+                &matches,
+                eth2_network_config,
+                additional_instances,
+            ),
             #[cfg(feature = "gnosis")]
-            EthSpecId::Gnosis => run(EnvironmentBuilder::gnosis(), &matches, eth2_network_config),
+            EthSpecId::Gnosis => run(
+                EnvironmentBuilder::gnosis(),
+                &matches,
+                eth2_network_config,
+                additional_instances,
+            ),
             #[cfg(feature = "spec-minimal")]
-            EthSpecId::Minimal => run(EnvironmentBuilder::minimal(), &matches, eth2_network_config),
+            EthSpecId::Minimal => run(
+                EnvironmentBuilder::minimal(),
+                &matches,
+                eth2_network_config,
+                additional_instances,
+            ),
             #[cfg(not(all(feature = "spec-minimal", feature = "gnosis")))]
             other => {
                 eprintln!(
@@ -494,6 +558,7 @@ fn run<E: EthSpec>(
     environment_builder: EnvironmentBuilder<E>,
     matches: &ArgMatches,
     eth2_network_config: Eth2NetworkConfig,
+    additional_instances: Vec<AdditionalInstance>,
 ) -> Result<(), String> {
     if std::mem::size_of::<usize>() != 8 {
         return Err(format!(
@@ -535,6 +600,12 @@ fn run<E: EthSpec>(
 
     let logfile_compress = matches.get_flag("logfile-compress");
 
+    let log_repeat_limit: usize = matches
+        .get_one::<String>("log-repeat-limit")
+        .ok_or("Expected --log-repeat-limit flag")?
+        .parse()
+        .map_err(|e| format!("Failed to parse `log-repeat-limit`: {:?}", e))?;
+
     let logfile_restricted = !matches.get_flag("logfile-no-restricted-perms");
 
     // Construct the path to the log file.
@@ -589,6 +660,7 @@ fn run<E: EthSpec>(
         compression: logfile_compress,
         is_restricted: logfile_restricted,
         sse_logging,
+        log_repeat_limit,
     };
 
     let builder = environment_builder.initialize_logger(logger_config.clone())?;
@@ -627,7 +699,12 @@ fn run<E: EthSpec>(
 
     let path = tracing_log_path.clone().unwrap();
 
-    logging::create_tracing_layer(path);
+    let tracing_endpoint: Option<String> = clap_utils::parse_optional(matches, "tracing-endpoint")?;
+    {
+        // The OTLP exporter runs on the Tokio runtime.
+        let _runtime_guard = environment.runtime().enter();
+        logging::create_tracing_layer(path, tracing_endpoint.as_deref());
+    }
 
     // Allow Prometheus to export the time at which the process was started.
     metrics::expose_process_start_time(&log);
@@ -707,21 +784,95 @@ fn run<E: EthSpec>(
             // Dump configs if `dump-config` or `dump-chain-config` flags are set
             clap_utils::check_dump_configs::<_, E>(matches, &config, &context.eth2_config.spec)?;
 
+            #[cfg(feature = "watch")]
+            let watch_config = matches
+                .get_one::<String>("watch-config")
+                .map(|path| watch::config::Config::load_from_file(path.clone()))
+                .transpose()?;
+            #[cfg(not(feature = "watch"))]
+            if matches.get_one::<String>("watch-config").is_some() {
+                return Err(
+                    "--watch-config requires Lighthouse to be compiled with the `watch` feature"
+                        .into(),
+                );
+            }
+
+            // Configure all of the additional instances before any beacon node is started, so
+            // that a misconfigured instance prevents the process from starting.
+            let mut instances = Vec::with_capacity(additional_instances.len());
+            for instance in additional_instances {
+                let instance_network_config = get_eth2_network_config(&instance.matches)?;
+                let instance_context = environment
+                    .network_service_context(instance.name.clone(), instance_network_config)?;
+                let mut instance_config =
+                    beacon_node::get_config::<E>(&instance.matches, &instance_context)?;
+                instance_config.logger_config = logger_config.clone();
+                instances.push((instance.name, instance_context, instance_config));
+            }
+            check_instance_configs(
+                &std::iter::once(("primary", &config))
+                    .chain(
+                        instances
+                            .iter()
+                            .map(|(name, _, config)| (name.as_str(), config)),
+                    )
+                    .collect::<Vec<_>>(),
+            )?;
+
             let shutdown_flag = matches.get_flag("immediate-shutdown");
             if shutdown_flag {
                 info!(log, "Beacon node immediate shutdown triggered.");
                 return Ok(());
             }
 
+            for (name, instance_context, instance_config) in instances {
+                let log = instance_context.log().clone();
+                let executor = instance_context.executor.clone();
+                info!(log, "Starting additional beacon node"; "name" => &name);
+                executor.clone().spawn(
+                    async move {
+                        match ProductionBeaconNode::new(instance_context, instance_config).await {
+                            Ok(node) => node.register_instance_metrics(name),
+                            Err(e) => {
+                                crit!(log, "Failed to start additional beacon node"; "reason" => e);
+                                // Ignore the error since it always occurs during normal operation
+                                // when shutting down.
+                                let _ =
+                                    executor.shutdown_sender().try_send(ShutdownReason::Failure(
+                                        "Failed to start additional beacon node",
+                                    ));
+                            }
+                        }
+                    },
+                    "additional_beacon_node",
+                );
+            }
+
             executor.clone().spawn(
                 async move {
-                    if let Err(e) = ProductionBeaconNode::new(context.clone(), config).await {
-                        crit!(log, "Failed to start beacon node"; "reason" => e);
-                        // Ignore the error since it always occurs during normal operation when
-                        // shutting down.
-                        let _ = executor
-                            .shutdown_sender()
-                            .try_send(ShutdownReason::Failure("Failed to start beacon node"));
+                    match ProductionBeaconNode::new(context.clone(), config).await {
+                        #[cfg(feature = "watch")]
+                        Ok(node) => {
+                            if let (Some(watch_config), Some(chain)) =
+                                (watch_config, node.beacon_chain())
+                            {
+                                if let Err(e) =
+                                    watch::ingest::spawn_ingest(executor, chain, watch_config)
+                                {
+                                    crit!(log, "Failed to start watch ingest"; "reason" => e);
+                                }
+                            }
+                        }
+                        #[cfg(not(feature = "watch"))]
+                        Ok(_) => {}
+                        Err(e) => {
+                            crit!(log, "Failed to start beacon node"; "reason" => e);
+                            // Ignore the error since it always occurs during normal operation
+                            // when shutting down.
+                            let _ = executor
+                                .shutdown_sender()
+                                .try_send(ShutdownReason::Failure("Failed to start beacon node"));
+                        }
                     }
                 },
                 "beacon_node",
@@ -759,6 +910,64 @@ fn run<E: EthSpec>(
                 "validator_client",
             );
         }
+        Some((slasher_standalone::CMD, matches)) => {
+            let context = environment.core_context();
+            let log = context.log().clone();
+            let executor = context.executor.clone();
+            let config = slasher_standalone::Config::from_cli(matches)
+                .map_err(|e| format!("Unable to initialize slasher config: {}", e))?;
+            // Dump configs if `dump-config` or `dump-chain-config` flags are set
+            clap_utils::check_dump_configs::<_, E>(matches, &config, &context.eth2_config.spec)?;
+
+            let shutdown_flag = matches.get_flag("immediate-shutdown");
+            if shutdown_flag {
+                info!(log, "Slasher immediate shutdown triggered.");
+                return Ok(());
+            }
+
+            executor.clone().spawn(
+                async move {
+                    if let Err(e) = slasher_standalone::run(context, config).await {
+                        crit!(log, "Failed to start slasher"; "reason" => e);
+                        // Ignore the error since it always occurs during normal operation when
+                        // shutting down.
+                        let _ = executor
+                            .shutdown_sender()
+                            .try_send(ShutdownReason::Failure("Failed to start slasher"));
+                    }
+                },
+                "slasher",
+            );
+        }
+        Some((light_client::CMD, matches)) => {
+            let context = environment.core_context();
+            let log = context.log().clone();
+            let executor = context.executor.clone();
+            let config = light_client::Config::from_cli(matches)
+                .map_err(|e| format!("Unable to initialize light client config: {}", e))?;
+            // Dump configs if `dump-config` or `dump-chain-config` flags are set
+            clap_utils::check_dump_configs::<_, E>(matches, &config, &context.eth2_config.spec)?;
+
+            let shutdown_flag = matches.get_flag("immediate-shutdown");
+            if shutdown_flag {
+                info!(log, "Light client immediate shutdown triggered.");
+                return Ok(());
+            }
+
+            executor.clone().spawn(
+                async move {
+                    if let Err(e) = light_client::run(context, config).await {
+                        crit!(log, "Failed to start light client"; "reason" => e);
+                        // Ignore the error since it always occurs during normal operation when
+                        // shutting down.
+                        let _ = executor
+                            .shutdown_sender()
+                            .try_send(ShutdownReason::Failure("Failed to start light client"));
+                    }
+                },
+                "light_client",
+            );
+        }
         _ => {
             crit!(log, "No subcommand supplied. See --help .");
             return Err("No subcommand supplied.".into());
@@ -771,6 +980,10 @@ fn run<E: EthSpec>(
 
     environment.fire_signal();
 
+    if tracing_endpoint.is_some() {
+        logging::shutdown_otlp();
+    }
+
     // Shutdown the environment once all tasks have completed.
     environment.shutdown_on_idle();
 
@@ -13,7 +13,7 @@ use account_manager::{
     CMD as ACCOUNT_CMD, WALLETS_DIR_FLAG, *,
 };
 use account_utils::{
-    eth2_keystore::KeystoreBuilder,
+    eth2_keystore::{json_keystore::Kdf, Keystore, KeystoreBuilder},
     validator_definitions::{SigningDefinition, ValidatorDefinition, ValidatorDefinitions},
     ZeroizeString,
 };
@@ -26,7 +26,7 @@ use std::process::{Child, Command, Output, Stdio};
 use std::str::from_utf8;
 use tempfile::{tempdir, TempDir};
 use types::{Keypair, PublicKey};
-use validator_dir::ValidatorDir;
+use validator_dir::{ValidatorDir, VOTING_KEYSTORE_FILE};
 
 /// Returns the `lighthouse account` command.
 fn account_cmd() -> Command {
@@ -244,6 +244,17 @@ impl TestValidator {
         quantity_flag: &str,
         quantity: usize,
         store_withdrawal_key: bool,
+    ) -> Result<Vec<String>, String> {
+        self.create_with_args(quantity_flag, quantity, store_withdrawal_key, &[])
+    }
+
+    /// Create validators with some `extra_args`, returning a list of validator pubkeys on success.
+    pub fn create_with_args(
+        &self,
+        quantity_flag: &str,
+        quantity: usize,
+        store_withdrawal_key: bool,
+        extra_args: &[&str],
     ) -> Result<Vec<String>, String> {
         let mut cmd = validator_cmd();
         cmd.arg(format!("--{}", VALIDATOR_DIR_FLAG))
@@ -260,12 +271,13 @@ impl TestValidator {
             .arg(format!("--{}", DEPOSIT_GWEI_FLAG))
             .arg("32000000000")
             .arg(format!("--{}", quantity_flag))
-            .arg(format!("{}", quantity));
+            .arg(format!("{}", quantity))
+            .args(extra_args);
 
         let output = if store_withdrawal_key {
-            output_result(cmd.arg(format!("--{}", STORE_WITHDRAW_FLAG))).unwrap()
+            output_result(cmd.arg(format!("--{}", STORE_WITHDRAW_FLAG)))?
         } else {
-            output_result(&mut cmd).unwrap()
+            output_result(&mut cmd)?
         };
 
         let stdout = from_utf8(&output.stdout)
@@ -399,6 +411,71 @@ fn validator_create() {
     assert_eq!(dir_validator_count(validator_dir.path()), 6);
 }
 
+#[test]
+fn validator_create_with_kdf_and_first_index() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+
+    let first_index = "3";
+    let pubkeys = validator
+        .create_with_args(
+            COUNT_FLAG,
+            2,
+            false,
+            &[
+                "--kdf",
+                "pbkdf2",
+                &format!("--{}", FIRST_INDEX_FLAG),
+                first_index,
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(pubkeys.len(), 2);
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+
+    for (i, pubkey) in pubkeys.iter().enumerate() {
+        let keystore =
+            Keystore::from_json_file(validator_dir.path().join(pubkey).join(VOTING_KEYSTORE_FILE))
+                .unwrap();
+
+        assert_eq!(
+            keystore.path().unwrap(),
+            format!("m/12381/3600/{}/0/0", 3 + i)
+        );
+        assert!(matches!(keystore.kdf(), Kdf::Pbkdf2(_)));
+    }
+
+    check_slashing_protection(
+        &validator_dir,
+        pubkeys.iter().map(|pubkey| {
+            ValidatorDir::open(validator_dir.path().join(pubkey))
+                .unwrap()
+                .voting_keypair(&secrets_dir)
+                .unwrap()
+                .pk
+        }),
+    );
+
+    // Should refuse to re-use indices that have already been allocated by the wallet.
+    validator
+        .create_with_args(
+            COUNT_FLAG,
+            1,
+            false,
+            &[&format!("--{}", FIRST_INDEX_FLAG), first_index],
+        )
+        .unwrap_err();
+
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+}
+
 #[test]
 fn validator_import_launchpad() {
     const PASSWORD: &str = "cats";
@@ -2,15 +2,18 @@ use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
-    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
+    DisallowedReOrgOffsets, InvalidPayloadHandling, ReOrgConfig, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
+    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
 };
 use beacon_node::beacon_chain::graffiti_calculator::GraffitiOrigin;
-use beacon_processor::BeaconProcessorConfig;
+use beacon_processor::{BeaconProcessorConfig, WorkClass};
 use eth1::Eth1Endpoint;
+use execution_layer::request_policy::{ClassPolicy, MethodPolicy, RequestPolicy};
+use http_api::{RequestLimits, RouteClass};
 use lighthouse_network::PeerId;
 use lighthouse_version;
-use std::fs::File;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::path::Path;
@@ -21,7 +24,9 @@ use std::string::ToString;
 use std::time::Duration;
 use tempfile::TempDir;
 use types::non_zero_usize::new_non_zero_usize;
-use types::{Address, Checkpoint, Epoch, ExecutionBlockHash, Hash256, MainnetEthSpec};
+use types::{
+    Address, Checkpoint, DepositTreeSnapshot, Epoch, ExecutionBlockHash, Hash256, MainnetEthSpec,
+};
 use unused_port::{unused_tcp4_port, unused_tcp6_port, unused_udp4_port, unused_udp6_port};
 
 const DEFAULT_ETH1_ENDPOINT: &str = "http://localhost:8545/";
@@ -136,6 +141,26 @@ fn allow_insecure_genesis_sync_enabled() {
         });
 }
 
+#[test]
+fn bls_accelerator_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.bls_accelerator, None));
+}
+
+#[test]
+fn bls_accelerator_flag() {
+    CommandLineTest::new()
+        .flag("bls-accelerator", Some("/opt/bls/libaccel.so"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.bls_accelerator,
+                Some(PathBuf::from("/opt/bls/libaccel.so"))
+            )
+        });
+}
+
 #[test]
 fn wss_checkpoint_flag() {
     let state = Some(Checkpoint {
@@ -225,6 +250,37 @@ fn checkpoint_sync_url_timeout_default() {
         });
 }
 
+#[test]
+fn deposit_snapshot_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("deposit_snapshot.json");
+    let snapshot = DepositTreeSnapshot::default();
+    fs::write(
+        &path,
+        serde_json::to_vec(&serde_json::json!({ "data": snapshot })).unwrap(),
+    )
+    .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("checkpoint-sync-url", Some("http://localhost:5052"))
+        .flag("deposit-snapshot-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.deposit_snapshot, Some(snapshot.clone())));
+}
+#[test]
+#[should_panic]
+fn deposit_snapshot_file_flag_without_checkpoint() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("deposit_snapshot.json");
+    fs::write(
+        &path,
+        serde_json::to_vec(&DepositTreeSnapshot::default()).unwrap(),
+    )
+    .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("deposit-snapshot-file", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+
 #[test]
 fn prepare_payload_lookahead_default() {
     CommandLineTest::new()
@@ -250,6 +306,51 @@ fn prepare_payload_lookahead_shorter() {
         });
 }
 
+#[test]
+fn block_production_budget_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.block_production_budget, None));
+}
+
+#[test]
+fn block_production_budget_flag() {
+    CommandLineTest::new()
+        .flag("block-production-budget", Some("1000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.block_production_budget,
+                Some(Duration::from_millis(1000))
+            )
+        });
+}
+
+#[test]
+fn block_production_endpoint_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.block_production_endpoint, None));
+}
+
+#[test]
+fn block_production_endpoint_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("block-production-endpoint", Some("http://localhost:5052"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .http_api
+                    .block_production_endpoint
+                    .as_ref()
+                    .map(|url| url.full.as_str()),
+                Some("http://localhost:5052/")
+            )
+        });
+}
+
 #[test]
 fn always_prepare_payload_default() {
     CommandLineTest::new()
@@ -305,6 +406,55 @@ fn reset_payload_statuses_present() {
         .with_config(|config| assert!(config.chain.always_reset_payload_statuses));
 }
 
+#[test]
+fn invalid_payload_handling_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.invalid_payload_handling,
+                InvalidPayloadHandling::Reorg
+            );
+            assert_eq!(config.chain.invalid_payload_max_reorg_depth, None);
+        });
+}
+
+#[test]
+fn invalid_payload_handling_halt() {
+    CommandLineTest::new()
+        .flag("invalid-payload-handling", Some("halt"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.invalid_payload_handling,
+                InvalidPayloadHandling::Halt
+            )
+        });
+}
+
+#[test]
+fn invalid_payload_max_reorg_depth_flag() {
+    CommandLineTest::new()
+        .flag("invalid-payload-max-reorg-depth", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.invalid_payload_max_reorg_depth, Some(4)));
+}
+
+#[test]
+fn strict_optimistic_sync_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.strict_optimistic_sync));
+}
+
+#[test]
+fn strict_optimistic_sync_flag() {
+    CommandLineTest::new()
+        .flag("strict-optimistic-sync", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.strict_optimistic_sync));
+}
+
 #[test]
 fn freezer_dir_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
@@ -397,6 +547,13 @@ fn dummy_eth1_flag() {
         .with_config(|config| assert!(config.dummy_eth1_backend));
 }
 #[test]
+#[should_panic]
+fn stub_eth1_votes_flag_without_electra() {
+    CommandLineTest::new()
+        .flag("stub-eth1-votes", None)
+        .run_with_zero_port();
+}
+#[test]
 fn eth1_flag() {
     CommandLineTest::new()
         .flag("eth1", None)
@@ -522,6 +679,85 @@ fn execution_timeout_multiplier_flag() {
         });
 }
 #[test]
+fn execution_request_policy_default() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.request_policy, RequestPolicy::default());
+        });
+}
+#[test]
+fn execution_request_policy_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let policy_path = dir.path().join("request-policy.json");
+    std::fs::write(
+        &policy_path,
+        r#"{
+            "critical": { "retries": 1, "backoff_ms": 50 },
+            "background": { "retries": 3, "deadline_ms": 30000 },
+            "methods": { "engine_getPayloadV3": { "retries": 0 } }
+        }"#,
+    )
+    .expect("Unable to write request policy");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag("execution-request-policy", policy_path.to_str())
+        .flag("execution-critical-retries", Some("2"))
+        .flag("execution-critical-deadline", Some("4000"))
+        .flag("execution-retry-backoff", Some("100"))
+        .flag(
+            "execution-method-timeouts",
+            Some("engine_newPayloadV3=12000,engine_getPayloadV3=1500"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let policy = &config.execution_layer.as_ref().unwrap().request_policy;
+            assert_eq!(
+                policy.critical,
+                ClassPolicy {
+                    retries: 2,
+                    backoff_ms: 100,
+                    deadline_ms: Some(4000),
+                }
+            );
+            assert_eq!(
+                policy.background,
+                ClassPolicy {
+                    retries: 3,
+                    backoff_ms: 100,
+                    deadline_ms: Some(30000),
+                }
+            );
+            assert_eq!(
+                policy.methods["engine_newPayloadV3"],
+                MethodPolicy {
+                    timeout_ms: Some(12000),
+                    retries: None,
+                    backoff_ms: None,
+                }
+            );
+            assert_eq!(
+                policy.methods["engine_getPayloadV3"],
+                MethodPolicy {
+                    timeout_ms: Some(1500),
+                    retries: Some(0),
+                    backoff_ms: None,
+                }
+            );
+        });
+}
+#[test]
 fn bellatrix_execution_endpoints_flag() {
     run_bellatrix_execution_endpoints_flag_test("execution-endpoints")
 }
@@ -750,6 +986,25 @@ fn jwt_optional_alias_flags() {
     run_jwt_optional_flags_test("jwt-secrets", "jwt-id", "jwt-version");
 }
 #[test]
+fn jwt_optional_flags_with_secret_key() {
+    let id = "bn-1";
+    let version = "Lighthouse-v2.1.3";
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt-secret-key",
+            Some("0000000000000000000000000000000000000000000000000000000000000000"),
+        )
+        .flag("execution-jwt-id", Some(id))
+        .flag("execution-jwt-version", Some(version))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let el_config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(el_config.jwt_id, Some(id.to_string()));
+            assert_eq!(el_config.jwt_version, Some(version.to_string()));
+        });
+}
+#[test]
 fn terminal_total_difficulty_override_flag() {
     use beacon_node::beacon_chain::types::Uint256;
     CommandLineTest::new()
@@ -852,6 +1107,38 @@ fn network_shutdown_after_sync_disabled_flag() {
         .with_config(|config| assert!(!config.network.shutdown_after_sync));
 }
 #[test]
+fn network_geoip_db_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.geoip_db, None));
+}
+#[test]
+fn network_geoip_db_flag() {
+    let path = "/tmp/geoip.csv";
+    CommandLineTest::new()
+        .flag("geoip-db", Some(path))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.geoip_db, Some(PathBuf::from(path))));
+}
+#[test]
+fn network_gossip_trace_capacity_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.gossip_trace_capacity,
+                lighthouse_network::types::DEFAULT_GOSSIP_TRACE_CAPACITY
+            )
+        });
+}
+#[test]
+fn network_gossip_trace_capacity_flag() {
+    CommandLineTest::new()
+        .flag("gossip-trace-capacity", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.gossip_trace_capacity, 0));
+}
+#[test]
 fn network_listen_address_flag_v4() {
     let addr = "127.0.0.2".parse::<Ipv4Addr>().unwrap();
     CommandLineTest::new()
@@ -1194,6 +1481,69 @@ fn disable_quic_flag() {
         .with_config(|config| assert!(config.network.disable_quic_support));
 }
 #[test]
+fn ip_preference_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.ip_preference, None));
+}
+#[test]
+fn ip_preference_flag() {
+    CommandLineTest::new()
+        .flag("ip-preference", Some("ipv6"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.ip_preference,
+                Some(lighthouse_network::IpVersion::Ipv6)
+            )
+        });
+}
+#[test]
+fn outbound_only_flag() {
+    CommandLineTest::new()
+        .flag("outbound-only", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.network.outbound_only);
+            assert!(!config.network.upnp_enabled);
+        });
+}
+#[test]
+fn trusted_peers_only_flag() {
+    let peers = vec![PeerId::random(), PeerId::random()];
+    let libp2p_address = format!("/ip4/127.0.0.1/tcp/9000/p2p/{}", peers[0]);
+    CommandLineTest::new()
+        .flag("trusted-peers", Some(&format!("{},{}", peers[0], peers[1])))
+        .flag("libp2p-addresses", Some(&libp2p_address))
+        .flag("trusted-peers-only", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.network.trusted_peers_only);
+            assert!(config.network.disable_discovery);
+            assert_eq!(config.network.libp2p_nodes[0].to_string(), libp2p_address);
+        });
+}
+#[test]
+#[should_panic]
+fn trusted_peers_only_flag_without_trusted_peers() {
+    CommandLineTest::new()
+        .flag("trusted-peers-only", None)
+        .run_with_zero_port();
+}
+#[test]
+fn aggregator_mesh_boost_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.aggregator_mesh_boost));
+}
+#[test]
+fn disable_aggregator_mesh_boost_flag() {
+    CommandLineTest::new()
+        .flag("disable-aggregator-mesh-boost", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.aggregator_mesh_boost));
+}
+#[test]
 fn disable_peer_scoring_flag() {
     CommandLineTest::new()
         .flag("disable-peer-scoring", None)
@@ -1264,6 +1614,33 @@ fn boot_nodes_multiaddr_flag() {
         });
 }
 #[test]
+fn boot_nodes_dns_flag() {
+    let trees = "enrtree://AKA3AM6LPBYEUDMVNU3BSVQJ5AD45Y7YPOHJLEF6W26QOE4VTUDPE@all.mainnet.ethdisco.net,\
+                 enrtree://AKA3AM6LPBYEUDMVNU3BSVQJ5AD45Y7YPOHJLEF6W26QOE4VTUDPE@les.mainnet.ethdisco.net";
+    let links: Vec<&str> = trees.split(',').collect();
+    CommandLineTest::new()
+        .flag("boot-nodes-dns", Some(trees))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.boot_nodes_dns.len(), 2);
+            assert_eq!(config.network.boot_nodes_dns[0].to_string(), links[0]);
+            assert_eq!(
+                config.network.boot_nodes_dns[1].domain,
+                "les.mainnet.ethdisco.net"
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn boot_nodes_dns_flag_invalid() {
+    CommandLineTest::new()
+        .flag(
+            "boot-nodes-dns",
+            Some("enrtree://invalid@nodes.example.org"),
+        )
+        .run_with_zero_port();
+}
+#[test]
 fn private_flag() {
     CommandLineTest::new()
         .flag("private", None)
@@ -1527,6 +1904,29 @@ fn enr_address_flag_with_ipv6() {
         });
 }
 #[test]
+fn enr_field_flag() {
+    CommandLineTest::new()
+        .flag("enr-field", Some("operator=0x6c68"))
+        .flag("enr-field", Some("region=0x"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.enr_custom_fields,
+                BTreeMap::from([
+                    ("operator".to_string(), vec![0x6c, 0x68]),
+                    ("region".to_string(), vec![]),
+                ])
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn enr_field_flag_reserved() {
+    CommandLineTest::new()
+        .flag("enr-field", Some("eth2=0x00"))
+        .run_with_zero_port();
+}
+#[test]
 fn enr_address_dns_flag() {
     let addr = Ipv4Addr::LOCALHOST;
     let ipv6addr = Ipv6Addr::LOCALHOST;
@@ -1648,28 +2048,138 @@ fn http_allow_origin_all_flag() {
 }
 
 #[test]
-fn http_allow_sync_stalled_flag() {
+fn http_path_prefix_default() {
     CommandLineTest::new()
         .flag("http", None)
-        .flag("http-allow-sync-stalled", None)
-        .run_with_zero_port();
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.path_prefix, None));
 }
-
 #[test]
-fn http_enable_beacon_processor() {
+fn http_path_prefix_flag() {
     CommandLineTest::new()
         .flag("http", None)
+        .flag("http-path-prefix", Some("/devnet/beacon/"))
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.http_api.enable_beacon_processor, true));
-
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.path_prefix,
+                Some("devnet/beacon".to_string())
+            )
+        });
+}
+#[test]
+#[should_panic]
+fn http_path_prefix_empty() {
     CommandLineTest::new()
         .flag("http", None)
-        .flag("http-enable-beacon-processor", Some("true"))
-        .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.http_api.enable_beacon_processor, true));
+        .flag("http-path-prefix", Some("/"))
+        .run_with_zero_port();
+}
 
-    CommandLineTest::new()
-        .flag("http", None)
+#[test]
+fn additional_instance_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("devnet.args");
+    fs::write(
+        &path,
+        format!(
+            "# A second mainnet node.\n--datadir {}\n--port 0\n",
+            dir.path().join("devnet").display()
+        ),
+    )
+    .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("additional-instance", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+#[test]
+#[should_panic]
+fn additional_instance_flag_invalid_args() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("devnet.args");
+    fs::write(&path, "--no-such-flag").expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("additional-instance", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+#[test]
+#[should_panic]
+fn additional_instance_flag_port_clash() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut test = CommandLineTest::new();
+    for name in ["devnet-a", "devnet-b"] {
+        let path = dir.path().join(format!("{name}.args"));
+        fs::write(
+            &path,
+            format!(
+                "--datadir {}\n--port 9100\n",
+                dir.path().join(name).display()
+            ),
+        )
+        .expect("Unable to write to file");
+        test.flag("additional-instance", path.as_os_str().to_str());
+    }
+    test.run_with_zero_port();
+}
+#[test]
+fn additional_instance_flag_metrics() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("devnet.args");
+    fs::write(
+        &path,
+        format!(
+            "--datadir {}\n--port 0\n",
+            dir.path().join("devnet").display()
+        ),
+    )
+    .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("additional-instance", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_metrics.enabled));
+}
+#[test]
+#[should_panic]
+fn additional_instance_flag_instance_metrics() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("devnet.args");
+    fs::write(
+        &path,
+        format!(
+            "--datadir {}\n--port 0\n--metrics\n--metrics-port 5264\n",
+            dir.path().join("devnet").display()
+        ),
+    )
+    .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("additional-instance", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+
+#[test]
+fn http_allow_sync_stalled_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-allow-sync-stalled", None)
+        .run_with_zero_port();
+}
+
+#[test]
+fn http_enable_beacon_processor() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.enable_beacon_processor, true));
+
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-enable-beacon-processor", Some("true"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.enable_beacon_processor, true));
+
+    CommandLineTest::new()
+        .flag("http", None)
         .flag("http-enable-beacon-processor", Some("false"))
         .run_with_zero_port()
         .with_config(|config| assert_eq!(config.http_api.enable_beacon_processor, false));
@@ -1693,6 +2203,86 @@ fn http_tls_flags() {
         });
 }
 
+#[test]
+fn http_compression_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.enable_compression));
+}
+#[test]
+fn http_disable_compression_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-disable-compression", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_api.enable_compression));
+}
+#[test]
+fn http_request_limits_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.request_limits, RequestLimits::default()));
+}
+#[test]
+fn http_request_limits_flags() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-request-timeouts", Some("validator=2000,all=6000"))
+        .flag("http-max-body-sizes", Some("submission=16777216"))
+        .flag("http-max-concurrent-requests", Some("debug=1"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let limits = &config.http_api.request_limits;
+            assert_eq!(
+                limits.timeout(RouteClass::Validator),
+                Some(Duration::from_millis(2000))
+            );
+            assert_eq!(
+                limits.timeout(RouteClass::Standard),
+                Some(Duration::from_millis(6000))
+            );
+            assert_eq!(limits.timeout(RouteClass::Events), None);
+            assert_eq!(limits.max_body_sizes.len(), 1);
+            assert_eq!(
+                limits.max_body_sizes.get(&RouteClass::Submission),
+                Some(&16777216)
+            );
+            assert_eq!(limits.max_concurrent.get(&RouteClass::Debug), Some(&1));
+        });
+}
+#[test]
+#[should_panic]
+fn http_request_timeouts_invalid_class() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-request-timeouts", Some("unknown=1000"))
+        .run_with_zero_port();
+}
+#[test]
+fn http_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("api-tokens.txt");
+    fs::write(&path, "admin:abc\nvalidator:def\n").expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-token-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.api_token_file, Some(path.clone())));
+}
+#[test]
+#[should_panic]
+fn http_token_file_invalid() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("api-tokens.txt");
+    fs::write(&path, "root:abc\n").expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-token-file", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+
 #[test]
 fn http_spec_fork_default() {
     CommandLineTest::new()
@@ -1813,6 +2403,37 @@ fn validator_monitor_file_flag() {
         });
 }
 #[test]
+fn slashing_guard_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.slashing_guard_pubkeys.is_empty()));
+}
+#[test]
+fn slashing_guard_pubkeys_flag() {
+    CommandLineTest::new()
+        .flag("slashing-guard-pubkeys", Some("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef,\
+                                             0xbeefdeadbeefdeaddeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.slashing_guard_pubkeys[0].to_string(), "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef");
+            assert_eq!(config.chain.slashing_guard_pubkeys[1].to_string(), "0xbeefdeadbeefdeaddeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef");
+        });
+}
+#[test]
+fn slashing_guard_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file = File::create(dir.path().join("pubkeys.txt")).expect("Unable to create file");
+    file.write_all(b"0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef\n")
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("slashing-guard-file", dir.path().join("pubkeys.txt").as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.slashing_guard_pubkeys.len(), 1);
+            assert_eq!(config.chain.slashing_guard_pubkeys[0].to_string(), "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef");
+        });
+}
+#[test]
 fn validator_monitor_metrics_threshold_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -1940,6 +2561,44 @@ fn compact_db_flag() {
         .with_config(|config| assert!(config.store.compact_on_init));
 }
 #[test]
+fn compaction_period_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.compaction_period,
+                Duration::from_secs(7 * 24 * 60 * 60)
+            )
+        });
+}
+#[test]
+fn compaction_period_flag() {
+    CommandLineTest::new()
+        .flag("compaction-period", Some("24"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.compaction_period,
+                Duration::from_secs(24 * 60 * 60)
+            )
+        });
+}
+#[test]
+fn compaction_throttle_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.compaction_throttle, Duration::from_millis(100))
+        });
+}
+#[test]
+fn compaction_throttle_flag() {
+    CommandLineTest::new()
+        .flag("compaction-throttle", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.compaction_throttle, Duration::ZERO));
+}
+#[test]
 fn prune_payloads_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -1992,6 +2651,32 @@ fn blob_prune_margin_epochs_on_startup_ten() {
         .with_config(|config| assert!(config.store.blob_prune_margin_epochs == 10));
 }
 #[test]
+fn prune_forks_after_epochs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.prune_forks_after_epochs, 0));
+}
+#[test]
+fn prune_forks_after_epochs_flag() {
+    CommandLineTest::new()
+        .flag("prune-forks-after-epochs", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.prune_forks_after_epochs, 16));
+}
+#[test]
+fn db_target_size_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.target_disk_usage, None));
+}
+#[test]
+fn db_target_size_flag() {
+    CommandLineTest::new()
+        .flag("db-target-size", Some("500"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.target_disk_usage, Some(500_000_000_000)));
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
@@ -2261,6 +2946,38 @@ fn slasher_backend_override_to_default() {
         });
 }
 
+#[test]
+fn slasher_backend_beacon_store() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-backend", Some("beacon-store"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config.slasher.as_ref().unwrap();
+            assert_eq!(
+                slasher_config.backend,
+                slasher::DatabaseBackend::BeaconStore
+            );
+        });
+}
+
+#[test]
+fn slasher_auto_default() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.slasher.as_ref().unwrap().auto));
+}
+
+#[test]
+fn slasher_auto_flag() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-auto", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.slasher.as_ref().unwrap().auto));
+}
+
 #[test]
 fn malloc_tuning_flag() {
     CommandLineTest::new()
@@ -2302,7 +3019,7 @@ fn enable_proposer_re_orgs_default() {
                 DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
             );
             assert_eq!(
-                config.chain.re_org_cutoff(12),
+                ReOrgConfig::from(&config.chain).cutoff(12),
                 Duration::from_secs(12) / DEFAULT_RE_ORG_CUTOFF_DENOMINATOR
             );
         });
@@ -2319,6 +3036,38 @@ fn disable_proposer_re_orgs() {
         });
 }
 
+#[test]
+fn reorg_capture_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config_and_dir(|config, dir| {
+            assert_eq!(
+                config.chain.reorg_capture_depth,
+                Some(beacon_node::beacon_chain::chain_config::DEFAULT_REORG_CAPTURE_DEPTH)
+            );
+            assert_eq!(
+                config.chain.reorg_capture_dir,
+                Some(dir.path().join("beacon").join("reorg_captures"))
+            );
+        });
+}
+
+#[test]
+fn reorg_capture_depth_flag() {
+    CommandLineTest::new()
+        .flag("reorg-capture-depth", Some("8"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.reorg_capture_depth, Some(8)));
+}
+
+#[test]
+fn disable_reorg_capture() {
+    CommandLineTest::new()
+        .flag("disable-reorg-capture", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.reorg_capture_depth, None));
+}
+
 #[test]
 fn proposer_re_org_parent_threshold() {
     CommandLineTest::new()
@@ -2354,7 +3103,10 @@ fn proposer_re_org_cutoff() {
         .flag("proposer-reorg-cutoff", Some("500"))
         .run_with_zero_port()
         .with_config(|config| {
-            assert_eq!(config.chain.re_org_cutoff(12), Duration::from_millis(500))
+            assert_eq!(
+                ReOrgConfig::from(&config.chain).cutoff(12),
+                Duration::from_millis(500)
+            )
         });
 }
 
@@ -2403,6 +3155,68 @@ fn monitoring_endpoint() {
             assert_eq!(api_conf.update_period_secs, Some(30));
         });
 }
+#[test]
+fn ntp_server_flag() {
+    CommandLineTest::new()
+        .flag("ntp-server", Some("pool.ntp.org:123"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.ntp_server.as_deref(), Some("pool.ntp.org:123")));
+}
+#[test]
+fn ntp_server_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.ntp_server, None));
+}
+#[test]
+fn webhook_config_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("webhooks.yaml");
+    let mut file = File::create(&path).expect("Unable to create file");
+    file.write_all(
+        b"endpoints:\n  - url: http://example:8000/hook\n    secret: hunter2\n    events: [slashing]\nlow_peer_count: 5\n",
+    )
+    .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("webhook-config", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let webhook_config = config.webhook.as_ref().unwrap();
+            assert_eq!(webhook_config.endpoints.len(), 1);
+            assert_eq!(
+                webhook_config.endpoints[0].url.full.as_str(),
+                "http://example:8000/hook"
+            );
+            assert_eq!(
+                webhook_config.endpoints[0].events,
+                vec![webhook::NotificationKind::Slashing]
+            );
+            assert_eq!(webhook_config.low_peer_count, 5);
+        });
+}
+#[test]
+fn webhook_config_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.webhook.is_none()));
+}
+#[test]
+fn replay_from_dir_flag() {
+    CommandLineTest::new()
+        .flag("replay-from-dir", Some("/tmp/archive"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let replay_config = config.replay.as_ref().unwrap();
+            assert_eq!(replay_config.dir, PathBuf::from("/tmp/archive"));
+            assert!(!replay_config.execution_layer);
+        });
+}
+#[test]
+fn replay_from_dir_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.replay.is_none()));
+}
 
 // Tests for Logger flags.
 #[test]
@@ -2423,6 +3237,13 @@ fn enabled_log_color_flag() {
         });
 }
 #[test]
+fn tracing_endpoint_flag() {
+    // The exporter connects lazily, so the node starts without a collector listening.
+    CommandLineTest::new()
+        .flag("tracing-endpoint", Some("http://localhost:4317"))
+        .run_with_zero_port();
+}
+#[test]
 fn default_disable_log_timestamp_flag() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -2475,6 +3296,19 @@ fn logfile_format_flag() {
         });
 }
 #[test]
+fn log_repeat_limit_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.logger_config.log_repeat_limit, 0));
+}
+#[test]
+fn log_repeat_limit_flag() {
+    CommandLineTest::new()
+        .flag("log-repeat-limit", Some("10"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.logger_config.log_repeat_limit, 10));
+}
+#[test]
 fn sync_eth1_chain_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -2583,6 +3417,44 @@ fn disable_optimistic_finalized_sync() {
         });
 }
 
+#[test]
+fn speculative_sync_verification_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.chain.speculative_sync_verification);
+        });
+}
+
+#[test]
+fn disable_speculative_sync_verification() {
+    CommandLineTest::new()
+        .flag("disable-speculative-sync-verification", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(!config.chain.speculative_sync_verification);
+        });
+}
+
+#[test]
+fn detect_proposer_equivocations_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(!config.chain.detect_proposer_equivocations);
+        });
+}
+
+#[test]
+fn detect_proposer_equivocations_flag() {
+    CommandLineTest::new()
+        .flag("detect-proposer-equivocations", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.chain.detect_proposer_equivocations);
+        });
+}
+
 #[test]
 fn invalid_gossip_verified_blocks_path_default() {
     CommandLineTest::new()
@@ -2616,7 +3488,10 @@ fn progressive_balances_checked() {
 fn beacon_processor() {
     CommandLineTest::new()
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.beacon_processor, <_>::default()));
+        .with_config(|config| {
+            assert_eq!(config.beacon_processor, <_>::default());
+            assert!(config.beacon_processor.work_class_deadlines.is_empty());
+        });
 
     CommandLineTest::new()
         .flag("beacon-processor-max-workers", Some("1"))
@@ -2625,6 +3500,18 @@ fn beacon_processor() {
         .flag("beacon-processor-attestation-batch-size", Some("4"))
         .flag("beacon-processor-aggregate-batch-size", Some("5"))
         .flag("disable-backfill-rate-limiting", None)
+        .flag("beacon-processor-signature-batching", None)
+        .flag("beacon-processor-signature-batch-size", Some("6"))
+        .flag("beacon-processor-signature-batch-window", Some("7"))
+        .flag(
+            "beacon-processor-work-deadlines",
+            Some("attestation=8,api=9"),
+        )
+        .flag(
+            "beacon-processor-class-workers",
+            Some("attestation=10,api=11"),
+        )
+        .flag("beacon-processor-pin-workers", None)
         .run_with_zero_port()
         .with_config(|config| {
             assert_eq!(
@@ -2635,7 +3522,19 @@ fn beacon_processor() {
                     max_scheduled_work_queue_len: 3,
                     max_gossip_attestation_batch_size: 4,
                     max_gossip_aggregate_batch_size: 5,
-                    enable_backfill_rate_limiting: false
+                    enable_backfill_rate_limiting: false,
+                    enable_gossip_signature_batching: true,
+                    gossip_signature_batch_window: Duration::from_millis(7),
+                    max_gossip_signature_batch_size: 6,
+                    work_class_deadlines: BTreeMap::from([
+                        (WorkClass::Attestation, Duration::from_millis(8)),
+                        (WorkClass::Api, Duration::from_millis(9)),
+                    ]),
+                    class_workers: BTreeMap::from([
+                        (WorkClass::Attestation, 10),
+                        (WorkClass::Api, 11),
+                    ]),
+                    pin_workers: true,
                 }
             )
         });
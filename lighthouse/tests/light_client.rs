@@ -0,0 +1,95 @@
+use crate::exec::CommandLineTestExec;
+use light_client::Config;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::process::Command;
+use types::Hash256;
+
+const CHECKPOINT_ROOT: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+/// Returns the `lighthouse light_client` command.
+fn base_cmd() -> Command {
+    let lighthouse_bin = env!("CARGO_BIN_EXE_lighthouse");
+    let path = lighthouse_bin
+        .parse::<PathBuf>()
+        .expect("should parse CARGO_TARGET_DIR");
+
+    let mut cmd = Command::new(path);
+    cmd.arg("light_client");
+    cmd
+}
+
+struct CommandLineTest {
+    cmd: Command,
+}
+
+impl CommandLineTest {
+    /// A command with the required `--checkpoint-root` flag.
+    fn new() -> CommandLineTest {
+        let mut cmd = base_cmd();
+        cmd.arg("--checkpoint-root").arg(CHECKPOINT_ROOT);
+        CommandLineTest { cmd }
+    }
+}
+
+impl CommandLineTestExec for CommandLineTest {
+    type Config = Config;
+
+    fn cmd_mut(&mut self) -> &mut Command {
+        &mut self.cmd
+    }
+}
+
+#[test]
+fn defaults() {
+    CommandLineTest::new().run().with_config(|config| {
+        let beacon_nodes = config
+            .beacon_nodes
+            .iter()
+            .map(|url| url.full.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(beacon_nodes, vec!["http://localhost:5052/"]);
+        assert_eq!(config.checkpoint_root, Hash256::from_low_u64_be(1));
+        assert_eq!(config.listen_addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(config.listen_port, 5072);
+    });
+}
+
+#[test]
+fn beacon_nodes_flag() {
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes",
+            Some("http://10.0.0.1:5052,https://lc.example.com"),
+        )
+        .run()
+        .with_config(|config| {
+            let beacon_nodes = config
+                .beacon_nodes
+                .iter()
+                .map(|url| url.full.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                beacon_nodes,
+                vec!["http://10.0.0.1:5052/", "https://lc.example.com/"]
+            );
+        });
+}
+
+#[test]
+fn http_flags() {
+    CommandLineTest::new()
+        .flag("http-address", Some("0.0.0.0"))
+        .flag("http-port", Some("6072"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.listen_addr, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+            assert_eq!(config.listen_port, 6072);
+        });
+}
+
+#[test]
+#[should_panic]
+fn missing_checkpoint_root() {
+    CommandLineTest { cmd: base_cmd() }.run();
+}
@@ -4,5 +4,7 @@ mod account_manager;
 mod beacon_node;
 mod boot_node;
 mod exec;
+mod light_client;
+mod slasher;
 mod validator_client;
 mod validator_manager;
@@ -0,0 +1,101 @@
+use crate::exec::CommandLineTestExec;
+use slasher_standalone::Config;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Returns the `lighthouse slasher` command.
+fn base_cmd() -> Command {
+    let lighthouse_bin = env!("CARGO_BIN_EXE_lighthouse");
+    let path = lighthouse_bin
+        .parse::<PathBuf>()
+        .expect("should parse CARGO_TARGET_DIR");
+
+    let mut cmd = Command::new(path);
+    cmd.arg("slasher");
+    cmd
+}
+
+struct CommandLineTest {
+    cmd: Command,
+}
+
+impl CommandLineTest {
+    fn new() -> CommandLineTest {
+        CommandLineTest { cmd: base_cmd() }
+    }
+}
+
+impl CommandLineTestExec for CommandLineTest {
+    type Config = Config;
+
+    fn cmd_mut(&mut self) -> &mut Command {
+        &mut self.cmd
+    }
+}
+
+#[test]
+fn defaults() {
+    CommandLineTest::new()
+        .run()
+        .with_config_and_dir(|config, dir| {
+            assert_eq!(
+                config.beacon_node.full.to_string(),
+                "http://localhost:5052/"
+            );
+            assert_eq!(config.slasher.database_path, dir.path().join("slasher_db"));
+            assert!(!config.slasher.auto);
+        });
+}
+
+#[test]
+fn beacon_node_flag() {
+    CommandLineTest::new()
+        .flag("beacon-node", Some("http://10.0.0.1:5052"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.beacon_node.full.to_string(), "http://10.0.0.1:5052/");
+            assert_eq!(config.beacon_node_token_file, None);
+        });
+}
+
+#[test]
+fn beacon_node_token_file_flag() {
+    CommandLineTest::new()
+        .flag("beacon-node-token-file", Some("/tmp/api-token.txt"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_node_token_file,
+                Some(PathBuf::from("/tmp/api-token.txt"))
+            );
+        });
+}
+
+#[test]
+fn slasher_flags() {
+    let dir = tempfile::TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("slasher-dir", dir.path().as_os_str().to_str())
+        .flag("slasher-history-length", Some("2048"))
+        .flag("slasher-chunk-size", Some("32"))
+        .flag("slasher-validator-chunk-size", Some("512"))
+        .flag("slasher-update-period", Some("24"))
+        .flag("slasher-auto", None)
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.slasher.database_path, dir.path());
+            assert_eq!(config.slasher.history_length, 2048);
+            assert_eq!(config.slasher.chunk_size, 32);
+            assert_eq!(config.slasher.validator_chunk_size, 512);
+            assert_eq!(config.slasher.update_period, 24);
+            assert!(config.slasher.auto);
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_store_backend_rejected() {
+    CommandLineTest::new()
+        .flag("slasher-backend", Some("beacon-store"))
+        .run();
+}
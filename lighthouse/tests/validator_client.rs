@@ -1,4 +1,7 @@
-use validator_client::{config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, ApiTopic, Config};
+use validator_client::{
+    config::{DEFAULT_WEB3SIGNER_HEALTH_CHECK_INTERVAL, DEFAULT_WEB3SIGNER_KEEP_ALIVE},
+    ApiTopic, Config, Web3SignerSlashingProtection,
+};
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
@@ -154,6 +157,23 @@ fn beacon_nodes_tls_certs_flag() {
         });
 }
 
+#[test]
+fn beacon_nodes_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes-token-file",
+            dir.path().join("api-token.txt").to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes_token_file,
+                Some(dir.path().join("api-token.txt"))
+            )
+        });
+}
+
 // Tests for Graffiti flags.
 #[test]
 fn graffiti_flag() {
@@ -605,6 +625,47 @@ fn latency_measurement_service() {
             assert!(config.enable_latency_measurement_service);
         });
 }
+#[test]
+fn ntp_server_flag() {
+    CommandLineTest::new()
+        .flag("ntp-server", Some("time.example.com"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.ntp_server.as_deref(), Some("time.example.com"));
+        });
+}
+#[test]
+fn ntp_server_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.ntp_server, None));
+}
+
+#[test]
+fn leader_election_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.leader_election_url, None);
+        assert_eq!(
+            config.leader_election_key,
+            "lighthouse/validator-client/leader"
+        );
+    });
+}
+
+#[test]
+fn leader_election_flags() {
+    CommandLineTest::new()
+        .flag("leader-election-url", Some("http://localhost:8500"))
+        .flag("leader-election-key", Some("mainnet/validators-a"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.leader_election_url.as_ref().unwrap().full.as_str(),
+                "http://localhost:8500/"
+            );
+            assert_eq!(config.leader_election_key, "mainnet/validators-a");
+        });
+}
 
 #[test]
 fn validator_registration_batch_size() {
@@ -630,7 +691,10 @@ fn validator_registration_batch_size_zero_value() {
 #[test]
 fn validator_disable_web3_signer_slashing_protection_default() {
     CommandLineTest::new().run().with_config(|config| {
-        assert!(config.enable_web3signer_slashing_protection);
+        assert_eq!(
+            config.web3signer_slashing_protection,
+            Web3SignerSlashingProtection::Local
+        );
     });
 }
 
@@ -640,10 +704,34 @@ fn validator_disable_web3_signer_slashing_protection() {
         .flag("disable-slashing-protection-web3signer", None)
         .run()
         .with_config(|config| {
-            assert!(!config.enable_web3signer_slashing_protection);
+            assert_eq!(
+                config.web3signer_slashing_protection,
+                Web3SignerSlashingProtection::Disabled
+            );
+        });
+}
+
+#[test]
+fn validator_web3_signer_slashing_protection_remote() {
+    CommandLineTest::new()
+        .flag("web3-signer-slashing-protection", Some("remote"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.web3signer_slashing_protection,
+                Web3SignerSlashingProtection::Remote
+            );
         });
 }
 
+#[test]
+#[should_panic]
+fn validator_web3_signer_slashing_protection_invalid() {
+    CommandLineTest::new()
+        .flag("web3-signer-slashing-protection", Some("none"))
+        .run();
+}
+
 #[test]
 fn validator_web3_signer_keep_alive_default() {
     CommandLineTest::new().run().with_config(|config| {
@@ -667,6 +755,37 @@ fn validator_web3_signer_keep_alive_override() {
         });
 }
 
+#[test]
+fn validator_web3_signer_health_check_interval_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(
+            config.web3_signer_health_check_interval,
+            Some(DEFAULT_WEB3SIGNER_HEALTH_CHECK_INTERVAL)
+        );
+    });
+}
+
+#[test]
+fn validator_web3_signer_health_check_interval_override() {
+    CommandLineTest::new()
+        .flag("web3-signer-health-check-interval", Some("5"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.web3_signer_health_check_interval,
+                Some(Duration::from_secs(5))
+            );
+        });
+}
+
+#[test]
+fn validator_web3_signer_health_check_interval_disabled() {
+    CommandLineTest::new()
+        .flag("web3-signer-health-check-interval", Some("0"))
+        .run()
+        .with_config(|config| assert_eq!(config.web3_signer_health_check_interval, None));
+}
+
 #[test]
 fn validator_proposer_nodes_default_empty() {
     CommandLineTest::new().run().with_config(|config| {
@@ -689,3 +808,38 @@ fn validator_proposer_nodes() {
             );
         });
 }
+
+#[test]
+fn presign_exits_flags_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.presign_exits_password_file, None);
+        assert_eq!(config.exit_dead_mans_switch_epochs, None);
+    });
+}
+
+#[test]
+fn presign_exits_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "presign-exits-password-file",
+            dir.path().join("password.txt").to_str(),
+        )
+        .flag("exit-dead-mans-switch-epochs", Some("225"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.presign_exits_password_file,
+                Some(dir.path().join("password.txt"))
+            );
+            assert_eq!(config.exit_dead_mans_switch_epochs, Some(225));
+        });
+}
+
+#[test]
+#[should_panic]
+fn exit_dead_mans_switch_epochs_without_password_file() {
+    CommandLineTest::new()
+        .flag("exit-dead-mans-switch-epochs", Some("225"))
+        .run();
+}
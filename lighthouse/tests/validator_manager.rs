@@ -9,6 +9,7 @@ use tempfile::{tempdir, TempDir};
 use types::*;
 use validator_manager::{
     create_validators::CreateConfig,
+    export_performance::{ExportFormat, ExportPerformanceConfig},
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
 };
@@ -105,6 +106,12 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<ExportPerformanceConfig> {
+    fn export_performance() -> Self {
+        Self::default().flag("export-performance", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -394,3 +401,51 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn export_performance_defaults() {
+    CommandLineTest::export_performance()
+        .flag("--start-epoch", Some("10"))
+        .flag("--end-epoch", Some("20"))
+        .flag("--output-path", Some("./performance.csv"))
+        .assert_success(|config| {
+            let expected = ExportPerformanceConfig {
+                bn_url: SensitiveUrl::parse("http://localhost:5052").unwrap(),
+                bn_token_path: None,
+                start_epoch: Epoch::new(10),
+                end_epoch: Epoch::new(20),
+                format: ExportFormat::Csv,
+                output_path: PathBuf::from("./performance.csv"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn export_performance_json() {
+    CommandLineTest::export_performance()
+        .flag("--beacon-node", Some("http://localhost:1"))
+        .flag("--beacon-node-token", Some("./token.txt"))
+        .flag("--start-epoch", Some("10"))
+        .flag("--end-epoch", Some("20"))
+        .flag("--format", Some("json"))
+        .flag("--output-path", Some("./performance.json"))
+        .assert_success(|config| {
+            assert_eq!(
+                config.bn_url,
+                SensitiveUrl::parse("http://localhost:1").unwrap()
+            );
+            assert_eq!(config.bn_token_path, Some(PathBuf::from("./token.txt")));
+            assert_eq!(config.format, ExportFormat::Json);
+        });
+}
+
+#[test]
+pub fn export_performance_parquet_unsupported() {
+    CommandLineTest::export_performance()
+        .flag("--start-epoch", Some("10"))
+        .flag("--end-epoch", Some("20"))
+        .flag("--format", Some("parquet"))
+        .flag("--output-path", Some("./performance.parquet"))
+        .assert_failed();
+}
@@ -29,6 +29,9 @@ vm_cli=$($CMD vm --help)
 vm_cli_create=$($CMD vm create --help)
 vm_cli_import=$($CMD vm import --help)
 vm_cli_move=$($CMD vm move --help)
+vm_cli_export_performance=$($CMD vm export-performance --help)
+db_cli_export_checkpoint=$($CMD db export-checkpoint --help)
+db_cli_import_checkpoint=$($CMD db import-checkpoint --help)
 
 general=./help_general.md
 bn=./help_bn.md
@@ -38,6 +41,9 @@ vm=./help_vm.md
 vm_create=./help_vm_create.md
 vm_import=./help_vm_import.md
 vm_move=./help_vm_move.md
+vm_export_performance=./help_vm_export_performance.md
+db_export_checkpoint=./help_db_export_checkpoint.md
+db_import_checkpoint=./help_db_import_checkpoint.md
 
 # create .md files
 write_to_file "$general_cli" "$general" "Lighthouse General Commands"
@@ -47,10 +53,13 @@ write_to_file "$vm_cli" "$vm" "Validator Manager"
 write_to_file "$vm_cli_create" "$vm_create" "Validator Manager Create"
 write_to_file "$vm_cli_import" "$vm_import" "Validator Manager Import"
 write_to_file "$vm_cli_move" "$vm_move" "Validator Manager Move"
+write_to_file "$vm_cli_export_performance" "$vm_export_performance" "Validator Manager Export Performance"
+write_to_file "$db_cli_export_checkpoint" "$db_export_checkpoint" "Database Manager Export Checkpoint"
+write_to_file "$db_cli_import_checkpoint" "$db_import_checkpoint" "Database Manager Import Checkpoint"
 
 #input 1 = $1 = files; input 2 = $2 = new files
-files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md)
-new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move)
+files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md ./book/src/help_vm_export_performance.md ./book/src/help_db_export_checkpoint.md ./book/src/help_db_import_checkpoint.md)
+new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move $vm_export_performance $db_export_checkpoint $db_import_checkpoint)
 
 # function to check
 check() {
@@ -84,9 +93,12 @@ check ${files[3]} ${new_files[3]}
 check ${files[4]} ${new_files[4]}
 check ${files[5]} ${new_files[5]}
 check ${files[6]} ${new_files[6]}
+check ${files[7]} ${new_files[7]}
+check ${files[8]} ${new_files[8]}
+check ${files[9]} ${new_files[9]}
 
 # remove help files
-rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md
+rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md help_vm_export_performance.md help_db_export_checkpoint.md help_db_import_checkpoint.md
 
 # only exit at the very end
 if [[ $changes == true ]]; then
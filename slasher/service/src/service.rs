@@ -54,19 +54,11 @@ impl<T: BeaconChainTypes> SlasherService<T> {
         // Buffer just a single message in the channel. If the receiver is still processing, we
         // don't need to burden them with more work (we can wait).
         let (notif_sender, notif_receiver) = sync_channel(1);
-        let update_period = slasher.config().update_period;
-        let slot_offset = slasher.config().slot_offset;
         let beacon_chain = self.beacon_chain.clone();
         let network_sender = self.network_sender.clone();
 
         executor.spawn(
-            Self::run_notifier(
-                beacon_chain.clone(),
-                update_period,
-                slot_offset,
-                notif_sender,
-                log,
-            ),
+            Self::run_notifier(beacon_chain.clone(), slasher.clone(), notif_sender, log),
             "slasher_server_notifier",
         );
 
@@ -79,14 +71,15 @@ impl<T: BeaconChainTypes> SlasherService<T> {
     }
 
     /// Run the async notifier which periodically prompts the processor to run.
+    ///
+    /// The update period is re-read after every tick, as it may be tuned by the slasher.
     async fn run_notifier(
         beacon_chain: Arc<BeaconChain<T>>,
-        update_period: u64,
-        slot_offset: f64,
+        slasher: Arc<Slasher<T::EthSpec>>,
         notif_sender: SyncSender<Epoch>,
         log: Logger,
     ) {
-        let slot_offset = Duration::from_secs_f64(slot_offset);
+        let slot_offset = Duration::from_secs_f64(slasher.config().slot_offset);
         let start_instant =
             if let Some(duration_to_next_slot) = beacon_chain.slot_clock.duration_to_next_slot() {
                 Instant::now() + duration_to_next_slot + slot_offset
@@ -94,10 +87,11 @@ impl<T: BeaconChainTypes> SlasherService<T> {
                 error!(log, "Error aligning slasher to slot clock");
                 Instant::now()
             };
+        let mut update_period = slasher.update_period();
         let mut interval = interval_at(start_instant, Duration::from_secs(update_period));
 
         loop {
-            interval.tick().await;
+            let tick_instant = interval.tick().await;
             if let Some(current_slot) = beacon_chain.slot_clock.now() {
                 let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
                 if let Err(TrySendError::Disconnected(_)) = notif_sender.try_send(current_epoch) {
@@ -106,6 +100,19 @@ impl<T: BeaconChainTypes> SlasherService<T> {
             } else {
                 trace!(log, "Slasher has nothing to do: we are pre-genesis");
             }
+
+            let new_update_period = slasher.update_period();
+            if new_update_period != update_period {
+                debug!(
+                    log,
+                    "Changing slasher update period";
+                    "from" => update_period,
+                    "to" => new_update_period,
+                );
+                update_period = new_update_period;
+                let period = Duration::from_secs(update_period);
+                interval = interval_at(tick_instant + period, period);
+            }
         }
     }
 
@@ -184,6 +191,7 @@ impl<T: BeaconChainTypes> SlasherService<T> {
         let log = slasher.log();
         let attester_slashings = slasher.get_attester_slashings();
 
+        let mut verified_slashings = Vec::with_capacity(attester_slashings.len());
         for slashing in attester_slashings {
             // Verify slashing signature.
             let verified_slashing = match beacon_chain.with_head(|head| {
@@ -214,6 +222,40 @@ impl<T: BeaconChainTypes> SlasherService<T> {
                     continue;
                 }
             };
+            verified_slashings.push(verified_slashing);
+        }
+
+        // Drop slashings for validators that are already slashed on the canonical chain or by a
+        // slashing in the op pool, and keep only a minimal set of slashings covering the rest.
+        let num_verified = verified_slashings.len();
+        let verified_slashings = match beacon_chain.with_head(|head| {
+            Ok::<_, BeaconChainError>(
+                beacon_chain
+                    .op_pool
+                    .filter_redundant_attester_slashings(verified_slashings, &head.beacon_state),
+            )
+        }) {
+            Ok(verified_slashings) => verified_slashings,
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to filter attester slashings";
+                    "error" => ?e,
+                );
+                return;
+            }
+        };
+        if verified_slashings.len() < num_verified {
+            debug!(
+                log,
+                "Skipping redundant attester slashings";
+                "num_skipped" => num_verified - verified_slashings.len(),
+                "num_remaining" => verified_slashings.len(),
+            );
+        }
+
+        for verified_slashing in verified_slashings {
+            let slashing = verified_slashing.as_inner().clone();
 
             // Add to local op pool.
             beacon_chain.import_attester_slashing(verified_slashing);
@@ -240,8 +282,39 @@ impl<T: BeaconChainTypes> SlasherService<T> {
     ) {
         let log = slasher.log();
         let proposer_slashings = slasher.get_proposer_slashings();
+        if proposer_slashings.is_empty() {
+            return;
+        }
+
+        let mut pooled_indices = match beacon_chain.with_head(|head| {
+            Ok::<_, BeaconChainError>(
+                beacon_chain
+                    .op_pool
+                    .get_pooled_slashing_indices(&head.beacon_state),
+            )
+        }) {
+            Ok(indices) => indices,
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to load pooled slashings";
+                    "error" => ?e,
+                );
+                return;
+            }
+        };
 
         for slashing in proposer_slashings {
+            let proposer_index = slashing.proposer_index();
+            if pooled_indices.contains(&proposer_index) {
+                debug!(
+                    log,
+                    "Skipping proposer slashing for validator with pooled slashing";
+                    "validator_index" => proposer_index,
+                );
+                continue;
+            }
+
             let verified_slashing = match beacon_chain.with_head(|head| {
                 Ok(slashing
                     .clone()
@@ -271,6 +344,7 @@ impl<T: BeaconChainTypes> SlasherService<T> {
                 }
             };
             beacon_chain.import_proposer_slashing(verified_slashing);
+            pooled_indices.insert(proposer_index);
 
             if slasher.config().broadcast {
                 if let Err(e) =
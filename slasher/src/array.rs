@@ -5,14 +5,18 @@ use crate::{
 };
 use flate2::bufread::{ZlibDecoder, ZlibEncoder};
 use serde::{Deserialize, Serialize};
-use std::borrow::Borrow;
-use std::collections::{btree_map::Entry, BTreeMap, HashSet};
+use std::borrow::{Borrow, Cow};
+use std::collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet};
 use std::io::Read;
 use std::sync::Arc;
 use types::{AttesterSlashing, Epoch, EthSpec, IndexedAttestation};
 
 pub const MAX_DISTANCE: u16 = u16::MAX;
 
+/// Bit set on the disk keys of chunks written during a layout migration, so that they don't
+/// collide with the chunks of the old layout which are still being read.
+const STAGING_KEY_BIT: u8 = 0x80;
+
 /// Terminology:
 ///
 /// Let
@@ -51,6 +55,21 @@ impl Chunk {
             .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
     }
 
+    pub fn get_raw_distance(
+        &self,
+        validator_index: u64,
+        epoch: Epoch,
+        config: &Config,
+    ) -> Result<u16, Error> {
+        let validator_offset = config.validator_offset(validator_index);
+        let chunk_offset = config.chunk_offset(epoch);
+        let cell_index = config.cell_index(validator_offset, chunk_offset);
+        self.data
+            .get(cell_index)
+            .copied()
+            .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
+    }
+
     pub fn set_target(
         &mut self,
         validator_index: u64,
@@ -175,6 +194,15 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         config: &Config,
     ) -> Result<(), Error> {
         let disk_key = config.disk_key(validator_chunk_index, chunk_index);
+        self.store_at_key(db, txn, disk_key.to_be_bytes())
+    }
+
+    fn store_at_key<E: EthSpec>(
+        &self,
+        db: &SlasherDB<E>,
+        txn: &mut RwTransaction<'_>,
+        disk_key: impl AsRef<[u8]>,
+    ) -> Result<(), Error> {
         let value = bincode::serialize(self)?;
         let mut encoder = ZlibEncoder::new(&value[..], flate2::Compression::default());
         let mut compressed_value = vec![];
@@ -183,11 +211,7 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         let compression_ratio = value.len() as f64 / compressed_value.len() as f64;
         metrics::set_float_gauge(&SLASHER_COMPRESSION_RATIO, compression_ratio);
 
-        txn.put(
-            Self::select_db(db),
-            disk_key.to_be_bytes(),
-            &compressed_value,
-        )?;
+        txn.put(Self::select_db(db), disk_key, &compressed_value)?;
         Ok(())
     }
 }
@@ -623,3 +647,139 @@ pub fn update_array<E: EthSpec, T: TargetArrayChunk>(
 
     Ok(slashings)
 }
+
+/// Write the `T` target array of a batch of `validators` in the layout of `to` to staging keys,
+/// reading it from the layout of `from`.
+///
+/// The `validators` must contain the current epoch of every validator with data in the array,
+/// sorted by validator index. Whole validator chunks are staged until at least `batch_size`
+/// chunks have been written, and the first validator which remains to be staged is returned.
+/// Only the epochs within the shorter of the two history lengths are carried over, with all
+/// other cells taking the neutral value.
+pub fn stage_array<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    validators: &[(u64, Epoch)],
+    from: &Config,
+    to: &Config,
+    batch_size: usize,
+) -> Result<Option<u64>, Error> {
+    let history_length = std::cmp::min(from.history_length, to.history_length) as u64;
+    let mut old_chunks: HashMap<(usize, usize), Option<T>> = HashMap::new();
+    let mut num_staged = 0;
+
+    let mut remaining = validators;
+    while let Some((first_validator, _)) = remaining.first() {
+        if num_staged >= batch_size {
+            return Ok(Some(*first_validator));
+        }
+
+        let validator_chunk_index = to.validator_chunk_index(*first_validator);
+        let row_len = remaining
+            .iter()
+            .position(|(v, _)| to.validator_chunk_index(*v) != validator_chunk_index)
+            .unwrap_or(remaining.len());
+        let (row, rest) = remaining.split_at(row_len);
+        remaining = rest;
+
+        let first_old_row = from.validator_chunk_index(*first_validator);
+        old_chunks.retain(|(old_row, _), _| *old_row >= first_old_row);
+
+        let mut new_chunks = BTreeMap::new();
+        for (validator_index, current_epoch) in row {
+            let start_epoch = (current_epoch.as_u64() + 1).saturating_sub(history_length);
+            for epoch in (start_epoch..=current_epoch.as_u64()).map(Epoch::new) {
+                let old_key = (
+                    from.validator_chunk_index(*validator_index),
+                    from.chunk_index(epoch),
+                );
+                let old_chunk = match old_chunks.entry(old_key) {
+                    hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(T::load(db, txn, old_key.0, old_key.1, from)?)
+                    }
+                };
+                let Some(old_chunk) = old_chunk else {
+                    continue;
+                };
+
+                let distance = old_chunk
+                    .chunk()
+                    .get_raw_distance(*validator_index, epoch, from)?;
+                if distance != T::neutral_element() {
+                    new_chunks
+                        .entry(to.chunk_index(epoch))
+                        .or_insert_with(|| T::empty(to))
+                        .chunk()
+                        .set_raw_distance(*validator_index, epoch, distance, to)?;
+                }
+            }
+        }
+
+        num_staged += new_chunks.len();
+        for (chunk_index, chunk) in new_chunks {
+            let disk_key = to.disk_key(validator_chunk_index, chunk_index);
+            let mut staging_key = disk_key.to_be_bytes();
+            staging_key[0] |= STAGING_KEY_BIT;
+            chunk.store_at_key(db, txn, staging_key)?;
+        }
+    }
+
+    Ok(None)
+}
+
+/// Delete up to `batch_size` chunks of the `T` target array in the old layout, returning `true`
+/// once none remain.
+///
+/// The keys of the old layout sort before the staging keys.
+pub fn delete_array_layout<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    batch_size: usize,
+) -> Result<bool, Error> {
+    let is_old_key = |key_byte: Option<u8>| key_byte.map_or(false, |b| b & STAGING_KEY_BIT == 0);
+
+    let mut cursor = txn.cursor(T::select_db(db))?;
+    let mut next_key_byte = cursor.first_key()?.map(|key| key[0]);
+    for _ in 0..batch_size {
+        if !is_old_key(next_key_byte) {
+            break;
+        }
+        cursor.delete_current()?;
+        next_key_byte = cursor.next_key()?.map(|key| key[0]);
+    }
+    Ok(!is_old_key(next_key_byte))
+}
+
+/// Move up to `batch_size` chunks of the `T` target array from the staging keys into place,
+/// returning `true` once none remain.
+pub fn unstage_array<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    batch_size: usize,
+) -> Result<bool, Error> {
+    let mut staged = vec![];
+    let mut finished = true;
+
+    let mut cursor = txn.cursor(T::select_db(db))?;
+    let mut next = cursor.seek_key(&[STAGING_KEY_BIT])?.map(Cow::into_owned);
+    while let Some(staging_key) = next {
+        if staged.len() == batch_size {
+            finished = false;
+            break;
+        }
+        let (_, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+        staged.push((staging_key, value.into_owned()));
+        next = cursor.next_key()?.map(Cow::into_owned);
+    }
+    drop(cursor);
+
+    for (staging_key, value) in staged {
+        let mut disk_key = staging_key.clone();
+        disk_key[0] &= !STAGING_KEY_BIT;
+        txn.put(T::select_db(db), &disk_key, &value)?;
+        txn.del(T::select_db(db), &staging_key)?;
+    }
+
+    Ok(finished)
+}
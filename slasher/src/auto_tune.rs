@@ -0,0 +1,292 @@
+//! Heuristics for the slasher's adaptive mode (`Config::auto`).
+//!
+//! The slasher records the number of validators it has seen and how long each batch takes to
+//! process (including the database commit). From these statistics it derives:
+//!
+//! - The update period, which takes effect immediately.
+//! - The chunk sizes and history length, which are applied when the database is next opened by
+//!   migrating the min-max target arrays to the new layout.
+//!
+//! The tuned parameters are persisted alongside the statistics, and are only replaced once the
+//! statistics have moved past the `HYSTERESIS` margin, so that small fluctuations don't trigger
+//! repeated database migrations.
+use crate::{config::DiskConfig, Config};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Aim for each validator chunk row to cover this many validators at most.
+pub const TARGET_NUM_VALIDATOR_CHUNKS: u64 = 1024;
+/// Upper bound on the tuned validator chunk size.
+pub const MAX_AUTO_VALIDATOR_CHUNK_SIZE: usize = 4096;
+/// Upper bound on the tuned chunk size.
+pub const MAX_AUTO_CHUNK_SIZE: usize = 64;
+/// Lower bound on the tuned history length.
+pub const MIN_AUTO_HISTORY_LENGTH: usize = 1024;
+/// Maximum multiple of the configured update period that the update period may be tuned to.
+pub const MAX_UPDATE_PERIOD_MULTIPLIER: u64 = 8;
+/// Fraction of the update period that batch processing should take up.
+pub const TARGET_LOAD: f64 = 0.5;
+/// Number of batches that must be observed before throughput-based tuning kicks in.
+pub const MIN_BATCHES_FOR_TUNING: u64 = 8;
+/// Weight given to the most recent batch in the moving average of batch times.
+const BATCH_TIME_SMOOTHING: f64 = 0.2;
+/// Relative change in the statistics required before persisted tuned parameters are replaced.
+pub const HYSTERESIS: f64 = 0.25;
+
+/// Statistics gathered while processing batches, persisted to the slasher database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TuningStats {
+    /// One more than the highest validator index observed in an attestation.
+    pub num_validators: u64,
+    /// Exponentially-weighted moving average of the batch processing time in milliseconds.
+    pub batch_time_ms: f64,
+    /// Number of batches included in `batch_time_ms`.
+    pub num_batches: u64,
+}
+
+impl TuningStats {
+    pub fn observe_validator_index(&mut self, validator_index: u64) {
+        self.num_validators = std::cmp::max(self.num_validators, validator_index + 1);
+    }
+
+    pub fn record_batch(&mut self, batch_time: Duration) {
+        let batch_time_ms = batch_time.as_secs_f64() * 1000.0;
+        if self.num_batches == 0 {
+            self.batch_time_ms = batch_time_ms;
+        } else {
+            self.batch_time_ms = BATCH_TIME_SMOOTHING * batch_time_ms
+                + (1.0 - BATCH_TIME_SMOOTHING) * self.batch_time_ms;
+        }
+        self.num_batches = self.num_batches.saturating_add(1);
+    }
+
+    /// Scale the validator count and batch time by `factor`.
+    fn scaled(&self, factor: f64) -> Self {
+        Self {
+            num_validators: (self.num_validators as f64 * factor).ceil() as u64,
+            batch_time_ms: self.batch_time_ms * factor,
+            num_batches: self.num_batches,
+        }
+    }
+}
+
+/// The tuned parameters, persisted to the slasher database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TunedConfig {
+    pub update_period: u64,
+    pub disk_config: DiskConfig,
+}
+
+impl TunedConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            update_period: config.update_period,
+            disk_config: config.disk_config(),
+        }
+    }
+
+    /// Return a copy of `config` with the tuned parameters applied.
+    pub fn apply(&self, config: &Config) -> Config {
+        let mut config = config.clone();
+        config.update_period = self.update_period;
+        config.chunk_size = self.disk_config.chunk_size;
+        config.validator_chunk_size = self.disk_config.validator_chunk_size;
+        config.history_length = self.disk_config.history_length;
+        config
+    }
+
+    /// Check whether every parameter of `self` lies between those of `lower` and `upper`.
+    ///
+    /// The history length shrinks as the statistics grow, so its bounds are reversed.
+    fn is_between(&self, lower: &Self, upper: &Self) -> bool {
+        let (disk, lower_disk, upper_disk) =
+            (&self.disk_config, &lower.disk_config, &upper.disk_config);
+        (lower.update_period..=upper.update_period).contains(&self.update_period)
+            && (lower_disk.chunk_size..=upper_disk.chunk_size).contains(&disk.chunk_size)
+            && (lower_disk.validator_chunk_size..=upper_disk.validator_chunk_size)
+                .contains(&disk.validator_chunk_size)
+            && (upper_disk.history_length..=lower_disk.history_length)
+                .contains(&disk.history_length)
+    }
+}
+
+/// Compute the tuned parameters for `config` given the observed `stats`, keeping the `previous`
+/// parameters unless they differ from the parameters that would be tuned for statistics
+/// `HYSTERESIS` lower or higher than `stats`.
+///
+/// Every tuned parameter is monotonic in the statistics, so this bounds the parameters which are
+/// kept to those which are still close to the ideal.
+pub fn retune(config: &Config, stats: &TuningStats, previous: Option<&TunedConfig>) -> TunedConfig {
+    let tuned = TunedConfig::from_config(&tuned_config(config, stats));
+    let Some(previous) = previous.filter(|previous| **previous != tuned) else {
+        return tuned;
+    };
+
+    let lower = TunedConfig::from_config(&tuned_config(
+        config,
+        &stats.scaled(1.0 / (1.0 + HYSTERESIS)),
+    ));
+    let upper = TunedConfig::from_config(&tuned_config(config, &stats.scaled(1.0 + HYSTERESIS)));
+    if previous.is_between(&lower, &upper) && previous.apply(config).validate().is_ok() {
+        previous.clone()
+    } else {
+        tuned
+    }
+}
+
+/// Compute the tuned version of `config` given the observed `stats`.
+///
+/// The values in `config` act as the baseline: the validator chunk size and update period are
+/// never tuned below their configured values, and the history length is never tuned above it.
+pub fn tuned_config(config: &Config, stats: &TuningStats) -> Config {
+    let mut tuned = config.clone();
+
+    if stats.num_validators > 0 {
+        let per_chunk = stats.num_validators.div_ceil(TARGET_NUM_VALIDATOR_CHUNKS);
+        tuned.validator_chunk_size = (per_chunk.next_power_of_two() as usize).clamp(
+            config.validator_chunk_size,
+            std::cmp::max(config.validator_chunk_size, MAX_AUTO_VALIDATOR_CHUNK_SIZE),
+        );
+    }
+
+    if stats.num_batches < MIN_BATCHES_FOR_TUNING {
+        return tuned;
+    }
+
+    // Use the smallest multiple of the configured update period which keeps the load on target.
+    // Multiples keep the slasher aligned to the slot offset.
+    let base_period_ms = config.update_period as f64 * 1000.0;
+    let multiplier = (1..=MAX_UPDATE_PERIOD_MULTIPLIER)
+        .find(|m| stats.batch_time_ms <= TARGET_LOAD * base_period_ms * *m as f64)
+        .unwrap_or(MAX_UPDATE_PERIOD_MULTIPLIER);
+    tuned.update_period = config.update_period * multiplier;
+
+    // If even the longest update period can't keep the load on target then the disk is struggling:
+    // write fewer, larger chunks, and if the slasher can't keep up at all, track less history.
+    let load = stats.batch_time_ms / (tuned.update_period as f64 * 1000.0);
+    if load > TARGET_LOAD {
+        let chunk_size = config.chunk_size * 2;
+        if chunk_size <= MAX_AUTO_CHUNK_SIZE && config.history_length % chunk_size == 0 {
+            tuned.chunk_size = chunk_size;
+        }
+    }
+    if load > 1.0 {
+        let history_length = config.history_length / 2;
+        if history_length >= MIN_AUTO_HISTORY_LENGTH && history_length % tuned.chunk_size == 0 {
+            tuned.history_length = history_length;
+        }
+    }
+
+    tuned
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stats(num_validators: u64, batch_time_ms: f64) -> TuningStats {
+        TuningStats {
+            num_validators,
+            batch_time_ms,
+            num_batches: MIN_BATCHES_FOR_TUNING,
+        }
+    }
+
+    #[test]
+    fn no_stats_is_noop() {
+        let config = Config::new("".into());
+        let tuned = tuned_config(&config, &TuningStats::default());
+        assert_eq!(tuned.disk_config(), config.disk_config());
+        assert_eq!(tuned.update_period, config.update_period);
+    }
+
+    #[test]
+    fn validator_chunk_size_scales_with_validators() {
+        let config = Config::new("".into());
+
+        let tuned = tuned_config(&config, &stats(1000, 0.0));
+        assert_eq!(tuned.validator_chunk_size, config.validator_chunk_size);
+
+        let tuned = tuned_config(&config, &stats(1_000_000, 0.0));
+        assert_eq!(tuned.validator_chunk_size, 1024);
+
+        let tuned = tuned_config(&config, &stats(100_000_000, 0.0));
+        assert_eq!(tuned.validator_chunk_size, MAX_AUTO_VALIDATOR_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn update_period_scales_with_batch_time() {
+        let config = Config::new("".into());
+        let period_ms = config.update_period as f64 * 1000.0;
+
+        let tuned = tuned_config(&config, &stats(0, period_ms * TARGET_LOAD));
+        assert_eq!(tuned.update_period, config.update_period);
+
+        let tuned = tuned_config(&config, &stats(0, period_ms));
+        assert_eq!(tuned.update_period, 2 * config.update_period);
+        assert_eq!(tuned.disk_config(), config.disk_config());
+    }
+
+    #[test]
+    fn slow_disk_shrinks_layout() {
+        let config = Config::new("".into());
+        let max_period_ms = (config.update_period * MAX_UPDATE_PERIOD_MULTIPLIER) as f64 * 1000.0;
+
+        let tuned = tuned_config(&config, &stats(0, max_period_ms * 0.75));
+        assert_eq!(tuned.chunk_size, 2 * config.chunk_size);
+        assert_eq!(tuned.history_length, config.history_length);
+
+        let tuned = tuned_config(&config, &stats(0, max_period_ms * 2.0));
+        assert_eq!(tuned.chunk_size, 2 * config.chunk_size);
+        assert_eq!(tuned.history_length, config.history_length / 2);
+        tuned.validate().unwrap();
+    }
+
+    #[test]
+    fn retune_keeps_previous_validator_chunk_size_within_hysteresis() {
+        let config = Config::new("".into());
+
+        let tuned = retune(&config, &stats(1_000_000, 0.0), None);
+        assert_eq!(tuned.disk_config.validator_chunk_size, 1024);
+
+        // Without hysteresis this would tune the validator chunk size to 2048.
+        let retuned = retune(&config, &stats(1_100_000, 0.0), Some(&tuned));
+        assert_eq!(
+            tuned_config(&config, &stats(1_100_000, 0.0)).validator_chunk_size,
+            2048
+        );
+        assert_eq!(retuned, tuned);
+
+        let retuned = retune(&config, &stats(1_400_000, 0.0), Some(&tuned));
+        assert_eq!(retuned.disk_config.validator_chunk_size, 2048);
+    }
+
+    #[test]
+    fn retune_keeps_previous_update_period_within_hysteresis() {
+        let config = Config::new("".into());
+        let target_ms = config.update_period as f64 * 1000.0 * TARGET_LOAD;
+
+        let tuned = retune(&config, &stats(0, target_ms * 1.1), None);
+        assert_eq!(tuned.update_period, 2 * config.update_period);
+
+        // Slightly below target the longer update period is kept.
+        let retuned = retune(&config, &stats(0, target_ms * 0.95), Some(&tuned));
+        assert_eq!(retuned, tuned);
+
+        // Well below target the update period is reduced.
+        let retuned = retune(&config, &stats(0, target_ms * 0.7), Some(&tuned));
+        assert_eq!(retuned.update_period, config.update_period);
+    }
+
+    #[test]
+    fn retune_replaces_previous_outside_configured_bounds() {
+        let config = Config::new("".into());
+        let tuned = retune(&config, &stats(1_000_000, 0.0), None);
+
+        // The configured validator chunk size is a lower bound, even for persisted values.
+        let mut larger_config = config.clone();
+        larger_config.validator_chunk_size = 2048;
+        let retuned = retune(&larger_config, &stats(1_000_000, 0.0), Some(&tuned));
+        assert_eq!(retuned.disk_config.validator_chunk_size, 2048);
+    }
+}
@@ -14,6 +14,7 @@ pub const DEFAULT_SLOT_OFFSET: f64 = 10.5;
 pub const DEFAULT_MAX_DB_SIZE: usize = 512 * 1024; // 512 GiB
 pub const DEFAULT_ATTESTATION_ROOT_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(100_000);
 pub const DEFAULT_BROADCAST: bool = false;
+pub const DEFAULT_AUTO: bool = false;
 
 #[cfg(all(feature = "mdbx", not(any(feature = "lmdb", feature = "redb"))))]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Mdbx;
@@ -48,6 +49,10 @@ pub struct Config {
     pub broadcast: bool,
     /// Database backend to use.
     pub backend: DatabaseBackend,
+    /// Whether to tune the chunk sizes, history length and update period automatically.
+    ///
+    /// In this mode the values above are treated as starting points.
+    pub auto: bool,
 }
 
 /// Immutable configuration parameters which are stored on disk and checked for consistency.
@@ -69,6 +74,10 @@ pub enum DatabaseBackend {
     Lmdb,
     #[cfg(feature = "redb")]
     Redb,
+    /// Store the slasher's tables in the beacon node's own database.
+    #[strum(serialize = "beacon-store")]
+    #[serde(rename = "beacon-store")]
+    BeaconStore,
     Disabled,
 }
 
@@ -92,6 +101,7 @@ impl Config {
             attestation_root_cache_size: DEFAULT_ATTESTATION_ROOT_CACHE_SIZE,
             broadcast: DEFAULT_BROADCAST,
             backend: DEFAULT_BACKEND,
+            auto: DEFAULT_AUTO,
         }
     }
 
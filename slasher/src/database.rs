@@ -2,9 +2,14 @@ pub mod interface;
 mod lmdb_impl;
 mod mdbx_impl;
 mod redb_impl;
+pub mod store_impl;
 
 use crate::{
-    metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
+    auto_tune::{self, TunedConfig, TuningStats},
+    config::DiskConfig,
+    metrics,
+    migrate::MigrationProgress,
+    AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
     ProposerSlashingStatus,
 };
 use byteorder::{BigEndian, ByteOrder};
@@ -18,6 +23,7 @@ use ssz_derive::{Decode, Encode};
 use std::borrow::{Borrow, Cow};
 use std::marker::PhantomData;
 use std::sync::Arc;
+use store_impl::BackingStore;
 use tree_hash::TreeHash;
 use types::{
     AggregateSignature, AttestationData, ChainSpec, Epoch, EthSpec, Hash256, IndexedAttestation,
@@ -59,8 +65,14 @@ const MAX_NUM_DBS: usize = 9;
 const METADATA_VERSION_KEY: &[u8] = &[0];
 /// Constant key under which the slasher configuration is stored in the `metadata_db`.
 const METADATA_CONFIG_KEY: &[u8] = &[1];
-
-const ATTESTER_KEY_SIZE: usize = 7;
+/// Constant key under which the statistics used by the adaptive mode are stored.
+const METADATA_TUNING_STATS_KEY: &[u8] = &[2];
+/// Constant key under which the progress of an interrupted layout migration is stored.
+const METADATA_MIGRATION_KEY: &[u8] = &[3];
+/// Constant key under which the parameters tuned by the adaptive mode are stored.
+const METADATA_TUNED_CONFIG_KEY: &[u8] = &[4];
+
+pub const ATTESTER_KEY_SIZE: usize = 7;
 const PROPOSER_KEY_SIZE: usize = 16;
 const CURRENT_EPOCH_KEY_SIZE: usize = 8;
 const INDEXED_ATTESTATION_ID_SIZE: usize = 6;
@@ -156,6 +168,14 @@ impl CurrentEpochKey {
             validator_index: validator_index.to_be_bytes(),
         }
     }
+
+    pub fn parse(data: Cow<[u8]>) -> Result<u64, Error> {
+        if data.len() == CURRENT_EPOCH_KEY_SIZE {
+            Ok(BigEndian::read_u64(&data))
+        } else {
+            Err(Error::CurrentEpochKeyCorrupt { length: data.len() })
+        }
+    }
 }
 
 impl AsRef<[u8]> for CurrentEpochKey {
@@ -287,12 +307,17 @@ fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
 }
 
 impl<E: EthSpec> SlasherDB<E> {
-    pub fn open(config: Arc<Config>, spec: Arc<ChainSpec>, log: Logger) -> Result<Self, Error> {
+    pub fn open(
+        config: Arc<Config>,
+        backing_store: Option<Arc<dyn BackingStore>>,
+        spec: Arc<ChainSpec>,
+        log: Logger,
+    ) -> Result<Self, Error> {
         info!(log, "Opening slasher database"; "backend" => %config.backend);
 
         std::fs::create_dir_all(&config.database_path)?;
 
-        let env = Box::leak(Box::new(Environment::new(&config)?));
+        let env = Box::leak(Box::new(Environment::new(&config, backing_store)?));
         let databases = env.create_databases()?;
 
         #[cfg(windows)]
@@ -317,15 +342,56 @@ impl<E: EthSpec> SlasherDB<E> {
         db = db.migrate()?;
 
         let mut txn = db.begin_rw_txn()?;
-        if let Some(on_disk_config) = db.load_config(&mut txn)? {
+        if db.config.auto {
+            let stats = db.load_tuning_stats(&mut txn)?.unwrap_or_default();
+            let previous = db.load_tuned_config(&mut txn)?;
+            let tuned = auto_tune::retune(&db.config, &stats, previous.as_ref());
+            let tuned_config = tuned.apply(&db.config);
+            tuned_config.validate()?;
+            db.store_tuned_config(&tuned, &mut txn)?;
+            db.config = Arc::new(tuned_config);
+        }
+        let mut on_disk_config = db.load_config::<DiskConfig>(&mut txn)?;
+        let migration_progress = db.load_migration_progress(&mut txn)?;
+        txn.commit()?;
+
+        // Finish any migration which was interrupted before considering the current config.
+        if let Some(progress) = migration_progress {
+            info!(
+                log,
+                "Resuming slasher database migration";
+                "from" => ?progress.from,
+                "to" => ?progress.to,
+                "step" => ?progress.step,
+            );
+            on_disk_config = Some(progress.to.clone());
+            db.migrate_disk_config(progress, &log)?;
+        }
+
+        if let Some(on_disk_config) = on_disk_config {
             let current_disk_config = db.config.disk_config();
             if current_disk_config != on_disk_config {
-                return Err(Error::ConfigIncompatible {
-                    on_disk_config,
-                    config: current_disk_config,
-                });
+                if !db.config.auto {
+                    return Err(Error::ConfigIncompatible {
+                        on_disk_config,
+                        config: current_disk_config,
+                    });
+                }
+                info!(
+                    log,
+                    "Migrating slasher database layout";
+                    "info" => "this may take several minutes",
+                    "from" => ?on_disk_config,
+                    "to" => ?current_disk_config,
+                );
+                db.migrate_disk_config(
+                    MigrationProgress::new(on_disk_config, current_disk_config),
+                    &log,
+                )?;
             }
         }
+        let mut txn = db.begin_rw_txn()?;
+        db.store_config(&db.config.disk_config(), &mut txn)?;
         txn.commit()?;
 
         Ok(db)
@@ -363,7 +429,11 @@ impl<E: EthSpec> SlasherDB<E> {
             .transpose()
     }
 
-    pub fn store_config(&self, config: &Config, txn: &mut RwTransaction<'_>) -> Result<(), Error> {
+    pub fn store_config(
+        &self,
+        config: &DiskConfig,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
         txn.put(
             &self.databases.metadata_db,
             METADATA_CONFIG_KEY,
@@ -372,6 +442,77 @@ impl<E: EthSpec> SlasherDB<E> {
         Ok(())
     }
 
+    pub fn load_migration_progress(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Option<MigrationProgress>, Error> {
+        txn.get(&self.databases.metadata_db, METADATA_MIGRATION_KEY)?
+            .map(bincode_deserialize)
+            .transpose()
+    }
+
+    pub fn store_migration_progress(
+        &self,
+        progress: &MigrationProgress,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        txn.put(
+            &self.databases.metadata_db,
+            METADATA_MIGRATION_KEY,
+            &bincode::serialize(progress)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_migration_progress(&self, txn: &mut RwTransaction<'_>) -> Result<(), Error> {
+        txn.del(&self.databases.metadata_db, METADATA_MIGRATION_KEY)?;
+        Ok(())
+    }
+
+    pub fn load_tuning_stats(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Option<TuningStats>, Error> {
+        txn.get(&self.databases.metadata_db, METADATA_TUNING_STATS_KEY)?
+            .map(bincode_deserialize)
+            .transpose()
+    }
+
+    pub fn store_tuning_stats(
+        &self,
+        stats: &TuningStats,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        txn.put(
+            &self.databases.metadata_db,
+            METADATA_TUNING_STATS_KEY,
+            &bincode::serialize(stats)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn load_tuned_config(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Option<TunedConfig>, Error> {
+        txn.get(&self.databases.metadata_db, METADATA_TUNED_CONFIG_KEY)?
+            .map(bincode_deserialize)
+            .transpose()
+    }
+
+    pub fn store_tuned_config(
+        &self,
+        tuned: &TunedConfig,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        txn.put(
+            &self.databases.metadata_db,
+            METADATA_TUNED_CONFIG_KEY,
+            &bincode::serialize(tuned)?,
+        )?;
+        Ok(())
+    }
+
     pub fn get_attester_max_target(
         &self,
         validator_index: u64,
@@ -1,7 +1,9 @@
+use crate::database::store_impl::{self, BackingStore};
 use crate::{Config, DatabaseBackend, Error};
 use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 #[cfg(feature = "lmdb")]
 use crate::database::lmdb_impl;
@@ -18,6 +20,7 @@ pub enum Environment {
     Lmdb(lmdb_impl::Environment),
     #[cfg(feature = "redb")]
     Redb(redb_impl::Environment),
+    BeaconStore(store_impl::Environment),
     Disabled,
 }
 
@@ -29,6 +32,7 @@ pub enum RwTransaction<'env> {
     Lmdb(lmdb_impl::RwTransaction<'env>),
     #[cfg(feature = "redb")]
     Redb(redb_impl::RwTransaction<'env>),
+    BeaconStore(store_impl::RwTransaction<'env>),
     Disabled(PhantomData<&'env ()>),
 }
 
@@ -40,6 +44,7 @@ pub enum Database<'env> {
     Lmdb(lmdb_impl::Database<'env>),
     #[cfg(feature = "redb")]
     Redb(redb_impl::Database<'env>),
+    BeaconStore(store_impl::Database<'env>),
     Disabled(PhantomData<&'env ()>),
 }
 
@@ -64,6 +69,7 @@ pub enum Cursor<'env> {
     Lmdb(lmdb_impl::Cursor<'env>),
     #[cfg(feature = "redb")]
     Redb(redb_impl::Cursor<'env>),
+    BeaconStore(store_impl::Cursor<'env>),
     Disabled(PhantomData<&'env ()>),
 }
 
@@ -71,7 +77,13 @@ pub type Key<'a> = Cow<'a, [u8]>;
 pub type Value<'a> = Cow<'a, [u8]>;
 
 impl Environment {
-    pub fn new(config: &Config) -> Result<Environment, Error> {
+    /// Open the database environment for `config.backend`.
+    ///
+    /// The `backing_store` is only used by the `BeaconStore` backend, for which it is required.
+    pub fn new(
+        config: &Config,
+        backing_store: Option<Arc<dyn BackingStore>>,
+    ) -> Result<Environment, Error> {
         match config.backend {
             #[cfg(feature = "mdbx")]
             DatabaseBackend::Mdbx => mdbx_impl::Environment::new(config).map(Environment::Mdbx),
@@ -79,6 +91,10 @@ impl Environment {
             DatabaseBackend::Lmdb => lmdb_impl::Environment::new(config).map(Environment::Lmdb),
             #[cfg(feature = "redb")]
             DatabaseBackend::Redb => redb_impl::Environment::new(config).map(Environment::Redb),
+            DatabaseBackend::BeaconStore => backing_store
+                .ok_or(Error::SlasherBackingStoreMissing)
+                .and_then(store_impl::Environment::new)
+                .map(Environment::BeaconStore),
             DatabaseBackend::Disabled => Err(Error::SlasherDatabaseBackendDisabled),
         }
     }
@@ -91,6 +107,7 @@ impl Environment {
             Self::Lmdb(env) => env.create_databases(),
             #[cfg(feature = "redb")]
             Self::Redb(env) => env.create_databases(),
+            Self::BeaconStore(env) => env.create_databases(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -103,6 +120,7 @@ impl Environment {
             Self::Lmdb(env) => env.begin_rw_txn().map(RwTransaction::Lmdb),
             #[cfg(feature = "redb")]
             Self::Redb(env) => env.begin_rw_txn().map(RwTransaction::Redb),
+            Self::BeaconStore(env) => env.begin_rw_txn().map(RwTransaction::BeaconStore),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -116,6 +134,7 @@ impl Environment {
             Self::Lmdb(env) => env.filenames(config),
             #[cfg(feature = "redb")]
             Self::Redb(env) => env.filenames(config),
+            Self::BeaconStore(env) => env.filenames(),
             _ => vec![],
         }
     }
@@ -134,6 +153,7 @@ impl<'env> RwTransaction<'env> {
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.get(db, key),
             #[cfg(feature = "redb")]
             (Self::Redb(txn), Database::Redb(db)) => txn.get(db, key),
+            (Self::BeaconStore(txn), Database::BeaconStore(db)) => txn.get(db, key),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -151,6 +171,7 @@ impl<'env> RwTransaction<'env> {
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.put(db, key, value),
             #[cfg(feature = "redb")]
             (Self::Redb(txn), Database::Redb(db)) => txn.put(db, key, value),
+            (Self::BeaconStore(txn), Database::BeaconStore(db)) => txn.put(db, key, value),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -163,6 +184,7 @@ impl<'env> RwTransaction<'env> {
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.del(db, key),
             #[cfg(feature = "redb")]
             (Self::Redb(txn), Database::Redb(db)) => txn.del(db, key),
+            (Self::BeaconStore(txn), Database::BeaconStore(db)) => txn.del(db, key),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -175,6 +197,7 @@ impl<'env> RwTransaction<'env> {
             Self::Lmdb(txn) => txn.commit(),
             #[cfg(feature = "redb")]
             Self::Redb(txn) => txn.commit(),
+            Self::BeaconStore(txn) => txn.commit(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -187,6 +210,9 @@ impl<'env> RwTransaction<'env> {
             (Self::Lmdb(txn), Database::Lmdb(db)) => txn.cursor(db).map(Cursor::Lmdb),
             #[cfg(feature = "redb")]
             (Self::Redb(txn), Database::Redb(db)) => txn.cursor(db).map(Cursor::Redb),
+            (Self::BeaconStore(txn), Database::BeaconStore(db)) => {
+                txn.cursor(db).map(Cursor::BeaconStore)
+            }
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -202,6 +228,7 @@ impl<'env> Cursor<'env> {
             Cursor::Lmdb(cursor) => cursor.first_key(),
             #[cfg(feature = "redb")]
             Cursor::Redb(cursor) => cursor.first_key(),
+            Cursor::BeaconStore(cursor) => cursor.first_key(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -215,6 +242,7 @@ impl<'env> Cursor<'env> {
             Cursor::Lmdb(cursor) => cursor.last_key(),
             #[cfg(feature = "redb")]
             Cursor::Redb(cursor) => cursor.last_key(),
+            Cursor::BeaconStore(cursor) => cursor.last_key(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -227,6 +255,36 @@ impl<'env> Cursor<'env> {
             Cursor::Lmdb(cursor) => cursor.next_key(),
             #[cfg(feature = "redb")]
             Cursor::Redb(cursor) => cursor.next_key(),
+            Cursor::BeaconStore(cursor) => cursor.next_key(),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+
+    /// Return the first key `>= key` in the current database while advancing the cursor's
+    /// position.
+    pub fn seek_key(&mut self, key: &[u8]) -> Result<Option<Key>, Error> {
+        match self {
+            #[cfg(feature = "mdbx")]
+            Cursor::Mdbx(cursor) => cursor.seek_key(key),
+            #[cfg(feature = "lmdb")]
+            Cursor::Lmdb(cursor) => cursor.seek_key(key),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.seek_key(key),
+            Cursor::BeaconStore(cursor) => cursor.seek_key(key),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+
+    /// Return the key and value at the cursor's position.
+    pub fn get_current(&mut self) -> Result<Option<(Key, Value)>, Error> {
+        match self {
+            #[cfg(feature = "mdbx")]
+            Cursor::Mdbx(cursor) => cursor.get_current(),
+            #[cfg(feature = "lmdb")]
+            Cursor::Lmdb(cursor) => cursor.get_current(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.get_current(),
+            Cursor::BeaconStore(cursor) => cursor.get_current(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -239,6 +297,7 @@ impl<'env> Cursor<'env> {
             Cursor::Lmdb(cursor) => cursor.delete_current(),
             #[cfg(feature = "redb")]
             Cursor::Redb(cursor) => cursor.delete_current(),
+            Cursor::BeaconStore(cursor) => cursor.delete_current(),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -251,6 +310,7 @@ impl<'env> Cursor<'env> {
             Self::Lmdb(cursor) => cursor.put(key, value),
             #[cfg(feature = "redb")]
             Self::Redb(cursor) => cursor.put(key, value),
+            Self::BeaconStore(cursor) => cursor.put(key, value),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
@@ -266,6 +326,7 @@ impl<'env> Cursor<'env> {
             Self::Lmdb(txn) => txn.delete_while(f),
             #[cfg(feature = "redb")]
             Self::Redb(txn) => txn.delete_while(f),
+            Self::BeaconStore(txn) => txn.delete_while(f),
             _ => Err(Error::MismatchedDatabaseVariant),
         }
     }
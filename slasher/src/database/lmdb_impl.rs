@@ -8,7 +8,7 @@ use crate::{
     },
 };
 use lmdb::{Cursor as _, DatabaseFlags, Transaction, WriteFlags};
-use lmdb_sys::{MDB_FIRST, MDB_GET_CURRENT, MDB_LAST, MDB_NEXT};
+use lmdb_sys::{MDB_FIRST, MDB_GET_CURRENT, MDB_LAST, MDB_NEXT, MDB_SET_RANGE};
 use std::path::PathBuf;
 
 #[derive(Debug)]
@@ -164,6 +164,15 @@ impl<'env> Cursor<'env> {
         Ok(opt_key)
     }
 
+    pub fn seek_key(&mut self, key: &[u8]) -> Result<Option<Key<'env>>, Error> {
+        let opt_key = self
+            .cursor
+            .get(Some(key), None, MDB_SET_RANGE)
+            .optional()?
+            .and_then(|(key, _)| Some(Cow::Borrowed(key?)));
+        Ok(opt_key)
+    }
+
     pub fn get_current(&mut self) -> Result<Option<(Key<'env>, Value<'env>)>, Error> {
         // FIXME: lmdb has an extremely broken API which can mutate the SHARED REFERENCE
         // `value` after `get_current` is called. We need to convert it to a Vec here in order
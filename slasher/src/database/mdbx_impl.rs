@@ -169,6 +169,11 @@ impl<'env> Cursor<'env> {
         Ok(opt_key)
     }
 
+    pub fn seek_key(&mut self, key: &[u8]) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        let opt_key = self.cursor.set_range(key)?.map(|(key_bytes, ())| key_bytes);
+        Ok(opt_key)
+    }
+
     pub fn get_current(&mut self) -> Result<Option<(Key<'env>, Value<'env>)>, Error> {
         Ok(self.cursor.get_current()?)
     }
@@ -195,6 +195,24 @@ impl<'env> Cursor<'env> {
         Ok(None)
     }
 
+    pub fn seek_key(&mut self, key: &[u8]) -> Result<Option<Key<'env>>, Error> {
+        let table_definition: TableDefinition<'_, &[u8], &[u8]> =
+            TableDefinition::new(&self.db.table_name);
+        let table = self.txn.open_table(table_definition)?;
+        let range: std::ops::RangeFrom<&[u8]> = key..;
+        let next = table
+            .range(range)?
+            .next()
+            .map(|x| x.map(|(key, _)| key.value().to_vec()));
+
+        if let Some(owned_key) = next {
+            let owned_key = owned_key?;
+            self.current_key = Some(Cow::from(owned_key));
+            return Ok(self.current_key.clone());
+        }
+        Ok(None)
+    }
+
     pub fn get_current(&self) -> Result<Option<(Key<'env>, Value<'env>)>, Error> {
         let table_definition: TableDefinition<'_, &[u8], &[u8]> =
             TableDefinition::new(&self.db.table_name);
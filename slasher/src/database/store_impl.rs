@@ -0,0 +1,419 @@
+//! Slasher database backend which stores its tables inside a key-value store provided by the
+//! host application, i.e. the beacon node's hot database.
+//!
+//! The backing store has no notion of transactions, so writes are buffered in memory by the
+//! `RwTransaction` and applied as a single atomic batch on commit. Only one write transaction
+//! may be open at a time.
+use crate::{
+    database::{
+        interface::{Key, OpenDatabases, Value},
+        *,
+    },
+    Error,
+};
+use derivative::Derivative;
+use parking_lot::{Mutex, MutexGuard};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::Bound;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Iterator over the `(key, value)` pairs of a `BackingStore`.
+pub type BackingStoreIter<'a> = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), String>> + 'a>;
+
+/// A key-value store which the slasher can keep its tables in.
+///
+/// Keys are opaque byte strings which must be iterated in lexicographic order.
+pub trait BackingStore: Send + Sync {
+    /// Fetch the value for `key`.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, String>;
+
+    /// Iterate the entries whose keys start with `prefix`, beginning at the first key `>= from`.
+    fn iter_from<'a>(&'a self, prefix: &[u8], from: &[u8]) -> BackingStoreIter<'a>;
+
+    /// Atomically apply a batch of writes. A value of `None` deletes the key.
+    fn write_batch(&self, ops: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<(), String>;
+}
+
+/// Table names in the order of their one-byte key prefixes.
+///
+/// New tables must be appended to preserve the prefixes of existing tables.
+const TABLES: [&str; MAX_NUM_DBS] = [
+    INDEXED_ATTESTATION_DB,
+    INDEXED_ATTESTATION_ID_DB,
+    ATTESTERS_DB,
+    ATTESTERS_MAX_TARGETS_DB,
+    MIN_TARGETS_DB,
+    MAX_TARGETS_DB,
+    CURRENT_EPOCHS_DB,
+    PROPOSERS_DB,
+    METADATA_DB,
+];
+
+pub struct Environment {
+    store: Arc<dyn BackingStore>,
+    write_lock: Mutex<()>,
+    /// The last committed key in each table, if known.
+    ///
+    /// Used to avoid scanning an entire table to find its last key.
+    last_keys: Mutex<HashMap<u8, Option<Vec<u8>>>>,
+}
+
+impl fmt::Debug for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Environment").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+pub struct Database<'env> {
+    prefix: u8,
+    _phantom: PhantomData<&'env ()>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct RwTransaction<'env> {
+    #[derivative(Debug = "ignore")]
+    env: &'env Environment,
+    #[derivative(Debug = "ignore")]
+    _write_guard: MutexGuard<'env, ()>,
+    /// Pending writes, keyed by full (prefixed) key. `None` marks a deletion.
+    pending: RefCell<BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+#[derive(Debug)]
+pub struct Cursor<'env> {
+    txn: &'env RwTransaction<'env>,
+    prefix: u8,
+    current_key: Option<Vec<u8>>,
+}
+
+fn prefixed_key(prefix: u8, key: &[u8]) -> Vec<u8> {
+    let mut full_key = Vec::with_capacity(key.len() + 1);
+    full_key.push(prefix);
+    full_key.extend_from_slice(key);
+    full_key
+}
+
+impl Environment {
+    pub fn new(store: Arc<dyn BackingStore>) -> Result<Environment, Error> {
+        Ok(Environment {
+            store,
+            write_lock: Mutex::new(()),
+            last_keys: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn create_databases(&self) -> Result<OpenDatabases, Error> {
+        let open = |name: &str| -> Result<crate::Database, Error> {
+            let prefix = TABLES
+                .iter()
+                .position(|table| *table == name)
+                .ok_or(Error::MismatchedDatabaseVariant)?;
+            Ok(crate::Database::BeaconStore(Database {
+                prefix: prefix as u8,
+                _phantom: PhantomData,
+            }))
+        };
+
+        Ok(OpenDatabases {
+            indexed_attestation_db: open(INDEXED_ATTESTATION_DB)?,
+            indexed_attestation_id_db: open(INDEXED_ATTESTATION_ID_DB)?,
+            attesters_db: open(ATTESTERS_DB)?,
+            attesters_max_targets_db: open(ATTESTERS_MAX_TARGETS_DB)?,
+            min_targets_db: open(MIN_TARGETS_DB)?,
+            max_targets_db: open(MAX_TARGETS_DB)?,
+            current_epochs_db: open(CURRENT_EPOCHS_DB)?,
+            proposers_db: open(PROPOSERS_DB)?,
+            metadata_db: open(METADATA_DB)?,
+        })
+    }
+
+    /// The slasher's tables live inside the host's database, so there are no files to report.
+    pub fn filenames(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction, Error> {
+        Ok(RwTransaction {
+            env: self,
+            _write_guard: self.write_lock.lock(),
+            pending: RefCell::new(BTreeMap::new()),
+        })
+    }
+
+    /// Return the first committed key `>= from` in the table with `prefix` which satisfies
+    /// `filter`, with the table prefix removed.
+    fn committed_key_from(
+        &self,
+        prefix: u8,
+        from: &[u8],
+        filter: impl Fn(&[u8]) -> bool,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        for result in self.store.iter_from(&[prefix], from) {
+            let (key, _) = result.map_err(Error::BackingStoreError)?;
+            if filter(&key) {
+                return Ok(Some(key[1..].to_vec()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return the last committed key in the table with `prefix` which satisfies `filter`.
+    ///
+    /// Finding the last key requires a scan of the table, so the unfiltered result is cached
+    /// and only recomputed when the cached key is rejected by `filter`.
+    fn committed_last_key(
+        &self,
+        prefix: u8,
+        filter: impl Fn(&[u8]) -> bool,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut last_keys = self.last_keys.lock();
+        if let Some(last_key) = last_keys.get(&prefix) {
+            if last_key
+                .as_ref()
+                .map_or(true, |key| filter(&prefixed_key(prefix, key)))
+            {
+                return Ok(last_key.clone());
+            }
+        }
+
+        let mut last_key = None;
+        let mut last_filtered_key = None;
+        for result in self.store.iter_from(&[prefix], &[prefix]) {
+            let (key, _) = result.map_err(Error::BackingStoreError)?;
+            if filter(&key) {
+                last_filtered_key = Some(key[1..].to_vec());
+            }
+            last_key = Some(key[1..].to_vec());
+        }
+        last_keys.insert(prefix, last_key);
+        Ok(last_filtered_key)
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    fn get_prefixed(&self, full_key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        if let Some(pending) = self.pending.borrow().get(full_key) {
+            return Ok(pending.clone());
+        }
+        self.env
+            .store
+            .get(full_key)
+            .map_err(Error::BackingStoreError)
+    }
+
+    fn is_deleted(&self, full_key: &[u8]) -> bool {
+        matches!(self.pending.borrow().get(full_key), Some(None))
+    }
+
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &'env Database,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        Ok(self
+            .get_prefixed(&prefixed_key(db.prefix, key.as_ref()))?
+            .map(Cow::from))
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: &Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        self.pending.borrow_mut().insert(
+            prefixed_key(db.prefix, key.as_ref()),
+            Some(value.as_ref().to_vec()),
+        );
+        Ok(())
+    }
+
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: &Database, key: K) -> Result<(), Error> {
+        self.pending
+            .borrow_mut()
+            .insert(prefixed_key(db.prefix, key.as_ref()), None);
+        Ok(())
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        let pending = self.pending.into_inner();
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        // Keep the cache of last keys in sync with the batch, forgetting any table whose last key
+        // is deleted.
+        {
+            let mut last_keys = self.env.last_keys.lock();
+            for (full_key, value) in &pending {
+                let prefix = full_key[0];
+                let key = &full_key[1..];
+                let Some(last_key) = last_keys.get(&prefix).cloned() else {
+                    continue;
+                };
+                match (value, last_key.as_deref()) {
+                    (Some(_), Some(last)) if key <= last => (),
+                    (Some(_), _) => {
+                        last_keys.insert(prefix, Some(key.to_vec()));
+                    }
+                    (None, Some(last)) if key == last => {
+                        last_keys.remove(&prefix);
+                    }
+                    (None, _) => (),
+                }
+            }
+        }
+
+        self.env
+            .store
+            .write_batch(pending.into_iter().collect())
+            .map_err(|e| {
+                // The cache may now be ahead of the database, so discard it.
+                self.env.last_keys.lock().clear();
+                Error::BackingStoreError(e)
+            })
+    }
+
+    pub fn cursor<'a>(&'a mut self, db: &'a Database) -> Result<Cursor<'a>, Error> {
+        Ok(Cursor {
+            txn: self,
+            prefix: db.prefix,
+            current_key: None,
+        })
+    }
+
+    /// Return the first key in the table with `prefix` which is within the `start` bound, i.e.
+    /// the first key in the table if `start` is unbounded. Pending writes are taken into account.
+    fn key_from(&self, prefix: u8, start: Bound<&[u8]>) -> Result<Option<Vec<u8>>, Error> {
+        let (from, exclusive) = match start {
+            Bound::Included(key) => (prefixed_key(prefix, key), false),
+            Bound::Excluded(key) => (prefixed_key(prefix, key), true),
+            Bound::Unbounded => (vec![prefix], false),
+        };
+        let start = if exclusive {
+            Bound::Excluded(from.clone())
+        } else {
+            Bound::Included(from.clone())
+        };
+        let end = prefix
+            .checked_add(1)
+            .map_or(Bound::Unbounded, |next_prefix| {
+                Bound::Excluded(vec![next_prefix])
+            });
+
+        let pending_key = self
+            .pending
+            .borrow()
+            .range((start, end))
+            .find(|(_, value)| value.is_some())
+            .map(|(key, _)| key[1..].to_vec());
+
+        let committed_key = self.env.committed_key_from(prefix, &from, |full_key| {
+            (!exclusive || full_key != from.as_slice()) && !self.is_deleted(full_key)
+        })?;
+
+        Ok(match (pending_key, committed_key) {
+            (Some(a), Some(b)) => Some(std::cmp::min(a, b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    fn last_key_in_table(&self, prefix: u8) -> Result<Option<Vec<u8>>, Error> {
+        let pending_key = self
+            .pending
+            .borrow()
+            .range(vec![prefix]..)
+            .take_while(|(key, _)| key[0] == prefix)
+            .filter(|(_, value)| value.is_some())
+            .last()
+            .map(|(key, _)| key[1..].to_vec());
+
+        let committed_key = self
+            .env
+            .committed_last_key(prefix, |full_key| !self.is_deleted(full_key))?;
+
+        Ok(std::cmp::max(pending_key, committed_key))
+    }
+}
+
+impl<'env> Cursor<'env> {
+    pub fn first_key(&mut self) -> Result<Option<Key>, Error> {
+        self.current_key = self.txn.key_from(self.prefix, Bound::Unbounded)?;
+        Ok(self.current_key.clone().map(Cow::from))
+    }
+
+    pub fn seek_key(&mut self, key: &[u8]) -> Result<Option<Key<'env>>, Error> {
+        self.current_key = self.txn.key_from(self.prefix, Bound::Included(key))?;
+        Ok(self.current_key.clone().map(Cow::from))
+    }
+
+    pub fn last_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        self.current_key = self.txn.last_key_in_table(self.prefix)?;
+        Ok(self.current_key.clone().map(Cow::from))
+    }
+
+    pub fn get_current(&self) -> Result<Option<(Key<'env>, Value<'env>)>, Error> {
+        let Some(key) = &self.current_key else {
+            return Ok(None);
+        };
+        Ok(self
+            .txn
+            .get_prefixed(&prefixed_key(self.prefix, key))?
+            .map(|value| (Cow::from(key.clone()), Cow::from(value))))
+    }
+
+    pub fn next_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        let Some(current_key) = &self.current_key else {
+            return Ok(None);
+        };
+        self.current_key = self
+            .txn
+            .key_from(self.prefix, Bound::Excluded(current_key))?;
+        Ok(self.current_key.clone().map(Cow::from))
+    }
+
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        if let Some(key) = &self.current_key {
+            self.txn
+                .pending
+                .borrow_mut()
+                .insert(prefixed_key(self.prefix, key), None);
+        }
+        Ok(())
+    }
+
+    pub fn delete_while(
+        &mut self,
+        f: impl Fn(&[u8]) -> Result<bool, Error>,
+    ) -> Result<Vec<Cow<'_, [u8]>>, Error> {
+        let mut result = vec![];
+
+        while let Some((key, value)) = self.get_current()? {
+            if f(&key)? {
+                result.push(Cow::from(value.into_owned()));
+                self.delete_current()?;
+                if self.next_key()?.is_none() {
+                    break;
+                }
+            } else {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        self.txn.pending.borrow_mut().insert(
+            prefixed_key(self.prefix, key.as_ref()),
+            Some(value.as_ref().to_vec()),
+        );
+        Ok(())
+    }
+}
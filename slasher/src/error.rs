@@ -11,6 +11,8 @@ pub enum Error {
     #[cfg(feature = "redb")]
     DatabaseRedbError(redb::Error),
     SlasherDatabaseBackendDisabled,
+    SlasherBackingStoreMissing,
+    BackingStoreError(String),
     MismatchedDatabaseVariant,
     DatabaseIOError(io::Error),
     DatabasePermissionsError(filesystem::Error),
@@ -55,6 +57,9 @@ pub enum Error {
     ProposerKeyCorrupt {
         length: usize,
     },
+    CurrentEpochKeyCorrupt {
+        length: usize,
+    },
     IndexedAttestationIdKeyCorrupt {
         length: usize,
     },
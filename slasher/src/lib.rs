@@ -7,6 +7,7 @@
 mod array;
 mod attestation_queue;
 mod attester_record;
+pub mod auto_tune;
 mod batch_stats;
 mod block_queue;
 pub mod config;
@@ -24,6 +25,7 @@ pub use block_queue::BlockQueue;
 pub use config::{Config, DatabaseBackend, DatabaseBackendOverride};
 pub use database::{
     interface::{Database, Environment, RwTransaction},
+    store_impl::{BackingStore, BackingStoreIter},
     IndexedAttestationId, SlasherDB,
 };
 pub use error::Error;
@@ -1,5 +1,66 @@
-use crate::{database::CURRENT_SCHEMA_VERSION, Error, SlasherDB};
-use types::EthSpec;
+use crate::array::{self, MaxTargetChunk, MinTargetChunk};
+use crate::config::DiskConfig;
+use crate::database::{AttesterKey, CurrentEpochKey, ATTESTER_KEY_SIZE, CURRENT_SCHEMA_VERSION};
+use crate::{Config, Error, RwTransaction, SlasherDB};
+use byteorder::{BigEndian, ByteOrder};
+use serde::{Deserialize, Serialize};
+use slog::{debug, info, Logger};
+use ssz::Decode;
+use std::borrow::Cow;
+use std::collections::{hash_map, HashMap};
+use std::mem;
+use types::{Epoch, EthSpec};
+
+/// The maximum number of chunks or attester records written by each transaction of a layout
+/// migration.
+const MIGRATION_BATCH_SIZE: usize = 4096;
+
+/// One of the min-max target arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetArray {
+    Min,
+    Max,
+}
+
+/// The next step of a layout migration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MigrationStep {
+    /// Write the array in the new layout to staging keys, from the validator with the given index.
+    StageArray {
+        array: TargetArray,
+        next_validator: u64,
+    },
+    /// Delete the array in the old layout.
+    DeleteArray { array: TargetArray },
+    /// Move the array in the new layout from the staging keys into place.
+    UnstageArray { array: TargetArray },
+    /// Re-key the attester records to staging keys, from the given key.
+    StageAttesters { next_key: Option<Vec<u8>> },
+    /// Move the re-keyed attester records from the staging keys into place, from the given key.
+    UnstageAttesters { next_key: Option<Vec<u8>> },
+}
+
+/// The progress of a layout migration, which is stored with each batch so that an interrupted
+/// migration can be resumed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationProgress {
+    pub from: DiskConfig,
+    pub to: DiskConfig,
+    pub step: MigrationStep,
+}
+
+impl MigrationProgress {
+    pub fn new(from: DiskConfig, to: DiskConfig) -> Self {
+        Self {
+            from,
+            to,
+            step: MigrationStep::StageArray {
+                array: TargetArray::Min,
+                next_validator: 0,
+            },
+        }
+    }
+}
 
 impl<E: EthSpec> SlasherDB<E> {
     /// If the database exists, and has a schema, attempt to migrate it to the current version.
@@ -26,4 +87,281 @@ impl<E: EthSpec> SlasherDB<E> {
             Ok(self)
         }
     }
+
+    /// Rewrite the data stored using the `progress.from` layout to the `progress.to` layout,
+    /// starting from `progress.step`.
+    ///
+    /// This rewrites the min-max target arrays, and if the history length has changed, re-keys
+    /// the attester records which are stored modulo the history length. Each step is applied in
+    /// batches which are committed along with the progress of the migration, and the `to` layout
+    /// is stored once the migration is complete.
+    pub(crate) fn migrate_disk_config(
+        &self,
+        mut progress: MigrationProgress,
+        log: &Logger,
+    ) -> Result<(), Error> {
+        let from = self.layout_config(&progress.from);
+        let to = self.layout_config(&progress.to);
+
+        let mut txn = self.begin_rw_txn()?;
+        let validators = self.load_current_epochs(&mut txn)?;
+        self.store_migration_progress(&progress, &mut txn)?;
+        txn.commit()?;
+
+        loop {
+            let mut txn = self.begin_rw_txn()?;
+            let next_step = self.migration_step(&progress, &validators, &from, &to, &mut txn)?;
+            let Some(next_step) = next_step else {
+                self.store_config(&progress.to, &mut txn)?;
+                self.delete_migration_progress(&mut txn)?;
+                txn.commit()?;
+                break;
+            };
+            if mem::discriminant(&next_step) != mem::discriminant(&progress.step) {
+                debug!(log, "Slasher migration step complete"; "next_step" => ?next_step);
+            }
+            progress.step = next_step;
+            self.store_migration_progress(&progress, &mut txn)?;
+            txn.commit()?;
+        }
+
+        info!(
+            log,
+            "Slasher database migration complete";
+            "layout" => ?progress.to,
+        );
+        Ok(())
+    }
+
+    /// The config with the chunk sizes and history length of the `layout`.
+    fn layout_config(&self, layout: &DiskConfig) -> Config {
+        let mut config = self.config.as_ref().clone();
+        config.chunk_size = layout.chunk_size;
+        config.validator_chunk_size = layout.validator_chunk_size;
+        config.history_length = layout.history_length;
+        config
+    }
+
+    /// Apply a batch of the current step of the migration, returning the next step, or `None`
+    /// if the migration is complete.
+    fn migration_step(
+        &self,
+        progress: &MigrationProgress,
+        validators: &[(u64, Epoch)],
+        from: &Config,
+        to: &Config,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Option<MigrationStep>, Error> {
+        let next_step = match &progress.step {
+            MigrationStep::StageArray {
+                array,
+                next_validator,
+            } => {
+                let start = validators.partition_point(|(v, _)| v < next_validator);
+                let validators = &validators[start..];
+                let next_validator = match array {
+                    TargetArray::Min => array::stage_array::<E, MinTargetChunk>(
+                        self,
+                        txn,
+                        validators,
+                        from,
+                        to,
+                        MIGRATION_BATCH_SIZE,
+                    )?,
+                    TargetArray::Max => array::stage_array::<E, MaxTargetChunk>(
+                        self,
+                        txn,
+                        validators,
+                        from,
+                        to,
+                        MIGRATION_BATCH_SIZE,
+                    )?,
+                };
+                match next_validator {
+                    Some(next_validator) => MigrationStep::StageArray {
+                        array: *array,
+                        next_validator,
+                    },
+                    None => MigrationStep::DeleteArray { array: *array },
+                }
+            }
+            MigrationStep::DeleteArray { array } => {
+                let finished = match array {
+                    TargetArray::Min => array::delete_array_layout::<E, MinTargetChunk>(
+                        self,
+                        txn,
+                        MIGRATION_BATCH_SIZE,
+                    )?,
+                    TargetArray::Max => array::delete_array_layout::<E, MaxTargetChunk>(
+                        self,
+                        txn,
+                        MIGRATION_BATCH_SIZE,
+                    )?,
+                };
+                if finished {
+                    MigrationStep::UnstageArray { array: *array }
+                } else {
+                    MigrationStep::DeleteArray { array: *array }
+                }
+            }
+            MigrationStep::UnstageArray { array } => {
+                let finished = match array {
+                    TargetArray::Min => {
+                        array::unstage_array::<E, MinTargetChunk>(self, txn, MIGRATION_BATCH_SIZE)?
+                    }
+                    TargetArray::Max => {
+                        array::unstage_array::<E, MaxTargetChunk>(self, txn, MIGRATION_BATCH_SIZE)?
+                    }
+                };
+                match array {
+                    _ if !finished => MigrationStep::UnstageArray { array: *array },
+                    TargetArray::Min => MigrationStep::StageArray {
+                        array: TargetArray::Max,
+                        next_validator: 0,
+                    },
+                    TargetArray::Max if from.history_length == to.history_length => {
+                        return Ok(None)
+                    }
+                    TargetArray::Max => MigrationStep::StageAttesters { next_key: None },
+                }
+            }
+            MigrationStep::StageAttesters { next_key } => {
+                match self.stage_attesters(txn, next_key.as_deref(), from, to)? {
+                    Some(next_key) => MigrationStep::StageAttesters {
+                        next_key: Some(next_key),
+                    },
+                    None => MigrationStep::UnstageAttesters { next_key: None },
+                }
+            }
+            MigrationStep::UnstageAttesters { next_key } => {
+                match self.unstage_attesters(txn, next_key.as_deref())? {
+                    Some(next_key) => MigrationStep::UnstageAttesters {
+                        next_key: Some(next_key),
+                    },
+                    None => return Ok(None),
+                }
+            }
+        };
+        Ok(Some(next_step))
+    }
+
+    /// Re-key a batch of the attester records stored under the `from` history length, starting
+    /// from the key `start`, and return the key to continue from if any remain.
+    ///
+    /// The key of a record only contains its target epoch modulo the history length, so the
+    /// target epoch is recovered relative to the validator's maximum target. The re-keyed records
+    /// are written to staging keys with an extra byte, which don't collide with the keys of
+    /// either history length.
+    fn stage_attesters(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        start: Option<&[u8]>,
+        from: &Config,
+        to: &Config,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let (records, next_key) =
+            self.scan_attesters(txn, start, |key| key.len() == ATTESTER_KEY_SIZE)?;
+
+        let history_length = std::cmp::min(from.history_length, to.history_length) as u64;
+        let mut max_targets = HashMap::new();
+        for (old_key, value) in records {
+            txn.del(&self.databases.attesters_db, &old_key)?;
+
+            let offset = BigEndian::read_uint(&old_key[..2], 2);
+            let validator_index = BigEndian::read_uint(&old_key[2..], 5);
+            let max_target = match max_targets.entry(validator_index) {
+                hash_map::Entry::Occupied(entry) => *entry.get(),
+                hash_map::Entry::Vacant(entry) => {
+                    *entry.insert(self.get_attester_max_target(validator_index, txn)?)
+                }
+            };
+            let Some(max_target) = max_target else {
+                continue;
+            };
+
+            let min_target = (max_target.as_u64() + 1).saturating_sub(history_length);
+            let distance = (max_target.as_u64() + from.history_length as u64 - offset)
+                % from.history_length as u64;
+            if let Some(target_epoch) = max_target.as_u64().checked_sub(distance) {
+                if target_epoch >= min_target {
+                    let new_key = AttesterKey::new(validator_index, Epoch::new(target_epoch), to);
+                    let mut staging_key = new_key.as_ref().to_vec();
+                    staging_key.push(0);
+                    txn.put(&self.databases.attesters_db, staging_key, value)?;
+                }
+            }
+        }
+
+        Ok(next_key)
+    }
+
+    /// Move a batch of the re-keyed attester records from their staging keys into place, starting
+    /// from the key `start`, and return the key to continue from if any remain.
+    ///
+    /// The key of each record sorts immediately before its staging key, so records are never
+    /// visited twice.
+    fn unstage_attesters(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        start: Option<&[u8]>,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let (records, next_key) =
+            self.scan_attesters(txn, start, |key| key.len() == ATTESTER_KEY_SIZE + 1)?;
+
+        for (staging_key, value) in records {
+            txn.put(
+                &self.databases.attesters_db,
+                &staging_key[..ATTESTER_KEY_SIZE],
+                value,
+            )?;
+            txn.del(&self.databases.attesters_db, staging_key)?;
+        }
+
+        Ok(next_key)
+    }
+
+    /// Read up to `MIGRATION_BATCH_SIZE` keys of the attesters database from `start`, or from the
+    /// first key, returning the records whose keys satisfy `filter` and the next key if any.
+    #[allow(clippy::type_complexity)]
+    fn scan_attesters(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        start: Option<&[u8]>,
+        filter: impl Fn(&[u8]) -> bool,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), Error> {
+        let mut records = vec![];
+        let mut cursor = txn.cursor(&self.databases.attesters_db)?;
+        let mut next = match start {
+            Some(key) => cursor.seek_key(key)?,
+            None => cursor.first_key()?,
+        }
+        .map(Cow::into_owned);
+
+        for _ in 0..MIGRATION_BATCH_SIZE {
+            let Some(key) = next else {
+                return Ok((records, None));
+            };
+            if filter(&key) {
+                let (_, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+                records.push((key, value.into_owned()));
+            }
+            next = cursor.next_key()?.map(Cow::into_owned);
+        }
+        Ok((records, next))
+    }
+
+    /// Load the current epoch of every validator with data in the min-max target arrays, sorted
+    /// by validator index.
+    fn load_current_epochs(&self, txn: &mut RwTransaction<'_>) -> Result<Vec<(u64, Epoch)>, Error> {
+        let mut current_epochs = vec![];
+        let mut cursor = txn.cursor(&self.databases.current_epochs_db)?;
+        let mut next = cursor.first_key()?.map(Cow::into_owned);
+        while let Some(key) = next {
+            let validator_index = CurrentEpochKey::parse(Cow::Owned(key))?;
+            let (_, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+            current_epochs.push((validator_index, Epoch::from_ssz_bytes(&value)?));
+            next = cursor.next_key()?.map(Cow::into_owned);
+        }
+        Ok(current_epochs)
+    }
 }
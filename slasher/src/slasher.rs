@@ -1,3 +1,4 @@
+use crate::auto_tune::{self, TunedConfig, TuningStats};
 use crate::batch_stats::{AttestationStats, BatchStats, BlockStats};
 use crate::metrics::{
     self, SLASHER_NUM_ATTESTATIONS_DEFERRED, SLASHER_NUM_ATTESTATIONS_DROPPED,
@@ -5,13 +6,14 @@ use crate::metrics::{
     SLASHER_NUM_BLOCKS_PROCESSED,
 };
 use crate::{
-    array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, Error,
-    IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
+    array, AttestationBatch, AttestationQueue, AttesterRecord, BackingStore, BlockQueue, Config,
+    Error, IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
 };
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Instant;
 use types::{
     AttesterSlashing, ChainSpec, Epoch, EthSpec, IndexedAttestation, ProposerSlashing,
     SignedBeaconBlockHeader,
@@ -24,15 +26,37 @@ pub struct Slasher<E: EthSpec> {
     block_queue: BlockQueue,
     attester_slashings: Mutex<HashSet<AttesterSlashing<E>>>,
     proposer_slashings: Mutex<HashSet<ProposerSlashing>>,
+    /// Statistics used to tune the slasher, only updated if `config.auto` is set.
+    tuning_stats: Mutex<TuningStats>,
+    /// The parameters currently tuned from `tuning_stats`, only updated if `config.auto` is set.
+    tuned_config: Mutex<TunedConfig>,
+    /// The config supplied by the user, which `tuned_config` is tuned from.
+    base_config: Arc<Config>,
     config: Arc<Config>,
     log: Logger,
 }
 
 impl<E: EthSpec> Slasher<E> {
     pub fn open(config: Config, spec: Arc<ChainSpec>, log: Logger) -> Result<Self, Error> {
+        Self::open_with_backing_store(config, None, spec, log)
+    }
+
+    /// Open the slasher, supplying the store to use for the `BeaconStore` database backend.
+    pub fn open_with_backing_store(
+        config: Config,
+        backing_store: Option<Arc<dyn BackingStore>>,
+        spec: Arc<ChainSpec>,
+        log: Logger,
+    ) -> Result<Self, Error> {
         config.validate()?;
-        let config = Arc::new(config);
-        let db = SlasherDB::open(config.clone(), spec, log.clone())?;
+        let base_config = Arc::new(config);
+        let db = SlasherDB::open(base_config.clone(), backing_store, spec, log.clone())?;
+        // The database may have tuned the config if it is running in adaptive mode.
+        let config = db.config.clone();
+        let tuned_config = Mutex::new(TunedConfig::from_config(&config));
+        let mut txn = db.begin_rw_txn()?;
+        let tuning_stats = Mutex::new(db.load_tuning_stats(&mut txn)?.unwrap_or_default());
+        drop(txn);
         let attester_slashings = Mutex::new(HashSet::new());
         let proposer_slashings = Mutex::new(HashSet::new());
         let attestation_queue = AttestationQueue::default();
@@ -43,6 +67,9 @@ impl<E: EthSpec> Slasher<E> {
             block_queue,
             attester_slashings,
             proposer_slashings,
+            tuning_stats,
+            tuned_config,
+            base_config,
             config,
             log,
         })
@@ -66,6 +93,17 @@ impl<E: EthSpec> Slasher<E> {
         &self.log
     }
 
+    /// Return the period in seconds at which batches should be processed.
+    ///
+    /// In adaptive mode this changes as the slasher observes its own batch processing time.
+    pub fn update_period(&self) -> u64 {
+        if self.config.auto {
+            self.tuned_config.lock().update_period
+        } else {
+            self.config.update_period
+        }
+    }
+
     /// Accept an attestation from the network and queue it for processing.
     pub fn accept_attestation(&self, attestation: IndexedAttestation<E>) {
         self.attestation_queue.queue(attestation);
@@ -78,16 +116,61 @@ impl<E: EthSpec> Slasher<E> {
 
     /// Apply queued blocks and attestations to the on-disk database, and detect slashings!
     pub fn process_queued(&self, current_epoch: Epoch) -> Result<BatchStats, Error> {
+        let t = Instant::now();
         let mut txn = self.db.begin_rw_txn()?;
         let block_stats = self.process_blocks(&mut txn)?;
         let attestation_stats = self.process_attestations(current_epoch, &mut txn)?;
         txn.commit()?;
+
+        if self.config.auto {
+            self.record_batch_time(t)?;
+        }
+
         Ok(BatchStats {
             block_stats,
             attestation_stats,
         })
     }
 
+    /// Update the tuning statistics with the time taken by the batch started at `t`, and re-tune
+    /// if the statistics have moved far enough from those the current parameters were tuned for.
+    fn record_batch_time(&self, t: Instant) -> Result<(), Error> {
+        let mut stats = self.tuning_stats.lock();
+        stats.record_batch(t.elapsed());
+        let mut tuned_config = self.tuned_config.lock();
+        let tuned = auto_tune::retune(&self.base_config, &stats, Some(&tuned_config));
+
+        let mut txn = self.db.begin_rw_txn()?;
+        self.db.store_tuning_stats(&stats, &mut txn)?;
+        if tuned != *tuned_config {
+            self.db.store_tuned_config(&tuned, &mut txn)?;
+        }
+        txn.commit()?;
+        drop(stats);
+        let previous = std::mem::replace(&mut *tuned_config, tuned.clone());
+        drop(tuned_config);
+
+        if tuned.update_period != previous.update_period {
+            info!(
+                self.log,
+                "Tuned slasher update period";
+                "update_period" => tuned.update_period,
+            );
+        }
+        if tuned.disk_config != previous.disk_config
+            && tuned.disk_config != self.config.disk_config()
+        {
+            info!(
+                self.log,
+                "Slasher database layout will be tuned on restart";
+                "chunk_size" => tuned.disk_config.chunk_size,
+                "validator_chunk_size" => tuned.disk_config.validator_chunk_size,
+                "history_length" => tuned.disk_config.history_length,
+            );
+        }
+        Ok(())
+    }
+
     /// Apply queued blocks to the on-disk database.
     ///
     /// Return the number of blocks
@@ -131,6 +214,18 @@ impl<E: EthSpec> Slasher<E> {
         let snapshot = self.attestation_queue.dequeue();
         let num_processed = snapshot.len();
 
+        if self.config.auto {
+            if let Some(max_validator_index) = snapshot
+                .iter()
+                .flat_map(|record| record.indexed.attesting_indices_iter())
+                .max()
+            {
+                self.tuning_stats
+                    .lock()
+                    .observe_validator_index(*max_validator_index);
+            }
+        }
+
         // Filter attestations for relevance.
         let (snapshot, deferred, num_dropped) = self.validate(snapshot, current_epoch);
         let num_valid = snapshot.len();
@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use crate::{BackingStore, BackingStoreIter};
+use parking_lot::RwLock;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use types::{
     indexed_attestation::{IndexedAttestationBase, IndexedAttestationElectra},
@@ -150,3 +152,48 @@ pub fn block(slot: u64, proposer_index: u64, block_root: u64) -> SignedBeaconBlo
 pub fn chain_spec() -> Arc<ChainSpec> {
     Arc::new(E::default_spec())
 }
+
+/// An in-memory `BackingStore` for testing the `BeaconStore` database backend.
+#[derive(Debug, Default)]
+pub struct MemoryBackingStore {
+    map: RwLock<BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryBackingStore {
+    pub fn len(&self) -> usize {
+        self.map.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.read().is_empty()
+    }
+}
+
+impl BackingStore for MemoryBackingStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.map.read().get(key).cloned())
+    }
+
+    fn iter_from<'a>(&'a self, prefix: &[u8], from: &[u8]) -> BackingStoreIter<'a> {
+        let prefix = prefix.to_vec();
+        let entries = self
+            .map
+            .read()
+            .range(from.to_vec()..)
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, value)| Ok((key.clone(), value.clone())))
+            .collect::<Vec<_>>();
+        Box::new(entries.into_iter())
+    }
+
+    fn write_batch(&self, ops: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<(), String> {
+        let mut map = self.map.write();
+        for (key, value) in ops {
+            match value {
+                Some(value) => map.insert(key, value),
+                None => map.remove(&key),
+            };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use clap::{Arg, ArgAction, Command};
+use clap_utils::{get_color_style, FLAG_HEADER};
+
+pub const CMD: &str = "slasher";
+pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Run a slasher as a separate process. Attestations and blocks are streamed from a \
+             beacon node's HTTP API, and any slashings found are submitted back to the beacon \
+             node's operation pool, from where they are broadcast to the network. This allows \
+             the disk and CPU load of the slasher to be moved off the staking node.",
+        )
+        .styles(get_color_style())
+        .display_order(0)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new("beacon-node")
+                .long("beacon-node")
+                .value_name("NETWORK_ADDRESS")
+                .help(
+                    "HTTP API endpoint of the beacon node to collect attestations and blocks \
+                     from, and to submit slashings to.",
+                )
+                .default_value(DEFAULT_BEACON_NODE)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("beacon-node-token-file")
+                .long("beacon-node-token-file")
+                .value_name("PATH")
+                .help(
+                    "Path to a file containing an API token to present to the beacon node, if it \
+                     runs with --http-token-file. The token must have the validator role.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-dir")
+                .long("slasher-dir")
+                .help(
+                    "Set the slasher's database directory. Defaults to `slasher_db` in the data \
+                     directory.",
+                )
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-update-period")
+                .long("slasher-update-period")
+                .help("Configure how often the slasher runs batch processing.")
+                .value_name("SECONDS")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-slot-offset")
+                .long("slasher-slot-offset")
+                .help(
+                    "Set the delay from the start of the slot at which the slasher should ingest \
+                     attestations. Only effective if the slasher-update-period is a multiple of \
+                     the slot duration.",
+                )
+                .value_name("SECONDS")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-history-length")
+                .long("slasher-history-length")
+                .help(
+                    "Configure how many epochs of history the slasher keeps. Immutable after \
+                     initialization.",
+                )
+                .value_name("EPOCHS")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-max-db-size")
+                .long("slasher-max-db-size")
+                .help("Maximum size of the MDBX database used by the slasher.")
+                .value_name("GIGABYTES")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-att-cache-size")
+                .long("slasher-att-cache-size")
+                .help("Set the maximum number of attestation roots for the slasher to cache")
+                .value_name("COUNT")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-chunk-size")
+                .long("slasher-chunk-size")
+                .help("Number of epochs per validator per chunk stored on disk.")
+                .value_name("EPOCHS")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-validator-chunk-size")
+                .long("slasher-validator-chunk-size")
+                .help("Number of validators per chunk stored on disk.")
+                .value_name("NUM_VALIDATORS")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-auto")
+                .long("slasher-auto")
+                .help(
+                    "Tune the slasher's chunk sizes, history length and update period \
+                     automatically, based on the number of validators and the time taken to \
+                     process each batch. The values of the other slasher flags are used as \
+                     starting points. Changes to the database layout are applied on restart by \
+                     migrating the existing database.",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("slasher-backend")
+                .long("slasher-backend")
+                .value_name("DATABASE")
+                .help(
+                    "Set the database backend to be used by the slasher. The `beacon-store` \
+                     backend is not available to a standalone slasher.",
+                )
+                .action(ArgAction::Set)
+                .value_parser(slasher::DatabaseBackend::VARIANTS.to_vec())
+                .display_order(0),
+        )
+}
@@ -0,0 +1,97 @@
+use crate::cli::DEFAULT_BEACON_NODE;
+use clap::ArgMatches;
+use clap_utils::parse_optional;
+use directory::parse_path_or_default;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slasher::DatabaseBackend;
+use std::path::PathBuf;
+
+/// Configuration for a slasher running in its own process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// The beacon node to stream attestations and blocks from, and to submit slashings to.
+    pub beacon_node: SensitiveUrl,
+    /// A file containing the API token to present to the beacon node, if it requires one.
+    pub beacon_node_token_file: Option<PathBuf>,
+    pub slasher: slasher::Config,
+}
+
+impl Config {
+    pub fn from_cli(cli_args: &ArgMatches) -> Result<Self, String> {
+        let beacon_node = SensitiveUrl::parse(
+            cli_args
+                .get_one::<String>("beacon-node")
+                .map(String::as_str)
+                .unwrap_or(DEFAULT_BEACON_NODE),
+        )
+        .map_err(|e| format!("Unable to parse beacon node URL: {:?}", e))?;
+
+        let beacon_node_token_file =
+            parse_optional::<String>(cli_args, "beacon-node-token-file")?.map(PathBuf::from);
+
+        let slasher_dir = if let Some(slasher_dir) = cli_args.get_one::<String>("slasher-dir") {
+            PathBuf::from(slasher_dir)
+        } else {
+            parse_path_or_default(cli_args, "datadir")?.join("slasher_db")
+        };
+
+        let mut slasher = slasher::Config::new(slasher_dir);
+
+        if let Some(update_period) = parse_optional(cli_args, "slasher-update-period")? {
+            slasher.update_period = update_period;
+        }
+
+        if let Some(slot_offset) = parse_optional::<f64>(cli_args, "slasher-slot-offset")? {
+            if slot_offset.is_finite() {
+                slasher.slot_offset = slot_offset;
+            } else {
+                return Err(format!(
+                    "invalid float for slasher-slot-offset: {}",
+                    slot_offset
+                ));
+            }
+        }
+
+        if let Some(history_length) = parse_optional(cli_args, "slasher-history-length")? {
+            slasher.history_length = history_length;
+        }
+
+        if let Some(max_db_size_gbs) = parse_optional::<usize>(cli_args, "slasher-max-db-size")? {
+            slasher.max_db_size_mbs = max_db_size_gbs * 1024;
+        }
+
+        if let Some(attestation_cache_size) = parse_optional(cli_args, "slasher-att-cache-size")? {
+            slasher.attestation_root_cache_size = attestation_cache_size;
+        }
+
+        if let Some(chunk_size) = parse_optional(cli_args, "slasher-chunk-size")? {
+            slasher.chunk_size = chunk_size;
+        }
+
+        if let Some(validator_chunk_size) =
+            parse_optional(cli_args, "slasher-validator-chunk-size")?
+        {
+            slasher.validator_chunk_size = validator_chunk_size;
+        }
+
+        if let Some(backend) = parse_optional(cli_args, "slasher-backend")? {
+            slasher.backend = backend;
+        }
+        if slasher.backend == DatabaseBackend::BeaconStore {
+            return Err(
+                "The beacon-store slasher backend requires the slasher to run inside the beacon \
+                 node"
+                    .to_string(),
+            );
+        }
+
+        slasher.auto = cli_args.get_flag("slasher-auto");
+
+        Ok(Self {
+            beacon_node,
+            beacon_node_token_file,
+            slasher,
+        })
+    }
+}
@@ -0,0 +1,360 @@
+//! A slasher which runs in its own process, separate from the beacon node.
+//!
+//! Attestations and block headers are streamed from the `slasher_attestation` and
+//! `slasher_block_header` events of a beacon node's HTTP API. The beacon node emits these events
+//! at the same points where it would feed an in-process slasher, so messages which fail gossip
+//! verification for being slashable are included. Slashings are submitted back to the beacon
+//! node's operation pool via the standard API, which verifies and broadcasts them.
+mod cli;
+mod config;
+
+pub use cli::{cli_app, CMD};
+pub use config::Config;
+
+use directory::size_of_dir;
+use environment::RuntimeContext;
+use eth2::types::{EventKind, EventTopic};
+use eth2::{reqwest::ClientBuilder, BeaconNodeHttpClient, Timeouts};
+use futures::StreamExt;
+use slasher::{
+    metrics::{self, SLASHER_DATABASE_SIZE, SLASHER_RUN_TIME},
+    DatabaseBackendOverride, Slasher,
+};
+use slog::{debug, error, info, trace, warn, Logger};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::time::{interval_at, sleep, Duration, Instant, MissedTickBehavior};
+use types::{ChainSpec, Epoch, EthSpec, IndexedAttestation};
+
+/// Timeout for requests to the beacon node.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+/// Time to wait before retrying after a failed request or a dropped event stream.
+const RETRY_DELAY: Duration = Duration::from_secs(6);
+
+/// Open the slasher and spawn its tasks on the `context`'s executor.
+pub async fn run<E: EthSpec>(context: RuntimeContext<E>, mut config: Config) -> Result<(), String> {
+    let log = context.log().clone();
+    let executor = context.executor.clone();
+    let spec = Arc::new(context.eth2_config.spec.clone());
+
+    let mut client_builder = ClientBuilder::new();
+    if let Some(token_file) = &config.beacon_node_token_file {
+        client_builder = client_builder.default_headers(eth2::api_token_headers(token_file)?);
+    }
+    let client = client_builder
+        .build()
+        .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+    let beacon_node = BeaconNodeHttpClient::from_components(
+        config.beacon_node.clone(),
+        client,
+        Timeouts::set_all(HTTP_TIMEOUT),
+    );
+    let slot_clock = wait_for_genesis(&beacon_node, &spec, &log).await?;
+
+    match config.slasher.override_backend() {
+        DatabaseBackendOverride::Success(old_backend) => {
+            info!(
+                log,
+                "Slasher backend overridden";
+                "reason" => "database exists",
+                "configured_backend" => %old_backend,
+                "override_backend" => %config.slasher.backend,
+            );
+        }
+        DatabaseBackendOverride::Failure(path) => {
+            warn!(
+                log,
+                "Slasher backend override failed";
+                "advice" => "delete old MDBX database or enable MDBX backend",
+                "path" => path.display()
+            );
+        }
+        _ => {}
+    }
+    let slasher = Arc::new(
+        Slasher::open(
+            config.slasher,
+            spec.clone(),
+            log.new(slog::o!("service" => "slasher")),
+        )
+        .map_err(|e| format!("Slasher open error: {:?}", e))?,
+    );
+
+    info!(
+        log,
+        "Starting standalone slasher";
+        "beacon_node" => %beacon_node,
+        "database_path" => slasher.config().database_path.display(),
+    );
+
+    executor.spawn(
+        stream_events(beacon_node.clone(), slasher.clone(), spec.clone()),
+        "slasher_event_stream",
+    );
+    executor.spawn(
+        run_processor(beacon_node, slasher, slot_clock, spec, executor.clone()),
+        "slasher_processor",
+    );
+
+    Ok(())
+}
+
+/// Wait for the beacon node to report the genesis time, and check that it is on our network.
+async fn wait_for_genesis(
+    beacon_node: &BeaconNodeHttpClient,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<SystemTimeSlotClock, String> {
+    let genesis = loop {
+        match beacon_node.get_beacon_genesis().await {
+            Ok(genesis) => break genesis.data,
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to read genesis from beacon node";
+                    "error" => %e,
+                    "beacon_node" => %beacon_node,
+                );
+                sleep(RETRY_DELAY).await;
+            }
+        }
+    };
+
+    if genesis.genesis_fork_version != spec.genesis_fork_version {
+        return Err(format!(
+            "Beacon node is on a different network: genesis fork version {:?}, expected {:?}",
+            genesis.genesis_fork_version, spec.genesis_fork_version
+        ));
+    }
+
+    Ok(SystemTimeSlotClock::new(
+        spec.genesis_slot,
+        Duration::from_secs(genesis.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    ))
+}
+
+/// Feed the slasher from the beacon node's event stream, reconnecting whenever it drops.
+async fn stream_events<E: EthSpec>(
+    beacon_node: BeaconNodeHttpClient,
+    slasher: Arc<Slasher<E>>,
+    spec: Arc<ChainSpec>,
+) {
+    let log = slasher.log().clone();
+    let topics = [
+        EventTopic::SlasherAttestation,
+        EventTopic::SlasherBlockHeader,
+    ];
+
+    loop {
+        match beacon_node.get_events::<E>(&topics).await {
+            Ok(stream) => {
+                info!(log, "Subscribed to beacon node events"; "beacon_node" => %beacon_node);
+                let mut stream = Box::pin(stream);
+                while let Some(event) = stream.next().await {
+                    match event {
+                        Ok(EventKind::SlasherAttestation(attestation)) => {
+                            slasher.accept_attestation(with_fork_variant(*attestation, &spec));
+                        }
+                        Ok(EventKind::SlasherBlockHeader(header)) => {
+                            slasher.accept_block_header(*header);
+                        }
+                        Ok(event) => {
+                            trace!(log, "Ignoring event"; "topic" => event.topic_name());
+                        }
+                        Err(e) => {
+                            debug!(log, "Unable to parse beacon node event"; "error" => %e);
+                        }
+                    }
+                }
+                warn!(log, "Beacon node event stream ended"; "beacon_node" => %beacon_node);
+            }
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to subscribe to beacon node events";
+                    "error" => %e,
+                    "beacon_node" => %beacon_node,
+                    "advice" => "check that the beacon node's HTTP API is enabled and up to date",
+                );
+            }
+        }
+        sleep(RETRY_DELAY).await;
+    }
+}
+
+/// Restore the variant of an indexed attestation received from the event stream.
+///
+/// The JSON encoding of an `IndexedAttestation` does not indicate its variant, so an Electra
+/// attestation can be decoded as a `Base` one. The variant determines that of the slashings
+/// produced, so it must match the fork of the attestation.
+fn with_fork_variant<E: EthSpec>(
+    attestation: IndexedAttestation<E>,
+    spec: &ChainSpec,
+) -> IndexedAttestation<E> {
+    if spec
+        .fork_name_at_slot::<E>(attestation.data().slot)
+        .electra_enabled()
+    {
+        IndexedAttestation::Electra(attestation.to_electra())
+    } else {
+        attestation
+    }
+}
+
+/// Periodically process the slasher's queue and submit any slashings found.
+async fn run_processor<E: EthSpec>(
+    beacon_node: BeaconNodeHttpClient,
+    slasher: Arc<Slasher<E>>,
+    slot_clock: SystemTimeSlotClock,
+    spec: Arc<ChainSpec>,
+    executor: TaskExecutor,
+) {
+    let log = slasher.log().clone();
+    let slot_offset = Duration::from_secs_f64(slasher.config().slot_offset);
+    let start_instant = if let Some(duration_to_next_slot) = slot_clock.duration_to_next_slot() {
+        Instant::now() + duration_to_next_slot + slot_offset
+    } else {
+        error!(log, "Error aligning slasher to slot clock");
+        Instant::now()
+    };
+    let mut update_period = slasher.update_period();
+    let mut interval = interval_at(start_instant, Duration::from_secs(update_period));
+    // Skip any ticks missed while processing, like the in-process slasher does.
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        let tick_instant = interval.tick().await;
+        if let Some(current_slot) = slot_clock.now() {
+            let current_epoch = current_slot.epoch(E::slots_per_epoch());
+
+            let batch_slasher = slasher.clone();
+            let Some(handle) = executor.spawn_blocking_handle(
+                move || process_batch(&batch_slasher, current_epoch),
+                "slasher_batch",
+            ) else {
+                break;
+            };
+            if handle.await.is_err() {
+                break;
+            }
+
+            submit_slashings(&beacon_node, &slasher, &spec, current_epoch).await;
+        } else {
+            trace!(log, "Slasher has nothing to do: we are pre-genesis");
+        }
+
+        let new_update_period = slasher.update_period();
+        if new_update_period != update_period {
+            debug!(
+                log,
+                "Changing slasher update period";
+                "from" => update_period,
+                "to" => new_update_period,
+            );
+            update_period = new_update_period;
+            let period = Duration::from_secs(update_period);
+            interval = interval_at(tick_instant + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        }
+    }
+}
+
+/// Process the queued attestations and blocks, and prune the database.
+fn process_batch<E: EthSpec>(slasher: &Slasher<E>, current_epoch: Epoch) {
+    let log = slasher.log();
+    let t = Instant::now();
+
+    let batch_timer = metrics::start_timer(&SLASHER_RUN_TIME);
+    let stats = match slasher.process_queued(current_epoch) {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            error!(
+                log,
+                "Error during scheduled slasher processing";
+                "epoch" => current_epoch,
+                "error" => ?e,
+            );
+            None
+        }
+    };
+    drop(batch_timer);
+
+    // Prune the database, even in the case where batch processing failed.
+    // If the database is full then pruning could help to free it up.
+    if let Err(e) = slasher.prune_database(current_epoch) {
+        error!(
+            log,
+            "Error during slasher database pruning";
+            "epoch" => current_epoch,
+            "error" => ?e,
+        );
+        return;
+    }
+
+    let database_size = size_of_dir(&slasher.config().database_path);
+    metrics::set_gauge(&SLASHER_DATABASE_SIZE, database_size as i64);
+
+    if let Some(stats) = stats {
+        debug!(
+            log,
+            "Completed slasher update";
+            "epoch" => current_epoch,
+            "time_taken" => format!("{}ms", t.elapsed().as_millis()),
+            "num_attestations" => stats.attestation_stats.num_processed,
+            "num_blocks" => stats.block_stats.num_processed,
+        );
+    }
+}
+
+/// Submit the slashings found to the beacon node, which will verify and broadcast them.
+async fn submit_slashings<E: EthSpec>(
+    beacon_node: &BeaconNodeHttpClient,
+    slasher: &Slasher<E>,
+    spec: &ChainSpec,
+    current_epoch: Epoch,
+) {
+    let log = slasher.log();
+    let fork_name = spec.fork_name_at_epoch(current_epoch);
+
+    for slashing in slasher.get_attester_slashings() {
+        match beacon_node
+            .post_beacon_pool_attester_slashings_v2(&slashing, fork_name)
+            .await
+        {
+            Ok(()) => info!(
+                log,
+                "Submitted attester slashing";
+                "attestation_1_target" => slashing.attestation_1().data().target.epoch,
+                "attestation_2_target" => slashing.attestation_2().data().target.epoch,
+            ),
+            Err(e) => warn!(
+                log,
+                "Unable to submit attester slashing";
+                "error" => %e,
+                "slashing" => ?slashing,
+            ),
+        }
+    }
+
+    for slashing in slasher.get_proposer_slashings() {
+        match beacon_node
+            .post_beacon_pool_proposer_slashings(&slashing)
+            .await
+        {
+            Ok(()) => info!(
+                log,
+                "Submitted proposer slashing";
+                "validator_index" => slashing.proposer_index(),
+                "slot" => slashing.signed_header_1.message.slot,
+            ),
+            Err(e) => warn!(
+                log,
+                "Unable to submit proposer slashing";
+                "error" => %e,
+                "slashing" => ?slashing,
+            ),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    test_utils::{att_slashing, chain_spec, indexed_att, MemoryBackingStore, E},
+    Config, DatabaseBackend, Error, Slasher,
+};
+use std::sync::Arc;
+use tempfile::tempdir;
+use types::Epoch;
+
+fn open_slasher(config: Config, store: Arc<MemoryBackingStore>) -> Result<Slasher<E>, Error> {
+    Slasher::open_with_backing_store(config, Some(store), chain_spec(), test_logger())
+}
+
+fn small_config(tempdir: &tempfile::TempDir) -> Config {
+    let mut config = Config::new(tempdir.path().into());
+    config.backend = DatabaseBackend::BeaconStore;
+    config.chunk_size = 4;
+    config.validator_chunk_size = 2;
+    config.history_length = 32;
+    config
+}
+
+#[test]
+fn layout_change_without_auto_is_rejected() {
+    let tempdir = tempdir().unwrap();
+    let store = Arc::new(MemoryBackingStore::default());
+    let config = small_config(&tempdir);
+
+    drop(open_slasher(config.clone(), store.clone()).unwrap());
+
+    let mut new_config = config;
+    new_config.chunk_size = 8;
+    assert!(matches!(
+        open_slasher(new_config, store),
+        Err(Error::ConfigIncompatible { .. })
+    ));
+}
+
+/// Store attestations using the `config` layout, re-open the slasher in adaptive mode with the
+/// `new_config` layout, then check that slashings against the old attestations are still found.
+fn migration_test(config: Config, new_config: Config) {
+    let store = Arc::new(MemoryBackingStore::default());
+    let current_epoch = Epoch::new(40);
+
+    let att1 = indexed_att([0, 3], 20, 22, 0);
+    let att2 = indexed_att([5], 18, 24, 0);
+    let att3 = indexed_att([7], 30, 31, 0);
+
+    let slasher = open_slasher(config, store.clone()).unwrap();
+    for att in [&att1, &att2, &att3] {
+        slasher.accept_attestation(att.clone());
+    }
+    slasher.process_queued(current_epoch).unwrap();
+    drop(slasher);
+
+    let mut new_config = new_config;
+    new_config.auto = true;
+    let slasher = open_slasher(new_config.clone(), store).unwrap();
+    assert_eq!(slasher.config().disk_config(), new_config.disk_config());
+
+    // Surrounds `att1`.
+    let att4 = indexed_att([0, 3], 19, 23, 0);
+    // Surrounded by `att2`.
+    let att5 = indexed_att([5], 19, 23, 0);
+    // Double vote with `att3`.
+    let att6 = indexed_att([7], 29, 31, 1);
+    for att in [&att4, &att5, &att6] {
+        slasher.accept_attestation(att.clone());
+    }
+    slasher.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![
+            att_slashing(&att4, &att1),
+            att_slashing(&att2, &att5),
+            att_slashing(&att3, &att6),
+        ]
+    );
+}
+
+#[test]
+fn migrate_chunk_sizes() {
+    let tempdir = tempdir().unwrap();
+    let config = small_config(&tempdir);
+    let mut new_config = config.clone();
+    new_config.chunk_size = 8;
+    new_config.validator_chunk_size = 4;
+    migration_test(config, new_config);
+}
+
+#[test]
+fn migrate_to_smaller_validator_chunks() {
+    let tempdir = tempdir().unwrap();
+    let mut config = small_config(&tempdir);
+    config.validator_chunk_size = 8;
+    let mut new_config = config.clone();
+    new_config.validator_chunk_size = 1;
+    migration_test(config, new_config);
+}
+
+#[test]
+fn migrate_history_length() {
+    let tempdir = tempdir().unwrap();
+    let config = small_config(&tempdir);
+
+    let mut longer = config.clone();
+    longer.history_length = 64;
+    migration_test(config.clone(), longer.clone());
+
+    let tempdir = tempdir().unwrap();
+    let mut shorter = small_config(&tempdir);
+    shorter.history_length = 24;
+    let mut config = small_config(&tempdir);
+    config.history_length = 64;
+    migration_test(config, shorter);
+}
+
+#[test]
+fn tuning_stats_persist() {
+    let tempdir = tempdir().unwrap();
+    let store = Arc::new(MemoryBackingStore::default());
+    let mut config = small_config(&tempdir);
+    config.auto = true;
+
+    let slasher = open_slasher(config.clone(), store.clone()).unwrap();
+    slasher.accept_attestation(indexed_att([4096], 0, 1, 0));
+    slasher.process_queued(Epoch::new(1)).unwrap();
+    drop(slasher);
+
+    // The validator chunk size should grow to keep the number of validator chunks low.
+    let slasher = open_slasher(config.clone(), store).unwrap();
+    assert!(slasher.config().validator_chunk_size > config.validator_chunk_size);
+    assert_eq!(slasher.update_period(), config.update_period);
+}
+
+#[test]
+fn tuned_config_persists_within_hysteresis() {
+    let tempdir = tempdir().unwrap();
+    let store = Arc::new(MemoryBackingStore::default());
+    let mut config = small_config(&tempdir);
+    config.auto = true;
+
+    let process_validator = |validator_index: u64, target: u64| {
+        let slasher = open_slasher(config.clone(), store.clone()).unwrap();
+        slasher.accept_attestation(indexed_att([validator_index], 0, target, 0));
+        slasher.process_queued(Epoch::new(target)).unwrap();
+    };
+    let validator_chunk_size = || {
+        open_slasher(config.clone(), store.clone())
+            .unwrap()
+            .config()
+            .validator_chunk_size
+    };
+
+    process_validator(4096, 1);
+    assert_eq!(validator_chunk_size(), 8);
+
+    // Slightly more than 8 validators per chunk is tolerated rather than migrating the database.
+    process_validator(8200, 2);
+    assert_eq!(validator_chunk_size(), 8);
+
+    // Beyond the hysteresis margin the validator chunk size is tuned again.
+    process_validator(11_000, 3);
+    assert_eq!(validator_chunk_size(), 16);
+}
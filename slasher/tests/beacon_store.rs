@@ -0,0 +1,115 @@
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    test_utils::{
+        att_slashing, block as test_block, chain_spec, indexed_att, MemoryBackingStore, E,
+    },
+    Config, DatabaseBackend, Error, Slasher,
+};
+use std::sync::Arc;
+use tempfile::tempdir;
+use types::{Epoch, EthSpec};
+
+fn open_slasher(config: Config, store: Arc<MemoryBackingStore>) -> Slasher<E> {
+    Slasher::open_with_backing_store(config, Some(store), chain_spec(), test_logger()).unwrap()
+}
+
+fn beacon_store_config(tempdir: &tempfile::TempDir) -> Config {
+    let mut config = Config::new(tempdir.path().into());
+    config.backend = DatabaseBackend::BeaconStore;
+    config
+}
+
+#[test]
+fn backing_store_required() {
+    let tempdir = tempdir().unwrap();
+    let config = beacon_store_config(&tempdir);
+    assert!(matches!(
+        Slasher::<E>::open(config, chain_spec(), test_logger()),
+        Err(Error::SlasherBackingStoreMissing)
+    ));
+}
+
+#[test]
+fn double_vote() {
+    let tempdir = tempdir().unwrap();
+    let store = Arc::new(MemoryBackingStore::default());
+    let slasher = open_slasher(beacon_store_config(&tempdir), store.clone());
+
+    let v = vec![0, 1, 2];
+    let att1 = indexed_att(&v, 0, 1, 0);
+    let att2 = indexed_att(&v, 0, 1, 1);
+    let current_epoch = Epoch::new(1);
+
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    slasher.accept_attestation(att2.clone());
+    slasher.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att1, &att2)]
+    );
+    assert!(!store.is_empty());
+    slasher.prune_database(current_epoch).unwrap();
+}
+
+#[test]
+fn surround_vote_across_reopen() {
+    let tempdir = tempdir().unwrap();
+    let store = Arc::new(MemoryBackingStore::default());
+    let config = beacon_store_config(&tempdir);
+    let current_epoch = Epoch::new(4);
+
+    let v = vec![99];
+    let att1 = indexed_att(&v, 1, 2, 0);
+    let att2 = indexed_att(&v, 0, 3, 0);
+
+    let slasher = open_slasher(config.clone(), store.clone());
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    drop(slasher);
+
+    // The first attestation should be loaded from the backing store.
+    let slasher = open_slasher(config, store);
+    slasher.accept_attestation(att2.clone());
+    slasher.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att2, &att1)]
+    );
+}
+
+#[test]
+fn block_pruning() {
+    let slots_per_epoch = E::slots_per_epoch();
+
+    let tempdir = tempdir().unwrap();
+    let store = Arc::new(MemoryBackingStore::default());
+    let mut config = beacon_store_config(&tempdir);
+    config.chunk_size = 2;
+    config.history_length = 2;
+
+    let slasher = open_slasher(config.clone(), store.clone());
+    let current_epoch = Epoch::from(2 * config.history_length);
+
+    let proposer_index = 100_000;
+    for slot in 1..=current_epoch.as_u64() * slots_per_epoch {
+        slasher.accept_block_header(test_block(slot, proposer_index, 0));
+    }
+    slasher.process_queued(current_epoch).unwrap();
+    let num_entries = store.len();
+    slasher.prune_database(current_epoch).unwrap();
+    assert!(store.len() < num_entries, "pruning should delete entries");
+
+    for slot in 1..=current_epoch.as_u64() * slots_per_epoch {
+        slasher.accept_block_header(test_block(slot, proposer_index, 1));
+    }
+    slasher.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        slasher.get_proposer_slashings().len(),
+        (config.history_length - 1) * slots_per_epoch as usize + 1
+    );
+}
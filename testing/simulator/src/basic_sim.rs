@@ -93,6 +93,7 @@ pub fn run_basic_sim(matches: &ArgMatches) -> Result<(), String> {
             compression: false,
             is_restricted: true,
             sse_logging: false,
+            log_repeat_limit: 0,
         })?
         .multi_threaded_tokio_runtime()?
         .build()?;
@@ -138,6 +139,7 @@ pub fn run_basic_sim(matches: &ArgMatches) -> Result<(), String> {
                     extra_nodes,
                     proposer_nodes,
                     genesis_delay,
+                    fault_config: None,
                 },
                 context.clone(),
             ))
@@ -122,4 +122,118 @@ pub fn cli_app() -> Command {
                         .help("Continue after checks (default false)"),
                 ),
         )
+        .subcommand(
+            Command::new("fault-sim")
+                .about(
+                    "Runs a long Beacon Chain simulation with `n` beacon nodes and validator \
+                    clients, each with `v` validators, whilst injecting faults. \
+                    Connections between nodes are delayed and lose packets, and nodes can be \
+                    periodically restarted with an empty database. \
+                    Rather than exiting on the first failed check, the simulation records the \
+                    finality and attestation performance of the network in each epoch and \
+                    prints a report at the end.",
+                )
+                .arg(
+                    Arg::new("nodes")
+                        .short('n')
+                        .long("nodes")
+                        .action(ArgAction::Set)
+                        .default_value("4")
+                        .help("Number of beacon nodes"),
+                )
+                .arg(
+                    Arg::new("validators-per-node")
+                        .short('v')
+                        .long("validators-per-node")
+                        .action(ArgAction::Set)
+                        .default_value("20")
+                        .help("Number of validators per beacon node"),
+                )
+                .arg(
+                    Arg::new("speed-up-factor")
+                        .short('s')
+                        .long("speed-up-factor")
+                        .action(ArgAction::Set)
+                        .default_value("3")
+                        .help("Speed up factor. Please use a divisor of 12."),
+                )
+                .arg(
+                    Arg::new("end-epoch")
+                        .long("end-epoch")
+                        .action(ArgAction::Set)
+                        .default_value("32")
+                        .help("The epoch at which the simulation ends"),
+                )
+                .arg(
+                    Arg::new("latency-ms")
+                        .long("latency-ms")
+                        .action(ArgAction::Set)
+                        .default_value("0")
+                        .help("Delay added to the traffic between nodes, in milliseconds"),
+                )
+                .arg(
+                    Arg::new("jitter-ms")
+                        .long("jitter-ms")
+                        .action(ArgAction::Set)
+                        .default_value("0")
+                        .help(
+                            "Maximum random delay added to the traffic between nodes on top of \
+                            the latency, in milliseconds",
+                        ),
+                )
+                .arg(
+                    Arg::new("packet-loss")
+                        .long("packet-loss")
+                        .action(ArgAction::Set)
+                        .default_value("0")
+                        .help(
+                            "Percentage of packets lost between nodes. Lost packets are \
+                            retransmitted, delaying the traffic behind them.",
+                        ),
+                )
+                .arg(
+                    Arg::new("restart-interval-epochs")
+                        .long("restart-interval-epochs")
+                        .action(ArgAction::Set)
+                        .help(
+                            "Restart a beacon node every this many epochs, cycling through all \
+                            nodes except the boot node. Restarted nodes must resync from their \
+                            peers.",
+                        ),
+                )
+                .arg(
+                    Arg::new("capella-fork-epoch")
+                        .long("capella-fork-epoch")
+                        .action(ArgAction::Set)
+                        .default_value("1")
+                        .help("Epoch of the Capella fork"),
+                )
+                .arg(
+                    Arg::new("deneb-fork-epoch")
+                        .long("deneb-fork-epoch")
+                        .action(ArgAction::Set)
+                        .default_value("2")
+                        .help("Epoch of the Deneb fork"),
+                )
+                .arg(
+                    Arg::new("electra-fork-epoch")
+                        .long("electra-fork-epoch")
+                        .action(ArgAction::Set)
+                        .help("Epoch of the Electra fork. Electra is not scheduled by default."),
+                )
+                .arg(
+                    Arg::new("report-path")
+                        .long("report-path")
+                        .action(ArgAction::Set)
+                        .help("Path to write a JSON report of the simulation to"),
+                )
+                .arg(
+                    Arg::new("debug-level")
+                        .short('d')
+                        .long("debug-level")
+                        .action(ArgAction::Set)
+                        .default_value("info")
+                        .help("Set the severity level of the logs."),
+                ),
+        )
 }
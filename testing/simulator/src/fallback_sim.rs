@@ -101,6 +101,7 @@ pub fn run_fallback_sim(matches: &ArgMatches) -> Result<(), String> {
             compression: false,
             is_restricted: true,
             sse_logging: false,
+            log_repeat_limit: 0,
         })?
         .multi_threaded_tokio_runtime()?
         .build()?;
@@ -146,6 +147,7 @@ pub fn run_fallback_sim(matches: &ArgMatches) -> Result<(), String> {
                     extra_nodes: 0,
                     proposer_nodes: 0,
                     genesis_delay,
+                    fault_config: None,
                 },
                 context.clone(),
             ))
@@ -0,0 +1,193 @@
+//! A TCP proxy which injects latency, jitter and packet loss into the connections of a node.
+//!
+//! Each node advertises the port of its proxy in its ENR, so that every connection made by its
+//! peers passes through the proxy and is delayed in both directions.
+use rand::Rng;
+use std::cmp::max;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Instant};
+
+/// The extra delay for a chunk of data which is lost and has to be retransmitted.
+///
+/// Loss is simulated at the level of the TCP stream, so a lost chunk also holds up all of the
+/// data behind it.
+const RETRANSMISSION_DELAY: Duration = Duration::from_millis(200);
+
+const BUFFER_SIZE: usize = 16 * 1024;
+
+/// The faults to inject into the connections between nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct FaultConfig {
+    /// The fixed delay of every chunk of data.
+    pub latency: Duration,
+    /// The maximum random delay added to `latency`.
+    pub jitter: Duration,
+    /// The probability that a chunk of data is lost, between 0 and 1.
+    pub packet_loss: f64,
+}
+
+impl FaultConfig {
+    /// Returns a random delay for a chunk of data crossing the link.
+    fn delay(&self) -> Duration {
+        let mut rng = rand::thread_rng();
+        let mut delay = self.latency;
+        if !self.jitter.is_zero() {
+            delay += rng.gen_range(Duration::ZERO..=self.jitter);
+        }
+        if rng.gen_bool(self.packet_loss) {
+            delay += RETRANSMISSION_DELAY;
+        }
+        delay
+    }
+}
+
+/// Listens on `listen_port` and forwards each connection to `target_port`, delaying the data in
+/// both directions according to `config`.
+///
+/// The proxy keeps running while the node behind it is restarted, refusing connections until it
+/// is back up.
+pub async fn spawn_tcp_proxy(
+    executor: &TaskExecutor,
+    listen_port: u16,
+    target_port: u16,
+    config: FaultConfig,
+) -> Result<(), String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, listen_port))
+        .await
+        .map_err(|e| format!("Unable to bind proxy to port {}: {:?}", listen_port, e))?;
+
+    let executor_1 = executor.clone();
+    executor.spawn(
+        async move {
+            loop {
+                let Ok((inbound, _)) = listener.accept().await else {
+                    continue;
+                };
+                executor_1.spawn(
+                    async move {
+                        if let Ok(outbound) =
+                            TcpStream::connect((Ipv4Addr::LOCALHOST, target_port)).await
+                        {
+                            proxy_connection(inbound, outbound, config).await;
+                        }
+                    },
+                    "fault_proxy_connection",
+                );
+            }
+        },
+        "fault_proxy",
+    );
+
+    Ok(())
+}
+
+/// Forwards data between `inbound` and `outbound` until either side closes the connection.
+async fn proxy_connection(inbound: TcpStream, outbound: TcpStream, config: FaultConfig) {
+    let (inbound_reader, inbound_writer) = inbound.into_split();
+    let (outbound_reader, outbound_writer) = outbound.into_split();
+
+    tokio::select! {
+        _ = forward(inbound_reader, outbound_writer, config) => (),
+        _ = forward(outbound_reader, inbound_writer, config) => (),
+    }
+}
+
+/// Copies data from `reader` to `writer`, delaying each chunk without reordering them.
+async fn forward(mut reader: OwnedReadHalf, mut writer: OwnedWriteHalf, config: FaultConfig) {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+
+    let read = async move {
+        let mut buffer = vec![0; BUFFER_SIZE];
+        let mut previous_deadline = Instant::now();
+        loop {
+            let len = match reader.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(len) => len,
+            };
+            // A chunk can't overtake the chunk before it on a TCP stream.
+            let deadline = max(Instant::now() + config.delay(), previous_deadline);
+            previous_deadline = deadline;
+            if sender.send((deadline, buffer[..len].to_vec())).is_err() {
+                break;
+            }
+        }
+    };
+
+    let write = async move {
+        while let Some((deadline, bytes)) = receiver.recv().await {
+            sleep_until(deadline).await;
+            if writer.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    };
+
+    tokio::join!(read, write);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns both ends of a local TCP connection.
+    async fn connection() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (client, server) = tokio::join!(
+            TcpStream::connect((Ipv4Addr::LOCALHOST, port)),
+            listener.accept()
+        );
+        (client.unwrap(), server.unwrap().0)
+    }
+
+    #[tokio::test]
+    async fn proxy_delays_without_reordering() {
+        let config = FaultConfig {
+            latency: Duration::from_millis(50),
+            jitter: Duration::from_millis(50),
+            packet_loss: 0.5,
+        };
+        let (mut sender, inbound) = connection().await;
+        let (outbound, mut receiver) = connection().await;
+        tokio::spawn(proxy_connection(inbound, outbound, config));
+
+        let start = Instant::now();
+        let mut expected = vec![];
+        for i in 0..20u8 {
+            let chunk = vec![i; 100];
+            sender.write_all(&chunk).await.unwrap();
+            expected.extend(chunk);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let mut received = vec![0; expected.len()];
+        receiver.read_exact(&mut received).await.unwrap();
+        assert!(start.elapsed() >= config.latency);
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn delay_is_bounded() {
+        let config = FaultConfig {
+            latency: Duration::from_millis(10),
+            jitter: Duration::from_millis(5),
+            packet_loss: 0.0,
+        };
+        for _ in 0..100 {
+            let delay = config.delay();
+            assert!(delay >= config.latency && delay <= config.latency + config.jitter);
+        }
+
+        let lossy = FaultConfig {
+            packet_loss: 1.0,
+            ..config
+        };
+        assert!(lossy.delay() >= config.latency + RETRANSMISSION_DELAY);
+    }
+}
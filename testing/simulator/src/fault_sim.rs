@@ -0,0 +1,559 @@
+use crate::fault_proxy::FaultConfig;
+use crate::local_network::LocalNetworkParams;
+use crate::retry::with_retry;
+use crate::LocalNetwork;
+use clap::ArgMatches;
+use node_test_rig::{
+    environment::{EnvironmentBuilder, LoggerConfig},
+    eth2::types::{BlockId, StateId},
+    testing_validator_config, ValidatorFiles,
+};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::cmp::max;
+use std::fmt::Debug;
+use std::fs::File;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+use types::{Epoch, EthSpec, Slot};
+
+const GENESIS_DELAY: u64 = 32;
+const ALTAIR_FORK_EPOCH: u64 = 0;
+const BELLATRIX_FORK_EPOCH: u64 = 0;
+
+/// The number of slots for which a node is stopped when it is restarted.
+const RESTART_DOWNTIME_SLOTS: u64 = 4;
+
+const SUGGESTED_FEE_RECIPIENT: [u8; 20] =
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+
+/// The finality and attestation performance of the network over a simulation.
+#[derive(Serialize)]
+struct SimulationReport {
+    nodes: usize,
+    validators_per_node: usize,
+    seconds_per_slot: u64,
+    latency_ms: u64,
+    jitter_ms: u64,
+    packet_loss_percent: f64,
+    restart_interval_epochs: Option<u64>,
+    capella_fork_epoch: u64,
+    deneb_fork_epoch: u64,
+    electra_fork_epoch: Option<u64>,
+    epochs: Vec<EpochReport>,
+    restarts: Vec<RestartReport>,
+    summary: SummaryReport,
+}
+
+/// The state of the network shortly after the start of `epoch`.
+#[derive(Serialize)]
+struct EpochReport {
+    epoch: u64,
+    /// The epoch in which the participation was measured, which is the latest epoch whose
+    /// attestations can no longer be included in a block.
+    participation_epoch: Option<u64>,
+    /// The fraction of the active balance which attested to the correct target, according to the
+    /// boot node.
+    target_participation: Option<f64>,
+    /// The fraction of the active balance which attested to the correct head, according to the
+    /// boot node.
+    head_participation: Option<f64>,
+    nodes: Vec<NodeReport>,
+}
+
+#[derive(Serialize)]
+struct NodeReport {
+    node: usize,
+    /// Whether the node responded to its HTTP API.
+    online: bool,
+    head_slot: Option<u64>,
+    justified_epoch: Option<u64>,
+    finalized_epoch: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct RestartReport {
+    node: usize,
+    epoch: u64,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SummaryReport {
+    /// The latest epoch finalized by the boot node.
+    finalized_epoch: Option<u64>,
+    /// The largest distance between the current and finalized epochs of the boot node.
+    max_finality_distance: Option<u64>,
+    mean_target_participation: Option<f64>,
+    min_target_participation: Option<f64>,
+}
+
+impl SummaryReport {
+    fn new(epochs: &[EpochReport]) -> Self {
+        let boot_node_finality = epochs
+            .iter()
+            .filter_map(|report| Some((report.epoch, report.nodes.first()?.finalized_epoch?)));
+        let participation = epochs
+            .iter()
+            .filter_map(|report| report.target_participation)
+            .collect::<Vec<_>>();
+
+        Self {
+            finalized_epoch: boot_node_finality
+                .clone()
+                .map(|(_, finalized_epoch)| finalized_epoch)
+                .max(),
+            max_finality_distance: boot_node_finality
+                .map(|(epoch, finalized_epoch)| epoch.saturating_sub(finalized_epoch))
+                .max(),
+            mean_target_participation: (!participation.is_empty())
+                .then(|| participation.iter().sum::<f64>() / participation.len() as f64),
+            min_target_participation: participation.iter().copied().reduce(f64::min),
+        }
+    }
+}
+
+pub fn run_fault_sim(matches: &ArgMatches) -> Result<(), String> {
+    let node_count: usize = parse_required(matches, "nodes")?;
+    let validators_per_node: usize = parse_required(matches, "validators-per-node")?;
+    let speed_up_factor: u64 = parse_required(matches, "speed-up-factor")?;
+    let end_epoch: u64 = parse_required(matches, "end-epoch")?;
+    let latency_ms: u64 = parse_required(matches, "latency-ms")?;
+    let jitter_ms: u64 = parse_required(matches, "jitter-ms")?;
+    let packet_loss_percent: f64 = parse_required(matches, "packet-loss")?;
+    let restart_interval_epochs: Option<u64> = parse_optional(matches, "restart-interval-epochs")?;
+    let capella_fork_epoch: u64 = parse_required(matches, "capella-fork-epoch")?;
+    let deneb_fork_epoch: u64 = parse_required(matches, "deneb-fork-epoch")?;
+    let electra_fork_epoch: Option<u64> = parse_optional(matches, "electra-fork-epoch")?;
+    let report_path: Option<PathBuf> = parse_optional(matches, "report-path")?;
+    let log_level: String = parse_required(matches, "debug-level")?;
+
+    if node_count == 0 || speed_up_factor == 0 {
+        return Err("--nodes and --speed-up-factor must be greater than zero".to_string());
+    }
+    if !(0.0..100.0).contains(&packet_loss_percent) {
+        return Err("--packet-loss must be a percentage below 100".to_string());
+    }
+    if restart_interval_epochs.is_some() && node_count < 2 {
+        return Err("Restarts require at least one node besides the boot node".to_string());
+    }
+    if restart_interval_epochs == Some(0) {
+        return Err("--restart-interval-epochs must be greater than zero".to_string());
+    }
+    if deneb_fork_epoch < capella_fork_epoch
+        || electra_fork_epoch.map_or(false, |epoch| epoch < deneb_fork_epoch)
+    {
+        return Err("Fork epochs must be in the order capella, deneb, electra".to_string());
+    }
+
+    println!("Fault Simulator:");
+    println!(" nodes: {}", node_count);
+    println!(" validators-per-node: {}", validators_per_node);
+    println!(" speed-up-factor: {}", speed_up_factor);
+    println!(" end-epoch: {}", end_epoch);
+    println!(" latency-ms: {}", latency_ms);
+    println!(" jitter-ms: {}", jitter_ms);
+    println!(" packet-loss: {}%", packet_loss_percent);
+    println!(" restart-interval-epochs: {:?}", restart_interval_epochs);
+
+    // Generate the directories and keystores required for the validator clients.
+    let validator_files = (0..node_count)
+        .into_par_iter()
+        .map(|i| {
+            println!(
+                "Generating keystores for validator {} of {}",
+                i + 1,
+                node_count
+            );
+
+            let indices =
+                (i * validators_per_node..(i + 1) * validators_per_node).collect::<Vec<_>>();
+            ValidatorFiles::with_keystores(&indices).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut env = EnvironmentBuilder::minimal()
+        .initialize_logger(LoggerConfig {
+            path: None,
+            debug_level: log_level.clone(),
+            logfile_debug_level: log_level,
+            log_format: None,
+            logfile_format: None,
+            log_color: false,
+            disable_log_timestamp: false,
+            max_log_size: 0,
+            max_log_number: 0,
+            compression: false,
+            is_restricted: true,
+            sse_logging: false,
+            log_repeat_limit: 0,
+        })?
+        .multi_threaded_tokio_runtime()?
+        .build()?;
+
+    let spec = &mut env.eth2_config.spec;
+
+    let total_validator_count = validators_per_node * node_count;
+
+    spec.seconds_per_slot /= speed_up_factor;
+    spec.seconds_per_slot = max(1, spec.seconds_per_slot);
+    spec.genesis_delay = GENESIS_DELAY;
+    spec.min_genesis_time = 0;
+    spec.min_genesis_active_validator_count = total_validator_count as u64;
+    // The network starts from a post-merge genesis.
+    spec.altair_fork_epoch = Some(Epoch::new(ALTAIR_FORK_EPOCH));
+    spec.bellatrix_fork_epoch = Some(Epoch::new(BELLATRIX_FORK_EPOCH));
+    spec.capella_fork_epoch = Some(Epoch::new(capella_fork_epoch));
+    spec.deneb_fork_epoch = Some(Epoch::new(deneb_fork_epoch));
+    spec.electra_fork_epoch = electra_fork_epoch.map(Epoch::new);
+
+    let seconds_per_slot = spec.seconds_per_slot;
+    let slot_duration = Duration::from_secs(seconds_per_slot);
+
+    // Only route traffic through the proxies if there is a fault to inject.
+    let fault_config =
+        (latency_ms > 0 || jitter_ms > 0 || packet_loss_percent > 0.0).then_some(FaultConfig {
+            latency: Duration::from_millis(latency_ms),
+            jitter: Duration::from_millis(jitter_ms),
+            packet_loss: packet_loss_percent / 100.0,
+        });
+
+    let context = env.core_context();
+
+    let main_future = async {
+        /*
+         * Create a new `LocalNetwork` with one beacon node.
+         */
+        let max_retries = 3;
+        let (network, beacon_config, mock_execution_config) = with_retry(max_retries, || {
+            Box::pin(LocalNetwork::create_local_network(
+                None,
+                None,
+                LocalNetworkParams {
+                    validator_count: total_validator_count,
+                    node_count,
+                    proposer_nodes: 0,
+                    extra_nodes: 0,
+                    genesis_delay: GENESIS_DELAY,
+                    fault_config,
+                },
+                context.clone(),
+            ))
+        })
+        .await?;
+
+        // Every node besides the boot node can be restarted.
+        network
+            .add_beacon_node(beacon_config.clone(), mock_execution_config.clone(), false)
+            .await?;
+        for _ in 1..node_count {
+            network
+                .add_restartable_beacon_node(beacon_config.clone(), mock_execution_config.clone())
+                .await?;
+        }
+
+        /*
+         * One by one, add validators to the network.
+         */
+        let executor = context.executor.clone();
+        for (i, files) in validator_files.into_iter().enumerate() {
+            let network_1 = network.clone();
+            executor.spawn(
+                async move {
+                    let mut validator_config = testing_validator_config();
+                    validator_config.fee_recipient = Some(SUGGESTED_FEE_RECIPIENT.into());
+                    println!("Adding validator client {}", i);
+                    network_1
+                        .add_validator_client(validator_config, i, files)
+                        .await
+                        .expect("should add validator");
+                },
+                "vc",
+            );
+        }
+
+        // Set all payloads as valid. This effectively assumes the EL is infalliable.
+        network.execution_nodes.write().iter().for_each(|node| {
+            node.server.all_payloads_valid();
+        });
+
+        let genesis = Instant::now() + network.duration_to_genesis().await;
+        println!(
+            "Duration to genesis: {}",
+            genesis.saturating_duration_since(Instant::now()).as_secs()
+        );
+        sleep_until(genesis).await;
+
+        let (epochs, restarts) = futures::join!(
+            monitor_network(network.clone(), genesis, end_epoch, slot_duration),
+            restart_nodes(
+                network.clone(),
+                genesis,
+                end_epoch,
+                restart_interval_epochs,
+                slot_duration
+            ),
+        );
+        let epochs = epochs?;
+
+        println!(
+            "Simulation complete. Finished with {} beacon nodes and {} validator clients",
+            network.beacon_node_count(),
+            network.validator_client_count()
+        );
+
+        // Be explicit about dropping the network, as this kills all the nodes.
+        drop(network);
+
+        let summary = SummaryReport::new(&epochs);
+        Ok::<_, String>(SimulationReport {
+            nodes: node_count,
+            validators_per_node,
+            seconds_per_slot,
+            latency_ms,
+            jitter_ms,
+            packet_loss_percent,
+            restart_interval_epochs,
+            capella_fork_epoch,
+            deneb_fork_epoch,
+            electra_fork_epoch,
+            epochs,
+            restarts,
+            summary,
+        })
+    };
+
+    let result = env.runtime().block_on(main_future);
+
+    env.fire_signal();
+    env.shutdown_on_idle();
+
+    let report = result?;
+    let summary = &report.summary;
+    println!("Summary:");
+    println!(" finalized-epoch: {:?}", summary.finalized_epoch);
+    println!(
+        " max-finality-distance: {:?}",
+        summary.max_finality_distance
+    );
+    println!(
+        " mean-target-participation: {:?}",
+        summary.mean_target_participation
+    );
+    println!(
+        " min-target-participation: {:?}",
+        summary.min_target_participation
+    );
+    println!(" restarts: {}", report.restarts.len());
+
+    if let Some(path) = report_path {
+        let file =
+            File::create(&path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+        serde_json::to_writer_pretty(file, &report)
+            .map_err(|e| format!("Unable to write report to {:?}: {:?}", path, e))?;
+        println!("Wrote report to {:?}", path);
+    }
+
+    Ok(())
+}
+
+/// Records the state of the network shortly after the start of each epoch, up to `end_epoch`.
+async fn monitor_network<E: EthSpec>(
+    network: LocalNetwork<E>,
+    genesis: Instant,
+    end_epoch: u64,
+    slot_duration: Duration,
+) -> Result<Vec<EpochReport>, String> {
+    let mut epochs = vec![];
+    for epoch in (1..=end_epoch).map(Epoch::new) {
+        // Leave half a slot for the first block of the epoch to be imported.
+        let start_slot = epoch.start_slot(E::slots_per_epoch());
+        sleep_until(slot_start(genesis, start_slot, slot_duration) + slot_duration / 2).await;
+
+        let report = epoch_report(&network, epoch).await?;
+        println!(
+            "Epoch {}: {} of {} nodes online, finalized epochs {:?}, target participation {:?}",
+            report.epoch,
+            report.nodes.iter().filter(|node| node.online).count(),
+            report.nodes.len(),
+            report
+                .nodes
+                .iter()
+                .map(|node| node.finalized_epoch)
+                .collect::<Vec<_>>(),
+            report.target_participation,
+        );
+        epochs.push(report);
+    }
+    Ok(epochs)
+}
+
+/// Queries each node for its view of the network. Nodes which are down for a restart are
+/// reported as offline.
+async fn epoch_report<E: EthSpec>(
+    network: &LocalNetwork<E>,
+    epoch: Epoch,
+) -> Result<EpochReport, String> {
+    let remote_nodes = network.remote_nodes()?;
+
+    let mut nodes = vec![];
+    for (node, remote_node) in remote_nodes.iter().enumerate() {
+        let head_slot = remote_node
+            .get_beacon_headers_block_id(BlockId::Head)
+            .await
+            .ok()
+            .flatten()
+            .map(|response| response.data.header.message.slot.as_u64());
+        let finality = remote_node
+            .get_beacon_states_finality_checkpoints(StateId::Head)
+            .await
+            .ok()
+            .flatten()
+            .map(|response| response.data);
+
+        nodes.push(NodeReport {
+            node,
+            online: head_slot.is_some(),
+            head_slot,
+            justified_epoch: finality
+                .as_ref()
+                .map(|finality| finality.current_justified.epoch.as_u64()),
+            finalized_epoch: finality.map(|finality| finality.finalized.epoch.as_u64()),
+        });
+    }
+
+    // Attestations can be included until the end of the epoch after the one they are for, so
+    // the participation of the epoch before last is final. It is read from the end of the last
+    // epoch, where it is the previous epoch.
+    let participation_epoch = epoch.as_u64().checked_sub(2);
+    let participation = match (participation_epoch, remote_nodes.first()) {
+        (Some(_), Some(boot_node)) => boot_node
+            .get_lighthouse_validator_inclusion_global(epoch - 1)
+            .await
+            .ok()
+            .map(|response| response.data)
+            .filter(|data| data.current_epoch_active_gwei > 0),
+        _ => None,
+    };
+
+    Ok(EpochReport {
+        epoch: epoch.as_u64(),
+        participation_epoch,
+        target_participation: participation.as_ref().map(|data| {
+            data.previous_epoch_target_attesting_gwei as f64 / data.current_epoch_active_gwei as f64
+        }),
+        head_participation: participation.as_ref().map(|data| {
+            data.previous_epoch_head_attesting_gwei as f64 / data.current_epoch_active_gwei as f64
+        }),
+        nodes,
+    })
+}
+
+/// Restarts a node every `restart_interval_epochs`, cycling through every node besides the boot
+/// node.
+async fn restart_nodes<E: EthSpec>(
+    network: LocalNetwork<E>,
+    genesis: Instant,
+    end_epoch: u64,
+    restart_interval_epochs: Option<u64>,
+    slot_duration: Duration,
+) -> Vec<RestartReport> {
+    let Some(interval) = restart_interval_epochs else {
+        return vec![];
+    };
+    let restartable_nodes = network.beacon_node_count() - 1;
+    let downtime = slot_duration * RESTART_DOWNTIME_SLOTS as u32;
+
+    let mut restarts = vec![];
+    for (i, epoch) in (interval..end_epoch).step_by(interval as usize).enumerate() {
+        let start_slot = Epoch::new(epoch).start_slot(E::slots_per_epoch());
+        sleep_until(slot_start(genesis, start_slot, slot_duration)).await;
+
+        let node = 1 + i % restartable_nodes;
+        println!("Restarting node {} at epoch {}", node, epoch);
+        let error = network.restart_beacon_node(node, downtime).await.err();
+        if let Some(e) = &error {
+            eprintln!("Unable to restart node {}: {}", node, e);
+        }
+        restarts.push(RestartReport { node, epoch, error });
+    }
+    restarts
+}
+
+fn slot_start(genesis: Instant, slot: Slot, slot_duration: Duration) -> Instant {
+    genesis + slot_duration * slot.as_u64() as u32
+}
+
+fn parse_optional<T: FromStr>(matches: &ArgMatches, name: &str) -> Result<Option<T>, String>
+where
+    T::Err: Debug,
+{
+    matches
+        .get_one::<String>(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| format!("Invalid --{} {:?}: {:?}", name, value, e))
+        })
+        .transpose()
+}
+
+fn parse_required<T: FromStr>(matches: &ArgMatches, name: &str) -> Result<T, String>
+where
+    T::Err: Debug,
+{
+    parse_optional(matches, name)?.ok_or_else(|| format!("Missing --{}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch_report(
+        epoch: u64,
+        finalized_epoch: Option<u64>,
+        target_participation: Option<f64>,
+    ) -> EpochReport {
+        EpochReport {
+            epoch,
+            participation_epoch: epoch.checked_sub(2),
+            target_participation,
+            head_participation: None,
+            nodes: vec![NodeReport {
+                node: 0,
+                online: finalized_epoch.is_some(),
+                head_slot: None,
+                justified_epoch: None,
+                finalized_epoch,
+            }],
+        }
+    }
+
+    #[test]
+    fn summary_report() {
+        let epochs = vec![
+            epoch_report(2, Some(0), None),
+            epoch_report(3, Some(1), Some(1.0)),
+            // The boot node is offline.
+            epoch_report(4, None, Some(0.5)),
+            epoch_report(5, Some(2), Some(0.75)),
+        ];
+        let summary = SummaryReport::new(&epochs);
+
+        assert_eq!(summary.finalized_epoch, Some(2));
+        assert_eq!(summary.max_finality_distance, Some(3));
+        assert_eq!(summary.mean_target_participation, Some(0.75));
+        assert_eq!(summary.min_target_participation, Some(0.5));
+    }
+
+    #[test]
+    fn empty_summary_report() {
+        let summary = SummaryReport::new(&[]);
+        assert_eq!(summary.finalized_epoch, None);
+        assert_eq!(summary.max_finality_distance, None);
+        assert_eq!(summary.mean_target_participation, None);
+        assert_eq!(summary.min_target_participation, None);
+    }
+}
@@ -0,0 +1,41 @@
+//! This crate provides various simulations that create both beacon nodes and validator clients,
+//! each with `v` validators.
+//!
+//! When a simulation runs, there are checks made to ensure that all components are operating
+//! as expected. If any of these checks fail, the simulation will exit immediately.
+//!
+//! The simulations are run by the `simulator` binary, or by `lighthouse simulate` when Lighthouse
+//! is compiled with the `simulator` feature.
+//!
+//! ## Future works
+//!
+//! Presently all the beacon nodes and validator clients all log to stdout. Additionally, the
+//! simulation uses `println` to communicate some info. It might be nice if the nodes logged to
+//! easy-to-find files and stdout only contained info from the simulation.
+//!
+mod basic_sim;
+mod checks;
+mod cli;
+mod fallback_sim;
+mod fault_proxy;
+mod fault_sim;
+mod local_network;
+mod retry;
+
+use clap::ArgMatches;
+use local_network::LocalNetwork;
+use types::MinimalEthSpec;
+
+pub use cli::cli_app;
+
+pub type E = MinimalEthSpec;
+
+/// Runs the simulation selected by the subcommand in `matches`.
+pub fn run_simulation(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        Some(("basic-sim", matches)) => basic_sim::run_basic_sim(matches),
+        Some(("fallback-sim", matches)) => fallback_sim::run_fallback_sim(matches),
+        Some(("fault-sim", matches)) => fault_sim::run_fault_sim(matches),
+        _ => Err("Invalid subcommand. Use --help to see available options".to_string()),
+    }
+}
@@ -1,4 +1,5 @@
 use crate::checks::epoch_delay;
+use crate::fault_proxy::{spawn_tcp_proxy, FaultConfig};
 use eth2_network_config::TRUSTED_SETUP_BYTES;
 use node_test_rig::{
     environment::RuntimeContext,
@@ -9,15 +10,20 @@ use node_test_rig::{
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
 use std::{
+    collections::HashMap,
     net::Ipv4Addr,
+    num::NonZeroU16,
     ops::Deref,
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use task_executor::TaskExecutor;
 use types::{ChainSpec, Epoch, EthSpec};
 
 const BOOTNODE_PORT: u16 = 42424;
 const QUIC_PORT: u16 = 43424;
+const PROXY_PORT: u16 = 44424;
+const HTTP_PORT: u16 = 45424;
 
 pub const EXECUTION_PORT: u16 = 4000;
 
@@ -29,6 +35,8 @@ pub struct LocalNetworkParams {
     pub proposer_nodes: usize,
     pub extra_nodes: usize,
     pub genesis_delay: u64,
+    /// The faults to inject into the connections between nodes, if any.
+    pub fault_config: Option<FaultConfig>,
 }
 
 fn default_client_config(network_params: LocalNetworkParams, genesis_time: u64) -> ClientConfig {
@@ -99,6 +107,14 @@ pub struct Inner<E: EthSpec> {
     pub proposer_nodes: RwLock<Vec<LocalBeaconNode<E>>>,
     pub validator_clients: RwLock<Vec<LocalValidatorClient<E>>>,
     pub execution_nodes: RwLock<Vec<LocalExecutionNode<E>>>,
+    pub fault_config: Option<FaultConfig>,
+    restartable_nodes: RwLock<HashMap<usize, RestartableNode>>,
+}
+
+/// The state required to stop a beacon node and start it again on the same ports.
+struct RestartableNode {
+    config: ClientConfig,
+    exit: async_channel::Sender<()>,
 }
 
 /// Represents a set of interconnected `LocalBeaconNode` and `LocalValidatorClient`.
@@ -137,6 +153,7 @@ impl<E: EthSpec> LocalNetwork<E> {
             + Duration::from_secs(network_params.genesis_delay))
         .as_secs();
 
+        let fault_config = network_params.fault_config;
         let beacon_config = if let Some(config) = client_config {
             config
         } else {
@@ -156,6 +173,8 @@ impl<E: EthSpec> LocalNetwork<E> {
                 proposer_nodes: RwLock::new(vec![]),
                 execution_nodes: RwLock::new(vec![]),
                 validator_clients: RwLock::new(vec![]),
+                fault_config,
+                restartable_nodes: RwLock::new(HashMap::new()),
             }),
         };
 
@@ -199,7 +218,10 @@ impl<E: EthSpec> LocalNetwork<E> {
         );
 
         beacon_config.network.enr_udp4_port = Some(BOOTNODE_PORT.try_into().expect("non zero"));
-        beacon_config.network.enr_tcp4_port = Some(BOOTNODE_PORT.try_into().expect("non zero"));
+        beacon_config.network.enr_tcp4_port = Some(
+            self.advertised_tcp_port(&mut beacon_config, BOOTNODE_PORT, 0)
+                .await?,
+        );
         beacon_config.network.discv5_config.table_filter = |_| true;
 
         let execution_node = LocalExecutionNode::new(
@@ -228,6 +250,7 @@ impl<E: EthSpec> LocalNetwork<E> {
         mut beacon_config: ClientConfig,
         mut mock_execution_config: MockExecutionConfig,
         is_proposer: bool,
+        is_restartable: bool,
     ) -> Result<(LocalBeaconNode<E>, LocalExecutionNode<E>), String> {
         let count = (self.beacon_node_count() + self.proposer_node_count()) as u16;
 
@@ -241,7 +264,10 @@ impl<E: EthSpec> LocalNetwork<E> {
             QUIC_PORT + count,
         );
         beacon_config.network.enr_udp4_port = Some(discv5_port.try_into().unwrap());
-        beacon_config.network.enr_tcp4_port = Some(libp2p_tcp_port.try_into().unwrap());
+        beacon_config.network.enr_tcp4_port = Some(
+            self.advertised_tcp_port(&mut beacon_config, libp2p_tcp_port, count)
+                .await?,
+        );
         beacon_config.network.discv5_config.table_filter = |_| true;
         beacon_config.network.proposer_only = is_proposer;
 
@@ -261,14 +287,89 @@ impl<E: EthSpec> LocalNetwork<E> {
             ..Default::default()
         });
 
-        // Construct beacon node using the config,
-        let beacon_node = LocalBeaconNode::production(
-            self.context.service_context(format!("node_{}", count)),
-            beacon_config,
+        let service_name = format!("node_{}", count);
+        let beacon_node = if is_restartable {
+            // A fixed HTTP port allows validator clients to reconnect after a restart.
+            beacon_config.http_api.listen_port = HTTP_PORT + count;
+
+            let (context, exit) = self.restartable_context(service_name)?;
+            let beacon_node = LocalBeaconNode::production(context, beacon_config.clone()).await?;
+            self.restartable_nodes.write().insert(
+                self.beacon_node_count(),
+                RestartableNode {
+                    config: beacon_config,
+                    exit,
+                },
+            );
+            beacon_node
+        } else {
+            // Construct beacon node using the config,
+            LocalBeaconNode::production(self.context.service_context(service_name), beacon_config)
+                .await?
+        };
+
+        Ok((beacon_node, execution_node))
+    }
+
+    /// Returns the TCP port to advertise in the ENR of the node listening on `libp2p_tcp_port`.
+    ///
+    /// If faults are configured, this is the port of a proxy which injects them into every
+    /// connection to the node.
+    async fn advertised_tcp_port(
+        &self,
+        beacon_config: &mut ClientConfig,
+        libp2p_tcp_port: u16,
+        count: u16,
+    ) -> Result<NonZeroU16, String> {
+        let Some(fault_config) = self.fault_config else {
+            return Ok(libp2p_tcp_port.try_into().expect("non zero"));
+        };
+
+        // QUIC connections would bypass the proxy.
+        beacon_config.network.disable_quic_support = true;
+        let proxy_port = PROXY_PORT + count;
+        spawn_tcp_proxy(
+            &self.context.executor,
+            proxy_port,
+            libp2p_tcp_port,
+            fault_config,
         )
         .await?;
 
-        Ok((beacon_node, execution_node))
+        Ok(proxy_port.try_into().expect("non zero"))
+    }
+
+    /// Returns a context for a beacon node which can be stopped independently of the rest of
+    /// the network, by closing the returned sender.
+    fn restartable_context(
+        &self,
+        service_name: String,
+    ) -> Result<(RuntimeContext<E>, async_channel::Sender<()>), String> {
+        let (exit_sender, exit_receiver) = async_channel::bounded(1);
+        let executor = &self.context.executor;
+        let handle = executor.handle().ok_or("Runtime is shutting down")?;
+
+        let mut context = self.context.service_context(service_name.clone());
+        context.executor = TaskExecutor::new(
+            handle,
+            exit_receiver,
+            executor.log().clone(),
+            executor.shutdown_sender(),
+        )
+        .clone_with_name(service_name);
+
+        // Stop the node along with the rest of the network.
+        let network_exit = executor.exit();
+        let node_exit = exit_sender.clone();
+        executor.spawn_without_exit(
+            async move {
+                network_exit.await;
+                node_exit.close();
+            },
+            "node_exit",
+        );
+
+        Ok((context, exit_sender))
     }
 
     /// Adds a beacon node to the network, connecting to the 0'th beacon node via ENR.
@@ -296,7 +397,7 @@ impl<E: EthSpec> LocalNetwork<E> {
         }
         let (beacon_node, execution_node) = if first_bn_exists {
             // Network already exists. We construct a new node.
-            self.construct_beacon_node(beacon_config, mock_execution_config, is_proposer)
+            self.construct_beacon_node(beacon_config, mock_execution_config, is_proposer, false)
                 .await?
         } else {
             // Network does not exist. We construct a boot node.
@@ -313,6 +414,73 @@ impl<E: EthSpec> LocalNetwork<E> {
         Ok(())
     }
 
+    /// Adds a beacon node which can be restarted with `Self::restart_beacon_node`.
+    ///
+    /// The boot node must already exist, it is never restarted.
+    pub async fn add_restartable_beacon_node(
+        &self,
+        mut beacon_config: ClientConfig,
+        mock_execution_config: MockExecutionConfig,
+    ) -> Result<(), String> {
+        {
+            let read_lock = self.beacon_nodes.read();
+            let boot_node = read_lock
+                .first()
+                .ok_or("The boot node must be added before restartable nodes")?;
+            beacon_config.network.boot_nodes_enr.push(
+                boot_node
+                    .client
+                    .enr()
+                    .expect("Bootnode must have a network."),
+            );
+        }
+        let (beacon_node, execution_node) = self
+            .construct_beacon_node(beacon_config, mock_execution_config, false, true)
+            .await?;
+        self.execution_nodes.write().push(execution_node);
+        self.beacon_nodes.write().push(beacon_node);
+        Ok(())
+    }
+
+    /// Stops the beacon node at `index`, waits for `downtime` and then starts it again on the
+    /// same ports.
+    ///
+    /// The node starts again with an empty database, so it must sync back to the head from its
+    /// peers.
+    pub async fn restart_beacon_node(
+        &self,
+        index: usize,
+        downtime: Duration,
+    ) -> Result<(), String> {
+        let (config, exit) = {
+            let restartable_nodes = self.restartable_nodes.read();
+            let node = restartable_nodes
+                .get(&index)
+                .ok_or_else(|| format!("Beacon node {} is not restartable", index))?;
+            (node.config.clone(), node.exit.clone())
+        };
+
+        exit.close();
+        tokio::time::sleep(downtime).await;
+
+        let (context, exit) = self.restartable_context(format!("node_{}", index))?;
+        let beacon_node = LocalBeaconNode::production(context, config.clone()).await?;
+        let stopped_node = {
+            let mut beacon_nodes = self.beacon_nodes.write();
+            let slot = beacon_nodes
+                .get_mut(index)
+                .ok_or_else(|| format!("No beacon node for index {}", index))?;
+            std::mem::replace(slot, beacon_node)
+        };
+        // Deletes the database of the stopped node.
+        drop(stopped_node);
+
+        self.restartable_nodes
+            .write()
+            .insert(index, RestartableNode { config, exit });
+        Ok(())
+    }
+
     // Add a new node with a delay. This node will not have validators and is only used to test
     // sync.
     pub async fn add_beacon_node_with_delay(
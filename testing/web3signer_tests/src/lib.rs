@@ -46,7 +46,7 @@ mod tests {
             load_pem_certificate, load_pkcs12_identity, InitializedValidators,
         },
         validator_store::{Error as ValidatorStoreError, ValidatorStore},
-        SlashingDatabase, SLASHING_PROTECTION_FILENAME,
+        SlashingDatabase, Web3SignerSlashingProtection, SLASHING_PROTECTION_FILENAME,
     };
 
     /// If the we are unable to reach the Web3Signer HTTP API within this time out then we will
@@ -134,6 +134,11 @@ mod tests {
         tls_dir().join("lighthouse").join("key.p12")
     }
 
+    /// The SPIFFE ID in the certificate of the Web3Signer.
+    fn server_identity() -> String {
+        "spiffe://lighthouse.test/web3signer".to_string()
+    }
+
     fn client_identity_password() -> String {
         fs::read_to_string(tls_dir().join("lighthouse").join("password.txt"))
             .unwrap()
@@ -354,7 +359,11 @@ mod tests {
             let slot_clock =
                 TestingSlotClock::new(Slot::new(0), Duration::from_secs(0), Duration::from_secs(1));
             let config = validator_client::Config {
-                enable_web3signer_slashing_protection: slashing_protection_config.local,
+                web3signer_slashing_protection: if slashing_protection_config.local {
+                    Web3SignerSlashingProtection::Local
+                } else {
+                    Web3SignerSlashingProtection::Remote
+                },
                 ..Default::default()
             };
 
@@ -364,6 +373,7 @@ mod tests {
                 Hash256::repeat_byte(42),
                 spec,
                 None,
+                None,
                 slot_clock,
                 &config,
                 executor,
@@ -457,6 +467,7 @@ mod tests {
                         request_timeout_ms: None,
                         client_identity_path: Some(client_identity_path()),
                         client_identity_password: Some(client_identity_password()),
+                        server_identity: Some(server_identity()),
                     }),
                 };
                 ValidatorStoreRig::new(
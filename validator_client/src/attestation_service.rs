@@ -1,5 +1,6 @@
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
+    clock_monitor::ClockOffset,
     duties_service::{DutiesService, DutyAndProof},
     http_metrics::metrics,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
@@ -22,6 +23,7 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     validator_store: Option<Arc<ValidatorStore<T, E>>>,
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    clock_offset: Option<Arc<ClockOffset>>,
     context: Option<RuntimeContext<E>>,
 }
 
@@ -32,6 +34,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             validator_store: None,
             slot_clock: None,
             beacon_nodes: None,
+            clock_offset: None,
             context: None,
         }
     }
@@ -56,6 +59,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn clock_offset(mut self, clock_offset: Arc<ClockOffset>) -> Self {
+        self.clock_offset = Some(clock_offset);
+        self
+    }
+
     pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
         self.context = Some(context);
         self
@@ -76,6 +84,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 beacon_nodes: self
                     .beacon_nodes
                     .ok_or("Cannot build AttestationService without beacon_nodes")?,
+                clock_offset: self
+                    .clock_offset
+                    .ok_or("Cannot build AttestationService without clock_offset")?,
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
@@ -90,6 +101,7 @@ pub struct Inner<T, E: EthSpec> {
     validator_store: Arc<ValidatorStore<T, E>>,
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    clock_offset: Arc<ClockOffset>,
     context: RuntimeContext<E>,
 }
 
@@ -140,10 +152,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         let interval_fut = async move {
             loop {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    let duty_delay = self.clock_offset.duty_delay(slot_duration);
+                    sleep(duration_to_next_slot + slot_duration / 3 + duty_delay).await;
                     let log = self.context.log();
 
-                    if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
+                    if let Err(e) = self.spawn_attestation_tasks(slot_duration, duty_delay) {
                         crit!(
                             log,
                             "Failed to spawn attestation tasks";
@@ -170,7 +183,14 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
     /// For each each required attestation, spawn a new task that downloads, signs and uploads the
     /// attestation to the beacon node.
-    fn spawn_attestation_tasks(&self, slot_duration: Duration) -> Result<(), String> {
+    ///
+    /// Aggregates are published `duty_delay` after 2/3rds of the way through the slot, to
+    /// compensate for a local clock which is ahead.
+    fn spawn_attestation_tasks(
+        &self,
+        slot_duration: Duration,
+        duty_delay: Duration,
+    ) -> Result<(), String> {
         let slot = self.slot_clock.now().ok_or("Failed to read slot clock")?;
         let duration_to_next_slot = self
             .slot_clock
@@ -182,7 +202,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         let aggregate_production_instant = Instant::now()
             + duration_to_next_slot
                 .checked_sub(slot_duration / 3)
-                .unwrap_or_else(|| Duration::from_secs(0));
+                .unwrap_or_else(|| Duration::from_secs(0))
+            + duty_delay;
 
         let duties_by_committee_index: HashMap<CommitteeIndex, Vec<DutyAndProof>> = self
             .duties_service
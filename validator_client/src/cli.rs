@@ -139,6 +139,16 @@ pub fn cli_app() -> Command {
                         delimiter, and must not be part of the certificate path.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-nodes-token-file")
+                .long("beacon-nodes-token-file")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .help("Path to a file containing an API token to present to the beacon nodes \
+                        (and/or proposer nodes), for beacon nodes which run with \
+                        --http-token-file. The token must have the validator role.")
+                .display_order(0)
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::new("graffiti")
@@ -249,11 +259,12 @@ pub fn cli_app() -> Command {
             Arg::new("http-allow-keystore-export")
                 .long("http-allow-keystore-export")
                 .requires("http")
-                .help("If present, allow access to the DELETE /lighthouse/keystores HTTP \
-                    API method, which allows exporting keystores and passwords to HTTP API \
-                    consumers who have access to the API token. This method is useful for \
-                    exporting validators, however it should be used with caution since it \
-                    exposes private key data to authorized users.")
+                .help("If present, allow access to the DELETE /lighthouse/keystores and \
+                    POST /lighthouse/validators/handoff/export HTTP API methods, which allow \
+                    exporting keystores and passwords to HTTP API consumers who have access to \
+                    the API token. These methods are useful for exporting validators, however \
+                    they should be used with caution since they expose private key data to \
+                    authorized users.")
                 .action(ArgAction::SetTrue)
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
@@ -269,6 +280,28 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-handoff-dead-time")
+                .long("http-handoff-dead-time")
+                .requires("http")
+                .value_name("SECONDS")
+                .help("The number of seconds which must pass after a validator is disabled on \
+                    another validator client before it may be handed off to this one.")
+                .default_value("768")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-require-handoff")
+                .long("http-require-handoff")
+                .requires("http")
+                .help("If present, keystores may only be imported over the HTTP API when they \
+                    are handed off from another validator client, with a handoff signed by \
+                    the validator once it was disabled there.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::new("metrics")
@@ -403,6 +436,37 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("ntp-server")
+                .long("ntp-server")
+                .value_name("HOST[:PORT]")
+                .help("An NTP server to check the local clock against once per epoch. If the \
+                    local clock is found to be ahead, attestations and sync committee messages \
+                    are delayed to compensate.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("leader-election-url")
+                .long("leader-election-url")
+                .value_name("URL")
+                .help("The URL of a Consul agent used to elect a leader among validator clients \
+                    which run the same validators. Only the leader signs, from the epoch after it \
+                    is elected, and the other validator clients take over if it fails.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("leader-election-key")
+                .long("leader-election-key")
+                .value_name("KEY")
+                .help("The Consul key of the lock held by the leader. Each set of validator \
+                    clients which run the same validators must use a different key.")
+                .requires("leader-election-url")
+                .default_value("lighthouse/validator-client/leader")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("latency-measurement-service")
                 .long("latency-measurement-service")
@@ -452,11 +516,51 @@ pub fn cli_app() -> Command {
                        is enabled on the remote signer and is implemented correctly. DO NOT ENABLE \
                        THIS FLAG UNLESS YOU ARE CERTAIN THAT SLASHING PROTECTION IS ENABLED ON \
                        THE REMOTE SIGNER. YOU WILL GET SLASHED IF YOU USE THIS FLAG WITHOUT \
-                       ENABLING WEB3SIGNER'S SLASHING PROTECTION.")
+                       ENABLING WEB3SIGNER'S SLASHING PROTECTION. Unlike \
+                       --web3-signer-slashing-protection remote, the web3signer is not checked at \
+                       startup.")
                 .action(ArgAction::SetTrue)
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("web3-signer-slashing-protection")
+                .long("web3-signer-slashing-protection")
+                .value_name("MODE")
+                .help("Where the slashing protection of web3signer keys is checked. With `local`, \
+                       messages are checked against the slashing protection database of the VC. \
+                       With `remote`, the VC delegates all checks to the slashing protection \
+                       database of the web3signer, and refuses to start unless each web3signer \
+                       reports that its slashing protection is healthy and consistent with the \
+                       local database.")
+                .value_parser(["local", "remote"])
+                .default_value("local")
+                .conflicts_with("disable-slashing-protection-web3signer")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("presign-exits-password-file")
+                .long("presign-exits-password-file")
+                .value_name("PATH")
+                .help("If present, pre-sign a voluntary exit for each validator once its index is \
+                       known and store the exits in the validators directory, encrypted with the \
+                       password in this file. The exits can be broadcast or exported via the HTTP \
+                       API.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("exit-dead-mans-switch-epochs")
+                .long("exit-dead-mans-switch-epochs")
+                .value_name("EPOCHS")
+                .help("Broadcast the pre-signed voluntary exits if the dead man's switch is not \
+                       reset via the HTTP API for this many epochs. Requires \
+                       --presign-exits-password-file.")
+                .requires("presign-exits-password-file")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Experimental/development options.
          */
@@ -479,4 +583,15 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("web3-signer-health-check-interval")
+                .long("web3-signer-health-check-interval")
+                .value_name("SECONDS")
+                .default_value("30")
+                .help("The number of seconds between health checks of each web3signer, at which \
+                       its certificates are also reloaded if their files have changed. Set to 0 \
+                       to disable health checks and certificate reloading.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
 }
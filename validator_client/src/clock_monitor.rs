@@ -0,0 +1,100 @@
+use crate::http_metrics::metrics;
+use environment::RuntimeContext;
+use parking_lot::RwLock;
+use slog::{debug, warn};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use std::time::Duration;
+use system_health::clock::{is_safe_ntp_clock_offset, query_ntp_offset};
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The time to wait for a response from the NTP server.
+const NTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The most recent measurement of the local clock against an NTP server.
+#[derive(Default)]
+pub struct ClockOffset {
+    offset_ms: RwLock<Option<i64>>,
+}
+
+impl ClockOffset {
+    /// Returns how far the local clock is ahead of the NTP server, if it has been measured.
+    pub fn offset_ms(&self) -> Option<i64> {
+        *self.offset_ms.read()
+    }
+
+    /// Returns how long duties which are due part way through a slot should be delayed, so that
+    /// they are published at the intended time despite the local clock being ahead.
+    ///
+    /// A clock that is behind cannot be corrected by delaying. The delay is capped at a third of
+    /// a slot so that each duty is still published before the next one is due.
+    pub fn duty_delay(&self, slot_duration: Duration) -> Duration {
+        match self.offset_ms() {
+            Some(offset) if offset > 0 && !is_safe_ntp_clock_offset(offset) => {
+                Duration::from_millis(offset as u64).min(slot_duration / 3)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Starts a service which compares the local clock to `ntp_server` once per epoch, updating
+/// `clock_offset` and logging a warning whenever the offset is unsafe.
+pub fn start_clock_monitor<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    ntp_server: String,
+    clock_offset: Arc<ClockOffset>,
+) {
+    let log = context.log().clone();
+    let executor = context.executor.clone();
+
+    let future = async move {
+        loop {
+            let server = ntp_server.clone();
+            let Some(query) = executor
+                .spawn_blocking_handle(move || query_ntp_offset(&server, NTP_TIMEOUT), "ntp_query")
+            else {
+                break;
+            };
+
+            match query.await {
+                Ok(Ok(offset)) => {
+                    *clock_offset.offset_ms.write() = Some(offset);
+                    metrics::set_gauge(&metrics::CLOCK_OFFSET, offset);
+
+                    let delay = clock_offset.duty_delay(slot_clock.slot_duration());
+                    if is_safe_ntp_clock_offset(offset) {
+                        debug!(log, "Measured clock offset"; "offset_ms" => offset);
+                    } else if !delay.is_zero() {
+                        warn!(
+                            log,
+                            "Local clock is ahead, delaying duties";
+                            "info" => "attestations and sync committee messages will be \
+                                       delayed to compensate, check NTP",
+                            "offset_ms" => offset,
+                            "delay_ms" => delay.as_millis(),
+                        );
+                    } else {
+                        warn!(
+                            log,
+                            "Local clock is behind";
+                            "info" => "duties may be missed, check NTP",
+                            "offset_ms" => offset,
+                        );
+                    }
+                }
+                Ok(Err(e)) => warn!(log, "Unable to query NTP server"; "error" => e),
+                Err(e) => warn!(log, "NTP query failed"; "error" => ?e),
+            }
+
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+        }
+    };
+
+    context.executor.spawn(future, "clock_monitor");
+}
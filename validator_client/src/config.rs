@@ -1,5 +1,6 @@
 use crate::beacon_node_fallback::ApiTopic;
 use crate::graffiti_file::GraffitiFile;
+use crate::leader_election::DEFAULT_LEADER_ELECTION_KEY;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, parse_optional, parse_required};
@@ -15,10 +16,27 @@ use std::fs;
 use std::net::IpAddr;
 use std::path::PathBuf;
 use std::time::Duration;
+use strum::EnumString;
 use types::{Address, GRAFFITI_BYTES_LEN};
 
 pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
 pub const DEFAULT_WEB3SIGNER_KEEP_ALIVE: Option<Duration> = Some(Duration::from_secs(20));
+pub const DEFAULT_WEB3SIGNER_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Where the slashing protection of validators which use a web3signer is checked.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Web3SignerSlashingProtection {
+    /// Messages are checked against the local slashing protection database before they are sent
+    /// to the web3signer.
+    Local,
+    /// Messages are only checked by the slashing protection database of the web3signer, which is
+    /// checked for consistency with the local database at startup.
+    Remote,
+    /// Messages are only checked by the web3signer, which is not checked at startup. This is set by
+    /// `--disable-slashing-protection-web3signer`.
+    Disabled,
+}
 
 /// Stores the core configuration for this validator instance.
 #[derive(Clone, Serialize, Deserialize)]
@@ -71,14 +89,22 @@ pub struct Config {
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
+    /// A file containing the API token to present to the beacon nodes, if they require one.
+    pub beacon_nodes_token_file: Option<PathBuf>,
     /// Enables broadcasting of various requests (by topic) to all beacon nodes.
     pub broadcast_topics: Vec<ApiTopic>,
     /// Enables a service which attempts to measure latency between the VC and BNs.
     pub enable_latency_measurement_service: bool,
+    /// An NTP server to check the local clock against.
+    pub ntp_server: Option<String>,
+    /// The URL of a Consul agent used to elect a leader among validator clients, if any.
+    pub leader_election_url: Option<SensitiveUrl>,
+    /// The Consul key of the lock held by the leader.
+    pub leader_election_key: String,
     /// Defines the number of validators per `validator/register_validator` request sent to the BN.
     pub validator_registration_batch_size: usize,
-    /// Enable slashing protection even while using web3signer keys.
-    pub enable_web3signer_slashing_protection: bool,
+    /// Where the slashing protection of web3signer keys is checked.
+    pub web3signer_slashing_protection: Web3SignerSlashingProtection,
     /// Specifies the boost factor, a percentage multiplier to apply to the builder's payload value.
     pub builder_boost_factor: Option<u64>,
     /// If true, Lighthouse will prefer builder proposals, if available.
@@ -87,6 +113,12 @@ pub struct Config {
     pub distributed: bool,
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
+    /// The interval between health checks of each web3signer, or `None` to disable them.
+    pub web3_signer_health_check_interval: Option<Duration>,
+    /// If set, voluntary exits are pre-signed and encrypted with the password in this file.
+    pub presign_exits_password_file: Option<PathBuf>,
+    /// Broadcast the pre-signed exits if the dead man's switch is not reset for this many epochs.
+    pub exit_dead_mans_switch_epochs: Option<u64>,
 }
 
 impl Default for Config {
@@ -121,18 +153,25 @@ impl Default for Config {
             enable_doppelganger_protection: false,
             enable_high_validator_count_metrics: false,
             beacon_nodes_tls_certs: None,
+            beacon_nodes_token_file: None,
             builder_proposals: false,
             builder_registration_timestamp_override: None,
             gas_limit: None,
             broadcast_topics: vec![ApiTopic::Subscriptions],
             enable_latency_measurement_service: true,
+            ntp_server: None,
+            leader_election_url: None,
+            leader_election_key: DEFAULT_LEADER_ELECTION_KEY.to_string(),
             validator_registration_batch_size: 500,
-            enable_web3signer_slashing_protection: true,
+            web3signer_slashing_protection: Web3SignerSlashingProtection::Local,
             builder_boost_factor: None,
             prefer_builder_proposals: false,
             distributed: false,
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
+            web3_signer_health_check_interval: Some(DEFAULT_WEB3SIGNER_HEALTH_CHECK_INTERVAL),
+            presign_exits_password_file: None,
+            exit_dead_mans_switch_epochs: None,
         }
     }
 }
@@ -234,6 +273,10 @@ impl Config {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
 
+        if let Some(token_file) = parse_optional::<String>(cli_args, "beacon-nodes-token-file")? {
+            config.beacon_nodes_token_file = Some(PathBuf::from(token_file));
+        }
+
         if cli_args.get_flag("distributed") {
             config.distributed = true;
         }
@@ -273,6 +316,21 @@ impl Config {
         if let Some(n) = parse_optional::<usize>(cli_args, "web3-signer-max-idle-connections")? {
             config.web3_signer_max_idle_connections = Some(n);
         }
+        if let Some(secs) = parse_optional::<u64>(cli_args, "web3-signer-health-check-interval")? {
+            config.web3_signer_health_check_interval =
+                (secs != 0).then(|| Duration::from_secs(secs));
+        }
+
+        /*
+         * Pre-signed exits
+         */
+        config.presign_exits_password_file =
+            parse_optional(cli_args, "presign-exits-password-file")?;
+        config.exit_dead_mans_switch_epochs =
+            parse_optional(cli_args, "exit-dead-mans-switch-epochs")?;
+        if config.exit_dead_mans_switch_epochs == Some(0) {
+            return Err("exit-dead-mans-switch-epochs cannot be 0".to_string());
+        }
 
         /*
          * Http API server
@@ -318,6 +376,12 @@ impl Config {
             config.http_api.store_passwords_in_secrets_dir = true;
         }
 
+        config.http_api.handoff_dead_time = parse_required(cli_args, "http-handoff-dead-time")?;
+
+        if cli_args.get_flag("http-require-handoff") {
+            config.http_api.require_handoff = true;
+        }
+
         /*
          * Prometheus metrics HTTP server
          */
@@ -413,6 +477,18 @@ impl Config {
         config.enable_latency_measurement_service =
             !cli_args.get_flag("disable-latency-measurement-service");
 
+        config.ntp_server = cli_args.get_one::<String>("ntp-server").cloned();
+
+        if let Some(url) = cli_args.get_one::<String>("leader-election-url") {
+            config.leader_election_url = Some(
+                SensitiveUrl::parse(url)
+                    .map_err(|e| format!("Invalid leader election URL: {:?}", e))?,
+            );
+        }
+        if let Some(key) = cli_args.get_one::<String>("leader-election-key") {
+            config.leader_election_key = key.clone();
+        }
+
         if cli_args
             .get_one::<String>("latency-measurement-service")
             .is_some()
@@ -430,18 +506,22 @@ impl Config {
             return Err("validator-registration-batch-size cannot be 0".to_string());
         }
 
-        config.enable_web3signer_slashing_protection =
-            if cli_args.get_flag("disable-slashing-protection-web3signer") {
-                warn!(
-                    log,
-                    "Slashing protection for remote keys disabled";
-                    "info" => "ensure slashing protection on web3signer is enabled or you WILL \
-                               get slashed"
-                );
-                false
-            } else {
-                true
-            };
+        if let Some(mode) = cli_args.get_one::<String>("web3-signer-slashing-protection") {
+            config.web3signer_slashing_protection = mode
+                .parse()
+                .map_err(|_| format!("Unknown web3signer slashing protection mode: {mode}"))?;
+        }
+        if cli_args.get_flag("disable-slashing-protection-web3signer") {
+            config.web3signer_slashing_protection = Web3SignerSlashingProtection::Disabled;
+        }
+        if config.web3signer_slashing_protection != Web3SignerSlashingProtection::Local {
+            warn!(
+                log,
+                "Slashing protection for remote keys delegated to web3signer";
+                "info" => "ensure slashing protection on web3signer is enabled or you WILL \
+                           get slashed"
+            );
+        }
 
         Ok(config)
     }
@@ -1381,7 +1381,7 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
 ) {
     let non_doppelganger_proposers = block_proposers
         .iter()
-        .filter(|pubkey| validator_store.doppelganger_protection_allows_signing(**pubkey))
+        .filter(|pubkey| validator_store.signing_allowed(**pubkey))
         .copied()
         .collect::<Vec<_>>();
 
@@ -13,7 +13,9 @@ use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::sync::Arc;
-use types::{ChainSpec, EthSpec, PublicKeyBytes, Slot, SyncDuty, SyncSelectionProof, SyncSubnetId};
+use types::{
+    ChainSpec, Epoch, EthSpec, PublicKeyBytes, Slot, SyncDuty, SyncSelectionProof, SyncSubnetId,
+};
 
 /// Number of epochs in advance to compute selection proofs when not in `distributed` mode.
 pub const AGGREGATION_PRE_COMPUTE_EPOCHS: u64 = 2;
@@ -244,6 +246,46 @@ impl<E: EthSpec> SyncDutiesMap<E> {
         })
     }
 
+    /// Get the duties of all known sync committee members during `epoch`, along with any
+    /// pre-computed selection proofs for the (wall-clock) slots of that epoch.
+    ///
+    /// Used by the HTTP API to expose the duties the VC has scheduled.
+    pub fn get_duties_for_epoch(
+        &self,
+        epoch: Epoch,
+        spec: &ChainSpec,
+    ) -> Vec<(SyncDuty, Vec<(Slot, SyncSubnetId, SyncSelectionProof)>)> {
+        let Ok(sync_committee_period) = epoch.sync_committee_period(spec) else {
+            return vec![];
+        };
+
+        let committees_reader = self.committees.read();
+        let Some(committee_duties) = committees_reader.get(&sync_committee_period) else {
+            return vec![];
+        };
+
+        let mut duties = committee_duties
+            .validators
+            .read()
+            .values()
+            .filter_map(|opt_duties| {
+                let duty = opt_duties.as_ref()?;
+                let mut proofs = duty
+                    .aggregation_duties
+                    .proofs
+                    .read()
+                    .iter()
+                    .filter(|((slot, _), _)| slot.epoch(E::slots_per_epoch()) == epoch)
+                    .map(|((slot, subnet_id), proof)| (*slot, *subnet_id, proof.clone()))
+                    .collect::<Vec<_>>();
+                proofs.sort_by_key(|(slot, subnet_id, _)| (*slot, **subnet_id));
+                Some((duty.duty.clone(), proofs))
+            })
+            .collect::<Vec<_>>();
+        duties.sort_by_key(|(duty, _)| duty.validator_index);
+        duties
+    }
+
     /// Prune duties for past sync committee periods from the map.
     fn prune(&self, current_sync_committee_period: u64) {
         self.committees
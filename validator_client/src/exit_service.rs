@@ -0,0 +1,591 @@
+//! Publishes voluntary exits for many validators over several epochs.
+//!
+//! Exiting a large number of validators at once can fill the exit queue of the beacon chain, so
+//! the exits scheduled via the HTTP API are published a few at a time. The schedule is persisted
+//! in the validator directory so that it resumes after the validator client restarts.
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{
+    ExitQueueEstimate, ExitSchedule, ScheduledExit, ScheduledExitStatus,
+};
+use eth2::types::{StateId, ValidatorId, ValidatorStatus};
+use parking_lot::RwLock;
+use slog::{debug, error, info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use types::{ChainSpec, Epoch, EthSpec, PublicKeyBytes, VoluntaryExit};
+
+/// The file in the validator directory which stores the exit schedule.
+pub const EXIT_SCHEDULE_FILENAME: &str = "exit_schedule.json";
+
+/// Stores the voluntary exits scheduled via the HTTP API and their progress.
+pub struct ExitScheduler {
+    path: PathBuf,
+    schedule: RwLock<ExitSchedule>,
+    spec: ChainSpec,
+}
+
+impl ExitScheduler {
+    /// Loads the schedule from `validator_dir`, or starts an empty schedule if there is none.
+    pub fn open_or_create(validator_dir: &Path, spec: ChainSpec) -> Result<Self, String> {
+        let path = validator_dir.join(EXIT_SCHEDULE_FILENAME);
+        let schedule = if path.exists() {
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Unable to read exit schedule {:?}: {:?}", path, e))?;
+            serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Unable to parse exit schedule {:?}: {:?}", path, e))?
+        } else {
+            ExitSchedule::default()
+        };
+
+        Ok(Self {
+            path,
+            schedule: RwLock::new(schedule),
+            spec,
+        })
+    }
+
+    pub fn schedule(&self) -> ExitSchedule {
+        self.schedule.read().clone()
+    }
+
+    /// Schedules exits for `pubkeys` and sets the number of exits published each epoch.
+    ///
+    /// Validators which are already scheduled keep their progress, unless their exit failed, in
+    /// which case it is retried.
+    pub fn schedule_exits(
+        &self,
+        pubkeys: &[PublicKeyBytes],
+        exits_per_epoch: u64,
+    ) -> Result<ExitSchedule, String> {
+        self.update(|schedule| {
+            schedule.exits_per_epoch = exits_per_epoch;
+            for pubkey in pubkeys {
+                match schedule
+                    .exits
+                    .iter_mut()
+                    .find(|exit| exit.pubkey == *pubkey)
+                {
+                    Some(exit) => {
+                        if matches!(exit.status, ScheduledExitStatus::Failed { .. }) {
+                            exit.status = ScheduledExitStatus::Pending;
+                        }
+                    }
+                    None => schedule.exits.push(ScheduledExit {
+                        pubkey: *pubkey,
+                        status: ScheduledExitStatus::Pending,
+                        exit_epoch: None,
+                    }),
+                }
+            }
+        })
+    }
+
+    /// Applies `f` to the schedule, then updates the estimated exit epochs and persists it.
+    fn update<F: FnOnce(&mut ExitSchedule)>(&self, f: F) -> Result<ExitSchedule, String> {
+        let mut schedule = self.schedule.write();
+        f(&mut schedule);
+        estimate_exit_epochs(&mut schedule, &self.spec);
+
+        let bytes = serde_json::to_vec_pretty(&*schedule)
+            .map_err(|e| format!("Unable to encode exit schedule: {:?}", e))?;
+        fs::write(&self.path, bytes)
+            .map_err(|e| format!("Unable to write exit schedule {:?}: {:?}", self.path, e))?;
+
+        Ok(schedule.clone())
+    }
+}
+
+/// Sets the expected exit epoch of each pending exit, assuming that they are published at the
+/// scheduled rate from the epoch after the exit queue estimate was made.
+fn estimate_exit_epochs(schedule: &mut ExitSchedule, spec: &ChainSpec) {
+    let exits_per_epoch = schedule.exits_per_epoch;
+    let exit_queue = schedule.exit_queue.clone();
+    let pending_exits = schedule
+        .exits
+        .iter_mut()
+        .filter(|exit| exit.status == ScheduledExitStatus::Pending);
+
+    for (position, exit) in pending_exits.enumerate() {
+        let position = position as u64;
+        exit.exit_epoch = exit_queue.as_ref().and_then(|queue| {
+            let publish_epoch = queue.epoch + 1 + position.checked_div(exits_per_epoch)?;
+            let queue_epoch = queue.exit_epoch + position.checked_div(queue.churn_limit)?;
+            let earliest_epoch = spec.compute_activation_exit_epoch(publish_epoch).ok()?;
+            Some(std::cmp::max(queue_epoch, earliest_epoch))
+        });
+    }
+}
+
+/// Returns the number of validators with a 32 ETH balance which may exit each epoch, given the
+/// total active balance.
+fn exit_churn_limit(epoch: Epoch, total_active_balance: u64, spec: &ChainSpec) -> u64 {
+    if spec.fork_name_at_epoch(epoch).electra_enabled() {
+        let balance_churn = std::cmp::max(
+            spec.min_per_epoch_churn_limit_electra,
+            total_active_balance / spec.churn_limit_quotient,
+        );
+        let balance_churn = balance_churn - balance_churn % spec.effective_balance_increment;
+        let exit_churn = std::cmp::min(
+            spec.max_per_epoch_activation_exit_churn_limit,
+            balance_churn,
+        );
+        std::cmp::max(1, exit_churn / spec.min_activation_balance)
+    } else {
+        std::cmp::max(
+            spec.min_per_epoch_churn_limit,
+            total_active_balance / spec.max_effective_balance / spec.churn_limit_quotient,
+        )
+    }
+}
+
+/// Builds an `ExitService`.
+pub struct ExitServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
+    validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    slot_clock: Option<T>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    exit_scheduler: Option<Arc<ExitScheduler>>,
+    context: Option<RuntimeContext<E>>,
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> ExitServiceBuilder<T, E> {
+    pub fn new() -> Self {
+        Self {
+            validator_store: None,
+            slot_clock: None,
+            beacon_nodes: None,
+            exit_scheduler: None,
+            context: None,
+        }
+    }
+
+    pub fn validator_store(mut self, store: Arc<ValidatorStore<T, E>>) -> Self {
+        self.validator_store = Some(store);
+        self
+    }
+
+    pub fn slot_clock(mut self, slot_clock: T) -> Self {
+        self.slot_clock = Some(slot_clock);
+        self
+    }
+
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
+        self
+    }
+
+    pub fn exit_scheduler(mut self, exit_scheduler: Arc<ExitScheduler>) -> Self {
+        self.exit_scheduler = Some(exit_scheduler);
+        self
+    }
+
+    pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    pub fn build(self) -> Result<ExitService<T, E>, String> {
+        Ok(ExitService {
+            inner: Arc::new(Inner {
+                validator_store: self
+                    .validator_store
+                    .ok_or("Cannot build ExitService without validator_store")?,
+                slot_clock: self
+                    .slot_clock
+                    .ok_or("Cannot build ExitService without slot_clock")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or("Cannot build ExitService without beacon_nodes")?,
+                exit_scheduler: self
+                    .exit_scheduler
+                    .ok_or("Cannot build ExitService without exit_scheduler")?,
+                context: self
+                    .context
+                    .ok_or("Cannot build ExitService without runtime_context")?,
+            }),
+        })
+    }
+}
+
+/// Helper to minimise `Arc` usage.
+pub struct Inner<T, E: EthSpec> {
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    pub exit_scheduler: Arc<ExitScheduler>,
+    context: RuntimeContext<E>,
+}
+
+/// Publishes the scheduled voluntary exits at the beginning of each epoch.
+pub struct ExitService<T, E: EthSpec> {
+    inner: Arc<Inner<T, E>>,
+}
+
+impl<T, E: EthSpec> Clone for ExitService<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, E: EthSpec> Deref for ExitService<T, E> {
+    type Target = Inner<T, E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> ExitService<T, E> {
+    pub fn start_update_service(self, spec: &ChainSpec) -> Result<(), String> {
+        let log = self.context.log().clone();
+        let slot_duration = Duration::from_secs(spec.seconds_per_slot);
+
+        info!(log, "Exit service started");
+
+        let executor = self.context.executor.clone();
+        let spec = spec.clone();
+
+        let interval_fut = async move {
+            loop {
+                if let Some(duration_to_next_epoch) =
+                    self.slot_clock.duration_to_next_epoch(E::slots_per_epoch())
+                {
+                    sleep(duration_to_next_epoch).await;
+                    self.publish_scheduled_exits(&spec)
+                        .await
+                        .map_err(|e| {
+                            error!(
+                                log,
+                                "Error during scheduled exits";
+                                "error" => e,
+                            )
+                        })
+                        .unwrap_or(());
+                } else {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_duration).await;
+                }
+            }
+        };
+
+        executor.spawn(interval_fut, "exit_service");
+        Ok(())
+    }
+
+    /// Updates the exit queue estimate and publishes the next batch of pending exits.
+    async fn publish_scheduled_exits(&self, spec: &ChainSpec) -> Result<(), String> {
+        let log = self.context.log();
+        let schedule = self.exit_scheduler.schedule();
+        let awaiting_exit_epoch = |exit: &&ScheduledExit| {
+            matches!(exit.status, ScheduledExitStatus::Published { .. })
+                && exit.exit_epoch.is_none()
+        };
+        if !schedule
+            .exits
+            .iter()
+            .any(|exit| exit.status == ScheduledExitStatus::Pending || awaiting_exit_epoch(&exit))
+        {
+            return Ok(());
+        }
+
+        let epoch = self
+            .slot_clock
+            .now()
+            .ok_or("Unable to read slot clock")?
+            .epoch(E::slots_per_epoch());
+
+        // The estimate requires every active validator, so it is made at most once per epoch.
+        let exit_queue = match schedule.exit_queue.clone() {
+            Some(exit_queue) if exit_queue.epoch == epoch => Ok(exit_queue),
+            _ => self.estimate_exit_queue(epoch, spec).await,
+        };
+        let exit_queue = match exit_queue {
+            Ok(exit_queue) => Some(exit_queue),
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to estimate the exit queue";
+                    "error" => e,
+                );
+                None
+            }
+        };
+
+        let published_pubkeys = schedule
+            .exits
+            .iter()
+            .filter(awaiting_exit_epoch)
+            .map(|exit| exit.pubkey)
+            .collect::<Vec<_>>();
+        let exit_epochs = self
+            .get_exit_epochs(&published_pubkeys, spec)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(
+                    log,
+                    "Unable to get the exit epochs of validators";
+                    "error" => e,
+                );
+                HashMap::new()
+            });
+
+        let batch_size = exit_queue
+            .as_ref()
+            .map_or(schedule.exits_per_epoch, |exit_queue| {
+                std::cmp::min(schedule.exits_per_epoch, exit_queue.churn_limit)
+            });
+        let mut statuses = HashMap::new();
+        for exit in schedule
+            .exits
+            .iter()
+            .filter(|exit| exit.status == ScheduledExitStatus::Pending)
+            .take(batch_size as usize)
+        {
+            let status = match self.publish_exit(exit.pubkey, epoch).await {
+                Ok(()) => {
+                    info!(
+                        log,
+                        "Published scheduled voluntary exit";
+                        "validator" => exit.pubkey.as_hex_string(),
+                        "epoch" => epoch,
+                    );
+                    ScheduledExitStatus::Published { epoch }
+                }
+                Err(error) => {
+                    error!(
+                        log,
+                        "Failed to publish scheduled voluntary exit";
+                        "validator" => exit.pubkey.as_hex_string(),
+                        "error" => &error,
+                    );
+                    ScheduledExitStatus::Failed { error }
+                }
+            };
+            statuses.insert(exit.pubkey, status);
+        }
+
+        let schedule = self.exit_scheduler.update(|schedule| {
+            for exit in schedule.exits.iter_mut() {
+                if let Some(status) = statuses.remove(&exit.pubkey) {
+                    exit.status = status;
+                }
+                if let Some(exit_epoch) = exit_epochs.get(&exit.pubkey) {
+                    exit.exit_epoch = Some(*exit_epoch);
+                }
+            }
+            if exit_queue.is_some() {
+                schedule.exit_queue = exit_queue;
+            }
+        })?;
+
+        debug!(
+            log,
+            "Updated exit schedule";
+            "pending" => schedule
+                .exits
+                .iter()
+                .filter(|exit| exit.status == ScheduledExitStatus::Pending)
+                .count(),
+        );
+
+        Ok(())
+    }
+
+    /// Signs a voluntary exit for `pubkey` and publishes it to a beacon node.
+    async fn publish_exit(&self, pubkey: PublicKeyBytes, epoch: Epoch) -> Result<(), String> {
+        if !self.validator_store.has_validator(&pubkey) {
+            return Err("Validator is disabled or not managed by this validator client".into());
+        }
+        let validator_index = self
+            .validator_store
+            .validator_index(&pubkey)
+            .ok_or("The validator index is not known")?;
+
+        let signed_voluntary_exit = self
+            .validator_store
+            .sign_voluntary_exit(
+                pubkey,
+                VoluntaryExit {
+                    epoch,
+                    validator_index,
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to sign voluntary exit: {:?}", e))?;
+
+        let signed_voluntary_exit = &signed_voluntary_exit;
+        self.beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::No,
+                |beacon_node| async move {
+                    beacon_node
+                        .post_beacon_pool_voluntary_exits(signed_voluntary_exit)
+                        .await
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to publish voluntary exit: {}", e))
+    }
+
+    /// Estimates the churn limit and the length of the exit queue at `epoch`.
+    ///
+    /// The active validators are read from the standard validators endpoint of the head state,
+    /// so that any beacon node may be used.
+    async fn estimate_exit_queue(
+        &self,
+        epoch: Epoch,
+        spec: &ChainSpec,
+    ) -> Result<ExitQueueEstimate, String> {
+        let active_validators = self
+            .beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_states_validators(
+                            StateId::Head,
+                            None,
+                            Some(&[ValidatorStatus::Active]),
+                        )
+                        .await
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|response| response.data)
+            .unwrap_or_default();
+
+        // As per `get_total_active_balance`, the total is at least one increment.
+        let total_active_balance = std::cmp::max(
+            spec.effective_balance_increment,
+            active_validators
+                .iter()
+                .map(|validator| validator.validator.effective_balance)
+                .sum(),
+        );
+
+        let earliest_exit_epoch = spec
+            .compute_activation_exit_epoch(epoch)
+            .map_err(|e| format!("Unable to compute exit epoch: {:?}", e))?;
+        let exit_epoch = active_validators
+            .iter()
+            .filter(|validator| validator.status == ValidatorStatus::ActiveExiting)
+            .map(|validator| validator.validator.exit_epoch)
+            .fold(earliest_exit_epoch, std::cmp::max);
+
+        Ok(ExitQueueEstimate {
+            epoch,
+            churn_limit: exit_churn_limit(epoch, total_active_balance, spec),
+            exit_epoch,
+        })
+    }
+
+    /// Returns the exit epochs assigned by the beacon chain to any of `pubkeys`.
+    async fn get_exit_epochs(
+        &self,
+        pubkeys: &[PublicKeyBytes],
+        spec: &ChainSpec,
+    ) -> Result<HashMap<PublicKeyBytes, Epoch>, String> {
+        if pubkeys.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let ids = pubkeys
+            .iter()
+            .copied()
+            .map(ValidatorId::PublicKey)
+            .collect::<Vec<_>>();
+        let ids = &ids;
+
+        let validators = self
+            .beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_states_validators(StateId::Head, Some(ids), None)
+                        .await
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|response| response.data)
+            .unwrap_or_default();
+
+        Ok(validators
+            .into_iter()
+            .filter(|validator| validator.validator.exit_epoch != spec.far_future_epoch)
+            .map(|validator| (validator.validator.pubkey, validator.validator.exit_epoch))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    fn pubkey(i: u8) -> PublicKeyBytes {
+        let mut bytes = [0; 48];
+        bytes[0] = i;
+        PublicKeyBytes::deserialize(&bytes).unwrap()
+    }
+
+    #[test]
+    fn pending_exits_are_spread_over_epochs() {
+        let spec = MainnetEthSpec::default_spec();
+        let mut schedule = ExitSchedule {
+            exits_per_epoch: 2,
+            exits: (0..5)
+                .map(|i| ScheduledExit {
+                    pubkey: pubkey(i),
+                    status: ScheduledExitStatus::Pending,
+                    exit_epoch: None,
+                })
+                .collect(),
+            exit_queue: Some(ExitQueueEstimate {
+                epoch: Epoch::new(100),
+                churn_limit: 4,
+                exit_epoch: Epoch::new(105),
+            }),
+        };
+        schedule.exits[0].status = ScheduledExitStatus::Published {
+            epoch: Epoch::new(99),
+        };
+
+        estimate_exit_epochs(&mut schedule, &spec);
+
+        // Published exits are not estimated.
+        assert_eq!(schedule.exits[0].exit_epoch, None);
+        // The remaining exits are published two per epoch, from epoch 101.
+        let exit_epochs = schedule.exits[1..]
+            .iter()
+            .map(|exit| exit.exit_epoch.unwrap().as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(exit_epochs, vec![106, 106, 107, 107]);
+    }
+
+    #[test]
+    fn churn_limit() {
+        let spec = MainnetEthSpec::default_spec();
+        let epoch = Epoch::new(0);
+        // 1M validators with 32 ETH.
+        let total_active_balance = 1_000_000 * spec.max_effective_balance;
+        assert_eq!(exit_churn_limit(epoch, total_active_balance, &spec), 15);
+        assert_eq!(
+            exit_churn_limit(epoch, 0, &spec),
+            spec.min_per_epoch_churn_limit
+        );
+    }
+}
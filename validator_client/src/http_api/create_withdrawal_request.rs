@@ -0,0 +1,40 @@
+use crate::validator_store::ValidatorStore;
+use bls::{PublicKey, PublicKeyBytes};
+use deposit_contract::{encode_withdrawal_request_tx_data, WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS};
+use eth2::lighthouse_vc::types::{GenericResponse, WithdrawalRequestTransaction};
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::EthSpec;
+
+/// Returns the unsigned transaction which requests a withdrawal of `amount` gwei from the
+/// validator with `pubkey`.
+///
+/// The validator client does not hold the key of the withdrawal address, so the transaction must
+/// be signed and submitted by the caller, e.g. with `lighthouse account validator withdraw`.
+pub fn create_withdrawal_request<T: 'static + SlotClock + Clone, E: EthSpec>(
+    pubkey: PublicKey,
+    amount: u64,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    log: Logger,
+) -> Result<GenericResponse<WithdrawalRequestTransaction>, warp::Rejection> {
+    let pubkey_bytes = PublicKeyBytes::from(pubkey);
+    if !validator_store.has_validator(&pubkey_bytes) {
+        return Err(warp_utils::reject::custom_not_found(format!(
+            "{} is disabled or not managed by this validator client",
+            pubkey_bytes.as_hex_string()
+        )));
+    }
+
+    info!(
+        log,
+        "Creating withdrawal request";
+        "validator" => pubkey_bytes.as_hex_string(),
+        "amount" => amount,
+    );
+
+    Ok(GenericResponse::from(WithdrawalRequestTransaction {
+        to: WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+        data: encode_withdrawal_request_tx_data(&pubkey_bytes, amount),
+    }))
+}
@@ -0,0 +1,99 @@
+use crate::duties_service::DutiesService;
+use eth2::lighthouse_vc::types::{
+    DutySimulation, DutyTiming, SimulatedAttestation, SimulatedSyncAggregation,
+    SimulatedSyncCommitteeDuty,
+};
+use slot_clock::SlotClock;
+use types::{Epoch, EthSpec};
+
+/// Returns the duties that `duties_service` has computed for `epoch`, along with the offsets into
+/// each slot at which they will be performed.
+///
+/// Defaults to the next epoch, which is the furthest ahead that duties are known for.
+pub fn get_duty_simulation<T: SlotClock + 'static, E: EthSpec>(
+    epoch: Option<Epoch>,
+    duties_service: &DutiesService<T, E>,
+) -> Result<DutySimulation, warp::Rejection> {
+    let current_epoch = duties_service
+        .slot_clock
+        .now()
+        .map(|slot| slot.epoch(E::slots_per_epoch()))
+        .ok_or_else(|| {
+            warp_utils::reject::custom_server_error("Unable to read slot clock".to_string())
+        })?;
+    let epoch = epoch.unwrap_or(current_epoch + 1);
+    if epoch > current_epoch + 1 {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "duties are not known beyond the next epoch ({})",
+            current_epoch + 1
+        )));
+    }
+
+    let slot_clock = &duties_service.slot_clock;
+    let timing = DutyTiming {
+        block_proposal_ms: 0,
+        attestation_ms: slot_clock.unagg_attestation_production_delay().as_millis() as u64,
+        aggregate_ms: slot_clock.agg_attestation_production_delay().as_millis() as u64,
+        sync_committee_message_ms: slot_clock
+            .sync_committee_message_production_delay()
+            .as_millis() as u64,
+        sync_committee_contribution_ms: slot_clock
+            .sync_committee_contribution_production_delay()
+            .as_millis() as u64,
+    };
+
+    let mut proposals = duties_service
+        .proposers
+        .read()
+        .get(&epoch)
+        .map(|(_, proposers)| proposers.clone())
+        .unwrap_or_default();
+    proposals.sort_by_key(|proposer| proposer.slot);
+
+    let mut attestations = duties_service
+        .attesters
+        .read()
+        .values()
+        .filter_map(|epoch_duties| epoch_duties.get(&epoch))
+        .map(|(_, duty_and_proof)| {
+            let duty = &duty_and_proof.duty;
+            SimulatedAttestation {
+                slot: duty.slot,
+                pubkey: duty.pubkey,
+                validator_index: duty.validator_index,
+                committee_index: duty.committee_index,
+                committee_length: duty.committee_length,
+                validator_committee_index: duty.validator_committee_index,
+                selection_proof: duty_and_proof.selection_proof.clone().map(Into::into),
+            }
+        })
+        .collect::<Vec<_>>();
+    attestations.sort_by_key(|attestation| (attestation.slot, attestation.validator_index));
+
+    let sync_committee = duties_service
+        .sync_duties
+        .get_duties_for_epoch(epoch, &duties_service.spec)
+        .into_iter()
+        .map(|(duty, proofs)| SimulatedSyncCommitteeDuty {
+            pubkey: duty.pubkey,
+            validator_index: duty.validator_index,
+            validator_sync_committee_indices: duty.validator_sync_committee_indices,
+            aggregations: proofs
+                .into_iter()
+                .map(|(slot, subnet_id, proof)| SimulatedSyncAggregation {
+                    slot,
+                    subnet_id: *subnet_id,
+                    selection_proof: proof.into(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(DutySimulation {
+        epoch,
+        timing,
+        proposals,
+        attestations,
+        sync_committee,
+    })
+}
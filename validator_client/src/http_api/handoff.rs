@@ -0,0 +1,204 @@
+//! Handoff of validators between validator clients.
+//!
+//! The source validator client disables the validators and returns their keystores, slashing
+//! protection data and a handoff signed by each validator, stating when it was disabled. The
+//! destination validator client only imports the validators once its dead time has passed since
+//! they were disabled, giving any messages signed by the source time to be superseded.
+use super::keystores;
+use crate::{signing_method::SigningMethod, ValidatorStore};
+use eth2::lighthouse_vc::{
+    std_types::{ImportKeystoresRequest, ImportKeystoresResponse, KeystoreJsonStr},
+    types::{
+        ImportValidatorHandoffRequest, SingleValidatorHandoff, ValidatorHandoffRequest,
+        ValidatorHandoffResponse,
+    },
+};
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+use types::{ChainSpec, EthSpec, SignedRoot, SignedValidatorHandoff, ValidatorHandoff};
+use warp::Rejection;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// The number of seconds after its dead time has passed for which a handoff may be imported.
+pub const HANDOFF_EXPIRY: u64 = 24 * 60 * 60;
+
+fn unix_timestamp() -> Result<u64, Rejection> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .map_err(|e| custom_server_error(format!("system time is before the UNIX epoch: {e}")))
+}
+
+pub fn export<T: SlotClock + 'static, E: EthSpec>(
+    request: ValidatorHandoffRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    spec: Arc<ChainSpec>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<ValidatorHandoffResponse, Rejection> {
+    let handle = task_executor
+        .handle()
+        .ok_or_else(|| custom_server_error("validator client shutdown".into()))?;
+
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rwlock.write();
+
+    // Check all of the validators before disabling any, so that the request either hands off all
+    // of the validators or none of them.
+    let mut unique_pubkeys = HashSet::new();
+    let mut validators = Vec::with_capacity(request.pubkeys.len());
+    for pubkey_bytes in &request.pubkeys {
+        if !unique_pubkeys.insert(*pubkey_bytes) {
+            return Err(custom_bad_request(format!(
+                "duplicate validator {pubkey_bytes:?}"
+            )));
+        }
+        let pubkey = pubkey_bytes
+            .decompress()
+            .map_err(|e| custom_bad_request(format!("invalid pubkey {pubkey_bytes:?}: {e:?}")))?;
+        let signing_method = initialized_validators
+            .signing_method(pubkey_bytes)
+            .ok_or_else(|| custom_bad_request(format!("{pubkey_bytes:?} is not enabled")))?;
+        let SigningMethod::LocalKeystore {
+            ref voting_keypair, ..
+        } = *signing_method
+        else {
+            return Err(custom_bad_request(format!(
+                "{pubkey_bytes:?} is not a local keystore validator"
+            )));
+        };
+        validators.push((pubkey, voting_keypair.clone()));
+    }
+
+    let mut data = Vec::with_capacity(validators.len());
+    for (pubkey, voting_keypair) in validators {
+        let keystore_and_password = handle
+            .block_on(initialized_validators.disable_for_handoff(&pubkey))
+            .map_err(|e| custom_server_error(format!("unable to disable {pubkey:?}: {e:?}")))?;
+
+        let message = ValidatorHandoff {
+            pubkey: pubkey.compress(),
+            disabled_at: unix_timestamp()?,
+        };
+        let signature = voting_keypair
+            .sk
+            .sign(message.signing_root(spec.get_validator_handoff_domain()));
+        info!(
+            log,
+            "Disabled validator for handoff";
+            "pubkey" => ?pubkey,
+            "disabled_at" => message.disabled_at,
+        );
+
+        data.push(SingleValidatorHandoff {
+            validating_keystore: KeystoreJsonStr(keystore_and_password.keystore),
+            validating_keystore_password: keystore_and_password.password,
+            handoff: SignedValidatorHandoff { message, signature },
+        });
+    }
+    drop(initialized_validators);
+
+    let slashing_protection = validator_store
+        .export_slashing_protection_for_keys(&request.pubkeys)
+        .map_err(|e| {
+            custom_server_error(format!("error exporting slashing protection: {:?}", e))
+        })?;
+
+    Ok(ValidatorHandoffResponse {
+        data,
+        slashing_protection,
+    })
+}
+
+/// Check that `handoff` was signed by the validator of `keystore` at least `dead_time` seconds
+/// before `now`, and has not expired.
+fn verify_handoff(
+    handoff: &SignedValidatorHandoff,
+    keystore: &KeystoreJsonStr,
+    dead_time: u64,
+    now: u64,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let pubkey = handoff.message.pubkey;
+    if keystore.public_key().map(|pubkey| pubkey.compress()) != Some(pubkey) {
+        return Err(format!(
+            "handoff of {pubkey:?} does not match keystore {}",
+            keystore.pubkey()
+        ));
+    }
+    if !handoff.verify_signature(spec) {
+        return Err(format!("invalid handoff signature for {pubkey:?}"));
+    }
+
+    let dead_time_end = handoff.message.disabled_at.saturating_add(dead_time);
+    if now < dead_time_end {
+        return Err(format!(
+            "the dead time of {pubkey:?} ends in {} seconds",
+            dead_time_end - now
+        ));
+    }
+    if now > dead_time_end.saturating_add(HANDOFF_EXPIRY) {
+        return Err(format!("the handoff of {pubkey:?} has expired"));
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn import<T: SlotClock + 'static, E: EthSpec>(
+    request: ImportValidatorHandoffRequest,
+    dead_time: u64,
+    validator_dir: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    spec: Arc<ChainSpec>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<ImportKeystoresResponse, Rejection> {
+    let ImportValidatorHandoffRequest {
+        keystores,
+        passwords,
+        handoffs,
+        slashing_protection,
+    } = request;
+    if keystores.len() != handoffs.len() {
+        return Err(custom_bad_request(format!(
+            "mismatched numbers of keystores ({}) and handoffs ({})",
+            keystores.len(),
+            handoffs.len(),
+        )));
+    }
+
+    let now = unix_timestamp()?;
+    for (handoff, keystore) in handoffs.iter().zip(&keystores) {
+        verify_handoff(handoff, keystore, dead_time, now, &spec).map_err(custom_bad_request)?;
+        if !slashing_protection
+            .0
+            .data
+            .iter()
+            .any(|data| data.pubkey == handoff.message.pubkey)
+        {
+            return Err(custom_bad_request(format!(
+                "slashing protection data for {:?} not provided",
+                handoff.message.pubkey
+            )));
+        }
+    }
+
+    keystores::import(
+        ImportKeystoresRequest {
+            keystores,
+            passwords,
+            slashing_protection: Some(slashing_protection),
+        },
+        validator_dir,
+        secrets_dir,
+        validator_store,
+        task_executor,
+        log,
+    )
+}
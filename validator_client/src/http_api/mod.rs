@@ -1,7 +1,10 @@
 mod api_secret;
 mod create_signed_voluntary_exit;
 mod create_validator;
+mod create_withdrawal_request;
+mod duties;
 mod graffiti;
+mod handoff;
 mod keystores;
 mod remotekeys;
 mod tests;
@@ -10,7 +13,12 @@ pub mod test_utils;
 
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
+use crate::duties_service::DutiesService;
+use crate::exit_service::ExitScheduler;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
+use crate::http_api::create_withdrawal_request::create_withdrawal_request;
+use crate::http_api::duties::get_duty_simulation;
+use crate::presigned_exits::PresignedExitStore;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
@@ -42,7 +50,10 @@ use std::sync::Arc;
 use sysinfo::{System, SystemExt};
 use system_health::observe_system_health_vc;
 use task_executor::TaskExecutor;
-use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    StreamExt,
+};
 use types::{ChainSpec, ConfigAndPreset, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 use warp::{sse::Event, Filter};
@@ -73,6 +84,9 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub exit_scheduler: Option<Arc<ExitScheduler>>,
+    pub presigned_exit_store: Option<Arc<PresignedExitStore>>,
+    pub duties_service: Option<Arc<DutiesService<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -94,8 +108,16 @@ pub struct Config {
     pub allow_origin: Option<String>,
     pub allow_keystore_export: bool,
     pub store_passwords_in_secrets_dir: bool,
+    /// The number of seconds after a validator is disabled on its previous validator client
+    /// before it may be imported with a handoff.
+    pub handoff_dead_time: u64,
+    /// Only import keystores which are handed off from another validator client.
+    pub require_handoff: bool,
 }
 
+/// Two epochs on mainnet.
+pub const DEFAULT_HANDOFF_DEAD_TIME: u64 = 768;
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -105,6 +127,8 @@ impl Default for Config {
             allow_origin: None,
             allow_keystore_export: false,
             store_passwords_in_secrets_dir: false,
+            handoff_dead_time: DEFAULT_HANDOFF_DEAD_TIME,
+            require_handoff: false,
         }
     }
 }
@@ -131,6 +155,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let config = &ctx.config;
     let allow_keystore_export = config.allow_keystore_export;
     let store_passwords_in_secrets_dir = config.store_passwords_in_secrets_dir;
+    let handoff_dead_time = config.handoff_dead_time;
+    let require_handoff = config.require_handoff;
     let log = ctx.log.clone();
 
     // Configure CORS.
@@ -180,6 +206,39 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_exit_scheduler = ctx.exit_scheduler.clone();
+    let exit_scheduler_filter = warp::any()
+        .map(move || inner_exit_scheduler.clone())
+        .and_then(|exit_scheduler: Option<_>| async move {
+            exit_scheduler.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "exit scheduler is not initialized.".to_string(),
+                )
+            })
+        });
+
+    let inner_presigned_exit_store = ctx.presigned_exit_store.clone();
+    let presigned_exit_store_filter = warp::any()
+        .map(move || inner_presigned_exit_store.clone())
+        .and_then(|presigned_exit_store: Option<_>| async move {
+            presigned_exit_store.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "pre-signed exits are not enabled.".to_string(),
+                )
+            })
+        });
+
+    let inner_duties_service = ctx.duties_service.clone();
+    let duties_service_filter = warp::any()
+        .map(move || inner_duties_service.clone())
+        .and_then(|duties_service: Option<_>| async move {
+            duties_service.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "duties service is not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -507,6 +566,12 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                   validator_store: Arc<ValidatorStore<T, E>>,
                   task_executor: TaskExecutor| {
                 blocking_json_task(move || {
+                    if require_handoff {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "keystores may only be imported with a handoff".to_string(),
+                        ));
+                    }
+
                     // Check to ensure the password is correct.
                     let keypair = body
                         .keystore
@@ -614,6 +679,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                         client_identity_path: web3signer.client_identity_path,
                                         client_identity_password: web3signer
                                             .client_identity_password,
+                                        server_identity: web3signer.server_identity,
                                     },
                                 ),
                             })
@@ -785,6 +851,77 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET /lighthouse/validators/handoff
+    let validators_handoff = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("handoff"));
+    let get_lighthouse_validators_handoff = validators_handoff.and(warp::path::end()).then(|| {
+        blocking_json_task(move || {
+            Ok(api_types::GenericResponse::from(
+                api_types::ValidatorHandoffConfig {
+                    dead_time: handoff_dead_time,
+                    required: require_handoff,
+                },
+            ))
+        })
+    });
+
+    // POST /lighthouse/validators/handoff/export
+    let post_lighthouse_validators_handoff_export = validators_handoff
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(spec_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .then(move |request, validator_store, spec, task_executor, log| {
+            blocking_json_task(move || {
+                if allow_keystore_export {
+                    handoff::export(request, validator_store, spec, task_executor, log)
+                } else {
+                    Err(warp_utils::reject::custom_bad_request(
+                        "keystore export is disabled".to_string(),
+                    ))
+                }
+            })
+        });
+
+    // POST /lighthouse/validators/handoff/import
+    let post_lighthouse_validators_handoff_import = validators_handoff
+        .and(warp::path("import"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_dir_filter.clone())
+        .and(secrets_dir_filter.clone())
+        .and(validator_store_filter.clone())
+        .and(spec_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            move |request,
+                  validator_dir,
+                  secrets_dir,
+                  validator_store,
+                  spec,
+                  task_executor,
+                  log| {
+                let secrets_dir = store_passwords_in_secrets_dir.then_some(secrets_dir);
+                blocking_json_task(move || {
+                    handoff::import(
+                        request,
+                        handoff_dead_time,
+                        validator_dir,
+                        secrets_dir,
+                        validator_store,
+                        spec,
+                        task_executor,
+                        log,
+                    )
+                })
+            },
+        );
+
     // Standard key-manager endpoints.
     let eth_v1 = warp::path("eth").and(warp::path("v1"));
     let std_keystores = eth_v1.and(warp::path("keystores")).and(warp::path::end());
@@ -1016,7 +1153,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(warp::query::<api_types::VoluntaryExitQuery>())
         .and(warp::path::end())
         .and(validator_store_filter.clone())
-        .and(slot_clock_filter)
+        .and(slot_clock_filter.clone())
         .and(log_filter.clone())
         .and(task_executor_filter.clone())
         .then(
@@ -1046,6 +1183,158 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST /lighthouse/validators/{pubkey}/withdrawal_request
+    let post_validators_withdrawal_request = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("withdrawal_request"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |pubkey: PublicKey,
+             body: api_types::CreateWithdrawalRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             log| {
+                blocking_json_task(move || {
+                    create_withdrawal_request(pubkey, body.amount, validator_store, log)
+                })
+            },
+        );
+
+    // GET /lighthouse/validators/exit
+    let get_lighthouse_validators_exit = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("exit"))
+        .and(warp::path::end())
+        .and(exit_scheduler_filter.clone())
+        .then(|exit_scheduler: Arc<ExitScheduler>| {
+            blocking_json_task(move || Ok(GenericResponse::from(exit_scheduler.schedule())))
+        });
+
+    // POST /lighthouse/validators/exit
+    let post_lighthouse_validators_exit = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("exit"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(exit_scheduler_filter)
+        .and(log_filter.clone())
+        .then(
+            |body: api_types::ScheduleExitsRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             exit_scheduler: Arc<ExitScheduler>,
+             log| {
+                blocking_json_task(move || {
+                    if body.exits_per_epoch == 0 {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "exits_per_epoch must be greater than zero".to_string(),
+                        ));
+                    }
+                    if let Some(pubkey) = body
+                        .pubkeys
+                        .iter()
+                        .find(|pubkey| !validator_store.has_validator(pubkey))
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "{} is disabled or not managed by this validator client",
+                            pubkey.as_hex_string()
+                        )));
+                    }
+
+                    let schedule = exit_scheduler
+                        .schedule_exits(&body.pubkeys, body.exits_per_epoch)
+                        .map_err(warp_utils::reject::custom_server_error)?;
+                    info!(
+                        log,
+                        "Scheduled voluntary exits";
+                        "count" => body.pubkeys.len(),
+                        "exits_per_epoch" => body.exits_per_epoch,
+                    );
+                    Ok(GenericResponse::from(schedule))
+                })
+            },
+        );
+
+    // GET /lighthouse/validators/exit/presigned
+    let get_lighthouse_validators_exit_presigned = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("exit"))
+        .and(warp::path("presigned"))
+        .and(warp::path::end())
+        .and(presigned_exit_store_filter.clone())
+        .then(|presigned_exit_store: Arc<PresignedExitStore>| {
+            blocking_json_task(move || {
+                Ok(GenericResponse::from(
+                    presigned_exit_store.presigned_exits(),
+                ))
+            })
+        });
+
+    // POST /lighthouse/validators/exit/presigned/heartbeat
+    let post_lighthouse_validators_exit_presigned_heartbeat = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("exit"))
+        .and(warp::path("presigned"))
+        .and(warp::path("heartbeat"))
+        .and(warp::path::end())
+        .and(presigned_exit_store_filter.clone())
+        .and(slot_clock_filter)
+        .then(
+            |presigned_exit_store: Arc<PresignedExitStore>, slot_clock: T| {
+                blocking_json_task(move || {
+                    let epoch = slot_clock
+                        .now()
+                        .map(|slot| slot.epoch(E::slots_per_epoch()))
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_server_error(
+                                "Unable to determine current epoch".to_string(),
+                            )
+                        })?;
+                    presigned_exit_store
+                        .heartbeat(epoch)
+                        .map(GenericResponse::from)
+                        .map_err(warp_utils::reject::custom_server_error)
+                })
+            },
+        );
+
+    // POST /lighthouse/validators/exit/presigned/broadcast
+    let post_lighthouse_validators_exit_presigned_broadcast = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("exit"))
+        .and(warp::path("presigned"))
+        .and(warp::path("broadcast"))
+        .and(warp::path::end())
+        .and(presigned_exit_store_filter)
+        .and(log_filter.clone())
+        .then(|presigned_exit_store: Arc<PresignedExitStore>, log| {
+            blocking_json_task(move || {
+                warn!(log, "Broadcast of pre-signed voluntary exits requested");
+                presigned_exit_store
+                    .request_broadcast()
+                    .map(GenericResponse::from)
+                    .map_err(warp_utils::reject::custom_server_error)
+            })
+        });
+
+    // GET /lighthouse/duties/simulation
+    let get_lighthouse_duties_simulation = warp::path("lighthouse")
+        .and(warp::path("duties"))
+        .and(warp::path("simulation"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::DutySimulationQuery>())
+        .and(duties_service_filter)
+        .then(
+            |query: api_types::DutySimulationQuery, duties_service: Arc<DutiesService<T, E>>| {
+                blocking_json_task(move || {
+                    get_duty_simulation(query.epoch, &duties_service).map(GenericResponse::from)
+                })
+            },
+        );
+
     // GET /eth/v1/validator/{pubkey}/graffiti
     let get_graffiti = eth_v1
         .and(warp::path("validator"))
@@ -1139,6 +1428,11 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             move |request, validator_dir, secrets_dir, validator_store, task_executor, log| {
                 let secrets_dir = store_passwords_in_secrets_dir.then_some(secrets_dir);
                 blocking_json_task(move || {
+                    if require_handoff {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "keystores may only be imported with a handoff".to_string(),
+                        ));
+                    }
                     keystores::import(
                         request,
                         validator_dir,
@@ -1203,9 +1497,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and_then(|sse_component: Option<SSELoggingComponents>| {
             warp_utils::task::blocking_task(move || {
                 if let Some(logging_components) = sse_component {
+                    // Send the recent warnings and errors before any new events.
+                    let (recent, receiver) = logging_components.subscribe_with_recent();
                     // Build a JSON stream
-                    let s =
-                        BroadcastStream::new(logging_components.sender.subscribe()).map(|msg| {
+                    let s = tokio_stream::iter(recent)
+                        .map(Ok::<_, BroadcastStreamRecvError>)
+                        .chain(BroadcastStream::new(receiver))
+                        .map(|msg| {
                             match msg {
                                 Ok(data) => {
                                     // Serialize to json
@@ -1250,6 +1548,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_health)
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
+                        .or(get_lighthouse_validators_exit)
+                        .or(get_lighthouse_validators_exit_presigned)
+                        .or(get_lighthouse_validators_handoff)
+                        .or(get_lighthouse_duties_simulation)
                         .or(get_lighthouse_validators_pubkey)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
@@ -1266,6 +1568,12 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
                         .or(post_validators_voluntary_exits)
+                        .or(post_validators_withdrawal_request)
+                        .or(post_lighthouse_validators_exit)
+                        .or(post_lighthouse_validators_exit_presigned_heartbeat)
+                        .or(post_lighthouse_validators_exit_presigned_broadcast)
+                        .or(post_lighthouse_validators_handoff_export)
+                        .or(post_lighthouse_validators_handoff_import)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
                         .or(post_std_keystores)
@@ -1,7 +1,8 @@
 use crate::doppelganger_service::DoppelgangerService;
+use crate::exit_service::ExitScheduler;
 use crate::key_cache::{KeyCache, CACHE_FILENAME};
 use crate::{
-    http_api::{ApiSecret, Config as HttpConfig, Context},
+    http_api::{ApiSecret, Config as HttpConfig, Context, DEFAULT_HANDOFF_DEAD_TIME},
     initialized_validators::{InitializedValidators, OnDecryptFailure},
     Config, ValidatorDefinitions, ValidatorStore,
 };
@@ -112,6 +113,7 @@ impl ApiTester {
             Hash256::repeat_byte(42),
             spec,
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            None,
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -130,6 +132,11 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            exit_scheduler: Some(Arc::new(
+                ExitScheduler::open_or_create(validator_dir.path(), E::default_spec()).unwrap(),
+            )),
+            presigned_exit_store: None,
+            duties_service: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -179,6 +186,8 @@ impl ApiTester {
             allow_origin: None,
             allow_keystore_export: true,
             store_passwords_in_secrets_dir: false,
+            handoff_dead_time: DEFAULT_HANDOFF_DEAD_TIME,
+            require_handoff: false,
         }
     }
 
@@ -518,6 +527,7 @@ impl ApiTester {
                     request_timeout_ms: None,
                     client_identity_path: None,
                     client_identity_password: None,
+                    server_identity: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
                 }
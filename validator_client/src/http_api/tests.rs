@@ -4,6 +4,8 @@
 mod keystores;
 
 use crate::doppelganger_service::DoppelgangerService;
+use crate::exit_service::ExitScheduler;
+use crate::presigned_exits::PresignedExitStore;
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
@@ -100,6 +102,7 @@ impl ApiTester {
             Hash256::repeat_byte(42),
             spec.clone(),
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            None,
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -118,6 +121,18 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            exit_scheduler: Some(Arc::new(
+                ExitScheduler::open_or_create(validator_dir.path(), E::default_spec()).unwrap(),
+            )),
+            presigned_exit_store: Some(Arc::new(
+                PresignedExitStore::open_or_create(
+                    validator_dir.path(),
+                    random_password(),
+                    Some(4),
+                )
+                .unwrap(),
+            )),
+            duties_service: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -128,6 +143,8 @@ impl ApiTester {
                 allow_origin: None,
                 allow_keystore_export: true,
                 store_passwords_in_secrets_dir: false,
+                handoff_dead_time: config.http_api.handoff_dead_time,
+                require_handoff: config.http_api.require_handoff,
             },
             sse_logging_components: None,
             log,
@@ -481,6 +498,7 @@ impl ApiTester {
                     request_timeout_ms: None,
                     client_identity_path: None,
                     client_identity_password: None,
+                    server_identity: None,
                 }
             })
             .collect();
@@ -520,6 +538,171 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_create_withdrawal_request(self, index: usize, amount: u64) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        let tx = self
+            .client
+            .post_lighthouse_validators_withdrawal_request(&validator.voting_pubkey, amount)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            tx.to,
+            deposit_contract::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS
+        );
+        assert_eq!(
+            tx.data,
+            deposit_contract::encode_withdrawal_request_tx_data(&validator.voting_pubkey, amount)
+        );
+
+        self
+    }
+
+    pub async fn test_schedule_exits(self, indices: &[usize], exits_per_epoch: u64) -> Self {
+        let validators = self.client.get_lighthouse_validators().await.unwrap().data;
+        let pubkeys = indices
+            .iter()
+            .map(|&i| validators[i].voting_pubkey)
+            .collect::<Vec<_>>();
+        let previous = self
+            .client
+            .get_lighthouse_validators_exit()
+            .await
+            .unwrap()
+            .data;
+
+        let schedule = self
+            .client
+            .post_lighthouse_validators_exit(&ScheduleExitsRequest {
+                pubkeys: pubkeys.clone(),
+                exits_per_epoch,
+            })
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(schedule.exits_per_epoch, exits_per_epoch);
+        // Each validator is scheduled once, in the order it was first requested.
+        let mut expected_pubkeys = previous
+            .exits
+            .iter()
+            .map(|exit| exit.pubkey)
+            .collect::<Vec<_>>();
+        for pubkey in pubkeys {
+            if !expected_pubkeys.contains(&pubkey) {
+                expected_pubkeys.push(pubkey);
+            }
+        }
+        assert_eq!(
+            schedule
+                .exits
+                .iter()
+                .map(|exit| exit.pubkey)
+                .collect::<Vec<_>>(),
+            expected_pubkeys
+        );
+        assert!(schedule
+            .exits
+            .iter()
+            .all(|exit| exit.status == ScheduledExitStatus::Pending));
+
+        assert_eq!(
+            self.client
+                .get_lighthouse_validators_exit()
+                .await
+                .unwrap()
+                .data,
+            schedule
+        );
+
+        // The schedule is persisted so that it resumes after a restart.
+        let reopened =
+            ExitScheduler::open_or_create(self._validator_dir.path(), E::default_spec()).unwrap();
+        assert_eq!(reopened.schedule(), schedule);
+
+        self
+    }
+
+    pub async fn test_schedule_exits_invalid(self) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[0];
+
+        // The rate of exits must be positive.
+        self.client
+            .post_lighthouse_validators_exit(&ScheduleExitsRequest {
+                pubkeys: vec![validator.voting_pubkey],
+                exits_per_epoch: 0,
+            })
+            .await
+            .unwrap_err();
+
+        // Only validators managed by the validator client can be exited.
+        self.client
+            .post_lighthouse_validators_exit(&ScheduleExitsRequest {
+                pubkeys: vec![PublicKeyBytes::empty()],
+                exits_per_epoch: 1,
+            })
+            .await
+            .unwrap_err();
+
+        self
+    }
+
+    pub async fn test_presigned_exits(self) -> Self {
+        let presigned_exits = self
+            .client
+            .get_lighthouse_validators_exit_presigned()
+            .await
+            .unwrap()
+            .data;
+        assert!(presigned_exits.exits.is_empty());
+        assert_eq!(presigned_exits.heartbeat_epoch, None);
+        assert!(!presigned_exits.broadcast_requested);
+
+        // Resetting the dead man's switch moves the deadline.
+        let epoch = self.get_current_epoch();
+        let presigned_exits = self
+            .client
+            .post_lighthouse_validators_exit_presigned_heartbeat()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(presigned_exits.heartbeat_epoch, Some(epoch));
+        assert_eq!(presigned_exits.deadline_epoch, Some(epoch + 4));
+
+        let presigned_exits = self
+            .client
+            .post_lighthouse_validators_exit_presigned_broadcast()
+            .await
+            .unwrap()
+            .data;
+        assert!(presigned_exits.broadcast_requested);
+        assert_eq!(presigned_exits.broadcast_epoch, None);
+
+        assert_eq!(
+            self.client
+                .get_lighthouse_validators_exit_presigned()
+                .await
+                .unwrap()
+                .data,
+            presigned_exits
+        );
+
+        self
+    }
+
+    pub async fn test_duty_simulation_without_duties_service(self) -> Self {
+        let err = self
+            .client
+            .get_lighthouse_duties_simulation(Some(self.get_current_epoch()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        self
+    }
+
     fn get_current_epoch(&self) -> Epoch {
         self.slot_clock
             .now()
@@ -858,6 +1041,45 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_withdrawal_request(&PublicKeyBytes::empty(), 0)
+                .await
+        })
+        .await
+        .test_with_invalid_auth(
+            |client| async move { client.get_lighthouse_validators_exit().await },
+        )
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_validators_exit_presigned().await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_duties_simulation(None).await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_exit_presigned_heartbeat()
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_exit_presigned_broadcast()
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_exit(&ScheduleExitsRequest {
+                    pubkeys: vec![],
+                    exits_per_epoch: 1,
+                })
+                .await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .post_lighthouse_validators(vec![ValidatorRequest {
@@ -1030,6 +1252,55 @@ async fn validator_exit() {
         .await;
 }
 
+#[tokio::test]
+async fn validator_withdrawal_request() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_create_withdrawal_request(0, 1_000_000_000)
+        .await
+        .test_create_withdrawal_request(0, 0)
+        .await;
+}
+
+#[tokio::test]
+async fn validator_exit_schedule() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 3,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_schedule_exits(&[0, 1], 1)
+        .await
+        .test_schedule_exits(&[1, 2], 2)
+        .await
+        .test_schedule_exits_invalid()
+        .await;
+}
+
+#[tokio::test]
+async fn validator_presigned_exits() {
+    ApiTester::new().await.test_presigned_exits().await;
+}
+
+#[tokio::test]
+async fn duty_simulation_without_duties_service() {
+    ApiTester::new()
+        .await
+        .test_duty_simulation_without_duties_service()
+        .await;
+}
+
 #[tokio::test]
 async fn validator_enabling() {
     ApiTester::new()
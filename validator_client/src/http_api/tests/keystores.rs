@@ -1,8 +1,12 @@
 use super::super::super::validator_store::DEFAULT_GAS_LIMIT;
 use super::*;
+use crate::http_api::DEFAULT_HANDOFF_DEAD_TIME;
 use account_utils::random_password_string;
 use bls::PublicKeyBytes;
-use eth2::lighthouse_vc::types::UpdateFeeRecipientRequest;
+use eth2::lighthouse_vc::types::{
+    ImportValidatorHandoffRequest, UpdateFeeRecipientRequest, ValidatorHandoffRequest,
+    ValidatorHandoffResponse,
+};
 use eth2::lighthouse_vc::{
     http_client::ValidatorClientHttpClient as HttpClient,
     std_types::{KeystoreJsonStr as Keystore, *},
@@ -51,6 +55,7 @@ fn web3signer_validator_with_pubkey(pubkey: PublicKey) -> Web3SignerValidatorReq
         request_timeout_ms: None,
         client_identity_path: None,
         client_identity_password: None,
+        server_identity: None,
     }
 }
 
@@ -1410,6 +1415,142 @@ async fn delete_then_reimport() {
     .await
 }
 
+fn handoff_dest_config(dead_time: u64) -> Config {
+    let mut config = Config::default();
+    config.http_api.handoff_dead_time = dead_time;
+    config.http_api.require_handoff = true;
+    config
+}
+
+fn handoff_import_request(handoff: &ValidatorHandoffResponse) -> ImportValidatorHandoffRequest {
+    ImportValidatorHandoffRequest {
+        keystores: handoff
+            .data
+            .iter()
+            .map(|single| single.validating_keystore.clone())
+            .collect(),
+        passwords: handoff
+            .data
+            .iter()
+            .map(|single| single.validating_keystore_password.clone().unwrap())
+            .collect(),
+        handoffs: handoff
+            .data
+            .iter()
+            .map(|single| single.handoff.clone())
+            .collect(),
+        slashing_protection: InterchangeJsonStr(handoff.slashing_protection.clone()),
+    }
+}
+
+/// Import `count` new keystores into `tester`, returning the request to hand them off.
+async fn import_for_handoff(tester: &ApiTester, count: usize) -> ValidatorHandoffRequest {
+    let password = random_password_string();
+    let keystores = (0..count)
+        .map(|_| new_keystore(password.clone()))
+        .collect::<Vec<_>>();
+    let import_res = tester
+        .client
+        .post_keystores(&ImportKeystoresRequest {
+            keystores: keystores.clone(),
+            passwords: vec![password; count],
+            slashing_protection: None,
+        })
+        .await
+        .unwrap();
+    check_keystore_import_response(&import_res, all_imported(count));
+
+    ValidatorHandoffRequest {
+        pubkeys: keystores.iter().map(keystore_pubkey).collect(),
+    }
+}
+
+#[tokio::test]
+async fn handoff_between_vcs() {
+    let src = ApiTester::new().await;
+    let dest = ApiTester::new_with_config(handoff_dest_config(0)).await;
+    let handoff_req = import_for_handoff(&src, 2).await;
+
+    let handoff = src
+        .client
+        .post_lighthouse_validators_handoff_export(&handoff_req)
+        .await
+        .unwrap();
+    assert_eq!(handoff.data.len(), 2);
+    assert!(src.client.get_keystores().await.unwrap().data.is_empty());
+    // The validators can't be handed off again once they're disabled.
+    assert!(src
+        .client
+        .post_lighthouse_validators_handoff_export(&handoff_req)
+        .await
+        .is_err());
+
+    // The destination only imports keystores with a handoff.
+    let import_req = handoff_import_request(&handoff);
+    assert!(dest
+        .client
+        .post_keystores(&ImportKeystoresRequest {
+            keystores: import_req.keystores.clone(),
+            passwords: import_req.passwords.clone(),
+            slashing_protection: Some(import_req.slashing_protection.clone()),
+        })
+        .await
+        .is_err());
+
+    let import_res = dest
+        .client
+        .post_lighthouse_validators_handoff_import(&import_req)
+        .await
+        .unwrap();
+    check_keystore_import_response(&import_res, all_imported(2));
+    check_keystore_get_response(
+        &dest.client.get_keystores().await.unwrap(),
+        import_req.keystores.iter(),
+    );
+}
+
+#[tokio::test]
+async fn handoff_invalid() {
+    let src = ApiTester::new().await;
+    let dest = ApiTester::new_with_config(handoff_dest_config(DEFAULT_HANDOFF_DEAD_TIME)).await;
+    let handoff_req = import_for_handoff(&src, 2).await;
+    let handoff = src
+        .client
+        .post_lighthouse_validators_handoff_export(&handoff_req)
+        .await
+        .unwrap();
+
+    // The dead time hasn't passed.
+    let import_req = handoff_import_request(&handoff);
+    assert!(dest
+        .client
+        .post_lighthouse_validators_handoff_import(&import_req)
+        .await
+        .is_err());
+
+    // Backdating the handoff invalidates its signature.
+    let mut import_req = handoff_import_request(&handoff);
+    for handoff in &mut import_req.handoffs {
+        handoff.message.disabled_at -= DEFAULT_HANDOFF_DEAD_TIME;
+    }
+    assert!(dest
+        .client
+        .post_lighthouse_validators_handoff_import(&import_req)
+        .await
+        .is_err());
+
+    // The handoff of one validator can't be used for another.
+    let mut import_req = handoff_import_request(&handoff);
+    import_req.handoffs.swap(0, 1);
+    assert!(dest
+        .client
+        .post_lighthouse_validators_handoff_import(&import_req)
+        .await
+        .is_err());
+
+    assert!(dest.client.get_keystores().await.unwrap().data.is_empty());
+}
+
 #[tokio::test]
 async fn get_empty_remotekeys() {
     run_test(|tester| async move {
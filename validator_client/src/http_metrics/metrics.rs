@@ -36,6 +36,8 @@ pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const SIGNER: &str = "signer";
+pub const LATE_START: &str = "late_start";
 
 pub use lighthouse_metrics::*;
 
@@ -182,6 +184,28 @@ lazy_static::lazy_static! {
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
     );
+    pub static ref SIGNING_MESSAGE_TIMES: Result<HistogramVec> = try_create_histogram_vec_with_buckets(
+        "vc_signing_message_times_seconds",
+        "Duration to obtain a signature for each signer backend and message type",
+        decimal_buckets(-4, 1),
+        &["type", "message"]
+    );
+    pub static ref SIGNING_ERRORS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signing_errors_total",
+        "Total count of failures to obtain a signature for each signer backend, message type and \
+         class of error",
+        &["type", "message", "error"]
+    );
+    pub static ref WEB3SIGNER_UNHEALTHY_COUNT: Result<IntGauge> = try_create_int_gauge(
+        "vc_web3signer_unhealthy_count",
+        "Number of web3signers which failed their most recent health check",
+    );
+    pub static ref SIGNING_DEADLINE_MISSES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signing_deadline_misses_total",
+        "Total count of duty signatures obtained after the deadline of the duty. The cause is \
+         `signer` if signing began before the deadline, otherwise `late_start`",
+        &["type", "duty", "cause"]
+    );
 
     pub static ref ATTESTATION_DUTY: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "vc_attestation_duty_slot",
@@ -200,6 +224,14 @@ lazy_static::lazy_static! {
         "vc_beacon_node_latency_primary_endpoint",
         "Round-trip latency for the primary BN endpoint",
     );
+    pub static ref LEADER_ELECTION_LEADER: Result<IntGauge> = try_create_int_gauge(
+        "vc_leader_election_leader",
+        "Set to 1 if this validator client is the elected leader, otherwise set to 0",
+    );
+    pub static ref CLOCK_OFFSET: Result<IntGauge> = try_create_int_gauge(
+        "vc_clock_offset_ntp_milliseconds",
+        "How far the local clock is ahead of the configured NTP server",
+    );
 }
 
 pub fn gather_prometheus_metrics<E: EthSpec>(
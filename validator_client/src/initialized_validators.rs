@@ -7,6 +7,7 @@
 //! validators are managed by this validator client.
 
 use crate::signing_method::SigningMethod;
+use crate::web3signer_client::Web3SignerClient;
 use account_utils::{
     read_password, read_password_from_user, read_password_string,
     validator_definitions::{
@@ -19,14 +20,13 @@ use eth2_keystore::Keystore;
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
-use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
+use reqwest::{Certificate, Error as ReqwestError, Identity};
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
 use types::graffiti::GraffitiString;
 use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
 use url::{ParseError, Url};
@@ -36,12 +36,6 @@ use crate::key_cache;
 use crate::key_cache::KeyCache;
 use crate::Config;
 
-/// Default timeout for a request to a remote signer for a signature.
-///
-/// Set to 12 seconds since that's the duration of a slot. A remote signer that cannot sign within
-/// that time is outside the synchronous assumptions of Eth2.
-const DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
-
 // Use TTY instead of stdin to capture passwords from users.
 const USE_STDIN: bool = false;
 
@@ -110,6 +104,8 @@ pub enum Error {
     InvalidWeb3SignerClientIdentityCertificateFile(io::Error),
     InvalidWeb3SignerClientIdentityCertificate(ReqwestError),
     UnableToBuildWeb3SignerClient(ReqwestError),
+    /// The server identity of the remote signer cannot be verified.
+    InvalidWeb3SignerServerIdentity(String),
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
     UnableToReadValidatorPassword(String),
@@ -208,7 +204,7 @@ impl InitializedValidator {
         def: ValidatorDefinition,
         key_cache: &mut KeyCache,
         key_stores: &mut HashMap<PathBuf, Keystore>,
-        web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+        web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Arc<Web3SignerClient>>>,
         config: &Config,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -298,43 +294,15 @@ impl InitializedValidator {
                 let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
                     .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
 
-                let request_timeout = web3_signer
-                    .request_timeout_ms
-                    .map(Duration::from_millis)
-                    .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
-
-                // Check if a client has already been initialized for this remote signer url.
-                let http_client = if let Some(client_map) = web3_signer_client_map {
-                    match client_map.get(&web3_signer) {
-                        Some(client) => client.clone(),
-                        None => {
-                            let client = build_web3_signer_client(
-                                web3_signer.root_certificate_path.clone(),
-                                web3_signer.client_identity_path.clone(),
-                                web3_signer.client_identity_password.clone(),
-                                request_timeout,
-                                config.web3_signer_keep_alive_timeout,
-                                config.web3_signer_max_idle_connections,
-                            )?;
-                            client_map.insert(web3_signer, client.clone());
-                            client
-                        }
+                // Check if a client has already been initialized for this remote signer.
+                let client_map = web3_signer_client_map.get_or_insert_with(HashMap::new);
+                let http_client = match client_map.get(&web3_signer) {
+                    Some(client) => client.clone(),
+                    None => {
+                        let client = Arc::new(Web3SignerClient::new(web3_signer.clone(), config)?);
+                        client_map.insert(web3_signer, client.clone());
+                        client
                     }
-                } else {
-                    // There are no clients in the map.
-                    let mut new_web3_signer_client_map: HashMap<Web3SignerDefinition, Client> =
-                        HashMap::new();
-                    let client = build_web3_signer_client(
-                        web3_signer.root_certificate_path.clone(),
-                        web3_signer.client_identity_path.clone(),
-                        web3_signer.client_identity_password.clone(),
-                        request_timeout,
-                        config.web3_signer_keep_alive_timeout,
-                        config.web3_signer_max_idle_connections,
-                    )?;
-                    new_web3_signer_client_map.insert(web3_signer, client.clone());
-                    *web3_signer_client_map = Some(new_web3_signer_client_map);
-                    client
                 };
 
                 SigningMethod::Web3Signer {
@@ -394,44 +362,6 @@ fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Resul
     Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
 
-fn build_web3_signer_client(
-    root_certificate_path: Option<PathBuf>,
-    client_identity_path: Option<PathBuf>,
-    client_identity_password: Option<String>,
-    request_timeout: Duration,
-    keep_alive_timeout: Option<Duration>,
-    max_idle_connections: Option<usize>,
-) -> Result<Client, Error> {
-    let builder = Client::builder()
-        .timeout(request_timeout)
-        .pool_idle_timeout(keep_alive_timeout)
-        .pool_max_idle_per_host(max_idle_connections.unwrap_or(usize::MAX));
-
-    let builder = if let Some(path) = root_certificate_path {
-        let certificate = load_pem_certificate(path)?;
-        builder.add_root_certificate(certificate)
-    } else {
-        builder
-    };
-
-    let builder = if let Some(path) = client_identity_path {
-        let identity = load_pkcs12_identity(
-            path,
-            &client_identity_password.ok_or(Error::MissingWeb3SignerClientIdentityPassword)?,
-        )?;
-        builder.identity(identity)
-    } else {
-        if client_identity_password.is_some() {
-            return Err(Error::MissingWeb3SignerClientIdentityCertificateFile);
-        }
-        builder
-    };
-
-    builder
-        .build()
-        .map_err(Error::UnableToBuildWeb3SignerClient)
-}
-
 /// Try to unlock `keystore` at `keystore_path` by prompting the user via `stdin`.
 fn unlock_keystore_via_stdin_password(
     keystore: &Keystore,
@@ -483,7 +413,7 @@ pub struct InitializedValidators {
     /// The canonical set of validators.
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
-    web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Arc<Web3SignerClient>>>,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -514,6 +444,33 @@ impl InitializedValidators {
         self.validators.len()
     }
 
+    /// Returns the clients of the remote signers used by the enabled validators.
+    pub fn web3_signer_clients(&self) -> Vec<Arc<Web3SignerClient>> {
+        self.web3_signer_validators()
+            .into_iter()
+            .map(|(client, _)| client)
+            .collect()
+    }
+
+    /// Returns the clients of the remote signers used by the enabled validators, each with the
+    /// voting public keys of the validators which use it.
+    pub fn web3_signer_validators(&self) -> Vec<(Arc<Web3SignerClient>, Vec<PublicKeyBytes>)> {
+        let mut clients: Vec<(Arc<Web3SignerClient>, Vec<PublicKeyBytes>)> = vec![];
+        for (pubkey, validator) in &self.validators {
+            if let SigningMethod::Web3Signer { http_client, .. } = validator.signing_method.as_ref()
+            {
+                match clients
+                    .iter_mut()
+                    .find(|(client, _)| Arc::ptr_eq(client, http_client))
+                {
+                    Some((_, pubkeys)) => pubkeys.push(*pubkey),
+                    None => clients.push((http_client.clone(), vec![*pubkey])),
+                }
+            }
+        }
+        clients
+    }
+
     /// The total count of enabled and disabled validators contained in `self`.
     pub fn num_total(&self) -> usize {
         self.definitions.as_slice().len()
@@ -946,6 +903,50 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Disable a local keystore validator so that it can be handed off to another validator
+    /// client, returning its keystore and password.
+    ///
+    /// Unlike `Self::delete_definition_and_keystore`, the keystore is kept on disk so that the
+    /// validator can be enabled again if the handoff is abandoned.
+    pub async fn disable_for_handoff(
+        &mut self,
+        pubkey: &PublicKey,
+    ) -> Result<KeystoreAndPassword, Error> {
+        let def = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| &def.voting_public_key == pubkey)
+            .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+        let SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            voting_keystore_password,
+            voting_keystore_password_path,
+            ..
+        } = &def.signing_definition
+        else {
+            return Err(Error::InvalidActionOnValidator);
+        };
+
+        let password = match (voting_keystore_password, voting_keystore_password_path) {
+            (Some(password), _) => Some(password.clone()),
+            (_, Some(path)) => {
+                Some(read_password_string(path).map_err(Error::UnableToReadValidatorPassword)?)
+            }
+            (None, None) => None,
+        };
+        let keystore = Keystore::from_json_file(voting_keystore_path)
+            .map_err(Error::UnableToReadKeystoreFile)?;
+
+        def.enabled = false;
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+        self.update_validators().await?;
+
+        Ok(KeystoreAndPassword { keystore, password })
+    }
+
     /// Sets the `InitializedValidator` and `ValidatorDefinition` `suggested_fee_recipient` values.
     ///
     /// ## Notes
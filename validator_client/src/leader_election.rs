@@ -0,0 +1,238 @@
+//! Election of a leader among validator clients which run the same validators, so that only one
+//! of them signs at a time.
+//!
+//! The election uses the sessions and locks of a [Consul](https://www.consul.io) agent. Each
+//! validator client holds a session, which it renews every `RENEW_INTERVAL`, and tries to acquire
+//! the lock on a key with its session. The validator client whose session holds the lock is the
+//! leader, and it only signs until `SESSION_TTL` after it last renewed its session. If the leader
+//! is unable to renew its session, Consul invalidates it no sooner than `SESSION_TTL` after it was
+//! last renewed, and the lock can't be acquired by another session until `LOCK_DELAY` after that.
+//! The leader therefore stops signing before any other validator client can be elected.
+//!
+//! A newly elected leader only signs from the epoch after it was elected, so that it doesn't sign
+//! conflicting messages for an epoch in which the previous leader may have signed.
+use crate::http_metrics::metrics;
+use environment::RuntimeContext;
+use parking_lot::Mutex;
+use reqwest::{Client, Response, StatusCode};
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{debug, info, warn};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use types::{Epoch, EthSpec};
+use url::Url;
+
+/// The default key of the lock which is held by the leader.
+pub const DEFAULT_LEADER_ELECTION_KEY: &str = "lighthouse/validator-client/leader";
+
+/// The TTL of each session, which is the longest the leader signs for without renewing it.
+const SESSION_TTL: Duration = Duration::from_secs(30);
+/// The time for which the lock can't be acquired after the session holding it is invalidated.
+const LOCK_DELAY: Duration = Duration::from_secs(30);
+const RENEW_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct CreateSessionRequest {
+    name: &'static str,
+    #[serde(rename = "TTL")]
+    ttl: String,
+    lock_delay: String,
+    behavior: &'static str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CreateSessionResponse {
+    #[serde(rename = "ID")]
+    id: String,
+}
+
+#[derive(Default)]
+struct Leadership {
+    /// The time until which the lock is held, if it was held at the last renewal.
+    until: Option<Instant>,
+    /// The first epoch in which the leader may sign.
+    signing_epoch: Epoch,
+}
+
+/// The state of this validator client in the election of a leader.
+pub struct LeaderElection {
+    client: Client,
+    url: SensitiveUrl,
+    key: String,
+    leadership: Mutex<Leadership>,
+}
+
+impl LeaderElection {
+    pub fn new(url: SensitiveUrl, key: String) -> Result<Self, String> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Unable to build leader election client: {e}"))?;
+        Ok(Self {
+            client,
+            url,
+            key,
+            leadership: Mutex::new(Leadership::default()),
+        })
+    }
+
+    /// Returns whether this validator client is the leader.
+    pub fn is_leader(&self) -> bool {
+        self.leadership
+            .lock()
+            .until
+            .map_or(false, |until| Instant::now() < until)
+    }
+
+    /// Returns whether this validator client is the leader and may sign in `epoch`.
+    pub fn allows_signing(&self, epoch: Epoch) -> bool {
+        let leadership = self.leadership.lock();
+        leadership
+            .until
+            .map_or(false, |until| Instant::now() < until)
+            && epoch >= leadership.signing_epoch
+    }
+
+    fn endpoint(&self, path: &str) -> Result<Url, String> {
+        self.url
+            .full
+            .join(path)
+            .map_err(|e| format!("Invalid leader election URL: {e}"))
+    }
+
+    async fn create_session(&self) -> Result<String, String> {
+        let request = CreateSessionRequest {
+            name: "lighthouse-validator-client",
+            ttl: format!("{}s", SESSION_TTL.as_secs()),
+            lock_delay: format!("{}s", LOCK_DELAY.as_secs()),
+            behavior: "release",
+        };
+        let response: CreateSessionResponse = self
+            .client
+            .put(self.endpoint("v1/session/create")?)
+            .json(&request)
+            .send()
+            .await
+            .and_then(Response::error_for_status)
+            .map_err(|e| format!("Unable to create session: {e}"))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid session: {e}"))?;
+        Ok(response.id)
+    }
+
+    /// Renew `session`, returning `false` if it has been invalidated.
+    async fn renew_session(&self, session: &str) -> Result<bool, String> {
+        let response = self
+            .client
+            .put(self.endpoint(&format!("v1/session/renew/{session}"))?)
+            .send()
+            .await
+            .map_err(|e| format!("Unable to renew session: {e}"))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        response
+            .error_for_status()
+            .map_err(|e| format!("Unable to renew session: {e}"))?;
+        Ok(true)
+    }
+
+    /// Try to acquire the lock with `session`, returning whether it is held by `session`.
+    async fn acquire_lock(&self, session: &str) -> Result<bool, String> {
+        let mut url = self.endpoint(&format!("v1/kv/{}", self.key))?;
+        url.query_pairs_mut().append_pair("acquire", session);
+        self.client
+            .put(url)
+            .body(lighthouse_version::VERSION)
+            .send()
+            .await
+            .and_then(Response::error_for_status)
+            .map_err(|e| format!("Unable to acquire lock: {e}"))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid lock response: {e}"))
+    }
+
+    /// Renew or replace `session`, and try to acquire the lock with it.
+    async fn update(&self, session: &mut Option<String>) -> Result<bool, String> {
+        if let Some(id) = session.as_deref() {
+            if !self.renew_session(id).await? {
+                *session = None;
+            }
+        }
+        let id = match session.take() {
+            Some(id) => id,
+            None => self.create_session().await?,
+        };
+        self.acquire_lock(session.insert(id)).await
+    }
+}
+
+/// Starts a service which takes part in the election of a leader every `RENEW_INTERVAL`.
+pub fn start_leader_election_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    leader_election: Arc<LeaderElection>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        let mut session = None;
+        loop {
+            let was_leader = leader_election.is_leader();
+            // The session can't be invalidated until `SESSION_TTL` after it was renewed, which is
+            // after this instant.
+            let renewed_at = Instant::now();
+            match leader_election.update(&mut session).await {
+                Ok(true) => {
+                    let mut leadership = leader_election.leadership.lock();
+                    leadership.until = Some(renewed_at + SESSION_TTL);
+                    if !was_leader {
+                        let current_epoch = slot_clock
+                            .now()
+                            .map_or(Epoch::new(0), |slot| slot.epoch(E::slots_per_epoch()));
+                        leadership.signing_epoch = current_epoch + 1;
+                        info!(
+                            log,
+                            "Elected as leader";
+                            "signing_epoch" => leadership.signing_epoch,
+                            "key" => &leader_election.key,
+                        );
+                    }
+                }
+                Ok(false) => {
+                    leader_election.leadership.lock().until = None;
+                    if was_leader {
+                        warn!(
+                            log,
+                            "No longer the leader, signing disabled";
+                            "key" => &leader_election.key,
+                        );
+                    } else {
+                        debug!(log, "Another validator client is the leader");
+                    }
+                }
+                Err(e) => warn!(
+                    log,
+                    "Unable to take part in leader election";
+                    "error" => e,
+                    "leader" => leader_election.is_leader(),
+                ),
+            }
+            metrics::set_gauge(
+                &metrics::LEADER_ELECTION_LEADER,
+                leader_election.is_leader() as i64,
+            );
+
+            sleep(RENEW_INTERVAL).await;
+        }
+    };
+    context.executor.spawn(future, "leader_election");
+}
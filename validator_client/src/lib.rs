@@ -3,15 +3,20 @@ mod beacon_node_fallback;
 mod block_service;
 mod check_synced;
 mod cli;
+mod clock_monitor;
 mod duties_service;
+mod exit_service;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
 mod latency;
+mod leader_election;
 mod notifier;
 mod preparation_service;
+mod presigned_exits;
 mod signing_method;
 mod sync_committee_service;
+mod web3signer_client;
 
 pub mod config;
 mod doppelganger_service;
@@ -21,7 +26,7 @@ pub mod validator_store;
 
 pub use beacon_node_fallback::ApiTopic;
 pub use cli::cli_app;
-pub use config::Config;
+pub use config::{Config, Web3SignerSlashingProtection};
 use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
@@ -39,13 +44,17 @@ use account_utils::validator_definitions::ValidatorDefinitions;
 use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
+use clock_monitor::ClockOffset;
 use duties_service::{sync::SyncDutiesMap, DutiesService};
 use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, types::Graffiti, BeaconNodeHttpClient, StatusCode, Timeouts};
+use exit_service::{ExitScheduler, ExitService, ExitServiceBuilder};
 use http_api::ApiSecret;
+use leader_election::LeaderElection;
 use notifier::spawn_notifier;
 use parking_lot::RwLock;
 use preparation_service::{PreparationService, PreparationServiceBuilder};
+use presigned_exits::{PresignedExitService, PresignedExitServiceBuilder, PresignedExitStore};
 use reqwest::Certificate;
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -96,12 +105,16 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     attestation_service: AttestationService<SystemTimeSlotClock, E>,
     sync_committee_service: SyncCommitteeService<SystemTimeSlotClock, E>,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    leader_election: Option<Arc<LeaderElection>>,
     preparation_service: PreparationService<SystemTimeSlotClock, E>,
+    exit_service: ExitService<SystemTimeSlotClock, E>,
+    presigned_exit_service: Option<PresignedExitService<SystemTimeSlotClock, E>>,
     validator_store: Arc<ValidatorStore<SystemTimeSlotClock, E>>,
     slot_clock: SystemTimeSlotClock,
     http_api_listen_addr: Option<SocketAddr>,
     config: Config,
     beacon_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, E>>,
+    clock_offset: Arc<ClockOffset>,
     genesis_time: u64,
 }
 
@@ -288,6 +301,15 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 })?;
         }
 
+        if config.web3signer_slashing_protection == Web3SignerSlashingProtection::Remote {
+            web3signer_client::check_remote_slashing_protection(
+                &validators,
+                &slashing_protection,
+                &log,
+            )
+            .await?;
+        }
+
         let last_beacon_node_index = config
             .beacon_nodes
             .len()
@@ -309,6 +331,12 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 }
             }
 
+            // Authorize requests if the beacon nodes require an API token.
+            if let Some(token_file) = &config.beacon_nodes_token_file {
+                beacon_node_http_client_builder = beacon_node_http_client_builder
+                    .default_headers(eth2::api_token_headers(token_file)?);
+            }
+
             let beacon_node_http_client = beacon_node_http_client_builder
                 // Set default timeout to be the full slot duration.
                 .timeout(slot_duration)
@@ -443,12 +471,19 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        let leader_election = config
+            .leader_election_url
+            .clone()
+            .map(|url| LeaderElection::new(url, config.leader_election_key.clone()).map(Arc::new))
+            .transpose()?;
+
         let validator_store = Arc::new(ValidatorStore::new(
             validators,
             slashing_protection,
             genesis_validators_root,
             context.eth2_config.spec.clone(),
             doppelganger_service.clone(),
+            leader_election.clone(),
             slot_clock.clone(),
             &config,
             context.executor.clone(),
@@ -507,11 +542,14 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         let block_service = block_service_builder.build()?;
 
+        let clock_offset = Arc::new(ClockOffset::default());
+
         let attestation_service = AttestationServiceBuilder::new()
             .duties_service(duties_service.clone())
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
+            .clock_offset(clock_offset.clone())
             .runtime_context(context.service_context("attestation".into()))
             .build()?;
 
@@ -524,11 +562,49 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .validator_registration_batch_size(config.validator_registration_batch_size)
             .build()?;
 
+        let exit_scheduler = Arc::new(ExitScheduler::open_or_create(
+            &config.validator_dir,
+            context.eth2_config.spec.clone(),
+        )?);
+        let exit_service = ExitServiceBuilder::new()
+            .slot_clock(slot_clock.clone())
+            .validator_store(validator_store.clone())
+            .beacon_nodes(beacon_nodes.clone())
+            .exit_scheduler(exit_scheduler)
+            .runtime_context(context.service_context("exit".into()))
+            .build()?;
+
+        let presigned_exit_service = config
+            .presign_exits_password_file
+            .as_ref()
+            .map(|password_file| {
+                let password = account_utils::read_password(password_file).map_err(|e| {
+                    format!(
+                        "Unable to read pre-signed exits password file {:?}: {:?}",
+                        password_file, e
+                    )
+                })?;
+                let presigned_exit_store = PresignedExitStore::open_or_create(
+                    &config.validator_dir,
+                    password,
+                    config.exit_dead_mans_switch_epochs,
+                )?;
+                PresignedExitServiceBuilder::new()
+                    .slot_clock(slot_clock.clone())
+                    .validator_store(validator_store.clone())
+                    .beacon_nodes(beacon_nodes.clone())
+                    .presigned_exit_store(Arc::new(presigned_exit_store))
+                    .runtime_context(context.service_context("presigned_exit".into()))
+                    .build()
+            })
+            .transpose()?;
+
         let sync_committee_service = SyncCommitteeService::new(
             duties_service.clone(),
             validator_store.clone(),
             slot_clock.clone(),
             beacon_nodes.clone(),
+            clock_offset.clone(),
             context.service_context("sync_committee".into()),
         );
 
@@ -539,13 +615,17 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             attestation_service,
             sync_committee_service,
             doppelganger_service,
+            leader_election,
             preparation_service,
+            exit_service,
+            presigned_exit_service,
             validator_store,
             config,
             slot_clock,
             http_api_listen_addr: None,
             genesis_time,
             beacon_nodes,
+            clock_offset,
         })
     }
 
@@ -564,6 +644,12 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                exit_scheduler: Some(self.exit_service.exit_scheduler.clone()),
+                presigned_exit_store: self
+                    .presigned_exit_service
+                    .as_ref()
+                    .map(|service| service.presigned_exit_store.clone()),
+                duties_service: Some(self.duties_service.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -592,6 +678,14 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        if let Some(leader_election) = self.leader_election.clone() {
+            leader_election::start_leader_election_service(
+                self.context.service_context("leader_election".into()),
+                self.slot_clock.clone(),
+                leader_election,
+            );
+        }
+
         // Wait until genesis has occurred.
         wait_for_genesis(&self.beacon_nodes, self.genesis_time, &self.context).await?;
 
@@ -617,6 +711,17 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .start_update_service(&self.context.eth2_config.spec)
             .map_err(|e| format!("Unable to start preparation service: {}", e))?;
 
+        self.exit_service
+            .clone()
+            .start_update_service(&self.context.eth2_config.spec)
+            .map_err(|e| format!("Unable to start exit service: {}", e))?;
+
+        if let Some(presigned_exit_service) = self.presigned_exit_service.clone() {
+            presigned_exit_service
+                .start_update_service(&self.context.eth2_config.spec)
+                .map_err(|e| format!("Unable to start pre-signed exit service: {}", e))?;
+        }
+
         if let Some(doppelganger_service) = self.doppelganger_service.clone() {
             DoppelgangerService::start_update_service(
                 doppelganger_service,
@@ -641,6 +746,23 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             );
         }
 
+        if let Some(ntp_server) = &self.config.ntp_server {
+            clock_monitor::start_clock_monitor(
+                self.context.service_context("clock_monitor".into()),
+                self.slot_clock.clone(),
+                ntp_server.clone(),
+                self.clock_offset.clone(),
+            );
+        }
+
+        if let Some(interval) = self.config.web3_signer_health_check_interval {
+            web3signer_client::start_web3signer_monitor(
+                self.context.service_context("web3signer_monitor".into()),
+                self.validator_store.clone(),
+                interval,
+            );
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,710 @@
+//! Pre-signs a voluntary exit for each validator and broadcasts them on request, or when a dead
+//! man's switch expires.
+//!
+//! The exits are stored in the validator directory, encrypted with a password supplied by the
+//! user. Since the Deneb fork, voluntary exits never expire (EIP-7044), so the stored exits allow
+//! the validators to be exited even if their keys later become unreachable.
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::validator_store::ValidatorStore;
+use account_utils::write_file_via_temporary;
+use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::PresignedExits;
+use eth2::types::{StateId, ValidatorId};
+use eth2_keystore::json_keystore::{
+    Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, KdfModule,
+    Sha256Checksum,
+};
+use eth2_keystore::{decrypt, default_kdf, encrypt, PlainText, IV_SIZE, SALT_SIZE};
+use parking_lot::RwLock;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use slog::{error, info, warn};
+use slot_clock::SlotClock;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use types::{ChainSpec, Epoch, EthSpec, PublicKeyBytes, SignedVoluntaryExit, VoluntaryExit};
+
+/// The file name for the pre-signed exits.
+pub const PRESIGNED_EXITS_FILENAME: &str = "presigned_exits.json";
+
+/// The file name for the temporary pre-signed exits.
+pub const TEMP_PRESIGNED_EXITS_FILENAME: &str = ".presigned_exits.json.tmp";
+
+/// A voluntary exit signed by the validator with `pubkey`.
+#[derive(Clone, Serialize, Deserialize)]
+struct PresignedExit {
+    pubkey: PublicKeyBytes,
+    exit: SignedVoluntaryExit,
+}
+
+/// The on-disk representation of the store.
+///
+/// Only the exits are encrypted, so that the dead man's switch can be reset without deriving the
+/// encryption key. `crypto` is `None` until the first exit is signed.
+#[derive(Serialize, Deserialize)]
+struct PresignedExitsFile {
+    crypto: Option<Crypto>,
+    heartbeat_epoch: Option<Epoch>,
+    broadcast_requested: bool,
+    broadcast_epoch: Option<Epoch>,
+    /// The indices of the validators whose exits have been broadcast, or which have already
+    /// exited, during the current broadcast.
+    #[serde(default)]
+    broadcast_validators: BTreeSet<u64>,
+}
+
+struct State {
+    exits: Vec<PresignedExit>,
+    file: PresignedExitsFile,
+}
+
+/// Stores the pre-signed voluntary exits, encrypted, in the validator directory.
+pub struct PresignedExitStore {
+    path: PathBuf,
+    temp_path: PathBuf,
+    password: PlainText,
+    dead_mans_switch_epochs: Option<u64>,
+    state: RwLock<State>,
+}
+
+impl PresignedExitStore {
+    /// Opens the store in `validator_dir`, or creates an empty one if it does not exist.
+    ///
+    /// Returns an error if the existing exits cannot be decrypted with `password`.
+    pub fn open_or_create(
+        validator_dir: &Path,
+        password: PlainText,
+        dead_mans_switch_epochs: Option<u64>,
+    ) -> Result<Self, String> {
+        let path = validator_dir.join(PRESIGNED_EXITS_FILENAME);
+        let temp_path = validator_dir.join(TEMP_PRESIGNED_EXITS_FILENAME);
+
+        let state = if path.exists() {
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Unable to read pre-signed exits {:?}: {:?}", path, e))?;
+            let file: PresignedExitsFile = serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Unable to parse pre-signed exits {:?}: {:?}", path, e))?;
+            let exits = match &file.crypto {
+                Some(crypto) => {
+                    let plain_text = decrypt(password.as_bytes(), crypto).map_err(|e| {
+                        format!("Unable to decrypt pre-signed exits {:?}: {:?}", path, e)
+                    })?;
+                    serde_json::from_slice(plain_text.as_bytes()).map_err(|e| {
+                        format!("Unable to parse pre-signed exits {:?}: {:?}", path, e)
+                    })?
+                }
+                None => vec![],
+            };
+            State { exits, file }
+        } else {
+            State {
+                exits: vec![],
+                file: PresignedExitsFile {
+                    crypto: None,
+                    heartbeat_epoch: None,
+                    broadcast_requested: false,
+                    broadcast_epoch: None,
+                    broadcast_validators: BTreeSet::new(),
+                },
+            }
+        };
+
+        Ok(Self {
+            path,
+            temp_path,
+            password,
+            dead_mans_switch_epochs,
+            state: RwLock::new(state),
+        })
+    }
+
+    pub fn presigned_exits(&self) -> PresignedExits {
+        let state = self.state.read();
+        PresignedExits {
+            exits: state.exits.iter().map(|exit| exit.exit.clone()).collect(),
+            heartbeat_epoch: state.file.heartbeat_epoch,
+            deadline_epoch: self.deadline_epoch(&state),
+            broadcast_requested: state.file.broadcast_requested,
+            broadcast_epoch: state.file.broadcast_epoch,
+        }
+    }
+
+    /// Returns the public keys of the validators which have a pre-signed exit.
+    pub fn pubkeys(&self) -> HashSet<PublicKeyBytes> {
+        self.state
+            .read()
+            .exits
+            .iter()
+            .map(|exit| exit.pubkey)
+            .collect()
+    }
+
+    /// Adds `exits` to the store.
+    ///
+    /// The key derivation used to encrypt the exits is slow, so this should be called from a
+    /// blocking task. The lock is not held during encryption, and an error is returned if the
+    /// exits are modified concurrently.
+    pub fn insert(&self, exits: Vec<(PublicKeyBytes, SignedVoluntaryExit)>) -> Result<(), String> {
+        let (num_exits, mut all_exits) = {
+            let state = self.state.read();
+            (state.exits.len(), state.exits.clone())
+        };
+        all_exits.extend(
+            exits
+                .into_iter()
+                .map(|(pubkey, exit)| PresignedExit { pubkey, exit }),
+        );
+        let crypto = encrypt_exits(&all_exits, &self.password)?;
+
+        let mut state = self.state.write();
+        if state.exits.len() != num_exits {
+            return Err("Pre-signed exits were modified concurrently".to_string());
+        }
+        state.exits = all_exits;
+        state.file.crypto = Some(crypto);
+        self.save(&state)
+    }
+
+    /// Resets the dead man's switch, delaying the broadcast of the exits until the configured
+    /// number of epochs after `epoch`.
+    pub fn heartbeat(&self, epoch: Epoch) -> Result<PresignedExits, String> {
+        self.update(|state| state.file.heartbeat_epoch = Some(epoch))?;
+        Ok(self.presigned_exits())
+    }
+
+    /// Requests that the exits are broadcast at the next slot.
+    pub fn request_broadcast(&self) -> Result<PresignedExits, String> {
+        self.update(|state| state.file.broadcast_requested = true)?;
+        Ok(self.presigned_exits())
+    }
+
+    /// Returns the exits which remain to be broadcast if they should be broadcast at `epoch`,
+    /// either because a broadcast was requested or because the dead man's switch has expired.
+    fn exits_to_broadcast(&self, epoch: Epoch) -> Option<Vec<SignedVoluntaryExit>> {
+        let state = self.state.read();
+        let deadline_passed = self
+            .deadline_epoch(&state)
+            .map_or(false, |deadline_epoch| epoch >= deadline_epoch);
+        if state.exits.is_empty()
+            || state.file.broadcast_epoch.is_some()
+            || !(state.file.broadcast_requested || deadline_passed)
+        {
+            return None;
+        }
+        Some(
+            state
+                .exits
+                .iter()
+                .filter(|exit| {
+                    !state
+                        .file
+                        .broadcast_validators
+                        .contains(&exit.exit.message.validator_index)
+                })
+                .map(|exit| exit.exit.clone())
+                .collect(),
+        )
+    }
+
+    /// Records that the exits of `validator_indices` were broadcast at `epoch`, completing the
+    /// broadcast once every exit has been broadcast.
+    fn record_broadcast(&self, validator_indices: &[u64], epoch: Epoch) -> Result<(), String> {
+        self.update(|state| {
+            state
+                .file
+                .broadcast_validators
+                .extend(validator_indices.iter().copied());
+            if state.exits.iter().all(|exit| {
+                state
+                    .file
+                    .broadcast_validators
+                    .contains(&exit.exit.message.validator_index)
+            }) {
+                state.file.broadcast_requested = false;
+                state.file.broadcast_epoch = Some(epoch);
+            }
+        })
+    }
+
+    fn deadline_epoch(&self, state: &State) -> Option<Epoch> {
+        Some(state.file.heartbeat_epoch? + self.dead_mans_switch_epochs?)
+    }
+
+    fn update<F: FnOnce(&mut State)>(&self, f: F) -> Result<(), String> {
+        let mut state = self.state.write();
+        f(&mut state);
+        self.save(&state)
+    }
+
+    fn save(&self, state: &State) -> Result<(), String> {
+        let bytes = serde_json::to_vec(&state.file)
+            .map_err(|e| format!("Unable to encode pre-signed exits: {:?}", e))?;
+        write_file_via_temporary(&self.path, &self.temp_path, &bytes)
+            .map_err(|e| format!("Unable to write pre-signed exits {:?}: {:?}", self.path, e))
+    }
+}
+
+/// Encrypts `exits` with `password`, using a new salt and IV.
+fn encrypt_exits(exits: &[PresignedExit], password: &PlainText) -> Result<Crypto, String> {
+    let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+    let iv = rand::thread_rng().gen::<[u8; IV_SIZE]>().to_vec().into();
+    let kdf = default_kdf(salt.to_vec());
+    let cipher = Cipher::Aes128Ctr(Aes128Ctr { iv });
+
+    let plain_text = PlainText::from(
+        serde_json::to_vec(exits)
+            .map_err(|e| format!("Unable to encode pre-signed exits: {:?}", e))?,
+    );
+    let (cipher_text, checksum) =
+        encrypt(plain_text.as_bytes(), password.as_bytes(), &kdf, &cipher)
+            .map_err(|e| format!("Unable to encrypt pre-signed exits: {:?}", e))?;
+
+    Ok(Crypto {
+        kdf: KdfModule {
+            function: kdf.function(),
+            params: kdf,
+            message: EmptyString,
+        },
+        checksum: ChecksumModule {
+            function: Sha256Checksum::function(),
+            params: EmptyMap,
+            message: checksum.to_vec().into(),
+        },
+        cipher: CipherModule {
+            function: cipher.function(),
+            params: cipher,
+            message: cipher_text.into(),
+        },
+    })
+}
+
+/// Builds a `PresignedExitService`.
+pub struct PresignedExitServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
+    validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    slot_clock: Option<T>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    presigned_exit_store: Option<Arc<PresignedExitStore>>,
+    context: Option<RuntimeContext<E>>,
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> PresignedExitServiceBuilder<T, E> {
+    pub fn new() -> Self {
+        Self {
+            validator_store: None,
+            slot_clock: None,
+            beacon_nodes: None,
+            presigned_exit_store: None,
+            context: None,
+        }
+    }
+
+    pub fn validator_store(mut self, store: Arc<ValidatorStore<T, E>>) -> Self {
+        self.validator_store = Some(store);
+        self
+    }
+
+    pub fn slot_clock(mut self, slot_clock: T) -> Self {
+        self.slot_clock = Some(slot_clock);
+        self
+    }
+
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
+        self
+    }
+
+    pub fn presigned_exit_store(mut self, presigned_exit_store: Arc<PresignedExitStore>) -> Self {
+        self.presigned_exit_store = Some(presigned_exit_store);
+        self
+    }
+
+    pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    pub fn build(self) -> Result<PresignedExitService<T, E>, String> {
+        Ok(PresignedExitService {
+            inner: Arc::new(Inner {
+                validator_store: self
+                    .validator_store
+                    .ok_or("Cannot build PresignedExitService without validator_store")?,
+                slot_clock: self
+                    .slot_clock
+                    .ok_or("Cannot build PresignedExitService without slot_clock")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or("Cannot build PresignedExitService without beacon_nodes")?,
+                presigned_exit_store: self
+                    .presigned_exit_store
+                    .ok_or("Cannot build PresignedExitService without presigned_exit_store")?,
+                context: self
+                    .context
+                    .ok_or("Cannot build PresignedExitService without runtime_context")?,
+            }),
+        })
+    }
+}
+
+/// Helper to minimise `Arc` usage.
+pub struct Inner<T, E: EthSpec> {
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    pub presigned_exit_store: Arc<PresignedExitStore>,
+    context: RuntimeContext<E>,
+}
+
+/// Pre-signs exits for new validators and broadcasts the stored exits when triggered, checking
+/// once per slot.
+pub struct PresignedExitService<T, E: EthSpec> {
+    inner: Arc<Inner<T, E>>,
+}
+
+impl<T, E: EthSpec> Clone for PresignedExitService<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, E: EthSpec> Deref for PresignedExitService<T, E> {
+    type Target = Inner<T, E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> PresignedExitService<T, E> {
+    pub fn start_update_service(self, spec: &ChainSpec) -> Result<(), String> {
+        let log = self.context.log().clone();
+        let slot_duration = Duration::from_secs(spec.seconds_per_slot);
+
+        info!(
+            log,
+            "Pre-signed exit service started";
+            "deadline_epoch" => ?self.presigned_exit_store.presigned_exits().deadline_epoch,
+        );
+
+        let executor = self.context.executor.clone();
+
+        let interval_fut = async move {
+            loop {
+                if let Err(e) = self.presign_exits().await {
+                    error!(
+                        log,
+                        "Error during exit pre-signing";
+                        "error" => e,
+                    );
+                }
+                if let Err(e) = self.broadcast_exits_if_triggered().await {
+                    error!(
+                        log,
+                        "Error during pre-signed exit broadcast";
+                        "error" => e,
+                    );
+                }
+
+                if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
+                    sleep(duration_to_next_slot).await;
+                } else {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_duration).await;
+                }
+            }
+        };
+
+        executor.spawn(interval_fut, "presigned_exit_service");
+        Ok(())
+    }
+
+    fn current_epoch(&self) -> Result<Epoch, String> {
+        self.slot_clock
+            .now()
+            .map(|slot| slot.epoch(E::slots_per_epoch()))
+            .ok_or_else(|| "Unable to read slot clock".to_string())
+    }
+
+    /// Signs an exit for each validator which has a known index and no pre-signed exit.
+    async fn presign_exits(&self) -> Result<(), String> {
+        let log = self.context.log();
+        let epoch = self.current_epoch()?;
+
+        let presigned_pubkeys = self.presigned_exit_store.pubkeys();
+        let pubkeys: Vec<PublicKeyBytes> = self
+            .validator_store
+            .voting_pubkeys(DoppelgangerStatus::ignored);
+
+        let mut exits = vec![];
+        for pubkey in pubkeys
+            .into_iter()
+            .filter(|pubkey| !presigned_pubkeys.contains(pubkey))
+        {
+            // The index is unknown until the deposit of the validator has been processed.
+            let Some(validator_index) = self.validator_store.validator_index(&pubkey) else {
+                continue;
+            };
+            match self
+                .validator_store
+                .sign_voluntary_exit(
+                    pubkey,
+                    VoluntaryExit {
+                        epoch,
+                        validator_index,
+                    },
+                )
+                .await
+            {
+                Ok(exit) => exits.push((pubkey, exit)),
+                Err(e) => warn!(
+                    log,
+                    "Unable to pre-sign voluntary exit";
+                    "validator" => pubkey.as_hex_string(),
+                    "error" => ?e,
+                ),
+            }
+        }
+
+        if !exits.is_empty() {
+            let count = exits.len();
+            let store = self.presigned_exit_store.clone();
+            self.context
+                .executor
+                .spawn_blocking_handle(move || store.insert(exits), "presigned_exits_insert")
+                .ok_or("Shutting down")?
+                .await
+                .map_err(|e| format!("Pre-signed exit encryption failed: {:?}", e))??;
+            info!(
+                log,
+                "Pre-signed voluntary exits";
+                "count" => count,
+            );
+        }
+
+        // Start the dead man's switch from the first epoch the service runs.
+        if self
+            .presigned_exit_store
+            .presigned_exits()
+            .heartbeat_epoch
+            .is_none()
+        {
+            self.presigned_exit_store.heartbeat(epoch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Broadcasts the pre-signed exits if a broadcast was requested or the dead man's switch has
+    /// expired.
+    ///
+    /// Exits which fail to broadcast are retried at the next slot, unless the validator has
+    /// already exited.
+    async fn broadcast_exits_if_triggered(&self) -> Result<(), String> {
+        let log = self.context.log();
+        let epoch = self.current_epoch()?;
+        let Some(exits) = self.presigned_exit_store.exits_to_broadcast(epoch) else {
+            return Ok(());
+        };
+
+        warn!(
+            log,
+            "Broadcasting pre-signed voluntary exits";
+            "count" => exits.len(),
+        );
+
+        let mut published = vec![];
+        let mut failed = vec![];
+        for exit in &exits {
+            let validator_index = exit.message.validator_index;
+            match self
+                .beacon_nodes
+                .first_success(
+                    RequireSynced::No,
+                    OfflineOnFailure::Yes,
+                    |beacon_node| async move {
+                        beacon_node.post_beacon_pool_voluntary_exits(exit).await
+                    },
+                )
+                .await
+            {
+                Ok(()) => published.push(validator_index),
+                Err(e) => {
+                    error!(
+                        log,
+                        "Failed to broadcast pre-signed voluntary exit";
+                        "validator_index" => validator_index,
+                        "error" => %e,
+                    );
+                    failed.push(validator_index);
+                }
+            }
+        }
+
+        // The beacon node rejects the exits of validators which have already exited, which don't
+        // need to be retried.
+        let exited = if failed.is_empty() {
+            vec![]
+        } else {
+            self.exited_validators(&failed).await.unwrap_or_else(|e| {
+                warn!(
+                    log,
+                    "Unable to check the status of validators";
+                    "error" => e,
+                );
+                vec![]
+            })
+        };
+
+        self.presigned_exit_store
+            .record_broadcast(&[published.as_slice(), exited.as_slice()].concat(), epoch)?;
+        info!(
+            log,
+            "Broadcast pre-signed voluntary exits";
+            "published" => published.len(),
+            "already_exited" => exited.len(),
+            "retrying" => failed.len() - exited.len(),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the indices of the `validator_indices` which have initiated an exit.
+    async fn exited_validators(&self, validator_indices: &[u64]) -> Result<Vec<u64>, String> {
+        let far_future_epoch = self.context.eth2_config.spec.far_future_epoch;
+        let ids = validator_indices
+            .iter()
+            .copied()
+            .map(ValidatorId::Index)
+            .collect::<Vec<_>>();
+        let ids = &ids;
+
+        let validators = self
+            .beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_states_validators(StateId::Head, Some(ids), None)
+                        .await
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|response| response.data)
+            .unwrap_or_default();
+
+        Ok(validators
+            .into_iter()
+            .filter(|validator| validator.validator.exit_epoch != far_future_epoch)
+            .map(|validator| validator.index)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use types::Signature;
+
+    fn exit(validator_index: u64) -> (PublicKeyBytes, SignedVoluntaryExit) {
+        (
+            PublicKeyBytes::empty(),
+            SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch: Epoch::new(1),
+                    validator_index,
+                },
+                signature: Signature::empty(),
+            },
+        )
+    }
+
+    #[test]
+    fn exits_are_encrypted_and_persisted() {
+        let dir = tempdir().unwrap();
+        let password = || PlainText::from(b"password".to_vec());
+
+        let store = PresignedExitStore::open_or_create(dir.path(), password(), Some(2)).unwrap();
+        store.insert(vec![exit(1), exit(2)]).unwrap();
+        store.heartbeat(Epoch::new(10)).unwrap();
+
+        let bytes = fs::read(dir.path().join(PRESIGNED_EXITS_FILENAME)).unwrap();
+        assert!(!String::from_utf8(bytes)
+            .unwrap()
+            .contains("validator_index"));
+
+        let store = PresignedExitStore::open_or_create(dir.path(), password(), Some(2)).unwrap();
+        let presigned_exits = store.presigned_exits();
+        assert_eq!(presigned_exits.exits, vec![exit(1).1, exit(2).1]);
+        assert_eq!(presigned_exits.deadline_epoch, Some(Epoch::new(12)));
+
+        assert!(PresignedExitStore::open_or_create(
+            dir.path(),
+            PlainText::from(b"wrong".to_vec()),
+            Some(2)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn dead_mans_switch() {
+        let dir = tempdir().unwrap();
+        let store = PresignedExitStore::open_or_create(
+            dir.path(),
+            PlainText::from(b"password".to_vec()),
+            Some(2),
+        )
+        .unwrap();
+        store.insert(vec![exit(1)]).unwrap();
+
+        // The switch does not run until it has been reset once.
+        assert!(store.exits_to_broadcast(Epoch::new(100)).is_none());
+
+        store.heartbeat(Epoch::new(10)).unwrap();
+        assert!(store.exits_to_broadcast(Epoch::new(11)).is_none());
+        assert_eq!(
+            store.exits_to_broadcast(Epoch::new(12)),
+            Some(vec![exit(1).1])
+        );
+
+        store.heartbeat(Epoch::new(12)).unwrap();
+        assert!(store.exits_to_broadcast(Epoch::new(12)).is_none());
+
+        store.request_broadcast().unwrap();
+        assert!(store.exits_to_broadcast(Epoch::new(12)).is_some());
+    }
+
+    #[test]
+    fn failed_exits_are_retried() {
+        let dir = tempdir().unwrap();
+        let store = PresignedExitStore::open_or_create(
+            dir.path(),
+            PlainText::from(b"password".to_vec()),
+            None,
+        )
+        .unwrap();
+        store.insert(vec![exit(1), exit(2)]).unwrap();
+        store.request_broadcast().unwrap();
+
+        store.record_broadcast(&[1], Epoch::new(5)).unwrap();
+        assert_eq!(store.presigned_exits().broadcast_epoch, None);
+        assert_eq!(
+            store.exits_to_broadcast(Epoch::new(5)),
+            Some(vec![exit(2).1])
+        );
+
+        store.record_broadcast(&[2], Epoch::new(6)).unwrap();
+        assert_eq!(store.presigned_exits().broadcast_epoch, Some(Epoch::new(6)));
+        assert!(store.exits_to_broadcast(Epoch::new(6)).is_none());
+    }
+}
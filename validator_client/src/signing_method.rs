@@ -4,10 +4,11 @@
 //! - Via a remote signer (Web3Signer)
 
 use crate::http_metrics::metrics;
+use crate::web3signer_client::Web3SignerClient;
 use eth2_keystore::Keystore;
 use lockfile::Lockfile;
 use parking_lot::Mutex;
-use reqwest::{header::ACCEPT, Client};
+use reqwest::header::ACCEPT;
 use std::path::PathBuf;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
@@ -26,13 +27,44 @@ pub enum Error {
         domain: Domain,
     },
     Web3SignerRequestFailed(String),
+    Web3SignerRequestTimeout(String),
+    Web3SignerErrorStatus(String),
     Web3SignerJsonParsingFailed(String),
+    Web3SignerInvalidServerIdentity(String),
     ShuttingDown,
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
 }
 
+impl Error {
+    /// Returns a label describing the class of `self`, for use in metrics.
+    pub fn metrics_label(&self) -> &'static str {
+        match self {
+            Error::InconsistentDomains { .. } => "inconsistent_domains",
+            Error::Web3SignerRequestFailed(_) => "request_failed",
+            Error::Web3SignerRequestTimeout(_) => "timeout",
+            Error::Web3SignerErrorStatus(_) => "error_status",
+            Error::Web3SignerJsonParsingFailed(_) => "invalid_response",
+            Error::Web3SignerInvalidServerIdentity(_) => "invalid_server_identity",
+            Error::ShuttingDown => "shutting_down",
+            Error::TokioJoin(_) => "task_failed",
+            Error::MergeForkNotSupported | Error::GenesisForkVersionRequired => "unsupported",
+        }
+    }
+
+    /// Maps a failed Web3Signer HTTP request into an `Error`.
+    fn from_web3signer_request(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Error::Web3SignerRequestTimeout(e.to_string())
+        } else if e.is_status() {
+            Error::Web3SignerErrorStatus(e.to_string())
+        } else {
+            Error::Web3SignerRequestFailed(e.to_string())
+        }
+    }
+}
+
 /// Enumerates all messages that can be signed by a validator.
 pub enum SignableMessage<'a, E: EthSpec, Payload: AbstractExecPayload<E> = FullPayload<E>> {
     RandaoReveal(Epoch),
@@ -71,6 +103,48 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> SignableMessage<'a, E, Pay
             SignableMessage::VoluntaryExit(exit) => exit.signing_root(domain),
         }
     }
+
+    /// Returns a label describing the type of `self`, for use in metrics.
+    pub fn metrics_label(&self) -> &'static str {
+        match self {
+            SignableMessage::RandaoReveal(_) => "randao_reveal",
+            SignableMessage::BeaconBlock(_) => "beacon_block",
+            SignableMessage::AttestationData(_) => "attestation",
+            SignableMessage::SignedAggregateAndProof(_) => "aggregate_and_proof",
+            SignableMessage::SelectionProof(_) => "selection_proof",
+            SignableMessage::SyncSelectionProof(_) => "sync_selection_proof",
+            SignableMessage::SyncCommitteeSignature { .. } => "sync_committee_message",
+            SignableMessage::SignedContributionAndProof(_) => "contribution_and_proof",
+            SignableMessage::ValidatorRegistration(_) => "validator_registration",
+            SignableMessage::VoluntaryExit(_) => "voluntary_exit",
+        }
+    }
+
+    /// Returns the slot of the duty that `self` is produced for, along with the deadline for
+    /// producing `self` in thirds of a slot after the start of that slot.
+    ///
+    /// The deadline is the point at which the message stops being useful to the network:
+    ///
+    /// - Blocks must be published before attesters vote at 1/3 of the slot.
+    /// - Attestations, sync committee messages and selection proofs must be produced before
+    ///   aggregation begins at 2/3 of the slot.
+    /// - Aggregates and contributions must be published before the end of the slot.
+    ///
+    /// Returns `None` for messages which are not produced for a particular slot.
+    pub fn duty_deadline(&self) -> Option<(Slot, u32)> {
+        match self {
+            SignableMessage::BeaconBlock(b) => Some((b.slot(), 1)),
+            SignableMessage::AttestationData(a) => Some((a.slot, 2)),
+            SignableMessage::SelectionProof(slot) => Some((*slot, 2)),
+            SignableMessage::SyncSelectionProof(s) => Some((s.slot, 2)),
+            SignableMessage::SyncCommitteeSignature { slot, .. } => Some((*slot, 2)),
+            SignableMessage::SignedAggregateAndProof(a) => Some((a.aggregate().data().slot, 3)),
+            SignableMessage::SignedContributionAndProof(c) => Some((c.contribution.slot, 3)),
+            SignableMessage::RandaoReveal(_)
+            | SignableMessage::ValidatorRegistration(_)
+            | SignableMessage::VoluntaryExit(_) => None,
+        }
+    }
 }
 
 /// A method used by a validator to sign messages.
@@ -90,7 +164,7 @@ pub enum SigningMethod {
     /// See: https://docs.web3signer.consensys.net/en/latest/
     Web3Signer {
         signing_url: Url,
-        http_client: Client,
+        http_client: Arc<Web3SignerClient>,
         voting_public_key: PublicKey,
     },
 }
@@ -117,6 +191,14 @@ impl SigningContext {
 }
 
 impl SigningMethod {
+    /// Returns a label describing the backend of `self`, for use in metrics.
+    pub fn metrics_label(&self) -> &'static str {
+        match self {
+            SigningMethod::LocalKeystore { .. } => metrics::LOCAL_KEYSTORE,
+            SigningMethod::Web3Signer { .. } => metrics::WEB3SIGNER,
+        }
+    }
+
     /// Return whether this signing method requires local slashing protection.
     pub fn requires_local_slashing_protection(
         &self,
@@ -163,12 +245,37 @@ impl SigningMethod {
         signing_root: Hash256,
         executor: &TaskExecutor,
         fork_info: Option<ForkInfo>,
+    ) -> Result<Signature, Error> {
+        let signer = self.metrics_label();
+        let message = signable_message.metrics_label();
+
+        let _timer = metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[signer]);
+        let _message_timer =
+            metrics::start_timer_vec(&metrics::SIGNING_MESSAGE_TIMES, &[signer, message]);
+
+        let result = self
+            .sign_root(signable_message, signing_root, executor, fork_info)
+            .await;
+
+        if let Err(e) = &result {
+            metrics::inc_counter_vec(
+                &metrics::SIGNING_ERRORS_TOTAL,
+                &[signer, message, e.metrics_label()],
+            );
+        }
+
+        result
+    }
+
+    async fn sign_root<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        signable_message: SignableMessage<'_, E, Payload>,
+        signing_root: Hash256,
+        executor: &TaskExecutor,
+        fork_info: Option<ForkInfo>,
     ) -> Result<Signature, Error> {
         match self {
             SigningMethod::LocalKeystore { voting_keypair, .. } => {
-                let _timer =
-                    metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::LOCAL_KEYSTORE]);
-
                 let voting_keypair = voting_keypair.clone();
                 // Spawn a blocking task to produce the signature. This avoids blocking the core
                 // tokio executor.
@@ -187,9 +294,6 @@ impl SigningMethod {
                 http_client,
                 ..
             } => {
-                let _timer =
-                    metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::WEB3SIGNER]);
-
                 // Map the message into a Web3Signer type.
                 let object = match signable_message {
                     SignableMessage::RandaoReveal(epoch) => {
@@ -241,15 +345,20 @@ impl SigningMethod {
                 };
 
                 // Request a signature from the Web3Signer instance via HTTP(S).
-                let response: SigningResponse = http_client
+                let response = http_client
+                    .client()
                     .post(signing_url.clone())
                     .header(ACCEPT, "application/json")
                     .json(&request)
                     .send()
                     .await
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+                    .map_err(Error::from_web3signer_request)?;
+                http_client
+                    .verify_server_identity(&response)
+                    .map_err(Error::Web3SignerInvalidServerIdentity)?;
+                let response: SigningResponse = response
                     .error_for_status()
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+                    .map_err(Error::from_web3signer_request)?
                     .json()
                     .await
                     .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
@@ -259,3 +368,93 @@ impl SigningMethod {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn duty_deadlines() {
+        let spec = E::default_spec();
+        let slot = Slot::new(7);
+        let mut block = BeaconBlock::<E>::empty(&spec);
+        *block.slot_mut() = slot;
+        let attestation_data = AttestationData {
+            slot,
+            ..AttestationData::default()
+        };
+
+        let deadline = |message: SignableMessage<E>| message.duty_deadline();
+
+        assert_eq!(
+            deadline(SignableMessage::BeaconBlock(&block)),
+            Some((slot, 1))
+        );
+        assert_eq!(
+            deadline(SignableMessage::AttestationData(&attestation_data)),
+            Some((slot, 2))
+        );
+        assert_eq!(
+            deadline(SignableMessage::SelectionProof(slot)),
+            Some((slot, 2))
+        );
+        assert_eq!(
+            deadline(SignableMessage::RandaoReveal(
+                slot.epoch(E::slots_per_epoch())
+            )),
+            None
+        );
+    }
+
+    /// Returns the error from a Web3Signer request to `url`, with a short timeout.
+    async fn request_error(url: String) -> Error {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        client
+            .post(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(Error::from_web3signer_request)
+            .unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn web3signer_request_errors_are_classified() {
+        // Nothing is listening on the port.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let error = request_error(format!("http://127.0.0.1:{port}")).await;
+        assert_eq!(error.metrics_label(), "request_failed");
+
+        // The connection is accepted but never answered.
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let error = request_error(format!("http://{}", silent.local_addr().unwrap())).await;
+        assert_eq!(error.metrics_label(), "timeout");
+
+        // The signer responds with an error status.
+        let failing = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", failing.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = failing.accept().unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(
+                b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            );
+        });
+        let error = request_error(url).await;
+        assert_eq!(error.metrics_label(), "error_status");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
+    clock_monitor::ClockOffset,
     duties_service::DutiesService,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
     OfflineOnFailure,
@@ -47,6 +48,7 @@ pub struct Inner<T: SlotClock + 'static, E: EthSpec> {
     validator_store: Arc<ValidatorStore<T, E>>,
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    clock_offset: Arc<ClockOffset>,
     context: RuntimeContext<E>,
     /// Boolean to track whether the service has posted subscriptions to the BN at least once.
     ///
@@ -60,6 +62,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         validator_store: Arc<ValidatorStore<T, E>>,
         slot_clock: T,
         beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+        clock_offset: Arc<ClockOffset>,
         context: RuntimeContext<E>,
     ) -> Self {
         Self {
@@ -68,6 +71,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 validator_store,
                 slot_clock,
                 beacon_nodes,
+                clock_offset,
                 context,
                 first_subscription_done: AtomicBool::new(false),
             }),
@@ -109,14 +113,18 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
                     // Wait for contribution broadcast interval 1/3 of the way through the slot.
                     let log = self.context.log();
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    let duty_delay = self.clock_offset.duty_delay(slot_duration);
+                    sleep(duration_to_next_slot + slot_duration / 3 + duty_delay).await;
 
                     // Do nothing if the Altair fork has not yet occurred.
                     if !self.altair_fork_activated() {
                         continue;
                     }
 
-                    if let Err(e) = self.spawn_contribution_tasks(slot_duration).await {
+                    if let Err(e) = self
+                        .spawn_contribution_tasks(slot_duration, duty_delay)
+                        .await
+                    {
                         crit!(
                             log,
                             "Failed to spawn sync contribution tasks";
@@ -143,7 +151,11 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         Ok(())
     }
 
-    async fn spawn_contribution_tasks(&self, slot_duration: Duration) -> Result<(), String> {
+    async fn spawn_contribution_tasks(
+        &self,
+        slot_duration: Duration,
+        duty_delay: Duration,
+    ) -> Result<(), String> {
         let log = self.context.log().clone();
         let slot = self.slot_clock.now().ok_or("Failed to read slot clock")?;
         let duration_to_next_slot = self
@@ -156,7 +168,8 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         let aggregate_production_instant = Instant::now()
             + duration_to_next_slot
                 .checked_sub(slot_duration / 3)
-                .unwrap_or_else(|| Duration::from_secs(0));
+                .unwrap_or_else(|| Duration::from_secs(0))
+            + duty_delay;
 
         let Some(slot_duties) = self
             .duties_service
@@ -2,8 +2,9 @@ use crate::{
     doppelganger_service::DoppelgangerService,
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
+    leader_election::LeaderElection,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
-    Config,
+    Config, Web3SignerSlashingProtection,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
 use parking_lot::{Mutex, RwLock};
@@ -32,6 +33,7 @@ use crate::preparation_service::ProposalData;
 #[derive(Debug, PartialEq)]
 pub enum Error {
     DoppelgangerProtected(PublicKeyBytes),
+    NotLeader(PublicKeyBytes),
     UnknownToDoppelgangerService(PublicKeyBytes),
     UnknownPubkey(PublicKeyBytes),
     Slashable(NotSafe),
@@ -66,6 +68,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     spec: Arc<ChainSpec>,
     log: Logger,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    leader_election: Option<Arc<LeaderElection>>,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
     gas_limit: Option<u64>,
@@ -87,6 +90,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
+        leader_election: Option<Arc<LeaderElection>>,
         slot_clock: T,
         config: &Config,
         task_executor: TaskExecutor,
@@ -100,11 +104,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             spec: Arc::new(spec),
             log,
             doppelganger_service,
+            leader_election,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
             gas_limit: config.gas_limit,
             builder_proposals: config.builder_proposals,
-            enable_web3signer_slashing_protection: config.enable_web3signer_slashing_protection,
+            enable_web3signer_slashing_protection: config.web3signer_slashing_protection
+                == Web3SignerSlashingProtection::Local,
             prefer_builder_proposals: config.prefer_builder_proposals,
             builder_boost_factor: config.builder_boost_factor,
             task_executor,
@@ -259,14 +265,27 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .cloned()
             .collect::<Vec<_>>();
 
+        let leader_election_allows_signing = self.leader_election_allows_signing();
+
         pubkeys
             .into_iter()
             .map(|pubkey| {
-                self.doppelganger_service
+                let status = self
+                    .doppelganger_service
                     .as_ref()
                     .map(|doppelganger_service| doppelganger_service.validator_status(pubkey))
                     // Allow signing on all pubkeys if doppelganger protection is disabled.
-                    .unwrap_or_else(|| DoppelgangerStatus::SigningEnabled(pubkey))
+                    .unwrap_or_else(|| DoppelgangerStatus::SigningEnabled(pubkey));
+                // A validator client which isn't the leader treats its validators as it would
+                // validators awaiting doppelganger protection.
+                match status {
+                    DoppelgangerStatus::SigningEnabled(pubkey)
+                        if !leader_election_allows_signing =>
+                    {
+                        DoppelgangerStatus::SigningDisabled(pubkey)
+                    }
+                    status => status,
+                }
             })
             .filter_map(filter_func)
             .collect()
@@ -311,6 +330,25 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             })
     }
 
+    /// Returns `true` if this validator client is the elected leader and may sign in the current
+    /// epoch, or if leader election is disabled.
+    pub fn leader_election_allows_signing(&self) -> bool {
+        self.leader_election
+            .as_ref()
+            .map_or(true, |leader_election| {
+                self.slot_clock.now().map_or(false, |slot| {
+                    leader_election.allows_signing(slot.epoch(E::slots_per_epoch()))
+                })
+            })
+    }
+
+    /// Returns `true` if both doppelganger protection and leader election allow signing slashable
+    /// messages with `validator_pubkey`.
+    pub fn signing_allowed(&self, validator_pubkey: PublicKeyBytes) -> bool {
+        self.leader_election_allows_signing()
+            && self.doppelganger_protection_allows_signing(validator_pubkey)
+    }
+
     pub fn num_voting_validators(&self) -> usize {
         self.validators.read().num_enabled()
     }
@@ -320,11 +358,15 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     }
 
     /// Returns a `SigningMethod` for `validator_pubkey` *only if* that validator is considered safe
-    /// by doppelganger protection.
+    /// by doppelganger protection, and this validator client is the leader if leader election is
+    /// enabled.
     fn doppelganger_checked_signing_method(
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
+        if !self.leader_election_allows_signing() {
+            return Err(Error::NotLeader(validator_pubkey));
+        }
         if self.doppelganger_protection_allows_signing(validator_pubkey) {
             self.validators
                 .read()
@@ -384,6 +426,52 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
+    /// Returns the signature of `signable_message` from `signing_method`.
+    ///
+    /// If `signable_message` is produced for a duty with a deadline (see
+    /// `SignableMessage::duty_deadline`) and the signature is obtained after that deadline, the miss
+    /// is attributed to the signer if signing started before the deadline.
+    async fn get_duty_signature<Payload: AbstractExecPayload<E>>(
+        &self,
+        signing_method: &SigningMethod,
+        signable_message: SignableMessage<'_, E, Payload>,
+        signing_context: SigningContext,
+    ) -> Result<Signature, SigningError> {
+        let duty = signable_message.metrics_label();
+        let deadline = signable_message.duty_deadline().and_then(|(slot, thirds)| {
+            let slot_start = self.slot_clock.start_of(slot)?;
+            Some(slot_start + self.slot_clock.slot_duration() * thirds / 3)
+        });
+        let started = self.slot_clock.now_duration();
+
+        let signature = signing_method
+            .get_signature(
+                signable_message,
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await?;
+
+        if let (Some(deadline), Some(started), Some(finished)) =
+            (deadline, started, self.slot_clock.now_duration())
+        {
+            if finished > deadline {
+                let cause = if started > deadline {
+                    metrics::LATE_START
+                } else {
+                    metrics::SIGNER
+                };
+                metrics::inc_counter_vec(
+                    &metrics::SIGNING_DEADLINE_MISSES_TOTAL,
+                    &[signing_method.metrics_label(), duty, cause],
+                );
+            }
+        }
+
+        Ok(signature)
+    }
+
     pub async fn randao_reveal(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -392,12 +480,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
         let signing_context = self.signing_context(Domain::Randao, signing_epoch);
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::RandaoReveal(signing_epoch),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await?;
 
@@ -593,12 +680,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             Ok(Safe::Valid) => {
                 metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::SUCCESS]);
 
-                let signature = signing_method
-                    .get_signature::<E, Payload>(
+                let signature = self
+                    .get_duty_signature::<Payload>(
+                        &signing_method,
                         SignableMessage::BeaconBlock(&block),
                         signing_context,
-                        &self.spec,
-                        &self.task_executor,
                     )
                     .await?;
                 Ok(SignedBeaconBlock::from_block(block, signature))
@@ -670,12 +756,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         match slashing_status {
             // We can safely sign this attestation.
             Ok(Safe::Valid) => {
-                let signature = signing_method
-                    .get_signature::<E, BlindedPayload<E>>(
+                let signature = self
+                    .get_duty_signature::<BlindedPayload<E>>(
+                        &signing_method,
                         SignableMessage::AttestationData(attestation.data()),
                         signing_context,
-                        &self.spec,
-                        &self.task_executor,
                     )
                     .await?;
                 attestation
@@ -735,12 +820,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let signing_context = self.signing_context(Domain::VoluntaryExit, signing_epoch);
         let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::VoluntaryExit(&voluntary_exit),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await?;
 
@@ -799,12 +883,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             AggregateAndProof::from_attestation(aggregator_index, aggregate, selection_proof);
 
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::SignedAggregateAndProof(message.to_ref()),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await?;
 
@@ -834,12 +917,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // be published on the network.
         let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::SelectionProof(slot),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -873,12 +955,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             subcommittee_index: subnet_id.into(),
         };
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::SyncSelectionProof(&message),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -899,15 +980,14 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // Bypass `with_validator_signing_method`: sync committee messages are not slashable.
         let signing_method = self.doppelganger_bypassed_signing_method(*validator_pubkey)?;
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::SyncCommitteeSignature {
                     beacon_block_root,
                     slot,
                 },
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -944,12 +1024,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             selection_proof: selection_proof.into(),
         };
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_duty_signature::<BlindedPayload<E>>(
+                &signing_method,
                 SignableMessage::SignedContributionAndProof(&message),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -0,0 +1,465 @@
+//! The HTTP clients used to request signatures from Web3Signer instances.
+//!
+//! A client is shared by all of the validators with the same `Web3SignerDefinition`, so each
+//! signer has its own connection pool and client certificate. The clients are monitored by a
+//! service which queries the `upcheck` endpoint of each signer, and rebuilds a client whenever its
+//! certificate files are modified so that rotated certificates are used without a restart.
+//!
+//! A definition with a `server_identity` only accepts responses from a server whose certificate
+//! has that identity as a URI subject alternative name, as with SPIFFE IDs.
+//!
+//! When slashing protection is delegated to the signers, each signer must report that its
+//! slashing protection database is healthy at startup, and that its high watermark allows its
+//! validators to sign after the messages in the local slashing protection database.
+use crate::http_metrics::metrics;
+use crate::initialized_validators::{
+    load_pem_certificate, load_pkcs12_identity, Error, InitializedValidators,
+};
+use crate::{Config, ValidatorStore};
+use account_utils::validator_definitions::Web3SignerDefinition;
+use environment::RuntimeContext;
+use futures::future::join_all;
+use parking_lot::{Mutex, RwLock};
+use reqwest::{tls::TlsInfo, Client, Response, StatusCode};
+use serde::Deserialize;
+use slashing_protection::SlashingDatabase;
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::time::sleep;
+use types::{Epoch, EthSpec, Slot};
+use url::Url;
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+
+/// Default timeout for a request to a remote signer for a signature.
+///
+/// Set to 12 seconds since that's the duration of a slot. A remote signer that cannot sign within
+/// that time is outside the synchronous assumptions of Eth2.
+const DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// The response of the `healthcheck` endpoint of a Web3Signer.
+#[derive(Deserialize)]
+struct HealthCheck {
+    checks: Vec<Check>,
+}
+
+#[derive(Deserialize)]
+struct Check {
+    id: String,
+    status: String,
+}
+
+/// The slot and epoch at or above which a Web3Signer refuses to sign blocks and attestations.
+#[derive(Debug, Default, Deserialize)]
+struct HighWatermark {
+    slot: Option<Slot>,
+    epoch: Option<Epoch>,
+}
+
+/// A client for a Web3Signer, shared by all of the validators with the same definition.
+pub struct Web3SignerClient {
+    definition: Web3SignerDefinition,
+    request_timeout: Duration,
+    keep_alive_timeout: Option<Duration>,
+    max_idle_connections: Option<usize>,
+    client: RwLock<Client>,
+    /// The modification times of the certificate files when `client` was built.
+    certificates_modified: Mutex<Vec<Option<SystemTime>>>,
+    healthy: AtomicBool,
+}
+
+impl Web3SignerClient {
+    pub fn new(definition: Web3SignerDefinition, config: &Config) -> Result<Self, Error> {
+        if let Some(server_identity) = &definition.server_identity {
+            let url = Url::parse(&definition.url)
+                .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+            if url.scheme() != "https" {
+                return Err(Error::InvalidWeb3SignerServerIdentity(format!(
+                    "{server_identity} can only be verified over https"
+                )));
+            }
+        }
+
+        let request_timeout = definition
+            .request_timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+        let certificates_modified = certificates_modified(&definition);
+        let client = build_client(
+            &definition,
+            request_timeout,
+            config.web3_signer_keep_alive_timeout,
+            config.web3_signer_max_idle_connections,
+        )?;
+
+        Ok(Self {
+            definition,
+            request_timeout,
+            keep_alive_timeout: config.web3_signer_keep_alive_timeout,
+            max_idle_connections: config.web3_signer_max_idle_connections,
+            client: RwLock::new(client),
+            certificates_modified: Mutex::new(certificates_modified),
+            healthy: AtomicBool::new(true),
+        })
+    }
+
+    /// Returns the client with the most recently loaded certificates.
+    pub fn client(&self) -> Client {
+        self.client.read().clone()
+    }
+
+    pub fn url(&self) -> &str {
+        &self.definition.url
+    }
+
+    /// Returns whether the signer passed its most recent health check.
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// Check that `response` is from a server with the `server_identity` of the definition, if it
+    /// has one.
+    pub fn verify_server_identity(&self, response: &Response) -> Result<(), String> {
+        let Some(server_identity) = &self.definition.server_identity else {
+            return Ok(());
+        };
+        let certificate = response
+            .extensions()
+            .get::<TlsInfo>()
+            .and_then(TlsInfo::peer_certificate)
+            .ok_or("the server did not present a certificate")?;
+        let identities = uri_subject_alt_names(certificate)?;
+        if identities.contains(server_identity) {
+            Ok(())
+        } else {
+            Err(format!(
+                "the server identities {identities:?} do not include {server_identity}"
+            ))
+        }
+    }
+
+    /// Rebuild the client if any of its certificate files have been modified since it was built,
+    /// returning whether it was rebuilt.
+    pub fn reload_if_modified(&self) -> Result<bool, Error> {
+        let modified = certificates_modified(&self.definition);
+        let mut current = self.certificates_modified.lock();
+        if *current == modified {
+            return Ok(false);
+        }
+
+        *self.client.write() = build_client(
+            &self.definition,
+            self.request_timeout,
+            self.keep_alive_timeout,
+            self.max_idle_connections,
+        )?;
+        *current = modified;
+        Ok(true)
+    }
+
+    fn endpoint(&self, path: &str) -> Result<Url, String> {
+        Url::parse(&self.definition.url)
+            .and_then(|url| url.join(path))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Query the `upcheck` endpoint of the signer.
+    async fn check_health(&self) -> Result<(), String> {
+        let response = self
+            .client()
+            .get(self.endpoint("upcheck")?)
+            .send()
+            .await
+            .and_then(Response::error_for_status)
+            .map_err(|e| e.to_string())?;
+        self.verify_server_identity(&response)
+    }
+
+    /// Check that the `healthcheck` endpoint of the signer reports that its slashing protection
+    /// database is up.
+    async fn check_slashing_protection(&self) -> Result<(), String> {
+        // The signer responds with an error status if any check is down, so the status of the
+        // slashing protection check is read from the body regardless.
+        let response = self
+            .client()
+            .get(self.endpoint("healthcheck")?)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        self.verify_server_identity(&response)?;
+        let health: HealthCheck = response
+            .json()
+            .await
+            .map_err(|e| format!("invalid healthcheck response: {e}"))?;
+        match health
+            .checks
+            .iter()
+            .find(|check| check.id.contains("slashing"))
+        {
+            Some(check) if check.status == "UP" => Ok(()),
+            Some(check) => Err(format!("slashing protection is {}", check.status)),
+            None => Err("slashing protection is not enabled".to_string()),
+        }
+    }
+
+    /// Query the high watermark of the signer, which is empty if none has been set.
+    async fn high_watermark(&self) -> Result<HighWatermark, String> {
+        let response = self
+            .client()
+            .get(self.endpoint("api/v1/eth2/highWatermark")?)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        self.verify_server_identity(&response)?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(HighWatermark::default());
+        }
+        response
+            .error_for_status()
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| format!("invalid high watermark: {e}"))
+    }
+}
+
+fn build_client(
+    definition: &Web3SignerDefinition,
+    request_timeout: Duration,
+    keep_alive_timeout: Option<Duration>,
+    max_idle_connections: Option<usize>,
+) -> Result<Client, Error> {
+    let builder = Client::builder()
+        .timeout(request_timeout)
+        .pool_idle_timeout(keep_alive_timeout)
+        .pool_max_idle_per_host(max_idle_connections.unwrap_or(usize::MAX))
+        .tls_info(definition.server_identity.is_some());
+
+    let builder = if let Some(path) = &definition.root_certificate_path {
+        let certificate = load_pem_certificate(path)?;
+        builder.add_root_certificate(certificate)
+    } else {
+        builder
+    };
+
+    let builder = if let Some(path) = &definition.client_identity_path {
+        let identity = load_pkcs12_identity(
+            path,
+            definition
+                .client_identity_password
+                .as_ref()
+                .ok_or(Error::MissingWeb3SignerClientIdentityPassword)?,
+        )?;
+        builder.identity(identity)
+    } else {
+        if definition.client_identity_password.is_some() {
+            return Err(Error::MissingWeb3SignerClientIdentityCertificateFile);
+        }
+        builder
+    };
+
+    builder
+        .build()
+        .map_err(Error::UnableToBuildWeb3SignerClient)
+}
+
+fn certificates_modified(definition: &Web3SignerDefinition) -> Vec<Option<SystemTime>> {
+    let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    [
+        &definition.root_certificate_path,
+        &definition.client_identity_path,
+    ]
+    .into_iter()
+    .map(|path| path.as_deref().and_then(modified))
+    .collect()
+}
+
+/// Returns the URI subject alternative names of the DER encoded `certificate`.
+fn uri_subject_alt_names(certificate: &[u8]) -> Result<Vec<String>, String> {
+    let (_, certificate) = X509Certificate::from_der(certificate)
+        .map_err(|e| format!("invalid server certificate: {e}"))?;
+    let subject_alt_names = certificate
+        .subject_alternative_name()
+        .map_err(|e| format!("invalid subject alternative names: {e}"))?;
+    Ok(subject_alt_names
+        .map(|extension| {
+            extension
+                .value
+                .general_names
+                .iter()
+                .filter_map(|name| match name {
+                    GeneralName::URI(uri) => Some(uri.to_string()),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Check that the slashing protection of each Web3Signer used by `validators` is healthy, and
+/// consistent with the local `slashing_protection` database.
+///
+/// The local database is inconsistent with a signer if it holds a message for one of the
+/// validators of the signer at or above the high watermark of the signer, such as when the
+/// database of the signer has been restored from a backup and its high watermark set from that.
+///
+/// A signer without a high watermark for a validator can't be compared with the local database, so
+/// a database restored from a backup without setting the high watermark is not detected.
+pub async fn check_remote_slashing_protection(
+    validators: &InitializedValidators,
+    slashing_protection: &SlashingDatabase,
+    log: &Logger,
+) -> Result<(), String> {
+    for (client, pubkeys) in validators.web3_signer_validators() {
+        client.check_slashing_protection().await.map_err(|e| {
+            format!(
+                "Unable to delegate slashing protection to Web3Signer {}: {e}",
+                client.url()
+            )
+        })?;
+        let high_watermark = client.high_watermark().await.map_err(|e| {
+            format!(
+                "Unable to read the high watermark of Web3Signer {}: {e}",
+                client.url()
+            )
+        })?;
+
+        for pubkey in &pubkeys {
+            let summary = slashing_protection
+                .with_transaction(|txn| slashing_protection.validator_summary(pubkey, txn))
+                .map_err(|e| format!("Unable to read slashing protection of {pubkey:?}: {e:?}"))?;
+            if let (Some(max_slot), Some(watermark)) = (summary.max_block_slot, high_watermark.slot)
+            {
+                if max_slot >= watermark {
+                    return Err(format!(
+                        "The local slashing protection database has a block by {pubkey:?} at \
+                         slot {max_slot}, at or above the high watermark of Web3Signer {} at slot \
+                         {watermark}",
+                        client.url()
+                    ));
+                }
+            }
+            if let (Some(max_target), Some(watermark)) =
+                (summary.max_attestation_target, high_watermark.epoch)
+            {
+                if max_target >= watermark {
+                    return Err(format!(
+                        "The local slashing protection database has an attestation by \
+                         {pubkey:?} with target epoch {max_target}, at or above the high \
+                         watermark of Web3Signer {} at epoch {watermark}",
+                        client.url()
+                    ));
+                }
+            }
+        }
+
+        info!(
+            log,
+            "Delegating slashing protection to Web3Signer";
+            "url" => client.url(),
+            "validators" => pubkeys.len(),
+            "high_watermark_slot" => ?high_watermark.slot,
+            "high_watermark_epoch" => ?high_watermark.epoch,
+        );
+    }
+    Ok(())
+}
+
+/// Starts a service which checks the health of each Web3Signer every `interval`, and reloads the
+/// certificates of any signer whose certificate files have been modified.
+pub fn start_web3signer_monitor<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    interval: Duration,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let clients = validator_store
+                .initialized_validators()
+                .read()
+                .web3_signer_clients();
+
+            for client in &clients {
+                match client.reload_if_modified() {
+                    Ok(true) => info!(
+                        log,
+                        "Reloaded Web3Signer certificates";
+                        "url" => client.url(),
+                    ),
+                    Ok(false) => (),
+                    Err(e) => error!(
+                        log,
+                        "Unable to reload Web3Signer certificates";
+                        "error" => ?e,
+                        "url" => client.url(),
+                    ),
+                }
+            }
+
+            let results = join_all(clients.iter().map(|client| client.check_health())).await;
+            for (client, result) in clients.iter().zip(results) {
+                let was_healthy = client.healthy.swap(result.is_ok(), Ordering::Relaxed);
+                match result {
+                    Ok(()) if !was_healthy => {
+                        info!(log, "Web3Signer is healthy again"; "url" => client.url())
+                    }
+                    Ok(()) => debug!(log, "Web3Signer is healthy"; "url" => client.url()),
+                    Err(e) => warn!(
+                        log,
+                        "Web3Signer health check failed";
+                        "error" => e,
+                        "url" => client.url(),
+                    ),
+                }
+            }
+            metrics::set_gauge(
+                &metrics::WEB3SIGNER_UNHEALTHY_COUNT,
+                clients.iter().filter(|client| !client.is_healthy()).count() as i64,
+            );
+
+            sleep(interval).await;
+        }
+    };
+    context.executor.spawn(future, "web3signer_monitor");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use x509_parser::pem::parse_x509_pem;
+
+    #[test]
+    fn web3signer_test_certificate_identity() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../testing/web3signer_tests/tls/web3signer/cert.pem");
+        let pem = fs::read(path).unwrap();
+        let (_, pem) = parse_x509_pem(&pem).unwrap();
+        assert_eq!(
+            uri_subject_alt_names(&pem.contents).unwrap(),
+            vec!["spiffe://lighthouse.test/web3signer".to_string()]
+        );
+    }
+
+    #[test]
+    fn server_identity_requires_https() {
+        let definition = Web3SignerDefinition {
+            url: "http://localhost:9000".to_string(),
+            root_certificate_path: None,
+            request_timeout_ms: None,
+            client_identity_path: None,
+            client_identity_password: None,
+            server_identity: Some("spiffe://lighthouse.test/web3signer".to_string()),
+        };
+        assert!(matches!(
+            Web3SignerClient::new(definition, &Config::default()),
+            Err(Error::InvalidWeb3SignerServerIdentity(_))
+        ));
+    }
+}
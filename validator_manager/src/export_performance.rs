@@ -0,0 +1,314 @@
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    lighthouse::ValidatorEpochPerformance, reqwest::ClientBuilder, BeaconNodeHttpClient,
+    SensitiveUrl, Timeouts,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use types::Epoch;
+
+pub const CMD: &str = "export-performance";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const BEACON_NODE_TOKEN_FLAG: &str = "beacon-node-token";
+pub const START_EPOCH_FLAG: &str = "start-epoch";
+pub const END_EPOCH_FLAG: &str = "end-epoch";
+pub const FORMAT_FLAG: &str = "format";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+/// The columns of the CSV output, in the order of the fields of `ValidatorEpochPerformance`.
+const CSV_HEADER: &str = "epoch,validator_index,attestation_included,attestation_source_hit,\
+    attestation_target_hit,attestation_head_hit,attestation_inclusion_distance,blocks_proposed,\
+    blocks_missed,sync_committee_messages,sync_signature_block_inclusions";
+
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Exports the outcome of the attestation, block proposal and sync committee duties \
+            of the validators monitored by a beacon node, with one row per validator per epoch. \
+            The output can be loaded directly into tools such as pandas or DuckDB.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("The HTTP address of the beacon node to export the performance from.")
+                .default_value("http://localhost:5052")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_TOKEN_FLAG)
+                .long(BEACON_NODE_TOKEN_FLAG)
+                .value_name("PATH")
+                .help(
+                    "Path to a file containing an API token to present to the beacon node, if \
+                    it runs with --http-token-file. The token must have the admin role.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(START_EPOCH_FLAG)
+                .long(START_EPOCH_FLAG)
+                .value_name("EPOCH")
+                .help("The first epoch to export (inclusive).")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(END_EPOCH_FLAG)
+                .long(END_EPOCH_FLAG)
+                .value_name("EPOCH")
+                .help(
+                    "The last epoch to export (inclusive). Epochs after the beacon node's \
+                    current epoch are ignored.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(FORMAT_FLAG)
+                .long(FORMAT_FLAG)
+                .value_name("FORMAT")
+                .help(
+                    "The format of the output. \"json\" writes one JSON object per line. Values \
+                    which the beacon node no longer holds are empty in CSV and null in JSON.",
+                )
+                .value_parser(["csv", "json"])
+                .default_value("csv")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("FILE")
+                .help("The path of the file to write. It must not already exist.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(format!("{} is not a supported format", other)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExportPerformanceConfig {
+    pub bn_url: SensitiveUrl,
+    pub bn_token_path: Option<PathBuf>,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub format: ExportFormat,
+    pub output_path: PathBuf,
+}
+
+impl ExportPerformanceConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            bn_url: clap_utils::parse_required(matches, BEACON_NODE_FLAG)?,
+            bn_token_path: clap_utils::parse_optional(matches, BEACON_NODE_TOKEN_FLAG)?,
+            start_epoch: clap_utils::parse_required(matches, START_EPOCH_FLAG)?,
+            end_epoch: clap_utils::parse_required(matches, END_EPOCH_FLAG)?,
+            format: clap_utils::parse_required(matches, FORMAT_FLAG)?,
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = ExportPerformanceConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: ExportPerformanceConfig) -> Result<(), String> {
+    let ExportPerformanceConfig {
+        bn_url,
+        bn_token_path,
+        start_epoch,
+        end_epoch,
+        format,
+        output_path,
+    } = config;
+
+    if output_path.exists() {
+        return Err(format!("{:?} already exists", output_path));
+    }
+
+    let mut client_builder = ClientBuilder::new();
+    if let Some(token_path) = &bn_token_path {
+        client_builder = client_builder.default_headers(eth2::api_token_headers(token_path)?);
+    }
+    let client = client_builder
+        .build()
+        .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+    let beacon_node = BeaconNodeHttpClient::from_components(
+        bn_url,
+        client,
+        Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT),
+    );
+
+    let performance = beacon_node
+        .get_lighthouse_ui_validator_performance(start_epoch, end_epoch)
+        .await
+        .map_err(|e| format!("Failed to fetch validator performance: {:?}", e))?
+        .data;
+
+    let file = File::create(&output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_performance(&mut writer, format, &performance)
+        .and_then(|()| writer.flush())
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output_path, e))?;
+
+    eprintln!(
+        "Exported {} rows for epochs {} to {} to {:?}",
+        performance.len(),
+        start_epoch,
+        end_epoch,
+        output_path
+    );
+
+    Ok(())
+}
+
+fn write_performance<W: Write>(
+    writer: &mut W,
+    format: ExportFormat,
+    performance: &[ValidatorEpochPerformance],
+) -> std::io::Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            writeln!(writer, "{}", CSV_HEADER)?;
+            for row in performance {
+                writeln!(writer, "{}", csv_row(row))?;
+            }
+        }
+        ExportFormat::Json => {
+            for row in performance {
+                serde_json::to_writer(&mut *writer, row)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn csv_row(row: &ValidatorEpochPerformance) -> String {
+    fn opt<T: Display>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    [
+        row.epoch.to_string(),
+        row.validator_index.to_string(),
+        opt(row.attestation_included),
+        opt(row.attestation_source_hit),
+        opt(row.attestation_target_hit),
+        opt(row.attestation_head_hit),
+        opt(row.attestation_inclusion_distance),
+        opt(row.blocks_proposed),
+        row.blocks_missed.to_string(),
+        opt(row.sync_committee_messages),
+        opt(row.sync_signature_block_inclusions),
+    ]
+    .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn performance() -> Vec<ValidatorEpochPerformance> {
+        vec![
+            ValidatorEpochPerformance {
+                epoch: Epoch::new(10),
+                validator_index: 3,
+                attestation_included: Some(true),
+                attestation_source_hit: Some(true),
+                attestation_target_hit: Some(true),
+                attestation_head_hit: Some(false),
+                attestation_inclusion_distance: Some(1),
+                blocks_proposed: Some(1),
+                blocks_missed: 0,
+                sync_committee_messages: Some(32),
+                sync_signature_block_inclusions: Some(31),
+            },
+            ValidatorEpochPerformance {
+                epoch: Epoch::new(11),
+                validator_index: 3,
+                blocks_missed: 1,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn csv_output() {
+        let mut output = vec![];
+        write_performance(&mut output, ExportFormat::Csv, &performance()).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0].split(',').count(),
+            lines[1].split(',').count(),
+            "header and rows have the same number of columns"
+        );
+        assert_eq!(lines[1], "10,3,true,true,true,false,1,1,0,32,31");
+        assert_eq!(lines[2], "11,3,,,,,,,1,,");
+    }
+
+    #[test]
+    fn json_output() {
+        let mut output = vec![];
+        write_performance(&mut output, ExportFormat::Json, &performance()).unwrap();
+        let rows = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<ValidatorEpochPerformance>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows, performance());
+    }
+}
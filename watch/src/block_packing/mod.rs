@@ -12,9 +12,20 @@ pub use database::{
 };
 pub use server::block_packing_routes;
 
-use eth2::BeaconNodeHttpClient;
+use eth2::{lighthouse::BlockPackingEfficiency, BeaconNodeHttpClient};
 use types::Epoch;
 
+impl From<BlockPackingEfficiency> for WatchBlockPacking {
+    fn from(data: BlockPackingEfficiency) -> Self {
+        WatchBlockPacking {
+            slot: WatchSlot::from_slot(data.slot),
+            available: data.available_attestations as i32,
+            included: data.included_attestations as i32,
+            prior_skip_slots: data.prior_skip_slots as i32,
+        }
+    }
+}
+
 /// Sends a request to `lighthouse/analysis/block_packing`.
 /// Formats the response into a vector of `WatchBlockPacking`.
 ///
@@ -28,11 +39,6 @@ pub async fn get_block_packing(
         .get_lighthouse_analysis_block_packing(start_epoch, end_epoch)
         .await?
         .into_iter()
-        .map(|data| WatchBlockPacking {
-            slot: WatchSlot::from_slot(data.slot),
-            available: data.available_attestations as i32,
-            included: data.included_attestations as i32,
-            prior_skip_slots: data.prior_skip_slots as i32,
-        })
+        .map(Into::into)
         .collect())
 }
@@ -12,9 +12,20 @@ pub use database::{
 };
 pub use server::block_rewards_routes;
 
-use eth2::BeaconNodeHttpClient;
+use eth2::{lighthouse::BlockReward, BeaconNodeHttpClient};
 use types::Slot;
 
+impl From<BlockReward> for WatchBlockRewards {
+    fn from(data: BlockReward) -> Self {
+        WatchBlockRewards {
+            slot: WatchSlot::from_slot(data.meta.slot),
+            total: data.total as i32,
+            attestation_reward: data.attestation_rewards.total as i32,
+            sync_committee_reward: data.sync_committee_rewards as i32,
+        }
+    }
+}
+
 /// Sends a request to `lighthouse/analysis/block_rewards`.
 /// Formats the response into a vector of `WatchBlockRewards`.
 ///
@@ -28,11 +39,6 @@ pub async fn get_block_rewards(
         .get_lighthouse_analysis_block_rewards(start_slot, end_slot)
         .await?
         .into_iter()
-        .map(|data| WatchBlockRewards {
-            slot: WatchSlot::from_slot(data.meta.slot),
-            total: data.total as i32,
-            attestation_reward: data.attestation_rewards.total as i32,
-            sync_committee_reward: data.sync_committee_rewards as i32,
-        })
+        .map(Into::into)
         .collect())
 }
@@ -5,6 +5,7 @@ pub mod updater;
 mod config;
 
 use crate::database::WatchSlot;
+use crate::updater::Error as UpdaterError;
 
 use eth2::SensitiveUrl;
 use reqwest::{Client, Response, Url};
@@ -65,6 +66,23 @@ pub struct BlockprintResponse {
 }
 
 impl WatchBlockprintClient {
+    /// Builds a client from `config`, or returns `None` if blockprint is disabled.
+    pub fn from_config(config: &Config) -> Result<Option<Self>, UpdaterError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let server = config.url.as_ref().ok_or_else(|| {
+            UpdaterError::NotEnabled("blockprint was enabled but url was not set".to_string())
+        })?;
+        let server = SensitiveUrl::parse(server).map_err(UpdaterError::SensitiveUrl)?;
+        Ok(Some(Self {
+            client: Client::new(),
+            server,
+            username: config.username.clone(),
+            password: config.password.clone(),
+        }))
+    }
+
     async fn get(&self, url: Url) -> Result<Response, Error> {
         let mut builder = self.client.get(url).timeout(TIMEOUT);
         if let Some(username) = &self.username {
@@ -81,7 +99,6 @@ impl WatchBlockprintClient {
 
     // Returns the `greatest_block_slot` as reported by the Blockprint server.
     // Will error if the Blockprint server is not synced.
-    #[allow(dead_code)]
     pub async fn ensure_synced(&self) -> Result<Slot, Error> {
         let url = self.server.full.join("sync/")?.join("status")?;
 
@@ -1,5 +1,6 @@
 use crate::blockprint::Config as BlockprintConfig;
 use crate::database::Config as DatabaseConfig;
+use crate::ingest::Config as IngestConfig;
 use crate::server::Config as ServerConfig;
 use crate::updater::Config as UpdaterConfig;
 
@@ -22,6 +23,9 @@ pub struct Config {
     pub server: ServerConfig,
     #[serde(default)]
     pub updater: UpdaterConfig,
+    /// Only used when ingesting from within the beacon node.
+    #[serde(default)]
+    pub ingest: IngestConfig,
     /// The minimum severity for log messages.
     #[serde(default = "log_level")]
     pub log_level: String,
@@ -34,6 +38,7 @@ impl Default for Config {
             database: DatabaseConfig::default(),
             server: ServerConfig::default(),
             updater: UpdaterConfig::default(),
+            ingest: IngestConfig::default(),
             log_level: log_level(),
         }
     }
@@ -19,7 +19,7 @@ use diesel::upsert::excluded;
 use log::{debug, info};
 use std::collections::HashMap;
 use std::time::Instant;
-use types::{EthSpec, SignedBeaconBlock};
+use types::{AbstractExecPayload, EthSpec, SignedBeaconBlock};
 
 pub use self::error::Error;
 pub use self::models::{WatchBeaconBlock, WatchCanonicalSlot, WatchProposerInfo, WatchValidator};
@@ -132,18 +132,7 @@ pub fn insert_beacon_block<E: EthSpec>(
     block: SignedBeaconBlock<E>,
     root: WatchHash,
 ) -> Result<(), Error> {
-    use self::canonical_slots::dsl::{beacon_block, slot as canonical_slot};
-
-    let block_message = block.message();
-
-    // Pull out relevant values from the block.
-    let slot = WatchSlot::from_slot(block.slot());
-    let parent_root = WatchHash::from_hash(block.parent_root());
-    let proposer_index = block_message.proposer_index() as i32;
-    let graffiti = block_message.body().graffiti().as_utf8_lossy();
-    let attestation_count = block_message.body().attestations_len() as i32;
-
-    let full_payload = block_message.execution_payload().ok();
+    let full_payload = block.message().execution_payload().ok();
 
     let transaction_count: Option<i32> = if let Some(bellatrix_payload) =
         full_payload.and_then(|payload| payload.execution_payload_bellatrix().ok())
@@ -159,6 +148,29 @@ pub fn insert_beacon_block<E: EthSpec>(
         .and_then(|payload| payload.execution_payload_capella().ok())
         .map(|payload| payload.withdrawals.len() as i32);
 
+    insert_beacon_block_with_payload_counts(conn, &block, root, transaction_count, withdrawal_count)
+}
+
+/// Inserts a block whose execution payload may be unavailable, such as a blinded block whose
+/// payload has been pruned. `transaction_count` and `withdrawal_count` are `None` if unknown.
+pub fn insert_beacon_block_with_payload_counts<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    conn: &mut PgConn,
+    block: &SignedBeaconBlock<E, Payload>,
+    root: WatchHash,
+    transaction_count: Option<i32>,
+    withdrawal_count: Option<i32>,
+) -> Result<(), Error> {
+    use self::canonical_slots::dsl::{beacon_block, slot as canonical_slot};
+
+    let block_message = block.message();
+
+    // Pull out relevant values from the block.
+    let slot = WatchSlot::from_slot(block.slot());
+    let parent_root = WatchHash::from_hash(block.parent_root());
+    let proposer_index = block_message.proposer_index() as i32;
+    let graffiti = block_message.body().graffiti().as_utf8_lossy();
+    let attestation_count = block_message.body().attestations_len() as i32;
+
     let block_to_add = WatchBeaconBlock {
         slot,
         root,
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+pub const fn max_slots_per_update() -> u64 {
+    64
+}
+pub const fn min_update_interval_millis() -> u64 {
+    500
+}
+pub const fn max_sync_distance() -> u64 {
+    32
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// The maximum number of slots written to the database in each update. Larger gaps are
+    /// filled over several updates.
+    #[serde(default = "max_slots_per_update")]
+    pub max_slots_per_update: u64,
+    /// The minimum time between consecutive updates whilst catching up (in milliseconds).
+    #[serde(default = "min_update_interval_millis")]
+    pub min_update_interval_millis: u64,
+    /// Ingestion is paused whilst the head is more than this many slots behind the current slot.
+    #[serde(default = "max_sync_distance")]
+    pub max_sync_distance: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_slots_per_update: max_slots_per_update(),
+            min_update_interval_millis: min_update_interval_millis(),
+            max_sync_distance: max_sync_distance(),
+        }
+    }
+}
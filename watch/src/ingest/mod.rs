@@ -0,0 +1,631 @@
+//! Ingests the canonical chain into the watch database from within the beacon node.
+//!
+//! Unlike the updater, which polls a beacon node over HTTP, the ingest reads blocks and states
+//! directly from the `BeaconChain` whenever the head changes. Block rewards and block packing are
+//! computed with the same functions which serve the beacon node's analysis endpoints. Blockprint
+//! is still queried over HTTP, since it is an external service.
+
+use crate::blockprint::WatchBlockprintClient;
+use crate::config::Config as FullConfig;
+use crate::database::{
+    self, PgConn, PgPool, WatchCanonicalSlot, WatchHash, WatchPK, WatchSlot,
+    WatchSuboptimalAttestation, WatchValidator,
+};
+use crate::updater::handler::{ensure_valid_database, MAX_EXPECTED_REORG_LENGTH};
+use crate::updater::{Error, WatchSpec};
+use beacon_node::beacon_chain::{
+    slot_clock::SlotClock, BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped,
+};
+use eth2::lighthouse::{BlockPackingEfficiencyQuery, BlockRewardsQuery};
+use eth2::types::ValidatorStatus;
+use parking_lot::Mutex;
+use slog::{crit, debug, info, warn, Logger};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::sync::broadcast::{error::RecvError, error::TryRecvError, Receiver};
+use tokio::time::{sleep, timeout};
+use types::consts::altair::{
+    TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+};
+use types::{Epoch, EthSpec, Slot};
+
+pub use config::Config;
+
+mod config;
+
+/// Spawns a task which writes the canonical chain of `chain` to the database described by
+/// `config` each time the head changes.
+pub fn spawn_ingest<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+    config: FullConfig,
+) -> Result<(), String> {
+    let log = executor.log().clone();
+    let network = chain
+        .spec
+        .config_name
+        .clone()
+        .ok_or("Watch ingest requires a network with a CONFIG_NAME")?;
+    let spec = WatchSpec::<T::EthSpec>::new(network);
+    let mut pool = database::build_connection_pool(&config.database)
+        .map_err(|e| format!("Unable to connect to the watch database: {:?}", e))?;
+    let blockprint = WatchBlockprintClient::from_config(&config.blockprint)
+        .map_err(|e| format!("Invalid blockprint config: {:?}", e))?;
+    let max_slots_per_update = config.ingest.max_slots_per_update;
+
+    let slot_duration = chain.slot_clock.slot_duration();
+    let min_update_interval = Duration::from_millis(config.ingest.min_update_interval_millis);
+    let mut head_events = chain
+        .event_handler
+        .as_ref()
+        .map(|handler| handler.subscribe_head());
+
+    let ingest = Arc::new(Ingest::new(chain, pool.clone(), config, log.clone()));
+    let inner_executor = executor.clone();
+
+    let ingest_future = async move {
+        if let Err(e) = ensure_valid_database(&spec, &mut pool).await {
+            crit!(log, "Watch database is incompatible"; "error" => ?e);
+            return;
+        }
+        info!(log, "Started watch ingest");
+
+        loop {
+            let inner_ingest = ingest.clone();
+            let Some(handle) = inner_executor
+                .spawn_blocking_handle(move || inner_ingest.update(), "watch_ingest_update")
+            else {
+                break;
+            };
+
+            let mut progress = match handle.await {
+                Ok(Ok(progress)) => progress,
+                Ok(Err(e)) => {
+                    warn!(log, "Watch ingest failed"; "error" => ?e);
+                    Progress::CaughtUp
+                }
+                Err(e) => {
+                    warn!(log, "Watch ingest task failed"; "error" => ?e);
+                    Progress::CaughtUp
+                }
+            };
+
+            if let Some(blockprint) = &blockprint {
+                match fill_blockprint(blockprint, &pool, max_slots_per_update).await {
+                    Ok(Progress::Behind) => progress = Progress::Behind,
+                    Ok(Progress::CaughtUp) => {}
+                    Err(e) => warn!(log, "Watch blockprint ingest failed"; "error" => ?e),
+                }
+            }
+
+            if let Progress::Behind = progress {
+                // Throttle catching up so that the database is not saturated.
+                sleep(min_update_interval).await;
+                continue;
+            }
+
+            wait_for_head(&mut head_events, slot_duration).await;
+        }
+    };
+
+    executor.spawn(ingest_future, "watch_ingest");
+    Ok(())
+}
+
+/// Waits for the next head event, or for `max_wait` if head events are unavailable.
+///
+/// Any further heads which arrived whilst the previous update was running are discarded, since
+/// each update syncs to the latest head.
+async fn wait_for_head<X: Clone>(head_events: &mut Option<Receiver<X>>, max_wait: Duration) {
+    let Some(receiver) = head_events else {
+        sleep(max_wait).await;
+        return;
+    };
+
+    loop {
+        match receiver.try_recv() {
+            Ok(_) | Err(TryRecvError::Lagged(_)) => continue,
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Closed) => {
+                *head_events = None;
+                sleep(max_wait).await;
+                return;
+            }
+        }
+    }
+
+    let closed = matches!(
+        timeout(max_wait, receiver.recv()).await,
+        Ok(Err(RecvError::Closed))
+    );
+    if closed {
+        *head_events = None;
+    }
+}
+
+/// Writes the blockprint classifications of the highest blocks which do not have one yet.
+///
+/// Only blocks which the blockprint server has already classified are requested, so that a
+/// lagging server does not hold up the rest of the table.
+async fn fill_blockprint(
+    blockprint: &WatchBlockprintClient,
+    pool: &PgPool,
+    max_slots: u64,
+) -> Result<Progress, Error> {
+    let greatest_slot = blockprint.ensure_synced().await?;
+    let mut conn = database::get_connection(pool)?;
+    let unknown = database::get_unknown_blockprint(&mut conn)?
+        .into_iter()
+        .flatten()
+        .map(|slot| slot.as_slot())
+        // The blockprint API cannot accept `start_slot == 0`.
+        .filter(|slot| *slot > 0 && *slot <= greatest_slot);
+    let Some(window) = UnknownWindow::highest(unknown, max_slots) else {
+        return Ok(Progress::CaughtUp);
+    };
+
+    let mut prints = blockprint
+        .get_blockprint(window.start_slot, window.end_slot)
+        .await?;
+    prints.retain(|print| window.slots.contains(&print.slot.as_slot()));
+    database::insert_batch_blockprint(&mut conn, prints)?;
+    Ok(window.progress())
+}
+
+/// The result of a single update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    /// The database has reached the head, or ingestion is paused.
+    CaughtUp,
+    /// More work remains and another update should follow shortly.
+    Behind,
+}
+
+/// The highest slots of `beacon_blocks` which are missing from one of the tables derived from
+/// them.
+struct UnknownWindow {
+    /// The lowest missing slot in the window.
+    start_slot: Slot,
+    /// The highest missing slot in the window.
+    end_slot: Slot,
+    slots: HashSet<Slot>,
+    /// Whether there are missing slots below the window.
+    remaining: bool,
+}
+
+impl UnknownWindow {
+    /// Returns the missing slots among the `max_slots` slots ending at the highest missing slot.
+    ///
+    /// `unknown` must be in descending order.
+    fn highest(unknown: impl Iterator<Item = Slot>, max_slots: u64) -> Option<Self> {
+        let mut unknown = unknown.peekable();
+        let end_slot = *unknown.peek()?;
+        let lowest_slot = end_slot.saturating_sub(max_slots.saturating_sub(1));
+
+        let mut slots = HashSet::new();
+        let mut remaining = false;
+        for slot in unknown {
+            if slot < lowest_slot {
+                remaining = true;
+                break;
+            }
+            slots.insert(slot);
+        }
+        let start_slot = slots.iter().min().copied().unwrap_or(end_slot);
+
+        Some(Self {
+            start_slot,
+            end_slot,
+            slots,
+            remaining,
+        })
+    }
+
+    fn progress(&self) -> Progress {
+        if self.remaining {
+            Progress::Behind
+        } else {
+            Progress::CaughtUp
+        }
+    }
+}
+
+/// Writes the canonical chain of a `BeaconChain` to the watch database.
+pub struct Ingest<T: BeaconChainTypes> {
+    chain: Arc<BeaconChain<T>>,
+    pool: PgPool,
+    config: FullConfig,
+    /// The epoch of the head state when the validator set was last written.
+    last_validator_epoch: Mutex<Option<Epoch>>,
+    /// The most recent epoch for which suboptimal attestations have been written.
+    last_attestation_epoch: Mutex<Option<Epoch>>,
+    log: Logger,
+}
+
+impl<T: BeaconChainTypes> Ingest<T> {
+    pub fn new(chain: Arc<BeaconChain<T>>, pool: PgPool, config: FullConfig, log: Logger) -> Self {
+        Self {
+            chain,
+            pool,
+            config,
+            last_validator_epoch: Mutex::new(None),
+            last_attestation_epoch: Mutex::new(None),
+            log,
+        }
+    }
+
+    fn slots_per_epoch(&self) -> u64 {
+        T::EthSpec::slots_per_epoch()
+    }
+
+    /// Brings the database up to date with the head of the chain, doing at most
+    /// `max_slots_per_update` slots of work.
+    pub fn update(&self) -> Result<Progress, Error> {
+        let mut conn = database::get_connection(&self.pool)?;
+
+        let head_slot = self.chain.canonical_head.cached_head().head_slot();
+        let current_slot = self.chain.slot()?;
+        let sync_distance = current_slot.saturating_sub(head_slot);
+        if sync_distance > self.config.ingest.max_sync_distance {
+            debug!(
+                self.log,
+                "Watch ingest paused whilst syncing";
+                "sync_distance" => sync_distance,
+            );
+            return Ok(Progress::CaughtUp);
+        }
+
+        let start_slot = match database::get_highest_canonical_slot(&mut conn)? {
+            Some(highest) => self.remove_reorged_slots(&mut conn, highest.slot.as_slot())? + 1,
+            // Start from the beginning of the head's epoch, so that backfills are always done in
+            // full epochs.
+            None => head_slot
+                .epoch(self.slots_per_epoch())
+                .start_slot(self.slots_per_epoch()),
+        };
+        let max_slots = self.config.ingest.max_slots_per_update;
+        let end_slot = std::cmp::min(head_slot, start_slot + max_slots.saturating_sub(1));
+        let caught_up = end_slot == head_slot;
+
+        if start_slot <= end_slot {
+            self.fill_canonical_slots(&mut conn, start_slot, end_slot)?;
+        }
+
+        // Blocks reference their proposer in the `validators` table.
+        self.update_validators(&mut conn)?;
+        self.fill_blocks(&mut conn)?;
+
+        if self.config.updater.attestations {
+            self.fill_suboptimal_attestations(&mut conn)?;
+        }
+
+        let mut analysis_behind = false;
+        if self.config.updater.block_rewards {
+            analysis_behind |= matches!(self.fill_block_rewards(&mut conn)?, Progress::Behind);
+        }
+        if self.config.updater.block_packing {
+            analysis_behind |= matches!(self.fill_block_packing(&mut conn)?, Progress::Behind);
+        }
+
+        if caught_up && !analysis_behind && !self.backfill_canonical_slots(&mut conn)? {
+            Ok(Progress::CaughtUp)
+        } else {
+            Ok(Progress::Behind)
+        }
+    }
+
+    /// Compares the tip of the database with the chain, deleting any slots which are no longer
+    /// canonical.
+    ///
+    /// Returns the highest slot of the database which is still canonical.
+    fn remove_reorged_slots(&self, conn: &mut PgConn, highest_slot: Slot) -> Result<Slot, Error> {
+        let lowest_slot = highest_slot.saturating_sub(MAX_EXPECTED_REORG_LENGTH);
+
+        let mut slot = highest_slot;
+        let canonical_slot = loop {
+            let db_root = database::get_canonical_slot(conn, WatchSlot::from_slot(slot))?
+                .map(|canonical_slot| canonical_slot.root.as_hash());
+            let chain_root = self.chain.block_root_at_slot(slot, WhenSlotSkipped::Prev)?;
+            if db_root.is_some() && db_root == chain_root {
+                break slot;
+            }
+            if slot == lowest_slot {
+                return Err(Error::ReorgTooDeep(highest_slot));
+            }
+            slot -= 1;
+        };
+
+        if canonical_slot < highest_slot {
+            let removed =
+                database::delete_canonical_slots_above(conn, WatchSlot::from_slot(canonical_slot))?;
+            // The attestations of the previous epoch may have changed with the re-org.
+            let epoch = canonical_slot
+                .epoch(self.slots_per_epoch())
+                .saturating_sub(1_u64);
+            database::delete_suboptimal_attestations_above(
+                conn,
+                WatchSlot::from_slot(epoch.start_slot(self.slots_per_epoch())),
+            )?;
+            *self.last_attestation_epoch.lock() = None;
+            info!(
+                self.log,
+                "Removed re-orged slots from watch database";
+                "removed" => removed,
+                "canonical_slot" => canonical_slot,
+            );
+        }
+
+        Ok(canonical_slot)
+    }
+
+    /// Inserts the canonical roots of `start_slot..=end_slot`.
+    ///
+    /// Skip slots use the root of the previous non-skipped slot.
+    fn fill_canonical_slots(
+        &self,
+        conn: &mut PgConn,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<(), Error> {
+        for slot in (start_slot.as_u64()..=end_slot.as_u64()).map(Slot::new) {
+            let root = self
+                .chain
+                .block_root_at_slot(slot, WhenSlotSkipped::Prev)?
+                .ok_or(Error::MissingCanonicalRoot(slot))?;
+            let skipped = self
+                .chain
+                .block_root_at_slot(slot, WhenSlotSkipped::None)?
+                .is_none();
+            database::insert_canonical_slot(
+                conn,
+                WatchCanonicalSlot {
+                    slot: WatchSlot::from_slot(slot),
+                    root: WatchHash::from_hash(root),
+                    skipped,
+                    beacon_block: None,
+                },
+            )?;
+        }
+        debug!(
+            self.log,
+            "Watch ingest filled canonical slots";
+            "start_slot" => start_slot,
+            "end_slot" => end_slot,
+        );
+        Ok(())
+    }
+
+    /// Fills up to `max_backfill_size_epochs` of slots below the lowest slot in the database.
+    ///
+    /// Returns `true` if any slots were filled.
+    fn backfill_canonical_slots(&self, conn: &mut PgConn) -> Result<bool, Error> {
+        let slots_per_epoch = self.slots_per_epoch();
+        let stop_slot = Slot::new(self.config.updater.backfill_stop_epoch * slots_per_epoch);
+        let Some(lowest_slot) =
+            database::get_lowest_canonical_slot(conn)?.map(|slot| slot.slot.as_slot())
+        else {
+            return Ok(false);
+        };
+        if lowest_slot <= stop_slot {
+            return Ok(false);
+        }
+
+        let backfill_slots = std::cmp::min(
+            self.config.updater.max_backfill_size_epochs * slots_per_epoch,
+            self.config.ingest.max_slots_per_update,
+        );
+        let end_slot = lowest_slot - 1;
+        let start_slot = std::cmp::max(lowest_slot.saturating_sub(backfill_slots), stop_slot);
+        self.fill_canonical_slots(conn, start_slot, end_slot)?;
+        Ok(true)
+    }
+
+    /// Inserts the blocks of canonical slots which do not have one yet, at most
+    /// `max_slots_per_update` at a time.
+    fn fill_blocks(&self, conn: &mut PgConn) -> Result<(), Error> {
+        let roots = database::get_unknown_canonical_blocks(conn)?;
+        for root in roots
+            .into_iter()
+            .take(self.config.ingest.max_slots_per_update as usize)
+        {
+            let block_root = root.as_hash();
+            // Pruned execution payloads can only be counted from the blinded block.
+            match self.chain.store.get_full_block(&block_root) {
+                Ok(Some(block)) => database::insert_beacon_block(conn, block, root)?,
+                Ok(None) => {
+                    return Err(BeaconChainError::MissingBeaconBlock(block_root).into());
+                }
+                Err(_) => {
+                    let block = self
+                        .chain
+                        .get_blinded_block(&block_root)?
+                        .ok_or(BeaconChainError::MissingBeaconBlock(block_root))?;
+                    database::insert_beacon_block_with_payload_counts(
+                        conn, &block, root, None, None,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes any new or changed validators from the head state, once per epoch.
+    fn update_validators(&self, conn: &mut PgConn) -> Result<(), Error> {
+        let head = self.chain.canonical_head.cached_head();
+        let state = &head.snapshot.beacon_state;
+        let epoch = state.current_epoch();
+        if *self.last_validator_epoch.lock() == Some(epoch) {
+            return Ok(());
+        }
+
+        let far_future_epoch = self.chain.spec.far_future_epoch;
+        let new_validators = state
+            .validators()
+            .iter()
+            .enumerate()
+            .map(|(index, validator)| WatchValidator {
+                index: index as i32,
+                public_key: WatchPK::from_pubkey(validator.pubkey),
+                status: ValidatorStatus::from_validator(validator, epoch, far_future_epoch)
+                    .to_string(),
+                activation_epoch: (validator.activation_epoch != far_future_epoch)
+                    .then(|| validator.activation_epoch.as_u64() as i32),
+                exit_epoch: (validator.exit_epoch != far_future_epoch)
+                    .then(|| validator.exit_epoch.as_u64() as i32),
+            })
+            .collect::<HashSet<_>>();
+        drop(head);
+
+        let current_validators = database::get_all_validators(conn)?;
+        if current_validators.is_empty() {
+            database::insert_batch_validators(conn, Vec::from_iter(new_validators))?;
+        } else {
+            let old_validators = HashSet::from_iter(current_validators);
+            for validator in new_validators.difference(&old_validators) {
+                database::insert_validator(conn, validator.clone())?;
+            }
+        }
+
+        *self.last_validator_epoch.lock() = Some(epoch);
+        Ok(())
+    }
+
+    /// Writes the suboptimal attestations of each epoch whose participation is final, starting
+    /// after the most recent epoch in the database.
+    fn fill_suboptimal_attestations(&self, conn: &mut PgConn) -> Result<(), Error> {
+        let slots_per_epoch = self.slots_per_epoch();
+        let Some(highest_slot) =
+            database::get_highest_canonical_slot(conn)?.map(|slot| slot.slot.as_slot())
+        else {
+            return Ok(());
+        };
+
+        let last_epoch = std::cmp::max(
+            *self.last_attestation_epoch.lock(),
+            database::get_highest_attestation(conn)?.map(|attestation| {
+                attestation
+                    .epoch_start_slot
+                    .as_slot()
+                    .epoch(slots_per_epoch)
+            }),
+        );
+        let mut epoch = match last_epoch {
+            Some(epoch) => epoch + 1,
+            None => match database::get_lowest_canonical_slot(conn)? {
+                Some(lowest) => lowest.slot.as_slot().epoch(slots_per_epoch),
+                None => return Ok(()),
+            },
+        };
+
+        // Attestations for an epoch may be included until the end of the following epoch.
+        while (epoch + 1).end_slot(slots_per_epoch) <= highest_slot {
+            let attestations = self.suboptimal_attestations(epoch)?;
+            database::insert_batch_suboptimal_attestations(conn, attestations)?;
+            *self.last_attestation_epoch.lock() = Some(epoch);
+            epoch += 1;
+        }
+        Ok(())
+    }
+
+    /// Writes the rewards of the highest blocks which do not have them yet.
+    fn fill_block_rewards(&self, conn: &mut PgConn) -> Result<Progress, Error> {
+        let unknown = database::get_unknown_block_rewards(conn)?
+            .into_iter()
+            .flatten()
+            .map(|slot| slot.as_slot())
+            // Block rewards cannot be computed for the genesis block.
+            .filter(|slot| *slot > 0);
+        let Some(window) = UnknownWindow::highest(unknown, self.config.ingest.max_slots_per_update)
+        else {
+            return Ok(Progress::CaughtUp);
+        };
+
+        let query = BlockRewardsQuery {
+            start_slot: window.start_slot,
+            end_slot: window.end_slot,
+            include_attestations: false,
+        };
+        let rewards = http_api::get_block_rewards(query, self.chain.clone(), self.log.clone())
+            .map_err(|e| Error::Analysis(format!("{:?}", e)))?
+            .into_iter()
+            .filter(|reward| window.slots.contains(&reward.meta.slot))
+            .map(Into::into)
+            .collect();
+        database::insert_batch_block_rewards(conn, rewards)?;
+        Ok(window.progress())
+    }
+
+    /// Writes the packing efficiency of the highest blocks which do not have it yet.
+    fn fill_block_packing(&self, conn: &mut PgConn) -> Result<Progress, Error> {
+        let slots_per_epoch = self.slots_per_epoch();
+        let unknown = database::get_unknown_block_packing(conn, slots_per_epoch)?
+            .into_iter()
+            .flatten()
+            .map(|slot| slot.as_slot());
+        let Some(window) = UnknownWindow::highest(unknown, self.config.ingest.max_slots_per_update)
+        else {
+            return Ok(Progress::CaughtUp);
+        };
+
+        let query = BlockPackingEfficiencyQuery {
+            // Block packing cannot be computed for epoch 0.
+            start_epoch: std::cmp::max(window.start_slot.epoch(slots_per_epoch), Epoch::new(1)),
+            end_epoch: window.end_slot.epoch(slots_per_epoch),
+        };
+        let packing = http_api::get_block_packing_efficiency(query, self.chain.clone())
+            .map_err(|e| Error::Analysis(format!("{:?}", e)))?
+            .into_iter()
+            .filter(|packing| window.slots.contains(&packing.slot))
+            .map(Into::into)
+            .collect();
+        database::insert_batch_block_packing(conn, packing)?;
+        Ok(window.progress())
+    }
+
+    /// Returns the validators which were active in `epoch` but did not attest to the correct
+    /// source, target and head in time.
+    ///
+    /// Participation flags are not available prior to Altair, in which case nothing is returned.
+    fn suboptimal_attestations(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Vec<WatchSuboptimalAttestation>, Error> {
+        let slots_per_epoch = self.slots_per_epoch();
+        // The previous epoch participation of this state is final for `epoch`.
+        let slot = (epoch + 1).end_slot(slots_per_epoch);
+        let state_root = self
+            .chain
+            .state_root_at_slot(slot)?
+            .ok_or(Error::MissingCanonicalRoot(slot))?;
+        let state = self
+            .chain
+            .get_state(&state_root, Some(slot))?
+            .ok_or(Error::MissingCanonicalRoot(slot))?;
+        let Ok(participation) = state.previous_epoch_participation() else {
+            return Ok(vec![]);
+        };
+
+        let epoch_start_slot = WatchSlot::from_slot(epoch.start_slot(slots_per_epoch));
+        Ok(state
+            .validators()
+            .iter()
+            .zip(participation.iter())
+            .enumerate()
+            .filter(|(_, (validator, _))| validator.is_active_at(epoch) && !validator.slashed)
+            .filter_map(|(index, (_, flags))| {
+                let source = flags.has_flag(TIMELY_SOURCE_FLAG_INDEX).unwrap_or(false);
+                let head = flags.has_flag(TIMELY_HEAD_FLAG_INDEX).unwrap_or(false);
+                let target = flags.has_flag(TIMELY_TARGET_FLAG_INDEX).unwrap_or(false);
+                (!source || !head || !target).then_some(WatchSuboptimalAttestation {
+                    epoch_start_slot,
+                    index: index as i32,
+                    source,
+                    head,
+                    target,
+                })
+            })
+            .collect())
+    }
+}
@@ -6,6 +6,7 @@ pub mod cli;
 pub mod client;
 pub mod config;
 pub mod database;
+pub mod ingest;
 pub mod logger;
 pub mod server;
 pub mod suboptimal_attestations;
@@ -3,6 +3,7 @@ use crate::database::Error as DbError;
 use beacon_node::beacon_chain::BeaconChainError;
 use eth2::{Error as Eth2Error, SensitiveError};
 use std::fmt;
+use types::Slot;
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -18,6 +19,9 @@ pub enum Error {
     NoValidatorsFound,
     BeaconNodeNotCompatible(String),
     InvalidConfig(String),
+    MissingCanonicalRoot(Slot),
+    ReorgTooDeep(Slot),
+    Analysis(String),
 }
 
 impl fmt::Display for Error {
@@ -5,7 +5,7 @@ use crate::updater::{Config, Error, WatchSpec};
 use beacon_node::beacon_chain::BeaconChainError;
 use eth2::{
     types::{BlockId, SyncingData},
-    BeaconNodeHttpClient, SensitiveUrl,
+    BeaconNodeHttpClient,
 };
 use log::{debug, error, info, warn};
 use std::collections::HashSet;
@@ -14,7 +14,7 @@ use types::{BeaconBlockHeader, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 use crate::updater::{get_beacon_block, get_header, get_validators};
 
-const MAX_EXPECTED_REORG_LENGTH: u64 = 32;
+pub const MAX_EXPECTED_REORG_LENGTH: u64 = 32;
 
 /// Ensure the existing database is valid for this run.
 pub async fn ensure_valid_database<E: EthSpec>(
@@ -57,23 +57,7 @@ impl<E: EthSpec> UpdateHandler<E> {
         spec: WatchSpec<E>,
         config: FullConfig,
     ) -> Result<UpdateHandler<E>, Error> {
-        let blockprint = if config.blockprint.enabled {
-            if let Some(server) = config.blockprint.url {
-                let blockprint_url = SensitiveUrl::parse(&server).map_err(Error::SensitiveUrl)?;
-                Some(WatchBlockprintClient {
-                    client: reqwest::Client::new(),
-                    server: blockprint_url,
-                    username: config.blockprint.username,
-                    password: config.blockprint.password,
-                })
-            } else {
-                return Err(Error::NotEnabled(
-                    "blockprint was enabled but url was not set".to_string(),
-                ));
-            }
-        } else {
-            None
-        };
+        let blockprint = WatchBlockprintClient::from_config(&config.blockprint)?;
 
         let mut pool = database::build_connection_pool(&config.database)?;
 
@@ -30,6 +30,15 @@ pub struct WatchSpec<E: EthSpec> {
 }
 
 impl<E: EthSpec> WatchSpec<E> {
+    /// Creates a spec for a network whose preset is already known, such as when running inside
+    /// the beacon node.
+    pub fn new(network: String) -> Self {
+        Self {
+            network,
+            spec: PhantomData,
+        }
+    }
+
     fn slots_per_epoch(&self) -> u64 {
         E::slots_per_epoch()
     }
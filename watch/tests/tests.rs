@@ -3,7 +3,7 @@
 
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
-    ChainConfig,
+    ChainConfig, WhenSlotSkipped,
 };
 use eth2::{types::BlockId, BeaconNodeHttpClient, SensitiveUrl, Timeouts};
 use http_api::test_utils::{create_api_server, ApiServer};
@@ -24,9 +24,10 @@ use url::Url;
 use watch::{
     client::WatchHttpClient,
     config::Config,
-    database::{self, Config as DatabaseConfig, PgPool, WatchSlot},
+    database::{self, Config as DatabaseConfig, PgPool, WatchCanonicalSlot, WatchHash, WatchSlot},
+    ingest::{Ingest, Progress},
     server::{start_server, Config as ServerConfig},
-    updater::{handler::*, run_updater, Config as UpdaterConfig, WatchSpec},
+    updater::{handler::*, run_updater, Config as UpdaterConfig, Error as UpdaterError, WatchSpec},
 };
 
 #[derive(Debug)]
@@ -249,6 +250,24 @@ impl Tester {
         self
     }
 
+    /// Build `num_blocks` blocks upon the canonical block at `previous_slot`, starting at the next
+    /// slot. If the fork becomes canonical, the blocks after `previous_slot` are re-orged out.
+    pub async fn fork_chain(&mut self, previous_slot: u64, num_blocks: u64) -> &mut Self {
+        self.harness.advance_slot();
+        let first_slot = self.harness.get_current_slot();
+        self.harness
+            .extend_chain(
+                num_blocks as usize,
+                BlockStrategy::ForkCanonicalChainAt {
+                    previous_slot: Slot::new(previous_slot),
+                    first_slot,
+                },
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        self
+    }
+
     // Advance the slot clock without a block. This results in a skipped slot.
     pub fn skip_slot(&mut self) -> &mut Self {
         self.harness.advance_slot();
@@ -340,6 +359,28 @@ impl Tester {
         self
     }
 
+    /// Run a single update of the in-process ingest, returning its progress.
+    pub fn ingest_update(&mut self) -> Result<Progress, UpdaterError> {
+        let pool = database::build_connection_pool(&self.config.database).unwrap();
+        Ingest::new(
+            self.harness.chain.clone(),
+            pool,
+            self.config.clone(),
+            self.harness.logger().clone(),
+        )
+        .update()
+    }
+
+    /// Run the in-process ingest until it reports that it has caught up.
+    pub fn run_ingest(&mut self) -> &mut Self {
+        for _ in 0..64 {
+            if self.ingest_update().unwrap() == Progress::CaughtUp {
+                return self;
+            }
+        }
+        panic!("ingest did not catch up");
+    }
+
     pub async fn assert_canonical_slots_empty(&mut self) -> &mut Self {
         let lowest_slot = self
             .client
@@ -1290,3 +1331,165 @@ async fn large_chain() {
         .assert_all_blocks_have_metadata()
         .await;
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ingest_with_reorg() {
+    let builder = TesterBuilder::new().await;
+
+    let docker = Cli::default();
+    let image = RunnableImage::from(Postgres::default())
+        .with_mapped_port((builder.config.database.port, 5432));
+    let _node = docker.run(image);
+
+    let pool = builder.initialize_database().await;
+    let mut tester = builder.build(pool).await;
+
+    tester
+        .extend_chain(5)
+        .await
+        .run_ingest()
+        .assert_all_validators_exist()
+        .await
+        .assert_highest_canonical_slot(5)
+        .await
+        .assert_lowest_canonical_slot(0)
+        .await
+        .assert_canonical_chain_consistent(0)
+        .await
+        // Re-org out the block at slot 5, which is already in the database.
+        .fork_chain(4, 2)
+        .await;
+
+    assert_eq!(
+        tester
+            .harness
+            .chain
+            .block_root_at_slot(Slot::new(5), WhenSlotSkipped::None)
+            .unwrap(),
+        None
+    );
+
+    tester
+        .run_ingest()
+        .assert_highest_canonical_slot(7)
+        .await
+        .assert_slot_is_skipped(5)
+        .await
+        .assert_canonical_chain_consistent(0)
+        .await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ingest_reorg_too_deep() {
+    let builder = TesterBuilder::new().await;
+
+    let docker = Cli::default();
+    let image = RunnableImage::from(Postgres::default())
+        .with_mapped_port((builder.config.database.port, 5432));
+    let _node = docker.run(image);
+
+    let pool = builder.initialize_database().await;
+    let mut tester = builder.build(pool.clone()).await;
+
+    let highest_slot = MAX_EXPECTED_REORG_LENGTH + 1;
+    tester.extend_chain(highest_slot + 1).await;
+
+    // None of the slots in the database match the chain.
+    let mut conn = database::get_connection(&pool).unwrap();
+    for slot in 0..=highest_slot {
+        database::insert_canonical_slot(
+            &mut conn,
+            WatchCanonicalSlot {
+                slot: WatchSlot::new(slot),
+                root: WatchHash::from_hash(Hash256::repeat_byte(0xff)),
+                skipped: false,
+                beacon_block: None,
+            },
+        )
+        .unwrap();
+    }
+
+    assert!(matches!(
+        tester.ingest_update(),
+        Err(UpdaterError::ReorgTooDeep(slot)) if slot == Slot::new(highest_slot)
+    ));
+    // Nothing is removed from the database.
+    tester
+        .assert_lowest_canonical_slot(0)
+        .await
+        .assert_highest_canonical_slot(highest_slot)
+        .await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ingest_limits_slots_per_update() {
+    let mut builder = TesterBuilder::new().await;
+    builder.config.ingest.max_slots_per_update = 4;
+
+    let docker = Cli::default();
+    let image = RunnableImage::from(Postgres::default())
+        .with_mapped_port((builder.config.database.port, 5432));
+    let _node = docker.run(image);
+
+    let pool = builder.initialize_database().await;
+    let mut tester = builder.build(pool).await;
+
+    tester.extend_chain(10).await;
+
+    assert_eq!(tester.ingest_update().unwrap(), Progress::Behind);
+    tester
+        .assert_lowest_canonical_slot(0)
+        .await
+        .assert_highest_canonical_slot(3)
+        .await;
+
+    assert_eq!(tester.ingest_update().unwrap(), Progress::Behind);
+    tester.assert_highest_canonical_slot(7).await;
+
+    tester
+        .run_ingest()
+        .assert_highest_canonical_slot(10)
+        .await
+        .assert_canonical_chain_consistent(0)
+        .await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ingest_paused_whilst_syncing() {
+    let mut builder = TesterBuilder::new().await;
+    builder.config.ingest.max_sync_distance = 2;
+
+    let docker = Cli::default();
+    let image = RunnableImage::from(Postgres::default())
+        .with_mapped_port((builder.config.database.port, 5432));
+    let _node = docker.run(image);
+
+    let pool = builder.initialize_database().await;
+    let mut tester = builder.build(pool).await;
+
+    // The head is 3 slots behind the current slot.
+    tester
+        .extend_chain(4)
+        .await
+        .skip_slot()
+        .skip_slot()
+        .skip_slot();
+
+    assert_eq!(tester.ingest_update().unwrap(), Progress::CaughtUp);
+    tester.assert_canonical_slots_empty().await;
+
+    tester
+        .extend_chain(1)
+        .await
+        .run_ingest()
+        .assert_lowest_canonical_slot(0)
+        .await
+        .assert_highest_canonical_slot(8)
+        .await
+        .assert_canonical_chain_consistent(0)
+        .await;
+}